@@ -9,6 +9,8 @@ use clap::Parser;
     long_version = build_version_string(),
     after_help = "ENVIRONMENT VARIABLES:
   STRATUM_CONFIG      Path to config file (overrides auto-discovery)
+  STRATUM_STATE       State location, like --state
+  STRATUM_PROFILE     Config profile, like --profile
   STRATUM_LOG_LEVEL   Log level: error, warn, info, debug, trace"
 )]
 pub struct Cli {
@@ -38,6 +40,26 @@ pub struct Cli {
     /// Write logs to file
     #[arg(long, value_name = "FILE", global = true)]
     pub(crate) log_file: Option<String>,
+
+    /// Audit every executed DDL/DML statement to file, with its parameters hashed
+    #[arg(long, value_name = "FILE", global = true)]
+    pub(crate) audit_log: Option<String>,
+
+    /// Rotate the audit log once it grows past this many megabytes
+    #[arg(long, value_name = "MB", default_value_t = 100, global = true)]
+    pub(crate) audit_log_max_size: u64,
+
+    /// Where to keep run state: a directory, or a redis:// URL (overrides the config's state block)
+    #[arg(long, value_name = "LOCATION", global = true)]
+    pub(crate) state: Option<String>,
+
+    /// Expire Redis checkpoints, WAL and run state after this long without a write (e.g. '7d')
+    #[arg(long, value_name = "DURATION", global = true)]
+    pub(crate) state_ttl: Option<String>,
+
+    /// Apply the config's profile of this name, overriding its connections and defines
+    #[arg(long, value_name = "NAME", global = true)]
+    pub(crate) profile: Option<String>,
 }
 
 impl Cli {
@@ -4,22 +4,28 @@ use engine_core::{
     utils::make_item_id,
 };
 use engine_infra::shutdown::ShutdownSignal;
+use engine_processing::transform::failed_row_writer::quarantined_rows;
 use engine_runtime::{
     dag::{Dag, builder::DagBuilder, executor::DagExecutor},
     error::MigrationError,
     execution::executor,
 };
+use engine_verify::snapshot::{pin_source_snapshots, snapshot_sources};
 use model::execution::flags::{ExecutionFlags, IntegrityMode};
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use tracing::{error, info, warn};
 
-/// Executes the apply command (run migration)
+/// Executes the apply command (run migration). `resumed_hash` continues the
+/// paused run with that plan hash, for a resume after the config was edited.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     config_path: Option<String>,
     tui: bool,
     pretty: bool,
     exact_filter: bool,
     integrity: IntegrityMode,
+    coordinator: bool,
+    resumed_hash: Option<String>,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
@@ -29,6 +35,11 @@ pub async fn execute(
             "Cannot use both --tui and --pretty modes simultaneously".to_string(),
         ));
     }
+    if coordinator && (tui || pretty) {
+        return Err(CliError::UserMessage(
+            "--coordinator cannot be combined with --tui or --pretty".to_string(),
+        ));
+    }
 
     let config_path = config::resolve_path(config_path)?;
     let flags = ExecutionFlags::new(false, integrity);
@@ -36,10 +47,34 @@ pub async fn execute(
     // Watch for pause sentinel file. Dropping the watcher cleans up the file.
     let _pause_watcher = PauseWatcher::start(&config_path, &shutdown, env.clone()).await?;
 
+    if coordinator {
+        return run_coordinator_mode(config_path, flags, shutdown, env).await;
+    }
+
     match (tui, pretty) {
-        (true, _) => run_tui_mode(config_path, flags, exact_filter, shutdown, env).await,
-        (_, true) => run_pretty_mode(config_path, flags, exact_filter, shutdown, env).await,
-        _ => run_headless_mode(config_path, flags, shutdown, env).await,
+        (true, _) => {
+            run_tui_mode(
+                config_path,
+                flags,
+                exact_filter,
+                resumed_hash,
+                shutdown,
+                env,
+            )
+            .await
+        }
+        (_, true) => {
+            run_pretty_mode(
+                config_path,
+                flags,
+                exact_filter,
+                resumed_hash,
+                shutdown,
+                env,
+            )
+            .await
+        }
+        _ => run_headless_mode(config_path, flags, resumed_hash, shutdown, env).await,
     }
 }
 
@@ -48,11 +83,26 @@ async fn run_tui_mode(
     config_path: String,
     flags: ExecutionFlags,
     exact_filter: bool,
+    resumed_hash: Option<String>,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
     info!(config = %config_path, "running migration with TUI");
-    run_tui(config_path, exact_filter, flags.integrity, shutdown, env).await
+    let mut plan = config::load_plan(&config_path, exact_filter, env.clone()).await?;
+    plan.resumed_hash = resumed_hash.clone();
+    let _snapshots = pin_source_snapshots(&mut plan).await?;
+    run_tui(
+        config_path.clone(),
+        exact_filter,
+        flags.integrity,
+        plan.source_snapshots.clone(),
+        resumed_hash,
+        shutdown,
+        env,
+    )
+    .await?;
+
+    super::verify::verify_after_copy(&plan).await
 }
 
 /// Runs migration with pretty output
@@ -60,10 +110,14 @@ async fn run_pretty_mode(
     config_path: String,
     flags: ExecutionFlags,
     exact_filter: bool,
+    resumed_hash: Option<String>,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
-    let plan = config::load_plan(&config_path, exact_filter, env.clone()).await?;
+    let mut plan = config::load_plan(&config_path, exact_filter, env.clone()).await?;
+    plan.resumed_hash = resumed_hash;
+    // Held until the comparison after the copy has read the snapshots
+    let _snapshots = pin_source_snapshots(&mut plan).await?;
     let event_bus = EventBus::new();
     let pipeline_names = build_pipeline_name_mapping(&plan);
 
@@ -77,6 +131,9 @@ async fn run_pretty_mode(
     });
 
     let dag = build_dag(&plan)?;
+    let run_id = plan.run_id();
+    let quarantine = quarantine_files(&plan);
+    let verify_plan = plan.clone();
     let executor = DagExecutor::with_event_bus(plan, flags, shutdown, event_bus, env)
         .await
         .map_err(CliError::Migration)?;
@@ -86,19 +143,61 @@ async fn run_pretty_mode(
     // Wait for printer to finish
     let _ = printer_handle.await;
 
-    handle_execution_result(result)
+    print_quarantine_summary(&run_id, &quarantine);
+    handle_execution_result(result)?;
+    super::verify::verify_after_copy(&verify_plan).await
 }
 
 /// Runs migration in headless mode (no TUI, no pretty output)
 async fn run_headless_mode(
     config_path: String,
     flags: ExecutionFlags,
+    resumed_hash: Option<String>,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
     info!(config = %config_path, "executing migration");
+    let mut plan = config::load_plan(&config_path, false, env.clone()).await?;
+    plan.resumed_hash = resumed_hash;
+    // Held until the comparison after the copy has read the snapshots
+    let _snapshots = pin_source_snapshots(&mut plan).await?;
+    let run_id = plan.run_id();
+    let quarantine = quarantine_files(&plan);
+    let verify_plan = plan.clone();
+
+    let result = executor::run(plan, flags, shutdown, env).await;
+    print_quarantine_summary(&run_id, &quarantine);
+    handle_execution_result(result)?;
+    super::verify::verify_after_copy(&verify_plan).await
+}
+
+/// Runs as the coordinator of a distributed migration: pipelines are
+/// published to the shared state store and run by `stratum worker` processes
+async fn run_coordinator_mode(
+    config_path: String,
+    flags: ExecutionFlags,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    info!(config = %config_path, "coordinating distributed migration");
     let plan = config::load_plan(&config_path, false, env.clone()).await?;
-    handle_execution_result(executor::run(plan, flags, shutdown, env).await)
+    super::require_shared_state(&plan)?;
+    // Workers are separate processes that cannot import a snapshot held here
+    for connection in snapshot_sources(&plan) {
+        warn!(
+            connection = %connection.name,
+            "distributed runs cannot pin a source to a snapshot; verifying against the current source"
+        );
+    }
+    for barrier in &plan.barriers {
+        warn!(
+            barrier = %barrier.name,
+            "distributed runs cannot pin barrier sources; each pipeline reads the current source"
+        );
+    }
+    let verify_plan = plan.clone();
+    handle_execution_result(executor::coordinate(plan, flags, shutdown, env).await)?;
+    super::verify::verify_after_copy(&verify_plan).await
 }
 
 /// Builds execution DAG from plan
@@ -117,7 +216,7 @@ fn build_dag(plan: &ExecutionPlan) -> Result<Dag, CliError> {
 }
 
 /// Handles execution result consistently across modes
-fn handle_execution_result(result: Result<(), MigrationError>) -> Result<(), CliError> {
+pub(super) fn handle_execution_result(result: Result<(), MigrationError>) -> Result<(), CliError> {
     match result {
         Ok(_) => {
             info!("migration completed successfully");
@@ -150,6 +249,70 @@ fn build_pipeline_name_mapping(
     mapping
 }
 
+/// (pipeline name, path) for pipelines that quarantine failed rows to a file
+fn quarantine_files(plan: &ExecutionPlan) -> Vec<(String, String)> {
+    plan.pipelines
+        .iter()
+        .filter_map(|p| {
+            let path = p
+                .error_handling
+                .as_ref()?
+                .failed_rows
+                .as_ref()?
+                .quarantine_path()?;
+            Some((p.name.clone(), path.to_string()))
+        })
+        .collect()
+}
+
+/// Rows this run of `plan` quarantined to files, across all pipelines
+pub(super) fn quarantined_total(plan: &ExecutionPlan) -> u64 {
+    let files = quarantine_files(plan);
+    quarantine_counts(&plan.run_id(), &files)
+        .iter()
+        .map(|(_, _, count)| count)
+        .sum()
+}
+
+/// Prints where this run's quarantined rows went, one line per pipeline
+fn print_quarantine_summary(run_id: &str, files: &[(String, String)]) {
+    for (pipeline, path, count) in quarantine_counts(run_id, files) {
+        if count > 0 {
+            println!("Quarantined {count} failed row(s) from pipeline '{pipeline}' to {path}");
+        }
+    }
+}
+
+/// (pipeline name, path, rows) quarantined by this run; files that cannot be
+/// read are logged and left out
+fn quarantine_counts<'a>(
+    run_id: &str,
+    files: &'a [(String, String)],
+) -> Vec<(&'a str, &'a str, u64)> {
+    let mut counts_by_path: HashMap<&str, HashMap<String, u64>> = HashMap::new();
+    let mut counts = Vec::with_capacity(files.len());
+    for (pipeline, path) in files {
+        if !counts_by_path.contains_key(path.as_str()) {
+            match quarantined_rows(path, run_id) {
+                Ok(counts) => {
+                    counts_by_path.insert(path, counts);
+                }
+                Err(e) => {
+                    warn!(path = %path, error = %e, "failed to read quarantine file");
+                    continue;
+                }
+            }
+        }
+
+        let count = counts_by_path[path.as_str()]
+            .get(pipeline)
+            .copied()
+            .unwrap_or(0);
+        counts.push((pipeline.as_str(), path.as_str(), count));
+    }
+    counts
+}
+
 /// Watches for a pause sentinel file and cancels the pause token when found.
 /// Also cleans up the sentinel file on drop.
 struct PauseWatcher {
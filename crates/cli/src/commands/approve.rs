@@ -0,0 +1,78 @@
+use super::{
+    open_state_store,
+    runbook::is_runbook_run,
+    status::{format_status, state_err},
+};
+use crate::error::CliError;
+use engine_state::models::{PauseReason, RunStatus, WalEntry};
+
+/// Approves a gate of a run. Gates are named after the pipeline or runbook
+/// step they hold back; the run passes them once it is resumed.
+pub async fn execute(run: &str, gate: &str) -> Result<(), CliError> {
+    let state = open_state_store(None).await?;
+
+    // A run history id stands for the run it executed
+    let run_id = match state.load_run_record(run).await.map_err(state_err)? {
+        Some(record) => record.run_id,
+        None => run.to_string(),
+    };
+    let run_state = state
+        .load_run_state(&run_id)
+        .await
+        .map_err(state_err)?
+        .ok_or_else(|| {
+            CliError::UserMessage(format!("No run '{run}' found (see `stratum status`)"))
+        })?;
+
+    if !run_state.pipelines.iter().any(|p| p.name == gate) {
+        let known: Vec<_> = run_state
+            .pipelines
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        return Err(CliError::UserMessage(format!(
+            "Run '{run_id}' has no pipeline or step '{gate}' (known: {})",
+            known.join(", ")
+        )));
+    }
+    if matches!(
+        run_state.status,
+        RunStatus::Completed { .. } | RunStatus::Failed { .. }
+    ) {
+        return Err(CliError::UserMessage(format!(
+            "Run '{run_id}' is {} and has no gate to approve",
+            format_status(&run_state.status)
+        )));
+    }
+
+    state
+        .append_wal(&WalEntry::GateApproved {
+            run_id: run_id.clone(),
+            gate: gate.to_string(),
+            at: chrono::Utc::now(),
+        })
+        .await
+        .map_err(state_err)?;
+    println!("✓ Approved '{gate}' for run '{run_id}'");
+
+    if let RunStatus::Paused {
+        reason: PauseReason::Approval { gate: waiting },
+        ..
+    } = &run_state.status
+    {
+        if waiting != gate {
+            println!("  The run is still waiting at '{waiting}'");
+        } else if is_runbook_run(&run_id) {
+            println!(
+                "  Continue with `stratum runbook run -c {}`",
+                run_state.config_path
+            );
+        } else {
+            println!(
+                "  Continue with `stratum resume -c {}`",
+                run_state.config_path
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,21 +1,28 @@
 use crate::{Cli, error::CliError};
 use clap::{Subcommand, ValueEnum};
+use engine_core::plan::execution::ExecutionPlan;
 use engine_infra::shutdown::ShutdownSignal;
 use engine_processing::EnvContext;
-use engine_state::sled_store::SledStateStore;
-use model::execution::flags::IntegrityMode;
+use engine_state::SharedStateStore;
+use model::execution::{flags::IntegrityMode, state_config::StateConfig, verify::VerifyMode};
 use std::{path::PathBuf, sync::Arc};
 
 pub mod apply;
+pub mod approve;
 pub mod pause;
 pub mod ping;
 pub mod plan;
 pub mod plugin;
 pub mod reset;
 pub mod resume;
+pub mod runbook;
+pub mod runs;
+pub mod schema;
+pub mod state;
 pub mod status;
 pub mod verify;
 pub mod version;
+pub mod worker;
 
 const STATE_DIR: &str = ".stratum/state";
 
@@ -26,15 +33,27 @@ pub fn state_dir() -> Result<PathBuf, CliError> {
     Ok(home.join(STATE_DIR))
 }
 
-/// Opens the sled state store from the default location.
-pub fn open_state_store() -> Result<SledStateStore, CliError> {
-    let path = state_dir()?;
-    SledStateStore::open(&path).map_err(|e| {
-        CliError::Unknown(format!(
-            "Failed to open state store at {}: {e}",
-            path.display()
-        ))
-    })
+/// Opens the state store for a loaded plan (`plan.state`), or for the
+/// command-line backend when there is no plan.
+pub async fn open_state_store(state: Option<&StateConfig>) -> Result<SharedStateStore, CliError> {
+    let state = match state {
+        Some(state) => state.clone(),
+        None => crate::config::resolve_state(&StateConfig::default()),
+    };
+    engine_state::open_state_store(&state, &state_dir()?)
+        .await
+        .map_err(|e| CliError::Unknown(e.to_string()))
+}
+
+/// Distributed runs coordinate through the state store, so every process
+/// must reach the same one; a local store is private to one machine.
+pub fn require_shared_state(plan: &ExecutionPlan) -> Result<(), CliError> {
+    match plan.state {
+        StateConfig::Redis { .. } => Ok(()),
+        StateConfig::Local | StateConfig::LocalPath(_) => Err(CliError::UserMessage(
+            "Distributed mode needs a shared state store: add a `state { backend = \"redis\" url = \"...\" }` block or pass --state redis://...".to_string(),
+        )),
+    }
 }
 
 /// Sampling method for data preview
@@ -49,6 +68,64 @@ pub enum SampleMethod {
     Id,
 }
 
+/// What `verify --compare` compares for each chunk of keys
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CompareMode {
+    /// Row counts only
+    Count,
+    /// Row counts plus per-column non-null counts, min/max and sums
+    Aggregate,
+    /// Row counts plus md5 checksums of every column
+    Checksum,
+}
+
+impl From<CompareMode> for VerifyMode {
+    fn from(mode: CompareMode) -> Self {
+        match mode {
+            CompareMode::Count => VerifyMode::Count,
+            CompareMode::Aggregate => VerifyMode::Aggregate,
+            CompareMode::Checksum => VerifyMode::Checksum,
+        }
+    }
+}
+
+/// Diagram syntax for `plan graph`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT (render with `dot -Tsvg`)
+    Dot,
+    /// Mermaid flowchart (renders in GitHub and GitLab markdown)
+    Mermaid,
+}
+
+#[derive(clap::Subcommand)]
+pub enum PlanCmd {
+    /// Export the migration topology (connections, pipelines, dependencies,
+    /// joins and destinations) as a diagram.
+    Graph {
+        /// Path to SMQL config file (auto-discovered if not specified)
+        config: Option<String>,
+        /// Write the diagram to this file instead of stdout.
+        #[arg(short = 'o', long)]
+        output: Option<String>,
+        /// Diagram syntax; defaults to mermaid for .mmd/.md outputs and dot otherwise.
+        #[arg(long, value_enum)]
+        format: Option<GraphFormat>,
+    },
+    /// Show which source columns and expressions produce each destination
+    /// column, or which pipelines read a given source column.
+    Lineage {
+        /// Path to SMQL config file (auto-discovered if not specified)
+        config: Option<String>,
+        /// Only show the pipelines that read this source column.
+        #[arg(long, value_name = "TABLE.COLUMN")]
+        column: Option<String>,
+        /// Emit machine-readable JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(clap::Subcommand)]
 pub enum PluginCmd {
     /// Compile a JavaScript plugin to WASM.
@@ -99,10 +176,117 @@ pub enum PluginCmd {
     },
 }
 
+#[derive(clap::Subcommand)]
+pub enum RunsCmd {
+    /// List recorded migration runs, most recent first.
+    List {
+        /// Only show the N most recent runs.
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Emit machine-readable JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show one recorded run in detail.
+    Show {
+        /// Run id, as printed by `stratum runs list`.
+        id: String,
+        /// Emit machine-readable JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum StateCmd {
+    /// Write the state of a run (checkpoints, WAL, run history) to an archive.
+    Export {
+        /// Run id, as shown by `stratum status`.
+        #[arg(long)]
+        run: String,
+        /// Archive file to write, e.g. state.tar.zst.
+        #[arg(short = 'o', long)]
+        output: String,
+    },
+    /// Load a run's state from an archive written by `state export`.
+    Import {
+        /// Archive file to read.
+        input: String,
+        /// Replace the state the store already holds for the run.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum RunbookCmd {
+    /// Run the steps of a runbook in order, stopping at the first failed gate.
+    Run {
+        #[arg(
+            short = 'c',
+            long,
+            default_value = "runbook.smql",
+            help = "Path to the runbook file"
+        )]
+        config: String,
+        /// Start at this step, skipping the ones before it.
+        #[arg(long, value_name = "STEP")]
+        from: Option<String>,
+        /// Approve steps that ask for approval without prompting.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// List the steps of a runbook and their gates.
+    Show {
+        #[arg(
+            short = 'c',
+            long,
+            default_value = "runbook.smql",
+            help = "Path to the runbook file"
+        )]
+        config: String,
+        /// Emit machine-readable JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum SchemaCmd {
+    /// Compare a source table's structure with a destination table.
+    Diff {
+        #[arg(
+            short = 'c',
+            long,
+            help = "Path to SMQL config file declaring the connections (auto-discovered if not specified)"
+        )]
+        config: Option<String>,
+        /// Name of the source connection in the config.
+        #[arg(long)]
+        source: String,
+        /// Name of the destination connection in the config.
+        #[arg(long)]
+        dest: String,
+        /// Table to compare.
+        #[arg(long)]
+        table: String,
+        /// Destination table, when it is named differently from the source table.
+        #[arg(long, value_name = "TABLE")]
+        dest_table: Option<String>,
+        /// Emit machine-readable JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run dry-run migration and show results without making changes
+    #[command(args_conflicts_with_subcommands = true)]
     Plan {
+        #[command(subcommand)]
+        cmd: Option<PlanCmd>,
+
         #[arg(
             short = 'c',
             long,
@@ -140,6 +324,10 @@ pub enum Commands {
         /// Use exact COUNT for filtered rows (slower but accurate). By default uses EXPLAIN estimates (faster)
         #[arg(long)]
         exact_filter: bool,
+
+        /// Count duplicate pagination keys that no primary key or unique index covers (scans each such table)
+        #[arg(long)]
+        check_keys: bool,
     },
     /// Execute the migration
     Apply {
@@ -170,6 +358,37 @@ pub enum Commands {
                     ~32 bytes per row of additional storage."
         )]
         full_integrity: bool,
+
+        #[arg(
+            long,
+            help = "Publish pipelines to the shared state store for `stratum worker` \
+                    processes to run, and wait for them to finish"
+        )]
+        coordinator: bool,
+    },
+    /// Run pipelines published by an `apply --coordinator` process
+    Worker {
+        #[arg(
+            short = 'c',
+            long,
+            help = "Path to SMQL config file (must match the coordinator's)"
+        )]
+        config: Option<String>,
+
+        #[arg(
+            long,
+            help = "Worker name shown in logs and status (default: host-pid)"
+        )]
+        id: Option<String>,
+
+        #[arg(long, help = "Compute integrity hashes and receipts during migration")]
+        integrity: bool,
+
+        #[arg(
+            long,
+            help = "Store individual row hashes in the receipt (implies --integrity)"
+        )]
+        full_integrity: bool,
     },
     /// Verify migrated data matches source data
     Verify {
@@ -186,6 +405,22 @@ pub enum Commands {
             help = "If specified, writes the verification report to this file instead of stdout"
         )]
         output: Option<String>,
+
+        #[arg(
+            long,
+            value_enum,
+            help = "Compare every pipeline against its source in this mode, \
+                    not only those with a `verify` block"
+        )]
+        compare: Option<CompareMode>,
+
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Re-run the transforms over N random source rows per pipeline \
+                    and compare every field with the destination row"
+        )]
+        sample: Option<usize>,
     },
     /// Test database connectivity
     Ping {
@@ -233,6 +468,32 @@ pub enum Commands {
             help = "Store individual row hashes in the receipt (implies --integrity)"
         )]
         full_integrity: bool,
+
+        #[arg(
+            long,
+            help = "Resume even if the config changed in a way that affects pipelines already under way"
+        )]
+        allow_config_change: bool,
+    },
+    /// Audit previous migration runs
+    Runs {
+        #[command(subcommand)]
+        cmd: RunsCmd,
+    },
+    /// Move a run's state between state stores
+    State {
+        #[command(subcommand)]
+        cmd: StateCmd,
+    },
+    /// Run a multi-step migration program from a runbook file
+    Runbook {
+        #[command(subcommand)]
+        cmd: RunbookCmd,
+    },
+    /// Inspect source and destination schemas
+    Schema {
+        #[command(subcommand)]
+        cmd: SchemaCmd,
     },
     /// Clear all state for a migration (checkpoints, WAL, run state)
     Reset {
@@ -251,6 +512,14 @@ pub enum Commands {
         #[arg(short = 'c', long, help = "Path to SMQL config file")]
         config: String,
     },
+    /// Approve a gate a paused run is waiting at
+    Approve {
+        #[arg(help = "Run id, as shown by `stratum status`")]
+        run: String,
+
+        #[arg(help = "Gated pipeline or runbook step")]
+        gate: String,
+    },
     /// Show version information
     Version,
     /// Plugin tooling: compile, inspect, validate, and test WASM/JS plugins.
@@ -275,6 +544,7 @@ pub async fn execute_command(
             exact_filter,
             integrity,
             full_integrity,
+            coordinator,
         } => {
             let integrity_mode = IntegrityMode::new(*integrity, *full_integrity);
             apply::execute(
@@ -283,15 +553,36 @@ pub async fn execute_command(
                 *pretty,
                 *exact_filter,
                 integrity_mode,
+                *coordinator,
+                None,
                 shutdown,
                 env,
             )
             .await
         }
-        Commands::Verify { config, output } => {
-            verify::execute(config.clone(), output.clone(), env.clone()).await
+        Commands::Worker {
+            config,
+            id,
+            integrity,
+            full_integrity,
+        } => {
+            let integrity_mode = IntegrityMode::new(*integrity, *full_integrity);
+            worker::execute(config.clone(), id.clone(), integrity_mode, shutdown, env).await
+        }
+        Commands::Verify {
+            config,
+            output,
+            compare,
+            sample,
+        } => {
+            let mode = compare.map(VerifyMode::from);
+            verify::execute(config.clone(), output.clone(), mode, *sample, env.clone()).await
         }
         Commands::Status { config } => status::execute(config.clone(), env).await,
+        Commands::Runs { cmd } => runs::run(cmd).await,
+        Commands::State { cmd } => state::run(cmd).await,
+        Commands::Runbook { cmd } => runbook::run(cmd, shutdown, env).await,
+        Commands::Schema { cmd } => schema::run(cmd, env).await,
         Commands::Ping { url, format } => ping::execute(cli, url.clone(), format.clone()).await,
         Commands::Version => {
             version::execute();
@@ -303,12 +594,23 @@ pub async fn execute_command(
             pretty,
             integrity,
             full_integrity,
+            allow_config_change,
         } => {
             let integrity_mode = IntegrityMode::new(*integrity, *full_integrity);
-            resume::execute(config.clone(), *tui, *pretty, integrity_mode, shutdown, env).await
+            resume::execute(
+                config.clone(),
+                *tui,
+                *pretty,
+                integrity_mode,
+                *allow_config_change,
+                shutdown,
+                env,
+            )
+            .await
         }
         Commands::Reset { config, force } => reset::execute(config.clone(), *force, env).await,
         Commands::Pause { config } => pause::execute(Some(config.clone()), env).await,
+        Commands::Approve { run, gate } => approve::execute(run, gate).await,
         Commands::Plugin { cmd } => plugin::run(cmd, env.clone()).await,
     }
 }
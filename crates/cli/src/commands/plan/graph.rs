@@ -0,0 +1,423 @@
+use crate::{Cli, commands::GraphFormat, config, error::CliError};
+use engine_core::{context::env::EnvContext, plan::execution::ExecutionPlan};
+use model::execution::connection::Connection;
+use std::{fmt::Write, path::Path, sync::Arc};
+use tracing::info;
+
+/// Writes a diagram of the plan's connections, pipelines and the tables they
+/// read, join and write, to `output` or stdout.
+pub async fn execute(
+    cli: &Cli,
+    config: Option<String>,
+    output: Option<&str>,
+    format: Option<GraphFormat>,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    let config_path = config::resolve_path(config)?;
+    info!(config = %config_path, "exporting plan graph");
+
+    let plan = config::load_plan(&config_path, false, env).await?;
+    let format = format.unwrap_or_else(|| output.map_or(GraphFormat::Dot, format_for_path));
+    let graph = PlanGraph::from_plan(&plan);
+    let rendered = match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+    };
+
+    match output {
+        Some(path) => {
+            tokio::fs::write(path, rendered).await?;
+            if !cli.quiet {
+                println!("Plan graph written to {path}");
+            }
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// `.mmd`, `.mermaid` and `.md` files get Mermaid, anything else DOT.
+fn format_for_path(path: &str) -> GraphFormat {
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("mmd" | "mermaid" | "md") => GraphFormat::Mermaid,
+        _ => GraphFormat::Dot,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NodeKind {
+    Table,
+    View,
+    Pipeline,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    label: String,
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EdgeStyle {
+    /// Rows flowing from a table into a pipeline or out to its destination
+    Data,
+    /// A joined lookup table
+    Join,
+    /// Ordering only: dependencies and view refreshes
+    Order,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: String,
+    to: String,
+    label: String,
+    style: EdgeStyle,
+}
+
+/// Tables and views grouped under the connection that holds them.
+#[derive(Debug)]
+struct Cluster {
+    label: String,
+    nodes: Vec<Node>,
+}
+
+/// Topology of a plan, independent of the output syntax.
+#[derive(Debug, Default)]
+struct PlanGraph {
+    clusters: Vec<(String, Cluster)>,
+    pipelines: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl PlanGraph {
+    fn from_plan(plan: &ExecutionPlan) -> Self {
+        let mut graph = PlanGraph::default();
+        for connection in &plan.connections {
+            graph.cluster(connection);
+        }
+
+        for pipeline in &plan.pipelines {
+            let id = pipeline_id(&pipeline.name);
+            let mut label = pipeline.name.clone();
+            if pipeline.validate_only {
+                label.push_str(" (validate only)");
+            }
+            if pipeline.approval_gate.is_some() {
+                label.push_str(" (approval)");
+            }
+            graph.pipelines.push(Node {
+                id: id.clone(),
+                label,
+                kind: NodeKind::Pipeline,
+            });
+
+            let source = &pipeline.source;
+            let table = graph.table(&source.connection, &source.table);
+            graph.edge(&table, &id, "reads", EdgeStyle::Data);
+            for join in &source.joins {
+                let joined = graph.table(&source.connection, &join.table);
+                graph.edge(
+                    &joined,
+                    &id,
+                    &format!("join {}", join.alias),
+                    EdgeStyle::Join,
+                );
+            }
+
+            if !pipeline.validate_only {
+                let dest = &pipeline.destination;
+                let table = graph.table(&dest.connection, &dest.table);
+                let mode = format!("{:?}", dest.mode).to_lowercase();
+                graph.edge(&id, &table, &mode, EdgeStyle::Data);
+            }
+
+            for dependency in &pipeline.dependencies {
+                let label = if pipeline.inferred_dependencies.contains(dependency) {
+                    "after (inferred)"
+                } else {
+                    "after"
+                };
+                graph.edge(&pipeline_id(dependency), &id, label, EdgeStyle::Order);
+            }
+        }
+
+        for view in &plan.materialized_views {
+            let id = graph.node(&view.connection, &view.name, NodeKind::View);
+            for pipeline in &view.pipelines {
+                graph.edge(&pipeline_id(pipeline), &id, "refresh", EdgeStyle::Order);
+            }
+            for other in &view.views {
+                let other = plan
+                    .materialized_views
+                    .iter()
+                    .find(|v| &v.name == other)
+                    .map(|v| node_id(&v.connection.name, &v.name));
+                if let Some(other) = other {
+                    graph.edge(&other, &id, "after", EdgeStyle::Order);
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn cluster(&mut self, connection: &Connection) -> &mut Cluster {
+        let index = match self
+            .clusters
+            .iter()
+            .position(|(name, _)| name == &connection.name)
+        {
+            Some(index) => index,
+            None => {
+                self.clusters.push((
+                    connection.name.clone(),
+                    Cluster {
+                        label: format!("{} ({})", connection.name, connection.driver),
+                        nodes: Vec::new(),
+                    },
+                ));
+                self.clusters.len() - 1
+            }
+        };
+        &mut self.clusters[index].1
+    }
+
+    fn table(&mut self, connection: &Connection, table: &str) -> String {
+        self.node(connection, table, NodeKind::Table)
+    }
+
+    fn node(&mut self, connection: &Connection, name: &str, kind: NodeKind) -> String {
+        let id = node_id(&connection.name, name);
+        let cluster = self.cluster(connection);
+        if !cluster.nodes.iter().any(|n| n.id == id) {
+            cluster.nodes.push(Node {
+                id: id.clone(),
+                label: name.to_string(),
+                kind,
+            });
+        }
+        id
+    }
+
+    fn edge(&mut self, from: &str, to: &str, label: &str, style: EdgeStyle) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.to_string(),
+            style,
+        });
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from(
+            "digraph plan {\n    rankdir=LR;\n    node [fontname=\"Helvetica\"];\n    edge [fontname=\"Helvetica\", fontsize=10];\n",
+        );
+        for (i, (_, cluster)) in self.clusters.iter().enumerate() {
+            if cluster.nodes.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n    subgraph cluster_{i} {{");
+            let _ = writeln!(out, "        label=\"{}\";", dot_escape(&cluster.label));
+            let _ = writeln!(out, "        style=rounded;");
+            for node in &cluster.nodes {
+                let _ = writeln!(out, "        {};", dot_node(node));
+            }
+            let _ = writeln!(out, "    }}");
+        }
+        out.push('\n');
+        for node in &self.pipelines {
+            let _ = writeln!(out, "    {};", dot_node(node));
+        }
+        out.push('\n');
+        for edge in &self.edges {
+            let style = match edge.style {
+                EdgeStyle::Data => "",
+                EdgeStyle::Join => ", style=dashed",
+                EdgeStyle::Order => ", style=dotted",
+            };
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"{}\"{style}];",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to),
+                dot_escape(&edge.label)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        // Mermaid ids must be plain words, so nodes are numbered.
+        let ids: Vec<&str> = self
+            .clusters
+            .iter()
+            .flat_map(|(_, c)| c.nodes.iter())
+            .chain(self.pipelines.iter())
+            .map(|n| n.id.as_str())
+            .collect();
+        let short = |id: &str| {
+            ids.iter()
+                .position(|other| *other == id)
+                .map_or_else(|| mermaid_word(id), |i| format!("n{i}"))
+        };
+
+        let mut out = String::from("flowchart LR\n");
+        for (i, (_, cluster)) in self.clusters.iter().enumerate() {
+            if cluster.nodes.is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "    subgraph c{i}[\"{}\"]",
+                mermaid_escape(&cluster.label)
+            );
+            for node in &cluster.nodes {
+                let _ = writeln!(out, "        {}", mermaid_node(&short(&node.id), node));
+            }
+            let _ = writeln!(out, "    end");
+        }
+        for node in &self.pipelines {
+            let _ = writeln!(out, "    {}", mermaid_node(&short(&node.id), node));
+        }
+        for edge in &self.edges {
+            let arrow = match edge.style {
+                EdgeStyle::Data => "-->",
+                EdgeStyle::Join | EdgeStyle::Order => "-.->",
+            };
+            let _ = writeln!(
+                out,
+                "    {} {arrow}|\"{}\"| {}",
+                short(&edge.from),
+                mermaid_escape(&edge.label),
+                short(&edge.to)
+            );
+        }
+        out
+    }
+}
+
+fn pipeline_id(name: &str) -> String {
+    format!("pipeline:{name}")
+}
+
+fn node_id(connection: &str, name: &str) -> String {
+    format!("{connection}.{name}")
+}
+
+fn dot_node(node: &Node) -> String {
+    let shape = match node.kind {
+        NodeKind::Table => "shape=cylinder",
+        NodeKind::View => "shape=cylinder, style=dashed",
+        NodeKind::Pipeline => "shape=box, style=\"rounded,filled\", fillcolor=\"#e8f0fe\"",
+    };
+    format!(
+        "\"{}\" [label=\"{}\", {shape}]",
+        dot_escape(&node.id),
+        dot_escape(&node.label)
+    )
+}
+
+fn mermaid_node(id: &str, node: &Node) -> String {
+    let label = mermaid_escape(&node.label);
+    match node.kind {
+        NodeKind::Table => format!("{id}[(\"{label}\")]"),
+        NodeKind::View => format!("{id}[/\"{label}\"/]"),
+        NodeKind::Pipeline => format!("{id}(\"{label}\")"),
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+/// Fallback id for an edge endpoint that is not a node (a dependency on a
+/// pipeline the plan does not declare).
+fn mermaid_word(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smql_syntax::builder::parse;
+
+    fn graph(smql: &str) -> PlanGraph {
+        let doc = parse(smql).expect("Failed to parse SMQL");
+        let plan = ExecutionPlan::build(&doc, Arc::new(EnvContext::empty()))
+            .expect("Failed to build plan");
+        PlanGraph::from_plan(&plan)
+    }
+
+    const CONFIG: &str = r#"
+        connection "src" { driver = "mysql" host = "localhost" }
+        connection "dst" { driver = "postgres" host = "localhost" }
+        pipeline "users" {
+            from { connection = connection.src table = "users" }
+            to   { connection = connection.dst table = "users" }
+        }
+        pipeline "orders" {
+            after = [pipeline.users]
+            from { connection = connection.src table = "orders" }
+            to   { connection = connection.dst table = "orders" mode = "upsert" }
+            with {
+                u from users where u.id == orders.user_id
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_dot_graph() {
+        let dot = graph(CONFIG).to_dot();
+        assert!(dot.starts_with("digraph plan {"), "{dot}");
+        assert!(dot.contains("label=\"src (mysql)\";"), "{dot}");
+        assert!(
+            dot.contains("\"src.orders\" -> \"pipeline:orders\" [label=\"reads\"];"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("\"src.users\" -> \"pipeline:orders\" [label=\"join u\", style=dashed];"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("\"pipeline:orders\" -> \"dst.orders\" [label=\"upsert\"];"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains(
+                "\"pipeline:users\" -> \"pipeline:orders\" [label=\"after\", style=dotted];"
+            ),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn test_mermaid_graph() {
+        let mermaid = graph(CONFIG).to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"), "{mermaid}");
+        assert!(
+            mermaid.contains("subgraph c0[\"dst (postgres)\"]"),
+            "{mermaid}"
+        );
+        // dst.users, dst.orders, src.users, src.orders, then the pipelines
+        assert!(mermaid.contains("n4(\"users\")"), "{mermaid}");
+        assert!(mermaid.contains("n3 -->|\"reads\"| n5"), "{mermaid}");
+        assert!(mermaid.contains("n2 -.->|\"join u\"| n5"), "{mermaid}");
+        assert!(mermaid.contains("n4 -.->|\"after\"| n5"), "{mermaid}");
+    }
+
+    #[test]
+    fn test_format_from_output_extension() {
+        assert!(matches!(format_for_path("plan.mmd"), GraphFormat::Mermaid));
+        assert!(matches!(format_for_path("plan.dot"), GraphFormat::Dot));
+        assert!(matches!(format_for_path("plan"), GraphFormat::Dot));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{config, error::CliError};
+use engine_core::context::env::EnvContext;
+use model::execution::lineage::{ColumnRef, ColumnUse, PipelineLineage};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::info;
+
+/// Prints where each destination column comes from, or with `column` set,
+/// every pipeline that reads that source column.
+pub async fn execute(
+    config: Option<String>,
+    column: Option<&str>,
+    as_json: bool,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    let config_path = config::resolve_path(config)?;
+    info!(config = %config_path, "computing column lineage");
+    let plan = config::load_plan(&config_path, false, env).await?;
+
+    match column {
+        Some(column) => {
+            let source = ColumnRef::parse(column).ok_or_else(|| {
+                CliError::UserMessage(format!(
+                    "Invalid column '{column}': expected TABLE.COLUMN, e.g. users.email"
+                ))
+            })?;
+            print_impact(&plan.lineage, &source, as_json)
+        }
+        None if as_json => {
+            println!("{}", serde_json::to_string_pretty(&plan.lineage)?);
+            Ok(())
+        }
+        None => {
+            print_lineage(&plan.lineage);
+            Ok(())
+        }
+    }
+}
+
+fn print_lineage(lineage: &[PipelineLineage]) {
+    let mut rows = vec![row(["PIPELINE", "DESTINATION", "SOURCES", "EXPRESSION"])];
+    for pipeline in lineage {
+        for column in &pipeline.columns {
+            rows.push([
+                pipeline.pipeline.clone(),
+                column.destination.to_string(),
+                join(&column.sources),
+                column.expression.clone().unwrap_or_default(),
+            ]);
+        }
+        if pipeline.copies_unmapped
+            && let Some(destination) = &pipeline.destination_table
+        {
+            rows.push([
+                pipeline.pipeline.clone(),
+                format!("{destination}.*"),
+                format!("{}.*", pipeline.source_table),
+                "(unmapped columns copied by name)".to_string(),
+            ]);
+        }
+        if !pipeline.filter_columns.is_empty() {
+            rows.push([
+                pipeline.pipeline.clone(),
+                "(filter)".to_string(),
+                join(&pipeline.filter_columns),
+                String::new(),
+            ]);
+        }
+    }
+
+    if rows.len() == 1 {
+        println!("No pipelines in the config.");
+        return;
+    }
+    print_rows(&rows);
+}
+
+fn print_impact(
+    lineage: &[PipelineLineage],
+    source: &ColumnRef,
+    as_json: bool,
+) -> Result<(), CliError> {
+    let mut uses = Vec::new();
+    for pipeline in lineage {
+        for usage in pipeline.uses(source) {
+            let (kind, destination, expression) = match usage {
+                ColumnUse::Mapped(column) => (
+                    "mapped",
+                    Some(column.destination.to_string()),
+                    column.expression.clone(),
+                ),
+                ColumnUse::Copied(destination) => ("copied", Some(destination.to_string()), None),
+                ColumnUse::Filter => ("filter", None, None),
+            };
+            uses.push((pipeline.pipeline.as_str(), kind, destination, expression));
+        }
+    }
+
+    if as_json {
+        let uses: Vec<_> = uses
+            .iter()
+            .map(|(pipeline, kind, destination, expression)| {
+                json!({
+                    "pipeline": pipeline,
+                    "use": kind,
+                    "destination": destination,
+                    "expression": expression,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&uses)?);
+        return Ok(());
+    }
+
+    if uses.is_empty() {
+        println!("No pipeline reads {source}.");
+        return Ok(());
+    }
+
+    let mut rows = vec![row(["PIPELINE", "USE", "DESTINATION", "EXPRESSION"])];
+    for (pipeline, kind, destination, expression) in uses {
+        rows.push([
+            pipeline.to_string(),
+            kind.to_string(),
+            destination.unwrap_or_else(|| "-".to_string()),
+            expression.unwrap_or_default(),
+        ]);
+    }
+    print_rows(&rows);
+    Ok(())
+}
+
+fn row(cells: [&str; 4]) -> [String; 4] {
+    cells.map(str::to_string)
+}
+
+fn join(columns: &[ColumnRef]) -> String {
+    if columns.is_empty() {
+        return "-".to_string();
+    }
+    columns
+        .iter()
+        .map(ColumnRef::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Prints rows as left-aligned columns, the first row being the header.
+fn print_rows(rows: &[[String; 4]]) {
+    let mut widths = [0; 3];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        println!("{}", line.trim_end());
+        if i == 0 {
+            println!("{}", "-".repeat(line.trim_end().len().max(40)));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     Cli,
-    commands::{Commands, SampleMethod},
+    commands::{Commands, PlanCmd, SampleMethod},
     config,
     error::CliError,
     output,
@@ -15,9 +15,39 @@ use model::core::value::Value;
 use std::{path::Path, sync::Arc};
 use tracing::info;
 
+pub mod graph;
+pub mod lineage;
+
 /// Executes the plan command (dry-run migration planning)
 pub async fn execute(cli: &Cli, commands: &Commands, env: Arc<EnvContext>) -> Result<(), CliError> {
     if let Commands::Plan {
+        cmd:
+            Some(PlanCmd::Graph {
+                config,
+                output,
+                format,
+            }),
+        ..
+    } = commands
+    {
+        return graph::execute(cli, config.clone(), output.as_deref(), *format, env).await;
+    }
+
+    if let Commands::Plan {
+        cmd:
+            Some(PlanCmd::Lineage {
+                config,
+                column,
+                json,
+            }),
+        ..
+    } = commands
+    {
+        return lineage::execute(config.clone(), column.as_deref(), *json, env).await;
+    }
+
+    if let Commands::Plan {
+        cmd: None,
         config,
         output: output_path,
         sample,
@@ -26,6 +56,7 @@ pub async fn execute(cli: &Cli, commands: &Commands, env: Arc<EnvContext>) -> Re
         id_column,
         sample_ids,
         exact_filter: exact_where,
+        check_keys,
     } = commands
     {
         let config_path = config::resolve_path(config.clone())?;
@@ -45,6 +76,7 @@ pub async fn execute(cli: &Cli, commands: &Commands, env: Arc<EnvContext>) -> Re
             id_column.clone(),
             sample_ids.clone(),
             *exact_where,
+            *check_keys,
         );
 
         // Build detailed report
@@ -88,6 +120,7 @@ fn build_plan_config(
     id_column: Option<String>,
     sample_ids: Option<Vec<String>>,
     exact_where: bool,
+    check_keys: bool,
 ) -> ReportBuilderConfig {
     // Convert CLI sample method to engine SamplingMethod
     let sampling_method = match sample_method {
@@ -117,6 +150,7 @@ fn build_plan_config(
         id_column: id_column.unwrap_or_default(),
         sample_ids: sample_ids_values,
         exact_where,
+        check_keys,
         ..Default::default()
     }
 }
@@ -2,7 +2,7 @@ use crate::error::CliError;
 use engine_wasm::{
     registry::resolve_limits, runtime::limits::ResourceLimits, schema::PluginMetadata,
 };
-use model::{core::value::Value, execution::plugin::PluginDecl};
+use model::execution::plugin::PluginDecl;
 use std::io::Read;
 
 pub use engine_wasm::registry::caps_from_decl;
@@ -17,26 +17,6 @@ pub fn limits_for(meta: &PluginMetadata, decl: Option<&PluginDecl>) -> ResourceL
     }
 }
 
-/// Convert a scalar JSON value into a model `Value` (plain author-facing JSON,
-/// not the `{type,value}` wire envelope - the exchange layer adds that).
-pub fn json_to_value(v: &serde_json::Value) -> Value {
-    match v {
-        serde_json::Value::Null => Value::Null,
-        serde_json::Value::Bool(b) => Value::Boolean(*b),
-        serde_json::Value::Number(n) => {
-            if let Some(i) = n.as_i64() {
-                Value::Int(i)
-            } else if let Some(u) = n.as_u64() {
-                Value::UInt(u)
-            } else {
-                Value::Float(n.as_f64().unwrap_or(0.0))
-            }
-        }
-        serde_json::Value::String(s) => Value::String(s.clone()),
-        other => Value::Json(other.clone()),
-    }
-}
-
 pub fn read_input(src: Option<&str>) -> Result<String, CliError> {
     if let Some(s) = src
         && matches!(s.trim_start().chars().next(), Some('{' | '['))
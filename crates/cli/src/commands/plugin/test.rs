@@ -1,4 +1,4 @@
-use super::shared::{json_to_value, limits_for, read_input, read_text};
+use super::shared::{limits_for, read_input, read_text};
 use crate::{compile::resolve_plugin_wasm, error::CliError};
 use engine_wasm::{
     exchange::types::PluginInput,
@@ -8,7 +8,10 @@ use engine_wasm::{
     },
 };
 use model::{
-    core::{types::Type, value::FieldValue},
+    core::{
+        types::Type,
+        value::{FieldValue, Value},
+    },
     records::{OpType, Record},
 };
 use std::path::Path;
@@ -59,8 +62,8 @@ pub fn run(
             let out = inst.call_transform(&build_input(input)?)?;
             emit(
                 as_json,
-                &format!("{:?}", out.value),
-                || serde_json::json!({ "value": format!("{:?}", out.value) }),
+                &out.value.to_json().to_string(),
+                || serde_json::json!({ "value": out.value.to_json() }),
             );
         }
         "filter" => {
@@ -125,7 +128,7 @@ fn build_input(src: Option<&str>) -> Result<PluginInput, CliError> {
         .ok_or_else(|| CliError::UserMessage("input must be a JSON object".into()))?;
     let mut pin = PluginInput::new();
     for (k, v) in obj {
-        pin.insert(k.clone(), json_to_value(v));
+        pin.insert(k.clone(), Value::from_json(v));
     }
     Ok(pin)
 }
@@ -164,7 +167,7 @@ fn build_records(src: Option<&str>) -> Result<Vec<Record>, CliError> {
             .iter()
             .map(|(k, v)| FieldValue {
                 name: k.clone(),
-                value: Some(json_to_value(v)),
+                value: Some(Value::from_json(v)),
                 // Placeholder: the wire layer only serializes name + value.
                 data_type: Type::Unknown {
                     source_name: String::new(),
@@ -1,10 +1,7 @@
 use super::{open_state_store, state_dir};
 use crate::{config, error::CliError};
 use engine_processing::EnvContext;
-use engine_state::{
-    models::{PipelineStatus, RunStatus},
-    store::StateStore,
-};
+use engine_state::models::{PipelineStatus, RunStatus};
 use std::{
     io::{self, Write},
     sync::Arc,
@@ -20,7 +17,7 @@ pub async fn execute(
     let plan = config::load_plan(&config_path, false, env).await?;
     let run_id = plan.run_id();
 
-    let state = open_state_store()?;
+    let state = open_state_store(Some(&plan.state)).await?;
 
     let Some(run) = state
         .load_run_state(&run_id)
@@ -1,27 +1,37 @@
-use super::open_state_store;
+use super::{open_state_store, status::state_err};
 use crate::{commands::apply, config, error::CliError};
+use engine_core::plan::execution::ExecutionPlan;
 use engine_infra::shutdown::ShutdownSignal;
 use engine_processing::EnvContext;
-use engine_state::{models::RunStatus, store::StateStore};
+use engine_state::{
+    SharedStateStore,
+    models::{PipelineStatus, RunState, RunStatus},
+};
 use model::execution::flags::IntegrityMode;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
+use tracing::warn;
 
 /// Resumes a previously paused migration.
-/// Validates that a paused run exists before delegating to apply.
+/// Validates that a paused run exists before delegating to apply. Editing
+/// the config changes its run_id, so a paused run of the same config file is
+/// looked for next; it is only continued when the edits leave the
+/// pipelines already under way alone, or with `allow_config_change`.
 pub async fn execute(
     config_path: Option<String>,
     tui: bool,
     pretty: bool,
     integrity: IntegrityMode,
+    allow_config_change: bool,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
     let config_path = config::resolve_path(config_path)?;
     let plan = config::load_plan(&config_path, false, env.clone()).await?;
     let run_id = plan.run_id();
+    let mut resumed_hash = None;
 
     // Verify a paused run exists for this config
-    match open_state_store() {
+    match open_state_store(Some(&plan.state)).await {
         Ok(state) => match state.load_run_state(&run_id).await {
             Ok(Some(run)) => match &run.status {
                 RunStatus::Paused { .. } => {
@@ -43,11 +53,21 @@ pub async fn execute(
                     )));
                 }
             },
-            Ok(None) => {
-                return Err(CliError::UserMessage(format!(
-                    "No previous run found for '{config_path}' (run_id: {run_id})\nUse 'stratum apply' to start a new migration."
-                )));
-            }
+            Ok(None) => match paused_run(&state, &config_path).await? {
+                Some(run) => {
+                    check_config_change(&config_path, &plan, &run, allow_config_change)?;
+                    println!(
+                        "Resuming paused migration with an edited config (run_id: {})",
+                        run.run_id
+                    );
+                    resumed_hash = Some(run.config_hash);
+                }
+                None => {
+                    return Err(CliError::UserMessage(format!(
+                        "No previous run found for '{config_path}' (run_id: {run_id})\nUse 'stratum apply' to start a new migration."
+                    )));
+                }
+            },
             Err(_) => {
                 // State store error - fall through to apply
             }
@@ -66,8 +86,68 @@ pub async fn execute(
         pretty,
         false,
         integrity,
+        false,
+        resumed_hash,
         shutdown,
         env,
     )
     .await
 }
+
+/// The latest paused run of the config at `config_path`.
+async fn paused_run(
+    state: &SharedStateStore,
+    config_path: &str,
+) -> Result<Option<RunState>, CliError> {
+    let runs = state.list_runs().await.map_err(state_err)?;
+    Ok(runs
+        .into_iter()
+        .filter(|run| run.config_path == config_path)
+        .filter(|run| matches!(run.status, RunStatus::Paused { .. }))
+        .max_by_key(|run| run.started_at))
+}
+
+/// Refuses to resume `run` with `plan` when the config was edited in a way
+/// that changes what the run already wrote, unless `allow` is set.
+fn check_config_change(
+    config_path: &str,
+    plan: &ExecutionPlan,
+    run: &RunState,
+    allow: bool,
+) -> Result<(), CliError> {
+    let changes = if run.fingerprint.is_recorded() {
+        let started: HashSet<String> = run
+            .pipelines
+            .iter()
+            .filter(|p| {
+                p.rows_done > 0
+                    || !matches!(p.status, PipelineStatus::Pending | PipelineStatus::Blocked)
+            })
+            .map(|p| p.name.clone())
+            .collect();
+        run.fingerprint
+            .incompatible_changes(&plan.fingerprint(), &started)
+    } else {
+        vec!["the run was saved without a config fingerprint, so its edits are unknown".to_string()]
+    };
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let list = changes
+        .iter()
+        .map(|c| format!("  - {c}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if allow {
+        warn!(run_id = %run.run_id, changes = ?changes, "resuming with an incompatible config change");
+        println!("Config '{config_path}' changed since the run was paused:\n{list}");
+        return Ok(());
+    }
+    Err(CliError::UserMessage(format!(
+        "Config '{config_path}' changed since run {} was paused:\n{list}\n\
+         Use 'stratum resume --allow-config-change' to continue the run with the edited config, \
+         or 'stratum reset' and 'stratum apply' to start over.",
+        run.run_id
+    )))
+}
@@ -0,0 +1,420 @@
+use super::{RunbookCmd, apply, open_state_store, status::state_err, verify::format_report};
+use crate::{config, error::CliError};
+use chrono::Utc;
+use engine_core::plan::runbook::build_runbook;
+use engine_infra::shutdown::ShutdownSignal;
+use engine_processing::EnvContext;
+use engine_runtime::execution::notify::{WebhookPayload, send_event};
+use engine_state::models::{
+    PauseReason, PipelineRunState, PipelineStatus, RunState, RunStatus, WalEntry,
+};
+use engine_verify::{compare::compare, error::VerifyError};
+use model::execution::{
+    approval::ApprovalGate,
+    flags::IntegrityMode,
+    notify_config::NotifyEvent,
+    runbook::{Runbook, RunbookStep},
+};
+use std::{
+    io::{self, Write},
+    path::Path,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Prefix of the run id a runbook's progress is kept under, so that
+/// `stratum status` and `stratum approve` see it next to migration runs.
+const RUNBOOK_RUN_PREFIX: &str = "runbook-";
+
+/// Whether a run id names a runbook rather than a migration run.
+pub(super) fn is_runbook_run(run_id: &str) -> bool {
+    run_id.starts_with(RUNBOOK_RUN_PREFIX)
+}
+
+fn runbook_run_id(name: &str) -> String {
+    format!("{RUNBOOK_RUN_PREFIX}{name}")
+}
+
+pub async fn run(
+    cmd: &RunbookCmd,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    match cmd {
+        RunbookCmd::Run { config, from, yes } => {
+            run_steps(config, from.as_deref(), *yes, shutdown, env).await
+        }
+        RunbookCmd::Show { config, json } => show(config, *json, env).await,
+    }
+}
+
+/// Runs the steps of a runbook in order, from `from` when given. Each step
+/// is an `apply` of its config; the runbook stops at the first step that
+/// fails, is not approved, or misses one of its thresholds. A runbook
+/// paused at an `approve` gate continues at that step when run again.
+async fn run_steps(
+    path: &str,
+    from: Option<&str>,
+    yes: bool,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    let runbook = load_runbook(path, env.clone()).await?;
+    let run_id = runbook_run_id(&runbook.name);
+    let previous = open_state_store(None)
+        .await?
+        .load_run_state(&run_id)
+        .await
+        .map_err(state_err)?;
+
+    // Resuming keeps the approvals given since the paused run started
+    let waiting = previous.as_ref().and_then(|run| match &run.status {
+        RunStatus::Paused {
+            reason: PauseReason::Approval { gate },
+            ..
+        } => Some(gate.clone()),
+        _ => None,
+    });
+    let resuming = waiting.is_some() && (from.is_none() || from == waiting.as_deref());
+    let start = match from.or(waiting.as_deref()) {
+        Some(step) => runbook.position(step).ok_or_else(|| {
+            CliError::UserMessage(format!("Runbook '{}' has no step '{step}'", runbook.name))
+        })?,
+        None => 0,
+    };
+
+    let mut progress = new_progress(&runbook, &run_id, path, start)?;
+    if let (true, Some(previous)) = (resuming, &previous) {
+        progress.started_at = previous.started_at;
+    }
+    let entry = if resuming {
+        WalEntry::RunResumed {
+            run_id: run_id.clone(),
+        }
+    } else {
+        WalEntry::RunStart {
+            run_id: run_id.clone(),
+            plan_hash: progress.config_hash.clone(),
+        }
+    };
+    save_progress(&progress, Some(entry)).await?;
+
+    let total = runbook.steps.len();
+    for (idx, step) in runbook.steps.iter().enumerate().skip(start) {
+        let config_path = step_config_path(path, step);
+        println!(
+            "==> Step {}/{total} '{}' ({config_path})",
+            idx + 1,
+            step.name
+        );
+
+        if let Some(gate) = &step.approval_gate
+            && !gate_approved(&run_id, &gate.name).await?
+        {
+            return pause_at_gate(&runbook, &mut progress, path, gate).await;
+        }
+
+        if step.approval && !yes && !prompt_approval(step)? {
+            return Err(CliError::UserMessage(format!(
+                "Runbook stopped before step '{}'; continue with `stratum runbook run -c {path} --from {}`",
+                step.name, step.name
+            )));
+        }
+
+        info!(
+            runbook = %runbook.name,
+            step = %step.name,
+            config = %config_path,
+            "running runbook step"
+        );
+        progress.pipelines[idx].status = PipelineStatus::Running;
+        save_progress(&progress, None).await?;
+        if let Err(e) = run_step(step, &config_path, shutdown.clone(), env.clone()).await {
+            println!(
+                "Step '{}' did not pass; rerun it with `stratum runbook run -c {path} --from {}`",
+                step.name, step.name
+            );
+            progress.pipelines[idx].status = PipelineStatus::Failed {
+                error: e.to_string(),
+            };
+            progress.status = RunStatus::Failed {
+                error: e.to_string(),
+                failed_at: Utc::now(),
+            };
+            save_progress(&progress, None).await?;
+            return Err(e);
+        }
+        progress.pipelines[idx].status = PipelineStatus::Completed;
+        save_progress(&progress, None).await?;
+        println!("✓ Step '{}' done", step.name);
+    }
+
+    progress.status = RunStatus::Completed {
+        completed_at: Utc::now(),
+    };
+    save_progress(&progress, Some(WalEntry::RunDone { run_id })).await?;
+    println!(
+        "Runbook '{}' completed ({} of {total} steps run)",
+        runbook.name,
+        total - start
+    );
+    Ok(())
+}
+
+/// Progress of a runbook run, with the steps before `start` already done.
+fn new_progress(
+    runbook: &Runbook,
+    run_id: &str,
+    path: &str,
+    start: usize,
+) -> Result<RunState, CliError> {
+    let pipelines = runbook
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| PipelineRunState {
+            name: step.name.clone(),
+            item_id: step.name.clone(),
+            status: if idx < start {
+                PipelineStatus::Completed
+            } else {
+                PipelineStatus::Pending
+            },
+            rows_done: 0,
+            total_rows: None,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(RunState {
+        run_id: run_id.to_string(),
+        config_path: path.to_string(),
+        config_hash: blake3::hash(&serde_json::to_vec(runbook)?)
+            .to_hex()
+            .to_string(),
+        fingerprint: Default::default(),
+        status: RunStatus::Running,
+        started_at: Utc::now(),
+        total_pipelines: pipelines.len(),
+        pipelines,
+    })
+}
+
+/// Saves the runbook's progress. The store is opened for each save and
+/// released right after, since every step's `apply` opens it as well.
+async fn save_progress(progress: &RunState, entry: Option<WalEntry>) -> Result<(), CliError> {
+    let state = open_state_store(None).await?;
+    state.save_run_state(progress).await.map_err(state_err)?;
+    if let Some(entry) = entry {
+        state.append_wal(&entry).await.map_err(state_err)?;
+    }
+    Ok(())
+}
+
+async fn gate_approved(run_id: &str, gate: &str) -> Result<bool, CliError> {
+    let state = open_state_store(None).await?;
+    let wal = state.iter_wal(run_id).await.map_err(state_err)?;
+    Ok(WalEntry::approved_gates(&wal).contains(gate))
+}
+
+/// Pauses the runbook at a step's gate until `stratum approve` is called
+/// for it, notifying the runbook's webhooks.
+async fn pause_at_gate(
+    runbook: &Runbook,
+    progress: &mut RunState,
+    path: &str,
+    gate: &ApprovalGate,
+) -> Result<(), CliError> {
+    let reason = PauseReason::Approval {
+        gate: gate.name.clone(),
+    };
+    progress.status = RunStatus::Paused {
+        reason: reason.clone(),
+        paused_at: Utc::now(),
+    };
+    let entry = WalEntry::RunPaused {
+        run_id: progress.run_id.clone(),
+        reason,
+    };
+    save_progress(progress, Some(entry)).await?;
+
+    let approve = format!("stratum approve {} {}", progress.run_id, gate.name);
+    let payload = WebhookPayload {
+        event: "runbook.approval_required".to_string(),
+        run_id: progress.run_id.clone(),
+        pipeline: gate.name.clone(),
+        timestamp: Utc::now(),
+        details: serde_json::json!({
+            "gate": gate.name,
+            "message": gate.message,
+            "approve": approve,
+        }),
+    };
+    send_event(&runbook.notify, NotifyEvent::ApprovalRequired, &payload).await;
+
+    match &gate.message {
+        Some(message) => println!("Step '{}' needs approval: {message}", gate.name),
+        None => println!("Step '{}' needs approval", gate.name),
+    }
+    println!("  Approve it with `{approve}`, then continue with `stratum runbook run -c {path}`");
+    Err(CliError::Paused)
+}
+
+/// Applies one step's config, then checks its gates.
+async fn run_step(
+    step: &RunbookStep,
+    config_path: &str,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    apply::execute(
+        Some(config_path.to_string()),
+        false,
+        false,
+        false,
+        IntegrityMode::Off,
+        false,
+        None,
+        shutdown,
+        env.clone(),
+    )
+    .await?;
+
+    if step.max_failed_rows.is_none() && step.verify.is_none() {
+        return Ok(());
+    }
+    let plan = config::load_plan(config_path, false, env).await?;
+
+    if let Some(max) = step.max_failed_rows {
+        let failed = apply::quarantined_total(&plan);
+        if failed > max {
+            return Err(CliError::UserMessage(format!(
+                "Step '{}' quarantined {failed} failed row(s), more than max_failed_rows = {max}",
+                step.name
+            )));
+        }
+    }
+
+    if let Some(mode) = step.verify {
+        let reports = compare(&plan, Some(mode)).await?;
+        for report in &reports {
+            println!("{}", format_report(report));
+        }
+        if reports.iter().any(|r| !r.is_match()) {
+            return Err(CliError::Verification(VerifyError::Discrepancy));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the steps of a runbook and their gates.
+async fn show(path: &str, as_json: bool, env: Arc<EnvContext>) -> Result<(), CliError> {
+    let runbook = load_runbook(path, env).await?;
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&runbook)?);
+        return Ok(());
+    }
+
+    println!("Runbook: {}", runbook.name);
+    println!("\n  {:<4} {:<20} {:<30} GATES", "#", "STEP", "CONFIG");
+    println!("  {}", "-".repeat(80));
+    for (idx, step) in runbook.steps.iter().enumerate() {
+        println!(
+            "  {:<4} {:<20} {:<30} {}",
+            idx + 1,
+            step.name,
+            step.config,
+            format_gates(step)
+        );
+    }
+    Ok(())
+}
+
+async fn load_runbook(path: &str, env: Arc<EnvContext>) -> Result<Runbook, CliError> {
+    let doc = smql_syntax::builder::parse_file(path)?;
+    Ok(build_runbook(&doc, env)?)
+}
+
+/// A step's config path, relative to the directory of the runbook file.
+fn step_config_path(runbook_path: &str, step: &RunbookStep) -> String {
+    let config = Path::new(&step.config);
+    if config.is_absolute() {
+        return step.config.clone();
+    }
+    match Path::new(runbook_path).parent() {
+        Some(dir) => dir.join(config).to_string_lossy().to_string(),
+        None => step.config.clone(),
+    }
+}
+
+fn format_gates(step: &RunbookStep) -> String {
+    let mut gates = Vec::new();
+    if step.approval {
+        gates.push("approval".to_string());
+    }
+    if step.approval_gate.is_some() {
+        gates.push("approve".to_string());
+    }
+    if let Some(mode) = step.verify {
+        gates.push(format!("verify {}", mode.as_str()));
+    }
+    if let Some(max) = step.max_failed_rows {
+        gates.push(format!("max {max} failed rows"));
+    }
+    if gates.is_empty() {
+        "-".to_string()
+    } else {
+        gates.join(", ")
+    }
+}
+
+/// Asks the operator to approve a step before it runs.
+fn prompt_approval(step: &RunbookStep) -> Result<bool, CliError> {
+    print!("Run step '{}' ({})? [y/N] ", step.name, step.config);
+
+    io::stdout()
+        .flush()
+        .map_err(|e| CliError::Unknown(format!("Failed to flush stdout: {e}")))?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| CliError::Unknown(format!("Failed to read input: {e}")))?;
+
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(config: &str) -> RunbookStep {
+        RunbookStep {
+            name: "backfill".to_string(),
+            config: config.to_string(),
+            approval: false,
+            approval_gate: None,
+            verify: None,
+            max_failed_rows: None,
+        }
+    }
+
+    #[test]
+    fn test_step_config_path_is_relative_to_runbook() {
+        assert_eq!(
+            step_config_path("migrations/runbook.smql", &step("backfill.smql")),
+            "migrations/backfill.smql"
+        );
+        assert_eq!(
+            step_config_path("runbook.smql", &step("backfill.smql")),
+            "backfill.smql"
+        );
+        assert_eq!(
+            step_config_path(
+                "migrations/runbook.smql",
+                &step("/etc/stratum/backfill.smql")
+            ),
+            "/etc/stratum/backfill.smql"
+        );
+    }
+}
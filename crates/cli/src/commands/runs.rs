@@ -0,0 +1,120 @@
+use super::{
+    RunsCmd, open_state_store,
+    status::{DATE_FORMAT, format_elapsed, format_status, state_err},
+};
+use crate::error::CliError;
+use chrono::Utc;
+use engine_state::models::{PipelineStatus, RunRecord, RunStatus};
+
+pub async fn run(cmd: &RunsCmd) -> Result<(), CliError> {
+    match cmd {
+        RunsCmd::List { limit, json } => list(*limit, *json).await,
+        RunsCmd::Show { id, json } => show(id, *json).await,
+    }
+}
+
+async fn list(limit: Option<usize>, as_json: bool) -> Result<(), CliError> {
+    let state = open_state_store(None).await?;
+    let mut records = state.list_run_records().await.map_err(state_err)?;
+
+    // Most recent first
+    records.reverse();
+    if let Some(limit) = limit {
+        records.truncate(limit);
+    }
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!("No migration runs recorded.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<24} {:<12} {:>12} {:<10} {:<24} CONFIG",
+        "ID", "STATUS", "ROWS", "DURATION", "STARTED"
+    );
+    println!("{}", "-".repeat(100));
+
+    for record in &records {
+        let config = if record.config_path.is_empty() {
+            "-"
+        } else {
+            &record.config_path
+        };
+
+        println!(
+            "{:<24} {:<12} {:>12} {:<10} {:<24} {}",
+            record.id,
+            format_status(&record.status),
+            record.rows_migrated,
+            format_duration(record),
+            record.started_at.format(DATE_FORMAT),
+            config,
+        );
+    }
+
+    Ok(())
+}
+
+async fn show(id: &str, as_json: bool) -> Result<(), CliError> {
+    let state = open_state_store(None).await?;
+    let record = state
+        .load_run_record(id)
+        .await
+        .map_err(state_err)?
+        .ok_or_else(|| {
+            CliError::UserMessage(format!(
+                "No run '{id}' in the run history (see `stratum runs list`)"
+            ))
+        })?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&record)?);
+        return Ok(());
+    }
+
+    println!("Run:       {}", record.id);
+    println!("Run ID:    {}", record.run_id);
+    println!("Status:    {}", format_status(&record.status));
+    if record.resumed {
+        println!("Resumed:   yes");
+    }
+    if !record.config_path.is_empty() {
+        println!("Config:    {}", record.config_path);
+    }
+    println!("Plan hash: {}", record.plan_hash);
+    println!("Started:   {}", record.started_at.format(DATE_FORMAT));
+    if let Some(ended_at) = record.ended_at {
+        println!("Ended:     {}", ended_at.format(DATE_FORMAT));
+    }
+    println!("Duration:  {}", format_duration(&record));
+    println!("Rows:      {}", record.rows_migrated);
+
+    if let RunStatus::Failed { error, .. } = &record.status {
+        println!("Error:     {error}");
+    }
+
+    println!("\n  {:<30} {:<12} {:>10}", "PIPELINE", "STATUS", "ROWS");
+    println!("  {}", "-".repeat(54));
+
+    for p in &record.pipelines {
+        let status_str = match p.status {
+            PipelineStatus::Pending => "pending",
+            PipelineStatus::Running => "running",
+            PipelineStatus::Completed => "completed",
+            PipelineStatus::Failed { .. } => "failed",
+            PipelineStatus::Blocked => "blocked",
+        };
+        println!("  {:<30} {status_str:<12} {:>10}", p.name, p.rows_done);
+    }
+
+    Ok(())
+}
+
+fn format_duration(record: &RunRecord) -> String {
+    format_elapsed(record.started_at, record.ended_at.unwrap_or_else(Utc::now))
+}
@@ -0,0 +1,196 @@
+use super::SchemaCmd;
+use crate::{config, error::CliError};
+use engine_core::{
+    context::exec::ConnectionPool,
+    drivers::DriverRef,
+    plan::execution::ExecutionPlan,
+    schema::diff::{TableDiff, diff_table, same_name_columns},
+};
+use engine_processing::EnvContext;
+use model::execution::connection::Connection;
+use std::sync::Arc;
+use tracing::info;
+
+pub async fn run(cmd: &SchemaCmd, env: Arc<EnvContext>) -> Result<(), CliError> {
+    match cmd {
+        SchemaCmd::Diff {
+            config,
+            source,
+            dest,
+            table,
+            dest_table,
+            json,
+        } => {
+            let dest_table = dest_table.as_deref().unwrap_or(table);
+            diff(config.clone(), source, dest, table, dest_table, *json, env).await
+        }
+    }
+}
+
+/// Compares a source table with the destination table of the same name (or
+/// `dest_table`) and prints the differences. Connections are looked up by
+/// name in the config.
+async fn diff(
+    config_path: Option<String>,
+    source: &str,
+    dest: &str,
+    table: &str,
+    dest_table: &str,
+    as_json: bool,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    let config_path = config::resolve_path(config_path)?;
+    let plan = config::load_plan(&config_path, false, env).await?;
+    let source = find_connection(&plan, source)?;
+    let dest = find_connection(&plan, dest)?;
+    info!(source = %source.name, dest = %dest.name, table = %table, "diffing table schema");
+
+    let mut pool = ConnectionPool::new();
+    let src_driver = DriverRef::resolve(&source.driver, source, &mut pool).await?;
+    let dst_driver = DriverRef::resolve(&dest.driver, dest, &mut pool).await?;
+
+    let src_meta = src_driver.table_metadata(table).await?;
+    if src_meta.columns.is_empty() {
+        return Err(CliError::UserMessage(format!(
+            "Table '{table}' not found on connection '{}'",
+            source.name
+        )));
+    }
+    let dst_meta = dst_driver.table_metadata(dest_table).await?;
+    if dst_meta.columns.is_empty() {
+        return Err(CliError::UserMessage(format!(
+            "Table '{dest_table}' not found on connection '{}'",
+            dest.name
+        )));
+    }
+    let src_indexes = src_driver.index_metadata(table).await?;
+    let dst_indexes = dst_driver.index_metadata(dest_table).await?;
+
+    let diff = diff_table(
+        &same_name_columns(&src_meta),
+        (&src_meta, &src_indexes),
+        (&dst_meta, &dst_indexes),
+        src_driver.dialect(),
+        dst_driver.dialect(),
+    )
+    .map_err(|column| CliError::Unknown(format!("column '{column}' not in source")))?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", format_diff(&diff));
+    }
+    Ok(())
+}
+
+fn find_connection<'a>(plan: &'a ExecutionPlan, name: &str) -> Result<&'a Connection, CliError> {
+    plan.connections
+        .iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| {
+            let known: Vec<_> = plan.connections.iter().map(|c| c.name.as_str()).collect();
+            CliError::UserMessage(format!(
+                "No connection '{name}' in the config (known: {})",
+                known.join(", ")
+            ))
+        })
+}
+
+/// Human-readable diff, one section per kind of difference.
+fn format_diff(diff: &TableDiff) -> String {
+    let tables = format!("{} -> {}", diff.source_table, diff.destination_table);
+    if diff.is_empty() {
+        return format!("✓ {tables}: no differences\n");
+    }
+
+    let mut out = format!("✗ {tables}\n");
+    if !diff.missing_columns.is_empty() {
+        out.push_str("\n  Missing columns:\n");
+        for c in &diff.missing_columns {
+            out.push_str(&format!("    {:<30} {}\n", c.column, c.expected_type));
+        }
+    }
+    if !diff.type_mismatches.is_empty() {
+        out.push_str(&format!(
+            "\n  Type mismatches:\n    {:<30} {:<16} {}\n",
+            "COLUMN", "EXPECTED", "ACTUAL"
+        ));
+        for m in &diff.type_mismatches {
+            out.push_str(&format!(
+                "    {:<30} {:<16} {}\n",
+                m.column, m.expected_type, m.actual_type
+            ));
+        }
+    }
+    if !diff.nullable_differences.is_empty() {
+        out.push_str(&format!(
+            "\n  Nullable differences:\n    {:<30} {:<16} {}\n",
+            "COLUMN", "SOURCE", "DESTINATION"
+        ));
+        for n in &diff.nullable_differences {
+            out.push_str(&format!(
+                "    {:<30} {:<16} {}\n",
+                n.column,
+                nullability(n.source_nullable),
+                nullability(n.destination_nullable)
+            ));
+        }
+    }
+    if !diff.missing_indexes.is_empty() {
+        out.push_str("\n  Missing indexes:\n");
+        for i in &diff.missing_indexes {
+            let kind = if i.primary {
+                "primary key "
+            } else if i.unique {
+                "unique "
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    {:<30} {kind}({})\n",
+                i.name,
+                i.columns.join(", ")
+            ));
+        }
+    }
+    out
+}
+
+fn nullability(nullable: bool) -> &'static str {
+    if nullable { "NULL" } else { "NOT NULL" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_core::schema::diff::{MissingIndex, NullableDifference};
+
+    #[test]
+    fn test_format_diff_lists_each_section() {
+        let mut diff = TableDiff {
+            source_table: "orders".to_string(),
+            destination_table: "orders".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(format_diff(&diff), "✓ orders -> orders: no differences\n");
+
+        diff.nullable_differences.push(NullableDifference {
+            column: "status".to_string(),
+            source_nullable: false,
+            destination_nullable: true,
+        });
+        diff.missing_indexes.push(MissingIndex {
+            name: "orders_ref_key".to_string(),
+            columns: vec!["ref".to_string()],
+            unique: true,
+            primary: false,
+        });
+
+        let out = format_diff(&diff);
+        assert!(out.starts_with("✗ orders -> orders\n"));
+        assert!(out.contains("Nullable differences:"));
+        assert!(out.contains("NOT NULL         NULL"));
+        assert!(out.contains("orders_ref_key                 unique (ref)"));
+        assert!(!out.contains("Missing columns:"));
+    }
+}
@@ -0,0 +1,68 @@
+use super::{StateCmd, open_state_store, status::state_err};
+use crate::error::CliError;
+use engine_state::RunArchive;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+};
+
+pub async fn run(cmd: &StateCmd) -> Result<(), CliError> {
+    match cmd {
+        StateCmd::Export { run, output } => export(run, output).await,
+        StateCmd::Import { input, force } => import(input, *force).await,
+    }
+}
+
+/// Writes the state of one run to an archive file.
+async fn export(run_id: &str, output: &str) -> Result<(), CliError> {
+    let state = open_state_store(None).await?;
+    let archive = RunArchive::collect(&*state, run_id)
+        .await
+        .map_err(state_err)?
+        .ok_or_else(|| {
+            CliError::UserMessage(format!(
+                "No state for run '{run_id}' (see `stratum status`)"
+            ))
+        })?;
+
+    let file = File::create(output)
+        .map_err(|e| CliError::UserMessage(format!("Cannot create '{output}': {e}")))?;
+    archive.write(BufWriter::new(file)).map_err(state_err)?;
+
+    println!(
+        "Exported run {run_id} to {output} ({} checkpoints, {} WAL entries)",
+        archive.checkpoints.len(),
+        archive.wal.len()
+    );
+    Ok(())
+}
+
+/// Loads a run's state from an archive file, so it can be resumed here.
+async fn import(input: &str, force: bool) -> Result<(), CliError> {
+    let file = File::open(input)
+        .map_err(|e| CliError::UserMessage(format!("Cannot open '{input}': {e}")))?;
+    let archive = RunArchive::read(BufReader::new(file)).map_err(state_err)?;
+    let run_id = &archive.run.run_id;
+
+    let state = open_state_store(None).await?;
+    if state
+        .load_run_state(run_id)
+        .await
+        .map_err(state_err)?
+        .is_some()
+    {
+        if !force {
+            return Err(CliError::UserMessage(format!(
+                "The state store already has run {run_id}; pass --force to replace it"
+            )));
+        }
+        state.delete_run(run_id).await.map_err(state_err)?;
+    }
+    archive.restore(&*state).await.map_err(state_err)?;
+
+    println!(
+        "Imported run {run_id} (config: {}); resume it with `stratum resume`",
+        archive.run.config_path
+    );
+    Ok(())
+}
@@ -4,42 +4,41 @@ use chrono::{DateTime, Utc};
 use engine_processing::EnvContext;
 use engine_state::{
     error::StateStoreError,
-    models::{PipelineStatus, RunState, RunStatus},
-    sled_store::SledStateStore,
+    models::{PauseReason, PipelineStatus, RunState, RunStatus, WorkItem},
     store::StateStore,
 };
 use std::sync::Arc;
 
-const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+pub(super) const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
 
 pub async fn execute(config_path: Option<String>, env: Arc<EnvContext>) -> Result<(), CliError> {
-    let state = open_state_store()?;
-
     if let Some(path) = config_path {
-        show_config_status(&state, &path, env).await
+        show_config_status(&path, env).await
     } else {
-        show_all_runs(&state).await
+        let state = open_state_store(None).await?;
+        show_all_runs(state.as_ref()).await
     }
 }
 
-async fn show_config_status(
-    state: &SledStateStore,
-    config_path: &str,
-    env: Arc<EnvContext>,
-) -> Result<(), CliError> {
+async fn show_config_status(config_path: &str, env: Arc<EnvContext>) -> Result<(), CliError> {
     let resolved = config::resolve_path(Some(config_path.to_string()))?;
     let plan = config::load_plan(&resolved, false, env).await?;
     let run_id = plan.run_id();
 
+    let state = open_state_store(Some(&plan.state)).await?;
     match state.load_run_state(&run_id).await.map_err(state_err)? {
-        Some(run) => print_run_detail(&run),
+        Some(run) => {
+            // Only distributed runs have work items; they name each pipeline's workers
+            let work = state.list_work(&run_id).await.map_err(state_err)?;
+            print_run_detail(&run, &work);
+        }
         None => println!("No run found for config '{resolved}' (run_id: {run_id})"),
     }
 
     Ok(())
 }
 
-async fn show_all_runs(state: &SledStateStore) -> Result<(), CliError> {
+async fn show_all_runs(state: &dyn StateStore) -> Result<(), CliError> {
     let mut runs = state.list_runs().await.map_err(state_err)?;
 
     if runs.is_empty() {
@@ -87,7 +86,7 @@ async fn show_all_runs(state: &SledStateStore) -> Result<(), CliError> {
     Ok(())
 }
 
-fn print_run_detail(run: &RunState) {
+fn print_run_detail(run: &RunState, work: &[WorkItem]) {
     println!("Run:      {}", run.run_id);
     println!("Status:   {}", format_status(&run.status));
     if !run.config_path.is_empty() {
@@ -100,7 +99,11 @@ fn print_run_detail(run: &RunState) {
         RunStatus::Paused {
             paused_at, reason, ..
         } => {
-            println!("Paused:   {} ({reason:?})", paused_at.format(DATE_FORMAT));
+            println!(
+                "Paused:   {} ({})",
+                paused_at.format(DATE_FORMAT),
+                format_pause_reason(reason)
+            );
         }
         RunStatus::Completed { completed_at } => {
             println!("Finished: {}", completed_at.format(DATE_FORMAT));
@@ -111,8 +114,12 @@ fn print_run_detail(run: &RunState) {
         RunStatus::Running => {}
     }
 
-    println!("\n  {:<30} {:<12} {:>10}", "PIPELINE", "STATUS", "ROWS");
-    println!("  {}", "-".repeat(54));
+    let worker_header = if work.is_empty() { "" } else { "  WORKER" };
+    println!(
+        "\n  {:<30} {:<12} {:>10}{worker_header}",
+        "PIPELINE", "STATUS", "ROWS"
+    );
+    println!("  {}", "-".repeat(54 + worker_header.len()));
 
     for p in &run.pipelines {
         let status_str = match p.status {
@@ -128,11 +135,24 @@ fn print_run_detail(run: &RunState) {
             n => n.to_string(),
         };
 
-        println!("  {:<30} {status_str:<12} {rows:>10}", p.name);
+        // A pipeline split into key ranges may have run on several workers
+        let mut workers: Vec<&str> = work
+            .iter()
+            .filter(|w| w.pipeline == p.name)
+            .filter_map(|w| w.worker_id.as_deref())
+            .collect();
+        workers.sort();
+        workers.dedup();
+        let worker = match workers.is_empty() {
+            true => String::new(),
+            false => format!("  {}", workers.join(",")),
+        };
+
+        println!("  {:<30} {status_str:<12} {rows:>10}{worker}", p.name);
     }
 }
 
-fn format_status(status: &RunStatus) -> &'static str {
+pub(super) fn format_status(status: &RunStatus) -> &'static str {
     match status {
         RunStatus::Running => "running",
         RunStatus::Paused { .. } => "paused",
@@ -141,6 +161,17 @@ fn format_status(status: &RunStatus) -> &'static str {
     }
 }
 
+fn format_pause_reason(reason: &PauseReason) -> String {
+    match reason {
+        PauseReason::Manual => "manual".to_string(),
+        PauseReason::TimeLimit => "time limit".to_string(),
+        PauseReason::Approval { gate } => format!("waiting for approval of '{gate}'"),
+        PauseReason::SchemaDrift { table, changes } => {
+            format!("table '{table}' changed: {}", changes.join("; "))
+        }
+    }
+}
+
 fn format_duration(status: &RunStatus, started_at: DateTime<Utc>) -> String {
     let end = match status {
         RunStatus::Paused { paused_at, .. } => *paused_at,
@@ -149,6 +180,10 @@ fn format_duration(status: &RunStatus, started_at: DateTime<Utc>) -> String {
         RunStatus::Running => Utc::now(),
     };
 
+    format_elapsed(started_at, end)
+}
+
+pub(super) fn format_elapsed(started_at: DateTime<Utc>, end: DateTime<Utc>) -> String {
     let secs = (end - started_at).num_seconds().max(0);
     if secs < 60 {
         format!("{secs}s")
@@ -159,7 +194,7 @@ fn format_duration(status: &RunStatus, started_at: DateTime<Utc>) -> String {
     }
 }
 
-fn state_err(e: StateStoreError) -> CliError {
+pub(super) fn state_err(e: StateStoreError) -> CliError {
     CliError::Unknown(format!("State store error: {e}"))
 }
 
@@ -1,26 +1,70 @@
 use std::{
+    collections::HashSet,
     fs::File,
     io::{BufWriter, Write},
     sync::Arc,
 };
 
 use crate::{config, error::CliError};
+use engine_core::plan::execution::ExecutionPlan;
 use engine_processing::EnvContext;
-use engine_verify::{error::VerifyError, verifier::verify};
-use model::integrity::result::VerificationResult;
-use tracing::info;
+use engine_verify::{
+    compare::compare, error::VerifyError, reconcile::reconcile, snapshot::snapshot_sources,
+    verifier::verify,
+};
+use model::{
+    execution::verify::VerifyMode,
+    integrity::{
+        comparison::{ChunkDiscrepancy, ComparisonReport},
+        reconciliation::ReconciliationReport,
+        result::VerificationResult,
+    },
+};
+use tracing::{info, warn};
 
-/// Executes the verify command (post-migration verification)
+/// Executes the verify command (post-migration verification). Pipelines with
+/// a `verify` block, or every pipeline when `compare` is set, are compared
+/// against their source, and with `sample` set every pipeline is reconciled
+/// over that many random rows; the rest are checked against their integrity
+/// receipts.
 pub async fn execute(
     config_path: Option<String>,
     output: Option<String>,
+    compare_mode: Option<VerifyMode>,
+    sample: Option<usize>,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
     let config_path = config::resolve_path(config_path)?;
     info!(config = %config_path, "verifying migrated data");
 
-    let plan = config::load_plan(&config_path, false, env.clone()).await?;
-    let results = verify(plan, env).await?;
+    let mut plan = config::load_plan(&config_path, false, env.clone()).await?;
+    // Validate-only pipelines copy nothing, so there is nothing to verify
+    plan.pipelines.retain(|p| !p.validate_only);
+    // The snapshot a run pinned is released when the run ends
+    for connection in snapshot_sources(&plan) {
+        warn!(
+            connection = %connection.name,
+            "as_of = \"snapshot\" only applies when apply runs the comparison; verifying against the current source"
+        );
+    }
+    let reports = compare(&plan, compare_mode).await?;
+    let reconciliations = match sample {
+        Some(sample) => reconcile(&plan, sample, env.clone()).await?,
+        None => Vec::new(),
+    };
+
+    let checked: HashSet<&str> = reports
+        .iter()
+        .map(|r| r.pipeline.as_str())
+        .chain(reconciliations.iter().map(|r| r.pipeline.as_str()))
+        .collect();
+    plan.pipelines
+        .retain(|p| !checked.contains(p.name.as_str()));
+    let results = if plan.pipelines.is_empty() {
+        Vec::new()
+    } else {
+        verify(plan, env).await?
+    };
 
     let mut writer: Option<BufWriter<File>> = if let Some(ref path) = output {
         let file = File::create(path).map_err(CliError::ConfigFileRead)?;
@@ -29,16 +73,16 @@ pub async fn execute(
         None
     };
 
-    let mut all_match = true;
-    for result in &results {
-        println!("{}", format_result(result));
+    let lines = reports
+        .iter()
+        .map(format_report)
+        .chain(reconciliations.iter().map(format_reconciliation))
+        .chain(results.iter().map(format_result));
+    for line in lines {
+        println!("{}", line);
 
         if let Some(ref mut w) = writer {
-            writeln!(w, "{}", format_result(result)).map_err(CliError::ConfigFileRead)?;
-        }
-
-        if matches!(result, VerificationResult::Mismatch { .. }) {
-            all_match = false;
+            writeln!(w, "{}", line).map_err(CliError::ConfigFileRead)?;
         }
     }
 
@@ -50,13 +94,180 @@ pub async fn execute(
         );
     }
 
-    if !all_match {
+    if results
+        .iter()
+        .any(|r| matches!(r, VerificationResult::Mismatch { .. }))
+    {
         return Err(CliError::Verification(VerifyError::Mismatch));
     }
+    if reports.iter().any(|r| !r.is_match()) || reconciliations.iter().any(|r| !r.is_match()) {
+        return Err(CliError::Verification(VerifyError::Discrepancy));
+    }
+
+    Ok(())
+}
+
+/// Verification stage run by `apply` once the copy has finished: compares
+/// the pipelines that have a `verify` block against their source.
+pub(super) async fn verify_after_copy(plan: &ExecutionPlan) -> Result<(), CliError> {
+    if plan.pipelines.iter().all(|p| p.verify.is_none()) {
+        return Ok(());
+    }
+
+    let reports = compare(plan, None).await?;
+    for report in &reports {
+        println!("{}", format_report(report));
+    }
 
+    if reports.iter().any(|r| !r.is_match()) {
+        return Err(CliError::Verification(VerifyError::Discrepancy));
+    }
     Ok(())
 }
 
+/// Format a source/destination comparison as a human-readable string.
+pub fn format_report(report: &ComparisonReport) -> String {
+    let tables = format!(
+        "{}: {} -> {}",
+        report.pipeline, report.source_table, report.destination_table
+    );
+
+    if report.is_match() {
+        return format!(
+            "✓ {} - match ({}, {} chunks, {} rows, {}ms)",
+            tables,
+            report.mode.as_str(),
+            report.chunks,
+            report.source_rows,
+            report.duration_ms,
+        );
+    }
+
+    let mut out = format!(
+        "✗ {} - {} DISCREPANCIES ({}, {} chunks, {} source rows, {} destination rows, {}ms)",
+        tables,
+        report.discrepancies.len(),
+        report.mode.as_str(),
+        report.chunks,
+        report.source_rows,
+        report.destination_rows,
+        report.duration_ms,
+    );
+    for d in &report.discrepancies {
+        out.push_str(&format!(
+            "\n  chunk {} ({}):",
+            d.chunk_index,
+            key_range(&report.key, d)
+        ));
+        if d.source_rows != d.destination_rows {
+            out.push_str(&format!(
+                " {} source rows, {} destination rows",
+                d.source_rows, d.destination_rows
+            ));
+            if !d.columns.is_empty() {
+                out.push(';');
+            }
+        }
+        if !d.columns.is_empty() {
+            out.push_str(&format!(" columns differ: {}", d.columns.join(", ")));
+        }
+    }
+    out
+}
+
+fn key_range(key: &str, d: &ChunkDiscrepancy) -> String {
+    match (&d.key_after, &d.key_through) {
+        (Some(after), Some(through)) => format!("{key} > {after}, {key} <= {through}"),
+        (Some(after), None) => format!("{key} > {after}"),
+        (None, Some(through)) => format!("{key} <= {through}"),
+        (None, None) => "all keys".to_string(),
+    }
+}
+
+/// Mismatches listed per pipeline before the rest are only counted
+const MAX_LISTED_MISMATCHES: usize = 20;
+
+/// Format a sampled reconciliation as a human-readable string.
+pub fn format_reconciliation(report: &ReconciliationReport) -> String {
+    let tables = format!(
+        "{}: {} -> {}",
+        report.pipeline, report.source_table, report.destination_table
+    );
+    let counts = format!(
+        "{} sampled, {} compared, {} skipped, {}ms",
+        report.sampled,
+        report.compared(),
+        report.skipped,
+        report.duration_ms,
+    );
+
+    let mut out = if report.is_match() {
+        format!("✓ {} - sample matches ({})", tables, counts)
+    } else {
+        format!(
+            "✗ {} - {} MISSING ROWS, {} FIELD MISMATCHES ({})",
+            tables,
+            report.missing.len(),
+            report.mismatches.len(),
+            counts,
+        )
+    };
+
+    if !report.missing.is_empty() {
+        let listed: Vec<&str> = report
+            .missing
+            .iter()
+            .take(MAX_LISTED_MISMATCHES)
+            .map(String::as_str)
+            .collect();
+        out.push_str(&format!(
+            "
+  missing {}: {}",
+            report.key,
+            listed.join(", ")
+        ));
+        if report.missing.len() > listed.len() {
+            out.push_str(&format!(
+                " and {} more",
+                report.missing.len() - listed.len()
+            ));
+        }
+    }
+    for m in report.mismatches.iter().take(MAX_LISTED_MISMATCHES) {
+        out.push_str(&format!(
+            "
+  {} = {}: {} expected {}, actual {}",
+            report.key,
+            m.key,
+            m.column,
+            display_field(&m.expected),
+            display_field(&m.actual),
+        ));
+    }
+    if report.mismatches.len() > MAX_LISTED_MISMATCHES {
+        out.push_str(&format!(
+            "
+  ... and {} more mismatches",
+            report.mismatches.len() - MAX_LISTED_MISMATCHES
+        ));
+    }
+    if !report.unchecked_columns.is_empty() {
+        out.push_str(&format!(
+            "
+  not checked (read from joined tables): {}",
+            report.unchecked_columns.join(", ")
+        ));
+    }
+    out
+}
+
+fn display_field(value: &Option<String>) -> String {
+    match value {
+        Some(v) => format!("'{v}'"),
+        None => "NULL".to_string(),
+    }
+}
+
 /// Format a single `VerificationResult` as a human-readable string.
 pub fn format_result(result: &VerificationResult) -> String {
     match result {
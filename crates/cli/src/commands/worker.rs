@@ -0,0 +1,34 @@
+use super::{apply::handle_execution_result, require_shared_state};
+use crate::{config, error::CliError};
+use engine_infra::shutdown::ShutdownSignal;
+use engine_processing::EnvContext;
+use engine_runtime::execution::executor;
+use model::execution::flags::{ExecutionFlags, IntegrityMode};
+use std::sync::Arc;
+use tracing::info;
+
+/// Runs a worker for a distributed migration started with `apply --coordinator`.
+/// The worker loads the same config, so it joins the coordinator's run.
+pub async fn execute(
+    config_path: Option<String>,
+    worker_id: Option<String>,
+    integrity: IntegrityMode,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), CliError> {
+    let config_path = config::resolve_path(config_path)?;
+    let plan = config::load_plan(&config_path, false, env.clone()).await?;
+    require_shared_state(&plan)?;
+
+    let worker_id = worker_id.unwrap_or_else(default_worker_id);
+    info!(config = %config_path, worker = %worker_id, "starting worker");
+
+    let flags = ExecutionFlags::new(false, integrity);
+    handle_execution_result(executor::work(plan, flags, shutdown, env, &worker_id).await)
+}
+
+/// `<host>-<pid>`; containers set HOSTNAME to the container or pod name.
+fn default_worker_id() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "worker".to_string());
+    format!("{host}-{}", std::process::id())
+}
@@ -1,9 +1,22 @@
-use crate::{compile::ensure_plugins_compiled, error::CliError};
-use engine_core::{context::env::EnvContext, plan::execution::ExecutionPlan};
+use crate::{compile::ensure_plugins_compiled, error::CliError, logger};
+use engine_core::{
+    context::env::EnvContext,
+    plan::{builder::parse_duration, execution::ExecutionPlan},
+};
+use model::execution::state_config::StateConfig;
 use smql_syntax::ast::doc::SmqlDocument;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
 use tracing::{debug, info};
 
+/// State backend given on the command line or in STRATUM_STATE, set once at startup.
+static STATE_OVERRIDE: OnceLock<Option<StateConfig>> = OnceLock::new();
+
+/// Profile given on the command line or in STRATUM_PROFILE, set once at startup.
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
 /// Resolves config file path from CLI argument, environment, or auto-discovery
 pub fn resolve_path(config: Option<String>) -> Result<String, CliError> {
     // Priority order:
@@ -35,24 +48,82 @@ pub fn resolve_path(config: Option<String>) -> Result<String, CliError> {
     }
 }
 
+/// Records the `--state` / `--state-ttl` arguments (falling back to the
+/// STRATUM_STATE environment variable). Called once before any command runs.
+pub fn init_state_override(location: Option<&str>, ttl: Option<&str>) -> Result<(), CliError> {
+    let location = match location {
+        Some(location) => Some(location.to_string()),
+        None => std::env::var("STRATUM_STATE").ok(),
+    };
+    let ttl = ttl
+        .map(parse_duration)
+        .transpose()
+        .map_err(|e| CliError::UserMessage(format!("Invalid --state-ttl: {e}")))?;
+
+    let state = location.map(|location| StateConfig::from_location(&location, ttl));
+    if let Some(state) = &state {
+        info!(
+            backend = state.backend_name(),
+            "using state backend from command line"
+        );
+    }
+    let _ = STATE_OVERRIDE.set(state);
+    Ok(())
+}
+
+/// Records the `--profile` argument (falling back to the STRATUM_PROFILE
+/// environment variable). Called once before any command runs.
+pub fn init_profile(profile: Option<&str>) {
+    let profile = match profile {
+        Some(profile) => Some(profile.to_string()),
+        None => std::env::var("STRATUM_PROFILE").ok(),
+    };
+    let _ = PROFILE.set(profile.filter(|p| !p.is_empty()));
+}
+
+/// Applies the selected profile, if any, to a parsed config
+pub fn apply_profile(doc: &mut SmqlDocument) -> Result<(), CliError> {
+    if let Some(Some(profile)) = PROFILE.get() {
+        info!(profile = %profile, "applying config profile");
+        doc.apply_profile(profile)?;
+    }
+    Ok(())
+}
+
+/// Resolves the state backend
+pub fn resolve_state(configured: &StateConfig) -> StateConfig {
+    // Priority order:
+    // 1. Explicit --state argument
+    // 2. STRATUM_STATE environment variable
+    // 3. The config's `state` block (local disk if absent)
+    match STATE_OVERRIDE.get() {
+        Some(Some(state)) => state.clone(),
+        _ => configured.clone(),
+    }
+}
+
 /// Loads and parses a migration plan from the config file
 pub async fn load_plan(
     path: &str,
     from_ast: bool,
     env: Arc<EnvContext>,
 ) -> Result<ExecutionPlan, CliError> {
-    let source = tokio::fs::read_to_string(path).await?;
-    let doc: SmqlDocument = if from_ast {
+    let mut doc: SmqlDocument = if from_ast {
         // If `from_ast` is true, read the config file as a pre-parsed AST
+        let source = tokio::fs::read_to_string(path).await?;
         serde_json::from_str(&source)?
     } else {
-        // Otherwise, read the config file and parse it
-        smql_syntax::builder::parse(&source)?
+        // Otherwise, parse the config file and the files it includes
+        smql_syntax::builder::parse_file(path)?
     };
+    apply_profile(&mut doc)?;
     let mut plan = ExecutionPlan::build(&doc, env)?;
+    plan.expand_table_patterns().await?;
     plan.config_path = path.to_string();
+    plan.state = resolve_state(&plan.state);
     // Transparently compile any `.js` plugin sources to WASM (cached).
     ensure_plugins_compiled(&mut plan)?;
+    logger::route_pipelines(&plan.pipelines)?;
     Ok(plan)
 }
 
@@ -1,3 +1,4 @@
+use connectors::error::DriverError;
 use engine_planner::builder::errors::{ConnectionError, ReportBuilderError};
 use engine_runtime::{dag::error::DagError, error::MigrationError};
 use engine_verify::error::VerifyError;
@@ -47,6 +48,9 @@ pub enum CliError {
     #[error("Migration paused - resume with the same config to continue")]
     Paused,
 
+    #[error("Database error: {0}")]
+    Driver(#[from] DriverError),
+
     #[error("Plan build error: {0}")]
     PlanBuild(#[from] BuildError),
 
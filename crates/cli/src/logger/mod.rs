@@ -1,21 +1,68 @@
-use crate::Cli;
-use tracing::{Level, info};
-use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use crate::{Cli, error::CliError};
+use connectors::audit;
+use model::execution::pipeline::Pipeline;
+use pipelines::{PipelineFiles, PipelineRoutes, PipelineSpans, RouteFilter};
+use redact::Redacted;
+use std::sync::OnceLock;
+use tracing::{Level, Subscriber, info};
+use tracing_subscriber::{
+    EnvFilter, Layer, filter, fmt, layer::SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+mod pipelines;
+mod redact;
+
+/// Pipeline log routes, shared with the installed subscriber
+static PIPELINE_ROUTES: OnceLock<PipelineRoutes> = OnceLock::new();
 
 /// Initializes the tracing logger based on CLI configuration and mode
 pub fn init(cli: &Cli, is_tui_mode: bool, is_pretty_mode: bool) {
     let log_level = determine_log_level(cli);
-    let env_filter = create_env_filter(log_level);
 
     if is_tui_mode {
-        init_tui_logger(cli, env_filter);
+        init_tui_logger(cli, log_level);
     } else if is_pretty_mode {
-        init_pretty_logger(cli, env_filter);
+        init_pretty_logger(cli, log_level);
     } else if cli.log_file.is_some() {
-        init_dual_logger(cli, env_filter);
+        init_dual_logger(cli, log_level);
     } else {
-        init_stdout_logger(cli, env_filter);
+        init_stdout_logger(cli, log_level);
+    }
+}
+
+/// Applies the `log` blocks of `pipelines`: their events are logged at the
+/// block's level, and written to the block's file instead of the main log.
+/// Replaces the routes of a previously loaded config.
+pub fn route_pipelines(pipelines: &[Pipeline]) -> Result<(), CliError> {
+    let logs = pipelines
+        .iter()
+        .filter_map(|p| p.log.as_ref().map(|log| (p.name.as_str(), log)));
+    routes()
+        .set(logs)
+        .map_err(|e| CliError::Config(format!("failed to open pipeline log file: {e}")))
+}
+
+/// Starts auditing the statements executed on the drivers into `path`,
+/// rotating the file past `max_size_mb` megabytes.
+pub fn init_audit_log(path: Option<&str>, max_size_mb: u64) -> Result<(), CliError> {
+    let Some(path) = path else {
+        return Ok(());
+    };
+    if max_size_mb == 0 {
+        return Err(CliError::UserMessage(
+            "Invalid --audit-log-max-size: must be at least 1".into(),
+        ));
     }
+
+    audit::install(path, max_size_mb * 1024 * 1024)
+        .map_err(|e| CliError::UserMessage(format!("failed to open audit log {path}: {e}")))?;
+    info!(path = %path, "auditing executed statements");
+    Ok(())
+}
+
+fn routes() -> &'static PipelineRoutes {
+    PIPELINE_ROUTES.get_or_init(PipelineRoutes::default)
 }
 
 /// Determines the log level based on CLI arguments and environment
@@ -51,8 +98,33 @@ fn create_env_filter(level: Level) -> EnvFilter {
     EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(format!("{}", level)))
 }
 
+/// Filter of the main log: the level, except for pipelines with a `log` block
+fn main_filter(level: Level) -> RouteFilter {
+    RouteFilter::main(create_env_filter(level), routes().clone())
+}
+
+/// Layers writing the logs of pipelines with `log { file = ... }` to their
+/// files, and tracking which pipeline each thread is running
+fn pipeline_layers<S>(level: Level) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let routes = routes().clone();
+    let file_layer = fmt::layer()
+        .with_writer(Redacted(PipelineFiles::new(routes.clone())))
+        .with_ansi(false)
+        .with_filter(RouteFilter::pipeline_files(
+            create_env_filter(level),
+            routes,
+        ));
+
+    PipelineSpans
+        .with_filter(filter::filter_fn(|meta| meta.is_span()))
+        .and_then(file_layer)
+}
+
 /// Initializes file-only logging for TUI mode
-fn init_tui_logger(cli: &Cli, env_filter: EnvFilter) {
+fn init_tui_logger(cli: &Cli, level: Level) {
     let log_file_path = determine_tui_log_path(cli);
 
     // Open log file
@@ -64,11 +136,12 @@ fn init_tui_logger(cli: &Cli, env_filter: EnvFilter) {
 
     // Set up file-only logging (no stdout to avoid interfering with TUI)
     let file_layer = fmt::layer()
-        .with_writer(std::sync::Arc::new(file))
-        .with_ansi(false); // No colors in file
+        .with_writer(Redacted(std::sync::Arc::new(file)))
+        .with_ansi(false) // No colors in file
+        .with_filter(main_filter(level));
 
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(pipeline_layers(level))
         .with(file_layer)
         .init();
 
@@ -90,7 +163,7 @@ fn determine_tui_log_path(cli: &Cli) -> String {
 }
 
 /// Initializes file-only logging for pretty output mode
-fn init_pretty_logger(cli: &Cli, env_filter: EnvFilter) {
+fn init_pretty_logger(cli: &Cli, level: Level) {
     let log_file_path = determine_pretty_log_path(cli);
 
     // Open log file
@@ -102,11 +175,12 @@ fn init_pretty_logger(cli: &Cli, env_filter: EnvFilter) {
 
     // Set up file-only logging (no stdout to avoid interfering with pretty output)
     let file_layer = fmt::layer()
-        .with_writer(std::sync::Arc::new(file))
-        .with_ansi(false); // No colors in file
+        .with_writer(Redacted(std::sync::Arc::new(file)))
+        .with_ansi(false) // No colors in file
+        .with_filter(main_filter(level));
 
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(pipeline_layers(level))
         .with(file_layer)
         .init();
 
@@ -128,7 +202,7 @@ fn determine_pretty_log_path(cli: &Cli) -> String {
 }
 
 /// Initializes dual logging (stdout + file)
-fn init_dual_logger(cli: &Cli, env_filter: EnvFilter) {
+fn init_dual_logger(cli: &Cli, level: Level) {
     let log_file = cli.log_file.as_ref().unwrap();
 
     let file = std::fs::OpenOptions::new()
@@ -138,28 +212,31 @@ fn init_dual_logger(cli: &Cli, env_filter: EnvFilter) {
         .expect("Failed to open log file");
 
     let file_layer = fmt::layer()
-        .with_writer(std::sync::Arc::new(file))
-        .with_ansi(false); // No colors in file
+        .with_writer(Redacted(std::sync::Arc::new(file)))
+        .with_ansi(false) // No colors in file
+        .with_filter(main_filter(level));
 
     let stdout_layer = fmt::layer()
-        .with_writer(std::io::stdout)
-        .with_ansi(!cli.no_color);
+        .with_writer(Redacted(std::io::stdout))
+        .with_ansi(!cli.no_color)
+        .with_filter(main_filter(level));
 
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(pipeline_layers(level))
         .with(file_layer)
         .with(stdout_layer)
         .init();
 }
 
 /// Initializes stdout-only logging
-fn init_stdout_logger(cli: &Cli, env_filter: EnvFilter) {
+fn init_stdout_logger(cli: &Cli, level: Level) {
     let stdout_layer = fmt::layer()
-        .with_writer(std::io::stdout)
-        .with_ansi(!cli.no_color);
+        .with_writer(Redacted(std::io::stdout))
+        .with_ansi(!cli.no_color)
+        .with_filter(main_filter(level));
 
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(pipeline_layers(level))
         .with(stdout_layer)
         .init();
 }
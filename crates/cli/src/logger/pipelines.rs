@@ -0,0 +1,424 @@
+//! Per-pipeline log levels and files, set by `log {}` blocks.
+//!
+//! The subscriber is installed before any config is read, so the routes live
+//! in a shared table that is filled once the plan is built. An event belongs
+//! to the pipeline whose span (the `pipeline` field of `run_pipeline`) is
+//! entered on the thread that emits it.
+
+use model::execution::log_config::{LogLevel, PipelineLog};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, Mutex, RwLock},
+};
+use tracing::{
+    Metadata, Subscriber,
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span,
+    subscriber::Interest,
+};
+use tracing_subscriber::{
+    EnvFilter,
+    fmt::MakeWriter,
+    layer::{Context, Filter, Layer},
+    registry::LookupSpan,
+};
+
+/// Span field naming the pipeline a span runs
+const PIPELINE_FIELD: &str = "pipeline";
+
+thread_local! {
+    /// Pipelines whose spans are entered on this thread, innermost last
+    static ENTERED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Level and file of one pipeline with a `log` block
+struct Route {
+    level: Option<LevelFilter>,
+    file: Option<Arc<Mutex<File>>>,
+}
+
+/// Routes of the pipelines with a `log` block, by pipeline name. Shared by
+/// every filter and writer of the subscriber.
+#[derive(Clone, Default)]
+pub struct PipelineRoutes {
+    routes: Arc<RwLock<HashMap<String, Route>>>,
+}
+
+impl PipelineRoutes {
+    /// Replaces the routes with the `log` blocks of the given pipelines,
+    /// opening (and creating) each log file in append mode.
+    pub fn set<'a>(
+        &self,
+        logs: impl IntoIterator<Item = (&'a str, &'a PipelineLog)>,
+    ) -> io::Result<()> {
+        let mut files: HashMap<&str, Arc<Mutex<File>>> = HashMap::new();
+        let mut routes = HashMap::new();
+
+        for (pipeline, PipelineLog { level, file }) in logs {
+            if level.is_none() && file.is_none() {
+                continue;
+            }
+
+            // Pipelines logging to the same file share one handle
+            let file = match file {
+                Some(path) => match files.get(path.as_str()) {
+                    Some(file) => Some(file.clone()),
+                    None => {
+                        let file = Arc::new(Mutex::new(open_log_file(path)?));
+                        files.insert(path, file.clone());
+                        Some(file)
+                    }
+                },
+                None => None,
+            };
+            let route = Route {
+                level: level.map(level_filter),
+                file,
+            };
+            routes.insert(pipeline.to_string(), route);
+        }
+
+        *self.routes.write().unwrap_or_else(|e| e.into_inner()) = routes;
+        // Callsites cached as always/never enabled must be re-evaluated
+        tracing::callsite::rebuild_interest_cache();
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.routes.read().map(|r| r.is_empty()).unwrap_or(true)
+    }
+
+    /// Runs `f` with the route of the pipeline entered on this thread, if
+    /// that pipeline has one.
+    fn with_current<T>(&self, f: impl FnOnce(Option<&Route>) -> T) -> T {
+        ENTERED.with(|entered| {
+            let entered = entered.borrow();
+            let routes = self.routes.read().unwrap_or_else(|e| e.into_inner());
+            f(entered.last().and_then(|name| routes.get(name)))
+        })
+    }
+}
+
+fn open_log_file(path: &str) -> io::Result<File> {
+    if let Some(parent) = Path::new(path).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+}
+
+fn level_filter(level: LogLevel) -> LevelFilter {
+    match level {
+        LogLevel::Error => LevelFilter::ERROR,
+        LogLevel::Warn => LevelFilter::WARN,
+        LogLevel::Info => LevelFilter::INFO,
+        LogLevel::Debug => LevelFilter::DEBUG,
+        LogLevel::Trace => LevelFilter::TRACE,
+    }
+}
+
+/// Name of the pipeline a span runs, kept in the span's extensions
+struct PipelineName(String);
+
+struct PipelineNameVisitor(Option<String>);
+
+impl Visit for PipelineNameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == PIPELINE_FIELD {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == PIPELINE_FIELD {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Tracks which pipeline's span is entered on each thread. Must see every
+/// span, so it is registered with a filter that enables all spans.
+pub struct PipelineSpans;
+
+impl<S> Layer<S> for PipelineSpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field(PIPELINE_FIELD).is_none() {
+            return;
+        }
+        let mut visitor = PipelineNameVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(name), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().replace(PipelineName(name));
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(PipelineName(name)) = span.extensions().get::<PipelineName>()
+        {
+            ENTERED.with(|entered| entered.borrow_mut().push(name.clone()));
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && span.extensions().get::<PipelineName>().is_some()
+        {
+            ENTERED.with(|entered| entered.borrow_mut().pop());
+        }
+    }
+}
+
+/// Which output a [`RouteFilter`] guards
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Output {
+    /// The main log (stdout and/or `--log-file`)
+    Main,
+    /// The files of pipelines with `log { file = ... }`
+    PipelineFiles,
+}
+
+/// Filter of one log output. Events of a pipeline with a `log` block follow
+/// its route: they go to its file if it has one, otherwise to the main log,
+/// at its level. Spans, and events outside such pipelines, use `fallback`.
+pub struct RouteFilter {
+    fallback: EnvFilter,
+    routes: PipelineRoutes,
+    output: Output,
+}
+
+impl RouteFilter {
+    pub fn main(fallback: EnvFilter, routes: PipelineRoutes) -> Self {
+        Self {
+            fallback,
+            routes,
+            output: Output::Main,
+        }
+    }
+
+    pub fn pipeline_files(fallback: EnvFilter, routes: PipelineRoutes) -> Self {
+        Self {
+            fallback,
+            routes,
+            output: Output::PipelineFiles,
+        }
+    }
+}
+
+impl<S> Filter<S> for RouteFilter
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        if meta.is_span() {
+            return Filter::<S>::enabled(&self.fallback, meta, cx);
+        }
+
+        self.routes.with_current(|route| match route {
+            None => self.output == Output::Main && Filter::<S>::enabled(&self.fallback, meta, cx),
+            Some(route) if route.file.is_some() != (self.output == Output::PipelineFiles) => false,
+            Some(route) => match route.level {
+                Some(level) => level >= *meta.level(),
+                None => Filter::<S>::enabled(&self.fallback, meta, cx),
+            },
+        })
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        let interest = Filter::<S>::callsite_enabled(&self.fallback, meta);
+        if meta.is_event() && !self.routes.is_empty() {
+            // Whether an event is enabled depends on the pipeline emitting it
+            Interest::sometimes()
+        } else if self.output == Output::PipelineFiles && meta.is_event() {
+            Interest::never()
+        } else {
+            interest
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        // Routes can be added after the subscriber is installed
+        None
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        Filter::<S>::on_new_span(&self.fallback, attrs, id, ctx)
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        Filter::<S>::on_record(&self.fallback, id, values, ctx)
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        Filter::<S>::on_enter(&self.fallback, id, ctx)
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        Filter::<S>::on_exit(&self.fallback, id, ctx)
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        Filter::<S>::on_close(&self.fallback, id, ctx)
+    }
+}
+
+/// Writer for the pipeline files: each event is appended to the file of the
+/// pipeline entered on the emitting thread.
+#[derive(Clone)]
+pub struct PipelineFiles {
+    routes: PipelineRoutes,
+}
+
+impl PipelineFiles {
+    pub fn new(routes: PipelineRoutes) -> Self {
+        Self { routes }
+    }
+}
+
+pub struct PipelineFileWriter(Option<Arc<Mutex<File>>>);
+
+impl Write for PipelineFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &self.0 {
+            Some(file) => file.lock().unwrap_or_else(|e| e.into_inner()).write(buf),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        // One locked write per event keeps lines from interleaving
+        match &self.0 {
+            Some(file) => file
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .write_all(buf),
+            None => Ok(()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &self.0 {
+            Some(file) => file.lock().unwrap_or_else(|e| e.into_inner()).flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for PipelineFiles {
+    type Writer = PipelineFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        PipelineFileWriter(
+            self.routes
+                .with_current(|route| route.and_then(|route| route.file.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::{debug, info, info_span, warn};
+    use tracing_subscriber::{filter, fmt, layer::SubscriberExt};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Captured {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_pipeline_routes() {
+        let dir = std::env::temp_dir().join(format!("stratum-log-{}", std::process::id()));
+        let orders_log = dir.join("orders.log");
+        let orders = PipelineLog {
+            level: Some(LogLevel::Debug),
+            file: Some(orders_log.to_string_lossy().to_string()),
+        };
+        let users = PipelineLog {
+            level: Some(LogLevel::Warn),
+            file: None,
+        };
+
+        let routes = PipelineRoutes::default();
+        routes
+            .set([("orders", &orders), ("users", &users)])
+            .unwrap();
+
+        let main = Captured::default();
+        let main_writer = main.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                PipelineSpans
+                    .with_filter(filter::filter_fn(|meta| meta.is_span()))
+                    .and_then(
+                        fmt::layer()
+                            .with_writer(PipelineFiles::new(routes.clone()))
+                            .with_ansi(false)
+                            .with_filter(RouteFilter::pipeline_files(
+                                EnvFilter::new("info"),
+                                routes.clone(),
+                            )),
+                    ),
+            )
+            .with(
+                fmt::layer()
+                    .with_writer(move || main_writer.clone())
+                    .with_ansi(false)
+                    .with_filter(RouteFilter::main(EnvFilter::new("info"), routes)),
+            );
+
+        tracing::subscriber::with_default(subscriber, || {
+            info!("plan loaded");
+            debug!("hidden outside pipelines");
+            info_span!("run_pipeline", pipeline = "orders").in_scope(|| {
+                debug!("orders debug");
+            });
+            info_span!("run_pipeline", pipeline = "users").in_scope(|| {
+                info!("users info");
+                warn!("users warn");
+            });
+            info_span!("run_pipeline", pipeline = "events").in_scope(|| {
+                info!("events info");
+            });
+        });
+
+        let main = main.text();
+        assert!(main.contains("plan loaded"), "{main}");
+        assert!(main.contains("users warn"), "{main}");
+        assert!(main.contains("events info"), "{main}");
+        for hidden in ["hidden outside pipelines", "orders debug", "users info"] {
+            assert!(!main.contains(hidden), "{main}");
+        }
+
+        let orders_file = std::fs::read_to_string(&orders_log).unwrap();
+        assert!(orders_file.contains("orders debug"), "{orders_file}");
+        assert!(!orders_file.contains("users"), "{orders_file}");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+//! Every log line is written through [`Redacted`], so connection
+//! credentials in messages, fields and span context are masked whichever
+//! call site logged them.
+
+use model::core::redact::redact_credentials;
+use std::io::{self, Write};
+use tracing::Metadata;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The writers of `M`, masking connection credentials in what they write
+#[derive(Clone)]
+pub struct Redacted<M>(pub M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacted<M> {
+    type Writer = RedactedWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactedWriter(self.0.make_writer())
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        RedactedWriter(self.0.make_writer_for(meta))
+    }
+}
+
+/// A formatted event is written in one call, so a connection string is
+/// never split across writes
+pub struct RedactedWriter<W>(W);
+
+impl<W: Write> Write for RedactedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => {
+                self.0.write_all(redact_credentials(text).as_bytes())?;
+                Ok(buf.len())
+            }
+            Err(_) => self.0.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::{error, info_span};
+    use tracing_subscriber::{fmt, layer::SubscriberExt};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_log_lines_are_redacted() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .with_writer(Redacted(move || writer.clone()))
+                .with_ansi(false),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            info_span!("connect", url = "postgres://etl:s3cret@pg/dw").in_scope(|| {
+                error!(
+                    error = "access denied for mysql://app:hunter2@db/shop",
+                    "connection failed"
+                );
+            });
+        });
+
+        let text = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(text.contains("postgres://etl:***@pg/dw"), "{text}");
+        assert!(text.contains("mysql://app:***@db/shop"), "{text}");
+        assert!(
+            !text.contains("s3cret") && !text.contains("hunter2"),
+            "{text}"
+        );
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
 use clap::Parser;
 use engine_infra::shutdown::ShutdownSignal;
 use engine_processing::EnvContext;
+use model::core::redact::redact_credentials;
 use std::{process, sync::Arc};
 use tracing::info;
 
@@ -46,6 +47,9 @@ async fn run(cli: Cli) -> Result<(), CliError> {
 
     // Initialize environment variables
     let env = init_environment(cli.env_file.as_deref())?;
+    logger::init_audit_log(cli.audit_log.as_deref(), cli.audit_log_max_size)?;
+    config::init_state_override(cli.state.as_deref(), cli.state_ttl.as_deref())?;
+    config::init_profile(cli.profile.as_deref());
 
     // Execute the command
     execute_command(&cli, shutdown, env).await
@@ -80,7 +84,7 @@ fn handle_error(error: CliError) -> i32 {
             2
         }
         CliError::UserMessage(msg) => {
-            eprintln!("{}", msg);
+            eprintln!("{}", redact_credentials(&msg));
             1
         }
         _ => {
@@ -1,9 +1,11 @@
 use crate::error::CliError;
 use engine_planner::plan::execution::migration_report::MigrationReport;
+use model::core::redact::redact_credentials;
 
 async fn generate_report_json(plan: MigrationReport) -> Result<String, CliError> {
     let json = serde_json::to_string_pretty(&plan)?;
-    Ok(json)
+    // Reports are shared, so no connection string in one keeps its password
+    Ok(redact_credentials(&json).into_owned())
 }
 
 pub async fn write_report(plan: MigrationReport, path: String) -> Result<(), CliError> {
@@ -1,7 +1,10 @@
 use crossterm::ExecutableCommand;
 use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
 use engine_core::event_bus::bus::EventBus;
-use model::events::migration::MigrationEvent;
+use model::{
+    core::redact::redact_credentials,
+    events::migration::{CircuitState, MigrationEvent},
+};
 use std::collections::HashMap;
 use std::io::{self, Write, stdout};
 use std::time::{Duration, Instant};
@@ -130,6 +133,38 @@ impl PrettyPrinter {
                 self.print_snapshot_completed(*rows_processed, *duration_ms)?;
             }
 
+            MigrationEvent::CircuitBreakerChanged {
+                item_id,
+                state,
+                consecutive_failures,
+                error,
+                ..
+            } => {
+                self.print_circuit_state(item_id, *state, *consecutive_failures, error.as_deref())?;
+            }
+
+            MigrationEvent::ViewRefreshCompleted {
+                view, duration_ms, ..
+            } => {
+                self.print_line(
+                    Color::Green,
+                    "✓",
+                    &format!(
+                        "Materialized view '{}' refreshed ({})",
+                        view,
+                        format_duration(Duration::from_millis(*duration_ms))
+                    ),
+                )?;
+            }
+
+            MigrationEvent::ViewRefreshFailed { view, error, .. } => {
+                self.print_line(
+                    Color::Red,
+                    "✗",
+                    &format!("Materialized view '{}' refresh failed: {}", view, error),
+                )?;
+            }
+
             // Ignore other events in pretty mode
             _ => {}
         }
@@ -229,6 +264,39 @@ impl PrettyPrinter {
         )
     }
 
+    /// Prints a destination circuit breaker transition
+    fn print_circuit_state(
+        &self,
+        item_id: &str,
+        state: CircuitState,
+        failures: u32,
+        error: Option<&str>,
+    ) -> io::Result<()> {
+        let name = self.get_pipeline_name(item_id);
+        match state {
+            CircuitState::Open => self.print_line(
+                Color::Yellow,
+                "⏸",
+                &format!(
+                    "Pipeline '{}' paused writes after {} failures: {}",
+                    name,
+                    failures,
+                    error.unwrap_or("destination unavailable")
+                ),
+            ),
+            CircuitState::HalfOpen => self.print_line(
+                Color::Yellow,
+                "↻",
+                &format!("Pipeline '{}' destination reachable, retrying", name),
+            ),
+            CircuitState::Closed => self.print_line(
+                Color::Green,
+                "▶",
+                &format!("Pipeline '{}' resumed writes", name),
+            ),
+        }
+    }
+
     /// Prints snapshot completed summary
     fn print_snapshot_completed(&self, rows_processed: u64, duration_ms: u64) -> io::Result<()> {
         let duration = Duration::from_millis(duration_ms);
@@ -285,7 +353,7 @@ impl PrettyPrinter {
         Ok(())
     }
 
-    /// Prints a colored line to stdout
+    /// Prints a colored line to stdout, with connection credentials masked
     fn print_line(&self, color: Color, symbol: &str, message: &str) -> io::Result<()> {
         let mut out = stdout();
         out.execute(SetForegroundColor(Color::DarkGrey))?;
@@ -295,7 +363,7 @@ impl PrettyPrinter {
         out.execute(Print(symbol))?;
         out.execute(Print(" "))?;
         out.execute(ResetColor)?;
-        out.execute(Print(message))?;
+        out.execute(Print(redact_credentials(message)))?;
         out.execute(Print("\n"))?;
         out.flush()?;
         Ok(())
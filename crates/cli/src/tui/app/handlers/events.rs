@@ -4,7 +4,10 @@ use crate::tui::{
 };
 use crossterm::event::{Event, EventStream, KeyEvent};
 use futures_util::StreamExt;
-use model::events::migration::MigrationEvent;
+use model::{
+    core::redact::redact_credentials,
+    events::migration::{CircuitState, MigrationEvent},
+};
 use std::{collections::HashMap, time::Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, warn};
@@ -106,6 +109,10 @@ fn dispatch_event(
             handle_error_event(pipelines, errors, item_id, &event);
         }
 
+        ME::CircuitBreakerChanged { .. } => {
+            handle_circuit_event(pipelines, errors, item_id, &event);
+        }
+
         _ => debug!(event = %event.event_type(), "unhandled event type"),
     }
 }
@@ -258,6 +265,39 @@ fn handle_error_event(
     record_error(errors, Some(id), &msg);
 }
 
+/// Writes pause while the destination circuit breaker is open.
+fn handle_circuit_event(
+    pipelines: &mut std::collections::HashMap<String, PipelineState>,
+    errors: &mut Vec<ErrorEntry>,
+    id: &str,
+    event: &MigrationEvent,
+) {
+    let p = pipelines.get_mut(id).unwrap();
+
+    if let MigrationEvent::CircuitBreakerChanged {
+        state,
+        consecutive_failures,
+        error,
+        ..
+    } = event
+    {
+        match state {
+            CircuitState::Open => {
+                p.status = PipelineStatus::Paused;
+                let msg = format!(
+                    "Destination circuit open after {} failures: {}",
+                    consecutive_failures,
+                    error.as_deref().unwrap_or("unknown error")
+                );
+                p.last_error = Some(msg.clone());
+                record_error(errors, Some(id), &msg);
+            }
+            CircuitState::HalfOpen => {}
+            CircuitState::Closed => p.status = PipelineStatus::Running,
+        }
+    }
+}
+
 fn handle_io_event(
     pipelines: &mut std::collections::HashMap<String, PipelineState>,
     id: &str,
@@ -279,7 +319,10 @@ fn handle_io_event(
 fn record_error(errors: &mut Vec<ErrorEntry>, item_id: Option<&str>, message: &str) {
     errors.insert(
         0,
-        ErrorEntry::new(message.to_string(), item_id.map(|s| s.to_string())),
+        ErrorEntry::new(
+            redact_credentials(message).into_owned(),
+            item_id.map(|s| s.to_string()),
+        ),
     );
 
     // Keep only last 100 errors
@@ -18,7 +18,7 @@ use model::{
     events::migration::MigrationEvent,
     execution::flags::{ExecutionFlags, IntegrityMode},
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc;
 
 /// Orchestrates the TUI lifecycle and background engine tasks
@@ -30,15 +30,23 @@ use tokio::sync::mpsc;
 /// 4. Spawns background tasks for event forwarding, command handling, and execution
 /// 5. Runs the main TUI application loop
 /// 6. Ensures proper terminal cleanup on exit
+///
+/// `source_snapshots` pins source connections to exported snapshots, as set
+/// by `pin_source_snapshots`; the caller keeps the snapshots alive.
+/// `resumed_hash` continues a paused run whose config was edited since.
 pub async fn run_tui(
     config_path: String,
     exact_filter: bool,
     integrity: IntegrityMode,
+    source_snapshots: HashMap<String, String>,
+    resumed_hash: Option<String>,
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), CliError> {
     // Build Plan (Outside TUI mode so errors/logs show in standard terminal)
-    let plan_context = build_plan(&config_path, exact_filter, env.clone()).await?;
+    let mut plan_context =
+        build_plan(&config_path, exact_filter, resumed_hash, env.clone()).await?;
+    plan_context.core_plan.source_snapshots = source_snapshots;
 
     // Initialize Terminal Guard (Restores terminal on drop)
     let mut guard = TerminalGuard::init()?;
@@ -80,6 +88,7 @@ pub async fn run_tui(
 async fn build_plan(
     config_path: &str,
     exact_filter: bool,
+    resumed_hash: Option<String>,
     env: Arc<EnvContext>,
 ) -> Result<crate::tui::plan::PlanContext, CliError> {
     let spinner = ProgressBar::new_spinner();
@@ -101,7 +110,7 @@ async fn build_plan(
     spinner.set_message(msg);
     spinner.enable_steady_tick(std::time::Duration::from_millis(80));
 
-    let plan_context = build_plan_context(config_path, exact_filter, env).await?;
+    let plan_context = build_plan_context(config_path, exact_filter, resumed_hash, env).await?;
 
     // Clear the spinner before TUI takes over
     spinner.finish_and_clear();
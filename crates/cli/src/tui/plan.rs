@@ -1,11 +1,12 @@
 use crate::{
+    config,
     error::CliError,
     tui::{pipeline::PipelineState, planner::initialize_pipelines_from_plan},
 };
 use engine_core::{context::env::EnvContext, plan::execution::ExecutionPlan as CoreExecutionPlan};
 use engine_planner::{builder::ReportBuilder, plan::execution::migration_report::MigrationReport};
 use engine_runtime::dag::{Dag, builder::DagBuilder};
-use smql_syntax::builder::parse;
+use smql_syntax::builder::parse_file;
 use std::{collections::HashMap, path::Path, sync::Arc};
 use tracing::info;
 
@@ -17,21 +18,25 @@ pub struct PlanContext {
     pub pipelines: HashMap<String, PipelineState>,
 }
 
-/// Builds execution plan from SMQL configuration file
+/// Builds execution plan from SMQL configuration file. `resumed_hash` is the
+/// hash of the paused run a resume continues after the config was edited.
 pub async fn build_plan_context(
     config_path: &str,
     exact_filter: bool,
+    resumed_hash: Option<String>,
     env: Arc<EnvContext>,
 ) -> Result<PlanContext, CliError> {
     info!(config = %config_path, "building execution plan");
 
     // Parse SMQL
-    let smql_content = std::fs::read_to_string(config_path)?;
-    let ast = parse(&smql_content)?;
+    let mut ast = parse_file(config_path)?;
+    config::apply_profile(&mut ast)?;
 
     // Build core plan
     let mut core_plan = CoreExecutionPlan::build(&ast, env)?;
+    core_plan.expand_table_patterns().await?;
     core_plan.config_path = config_path.to_string();
+    core_plan.resumed_hash = resumed_hash;
 
     // Build DAG
     let dag = build_dag(&core_plan)?;
@@ -87,8 +87,10 @@ mod tests {
             filtered_rows: None,
             columns: vec![],
             primary_key: vec![],
+            collation: None,
             indexes: vec![],
             size_bytes: 0,
+            avg_row_bytes: None,
             last_analyzed: chrono::Utc::now(),
         }
     }
@@ -137,6 +139,7 @@ mod tests {
             diagnostics: vec![],
             estimations: PipelineEstimations {
                 duration: Default::default(),
+                volume: Default::default(),
                 rows_per_second: 100,
                 batches: 10,
                 memory_mb: 50,
@@ -197,6 +200,7 @@ mod tests {
             diagnostics: vec![],
             estimations: PipelineEstimations {
                 duration: Default::default(),
+                volume: Default::default(),
                 rows_per_second: 200,
                 batches: 25,
                 memory_mb: 100,
@@ -274,6 +278,7 @@ mod tests {
             diagnostics: vec![],
             estimations: PipelineEstimations {
                 duration: Default::default(),
+                volume: Default::default(),
                 rows_per_second: 100,
                 batches: 10,
                 memory_mb: 50,
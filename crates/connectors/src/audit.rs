@@ -0,0 +1,227 @@
+//! Statement audit log.
+//!
+//! Once installed, every DDL and DML statement the drivers execute is appended
+//! to the audit file as one JSON line: the statement, a hash of its bound
+//! parameters (never the values themselves), the batch it was executed for and
+//! its duration. The file is rotated when it grows past its size limit.
+
+use chrono::{DateTime, Utc};
+use model::{core::value::Value, integrity::canonical::serialize_value, records::Record};
+use serde::Serialize;
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    future::Future,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+use tracing::warn;
+
+/// Rotated files kept next to the audit log; `<file>.1` is the most recent
+pub const KEPT_FILES: usize = 5;
+
+static AUDIT_LOG: OnceLock<Mutex<AuditFile>> = OnceLock::new();
+
+tokio::task_local! {
+    static BATCH_ID: String;
+}
+
+/// Start auditing statements into `path`, rotating it past `max_bytes`.
+pub fn install(path: impl Into<PathBuf>, max_bytes: u64) -> io::Result<()> {
+    let file = AuditFile::open(path.into(), max_bytes)?;
+    AUDIT_LOG
+        .set(Mutex::new(file))
+        .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "audit log already installed"))
+}
+
+pub fn is_enabled() -> bool {
+    AUDIT_LOG.get().is_some()
+}
+
+/// Run `fut` with the statements it executes attributed to `batch_id`.
+pub async fn in_batch<F: Future>(batch_id: &str, fut: F) -> F::Output {
+    if !is_enabled() {
+        return fut.await;
+    }
+    BATCH_ID.scope(batch_id.to_string(), fut).await
+}
+
+/// Execute `fut`, auditing `sql` with the hash `params` computes. The hash is
+/// only computed when the audit log is installed.
+pub(crate) async fn statement<T, E, F>(
+    sql: &str,
+    params: impl FnOnce() -> Option<String>,
+    fut: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let Some(log) = AUDIT_LOG.get() else {
+        return fut.await;
+    };
+
+    let params_hash = params();
+    let start = Instant::now();
+    let result = fut.await;
+
+    let batch_id = BATCH_ID.try_with(Clone::clone).ok();
+    let entry = AuditEntry {
+        timestamp: Utc::now(),
+        statement: sql,
+        params_hash: params_hash.as_deref(),
+        batch_id: batch_id.as_deref(),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        ok: result.is_ok(),
+    };
+    if let Err(e) = log.lock().unwrap().append(&entry) {
+        warn!(error = %e, "failed to write audit log entry");
+    }
+
+    result
+}
+
+/// Hash of bound parameters; `None` for a statement without any.
+pub(crate) fn hash_params(params: &[Value]) -> Option<String> {
+    if params.is_empty() {
+        return None;
+    }
+    let mut buf = Vec::with_capacity(params.len() * 16);
+    for value in params {
+        serialize_value(value, &mut buf);
+    }
+    Some(blake3::hash(&buf).to_hex().to_string())
+}
+
+/// Hash of the values of `rows`, for statements streaming them (COPY).
+pub(crate) fn hash_rows(rows: &[Record]) -> Option<String> {
+    let values = rows
+        .iter()
+        .flat_map(|row| &row.fields)
+        .map(|f| f.value.clone().unwrap_or(Value::Null))
+        .collect::<Vec<_>>();
+    hash_params(&values)
+}
+
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    timestamp: DateTime<Utc>,
+    statement: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    batch_id: Option<&'a str>,
+    duration_ms: f64,
+    ok: bool,
+}
+
+/// The audit file and its size, rotated by `append`
+struct AuditFile {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    len: u64,
+}
+
+impl AuditFile {
+    fn open(path: PathBuf, max_bytes: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            file,
+            len,
+        })
+    }
+
+    fn append(&mut self, entry: &AuditEntry<'_>) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        if self.len > 0 && self.len + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(&line)?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `<file>.N` to `<file>.N+1`, dropping the oldest, and start a new file.
+    fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..KEPT_FILES).rev() {
+            match fs::rename(rotated(&self.path, n), rotated(&self.path, n + 1)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        fs::rename(&self.path, rotated(&self.path, 1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(statement: &str) -> AuditEntry<'_> {
+        AuditEntry {
+            timestamp: Utc::now(),
+            statement,
+            params_hash: None,
+            batch_id: Some("batch-1"),
+            duration_ms: 1.5,
+            ok: true,
+        }
+    }
+
+    #[test]
+    fn test_hash_params() {
+        let a = hash_params(&[Value::Int(1), Value::String("secret".into())]).unwrap();
+        let b = hash_params(&[Value::Int(1), Value::String("secret".into())]).unwrap();
+        let c = hash_params(&[Value::Int(2), Value::String("secret".into())]).unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(!a.contains("secret"));
+        assert_eq!(hash_params(&[]), None);
+    }
+
+    #[test]
+    fn test_audit_file_rotation() {
+        let dir = std::env::temp_dir().join(format!("stratum-audit-{}", std::process::id()));
+        let path = dir.join("audit.log");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut file = AuditFile::open(path.clone(), 200).unwrap();
+        for i in 0..20 {
+            file.append(&entry(&format!("INSERT INTO t VALUES ($1) -- {i}")))
+                .unwrap();
+        }
+
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.contains("-- 19"));
+        assert!(current.contains(r#""batch_id":"batch-1""#));
+        assert!(fs::metadata(&path).unwrap().len() <= 200);
+        assert!(rotated(&path, KEPT_FILES).exists());
+        assert!(!rotated(&path, KEPT_FILES + 1).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,29 +1,44 @@
-use crate::drivers::csv::{
-    error::FileError,
-    metadata::{CsvColumnMetadata, CsvMetadata, normalize_col_name},
-    settings::CsvSettings,
-    types::CsvType,
+use crate::{
+    drivers::csv::{
+        encoding::DecodedFile,
+        error::FileError,
+        metadata::{CsvColumnMetadata, CsvMetadata, normalize_col_name},
+        rejects::{ParseErrorReport, Rejects},
+        settings::{CsvSettings, ParseErrorPolicy, SKIP_COLUMN},
+        types::CsvType,
+    },
+    sql::metadata::capabilities::Capabilities,
 };
-use csv::Position;
+use csv::{ErrorKind, Position, StringRecord};
 use model::core::types::{IntSize, Type};
-use std::{
-    fs::File,
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
 pub struct CsvAdapter {
     /// Used only when inferring schema or re-reading headers
-    pub meta_reader: Arc<Mutex<csv::Reader<File>>>,
+    pub meta_reader: Arc<Mutex<csv::Reader<DecodedFile>>>,
 
     /// Used as a one-pass streaming iterator for actual data rows
-    pub data_iter: Arc<Mutex<csv::StringRecordsIntoIter<File>>>,
+    pub data_iter: Arc<Mutex<csv::StringRecordsIntoIter<DecodedFile>>>,
 
     pub settings: CsvSettings,
+    /// Name of each column of the file by position, `_skip` for a column
+    /// that is not read
     pub headers: Vec<String>,
+
+    /// Rows left out under the `on_parse_error` policy
+    pub rejects: Arc<Mutex<Rejects>>,
 }
 
 impl CsvAdapter {
+    /// CSV files are read front to back and never written to.
+    pub fn capabilities() -> Capabilities {
+        Capabilities {
+            pagination: &["default"],
+            ..Default::default()
+        }
+    }
+
     pub fn new(file_path: &str, settings: CsvSettings) -> Result<Self, FileError> {
         // Build a shared builder so we don't repeat options
         let mut builder = csv::ReaderBuilder::new();
@@ -33,35 +48,47 @@ impl CsvAdapter {
             .flexible(true);
 
         // Open file + reader for metadata
-        let meta_file = File::open(file_path)?;
+        let meta_file = DecodedFile::open(file_path, settings.format.encoding)?;
         let mut meta_rdr = builder.from_reader(meta_file);
-        let headers = meta_rdr.headers()?.iter().map(String::from).collect();
+        let headers = if settings.format.columns.is_empty() {
+            meta_rdr.headers()?.iter().map(String::from).collect()
+        } else {
+            settings.format.columns.clone()
+        };
 
         // Open file + into_records iterator for streaming data
-        let data_file = File::open(file_path)?;
+        let data_file = DecodedFile::open(file_path, settings.format.encoding)?;
         let data_rdr = builder.from_reader(data_file);
         let data_iter = data_rdr.into_records();
 
+        let rejects = Rejects::new(file_path, settings.format.on_parse_error);
         Ok(CsvAdapter {
             meta_reader: Arc::new(Mutex::new(meta_rdr)),
             data_iter: Arc::new(Mutex::new(data_iter)),
             headers,
             settings,
+            rejects: Arc::new(Mutex::new(rejects)),
         })
     }
 
-    pub fn read(&mut self, batch_size: usize) -> Result<Vec<csv::StringRecord>, FileError> {
+    pub fn read(&mut self, batch_size: usize) -> Result<Vec<StringRecord>, FileError> {
         let mut records = self
             .data_iter
             .lock()
             .map_err(|_| FileError::LockError("Failed to lock CSV reader".into()))?;
 
         let mut batch = Vec::with_capacity(batch_size);
-        for _ in 0..batch_size {
-            if let Some(record) = records.next() {
-                batch.push(record?);
-            } else {
-                break;
+        while batch.len() < batch_size {
+            let record = match records.next() {
+                Some(Ok(record)) => record,
+                Some(Err(e)) => {
+                    self.reject_error(e)?;
+                    continue;
+                }
+                None => break,
+            };
+            if let Some(record) = self.fit_record(record)? {
+                batch.push(self.settings.format.normalize_record(record));
             }
         }
 
@@ -76,10 +103,11 @@ impl CsvAdapter {
             .map_err(|_| FileError::LockError("Failed to lock CSV reader".into()))?;
 
         // Initialize column metadata from headers
-        let headers = reader.headers()?;
-        let mut columns: Vec<CsvColumnMetadata> = headers
+        let mut columns: Vec<CsvColumnMetadata> = self
+            .headers
             .iter()
             .enumerate()
+            .filter(|(_, h)| *h != SKIP_COLUMN)
             .map(|(i, h)| CsvColumnMetadata {
                 name: normalize_col_name(h),
                 data_type: Type::Int {
@@ -94,13 +122,25 @@ impl CsvAdapter {
             .collect();
 
         let skip = if self.settings.has_headers { 1 } else { 0 };
-        for result in reader.records().skip(skip).take(self.settings.sample_size) {
-            let record = result?;
-            for (col_meta, field) in columns.iter_mut().zip(record.iter()) {
+        // Rows that cannot be read are reported as the data is read
+        let fail = self.settings.format.on_parse_error == ParseErrorPolicy::Fail;
+        let records = reader.records().skip(skip).map(|result| match result {
+            Ok(record) => self.fit_record(record),
+            Err(e) if fail || matches!(e.kind(), ErrorKind::Io(_)) => Err(e.into()),
+            Err(_) => Ok(None),
+        });
+        for record in records
+            .filter_map(Result::transpose)
+            .take(self.settings.sample_size)
+        {
+            let record = record?;
+            for col_meta in columns.iter_mut() {
+                let field = record.get(col_meta.ordinal).unwrap_or("");
                 if field.is_empty() {
                     col_meta.is_nullable = true;
                 }
-                col_meta.data_type = col_meta.data_type.promote(field);
+                let field = self.settings.format.normalize(field);
+                col_meta.data_type = col_meta.data_type.promote(&field);
             }
         }
 
@@ -117,6 +157,47 @@ impl CsvAdapter {
         })
     }
 
+    /// `record` as the connection's `ragged_rows` reads it, `None` when it
+    /// is skipped
+    pub fn fit_record(&self, record: StringRecord) -> Result<Option<StringRecord>, FileError> {
+        self.settings
+            .format
+            .fit_record(record, self.headers.len())
+            .map_err(FileError::InvalidFormat)
+    }
+
+    /// Leaves out a row the reader rejected, under the `on_parse_error`
+    /// policy. I/O errors are not a row's and always fail.
+    pub fn reject_error(&self, error: csv::Error) -> Result<(), FileError> {
+        if matches!(error.kind(), ErrorKind::Io(_)) {
+            return Err(error.into());
+        }
+        let line = error.position().map_or(0, |p| p.line());
+        self.lock_rejects()?.reject(line, "", &error.to_string())
+    }
+
+    /// Leaves out a row with a cell that cannot be parsed, under the
+    /// `on_parse_error` policy.
+    pub fn reject_record(&self, record: &StringRecord, reason: &str) -> Result<(), FileError> {
+        let line = record.position().map_or(0, |p| p.line());
+        let raw = record
+            .iter()
+            .collect::<Vec<_>>()
+            .join(&self.settings.delimiter.to_string());
+        self.lock_rejects()?.reject(line, &raw, reason)
+    }
+
+    /// Rows left out so far because they could not be parsed
+    pub fn parse_errors(&self) -> Result<ParseErrorReport, FileError> {
+        Ok(self.lock_rejects()?.report().clone())
+    }
+
+    fn lock_rejects(&self) -> Result<std::sync::MutexGuard<'_, Rejects>, FileError> {
+        self.rejects
+            .lock()
+            .map_err(|_| FileError::LockError("Failed to lock CSV rejects".into()))
+    }
+
     fn is_primary_key(&self, col_name: &str) -> bool {
         self.settings
             .pk_column
@@ -124,3 +205,44 @@ impl CsvAdapter {
             .is_some_and(|pk| pk.eq_ignore_ascii_case(col_name))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::csv::settings::{CsvFormat, RaggedRows};
+    use std::io::Write;
+
+    #[test]
+    fn test_columns_and_ragged_rows() {
+        let path = std::env::temp_dir().join(format!("stratum_ragged_{}.csv", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"a,b,c\n1,x,Ada\n2,y\n3,z,Bob,extra\n4,w,Cy\n")
+            .unwrap();
+        let format = CsvFormat {
+            columns: vec!["id".into(), SKIP_COLUMN.into(), "name".into()],
+            ragged_rows: RaggedRows::Skip,
+            ..CsvFormat::default()
+        };
+        let settings = CsvSettings::new(',', true, None).with_format(format.clone());
+        let mut adapter = CsvAdapter::new(path.to_str().unwrap(), settings).unwrap();
+        assert_eq!(adapter.headers, ["id", "_skip", "name"]);
+        let ids: Vec<_> = adapter
+            .read(10)
+            .unwrap()
+            .iter()
+            .map(|r| r[0].to_string())
+            .collect();
+        assert_eq!(ids, ["1", "4"]);
+
+        let format = CsvFormat {
+            ragged_rows: RaggedRows::Error,
+            ..format
+        };
+        let settings = CsvSettings::new(',', true, None).with_format(format);
+        let mut adapter = CsvAdapter::new(path.to_str().unwrap(), settings).unwrap();
+        let err = adapter.read(10).unwrap_err().to_string();
+        assert!(err.contains("line 3 has 2 fields, but the file has 3 columns"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
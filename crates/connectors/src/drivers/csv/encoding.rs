@@ -0,0 +1,113 @@
+use encoding_rs::{Decoder, Encoding, UTF_8};
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+/// Raw bytes decoded per read
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A CSV file read as UTF-8, decoded from its encoding as it is read. A
+/// decoded file can only be rewound to its start, which is all the CSV
+/// reader needs to read the headers again; a UTF-8 file is read as it is.
+pub struct DecodedFile {
+    file: File,
+    encoding: &'static Encoding,
+    decoder: Option<Decoder>,
+    /// Decoded text not yet read, from `pending_start`
+    pending: Vec<u8>,
+    pending_start: usize,
+    finished: bool,
+}
+
+impl DecodedFile {
+    pub fn open(path: &str, encoding: &'static Encoding) -> io::Result<Self> {
+        Ok(DecodedFile {
+            file: File::open(path)?,
+            encoding,
+            decoder: (encoding != UTF_8).then(|| encoding.new_decoder()),
+            pending: Vec::new(),
+            pending_start: 0,
+            finished: false,
+        })
+    }
+}
+
+impl Read for DecodedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(decoder) = &mut self.decoder else {
+            return self.file.read(buf);
+        };
+        loop {
+            if self.pending_start < self.pending.len() {
+                let n = buf.len().min(self.pending.len() - self.pending_start);
+                buf[..n].copy_from_slice(&self.pending[self.pending_start..][..n]);
+                self.pending_start += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            let mut raw = vec![0u8; CHUNK_SIZE];
+            let read = self.file.read(&mut raw)?;
+            let last = read == 0;
+            let capacity = decoder
+                .max_utf8_buffer_length(read)
+                .ok_or_else(|| io::Error::other("decoded CSV chunk is too large"))?;
+            self.pending.resize(capacity, 0);
+            // The buffer fits the whole chunk, so all of it is decoded
+            let (_, _, written, _) = decoder.decode_to_utf8(&raw[..read], &mut self.pending, last);
+            self.pending.truncate(written);
+            self.pending_start = 0;
+            self.finished = last;
+        }
+    }
+}
+
+impl Seek for DecodedFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        if self.decoder.is_none() {
+            return self.file.seek(pos);
+        }
+        if pos != SeekFrom::Start(0) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "a decoded CSV file can only be rewound to its start",
+            ));
+        }
+        self.file.seek(SeekFrom::Start(0))?;
+        self.decoder = Some(self.encoding.new_decoder());
+        self.pending.clear();
+        self.pending_start = 0;
+        self.finished = false;
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_decodes_windows_1250() {
+        let path = std::env::temp_dir().join(format!("stratum_cp1250_{}.csv", std::process::id()));
+        // "město;cena\nPlzeň;1,5\n" in windows-1250
+        let (bytes, _, _) = encoding_rs::WINDOWS_1250.encode("město;cena\nPlzeň;1,5\n");
+        File::create(&path).unwrap().write_all(&bytes).unwrap();
+
+        let encoding = Encoding::for_label(b"windows-1250").unwrap();
+        let mut file = DecodedFile::open(path.to_str().unwrap(), encoding).unwrap();
+        let mut text = String::new();
+        file.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "město;cena\nPlzeň;1,5\n");
+
+        // Rewinding decodes from the start again
+        file.seek(SeekFrom::Start(0)).unwrap();
+        let mut again = String::new();
+        file.read_to_string(&mut again).unwrap();
+        assert_eq!(again, text);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
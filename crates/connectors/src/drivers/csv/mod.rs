@@ -1,7 +1,9 @@
 pub mod adapter;
+pub mod encoding;
 pub mod error;
 pub mod filter;
 pub mod metadata;
+pub mod rejects;
 pub mod settings;
 pub mod source;
 pub mod types;
@@ -0,0 +1,83 @@
+use crate::drivers::csv::{error::FileError, settings::ParseErrorPolicy};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+/// Rows of a report kept as read, from the first
+pub const PARSE_ERROR_SAMPLES: usize = 10;
+
+/// Appended to a file's path to name its quarantine file
+pub const QUARANTINE_SUFFIX: &str = ".rejected";
+
+/// Rows of a file left out because they could not be parsed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParseErrorReport {
+    pub file: String,
+    /// Line of each row left out
+    pub lines: Vec<u64>,
+    /// The first rows left out, each as `line N: reason: row`
+    pub samples: Vec<String>,
+}
+
+impl ParseErrorReport {
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// Where the rows of a file that cannot be parsed go under its
+/// `on_parse_error` policy
+pub struct Rejects {
+    policy: ParseErrorPolicy,
+    report: ParseErrorReport,
+    quarantine: Option<BufWriter<File>>,
+}
+
+impl Rejects {
+    pub fn new(file: &str, policy: ParseErrorPolicy) -> Self {
+        Rejects {
+            policy,
+            report: ParseErrorReport {
+                file: file.to_string(),
+                ..ParseErrorReport::default()
+            },
+            quarantine: None,
+        }
+    }
+
+    /// Leaves out the row at `line`, `raw` as read, or fails under the
+    /// `fail` policy.
+    pub fn reject(&mut self, line: u64, raw: &str, reason: &str) -> Result<(), FileError> {
+        if self.policy == ParseErrorPolicy::Fail {
+            return Err(FileError::InvalidFormat(format!(
+                "{} line {line}: {reason}",
+                self.report.file
+            )));
+        }
+
+        self.report.lines.push(line);
+        if self.report.samples.len() < PARSE_ERROR_SAMPLES {
+            self.report
+                .samples
+                .push(format!("line {line}: {reason}: {raw}"));
+        }
+        if self.policy == ParseErrorPolicy::Quarantine {
+            let quarantine = match &mut self.quarantine {
+                Some(quarantine) => quarantine,
+                None => {
+                    let path = format!("{}{QUARANTINE_SUFFIX}", self.report.file);
+                    self.quarantine.insert(BufWriter::new(File::create(path)?))
+                }
+            };
+            writeln!(quarantine, "{raw}")?;
+            quarantine.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn report(&self) -> &ParseErrorReport {
+        &self.report
+    }
+}
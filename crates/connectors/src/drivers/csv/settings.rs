@@ -1,9 +1,27 @@
+use chrono::{NaiveDate, NaiveDateTime, format::StrftimeItems};
+use csv::StringRecord;
+use encoding_rs::{Encoding, UTF_8};
+use model::core::value::Value;
+use std::{borrow::Cow, collections::HashMap};
+
+const ATTR_DECIMAL_SEPARATOR: &str = "decimal_separator";
+const ATTR_THOUSANDS_SEPARATOR: &str = "thousands_separator";
+const ATTR_DATE_FORMAT: &str = "date_format";
+const ATTR_ENCODING: &str = "encoding";
+const ATTR_COLUMNS: &str = "columns";
+const ATTR_RAGGED_ROWS: &str = "ragged_rows";
+const ATTR_ON_PARSE_ERROR: &str = "on_parse_error";
+
+/// Name in `columns` of a file column that is not read
+pub const SKIP_COLUMN: &str = "_skip";
+
 #[derive(Debug, Clone)]
 pub struct CsvSettings {
     pub delimiter: char,
     pub has_headers: bool,
     pub pk_column: Option<String>,
     pub sample_size: usize,
+    pub format: CsvFormat,
 }
 
 impl CsvSettings {
@@ -13,6 +31,358 @@ impl CsvSettings {
             has_headers,
             pk_column,
             sample_size: 50, // Default sample size
+            format: CsvFormat::default(),
+        }
+    }
+
+    pub fn with_format(mut self, format: CsvFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// What is done with a row that has more or fewer fields than the file has
+/// columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// Missing fields are read as empty and extra fields are dropped
+    #[default]
+    Pad,
+    /// The row is left out
+    Skip,
+    /// Reading the file fails
+    Error,
+}
+
+/// What is done with a row that cannot be parsed: one the CSV reader
+/// rejects, or with a cell that is not a value of its column's type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseErrorPolicy {
+    /// Reading the file fails at the row
+    #[default]
+    Fail,
+    /// The row is left out and reported
+    Skip,
+    /// The row is left out, reported and appended to the file's quarantine
+    /// file
+    Quarantine,
+}
+
+/// How a file connection's `csv {}` block says numbers, dates and text are
+/// written: cells are rewritten to the forms the type inference reads
+/// (`1234.5`, `2024-03-31`) before they are parsed.
+#[derive(Debug, Clone)]
+pub struct CsvFormat {
+    pub decimal_separator: char,
+    pub thousands_separator: Option<char>,
+    /// `strftime` format of dates and timestamps, e.g. `%d.%m.%Y`
+    pub date_format: Option<String>,
+    pub encoding: &'static Encoding,
+    /// Names of the file's columns by position, used instead of its header
+    /// row; a column named `_skip` is not read
+    pub columns: Vec<String>,
+    pub ragged_rows: RaggedRows,
+    pub on_parse_error: ParseErrorPolicy,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        CsvFormat {
+            decimal_separator: '.',
+            thousands_separator: None,
+            date_format: None,
+            encoding: UTF_8,
+            columns: Vec::new(),
+            ragged_rows: RaggedRows::default(),
+            on_parse_error: ParseErrorPolicy::default(),
         }
     }
 }
+
+impl CsvFormat {
+    /// Reads a connection's `csv {}` block.
+    pub fn from_block(block: &HashMap<String, Value>) -> Result<Self, String> {
+        let mut format = CsvFormat::default();
+        for (key, value) in block {
+            if key == ATTR_COLUMNS {
+                format.columns = columns(value)?;
+                continue;
+            }
+            let text = match value {
+                Value::String(s) => s.as_str(),
+                _ => return Err(format!("csv {key} must be a string")),
+            };
+            match key.as_str() {
+                ATTR_DECIMAL_SEPARATOR => format.decimal_separator = separator(key, text)?,
+                ATTR_THOUSANDS_SEPARATOR => {
+                    format.thousands_separator = Some(separator(key, text)?)
+                }
+                ATTR_DATE_FORMAT => {
+                    if StrftimeItems::new(text).parse().is_err() {
+                        return Err(format!("csv date_format '{text}' is not a valid format"));
+                    }
+                    format.date_format = Some(text.to_string());
+                }
+                ATTR_ENCODING => {
+                    format.encoding = Encoding::for_label(text.as_bytes())
+                        .ok_or_else(|| format!("csv encoding '{text}' is not a known encoding"))?;
+                }
+                ATTR_RAGGED_ROWS => {
+                    format.ragged_rows = match text {
+                        "pad" => RaggedRows::Pad,
+                        "skip" => RaggedRows::Skip,
+                        "error" => RaggedRows::Error,
+                        other => {
+                            return Err(format!(
+                                "csv ragged_rows '{other}' must be 'pad', 'skip' or 'error'"
+                            ));
+                        }
+                    };
+                }
+                ATTR_ON_PARSE_ERROR => {
+                    format.on_parse_error = match text {
+                        "fail" => ParseErrorPolicy::Fail,
+                        "skip" => ParseErrorPolicy::Skip,
+                        "quarantine" => ParseErrorPolicy::Quarantine,
+                        other => {
+                            return Err(format!(
+                                "csv on_parse_error '{other}' must be 'fail', 'skip' or 'quarantine'"
+                            ));
+                        }
+                    };
+                }
+                other => {
+                    return Err(format!(
+                        "unknown csv attribute '{other}'. Must be 'decimal_separator', 'thousands_separator', 'date_format', 'encoding', 'columns', 'ragged_rows' or 'on_parse_error'"
+                    ));
+                }
+            }
+        }
+        if format.thousands_separator == Some(format.decimal_separator) {
+            return Err(format!(
+                "csv decimal_separator and thousands_separator are both '{}'",
+                format.decimal_separator
+            ));
+        }
+        Ok(format)
+    }
+
+    /// The cell as the type inference reads it: a date in `date_format` as
+    /// `YYYY-MM-DD` (with ` HH:MM:SS` for a timestamp), a number with its
+    /// separators as `1234.5`. Other cells are returned as they are.
+    pub fn normalize<'a>(&self, cell: &'a str) -> Cow<'a, str> {
+        if let Some(format) = &self.date_format {
+            if let Ok(ts) = NaiveDateTime::parse_from_str(cell, format) {
+                return Cow::Owned(ts.format("%Y-%m-%d %H:%M:%S").to_string());
+            }
+            if let Ok(date) = NaiveDate::parse_from_str(cell, format) {
+                return Cow::Owned(date.format("%Y-%m-%d").to_string());
+            }
+        }
+        if self.decimal_separator == '.' && self.thousands_separator.is_none() {
+            return Cow::Borrowed(cell);
+        }
+        match self.number(cell) {
+            Some(number) => Cow::Owned(number),
+            None => Cow::Borrowed(cell),
+        }
+    }
+
+    /// `record` as `ragged_rows` reads it when the file has `width` columns:
+    /// `None` when the row is skipped.
+    pub fn fit_record(
+        &self,
+        record: StringRecord,
+        width: usize,
+    ) -> Result<Option<StringRecord>, String> {
+        if record.len() == width {
+            return Ok(Some(record));
+        }
+        match self.ragged_rows {
+            RaggedRows::Pad => Ok(Some(record)),
+            RaggedRows::Skip => Ok(None),
+            RaggedRows::Error => Err(format!(
+                "line {} has {} fields, but the file has {width} columns",
+                record.position().map_or(0, |p| p.line()),
+                record.len()
+            )),
+        }
+    }
+
+    /// `record` with every cell normalized
+    pub fn normalize_record(&self, record: StringRecord) -> StringRecord {
+        let rewrites = self.date_format.is_some()
+            || self.decimal_separator != '.'
+            || self.thousands_separator.is_some();
+        if !rewrites {
+            return record;
+        }
+        record.iter().map(|cell| self.normalize(cell)).collect()
+    }
+
+    /// `cell` written as a number with the configured separators, where
+    /// thousands separators split the integer part in groups of three.
+    fn number(&self, cell: &str) -> Option<String> {
+        let (sign, digits) = match cell.strip_prefix(['-', '+']) {
+            Some(rest) => (&cell[..1], rest),
+            None => ("", cell),
+        };
+        let (integer, fraction) = match digits.split_once(self.decimal_separator) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let groups: Vec<&str> = match self.thousands_separator {
+            Some(sep) => integer.split(sep).collect(),
+            None => vec![integer],
+        };
+        let (first, rest) = groups.split_first()?;
+        let grouped = (1..=3).contains(&first.len()) || rest.is_empty();
+        if first.is_empty()
+            || !grouped
+            || !first.chars().all(|c| c.is_ascii_digit())
+            || rest
+                .iter()
+                .any(|g| g.len() != 3 || !g.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+        if let Some(fraction) = fraction
+            && (fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        let mut number = format!("{sign}{}", groups.concat());
+        if let Some(fraction) = fraction {
+            number.push('.');
+            number.push_str(fraction);
+        }
+        Some(number)
+    }
+}
+
+/// Names of a `columns` list: each a column name or `_skip`, with no name
+/// given twice and at least one column read.
+fn columns(value: &Value) -> Result<Vec<String>, String> {
+    let err = || "csv columns must be a list of column names or \"_skip\"".to_string();
+    let Value::Array(items) = value else {
+        return Err(err());
+    };
+    let mut columns = Vec::with_capacity(items.len());
+    for item in items {
+        match item {
+            Value::String(name) if !name.trim().is_empty() => {
+                let name = name.trim().to_string();
+                if name != SKIP_COLUMN && columns.contains(&name) {
+                    return Err(format!("csv columns name '{name}' more than once"));
+                }
+                columns.push(name);
+            }
+            _ => return Err(err()),
+        }
+    }
+    if columns.iter().all(|c| c == SKIP_COLUMN) {
+        return Err("csv columns must name at least one column to read".to_string());
+    }
+    Ok(columns)
+}
+
+fn separator(key: &str, text: &str) -> Result<char, String> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_ascii_digit() => Ok(c),
+        _ => Err(format!(
+            "csv {key} must be a single character other than a digit"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn european() -> CsvFormat {
+        let block = HashMap::from([
+            ("decimal_separator".to_string(), Value::String(",".into())),
+            ("thousands_separator".to_string(), Value::String(".".into())),
+            ("date_format".to_string(), Value::String("%d.%m.%Y".into())),
+            ("encoding".to_string(), Value::String("windows-1250".into())),
+        ]);
+        CsvFormat::from_block(&block).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_european_cells() {
+        let format = european();
+        assert_eq!(format.encoding.name(), "windows-1250");
+
+        let cases = [
+            ("1.234.567,89", "1234567.89"),
+            ("-12,5", "-12.5"),
+            ("1234", "1234"),
+            ("31.03.2024", "2024-03-31"),
+            // Not grouped or written as numbers are here, so left alone
+            ("12.34", "12.34"),
+            ("1.234,", "1.234,"),
+            ("Praha, Brno", "Praha, Brno"),
+        ];
+        for (cell, expected) in cases {
+            assert_eq!(format.normalize(cell), expected, "cell '{cell}'");
+        }
+        assert_eq!(CsvFormat::default().normalize("1,5"), "1,5");
+    }
+
+    #[test]
+    fn test_csv_block_errors() {
+        let error = |key: &str, value: &str| {
+            let block = HashMap::from([(key.to_string(), Value::String(value.into()))]);
+            CsvFormat::from_block(&block).unwrap_err()
+        };
+        assert!(error("decimal_separator", ",,").contains("single character"));
+        assert!(error("thousands_separator", ".").contains("both '.'"));
+        assert!(error("encoding", "klingon").contains("not a known encoding"));
+        assert!(error("date_format", "%Q").contains("not a valid format"));
+        assert!(error("quote", "\"").contains("unknown csv attribute 'quote'"));
+        assert!(error("ragged_rows", "fill").contains("must be 'pad', 'skip' or 'error'"));
+        assert!(
+            error("on_parse_error", "ignore").contains("must be 'fail', 'skip' or 'quarantine'")
+        );
+        assert!(error("columns", "id").contains("csv columns must be a list"));
+
+        let columns = |names: &[&str]| {
+            let list = names.iter().map(|n| Value::String(n.to_string())).collect();
+            let block = HashMap::from([("columns".to_string(), Value::Array(list))]);
+            CsvFormat::from_block(&block)
+        };
+        assert_eq!(
+            columns(&["id", "_skip", "name", "_skip"]).unwrap().columns,
+            ["id", "_skip", "name", "_skip"]
+        );
+        assert!(
+            columns(&["id", "id"])
+                .unwrap_err()
+                .contains("'id' more than once")
+        );
+        assert!(
+            columns(&["_skip"])
+                .unwrap_err()
+                .contains("at least one column")
+        );
+    }
+
+    #[test]
+    fn test_fit_ragged_record() {
+        let record = StringRecord::from(vec!["1", "Ada"]);
+        let fit = |ragged_rows| {
+            let format = CsvFormat {
+                ragged_rows,
+                ..CsvFormat::default()
+            };
+            format.fit_record(record.clone(), 3)
+        };
+        assert_eq!(fit(RaggedRows::Pad).unwrap(), Some(record.clone()));
+        assert_eq!(fit(RaggedRows::Skip).unwrap(), None);
+        assert!(fit(RaggedRows::Error).unwrap_err().contains("has 2 fields"));
+    }
+}
@@ -1,3 +1,4 @@
+use csv::ErrorKind;
 use model::{
     core::value::FieldValue,
     pagination::{cursor::Cursor, page::FetchResult},
@@ -11,6 +12,7 @@ use crate::drivers::csv::{
     error::FileError,
     filter::CsvFilter,
     metadata::{CsvMetadata, MetadataHelper, normalize_col_name},
+    settings::{ParseErrorPolicy, SKIP_COLUMN},
     types::CsvType,
 };
 
@@ -49,7 +51,7 @@ impl FileDataSource for CsvDataSource {
 
         // Pre-map headers -> ColumnMetadata
         let mut headers_meta = Vec::with_capacity(self.adapter.headers.len());
-        for hdr in &self.adapter.headers {
+        for hdr in self.adapter.headers.iter().filter(|h| *h != SKIP_COLUMN) {
             let col_meta = meta
                 .columns
                 .iter()
@@ -87,6 +89,13 @@ impl FileDataSource for CsvDataSource {
                 Some(Ok(_)) => {
                     self.rows_read += 1;
                 }
+                // Left out when it was first read, unless the policy fails
+                Some(Err(e))
+                    if self.adapter.settings.format.on_parse_error != ParseErrorPolicy::Fail
+                        && !matches!(e.kind(), ErrorKind::Io(_)) =>
+                {
+                    self.rows_read += 1;
+                }
                 Some(Err(e)) => {
                     return Err(FileError::ReadError(format!(
                         "Error reading CSV record: {e}"
@@ -111,6 +120,10 @@ impl FileDataSource for CsvDataSource {
             match data_iter.next() {
                 Some(Ok(record)) => {
                     self.rows_read += 1;
+                    let Some(record) = self.adapter.fit_record(record)? else {
+                        continue;
+                    };
+                    let record = self.adapter.settings.format.normalize_record(record);
 
                     if let Some(ref filter) = self.filter
                         && !filter.eval(&record, &headers_meta)
@@ -125,6 +138,17 @@ impl FileDataSource for CsvDataSource {
                         let cell = record.get(col_meta.ordinal).unwrap_or("");
                         let value = col_meta.data_type.get_value(cell);
 
+                        if value.is_none() && !cell.is_empty() {
+                            self.adapter.reject_record(
+                                &record,
+                                &format!(
+                                    "'{cell}' is not a value of the type inferred for column '{hdr}'"
+                                ),
+                            )?;
+                            skip_row = true;
+                            break;
+                        }
+
                         // if value is None but column is not nullable, skip entire row
                         if value.is_none() && !col_meta.is_nullable {
                             warn!(column = %hdr, "skipping row: column is null but not nullable");
@@ -147,9 +171,8 @@ impl FileDataSource for CsvDataSource {
                     result.push(row);
                 }
                 Some(Err(e)) => {
-                    return Err(FileError::ReadError(format!(
-                        "Error reading CSV record: {e}"
-                    )));
+                    self.rows_read += 1;
+                    self.adapter.reject_error(e)?;
                 }
                 // End of file
                 None => {
@@ -188,3 +211,56 @@ impl MetadataHelper for CsvDataSource {
         self.primary_meta = Some(meta);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::csv::{
+        rejects::QUARANTINE_SUFFIX,
+        settings::{CsvFormat, CsvSettings},
+    };
+
+    #[tokio::test]
+    async fn test_quarantine_unparseable_rows() {
+        let path = std::env::temp_dir().join(format!("stratum_dirty_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        std::fs::write(&path, "id,qty\n1,5\n2,6\n3,abc\n4,7\n5,x\n").unwrap();
+
+        let format = CsvFormat {
+            on_parse_error: ParseErrorPolicy::Quarantine,
+            ..CsvFormat::default()
+        };
+        let mut settings = CsvSettings::new(',', true, None).with_format(format);
+        settings.sample_size = 1;
+        let adapter = CsvAdapter::new(&path, settings).unwrap();
+        let meta = adapter.fetch_metadata(&path).await.unwrap();
+        let mut source = CsvDataSource::new(adapter, None);
+        source.set_metadata(meta);
+
+        let fetched = source.fetch(100, Cursor::None).unwrap();
+        assert_eq!(fetched.row_count, 3);
+        let report = source.adapter.parse_errors().unwrap();
+        assert_eq!(report.lines, [4, 6]);
+        assert_eq!(
+            report.samples[0],
+            "line 4: 'abc' is not a value of the type inferred for column 'qty': 3,abc"
+        );
+        let quarantine = format!("{path}{QUARANTINE_SUFFIX}");
+        assert_eq!(
+            std::fs::read_to_string(&quarantine).unwrap(),
+            "3,abc\n5,x\n"
+        );
+
+        // The default policy fails at the first such row
+        let adapter = CsvAdapter::new(&path, CsvSettings::new(',', true, None)).unwrap();
+        let mut meta = adapter.fetch_metadata(&path).await.unwrap();
+        meta.columns[1].data_type = meta.columns[0].data_type.clone();
+        let mut source = CsvDataSource::new(adapter, None);
+        source.set_metadata(meta);
+        let err = source.fetch(100, Cursor::None).unwrap_err().to_string();
+        assert!(err.contains("line 4: 'abc' is not a value"), "{err}");
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&quarantine).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
 pub mod csv;
 pub mod mysql;
 pub mod postgres;
+pub mod tls;
@@ -0,0 +1,115 @@
+use crate::{
+    error::DriverError,
+    traits::{cursor::RowCursor, row_decoder::RowDecoder},
+};
+use async_trait::async_trait;
+use model::records::Record;
+use mysql_async::{Pool, Row as MySqlRow, prelude::Queryable};
+use mysql_common::params::Params;
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, warn};
+
+/// Decoded rows buffered between the streaming task and the reader. The server
+/// only sends as fast as the reader drains, so source memory stays bounded.
+const STREAM_BUFFER_ROWS: usize = 1024;
+
+/// The reader pauses between pages while the destination catches up, during
+/// which the server is blocked writing to the socket. Raise the write timeout
+/// (default 60s) so a slow destination doesn't abort the stream.
+const STREAM_SESSION_SETUP: &str = "SET SESSION net_write_timeout = 3600";
+
+/// MySQL has no declarable cursors, so this issues the query once and streams
+/// its result set row by row (the `mysql --quick` model) instead of buffering
+/// it. A single statement reads from one InnoDB snapshot.
+pub struct MySqlStreamCursor {
+    rows: mpsc::Receiver<Result<Record, DriverError>>,
+    task: JoinHandle<()>,
+}
+
+impl MySqlStreamCursor {
+    /// Check out a pooled connection and start streaming the query's rows.
+    pub fn open(pool: &Pool, table: &str, sql: String, params: Params) -> Self {
+        let (tx, rows) = mpsc::channel(STREAM_BUFFER_ROWS);
+        let task = tokio::spawn(stream_rows(
+            pool.clone(),
+            table.to_string(),
+            sql,
+            params,
+            tx,
+        ));
+
+        Self { rows, task }
+    }
+}
+
+async fn stream_rows(
+    pool: Pool,
+    table: String,
+    sql: String,
+    params: Params,
+    tx: mpsc::Sender<Result<Record, DriverError>>,
+) {
+    let streamed = async {
+        let mut conn = pool.get_conn().await?;
+        conn.query_drop(STREAM_SESSION_SETUP).await?;
+
+        debug!(table = %table, sql = %sql, "streaming result set");
+
+        let id = conn.id();
+        let mut result = conn.exec_iter(sql, params).await?;
+        while let Some(row) = result.next().await? {
+            let row: MySqlRow = row;
+            if tx.send(Ok(row.decode(&table))).await.is_err() {
+                // Reader closed the cursor early. The pool reads the rest of
+                // the result set before it reuses the connection, so stop
+                // the query rather than stream the table to nobody.
+                drop(result);
+                kill_query(&pool, id).await;
+                break;
+            }
+        }
+        Ok::<_, DriverError>(())
+    }
+    .await;
+
+    if let Err(e) = streamed {
+        let _ = tx.send(Err(e)).await;
+    }
+}
+
+/// Interrupt the statement running on connection `id`, from another
+/// connection of the pool. The connection itself stays open.
+async fn kill_query(pool: &Pool, id: u32) {
+    let killed = async {
+        let mut conn = pool.get_conn().await?;
+        conn.query_drop(format!("KILL QUERY {id}")).await?;
+        Ok::<_, DriverError>(())
+    }
+    .await;
+
+    if let Err(e) = killed {
+        warn!(connection = id, error = %e, "failed to stop abandoned streaming query");
+    }
+}
+
+#[async_trait]
+impl RowCursor for MySqlStreamCursor {
+    async fn fetch_next(&mut self, count: usize) -> Result<Vec<Record>, DriverError> {
+        let mut rows = Vec::with_capacity(count);
+        while rows.len() < count {
+            match self.rows.recv().await {
+                Some(row) => rows.push(row?),
+                None => break,
+            }
+        }
+        Ok(rows)
+    }
+
+    async fn close(self: Box<Self>) -> Result<(), DriverError> {
+        // Dropping the receiver stops the task at its next send.
+        let Self { rows, task } = *self;
+        drop(rows);
+        task.await
+            .map_err(|e| DriverError::QueryError(format!("result stream task failed: {e}")))
+    }
+}
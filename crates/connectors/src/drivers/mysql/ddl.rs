@@ -1,10 +1,12 @@
 use crate::{
-    drivers::mysql::driver::MySqlDriver,
+    audit,
+    drivers::mysql::{driver::MySqlDriver, queries},
     error::DriverError,
     sql::query::{column::ColumnDef, generator::QueryGenerator},
     traits::{ddl::DdlWriter, executor::QueryExecutor},
 };
 use async_trait::async_trait;
+use mysql_async::{Row as MySqlRow, prelude::Queryable};
 use query_builder::dialect;
 use tracing::debug;
 
@@ -19,4 +21,27 @@ impl DdlWriter for MySqlDriver {
 
         Ok(())
     }
+
+    async fn sync_sequence(&self, table: &str, column: &str) -> Result<Option<i64>, DriverError> {
+        let table = queries::escape_identifier(table);
+        let sql = queries::NEXT_AUTO_INCREMENT
+            .replace("{table}", &table)
+            .replace("{column}", &queries::escape_identifier(column));
+
+        debug!(column = %column, table = %table, "syncing auto_increment");
+
+        let mut conn = self.pool().get_conn().await?;
+        let row: Option<MySqlRow> = audit::statement(&sql, || None, conn.query_first(&sql)).await?;
+        let next: i64 = match row {
+            Some(row) => row.get("next_value").unwrap_or(1),
+            None => 1,
+        };
+
+        let sql = queries::SET_AUTO_INCREMENT
+            .replace("{table}", &table)
+            .replace("{value}", &next.to_string());
+        audit::statement(&sql, || None, conn.query_drop(&sql)).await?;
+
+        Ok(Some(next))
+    }
 }
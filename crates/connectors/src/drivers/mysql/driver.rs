@@ -1,10 +1,11 @@
 use crate::{
     drivers::mysql::tls,
     error::DriverError,
-    sql::metadata::capabilities::Capabilities,
+    sql::metadata::capabilities::{Capabilities, SQL_PAGINATION},
     traits::driver::{Driver, DriverInfo},
 };
-use mysql_async::{Pool, prelude::Queryable};
+use model::core::value::Value;
+use mysql_async::{OptsBuilder, Pool, PoolConstraints, PoolOpts, prelude::Queryable};
 use tracing::info;
 
 const MYSQL_MAX_PREPARED_STMT_PARAMS: u16 = 65535;
@@ -25,7 +26,18 @@ impl MySqlDriver {
 
     /// Establishes a connection pool and detects server capabilities.
     pub async fn connect(url: &str) -> Result<Self, DriverError> {
-        let pool = tls::pool_from_url(url)?;
+        Self::connect_with_session(url, &[]).await
+    }
+
+    /// Establishes a connection pool whose connections each run `SET SESSION`
+    /// for `settings`, such as `unique_checks = 0` for bulk loads, when
+    /// opened and again each time the pool resets it, so every checkout
+    /// sees them.
+    pub async fn connect_with_session(
+        url: &str,
+        settings: &[(String, Value)],
+    ) -> Result<Self, DriverError> {
+        let pool = tls::pool_from_url(url, session_init(settings))?;
         let capabilities = Self::detect_capabilities(&pool).await?;
 
         info!(driver = "mysql", "database connection established");
@@ -33,6 +45,40 @@ impl MySqlDriver {
         Ok(Self { pool, capabilities })
     }
 
+    /// Establishes a read-only driver whose every query sees one consistent
+    /// snapshot: its pool holds a single connection, kept in the
+    /// `START TRANSACTION WITH CONSISTENT SNAPSHOT` transaction it opens
+    /// here and never reset when returned to the pool. Open it while a
+    /// [`MySqlReadLock`](super::snapshot::MySqlReadLock) is held to start
+    /// the snapshot at a known binary log position.
+    pub async fn connect_at_snapshot(
+        url: &str,
+        settings: &[(String, Value)],
+    ) -> Result<Self, DriverError> {
+        let opts = tls::opts_from_url(url, session_init(settings))?;
+        let single = PoolConstraints::new(1, 1).expect("a pool of one connection is valid");
+        let pool_opts = PoolOpts::default()
+            .with_constraints(single)
+            .with_reset_connection(false);
+        let pool = Pool::new(OptsBuilder::from_opts(opts).pool_opts(pool_opts));
+
+        let mut conn = pool.get_conn().await?;
+        conn.query_drop("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .await?;
+        conn.query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+            .await
+            .map_err(|e| DriverError::TransactionError(e.to_string()))?;
+        drop(conn);
+
+        let capabilities = Self::detect_capabilities(&pool).await?;
+        info!(
+            driver = "mysql",
+            "database connection established at a consistent snapshot"
+        );
+
+        Ok(Self { pool, capabilities })
+    }
+
     pub fn pool(&self) -> &Pool {
         &self.pool
     }
@@ -57,7 +103,7 @@ impl MySqlDriver {
         Ok(Self::resolve_capabilities(version))
     }
 
-    fn resolve_capabilities(version: String) -> Capabilities {
+    pub(crate) fn resolve_capabilities(version: String) -> Capabilities {
         let version_lower = version.to_lowercase();
         let is_mariadb = version_lower.contains("mariadb");
 
@@ -72,11 +118,15 @@ impl MySqlDriver {
             copy_protocol: true, // Corresponds to LOAD DATA LOCAL INFILE
             upsert: true,        // ON DUPLICATE KEY UPDATE
             returning_clause: supports_returning,
+            ddl: true,
+            cdc: false,
             json_type: true,   // Supported in MySQL 5.7+ and MariaDB 10.2+ (as alias)
             jsonb_type: false, // MySQL has JSON, but not a distinct JSONB binary type like PG
             array_type: false,
             uuid_type: false, // Usually stored as BINARY(16) or CHAR(36)
             geometry_type: true,
+            materialized_views: false,
+            pagination: SQL_PAGINATION,
             max_parameters: Some(MYSQL_MAX_PREPARED_STMT_PARAMS.into()),
             max_query_size: None, // Depends on server's max_allowed_packet, usually dynamic
         }
@@ -96,3 +146,31 @@ impl Driver for MySqlDriver {
         &self.capabilities
     }
 }
+
+/// `SET SESSION` statements run as each connection opens or is reset
+fn session_init(settings: &[(String, Value)]) -> Vec<String> {
+    settings
+        .iter()
+        .map(|(name, value)| format!("SET SESSION {name} = {}", session_value(value)))
+        .collect()
+}
+
+/// `value` as the right-hand side of `SET SESSION`. Numbers stay unquoted,
+/// since MySQL rejects a string for a numeric variable.
+fn session_value(value: &Value) -> String {
+    match value {
+        Value::Int(_) | Value::UInt(_) | Value::Float(_) | Value::Decimal(_) => {
+            value.as_string().unwrap_or_default()
+        }
+        Value::Boolean(true) => "ON".to_string(),
+        Value::Boolean(false) => "OFF".to_string(),
+        other => format!(
+            "'{}'",
+            other
+                .as_string()
+                .unwrap_or_default()
+                .replace('\\', "\\\\")
+                .replace('\'', "''")
+        ),
+    }
+}
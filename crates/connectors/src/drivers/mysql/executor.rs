@@ -1,4 +1,5 @@
 use crate::{
+    audit,
     drivers::mysql::{driver::MySqlDriver, params::MySqlParamStore},
     error::DriverError,
     traits::{executor::QueryExecutor, row_decoder::RowDecoder},
@@ -11,14 +12,15 @@ use mysql_async::{Row as MySqlRow, prelude::Queryable};
 impl QueryExecutor for MySqlDriver {
     async fn execute(&self, sql: &str) -> Result<(), DriverError> {
         let mut conn = self.pool().get_conn().await?;
-        conn.query_drop(sql).await?;
+        audit::statement(sql, || None, conn.query_drop(sql)).await?;
         Ok(())
     }
 
     async fn execute_params(&self, sql: &str, params: &[Value]) -> Result<(), DriverError> {
+        let hash = || audit::hash_params(params);
         let params = MySqlParamStore::from_values(params).params();
         let mut conn = self.pool().get_conn().await?;
-        conn.exec_drop(sql, params).await?;
+        audit::statement(sql, hash, conn.exec_drop(sql, params)).await?;
         Ok(())
     }
 
@@ -49,8 +49,13 @@ impl SchemaIntrospector for MySqlDriver {
             })
             .collect::<Result<HashMap<_, _>, DriverError>>()?;
         let fks = self.fk_metadata(table).await?;
+        let row: Option<MySqlRow> = conn
+            .exec_first(queries::TABLE_COLLATION_SQL, (table,))
+            .await?;
 
-        MetadataProvider::construct_table_metadata(table, columns, fks)
+        let mut metadata = MetadataProvider::construct_table_metadata(table, columns, fks)?;
+        metadata.collation = row.and_then(|row| row.get_opt("table_collation")?.ok());
+        Ok(metadata)
     }
 
     async fn fk_metadata(&self, table: &str) -> Result<Vec<ForeignKeyMetadata>, DriverError> {
@@ -149,6 +154,17 @@ impl SchemaIntrospector for MySqlDriver {
         Ok(size_bytes)
     }
 
+    async fn avg_row_bytes(&self, table: &str) -> Result<Option<u64>, DriverError> {
+        let mut conn = self.pool().get_conn().await?;
+        let row: Option<MySqlRow> = conn
+            .exec_first(queries::AVG_ROW_BYTES_SQL, (table,))
+            .await?;
+        // InnoDB reports 0 until the table has rows and statistics
+        let avg_row_bytes =
+            row.and_then(|row| row.get::<Option<u64>, _>("avg_row_bytes").flatten());
+        Ok(avg_row_bytes.filter(|n| *n > 0))
+    }
+
     async fn unique_constraint_metadata(
         &self,
         table: &str,
@@ -1,3 +1,4 @@
+pub mod cursor;
 pub mod ddl;
 pub mod driver;
 pub mod encoder;
@@ -7,6 +8,7 @@ pub mod params;
 pub mod queries;
 pub mod reader;
 pub mod row;
+pub mod snapshot;
 pub mod tls;
 pub mod transaction;
 pub mod types;
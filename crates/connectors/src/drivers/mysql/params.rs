@@ -15,8 +15,8 @@ impl MySqlParam {
             Value::UInt(u) => MySqlValue::UInt(*u),
             Value::Float(f) => MySqlValue::Double(*f),
             Value::Decimal(d) => {
-                // MySQL expects decimal as bytes
-                MySqlValue::Bytes(d.to_string().into_bytes())
+                // MySQL expects decimal as bytes; plain notation keeps every digit
+                MySqlValue::Bytes(d.to_plain_string().into_bytes())
             }
 
             // String
@@ -62,20 +62,7 @@ impl MySqlParam {
             Value::Set(values) => MySqlValue::Bytes(values.join(",").into_bytes()),
 
             // Array - serialize as JSON for MySQL
-            Value::Array(arr) => {
-                let json_arr: Vec<String> = arr
-                    .iter()
-                    .map(|v| match v {
-                        Value::String(s) => format!("\"{}\"", s),
-                        Value::Int(i) => i.to_string(),
-                        Value::UInt(u) => u.to_string(),
-                        Value::Float(f) => f.to_string(),
-                        Value::Boolean(b) => b.to_string(),
-                        _ => "null".to_string(),
-                    })
-                    .collect();
-                MySqlValue::Bytes(format!("[{}]", json_arr.join(",")).into_bytes())
-            }
+            Value::Array(_) => MySqlValue::Bytes(value.to_json().to_string().into_bytes()),
 
             // Bits
             Value::Bits(bits) => {
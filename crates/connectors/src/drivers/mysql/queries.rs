@@ -9,6 +9,39 @@ pub const COUNT_NO_FILTER: &str = "SELECT COUNT(*) AS count FROM {table}";
 /// Fast row count estimate using information_schema (MySQL specific)
 pub const COUNT_ROWS_FAST: &str = include_str!("sql/count_rows_fast.sql");
 
+/// One past a column's largest value
+/// Placeholders: {table} - escaped table identifier, {column} - escaped column identifier
+pub const NEXT_AUTO_INCREMENT: &str =
+    "SELECT COALESCE(MAX({column}), 0) + 1 AS next_value FROM {table}";
+
+/// Smallest and largest value of an integer key
+/// Placeholders: {table} - escaped table identifier, {column} - escaped column identifier
+pub const KEY_BOUNDS: &str = "SELECT CAST(MIN({column}) AS SIGNED) AS min_key, CAST(MAX({column}) AS SIGNED) AS max_key FROM {table}";
+
+/// Optimizer estimate (index dive) of the rows with a key in `[?, ?)`
+/// Placeholders: {table} - escaped table identifier, {column} - escaped column identifier
+pub const KEY_RANGE_ROWS: &str =
+    "EXPLAIN SELECT 1 FROM {table} WHERE {column} >= ? AND {column} < ?";
+
+/// Placeholders: {table} - escaped table identifier, {value} - next counter value
+pub const SET_AUTO_INCREMENT: &str = "ALTER TABLE {table} AUTO_INCREMENT = {value}";
+
+/// Primary keys of the rows whose binary column holds more than ? bytes
+/// Placeholders: {keys} - escaped key columns, {table} - escaped table identifier,
+/// {column} - escaped column identifier
+pub const OVERSIZED_KEYS: &str = "SELECT {keys} FROM {table} WHERE OCTET_LENGTH({column}) > ?";
+
+/// One chunk of a binary value; {from} is 1-based
+/// Placeholders: {column}, {from}, {len}, {table}, {key} - key predicate
+pub const READ_CHUNK: &str =
+    "SELECT SUBSTRING({column}, {from}, {len}) AS chunk FROM {table} WHERE {key}";
+
+/// Placeholders: {table}, {column}, {key} - key predicate after the chunk
+pub const WRITE_CHUNK: &str = "UPDATE {table} SET {column} = ? WHERE {key}";
+
+/// Placeholders: {table}, {column}, {key} - key predicate after the chunk
+pub const APPEND_CHUNK: &str = "UPDATE {table} SET {column} = CONCAT({column}, ?) WHERE {key}";
+
 pub const TABLE_EXISTS_SQL: &str = include_str!("sql/table_exists.sql");
 pub const LIST_TABLES_SQL: &str = "SHOW TABLES";
 pub const TABLE_METADATA_SQL: &str = include_str!("sql/table_metadata.sql");
@@ -16,6 +49,8 @@ pub const INDEX_METADATA_SQL: &str = include_str!("sql/index_metadata.sql");
 pub const FK_METADATA_SQL: &str = include_str!("sql/fk_metadata.sql");
 pub const REFERRING_TABLES_SQL: &str = include_str!("sql/table_referencing.sql");
 pub const TABLE_SIZE_SQL: &str = include_str!("sql/table_size.sql");
+pub const TABLE_COLLATION_SQL: &str = include_str!("sql/table_collation.sql");
+pub const AVG_ROW_BYTES_SQL: &str = include_str!("sql/avg_row_bytes.sql");
 pub const UNIQUE_CONSTRAINT_METADATA_SQL: &str = include_str!("sql/unique_constraint_metadata.sql");
 pub const CHECK_CONSTRAINT_METADATA_SQL: &str = include_str!("sql/check_constraint_metadata.sql");
 
@@ -33,3 +68,12 @@ pub fn qualified_table_name(table: &str, schema: Option<&str>) -> String {
         None => escape_identifier(table),
     }
 }
+
+/// `` `a` = ? AND `b` = ? ... `` over the key columns.
+pub fn key_predicate<'a>(columns: impl IntoIterator<Item = &'a str>) -> String {
+    columns
+        .into_iter()
+        .map(|column| format!("{} = ?", escape_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
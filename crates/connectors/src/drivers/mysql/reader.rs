@@ -1,11 +1,13 @@
 use crate::{
-    drivers::mysql::{driver::MySqlDriver, params::MySqlParamStore, queries},
+    drivers::mysql::{
+        cursor::MySqlStreamCursor, driver::MySqlDriver, params::MySqlParamStore, queries,
+    },
     error::DriverError,
     sql::{filter::SqlFilter, query::generator::QueryGenerator, request::FetchRowsRequest},
-    traits::{reader::DataReader, row_decoder::RowDecoder},
+    traits::{cursor::RowCursor, reader::DataReader, row_decoder::RowDecoder},
 };
 use async_trait::async_trait;
-use model::records::Record;
+use model::{core::value::Value, records::Record};
 use mysql_async::{Row as MySqlRow, prelude::Queryable};
 use query_builder::dialect;
 use tracing::debug;
@@ -18,10 +20,16 @@ impl DataReader for MySqlDriver {
 
         debug!(sql = %sql, "generated SQL");
 
+        // Decode rows as they arrive instead of collecting the raw result set
+        // first, so a page is never held in memory twice.
         let mut conn = self.pool().get_conn().await?;
         let params = MySqlParamStore::from_values(&params).params();
-        let rows: Vec<MySqlRow> = conn.exec(sql, params).await?;
-        Ok(rows.iter().map(|r| r.decode(&request.table)).collect())
+        let records = conn
+            .exec_iter(sql, params)
+            .await?
+            .map_and_drop(|row: MySqlRow| row.decode(&request.table))
+            .await?;
+        Ok(records)
     }
 
     async fn count(
@@ -57,4 +65,135 @@ impl DataReader for MySqlDriver {
         };
         Ok(estimate)
     }
+
+    async fn key_bounds(
+        &self,
+        table: &str,
+        column: &str,
+    ) -> Result<Option<(i64, i64)>, DriverError> {
+        let query = queries::KEY_BOUNDS
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column));
+
+        let mut conn = self.pool().get_conn().await?;
+        let row: Option<MySqlRow> = conn.query_first(query).await?;
+        let bounds = row.and_then(|row| {
+            let min: Option<i64> = row.get("min_key").flatten();
+            let max: Option<i64> = row.get("max_key").flatten();
+            min.zip(max)
+        });
+        Ok(bounds)
+    }
+
+    async fn key_split_points(
+        &self,
+        table: &str,
+        column: &str,
+        partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        let Some((min, max)) = self.key_bounds(table, column).await? else {
+            return Ok(Vec::new());
+        };
+        let query = queries::KEY_RANGE_ROWS
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column));
+
+        let mut conn = self.pool().get_conn().await?;
+        let mut rows_below = async |key: i64| -> Result<u64, DriverError> {
+            let row: Option<MySqlRow> = conn.exec_first(query.as_str(), (min, key)).await?;
+            Ok(row
+                .and_then(|row| row.get::<Option<u64>, _>("rows").flatten())
+                .unwrap_or(0))
+        };
+
+        let total = rows_below(max.saturating_add(1)).await?;
+        if total == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Binary search each split point on the optimizer's estimate of the
+        // rows below it; each estimate is a cheap dive into the key's index.
+        let mut points: Vec<i64> = Vec::new();
+        for i in 1..partitions as u64 {
+            let target = total * i / partitions as u64;
+            let (mut lo, mut hi) = (points.last().map_or(min, |p| p + 1), max);
+            while lo < hi {
+                let mid = ((lo as i128 + hi as i128) / 2) as i64;
+                if rows_below(mid).await? < target {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            if lo > min && points.last().is_none_or(|last| lo > *last) {
+                points.push(lo);
+            }
+        }
+        Ok(points)
+    }
+
+    async fn oversized_keys(
+        &self,
+        table: &str,
+        key_columns: &[String],
+        column: &str,
+        threshold: usize,
+    ) -> Result<Vec<Record>, DriverError> {
+        let keys = key_columns
+            .iter()
+            .map(|c| queries::escape_identifier(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = queries::OVERSIZED_KEYS
+            .replace("{keys}", &keys)
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column));
+
+        let mut conn = self.pool().get_conn().await?;
+        let records = conn
+            .exec_iter(query, (threshold as u64,))
+            .await?
+            .map_and_drop(|row: MySqlRow| row.decode(table))
+            .await?;
+        Ok(records)
+    }
+
+    async fn read_chunk(
+        &self,
+        table: &str,
+        key: &[(String, Value)],
+        column: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, DriverError> {
+        let query = queries::READ_CHUNK
+            .replace("{column}", &queries::escape_identifier(column))
+            .replace("{from}", &(offset + 1).to_string())
+            .replace("{len}", &len.to_string())
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace(
+                "{key}",
+                &queries::key_predicate(key.iter().map(|(c, _)| c.as_str())),
+            );
+        let values: Vec<Value> = key.iter().map(|(_, v)| v.clone()).collect();
+        let params = MySqlParamStore::from_values(&values).params();
+
+        let mut conn = self.pool().get_conn().await?;
+        let row: Option<MySqlRow> = conn.exec_first(query, params).await?;
+        Ok(row
+            .and_then(|row| row.get::<Option<Vec<u8>>, _>("chunk").flatten())
+            .unwrap_or_default())
+    }
+
+    async fn open_cursor(
+        &self,
+        request: FetchRowsRequest,
+    ) -> Result<Box<dyn RowCursor>, DriverError> {
+        let generator = QueryGenerator::new(&dialect::MySql);
+        let (sql, params) = generator.select(&request);
+        let params = MySqlParamStore::from_values(&params).params();
+
+        let cursor = MySqlStreamCursor::open(self.pool(), &request.table, sql, params);
+        Ok(Box::new(cursor))
+    }
 }
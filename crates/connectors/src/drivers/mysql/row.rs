@@ -269,12 +269,15 @@ fn extract_value(
         ColumnType::MYSQL_TYPE_VARCHAR
         | ColumnType::MYSQL_TYPE_VAR_STRING
         | ColumnType::MYSQL_TYPE_STRING => {
+            let v: Vec<u8> = FromValue::from_value(mysql_value);
             if col_flags.contains(ColumnFlags::BINARY_FLAG) {
-                let v: Vec<u8> = FromValue::from_value(mysql_value);
-                Some(Value::Binary(v))
-            } else {
-                let v: String = FromValue::from_value(mysql_value);
-                Some(Value::String(v))
+                return Some(Value::Binary(v));
+            }
+            // Bytes that are not UTF-8 stay binary; they are decoded per the
+            // pipeline's invalid_utf8 setting before they are written
+            match String::from_utf8(v) {
+                Ok(s) => Some(Value::String(s)),
+                Err(e) => Some(Value::Binary(e.into_bytes())),
             }
         }
 
@@ -0,0 +1,66 @@
+use crate::{drivers::mysql::tls, error::DriverError};
+use mysql_async::{Conn, Row, prelude::Queryable};
+use std::fmt;
+use tracing::debug;
+
+/// Binary log file and offset of a point in a server's history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinlogPosition {
+    pub file: String,
+    pub position: u64,
+}
+
+impl fmt::Display for BinlogPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.position)
+    }
+}
+
+/// A global read lock, `FLUSH TABLES WITH READ LOCK`, held on a server.
+/// While it is held no transaction commits, so snapshots that
+/// [`MySqlDriver::connect_at_snapshot`](super::driver::MySqlDriver::connect_at_snapshot)
+/// starts on the server see the same data, as of [`Self::binlog_position`].
+/// Writes wait until [`Self::release`]; dropping the lock closes its
+/// connection, which releases it too.
+pub struct MySqlReadLock {
+    conn: Conn,
+}
+
+impl MySqlReadLock {
+    /// Open a connection and take the lock. Waits for running statements
+    /// to finish, and needs the `RELOAD` privilege.
+    pub async fn acquire(url: &str) -> Result<Self, DriverError> {
+        let mut conn = Conn::new(tls::opts_from_url(url, Vec::new())?).await?;
+        conn.query_drop("FLUSH TABLES WITH READ LOCK")
+            .await
+            .map_err(|e| {
+                DriverError::QueryError(format!("FLUSH TABLES WITH READ LOCK failed: {e}"))
+            })?;
+        debug!("took global read lock");
+        Ok(Self { conn })
+    }
+
+    /// Position the server's binary log is at, or `None` when binary
+    /// logging is off.
+    pub async fn binlog_position(&mut self) -> Result<Option<BinlogPosition>, DriverError> {
+        // MySQL 8.2 renamed SHOW MASTER STATUS, and 8.4 removed it
+        let row: Option<Row> = match self.conn.query_first("SHOW BINARY LOG STATUS").await {
+            Ok(row) => row,
+            Err(_) => self.conn.query_first("SHOW MASTER STATUS").await?,
+        };
+        Ok(row.and_then(|row| {
+            Some(BinlogPosition {
+                file: row.get_opt("File")?.ok()?,
+                position: row.get_opt("Position")?.ok()?,
+            })
+        }))
+    }
+
+    /// Release the lock, letting writes continue.
+    pub async fn release(mut self) -> Result<(), DriverError> {
+        self.conn.query_drop("UNLOCK TABLES").await?;
+        self.conn.disconnect().await?;
+        debug!("released global read lock");
+        Ok(())
+    }
+}
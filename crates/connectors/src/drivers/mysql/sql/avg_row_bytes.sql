@@ -0,0 +1,3 @@
+SELECT avg_row_length AS avg_row_bytes
+FROM information_schema.TABLES
+WHERE table_schema = DATABASE() AND table_name = ?
\ No newline at end of file
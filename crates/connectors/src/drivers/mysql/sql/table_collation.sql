@@ -0,0 +1,3 @@
+SELECT TABLE_COLLATION AS table_collation
+FROM information_schema.TABLES
+WHERE table_schema = DATABASE() AND table_name = ?
@@ -1,43 +1,70 @@
 use crate::error::DriverError;
-use mysql_async::{Opts, OptsBuilder, Pool};
+use mysql_async::{ClientIdentity, Opts, OptsBuilder, Pool};
 use std::path::PathBuf;
 
-/// Build a connection pool from a URL.
-pub(crate) fn pool_from_url(url: &str) -> Result<Pool, DriverError> {
-    let (cleaned, ca_path) = extract_ssl_ca(url)?;
+/// Build a connection pool from a URL. Each connection runs the `setup`
+/// statements when it is opened and again whenever the pool resets it.
+pub(crate) fn pool_from_url(url: &str, setup: Vec<String>) -> Result<Pool, DriverError> {
+    Ok(Pool::new(opts_from_url(url, setup)?))
+}
+
+/// Certificate files named by the URL's `ssl_ca` and `ssl_identity`
+/// parameters
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SslFiles {
+    ca: Option<String>,
+    /// PKCS#12 archive holding the client certificate and its key
+    identity: Option<String>,
+}
+
+/// Connection options from a URL, with its `ssl_ca` and `ssl_identity`
+/// parameters applied
+pub(crate) fn opts_from_url(url: &str, setup: Vec<String>) -> Result<Opts, DriverError> {
+    let (cleaned, files) = extract_ssl_files(url)?;
 
     let opts = Opts::from_url(&cleaned).map_err(|e| DriverError::ConnectionError(e.to_string()))?;
 
-    let opts = match ca_path {
-        Some(path) => {
-            let ssl = opts
-                .ssl_opts()
-                .cloned()
-                .unwrap_or_default()
-                .with_root_certs(vec![PathBuf::from(path).into()]);
-            Opts::from(OptsBuilder::from_opts(opts).ssl_opts(ssl))
+    let opts = if files == SslFiles::default() {
+        opts
+    } else {
+        let mut ssl = opts.ssl_opts().cloned().unwrap_or_default();
+        if let Some(path) = files.ca {
+            ssl = ssl.with_root_certs(vec![PathBuf::from(path).into()]);
         }
-        None => opts,
+        if let Some(path) = files.identity {
+            ssl = ssl.with_client_identity(Some(ClientIdentity::new(PathBuf::from(path).into())));
+        }
+        Opts::from(OptsBuilder::from_opts(opts).ssl_opts(ssl))
     };
-
-    Ok(Pool::new(opts))
+    // `setup` rather than `init`: the pool resets a connection it takes
+    // back, which clears session variables set only when it was opened
+    Ok(match setup.is_empty() {
+        true => opts,
+        false => Opts::from(OptsBuilder::from_opts(opts).setup(setup)),
+    })
 }
 
-/// Split an `ssl_ca` parameter out of the URL (mysql_async rejects unknown
-/// parameters). Returns the URL without `ssl_ca` and the extracted path, if
-/// any. All other parameters are preserved untouched.
-fn extract_ssl_ca(url: &str) -> Result<(String, Option<String>), DriverError> {
+/// Split the `ssl_ca` and `ssl_identity` parameters out of the URL
+/// (mysql_async rejects unknown parameters). Returns the URL without them
+/// and the extracted paths, if any. All other parameters are preserved
+/// untouched.
+fn extract_ssl_files(url: &str) -> Result<(String, SslFiles), DriverError> {
     let mut parsed = url::Url::parse(url).map_err(|e| DriverError::InvalidUrl(e.to_string()))?;
 
-    let mut ca_path: Option<String> = None;
+    let mut files = SslFiles::default();
     let mut has_require_ssl = false;
     let mut kept: Vec<(String, String)> = parsed
         .query_pairs()
-        .filter_map(|(k, v)| {
-            if k == "ssl_ca" {
-                ca_path = Some(v.into_owned());
+        .filter_map(|(k, v)| match k.as_ref() {
+            "ssl_ca" => {
+                files.ca = Some(v.into_owned());
+                None
+            }
+            "ssl_identity" => {
+                files.identity = Some(v.into_owned());
                 None
-            } else {
+            }
+            _ => {
                 if k == "require_ssl" {
                     has_require_ssl = true;
                 }
@@ -46,9 +73,9 @@ fn extract_ssl_ca(url: &str) -> Result<(String, Option<String>), DriverError> {
         })
         .collect();
 
-    // Only rewrite the query when `ssl_ca` was present, to avoid perturbing
-    // URLs that don't use it.
-    if ca_path.is_some() {
+    // Only rewrite the query when a file was present, to avoid perturbing
+    // URLs that don't use one.
+    if files != SslFiles::default() {
         if !has_require_ssl {
             kept.push(("require_ssl".to_string(), "true".to_string()));
         }
@@ -60,7 +87,7 @@ fn extract_ssl_ca(url: &str) -> Result<(String, Option<String>), DriverError> {
         }
     }
 
-    Ok((parsed.to_string(), ca_path))
+    Ok((parsed.to_string(), files))
 }
 
 #[cfg(test)]
@@ -73,9 +100,10 @@ mod tests {
 
     #[test]
     fn extracts_and_strips_ssl_ca() {
-        let (out, ca) =
-            extract_ssl_ca("mysql://u:p@host:3306/db?require_ssl=true&ssl_ca=/tmp/ca.pem").unwrap();
-        assert_eq!(ca.as_deref(), Some("/tmp/ca.pem"));
+        let (out, files) =
+            extract_ssl_files("mysql://u:p@host:3306/db?require_ssl=true&ssl_ca=/tmp/ca.pem")
+                .unwrap();
+        assert_eq!(files.ca.as_deref(), Some("/tmp/ca.pem"));
         let q = query_of(&out).unwrap();
         assert!(
             q.contains("require_ssl=true"),
@@ -87,15 +115,16 @@ mod tests {
     #[test]
     fn url_without_ssl_ca_is_unchanged() {
         let url = "mysql://u:p@host:3306/db?require_ssl=true";
-        let (out, ca) = extract_ssl_ca(url).unwrap();
-        assert_eq!(ca, None);
+        let (out, files) = extract_ssl_files(url).unwrap();
+        assert_eq!(files, SslFiles::default());
         assert_eq!(out, url);
     }
 
     #[test]
     fn ssl_ca_injects_require_ssl_when_absent() {
-        let (out, ca) = extract_ssl_ca("mysql://u:p@host:3306/db?ssl_ca=/tmp/ca.pem").unwrap();
-        assert_eq!(ca.as_deref(), Some("/tmp/ca.pem"));
+        let (out, files) =
+            extract_ssl_files("mysql://u:p@host:3306/db?ssl_ca=/tmp/ca.pem").unwrap();
+        assert_eq!(files.ca.as_deref(), Some("/tmp/ca.pem"));
         let q = query_of(&out).unwrap();
         assert!(q.contains("require_ssl=true"), "require_ssl injected: {q}");
         assert!(!q.contains("ssl_ca"), "ssl_ca stripped: {q}");
@@ -104,7 +133,8 @@ mod tests {
     #[test]
     fn ssl_ca_does_not_duplicate_existing_require_ssl() {
         let (out, _) =
-            extract_ssl_ca("mysql://u:p@host:3306/db?require_ssl=true&ssl_ca=/tmp/ca.pem").unwrap();
+            extract_ssl_files("mysql://u:p@host:3306/db?require_ssl=true&ssl_ca=/tmp/ca.pem")
+                .unwrap();
         let q = query_of(&out).unwrap();
         assert_eq!(
             q.matches("require_ssl").count(),
@@ -112,4 +142,16 @@ mod tests {
             "no duplicate require_ssl: {q}"
         );
     }
+
+    #[test]
+    fn ssl_identity_is_extracted_and_requires_ssl() {
+        let (out, files) =
+            extract_ssl_files("mysql://u@host/db?verify_ca=true&ssl_identity=/tls/client.p12")
+                .unwrap();
+        assert_eq!(files.identity.as_deref(), Some("/tls/client.p12"));
+        let q = query_of(&out).unwrap();
+        assert!(q.contains("require_ssl=true"), "require_ssl injected: {q}");
+        assert!(q.contains("verify_ca=true"), "other params preserved: {q}");
+        assert!(!q.contains("ssl_identity"), "ssl_identity stripped: {q}");
+    }
 }
@@ -4,6 +4,10 @@ use model::core::convert::{
 };
 use model::core::types::{FloatSize, GeomKind, IntSize, Type};
 
+/// Largest `DECIMAL(M,D)` MySQL accepts.
+const MYSQL_DECIMAL_MAX_PRECISION: u8 = 65;
+const MYSQL_DECIMAL_MAX_SCALE: u8 = 30;
+
 pub struct MySqlTypeConverter;
 
 impl IntoCanonical for MySqlTypeConverter {
@@ -240,11 +244,11 @@ impl IntoCanonical for MySqlTypeConverter {
             "enum" => TypeMapping {
                 canonical: Type::Enum {
                     name: col.name.clone(),
-                    values: vec![], // Values would need to be extracted from column_type
+                    values: col.enum_values().unwrap_or_default(),
                 },
-                fidelity: Fidelity::Equivalent,
+                fidelity: Fidelity::Lossless,
                 value_transform: None,
-                warnings: vec!["Enum values need to be extracted from column_type".to_string()],
+                warnings: vec![],
             },
 
             // Set
@@ -396,20 +400,59 @@ impl FromCanonical for MySqlTypeConverter {
             }
 
             // Decimal types
-            Type::Decimal { precision, scale } => {
-                let ddl = match (precision, scale) {
-                    (Some(p), Some(s)) => format!("DECIMAL({},{})", p, s),
-                    (Some(p), None) => format!("DECIMAL({})", p),
-                    _ => "DECIMAL".to_string(),
-                };
-                DdlMapping {
-                    ddl,
-                    fidelity: Fidelity::Lossless,
+            // A bare DECIMAL is DECIMAL(10,0) in MySQL, which would silently drop
+            // the fraction of an unconstrained source NUMERIC, so use the widest
+            // declaration instead and clamp anything beyond MySQL's limits.
+            Type::Decimal { precision, scale } => match (precision, scale) {
+                (Some(p), s)
+                    if *p <= MYSQL_DECIMAL_MAX_PRECISION
+                        && s.is_none_or(|s| s <= MYSQL_DECIMAL_MAX_SCALE) =>
+                {
+                    DdlMapping {
+                        ddl: match s {
+                            Some(s) => format!("DECIMAL({},{})", p, s),
+                            None => format!("DECIMAL({})", p),
+                        },
+                        fidelity: Fidelity::Lossless,
+                        transform: None,
+                        warnings: vec![],
+                        pre_ddl: None,
+                    }
+                }
+                (None, _) => DdlMapping {
+                    ddl: format!(
+                        "DECIMAL({},{})",
+                        MYSQL_DECIMAL_MAX_PRECISION, MYSQL_DECIMAL_MAX_SCALE
+                    ),
+                    fidelity: Fidelity::Equivalent,
                     transform: None,
-                    warnings: vec![],
+                    warnings: vec![format!(
+                        "Unconstrained DECIMAL mapped to DECIMAL({},{})",
+                        MYSQL_DECIMAL_MAX_PRECISION, MYSQL_DECIMAL_MAX_SCALE
+                    )],
                     pre_ddl: None,
+                },
+                (Some(p), s) => {
+                    let scale = s.unwrap_or(0).min(MYSQL_DECIMAL_MAX_SCALE);
+                    DdlMapping {
+                        ddl: format!(
+                            "DECIMAL({},{})",
+                            (*p).min(MYSQL_DECIMAL_MAX_PRECISION),
+                            scale
+                        ),
+                        fidelity: Fidelity::Lossy,
+                        transform: None,
+                        warnings: vec![format!(
+                            "DECIMAL({},{}) exceeds MySQL limits, clamped to DECIMAL({},{})",
+                            p,
+                            s.unwrap_or(0),
+                            (*p).min(MYSQL_DECIMAL_MAX_PRECISION),
+                            scale
+                        )],
+                        pre_ddl: None,
+                    }
                 }
-            }
+            },
 
             // String types
             Type::Char { length, .. } => {
@@ -584,8 +627,10 @@ impl FromCanonical for MySqlTypeConverter {
 
             // Enum
             Type::Enum { values, .. } => {
-                let quoted_values: Vec<String> =
-                    values.iter().map(|v| format!("'{}'", v)).collect();
+                let quoted_values: Vec<String> = values
+                    .iter()
+                    .map(|v| format!("'{}'", v.replace('\'', "''")))
+                    .collect();
                 let ddl = format!("ENUM({})", quoted_values.join(", "));
                 DdlMapping {
                     ddl,
@@ -1,11 +1,14 @@
 use crate::{
-    drivers::mysql::{driver::MySqlDriver, params::MySqlParamStore, types::MySqlTypeConverter},
+    audit,
+    drivers::mysql::{
+        driver::MySqlDriver, params::MySqlParamStore, queries, types::MySqlTypeConverter,
+    },
     error::DriverError,
     sql::{metadata::table::TableMetadata, query::generator::QueryGenerator},
     traits::writer::DataWriter,
 };
 use async_trait::async_trait;
-use model::records::Record;
+use model::{core::value::Value, records::Record};
 use mysql_async::prelude::Queryable;
 use query_builder::dialect;
 use tracing::debug;
@@ -23,13 +26,79 @@ impl DataWriter for MySqlDriver {
 
         debug!(rows = num_rows, table = %meta.name, "inserting rows");
 
+        let hash = || audit::hash_params(&params);
         let params = MySqlParamStore::from_values(&params).params();
         let mut conn = self.pool().get_conn().await?;
-        let result = conn
-            .exec_iter(&sql, params)
-            .await
-            .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+        let affected = audit::statement(&sql, hash, async {
+            conn.exec_iter(&sql, params)
+                .await
+                .map(|result| result.affected_rows())
+        })
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
 
-        Ok(result.affected_rows())
+        Ok(affected)
+    }
+
+    async fn insert_missing(
+        &self,
+        meta: &TableMetadata,
+        rows: &[Record],
+    ) -> Result<u64, DriverError> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let generator = QueryGenerator::new(&dialect::MySql);
+        let (sql, params) = generator.insert_missing(meta, rows, &MySqlTypeConverter);
+
+        let hash = || audit::hash_params(&params);
+        let params = MySqlParamStore::from_values(&params).params();
+        let mut conn = self.pool().get_conn().await?;
+        // A row left as it was counts as 0 affected rows under ON DUPLICATE KEY UPDATE
+        audit::statement(&sql, hash, async {
+            conn.exec_iter(&sql, params)
+                .await
+                .map(|result| result.affected_rows())
+        })
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))
+    }
+
+    async fn write_chunk(
+        &self,
+        table: &str,
+        key: &[(String, Value)],
+        column: &str,
+        chunk: &[u8],
+        append: bool,
+    ) -> Result<u64, DriverError> {
+        let template = if append {
+            queries::APPEND_CHUNK
+        } else {
+            queries::WRITE_CHUNK
+        };
+        let sql = template
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column))
+            .replace(
+                "{key}",
+                &queries::key_predicate(key.iter().map(|(c, _)| c.as_str())),
+            );
+        let mut values = vec![Value::Binary(chunk.to_vec())];
+        values.extend(key.iter().map(|(_, v)| v.clone()));
+
+        let hash = || audit::hash_params(&values);
+        let params = MySqlParamStore::from_values(&values).params();
+        let mut conn = self.pool().get_conn().await?;
+        // MySQL counts changed rows: rewriting a single-chunk value an earlier
+        // run already wrote reports 0 too, which leaves nothing to append.
+        audit::statement(&sql, hash, async {
+            conn.exec_iter(&sql, params)
+                .await
+                .map(|result| result.affected_rows())
+        })
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))
     }
 }
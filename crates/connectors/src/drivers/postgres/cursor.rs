@@ -0,0 +1,102 @@
+use crate::{
+    drivers::postgres::{
+        driver::{PgSession, begin_at_snapshot, set_search_path},
+        params::PgParamStore,
+        row::PgRowDecoder,
+        tls,
+    },
+    error::DriverError,
+    traits::{cursor::RowCursor, row_decoder::RowDecoder},
+};
+use async_trait::async_trait;
+use model::{core::value::Value, records::Record};
+use tokio_postgres::Client;
+use tracing::{debug, warn};
+
+/// Cursor name used on the dedicated connection. Each cursor owns its own
+/// connection, so a fixed name never collides.
+const CURSOR_NAME: &str = "stratum_source_cursor";
+
+/// PostgreSQL server-side cursor that owns a dedicated connection.
+/// The cursor is declared inside a `REPEATABLE READ` read-only transaction, so
+/// every page is read from the same snapshot and the query is planned once.
+/// With an exported snapshot given, the transaction reads at that snapshot.
+pub struct PgServerCursor {
+    client: Client,
+    table: String,
+    closed: bool,
+}
+
+impl PgServerCursor {
+    /// Open a fresh connection, start a snapshot transaction and declare the cursor.
+    pub async fn declare(
+        url: &str,
+        schema: &str,
+        session: &PgSession,
+        table: &str,
+        sql: &str,
+        params: &[Value],
+        snapshot: Option<&str>,
+    ) -> Result<Self, DriverError> {
+        let client = tls::connect(url).await?;
+        set_search_path(&client, schema).await?;
+        session.apply(&client).await?;
+
+        match snapshot {
+            Some(snapshot) => begin_at_snapshot(&client, snapshot).await?,
+            None => client
+                .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+                .await
+                .map_err(|e| DriverError::TransactionError(e.to_string()))?,
+        }
+
+        let declare = format!("DECLARE {CURSOR_NAME} NO SCROLL CURSOR FOR {sql}");
+        let param_store = PgParamStore::from_values(params);
+        client
+            .execute(&declare, &param_store.as_refs()[..])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        debug!(table, sql, "declared server-side cursor");
+
+        Ok(Self {
+            client,
+            table: table.to_string(),
+            closed: false,
+        })
+    }
+}
+
+impl Drop for PgServerCursor {
+    fn drop(&mut self) {
+        if !self.closed {
+            warn!(table = %self.table, "server-side cursor dropped without close, rolling back");
+        }
+    }
+}
+
+#[async_trait]
+impl RowCursor for PgServerCursor {
+    async fn fetch_next(&mut self, count: usize) -> Result<Vec<Record>, DriverError> {
+        let sql = format!("FETCH FORWARD {count} FROM {CURSOR_NAME}");
+        let rows = self
+            .client
+            .query(&sql, &[])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PgRowDecoder(row).decode(&self.table))
+            .collect())
+    }
+
+    async fn close(mut self: Box<Self>) -> Result<(), DriverError> {
+        self.client
+            .batch_execute(&format!("CLOSE {CURSOR_NAME}; COMMIT"))
+            .await
+            .map_err(|e| DriverError::TransactionError(e.to_string()))?;
+        self.closed = true;
+        Ok(())
+    }
+}
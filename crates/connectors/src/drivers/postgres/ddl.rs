@@ -1,10 +1,12 @@
 use crate::{
-    drivers::postgres::driver::PgDriver,
+    audit,
+    drivers::postgres::{driver::PgDriver, queries},
     error::DriverError,
     sql::query::{column::ColumnDef, generator::QueryGenerator},
     traits::{ddl::DdlWriter, executor::QueryExecutor},
 };
 use async_trait::async_trait;
+use model::core::value::Value;
 use query_builder::dialect;
 use tracing::debug;
 
@@ -20,4 +22,26 @@ impl DdlWriter for PgDriver {
 
         Ok(())
     }
+
+    async fn sync_sequence(&self, table: &str, column: &str) -> Result<Option<i64>, DriverError> {
+        let fqn = queries::qualified_table_name(table, Some(self.schema()));
+        let sql = queries::SYNC_SEQUENCE
+            .replace("{table}", &fqn)
+            .replace("{column}", &queries::escape_identifier(column));
+
+        debug!(column = %column, table = %table, "syncing sequence");
+
+        let client = self.client().read().await;
+        let params = || {
+            audit::hash_params(&[
+                Value::String(fqn.clone()),
+                Value::String(column.to_string()),
+            ])
+        };
+        let row = audit::statement(&sql, params, client.query_one(&sql, &[&fqn, &column]))
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        Ok(row.get("next_value"))
+    }
 }
@@ -1,9 +1,10 @@
 use super::{queries::escape_identifier, tls};
 use crate::{
     error::DriverError,
-    sql::metadata::capabilities::Capabilities,
+    sql::metadata::capabilities::{Capabilities, SQL_PAGINATION},
     traits::driver::{Driver, DriverInfo},
 };
+use model::core::value::Value;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_postgres::Client;
@@ -19,7 +20,11 @@ pub struct PgDriver {
     client: Arc<RwLock<Client>>,
     url: String,
     schema: String,
+    /// How every session of the driver is set up after connecting
+    session: PgSession,
     capabilities: Capabilities,
+    /// Exported snapshot every read of this driver is pinned to
+    snapshot: Option<String>,
 }
 
 impl PgDriver {
@@ -39,22 +44,63 @@ impl PgDriver {
     /// set so that unqualified reads, writes, and DDL target that schema, and
     /// the schema is used to scope introspection queries.
     pub async fn connect_with_schema(url: &str, schema: &str) -> Result<Self, DriverError> {
+        Self::establish(url, schema, &PgSession::default(), None).await
+    }
+
+    /// Establishes a connection scoped to `schema` whose sessions, including
+    /// the ones transactions and cursors open, are set up as `session`.
+    pub async fn connect_as(
+        url: &str,
+        schema: &str,
+        session: &PgSession,
+    ) -> Result<Self, DriverError> {
+        Self::establish(url, schema, session, None).await
+    }
+
+    /// Establishes a read-only connection whose session stays in one
+    /// `REPEATABLE READ` transaction importing `snapshot`, so that every
+    /// query, and every server-side cursor the driver declares, sees the
+    /// data as of that snapshot. An error aborts the transaction, after
+    /// which the driver can no longer be used.
+    pub async fn connect_at_snapshot(
+        url: &str,
+        schema: &str,
+        snapshot: &str,
+    ) -> Result<Self, DriverError> {
+        Self::establish(url, schema, &PgSession::default(), Some(snapshot)).await
+    }
+
+    async fn establish(
+        url: &str,
+        schema: &str,
+        session: &PgSession,
+        snapshot: Option<&str>,
+    ) -> Result<Self, DriverError> {
         let client = tls::connect(url).await?;
         set_search_path(&client, schema).await?;
+        session.apply(&client).await?;
+        if let Some(snapshot) = snapshot {
+            begin_at_snapshot(&client, snapshot).await?;
+        }
 
         let client = Arc::new(RwLock::new(client));
         let capabilities = Self::detect_capabilities(&client).await?;
 
         info!(
             driver = "postgres",
-            schema, "database connection established"
+            schema,
+            role = session.role.as_deref(),
+            snapshot,
+            "database connection established"
         );
 
         Ok(Self {
             client,
             url: url.to_string(),
             schema: schema.to_string(),
+            session: session.clone(),
             capabilities,
+            snapshot: snapshot.map(str::to_string),
         })
     }
 
@@ -70,6 +116,16 @@ impl PgDriver {
         &self.schema
     }
 
+    /// How the driver's sessions are set up after connecting
+    pub fn session(&self) -> &PgSession {
+        &self.session
+    }
+
+    /// The exported snapshot reads are pinned to, if any
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
     /// Fetches the version string from the DB and resolves capabilities.
     async fn detect_capabilities(
         client: &Arc<RwLock<Client>>,
@@ -86,7 +142,7 @@ impl PgDriver {
         Ok(Self::resolve_capabilities(version))
     }
 
-    fn resolve_capabilities(version: String) -> Capabilities {
+    pub(crate) fn resolve_capabilities(version: String) -> Capabilities {
         Capabilities {
             version,
             transactions: true,
@@ -94,11 +150,15 @@ impl PgDriver {
             copy_protocol: true,
             upsert: true,
             returning_clause: true,
+            ddl: true,
+            cdc: false,
             json_type: true,
             jsonb_type: true,
             array_type: true,
             uuid_type: true,
             geometry_type: true,
+            materialized_views: true,
+            pagination: SQL_PAGINATION,
             max_parameters: Some(PG_MAX_PREPARED_STMT_PARAMS),
             max_query_size: None,
         }
@@ -123,6 +183,60 @@ pub(crate) async fn set_search_path(client: &Client, schema: &str) -> Result<(),
         .map_err(|e| DriverError::QueryError(e.to_string()))
 }
 
+/// How a session is set up after connecting, beyond its `search_path`.
+#[derive(Debug, Clone, Default)]
+pub struct PgSession {
+    /// Role to `SET ROLE` to, so the statements the session runs, DDL
+    /// included, are checked against and create objects owned by that role
+    pub role: Option<String>,
+    /// Run-time parameters to `SET`, such as `synchronous_commit = off` for
+    /// bulk loads. They last as long as the session.
+    pub settings: Vec<(String, Value)>,
+}
+
+impl PgSession {
+    /// Apply the settings, then switch role, so that settings only the login
+    /// role may change are set before the switch.
+    pub(crate) async fn apply(&self, client: &Client) -> Result<(), DriverError> {
+        for (name, value) in &self.settings {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Boolean(true) => "on".to_string(),
+                Value::Boolean(false) => "off".to_string(),
+                other => other.as_string().unwrap_or_default(),
+            };
+            let sql = format!("SET {name} TO '{}'", value.replace('\'', "''"));
+            client
+                .batch_execute(&sql)
+                .await
+                .map_err(|e| DriverError::QueryError(format!("SET {name} failed: {e}")))?;
+        }
+
+        if let Some(role) = &self.role {
+            client
+                .batch_execute(&format!("SET ROLE {}", escape_identifier(role)))
+                .await
+                .map_err(|e| DriverError::QueryError(format!("SET ROLE {role} failed: {e}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Start a read-only `REPEATABLE READ` transaction that reads at the exported
+/// `snapshot`. The snapshot id comes from `pg_export_snapshot()`, but it is
+/// still quoted as a literal.
+pub(crate) async fn begin_at_snapshot(client: &Client, snapshot: &str) -> Result<(), DriverError> {
+    let sql = format!(
+        "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY; SET TRANSACTION SNAPSHOT '{}'",
+        snapshot.replace('\'', "''")
+    );
+
+    client
+        .batch_execute(&sql)
+        .await
+        .map_err(|e| DriverError::TransactionError(e.to_string()))
+}
+
 impl Driver for PgDriver {
     fn info(&self) -> &DriverInfo {
         &Self::INFO
@@ -1,4 +1,4 @@
-use crate::traits::encoder::CopyValueEncoder;
+use crate::{sql::query::coercion::array_literal, traits::encoder::CopyValueEncoder};
 use model::core::value::Value;
 
 /// PostgreSQL COPY protocol encoder.
@@ -22,23 +22,6 @@ impl PgCopyEncoder {
         let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
         format!("\\x{}", hex)
     }
-
-    /// Encodes an array in PostgreSQL array literal format.
-    fn encode_array(arr: &[Value]) -> String {
-        let elements: Vec<String> = arr
-            .iter()
-            .map(|v| match v {
-                Value::String(s) => format!("\"{}\"", s.replace('"', "\\\"")),
-                Value::Int(i) => i.to_string(),
-                Value::UInt(u) => u.to_string(),
-                Value::Float(f) => f.to_string(),
-                Value::Boolean(b) => b.to_string(),
-                Value::Null => "NULL".to_string(),
-                _ => "NULL".to_string(),
-            })
-            .collect();
-        format!("{{{}}}", elements.join(","))
-    }
 }
 
 impl CopyValueEncoder for PgCopyEncoder {
@@ -113,7 +96,7 @@ impl CopyValueEncoder for PgCopyEncoder {
             }
 
             // Array
-            Value::Array(arr) => Self::escape_csv(&Self::encode_array(arr)),
+            Value::Array(arr) => Self::escape_csv(&array_literal(arr)),
 
             // Enum
             Value::Enum { value, .. } => Self::escape_csv(value),
@@ -121,7 +104,7 @@ impl CopyValueEncoder for PgCopyEncoder {
             // Set - encode as array
             Value::Set(values) => {
                 let arr: Vec<Value> = values.iter().map(|s| Value::String(s.clone())).collect();
-                Self::escape_csv(&Self::encode_array(&arr))
+                Self::escape_csv(&array_literal(&arr))
             }
 
             // Geometry - WKB hex format
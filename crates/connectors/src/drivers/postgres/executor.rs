@@ -1,4 +1,5 @@
 use crate::{
+    audit,
     drivers::postgres::{driver::PgDriver, params::PgParamStore, row::PgRowDecoder},
     error::DriverError,
     traits::{executor::QueryExecutor, row_decoder::RowDecoder},
@@ -10,8 +11,7 @@ use model::{core::value::Value, records::Record};
 impl QueryExecutor for PgDriver {
     async fn execute(&self, sql: &str) -> Result<(), DriverError> {
         let client = self.client().read().await;
-        client
-            .batch_execute(sql)
+        audit::statement(sql, || None, client.batch_execute(sql))
             .await
             .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
         Ok(())
@@ -20,10 +20,13 @@ impl QueryExecutor for PgDriver {
     async fn execute_params(&self, sql: &str, params: &[Value]) -> Result<(), DriverError> {
         let client = self.client().read().await;
         let param_store = PgParamStore::from_values(params);
-        client
-            .execute(sql, &param_store.as_refs()[..])
-            .await
-            .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+        audit::statement(
+            sql,
+            || audit::hash_params(params),
+            client.execute(sql, &param_store.as_refs()[..]),
+        )
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
         Ok(())
     }
 
@@ -5,8 +5,10 @@ use crate::{
         column::ColumnMetadata,
         constraint::{CheckConstraintMetadata, UniqueConstraintMetadata},
         fk::ForeignKeyMetadata,
+        grant::TableGrant,
         index::IndexMetadata,
         provider::MetadataProvider,
+        sequence::SequenceMetadata,
         table::TableMetadata,
     },
     traits::introspector::SchemaIntrospector,
@@ -130,6 +132,19 @@ impl SchemaIntrospector for PgDriver {
         Ok(size_bytes as u64)
     }
 
+    async fn avg_row_bytes(&self, table: &str) -> Result<Option<u64>, DriverError> {
+        let client = self.client().read().await;
+        let schema = self.schema();
+
+        let row = client
+            .query_one(queries::AVG_ROW_BYTES_SQL, &[&table, &schema])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+        let avg_row_bytes: Option<i64> = row.get("avg_row_bytes");
+
+        Ok(avg_row_bytes.map(|n| n as u64))
+    }
+
     async fn unique_constraint_metadata(
         &self,
         table: &str,
@@ -175,4 +190,44 @@ impl SchemaIntrospector for PgDriver {
 
         Ok(constraints)
     }
+
+    async fn table_grants(&self, table: &str) -> Result<Vec<TableGrant>, DriverError> {
+        let client = self.client().read().await;
+        let schema = self.schema();
+
+        let rows = client
+            .query(queries::TABLE_GRANTS_SQL, &[&table, &schema])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        let grants = rows
+            .iter()
+            .map(|row| {
+                let decoder = PgRowDecoder(row);
+                TableGrant::from_row(&decoder)
+            })
+            .collect();
+
+        Ok(grants)
+    }
+
+    async fn standalone_sequences(&self) -> Result<Vec<SequenceMetadata>, DriverError> {
+        let client = self.client().read().await;
+        let schema = self.schema();
+
+        let rows = client
+            .query(queries::STANDALONE_SEQUENCES_SQL, &[&schema])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        let sequences = rows
+            .iter()
+            .map(|row| {
+                let decoder = PgRowDecoder(row);
+                SequenceMetadata::from_row(&decoder)
+            })
+            .collect();
+
+        Ok(sequences)
+    }
 }
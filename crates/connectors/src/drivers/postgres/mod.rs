@@ -1,13 +1,15 @@
-pub mod coercion;
+pub mod cursor;
 pub mod ddl;
 pub mod driver;
 pub mod encoder;
 pub mod executor;
 pub mod introspector;
+pub mod numeric;
 pub mod params;
 pub mod queries;
 pub mod reader;
 pub mod row;
+pub mod snapshot;
 pub mod tls;
 pub mod transaction;
 pub mod types;
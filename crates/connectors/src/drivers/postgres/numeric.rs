@@ -0,0 +1,210 @@
+//! Exact binary codec for PostgreSQL `NUMERIC`.
+//!
+//! `NUMERIC` is arbitrary precision on the wire: a sign, a base-10000 weight,
+//! a display scale and a list of base-10000 digits. Going through a
+//! fixed-width decimal type (or `f64`) silently truncates values outside its
+//! range, so both directions are implemented directly over `BigDecimal`.
+
+use bigdecimal::{BigDecimal, num_bigint::BigInt};
+use bytes::{Buf, BufMut, BytesMut};
+use std::error::Error;
+use tokio_postgres::types::{FromSql, IsNull, ToSql, Type, to_sql_checked};
+
+const SIGN_POSITIVE: u16 = 0x0000;
+const SIGN_NEGATIVE: u16 = 0x4000;
+const SIGN_NAN: u16 = 0xC000;
+const SIGN_POS_INF: u16 = 0xD000;
+const SIGN_NEG_INF: u16 = 0xF000;
+
+/// A `BigDecimal` that binds to and decodes from `NUMERIC` without loss.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgNumeric(pub BigDecimal);
+
+impl ToSql for PgNumeric {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let (int, exp) = self.0.as_bigint_and_exponent();
+        let negative = int < BigInt::from(0);
+        let mut digits = int.magnitude().to_string();
+
+        // A negative exponent means trailing zeros on the integer part.
+        let scale = if exp < 0 {
+            digits.push_str(&"0".repeat(exp.unsigned_abs() as usize));
+            0
+        } else {
+            exp as usize
+        };
+        let dscale = u16::try_from(scale).map_err(|_| "NUMERIC scale out of range")?;
+
+        if digits.len() <= scale {
+            digits = format!("{}{}", "0".repeat(scale - digits.len() + 1), digits);
+        }
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+
+        // Align both halves on base-10000 boundaries around the decimal point.
+        let int_pad = (4 - int_part.len() % 4) % 4;
+        let frac_pad = (4 - frac_part.len() % 4) % 4;
+        let aligned = format!(
+            "{}{}{}{}",
+            "0".repeat(int_pad),
+            int_part,
+            frac_part,
+            "0".repeat(frac_pad)
+        );
+        let mut groups: Vec<i16> = aligned
+            .as_bytes()
+            .chunks(4)
+            .map(|c| std::str::from_utf8(c).unwrap().parse().unwrap())
+            .collect();
+        let mut weight = ((int_part.len() + int_pad) / 4) as i32 - 1;
+
+        let leading = groups.iter().take_while(|g| **g == 0).count();
+        groups.drain(..leading);
+        weight -= leading as i32;
+        while groups.last() == Some(&0) {
+            groups.pop();
+        }
+        if groups.is_empty() {
+            weight = 0;
+        }
+
+        let ndigits =
+            i16::try_from(groups.len()).map_err(|_| "NUMERIC value has too many digits")?;
+        let weight = i16::try_from(weight).map_err(|_| "NUMERIC weight out of range")?;
+
+        out.put_i16(ndigits);
+        out.put_i16(weight);
+        out.put_u16(if negative && !groups.is_empty() {
+            SIGN_NEGATIVE
+        } else {
+            SIGN_POSITIVE
+        });
+        out.put_u16(dscale);
+        for group in groups {
+            out.put_i16(group);
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for PgNumeric {
+    fn from_sql(_ty: &Type, mut raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() < 8 {
+            return Err("NUMERIC value is truncated".into());
+        }
+        let ndigits = raw.get_i16();
+        let weight = raw.get_i16() as i64;
+        let sign = raw.get_u16();
+        let dscale = raw.get_u16() as i64;
+
+        match sign {
+            SIGN_POSITIVE | SIGN_NEGATIVE => {}
+            SIGN_NAN => return Err("NUMERIC NaN cannot be represented as a decimal".into()),
+            SIGN_POS_INF | SIGN_NEG_INF => {
+                return Err("NUMERIC infinity cannot be represented as a decimal".into());
+            }
+            other => return Err(format!("invalid NUMERIC sign 0x{other:04X}").into()),
+        }
+        if ndigits < 0 || raw.len() != ndigits as usize * 2 {
+            return Err("NUMERIC digit count does not match payload".into());
+        }
+
+        let mut digits = String::with_capacity(ndigits as usize * 4 + 1);
+        if sign == SIGN_NEGATIVE {
+            digits.push('-');
+        }
+        for _ in 0..ndigits {
+            let group = raw.get_i16();
+            if !(0..10000).contains(&group) {
+                return Err(format!("invalid NUMERIC digit {group}").into());
+            }
+            digits.push_str(&format!("{group:04}"));
+        }
+        if ndigits == 0 {
+            digits.push('0');
+        }
+
+        let int: BigInt = digits.parse()?;
+        let exp = 4 * (ndigits as i64 - 1 - weight);
+        Ok(PgNumeric(BigDecimal::new(int, exp).with_scale(dscale)))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn round_trip(s: &str) -> BigDecimal {
+        let mut buf = BytesMut::new();
+        PgNumeric(BigDecimal::from_str(s).unwrap())
+            .to_sql(&Type::NUMERIC, &mut buf)
+            .unwrap();
+        PgNumeric::from_sql(&Type::NUMERIC, &buf).unwrap().0
+    }
+
+    #[test]
+    fn test_round_trip_preserves_value_and_scale() {
+        for s in [
+            "0",
+            "0.00",
+            "12.50",
+            "-12.50",
+            "10000",
+            "0.0001",
+            "-0.000000001",
+            "123456789012345678901234567890.123456789012345678901234567890",
+            "99999999999999999999999999999999999999",
+        ] {
+            let d = round_trip(s);
+            assert_eq!(
+                d.to_string(),
+                BigDecimal::from_str(s).unwrap().to_string(),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encodes_postgres_layout() {
+        // 12345.678 => digits [1, 2345, 6780], weight 1, dscale 3
+        let mut buf = BytesMut::new();
+        PgNumeric(BigDecimal::from_str("12345.678").unwrap())
+            .to_sql(&Type::NUMERIC, &mut buf)
+            .unwrap();
+        assert_eq!(
+            buf.as_ref(),
+            &[0, 3, 0, 1, 0, 0, 0, 3, 0, 1, 0x09, 0x29, 0x1A, 0x7C]
+        );
+    }
+
+    #[test]
+    fn test_negative_exponent_expands_integer_digits() {
+        let d = BigDecimal::new(BigInt::from(5), -6);
+        let mut buf = BytesMut::new();
+        PgNumeric(d.clone())
+            .to_sql(&Type::NUMERIC, &mut buf)
+            .unwrap();
+        let decoded = PgNumeric::from_sql(&Type::NUMERIC, &buf).unwrap().0;
+        assert_eq!(decoded, d);
+    }
+
+    #[test]
+    fn test_rejects_nan() {
+        let raw = [0, 0, 0, 0, 0xC0, 0, 0, 0];
+        assert!(PgNumeric::from_sql(&Type::NUMERIC, &raw).is_err());
+    }
+}
@@ -1,8 +1,7 @@
+use super::numeric::PgNumeric;
 use bytes::BytesMut;
 use chrono::{DateTime, TimeZone, Utc};
 use model::core::value::Value;
-use rust_decimal::Decimal as RustDecimal;
-use std::str::FromStr;
 use tokio_postgres::types::{IsNull, Json as PgJson, ToSql, Type, to_sql_checked};
 
 /// Integer parameter that accepts any PG integer column type (INT2/INT4/INT8).
@@ -38,10 +37,7 @@ impl PgParam {
             Value::Int(i) => PgParam(Box::new(FlexInt(*i))),
             Value::UInt(u) => PgParam(Box::new(FlexInt(*u as i64))),
             Value::Float(f) => PgParam(Box::new(*f)),
-            Value::Decimal(d) => {
-                let decimal = RustDecimal::from_str(&d.to_string()).unwrap_or_default();
-                PgParam(Box::new(decimal))
-            }
+            Value::Decimal(d) => PgParam(Box::new(PgNumeric(d.clone()))),
 
             // String
             Value::String(s) => PgParam(Box::new(s.clone())),
@@ -87,6 +83,7 @@ impl PgParam {
                         Value::Int(i) => i.to_string(),
                         Value::UInt(u) => u.to_string(),
                         Value::Float(f) => f.to_string(),
+                        Value::Decimal(d) => d.to_string(),
                         Value::Boolean(b) => b.to_string(),
                         _ => String::new(),
                     })
@@ -139,6 +136,7 @@ impl PgParam {
                             Value::Int(i) => serde_json::json!(i),
                             Value::UInt(u) => serde_json::json!(u),
                             Value::Float(f) => serde_json::json!(f),
+                            Value::Decimal(d) => serde_json::Value::String(d.to_string()),
                             Value::Boolean(b) => serde_json::json!(b),
                             Value::Null => serde_json::Value::Null,
                             _ => serde_json::Value::Null,
@@ -9,6 +9,35 @@ pub const COUNT_NO_FILTER: &str = "SELECT COUNT(*) AS count FROM {table}";
 /// Count rows with fast estimate using pg_class statistics
 pub const COUNT_ROWS_FAST: &str = include_str!("sql/count_rows_fast.sql");
 
+/// Set a column's owned sequence to one past its largest value
+/// Placeholders: {table} - escaped table identifier, {column} - escaped column identifier
+pub const SYNC_SEQUENCE: &str = "SELECT setval(pg_get_serial_sequence($1, $2), COALESCE(MAX({column}), 0) + 1, false) AS next_value FROM {table}";
+
+/// Smallest and largest value of an integer key
+/// Placeholders: {table} - escaped table identifier, {column} - escaped column identifier
+pub const KEY_BOUNDS: &str =
+    "SELECT MIN({column})::bigint AS min_key, MAX({column})::bigint AS max_key FROM {table}";
+
+/// Equal-frequency histogram of an integer column, collected by ANALYZE
+/// Parameters: $1 - schema, $2 - table, $3 - column
+pub const KEY_HISTOGRAM: &str = "SELECT histogram_bounds::text::bigint[] AS bounds FROM pg_stats WHERE schemaname = $1 AND tablename = $2 AND attname = $3 ORDER BY inherited LIMIT 1";
+
+/// Primary keys of the rows whose binary column holds more than $1 bytes
+/// Placeholders: {keys} - escaped key columns, {table} - escaped table identifier,
+/// {column} - escaped column identifier
+pub const OVERSIZED_KEYS: &str = "SELECT {keys} FROM {table} WHERE octet_length({column}) > $1";
+
+/// One chunk of a binary value; {from} is 1-based
+/// Placeholders: {column}, {from}, {len}, {table}, {key} - key predicate from $1
+pub const READ_CHUNK: &str =
+    "SELECT substring({column} FROM {from} FOR {len}) AS chunk FROM {table} WHERE {key}";
+
+/// Placeholders: {table}, {column}, {key} - key predicate from $2; $1 - the chunk
+pub const WRITE_CHUNK: &str = "UPDATE {table} SET {column} = $1 WHERE {key}";
+
+/// Placeholders: {table}, {column}, {key} - key predicate from $2; $1 - the chunk
+pub const APPEND_CHUNK: &str = "UPDATE {table} SET {column} = {column} || $1 WHERE {key}";
+
 pub const TABLE_EXISTS_SQL: &str = include_str!("sql/table_exists.sql");
 pub const LIST_TABLES_SQL: &str = "SELECT table_name FROM information_schema.tables WHERE table_schema = $1 AND table_type = 'BASE TABLE'";
 pub const TABLE_METADATA_SQL: &str = include_str!("sql/table_metadata.sql");
@@ -16,8 +45,11 @@ pub const INDEX_METADATA_SQL: &str = include_str!("sql/index_metadata.sql");
 pub const FK_METADATA_SQL: &str = include_str!("sql/fk_metadata.sql");
 pub const REFERRING_TABLES_SQL: &str = include_str!("sql/table_referencing.sql");
 pub const TABLE_SIZE_SQL: &str = "SELECT pg_total_relation_size($1) AS size_bytes;";
+pub const AVG_ROW_BYTES_SQL: &str = "SELECT SUM(avg_width)::bigint AS avg_row_bytes FROM pg_stats WHERE tablename = $1 AND schemaname = $2";
 pub const UNIQUE_CONSTRAINT_METADATA_SQL: &str = include_str!("sql/unique_constraint_metadata.sql");
 pub const CHECK_CONSTRAINT_METADATA_SQL: &str = include_str!("sql/check_constraint_metadata.sql");
+pub const TABLE_GRANTS_SQL: &str = include_str!("sql/table_grants.sql");
+pub const STANDALONE_SEQUENCES_SQL: &str = include_str!("sql/standalone_sequences.sql");
 
 /// Escape a PostgreSQL identifier (table name, column name, etc.) to prevent SQL injection.
 /// Wraps the identifier in double quotes and escapes any internal quotes by doubling them.
@@ -33,3 +65,13 @@ pub fn qualified_table_name(table: &str, schema: Option<&str>) -> String {
         None => escape_identifier(table),
     }
 }
+
+/// `"a" = $n AND "b" = $n+1 ...` over the key columns, numbered from `first`.
+pub fn key_predicate<'a>(columns: impl IntoIterator<Item = &'a str>, first: usize) -> String {
+    columns
+        .into_iter()
+        .enumerate()
+        .map(|(i, column)| format!("{} = ${}", escape_identifier(column), first + i))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
@@ -1,11 +1,13 @@
 use crate::{
-    drivers::postgres::{driver::PgDriver, params::PgParamStore, queries, row::PgRowDecoder},
+    drivers::postgres::{
+        cursor::PgServerCursor, driver::PgDriver, params::PgParamStore, queries, row::PgRowDecoder,
+    },
     error::DriverError,
     sql::{filter::SqlFilter, query::generator::QueryGenerator, request::FetchRowsRequest},
-    traits::{reader::DataReader, row_decoder::RowDecoder},
+    traits::{cursor::RowCursor, reader::DataReader, row_decoder::RowDecoder},
 };
 use async_trait::async_trait;
-use model::records::Record;
+use model::{core::value::Value, records::Record};
 use query_builder::dialect;
 use tracing::debug;
 
@@ -72,4 +74,136 @@ impl DataReader for PgDriver {
             ))
         }
     }
+
+    async fn key_bounds(
+        &self,
+        table: &str,
+        column: &str,
+    ) -> Result<Option<(i64, i64)>, DriverError> {
+        let query = queries::KEY_BOUNDS
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column));
+
+        let client = self.client().read().await;
+        let row = client
+            .query_one(&query, &[])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        let min: Option<i64> = row.get("min_key");
+        let max: Option<i64> = row.get("max_key");
+        Ok(min.zip(max))
+    }
+
+    async fn key_split_points(
+        &self,
+        table: &str,
+        column: &str,
+        partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        let client = self.client().read().await;
+        let schema = self.schema();
+        let row = client
+            .query_opt(queries::KEY_HISTOGRAM, &[&schema, &table, &column])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        // Each pair of neighbouring bounds holds about the same number of rows
+        let bounds: Vec<i64> = row
+            .and_then(|row| row.get::<_, Option<Vec<i64>>>("bounds"))
+            .unwrap_or_default();
+        if bounds.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let mut points: Vec<i64> = Vec::new();
+        for i in 1..partitions {
+            let point = bounds[i * (bounds.len() - 1) / partitions];
+            if points.last().is_none_or(|last| point > *last) {
+                points.push(point);
+            }
+        }
+        Ok(points)
+    }
+
+    async fn oversized_keys(
+        &self,
+        table: &str,
+        key_columns: &[String],
+        column: &str,
+        threshold: usize,
+    ) -> Result<Vec<Record>, DriverError> {
+        let keys = key_columns
+            .iter()
+            .map(|c| queries::escape_identifier(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = queries::OVERSIZED_KEYS
+            .replace("{keys}", &keys)
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column));
+
+        let client = self.client().read().await;
+        let param_store = PgParamStore::from_values(&[Value::Int(threshold as i64)]);
+        let rows = client
+            .query(&query, &param_store.as_refs()[..])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PgRowDecoder(row).decode(table))
+            .collect())
+    }
+
+    async fn read_chunk(
+        &self,
+        table: &str,
+        key: &[(String, Value)],
+        column: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, DriverError> {
+        let query = queries::READ_CHUNK
+            .replace("{column}", &queries::escape_identifier(column))
+            .replace("{from}", &(offset + 1).to_string())
+            .replace("{len}", &len.to_string())
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace(
+                "{key}",
+                &queries::key_predicate(key.iter().map(|(c, _)| c.as_str()), 1),
+            );
+        let params: Vec<Value> = key.iter().map(|(_, v)| v.clone()).collect();
+
+        let client = self.client().read().await;
+        let param_store = PgParamStore::from_values(&params);
+        let row = client
+            .query_opt(&query, &param_store.as_refs()[..])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+
+        Ok(row
+            .and_then(|row| row.get::<_, Option<Vec<u8>>>("chunk"))
+            .unwrap_or_default())
+    }
+
+    async fn open_cursor(
+        &self,
+        request: FetchRowsRequest,
+    ) -> Result<Box<dyn RowCursor>, DriverError> {
+        let generator = QueryGenerator::new(&dialect::Postgres);
+        let (sql, params) = generator.select(&request);
+
+        let cursor = PgServerCursor::declare(
+            self.url(),
+            self.schema(),
+            self.session(),
+            &request.table,
+            &sql,
+            &params,
+            self.snapshot(),
+        )
+        .await?;
+        Ok(Box::new(cursor))
+    }
 }
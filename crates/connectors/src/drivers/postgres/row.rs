@@ -1,5 +1,5 @@
+use super::numeric::PgNumeric;
 use crate::traits::row_decoder::RowDecoder;
-use bigdecimal::BigDecimal;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use model::{
     core::{
@@ -8,10 +8,9 @@ use model::{
     },
     records::{OpType, Record},
 };
-use rust_decimal::Decimal as RustDecimal;
-use std::{net::IpAddr, str::FromStr};
+use std::net::IpAddr;
 use tokio_postgres::Row as PgRow;
-use tokio_postgres::types::Type as PgType;
+use tokio_postgres::types::{FromSql, Kind, Type as PgType};
 use uuid::Uuid;
 
 /// Wrapper for PostgreSQL Row to implement RowDecoder
@@ -89,6 +88,19 @@ impl<'a> RowDecoder for PgRowDecoder<'a> {
 fn pg_type_to_canonical(pg_type: &PgType) -> Type {
     use model::core::types::{FloatSize, IntSize};
 
+    // User-defined enum types
+    if let Kind::Enum(values) = pg_type.kind() {
+        return Type::Enum {
+            name: pg_type.name().to_string(),
+            values: values.clone(),
+        };
+    }
+    if let Kind::Array(element) = pg_type.kind() {
+        return Type::Array {
+            element: Box::new(pg_type_to_canonical(element)),
+        };
+    }
+
     match *pg_type {
         // Integer types
         PgType::INT2 => Type::Int {
@@ -179,6 +191,11 @@ fn pg_type_to_canonical(pg_type: &PgType) -> Type {
 
 /// Extract a value from a PostgreSQL row at the given index
 fn extract_value(row: &PgRow, idx: usize, pg_type: &PgType) -> Option<Value> {
+    // One-dimensional arrays, decoded by element type
+    if let Kind::Array(element) = pg_type.kind() {
+        return extract_array(row, idx, element);
+    }
+
     match *pg_type {
         // Integer types
         PgType::INT2 => row
@@ -200,9 +217,9 @@ fn extract_value(row: &PgRow, idx: usize, pg_type: &PgType) -> Option<Value> {
 
         // Decimal
         PgType::NUMERIC => row
-            .try_get::<_, RustDecimal>(idx)
+            .try_get::<_, PgNumeric>(idx)
             .ok()
-            .map(|d| Value::Decimal(BigDecimal::from_str(&d.to_string()).unwrap_or_default())),
+            .map(|d| Value::Decimal(d.0)),
 
         // String types.
         // BPCHAR (CHAR(n)) is blank-padded to the declared length on storage and
@@ -262,19 +279,85 @@ fn extract_value(row: &PgRow, idx: usize, pg_type: &PgType) -> Option<Value> {
         // Network types
         PgType::INET => row.try_get::<_, IpAddr>(idx).ok().map(Value::IpAddr),
 
-        // Arrays - handle text arrays as common case
-        _ if pg_type.name().starts_with('_') => {
-            // Array type
-            if let Ok(arr) = row.try_get::<_, Vec<String>>(idx) {
-                Some(Value::Array(arr.into_iter().map(Value::String).collect()))
-            } else if let Ok(arr) = row.try_get::<_, Vec<i64>>(idx) {
-                Some(Value::Array(arr.into_iter().map(Value::Int).collect()))
-            } else {
-                None
-            }
+        // User-defined enum types
+        _ if matches!(pg_type.kind(), Kind::Enum(_)) => {
+            row.try_get::<_, EnumLabel>(idx)
+                .ok()
+                .map(|label| Value::Enum {
+                    type_name: pg_type.name().to_string(),
+                    value: label.0,
+                })
         }
 
         // Fallback - try string
         _ => row.try_get::<_, String>(idx).ok().map(Value::String),
     }
 }
+
+/// Extract an array whose elements are of type `element`. NULL elements are
+/// kept as `Value::Null`.
+fn extract_array(row: &PgRow, idx: usize, element: &PgType) -> Option<Value> {
+    fn items<'a, T: FromSql<'a>>(
+        row: &'a PgRow,
+        idx: usize,
+        to_value: impl Fn(T) -> Value,
+    ) -> Option<Value> {
+        let items = row.try_get::<_, Vec<Option<T>>>(idx).ok()?;
+        Some(Value::Array(
+            items
+                .into_iter()
+                .map(|item| item.map_or(Value::Null, &to_value))
+                .collect(),
+        ))
+    }
+
+    match *element {
+        PgType::INT2 => items(row, idx, |v: i16| Value::Int(v as i64)),
+        PgType::INT4 => items(row, idx, |v: i32| Value::Int(v as i64)),
+        PgType::INT8 => items(row, idx, Value::Int),
+        PgType::FLOAT4 => items(row, idx, |v: f32| Value::Float(v as f64)),
+        PgType::FLOAT8 => items(row, idx, Value::Float),
+        PgType::NUMERIC => items(row, idx, |d: PgNumeric| Value::Decimal(d.0)),
+        PgType::BPCHAR => items(row, idx, |s: String| {
+            Value::String(s.trim_end_matches(' ').to_string())
+        }),
+        PgType::BOOL => items(row, idx, Value::Boolean),
+        PgType::BYTEA => items(row, idx, Value::Binary),
+        PgType::DATE => items(row, idx, Value::Date),
+        PgType::TIMESTAMP => items(row, idx, |v: NaiveDateTime| Value::Timestamp {
+            value: v,
+            offset_secs: None,
+        }),
+        PgType::TIMESTAMPTZ => items(row, idx, |v: DateTime<Utc>| Value::Timestamp {
+            value: v.naive_utc(),
+            offset_secs: Some(0),
+        }),
+        PgType::JSON | PgType::JSONB => items(row, idx, Value::Json),
+        PgType::UUID => items(row, idx, Value::Uuid),
+        PgType::INET => items(row, idx, Value::IpAddr),
+        _ if matches!(element.kind(), Kind::Enum(_)) => {
+            items(row, idx, |label: EnumLabel| Value::Enum {
+                type_name: element.name().to_string(),
+                value: label.0,
+            })
+        }
+        _ => items(row, idx, Value::String),
+    }
+}
+
+/// Label of an enum value. `String` only decodes text types, but an enum is
+/// sent as its label in both the text and binary formats.
+struct EnumLabel(String);
+
+impl<'a> FromSql<'a> for EnumLabel {
+    fn from_sql(
+        _: &PgType,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Self(std::str::from_utf8(raw)?.to_string()))
+    }
+
+    fn accepts(ty: &PgType) -> bool {
+        matches!(ty.kind(), Kind::Enum(_))
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{drivers::postgres::tls, error::DriverError};
+use tokio_postgres::Client;
+use tracing::debug;
+
+/// A snapshot exported with `pg_export_snapshot()`. Other sessions can read
+/// at it with `SET TRANSACTION SNAPSHOT` only while the exporting transaction
+/// is open, so the snapshot stays importable until this value is dropped.
+pub struct PgSnapshot {
+    /// Keeps the exporting transaction open; dropping the connection ends it
+    _client: Client,
+    id: String,
+}
+
+impl PgSnapshot {
+    /// Open a connection, start a read-only `REPEATABLE READ` transaction and
+    /// export its snapshot.
+    pub async fn export(url: &str) -> Result<Self, DriverError> {
+        let client = tls::connect(url).await?;
+        client
+            .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .await
+            .map_err(|e| DriverError::TransactionError(e.to_string()))?;
+
+        let row = client
+            .query_one("SELECT pg_export_snapshot()", &[])
+            .await
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+        let id: String = row.get(0);
+        debug!(snapshot = %id, "exported snapshot");
+
+        Ok(Self {
+            _client: client,
+            id,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
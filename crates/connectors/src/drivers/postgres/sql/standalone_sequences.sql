@@ -0,0 +1,20 @@
+SELECT s.sequencename AS sequence_name,
+       s.start_value,
+       s.increment_by,
+       s.min_value,
+       s.max_value,
+       s.cycle,
+       s.last_value
+FROM pg_sequences s
+JOIN pg_namespace n ON n.nspname = s.schemaname
+JOIN pg_class c ON c.relname = s.sequencename AND c.relnamespace = n.oid
+WHERE s.schemaname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_depend d
+      WHERE d.classid = 'pg_class'::regclass
+        AND d.objid = c.oid
+        AND d.refclassid = 'pg_class'::regclass
+        AND d.deptype IN ('a', 'i')
+  )
+ORDER BY s.sequencename
@@ -0,0 +1,10 @@
+SELECT CASE WHEN a.grantee = 0 THEN 'PUBLIC' ELSE pg_get_userbyid(a.grantee) END AS grantee,
+       a.privilege_type,
+       a.is_grantable
+FROM pg_class t
+JOIN pg_namespace n ON t.relnamespace = n.oid
+CROSS JOIN LATERAL aclexplode(t.relacl) a
+WHERE t.relname = $1
+  AND n.nspname = $2
+  AND a.grantee <> t.relowner
+ORDER BY 1, 2
@@ -31,7 +31,18 @@ SELECT
   c.collation_name,
   NULL::text AS character_set_name,
   (c.is_generated = 'ALWAYS') AS is_generated,
-  c.generation_expression AS generated_expression
+  EXISTS (
+    SELECT 1 FROM pg_attribute a
+    WHERE a.attrelid = (c.table_schema || '.' || c.table_name)::regclass
+      AND a.attname = c.column_name
+      AND a.attgenerated = 's'
+  ) AS is_stored,
+  c.generation_expression AS generated_expression,
+  CASE WHEN t.typtype = 'e' THEN (
+    SELECT 'enum(' || string_agg(quote_literal(e.enumlabel), ',' ORDER BY e.enumsortorder) || ')'
+    FROM pg_enum e
+    WHERE e.enumtypid = t.oid
+  ) END AS full_column_type
 FROM information_schema.columns AS c
 LEFT JOIN pg_type t ON t.typname = c.udt_name
 WHERE c.table_schema = '{schema}' AND c.table_name = '{table}'
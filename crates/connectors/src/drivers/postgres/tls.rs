@@ -1,9 +1,12 @@
 use crate::error::DriverError;
-use native_tls::{Certificate, TlsConnector};
+use native_tls::{Certificate, Identity, TlsConnector};
 use postgres_native_tls::MakeTlsConnector;
 use tokio_postgres::{Client, Config, NoTls};
 use tracing::{error, warn};
 
+/// `application_name` of sessions whose URL does not set one
+const APPLICATION_NAME: &str = "stratum";
+
 /// Certificate-verification policy derived from the URL's `sslmode`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SslPolicy {
@@ -44,16 +47,27 @@ impl SslPolicy {
     }
 }
 
+/// Certificate files named by the URL's `sslrootcert`, `sslcert` and
+/// `sslkey` parameters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct SslFiles {
+    root_cert: Option<String>,
+    /// PEM client certificate, presented with `key`
+    cert: Option<String>,
+    /// PEM PKCS#8 private key of `cert`
+    key: Option<String>,
+}
+
 /// Connect to PostgreSQL.
 pub(crate) async fn connect(url: &str) -> Result<Client, DriverError> {
-    let (config, policy, ca_path) = build_config(url)?;
+    let (config, policy, files) = build_config(url)?;
 
     match policy {
         SslPolicy::Disable => connect_no_tls(&config).await,
         SslPolicy::Prefer => {
             // Opportunistic: attempt TLS without verification, fall back to
             // plaintext if the handshake fails or the server has no SSL.
-            let connector = build_connector(true, true, ca_path.as_deref())?;
+            let connector = build_connector(true, true, &files)?;
             match connect_tls(&config, connector).await {
                 Ok(client) => Ok(client),
                 Err(error) => {
@@ -64,40 +78,45 @@ pub(crate) async fn connect(url: &str) -> Result<Client, DriverError> {
         }
         SslPolicy::Require => {
             // libpq `require`: encrypt, but do not authenticate the server.
-            let connector = build_connector(true, true, ca_path.as_deref())?;
+            let connector = build_connector(true, true, &files)?;
             connect_tls(&config, connector).await
         }
         SslPolicy::VerifyCa => {
             // Verify the certificate chain, but not the hostname.
-            let connector = build_connector(false, true, ca_path.as_deref())?;
+            let connector = build_connector(false, true, &files)?;
             connect_tls(&config, connector).await
         }
         SslPolicy::VerifyFull => {
             // Verify both the certificate chain and the hostname.
-            let connector = build_connector(false, false, ca_path.as_deref())?;
+            let connector = build_connector(false, false, &files)?;
             connect_tls(&config, connector).await
         }
     }
 }
 
 /// Parse the URL, splitting off the libpq-specific TLS parameters
-/// (`sslmode`, `sslrootcert`) that `tokio_postgres::Config` cannot handle, and
-/// return a `Config` with a normalized `sslmode`.
-fn build_config(url: &str) -> Result<(Config, SslPolicy, Option<String>), DriverError> {
-    let (normalized, policy, ca_path) = normalize_url(url)?;
-    let config: Config = normalized
+/// (`sslmode`, `sslrootcert`, `sslcert`, `sslkey`) that
+/// `tokio_postgres::Config` cannot handle, and return a `Config` with a
+/// normalized `sslmode`.
+fn build_config(url: &str) -> Result<(Config, SslPolicy, SslFiles), DriverError> {
+    let (normalized, policy, files) = normalize_url(url)?;
+    let mut config: Config = normalized
         .parse()
         .map_err(|e: tokio_postgres::Error| DriverError::InvalidUrl(e.to_string()))?;
-    Ok((config, policy, ca_path))
+    // Lets DBAs pick out stratum's sessions in pg_stat_activity
+    if config.get_application_name().is_none() {
+        config.application_name(APPLICATION_NAME);
+    }
+    Ok((config, policy, files))
 }
 
-/// Pure URL surgery: extract the `sslmode`/`sslrootcert` parameters, and return
-/// a URL whose `sslmode` is one `tokio_postgres::Config` accepts.
-fn normalize_url(url: &str) -> Result<(String, SslPolicy, Option<String>), DriverError> {
+/// Pure URL surgery: extract the `sslmode` and certificate file parameters,
+/// and return a URL whose `sslmode` is one `tokio_postgres::Config` accepts.
+fn normalize_url(url: &str) -> Result<(String, SslPolicy, SslFiles), DriverError> {
     let mut parsed = url::Url::parse(url).map_err(|e| DriverError::InvalidUrl(e.to_string()))?;
 
     let mut sslmode: Option<String> = None;
-    let mut sslrootcert: Option<String> = None;
+    let mut files = SslFiles::default();
     let kept: Vec<(String, String)> = parsed
         .query_pairs()
         .filter_map(|(k, v)| match k.as_ref() {
@@ -106,7 +125,15 @@ fn normalize_url(url: &str) -> Result<(String, SslPolicy, Option<String>), Drive
                 None
             }
             "sslrootcert" => {
-                sslrootcert = Some(v.into_owned());
+                files.root_cert = Some(v.into_owned());
+                None
+            }
+            "sslcert" => {
+                files.cert = Some(v.into_owned());
+                None
+            }
+            "sslkey" => {
+                files.key = Some(v.into_owned());
                 None
             }
             _ => Some((k.into_owned(), v.into_owned())),
@@ -130,34 +157,56 @@ fn normalize_url(url: &str) -> Result<(String, SslPolicy, Option<String>), Drive
         pairs.append_pair("sslmode", policy.tokio_sslmode());
     }
 
-    Ok((parsed.to_string(), policy, sslrootcert))
+    Ok((parsed.to_string(), policy, files))
 }
 
 fn build_connector(
     accept_invalid_certs: bool,
     accept_invalid_hostnames: bool,
-    ca_path: Option<&str>,
+    files: &SslFiles,
 ) -> Result<MakeTlsConnector, DriverError> {
     let mut builder = TlsConnector::builder();
     builder.danger_accept_invalid_certs(accept_invalid_certs);
     builder.danger_accept_invalid_hostnames(accept_invalid_hostnames);
 
-    if let Some(path) = ca_path {
-        let pem = std::fs::read(path).map_err(|e| {
-            DriverError::ConnectionError(format!("failed to read sslrootcert '{path}': {e}"))
-        })?;
+    if let Some(path) = &files.root_cert {
+        let pem = read_file("sslrootcert", path)?;
         let cert = Certificate::from_pem(&pem).map_err(|e| {
             DriverError::ConnectionError(format!("invalid sslrootcert '{path}': {e}"))
         })?;
         builder.add_root_certificate(cert);
     }
 
+    match (&files.cert, &files.key) {
+        (Some(cert_path), Some(key_path)) => {
+            let cert = read_file("sslcert", cert_path)?;
+            let key = read_file("sslkey", key_path)?;
+            let identity = Identity::from_pkcs8(&cert, &key).map_err(|e| {
+                DriverError::ConnectionError(format!(
+                    "invalid client certificate '{cert_path}' or key '{key_path}': {e}"
+                ))
+            })?;
+            builder.identity(identity);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(DriverError::InvalidUrl(
+                "sslcert and sslkey must be given together".to_string(),
+            ));
+        }
+    }
+
     let connector = builder
         .build()
         .map_err(|e| DriverError::ConnectionError(e.to_string()))?;
     Ok(MakeTlsConnector::new(connector))
 }
 
+fn read_file(param: &str, path: &str) -> Result<Vec<u8>, DriverError> {
+    std::fs::read(path)
+        .map_err(|e| DriverError::ConnectionError(format!("failed to read {param} '{path}': {e}")))
+}
+
 async fn connect_tls(config: &Config, tls: MakeTlsConnector) -> Result<Client, DriverError> {
     let (client, connection) = config
         .connect(tls)
@@ -198,9 +247,9 @@ mod tests {
 
     #[test]
     fn defaults_to_prefer_when_sslmode_absent() {
-        let (out, policy, ca) = normalize_url("postgres://u:p@host:5432/db").unwrap();
+        let (out, policy, files) = normalize_url("postgres://u:p@host:5432/db").unwrap();
         assert_eq!(policy, SslPolicy::Prefer);
-        assert_eq!(ca, None);
+        assert_eq!(files, SslFiles::default());
         assert_eq!(
             query_params(&out).get("sslmode").map(String::as_str),
             Some("prefer")
@@ -231,11 +280,11 @@ mod tests {
 
     #[test]
     fn sslrootcert_is_extracted_and_stripped() {
-        let (out, policy, ca) =
+        let (out, policy, files) =
             normalize_url("postgres://u:p@host/db?sslmode=verify-ca&sslrootcert=/tmp/ca.pem")
                 .unwrap();
         assert_eq!(policy, SslPolicy::VerifyCa);
-        assert_eq!(ca.as_deref(), Some("/tmp/ca.pem"));
+        assert_eq!(files.root_cert.as_deref(), Some("/tmp/ca.pem"));
         let params = query_params(&out);
         assert!(
             !params.contains_key("sslrootcert"),
@@ -244,6 +293,28 @@ mod tests {
         assert_eq!(params.get("sslmode").map(String::as_str), Some("require"));
     }
 
+    #[test]
+    fn client_certificate_is_extracted_and_stripped() {
+        let (out, _, files) = normalize_url(
+            "postgres://u@host/db?sslmode=verify-full&sslcert=/tls/client.crt&sslkey=/tls/client.key",
+        )
+        .unwrap();
+        assert_eq!(files.cert.as_deref(), Some("/tls/client.crt"));
+        assert_eq!(files.key.as_deref(), Some("/tls/client.key"));
+        let params = query_params(&out);
+        assert!(!params.contains_key("sslcert") && !params.contains_key("sslkey"));
+    }
+
+    #[test]
+    fn client_certificate_needs_key() {
+        let files = SslFiles {
+            cert: Some("/tls/client.crt".to_string()),
+            ..SslFiles::default()
+        };
+        let err = build_connector(false, false, &files).err().unwrap();
+        assert!(matches!(err, DriverError::InvalidUrl(_)));
+    }
+
     #[test]
     fn other_query_params_are_preserved() {
         let (out, _, _) =
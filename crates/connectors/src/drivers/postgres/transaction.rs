@@ -1,6 +1,6 @@
 use crate::{
     drivers::postgres::{
-        driver::{PgDriver, set_search_path},
+        driver::{PgDriver, PgSession, set_search_path},
         tls,
     },
     error::DriverError,
@@ -19,9 +19,10 @@ pub struct PgTransaction {
 
 impl PgTransaction {
     /// Start a new transaction on a fresh connection.
-    pub async fn begin(url: &str, schema: &str) -> Result<Self, DriverError> {
+    pub async fn begin(url: &str, schema: &str, session: &PgSession) -> Result<Self, DriverError> {
         let client = tls::connect(url).await?;
         set_search_path(&client, schema).await?;
+        session.apply(&client).await?;
 
         // Start the transaction
         client
@@ -68,7 +69,7 @@ impl Transaction for PgTransaction {
 #[async_trait]
 impl Transactional for PgDriver {
     async fn begin(&self) -> Result<Box<dyn Transaction>, DriverError> {
-        let tx = PgTransaction::begin(self.url(), self.schema()).await?;
+        let tx = PgTransaction::begin(self.url(), self.schema(), self.session()).await?;
         Ok(Box::new(tx))
     }
 }
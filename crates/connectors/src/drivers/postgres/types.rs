@@ -258,6 +258,17 @@ impl IntoCanonical for PgTypeConverter {
                 warnings: vec![],
             },
 
+            // Enum - labels come from the introspected full type
+            _ if col.enum_values().is_some() => TypeMapping {
+                canonical: Type::Enum {
+                    name: col.data_type.clone(),
+                    values: col.enum_values().unwrap_or_default(),
+                },
+                fidelity: Fidelity::Lossless,
+                value_transform: None,
+                warnings: vec![],
+            },
+
             // Arrays - detect by prefix
             _ if pg_type.ends_with("[]") || pg_type.starts_with("_") => {
                 let element_type = pg_type.trim_end_matches("[]").trim_start_matches('_');
@@ -1,20 +1,25 @@
 use crate::{
+    audit,
     drivers::postgres::{
-        coercion, driver::PgDriver, encoder::PgCopyEncoder, params::PgParamStore,
+        driver::PgDriver, encoder::PgCopyEncoder, params::PgParamStore, queries,
         types::PgTypeConverter,
     },
     error::DriverError,
     sql::{
         metadata::{column::ColumnMetadata, table::TableMetadata},
-        query::generator::QueryGenerator,
+        query::{coercion::coerce_value, generator::QueryGenerator},
     },
     traits::{encoder::CopyValueEncoder, writer::DataWriter},
 };
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures_util::{SinkExt, pin_mut};
-use model::records::Record;
+use model::{
+    core::{convert::IntoCanonical, types::Type, value::Value},
+    records::Record,
+};
 use query_builder::dialect;
+use tokio_postgres::Client;
 use tracing::debug;
 
 #[async_trait]
@@ -32,14 +37,74 @@ impl DataWriter for PgDriver {
 
         let client = self.client().read().await;
         let param_store = PgParamStore::from_values(&params);
-        let result = client
-            .execute(&sql, &param_store.as_refs()[..])
-            .await
-            .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+        let result = audit::statement(
+            &sql,
+            || audit::hash_params(&params),
+            client.execute(&sql, &param_store.as_refs()[..]),
+        )
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
 
         Ok(result)
     }
 
+    async fn insert_missing(
+        &self,
+        meta: &TableMetadata,
+        rows: &[Record],
+    ) -> Result<u64, DriverError> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let generator = QueryGenerator::new(&dialect::Postgres);
+        let (sql, params) = generator.insert_missing(meta, rows, &PgTypeConverter);
+
+        let client = self.client().read().await;
+        let param_store = PgParamStore::from_values(&params);
+        audit::statement(
+            &sql,
+            || audit::hash_params(&params),
+            client.execute(&sql, &param_store.as_refs()[..]),
+        )
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))
+    }
+
+    async fn write_chunk(
+        &self,
+        table: &str,
+        key: &[(String, Value)],
+        column: &str,
+        chunk: &[u8],
+        append: bool,
+    ) -> Result<u64, DriverError> {
+        let template = if append {
+            queries::APPEND_CHUNK
+        } else {
+            queries::WRITE_CHUNK
+        };
+        let sql = template
+            .replace("{table}", &queries::escape_identifier(table))
+            .replace("{column}", &queries::escape_identifier(column))
+            .replace(
+                "{key}",
+                &queries::key_predicate(key.iter().map(|(c, _)| c.as_str()), 2),
+            );
+        let mut params = vec![Value::Binary(chunk.to_vec())];
+        params.extend(key.iter().map(|(_, v)| v.clone()));
+
+        let client = self.client().read().await;
+        let param_store = PgParamStore::from_values(&params);
+        audit::statement(
+            &sql,
+            || audit::hash_params(&params),
+            client.execute(&sql, &param_store.as_refs()[..]),
+        )
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))
+    }
+
     /// Write rows using PostgreSQL COPY protocol for maximum throughput.
     /// Transaction handling should be done by the caller (e.g., Sink).
     async fn copy_rows(
@@ -52,8 +117,6 @@ impl DataWriter for PgDriver {
             return Ok(0);
         }
 
-        let encoder = PgCopyEncoder;
-
         // Get non-generated columns sorted by ordinal position.
         // Generated columns are computed by the DB and must be excluded from both
         // the COPY header and the CSV data to avoid a column-count mismatch.
@@ -71,43 +134,65 @@ impl DataWriter for PgDriver {
         debug!(rows = rows.len(), table = %table, "COPY rows into table");
 
         let client = self.client().write().await;
+        audit::statement(
+            &statement,
+            || audit::hash_rows(rows),
+            copy_in(&client, &statement, &columns, rows),
+        )
+        .await?;
 
-        let sink = client
-            .copy_in(&statement)
-            .await
-            .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
-        pin_mut!(sink);
-
-        // Write rows as CSV in column order
-        for row in rows {
-            let mut line = String::new();
-            for (i, col) in columns.iter().enumerate() {
-                if i > 0 {
-                    line.push(',');
-                }
-                let field = row.get(&col.name);
-                let encoded = match field.and_then(|f| f.value.clone()) {
-                    Some(value) => {
-                        // Coerce value to match target column type
-                        let coerced = coercion::coerce_value(value, col);
-                        encoder.encode_value(&coerced)
-                    }
-                    None => encoder.encode_null(),
-                };
-                line.push_str(&encoded);
+        Ok(rows.len() as u64)
+    }
+}
+
+/// Stream `rows` as CSV through a `COPY ... FROM STDIN` statement.
+async fn copy_in(
+    client: &Client,
+    statement: &str,
+    columns: &[ColumnMetadata],
+    rows: &[Record],
+) -> Result<(), DriverError> {
+    let encoder = PgCopyEncoder;
+    let sink = client
+        .copy_in(statement)
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+    pin_mut!(sink);
+
+    let types: Vec<Type> = columns
+        .iter()
+        .map(|col| PgTypeConverter.to_canonical(col).canonical)
+        .collect();
+
+    // Write rows as CSV in column order
+    for row in rows {
+        let mut line = String::new();
+        for (i, (col, data_type)) in columns.iter().zip(&types).enumerate() {
+            if i > 0 {
+                line.push(',');
             }
-            line.push('\n');
-            sink.as_mut()
-                .send(Bytes::from(line))
-                .await
-                .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+            let field = row.get(&col.name);
+            let encoded = match field.and_then(|f| f.value.clone()) {
+                Some(value) => {
+                    // Coerce value to match target column type
+                    let coerced = coerce_value(value, data_type);
+                    encoder.encode_value(&coerced)
+                }
+                None => encoder.encode_null(),
+            };
+            line.push_str(&encoded);
         }
-
+        line.push('\n');
         sink.as_mut()
-            .close()
+            .send(Bytes::from(line))
             .await
             .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
-
-        Ok(rows.len() as u64)
     }
+
+    sink.as_mut()
+        .close()
+        .await
+        .map_err(|e| DriverError::QueryError(format!("{:?}", e)))?;
+
+    Ok(())
 }
@@ -0,0 +1,263 @@
+//! A connection's `tls {}` block. The drivers take their TLS settings from
+//! URL parameters, so the block is written into the connection's URL as the
+//! parameters of its driver when the plan is built.
+
+use model::core::value::Value;
+use std::collections::HashMap;
+
+const ATTR_MODE: &str = "mode";
+const ATTR_CA_CERT: &str = "ca_cert";
+const ATTR_CLIENT_CERT: &str = "client_cert";
+const ATTR_CLIENT_KEY: &str = "client_key";
+
+/// How the server's certificate is checked, named as libpq's `sslmode`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TlsMode {
+    /// No TLS
+    Disable,
+    /// TLS when the server offers it, plaintext otherwise (postgres only)
+    Prefer,
+    /// TLS without verifying the certificate
+    Require,
+    /// TLS, verifying the certificate chain but not the host name
+    VerifyCa,
+    /// TLS, verifying the certificate chain and the host name
+    #[default]
+    VerifyFull,
+}
+
+/// A connection's `tls {}` block
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub mode: TlsMode,
+    /// PEM file of the certificate authority the server's certificate is
+    /// checked against, instead of the system roots
+    pub ca_cert: Option<String>,
+    /// Client certificate: a PEM file on postgres, a PKCS#12 archive holding
+    /// the certificate and its key on mysql
+    pub client_cert: Option<String>,
+    /// PEM PKCS#8 key of `client_cert` (postgres only)
+    pub client_key: Option<String>,
+}
+
+impl TlsConfig {
+    /// Reads a connection's `tls {}` block for `driver`.
+    pub fn from_block(driver: &str, block: &HashMap<String, Value>) -> Result<Self, String> {
+        let mysql = match driver.to_lowercase().as_str() {
+            "postgres" | "postgresql" => false,
+            "mysql" => true,
+            _ => return Err("a tls block is only supported on postgres and mysql".to_string()),
+        };
+
+        let mut tls = TlsConfig::default();
+        for (key, value) in block {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                _ => return Err(format!("tls {key} must be a string")),
+            };
+            match key.as_str() {
+                ATTR_MODE => {
+                    tls.mode = match text.as_str() {
+                        "disable" => TlsMode::Disable,
+                        "prefer" => TlsMode::Prefer,
+                        "require" => TlsMode::Require,
+                        "verify-ca" => TlsMode::VerifyCa,
+                        "verify-full" => TlsMode::VerifyFull,
+                        other => {
+                            return Err(format!(
+                                "tls mode '{other}' must be 'disable', 'prefer', 'require', 'verify-ca' or 'verify-full'"
+                            ));
+                        }
+                    };
+                }
+                ATTR_CA_CERT => tls.ca_cert = Some(text),
+                ATTR_CLIENT_CERT => tls.client_cert = Some(text),
+                ATTR_CLIENT_KEY => tls.client_key = Some(text),
+                other => {
+                    return Err(format!(
+                        "unknown tls attribute '{other}'. Must be 'mode', 'ca_cert', 'client_cert' or 'client_key'"
+                    ));
+                }
+            }
+        }
+
+        let has_files =
+            tls.ca_cert.is_some() || tls.client_cert.is_some() || tls.client_key.is_some();
+        if tls.mode == TlsMode::Disable && has_files {
+            return Err("tls mode 'disable' cannot be given certificates".to_string());
+        }
+        if mysql {
+            if tls.mode == TlsMode::Prefer {
+                return Err(
+                    "tls mode 'prefer' is not supported on mysql; use 'require' or stricter"
+                        .to_string(),
+                );
+            }
+            if tls.client_key.is_some() {
+                return Err(
+                    "mysql takes client_cert as a PKCS#12 archive holding the key; client_key is not supported"
+                        .to_string(),
+                );
+            }
+        } else if tls.client_cert.is_some() != tls.client_key.is_some() {
+            return Err("tls client_cert and client_key must be given together".to_string());
+        }
+        Ok(tls)
+    }
+
+    /// `url` with this block written as `driver`'s TLS parameters, replacing
+    /// any the URL already had.
+    pub fn apply_to_url(&self, driver: &str, url: &str) -> Result<String, String> {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        let replaced: &[&str] = if driver.eq_ignore_ascii_case("mysql") {
+            let (require, verify_ca, verify_identity) = match self.mode {
+                TlsMode::Disable => (false, false, false),
+                TlsMode::Prefer | TlsMode::Require => (true, false, false),
+                TlsMode::VerifyCa => (true, true, false),
+                TlsMode::VerifyFull => (true, true, true),
+            };
+            params.push(("require_ssl", require.to_string()));
+            if require {
+                params.push(("verify_ca", verify_ca.to_string()));
+                params.push(("verify_identity", verify_identity.to_string()));
+            }
+            params.extend(self.ca_cert.clone().map(|p| ("ssl_ca", p)));
+            params.extend(self.client_cert.clone().map(|p| ("ssl_identity", p)));
+            &[
+                "require_ssl",
+                "verify_ca",
+                "verify_identity",
+                "ssl_ca",
+                "ssl_identity",
+            ]
+        } else {
+            let mode = match self.mode {
+                TlsMode::Disable => "disable",
+                TlsMode::Prefer => "prefer",
+                TlsMode::Require => "require",
+                TlsMode::VerifyCa => "verify-ca",
+                TlsMode::VerifyFull => "verify-full",
+            };
+            params.push(("sslmode", mode.to_string()));
+            params.extend(self.ca_cert.clone().map(|p| ("sslrootcert", p)));
+            params.extend(self.client_cert.clone().map(|p| ("sslcert", p)));
+            params.extend(self.client_key.clone().map(|p| ("sslkey", p)));
+            &["sslmode", "sslrootcert", "sslcert", "sslkey"]
+        };
+
+        let mut parsed = url::Url::parse(url).map_err(|e| format!("invalid url: {e}"))?;
+        let kept: Vec<(String, String)> = parsed
+            .query_pairs()
+            .filter(|(k, _)| !replaced.contains(&k.as_ref()))
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        {
+            let mut pairs = parsed.query_pairs_mut();
+            pairs.clear();
+            for (k, v) in &kept {
+                pairs.append_pair(k, v);
+            }
+            for (k, v) in &params {
+                pairs.append_pair(k, v);
+            }
+        }
+        Ok(parsed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(pairs: &[(&str, &str)]) -> HashMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+            .collect()
+    }
+
+    fn query_params(url: &str) -> HashMap<String, String> {
+        url::Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn postgres_block_becomes_libpq_params() {
+        let tls = TlsConfig::from_block(
+            "postgres",
+            &block(&[
+                ("ca_cert", "/tls/ca.pem"),
+                ("client_cert", "/tls/client.crt"),
+                ("client_key", "/tls/client.key"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(tls.mode, TlsMode::VerifyFull);
+
+        let url = tls
+            .apply_to_url(
+                "postgres",
+                "postgres://etl@pg/dw?sslmode=disable&application_name=x",
+            )
+            .unwrap();
+        let params = query_params(&url);
+        assert_eq!(params["sslmode"], "verify-full");
+        assert_eq!(params["sslrootcert"], "/tls/ca.pem");
+        assert_eq!(params["sslcert"], "/tls/client.crt");
+        assert_eq!(params["sslkey"], "/tls/client.key");
+        assert_eq!(params["application_name"], "x");
+    }
+
+    #[test]
+    fn mysql_block_becomes_mysql_async_params() {
+        let tls = TlsConfig::from_block(
+            "mysql",
+            &block(&[("mode", "verify-ca"), ("client_cert", "/tls/client.p12")]),
+        )
+        .unwrap();
+        let params = query_params(&tls.apply_to_url("mysql", "mysql://app@db/shop").unwrap());
+        assert_eq!(params["require_ssl"], "true");
+        assert_eq!(params["verify_ca"], "true");
+        assert_eq!(params["verify_identity"], "false");
+        assert_eq!(params["ssl_identity"], "/tls/client.p12");
+        assert!(!params.contains_key("ssl_ca"));
+    }
+
+    #[test]
+    fn invalid_blocks_are_rejected() {
+        for (driver, pairs, expected) in [
+            ("csv", vec![], "only supported on postgres and mysql"),
+            ("postgres", vec![("mode", "strict")], "tls mode 'strict'"),
+            (
+                "postgres",
+                vec![("sni", "db")],
+                "unknown tls attribute 'sni'",
+            ),
+            (
+                "postgres",
+                vec![("client_cert", "/tls/client.crt")],
+                "must be given together",
+            ),
+            (
+                "postgres",
+                vec![("mode", "disable"), ("ca_cert", "/tls/ca.pem")],
+                "cannot be given certificates",
+            ),
+            ("mysql", vec![("mode", "prefer")], "not supported on mysql"),
+            (
+                "mysql",
+                vec![
+                    ("client_cert", "/tls/client.crt"),
+                    ("client_key", "/tls/client.key"),
+                ],
+                "PKCS#12",
+            ),
+        ] {
+            let err = TlsConfig::from_block(driver, &block(&pairs)).unwrap_err();
+            assert!(err.contains(expected), "{pairs:?}: '{err}'");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod drivers;
 pub mod error;
 pub mod registry;
@@ -1,6 +1,7 @@
 use crate::{
-    drivers::{mysql::driver::MySqlDriver, postgres::driver::PgDriver},
+    drivers::{csv::adapter::CsvAdapter, mysql::driver::MySqlDriver, postgres::driver::PgDriver},
     error::DriverError,
+    sql::metadata::capabilities::Capabilities,
     traits::driver::{Driver, DriverInfo},
 };
 use futures_util::future::BoxFuture;
@@ -119,6 +120,19 @@ impl DriverRegistry {
     }
 }
 
+/// Capabilities of a built-in driver, known without connecting, for checks at
+/// plan time. The version is empty and version-dependent features take their
+/// value for the oldest supported server. `None` for drivers this crate does
+/// not provide, e.g. WASM plugins.
+pub fn driver_capabilities(driver: &str) -> Option<Capabilities> {
+    match driver.to_lowercase().as_str() {
+        "mysql" | "mariadb" => Some(MySqlDriver::resolve_capabilities(String::new())),
+        "postgres" | "postgresql" => Some(PgDriver::resolve_capabilities(String::new())),
+        "csv" => Some(CsvAdapter::capabilities()),
+        _ => None,
+    }
+}
+
 fn extract_scheme(url: &str) -> Result<String, DriverError> {
     url.split("://")
         .next()
@@ -169,6 +169,7 @@ mod tests {
         TableMetadata {
             name: name.to_string(),
             schema: None,
+            collation: None,
             columns: Default::default(),
             primary_keys: vec![],
             foreign_keys: fks,
@@ -1,3 +1,6 @@
+/// Pagination strategies every SQL reader implements
+pub const SQL_PAGINATION: &[&str] = &["default", "pk", "numeric", "timestamp", "server_cursor"];
+
 #[derive(Debug, Clone, Default)]
 pub struct Capabilities {
     /// Database version string
@@ -9,13 +12,33 @@ pub struct Capabilities {
     pub copy_protocol: bool,    // COPY (PostgreSQL) or LOAD DATA (MySQL)
     pub upsert: bool,           // INSERT ... ON CONFLICT / ON DUPLICATE KEY
     pub returning_clause: bool, // RETURNING (PostgreSQL)
+    pub ddl: bool,              // CREATE TABLE / ALTER TABLE on the destination
+    pub cdc: bool,              // Change data capture as a source
     pub json_type: bool,
     pub jsonb_type: bool, // PostgreSQL only
     pub array_type: bool, // PostgreSQL only
     pub uuid_type: bool,
-    pub geometry_type: bool, // PostGIS / MySQL spatial
+    pub geometry_type: bool,      // PostGIS / MySQL spatial
+    pub materialized_views: bool, // REFRESH MATERIALIZED VIEW (PostgreSQL)
+
+    /// Pagination strategies the reader implements (`paginate { strategy = ... }`)
+    pub pagination: &'static [&'static str],
 
     /// Limits
     pub max_parameters: Option<usize>,
     pub max_query_size: Option<usize>,
 }
+
+impl Capabilities {
+    /// Whether the reader implements a `paginate` strategy; `offset` is an
+    /// alias of `default`.
+    pub fn supports_pagination(&self, strategy: &str) -> bool {
+        let strategy = strategy.to_lowercase();
+        let strategy = if strategy == "offset" {
+            "default"
+        } else {
+            strategy.as_str()
+        };
+        self.pagination.contains(&strategy)
+    }
+}
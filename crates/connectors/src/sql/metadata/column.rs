@@ -42,7 +42,7 @@ pub struct ColumnMetadata {
     pub generated_expression: Option<String>,
 
     /// Full column type string (e.g. "enum('G','PG','PG-13','R','NC-17')").
-    /// Populated by MySQL introspection; None for PostgreSQL.
+    /// Populated by MySQL introspection, and for enum columns by PostgreSQL's.
     pub full_column_type: Option<String>,
 }
 
@@ -72,4 +72,170 @@ impl ColumnMetadata {
             full_column_type: row.get_string(COL_FULL_COLUMN_TYPE),
         }
     }
+
+    /// Labels of an enum column, in declaration order, parsed from its full
+    /// type (`enum('G','PG','PG-13')`). `None` for any other column.
+    pub fn enum_values(&self) -> Option<Vec<String>> {
+        let raw = match &self.full_column_type {
+            Some(full) => full.as_str(),
+            None if self.data_type.contains('(') => self.data_type.as_str(),
+            None => return None,
+        };
+        let raw = raw.trim();
+        let list = raw
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("enum("))
+            .and_then(|_| raw[5..].strip_suffix(')'))?;
+
+        Some(parse_quoted_list(list))
+    }
+
+    /// Whether the column holds character data (CHAR, VARCHAR or any TEXT
+    /// type, as MySQL and PostgreSQL name them).
+    pub fn is_text(&self) -> bool {
+        let data_type = self.data_type.to_ascii_lowercase();
+        matches!(data_type.as_str(), "character" | "character varying")
+            || data_type.ends_with("char")
+            || data_type.ends_with("text")
+    }
+
+    /// Whether the column holds raw bytes (PostgreSQL BYTEA, MySQL BINARY,
+    /// VARBINARY or any BLOB type).
+    pub fn is_binary(&self) -> bool {
+        let data_type = self.data_type.to_ascii_lowercase();
+        data_type == "bytea" || data_type.ends_with("binary") || data_type.ends_with("blob")
+    }
+
+    /// Whether the column holds whole numbers (any INT type, as MySQL and
+    /// PostgreSQL name them).
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self.data_type.to_ascii_lowercase().as_str(),
+            "tinyint"
+                | "smallint"
+                | "mediumint"
+                | "int"
+                | "integer"
+                | "bigint"
+                | "int2"
+                | "int4"
+                | "int8"
+                | "smallserial"
+                | "serial"
+                | "bigserial"
+        )
+    }
+
+    /// Whether the column holds an array (a PostgreSQL `type[]` column).
+    pub fn is_array(&self) -> bool {
+        let data_type = self.data_type.to_ascii_lowercase();
+        data_type.ends_with("[]") || data_type.starts_with('_') || data_type == "array"
+    }
+}
+
+/// Parse `'a','b''c'` into its unquoted values. Quotes are escaped by doubling
+/// them or with a backslash, as MySQL and PostgreSQL print them.
+fn parse_quoted_list(list: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut chars = list.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                    value.push('\'');
+                }
+                '\'' => break,
+                '\\' => value.extend(chars.next()),
+                c => value.push(c),
+            }
+        }
+        values.push(value);
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(data_type: &str, full_column_type: Option<&str>) -> ColumnMetadata {
+        ColumnMetadata {
+            name: "rating".to_string(),
+            data_type: data_type.to_string(),
+            full_column_type: full_column_type.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let col = column("enum", Some("enum('G','PG','PG-13','R','NC-17')"));
+        assert_eq!(
+            col.enum_values().unwrap(),
+            vec!["G", "PG", "PG-13", "R", "NC-17"]
+        );
+
+        let col = column("mood", Some("enum('it''s','a,b',E'back\\\\slash')"));
+        assert_eq!(
+            col.enum_values().unwrap(),
+            vec!["it's", "a,b", "back\\slash"]
+        );
+
+        assert_eq!(column("ENUM('x')", None).enum_values().unwrap(), vec!["x"]);
+        assert_eq!(column("varchar", None).enum_values(), None);
+        assert_eq!(column("set", Some("set('a','b')")).enum_values(), None);
+    }
+
+    #[test]
+    fn test_is_text() {
+        for data_type in [
+            "varchar",
+            "character varying",
+            "bpchar",
+            "LONGTEXT",
+            "citext",
+        ] {
+            assert!(column(data_type, None).is_text(), "{data_type}");
+        }
+        for data_type in ["bytea", "blob", "varbinary", "json", "enum"] {
+            assert!(!column(data_type, None).is_text(), "{data_type}");
+        }
+    }
+
+    #[test]
+    fn test_is_binary() {
+        for data_type in ["bytea", "BLOB", "longblob", "varbinary", "binary"] {
+            assert!(column(data_type, None).is_binary(), "{data_type}");
+        }
+        for data_type in ["text", "varchar", "json", "bit"] {
+            assert!(!column(data_type, None).is_binary(), "{data_type}");
+        }
+    }
+
+    #[test]
+    fn test_is_integer() {
+        for data_type in ["integer", "BIGINT", "tinyint", "int8", "mediumint"] {
+            assert!(column(data_type, None).is_integer(), "{data_type}");
+        }
+        for data_type in ["numeric", "point", "uuid", "integer[]", "varchar"] {
+            assert!(!column(data_type, None).is_integer(), "{data_type}");
+        }
+    }
+
+    #[test]
+    fn test_is_array() {
+        for data_type in ["integer[]", "character varying[]", "_text", "ARRAY"] {
+            assert!(column(data_type, None).is_array(), "{data_type}");
+        }
+        for data_type in ["text", "json", "set"] {
+            assert!(!column(data_type, None).is_array(), "{data_type}");
+        }
+    }
 }
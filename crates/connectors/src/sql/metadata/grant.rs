@@ -0,0 +1,31 @@
+use crate::traits::row_decoder::RowDecoder;
+use serde::Serialize;
+
+/// A privilege on a table granted to a role other than its owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableGrant {
+    /// Role the privilege is granted to; `PUBLIC` for every role
+    pub grantee: String,
+    /// `SELECT`, `INSERT`, `UPDATE`, ...
+    pub privilege: String,
+    /// Whether the grantee may grant the privilege on
+    pub grantable: bool,
+}
+
+const GRANTEE_COL: &str = "grantee";
+const PRIVILEGE_TYPE_COL: &str = "privilege_type";
+const IS_GRANTABLE_COL: &str = "is_grantable";
+
+impl TableGrant {
+    pub fn from_row<R: RowDecoder>(row: &R) -> Self {
+        Self {
+            grantee: row.get_string(GRANTEE_COL).unwrap_or_default(),
+            privilege: row.get_string(PRIVILEGE_TYPE_COL).unwrap_or_default(),
+            grantable: row.get_bool(IS_GRANTABLE_COL).unwrap_or(false),
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.grantee.eq_ignore_ascii_case("PUBLIC")
+    }
+}
@@ -2,6 +2,8 @@ pub mod capabilities;
 pub mod column;
 pub mod constraint;
 pub mod fk;
+pub mod grant;
 pub mod index;
 pub mod provider;
+pub mod sequence;
 pub mod table;
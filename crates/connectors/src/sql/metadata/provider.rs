@@ -43,6 +43,7 @@ impl MetadataProvider {
         Ok(TableMetadata {
             name: table.to_string(),
             schema: None,
+            collation: None,
             columns,
             primary_keys,
             foreign_keys: fks,
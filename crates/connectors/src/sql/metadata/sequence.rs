@@ -0,0 +1,38 @@
+use crate::traits::row_decoder::RowDecoder;
+use serde::Serialize;
+
+/// A sequence that no column owns, as opposed to those behind serial and
+/// identity columns, which are created along with their tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SequenceMetadata {
+    pub name: String,
+    pub start: i64,
+    pub increment: i64,
+    pub min: i64,
+    pub max: i64,
+    pub cycle: bool,
+    /// Last value handed out; `None` before the first `nextval`
+    pub last_value: Option<i64>,
+}
+
+const SEQUENCE_NAME_COL: &str = "sequence_name";
+const START_VALUE_COL: &str = "start_value";
+const INCREMENT_BY_COL: &str = "increment_by";
+const MIN_VALUE_COL: &str = "min_value";
+const MAX_VALUE_COL: &str = "max_value";
+const CYCLE_COL: &str = "cycle";
+const LAST_VALUE_COL: &str = "last_value";
+
+impl SequenceMetadata {
+    pub fn from_row<R: RowDecoder>(row: &R) -> Self {
+        Self {
+            name: row.get_string(SEQUENCE_NAME_COL).unwrap_or_default(),
+            start: row.get_i64(START_VALUE_COL).unwrap_or(1),
+            increment: row.get_i64(INCREMENT_BY_COL).unwrap_or(1),
+            min: row.get_i64(MIN_VALUE_COL).unwrap_or(1),
+            max: row.get_i64(MAX_VALUE_COL).unwrap_or(i64::MAX),
+            cycle: row.get_bool(CYCLE_COL).unwrap_or(false),
+            last_value: row.get_i64(LAST_VALUE_COL),
+        }
+    }
+}
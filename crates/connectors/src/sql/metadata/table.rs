@@ -8,6 +8,9 @@ use std::collections::{HashMap, HashSet};
 pub struct TableMetadata {
     pub name: String,
     pub schema: Option<String>,
+    /// Default collation of the table's text columns; `None` where the
+    /// database has no table-level collation (Postgres)
+    pub collation: Option<String>,
     pub columns: HashMap<String, ColumnMetadata>,
     pub primary_keys: Vec<String>,
     pub foreign_keys: Vec<ForeignKeyMetadata>,
@@ -58,7 +61,9 @@ impl TableMetadata {
         table
             .columns
             .iter()
-            .filter(|(_name, col)| col.data_type.eq_ignore_ascii_case("enum"))
+            .filter(|(_name, col)| {
+                col.data_type.eq_ignore_ascii_case("enum") || col.enum_values().is_some()
+            })
             .map(|(_name, col)| col.clone())
             .collect()
     }
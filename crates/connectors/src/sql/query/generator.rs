@@ -1,12 +1,18 @@
 use model::{
     core::{convert::IntoCanonical, types::Type, value::Value},
+    execution::{
+        partition::{PartitionDef, PartitionScheme, TablePartitioning},
+        pipeline::{AggregateFunction, Aggregation},
+    },
+    pagination::cursor::QualCol,
     records::Record,
 };
 use query_builder::{
     ast::{
-        common::TypeName,
+        common::{OrderDir, TypeName},
         copy::{CopyDirection, CopyEndpoint},
         create_index::IndexColumnExpr,
+        create_table::{self, PartitionBy},
         expr::{BinaryOp, BinaryOperator, Expr, FunctionCall, Ident},
         insert::{ConflictAction, ConflictAssignment, Insert, OnConflict},
         merge::MergeAssignment,
@@ -28,11 +34,12 @@ use crate::{
     sql::{
         metadata::{column::ColumnMetadata, table::TableMetadata},
         query::{
-            coercion::coerce_value,
+            coercion::{array_literal, coerce_value},
             column::ColumnDef,
             constraint::{CheckConstraintDef, UniqueConstraintDef},
             fk::ForeignKeyDef,
             index::IndexDef,
+            select::SelectField,
             sequence::SequenceDef,
         },
         request::FetchRowsRequest,
@@ -54,11 +61,20 @@ impl<'a> QueryGenerator<'a> {
         let alias = request.alias.as_deref().unwrap_or(&request.table);
         let table = table_ref!(&request.table);
 
-        let columns = request
-            .columns
-            .iter()
-            .map(|c| ident!(c))
-            .collect::<Vec<_>>();
+        let columns = match &request.aggregate {
+            Some(aggregation) => Self::aggregate_columns(aggregation),
+            None => request
+                .columns
+                .iter()
+                .map(|c| {
+                    if request.is_lob_column(c) {
+                        Self::lob_column(c, request.lob_threshold)
+                    } else {
+                        ident!(c)
+                    }
+                })
+                .collect::<Vec<_>>(),
+        };
 
         // Start building the query
         let mut select = SelectBuilder::new()
@@ -88,6 +104,19 @@ impl<'a> QueryGenerator<'a> {
             };
         }
 
+        // Groups are ordered by their keys so offset pages stay stable
+        if let Some(aggregation) = &request.aggregate {
+            let keys = aggregation
+                .group_by
+                .iter()
+                .map(Self::qualified_column)
+                .collect::<Vec<_>>();
+            for key in &keys {
+                select = select.order_by(key.clone(), Some(OrderDir::Asc));
+            }
+            select = select.group_by(keys);
+        }
+
         // Apply random ordering if requested
         if request.order_random {
             select = select.order_by_random();
@@ -107,7 +136,75 @@ impl<'a> QueryGenerator<'a> {
                 .build()
         };
 
-        self.render_ast(select_ast)
+        let (sql, params) = self.render_ast(select_ast);
+        match &request.tag {
+            // A tag cannot close the comment early
+            Some(tag) => (format!("/* {} */ {sql}", tag.replace("*/", "* /")), params),
+            None => (sql, params),
+        }
+    }
+
+    /// Grouping columns under their own names, then `FN(col) AS name` for
+    /// each aggregate.
+    fn aggregate_columns(aggregation: &Aggregation) -> Vec<Expr> {
+        let keys = aggregation.group_by.iter().map(|key| Expr::Alias {
+            expr: Box::new(Self::qualified_column(key)),
+            alias: key.column.clone(),
+        });
+        let aggregates = aggregation.aggregates.iter().map(|aggregate| {
+            let call = match &aggregate.column {
+                Some(column) => FunctionCall {
+                    name: Self::aggregate_function(aggregate.function).to_string(),
+                    args: vec![Self::qualified_column(column)],
+                    wildcard: false,
+                },
+                None => FunctionCall::count_all(),
+            };
+            Expr::Alias {
+                expr: Box::new(Expr::FunctionCall(call)),
+                alias: aggregate.name.clone(),
+            }
+        });
+        keys.chain(aggregates).collect()
+    }
+
+    fn aggregate_function(function: AggregateFunction) -> &'static str {
+        match function {
+            AggregateFunction::Sum => "SUM",
+            AggregateFunction::Count => "COUNT",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::Max => "MAX",
+            AggregateFunction::Avg => "AVG",
+        }
+    }
+
+    fn qualified_column(column: &QualCol) -> Expr {
+        Expr::Identifier(Ident {
+            qualifier: Some(column.table.clone()),
+            name: column.column.clone(),
+        })
+    }
+
+    /// `CASE WHEN octet_length(col) > threshold THEN NULL ELSE col END`, so
+    /// values too large for a batch are left for the large-object pass.
+    fn lob_column(field: &SelectField, threshold: usize) -> Expr {
+        let column = Expr::Identifier(Ident {
+            qualifier: Some(field.table.clone()),
+            name: field.column.clone(),
+        });
+        let length = Expr::FunctionCall(FunctionCall {
+            name: "octet_length".to_string(),
+            args: vec![column.clone()],
+            wildcard: false,
+        });
+        let oversized = Expr::BinaryOp(Box::new(BinaryOp {
+            left: length,
+            op: BinaryOperator::Gt,
+            right: Expr::Value(Value::Int(threshold as i64)),
+        }));
+
+        Expr::case_when(oversized, Expr::Literal("NULL".to_string()), Some(column))
+            .alias(field.alias.as_deref().unwrap_or(&field.column))
     }
 
     pub fn insert_batch<T>(
@@ -123,6 +220,42 @@ impl<'a> QueryGenerator<'a> {
             return (String::new(), Vec::new());
         }
 
+        self.render_ast(self.insert_builder(meta, rows, type_converter).build())
+    }
+
+    /// Like [`insert_batch`](Self::insert_batch), but rows whose primary key
+    /// is already in the table are skipped instead of failing the statement.
+    pub fn insert_missing<T>(
+        &self,
+        meta: &TableMetadata,
+        rows: &[Record],
+        type_converter: &T,
+    ) -> (String, Vec<Value>)
+    where
+        T: IntoCanonical<ColumnMeta = ColumnMetadata>,
+    {
+        if rows.is_empty() {
+            return (String::new(), Vec::new());
+        }
+
+        let builder = self
+            .insert_builder(meta, rows, type_converter)
+            .on_conflict(OnConflict {
+                columns: meta.primary_keys.clone(),
+                action: ConflictAction::DoNothing,
+            });
+        self.render_ast(builder.build())
+    }
+
+    fn insert_builder<T>(
+        &self,
+        meta: &TableMetadata,
+        rows: &[Record],
+        type_converter: &T,
+    ) -> InsertBuilder
+    where
+        T: IntoCanonical<ColumnMeta = ColumnMetadata>,
+    {
         // Sorting ensures the column order is always consistent; exclude generated columns
         let mut sorted_columns: Vec<_> = meta
             .columns
@@ -156,14 +289,14 @@ impl<'a> QueryGenerator<'a> {
                         .cloned()
                         .unwrap_or(Value::Null);
                     let data_type = type_converter.to_canonical(col_meta).canonical;
-                    map_value_to_expr(value, col_meta, &data_type)
+                    map_value_to_expr(value, col_meta, &data_type, self.dialect)
                 })
                 .collect();
 
             builder = builder.values(ordered_values);
         }
 
-        self.render_ast(builder.build())
+        builder
     }
 
     pub fn copy_from_stdin(&self, table: &str, columns: &[ColumnMetadata]) -> String {
@@ -281,6 +414,25 @@ impl<'a> QueryGenerator<'a> {
         self.render_ast(query_ast)
     }
 
+    /// Change `column`'s type to its `data_type`, restating its nullability
+    /// and default where the dialect needs the whole column.
+    pub fn alter_column_type(&self, table: &str, column: &ColumnDef) -> (String, Vec<Value>) {
+        let query_ast = AlterTableBuilder::new(table_ref!(table))
+            .alter_column_type(create_table::ColumnDef {
+                name: column.name.clone(),
+                data_type: column.data_type.clone(),
+                is_nullable: column.is_nullable,
+                is_primary_key: false,
+                default_value: column.default.clone().map(Expr::Literal),
+                max_length: column.char_max_length,
+                generated_expression: None,
+                is_stored: false,
+            })
+            .build();
+
+        self.render_ast(query_ast)
+    }
+
     pub fn create_table(
         &self,
         table: &str,
@@ -299,6 +451,62 @@ impl<'a> QueryGenerator<'a> {
                 .collect()
         };
 
+        self.render_ast(
+            self.table_builder(table, columns, primary_keys, temp)
+                .build(),
+        )
+    }
+
+    /// CREATE TABLE for the parent of a partitioned table. The partition
+    /// column is added to the primary key, since a partitioned table can only
+    /// enforce keys that include it.
+    pub fn create_partitioned_table(
+        &self,
+        table: &str,
+        columns: &[ColumnDef],
+        ignore_constraints: bool,
+        partitioning: &TablePartitioning,
+    ) -> (String, Vec<Value>) {
+        let mut primary_keys: Vec<String> = if ignore_constraints {
+            vec![]
+        } else {
+            columns
+                .iter()
+                .filter(|c| c.is_primary_key)
+                .map(|c| c.name.clone())
+                .collect()
+        };
+        if !primary_keys.is_empty() && !primary_keys.contains(&partitioning.column) {
+            primary_keys.push(partitioning.column.clone());
+        }
+
+        let column = partitioning.column.clone();
+        let partition_by = match partitioning.scheme {
+            PartitionScheme::Range { .. } => PartitionBy::Range { column },
+            PartitionScheme::Hash { .. } => PartitionBy::Hash { column },
+        };
+
+        self.render_ast(
+            self.table_builder(table, columns, primary_keys, false)
+                .partition_by(partition_by)
+                .build(),
+        )
+    }
+
+    /// CREATE TABLE ... PARTITION OF for one child partition of `parent`.
+    pub fn create_partition(&self, parent: &str, partition: &PartitionDef) -> (String, Vec<Value>) {
+        let builder = CreateTableBuilder::new(table_ref!(&partition.name))
+            .partition_of(table_ref!(parent), partition.bound.clone());
+        self.render_ast(builder.build())
+    }
+
+    fn table_builder(
+        &self,
+        table: &str,
+        columns: &[ColumnDef],
+        primary_keys: Vec<String>,
+        temp: bool,
+    ) -> CreateTableBuilder {
         let initial_builder = if temp {
             CreateTableBuilder::new(table_ref!(table)).temporary()
         } else {
@@ -331,13 +539,11 @@ impl<'a> QueryGenerator<'a> {
         });
 
         // Add the composite primary key constraint at the table level if necessary
-        let final_builder = if primary_keys.len() > 1 {
+        if primary_keys.len() > 1 {
             builder_with_cols.primary_key(primary_keys)
         } else {
             builder_with_cols
-        };
-
-        self.render_ast(final_builder.build())
+        }
     }
 
     pub fn drop_table(&self, table: &str, if_exists: bool) -> (String, Vec<Value>) {
@@ -480,6 +686,30 @@ impl<'a> QueryGenerator<'a> {
             .build_key_existence_query(table_name, key_columns, keys_batch)
     }
 
+    /// Generates a query that counts the key values occurring more than once in
+    /// `table_name`. The count is returned as `duplicates`; zero means the key
+    /// columns uniquely identify every row.
+    ///
+    /// ```sql
+    /// SELECT COUNT(*) AS "duplicates" FROM (
+    ///   SELECT "k1", "k2" FROM "table" GROUP BY "k1", "k2" HAVING COUNT(*) > 1
+    /// ) AS "d"
+    /// ```
+    pub fn duplicate_keys(&self, table_name: &str, key_columns: &[String]) -> String {
+        let keys = key_columns
+            .iter()
+            .map(|col| self.dialect.quote_identifier(col))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "SELECT COUNT(*) AS {} FROM (SELECT {keys} FROM {} GROUP BY {keys} HAVING COUNT(*) > 1) AS {}",
+            self.dialect.quote_identifier("duplicates"),
+            self.dialect.quote_identifier(table_name),
+            self.dialect.quote_identifier("d"),
+        )
+    }
+
     /// Generates a validation estimation query that counts failures and total rows
     ///
     /// Returns a query that selects:
@@ -608,7 +838,12 @@ impl<'a> QueryGenerator<'a> {
 ///
 /// This function contains all the specific logic for handling different data types,
 /// like casting enums or parsing string representations of arrays.
-fn map_value_to_expr(value: Value, col_meta: &ColumnMetadata, data_type: &Type) -> Expr {
+fn map_value_to_expr(
+    value: Value,
+    col_meta: &ColumnMetadata,
+    data_type: &Type,
+    dialect: &dyn Dialect,
+) -> Expr {
     // If the value is NULL, generate a CAST to ensure the database knows the correct type.
     // This avoids the "expression is of type ..." error for bytea and other columns.
     if let Value::Null = value {
@@ -622,8 +857,32 @@ fn map_value_to_expr(value: Value, col_meta: &ColumnMetadata, data_type: &Type)
 
     // Handle type-specific expression wrapping
     match data_type {
-        // For set/array types, parse comma-separated string if needed
-        Type::Set { .. } | Type::Array { .. } => {
+        // Arrays are bound as their text literal and cast to the column type,
+        // so any element type goes through one text parameter
+        Type::Array { .. } => {
+            let items = match coerced_value {
+                Value::Array(items) => items,
+                Value::Set(items) => items.into_iter().map(Value::String).collect(),
+                Value::Json(serde_json::Value::Array(items)) => {
+                    items.iter().map(Value::from_json).collect()
+                }
+                Value::String(s) => s
+                    .split(',')
+                    .map(|item| Value::String(item.trim().to_string()))
+                    .collect(),
+                other => vec![other],
+            };
+            Expr::Cast {
+                data_type: col_meta.data_type.clone(),
+                expr: Box::new(Expr::Cast {
+                    expr: Box::new(Expr::Value(Value::String(array_literal(&items)))),
+                    data_type: "TEXT".into(),
+                }),
+            }
+        }
+
+        // For set types, parse comma-separated string if needed
+        Type::Set { .. } => {
             let string_array: Vec<String> = match coerced_value {
                 Value::String(s) => s.split(',').map(|item| item.trim().to_string()).collect(),
                 Value::Set(arr) => arr,
@@ -632,7 +891,13 @@ fn map_value_to_expr(value: Value, col_meta: &ColumnMetadata, data_type: &Type)
             Expr::Value(Value::Set(string_array))
         }
 
-        // For enums, wrap in CAST expression (e.g., `$1::TEXT::enum_type`)
+        // Inline enums (MySQL) take their label as a plain string
+        Type::Enum { .. } if !dialect.has_enum_types() => match coerced_value {
+            Value::Enum { value: v, .. } => Expr::Value(Value::String(v)),
+            other => Expr::Value(other),
+        },
+
+        // For enum types, wrap in CAST expression (e.g., `$1::TEXT::enum_type`)
         Type::Enum { name, .. } => {
             let base_expr = match coerced_value {
                 Value::Enum { value: v, .. } => Expr::Value(Value::String(v)),
@@ -703,12 +968,12 @@ mod tests {
     use crate::sql::{metadata::fk::ForeignKeyAction, request::FetchRowsRequestBuilder};
 
     use super::*;
-    use model::pagination::cursor::Cursor;
+    use model::pagination::cursor::{Cursor, QualCol};
     use query_builder::{
         dialect::{MySql, Postgres},
-        offsets::DefaultOffset,
+        offsets::{DefaultOffset, RangeOffset},
     };
-    use std::sync::Arc;
+    use std::sync::{Arc, atomic::AtomicU64};
 
     #[test]
     fn test_validation_estimation_postgres_simple() {
@@ -926,6 +1191,37 @@ mod tests {
         assert_eq!(params[2], Value::Int(20));
     }
 
+    #[test]
+    fn test_select_lob_columns() {
+        let field = |column: &str, data_type: &str| SelectField {
+            table: "docs".to_string(),
+            column: column.to_string(),
+            alias: Some(column.to_string()),
+            data_type: data_type.to_string(),
+        };
+        let request = FetchRowsRequestBuilder::new("docs".to_string())
+            .alias("docs".to_string())
+            .columns(vec![field("id", "int"), field("body", "bytea")])
+            .limit(10)
+            .lob_columns(vec!["body".to_string()], 1_048_576)
+            .build();
+
+        let (sql, params) = QueryGenerator::new(&Postgres).select(&request);
+        assert!(
+            sql.starts_with(
+                r#"SELECT "docs"."id" AS "id", CASE WHEN (octet_length("docs"."body") > $1) THEN NULL ELSE "docs"."body" END AS "body" FROM"#
+            ),
+            "{sql}"
+        );
+        assert_eq!(params[0], Value::Int(1_048_576));
+
+        let (sql, _) = QueryGenerator::new(&MySql).select(&request);
+        assert!(
+            sql.contains("CASE WHEN (octet_length(`docs`.`body`) > ?) THEN NULL ELSE `docs`.`body` END AS `body`"),
+            "{sql}"
+        );
+    }
+
     #[test]
     fn test_select_with_random_order_postgres() {
         let generator = QueryGenerator::new(&Postgres);
@@ -1050,6 +1346,59 @@ mod tests {
         assert_eq!(params[2], Value::Int(5)); // limit
     }
 
+    #[test]
+    fn test_select_aggregate() {
+        use model::execution::pipeline::Aggregate;
+
+        let col = |table: &str, column: &str| QualCol {
+            table: table.to_string(),
+            column: column.to_string(),
+        };
+        let request = FetchRowsRequestBuilder::new("orders".to_string())
+            .alias("orders".to_string())
+            .limit(100)
+            .cursor(Cursor::Default { offset: 200 })
+            .aggregate(Some(Aggregation {
+                group_by: vec![col("orders", "customer_id")],
+                aggregates: vec![
+                    Aggregate {
+                        name: "total".to_string(),
+                        function: AggregateFunction::Sum,
+                        column: Some(col("orders", "amount")),
+                    },
+                    Aggregate {
+                        name: "orders".to_string(),
+                        function: AggregateFunction::Count,
+                        column: None,
+                    },
+                ],
+            }))
+            .build();
+
+        let (sql, params) = QueryGenerator::new(&Postgres).select(&request);
+        assert_eq!(
+            sql,
+            r#"SELECT "orders"."customer_id" AS "customer_id", SUM("orders"."amount") AS "total", COUNT(*) AS "orders" FROM "orders" AS "orders" GROUP BY "orders"."customer_id" ORDER BY "orders"."customer_id" ASC LIMIT $1 OFFSET $2"#
+        );
+        assert_eq!(params, vec![Value::Int(100), Value::UInt(200)]);
+    }
+
+    #[test]
+    fn test_select_with_tag() {
+        let generator = QueryGenerator::new(&Postgres);
+
+        let request = FetchRowsRequestBuilder::new("users".to_string())
+            .limit(10)
+            .tag(Some("stratum run=r1 pipeline=*/users".to_string()))
+            .build();
+
+        let (sql, _) = generator.select(&request);
+        assert!(
+            sql.starts_with("/* stratum run=r1 pipeline=* /users */ SELECT "),
+            "{sql}"
+        );
+    }
+
     #[test]
     fn test_add_foreign_key_single_column_postgres() {
         let generator = QueryGenerator::new(&Postgres);
@@ -1206,4 +1555,110 @@ mod tests {
         assert!(sql.contains("CHECK"));
         assert!(sql.contains("price > 0"));
     }
+
+    #[test]
+    fn test_duplicate_keys_postgres() {
+        let generator = QueryGenerator::new(&Postgres);
+
+        let sql = generator.duplicate_keys("orders", &["created_at".to_string(), "id".to_string()]);
+
+        assert_eq!(
+            sql,
+            r#"SELECT COUNT(*) AS "duplicates" FROM (SELECT "created_at", "id" FROM "orders" GROUP BY "created_at", "id" HAVING COUNT(*) > 1) AS "d""#
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys_mysql() {
+        let generator = QueryGenerator::new(&MySql);
+
+        let sql = generator.duplicate_keys("orders", &["score".to_string()]);
+
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) AS `duplicates` FROM (SELECT `score` FROM `orders` GROUP BY `score` HAVING COUNT(*) > 1) AS `d`"
+        );
+    }
+
+    #[test]
+    fn test_select_key_range_postgres() {
+        let generator = QueryGenerator::new(&Postgres);
+        let pk = QualCol {
+            table: "orders".to_string(),
+            column: "id".to_string(),
+        };
+        let end = Arc::new(AtomicU64::new(5000));
+
+        let request = FetchRowsRequestBuilder::new("orders".to_string())
+            .alias("orders".to_string())
+            .limit(100)
+            .cursor(Cursor::Pk {
+                pk_col: pk.clone(),
+                id: 2499,
+            })
+            .strategy(Arc::new(RangeOffset {
+                pk,
+                end: end.clone(),
+            }))
+            .build();
+
+        let (sql, params) = generator.select(&request);
+        assert!(
+            sql.ends_with(
+                r#"WHERE (("orders"."id" > $1) AND ("orders"."id" < $2)) ORDER BY "orders"."id" ASC LIMIT $3"#
+            ),
+            "{sql}"
+        );
+        assert_eq!(params[..2], [Value::UInt(2499), Value::UInt(5000)]);
+
+        // A split lowers the bound for the next page
+        end.store(3000, std::sync::atomic::Ordering::Release);
+        let (_, params) = generator.select(&request);
+        assert_eq!(params[1], Value::UInt(3000));
+    }
+
+    #[test]
+    fn test_insert_array_postgres() {
+        use crate::drivers::postgres::types::PgTypeConverter;
+        use model::{
+            core::value::FieldValue,
+            records::{OpType, Record},
+        };
+
+        let generator = QueryGenerator::new(&Postgres);
+        let meta = TableMetadata {
+            name: "posts".to_string(),
+            columns: HashMap::from([(
+                "scores".to_string(),
+                ColumnMetadata {
+                    name: "scores".to_string(),
+                    data_type: "integer[]".to_string(),
+                    ..Default::default()
+                },
+            )]),
+            schema: None,
+            collation: None,
+            primary_keys: vec![],
+            foreign_keys: vec![],
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        };
+        let scores = Value::Array(vec![Value::Int(1), Value::Null, Value::Int(3)]);
+        let row = Record::new(
+            "posts",
+            vec![FieldValue {
+                name: "scores".to_string(),
+                value: Some(scores.clone()),
+                data_type: scores.data_type(),
+            }],
+            OpType::Insert,
+        );
+
+        let (sql, params) = generator.insert_batch(&meta, &[row], &PgTypeConverter);
+        assert_eq!(
+            sql,
+            r#"INSERT INTO "posts" ("scores") VALUES (CAST(CAST($1 AS TEXT) AS integer[]));"#
+        );
+        assert_eq!(params, vec![Value::String("{1,NULL,3}".to_string())]);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::sql::{filter::SqlFilter, join::clause::JoinClause, query::select::SelectField};
-use model::{core::value::Value, pagination::cursor::Cursor};
+use model::{core::value::Value, execution::pipeline::Aggregation, pagination::cursor::Cursor};
 use query_builder::offsets::{DefaultOffset, OffsetStrategy};
 use std::sync::Arc;
 
@@ -18,6 +18,27 @@ pub struct FetchRowsRequest {
     pub in_clause: Option<(String, Vec<Value>)>,
     /// Whether to order results randomly (ORDER BY RANDOM()/RAND())
     pub order_random: bool,
+    /// Binary columns of the main table whose values longer than
+    /// `lob_threshold` bytes are read as NULL, to be streamed separately
+    pub lob_columns: Vec<String>,
+    pub lob_threshold: usize,
+    /// Text of a comment the query starts with, naming who issued it
+    pub tag: Option<String>,
+    /// Grouping the rows are read with; replaces `columns` with the
+    /// grouping columns and the aggregates
+    pub aggregate: Option<Aggregation>,
+}
+
+impl FetchRowsRequest {
+    /// Whether `field` is read as NULL when its value is over `lob_threshold`.
+    pub fn is_lob_column(&self, field: &SelectField) -> bool {
+        self.lob_threshold > 0
+            && field.table == self.table
+            && self
+                .lob_columns
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(&field.column))
+    }
 }
 
 pub struct FetchRowsRequestBuilder {
@@ -31,6 +52,10 @@ pub struct FetchRowsRequestBuilder {
     strategy: Arc<dyn OffsetStrategy>,
     in_clause: Option<(String, Vec<Value>)>,
     order_random: bool,
+    lob_columns: Vec<String>,
+    lob_threshold: usize,
+    tag: Option<String>,
+    aggregate: Option<Aggregation>,
 }
 
 impl FetchRowsRequestBuilder {
@@ -46,6 +71,10 @@ impl FetchRowsRequestBuilder {
             strategy: Arc::new(DefaultOffset { offset: 0 }),
             in_clause: None,
             order_random: false,
+            lob_columns: Vec::new(),
+            lob_threshold: 0,
+            tag: None,
+            aggregate: None,
         }
     }
 
@@ -97,6 +126,28 @@ impl FetchRowsRequestBuilder {
         self
     }
 
+    /// Reads values of `columns` longer than `threshold` bytes as NULL
+    /// e.g., `CASE WHEN octet_length(photo) > 1048576 THEN NULL ELSE photo END`
+    pub fn lob_columns(mut self, columns: Vec<String>, threshold: usize) -> Self {
+        self.lob_columns = columns;
+        self.lob_threshold = threshold;
+        self
+    }
+
+    /// Starts the query with a comment, e.g. `/* stratum run=... */`, so
+    /// the statement can be attributed in the database's activity views
+    pub fn tag(mut self, tag: Option<String>) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Reads one row per group of `aggregation`
+    /// e.g., `SELECT customer_id, SUM(amount) AS total ... GROUP BY customer_id`
+    pub fn aggregate(mut self, aggregation: Option<Aggregation>) -> Self {
+        self.aggregate = aggregation;
+        self
+    }
+
     pub fn build(self) -> FetchRowsRequest {
         FetchRowsRequest {
             table: self.table,
@@ -109,6 +160,10 @@ impl FetchRowsRequestBuilder {
             strategy: self.strategy,
             in_clause: self.in_clause,
             order_random: self.order_random,
+            lob_columns: self.lob_columns,
+            lob_threshold: self.lob_threshold,
+            tag: self.tag,
+            aggregate: self.aggregate,
         }
     }
 }
@@ -0,0 +1,15 @@
+use crate::error::DriverError;
+use async_trait::async_trait;
+use model::records::Record;
+
+/// A handle to an open server-side cursor.
+/// The cursor is released by the server if the handle is dropped without calling close().
+#[async_trait]
+pub trait RowCursor: Send {
+    /// Fetch up to `count` rows from the current cursor position.
+    /// Fewer than `count` rows means the cursor is exhausted.
+    async fn fetch_next(&mut self, count: usize) -> Result<Vec<Record>, DriverError>;
+
+    /// Close the cursor and end its snapshot, consuming the handle.
+    async fn close(self: Box<Self>) -> Result<(), DriverError>;
+}
@@ -6,4 +6,9 @@ use async_trait::async_trait;
 pub trait DdlWriter: Driver {
     /// Add a column to an existing table.
     async fn add_column(&self, table: &str, column: &ColumnDef) -> Result<(), DriverError>;
+
+    /// Advance the sequence (or AUTO_INCREMENT counter) behind `column` to one
+    /// past its largest value. Returns the next value the table will hand out,
+    /// or `None` when the column is not backed by a sequence.
+    async fn sync_sequence(&self, table: &str, column: &str) -> Result<Option<i64>, DriverError>;
 }
@@ -3,7 +3,9 @@ use crate::{
     sql::metadata::{
         constraint::{CheckConstraintMetadata, UniqueConstraintMetadata},
         fk::ForeignKeyMetadata,
+        grant::TableGrant,
         index::IndexMetadata,
+        sequence::SequenceMetadata,
         table::TableMetadata,
     },
     traits::driver::Driver,
@@ -20,6 +22,12 @@ pub trait SchemaIntrospector: Driver {
     async fn referencing_tables(&self, table: &str) -> Result<Vec<String>, DriverError>;
     async fn table_size_bytes(&self, table: &str) -> Result<u64, DriverError>;
 
+    /// Average row width in bytes from the table statistics, or `None` when
+    /// the table has not been analyzed.
+    async fn avg_row_bytes(&self, _table: &str) -> Result<Option<u64>, DriverError> {
+        Ok(None)
+    }
+
     async fn unique_constraint_metadata(
         &self,
         _table: &str,
@@ -33,4 +41,14 @@ pub trait SchemaIntrospector: Driver {
     ) -> Result<Vec<CheckConstraintMetadata>, DriverError> {
         Ok(vec![])
     }
+
+    /// Privileges on the table granted to roles other than its owner.
+    async fn table_grants(&self, _table: &str) -> Result<Vec<TableGrant>, DriverError> {
+        Ok(vec![])
+    }
+
+    /// Sequences of the schema that no serial or identity column owns.
+    async fn standalone_sequences(&self) -> Result<Vec<SequenceMetadata>, DriverError> {
+        Ok(vec![])
+    }
 }
@@ -1,3 +1,4 @@
+pub mod cursor;
 pub mod ddl;
 pub mod driver;
 pub mod encoder;
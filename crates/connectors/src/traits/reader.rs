@@ -1,10 +1,10 @@
 use crate::{
     error::DriverError,
     sql::{filter::SqlFilter, request::FetchRowsRequest},
-    traits::driver::Driver,
+    traits::{cursor::RowCursor, driver::Driver},
 };
 use async_trait::async_trait;
-use model::records::Record;
+use model::{core::value::Value, records::Record};
 
 #[async_trait]
 pub trait DataReader: Driver {
@@ -16,4 +16,72 @@ pub trait DataReader: Driver {
         filter: Option<&SqlFilter>,
     ) -> Result<u64, DriverError>;
     async fn count_fast(&self, table: &str) -> Result<u64, DriverError>;
+
+    /// Smallest and largest value of the integer key `column`, or `None`
+    /// when the table is empty.
+    async fn key_bounds(
+        &self,
+        _table: &str,
+        _column: &str,
+    ) -> Result<Option<(i64, i64)>, DriverError> {
+        Err(DriverError::UnsupportedDriver(format!(
+            "{} does not support key bounds",
+            self.info().name
+        )))
+    }
+
+    /// Keys that split the integer key `column` into `partitions` ranges of
+    /// about the same number of rows, estimated from the table statistics.
+    /// Ascending; empty when there are no statistics to estimate from.
+    async fn key_split_points(
+        &self,
+        _table: &str,
+        _column: &str,
+        _partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        Ok(Vec::new())
+    }
+
+    /// Primary keys (the `key_columns` of each row) of the rows whose binary
+    /// `column` holds more than `threshold` bytes.
+    async fn oversized_keys(
+        &self,
+        _table: &str,
+        _key_columns: &[String],
+        _column: &str,
+        _threshold: usize,
+    ) -> Result<Vec<Record>, DriverError> {
+        Err(DriverError::UnsupportedDriver(format!(
+            "{} does not support large-object streaming",
+            self.info().name
+        )))
+    }
+
+    /// Up to `len` bytes of the binary `column` of the row with primary key
+    /// `key`, starting at byte `offset`. Empty past the end of the value.
+    async fn read_chunk(
+        &self,
+        _table: &str,
+        _key: &[(String, Value)],
+        _column: &str,
+        _offset: usize,
+        _len: usize,
+    ) -> Result<Vec<u8>, DriverError> {
+        Err(DriverError::UnsupportedDriver(format!(
+            "{} does not support large-object streaming",
+            self.info().name
+        )))
+    }
+
+    /// Declare a server-side cursor over the request's query. Rows are then
+    /// drained page by page with `RowCursor::fetch_next`.
+    async fn open_cursor(
+        &self,
+        _request: FetchRowsRequest,
+    ) -> Result<Box<dyn RowCursor>, DriverError> {
+        Err(DriverError::UnsupportedDriver(format!(
+            "{} does not support server-side cursors",
+            self.info().name
+        )))
+    }
 }
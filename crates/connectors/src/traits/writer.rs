@@ -4,13 +4,38 @@ use crate::{
     traits::driver::Driver,
 };
 use async_trait::async_trait;
-use model::records::Record;
+use model::{core::value::Value, records::Record};
 
 #[async_trait]
 pub trait DataWriter: Driver {
     /// Write a batch of rows using standard INSERT statements.
     async fn write_batch(&self, meta: &TableMetadata, rows: &[Record]) -> Result<u64, DriverError>;
 
+    /// Insert the rows whose primary key is not in the table yet; returns the
+    /// number inserted.
+    async fn insert_missing(
+        &self,
+        meta: &TableMetadata,
+        rows: &[Record],
+    ) -> Result<u64, DriverError>;
+
+    /// Set the binary `column` of the row with primary key `key` to `chunk`,
+    /// or with `append` add `chunk` to its end. Returns the rows affected,
+    /// 0 when the row does not exist.
+    async fn write_chunk(
+        &self,
+        _table: &str,
+        _key: &[(String, Value)],
+        _column: &str,
+        _chunk: &[u8],
+        _append: bool,
+    ) -> Result<u64, DriverError> {
+        Err(DriverError::UnsupportedDriver(format!(
+            "{} does not support large-object streaming",
+            self.info().name
+        )))
+    }
+
     /// Write rows using optimized bulk protocol (COPY/LOAD DATA).
     async fn copy_rows(
         &self,
@@ -6,9 +6,17 @@ use super::{
 use crate::settings::CopyColumns;
 use crate::settings::validated::ValidatedSettings;
 use engine_core::schema::planner::SchemaPlanner;
-use engine_core::schema::{plan::SchemaPlan, type_registry::TypeRegistry, types::TypeEngine};
-use model::transform::mapping::TransformationMetadata;
-use std::sync::Arc;
+use engine_core::schema::{
+    plan::{SchemaPlan, column_type},
+    type_registry::TypeRegistry,
+    types::TypeEngine,
+};
+use model::{
+    core::{timezone::SourceTimezones, types::Type},
+    execution::partition::TablePartitioning,
+    transform::mapping::TransformationMetadata,
+};
+use std::{collections::BTreeMap, sync::Arc};
 
 #[derive(Clone)]
 pub struct SchemaSettingContext<D: SchemaDriver> {
@@ -16,6 +24,14 @@ pub struct SchemaSettingContext<D: SchemaDriver> {
     pub destination: Endpoint<D>,
     pub mapping: TransformationMetadata,
     pub settings: ValidatedSettings,
+    /// Partitioning of the destination table, from the to {} block
+    pub partition_by: Option<TablePartitioning>,
+    /// Destination column types from the types {} block
+    pub column_types: BTreeMap<String, String>,
+    /// Time zones of source timestamps without an offset
+    pub source_timezones: SourceTimezones,
+    /// Destination role of each source role from the roles {} block
+    pub role_map: BTreeMap<String, String>,
 }
 
 impl<D: SchemaDriver> SchemaSettingContext<D> {
@@ -30,9 +46,70 @@ impl<D: SchemaDriver> SchemaSettingContext<D> {
             destination,
             mapping: mapping.clone(),
             settings: settings.clone(),
+            partition_by: None,
+            column_types: BTreeMap::new(),
+            source_timezones: SourceTimezones::default(),
+            role_map: BTreeMap::new(),
         }
     }
 
+    pub fn with_partitioning(mut self, partition_by: Option<TablePartitioning>) -> Self {
+        self.partition_by = partition_by;
+        self
+    }
+
+    pub fn with_column_types(mut self, column_types: BTreeMap<String, String>) -> Self {
+        self.column_types = column_types;
+        self
+    }
+
+    pub fn with_source_timezones(mut self, timezones: SourceTimezones) -> Self {
+        self.source_timezones = timezones;
+        self
+    }
+
+    pub fn with_role_map(mut self, role_map: BTreeMap<String, String>) -> Self {
+        self.role_map = role_map;
+        self
+    }
+
+    /// Marks the destination table of `plan` as partitioned and checks the
+    /// partition column against the planned table.
+    pub fn apply_partitioning(&self, plan: &mut SchemaPlan) -> Result<(), SettingsError> {
+        let Some(partition_by) = &self.partition_by else {
+            return Ok(());
+        };
+
+        plan.set_partitioning(&self.destination.name, partition_by.clone());
+        let errors = plan.partitioning_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SettingsError::ValidationFailed(errors))
+        }
+    }
+
+    /// Overrides the inferred types of the destination columns named in the
+    /// types {} block and checks the planned table has them.
+    pub async fn apply_column_types(&self, plan: &mut SchemaPlan) -> Result<(), SettingsError> {
+        if self.column_types.is_empty() {
+            return Ok(());
+        }
+
+        plan.set_column_types(&self.destination.name, self.column_types.clone());
+        let errors = plan.column_type_errors().await;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SettingsError::ValidationFailed(errors))
+        }
+    }
+
+    /// Type the types {} block gives the destination column `column`, if any.
+    pub fn column_type(&self, column: &str) -> Option<Type> {
+        self.column_types.get(column).map(|t| column_type(t))
+    }
+
     pub async fn destination_exists(&self) -> Result<bool, SettingsError> {
         self.destination
             .driver
@@ -57,7 +134,10 @@ impl<D: SchemaDriver> SchemaSettingContext<D> {
             ignore_constraints,
             mapped_columns_only,
             self.type_registry(),
-        ))
+        )
+        .with_max_identifier_length(self.settings.max_identifier_length())
+        .with_materialize_generated(self.settings.materialize_generated_columns())
+        .with_source_timezones(self.source_timezones.clone()))
     }
 
     pub async fn build_schema_plan(&self) -> Result<SchemaPlan, SettingsError> {
@@ -70,11 +150,15 @@ impl<D: SchemaDriver> SchemaSettingContext<D> {
         let type_engine =
             TypeEngine::new(introspector.clone(), registry.clone(), self.source.dialect);
 
-        Ok(SchemaPlan::new(
+        let mut plan = SchemaPlan::new(
             type_engine,
             ignore_constraints,
             mapped_columns_only,
             self.mapping.clone(),
-        ))
+        );
+        plan.set_max_identifier_length(self.settings.max_identifier_length());
+        plan.set_materialize_generated(self.settings.materialize_generated_columns());
+        plan.set_source_timezones(self.source_timezones.clone());
+        Ok(plan)
     }
 }
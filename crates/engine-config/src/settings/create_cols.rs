@@ -8,9 +8,9 @@ use connectors::sql::{
     query::{column::ColumnDef, generator::QueryGenerator},
 };
 use engine_core::schema::{
+    diff::missing_column_defs,
     schema_ops::{SchemaOp, SchemaOps},
     types::{ComputedTypes, ExpressionWrapper, TypeInferencer},
-    utils::create_column_def,
 };
 use engine_processing::context::PipelineContext;
 use model::{core::types::Type, execution::expr::CompiledExpression};
@@ -71,29 +71,63 @@ impl<D: SchemaDriver> CreateMissingColumnsSetting<D> {
         if let Some(columns) = self.context.mapping.field_mappings.get_entity(table) {
             let registry = Arc::new(self.context.type_registry());
             let source_dialect = &self.context.source.dialect;
+            let timezones = &self.context.source_timezones;
             let type_conv = |meta: &ColumnMetadata| -> (Type, Option<usize>) {
                 let source_type = source_dialect.to_canonical(meta);
                 let target_type = registry.convert(&source_type).target_type();
-                (target_type, meta.char_max_length)
+                (
+                    timezones.column_type(&meta.name, target_type),
+                    meta.char_max_length,
+                )
             };
 
             let query_dialect = self.context.destination.dialect.as_query_dialect();
             let generator = QueryGenerator::new(query_dialect.as_ref());
 
-            for (src_col, dst_col) in columns.forward_map() {
-                if dest_meta.get_column(&dst_col).is_none() {
-                    let meta = source_meta.column(&src_col).ok_or_else(|| {
-                        SettingsError::MissingSourceColumn(format!("{src_col} not in source"))
-                    })?;
-                    let def = create_column_def(&dst_col, &type_conv, meta);
-                    let (sql, _) = generator.add_column(table, def.clone());
+            let columns: Vec<_> = columns.forward_map().into_iter().collect();
+            let defs = missing_column_defs(&columns, source_meta, dest_meta, &type_conv).map_err(
+                |src_col| SettingsError::MissingSourceColumn(format!("{src_col} not in source")),
+            )?;
+            let materialize = self.context.settings.materialize_generated_columns();
+            for mut def in defs {
+                if materialize {
+                    def.generated_expression = None;
+                    def.is_generated = false;
+                    def.is_stored = false;
+                } else if let Some(expr) = &def.generated_expression {
+                    def.generated_expression =
+                        Some(source_dialect.normalize_generated_expression(expr));
+                }
+                if let Some(data_type) = self.context.column_type(&def.name) {
+                    def.data_type = data_type;
+                    def.char_max_length = None;
+                }
+
+                if let Type::Enum { name, values } = &def.data_type
+                    && query_dialect.has_enum_types()
+                {
+                    let (sql, _) = generator.create_enum(name, values);
                     ops.pre.push(SchemaOp {
                         sql,
-                        description: format!("Add column '{}' to table '{}'", def.name, table),
-                        idempotent: false,
+                        description: format!("Create enum type '{}'", name),
+                        idempotent: true,
                         skip_if_missing_ref: false,
+                        foreign_key: None,
+                        created_table: None,
+                        dropped_index: None,
                     });
                 }
+
+                let (sql, _) = generator.add_column(table, def.clone());
+                ops.pre.push(SchemaOp {
+                    sql,
+                    description: format!("Add column '{}' to table '{}'", def.name, table),
+                    idempotent: false,
+                    skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
+                });
             }
         }
         Ok(())
@@ -150,14 +184,25 @@ impl<D: SchemaDriver> CreateMissingColumnsSetting<D> {
                                 .await
                         }
                     };
-                    let data_type = col_type.ok_or_else(|| {
-                        SettingsError::DataTypeInference(format!(
-                            "Couldn't infer type for {}",
-                            comp.name
-                        ))
-                    })?;
-                    computed_types.insert(comp.name.to_ascii_lowercase(), data_type.clone());
-                    let def = ColumnDef::from_computed(&comp.name, &data_type.0);
+                    if let Some(inferred) = &col_type {
+                        computed_types.insert(comp.name.to_ascii_lowercase(), inferred.clone());
+                    }
+                    // A type from the types {} block wins, and saves a
+                    // column whose type cannot be inferred.
+                    let data_type = match self.context.column_type(&comp.name) {
+                        Some(data_type) => data_type,
+                        None => {
+                            col_type
+                                .ok_or_else(|| {
+                                    SettingsError::DataTypeInference(format!(
+                                        "Couldn't infer type for {}",
+                                        comp.name
+                                    ))
+                                })?
+                                .0
+                        }
+                    };
+                    let def = ColumnDef::from_computed(&comp.name, &data_type);
                     let (sql, _) = generator.add_column(table, def.clone());
                     ops.pre.push(SchemaOp {
                         sql,
@@ -167,6 +212,9 @@ impl<D: SchemaDriver> CreateMissingColumnsSetting<D> {
                         ),
                         idempotent: false,
                         skip_if_missing_ref: false,
+                        foreign_key: None,
+                        created_table: None,
+                        dropped_index: None,
                     });
                 }
             }
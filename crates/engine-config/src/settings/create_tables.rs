@@ -4,7 +4,10 @@ use super::{
 };
 use crate::settings::error::SettingsError;
 use async_trait::async_trait;
-use engine_core::schema::schema_ops::{SchemaOp, SchemaOps};
+use engine_core::schema::{
+    naming::log_renamed_identifiers,
+    schema_ops::{SchemaOp, SchemaOps},
+};
 use engine_processing::context::PipelineContext;
 use tracing::info;
 
@@ -40,7 +43,10 @@ impl<D: SchemaDriver> CreateMissingTablesSetting<D> {
         let src_name = self.context.mapping.entities.reverse_resolve(dest_name);
 
         let schema_planner = self.context.init_schema_planner().await?;
-        let plan = schema_planner.plan_schema(&src_name).await?;
+        let mut plan = schema_planner.plan_schema(&src_name).await?;
+        self.context.apply_partitioning(&mut plan)?;
+        self.context.apply_column_types(&mut plan).await?;
+        log_renamed_identifiers(&plan.identifier_report()?);
 
         let mut ops = SchemaOps::empty();
 
@@ -51,29 +57,58 @@ impl<D: SchemaDriver> CreateMissingTablesSetting<D> {
                 description: format!("Create enum type '{}'", name),
                 idempotent: true,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
             });
         }
 
         // Table queries -> pre
         for (sql, name) in plan.table_queries().await {
+            let created_table = (!plan.is_partitioned(&name)).then(|| name.clone());
             ops.pre.push(SchemaOp {
                 sql,
                 description: format!("Create table '{}'", name),
                 idempotent: false,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table,
+                dropped_index: None,
             });
         }
 
-        // FK queries -> post
-        for (sql, name) in plan.fk_queries() {
-            ops.post.push(SchemaOp {
+        // Partition queries -> pre, after their parent tables
+        for (sql, name) in plan.partition_queries() {
+            ops.pre.push(SchemaOp {
                 sql,
-                description: format!("Add foreign key constraint on '{}'", name),
+                description: format!("Create partition '{}'", name),
                 idempotent: false,
-                skip_if_missing_ref: true,
+                skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
             });
         }
 
+        // Index queries -> post (created after data migration, idempotent)
+        if self.context.settings.migrate_indexes() {
+            for (sql, name) in plan.index_queries() {
+                ops.post.push(SchemaOp {
+                    sql,
+                    description: format!("Create index '{}'", name),
+                    idempotent: true,
+                    skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
+                });
+            }
+        }
+
+        // FK queries -> post (created after data migration, moved to post-load
+        // by defer_foreign_keys)
+        ops.post.extend(plan.fk_ops());
+
         info!("planned create-missing-tables");
         Ok(ops)
     }
@@ -0,0 +1,33 @@
+use super::{MigrationSetting, phase::MigrationSettingsPhase, types::DeferForeignKeys};
+use async_trait::async_trait;
+use engine_core::schema::schema_ops::SchemaOps;
+use tracing::info;
+
+/// Holds back the foreign keys planned by the schema settings until every
+/// pipeline of the run has loaded, so that no table is loaded with one in
+/// place and no key is checked against a table that is still loading.
+pub struct DeferForeignKeysSetting {
+    mode: DeferForeignKeys,
+}
+
+#[async_trait]
+impl MigrationSetting for DeferForeignKeysSetting {
+    fn phase(&self) -> MigrationSettingsPhase {
+        MigrationSettingsPhase::PostLoad
+    }
+
+    fn rework(&self, ops: &mut SchemaOps) {
+        let before = ops.post_load.len();
+        ops.defer_foreign_keys(self.mode == DeferForeignKeys::NotValid);
+        let deferred = ops.post_load.len() - before;
+        if deferred > 0 {
+            info!(ops = deferred, mode = %self.mode, "deferred foreign keys until after the load");
+        }
+    }
+}
+
+impl DeferForeignKeysSetting {
+    pub fn new(mode: DeferForeignKeys) -> Self {
+        Self { mode }
+    }
+}
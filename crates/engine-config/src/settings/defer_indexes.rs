@@ -0,0 +1,85 @@
+use super::{
+    MigrationSetting, context::SchemaSettingContext, driver::SchemaDriver,
+    phase::MigrationSettingsPhase,
+};
+use crate::settings::error::SettingsError;
+use async_trait::async_trait;
+use engine_core::schema::schema_ops::SchemaOps;
+use engine_processing::context::PipelineContext;
+use model::core::value::Value;
+use query_builder::dialect::{Dialect, Postgres};
+use tracing::{info, warn};
+
+/// Valid, non-unique indexes of a table that back no constraint. Unique
+/// indexes stay, since the load relies on them for upserts and rejects.
+const DEFERRABLE_INDEXES_SQL: &str = "SELECT n.nspname AS schema, i.relname AS name, \
+     pg_get_indexdef(x.indexrelid) AS definition \
+     FROM pg_index x \
+     JOIN pg_class i ON i.oid = x.indexrelid \
+     JOIN pg_class t ON t.oid = x.indrelid AND t.relkind = 'r' \
+     JOIN pg_namespace n ON n.oid = i.relnamespace \
+     WHERE x.indrelid = to_regclass($1) \
+     AND NOT x.indisprimary AND NOT x.indisunique AND x.indisvalid \
+     AND NOT EXISTS (SELECT 1 FROM pg_constraint c WHERE c.conindid = x.indexrelid) \
+     ORDER BY i.relname";
+
+/// Drops the existing destination table's secondary indexes before the load
+/// and rebuilds them concurrently once it has loaded, so that the load does
+/// not maintain them row by row.
+pub struct DeferIndexesSetting<D: SchemaDriver> {
+    context: SchemaSettingContext<D>,
+}
+
+#[async_trait]
+impl<D: SchemaDriver> MigrationSetting for DeferIndexesSetting<D> {
+    fn phase(&self) -> MigrationSettingsPhase {
+        MigrationSettingsPhase::DeferIndexes
+    }
+
+    async fn plan(&mut self, _ctx: &PipelineContext) -> Result<SchemaOps, SettingsError> {
+        self.build_schema_ops().await
+    }
+}
+
+impl<D: SchemaDriver> DeferIndexesSetting<D> {
+    pub fn new(ctx: SchemaSettingContext<D>) -> Self {
+        Self { context: ctx }
+    }
+
+    async fn build_schema_ops(&self) -> Result<SchemaOps, SettingsError> {
+        // Tables created for this run have no indexes until after the load
+        if !self.context.destination_exists().await? {
+            return Ok(SchemaOps::empty());
+        }
+
+        let table = &self.context.destination.name;
+        let rows = self
+            .context
+            .destination
+            .driver
+            .query_params(
+                DEFERRABLE_INDEXES_SQL,
+                &[Value::String(Postgres.quote_identifier(table))],
+            )
+            .await?;
+
+        let mut ops = SchemaOps::empty();
+        for row in rows {
+            let (Value::String(schema), Value::String(name), Value::String(definition)) = (
+                row.get_value("schema"),
+                row.get_value("name"),
+                row.get_value("definition"),
+            ) else {
+                continue;
+            };
+            if !ops.defer_index(table, &schema, &name, &definition) {
+                warn!(index = %name, table = %table, "keeping index that cannot be rebuilt concurrently");
+            }
+        }
+
+        if !ops.is_empty() {
+            info!(table = %table, indexes = ops.pre.len(), "dropping indexes for the load");
+        }
+        Ok(ops)
+    }
+}
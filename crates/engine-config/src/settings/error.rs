@@ -1,4 +1,5 @@
 use connectors::error::DriverError;
+use engine_core::schema::error::NamingError;
 use thiserror::Error;
 
 /// Errors raised when processing migration settings or configuration.
@@ -32,6 +33,10 @@ pub enum SettingsError {
     #[error("Settings validation failed:\n{}", .0.join("\n"))]
     ValidationFailed(Vec<String>),
 
+    /// Two identifiers end up with the same name at the destination's length limit.
+    #[error("Identifier error in settings: {0}")]
+    Naming(#[from] NamingError),
+
     /// Multiple settings conflict with each other.
     #[error("Conflicting settings detected:\n{}", .0.join("\n"))]
     ConflictingSettings(Vec<String>),
@@ -0,0 +1,33 @@
+use super::{MigrationSetting, phase::MigrationSettingsPhase};
+use async_trait::async_trait;
+use engine_core::schema::schema_ops::SchemaOps;
+use tracing::info;
+
+/// Creates the tables planned by the schema settings `UNLOGGED` and switches
+/// them to logged once the pipeline has loaded, so that the load itself
+/// writes no WAL.
+pub struct FastLoadSetting {
+    /// The pipeline's destination table, switched to logged even when an
+    /// earlier, paused run created it
+    destination: String,
+}
+
+#[async_trait]
+impl MigrationSetting for FastLoadSetting {
+    fn phase(&self) -> MigrationSettingsPhase {
+        MigrationSettingsPhase::FastLoad
+    }
+
+    fn rework(&self, ops: &mut SchemaOps) {
+        let created = ops.load_unlogged(std::slice::from_ref(&self.destination));
+        if created > 0 {
+            info!(tables = created, "creating tables unlogged for the load");
+        }
+    }
+}
+
+impl FastLoadSetting {
+    pub fn new(destination: String) -> Self {
+        Self { destination }
+    }
+}
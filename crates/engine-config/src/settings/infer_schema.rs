@@ -7,6 +7,7 @@ use connectors::{
     sql::metadata::provider::MetadataProvider, traits::introspector::SchemaIntrospector,
 };
 use engine_core::schema::{
+    naming::log_renamed_identifiers,
     plan::SchemaPlan,
     schema_ops::{SchemaOp, SchemaOps},
 };
@@ -70,10 +71,22 @@ impl<D: SchemaDriver> InferSchemaSetting<D> {
             }
         }
 
+        if ctx.settings.migrate_indexes() {
+            let tables: Vec<String> = schema_plan.metadata_graph().keys().cloned().collect();
+            for table in tables {
+                let indexes = ctx.source.introspector.index_metadata(&table).await?;
+                schema_plan.add_source_indexes(&table, &indexes);
+            }
+        }
+
+        ctx.apply_partitioning(&mut schema_plan)?;
+        ctx.apply_column_types(&mut schema_plan).await?;
         Self::schema_plan_to_ops(&schema_plan).await
     }
 
     async fn schema_plan_to_ops(plan: &SchemaPlan) -> Result<SchemaOps, SettingsError> {
+        log_renamed_identifiers(&plan.identifier_report()?);
+
         let mut ops = SchemaOps::empty();
 
         // Enum queries -> pre (idempotent - safe to skip "already exists")
@@ -83,29 +96,56 @@ impl<D: SchemaDriver> InferSchemaSetting<D> {
                 description: format!("Create enum type '{}'", name),
                 idempotent: true,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
             });
         }
 
         // Table queries -> pre
         for (sql, name) in plan.table_queries().await {
+            let created_table = (!plan.is_partitioned(&name)).then(|| name.clone());
             ops.pre.push(SchemaOp {
                 sql,
                 description: format!("Create table '{}'", name),
                 idempotent: false,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table,
+                dropped_index: None,
             });
         }
 
-        // FK queries -> post (created after data migration)
-        for (sql, name) in plan.fk_queries() {
-            ops.post.push(SchemaOp {
+        // Partition queries -> pre, after their parent tables
+        for (sql, name) in plan.partition_queries() {
+            ops.pre.push(SchemaOp {
                 sql,
-                description: format!("Add foreign key constraint on '{}'", name),
+                description: format!("Create partition '{}'", name),
                 idempotent: false,
-                skip_if_missing_ref: true,
+                skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
             });
         }
 
+        // Index queries -> post (created after data migration, idempotent)
+        for (sql, name) in plan.index_queries() {
+            ops.post.push(SchemaOp {
+                sql,
+                description: format!("Create index '{}'", name),
+                idempotent: true,
+                skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
+            });
+        }
+
+        // FK queries -> post (created after data migration, moved to post-load
+        // by defer_foreign_keys)
+        ops.post.extend(plan.fk_ops());
+
         Ok(ops)
     }
 }
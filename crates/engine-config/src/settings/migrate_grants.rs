@@ -0,0 +1,243 @@
+use super::{
+    MigrationSetting, context::SchemaSettingContext, driver::SchemaDriver,
+    phase::MigrationSettingsPhase,
+};
+use crate::settings::error::SettingsError;
+use async_trait::async_trait;
+use connectors::sql::metadata::{grant::TableGrant, sequence::SequenceMetadata};
+use engine_core::schema::schema_ops::{SchemaOp, SchemaOps};
+use engine_processing::context::PipelineContext;
+use model::core::value::Value;
+use query_builder::dialect::{Dialect, Postgres};
+use std::collections::{BTreeMap, HashSet};
+use tracing::{info, warn};
+
+const ROLES_SQL: &str = "SELECT rolname FROM pg_roles";
+
+/// Copies the source table's grants to the destination table, under the
+/// roles the roles {} block maps them to, and the source schema's
+/// standalone sequences, which no table's DDL creates.
+pub struct MigrateGrantsSetting<D: SchemaDriver> {
+    context: SchemaSettingContext<D>,
+}
+
+#[async_trait]
+impl<D: SchemaDriver> MigrationSetting for MigrateGrantsSetting<D> {
+    fn phase(&self) -> MigrationSettingsPhase {
+        MigrationSettingsPhase::MigrateGrants
+    }
+
+    async fn plan(&mut self, _ctx: &PipelineContext) -> Result<SchemaOps, SettingsError> {
+        self.build_schema_ops().await
+    }
+}
+
+impl<D: SchemaDriver> MigrateGrantsSetting<D> {
+    pub fn new(ctx: SchemaSettingContext<D>) -> Self {
+        Self { context: ctx }
+    }
+
+    async fn build_schema_ops(&self) -> Result<SchemaOps, SettingsError> {
+        let introspector = &self.context.source.introspector;
+        let grants = introspector.table_grants(&self.context.source.name).await?;
+        let sequences = introspector.standalone_sequences().await?;
+
+        let mut ops = SchemaOps::empty();
+        for sequence in &sequences {
+            let (create, set_value) = sequence_ops(sequence);
+            ops.pre.push(create);
+            ops.post.extend(set_value);
+        }
+
+        if !grants.is_empty() {
+            let roles: HashSet<String> = self
+                .context
+                .destination
+                .driver
+                .query(ROLES_SQL)
+                .await?
+                .iter()
+                .filter_map(|row| match row.get_value("rolname") {
+                    Value::String(role) => Some(role),
+                    _ => None,
+                })
+                .collect();
+
+            let table = &self.context.destination.name;
+            let (grant_ops, missing) = grant_ops(table, &grants, &self.context.role_map, &roles);
+            for role in missing {
+                warn!(role = %role, table = %table, "skipping grants to a role the destination does not have; map it in the roles {{}} block");
+            }
+            ops.post.extend(grant_ops);
+        }
+
+        info!(
+            sequences = sequences.len(),
+            grants = grants.len(),
+            "copying grants and standalone sequences"
+        );
+        Ok(ops)
+    }
+}
+
+/// `GRANT` ops for `grants` on `table`, one per role and grant option, and
+/// the destination roles that do not exist, whose grants are left out.
+fn grant_ops(
+    table: &str,
+    grants: &[TableGrant],
+    role_map: &BTreeMap<String, String>,
+    roles: &HashSet<String>,
+) -> (Vec<SchemaOp>, Vec<String>) {
+    let mut privileges: BTreeMap<(String, bool), Vec<&str>> = BTreeMap::new();
+    let mut missing = Vec::new();
+
+    for grant in grants {
+        let role = role_map.get(&grant.grantee).unwrap_or(&grant.grantee);
+        let public = role.eq_ignore_ascii_case("PUBLIC");
+        if !public && !roles.contains(role) {
+            if !missing.contains(role) {
+                missing.push(role.clone());
+            }
+            continue;
+        }
+        let grantee = match public {
+            true => "PUBLIC".to_string(),
+            false => Postgres.quote_identifier(role),
+        };
+        privileges
+            .entry((grantee, grant.grantable))
+            .or_default()
+            .push(&grant.privilege);
+    }
+
+    let quoted_table = Postgres.quote_identifier(table);
+    let ops = privileges
+        .into_iter()
+        .map(|((grantee, grantable), privileges)| SchemaOp {
+            sql: format!(
+                "GRANT {} ON {quoted_table} TO {grantee}{};",
+                privileges.join(", "),
+                if grantable { " WITH GRANT OPTION" } else { "" }
+            ),
+            description: format!("Grant {} on '{table}' to {grantee}", privileges.join(", ")),
+            idempotent: true,
+            skip_if_missing_ref: false,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: None,
+        })
+        .collect();
+
+    (ops, missing)
+}
+
+/// The op that creates `sequence` and, once it has been used, the op that
+/// advances it to the source's value, never moving it back.
+fn sequence_ops(sequence: &SequenceMetadata) -> (SchemaOp, Option<SchemaOp>) {
+    let quoted = Postgres.quote_identifier(&sequence.name);
+    let create = SchemaOp {
+        sql: format!(
+            "CREATE SEQUENCE IF NOT EXISTS {quoted} INCREMENT BY {} MINVALUE {} MAXVALUE {} START WITH {} {};",
+            sequence.increment,
+            sequence.min,
+            sequence.max,
+            sequence.start,
+            if sequence.cycle { "CYCLE" } else { "NO CYCLE" }
+        ),
+        description: format!("Create sequence '{}'", sequence.name),
+        idempotent: true,
+        skip_if_missing_ref: false,
+        foreign_key: None,
+        created_table: None,
+        dropped_index: None,
+    };
+
+    let set_value = sequence.last_value.map(|value| {
+        // A descending sequence moves forward by going down
+        let furthest = if sequence.increment < 0 {
+            "LEAST"
+        } else {
+            "GREATEST"
+        };
+        SchemaOp {
+            sql: format!(
+                "SELECT setval('{}', {furthest}({value}, last_value)) FROM {quoted};",
+                quoted.replace('\'', "''")
+            ),
+            description: format!("Set sequence '{}' to {value}", sequence.name),
+            idempotent: false,
+            skip_if_missing_ref: false,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: None,
+        }
+    });
+
+    (create, set_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(grantee: &str, privilege: &str, grantable: bool) -> TableGrant {
+        TableGrant {
+            grantee: grantee.to_string(),
+            privilege: privilege.to_string(),
+            grantable,
+        }
+    }
+
+    #[test]
+    fn test_grant_ops_map_roles_and_skip_missing_ones() {
+        let grants = vec![
+            grant("PUBLIC", "SELECT", false),
+            grant("app_rw", "INSERT", false),
+            grant("app_rw", "SELECT", false),
+            grant("app_rw", "UPDATE", true),
+            grant("legacy", "SELECT", false),
+            grant("reporting", "SELECT", false),
+        ];
+        let role_map = BTreeMap::from([("app_rw".to_string(), "etl_writer".to_string())]);
+        let roles = HashSet::from(["etl_writer".to_string(), "reporting".to_string()]);
+
+        let (ops, missing) = grant_ops("orders", &grants, &role_map, &roles);
+        let sql: Vec<&str> = ops.iter().map(|op| op.sql.as_str()).collect();
+        assert_eq!(
+            sql,
+            vec![
+                r#"GRANT INSERT, SELECT ON "orders" TO "etl_writer";"#,
+                r#"GRANT UPDATE ON "orders" TO "etl_writer" WITH GRANT OPTION;"#,
+                r#"GRANT SELECT ON "orders" TO "reporting";"#,
+                r#"GRANT SELECT ON "orders" TO PUBLIC;"#,
+            ]
+        );
+        assert_eq!(missing, vec!["legacy".to_string()]);
+    }
+
+    #[test]
+    fn test_sequence_ops() {
+        let mut sequence = SequenceMetadata {
+            name: "invoice_numbers".to_string(),
+            start: 1000,
+            increment: 1,
+            min: 1,
+            max: 999_999,
+            cycle: false,
+            last_value: Some(1234),
+        };
+
+        let (create, set_value) = sequence_ops(&sequence);
+        assert_eq!(
+            create.sql,
+            r#"CREATE SEQUENCE IF NOT EXISTS "invoice_numbers" INCREMENT BY 1 MINVALUE 1 MAXVALUE 999999 START WITH 1000 NO CYCLE;"#
+        );
+        assert_eq!(
+            set_value.unwrap().sql,
+            r#"SELECT setval('"invoice_numbers"', GREATEST(1234, last_value)) FROM "invoice_numbers";"#
+        );
+
+        sequence.last_value = None;
+        assert!(sequence_ops(&sequence).1.is_none());
+    }
+}
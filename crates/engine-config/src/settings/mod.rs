@@ -1,10 +1,14 @@
 pub mod context;
 pub mod create_cols;
 pub mod create_tables;
+pub mod defer_fks;
+pub mod defer_indexes;
 pub mod driver;
 pub mod endpoint;
 pub mod error;
+pub mod fast_load;
 pub mod infer_schema;
+pub mod migrate_grants;
 pub mod orchestrator;
 pub mod phase;
 pub mod schema_manager;
@@ -22,6 +26,6 @@ pub use orchestrator::{collect_settings, validate_and_plan};
 pub use phase::MigrationSettingsPhase;
 pub use schema_manager::apply_schema_ops;
 pub use traits::MigrationSetting;
-pub use types::{CopyColumns, Settings};
+pub use types::{CopyColumns, DeferForeignKeys, FastLoad, Settings};
 pub use validated::{ValidatedSettings, ValidatedSettingsBuilder};
-pub use validator::SettingsValidator;
+pub use validator::{SettingFinding, SettingsValidator};
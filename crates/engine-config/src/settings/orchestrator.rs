@@ -1,8 +1,19 @@
 use super::{
-    create_cols::CreateMissingColumnsSetting, create_tables::CreateMissingTablesSetting,
-    driver::SchemaDriver, endpoint::Endpoint, endpoint::SchemaSource, error::SettingsError,
-    infer_schema::InferSchemaSetting, traits::MigrationSetting, types::Settings,
-    validated::ValidatedSettings, validator::SettingsValidator,
+    create_cols::CreateMissingColumnsSetting,
+    create_tables::CreateMissingTablesSetting,
+    defer_fks::DeferForeignKeysSetting,
+    defer_indexes::DeferIndexesSetting,
+    driver::SchemaDriver,
+    endpoint::Endpoint,
+    endpoint::SchemaSource,
+    error::SettingsError,
+    fast_load::FastLoadSetting,
+    infer_schema::InferSchemaSetting,
+    migrate_grants::MigrateGrantsSetting,
+    traits::MigrationSetting,
+    types::{FastLoad, Settings},
+    validated::ValidatedSettings,
+    validator::SettingsValidator,
 };
 use crate::settings::SchemaSettingContext;
 use connectors::traits::introspector::SchemaIntrospector;
@@ -14,7 +25,7 @@ use std::{collections::HashMap, sync::Arc};
 /// Validate settings and collect schema operations without executing DDL.
 ///
 /// Returns validated settings (for non-schema config like batch_size) and
-/// the collected schema operations split into pre/post migration phases.
+/// the collected schema operations split into pre/post migration and post-load phases.
 pub async fn validate_and_plan<D>(
     ctx: &mut PipelineContext,
     src_introspector: Arc<dyn SchemaIntrospector>,
@@ -46,7 +57,7 @@ where
         dst_driver.clone(),
         &validated_settings,
     )
-    .await;
+    .await?;
 
     let mut schema_ops = SchemaOps::empty();
 
@@ -55,6 +66,7 @@ where
             // Collect schema ops (no-op for non-schema settings)
             let ops = setting.plan(ctx).await?;
             schema_ops.merge(ops);
+            setting.rework(&mut schema_ops);
         }
     }
 
@@ -67,19 +79,25 @@ pub async fn collect_settings<D>(
     src_dialect: Dialect,
     dst_driver: Arc<D>,
     validated: &ValidatedSettings,
-) -> Vec<Box<dyn MigrationSetting>>
+) -> Result<Vec<Box<dyn MigrationSetting>>, SettingsError>
 where
     D: SchemaDriver,
 {
     let source_info = SchemaSource::new(src_introspector, ctx.source.name.clone(), src_dialect);
 
-    let dest_info = Endpoint::new(
-        dst_driver,
-        ctx.destination.name.clone(),
-        ctx.destination.format.to_dialect(),
-    );
+    let dst_dialect = ctx.destination.format.to_dialect().map_err(|e| {
+        SettingsError::UnsupportedDestination(format!(
+            "connection '{}': {e}",
+            ctx.pipeline.destination.connection.name
+        ))
+    })?;
+    let dest_info = Endpoint::new(dst_driver, ctx.destination.name.clone(), dst_dialect);
 
-    let schema_ctx = SchemaSettingContext::new(source_info, dest_info, &ctx.mapping, validated);
+    let schema_ctx = SchemaSettingContext::new(source_info, dest_info, &ctx.mapping, validated)
+        .with_partitioning(ctx.pipeline.destination.partition_by.clone())
+        .with_column_types(ctx.pipeline.destination.column_types.clone())
+        .with_source_timezones(ctx.pipeline.source.timezones.clone())
+        .with_role_map(ctx.pipeline.destination.role_map.clone());
     let mut all_settings: Vec<Box<dyn MigrationSetting>> = Vec::new();
 
     if validated.infer_schema() {
@@ -97,8 +115,28 @@ where
         all_settings.push(Box::new(missing_cols_setting));
     }
 
+    if validated.defer_foreign_keys().is_deferred() {
+        let defer_setting = DeferForeignKeysSetting::new(validated.defer_foreign_keys());
+        all_settings.push(Box::new(defer_setting));
+    }
+
+    if validated.migrate_grants() {
+        let migrate_grants_setting = MigrateGrantsSetting::new(schema_ctx.clone());
+        all_settings.push(Box::new(migrate_grants_setting));
+    }
+
+    if validated.defer_indexes() {
+        let defer_indexes_setting = DeferIndexesSetting::new(schema_ctx.clone());
+        all_settings.push(Box::new(defer_indexes_setting));
+    }
+
+    if validated.fast_load() == FastLoad::Unlogged {
+        let fast_load_setting = FastLoadSetting::new(ctx.destination.name.clone());
+        all_settings.push(Box::new(fast_load_setting));
+    }
+
     // Settings are already created in phase order due to enum ordering
     all_settings.sort_by_key(|s| s.phase());
 
-    all_settings
+    Ok(all_settings)
 }
@@ -7,4 +7,8 @@ pub enum MigrationSettingsPhase {
     CreateMissingTables,
     CreateMissingColumns,
     CascadeSchema,
+    MigrateGrants,
+    DeferIndexes,
+    FastLoad,
+    PostLoad,
 }
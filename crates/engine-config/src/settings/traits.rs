@@ -19,4 +19,8 @@ pub trait MigrationSetting: Send + Sync {
     async fn plan(&mut self, _ctx: &PipelineContext) -> Result<SchemaOps, SettingsError> {
         Ok(SchemaOps::empty())
     }
+
+    /// Rework the ops planned so far, e.g. to move them to a later phase.
+    /// Settings run in phase order, so this sees the ops of every earlier one.
+    fn rework(&self, _ops: &mut SchemaOps) {}
 }
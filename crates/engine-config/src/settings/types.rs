@@ -1,21 +1,47 @@
 use super::value_ext::CanonicalValueMapExt;
-use model::core::value::Value;
+use engine_processing::io::sink::{
+    csv::{FileCompression, FileRotation},
+    file_schema::FileSchema,
+};
+use model::core::{
+    encoding::{ArrayFormat, InvalidUtf8},
+    value::Value,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, time::Duration};
 
 /// Migration settings structure
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub infer_schema: bool,
     pub ignore_constraints: bool,
+    pub migrate_indexes: bool,
+    pub sync_sequences: bool,
+    pub defer_foreign_keys: DeferForeignKeys,
+    pub fast_load: FastLoad,
+    pub defer_indexes: bool,
+    pub migrate_grants: bool,
     pub create_missing_columns: bool,
     pub create_missing_tables: bool,
     pub copy_columns: CopyColumns,
+    pub materialize_generated_columns: bool,
+    pub invalid_utf8: InvalidUtf8,
+    pub array_format: ArrayFormat,
+    pub array_delimiter: String,
     pub batch_size: usize,
+    pub parallel_partitions: usize,
+    pub lob_threshold: Option<usize>,
+    pub auto_widen_columns: bool,
     pub cascade_schema: bool,
+    pub max_identifier_length: Option<usize>,
     pub csv_header: bool,
     pub csv_delimiter: char,
     pub csv_id_column: Option<String>,
+    pub file_max_rows: Option<usize>,
+    pub file_max_bytes: Option<usize>,
+    pub file_max_secs: Option<usize>,
+    pub file_compression: FileCompression,
+    pub file_schema: FileSchema,
 }
 
 impl Settings {
@@ -23,6 +49,27 @@ impl Settings {
         Settings {
             infer_schema: map.get_bool("infer_schema").unwrap_or(false),
             ignore_constraints: map.get_bool("ignore_constraints").unwrap_or(false),
+            migrate_indexes: map.get_bool("migrate_indexes").unwrap_or(false),
+            sync_sequences: map.get_bool("sync_sequences").unwrap_or(false),
+            defer_foreign_keys: map
+                .get_string("defer_foreign_keys")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "OFF" => Some(DeferForeignKeys::Off),
+                    "POST_LOAD" => Some(DeferForeignKeys::PostLoad),
+                    "NOT_VALID" => Some(DeferForeignKeys::NotValid),
+                    _ => None,
+                })
+                .unwrap_or(DeferForeignKeys::Off),
+            fast_load: map
+                .get_string("fast_load")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "OFF" => Some(FastLoad::Off),
+                    "UNLOGGED" => Some(FastLoad::Unlogged),
+                    _ => None,
+                })
+                .unwrap_or(FastLoad::Off),
+            defer_indexes: map.get_bool("defer_indexes").unwrap_or(false),
+            migrate_grants: map.get_bool("migrate_grants").unwrap_or(false),
             create_missing_columns: map.get_bool("create_missing_columns").unwrap_or(false),
             create_missing_tables: map.get_bool("create_missing_tables").unwrap_or(false),
             copy_columns: map
@@ -33,13 +80,74 @@ impl Settings {
                     _ => None,
                 })
                 .unwrap_or(CopyColumns::All),
+            materialize_generated_columns: map
+                .get_bool("materialize_generated_columns")
+                .unwrap_or(false),
+            invalid_utf8: map
+                .get_string("invalid_utf8")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "REPLACE" => Some(InvalidUtf8::Replace),
+                    "LATIN1" => Some(InvalidUtf8::Latin1),
+                    "ERROR" => Some(InvalidUtf8::Error),
+                    _ => None,
+                })
+                .unwrap_or(InvalidUtf8::Replace),
+            array_format: map
+                .get_string("array_format")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "JSON" => Some(ArrayFormat::Json),
+                    "DELIMITED" => Some(ArrayFormat::Delimited),
+                    _ => None,
+                })
+                .unwrap_or(ArrayFormat::Json),
+            array_delimiter: map
+                .get_string("array_delimiter")
+                .unwrap_or_else(|| ",".to_string()),
             batch_size: map.get_usize("batch_size").unwrap_or(0),
+            parallel_partitions: map.get_usize("parallel_partitions").unwrap_or(1),
+            lob_threshold: map.get_usize("lob_threshold"),
+            auto_widen_columns: map.get_bool("auto_widen_columns").unwrap_or(false),
             cascade_schema: map.get_bool("cascade_schema").unwrap_or(false),
+            max_identifier_length: map.get_usize("max_identifier_length"),
             csv_header: map.get_bool("csv_header").unwrap_or(true),
             csv_delimiter: map.get_char("csv_delimiter").unwrap_or(','),
             csv_id_column: map.get_string("csv_id_column"),
+            file_max_rows: map.get_usize("file_max_rows"),
+            file_max_bytes: map.get_usize("file_max_bytes"),
+            file_max_secs: map.get_usize("file_max_secs"),
+            file_compression: map
+                .get_string("file_compression")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "NONE" => Some(FileCompression::None),
+                    "GZIP" => Some(FileCompression::Gzip),
+                    _ => None,
+                })
+                .unwrap_or(FileCompression::None),
+            file_schema: map
+                .get_string("file_schema")
+                .and_then(|s| match s.to_uppercase().as_str() {
+                    "JSON_SCHEMA" => Some(FileSchema::JsonSchema),
+                    "ARROW" => Some(FileSchema::Arrow),
+                    "NONE" => Some(FileSchema::None),
+                    _ => None,
+                })
+                .unwrap_or(FileSchema::JsonSchema),
         }
     }
+
+    /// When a CSV destination starts a new file.
+    pub fn file_rotation(&self) -> FileRotation {
+        FileRotation {
+            max_rows: self.file_max_rows.map(|n| n as u64),
+            max_bytes: self.file_max_bytes.map(|n| n as u64),
+            max_age: self.file_max_secs.map(|n| Duration::from_secs(n as u64)),
+        }
+    }
+
+    /// Whether any setting asks for destination schema to be created.
+    pub fn requires_schema_op(&self) -> bool {
+        self.infer_schema || self.create_missing_tables || self.create_missing_columns
+    }
 }
 
 /// Copy columns strategy
@@ -57,3 +165,48 @@ impl fmt::Display for CopyColumns {
         }
     }
 }
+
+/// When foreign keys of created tables are added
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeferForeignKeys {
+    /// After each pipeline's own data load
+    Off,
+    /// Once every pipeline of the run has loaded
+    PostLoad,
+    /// Once every pipeline has loaded, `NOT VALID` and then validated (Postgres)
+    NotValid,
+}
+
+impl DeferForeignKeys {
+    pub fn is_deferred(&self) -> bool {
+        !matches!(self, DeferForeignKeys::Off)
+    }
+}
+
+impl fmt::Display for DeferForeignKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeferForeignKeys::Off => write!(f, "OFF"),
+            DeferForeignKeys::PostLoad => write!(f, "POST_LOAD"),
+            DeferForeignKeys::NotValid => write!(f, "NOT_VALID"),
+        }
+    }
+}
+
+/// How tables the migration creates are set up for the load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FastLoad {
+    /// As regular tables
+    Off,
+    /// `UNLOGGED` during the load and switched to logged after it (Postgres)
+    Unlogged,
+}
+
+impl fmt::Display for FastLoad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastLoad::Off => write!(f, "OFF"),
+            FastLoad::Unlogged => write!(f, "UNLOGGED"),
+        }
+    }
+}
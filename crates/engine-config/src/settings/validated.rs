@@ -1,7 +1,13 @@
 use std::collections::HashMap;
 
-use crate::settings::CopyColumns;
-use model::{core::value::Value, execution::flags::IntegrityMode};
+use crate::settings::{CopyColumns, DeferForeignKeys, FastLoad};
+use model::{
+    core::{
+        encoding::{ArrayFormat, InvalidUtf8},
+        value::Value,
+    },
+    execution::flags::IntegrityMode,
+};
 use serde::Serialize;
 
 /// Immutable, validated configuration used throughout the migration.
@@ -9,8 +15,19 @@ use serde::Serialize;
 pub struct ValidatedSettings {
     /// Batch size for reading and writing data
     pub batch_size: usize,
+    /// Key ranges the source table is read in concurrently
+    pub parallel_partitions: usize,
     /// Which columns to copy from source to destination
     pub copy_columns: CopyColumns,
+    /// Whether created columns for the source's generated columns are plain
+    /// columns holding the source's computed values
+    pub materialize_generated_columns: bool,
+    /// How text bytes that are not valid UTF-8 are written
+    pub invalid_utf8: InvalidUtf8,
+    /// How arrays are written to destination columns that do not hold arrays
+    pub array_format: ArrayFormat,
+    /// Separator between array elements with `ArrayFormat::Delimited`
+    pub array_delimiter: String,
     /// Whether to infer the entire schema from source
     pub infer_schema: bool,
     /// Whether to create missing tables at destination
@@ -19,6 +36,20 @@ pub struct ValidatedSettings {
     pub create_missing_columns: bool,
     /// Whether to ignore constraints during migration
     pub ignore_constraints: bool,
+    /// Whether to create the source's secondary indexes on created tables
+    pub migrate_indexes: bool,
+    /// Whether to advance the destination's sequences past the loaded keys
+    pub sync_sequences: bool,
+    /// When the foreign keys of created tables are added
+    pub defer_foreign_keys: DeferForeignKeys,
+    /// Whether created tables are loaded unlogged
+    pub fast_load: FastLoad,
+    /// Whether existing destination indexes are dropped for the load and rebuilt after
+    pub defer_indexes: bool,
+    /// Whether the source table's grants and standalone sequences are copied
+    pub migrate_grants: bool,
+    /// Identifier length limit overriding the destination's own
+    pub max_identifier_length: Option<usize>,
     /// Whether this is a dry run (no changes applied)
     pub dry_run: bool,
     /// Integrity hashing mode for this migration run.
@@ -29,11 +60,23 @@ impl ValidatedSettings {
     pub fn default(dry_run: bool) -> Self {
         Self {
             batch_size: 1000,
+            parallel_partitions: 1,
             copy_columns: CopyColumns::All,
+            materialize_generated_columns: false,
+            invalid_utf8: InvalidUtf8::Replace,
+            array_format: ArrayFormat::Json,
+            array_delimiter: ",".to_string(),
             infer_schema: false,
             create_missing_tables: false,
             create_missing_columns: false,
             ignore_constraints: false,
+            migrate_indexes: false,
+            sync_sequences: false,
+            defer_foreign_keys: DeferForeignKeys::Off,
+            fast_load: FastLoad::Off,
+            defer_indexes: false,
+            migrate_grants: false,
+            max_identifier_length: None,
             dry_run,
             integrity: IntegrityMode::Off,
         }
@@ -59,11 +102,23 @@ impl ValidatedSettings {
     pub fn from_builder(builder: ValidatedSettingsBuilder) -> Self {
         Self {
             batch_size: builder.batch_size.unwrap_or(1000),
+            parallel_partitions: builder.parallel_partitions.unwrap_or(1),
             copy_columns: builder.copy_columns.unwrap_or(CopyColumns::All),
+            materialize_generated_columns: builder.materialize_generated_columns.unwrap_or(false),
+            invalid_utf8: builder.invalid_utf8.unwrap_or(InvalidUtf8::Replace),
+            array_format: builder.array_format.unwrap_or(ArrayFormat::Json),
+            array_delimiter: builder.array_delimiter.unwrap_or_else(|| ",".to_string()),
             infer_schema: builder.infer_schema.unwrap_or(false),
             create_missing_tables: builder.create_missing_tables.unwrap_or(false),
             create_missing_columns: builder.create_missing_columns.unwrap_or(false),
             ignore_constraints: builder.ignore_constraints.unwrap_or(false),
+            migrate_indexes: builder.migrate_indexes.unwrap_or(false),
+            sync_sequences: builder.sync_sequences.unwrap_or(false),
+            defer_foreign_keys: builder.defer_foreign_keys.unwrap_or(DeferForeignKeys::Off),
+            fast_load: builder.fast_load.unwrap_or(FastLoad::Off),
+            defer_indexes: builder.defer_indexes.unwrap_or(false),
+            migrate_grants: builder.migrate_grants.unwrap_or(false),
+            max_identifier_length: builder.max_identifier_length,
             dry_run: builder.dry_run,
             integrity: builder.integrity,
         }
@@ -73,10 +128,30 @@ impl ValidatedSettings {
         self.batch_size
     }
 
+    pub fn parallel_partitions(&self) -> usize {
+        self.parallel_partitions
+    }
+
     pub fn copy_columns(&self) -> &CopyColumns {
         &self.copy_columns
     }
 
+    pub fn materialize_generated_columns(&self) -> bool {
+        self.materialize_generated_columns
+    }
+
+    pub fn invalid_utf8(&self) -> InvalidUtf8 {
+        self.invalid_utf8
+    }
+
+    pub fn array_format(&self) -> ArrayFormat {
+        self.array_format
+    }
+
+    pub fn array_delimiter(&self) -> &str {
+        &self.array_delimiter
+    }
+
     pub fn infer_schema(&self) -> bool {
         self.infer_schema
     }
@@ -93,6 +168,34 @@ impl ValidatedSettings {
         self.ignore_constraints
     }
 
+    pub fn migrate_indexes(&self) -> bool {
+        self.migrate_indexes
+    }
+
+    pub fn sync_sequences(&self) -> bool {
+        self.sync_sequences
+    }
+
+    pub fn defer_foreign_keys(&self) -> DeferForeignKeys {
+        self.defer_foreign_keys
+    }
+
+    pub fn fast_load(&self) -> FastLoad {
+        self.fast_load
+    }
+
+    pub fn defer_indexes(&self) -> bool {
+        self.defer_indexes
+    }
+
+    pub fn migrate_grants(&self) -> bool {
+        self.migrate_grants
+    }
+
+    pub fn max_identifier_length(&self) -> Option<usize> {
+        self.max_identifier_length
+    }
+
     pub fn is_dry_run(&self) -> bool {
         self.dry_run
     }
@@ -113,11 +216,23 @@ impl ValidatedSettings {
 #[derive(Debug, Default)]
 pub struct ValidatedSettingsBuilder {
     pub batch_size: Option<usize>,
+    pub parallel_partitions: Option<usize>,
     pub copy_columns: Option<CopyColumns>,
+    pub materialize_generated_columns: Option<bool>,
+    pub invalid_utf8: Option<InvalidUtf8>,
+    pub array_format: Option<ArrayFormat>,
+    pub array_delimiter: Option<String>,
     pub infer_schema: Option<bool>,
     pub create_missing_tables: Option<bool>,
     pub create_missing_columns: Option<bool>,
     pub ignore_constraints: Option<bool>,
+    pub migrate_indexes: Option<bool>,
+    pub sync_sequences: Option<bool>,
+    pub defer_foreign_keys: Option<DeferForeignKeys>,
+    pub fast_load: Option<FastLoad>,
+    pub defer_indexes: Option<bool>,
+    pub migrate_grants: Option<bool>,
+    pub max_identifier_length: Option<usize>,
     pub dry_run: bool,
     pub integrity: IntegrityMode,
 }
@@ -136,11 +251,32 @@ impl ValidatedSettingsBuilder {
         self
     }
 
+    pub fn parallel_partitions(mut self, parallel_partitions: usize) -> Self {
+        self.parallel_partitions = Some(parallel_partitions);
+        self
+    }
+
     pub fn copy_columns(mut self, copy_columns: CopyColumns) -> Self {
         self.copy_columns = Some(copy_columns);
         self
     }
 
+    pub fn materialize_generated_columns(mut self, materialize: bool) -> Self {
+        self.materialize_generated_columns = Some(materialize);
+        self
+    }
+
+    pub fn invalid_utf8(mut self, invalid_utf8: InvalidUtf8) -> Self {
+        self.invalid_utf8 = Some(invalid_utf8);
+        self
+    }
+
+    pub fn array_format(mut self, array_format: ArrayFormat, delimiter: &str) -> Self {
+        self.array_format = Some(array_format);
+        self.array_delimiter = Some(delimiter.to_string());
+        self
+    }
+
     pub fn infer_schema(mut self, infer_schema: bool) -> Self {
         self.infer_schema = Some(infer_schema);
         self
@@ -161,6 +297,41 @@ impl ValidatedSettingsBuilder {
         self
     }
 
+    pub fn migrate_indexes(mut self, migrate_indexes: bool) -> Self {
+        self.migrate_indexes = Some(migrate_indexes);
+        self
+    }
+
+    pub fn sync_sequences(mut self, sync_sequences: bool) -> Self {
+        self.sync_sequences = Some(sync_sequences);
+        self
+    }
+
+    pub fn defer_foreign_keys(mut self, defer_foreign_keys: DeferForeignKeys) -> Self {
+        self.defer_foreign_keys = Some(defer_foreign_keys);
+        self
+    }
+
+    pub fn fast_load(mut self, fast_load: FastLoad) -> Self {
+        self.fast_load = Some(fast_load);
+        self
+    }
+
+    pub fn defer_indexes(mut self, defer_indexes: bool) -> Self {
+        self.defer_indexes = Some(defer_indexes);
+        self
+    }
+
+    pub fn migrate_grants(mut self, migrate_grants: bool) -> Self {
+        self.migrate_grants = Some(migrate_grants);
+        self
+    }
+
+    pub fn max_identifier_length(mut self, max_identifier_length: usize) -> Self {
+        self.max_identifier_length = Some(max_identifier_length);
+        self
+    }
+
     pub fn build(self) -> ValidatedSettings {
         ValidatedSettings::from_builder(self)
     }
@@ -174,6 +345,7 @@ mod tests {
     fn test_default_settings() {
         let settings = ValidatedSettings::default(false);
         assert_eq!(settings.batch_size(), 1000);
+        assert_eq!(settings.parallel_partitions(), 1);
         assert!(!settings.is_dry_run());
         assert!(!settings.requires_schema_op());
     }
@@ -182,13 +354,53 @@ mod tests {
     fn test_builder() {
         let settings = ValidatedSettingsBuilder::new(true, IntegrityMode::BatchHashes)
             .batch_size(500)
+            .parallel_partitions(4)
             .infer_schema(true)
             .create_missing_tables(true)
+            .migrate_indexes(true)
+            .sync_sequences(true)
+            .defer_foreign_keys(DeferForeignKeys::NotValid)
+            .fast_load(FastLoad::Unlogged)
+            .defer_indexes(true)
+            .migrate_grants(true)
+            .materialize_generated_columns(true)
+            .invalid_utf8(InvalidUtf8::Latin1)
+            .array_format(ArrayFormat::Delimited, "|")
             .build();
 
         assert_eq!(settings.batch_size(), 500);
+        assert_eq!(settings.parallel_partitions(), 4);
         assert!(settings.is_dry_run());
         assert!(settings.infer_schema());
+        assert!(settings.migrate_indexes());
+        assert!(settings.sync_sequences());
         assert!(settings.requires_schema_op());
+        assert_eq!(settings.defer_foreign_keys(), DeferForeignKeys::NotValid);
+        assert_eq!(settings.fast_load(), FastLoad::Unlogged);
+        assert!(settings.defer_indexes());
+        assert!(settings.migrate_grants());
+        assert!(settings.materialize_generated_columns());
+        assert_eq!(settings.invalid_utf8(), InvalidUtf8::Latin1);
+        assert_eq!(settings.array_format(), ArrayFormat::Delimited);
+        assert_eq!(settings.array_delimiter(), "|");
+        assert_eq!(
+            ValidatedSettings::default(false).array_format(),
+            ArrayFormat::Json
+        );
+        assert_eq!(
+            ValidatedSettings::default(false).invalid_utf8(),
+            InvalidUtf8::Replace
+        );
+        assert!(!ValidatedSettings::default(false).materialize_generated_columns());
+        assert!(!ValidatedSettings::default(false).migrate_indexes());
+        assert!(!ValidatedSettings::default(false).sync_sequences());
+        assert_eq!(ValidatedSettings::default(false).fast_load(), FastLoad::Off);
+        assert!(!ValidatedSettings::default(false).defer_indexes());
+        assert!(!ValidatedSettings::default(false).migrate_grants());
+        assert!(
+            !ValidatedSettings::default(false)
+                .defer_foreign_keys()
+                .is_deferred()
+        );
     }
 }
@@ -1,13 +1,26 @@
 use crate::settings::{
-    Settings,
+    DeferForeignKeys, FastLoad, Settings,
     error::SettingsError,
     validated::{ValidatedSettings, ValidatedSettingsBuilder},
 };
 use connectors::traits::introspector::SchemaIntrospector;
-use engine_processing::io::{destination::Destination, format::DataFormat, source::Source};
-use model::execution::flags::IntegrityMode;
+use engine_processing::io::{
+    destination::Destination,
+    format::DataFormat,
+    sink::{csv::FileCompression, file_schema::FileSchema},
+    source::Source,
+};
+use model::{core::encoding::ArrayFormat, execution::flags::IntegrityMode};
+use serde::Serialize;
 use tracing::{debug, warn};
 
+/// A setting that is accepted but has no effect with the pipeline's drivers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingFinding {
+    pub setting: &'static str,
+    pub message: String,
+}
+
 /// Validates migration settings before they are applied.
 pub struct SettingsValidator<'a> {
     source: &'a Source,
@@ -41,7 +54,20 @@ impl<'a> SettingsValidator<'a> {
         let mut errors: Vec<String> = Vec::new();
 
         self.validate_batch_size(settings, &mut builder);
+        self.validate_parallel_partitions(settings, &mut builder, &mut errors);
+        self.validate_lob_threshold(settings, &mut errors);
+        self.validate_auto_widen_columns(settings, &mut errors);
         self.validate_copy_columns(settings, &mut builder);
+        self.validate_materialize_generated_columns(settings, &mut builder);
+        self.validate_invalid_utf8(settings, &mut builder);
+        self.validate_array_format(settings, &mut builder, &mut errors);
+        self.validate_max_identifier_length(settings, &mut builder);
+        self.validate_migrate_indexes(settings, &mut builder);
+        self.validate_sync_sequences(settings, &mut builder, &mut errors);
+        self.validate_defer_foreign_keys(settings, &mut builder, &mut errors);
+        self.validate_fast_load(settings, &mut builder, &mut errors);
+        self.validate_defer_indexes(settings, &mut builder, &mut errors);
+        self.validate_migrate_grants(settings, &mut builder, &mut errors);
         self.validate_ignore_constraints(settings, &mut builder, &mut errors);
         self.validate_infer_schema(settings, &mut builder, &mut errors)
             .await?;
@@ -56,6 +82,10 @@ impl<'a> SettingsValidator<'a> {
 
         self.check_conflicts(&builder)?;
 
+        for finding in self.findings(settings) {
+            warn!(setting = finding.setting, "{}", finding.message);
+        }
+
         let validated = builder.build();
         debug!("settings validation completed");
         self.log_validated_settings(&validated);
@@ -63,6 +93,129 @@ impl<'a> SettingsValidator<'a> {
         Ok(validated)
     }
 
+    /// Settings that the source or destination driver will ignore. Only
+    /// settings changed from their defaults are reported.
+    pub fn findings(&self, settings: &Settings) -> Vec<SettingFinding> {
+        let mut findings = Vec::new();
+
+        if self.source.format != DataFormat::Csv {
+            let csv_settings = [
+                ("csv_header", !settings.csv_header),
+                (
+                    "csv_delimiter",
+                    settings.csv_delimiter != ',' && self.destination.format != DataFormat::Csv,
+                ),
+                ("csv_id_column", settings.csv_id_column.is_some()),
+            ];
+            for (setting, is_set) in csv_settings {
+                if is_set {
+                    findings.push(SettingFinding {
+                        setting,
+                        message: format!(
+                            "{setting} only applies to CSV sources and is ignored for a {} source",
+                            self.source.format
+                        ),
+                    });
+                }
+            }
+        }
+
+        if self.destination.format != DataFormat::Csv {
+            let file_settings = [
+                ("file_max_rows", settings.file_max_rows.is_some()),
+                ("file_max_bytes", settings.file_max_bytes.is_some()),
+                ("file_max_secs", settings.file_max_secs.is_some()),
+                (
+                    "file_compression",
+                    settings.file_compression != FileCompression::None,
+                ),
+                (
+                    "file_schema",
+                    settings.file_schema != FileSchema::JsonSchema,
+                ),
+            ];
+            for (setting, is_set) in file_settings {
+                if is_set {
+                    findings.push(SettingFinding {
+                        setting,
+                        message: format!(
+                            "{setting} only applies to CSV destinations and is ignored for a {} destination",
+                            self.destination.format
+                        ),
+                    });
+                }
+            }
+        }
+
+        // Constraints are only left out of schema the migration creates
+        if settings.ignore_constraints && self.supports_ddl() && !settings.requires_schema_op() {
+            findings.push(SettingFinding {
+                setting: "ignore_constraints",
+                message: "ignore_constraints only affects tables and columns created by infer_schema, create_missing_tables or create_missing_columns".to_string(),
+            });
+        }
+
+        // Indexes are only migrated onto tables the migration creates
+        if settings.migrate_indexes
+            && self.supports_ddl()
+            && !(settings.infer_schema || settings.create_missing_tables)
+        {
+            findings.push(SettingFinding {
+                setting: "migrate_indexes",
+                message: "migrate_indexes only affects tables created by infer_schema or create_missing_tables".to_string(),
+            });
+        }
+
+        // Generated columns are only materialized in schema the migration creates
+        if settings.materialize_generated_columns
+            && self.supports_ddl()
+            && !settings.requires_schema_op()
+        {
+            findings.push(SettingFinding {
+                setting: "materialize_generated_columns",
+                message: "materialize_generated_columns only affects tables and columns created by infer_schema, create_missing_tables or create_missing_columns".to_string(),
+            });
+        }
+
+        // Only foreign keys of tables the migration creates are deferred
+        if settings.defer_foreign_keys.is_deferred()
+            && self.supports_ddl()
+            && !(settings.infer_schema || settings.create_missing_tables || settings.cascade_schema)
+        {
+            findings.push(SettingFinding {
+                setting: "defer_foreign_keys",
+                message: "defer_foreign_keys only affects foreign keys of tables created by infer_schema, create_missing_tables or cascade_schema".to_string(),
+            });
+        }
+
+        // Only tables the migration creates are loaded unlogged
+        if settings.fast_load == FastLoad::Unlogged
+            && self.destination.format == DataFormat::Postgres
+            && !(settings.infer_schema || settings.create_missing_tables || settings.cascade_schema)
+        {
+            findings.push(SettingFinding {
+                setting: "fast_load",
+                message: "fast_load only affects tables created by infer_schema, create_missing_tables or cascade_schema".to_string(),
+            });
+        }
+
+        // Grants and sequences are only read from Postgres sources
+        if settings.migrate_grants
+            && self.destination.format == DataFormat::Postgres
+            && self.source.format != DataFormat::Postgres
+        {
+            findings.push(SettingFinding {
+                setting: "migrate_grants",
+                message: format!(
+                    "migrate_grants only copies grants and sequences from Postgres sources, not {}",
+                    self.source.format
+                ),
+            });
+        }
+
+        findings
+    }
+
     fn validate_batch_size(&self, settings: &Settings, builder: &mut ValidatedSettingsBuilder) {
         if settings.batch_size > 0 {
             if settings.batch_size > 100_000 {
@@ -75,10 +228,220 @@ impl<'a> SettingsValidator<'a> {
         }
     }
 
+    fn validate_parallel_partitions(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if settings.parallel_partitions <= 1 {
+            return;
+        }
+        // Partitions are primary key ranges of a database table
+        if !matches!(self.source.format, DataFormat::MySql | DataFormat::Postgres) {
+            errors.push(format!(
+                "parallel_partitions is not supported for {} sources",
+                self.source.format
+            ));
+            return;
+        }
+        // Batch hashes chain in read order, which partitions do not have
+        if self.integrity.is_enabled() {
+            errors.push("parallel_partitions cannot be combined with --integrity".to_string());
+            return;
+        }
+        builder.parallel_partitions = Some(settings.parallel_partitions);
+    }
+
+    fn validate_lob_threshold(&self, settings: &Settings, errors: &mut Vec<String>) {
+        if settings.lob_threshold.is_none() {
+            return;
+        }
+        // Values are streamed by primary key between two database tables
+        let is_sql = |format| matches!(format, DataFormat::MySql | DataFormat::Postgres);
+        if !is_sql(self.source.format) || !is_sql(self.destination.format) {
+            errors.push(format!(
+                "lob_threshold is not supported for {} -> {} migration",
+                self.source.format, self.destination.format
+            ));
+            return;
+        }
+        // Streamed values are written after the batches were hashed
+        if self.integrity.is_enabled() {
+            errors.push("lob_threshold cannot be combined with --integrity".to_string());
+        }
+    }
+
+    fn validate_auto_widen_columns(&self, settings: &Settings, errors: &mut Vec<String>) {
+        if !settings.auto_widen_columns {
+            return;
+        }
+        // Columns are widened with ALTER TABLE on the destination
+        if !matches!(
+            self.destination.format,
+            DataFormat::MySql | DataFormat::Postgres
+        ) {
+            errors.push(format!(
+                "auto_widen_columns is not supported for {} destinations",
+                self.destination.format
+            ));
+        }
+    }
+
     fn validate_copy_columns(&self, settings: &Settings, builder: &mut ValidatedSettingsBuilder) {
         builder.copy_columns = Some(settings.copy_columns);
     }
 
+    fn validate_invalid_utf8(&self, settings: &Settings, builder: &mut ValidatedSettingsBuilder) {
+        builder.invalid_utf8 = Some(settings.invalid_utf8);
+    }
+
+    fn validate_array_format(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if settings.array_format == ArrayFormat::Delimited && settings.array_delimiter.is_empty() {
+            errors.push(
+                "array_delimiter cannot be empty with array_format = \"DELIMITED\"".to_string(),
+            );
+            return;
+        }
+        builder.array_format = Some(settings.array_format);
+        builder.array_delimiter = Some(settings.array_delimiter.clone());
+    }
+
+    fn validate_materialize_generated_columns(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+    ) {
+        if settings.materialize_generated_columns && self.supports_ddl() {
+            builder.materialize_generated_columns = Some(true);
+        }
+    }
+
+    fn validate_max_identifier_length(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+    ) {
+        builder.max_identifier_length = settings.max_identifier_length;
+    }
+
+    fn validate_migrate_indexes(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+    ) {
+        if settings.migrate_indexes && self.supports_ddl() {
+            builder.migrate_indexes = Some(true);
+        }
+    }
+
+    fn validate_sync_sequences(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if settings.sync_sequences {
+            if !self.supports_ddl() {
+                errors.push(format!(
+                    "sync_sequences is not supported for {} destinations",
+                    self.destination.format
+                ));
+                return;
+            }
+            builder.sync_sequences = Some(true);
+        }
+    }
+
+    fn validate_defer_foreign_keys(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if !settings.defer_foreign_keys.is_deferred() || !self.supports_ddl() {
+            return;
+        }
+        // NOT VALID constraints are a Postgres feature
+        if settings.defer_foreign_keys == DeferForeignKeys::NotValid
+            && self.destination.format != DataFormat::Postgres
+        {
+            errors.push(format!(
+                "defer_foreign_keys = \"NOT_VALID\" is not supported for {} destinations (use \"POST_LOAD\")",
+                self.destination.format
+            ));
+            return;
+        }
+        builder.defer_foreign_keys = Some(settings.defer_foreign_keys);
+    }
+
+    fn validate_fast_load(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if settings.fast_load == FastLoad::Off {
+            return;
+        }
+        // UNLOGGED tables are a Postgres feature
+        if self.destination.format != DataFormat::Postgres {
+            errors.push(format!(
+                "fast_load = \"UNLOGGED\" is not supported for {} destinations",
+                self.destination.format
+            ));
+            return;
+        }
+        builder.fast_load = Some(settings.fast_load);
+    }
+
+    fn validate_defer_indexes(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if !settings.defer_indexes {
+            return;
+        }
+        // Indexes are rebuilt with CREATE INDEX CONCURRENTLY, a Postgres feature
+        if self.destination.format != DataFormat::Postgres {
+            errors.push(format!(
+                "defer_indexes is not supported for {} destinations",
+                self.destination.format
+            ));
+            return;
+        }
+        builder.defer_indexes = Some(true);
+    }
+
+    fn validate_migrate_grants(
+        &self,
+        settings: &Settings,
+        builder: &mut ValidatedSettingsBuilder,
+        errors: &mut Vec<String>,
+    ) {
+        if !settings.migrate_grants {
+            return;
+        }
+        if self.destination.format != DataFormat::Postgres {
+            errors.push(format!(
+                "migrate_grants is not supported for {} destinations",
+                self.destination.format
+            ));
+            return;
+        }
+        // Grants and sequences are only read from Postgres sources
+        if self.source.format == DataFormat::Postgres {
+            builder.migrate_grants = Some(true);
+        }
+    }
+
     fn validate_ignore_constraints(
         &self,
         settings: &Settings,
@@ -86,9 +449,11 @@ impl<'a> SettingsValidator<'a> {
         errors: &mut Vec<String>,
     ) {
         if settings.ignore_constraints {
-            if !self.is_sql_destination() {
-                errors
-                    .push("ignore_constraints is only supported for SQL destinations".to_string());
+            if !self.supports_ddl() {
+                errors.push(format!(
+                    "ignore_constraints is not supported for {} destinations",
+                    self.destination.format
+                ));
                 return;
             }
             builder.ignore_constraints = Some(true);
@@ -133,8 +498,11 @@ impl<'a> SettingsValidator<'a> {
             return Ok(());
         }
 
-        if !self.is_sql_destination() {
-            errors.push("create_missing_tables is only supported for SQL destinations".to_string());
+        if !self.supports_ddl() {
+            errors.push(format!(
+                "create_missing_tables is not supported for {} destinations",
+                self.destination.format
+            ));
             return Ok(());
         }
 
@@ -157,9 +525,11 @@ impl<'a> SettingsValidator<'a> {
             return Ok(());
         }
 
-        if !self.is_sql_destination() {
-            errors
-                .push("create_missing_columns is only supported for SQL destinations".to_string());
+        if !self.supports_ddl() {
+            errors.push(format!(
+                "create_missing_columns is not supported for {} destinations",
+                self.destination.format
+            ));
             return Ok(());
         }
 
@@ -201,11 +571,9 @@ impl<'a> SettingsValidator<'a> {
         Ok(())
     }
 
-    fn is_sql_destination(&self) -> bool {
-        matches!(
-            self.destination.format,
-            DataFormat::Postgres | DataFormat::MySql
-        )
+    /// Whether the destination driver can create and alter tables.
+    fn supports_ddl(&self) -> bool {
+        self.destination.format.capabilities().ddl
     }
 
     fn is_supported_schema_inference(&self) -> bool {
@@ -224,11 +592,21 @@ impl<'a> SettingsValidator<'a> {
     fn log_validated_settings(&self, settings: &ValidatedSettings) {
         debug!(
             batch_size = settings.batch_size(),
+            parallel_partitions = settings.parallel_partitions(),
             copy_columns = ?settings.copy_columns(),
+            materialize_generated_columns = settings.materialize_generated_columns(),
+            invalid_utf8 = %settings.invalid_utf8(),
+            array_format = %settings.array_format(),
             infer_schema = settings.infer_schema(),
             create_missing_tables = settings.create_missing_tables(),
             create_missing_columns = settings.create_missing_columns(),
             ignore_constraints = settings.ignore_constraints(),
+            migrate_indexes = settings.migrate_indexes(),
+            sync_sequences = settings.sync_sequences(),
+            defer_foreign_keys = %settings.defer_foreign_keys(),
+            fast_load = %settings.fast_load(),
+            defer_indexes = settings.defer_indexes(),
+            migrate_grants = settings.migrate_grants(),
             dry_run = settings.is_dry_run(),
             "validated settings"
         );
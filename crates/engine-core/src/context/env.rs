@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone)]
 pub struct EnvContext {
@@ -41,6 +41,15 @@ impl EnvContext {
     pub fn merge(&mut self, vars: HashMap<String, String>) {
         self.vars.extend(vars);
     }
+
+    /// A copy of the context with `vars` set over its own
+    pub fn scoped(&self, vars: &BTreeMap<String, String>) -> Self {
+        let mut scoped = self.clone();
+        scoped
+            .vars
+            .extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        scoped
+    }
 }
 
 impl Default for EnvContext {
@@ -1,9 +1,18 @@
 use crate::{
     context::env::EnvContext, drivers::DriverRef, plan::execution::ExecutionPlan,
-    state::sled_store::SledStateStore,
+    state::SharedStateStore,
 };
 use connectors::{
-    drivers::{mysql::driver::MySqlDriver, postgres::driver::PgDriver},
+    drivers::{
+        mysql::{
+            driver::MySqlDriver,
+            snapshot::{BinlogPosition, MySqlReadLock},
+        },
+        postgres::{
+            driver::{DEFAULT_SCHEMA, PgDriver, PgSession},
+            snapshot::PgSnapshot,
+        },
+    },
     error::DriverError,
     traits::driver::Driver,
 };
@@ -18,20 +27,22 @@ pub struct ExecutionContext {
     connection_pool: Arc<RwLock<ConnectionPool>>,
 
     pub run_id: String,
-    pub state: Arc<SledStateStore>,
+    pub state: SharedStateStore,
     pub env: Arc<EnvContext>,
 }
 
 impl ExecutionContext {
     pub async fn new(
         plan: &ExecutionPlan,
-        state: Arc<SledStateStore>,
+        state: SharedStateStore,
         env: Arc<EnvContext>,
     ) -> Result<Self, DriverError> {
         let run_id = plan.run_id();
 
         Ok(ExecutionContext {
-            connection_pool: Arc::new(RwLock::new(ConnectionPool::new())),
+            connection_pool: Arc::new(RwLock::new(ConnectionPool::with_snapshots(
+                plan.source_snapshots.clone(),
+            ))),
             run_id,
             state,
             env,
@@ -63,6 +74,58 @@ impl ExecutionContext {
         pool.get_or_create_mysql(conn).await
     }
 
+    /// Pin `sources` to one point in time for the rest of the run. Every
+    /// MySQL server among them is read-locked while the PostgreSQL sources
+    /// import a freshly exported snapshot and the MySQL sources start a
+    /// consistent snapshot. Only the MySQL servers are held still, so the
+    /// sources read as of the same moment when at most one is PostgreSQL,
+    /// as `check_barriers` requires; further PostgreSQL sources would each
+    /// be pinned a little later than the one before.
+    /// Returns the binary log position each MySQL source was pinned at,
+    /// `None` when the server does not log.
+    pub async fn pin_sources(
+        &self,
+        sources: &[&Connection],
+    ) -> Result<Vec<(String, Option<BinlogPosition>)>, DriverError> {
+        let url = |conn: &Connection| {
+            conn.properties.get_string("url").ok_or_else(|| {
+                DriverError::InvalidUrl(format!("connection '{}' has no url", conn.name))
+            })
+        };
+
+        let mut locks = Vec::new();
+        for conn in sources.iter().filter(|c| c.driver == "mysql") {
+            locks.push((
+                conn.name.clone(),
+                MySqlReadLock::acquire(&url(conn)?).await?,
+            ));
+        }
+
+        let mut pool = self.connection_pool.write().await;
+        for conn in sources {
+            match conn.driver.as_str() {
+                "postgres" | "postgresql" => {
+                    // The importing session keeps the snapshot once it has it
+                    let snapshot = PgSnapshot::export(&url(conn)?).await?;
+                    pool.pin_postgres(conn, snapshot.id()).await?;
+                }
+                "mysql" => {
+                    pool.pin_mysql(conn).await?;
+                }
+                _ => {}
+            }
+        }
+        drop(pool);
+
+        let mut positions = Vec::with_capacity(locks.len());
+        for (name, mut lock) in locks {
+            let position = lock.binlog_position().await?;
+            lock.release().await?;
+            positions.push((name, position));
+        }
+        Ok(positions)
+    }
+
     /// Resolve a connection to a typed `DriverRef`, reusing pooled connections.
     pub async fn resolve_driver(&self, conn: &Connection) -> Result<DriverRef, DriverError> {
         let mut pool = self.connection_pool.write().await;
@@ -74,13 +137,22 @@ impl ExecutionContext {
 pub struct ConnectionPool {
     pg_drivers: HashMap<String, Arc<PgDriver>>,
     mysql_drivers: HashMap<String, Arc<MySqlDriver>>,
+    /// Exported snapshot each pinned PostgreSQL connection reads at
+    snapshots: HashMap<String, String>,
 }
 
 impl ConnectionPool {
     pub fn new() -> Self {
+        Self::with_snapshots(HashMap::new())
+    }
+
+    /// Pool whose PostgreSQL drivers for the given connections import the
+    /// exported snapshot instead of reading the latest data.
+    pub fn with_snapshots(snapshots: HashMap<String, String>) -> Self {
         ConnectionPool {
             pg_drivers: HashMap::new(),
             mysql_drivers: HashMap::new(),
+            snapshots,
         }
     }
 
@@ -115,15 +187,52 @@ impl ConnectionPool {
             .get_string("url")
             .ok_or_else(|| DriverError::InvalidUrl("missing 'url' property".to_string()))?;
 
-        let driver = match conn.properties.get_string("schema") {
-            Some(schema) => PgDriver::connect_with_schema(&url, &schema).await?,
-            None => PgDriver::connect(&url).await?,
+        let schema = conn
+            .properties
+            .get_string("schema")
+            .unwrap_or_else(|| DEFAULT_SCHEMA.to_string());
+        let driver = match self.snapshots.get(&conn.name) {
+            Some(snapshot) => PgDriver::connect_at_snapshot(&url, &schema, snapshot).await?,
+            None => {
+                let session = PgSession {
+                    role: conn.properties.get_string("role"),
+                    settings: conn.session_settings(),
+                };
+                PgDriver::connect_as(&url, &schema, &session).await?
+            }
         };
         let driver = Arc::new(driver);
         self.pg_drivers.insert(conn.name.clone(), driver.clone());
         Ok(driver)
     }
 
+    /// Connect `conn` at the exported `snapshot`, in place of any driver it
+    /// already had.
+    pub async fn pin_postgres(
+        &mut self,
+        conn: &Connection,
+        snapshot: &str,
+    ) -> Result<Arc<PgDriver>, DriverError> {
+        self.pg_drivers.remove(&conn.name);
+        self.snapshots
+            .insert(conn.name.clone(), snapshot.to_string());
+        self.get_or_create_postgres(conn).await
+    }
+
+    /// Connect `conn` in a consistent snapshot transaction, in place of any
+    /// driver it already had.
+    pub async fn pin_mysql(&mut self, conn: &Connection) -> Result<Arc<MySqlDriver>, DriverError> {
+        let url = conn
+            .properties
+            .get_string("url")
+            .ok_or_else(|| DriverError::InvalidUrl("missing 'url' property".to_string()))?;
+
+        let driver =
+            Arc::new(MySqlDriver::connect_at_snapshot(&url, &conn.session_settings()).await?);
+        self.mysql_drivers.insert(conn.name.clone(), driver.clone());
+        Ok(driver)
+    }
+
     /// Get or create a MySQL driver with full type information.
     pub async fn get_or_create_mysql(
         &mut self,
@@ -138,7 +247,8 @@ impl ConnectionPool {
             .get_string("url")
             .ok_or_else(|| DriverError::InvalidUrl("missing 'url' property".to_string()))?;
 
-        let driver = Arc::new(MySqlDriver::connect(&url).await?);
+        let driver = MySqlDriver::connect_with_session(&url, &conn.session_settings()).await?;
+        let driver = Arc::new(driver);
         self.mysql_drivers.insert(conn.name.clone(), driver.clone());
         Ok(driver)
     }
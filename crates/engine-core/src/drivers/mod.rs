@@ -2,7 +2,7 @@ use crate::{context::exec::ConnectionPool, dispatch_driver, schema::type_registr
 use connectors::{
     drivers::{mysql::driver::MySqlDriver, postgres::driver::PgDriver},
     error::DriverError,
-    sql::metadata::table::TableMetadata,
+    sql::metadata::{index::IndexMetadata, table::TableMetadata},
     traits::introspector::SchemaIntrospector,
 };
 use model::execution::connection::Connection;
@@ -51,6 +51,15 @@ impl DriverRef {
         dispatch_driver!(self, |d| Ok(d.table_metadata(table).await?))
     }
 
+    /// Tables of the connection's default schema.
+    pub async fn list_tables(&self) -> Result<Vec<String>, DriverError> {
+        dispatch_driver!(self, |d| Ok(d.list_tables(None).await?))
+    }
+
+    pub async fn index_metadata(&self, table: &str) -> Result<Vec<IndexMetadata>, DriverError> {
+        dispatch_driver!(self, |d| Ok(d.index_metadata(table).await?))
+    }
+
     /// Extract PostgreSQL driver if this is a Postgres variant.
     pub fn as_postgres(&self) -> Option<&Arc<PgDriver>> {
         match self {
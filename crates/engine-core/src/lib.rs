@@ -13,3 +13,4 @@ pub use engine_infra::event_bus;
 pub use engine_infra::metrics;
 pub use engine_infra::progress;
 pub use engine_infra::retry;
+pub use engine_infra::timing;
@@ -1,33 +1,76 @@
-use crate::context::env::EnvContext;
+use crate::{
+    context::env::EnvContext,
+    plan::{
+        settings::{self, check_settings},
+        table_pattern::TABLE_PLACEHOLDER,
+    },
+};
+use connectors::{
+    drivers::{csv::settings::CsvFormat, tls::TlsConfig},
+    registry::driver_capabilities,
+};
 use model::{
-    core::value::Value,
+    core::{
+        timezone::{SourceTimezones, parse_zone},
+        value::Value,
+    },
     execution::{
-        connection::Connection,
+        approval::ApprovalGate,
+        barrier::Barrier,
+        connection::{CSV_BLOCK, Connection, MAX_CONCURRENT_PIPELINES, SESSION_BLOCK, TLS_BLOCK},
         define::DefinitionInfo,
         errors::ConvertError,
         execution_config::{ExecutionConfig, ExecutionStrategy, FailureStrategy},
-        expr::{BinaryOp, CompiledExpression, UnaryOp, WhenBranch},
+        expr::{
+            BinaryOp, CompiledExpression, FN_EXPLODE, FN_RUN_ID, FN_RUN_STARTED_AT, FN_UNNEST,
+            UnaryOp, WhenBranch,
+        },
+        log_config::{LogLevel, PipelineLog},
+        materialized_view::{DEFAULT_REFRESH_ATTEMPTS, MaterializedView},
+        notify_config::{
+            DEFAULT_NOTIFY_TIMEOUT_SECS, DEFAULT_STALL_AFTER_SECS, EmailConfig, IncidentConfig,
+            IncidentService, NotifyConfig, NotifyEvent, NotifyTarget,
+        },
+        partition::{PartitionInterval, PartitionScheme, TablePartitioning},
         pipeline::{
-            BackoffStrategy, DataDestination, DataSource, ErrorHandling, FailedRowsAction,
-            FailedRowsConfig, FailedRowsDestination, FileFormat, Filter, Join, LifecycleHooks,
-            Pagination, Pipeline, PluginTransformCall, RetryConfig, Transformation,
+            Aggregate, AggregateFunction, Aggregation, BackoffStrategy, BatchHook,
+            DEFAULT_SCRIPT_MAX_OPERATIONS, DataDestination, DataSource, ErrorHandling,
+            FailedRowsAction, FailedRowsConfig, FailedRowsDestination, FileFormat, Filter, Join,
+            LifecycleHooks, Pagination, Pipeline, Pivot, PivotDirection, PluginTransformCall,
+            RetryConfig, RowScript, SchemaScope, SeedTable, Sink, Transformation, Unnest,
             ValidationAction, ValidationKind as RuleKind, ValidationRule, ValidationSeverity,
             WriteMode,
         },
         plugin::PluginDecl,
         properties::Properties,
         references::{DataMode, GraphReferences, TraversalDepth},
+        runbook::{Runbook, RunbookStep},
+        state_config::{DEFAULT_REDIS_KEY_PREFIX, StateConfig},
+        verify::{DEFAULT_VERIFY_CHUNK_SIZE, VerifyAsOf, VerifyConfig, VerifyMode},
     },
+    pagination::cursor::QualCol,
 };
 use smql_syntax::ast::{
-    block::{ConnectionBlock, DefineBlock, ExecutionBlock, PluginBlock},
+    block::{
+        BarrierBlock, ConnectionBlock, DefineBlock, ExecutionBlock, MaterializedViewBlock,
+        NotifyBlock, PluginBlock, RunbookBlock, StateBlock,
+    },
     expr::{Expression, ExpressionKind},
     literal::Literal,
     operator::{BinaryOperator, UnaryOperator},
-    pipeline::{FromBlock, PipelineBlock, ToBlock},
+    pipeline::{
+        AggregateBlock, ApproveBlock, FromBlock, LogBlock, NestedBlock, OnBatchBlock,
+        PipelineBlock, PivotBlock, ScriptBlock, SeedBlock, SettingsBlock, ToBlock, TypesBlock,
+        VerifyBlock,
+    },
     validation::ValidationKind,
 };
-use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
 
 // ============================================================
 // Attribute Name Constants
@@ -35,6 +78,11 @@ use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc};
 
 // Connection attributes
 const ATTR_DRIVER: &str = "driver";
+const ATTR_ROLE: &str = "role";
+
+// Drivers loaded from plugins rather than provided by the connectors crate
+const DRIVER_WASM: &str = "wasm";
+const DRIVER_CSV: &str = "csv";
 
 // Execution config attributes
 const ATTR_STRATEGY: &str = "strategy";
@@ -43,19 +91,41 @@ const ATTR_ON_FAILURE: &str = "on_failure";
 const ATTR_PIPELINE_TIMEOUT: &str = "pipeline_timeout";
 const ATTR_TOTAL_TIMEOUT: &str = "total_timeout";
 
+// State block attributes
+const ATTR_BACKEND: &str = "backend";
+const ATTR_URL: &str = "url";
+const ATTR_KEY_PREFIX: &str = "key_prefix";
+const ATTR_TTL: &str = "ttl";
+const ATTR_ARTIFACTS_URL: &str = "artifacts_url";
+
+// Notify block attributes
+const ATTR_EVENTS: &str = "events";
+const ATTR_VALIDATION_THRESHOLD: &str = "validation_threshold";
+const ATTR_TIMEOUT: &str = "timeout";
+const ATTR_TO: &str = "to";
+const ATTR_FROM: &str = "from";
+const ATTR_STALL_AFTER: &str = "stall_after";
+
 // Pipeline attributes
 const ATTR_CONNECTION: &str = "connection";
 const ATTR_TABLE: &str = "table";
+const ATTR_SOURCE_COLUMN: &str = "source_column";
 const ATTR_MODE: &str = "mode";
 const ATTR_STRATEGY_PAGINATION: &str = "strategy";
 const ATTR_CURSOR: &str = "cursor";
 const ATTR_TIEBREAKER: &str = "tiebreaker";
 const ATTR_TIMEZONE: &str = "timezone";
+const ATTR_NULLS: &str = "nulls";
 const ATTR_MAX_ATTEMPTS: &str = "max_attempts";
+const ATTR_DELAY: &str = "delay";
+const ATTR_BACKOFF: &str = "backoff";
+const ATTR_MAX_DELAY: &str = "max_delay";
+const ATTR_JITTER: &str = "jitter";
 const ATTR_ACTION: &str = "action";
 const ATTR_PATH: &str = "path";
 const ATTR_FORMAT: &str = "format";
 const ATTR_SCHEMA: &str = "schema";
+const ATTR_EVERY: &str = "every";
 
 // References block attributes
 const ATTR_DATA: &str = "data";
@@ -74,14 +144,75 @@ const DEPTH_ALL: &str = "all";
 const BLOCK_TABLE: &str = "table";
 const BLOCK_FILE: &str = "file";
 const BLOCK_PIPELINE: &str = "pipeline";
+const BLOCK_MATERIALIZED_VIEW: &str = "materialized_view";
+const BLOCK_PARTITION_BY: &str = "partition_by";
+const BLOCK_ROLES: &str = "roles";
+
+// Materialized view attributes
+const ATTR_AFTER: &str = "after";
+const ATTR_CONCURRENTLY: &str = "concurrently";
+
+// Barrier attributes
+const ATTR_PIPELINES: &str = "pipelines";
+
+// Runbook step attributes
+const ATTR_CONFIG: &str = "config";
+const ATTR_APPROVAL: &str = "approval";
+const ATTR_VERIFY: &str = "verify";
+const ATTR_MAX_FAILED_ROWS: &str = "max_failed_rows";
+
+// Verify block attributes
+const ATTR_KEY: &str = "key";
+const ATTR_CHUNK_SIZE: &str = "chunk_size";
+const ATTR_COLUMNS: &str = "columns";
+const ATTR_AS_OF: &str = "as_of";
+
+// Approve block attributes
+const ATTR_MESSAGE: &str = "message";
+
+// Log block attributes
+const ATTR_LEVEL: &str = "level";
+const ATTR_FILE: &str = "file";
+
+// Script block attributes
+const ATTR_CODE: &str = "code";
+const ATTR_MAX_OPERATIONS: &str = "max_operations";
+
+// Aggregate block attributes
+const ATTR_GROUP_BY: &str = "group_by";
+
+// To block attributes
+const ATTR_ORDER_BY: &str = "order_by";
+
+// Pivot block attributes
+const ATTR_VALUE: &str = "value";
+const ATTR_KEYS: &str = "keys";
+const ATTR_UNPIVOT: &str = "unpivot";
+
+// Partition block attributes
+const ATTR_COLUMN: &str = "column";
+const ATTR_INTERVAL: &str = "interval";
+const ATTR_START: &str = "start";
+const ATTR_END: &str = "end";
+const ATTR_PARTITIONS: &str = "partitions";
+
+// Settings keys read while building the plan
+const SETTING_TIMEZONE: &str = "timezone";
 
 // Keywords
 const KEYWORD_CONNECTION: &str = "connection";
 const KEYWORD_DEFINE: &str = "define";
+const KEYWORD_SETTINGS: &str = "settings";
+const KEYWORD_PAGINATION: &str = "pagination";
+const KEYWORD_RUN: &str = "run";
 
 // Default values
 const DEFAULT_PAGINATION_STRATEGY: &str = "default";
 const DEFAULT_CURSOR: &str = "id";
+const DEFAULT_UNNEST_DELIMITER: &str = ",";
+
+// Most child partitions a partition_by block may create
+const MAX_PARTITIONS: usize = 1024;
 
 // Write modes
 const MODE_INSERT: &str = "insert";
@@ -98,6 +229,12 @@ const ACTION_CONTINUE: &str = "continue";
 // Failed rows actions
 const FAILED_ACTION_LOG: &str = "log";
 const FAILED_ACTION_SAVE_TO_TABLE: &str = "save_to_table";
+const FAILED_ACTION_SAVE_TO_FILE: &str = "save_to_file";
+
+// Retry backoff strategies
+const BACKOFF_FIXED: &str = "fixed";
+const BACKOFF_LINEAR: &str = "linear";
+const BACKOFF_EXPONENTIAL: &str = "exponential";
 
 // File formats
 const FORMAT_JSON: &str = "json";
@@ -106,8 +243,30 @@ const FORMAT_PARQUET: &str = "parquet";
 
 // Error messages
 const ERR_MISSING_DRIVER: &str = "Connection missing driver";
+const ERR_UNSUPPORTED_DRIVER: &str = "connection '{name}': unsupported driver '{driver}'. Must be 'mysql', 'mariadb', 'postgres', 'csv' or 'wasm'";
+const ERR_ROLE_UNSUPPORTED: &str =
+    "connection '{name}': 'role' is only supported for postgres connections";
+const ERR_SESSION_UNSUPPORTED: &str =
+    "connection '{name}': a 'session' block is only supported for postgres and mysql connections";
+const ERR_CSV_BLOCK_DRIVER: &str =
+    "connection '{name}': a csv block is only supported on connections with driver 'csv'";
+const ERR_SESSION_NAME: &str = "connection '{name}': invalid session setting name '{setting}'";
+const ERR_SESSION_VALUE: &str =
+    "connection '{name}': session setting '{setting}' must be a string, number or boolean";
+const ERR_MAX_CONCURRENT_PIPELINES: &str =
+    "connection '{name}': 'max_concurrent_pipelines' must be a positive integer";
 const ERR_MISSING_FROM: &str = "Pipeline missing 'from' block";
 const ERR_MISSING_TO: &str = "Pipeline missing 'to' block";
+const ERR_CONTEXT_REF: &str = "pipeline '{pipeline}': cannot resolve '{reference}': {reason}";
+const ERR_VALIDATE_ONLY_BLOCK: &str = "pipeline '{pipeline}': the '{block}' block needs a 'to' block; a pipeline without one only runs its validate rules";
+const ERR_REQUIRES_ENV: &str =
+    "pipeline '{}': requires_env must be a list of environment variable names";
+const ERR_PIPELINE_ENV: &str =
+    "pipeline '{}': env must be a map of environment variable names to values";
+const ERR_PIPELINE_ENV_VALUE: &str =
+    "pipeline '{pipeline}': env variable '{var}' must be a string, number or boolean";
+const ERR_MISSING_ENV: &str =
+    "pipeline '{pipeline}' requires environment variables that are not set: {vars}";
 const ERR_MISSING_TABLE: &str = "Missing 'table' attribute";
 const ERR_INVALID_STRATEGY: &str =
     "Invalid execution strategy: '{}'. Must be 'sequential' or 'parallel'";
@@ -120,22 +279,755 @@ const ERR_INVALID_FAILURE_STRATEGY: &str =
     "Invalid failure strategy: '{}'. Must be 'fail_fast' or 'continue'";
 const ERR_ON_FAILURE_NOT_STRING: &str = "on_failure must be a string";
 const ERR_TIMEOUT_NOT_STRING: &str = "{} must be a string (e.g., '30s', '5m', '2h')";
+const ERR_INVALID_STATE_BACKEND: &str = "Invalid state backend: '{}'. Must be 'local' or 'redis'";
+const ERR_STATE_ATTR_NOT_STRING: &str = "state {} must be a string";
+const ERR_REDIS_URL_REQUIRED: &str = "state url is required when backend is 'redis'";
+const ERR_ARTIFACTS_URL_SCHEME: &str =
+    "state artifacts_url must start with s3://, gs:// or file://, got '{}'";
+const ERR_NOTIFY_URL_REQUIRED: &str = "notify url is required";
+const ERR_NOTIFY_URL_SCHEME: &str = "notify url must start with http:// or https://, got '{}'";
+const ERR_NOTIFY_EVENTS: &str = "notify events must be a string or an array of strings";
+const ERR_NOTIFY_THRESHOLD: &str = "notify validation_threshold must be a positive integer";
+const ERR_NOTIFY_STALL_AFTER: &str =
+    "notify stall_after must be a positive duration (e.g., '15m', '1h')";
+const ERR_NOTIFY_THRESHOLD_REQUIRED: &str =
+    "notify event 'validation_threshold' requires validation_threshold to be set";
+const ERR_NOTIFY_UNKNOWN_ATTR: &str = "unknown notify attribute '{}'. Must be 'url', 'events', 'validation_threshold', 'stall_after' or 'timeout'";
+const ERR_EMAIL_URL_SCHEME: &str =
+    "notify url must start with smtp:// or smtps:// when the block has a 'to' list, got '{}'";
+const ERR_EMAIL_TO: &str = "notify to must be an address or a non-empty array of addresses";
+const ERR_EMAIL_FROM_REQUIRED: &str = "notify from is required when the block has a 'to' list";
+const ERR_EMAIL_EVENT: &str =
+    "notify event '{}' cannot be emailed; emails are sent for 'completed' and 'failed' runs";
+const ERR_EMAIL_UNKNOWN_ATTR: &str =
+    "unknown notify attribute '{}'. Must be 'url', 'to', 'from', 'events' or 'timeout'";
+const ERR_INCIDENT_KEY: &str = "notify {} must be a non-empty string key";
+const ERR_INCIDENT_SERVICES: &str =
+    "a notify block can page either pagerduty or opsgenie, not both";
+const ERR_INCIDENT_EVENT: &str = "notify event '{}' cannot open an incident; incidents are opened for 'failed', 'stalled' and 'validation_threshold'";
+const ERR_INCIDENT_UNKNOWN_ATTR: &str = "unknown notify attribute '{}'. Must be 'pagerduty', 'opsgenie', 'url', 'events', 'validation_threshold', 'stall_after' or 'timeout'";
+const ERR_INVALID_BACKOFF: &str =
+    "Invalid retry backoff: '{}'. Must be 'fixed', 'linear', 'exponential' or a delay like '5s'";
+const ERR_RETRY_DURATION_NOT_STRING: &str =
+    "retry {} must be a duration string like '500ms' or '5s'";
+const ERR_JITTER_NOT_BOOL: &str = "retry jitter must be true or false";
+const ERR_ON_BATCH_SQL_REQUIRED: &str = "on_batch requires at least one sql statement";
+const ERR_ON_BATCH_EVERY: &str = "on_batch every must be a positive integer";
+const ERR_ON_BATCH_UNKNOWN_ATTR: &str = "unknown on_batch attribute '{}'. Must be 'sql' or 'every'";
+const ERR_VERIFY_MODE: &str =
+    "Invalid verify mode: '{}'. Must be 'count', 'aggregate' or 'checksum'";
+const ERR_VERIFY_KEY: &str = "verify key must be a column name string";
+const ERR_VERIFY_CHUNK_SIZE: &str = "verify chunk_size must be a positive integer";
+const ERR_VERIFY_COLUMNS: &str = "verify columns must be an array of column name strings";
+const ERR_VERIFY_COUNT_COLUMNS: &str = "verify columns cannot be used with mode = \"count\"";
+const ERR_VERIFY_FOREIGN_COLUMN: &str =
+    "verify column '{column}' is not a column of source table '{table}'";
+const ERR_VERIFY_AS_OF: &str = "Invalid verify as_of: '{}'. Must be 'now' or 'snapshot'";
+const ERR_VERIFY_AS_OF_DRIVER: &str =
+    "verify as_of = \"snapshot\" requires a PostgreSQL source; connection '{name}' uses '{driver}'";
+const ERR_VERIFY_AS_OF_SHARED: &str = "verify as_of = \"snapshot\" cannot be used when the source connection '{}' is also the destination";
+const ERR_VERIFY_UNKNOWN_ATTR: &str =
+    "unknown verify attribute '{}'. Must be 'mode', 'key', 'chunk_size', 'columns' or 'as_of'";
+const ERR_APPROVE_MESSAGE: &str = "approve '{}': message must be a string";
+const ERR_APPROVE_UNKNOWN_ATTR: &str =
+    "approve '{gate}': unknown attribute '{attr}'. Must be 'message'";
+const ERR_LOG_LEVEL: &str =
+    "Invalid log level: '{}'. Must be 'error', 'warn', 'info', 'debug' or 'trace'";
+const ERR_LOG_FILE: &str = "log file must be a non-empty string";
+const ERR_LOG_UNKNOWN_ATTR: &str = "unknown log attribute '{}'. Must be 'level' or 'file'";
+const ERR_PARTITION_DRIVER: &str =
+    "partition_by: connection '{name}' ({driver}) does not support partitioned tables";
+const ERR_PARTITION_STRATEGY: &str =
+    "partition_by: invalid strategy '{}'. Must be 'range' or 'hash'";
+const ERR_PARTITION_COLUMN: &str = "partition_by: column must be a column name string";
+const ERR_PARTITION_INTERVAL: &str =
+    "partition_by: invalid interval '{}'. Must be 'day', 'month' or 'year'";
+const ERR_PARTITION_DATE: &str =
+    "partition_by: {attr} must be a date string (YYYY-MM-DD), got '{value}'";
+const ERR_PARTITION_RANGE: &str =
+    "partition_by: strategy 'range' requires start and end, with start before end";
+const ERR_PARTITION_COUNT: &str = "partition_by: partitions must be an integer between 2 and 1024";
+const ERR_PARTITION_TOO_MANY: &str =
+    "partition_by: start and end span more than 1024 partitions; use a longer interval";
+const ERR_PARTITION_ATTR: &str = "partition_by: '{attr}' cannot be used with strategy '{strategy}'";
+const ERR_PARTITION_UNKNOWN_ATTR: &str = "partition_by: unknown attribute '{}'. Must be 'strategy', 'column', 'interval', 'start', 'end' or 'partitions'";
+const ERR_TYPES_VALUE: &str =
+    "types: '{}' must be a destination type string, e.g. \"numeric(12,2)\"";
+const ERR_TYPES_DUPLICATE: &str = "types: column '{}' is given more than once";
+const ERR_ROLES_VALUE: &str = "roles: '{}' must be a non-empty destination role name string";
+const ERR_ROLES_DUPLICATE: &str = "roles: role '{}' is given more than once";
+const ERR_TIMEZONES_VALUE: &str =
+    "timezones: '{}' must be a time zone name such as \"America/New_York\"";
+const ERR_TIMEZONES_DUPLICATE: &str = "timezones: column '{}' is given more than once";
+const ERR_SCRIPT_CODE: &str = "script: code must be a non-empty string";
+const ERR_SCRIPT_MAX_OPERATIONS: &str = "script: max_operations must be a positive integer";
+const ERR_SCRIPT_UNKNOWN_ATTR: &str =
+    "script: unknown attribute '{}'. Must be 'code' or 'max_operations'";
+const ERR_AGGREGATE_GROUP_BY: &str = "aggregate: group_by must be a list of columns";
+const ERR_AGGREGATE_FUNCTION: &str =
+    "aggregate: '{}' must be sum(), count(), min(), max() or avg() of a column";
+const ERR_AGGREGATE_ARGUMENTS: &str =
+    "aggregate: '{}' must take one column; only count() may take none";
+const ERR_AGGREGATE_COLUMN: &str =
+    "aggregate: '{}' is not a column of the source table or one of its joins";
+const ERR_AGGREGATE_DUPLICATE: &str = "aggregate: column '{}' is given more than once";
+const ERR_AGGREGATE_EMPTY: &str = "aggregate: group_by or at least one aggregate is required";
+const ERR_ORDER_BY: &str = "order_by must be a list of columns";
+const ERR_ORDER_BY_COLUMN: &str =
+    "order_by: '{}' is not a column of the source table or one of its joins";
+const ERR_ORDER_BY_DUPLICATE: &str = "order_by: column '{}' is given more than once";
+const ERR_RESHAPE_CONFLICT: &str = "pipeline '{pipeline}': {feature} cannot be used with {block}";
+const ERR_UNNEST_ARGUMENTS: &str = "unnest: '{}' must take a source column and an optional delimiter string, e.g. unnest(posts.tags, \",\")";
+const ERR_UNNEST_COLUMN: &str = "unnest: '{}' is not a column of the source table";
+const ERR_UNNEST_MULTIPLE: &str = "unnest: only one select field may call unnest() or explode()";
+const ERR_SINK_TABLE: &str =
+    "pipeline '{}': every 'to' block needs a table when the pipeline has more than one";
+const ERR_SINK_CONTENT: &str = "pipeline '{pipeline}': a 'to' block after the first takes only 'connection', 'table' and a select block, got '{content}'";
+const ERR_SINK_SELECT: &str = "pipeline '{}': the first 'to' block is mapped by the pipeline's select; only later 'to' blocks take their own";
+const ERR_SINK_CONNECTION: &str = "pipeline '{pipeline}': 'to' table '{table}' uses connection '{connection}', but every 'to' block of a pipeline must write to '{first}'";
+const ERR_SINK_DUPLICATE: &str =
+    "pipeline '{pipeline}': table '{table}' is the destination of more than one 'to' block";
+const ERR_UNION_CONTENT: &str = "pipeline '{pipeline}': a 'from' block after the first takes only 'connection' and 'table', got '{content}'";
+const ERR_UNION_CONNECTION: &str = "pipeline '{pipeline}': 'from' table '{table}' uses connection '{connection}', but every 'from' block of a pipeline must read from '{first}'";
+const ERR_UNION_DUPLICATE: &str =
+    "pipeline '{pipeline}': table '{table}' is the source of more than one 'from' block";
+const ERR_UNION_SOURCE_COLUMN: &str = "from: source_column must be a column name";
+const ERR_PATTERN_TARGET: &str = "pipeline '{pipeline}': 'from' table '{pattern}' is a pattern, so the 'to' table must contain {table} to name a destination for each matching table";
+const ERR_PATTERN_PLACEHOLDER: &str = "pipeline '{pipeline}': 'to' table '{target}' contains {table}, which only a 'from' table pattern such as \"audit_*\" can fill";
+const ERR_SCHEMA_TABLE: &str = "migrate_schema '{}': 'from' and 'to' take no table; include and exclude select the tables to copy";
+const ERR_SCHEMA_PATTERNS: &str = "migrate_schema '{pipeline}': {attr} must be a non-empty list of table patterns such as \"audit_*\"";
+const ERR_PIVOT_NAME: &str = "pivot: {} must be a column name";
+const ERR_PIVOT_LIST: &str = "pivot: {} must be a non-empty list of column names";
+const ERR_PIVOT_UNPIVOT: &str = "pivot: unpivot must be true or false";
+const ERR_PIVOT_MISSING: &str = "pivot: key, value and keys are required";
+const ERR_PIVOT_GROUP_BY: &str = "pivot: group_by cannot be used with unpivot = true";
+const ERR_PIVOT_DUPLICATE: &str = "pivot: column '{}' is given more than once";
+const ERR_PIVOT_UNKNOWN_ATTR: &str =
+    "pivot: unknown attribute '{}'. Must be 'key', 'value', 'keys', 'group_by' or 'unpivot'";
+const ERR_SEED_TABLE: &str = "seed: table must be a non-empty string";
+const ERR_SEED_UNKNOWN_ATTR: &str = "seed: unknown attribute '{}'. Must be 'table' or 'rows'";
+const ERR_SEED_NO_ROWS: &str = "seed '{}': rows must list at least one row";
+const ERR_SEED_DUPLICATE_COLUMN: &str = "seed '{table}': a row sets column '{column}' twice";
+const ERR_SEED_COLUMNS: &str =
+    "seed '{table}': every row must set the same columns as the first ({columns})";
+const ERR_SAVE_TO_FILE_DESTINATION: &str = "failed_rows action 'save_to_file' requires a file block with a .json path or format = \"json\"";
 const ERR_MISSING_CONNECTION: &str = "From block missing connection attribute";
 const ERR_MISSING_TO_CONNECTION: &str = "To block missing connection attribute";
 const ERR_INVALID_PIPELINE_DEPENDENCY: &str =
     "Invalid pipeline dependency reference: {}. Expected format: pipeline.name";
 const ERR_PIPELINE_DEPS_MUST_BE_STRINGS: &str =
     "Pipeline dependencies must be string literals or pipeline references";
+const ERR_VIEW_MISSING_CONNECTION: &str = "materialized_view '{}' missing connection attribute";
+const ERR_VIEW_UNSUPPORTED: &str = "materialized_view '{view}': connection '{name}' ({driver}) does not support materialized views";
+const ERR_INVALID_VIEW_DEPENDENCY: &str = "materialized_view '{view}': invalid dependency '{dep}'. Expected pipeline.name or materialized_view.name";
+const ERR_VIEW_ATTEMPTS: &str = "materialized_view '{}': max_attempts must be a positive integer";
+const ERR_VIEW_CONCURRENTLY: &str = "materialized_view '{}': concurrently must be true or false";
+const ERR_VIEW_UNKNOWN_ATTR: &str = "materialized_view '{view}': unknown attribute '{attr}'. Must be 'connection', 'after', 'concurrently' or 'max_attempts'";
+const ERR_VIEW_UNKNOWN_DEPENDENCY: &str =
+    "materialized_view '{view}' depends on unknown {kind} '{dep}'";
+const ERR_VIEW_DUPLICATE: &str = "materialized_view '{}' is declared more than once";
+const ERR_VIEW_CYCLE: &str = "materialized_views depend on each other in a cycle: {}";
+const ERR_BARRIER_PIPELINES: &str =
+    "barrier '{}': pipelines must be a non-empty list of pipeline references";
+const ERR_BARRIER_UNKNOWN_ATTR: &str =
+    "barrier '{barrier}': unknown attribute '{attr}'. Must be 'pipelines'";
+const ERR_BARRIER_DUPLICATE: &str = "barrier '{}' is declared more than once";
+const ERR_BARRIER_UNKNOWN_PIPELINE: &str =
+    "barrier '{barrier}' names unknown pipeline '{pipeline}'";
+const ERR_BARRIER_SHARED_PIPELINE: &str = "pipeline '{pipeline}' is in barriers '{first}' and '{second}'; a pipeline can be in one barrier";
+const ERR_BARRIER_SHARED_CONNECTION: &str = "connection '{name}' is a source of barriers '{first}' and '{second}'; a connection can be pinned by one barrier";
+const ERR_BARRIER_DRIVER: &str = "barrier '{barrier}': pipeline '{pipeline}' reads connection '{name}' ({driver}); only postgres and mysql sources can be pinned to a snapshot";
+const ERR_BARRIER_WRITTEN: &str = "barrier '{barrier}': connection '{name}' is read at a snapshot, so pipeline '{pipeline}' cannot write to it";
+const ERR_BARRIER_SERVER_CURSOR: &str = "barrier '{barrier}': pipeline '{pipeline}' pages mysql connection '{name}' with a server_cursor, which would hold the connection's only snapshot session";
+const ERR_BARRIER_POSTGRES_SOURCES: &str = "barrier '{barrier}' reads postgres connections '{first}' and '{second}'; postgres snapshots are taken one server at a time, so a barrier can pin one postgres source";
+const ERR_BARRIER_VERIFY_SNAPSHOT: &str = "barrier '{barrier}': connection '{name}' is already pinned by the verify block of pipeline '{pipeline}' (as_of = \"snapshot\")";
+const ERR_RUNBOOK_EMPTY: &str = "runbook '{}' has no steps";
+const ERR_STEP_DUPLICATE: &str = "runbook step '{}' is declared more than once";
+const ERR_STEP_MISSING_CONFIG: &str = "runbook step '{}' missing config attribute";
+const ERR_STEP_CONFIG: &str = "runbook step '{}': config must be a file path string";
+const ERR_STEP_APPROVAL: &str = "runbook step '{}': approval must be true or false";
+const ERR_STEP_VERIFY: &str = "runbook step '{step}': invalid verify mode '{mode}'. Must be 'count', 'aggregate' or 'checksum'";
+const ERR_STEP_MAX_FAILED_ROWS: &str =
+    "runbook step '{}': max_failed_rows must be a non-negative integer";
+const ERR_STEP_UNKNOWN_ATTR: &str = "runbook step '{step}': unknown attribute '{attr}'. Must be 'config', 'approval', 'verify' or 'max_failed_rows'";
 
 // Validation constants
 const MAX_CONCURRENCY_MIN: u32 = 1;
 const MAX_CONCURRENCY_MAX: u32 = 100;
 
+/// Rejects features the source or destination driver does not implement.
+/// Drivers without static capabilities, like WASM plugins, are checked when
+/// they are loaded instead.
+fn check_capabilities(
+    pipeline: &str,
+    source: &DataSource,
+    destination: &DataDestination,
+) -> Result<(), ConvertError> {
+    let unsupported = |conn: &Connection, feature: String| {
+        ConvertError::Plan(format!(
+            "pipeline '{pipeline}': connection '{}' ({}) does not support {feature}",
+            conn.name, conn.driver
+        ))
+    };
+
+    if let (Some(caps), Some(pagination)) = (
+        driver_capabilities(&source.connection.driver),
+        &source.pagination,
+    ) && !caps.supports_pagination(&pagination.strategy)
+    {
+        return Err(unsupported(
+            &source.connection,
+            format!("the '{}' pagination strategy", pagination.strategy),
+        ));
+    }
+
+    if let Some(caps) = driver_capabilities(&destination.connection.driver)
+        && matches!(destination.mode, WriteMode::Upsert)
+        && !caps.upsert
+    {
+        return Err(unsupported(&destination.connection, "upsert".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Rejects features that need the source's own rows when `block` reshapes
+/// them: cascaded references, destination tables created from the source
+/// schema and, unless the rows are still read page by page, key pagination.
+fn check_reshaped(
+    pipeline: &str,
+    block: &str,
+    paged: bool,
+    source: &DataSource,
+    settings: &HashMap<String, Value>,
+) -> Result<(), ConvertError> {
+    let enabled = |key: &str| matches!(settings.get(key), Some(Value::Boolean(true)));
+    let features = [
+        ("a paginate block", !paged && source.pagination.is_some()),
+        ("a references block", source.graph_references.is_some()),
+        ("create_missing_tables", enabled("create_missing_tables")),
+        ("create_missing_columns", enabled("create_missing_columns")),
+        ("infer_schema", enabled("infer_schema")),
+    ];
+    match features.iter().find(|(_, used)| *used) {
+        Some((feature, _)) => Err(ConvertError::Plan(
+            ERR_RESHAPE_CONFLICT
+                .replace("{pipeline}", pipeline)
+                .replace("{feature}", feature)
+                .replace("{block}", block),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// A `from` table pattern is expanded into a pipeline per matching table
+/// once the source can be listed, so it cannot be combined with blocks that
+/// read other tables, and the `to` table must name each destination with
+/// `{table}`. Without a pattern, `{table}` has nothing to stand for.
+/// `destination` is `None` for a validate-only pipeline.
+fn check_table_pattern(
+    pipeline: &str,
+    source: &DataSource,
+    destination: Option<&DataDestination>,
+    sinks: &[Sink],
+) -> Result<(), ConvertError> {
+    let mut targets = destination
+        .map(|d| &d.table)
+        .into_iter()
+        .chain(sinks.iter().map(|s| &s.table));
+    if !source.is_table_pattern() {
+        return match targets.find(|t| t.contains(TABLE_PLACEHOLDER)) {
+            Some(table) => Err(ConvertError::Plan(
+                ERR_PATTERN_PLACEHOLDER
+                    .replace("{pipeline}", pipeline)
+                    .replace("{target}", table),
+            )),
+            None => Ok(()),
+        };
+    }
+
+    let features = [
+        ("more than one 'from' block", !source.union.is_empty()),
+        ("a references block", source.graph_references.is_some()),
+    ];
+    if let Some((feature, _)) = features.iter().find(|(_, used)| *used) {
+        return Err(ConvertError::Plan(
+            ERR_RESHAPE_CONFLICT
+                .replace("{pipeline}", pipeline)
+                .replace("{feature}", feature)
+                .replace("{block}", "a table pattern"),
+        ));
+    }
+    if destination.is_some_and(|d| !d.table.contains(TABLE_PLACEHOLDER)) {
+        return Err(ConvertError::Plan(
+            ERR_PATTERN_TARGET
+                .replace("{pipeline}", pipeline)
+                .replace("{pattern}", &source.table),
+        ));
+    }
+    Ok(())
+}
+
+/// A `migrate_schema` block copies each table as it is, with only the
+/// shared settings and handlers, so it takes no block that selects,
+/// filters or reshapes the rows of one table.
+fn check_schema_scope(block: &PipelineBlock) -> Result<(), ConvertError> {
+    let features = [
+        ("a select block", block.select_block.is_some()),
+        (
+            "a named select block",
+            !block.named_select_blocks.is_empty(),
+        ),
+        ("a where clause", !block.where_clauses.is_empty()),
+        ("a with block", block.with_block.is_some()),
+        ("a paginate block", block.paginate_block.is_some()),
+        ("a types block", block.types_block.is_some()),
+        ("a timezones block", block.timezones_block.is_some()),
+        ("a verify block", block.verify_block.is_some()),
+        ("a seed block", !block.seed_blocks.is_empty()),
+        ("an aggregate block", block.aggregate_block.is_some()),
+        ("a pivot block", block.pivot_block.is_some()),
+        ("more than one 'from' block", !block.unions.is_empty()),
+        ("more than one 'to' block", !block.sinks.is_empty()),
+        (
+            "a references block",
+            block.from.as_ref().is_some_and(|f| f.references.is_some()),
+        ),
+    ];
+    match features.iter().find(|(_, used)| *used) {
+        Some((feature, _)) => Err(ConvertError::Plan(
+            ERR_RESHAPE_CONFLICT
+                .replace("{pipeline}", &block.name)
+                .replace("{feature}", feature)
+                .replace("{block}", "migrate_schema"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Source column an aggregate block names: `table.column`, where `table` is
+/// the source table or a join alias, or a bare source table column.
+fn aggregate_column(expr: &Expression, source: &DataSource) -> Result<QualCol, ConvertError> {
+    let (table, column) = match &expr.kind {
+        ExpressionKind::Identifier(name) => (source.table.clone(), name.clone()),
+        ExpressionKind::DotNotation(path) => match path.segments.as_slice() {
+            [table, column]
+                if *table == source.table || source.joins.iter().any(|j| j.alias == *table) =>
+            {
+                (table.clone(), column.clone())
+            }
+            segments => {
+                return Err(ConvertError::Plan(
+                    ERR_AGGREGATE_COLUMN.replace("{}", &segments.join(".")),
+                ));
+            }
+        },
+        _ => return Err(ConvertError::Plan(ERR_AGGREGATE_GROUP_BY.to_string())),
+    };
+    Ok(QualCol { table, column })
+}
+
+/// Source column an `order_by` list names, like an aggregate block's
+/// `group_by` columns.
+fn order_by_column(expr: &Expression, source: &DataSource) -> Result<QualCol, ConvertError> {
+    let (table, column) = match &expr.kind {
+        ExpressionKind::Identifier(name) => (source.table.clone(), name.clone()),
+        ExpressionKind::DotNotation(path) => match path.segments.as_slice() {
+            [table, column]
+                if *table == source.table || source.joins.iter().any(|j| j.alias == *table) =>
+            {
+                (table.clone(), column.clone())
+            }
+            segments => {
+                return Err(ConvertError::Plan(
+                    ERR_ORDER_BY_COLUMN.replace("{}", &segments.join(".")),
+                ));
+            }
+        },
+        _ => return Err(ConvertError::Plan(ERR_ORDER_BY.to_string())),
+    };
+    Ok(QualCol { table, column })
+}
+
+/// Arguments of an `unnest(...)` or `explode(...)` call.
+fn unnest_arguments(expr: &Expression) -> Option<&[Expression]> {
+    match &expr.kind {
+        ExpressionKind::FunctionCall { name, arguments }
+            if name.eq_ignore_ascii_case(FN_UNNEST) || name.eq_ignore_ascii_case(FN_EXPLODE) =>
+        {
+            Some(arguments)
+        }
+        _ => None,
+    }
+}
+
+/// Column name a pivot block gives, as an identifier or a string.
+fn pivot_name(expr: &Expression) -> Option<String> {
+    match &expr.kind {
+        ExpressionKind::Identifier(name) => Some(name.clone()),
+        ExpressionKind::Literal(Literal::String(s)) if !s.trim().is_empty() => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Error naming the variables `pipeline` requires that are not set.
+pub fn missing_env_message(pipeline: &str, vars: &[String]) -> String {
+    ERR_MISSING_ENV
+        .replace("{pipeline}", pipeline)
+        .replace("{vars}", &vars.join(", "))
+}
+
+/// A pipeline with validate rules and no `to` block only checks its source.
+fn is_validate_only(pipeline_block: &PipelineBlock) -> bool {
+    pipeline_block.to.is_none()
+        && pipeline_block
+            .validate_block
+            .as_ref()
+            .is_some_and(|v| !v.checks.is_empty() || !v.wasm_rules.is_empty())
+}
+
+/// Rejects blocks of a validate-only pipeline that need a destination.
+fn check_validate_only(
+    pipeline_block: &PipelineBlock,
+    source: &DataSource,
+) -> Result<(), ConvertError> {
+    let blocks = [
+        ("before", pipeline_block.before_block.is_some()),
+        ("after", pipeline_block.after_block.is_some()),
+        ("on_batch", pipeline_block.on_batch_block.is_some()),
+        ("verify", pipeline_block.verify_block.is_some()),
+        ("types", pipeline_block.types_block.is_some()),
+        ("seed", !pipeline_block.seed_blocks.is_empty()),
+        ("references", source.graph_references.is_some()),
+    ];
+    match blocks.iter().find(|(_, used)| *used) {
+        Some((block, _)) => Err(ConvertError::Plan(
+            ERR_VALIDATE_ONLY_BLOCK
+                .replace("{pipeline}", &pipeline_block.name)
+                .replace("{block}", block),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// A `settings.*`, `pagination.*` or `run.*` reference.
+enum ContextRef {
+    /// Fixed by the plan
+    Value(Value),
+    /// Known once the run starts; the function it is bound through
+    Run(&'static str),
+}
+
+/// Resolves `scope.field` when `scope` is one of the context keywords.
+fn context_ref(
+    pipeline: &Pipeline,
+    scope: &str,
+    field: &str,
+) -> Result<Option<ContextRef>, ConvertError> {
+    let err = |reason: String| {
+        ConvertError::Plan(
+            ERR_CONTEXT_REF
+                .replace("{pipeline}", &pipeline.name)
+                .replace("{reference}", &format!("{scope}.{field}"))
+                .replace("{reason}", &reason),
+        )
+    };
+
+    let value = match scope {
+        KEYWORD_SETTINGS => settings::effective_value(&pipeline.settings, field).map_err(err)?,
+        KEYWORD_PAGINATION => {
+            let Some(p) = &pipeline.source.pagination else {
+                return Err(err("the pipeline has no paginate block".to_string()));
+            };
+            let text = match field {
+                ATTR_STRATEGY_PAGINATION => Some(p.strategy.clone()),
+                ATTR_CURSOR => Some(p.column.clone()),
+                ATTR_TIEBREAKER => p.tiebreaker.clone(),
+                ATTR_TIMEZONE => p.timezone.clone(),
+                ATTR_NULLS => p.nulls.clone(),
+                _ => {
+                    return Err(err(
+                        "must be one of strategy, cursor, tiebreaker, timezone or nulls"
+                            .to_string(),
+                    ));
+                }
+            };
+            text.map(Value::String).unwrap_or(Value::Null)
+        }
+        KEYWORD_RUN => {
+            return match field {
+                "id" => Ok(Some(ContextRef::Run(FN_RUN_ID))),
+                "started_at" => Ok(Some(ContextRef::Run(FN_RUN_STARTED_AT))),
+                _ => Err(err("must be one of id or started_at".to_string())),
+            };
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(ContextRef::Value(value)))
+}
+
+/// Resolves `settings.*`, `pagination.*` and `run.*` references in the
+/// pipeline's expressions, and `${settings.*}` and `${pagination.*}`
+/// placeholders in its hook SQL. Settings and pagination values are fixed
+/// by the plan and become literals; run values are bound when the pipeline
+/// runs. A source table or join alias of the same name as a keyword keeps
+/// its column references.
+fn resolve_context_refs(pipeline: &mut Pipeline) -> Result<(), ConvertError> {
+    let shadowed = |scope: &str| {
+        pipeline.source.table.eq_ignore_ascii_case(scope)
+            || pipeline
+                .source
+                .joins
+                .iter()
+                .any(|j| j.alias.eq_ignore_ascii_case(scope))
+    };
+
+    let mut resolved = pipeline.clone();
+    for expr in resolved.expressions_mut() {
+        expr.try_rewrite(&mut |e| {
+            let CompiledExpression::DotPath(segments) = e else {
+                return Ok(None);
+            };
+            let [scope, field] = segments.as_slice() else {
+                return Ok(None);
+            };
+            if shadowed(scope) {
+                return Ok(None);
+            }
+            Ok(match context_ref(pipeline, scope, field)? {
+                Some(ContextRef::Value(value)) => Some(CompiledExpression::Literal(value)),
+                Some(ContextRef::Run(name)) => Some(CompiledExpression::FunctionCall {
+                    name: name.to_string(),
+                    args: Vec::new(),
+                }),
+                None => None,
+            })
+        })?;
+    }
+
+    if let Some(lifecycle) = &mut resolved.lifecycle {
+        lifecycle.try_substitute(&mut |name| {
+            let Some((scope, field)) = name.split_once('.') else {
+                return Ok(None);
+            };
+            Ok(match context_ref(pipeline, scope, field)? {
+                Some(ContextRef::Value(Value::Null)) => Some("NULL".to_string()),
+                Some(ContextRef::Value(value)) => value.as_string(),
+                Some(ContextRef::Run(_)) | None => None,
+            })
+        })?;
+    }
+
+    *pipeline = resolved;
+    Ok(())
+}
+
+/// Column name from a verify attribute, with an optional `table.` prefix
+/// naming the source table removed.
+fn verify_column(name: &str, source_table: &str) -> Result<String, ConvertError> {
+    match name.split_once('.') {
+        None => Ok(name.to_string()),
+        Some((table, column)) if table.eq_ignore_ascii_case(source_table) => Ok(column.to_string()),
+        Some(_) => Err(ConvertError::Plan(
+            ERR_VERIFY_FOREIGN_COLUMN
+                .replace("{column}", name)
+                .replace("{table}", source_table),
+        )),
+    }
+}
+
+/// Whether `s` reads as a destination type: a name with optional
+/// modifiers and array brackets, like `numeric(12, 2)` or `text[]`.
+fn is_type_name(s: &str) -> bool {
+    let s = s.trim();
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | ' ' | '(' | ')' | ',' | '[' | ']')
+        })
+}
+
+/// Checks every view's dependencies exist and returns the views in refresh
+/// order: each view after the views it reads, otherwise in declaration order.
+pub(crate) fn order_materialized_views(
+    views: Vec<MaterializedView>,
+    pipelines: &[Pipeline],
+) -> Result<Vec<MaterializedView>, ConvertError> {
+    let mut names = HashSet::new();
+    for view in &views {
+        if !names.insert(view.name.as_str()) {
+            return Err(ConvertError::Plan(
+                ERR_VIEW_DUPLICATE.replace("{}", &view.name),
+            ));
+        }
+    }
+
+    let unknown = |view: &MaterializedView, kind: &str, dep: &str| {
+        ConvertError::Plan(
+            ERR_VIEW_UNKNOWN_DEPENDENCY
+                .replace("{view}", &view.name)
+                .replace("{kind}", kind)
+                .replace("{dep}", dep),
+        )
+    };
+    for view in &views {
+        if let Some(dep) = view
+            .pipelines
+            .iter()
+            .find(|dep| !pipelines.iter().any(|p| &p.name == *dep))
+        {
+            return Err(unknown(view, BLOCK_PIPELINE, dep));
+        }
+        if let Some(dep) = view.views.iter().find(|dep| !names.contains(dep.as_str())) {
+            return Err(unknown(view, BLOCK_MATERIALIZED_VIEW, dep));
+        }
+    }
+
+    let mut pending = views;
+    let mut ordered: Vec<MaterializedView> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|view| {
+            view.views
+                .iter()
+                .all(|dep| ordered.iter().any(|done| &done.name == dep))
+        });
+        match ready {
+            Some(idx) => ordered.push(pending.remove(idx)),
+            None => {
+                let cycle: Vec<&str> = pending.iter().map(|v| v.name.as_str()).collect();
+                return Err(ConvertError::Plan(
+                    ERR_VIEW_CYCLE.replace("{}", &cycle.join(", ")),
+                ));
+            }
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Checks every barrier names known pipelines, each in one barrier, whose
+/// sources can be pinned to a snapshot: postgres or mysql connections that
+/// no pipeline writes to and no other barrier or `verify` block pins. Only
+/// mysql servers are held still while the snapshots are taken, so a barrier
+/// reads at most one postgres connection.
+pub(crate) fn check_barriers(
+    barriers: &[Barrier],
+    pipelines: &[Pipeline],
+) -> Result<(), ConvertError> {
+    let mut names = HashSet::new();
+    let mut members: HashMap<&str, &str> = HashMap::new();
+    let mut pinned: HashMap<&str, &str> = HashMap::new();
+
+    for barrier in barriers {
+        if !names.insert(barrier.name.as_str()) {
+            return Err(ConvertError::Plan(
+                ERR_BARRIER_DUPLICATE.replace("{}", &barrier.name),
+            ));
+        }
+
+        for name in &barrier.pipelines {
+            let Some(pipeline) = pipelines.iter().find(|p| &p.name == name) else {
+                return Err(ConvertError::Plan(
+                    ERR_BARRIER_UNKNOWN_PIPELINE
+                        .replace("{barrier}", &barrier.name)
+                        .replace("{pipeline}", name),
+                ));
+            };
+            if let Some(first) = members.insert(name, &barrier.name) {
+                return Err(ConvertError::Plan(
+                    ERR_BARRIER_SHARED_PIPELINE
+                        .replace("{pipeline}", name)
+                        .replace("{first}", first)
+                        .replace("{second}", &barrier.name),
+                ));
+            }
+
+            let connection = &pipeline.source.connection;
+            let driver = connection.driver.to_lowercase();
+            if !matches!(driver.as_str(), "postgres" | "postgresql" | "mysql") {
+                return Err(ConvertError::Plan(
+                    ERR_BARRIER_DRIVER
+                        .replace("{barrier}", &barrier.name)
+                        .replace("{pipeline}", name)
+                        .replace("{name}", &connection.name)
+                        .replace("{driver}", &connection.driver),
+                ));
+            }
+            // A pinned mysql connection is one session, which a server
+            // cursor would hold until its table is read
+            let server_cursor = pipeline
+                .source
+                .pagination
+                .as_ref()
+                .is_some_and(|p| p.strategy.eq_ignore_ascii_case("server_cursor"));
+            if driver == "mysql" && server_cursor {
+                return Err(ConvertError::Plan(
+                    ERR_BARRIER_SERVER_CURSOR
+                        .replace("{barrier}", &barrier.name)
+                        .replace("{pipeline}", name)
+                        .replace("{name}", &connection.name),
+                ));
+            }
+        }
+
+        let mut postgres: Option<&str> = None;
+        for connection in barrier.sources(pipelines) {
+            if connection.driver.to_lowercase() != "mysql" {
+                if let Some(first) = postgres {
+                    return Err(ConvertError::Plan(
+                        ERR_BARRIER_POSTGRES_SOURCES
+                            .replace("{barrier}", &barrier.name)
+                            .replace("{first}", first)
+                            .replace("{second}", &connection.name),
+                    ));
+                }
+                postgres = Some(&connection.name);
+            }
+            if let Some(first) = pinned.insert(&connection.name, &barrier.name)
+                && first != barrier.name
+            {
+                return Err(ConvertError::Plan(
+                    ERR_BARRIER_SHARED_CONNECTION
+                        .replace("{name}", &connection.name)
+                        .replace("{first}", first)
+                        .replace("{second}", &barrier.name),
+                ));
+            }
+            let error = |template: &str, pipeline: &Pipeline| {
+                ConvertError::Plan(
+                    template
+                        .replace("{barrier}", &barrier.name)
+                        .replace("{name}", &connection.name)
+                        .replace("{pipeline}", &pipeline.name),
+                )
+            };
+            if let Some(writer) = pipelines
+                .iter()
+                .find(|p| p.destination.connection.name == connection.name)
+            {
+                return Err(error(ERR_BARRIER_WRITTEN, writer));
+            }
+            if let Some(verifier) = pipelines.iter().find(|p| {
+                p.source.connection.name == connection.name
+                    && p.verify
+                        .as_ref()
+                        .is_some_and(|v| v.as_of == VerifyAsOf::Snapshot)
+            }) {
+                return Err(error(ERR_BARRIER_VERIFY_SNAPSHOT, verifier));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Convert validated AST to execution plan
+#[derive(Clone)]
 pub struct PlanBuilder {
     // For resolving references
     pub global_definitions: HashMap<String, DefinitionInfo>,
     pub connections: HashMap<String, Connection>,
+    /// Top-level settings, inherited by every pipeline
+    pub global_settings: HashMap<String, Value>,
     pub env: Arc<EnvContext>,
 }
 
@@ -144,6 +1036,7 @@ impl PlanBuilder {
         Self {
             global_definitions: HashMap::new(),
             connections: HashMap::new(),
+            global_settings: HashMap::new(),
             env,
         }
     }
@@ -169,16 +1062,124 @@ impl PlanBuilder {
             nested_configs.insert(nested.kind.clone(), nested_props);
         }
 
+        let driver = properties
+            .get_string(ATTR_DRIVER)
+            .ok_or_else(|| ConvertError::Connection(ERR_MISSING_DRIVER.to_string()))?;
+        if driver_capabilities(&driver).is_none() && !driver.eq_ignore_ascii_case(DRIVER_WASM) {
+            return Err(ConvertError::Connection(
+                ERR_UNSUPPORTED_DRIVER
+                    .replace("{name}", &conn_block.name)
+                    .replace("{driver}", &driver),
+            ));
+        }
+        // The runtime looks drivers up by their lowercase name; MariaDB is
+        // served by the MySQL driver
+        let driver = match driver.to_lowercase().as_str() {
+            "mariadb" => "mysql".to_string(),
+            driver => driver.to_string(),
+        };
+        if properties.get_string(ATTR_ROLE).is_some()
+            && !matches!(driver.as_str(), "postgres" | "postgresql")
+        {
+            return Err(ConvertError::Connection(
+                ERR_ROLE_UNSUPPORTED.replace("{name}", &conn_block.name),
+            ));
+        }
+        if let Some(value) = properties.get(MAX_CONCURRENT_PIPELINES) {
+            // Stored as an unsigned integer, which the executor reads back
+            let max = match *value {
+                Value::Int(n) if n > 0 => n as u64,
+                Value::UInt(n) if n > 0 => n,
+                Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => f as u64,
+                _ => {
+                    return Err(ConvertError::Connection(
+                        ERR_MAX_CONCURRENT_PIPELINES.replace("{name}", &conn_block.name),
+                    ));
+                }
+            };
+            properties.insert(MAX_CONCURRENT_PIPELINES.to_string(), Value::UInt(max));
+        }
+        if let Some(session) = nested_configs.get(SESSION_BLOCK) {
+            Self::validate_session(&conn_block.name, &driver, session)?;
+        }
+        if let Some(csv) = nested_configs.get(CSV_BLOCK) {
+            if !driver.eq_ignore_ascii_case(DRIVER_CSV) {
+                return Err(ConvertError::Connection(
+                    ERR_CSV_BLOCK_DRIVER.replace("{name}", &conn_block.name),
+                ));
+            }
+            CsvFormat::from_block(csv).map_err(|reason| {
+                ConvertError::Connection(format!("connection '{}': {reason}", conn_block.name))
+            })?;
+        }
+        if let Some(block) = nested_configs.get(TLS_BLOCK) {
+            // The drivers read TLS settings from the URL, so every connection
+            // they open honors the block
+            let tls_error = |reason| {
+                ConvertError::Connection(format!("connection '{}': {reason}", conn_block.name))
+            };
+            let tls = TlsConfig::from_block(&driver, block).map_err(tls_error)?;
+            if let Some(url) = properties.get_string(ATTR_URL) {
+                let url = tls.apply_to_url(&driver, &url).map_err(tls_error)?;
+                properties.insert(ATTR_URL.to_string(), Value::String(url));
+            }
+        }
+
         Ok(Connection {
             name: conn_block.name.clone(),
-            driver: properties
-                .get_string(ATTR_DRIVER)
-                .ok_or_else(|| ConvertError::Connection(ERR_MISSING_DRIVER.to_string()))?,
+            driver,
             properties,
             nested_configs,
         })
     }
 
+    /// Session settings are spliced into `SET` statements, so names must be
+    /// plain identifiers and values scalars.
+    fn validate_session(
+        name: &str,
+        driver: &str,
+        session: &HashMap<String, Value>,
+    ) -> Result<(), ConvertError> {
+        if !matches!(
+            driver.to_lowercase().as_str(),
+            "postgres" | "postgresql" | "mysql"
+        ) {
+            return Err(ConvertError::Connection(
+                ERR_SESSION_UNSUPPORTED.replace("{name}", name),
+            ));
+        }
+
+        for (setting, value) in session {
+            let mut chars = setting.chars();
+            let valid_name = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            if !valid_name {
+                return Err(ConvertError::Connection(
+                    ERR_SESSION_NAME
+                        .replace("{name}", name)
+                        .replace("{setting}", setting),
+                ));
+            }
+            if !matches!(
+                value,
+                Value::String(_)
+                    | Value::Int(_)
+                    | Value::UInt(_)
+                    | Value::Float(_)
+                    | Value::Boolean(_)
+            ) {
+                return Err(ConvertError::Connection(
+                    ERR_SESSION_VALUE
+                        .replace("{name}", name)
+                        .replace("{setting}", setting),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub fn build_execution_config(
         &self,
         exec_block: &ExecutionBlock,
@@ -266,58 +1267,611 @@ impl PlanBuilder {
         })
     }
 
-    pub fn build_pipeline(&self, pipeline_block: &PipelineBlock) -> Result<Pipeline, ConvertError> {
-        let source = self.build_source(pipeline_block)?;
-        let destination = self.build_destination(pipeline_block)?;
-        let dependencies = self.build_dependencies(pipeline_block)?;
-        let transformations = self.build_transformations(pipeline_block)?;
-        let named_transformations = self.build_named_transformations(pipeline_block)?;
-        let validation_rules = self.build_validation_rules(pipeline_block)?;
-        let error_handling = self.build_error_handling(pipeline_block)?;
-        let lifecycle = self.build_lifecycle(pipeline_block)?;
-        let settings = self.build_settings(pipeline_block)?;
-        let plugin_transforms = self.build_plugin_transforms(pipeline_block);
+    pub fn build_state_config(
+        &self,
+        state_block: &StateBlock,
+    ) -> Result<StateConfig, ConvertError> {
+        let mut attrs = HashMap::new();
+        for attr in &state_block.attributes {
+            match self.eval_with_definitions(&attr.value)? {
+                Value::String(s) => {
+                    attrs.insert(attr.key.name.as_str(), s);
+                }
+                _ => {
+                    return Err(ConvertError::Plan(
+                        ERR_STATE_ATTR_NOT_STRING.replace("{}", &attr.key.name),
+                    ));
+                }
+            }
+        }
 
-        Ok(Pipeline {
-            name: pipeline_block.name.clone(),
-            description: pipeline_block.description.clone(),
-            dependencies,
-            source,
-            destination,
-            transformations,
-            named_transformations,
-            validations: validation_rules,
-            lifecycle: Some(lifecycle),
-            error_handling: Some(error_handling),
-            settings,
-            plugin_transforms,
-        })
+        let backend = attrs.get(ATTR_BACKEND).map_or("local", String::as_str);
+        match backend.to_lowercase().as_str() {
+            "local" => Ok(match attrs.remove(ATTR_PATH) {
+                Some(path) => StateConfig::LocalPath(path),
+                None => StateConfig::Local,
+            }),
+            "redis" => {
+                let url = attrs
+                    .remove(ATTR_URL)
+                    .ok_or_else(|| ConvertError::Plan(ERR_REDIS_URL_REQUIRED.to_string()))?;
+                let key_prefix = attrs
+                    .remove(ATTR_KEY_PREFIX)
+                    .unwrap_or_else(|| DEFAULT_REDIS_KEY_PREFIX.to_string());
+                let ttl = attrs.get(ATTR_TTL).map(|s| parse_duration(s)).transpose()?;
+                Ok(StateConfig::Redis {
+                    url,
+                    key_prefix,
+                    ttl,
+                })
+            }
+            other => Err(ConvertError::Plan(
+                ERR_INVALID_STATE_BACKEND.replace("{}", other),
+            )),
+        }
     }
 
-    fn build_source(&self, pipeline_block: &PipelineBlock) -> Result<DataSource, ConvertError> {
-        let from = pipeline_block
-            .from
-            .as_ref()
-            .ok_or_else(|| ConvertError::Plan(ERR_MISSING_FROM.to_string()))?;
-
-        let connection = self.resolve_connection_from(from)?;
+    /// Object storage prefix the run's artifacts are uploaded to, from the
+    /// state block's `artifacts_url`
+    pub fn build_artifacts_url(
+        &self,
+        state_block: &StateBlock,
+    ) -> Result<Option<String>, ConvertError> {
+        let Some(attr) = state_block
+            .attributes
+            .iter()
+            .find(|attr| attr.key.name == ATTR_ARTIFACTS_URL)
+        else {
+            return Ok(None);
+        };
 
-        let mut filters = Vec::new();
-        for wc in &pipeline_block.where_clauses {
-            for expr in &wc.conditions {
-                filters.push(Filter {
-                    label: wc.label.clone(),
-                    condition: self.compile_expression(expr)?,
-                });
+        match self.eval_with_definitions(&attr.value)? {
+            Value::String(url)
+                if ["s3://", "gs://", "file://"]
+                    .iter()
+                    .any(|scheme| url.starts_with(scheme)) =>
+            {
+                Ok(Some(url))
             }
+            Value::String(url) => Err(ConvertError::Plan(
+                ERR_ARTIFACTS_URL_SCHEME.replace("{}", &url),
+            )),
+            _ => Err(ConvertError::Plan(
+                ERR_STATE_ATTR_NOT_STRING.replace("{}", ATTR_ARTIFACTS_URL),
+            )),
         }
+    }
 
-        let mut joins = Vec::new();
-        if let Some(with_block) = &pipeline_block.with_block {
-            for j in &with_block.joins {
-                let condition = match &j.condition {
-                    Some(expr) => Some(self.compile_expression(expr)?),
-                    None => None,
+    /// A notify {} block is an email list when it has a `to` attribute and
+    /// a webhook otherwise.
+    pub fn build_notify(&self, notify_block: &NotifyBlock) -> Result<NotifyTarget, ConvertError> {
+        let has = |name: &str| {
+            notify_block
+                .attributes
+                .iter()
+                .any(|attr| attr.key.name == name)
+        };
+        if has(ATTR_TO) {
+            self.build_email_config(notify_block)
+                .map(NotifyTarget::Email)
+        } else if has(IncidentService::PagerDuty.as_str())
+            || has(IncidentService::Opsgenie.as_str())
+        {
+            self.build_incident_config(notify_block)
+                .map(NotifyTarget::Incident)
+        } else {
+            self.build_notify_config(notify_block)
+                .map(NotifyTarget::Webhook)
+        }
+    }
+
+    pub fn build_email_config(
+        &self,
+        notify_block: &NotifyBlock,
+    ) -> Result<EmailConfig, ConvertError> {
+        let mut url = None;
+        let mut from = None;
+        let mut to = Vec::new();
+        let mut events = EmailConfig::EVENTS.to_vec();
+        let mut timeout = DEFAULT_NOTIFY_TIMEOUT_SECS;
+
+        for attr in &notify_block.attributes {
+            let value = self.eval_with_definitions(&attr.value)?;
+            match attr.key.name.as_str() {
+                ATTR_URL => match value {
+                    Value::String(s) if s.starts_with("smtp://") || s.starts_with("smtps://") => {
+                        url = Some(s)
+                    }
+                    Value::String(s) => {
+                        return Err(ConvertError::Plan(ERR_EMAIL_URL_SCHEME.replace("{}", &s)));
+                    }
+                    _ => return Err(ConvertError::Plan(ERR_NOTIFY_URL_REQUIRED.to_string())),
+                },
+                ATTR_TO => {
+                    let addresses = match value {
+                        Value::String(s) => vec![Value::String(s)],
+                        Value::Array(items) => items,
+                        _ => return Err(ConvertError::Plan(ERR_EMAIL_TO.to_string())),
+                    };
+                    for address in addresses {
+                        let Value::String(address) = address else {
+                            return Err(ConvertError::Plan(ERR_EMAIL_TO.to_string()));
+                        };
+                        to.push(address);
+                    }
+                    if to.is_empty() {
+                        return Err(ConvertError::Plan(ERR_EMAIL_TO.to_string()));
+                    }
+                }
+                ATTR_FROM => match value {
+                    Value::String(s) => from = Some(s),
+                    _ => return Err(ConvertError::Plan(ERR_EMAIL_FROM_REQUIRED.to_string())),
+                },
+                ATTR_EVENTS => {
+                    events = parse_notify_events(value)?;
+                    if let Some(event) = events.iter().find(|e| !EmailConfig::EVENTS.contains(e)) {
+                        return Err(ConvertError::Plan(
+                            ERR_EMAIL_EVENT.replace("{}", event.as_str()),
+                        ));
+                    }
+                }
+                ATTR_TIMEOUT => match value {
+                    Value::String(s) => timeout = parse_duration(&s)?,
+                    _ => {
+                        return Err(ConvertError::Plan(
+                            ERR_TIMEOUT_NOT_STRING.replace("{}", ATTR_TIMEOUT),
+                        ));
+                    }
+                },
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_EMAIL_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        Ok(EmailConfig {
+            url: url.ok_or_else(|| ConvertError::Plan(ERR_NOTIFY_URL_REQUIRED.to_string()))?,
+            from: from.ok_or_else(|| ConvertError::Plan(ERR_EMAIL_FROM_REQUIRED.to_string()))?,
+            to,
+            events,
+            timeout,
+        })
+    }
+
+    pub fn build_incident_config(
+        &self,
+        notify_block: &NotifyBlock,
+    ) -> Result<IncidentConfig, ConvertError> {
+        let mut service = None;
+        let mut key = None;
+        let mut url = None;
+        let mut events = None;
+        let mut validation_threshold = None;
+        let mut stall_after = DEFAULT_STALL_AFTER_SECS;
+        let mut timeout = DEFAULT_NOTIFY_TIMEOUT_SECS;
+
+        for attr in &notify_block.attributes {
+            let value = self.eval_with_definitions(&attr.value)?;
+            match attr.key.name.as_str() {
+                name @ ("pagerduty" | "opsgenie") => {
+                    if service.is_some() {
+                        return Err(ConvertError::Plan(ERR_INCIDENT_SERVICES.to_string()));
+                    }
+                    service = Some(if name == IncidentService::PagerDuty.as_str() {
+                        IncidentService::PagerDuty
+                    } else {
+                        IncidentService::Opsgenie
+                    });
+                    match value {
+                        Value::String(s) if !s.trim().is_empty() => key = Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_INCIDENT_KEY.replace("{}", name))),
+                    }
+                }
+                ATTR_URL => match value {
+                    Value::String(s) if s.starts_with("http://") || s.starts_with("https://") => {
+                        url = Some(s)
+                    }
+                    Value::String(s) => {
+                        return Err(ConvertError::Plan(ERR_NOTIFY_URL_SCHEME.replace("{}", &s)));
+                    }
+                    _ => return Err(ConvertError::Plan(ERR_NOTIFY_URL_REQUIRED.to_string())),
+                },
+                ATTR_EVENTS => {
+                    let listed = parse_notify_events(value)?;
+                    if let Some(event) = listed.iter().find(|e| !IncidentConfig::EVENTS.contains(e))
+                    {
+                        return Err(ConvertError::Plan(
+                            ERR_INCIDENT_EVENT.replace("{}", event.as_str()),
+                        ));
+                    }
+                    events = Some(listed);
+                }
+                ATTR_VALIDATION_THRESHOLD => {
+                    validation_threshold = Some(parse_validation_threshold(value)?)
+                }
+                ATTR_STALL_AFTER => stall_after = parse_stall_after(value)?,
+                ATTR_TIMEOUT => match value {
+                    Value::String(s) => timeout = parse_duration(&s)?,
+                    _ => {
+                        return Err(ConvertError::Plan(
+                            ERR_TIMEOUT_NOT_STRING.replace("{}", ATTR_TIMEOUT),
+                        ));
+                    }
+                },
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_INCIDENT_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        // build_notify only calls this for blocks with a service attribute
+        let (Some(service), Some(key)) = (service, key) else {
+            return Err(ConvertError::Plan(
+                ERR_INCIDENT_KEY.replace("{}", IncidentService::PagerDuty.as_str()),
+            ));
+        };
+        if let Some(events) = &events
+            && events.contains(&NotifyEvent::ValidationThreshold)
+            && validation_threshold.is_none()
+        {
+            return Err(ConvertError::Plan(
+                ERR_NOTIFY_THRESHOLD_REQUIRED.to_string(),
+            ));
+        }
+
+        Ok(IncidentConfig {
+            service,
+            key,
+            url: url.unwrap_or_else(|| service.default_url().to_string()),
+            events: events.unwrap_or_else(|| vec![NotifyEvent::Failed, NotifyEvent::Stalled]),
+            validation_threshold,
+            stall_after,
+            timeout,
+        })
+    }
+
+    pub fn build_notify_config(
+        &self,
+        notify_block: &NotifyBlock,
+    ) -> Result<NotifyConfig, ConvertError> {
+        let mut url = None;
+        let mut events = None;
+        let mut validation_threshold = None;
+        let mut stall_after = DEFAULT_STALL_AFTER_SECS;
+        let mut timeout = DEFAULT_NOTIFY_TIMEOUT_SECS;
+
+        for attr in &notify_block.attributes {
+            let value = self.eval_with_definitions(&attr.value)?;
+            match attr.key.name.as_str() {
+                ATTR_URL => match value {
+                    Value::String(s) if s.starts_with("http://") || s.starts_with("https://") => {
+                        url = Some(s)
+                    }
+                    Value::String(s) => {
+                        return Err(ConvertError::Plan(ERR_NOTIFY_URL_SCHEME.replace("{}", &s)));
+                    }
+                    _ => return Err(ConvertError::Plan(ERR_NOTIFY_URL_REQUIRED.to_string())),
+                },
+                ATTR_EVENTS => events = Some(parse_notify_events(value)?),
+                ATTR_VALIDATION_THRESHOLD => {
+                    validation_threshold = Some(parse_validation_threshold(value)?)
+                }
+                ATTR_STALL_AFTER => stall_after = parse_stall_after(value)?,
+                ATTR_TIMEOUT => match value {
+                    Value::String(s) => timeout = parse_duration(&s)?,
+                    _ => {
+                        return Err(ConvertError::Plan(
+                            ERR_TIMEOUT_NOT_STRING.replace("{}", ATTR_TIMEOUT),
+                        ));
+                    }
+                },
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_NOTIFY_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        let url = url.ok_or_else(|| ConvertError::Plan(ERR_NOTIFY_URL_REQUIRED.to_string()))?;
+        if let Some(events) = &events
+            && events.contains(&NotifyEvent::ValidationThreshold)
+            && validation_threshold.is_none()
+        {
+            return Err(ConvertError::Plan(
+                ERR_NOTIFY_THRESHOLD_REQUIRED.to_string(),
+            ));
+        }
+        // Without a threshold the default `validation_threshold` event never
+        // fires. `stalled` is only sent to blocks that list it.
+        let events = events.unwrap_or_else(|| {
+            NotifyEvent::ALL
+                .into_iter()
+                .filter(|e| *e != NotifyEvent::Stalled)
+                .collect()
+        });
+
+        Ok(NotifyConfig {
+            url,
+            events,
+            validation_threshold,
+            stall_after,
+            timeout,
+        })
+    }
+
+    pub fn build_pipeline(&self, pipeline_block: &PipelineBlock) -> Result<Pipeline, ConvertError> {
+        let env = self.build_pipeline_env(pipeline_block)?;
+        if env.is_empty() {
+            return self.build_scoped_pipeline(pipeline_block, env);
+        }
+        // The pipeline's own variables shadow the process environment for
+        // the env() calls of this pipeline only
+        let scoped = PlanBuilder {
+            env: Arc::new(self.env.scoped(&env)),
+            ..self.clone()
+        };
+        scoped.build_scoped_pipeline(pipeline_block, env)
+    }
+
+    fn build_scoped_pipeline(
+        &self,
+        pipeline_block: &PipelineBlock,
+        env: BTreeMap<String, String>,
+    ) -> Result<Pipeline, ConvertError> {
+        let requires_env = self.build_requires_env(pipeline_block)?;
+        let missing = self.unset_env(&requires_env);
+        if !missing.is_empty() {
+            return Err(ConvertError::Plan(missing_env_message(
+                &pipeline_block.name,
+                &missing,
+            )));
+        }
+
+        if pipeline_block.schema_scope.is_some() {
+            check_schema_scope(pipeline_block)?;
+        }
+        let mut source = self.build_source(pipeline_block)?;
+        let validate_only = is_validate_only(pipeline_block);
+        let destination = if validate_only {
+            check_validate_only(pipeline_block, &source)?;
+            // Nothing is written; the source stands in as the destination.
+            DataDestination {
+                connection: source.connection.clone(),
+                table: source.table.clone(),
+                mode: WriteMode::Insert,
+                table_map: HashMap::new(),
+                partition_by: None,
+                column_types: BTreeMap::new(),
+                role_map: BTreeMap::new(),
+                order_by: Vec::new(),
+            }
+        } else {
+            let mut destination = self.build_destination(pipeline_block)?;
+            destination.order_by = self.build_order_by(pipeline_block, &source)?;
+            destination
+        };
+        let dependencies = self.build_dependencies(pipeline_block)?;
+        let transformations = self.build_transformations(pipeline_block)?;
+        let named_transformations = self.build_named_transformations(pipeline_block)?;
+        let sinks = self.build_sinks(pipeline_block, &destination)?;
+        let validation_rules = self.build_validation_rules(pipeline_block)?;
+        let error_handling = self.build_error_handling(pipeline_block)?;
+        let lifecycle = self.build_lifecycle(pipeline_block)?;
+        let settings = self.build_settings(pipeline_block)?;
+        source.timezones = self.build_timezones(pipeline_block, &settings)?;
+        let plugin_transforms = self.build_plugin_transforms(pipeline_block);
+        let verify = pipeline_block
+            .verify_block
+            .as_ref()
+            .map(|block| self.build_verify(block, &source, &destination))
+            .transpose()?;
+        let approval_gate = pipeline_block
+            .approve_block
+            .as_ref()
+            .map(|block| self.build_approval_gate(block, &pipeline_block.name))
+            .transpose()?;
+        let log = pipeline_block
+            .log_block
+            .as_ref()
+            .map(|block| self.build_log(block))
+            .transpose()?;
+        let seeds = pipeline_block
+            .seed_blocks
+            .iter()
+            .map(|block| self.build_seed(block))
+            .collect::<Result<Vec<_>, _>>()?;
+        let script = pipeline_block
+            .script_block
+            .as_ref()
+            .map(|block| self.build_script(block))
+            .transpose()?;
+        let aggregate = pipeline_block
+            .aggregate_block
+            .as_ref()
+            .map(|block| self.build_aggregate(block, &source))
+            .transpose()?;
+        if aggregate.is_some() {
+            check_reshaped(
+                &pipeline_block.name,
+                "an aggregate block",
+                false,
+                &source,
+                &settings,
+            )?;
+        }
+        let pivot = pipeline_block
+            .pivot_block
+            .as_ref()
+            .map(|block| self.build_pivot(block))
+            .transpose()?;
+        if let Some(pivot) = &pivot {
+            // Unpivoted rows are still read a page at a time
+            let paged = pivot.direction == PivotDirection::Unpivot;
+            check_reshaped(
+                &pipeline_block.name,
+                "a pivot block",
+                paged,
+                &source,
+                &settings,
+            )?;
+            if aggregate.is_some() {
+                return Err(ConvertError::Plan(
+                    ERR_RESHAPE_CONFLICT
+                        .replace("{pipeline}", &pipeline_block.name)
+                        .replace("{feature}", "an aggregate block")
+                        .replace("{block}", "a pivot block"),
+                ));
+            }
+        }
+
+        let unnest = self.build_unnest(pipeline_block, &source)?;
+        if unnest.is_some() {
+            // Split rows are still read a page at a time
+            check_reshaped(&pipeline_block.name, "unnest()", true, &source, &settings)?;
+            let reshaped = [
+                ("an aggregate block", aggregate.is_some()),
+                ("a pivot block", pivot.is_some()),
+            ];
+            if let Some((feature, _)) = reshaped.iter().find(|(_, used)| *used) {
+                return Err(ConvertError::Plan(
+                    ERR_RESHAPE_CONFLICT
+                        .replace("{pipeline}", &pipeline_block.name)
+                        .replace("{feature}", feature)
+                        .replace("{block}", "unnest()"),
+                ));
+            }
+        }
+
+        if !sinks.is_empty() {
+            // Sink tables are written as they are; copies of the source rows
+            // cannot follow cascaded references or create tables
+            check_reshaped(
+                &pipeline_block.name,
+                "more than one 'to' block",
+                true,
+                &source,
+                &settings,
+            )?;
+        }
+
+        let partitioned = matches!(
+            settings::effective_value(&settings, "parallel_partitions"),
+            Ok(Value::Int(n)) if n > 1
+        );
+        if !source.union.is_empty() {
+            // The tables after the first are read under its name and a page
+            // at a time; grouping, key ranges and row-count checks would
+            // each see one table only
+            check_reshaped(
+                &pipeline_block.name,
+                "more than one 'from' block",
+                true,
+                &source,
+                &settings,
+            )?;
+            let features = [
+                ("an aggregate block", aggregate.is_some()),
+                ("a verify block", verify.is_some()),
+                ("parallel_partitions", partitioned),
+            ];
+            if let Some((feature, _)) = features.iter().find(|(_, used)| *used) {
+                return Err(ConvertError::Plan(
+                    ERR_RESHAPE_CONFLICT
+                        .replace("{pipeline}", &pipeline_block.name)
+                        .replace("{feature}", feature)
+                        .replace("{block}", "more than one 'from' block"),
+                ));
+            }
+        }
+
+        if !destination.order_by.is_empty() {
+            // Sorted rows are read in one pass and written in that order;
+            // key ranges would be written side by side, and a references
+            // block reads the rows of several tables
+            let features = [
+                ("a references block", source.graph_references.is_some()),
+                ("parallel_partitions", partitioned),
+            ];
+            if let Some((feature, _)) = features.iter().find(|(_, used)| *used) {
+                return Err(ConvertError::Plan(
+                    ERR_RESHAPE_CONFLICT
+                        .replace("{pipeline}", &pipeline_block.name)
+                        .replace("{feature}", feature)
+                        .replace("{block}", "order_by"),
+                ));
+            }
+        }
+
+        check_table_pattern(
+            &pipeline_block.name,
+            &source,
+            (!validate_only).then_some(&destination),
+            &sinks,
+        )?;
+        check_capabilities(&pipeline_block.name, &source, &destination)?;
+
+        let mut pipeline = Pipeline {
+            name: pipeline_block.name.clone(),
+            description: pipeline_block.description.clone(),
+            dependencies,
+            inferred_dependencies: Vec::new(),
+            concurrent_writers: Vec::new(),
+            source,
+            destination,
+            transformations,
+            named_transformations,
+            sinks,
+            validations: validation_rules,
+            lifecycle: Some(lifecycle),
+            error_handling: Some(error_handling),
+            settings,
+            requires_env,
+            env,
+            plugin_transforms,
+            verify,
+            approval_gate,
+            seeds,
+            script,
+            aggregate,
+            pivot,
+            unnest,
+            validate_only,
+            log,
+            run_id: None,
+        };
+        resolve_context_refs(&mut pipeline)?;
+        Ok(pipeline)
+    }
+
+    fn build_source(&self, pipeline_block: &PipelineBlock) -> Result<DataSource, ConvertError> {
+        let from = pipeline_block
+            .from
+            .as_ref()
+            .ok_or_else(|| ConvertError::Plan(ERR_MISSING_FROM.to_string()))?;
+
+        let connection = self.resolve_connection_from(from)?;
+
+        let mut filters = Vec::new();
+        for wc in &pipeline_block.where_clauses {
+            for expr in &wc.conditions {
+                filters.push(Filter {
+                    label: wc.label.clone(),
+                    condition: self.compile_expression(expr)?,
+                });
+            }
+        }
+
+        let mut joins = Vec::new();
+        if let Some(with_block) = &pipeline_block.with_block {
+            for j in &with_block.joins {
+                let condition = match &j.condition {
+                    Some(expr) => Some(self.compile_expression(expr)?),
+                    None => None,
                 };
                 joins.push(Join {
                     alias: j.alias.name.clone(),
@@ -370,37 +1924,115 @@ impl PlanBuilder {
                     _ => None,
                 });
 
+            let nulls = p
+                .attributes
+                .iter()
+                .find(|a| a.key.name == ATTR_NULLS)
+                .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                .and_then(|v| match v {
+                    Value::String(s) => Some(s),
+                    _ => None,
+                });
+
             Pagination {
                 strategy,
                 column: cursor,
                 tiebreaker,
                 timezone,
+                nulls,
             }
         });
 
-        // Extract table name
-        let table = from
-            .attributes
-            .iter()
-            .find(|a| a.key.name == ATTR_TABLE)
-            .and_then(|a| self.eval_with_definitions(&a.value).ok())
-            .and_then(|v| match v {
-                Value::String(s) => Some(s),
-                _ => None,
-            })
-            .ok_or_else(|| ConvertError::Plan(ERR_MISSING_TABLE.to_string()))?;
+        let schema_scope = match &pipeline_block.schema_scope {
+            Some(scope) => Some(self.build_schema_scope(&pipeline_block.name, scope)?),
+            None => None,
+        };
+        let table_attr = from.attributes.iter().find(|a| a.key.name == ATTR_TABLE);
+        let table = if schema_scope.is_some() {
+            if table_attr.is_some() {
+                return Err(ConvertError::Plan(
+                    ERR_SCHEMA_TABLE.replace("{}", &pipeline_block.name),
+                ));
+            }
+            // Every table is listed, then include and exclude pick from them
+            "*".to_string()
+        } else {
+            table_attr
+                .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                .and_then(|v| match v {
+                    Value::String(s) => Some(s),
+                    _ => None,
+                })
+                .ok_or_else(|| ConvertError::Plan(ERR_MISSING_TABLE.to_string()))?
+        };
 
         let graph_references = self.build_graph_references(from)?;
+        let union = self.build_union(pipeline_block, &table, &connection)?;
+        let source_column = match from
+            .attributes
+            .iter()
+            .find(|a| a.key.name == ATTR_SOURCE_COLUMN)
+        {
+            Some(attr) => match self.eval_with_definitions(&attr.value)? {
+                Value::String(s) if !s.trim().is_empty() => Some(s),
+                _ => return Err(ConvertError::Plan(ERR_UNION_SOURCE_COLUMN.to_string())),
+            },
+            None => None,
+        };
 
         Ok(DataSource {
             connection: self.connections.get(&connection).cloned().ok_or_else(|| {
                 ConvertError::Connection(format!("Connection `{}` not found", connection))
             })?,
             table,
+            union,
+            source_column,
             filters,
             joins,
             pagination,
             graph_references,
+            // Set by build_pipeline once the settings are built
+            timezones: SourceTimezones::default(),
+            schema_scope,
+        })
+    }
+
+    /// `include` (every table when omitted) and `exclude` of a
+    /// `migrate_schema` block.
+    fn build_schema_scope(
+        &self,
+        pipeline: &str,
+        scope: &smql_syntax::ast::pipeline::SchemaScope,
+    ) -> Result<SchemaScope, ConvertError> {
+        let patterns = |attr: &str, expr: &Option<Expression>| {
+            let Some(expr) = expr else {
+                return Ok(None);
+            };
+            let err = || {
+                ConvertError::Plan(
+                    ERR_SCHEMA_PATTERNS
+                        .replace("{pipeline}", pipeline)
+                        .replace("{attr}", attr),
+                )
+            };
+            let Value::Array(items) = self.eval_with_definitions(expr)? else {
+                return Err(err());
+            };
+            if items.is_empty() {
+                return Err(err());
+            }
+            items
+                .into_iter()
+                .map(|item| match item {
+                    Value::String(s) if !s.trim().is_empty() => Ok(s.trim().to_string()),
+                    _ => Err(err()),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some)
+        };
+        Ok(SchemaScope {
+            include: patterns("include", &scope.include)?.unwrap_or_else(|| vec!["*".to_string()]),
+            exclude: patterns("exclude", &scope.exclude)?.unwrap_or_default(),
         })
     }
 
@@ -416,16 +2048,24 @@ impl PlanBuilder {
         let connection = self.resolve_connection_to(to)?;
 
         // Extract table name (optional - omitted in schema_only / graph-reference pipelines)
-        let table = to
-            .attributes
-            .iter()
-            .find(|a| a.key.name == ATTR_TABLE)
-            .and_then(|a| self.eval_with_definitions(&a.value).ok())
-            .and_then(|v| match v {
-                Value::String(s) => Some(s),
-                _ => None,
-            })
-            .unwrap_or_default();
+        let table_attr = to.attributes.iter().find(|a| a.key.name == ATTR_TABLE);
+        let table = if pipeline_block.schema_scope.is_some() {
+            if table_attr.is_some() {
+                return Err(ConvertError::Plan(
+                    ERR_SCHEMA_TABLE.replace("{}", &pipeline_block.name),
+                ));
+            }
+            // Each table is copied to a table of the same name
+            TABLE_PLACEHOLDER.to_string()
+        } else {
+            table_attr
+                .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                .and_then(|v| match v {
+                    Value::String(s) => Some(s),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
 
         let mode = to
             .attributes
@@ -446,31 +2086,393 @@ impl PlanBuilder {
 
         let table_map = self.build_table_map(to)?;
 
+        let connection = self.connections.get(&connection).cloned().ok_or_else(|| {
+            ConvertError::Connection(format!("Connection `{}` not found", connection))
+        })?;
+
+        let partition_by = match to
+            .nested_blocks
+            .iter()
+            .find(|b| b.kind == BLOCK_PARTITION_BY)
+        {
+            Some(block) => Some(self.build_partitioning(block, &connection)?),
+            None => None,
+        };
+
+        let column_types = match &pipeline_block.types_block {
+            Some(block) => self.build_column_types(block)?,
+            None => BTreeMap::new(),
+        };
+
+        let role_map = match to.nested_blocks.iter().find(|b| b.kind == BLOCK_ROLES) {
+            Some(block) => self.build_role_map(block)?,
+            None => BTreeMap::new(),
+        };
+
         Ok(DataDestination {
-            connection: self.connections.get(&connection).cloned().ok_or_else(|| {
-                ConvertError::Connection(format!("Connection `{}` not found", connection))
-            })?,
+            connection,
             table,
             mode,
             table_map,
+            partition_by,
+            column_types,
+            role_map,
+            // Read with the source, once it is built
+            order_by: Vec::new(),
         })
     }
 
-    fn build_dependencies(
+    /// Source columns the `to` block's `order_by` list sorts the rows by.
+    fn build_order_by(
         &self,
         pipeline_block: &PipelineBlock,
-    ) -> Result<Vec<String>, ConvertError> {
-        // Extract dependencies from the 'after' field which contains pipeline names
-        // The 'after' field is Vec<Expression>, where each expression is typically an array
-        if let Some(after) = &pipeline_block.after {
-            let mut deps = Vec::new();
-            for expr in after {
-                match &expr.kind {
-                    // Handle array of dependencies: after = [pipeline.name1, pipeline.name2]
-                    ExpressionKind::Array(items) => {
-                        for item in items {
-                            match &item.kind {
-                                // String literal in array: after = ["pipeline1"]
+        source: &DataSource,
+    ) -> Result<Vec<QualCol>, ConvertError> {
+        let Some(attr) = pipeline_block
+            .to
+            .as_ref()
+            .and_then(|to| to.attributes.iter().find(|a| a.key.name == ATTR_ORDER_BY))
+        else {
+            return Ok(Vec::new());
+        };
+        let ExpressionKind::Array(items) = &attr.value.kind else {
+            return Err(ConvertError::Plan(ERR_ORDER_BY.to_string()));
+        };
+        if items.is_empty() {
+            return Err(ConvertError::Plan(ERR_ORDER_BY.to_string()));
+        }
+
+        let mut order_by: Vec<QualCol> = Vec::new();
+        for item in items {
+            let column = order_by_column(item, source)?;
+            if order_by.iter().any(|c| c.column == column.column) {
+                return Err(ConvertError::Plan(
+                    ERR_ORDER_BY_DUPLICATE.replace("{}", &column.column),
+                ));
+            }
+            order_by.push(column);
+        }
+        Ok(order_by)
+    }
+
+    /// Destination role of each source role of a roles {} block.
+    fn build_role_map(
+        &self,
+        block: &NestedBlock,
+    ) -> Result<BTreeMap<String, String>, ConvertError> {
+        let mut roles = BTreeMap::new();
+
+        for attr in &block.attributes {
+            let role = &attr.key.name;
+            let mapped = match self.eval_with_definitions(&attr.value)? {
+                Value::String(s) if !s.trim().is_empty() => s.trim().to_string(),
+                _ => return Err(ConvertError::Plan(ERR_ROLES_VALUE.replace("{}", role))),
+            };
+            if roles.insert(role.clone(), mapped).is_some() {
+                return Err(ConvertError::Plan(ERR_ROLES_DUPLICATE.replace("{}", role)));
+            }
+        }
+
+        Ok(roles)
+    }
+
+    /// Destination column types of a types {} block. The type is written
+    /// into the CREATE TABLE as is, so only characters a type name can hold
+    /// are accepted.
+    fn build_column_types(
+        &self,
+        block: &TypesBlock,
+    ) -> Result<BTreeMap<String, String>, ConvertError> {
+        let mut types = BTreeMap::new();
+
+        for attr in &block.attributes {
+            let column = &attr.key.name;
+            let data_type = match self.eval_with_definitions(&attr.value)? {
+                Value::String(s) if is_type_name(&s) => s.trim().to_string(),
+                _ => return Err(ConvertError::Plan(ERR_TYPES_VALUE.replace("{}", column))),
+            };
+            if types.insert(column.clone(), data_type).is_some() {
+                return Err(ConvertError::Plan(
+                    ERR_TYPES_DUPLICATE.replace("{}", column),
+                ));
+            }
+        }
+
+        Ok(types)
+    }
+
+    /// Time zones of the source's timestamps without an offset: the
+    /// `timezone` setting, overridden per column by the timezones {} block.
+    fn build_timezones(
+        &self,
+        pipeline_block: &PipelineBlock,
+        settings: &HashMap<String, Value>,
+    ) -> Result<SourceTimezones, ConvertError> {
+        // The setting's value was checked with the other settings
+        let default = match settings.get(SETTING_TIMEZONE) {
+            Some(Value::String(zone)) => Some(zone.clone()),
+            _ => None,
+        };
+
+        let mut columns = BTreeMap::new();
+        if let Some(block) = &pipeline_block.timezones_block {
+            for attr in &block.attributes {
+                let column = &attr.key.name;
+                let zone = match self.eval_with_definitions(&attr.value)? {
+                    Value::String(zone) if parse_zone(&zone).is_some() => zone,
+                    _ => {
+                        return Err(ConvertError::Plan(
+                            ERR_TIMEZONES_VALUE.replace("{}", column),
+                        ));
+                    }
+                };
+                if columns.insert(column.clone(), zone).is_some() {
+                    return Err(ConvertError::Plan(
+                        ERR_TIMEZONES_DUPLICATE.replace("{}", column),
+                    ));
+                }
+            }
+        }
+
+        Ok(SourceTimezones { default, columns })
+    }
+
+    /// Range or hash partitioning of a destination table the migration
+    /// creates. Only PostgreSQL destinations have declarative partitioning.
+    fn build_partitioning(
+        &self,
+        block: &NestedBlock,
+        connection: &Connection,
+    ) -> Result<TablePartitioning, ConvertError> {
+        if !matches!(connection.driver.as_str(), "postgres" | "postgresql") {
+            return Err(ConvertError::Plan(
+                ERR_PARTITION_DRIVER
+                    .replace("{name}", &connection.name)
+                    .replace("{driver}", &connection.driver),
+            ));
+        }
+
+        let mut strategy = None;
+        let mut column = None;
+        let mut interval = PartitionInterval::Month;
+        let mut start = None;
+        let mut end = None;
+        let mut partitions = None;
+
+        let date = |attr: &str, value: Value| match value {
+            Value::String(s) => TablePartitioning::parse_bound(&s).ok_or_else(|| {
+                ConvertError::Plan(
+                    ERR_PARTITION_DATE
+                        .replace("{attr}", attr)
+                        .replace("{value}", &s),
+                )
+            }),
+            other => Err(ConvertError::Plan(
+                ERR_PARTITION_DATE
+                    .replace("{attr}", attr)
+                    .replace("{value}", &format!("{:?}", other)),
+            )),
+        };
+
+        for attr in &block.attributes {
+            let name = attr.key.name.as_str();
+            let value = self.eval_with_definitions(&attr.value)?;
+            match name {
+                ATTR_STRATEGY => {
+                    strategy = match value {
+                        Value::String(s) => Some(s.to_lowercase()),
+                        other => {
+                            return Err(ConvertError::Plan(
+                                ERR_PARTITION_STRATEGY.replace("{}", &format!("{:?}", other)),
+                            ));
+                        }
+                    };
+                }
+                ATTR_COLUMN => {
+                    column = match value {
+                        Value::String(s) if !s.trim().is_empty() => Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_PARTITION_COLUMN.to_string())),
+                    };
+                }
+                ATTR_INTERVAL => {
+                    interval = match value {
+                        Value::String(s) => PartitionInterval::from_str(&s).map_err(|_| {
+                            ConvertError::Plan(ERR_PARTITION_INTERVAL.replace("{}", &s))
+                        })?,
+                        other => {
+                            return Err(ConvertError::Plan(
+                                ERR_PARTITION_INTERVAL.replace("{}", &format!("{:?}", other)),
+                            ));
+                        }
+                    };
+                }
+                ATTR_START => start = Some(date(name, value)?),
+                ATTR_END => end = Some(date(name, value)?),
+                ATTR_PARTITIONS => {
+                    partitions = match value {
+                        Value::Int(n) if n >= 2 && n as usize <= MAX_PARTITIONS => Some(n as u32),
+                        Value::UInt(n) if n >= 2 && n as usize <= MAX_PARTITIONS => Some(n as u32),
+                        Value::Float(f)
+                            if f >= 2.0 && f <= MAX_PARTITIONS as f64 && f.fract() == 0.0 =>
+                        {
+                            Some(f as u32)
+                        }
+                        _ => return Err(ConvertError::Plan(ERR_PARTITION_COUNT.to_string())),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_PARTITION_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        let column = column.ok_or_else(|| ConvertError::Plan(ERR_PARTITION_COLUMN.to_string()))?;
+        let strategy = strategy.unwrap_or_default();
+        let misplaced = |attrs: &[&str]| {
+            block
+                .attributes
+                .iter()
+                .find(|a| attrs.contains(&a.key.name.as_str()))
+                .map(|a| {
+                    ConvertError::Plan(
+                        ERR_PARTITION_ATTR
+                            .replace("{attr}", &a.key.name)
+                            .replace("{strategy}", &strategy),
+                    )
+                })
+        };
+
+        let scheme = match strategy.as_str() {
+            "range" => {
+                if let Some(err) = misplaced(&[ATTR_PARTITIONS]) {
+                    return Err(err);
+                }
+                match (start, end) {
+                    (Some(start), Some(end)) if start < end => PartitionScheme::Range {
+                        interval,
+                        start,
+                        end,
+                    },
+                    _ => return Err(ConvertError::Plan(ERR_PARTITION_RANGE.to_string())),
+                }
+            }
+            "hash" => {
+                if let Some(err) = misplaced(&[ATTR_INTERVAL, ATTR_START, ATTR_END]) {
+                    return Err(err);
+                }
+                PartitionScheme::Hash {
+                    partitions: partitions
+                        .ok_or_else(|| ConvertError::Plan(ERR_PARTITION_COUNT.to_string()))?,
+                }
+            }
+            other => {
+                return Err(ConvertError::Plan(
+                    ERR_PARTITION_STRATEGY.replace("{}", other),
+                ));
+            }
+        };
+
+        let partitioning = TablePartitioning { column, scheme };
+        if partitioning.partitions("").len() > MAX_PARTITIONS {
+            return Err(ConvertError::Plan(ERR_PARTITION_TOO_MANY.to_string()));
+        }
+        Ok(partitioning)
+    }
+
+    /// Variables listed in the pipeline's `requires_env`
+    fn build_requires_env(&self, block: &PipelineBlock) -> Result<Vec<String>, ConvertError> {
+        let Some(expr) = &block.requires_env else {
+            return Ok(Vec::new());
+        };
+        let err = || ConvertError::Plan(ERR_REQUIRES_ENV.replace("{}", &block.name));
+        let ExpressionKind::Array(items) = &expr.kind else {
+            return Err(err());
+        };
+        items
+            .iter()
+            .map(|item| match &item.kind {
+                ExpressionKind::Literal(Literal::String(name)) if !name.trim().is_empty() => {
+                    Ok(name.trim().to_string())
+                }
+                _ => Err(err()),
+            })
+            .collect()
+    }
+
+    /// The pipeline's own `env` variables. Values are evaluated against the
+    /// process environment, so `env = { DB = env("EU_DB") }` renames a
+    /// variable for one pipeline.
+    fn build_pipeline_env(
+        &self,
+        block: &PipelineBlock,
+    ) -> Result<BTreeMap<String, String>, ConvertError> {
+        let Some(expr) = &block.env else {
+            return Ok(BTreeMap::new());
+        };
+        let ExpressionKind::Map(entries) = &expr.kind else {
+            return Err(ConvertError::Plan(
+                ERR_PIPELINE_ENV.replace("{}", &block.name),
+            ));
+        };
+        let mut vars = BTreeMap::new();
+        for entry in entries {
+            let value = match self.eval_with_definitions(&entry.value)? {
+                value @ (Value::String(_)
+                | Value::Int(_)
+                | Value::UInt(_)
+                | Value::Float(_)
+                | Value::Boolean(_)) => value.as_string().unwrap_or_default(),
+                _ => {
+                    return Err(ConvertError::Plan(
+                        ERR_PIPELINE_ENV_VALUE
+                            .replace("{pipeline}", &block.name)
+                            .replace("{var}", &entry.key),
+                    ));
+                }
+            };
+            vars.insert(entry.key.clone(), value);
+        }
+        Ok(vars)
+    }
+
+    /// Variables of `names` that are not set
+    fn unset_env(&self, names: &[String]) -> Vec<String> {
+        names
+            .iter()
+            .filter(|name| self.env.get(name).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Variables the pipeline's `requires_env` lists that neither the
+    /// environment nor the pipeline's `env` sets
+    pub fn missing_env(&self, block: &PipelineBlock) -> Result<Vec<String>, ConvertError> {
+        let required = self.build_requires_env(block)?;
+        let env = self.build_pipeline_env(block)?;
+        Ok(self
+            .unset_env(&required)
+            .into_iter()
+            .filter(|name| !env.contains_key(name))
+            .collect())
+    }
+
+    fn build_dependencies(
+        &self,
+        pipeline_block: &PipelineBlock,
+    ) -> Result<Vec<String>, ConvertError> {
+        // Extract dependencies from the 'after' field which contains pipeline names
+        // The 'after' field is Vec<Expression>, where each expression is typically an array
+        if let Some(after) = &pipeline_block.after {
+            let mut deps = Vec::new();
+            for expr in after {
+                match &expr.kind {
+                    // Handle array of dependencies: after = [pipeline.name1, pipeline.name2]
+                    ExpressionKind::Array(items) => {
+                        for item in items {
+                            match &item.kind {
+                                // String literal in array: after = ["pipeline1"]
                                 ExpressionKind::Literal(Literal::String(s)) => {
                                     deps.push(s.clone());
                                 }
@@ -535,9 +2537,14 @@ impl PlanBuilder {
                 // expression transformations.
                 .filter(|f| !matches!(f.value.kind, ExpressionKind::PluginCall(_)))
                 .map(|f| {
+                    // The source reader splits an unnested column, so the
+                    // field maps the column that holds one element per row
+                    let value = unnest_arguments(&f.value)
+                        .and_then(|args| args.first())
+                        .unwrap_or(&f.value);
                     Ok(Transformation {
                         target_field: f.name.name.clone(),
-                        expression: self.compile_expression(&f.value)?,
+                        expression: self.compile_expression(value)?,
                     })
                 })
                 .collect()
@@ -546,6 +2553,54 @@ impl PlanBuilder {
         }
     }
 
+    /// Splitting of the select field that calls `unnest(column, delimiter)`
+    /// or `explode(...)`. The column must belong to the source table; the
+    /// delimiter defaults to a comma.
+    fn build_unnest(
+        &self,
+        pipeline_block: &PipelineBlock,
+        source: &DataSource,
+    ) -> Result<Option<Unnest>, ConvertError> {
+        let Some(select) = &pipeline_block.select_block else {
+            return Ok(None);
+        };
+        let mut calls = select
+            .fields
+            .iter()
+            .filter_map(|f| unnest_arguments(&f.value).map(|args| (&f.name.name, args)));
+        let Some((field, args)) = calls.next() else {
+            return Ok(None);
+        };
+        if calls.next().is_some() {
+            return Err(ConvertError::Plan(ERR_UNNEST_MULTIPLE.to_string()));
+        }
+
+        let arguments_err = || ConvertError::Plan(ERR_UNNEST_ARGUMENTS.replace("{}", field));
+        let (column, delimiter) = match args {
+            [column] => (column, None),
+            [column, delimiter] => (column, Some(delimiter)),
+            _ => return Err(arguments_err()),
+        };
+        let column = match &column.kind {
+            ExpressionKind::Identifier(name) => name.clone(),
+            ExpressionKind::DotNotation(path) => match path.segments.as_slice() {
+                [table, column] if *table == source.table => column.clone(),
+                segments => {
+                    return Err(ConvertError::Plan(
+                        ERR_UNNEST_COLUMN.replace("{}", &segments.join(".")),
+                    ));
+                }
+            },
+            _ => return Err(arguments_err()),
+        };
+        let delimiter = match delimiter.map(|d| &d.kind) {
+            None => DEFAULT_UNNEST_DELIMITER.to_string(),
+            Some(ExpressionKind::Literal(Literal::String(d))) if !d.is_empty() => d.clone(),
+            Some(_) => return Err(arguments_err()),
+        };
+        Ok(Some(Unnest { column, delimiter }))
+    }
+
     /// Extract `output = plugin.name({ field: src.col, ... })` entries from the
     /// select block into `PluginTransformCall`s.
     fn build_plugin_transforms(&self, pipeline_block: &PipelineBlock) -> Vec<PluginTransformCall> {
@@ -606,6 +2661,167 @@ impl PlanBuilder {
         Ok(result)
     }
 
+    /// Destination tables of the `to` blocks after the first. They write
+    /// through the first block's connection and mode, each with its own
+    /// select over the source row.
+    /// Tables of the `from` blocks after the first, which read through the
+    /// first block's connection.
+    fn build_union(
+        &self,
+        pipeline_block: &PipelineBlock,
+        table: &str,
+        connection: &str,
+    ) -> Result<Vec<String>, ConvertError> {
+        let name = &pipeline_block.name;
+        let content_err = |content: &str| {
+            ConvertError::Plan(
+                ERR_UNION_CONTENT
+                    .replace("{pipeline}", name)
+                    .replace("{content}", content),
+            )
+        };
+        let mut tables = HashSet::from([table.to_ascii_lowercase()]);
+        let mut union = Vec::with_capacity(pipeline_block.unions.len());
+        for from in &pipeline_block.unions {
+            if from.references.is_some() {
+                return Err(content_err("references"));
+            }
+            if let Some(block) = from.nested_blocks.first() {
+                return Err(content_err(&block.kind));
+            }
+            let mut table = None;
+            for attr in &from.attributes {
+                match attr.key.name.as_str() {
+                    ATTR_TABLE => match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) if !s.trim().is_empty() => table = Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_MISSING_TABLE.to_string())),
+                    },
+                    ATTR_CONNECTION => {}
+                    other => return Err(content_err(other)),
+                }
+            }
+            let table = table.ok_or_else(|| ConvertError::Plan(ERR_MISSING_TABLE.to_string()))?;
+
+            if from
+                .attributes
+                .iter()
+                .any(|a| a.key.name == ATTR_CONNECTION)
+            {
+                let other = self.resolve_connection_from(from)?;
+                if other != connection {
+                    return Err(ConvertError::Plan(
+                        ERR_UNION_CONNECTION
+                            .replace("{pipeline}", name)
+                            .replace("{table}", &table)
+                            .replace("{connection}", &other)
+                            .replace("{first}", connection),
+                    ));
+                }
+            }
+            if !tables.insert(table.to_ascii_lowercase()) {
+                return Err(ConvertError::Plan(
+                    ERR_UNION_DUPLICATE
+                        .replace("{pipeline}", name)
+                        .replace("{table}", &table),
+                ));
+            }
+            union.push(table);
+        }
+        Ok(union)
+    }
+
+    fn build_sinks(
+        &self,
+        pipeline_block: &PipelineBlock,
+        destination: &DataDestination,
+    ) -> Result<Vec<Sink>, ConvertError> {
+        let name = &pipeline_block.name;
+        if pipeline_block
+            .to
+            .as_ref()
+            .is_some_and(|to| to.select_block.is_some())
+        {
+            return Err(ConvertError::Plan(ERR_SINK_SELECT.replace("{}", name)));
+        }
+        if pipeline_block.sinks.is_empty() {
+            return Ok(Vec::new());
+        }
+        if destination.table.is_empty() {
+            return Err(ConvertError::Plan(ERR_SINK_TABLE.replace("{}", name)));
+        }
+
+        let content_err = |content: &str| {
+            ConvertError::Plan(
+                ERR_SINK_CONTENT
+                    .replace("{pipeline}", name)
+                    .replace("{content}", content),
+            )
+        };
+        let mut tables = HashSet::from([destination.table.to_ascii_lowercase()]);
+        let mut sinks = Vec::with_capacity(pipeline_block.sinks.len());
+        for to in &pipeline_block.sinks {
+            if to.map_block.is_some() {
+                return Err(content_err("map"));
+            }
+            if let Some(block) = to.nested_blocks.first() {
+                return Err(content_err(&block.kind));
+            }
+            let mut table = None;
+            for attr in &to.attributes {
+                match attr.key.name.as_str() {
+                    ATTR_TABLE => match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) if !s.trim().is_empty() => table = Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_SINK_TABLE.replace("{}", name))),
+                    },
+                    ATTR_CONNECTION => {}
+                    other => return Err(content_err(other)),
+                }
+            }
+            let table =
+                table.ok_or_else(|| ConvertError::Plan(ERR_SINK_TABLE.replace("{}", name)))?;
+
+            if to.attributes.iter().any(|a| a.key.name == ATTR_CONNECTION) {
+                let connection = self.resolve_connection_to(to)?;
+                if connection != destination.connection.name {
+                    return Err(ConvertError::Plan(
+                        ERR_SINK_CONNECTION
+                            .replace("{pipeline}", name)
+                            .replace("{table}", &table)
+                            .replace("{connection}", &connection)
+                            .replace("{first}", &destination.connection.name),
+                    ));
+                }
+            }
+            if !tables.insert(table.to_ascii_lowercase()) {
+                return Err(ConvertError::Plan(
+                    ERR_SINK_DUPLICATE
+                        .replace("{pipeline}", name)
+                        .replace("{table}", &table),
+                ));
+            }
+
+            let transformations = match &to.select_block {
+                Some(select) => select
+                    .fields
+                    .iter()
+                    .filter(|f| !matches!(f.value.kind, ExpressionKind::PluginCall(_)))
+                    .map(|f| {
+                        Ok(Transformation {
+                            target_field: f.name.name.clone(),
+                            expression: self.compile_expression(&f.value)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ConvertError>>()?,
+                None => Vec::new(),
+            };
+            sinks.push(Sink {
+                table,
+                transformations,
+            });
+        }
+        Ok(sinks)
+    }
+
     fn build_validation_rules(
         &self,
         pipeline_block: &PipelineBlock,
@@ -671,201 +2887,752 @@ impl PlanBuilder {
         pipeline_block: &PipelineBlock,
     ) -> Result<ErrorHandling, ConvertError> {
         if let Some(on_error) = &pipeline_block.on_error_block {
-            let retry = on_error.retry.as_ref().map(|r| {
-                // Extract retry config from attributes
-                let max_attempts = r
-                    .attributes
-                    .iter()
-                    .find(|a| a.key.name == ATTR_MAX_ATTEMPTS)
-                    .and_then(|a| self.eval_with_definitions(&a.value).ok())
-                    .and_then(|v| match v {
-                        Value::Int(n) => Some(n as u32),
-                        Value::Float(f) => Some(f as u32),
-                        _ => None,
-                    })
-                    .unwrap_or(3);
-
-                RetryConfig {
-                    max_attempts,
-                    delay_ms: 1000,
-                    backoff: BackoffStrategy::Exponential,
-                }
-            });
+            let retry = on_error
+                .retry
+                .as_ref()
+                .map(|r| {
+                    let attr = |name: &str| {
+                        r.attributes
+                            .iter()
+                            .find(|a| a.key.name == name)
+                            .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                    };
+                    let duration_ms = |name: &str| match attr(name) {
+                        Some(Value::String(s)) => parse_duration_ms(&s).map(Some),
+                        Some(_) => Err(ConvertError::Plan(
+                            ERR_RETRY_DURATION_NOT_STRING.replace("{}", name),
+                        )),
+                        None => Ok(None),
+                    };
 
-            let failed_rows = on_error.failed_rows.as_ref().map(|fr| {
-                // Extract action from attributes (optional, defaults to Log)
-                let action = fr
-                    .attributes
-                    .iter()
-                    .find(|a| a.key.name == ATTR_ACTION)
-                    .and_then(|a| self.eval_with_definitions(&a.value).ok())
-                    .and_then(|v| match v {
-                        Value::String(s) => match s.as_str() {
-                            ACTION_SKIP => Some(FailedRowsAction::Skip),
-                            FAILED_ACTION_LOG => Some(FailedRowsAction::Log),
-                            FAILED_ACTION_SAVE_TO_TABLE => Some(FailedRowsAction::SaveToTable),
+                    // Extract retry config from attributes
+                    let max_attempts = attr(ATTR_MAX_ATTEMPTS)
+                        .and_then(|v| match v {
+                            Value::Int(n) => Some(n as u32),
+                            Value::Float(f) => Some(f as u32),
                             _ => None,
-                        },
-                        _ => None,
-                    })
-                    .unwrap_or(FailedRowsAction::Log);
-
-                // Extract destination from nested blocks or attributes
-                let destination = if let Some(table_block) =
-                    fr.nested_blocks.iter().find(|b| b.kind == BLOCK_TABLE)
-                {
-                    // Parse table block
-                    let connection_name = table_block
-                        .attributes
-                        .iter()
-                        .find(|a| a.key.name == ATTR_CONNECTION)
-                        .and_then(|a| {
-                            if let ExpressionKind::DotNotation(path) = &a.value.kind {
-                                // connection.name format
-                                if path.segments.len() == 2
-                                    && path.segments[0] == KEYWORD_CONNECTION
-                                {
-                                    Some(path.segments[1].clone())
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
+                        })
+                        .unwrap_or(3);
+
+                    let mut delay_ms = duration_ms(ATTR_DELAY)?;
+
+                    // `backoff` names the strategy; a duration is accepted as
+                    // shorthand for an exponential backoff from that delay
+                    let backoff = match attr(ATTR_BACKOFF) {
+                        Some(Value::String(s)) => match s.as_str() {
+                            BACKOFF_FIXED => BackoffStrategy::Fixed,
+                            BACKOFF_LINEAR => BackoffStrategy::Linear,
+                            BACKOFF_EXPONENTIAL => BackoffStrategy::Exponential,
+                            other => {
+                                let ms = parse_duration_ms(other).map_err(|_| {
+                                    ConvertError::Plan(ERR_INVALID_BACKOFF.replace("{}", other))
+                                })?;
+                                delay_ms = delay_ms.or(Some(ms));
+                                BackoffStrategy::Exponential
                             }
-                        });
+                        },
+                        Some(other) => {
+                            return Err(ConvertError::Plan(
+                                ERR_INVALID_BACKOFF.replace("{}", &format!("{other:?}")),
+                            ));
+                        }
+                        None => BackoffStrategy::Exponential,
+                    };
 
-                    let schema = table_block
-                        .attributes
-                        .iter()
-                        .find(|a| a.key.name == ATTR_SCHEMA)
-                        .and_then(|a| self.eval_with_definitions(&a.value).ok())
-                        .and_then(|v| match v {
-                            Value::String(s) => Some(s),
-                            _ => None,
-                        });
+                    let jitter = match attr(ATTR_JITTER) {
+                        Some(Value::Boolean(b)) => b,
+                        Some(_) => return Err(ConvertError::Plan(ERR_JITTER_NOT_BOOL.to_string())),
+                        None => true,
+                    };
 
-                    let table = table_block
+                    Ok(RetryConfig {
+                        max_attempts,
+                        delay_ms: delay_ms.unwrap_or(1000),
+                        backoff,
+                        max_delay_ms: duration_ms(ATTR_MAX_DELAY)?,
+                        jitter,
+                    })
+                })
+                .transpose()?;
+
+            let failed_rows = on_error
+                .failed_rows
+                .as_ref()
+                .map(|fr| {
+                    // Extract action from attributes (optional, defaults to Log)
+                    let action = fr
                         .attributes
                         .iter()
-                        .find(|a| a.key.name == ATTR_TABLE)
+                        .find(|a| a.key.name == ATTR_ACTION)
                         .and_then(|a| self.eval_with_definitions(&a.value).ok())
                         .and_then(|v| match v {
-                            Value::String(s) => Some(s),
+                            Value::String(s) => match s.as_str() {
+                                ACTION_SKIP => Some(FailedRowsAction::Skip),
+                                FAILED_ACTION_LOG => Some(FailedRowsAction::Log),
+                                FAILED_ACTION_SAVE_TO_TABLE => Some(FailedRowsAction::SaveToTable),
+                                FAILED_ACTION_SAVE_TO_FILE => Some(FailedRowsAction::SaveToFile),
+                                _ => None,
+                            },
                             _ => None,
-                        });
-
-                    if let (Some(conn_name), Some(tbl)) = (connection_name, table) {
-                        // Look up the connection from the connections map
-                        self.connections.get(&conn_name).map(|connection| {
-                            FailedRowsDestination::Table {
-                                connection: connection.clone(),
-                                table: tbl,
-                                schema,
-                            }
+                        })
+                        .unwrap_or(FailedRowsAction::Log);
+
+                    // Extract destination from nested blocks or attributes
+                    let destination = if let Some(table_block) =
+                        fr.nested_blocks.iter().find(|b| b.kind == BLOCK_TABLE)
+                    {
+                        // Parse table block
+                        let connection_name = table_block
+                            .attributes
+                            .iter()
+                            .find(|a| a.key.name == ATTR_CONNECTION)
+                            .and_then(|a| {
+                                if let ExpressionKind::DotNotation(path) = &a.value.kind {
+                                    // connection.name format
+                                    if path.segments.len() == 2
+                                        && path.segments[0] == KEYWORD_CONNECTION
+                                    {
+                                        Some(path.segments[1].clone())
+                                    } else {
+                                        None
+                                    }
+                                } else {
+                                    None
+                                }
+                            });
+
+                        let schema = table_block
+                            .attributes
+                            .iter()
+                            .find(|a| a.key.name == ATTR_SCHEMA)
+                            .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                            .and_then(|v| match v {
+                                Value::String(s) => Some(s),
+                                _ => None,
+                            });
+
+                        let table = table_block
+                            .attributes
+                            .iter()
+                            .find(|a| a.key.name == ATTR_TABLE)
+                            .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                            .and_then(|v| match v {
+                                Value::String(s) => Some(s),
+                                _ => None,
+                            });
+
+                        if let (Some(conn_name), Some(tbl)) = (connection_name, table) {
+                            // Look up the connection from the connections map
+                            self.connections.get(&conn_name).map(|connection| {
+                                FailedRowsDestination::Table {
+                                    connection: connection.clone(),
+                                    table: tbl,
+                                    schema,
+                                }
+                            })
+                        } else {
+                            None
+                        }
+                    } else if let Some(file_block) =
+                        fr.nested_blocks.iter().find(|b| b.kind == BLOCK_FILE)
+                    {
+                        // Parse file block
+                        let path = file_block
+                            .attributes
+                            .iter()
+                            .find(|a| a.key.name == ATTR_PATH)
+                            .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                            .and_then(|v| match v {
+                                Value::String(s) => Some(s),
+                                _ => None,
+                            });
+
+                        let format = file_block
+                            .attributes
+                            .iter()
+                            .find(|a| a.key.name == ATTR_FORMAT)
+                            .and_then(|a| self.eval_with_definitions(&a.value).ok())
+                            .and_then(|v| match v {
+                                Value::String(s) => match s.as_str() {
+                                    FORMAT_JSON => Some(FileFormat::Json),
+                                    FORMAT_CSV => Some(FileFormat::Csv),
+                                    FORMAT_PARQUET => Some(FileFormat::Parquet),
+                                    _ => None,
+                                },
+                                _ => None,
+                            });
+
+                        path.map(|p| FailedRowsDestination::File {
+                            path: p.clone(),
+                            format: format.unwrap_or_else(|| {
+                                // Auto-detect format from extension if not specified
+                                let ext_json = format!(".{}", FORMAT_JSON);
+                                let ext_csv = format!(".{}", FORMAT_CSV);
+                                let ext_parquet = format!(".{}", FORMAT_PARQUET);
+
+                                if p.ends_with(&ext_json) {
+                                    FileFormat::Json
+                                } else if p.ends_with(&ext_csv) {
+                                    FileFormat::Csv
+                                } else if p.ends_with(&ext_parquet) {
+                                    FileFormat::Parquet
+                                } else {
+                                    FileFormat::Json
+                                }
+                            }),
                         })
                     } else {
                         None
+                    };
+
+                    let config = FailedRowsConfig {
+                        action,
+                        destination,
+                    };
+                    if config.action == FailedRowsAction::SaveToFile
+                        && config.quarantine_path().is_none()
+                    {
+                        return Err(ConvertError::Plan(ERR_SAVE_TO_FILE_DESTINATION.to_string()));
                     }
-                } else if let Some(file_block) =
-                    fr.nested_blocks.iter().find(|b| b.kind == BLOCK_FILE)
-                {
-                    // Parse file block
-                    let path = file_block
-                        .attributes
-                        .iter()
-                        .find(|a| a.key.name == ATTR_PATH)
-                        .and_then(|a| self.eval_with_definitions(&a.value).ok())
-                        .and_then(|v| match v {
-                            Value::String(s) => Some(s),
-                            _ => None,
-                        });
+                    Ok(config)
+                })
+                .transpose()?;
 
-                    let format = file_block
-                        .attributes
-                        .iter()
-                        .find(|a| a.key.name == ATTR_FORMAT)
-                        .and_then(|a| self.eval_with_definitions(&a.value).ok())
-                        .and_then(|v| match v {
-                            Value::String(s) => match s.as_str() {
-                                FORMAT_JSON => Some(FileFormat::Json),
-                                FORMAT_CSV => Some(FileFormat::Csv),
-                                FORMAT_PARQUET => Some(FileFormat::Parquet),
-                                _ => None,
-                            },
-                            _ => None,
-                        });
-
-                    path.map(|p| FailedRowsDestination::File {
-                        path: p.clone(),
-                        format: format.unwrap_or_else(|| {
-                            // Auto-detect format from extension if not specified
-                            let ext_json = format!(".{}", FORMAT_JSON);
-                            let ext_csv = format!(".{}", FORMAT_CSV);
-                            let ext_parquet = format!(".{}", FORMAT_PARQUET);
-
-                            if p.ends_with(&ext_json) {
-                                FileFormat::Json
-                            } else if p.ends_with(&ext_csv) {
-                                FileFormat::Csv
-                            } else if p.ends_with(&ext_parquet) {
-                                FileFormat::Parquet
-                            } else {
-                                FileFormat::Json
-                            }
-                        }),
-                    })
-                } else {
-                    None
+            Ok(ErrorHandling { retry, failed_rows })
+        } else {
+            Ok(ErrorHandling {
+                retry: None,
+                failed_rows: None,
+            })
+        }
+    }
+
+    fn build_lifecycle(
+        &self,
+        pipeline_block: &PipelineBlock,
+    ) -> Result<LifecycleHooks, ConvertError> {
+        let before = pipeline_block
+            .before_block
+            .as_ref()
+            .map(|b| b.sql.clone())
+            .unwrap_or_default();
+
+        let after = pipeline_block
+            .after_block
+            .as_ref()
+            .map(|b| b.sql.clone())
+            .unwrap_or_default();
+
+        let on_batch = pipeline_block
+            .on_batch_block
+            .as_ref()
+            .map(|b| self.build_batch_hook(b))
+            .transpose()?;
+
+        Ok(LifecycleHooks {
+            before,
+            after,
+            on_batch,
+        })
+    }
+
+    /// SQL run on the destination every `every` committed batches
+    /// (default: after each one).
+    fn build_batch_hook(&self, block: &OnBatchBlock) -> Result<BatchHook, ConvertError> {
+        if block.sql.is_empty() {
+            return Err(ConvertError::Plan(ERR_ON_BATCH_SQL_REQUIRED.to_string()));
+        }
+
+        let mut every = 1;
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_EVERY => {
+                    every = match self.eval_with_definitions(&attr.value)? {
+                        Value::Int(n) if n > 0 => n as u64,
+                        Value::UInt(n) if n > 0 => n,
+                        Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => f as u64,
+                        _ => return Err(ConvertError::Plan(ERR_ON_BATCH_EVERY.to_string())),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_ON_BATCH_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        Ok(BatchHook {
+            sql: block.sql.clone(),
+            every,
+        })
+    }
+
+    /// Post-copy comparison of the source and destination tables; column
+    /// names refer to the source table and may be qualified with it.
+    fn build_verify(
+        &self,
+        block: &VerifyBlock,
+        source: &DataSource,
+        destination: &DataDestination,
+    ) -> Result<VerifyConfig, ConvertError> {
+        let source_table = source.table.as_str();
+        let mut mode = VerifyMode::Checksum;
+        let mut key = None;
+        let mut chunk_size = DEFAULT_VERIFY_CHUNK_SIZE;
+        let mut columns = Vec::new();
+        let mut as_of = VerifyAsOf::Now;
+
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_MODE => {
+                    mode = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) => VerifyMode::from_str(&s)
+                            .map_err(|_| ConvertError::Plan(ERR_VERIFY_MODE.replace("{}", &s)))?,
+                        other => {
+                            return Err(ConvertError::Plan(
+                                ERR_VERIFY_MODE.replace("{}", &format!("{:?}", other)),
+                            ));
+                        }
+                    };
+                }
+                ATTR_KEY => {
+                    key = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) => Some(verify_column(&s, source_table)?),
+                        _ => return Err(ConvertError::Plan(ERR_VERIFY_KEY.to_string())),
+                    };
+                }
+                ATTR_CHUNK_SIZE => {
+                    chunk_size = match self.eval_with_definitions(&attr.value)? {
+                        Value::Int(n) if n > 0 => n as usize,
+                        Value::UInt(n) if n > 0 => n as usize,
+                        Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => f as usize,
+                        _ => return Err(ConvertError::Plan(ERR_VERIFY_CHUNK_SIZE.to_string())),
+                    };
+                }
+                ATTR_COLUMNS => {
+                    let Value::Array(items) = self.eval_with_definitions(&attr.value)? else {
+                        return Err(ConvertError::Plan(ERR_VERIFY_COLUMNS.to_string()));
+                    };
+                    for item in items {
+                        let Value::String(s) = item else {
+                            return Err(ConvertError::Plan(ERR_VERIFY_COLUMNS.to_string()));
+                        };
+                        columns.push(verify_column(&s, source_table)?);
+                    }
+                }
+                ATTR_AS_OF => {
+                    as_of = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) => VerifyAsOf::from_str(&s)
+                            .map_err(|_| ConvertError::Plan(ERR_VERIFY_AS_OF.replace("{}", &s)))?,
+                        other => {
+                            return Err(ConvertError::Plan(
+                                ERR_VERIFY_AS_OF.replace("{}", &format!("{:?}", other)),
+                            ));
+                        }
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_VERIFY_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        if mode == VerifyMode::Count && !columns.is_empty() {
+            return Err(ConvertError::Plan(ERR_VERIFY_COUNT_COLUMNS.to_string()));
+        }
+        if as_of == VerifyAsOf::Snapshot {
+            // Only PostgreSQL can export a snapshot for other sessions to read at
+            let connection = &source.connection;
+            if !matches!(connection.driver.as_str(), "postgres" | "postgresql") {
+                return Err(ConvertError::Plan(
+                    ERR_VERIFY_AS_OF_DRIVER
+                        .replace("{name}", &connection.name)
+                        .replace("{driver}", &connection.driver),
+                ));
+            }
+            if destination.connection.name == connection.name {
+                return Err(ConvertError::Plan(
+                    ERR_VERIFY_AS_OF_SHARED.replace("{}", &connection.name),
+                ));
+            }
+        }
+
+        Ok(VerifyConfig {
+            mode,
+            key,
+            chunk_size,
+            columns,
+            as_of,
+        })
+    }
+
+    /// Approval gate of a pipeline or runbook step, named after it.
+    fn build_approval_gate(
+        &self,
+        block: &ApproveBlock,
+        name: &str,
+    ) -> Result<ApprovalGate, ConvertError> {
+        let mut message = None;
+
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_MESSAGE => {
+                    message = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) => Some(s),
+                        _ => {
+                            return Err(ConvertError::Plan(
+                                ERR_APPROVE_MESSAGE.replace("{}", name),
+                            ));
+                        }
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_APPROVE_UNKNOWN_ATTR
+                            .replace("{gate}", name)
+                            .replace("{attr}", other),
+                    ));
+                }
+            }
+        }
+
+        Ok(ApprovalGate {
+            name: name.to_string(),
+            message,
+        })
+    }
+
+    fn build_log(&self, block: &LogBlock) -> Result<PipelineLog, ConvertError> {
+        let mut log = PipelineLog::default();
+
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_LEVEL => {
+                    log.level = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) => Some(
+                            LogLevel::from_str(&s)
+                                .map_err(|_| ConvertError::Plan(ERR_LOG_LEVEL.replace("{}", &s)))?,
+                        ),
+                        other => {
+                            return Err(ConvertError::Plan(
+                                ERR_LOG_LEVEL.replace("{}", &format!("{:?}", other)),
+                            ));
+                        }
+                    };
+                }
+                ATTR_FILE => {
+                    log.file = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) if !s.trim().is_empty() => Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_LOG_FILE.to_string())),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_LOG_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+
+        Ok(log)
+    }
+
+    /// Rows of a seed {} block. Every row must set the same columns; they
+    /// are kept in the order the first row lists them.
+    fn build_seed(&self, block: &SeedBlock) -> Result<SeedTable, ConvertError> {
+        let mut table = None;
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_TABLE => {
+                    table = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) if !s.trim().is_empty() => Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_SEED_TABLE.to_string())),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_SEED_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+        let table = table.ok_or_else(|| ConvertError::Plan(ERR_SEED_TABLE.to_string()))?;
+
+        let Some(first) = block.rows.first() else {
+            return Err(ConvertError::Plan(ERR_SEED_NO_ROWS.replace("{}", &table)));
+        };
+        let columns: Vec<String> = first.fields.iter().map(|f| f.key.name.clone()).collect();
+
+        let mut rows = Vec::with_capacity(block.rows.len());
+        for row in &block.rows {
+            let mut values: Vec<Option<Value>> = vec![None; columns.len()];
+            for field in &row.fields {
+                let column = &field.key.name;
+                let Some(idx) = columns.iter().position(|c| c == column) else {
+                    return Err(ConvertError::Plan(
+                        ERR_SEED_COLUMNS
+                            .replace("{table}", &table)
+                            .replace("{columns}", &columns.join(", ")),
+                    ));
+                };
+                if values[idx].is_some() {
+                    return Err(ConvertError::Plan(
+                        ERR_SEED_DUPLICATE_COLUMN
+                            .replace("{table}", &table)
+                            .replace("{column}", column),
+                    ));
+                }
+                values[idx] = Some(self.eval_with_definitions(&field.value)?);
+            }
+
+            let values: Option<Vec<Value>> = values.into_iter().collect();
+            let values = values.ok_or_else(|| {
+                ConvertError::Plan(
+                    ERR_SEED_COLUMNS
+                        .replace("{table}", &table)
+                        .replace("{columns}", &columns.join(", ")),
+                )
+            })?;
+            rows.push(values);
+        }
+
+        Ok(SeedTable {
+            table,
+            columns,
+            rows,
+        })
+    }
+
+    /// Script of a script {} block. Compiling it is left to the transform
+    /// that runs it.
+    fn build_script(&self, block: &ScriptBlock) -> Result<RowScript, ConvertError> {
+        let mut code = None;
+        let mut max_operations = DEFAULT_SCRIPT_MAX_OPERATIONS;
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_CODE => {
+                    code = match self.eval_with_definitions(&attr.value)? {
+                        Value::String(s) if !s.trim().is_empty() => Some(s),
+                        _ => return Err(ConvertError::Plan(ERR_SCRIPT_CODE.to_string())),
+                    };
+                }
+                ATTR_MAX_OPERATIONS => {
+                    max_operations = match self.eval_with_definitions(&attr.value)? {
+                        Value::Int(n) if n > 0 => n as u64,
+                        Value::UInt(n) if n > 0 => n,
+                        Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => f as u64,
+                        _ => {
+                            return Err(ConvertError::Plan(ERR_SCRIPT_MAX_OPERATIONS.to_string()));
+                        }
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_SCRIPT_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+        let code = code.ok_or_else(|| ConvertError::Plan(ERR_SCRIPT_CODE.to_string()))?;
+
+        Ok(RowScript {
+            code,
+            max_operations,
+        })
+    }
+
+    /// Grouping of an aggregate {} block. `group_by` lists the grouping
+    /// columns; every other attribute is an aggregate column.
+    fn build_aggregate(
+        &self,
+        block: &AggregateBlock,
+        source: &DataSource,
+    ) -> Result<Aggregation, ConvertError> {
+        let mut group_by = Vec::new();
+        let mut aggregates = Vec::new();
+        for attr in &block.attributes {
+            let name = attr.key.name.as_str();
+            if name == ATTR_GROUP_BY {
+                let ExpressionKind::Array(items) = &attr.value.kind else {
+                    return Err(ConvertError::Plan(ERR_AGGREGATE_GROUP_BY.to_string()));
                 };
+                for item in items {
+                    group_by.push(aggregate_column(item, source)?);
+                }
+                continue;
+            }
 
-                FailedRowsConfig {
-                    action,
-                    destination,
+            let ExpressionKind::FunctionCall {
+                name: function,
+                arguments,
+            } = &attr.value.kind
+            else {
+                return Err(ConvertError::Plan(
+                    ERR_AGGREGATE_FUNCTION.replace("{}", name),
+                ));
+            };
+            let function = match function.to_ascii_lowercase().as_str() {
+                "sum" => AggregateFunction::Sum,
+                "count" => AggregateFunction::Count,
+                "min" => AggregateFunction::Min,
+                "max" => AggregateFunction::Max,
+                "avg" => AggregateFunction::Avg,
+                _ => {
+                    return Err(ConvertError::Plan(
+                        ERR_AGGREGATE_FUNCTION.replace("{}", name),
+                    ));
+                }
+            };
+            let column = match arguments.as_slice() {
+                [] if function == AggregateFunction::Count => None,
+                [arg] => Some(aggregate_column(arg, source)?),
+                _ => {
+                    return Err(ConvertError::Plan(
+                        ERR_AGGREGATE_ARGUMENTS.replace("{}", name),
+                    ));
                 }
+            };
+            aggregates.push(Aggregate {
+                name: name.to_string(),
+                function,
+                column,
             });
+        }
 
-            Ok(ErrorHandling { retry, failed_rows })
-        } else {
-            Ok(ErrorHandling {
-                retry: None,
-                failed_rows: None,
-            })
+        if group_by.is_empty() && aggregates.is_empty() {
+            return Err(ConvertError::Plan(ERR_AGGREGATE_EMPTY.to_string()));
+        }
+        // Grouped rows carry every output under its bare name
+        let mut seen = HashSet::new();
+        let outputs = group_by
+            .iter()
+            .map(|c| c.column.as_str())
+            .chain(aggregates.iter().map(|a| a.name.as_str()));
+        for output in outputs {
+            if !seen.insert(output.to_ascii_lowercase()) {
+                return Err(ConvertError::Plan(
+                    ERR_AGGREGATE_DUPLICATE.replace("{}", output),
+                ));
+            }
         }
+
+        Ok(Aggregation {
+            group_by,
+            aggregates,
+        })
     }
 
-    fn build_lifecycle(
-        &self,
-        pipeline_block: &PipelineBlock,
-    ) -> Result<LifecycleHooks, ConvertError> {
-        let before = pipeline_block
-            .before_block
-            .as_ref()
-            .map(|b| b.sql.clone())
-            .unwrap_or_default();
+    /// Reshaping of a pivot {} block. `key` and `value` name the key-value
+    /// columns, `keys` the keys that become columns (or, with
+    /// `unpivot = true`, the columns that become keys) and `group_by` the
+    /// columns identifying an entity.
+    fn build_pivot(&self, block: &PivotBlock) -> Result<Pivot, ConvertError> {
+        let mut key = None;
+        let mut value = None;
+        let mut keys = None;
+        let mut group_by = Vec::new();
+        let mut unpivot = false;
+
+        let names = |expr: &Expression, attr: &str| {
+            let err = || ConvertError::Plan(ERR_PIVOT_LIST.replace("{}", attr));
+            let ExpressionKind::Array(items) = &expr.kind else {
+                return Err(err());
+            };
+            let names = items
+                .iter()
+                .map(pivot_name)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(err)?;
+            if names.is_empty() {
+                return Err(err());
+            }
+            Ok(names)
+        };
+        for attr in &block.attributes {
+            let name = attr.key.name.as_str();
+            match name {
+                ATTR_KEY | ATTR_VALUE => {
+                    let column = pivot_name(&attr.value)
+                        .ok_or_else(|| ConvertError::Plan(ERR_PIVOT_NAME.replace("{}", name)))?;
+                    if name == ATTR_KEY {
+                        key = Some(column);
+                    } else {
+                        value = Some(column);
+                    }
+                }
+                ATTR_KEYS => keys = Some(names(&attr.value, name)?),
+                ATTR_GROUP_BY => group_by = names(&attr.value, name)?,
+                ATTR_UNPIVOT => {
+                    unpivot = match self.eval_with_definitions(&attr.value)? {
+                        Value::Boolean(b) => b,
+                        _ => return Err(ConvertError::Plan(ERR_PIVOT_UNPIVOT.to_string())),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_PIVOT_UNKNOWN_ATTR.replace("{}", other),
+                    ));
+                }
+            }
+        }
+        let (Some(key), Some(value), Some(keys)) = (key, value, keys) else {
+            return Err(ConvertError::Plan(ERR_PIVOT_MISSING.to_string()));
+        };
+        if unpivot && !group_by.is_empty() {
+            return Err(ConvertError::Plan(ERR_PIVOT_GROUP_BY.to_string()));
+        }
 
-        let after = pipeline_block
-            .after_block
-            .as_ref()
-            .map(|b| b.sql.clone())
-            .unwrap_or_default();
+        let mut seen = HashSet::new();
+        let columns = [&key, &value].into_iter().chain(&group_by).chain(&keys);
+        for column in columns {
+            if !seen.insert(column.to_ascii_lowercase()) {
+                return Err(ConvertError::Plan(
+                    ERR_PIVOT_DUPLICATE.replace("{}", column),
+                ));
+            }
+        }
 
-        Ok(LifecycleHooks { before, after })
+        Ok(Pivot {
+            direction: if unpivot {
+                PivotDirection::Unpivot
+            } else {
+                PivotDirection::Pivot
+            },
+            key,
+            value,
+            keys,
+            group_by,
+        })
     }
 
+    /// Pipeline settings layered over the global settings block: keys set on
+    /// the pipeline win, everything else is inherited.
     fn build_settings(
         &self,
         pipeline_block: &PipelineBlock,
     ) -> Result<HashMap<String, Value>, ConvertError> {
+        let mut settings_map = self.global_settings.clone();
         if let Some(settings) = &pipeline_block.settings_block {
-            let mut settings_map = HashMap::new();
-            for attr in &settings.attributes {
-                let value = self.eval_with_definitions(&attr.value)?;
-                settings_map.insert(attr.key.name.clone(), value);
-            }
-            Ok(settings_map)
-        } else {
-            Ok(HashMap::new())
+            let own = self.build_settings_block(settings)?;
+            check_settings(&format!("pipeline '{}'", pipeline_block.name), &own)?;
+            settings_map.extend(own);
+        }
+        Ok(settings_map)
+    }
+
+    pub fn build_settings_block(
+        &self,
+        settings: &SettingsBlock,
+    ) -> Result<HashMap<String, Value>, ConvertError> {
+        let mut settings_map = HashMap::new();
+        for attr in &settings.attributes {
+            let value = self.eval_with_definitions(&attr.value)?;
+            settings_map.insert(attr.key.name.clone(), value);
         }
+        Ok(settings_map)
     }
 
     fn build_graph_references(
@@ -1050,6 +3817,10 @@ impl PlanBuilder {
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(CompiledExpression::Array(compiled))
             }
+            // Maps are lookup tables, so their values are fixed when the plan is built
+            ExpressionKind::Map(_) => Ok(CompiledExpression::Literal(
+                self.eval_with_definitions(expr)?,
+            )),
             ExpressionKind::WhenExpression {
                 branches,
                 else_value,
@@ -1212,6 +3983,260 @@ impl PlanBuilder {
         Ok(decl)
     }
 
+    /// Convert a `materialized_view "name" { ... }` block. Dependencies are
+    /// checked against the rest of the plan by `order_materialized_views`.
+    pub fn build_materialized_view(
+        &self,
+        block: &MaterializedViewBlock,
+    ) -> Result<MaterializedView, ConvertError> {
+        let name = &block.name;
+        let mut connection = None;
+        let mut pipelines = Vec::new();
+        let mut views = Vec::new();
+        let mut concurrently = false;
+        let mut max_attempts = DEFAULT_REFRESH_ATTEMPTS;
+
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_CONNECTION => {
+                    if let ExpressionKind::DotNotation(path) = &attr.value.kind
+                        && path.segments.len() == 2
+                        && path.segments[0] == KEYWORD_CONNECTION
+                    {
+                        connection = Some(path.segments[1].clone());
+                    }
+                }
+                ATTR_AFTER => {
+                    let deps = match &attr.value.kind {
+                        ExpressionKind::Array(items) => items.iter().collect(),
+                        _ => vec![&attr.value],
+                    };
+                    for dep in deps {
+                        let invalid = || {
+                            ConvertError::Plan(
+                                ERR_INVALID_VIEW_DEPENDENCY
+                                    .replace("{view}", name)
+                                    .replace("{dep}", &format!("{:?}", dep.kind)),
+                            )
+                        };
+                        let ExpressionKind::DotNotation(path) = &dep.kind else {
+                            return Err(invalid());
+                        };
+                        match path.segments.as_slice() {
+                            [kind, dep] if kind == BLOCK_PIPELINE => pipelines.push(dep.clone()),
+                            [kind, dep] if kind == BLOCK_MATERIALIZED_VIEW => {
+                                views.push(dep.clone())
+                            }
+                            _ => {
+                                return Err(ConvertError::Plan(
+                                    ERR_INVALID_VIEW_DEPENDENCY
+                                        .replace("{view}", name)
+                                        .replace("{dep}", &path.segments.join(".")),
+                                ));
+                            }
+                        }
+                    }
+                }
+                ATTR_CONCURRENTLY => {
+                    concurrently = match self.eval_with_definitions(&attr.value)? {
+                        Value::Boolean(b) => b,
+                        _ => {
+                            return Err(ConvertError::Plan(
+                                ERR_VIEW_CONCURRENTLY.replace("{}", name),
+                            ));
+                        }
+                    };
+                }
+                ATTR_MAX_ATTEMPTS => {
+                    max_attempts = match self.eval_with_definitions(&attr.value)? {
+                        Value::Int(n) if n > 0 => n as u32,
+                        Value::UInt(n) if n > 0 => n as u32,
+                        Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => f as u32,
+                        _ => return Err(ConvertError::Plan(ERR_VIEW_ATTEMPTS.replace("{}", name))),
+                    };
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_VIEW_UNKNOWN_ATTR
+                            .replace("{view}", name)
+                            .replace("{attr}", other),
+                    ));
+                }
+            }
+        }
+
+        let connection = connection
+            .ok_or_else(|| ConvertError::Plan(ERR_VIEW_MISSING_CONNECTION.replace("{}", name)))?;
+        let connection = self.connections.get(&connection).cloned().ok_or_else(|| {
+            ConvertError::Connection(format!("Connection `{}` not found", connection))
+        })?;
+        if !driver_capabilities(&connection.driver).is_some_and(|c| c.materialized_views) {
+            return Err(ConvertError::Plan(
+                ERR_VIEW_UNSUPPORTED
+                    .replace("{view}", name)
+                    .replace("{name}", &connection.name)
+                    .replace("{driver}", &connection.driver),
+            ));
+        }
+
+        Ok(MaterializedView {
+            name: name.clone(),
+            connection,
+            pipelines,
+            views,
+            concurrently,
+            max_attempts,
+        })
+    }
+
+    /// Convert a `barrier "name" { pipelines = [...] }` block. Its pipelines
+    /// are checked against the plan by `check_barriers`.
+    pub fn build_barrier(&self, block: &BarrierBlock) -> Result<Barrier, ConvertError> {
+        let name = &block.name;
+        let invalid = || ConvertError::Plan(ERR_BARRIER_PIPELINES.replace("{}", name));
+        let mut pipelines = Vec::new();
+
+        for attr in &block.attributes {
+            match attr.key.name.as_str() {
+                ATTR_PIPELINES => {
+                    let ExpressionKind::Array(items) = &attr.value.kind else {
+                        return Err(invalid());
+                    };
+                    for item in items {
+                        match &item.kind {
+                            ExpressionKind::DotNotation(path)
+                                if path.segments.len() == 2
+                                    && path.segments[0] == BLOCK_PIPELINE =>
+                            {
+                                pipelines.push(path.segments[1].clone());
+                            }
+                            _ => return Err(invalid()),
+                        }
+                    }
+                }
+                other => {
+                    return Err(ConvertError::Plan(
+                        ERR_BARRIER_UNKNOWN_ATTR
+                            .replace("{barrier}", name)
+                            .replace("{attr}", other),
+                    ));
+                }
+            }
+        }
+
+        if pipelines.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Barrier {
+            name: name.clone(),
+            pipelines,
+        })
+    }
+
+    /// Convert a `runbook "name" { step "..." { ... } }` block. Step configs
+    /// are kept as written; they are resolved against the runbook file when
+    /// the step runs.
+    pub fn build_runbook(&self, block: &RunbookBlock) -> Result<Runbook, ConvertError> {
+        if block.steps.is_empty() {
+            return Err(ConvertError::Plan(
+                ERR_RUNBOOK_EMPTY.replace("{}", &block.name),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut steps = Vec::with_capacity(block.steps.len());
+        for step in &block.steps {
+            let name = &step.name;
+            if !seen.insert(name.as_str()) {
+                return Err(ConvertError::Plan(ERR_STEP_DUPLICATE.replace("{}", name)));
+            }
+
+            let mut config = None;
+            let mut approval = false;
+            let mut verify = None;
+            let mut max_failed_rows = None;
+
+            for attr in &step.attributes {
+                match attr.key.name.as_str() {
+                    ATTR_CONFIG => {
+                        config = match self.eval_with_definitions(&attr.value)? {
+                            Value::String(s) if !s.is_empty() => Some(s),
+                            _ => {
+                                return Err(ConvertError::Plan(
+                                    ERR_STEP_CONFIG.replace("{}", name),
+                                ));
+                            }
+                        };
+                    }
+                    ATTR_APPROVAL => {
+                        approval = match self.eval_with_definitions(&attr.value)? {
+                            Value::Boolean(b) => b,
+                            _ => {
+                                return Err(ConvertError::Plan(
+                                    ERR_STEP_APPROVAL.replace("{}", name),
+                                ));
+                            }
+                        };
+                    }
+                    ATTR_VERIFY => {
+                        let mode = match self.eval_with_definitions(&attr.value)? {
+                            Value::String(s) => s,
+                            other => format!("{:?}", other),
+                        };
+                        verify = Some(VerifyMode::from_str(&mode).map_err(|_| {
+                            ConvertError::Plan(
+                                ERR_STEP_VERIFY
+                                    .replace("{step}", name)
+                                    .replace("{mode}", &mode),
+                            )
+                        })?);
+                    }
+                    ATTR_MAX_FAILED_ROWS => {
+                        max_failed_rows = match self.eval_with_definitions(&attr.value)? {
+                            Value::Int(n) if n >= 0 => Some(n as u64),
+                            Value::UInt(n) => Some(n),
+                            Value::Float(f) if f >= 0.0 && f.fract() == 0.0 => Some(f as u64),
+                            _ => {
+                                return Err(ConvertError::Plan(
+                                    ERR_STEP_MAX_FAILED_ROWS.replace("{}", name),
+                                ));
+                            }
+                        };
+                    }
+                    other => {
+                        return Err(ConvertError::Plan(
+                            ERR_STEP_UNKNOWN_ATTR
+                                .replace("{step}", name)
+                                .replace("{attr}", other),
+                        ));
+                    }
+                }
+            }
+
+            let config = config
+                .ok_or_else(|| ConvertError::Plan(ERR_STEP_MISSING_CONFIG.replace("{}", name)))?;
+            let approval_gate = step
+                .approve_block
+                .as_ref()
+                .map(|block| self.build_approval_gate(block, name))
+                .transpose()?;
+            steps.push(RunbookStep {
+                name: name.clone(),
+                config,
+                approval,
+                approval_gate,
+                verify,
+                max_failed_rows,
+            });
+        }
+
+        Ok(Runbook {
+            name: block.name.clone(),
+            steps,
+            notify: Vec::new(),
+        })
+    }
+
     /// Convert an evaluated SMQL value into plain JSON for a plugin's config
     /// (natural scalars/arrays, not the `{type,value}` exchange envelope).
     fn value_to_plain_json(v: &Value) -> serde_json::Value {
@@ -1256,8 +4281,54 @@ impl Default for PlanBuilder {
     }
 }
 
+/// A notify block's `events`: one event name or an array of them, without
+/// repeats
+fn parse_notify_events(value: Value) -> Result<Vec<NotifyEvent>, ConvertError> {
+    let names = match value {
+        Value::String(s) => vec![Value::String(s)],
+        Value::Array(items) => items,
+        _ => return Err(ConvertError::Plan(ERR_NOTIFY_EVENTS.to_string())),
+    };
+    let mut events = Vec::new();
+    for name in names {
+        let Value::String(name) = name else {
+            return Err(ConvertError::Plan(ERR_NOTIFY_EVENTS.to_string()));
+        };
+        let event = NotifyEvent::from_str(&name).map_err(ConvertError::Plan)?;
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+fn parse_validation_threshold(value: Value) -> Result<u64, ConvertError> {
+    match value {
+        Value::Int(n) if n > 0 => Ok(n as u64),
+        Value::UInt(n) if n > 0 => Ok(n),
+        Value::Float(f) if f >= 1.0 && f.fract() == 0.0 => Ok(f as u64),
+        _ => Err(ConvertError::Plan(ERR_NOTIFY_THRESHOLD.to_string())),
+    }
+}
+
+fn parse_stall_after(value: Value) -> Result<u64, ConvertError> {
+    let secs = match value {
+        Value::String(s) => parse_duration(&s)?,
+        _ => 0,
+    };
+    if secs == 0 {
+        return Err(ConvertError::Plan(ERR_NOTIFY_STALL_AFTER.to_string()));
+    }
+    Ok(secs)
+}
+
 /// Parse duration string (e.g., "30s", "5m", "2h") to seconds
-fn parse_duration(s: &str) -> Result<u64, ConvertError> {
+pub fn parse_duration(s: &str) -> Result<u64, ConvertError> {
+    parse_duration_ms(s).map(|ms| ms / 1000)
+}
+
+/// Parse duration string (e.g., "500ms", "30s", "5m") to milliseconds
+pub fn parse_duration_ms(s: &str) -> Result<u64, ConvertError> {
     let s = s.trim();
     if s.is_empty() {
         return Err(ConvertError::Plan("Empty duration string".to_string()));
@@ -1281,12 +4352,12 @@ fn parse_duration(s: &str) -> Result<u64, ConvertError> {
         ))
     })?;
 
-    let seconds = match unit {
-        "ms" => num / 1000, // milliseconds to seconds
-        "s" => num,
-        "m" => num * 60,
-        "h" => num * 3600,
-        "d" => num * 86400,
+    let unit_ms: u64 = match unit {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
         _ => {
             return Err(ConvertError::Plan(format!(
                 "Invalid duration unit: '{}'. Supported units: ms, s, m, h, d",
@@ -1295,7 +4366,8 @@ fn parse_duration(s: &str) -> Result<u64, ConvertError> {
         }
     };
 
-    Ok(seconds)
+    num.checked_mul(unit_ms)
+        .ok_or_else(|| ConvertError::Plan(format!("Duration '{}' is too long", s)))
 }
 
 #[cfg(test)]
@@ -1306,7 +4378,7 @@ mod tests {
         attribute::Attribute,
         dotpath::DotPath,
         ident::Identifier,
-        pipeline::{AfterBlock, BeforeBlock, NestedBlock, PaginateBlock, SettingsBlock},
+        pipeline::{AfterBlock, BeforeBlock, NestedBlock, PaginateBlock, SeedRow},
         span::Span,
         validation::{
             FailedRowsBlock, OnErrorBlock, RetryBlock, ValidateBlock, ValidationBody,
@@ -1337,77 +4409,284 @@ mod tests {
         Expression::new(ExpressionKind::Identifier(s.to_string()), test_span())
     }
 
-    fn make_dotpath_expr(segments: Vec<&str>) -> Expression {
-        let path = DotPath::new(
-            segments.into_iter().map(|s| s.to_string()).collect(),
-            test_span(),
+    fn make_dotpath_expr(segments: Vec<&str>) -> Expression {
+        let path = DotPath::new(
+            segments.into_iter().map(|s| s.to_string()).collect(),
+            test_span(),
+        );
+        Expression::new(ExpressionKind::DotNotation(path), test_span())
+    }
+
+    fn make_attribute(key: &str, value: Expression) -> Attribute {
+        Attribute {
+            key: Identifier::new(key, test_span()),
+            value,
+            span: test_span(),
+        }
+    }
+
+    fn make_nested_block(kind: &str, attributes: Vec<Attribute>) -> NestedBlock {
+        NestedBlock {
+            kind: kind.to_string(),
+            attributes,
+            span: test_span(),
+        }
+    }
+
+    #[test]
+    fn test_extract_definitions() {
+        let mut builder = PlanBuilder::default();
+        let def_block = DefineBlock {
+            attributes: vec![
+                make_attribute("tax_rate", make_number_expr(1.4)),
+                make_attribute("country", make_string_expr("US")),
+                make_attribute("enabled", make_bool_expr(true)),
+            ],
+            span: test_span(),
+        };
+
+        let result = builder.extract_definitions(&def_block).unwrap();
+        assert_eq!(result.len(), 3);
+
+        let definition_info = result.get("tax_rate").unwrap();
+        assert_eq!(definition_info.value, Value::Float(1.4));
+
+        let definition_info = result.get("country").unwrap();
+        assert_eq!(definition_info.value, Value::String("US".to_string()));
+
+        let definition_info = result.get("enabled").unwrap();
+        assert_eq!(definition_info.value, Value::Boolean(true));
+    }
+
+    #[test]
+    fn test_build_connection_basic() {
+        let builder = PlanBuilder::default();
+        let conn_block = ConnectionBlock {
+            name: "postgres_prod".to_string(),
+            attributes: vec![
+                make_attribute("driver", make_string_expr("postgres")),
+                make_attribute("host", make_string_expr("localhost")),
+                make_attribute("port", make_number_expr(5432.0)),
+            ],
+            nested_blocks: vec![],
+            span: test_span(),
+        };
+
+        let result = builder.build_connection(&conn_block).unwrap();
+        assert_eq!(result.name, "postgres_prod");
+        assert_eq!(result.driver, "postgres");
+        assert_eq!(result.properties.len(), 3);
+        assert_eq!(
+            result.properties.get("host"),
+            Some(&Value::String("localhost".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_build_connection_unsupported_driver() {
+        let builder = PlanBuilder::default();
+        let conn_block = ConnectionBlock {
+            name: "legacy".to_string(),
+            attributes: vec![make_attribute("driver", make_string_expr("oracle"))],
+            nested_blocks: vec![],
+            span: test_span(),
+        };
+
+        let err = builder.build_connection(&conn_block).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "connection error: connection 'legacy': unsupported driver 'oracle'. \
+             Must be 'mysql', 'mariadb', 'postgres', 'csv' or 'wasm'"
+        );
+
+        // MariaDB is served by the MySQL driver
+        let mariadb = ConnectionBlock {
+            name: "shop".to_string(),
+            attributes: vec![make_attribute("driver", make_string_expr("mariadb"))],
+            nested_blocks: vec![],
+            span: test_span(),
+        };
+        assert_eq!(builder.build_connection(&mariadb).unwrap().driver, "mysql");
+
+        // Drivers are matched by their lowercase name at runtime
+        let upper = ConnectionBlock {
+            name: "shop".to_string(),
+            attributes: vec![make_attribute("driver", make_string_expr("MySQL"))],
+            nested_blocks: vec![],
+            span: test_span(),
+        };
+        assert_eq!(builder.build_connection(&upper).unwrap().driver, "mysql");
+    }
+
+    #[test]
+    fn test_build_connection_role() {
+        let builder = PlanBuilder::default();
+        let conn_block = |driver: &str| ConnectionBlock {
+            name: "warehouse".to_string(),
+            attributes: vec![
+                make_attribute("driver", make_string_expr(driver)),
+                make_attribute("role", make_string_expr("app_owner")),
+            ],
+            nested_blocks: vec![],
+            span: test_span(),
+        };
+
+        let conn = builder.build_connection(&conn_block("postgres")).unwrap();
+        assert_eq!(
+            conn.properties.get_string("role").as_deref(),
+            Some("app_owner")
+        );
+
+        let err = builder.build_connection(&conn_block("mysql")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "connection error: connection 'warehouse': 'role' is only supported for postgres connections"
         );
-        Expression::new(ExpressionKind::DotNotation(path), test_span())
     }
 
-    fn make_attribute(key: &str, value: Expression) -> Attribute {
-        Attribute {
-            key: Identifier::new(key, test_span()),
-            value,
+    #[test]
+    fn test_build_connection_max_concurrent_pipelines() {
+        let builder = PlanBuilder::default();
+        let conn_block = |max: Expression| ConnectionBlock {
+            name: "shop_prod".to_string(),
+            attributes: vec![
+                make_attribute("driver", make_string_expr("mysql")),
+                make_attribute("max_concurrent_pipelines", max),
+            ],
+            nested_blocks: vec![],
             span: test_span(),
-        }
-    }
+        };
 
-    fn make_nested_block(kind: &str, attributes: Vec<Attribute>) -> NestedBlock {
-        NestedBlock {
-            kind: kind.to_string(),
-            attributes,
-            span: test_span(),
+        let conn = builder
+            .build_connection(&conn_block(make_number_expr(2.0)))
+            .unwrap();
+        assert_eq!(conn.max_concurrent_pipelines(), Some(2));
+
+        for bad in [
+            make_number_expr(0.0),
+            make_number_expr(1.5),
+            make_string_expr("2"),
+        ] {
+            let err = builder.build_connection(&conn_block(bad)).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "connection error: connection 'shop_prod': 'max_concurrent_pipelines' must be a positive integer"
+            );
         }
     }
 
     #[test]
-    fn test_extract_definitions() {
-        let mut builder = PlanBuilder::default();
-        let def_block = DefineBlock {
+    fn test_build_connection_tls() {
+        let builder = PlanBuilder::default();
+        let conn_block = |driver: &str, url: &str, tls: Vec<Attribute>| ConnectionBlock {
+            name: "managed".to_string(),
             attributes: vec![
-                make_attribute("tax_rate", make_number_expr(1.4)),
-                make_attribute("country", make_string_expr("US")),
-                make_attribute("enabled", make_bool_expr(true)),
+                make_attribute("driver", make_string_expr(driver)),
+                make_attribute("url", make_string_expr(url)),
             ],
+            nested_blocks: vec![make_nested_block("tls", tls)],
             span: test_span(),
         };
 
-        let result = builder.extract_definitions(&def_block).unwrap();
-        assert_eq!(result.len(), 3);
-
-        let definition_info = result.get("tax_rate").unwrap();
-        assert_eq!(definition_info.value, Value::Float(1.4));
+        let conn = builder
+            .build_connection(&conn_block(
+                "postgres",
+                "postgres://etl@pg.example.com/dw?sslmode=prefer",
+                vec![
+                    make_attribute("mode", make_string_expr("verify-full")),
+                    make_attribute("ca_cert", make_string_expr("/tls/ca.pem")),
+                ],
+            ))
+            .unwrap();
+        assert_eq!(
+            conn.properties.get_string("url").as_deref(),
+            Some(
+                "postgres://etl@pg.example.com/dw?sslmode=verify-full&sslrootcert=%2Ftls%2Fca.pem"
+            )
+        );
 
-        let definition_info = result.get("country").unwrap();
-        assert_eq!(definition_info.value, Value::String("US".to_string()));
+        let conn = builder
+            .build_connection(&conn_block(
+                "mysql",
+                "mysql://app@db.example.com/shop",
+                vec![make_attribute("mode", make_string_expr("require"))],
+            ))
+            .unwrap();
+        assert_eq!(
+            conn.properties.get_string("url").as_deref(),
+            Some(
+                "mysql://app@db.example.com/shop?require_ssl=true&verify_ca=false&verify_identity=false"
+            )
+        );
 
-        let definition_info = result.get("enabled").unwrap();
-        assert_eq!(definition_info.value, Value::Boolean(true));
+        let err = builder
+            .build_connection(&conn_block(
+                "mysql",
+                "mysql://app@db.example.com/shop",
+                vec![make_attribute("mode", make_string_expr("prefer"))],
+            ))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("connection 'managed': tls mode 'prefer' is not supported on mysql"),
+            "{err}"
+        );
     }
 
     #[test]
-    fn test_build_connection_basic() {
+    fn test_build_connection_session() {
         let builder = PlanBuilder::default();
-        let conn_block = ConnectionBlock {
-            name: "postgres_prod".to_string(),
-            attributes: vec![
-                make_attribute("driver", make_string_expr("postgres")),
-                make_attribute("host", make_string_expr("localhost")),
-                make_attribute("port", make_number_expr(5432.0)),
-            ],
-            nested_blocks: vec![],
+        let conn_block = |driver: &str, session: Vec<Attribute>| ConnectionBlock {
+            name: "warehouse".to_string(),
+            attributes: vec![make_attribute("driver", make_string_expr(driver))],
+            nested_blocks: vec![make_nested_block("session", session)],
             span: test_span(),
         };
+        let settings = || {
+            vec![
+                make_attribute("synchronous_commit", make_string_expr("off")),
+                make_attribute("maintenance_work_mem", make_string_expr("1GB")),
+            ]
+        };
 
-        let result = builder.build_connection(&conn_block).unwrap();
-        assert_eq!(result.name, "postgres_prod");
-        assert_eq!(result.driver, "postgres");
-        assert_eq!(result.properties.len(), 3);
+        let conn = builder
+            .build_connection(&conn_block("postgres", settings()))
+            .unwrap();
         assert_eq!(
-            result.properties.get("host"),
-            Some(&Value::String("localhost".to_string()))
+            conn.session_settings(),
+            vec![
+                (
+                    "maintenance_work_mem".to_string(),
+                    Value::String("1GB".to_string())
+                ),
+                (
+                    "synchronous_commit".to_string(),
+                    Value::String("off".to_string())
+                ),
+            ]
         );
+        assert!(
+            builder
+                .build_connection(&conn_block("mysql", settings()))
+                .is_ok()
+        );
+
+        let err = builder
+            .build_connection(&conn_block("csv", settings()))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "connection error: connection 'warehouse': a 'session' block is only supported for postgres and mysql connections"
+        );
+
+        let bad_name = vec![make_attribute(
+            "work_mem; DROP TABLE users",
+            make_string_expr("64MB"),
+        )];
+        let err = builder
+            .build_connection(&conn_block("postgres", bad_name))
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid session setting name"));
     }
 
     #[test]
@@ -1427,8 +4706,12 @@ mod tests {
             name: "pipeline2".to_string(),
             description: None,
             after: Some(vec![array_expr]),
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1438,7 +4721,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1455,8 +4749,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1472,7 +4770,18 @@ mod tests {
                 sql: vec!["DROP TABLE temp".to_string()],
                 span: test_span(),
             }),
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1481,6 +4790,509 @@ mod tests {
         assert_eq!(lifecycle.before[0], "CREATE TABLE IF NOT EXISTS temp");
         assert_eq!(lifecycle.after.len(), 1);
         assert_eq!(lifecycle.after[0], "DROP TABLE temp");
+        assert!(lifecycle.on_batch.is_none());
+    }
+
+    #[test]
+    fn test_build_batch_hook() {
+        let builder = PlanBuilder::default();
+        let block = |attributes| OnBatchBlock {
+            sql: vec!["REFRESH MATERIALIZED VIEW CONCURRENTLY mv_orders".to_string()],
+            attributes,
+            span: test_span(),
+        };
+
+        let hook = builder
+            .build_batch_hook(&block(vec![make_attribute(
+                "every",
+                make_number_expr(10.0),
+            )]))
+            .unwrap();
+        assert_eq!(hook.every, 10);
+        assert_eq!(hook.sql.len(), 1);
+
+        // Runs after every batch unless told otherwise
+        assert_eq!(builder.build_batch_hook(&block(vec![])).unwrap().every, 1);
+
+        for bad in [make_number_expr(0.0), make_number_expr(2.5)] {
+            let err = builder
+                .build_batch_hook(&block(vec![make_attribute("every", bad)]))
+                .unwrap_err();
+            assert!(err.to_string().contains("positive integer"));
+        }
+
+        let err = builder
+            .build_batch_hook(&block(vec![make_attribute(
+                "interval",
+                make_number_expr(5.0),
+            )]))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown on_batch attribute 'interval'")
+        );
+
+        let empty = OnBatchBlock {
+            sql: vec![],
+            attributes: vec![],
+            span: test_span(),
+        };
+        assert!(builder.build_batch_hook(&empty).is_err());
+    }
+
+    #[test]
+    fn test_build_partitioning() {
+        let builder = PlanBuilder::default();
+        let postgres = Connection {
+            name: "warehouse".to_string(),
+            driver: "postgres".to_string(),
+            properties: Properties::new(),
+            nested_configs: HashMap::new(),
+        };
+
+        let range = make_nested_block(
+            "partition_by",
+            vec![
+                make_attribute("strategy", make_string_expr("range")),
+                make_attribute("column", make_string_expr("created_at")),
+                make_attribute("interval", make_string_expr("year")),
+                make_attribute("start", make_string_expr("2023-01-01")),
+                make_attribute("end", make_string_expr("2025-01-01")),
+            ],
+        );
+        let partitioning = builder.build_partitioning(&range, &postgres).unwrap();
+        assert_eq!(partitioning.column, "created_at");
+        assert_eq!(partitioning.partitions("events").len(), 3);
+
+        let hash = make_nested_block(
+            "partition_by",
+            vec![
+                make_attribute("strategy", make_string_expr("hash")),
+                make_attribute("column", make_string_expr("customer_id")),
+                make_attribute("partitions", make_number_expr(8.0)),
+            ],
+        );
+        let partitioning = builder.build_partitioning(&hash, &postgres).unwrap();
+        assert_eq!(partitioning.scheme, PartitionScheme::Hash { partitions: 8 });
+
+        let misplaced = make_nested_block(
+            "partition_by",
+            vec![
+                make_attribute("strategy", make_string_expr("hash")),
+                make_attribute("column", make_string_expr("customer_id")),
+                make_attribute("partitions", make_number_expr(8.0)),
+                make_attribute("interval", make_string_expr("month")),
+            ],
+        );
+        let err = builder
+            .build_partitioning(&misplaced, &postgres)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'interval' cannot be used with strategy 'hash'")
+        );
+
+        let mysql = Connection {
+            driver: "mysql".to_string(),
+            ..postgres
+        };
+        let err = builder.build_partitioning(&hash, &mysql).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("does not support partitioned tables")
+        );
+    }
+
+    #[test]
+    fn test_build_role_map() {
+        let builder = PlanBuilder::default();
+
+        let roles = builder
+            .build_role_map(&make_nested_block(
+                "roles",
+                vec![
+                    make_attribute("app_rw", make_string_expr("etl_writer")),
+                    make_attribute("legacy_ro", make_string_expr(" reporting ")),
+                ],
+            ))
+            .unwrap();
+        assert_eq!(roles["app_rw"], "etl_writer");
+        assert_eq!(roles["legacy_ro"], "reporting");
+
+        let err = builder
+            .build_role_map(&make_nested_block(
+                "roles",
+                vec![make_attribute("app_rw", make_string_expr(""))],
+            ))
+            .unwrap_err();
+        assert!(err.to_string().contains("'app_rw' must be a non-empty"));
+
+        let err = builder
+            .build_role_map(&make_nested_block(
+                "roles",
+                vec![
+                    make_attribute("app_rw", make_string_expr("a")),
+                    make_attribute("app_rw", make_string_expr("b")),
+                ],
+            ))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("role 'app_rw' is given more than once")
+        );
+    }
+
+    #[test]
+    fn test_build_column_types() {
+        let builder = PlanBuilder::default();
+        let block = |attributes| TypesBlock {
+            attributes,
+            span: test_span(),
+        };
+
+        let types = builder
+            .build_column_types(&block(vec![
+                make_attribute("order_total", make_string_expr("numeric(12, 2)")),
+                make_attribute("tags", make_string_expr(" text[] ")),
+            ]))
+            .unwrap();
+        assert_eq!(types["order_total"], "numeric(12, 2)");
+        assert_eq!(types["tags"], "text[]");
+
+        for bad in [
+            make_string_expr("text; drop table orders"),
+            make_string_expr(""),
+            make_number_expr(12.0),
+        ] {
+            let err = builder
+                .build_column_types(&block(vec![make_attribute("notes", bad)]))
+                .unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("'notes' must be a destination type")
+            );
+        }
+
+        let err = builder
+            .build_column_types(&block(vec![
+                make_attribute("notes", make_string_expr("text")),
+                make_attribute("notes", make_string_expr("varchar(64)")),
+            ]))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("column 'notes' is given more than once")
+        );
+    }
+
+    #[test]
+    fn test_build_seed() {
+        let builder = PlanBuilder::default();
+        let row = |fields| SeedRow {
+            fields,
+            span: test_span(),
+        };
+        let block = |rows| SeedBlock {
+            attributes: vec![make_attribute("table", make_string_expr("statuses"))],
+            rows,
+            span: test_span(),
+        };
+
+        let seed = builder
+            .build_seed(&block(vec![
+                row(vec![
+                    make_attribute("id", make_number_expr(1.0)),
+                    make_attribute("name", make_string_expr("new")),
+                ]),
+                row(vec![
+                    make_attribute("name", make_string_expr("shipped")),
+                    make_attribute("id", make_number_expr(2.0)),
+                ]),
+            ]))
+            .unwrap();
+        assert_eq!(seed.table, "statuses");
+        assert_eq!(seed.columns, ["id", "name"]);
+        assert_eq!(
+            seed.rows[1],
+            [Value::Float(2.0), Value::String("shipped".to_string())]
+        );
+
+        let err = builder
+            .build_seed(&block(vec![
+                row(vec![make_attribute("id", make_number_expr(1.0))]),
+                row(vec![
+                    make_attribute("id", make_number_expr(2.0)),
+                    make_attribute("name", make_string_expr("shipped")),
+                ]),
+            ]))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("every row must set the same columns as the first (id)")
+        );
+
+        let err = builder
+            .build_seed(&block(vec![row(vec![
+                make_attribute("id", make_number_expr(1.0)),
+                make_attribute("id", make_number_expr(2.0)),
+            ])]))
+            .unwrap_err();
+        assert!(err.to_string().contains("a row sets column 'id' twice"));
+
+        let err = builder.build_seed(&block(vec![])).unwrap_err();
+        assert!(err.to_string().contains("rows must list at least one row"));
+    }
+
+    #[test]
+    fn test_build_script() {
+        let builder = PlanBuilder::default();
+        let block = |attributes| ScriptBlock {
+            attributes,
+            span: test_span(),
+        };
+
+        let script = builder
+            .build_script(&block(vec![make_attribute(
+                "code",
+                make_string_expr("row.region = row.packed.sub_string(0, 2);"),
+            )]))
+            .unwrap();
+        assert_eq!(script.code, "row.region = row.packed.sub_string(0, 2);");
+        assert_eq!(script.max_operations, DEFAULT_SCRIPT_MAX_OPERATIONS);
+
+        let script = builder
+            .build_script(&block(vec![
+                make_attribute("code", make_string_expr("row.x = 1;")),
+                make_attribute("max_operations", make_number_expr(500.0)),
+            ]))
+            .unwrap();
+        assert_eq!(script.max_operations, 500);
+
+        let err = builder
+            .build_script(&block(vec![
+                make_attribute("code", make_string_expr("row.x = 1;")),
+                make_attribute("max_operations", make_number_expr(0.0)),
+            ]))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("max_operations must be a positive")
+        );
+
+        let err = builder.build_script(&block(vec![])).unwrap_err();
+        assert!(err.to_string().contains("code must be a non-empty string"));
+
+        let err = builder
+            .build_script(&block(vec![make_attribute(
+                "language",
+                make_string_expr("lua"),
+            )]))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown attribute 'language'"));
+    }
+
+    #[test]
+    fn test_build_aggregate() {
+        let builder = PlanBuilder::default();
+        let source = DataSource {
+            connection: Connection {
+                name: "shop".to_string(),
+                driver: "postgres".to_string(),
+                properties: Properties::new(),
+                nested_configs: HashMap::new(),
+            },
+            table: "orders".to_string(),
+            union: vec![],
+            source_column: None,
+            filters: vec![],
+            joins: vec![Join {
+                alias: "customers".to_string(),
+                table: "customers".to_string(),
+                condition: None,
+            }],
+            pagination: None,
+            graph_references: None,
+            timezones: SourceTimezones::default(),
+            schema_scope: None,
+        };
+        let call = |name: &str, args: Vec<Expression>| {
+            Expression::new(
+                ExpressionKind::FunctionCall {
+                    name: name.to_string(),
+                    arguments: args,
+                },
+                test_span(),
+            )
+        };
+        let group_by = |columns: Vec<Expression>| {
+            make_attribute(
+                "group_by",
+                Expression::new(ExpressionKind::Array(columns), test_span()),
+            )
+        };
+        let block = |attributes| AggregateBlock {
+            attributes,
+            span: test_span(),
+        };
+
+        let aggregation = builder
+            .build_aggregate(
+                &block(vec![
+                    group_by(vec![
+                        make_dotpath_expr(vec!["customers", "country"]),
+                        make_ident_expr("status"),
+                    ]),
+                    make_attribute(
+                        "total",
+                        call("SUM", vec![make_dotpath_expr(vec!["orders", "amount"])]),
+                    ),
+                    make_attribute("order_count", call("count", vec![])),
+                ]),
+                &source,
+            )
+            .unwrap();
+        assert_eq!(
+            aggregation.group_by,
+            vec![
+                QualCol {
+                    table: "customers".to_string(),
+                    column: "country".to_string()
+                },
+                QualCol {
+                    table: "orders".to_string(),
+                    column: "status".to_string()
+                },
+            ]
+        );
+        assert_eq!(aggregation.aggregates[0].function, AggregateFunction::Sum);
+        assert_eq!(
+            aggregation.aggregates[0].column.as_ref().unwrap().column,
+            "amount"
+        );
+        assert_eq!(aggregation.aggregates[1].name, "order_count");
+        assert!(aggregation.aggregates[1].column.is_none());
+
+        let err = builder
+            .build_aggregate(
+                &block(vec![make_attribute(
+                    "total",
+                    call("median", vec![make_ident_expr("amount")]),
+                )]),
+                &source,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("'total' must be sum()"));
+
+        let err = builder
+            .build_aggregate(
+                &block(vec![make_attribute("total", call("sum", vec![]))]),
+                &source,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("only count() may take none"));
+
+        let err = builder
+            .build_aggregate(
+                &block(vec![group_by(vec![make_dotpath_expr(vec![
+                    "invoices", "id",
+                ])])]),
+                &source,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("'invoices.id' is not a column"));
+
+        let err = builder
+            .build_aggregate(
+                &block(vec![
+                    group_by(vec![make_ident_expr("status")]),
+                    make_attribute("status", call("count", vec![])),
+                ]),
+                &source,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("'status' is given more than once"));
+
+        let err = builder
+            .build_aggregate(&block(vec![]), &source)
+            .unwrap_err();
+        assert!(err.to_string().contains("at least one aggregate"));
+
+        let settings = HashMap::from([("infer_schema".to_string(), Value::Boolean(true))]);
+        let err =
+            check_reshaped("daily", "an aggregate block", false, &source, &settings).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("pipeline 'daily': infer_schema cannot be used")
+        );
+        assert!(
+            check_reshaped(
+                "daily",
+                "an aggregate block",
+                false,
+                &source,
+                &HashMap::new()
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_build_pivot() {
+        let builder = PlanBuilder::default();
+        let list =
+            |items: Vec<Expression>| Expression::new(ExpressionKind::Array(items), test_span());
+        let block = |attributes| PivotBlock {
+            attributes,
+            span: test_span(),
+        };
+
+        let pivot = builder
+            .build_pivot(&block(vec![
+                make_attribute("key", make_ident_expr("attr_name")),
+                make_attribute("value", make_ident_expr("attr_value")),
+                make_attribute(
+                    "keys",
+                    list(vec![make_string_expr("color"), make_string_expr("size")]),
+                ),
+                make_attribute("group_by", list(vec![make_ident_expr("product_id")])),
+            ]))
+            .unwrap();
+        assert_eq!(pivot.direction, PivotDirection::Pivot);
+        assert_eq!(pivot.key, "attr_name");
+        assert_eq!(pivot.keys, vec!["color", "size"]);
+        assert_eq!(pivot.group_by, vec!["product_id"]);
+
+        let pivot = builder
+            .build_pivot(&block(vec![
+                make_attribute("unpivot", make_bool_expr(true)),
+                make_attribute("key", make_string_expr("attribute")),
+                make_attribute("value", make_string_expr("value")),
+                make_attribute("keys", list(vec![make_ident_expr("color")])),
+            ]))
+            .unwrap();
+        assert_eq!(pivot.direction, PivotDirection::Unpivot);
+
+        let err = builder
+            .build_pivot(&block(vec![
+                make_attribute("key", make_ident_expr("attr")),
+                make_attribute("value", make_ident_expr("val")),
+            ]))
+            .unwrap_err();
+        assert!(err.to_string().contains("key, value and keys are required"));
+
+        let err = builder
+            .build_pivot(&block(vec![
+                make_attribute("key", make_ident_expr("attr")),
+                make_attribute("value", make_ident_expr("val")),
+                make_attribute("keys", list(vec![make_ident_expr("Attr")])),
+            ]))
+            .unwrap_err();
+        assert!(err.to_string().contains("'Attr' is given more than once"));
+
+        let err = builder
+            .build_pivot(&block(vec![make_attribute("keys", list(vec![]))]))
+            .unwrap_err();
+        assert!(err.to_string().contains("keys must be a non-empty list"));
     }
 
     #[test]
@@ -1490,8 +5302,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1501,20 +5317,34 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: Some(SettingsBlock {
                 attributes: vec![
                     make_attribute("batch_size", make_number_expr(100.0)),
-                    make_attribute("parallel", make_bool_expr(true)),
+                    make_attribute("create_missing_tables", make_bool_expr(true)),
                 ],
                 span: test_span(),
             }),
+            schema_scope: None,
             span: test_span(),
         };
 
         let settings = builder.build_settings(&pipeline).unwrap();
         assert_eq!(settings.len(), 2);
         assert_eq!(settings.get("batch_size"), Some(&Value::Float(100.0)));
-        assert_eq!(settings.get("parallel"), Some(&Value::Boolean(true)));
+        assert_eq!(
+            settings.get("create_missing_tables"),
+            Some(&Value::Boolean(true))
+        );
     }
 
     #[test]
@@ -1683,6 +5513,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compile_map_literal() {
+        use smql_syntax::ast::expr::MapEntry;
+
+        let builder = PlanBuilder::default();
+        let entry = |key: &str, value| MapEntry {
+            key: key.to_string(),
+            value,
+            span: test_span(),
+        };
+        let expr = Expression::new(
+            ExpressionKind::Map(vec![
+                entry("A", make_string_expr("active")),
+                entry("rank", make_number_expr(2.0)),
+            ]),
+            test_span(),
+        );
+
+        match builder.compile_expression(&expr).unwrap() {
+            CompiledExpression::Literal(Value::Json(json)) => {
+                assert_eq!(json, serde_json::json!({ "A": "active", "rank": 2.0 }))
+            }
+            other => panic!("Expected map literal, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_convert_binop_all_operators() {
         let builder = PlanBuilder::default();
@@ -1746,6 +5602,8 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: Some(FromBlock {
                 attributes: vec![
                     make_attribute(
@@ -1759,6 +5617,8 @@ mod tests {
                 span: test_span(),
             }),
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1774,7 +5634,18 @@ mod tests {
             }),
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1791,8 +5662,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1841,7 +5716,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1864,8 +5750,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1882,7 +5772,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1893,6 +5794,82 @@ mod tests {
         assert!(matches!(retry.backoff, BackoffStrategy::Exponential));
     }
 
+    #[test]
+    fn test_build_retry_backoff() {
+        let builder = PlanBuilder::default();
+        let retry = |attributes: Vec<Attribute>| {
+            let pipeline = PipelineBlock {
+                name: "test".to_string(),
+                description: None,
+                after: None,
+                requires_env: None,
+                env: None,
+                from: None,
+                to: None,
+                unions: vec![],
+                sinks: vec![],
+                where_clauses: vec![],
+                with_block: None,
+                select_block: None,
+                named_select_blocks: vec![],
+                validate_block: None,
+                on_error_block: Some(OnErrorBlock {
+                    retry: Some(RetryBlock {
+                        attributes,
+                        span: test_span(),
+                    }),
+                    failed_rows: None,
+                    span: test_span(),
+                }),
+                paginate_block: None,
+                before_block: None,
+                after_block: None,
+                on_batch_block: None,
+                verify_block: None,
+                approve_block: None,
+                log_block: None,
+                types_block: None,
+                timezones_block: None,
+                seed_blocks: vec![],
+                script_block: None,
+                aggregate_block: None,
+                pivot_block: None,
+                settings_block: None,
+                schema_scope: None,
+                span: test_span(),
+            };
+            builder
+                .build_error_handling(&pipeline)
+                .map(|eh| eh.retry.unwrap())
+        };
+
+        let linear = retry(vec![
+            make_attribute("backoff", make_string_expr("linear")),
+            make_attribute("delay", make_string_expr("250ms")),
+            make_attribute("max_delay", make_string_expr("2s")),
+            make_attribute("jitter", make_bool_expr(false)),
+        ])
+        .unwrap();
+        assert_eq!(linear.backoff, BackoffStrategy::Linear);
+        assert_eq!(linear.delay_ms, 250);
+        assert_eq!(linear.max_delay_ms, Some(2000));
+        assert!(!linear.jitter);
+
+        // A duration is shorthand for exponential backoff from that delay
+        let shorthand = retry(vec![make_attribute("backoff", make_string_expr("5s"))]).unwrap();
+        assert_eq!(shorthand.backoff, BackoffStrategy::Exponential);
+        assert_eq!(shorthand.delay_ms, 5000);
+        assert!(shorthand.jitter);
+
+        assert!(
+            retry(vec![make_attribute(
+                "backoff",
+                make_string_expr("quadratic")
+            )])
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_failed_rows_with_table_block_with_schema() {
         let mut builder = PlanBuilder::default();
@@ -1912,8 +5889,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -1941,7 +5922,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -1984,8 +5976,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -2012,7 +6008,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -2044,8 +6051,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -2069,7 +6080,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -2094,8 +6116,12 @@ mod tests {
             name: "test".to_string(),
             description: None,
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -2119,7 +6145,18 @@ mod tests {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: test_span(),
         };
 
@@ -2136,6 +6173,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_failed_rows_save_to_file_action() {
+        let builder = PlanBuilder::default();
+        let pipeline = |file_attrs: Vec<Attribute>| PipelineBlock {
+            name: "test".to_string(),
+            description: None,
+            after: None,
+            requires_env: None,
+            env: None,
+            from: None,
+            to: None,
+            unions: vec![],
+            sinks: vec![],
+            where_clauses: vec![],
+            with_block: None,
+            select_block: None,
+            named_select_blocks: vec![],
+            validate_block: None,
+            on_error_block: Some(OnErrorBlock {
+                retry: None,
+                failed_rows: Some(FailedRowsBlock {
+                    attributes: vec![make_attribute("action", make_string_expr("save_to_file"))],
+                    nested_blocks: vec![make_nested_block("file", file_attrs)],
+                    span: test_span(),
+                }),
+                span: test_span(),
+            }),
+            paginate_block: None,
+            before_block: None,
+            after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
+            settings_block: None,
+            schema_scope: None,
+            span: test_span(),
+        };
+
+        let error_handling = builder
+            .build_error_handling(&pipeline(vec![make_attribute(
+                "path",
+                make_string_expr("./quarantine/orders.json"),
+            )]))
+            .unwrap();
+        let failed_rows = error_handling.failed_rows.unwrap();
+        assert_eq!(failed_rows.action, FailedRowsAction::SaveToFile);
+        assert_eq!(
+            failed_rows.quarantine_path(),
+            Some("./quarantine/orders.json")
+        );
+
+        let err = builder
+            .build_error_handling(&pipeline(vec![make_attribute(
+                "path",
+                make_string_expr("./quarantine/orders.csv"),
+            )]))
+            .unwrap_err();
+        assert!(err.to_string().contains("save_to_file"), "{err}");
+    }
+
     #[test]
     fn test_env_function_with_typed_defaults() {
         let mut env_ctx = EnvContext::empty();
@@ -2226,4 +6330,14 @@ mod tests {
                 .contains("Failed to parse environment variable")
         );
     }
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("250ms").unwrap(), 250);
+        assert_eq!(parse_duration_ms("2m").unwrap(), 120_000);
+        assert_eq!(parse_duration_ms("1d").unwrap(), 86_400_000);
+
+        let err = parse_duration_ms(&format!("{}d", u64::MAX / 1000)).unwrap_err();
+        assert!(matches!(err, ConvertError::Plan(msg) if msg.contains("too long")));
+    }
 }
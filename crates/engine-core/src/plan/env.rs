@@ -43,6 +43,13 @@ impl EnvVarCollector {
             self.collect_from_connection_block(connection_block, &eval_fn);
         }
 
+        // Collect from global settings block
+        if let Some(settings) = &document.settings_block {
+            for attr in &settings.attributes {
+                self.collect_from_expr(&attr.value, Some("settings"), &eval_fn);
+            }
+        }
+
         // Collect from all pipeline blocks
         for pipeline_block in &document.pipelines {
             self.collect_from_pipeline_block(pipeline_block, &eval_fn);
@@ -102,18 +109,28 @@ impl EnvVarCollector {
     ) {
         let context = format!("pipeline.{}", pipeline_block.name);
 
-        // Collect from 'from' block
-        if let Some(from) = &pipeline_block.from {
+        // Collect from the pipeline's own env map
+        if let Some(env) = &pipeline_block.env {
+            self.collect_from_expr(env, Some(&context), &eval_fn);
+        }
+
+        // Collect from 'from' blocks
+        for from in pipeline_block.from.iter().chain(&pipeline_block.unions) {
             for attr in &from.attributes {
                 self.collect_from_expr(&attr.value, Some(&context), &eval_fn);
             }
         }
 
-        // Collect from 'to' block
-        if let Some(to) = &pipeline_block.to {
+        // Collect from 'to' blocks
+        for to in pipeline_block.to.iter().chain(&pipeline_block.sinks) {
             for attr in &to.attributes {
                 self.collect_from_expr(&attr.value, Some(&context), &eval_fn);
             }
+            if let Some(select) = &to.select_block {
+                for field in &select.fields {
+                    self.collect_from_expr(&field.value, Some(&context), &eval_fn);
+                }
+            }
         }
 
         // Collect from where clauses
@@ -231,6 +248,11 @@ impl EnvVarCollector {
                     self.collect_from_expr(item, context_name, eval_fn);
                 }
             }
+            ExpressionKind::Map(entries) => {
+                for entry in entries {
+                    self.collect_from_expr(&entry.value, context_name, eval_fn);
+                }
+            }
             // Literals, identifiers, and dot notation don't need recursive collection
             _ => {}
         }
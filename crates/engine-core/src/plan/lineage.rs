@@ -0,0 +1,396 @@
+use crate::plan::settings::effective_value;
+use expression_engine::analyzer::ExpressionAnalyzer;
+use model::{
+    core::value::Value,
+    execution::{
+        errors::ConvertError,
+        expr::CompiledExpression,
+        lineage::{ColumnLineage, ColumnRef, PipelineLineage},
+        pipeline::{Pipeline, Transformation, WriteMode},
+    },
+};
+
+const SETTING_INFER_DEPENDENCIES: &str = "infer_dependencies";
+const SETTING_EMIT_KEYMAP: &str = "emit_keymap";
+const ERR_SHARED_SETTING: &str = "pipelines '{first}' and '{second}' both write table '{table}' but set {setting} differently ({first_value} and {second_value}); the table's schema changes must agree";
+const ERR_SHARED_MODE: &str = "pipelines '{first}' and '{second}' both write table '{table}' and may run at the same time, in modes {first_mode} and {second_mode}; give them the same mode or order them with `after`";
+const ERR_SHARED_REPLACE: &str = "pipelines '{first}' and '{second}' both write table '{table}' and may run at the same time, but replace mode empties the table first; order them with `after`";
+/// Settings that change the destination table itself, before or after the
+/// load, rather than how one pipeline writes its rows
+const TABLE_SETTINGS: [&str; 6] = [
+    "create_missing_tables",
+    "create_missing_columns",
+    "ignore_constraints",
+    "migrate_indexes",
+    "defer_indexes",
+    "fast_load",
+];
+const ERR_INFERRED_CYCLE: &str = "pipeline '{pipeline}' reads table '{table}', which pipeline '{writer}' writes, but '{writer}' already runs after '{pipeline}'; order them with `after` and set infer_dependencies = false on '{pipeline}'";
+
+/// Column-level lineage of a compiled pipeline.
+pub fn pipeline_lineage(pipeline: &Pipeline) -> PipelineLineage {
+    let source = &pipeline.source;
+    let destination_table = (!pipeline.validate_only).then(|| pipeline.destination.table.clone());
+    let resolver = TableResolver { pipeline };
+
+    let mut columns = Vec::new();
+    if let Some(table) = &destination_table {
+        columns.extend(
+            pipeline
+                .transformations
+                .iter()
+                .map(|t| column_lineage(table, t, &resolver)),
+        );
+
+        // Named selects write the referenced table's own destination table
+        let mut named: Vec<_> = pipeline.named_transformations.iter().collect();
+        named.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, transformations) in named {
+            let table = pipeline.destination.table_map.get(name).unwrap_or(name);
+            columns.extend(
+                transformations
+                    .iter()
+                    .map(|t| column_lineage(table, t, &resolver)),
+            );
+        }
+
+        for sink in &pipeline.sinks {
+            columns.extend(
+                sink.transformations
+                    .iter()
+                    .map(|t| column_lineage(&sink.table, t, &resolver)),
+            );
+        }
+
+        for call in &pipeline.plugin_transforms {
+            let mut inputs: Vec<_> = call.input_mapping.iter().collect();
+            inputs.sort();
+            columns.push(ColumnLineage {
+                destination: ColumnRef::new(table.clone(), call.output_column.clone()),
+                sources: dedup(
+                    inputs
+                        .iter()
+                        .map(|(_, column)| ColumnRef::new(source.table.clone(), (*column).clone()))
+                        .collect(),
+                ),
+                expression: Some(format!(
+                    "{}({})",
+                    call.plugin_name,
+                    inputs
+                        .iter()
+                        .map(|(field, column)| format!("{field} = {column}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            });
+        }
+    }
+
+    let conditions = source
+        .filters
+        .iter()
+        .map(|f| &f.condition)
+        .chain(source.joins.iter().filter_map(|j| j.condition.as_ref()));
+    let filter_columns = dedup(conditions.flat_map(|c| resolver.sources(c)).collect());
+
+    let copies_unmapped = !matches!(
+        effective_value(&pipeline.settings, "copy_columns"),
+        Ok(Value::String(mode)) if mode.eq_ignore_ascii_case("MAP_ONLY")
+    );
+
+    PipelineLineage {
+        pipeline: pipeline.name.clone(),
+        source_table: source.table.clone(),
+        destination_table,
+        columns,
+        copies_unmapped,
+        filter_columns,
+    }
+}
+
+fn column_lineage(
+    table: &str,
+    transformation: &Transformation,
+    resolver: &TableResolver,
+) -> ColumnLineage {
+    let expr = &transformation.expression;
+    let expression =
+        (!ExpressionAnalyzer::is_simple_column(expr)).then(|| ExpressionAnalyzer::to_string(expr));
+    ColumnLineage {
+        destination: ColumnRef::new(table, transformation.target_field.clone()),
+        sources: dedup(resolver.sources(expr)),
+        expression,
+    }
+}
+
+/// Resolves the table part of column references against the pipeline's
+/// main table and join aliases.
+struct TableResolver<'a> {
+    pipeline: &'a Pipeline,
+}
+
+impl TableResolver<'_> {
+    fn sources(&self, expr: &CompiledExpression) -> Vec<ColumnRef> {
+        ExpressionAnalyzer::extract_columns(expr)
+            .into_iter()
+            .map(|column| match column.split_once('.') {
+                Some((qualifier, column)) => ColumnRef::new(self.table(qualifier), column),
+                None => ColumnRef::new(self.pipeline.source.table.clone(), column),
+            })
+            .collect()
+    }
+
+    fn table(&self, qualifier: &str) -> String {
+        self.pipeline
+            .source
+            .joins
+            .iter()
+            .find(|j| j.alias.eq_ignore_ascii_case(qualifier))
+            .map_or_else(|| qualifier.to_string(), |j| j.table.clone())
+    }
+}
+
+fn dedup(mut columns: Vec<ColumnRef>) -> Vec<ColumnRef> {
+    columns.sort();
+    columns.dedup();
+    columns
+}
+
+/// Makes each pipeline run after the pipelines that write a table it reads
+/// (its `from` tables, joined tables and tables its select references) over
+/// the same connection, and after the pipelines with `emit_keymap = true`
+/// whose tables its `lookup_key` calls name. Pipelines with
+/// `infer_dependencies = false` keep only their `after` list, and pipelines
+/// reading a table pattern are skipped until it is expanded. `lineage` is
+/// in pipeline order.
+pub(crate) fn infer_dependencies(
+    pipelines: &mut [Pipeline],
+    lineage: &[PipelineLineage],
+) -> Result<(), ConvertError> {
+    // (connection, table) -> pipelines writing it
+    let mut writers: Vec<(&str, String, String)> = Vec::new();
+    for pipeline in pipelines
+        .iter()
+        .filter(|p| !p.validate_only && !p.source.is_table_pattern())
+    {
+        let destination = &pipeline.destination;
+        let tables = std::iter::once(&destination.table)
+            .chain(destination.table_map.values())
+            .chain(pipeline.sinks.iter().map(|s| &s.table));
+        for table in tables {
+            writers.push((
+                destination.connection.name.as_str(),
+                table.to_ascii_lowercase(),
+                pipeline.name.clone(),
+            ));
+        }
+    }
+
+    // table -> pipelines recording its key crosswalk, on any connection
+    let keymap_writers: Vec<(String, String)> = pipelines
+        .iter()
+        .filter(|p| {
+            matches!(
+                effective_value(&p.settings, SETTING_EMIT_KEYMAP),
+                Ok(Value::Boolean(true))
+            )
+        })
+        .map(|p| (p.destination.table.to_ascii_lowercase(), p.name.clone()))
+        .collect();
+
+    let mut inferred: Vec<(usize, String, String)> = Vec::new();
+    for (index, (pipeline, lineage)) in pipelines.iter().zip(lineage).enumerate() {
+        if pipeline.source.is_table_pattern()
+            || matches!(
+                effective_value(&pipeline.settings, SETTING_INFER_DEPENDENCIES),
+                Ok(Value::Boolean(false))
+            )
+        {
+            continue;
+        }
+
+        let mut tables: Vec<String> = std::iter::once(lineage.source_table.clone())
+            .chain(pipeline.source.union.iter().cloned())
+            .chain(pipeline.source.joins.iter().map(|j| j.table.clone()))
+            .chain(
+                lineage
+                    .columns
+                    .iter()
+                    .flat_map(|c| c.sources.iter())
+                    .chain(lineage.filter_columns.iter())
+                    .map(|c| c.table.clone()),
+            )
+            .map(|t| t.to_ascii_lowercase())
+            .collect();
+        tables.sort();
+        tables.dedup();
+
+        for table in tables {
+            for (connection, written, writer) in &writers {
+                if *connection == pipeline.source.connection.name
+                    && *written == table
+                    && *writer != pipeline.name
+                    && !pipeline.dependencies.contains(writer)
+                    && !inferred.iter().any(|(i, _, w)| *i == index && w == writer)
+                {
+                    inferred.push((index, table.clone(), writer.clone()));
+                }
+            }
+        }
+
+        for table in pipeline.lookup_key_tables() {
+            for (written, writer) in &keymap_writers {
+                if *written == table
+                    && *writer != pipeline.name
+                    && !pipeline.dependencies.contains(writer)
+                    && !inferred.iter().any(|(i, _, w)| *i == index && w == writer)
+                {
+                    inferred.push((index, table.clone(), writer.clone()));
+                }
+            }
+        }
+    }
+
+    for (index, table, writer) in inferred {
+        let name = pipelines[index].name.clone();
+        if runs_after(pipelines, &writer, &name) {
+            return Err(ConvertError::Plan(
+                ERR_INFERRED_CYCLE
+                    .replace("{pipeline}", &name)
+                    .replace("{table}", &table)
+                    .replace("{writer}", &writer),
+            ));
+        }
+        let pipeline = &mut pipelines[index];
+        pipeline.dependencies.push(writer.clone());
+        pipeline.inferred_dependencies.push(writer);
+    }
+    Ok(())
+}
+
+/// Checks the pipelines that write the same destination table over the same
+/// connection: their table settings must agree, and those that may run at
+/// the same time must write in the same mode, other than replace. Each such
+/// pipeline lists the others in `concurrent_writers`. Runs after
+/// `infer_dependencies`, whose dependencies order writers too.
+pub(crate) fn check_shared_tables(pipelines: &mut [Pipeline]) -> Result<(), ConvertError> {
+    // (connection, table) -> indexes of the pipelines writing it
+    let mut tables: Vec<((String, String), Vec<usize>)> = Vec::new();
+    for (index, pipeline) in pipelines.iter().enumerate() {
+        // A table pattern's tables are known once it is expanded
+        if pipeline.validate_only || pipeline.source.is_table_pattern() {
+            continue;
+        }
+        let mut written: Vec<String> = std::iter::once(&pipeline.destination.table)
+            .chain(pipeline.sinks.iter().map(|s| &s.table))
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_ascii_lowercase())
+            .collect();
+        written.dedup();
+        for table in written {
+            let key = (pipeline.destination.connection.name.clone(), table);
+            match tables.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, writers)) => writers.push(index),
+                None => tables.push((key, vec![index])),
+            }
+        }
+    }
+
+    let mut concurrent: Vec<(usize, String)> = Vec::new();
+    for ((_, table), writers) in tables.iter().filter(|(_, w)| w.len() > 1) {
+        let (first, rest) = writers.split_first().expect("shared tables have writers");
+        let first = &pipelines[*first];
+        for other in rest.iter().map(|i| &pipelines[*i]) {
+            for setting in TABLE_SETTINGS {
+                let first_value = effective_value(&first.settings, setting).ok();
+                let other_value = effective_value(&other.settings, setting).ok();
+                if first_value != other_value {
+                    return Err(ConvertError::Plan(
+                        ERR_SHARED_SETTING
+                            .replace("{first}", &first.name)
+                            .replace("{second}", &other.name)
+                            .replace("{table}", table)
+                            .replace("{setting}", setting)
+                            .replace("{first_value}", &setting_text(first_value))
+                            .replace("{second_value}", &setting_text(other_value)),
+                    ));
+                }
+            }
+        }
+
+        for (n, &a) in writers.iter().enumerate() {
+            for &b in &writers[n + 1..] {
+                let (first, second) = (&pipelines[a], &pipelines[b]);
+                if runs_after(pipelines, &first.name, &second.name)
+                    || runs_after(pipelines, &second.name, &first.name)
+                {
+                    continue;
+                }
+                let first_mode = mode_name(&first.destination.mode);
+                let second_mode = mode_name(&second.destination.mode);
+                let err = if first_mode == "replace" || second_mode == "replace" {
+                    ERR_SHARED_REPLACE
+                } else if first_mode != second_mode {
+                    ERR_SHARED_MODE
+                } else {
+                    concurrent.push((a, second.name.clone()));
+                    concurrent.push((b, first.name.clone()));
+                    continue;
+                };
+                return Err(ConvertError::Plan(
+                    err.replace("{first}", &first.name)
+                        .replace("{second}", &second.name)
+                        .replace("{table}", table)
+                        .replace("{first_mode}", first_mode)
+                        .replace("{second_mode}", second_mode),
+                ));
+            }
+        }
+    }
+
+    for (index, writer) in concurrent {
+        let writers = &mut pipelines[index].concurrent_writers;
+        if !writers.contains(&writer) {
+            writers.push(writer);
+        }
+    }
+    Ok(())
+}
+
+fn mode_name(mode: &WriteMode) -> &'static str {
+    match mode {
+        WriteMode::Insert => "insert",
+        WriteMode::Update => "update",
+        WriteMode::Upsert => "upsert",
+        WriteMode::Replace => "replace",
+    }
+}
+
+fn setting_text(value: Option<Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s,
+        Some(Value::Boolean(b)) => b.to_string(),
+        Some(Value::Int(i)) => i.to_string(),
+        Some(other) => format!("{other:?}"),
+        None => "unset".to_string(),
+    }
+}
+
+/// Whether `pipeline` (transitively) depends on `other`.
+fn runs_after(pipelines: &[Pipeline], pipeline: &str, other: &str) -> bool {
+    let mut pending = vec![pipeline];
+    let mut seen = Vec::new();
+    while let Some(name) = pending.pop() {
+        if name == other {
+            return true;
+        }
+        if seen.contains(&name) {
+            continue;
+        }
+        seen.push(name);
+        if let Some(p) = pipelines.iter().find(|p| p.name == name) {
+            pending.extend(p.dependencies.iter().map(String::as_str));
+        }
+    }
+    false
+}
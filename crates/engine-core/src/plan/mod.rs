@@ -2,3 +2,8 @@ pub mod builder;
 pub mod cascade;
 pub mod env;
 pub mod execution;
+pub mod lineage;
+pub mod pagination;
+pub mod runbook;
+pub mod settings;
+pub mod table_pattern;
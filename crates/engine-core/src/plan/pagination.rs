@@ -0,0 +1,96 @@
+use connectors::sql::metadata::table::TableMetadata;
+use model::execution::pipeline::Pagination;
+
+/// Whether a pagination strategy pages on a `(cursor, tiebreaker)` keyset.
+pub fn uses_tiebreaker(strategy: &str) -> bool {
+    matches!(strategy.to_lowercase().as_str(), "numeric" | "timestamp")
+}
+
+/// Fill in a missing tiebreaker with the table's primary key.
+///
+/// Numeric and timestamp cursors are rarely unique on their own; rows sharing a
+/// cursor value at a page boundary would be skipped or read twice. When no
+/// tiebreaker is configured the single-column PK is used, qualified the same
+/// way as the cursor. Tables without a single-column PK are left unchanged.
+pub fn with_default_tiebreaker(pagination: &Pagination, table: &TableMetadata) -> Pagination {
+    let mut resolved = pagination.clone();
+    if resolved.tiebreaker.is_some() || !uses_tiebreaker(&resolved.strategy) {
+        return resolved;
+    }
+
+    if let [pk] = table.primary_keys.as_slice() {
+        let tiebreaker = match pagination.column.split_once('.') {
+            Some((qualifier, _)) => format!("{qualifier}.{pk}"),
+            None => pk.clone(),
+        };
+        resolved.tiebreaker = Some(tiebreaker);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn pagination(strategy: &str, column: &str, tiebreaker: Option<&str>) -> Pagination {
+        Pagination {
+            strategy: strategy.to_string(),
+            column: column.to_string(),
+            tiebreaker: tiebreaker.map(str::to_string),
+            timezone: None,
+            nulls: None,
+        }
+    }
+
+    fn table(primary_keys: &[&str]) -> TableMetadata {
+        TableMetadata {
+            name: "orders".to_string(),
+            schema: None,
+            collation: None,
+            columns: HashMap::new(),
+            primary_keys: primary_keys.iter().map(|pk| pk.to_string()).collect(),
+            foreign_keys: Vec::new(),
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn defaults_tiebreaker_to_qualified_pk() {
+        let resolved = with_default_tiebreaker(
+            &pagination("timestamp", "o.created_at", None),
+            &table(&["id"]),
+        );
+        assert_eq!(resolved.tiebreaker.as_deref(), Some("o.id"));
+
+        let resolved =
+            with_default_tiebreaker(&pagination("numeric", "score", None), &table(&["id"]));
+        assert_eq!(resolved.tiebreaker.as_deref(), Some("id"));
+    }
+
+    #[test]
+    fn keeps_configured_or_unresolvable_tiebreaker() {
+        let explicit = pagination("numeric", "score", Some("uuid"));
+        assert_eq!(
+            with_default_tiebreaker(&explicit, &table(&["id"]))
+                .tiebreaker
+                .as_deref(),
+            Some("uuid")
+        );
+
+        let composite_pk = pagination("numeric", "score", None);
+        assert!(
+            with_default_tiebreaker(&composite_pk, &table(&["a", "b"]))
+                .tiebreaker
+                .is_none()
+        );
+
+        let pk_strategy = pagination("pk", "id", None);
+        assert!(
+            with_default_tiebreaker(&pk_strategy, &table(&["id"]))
+                .tiebreaker
+                .is_none()
+        );
+    }
+}
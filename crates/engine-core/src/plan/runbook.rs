@@ -0,0 +1,143 @@
+use crate::{context::env::EnvContext, plan::builder::PlanBuilder};
+use model::execution::{errors::ConvertError, notify_config::NotifyTarget, runbook::Runbook};
+use smql_syntax::ast::doc::SmqlDocument;
+use std::sync::Arc;
+
+const ERR_RUNBOOK_NOTIFY: &str = "a runbook's notify blocks must be webhooks; email run summaries or page from the notify blocks of its step configs";
+const ERR_NOT_A_RUNBOOK: &str = "no runbook block found; a runbook file declares `runbook \"name\" { step \"...\" { config = \"...\" } }`";
+
+/// Build the runbook declared in a runbook file. Definitions from its
+/// `define` block can be used in step attributes, and its `notify` blocks
+/// are told when a step waits for approval.
+pub fn build_runbook(doc: &SmqlDocument, env: Arc<EnvContext>) -> Result<Runbook, ConvertError> {
+    let block = doc
+        .runbook_block
+        .as_ref()
+        .ok_or_else(|| ConvertError::Plan(ERR_NOT_A_RUNBOOK.to_string()))?;
+
+    let mut builder = PlanBuilder::new(env);
+    if let Some(def_block) = &doc.define_block {
+        builder.global_definitions = builder.extract_definitions(def_block)?;
+    }
+    let mut runbook = builder.build_runbook(block)?;
+    for block in &doc.notify_blocks {
+        match builder.build_notify(block)? {
+            NotifyTarget::Webhook(webhook) => runbook.notify.push(webhook),
+            NotifyTarget::Email(_) | NotifyTarget::Incident(_) => {
+                return Err(ConvertError::Plan(ERR_RUNBOOK_NOTIFY.to_string()));
+            }
+        }
+    }
+    Ok(runbook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::execution::verify::VerifyMode;
+    use smql_syntax::builder::parse;
+
+    fn build(smql: &str) -> Result<Runbook, ConvertError> {
+        build_runbook(&parse(smql).unwrap(), Arc::new(EnvContext::empty()))
+    }
+
+    #[test]
+    fn test_build_runbook_steps_in_order() {
+        let runbook = build(
+            r#"
+            define { backfill_limit = 100 }
+            runbook "orders_cutover" {
+                step "schema" { config = "schema.smql" }
+                step "backfill" {
+                    config          = "backfill.smql"
+                    verify          = "count"
+                    max_failed_rows = define.backfill_limit
+                }
+                step "cutover" {
+                    config   = "cutover.smql"
+                    approval = true
+                    verify   = "checksum"
+                    approve { message = "Confirm cutover" }
+                }
+            }
+            notify {
+                url    = "https://hooks.example.com/stratum"
+                events = ["approval_required"]
+            }
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(runbook.name, "orders_cutover");
+        let names: Vec<_> = runbook.steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["schema", "backfill", "cutover"]);
+        assert_eq!(runbook.position("cutover"), Some(2));
+
+        let schema = &runbook.steps[0];
+        assert_eq!(schema.config, "schema.smql");
+        assert!(!schema.approval);
+        assert_eq!(schema.verify, None);
+        assert_eq!(schema.max_failed_rows, None);
+
+        assert_eq!(runbook.steps[1].verify, Some(VerifyMode::Count));
+        assert_eq!(runbook.steps[1].max_failed_rows, Some(100));
+        assert!(runbook.steps[2].approval);
+        assert_eq!(runbook.steps[2].verify, Some(VerifyMode::Checksum));
+
+        assert_eq!(schema.approval_gate, None);
+        let gate = runbook.steps[2].approval_gate.as_ref().unwrap();
+        assert_eq!(gate.name, "cutover");
+        assert_eq!(gate.message.as_deref(), Some("Confirm cutover"));
+        assert_eq!(runbook.notify.len(), 1);
+    }
+
+    #[test]
+    fn test_build_runbook_errors() {
+        for (smql, expected) in [
+            (
+                r#"connection "db" { driver = "postgres" host = "localhost" }"#,
+                "no runbook block found",
+            ),
+            (r#"runbook "r" {}"#, "has no steps"),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" } step "a" { config = "b.smql" } }"#,
+                "'a' is declared more than once",
+            ),
+            (
+                r#"runbook "r" { step "a" { approval = true } }"#,
+                "missing config attribute",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" verify = "rows" } }"#,
+                "invalid verify mode 'rows'",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" max_failed_rows = -1 } }"#,
+                "max_failed_rows",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" timeout = "1h" } }"#,
+                "unknown attribute 'timeout'",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" approve { message = 1 } } }"#,
+                "approve 'a': message must be a string",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" approve { after = "1h" } } }"#,
+                "approve 'a': unknown attribute 'after'",
+            ),
+            (
+                r#"runbook "r" { step "a" { config = "a.smql" } }
+                notify { url = "smtp://mail:25" from = "etl@example.com" to = "dba@example.com" }"#,
+                "must be webhooks",
+            ),
+        ] {
+            let err = build(smql).unwrap_err();
+            assert!(
+                err.to_string().contains(expected),
+                "'{smql}' failed with '{err}', expected '{expected}'"
+            );
+        }
+    }
+}
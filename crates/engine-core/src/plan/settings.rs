@@ -0,0 +1,580 @@
+//! Schema for the keys of `settings` blocks.
+//!
+//! Every key the engine reads is declared here with its type, allowed range
+//! and default. Settings are checked against the schema at plan build time,
+//! so a typo such as `batc_size` fails the build instead of silently falling
+//! back to the default.
+
+use model::{
+    core::{timezone::parse_zone, value::Value},
+    execution::errors::ConvertError,
+};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Largest edit distance at which an unknown key is matched to a known one
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Type of a setting's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Bool,
+    /// Non-negative integer within `min..=max`
+    Integer {
+        min: u64,
+        max: u64,
+    },
+    /// One of a fixed set of strings, matched case-insensitively
+    Enum(&'static [&'static str]),
+    /// Single-character string
+    Char,
+    /// IANA time zone name, e.g. `Europe/Berlin`
+    TimeZone,
+    String,
+    /// Any value; only used for deprecated keys, which are ignored
+    Any,
+}
+
+/// Declaration of a single settings key.
+#[derive(Debug, Clone, Copy)]
+pub struct SettingSpec {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    /// Default as written in SMQL; `None` when unset means "not used"
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+    /// Why the key is deprecated and what to use instead
+    pub deprecated: Option<&'static str>,
+}
+
+impl SettingSpec {
+    const fn new(key: &'static str, kind: SettingKind, description: &'static str) -> Self {
+        Self {
+            key,
+            kind,
+            default: None,
+            description,
+            deprecated: None,
+        }
+    }
+
+    const fn default(mut self, default: &'static str) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    const fn deprecated(mut self, note: &'static str) -> Self {
+        self.deprecated = Some(note);
+        self
+    }
+}
+
+/// All known settings keys.
+pub const SETTINGS: &[SettingSpec] = &[
+    SettingSpec::new(
+        "batch_size",
+        SettingKind::Integer {
+            min: 1,
+            max: 1_000_000,
+        },
+        "Rows per batch",
+    )
+    .default("1000"),
+    SettingSpec::new(
+        "parallel_partitions",
+        SettingKind::Integer { min: 1, max: 64 },
+        "Read the source table in this many primary key ranges at once; idle workers split the slowest remaining range",
+    )
+    .default("1"),
+    SettingSpec::new(
+        "lob_threshold",
+        SettingKind::Integer {
+            min: 1024,
+            max: 1 << 30,
+        },
+        "Binary values larger than this many bytes are left out of the batches and streamed in chunks after the load, keyed by primary key",
+    ),
+    SettingSpec::new(
+        "lob_chunk_size",
+        SettingKind::Integer {
+            min: 1024,
+            max: 64 << 20,
+        },
+        "Bytes read and written per statement when streaming values over lob_threshold",
+    )
+    .default("1048576"),
+    SettingSpec::new(
+        "sort_buffer_rows",
+        SettingKind::Integer {
+            min: 1000,
+            max: 10_000_000,
+        },
+        "Rows the to block's order_by sorts in memory; larger sources are sorted in runs of this many rows, spilled to temporary files and merged",
+    )
+    .default("100000"),
+    SettingSpec::new(
+        "copy_columns",
+        SettingKind::Enum(&["ALL", "MAP_ONLY"]),
+        "Copy all source columns, or only the mapped ones",
+    )
+    .default("\"ALL\""),
+    SettingSpec::new(
+        "infer_dependencies",
+        SettingKind::Bool,
+        "Run after every pipeline that writes a table this pipeline reads over the same connection",
+    )
+    .default("true"),
+    SettingSpec::new(
+        "materialize_generated_columns",
+        SettingKind::Bool,
+        "Create the source's generated columns as plain columns holding the source's computed values",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "invalid_utf8",
+        SettingKind::Enum(&["REPLACE", "LATIN1", "ERROR"]),
+        "Text bytes that are not valid UTF-8: replace invalid sequences with U+FFFD, decode the value as Latin-1, or fail the row",
+    )
+    .default("\"REPLACE\""),
+    SettingSpec::new(
+        "array_format",
+        SettingKind::Enum(&["JSON", "DELIMITED"]),
+        "How arrays are written to destination columns that do not hold arrays: a JSON array, or the elements joined with array_delimiter",
+    )
+    .default("\"JSON\""),
+    SettingSpec::new(
+        "array_delimiter",
+        SettingKind::String,
+        "Separator between array elements with array_format = \"DELIMITED\"",
+    )
+    .default("\",\""),
+    SettingSpec::new(
+        "timezone",
+        SettingKind::TimeZone,
+        "Time zone the source's timestamps without an offset (MySQL DATETIME) were written in; they are converted to UTC timestamps with a time zone",
+    ),
+    SettingSpec::new(
+        "create_missing_tables",
+        SettingKind::Bool,
+        "Create the destination table if it does not exist",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "create_missing_columns",
+        SettingKind::Bool,
+        "Add source columns missing from the destination table",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "infer_schema",
+        SettingKind::Bool,
+        "Create the whole destination schema from the source",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "ignore_constraints",
+        SettingKind::Bool,
+        "Skip foreign keys and other constraints when creating tables",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "migrate_indexes",
+        SettingKind::Bool,
+        "Create the source's secondary indexes on created tables, after the data load",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "auto_widen_columns",
+        SettingKind::Bool,
+        "Widen a destination varchar, char or numeric column that a value does not fit in and write the batch again",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "sync_sequences",
+        SettingKind::Bool,
+        "After the load, advance the destination table's sequences (AUTO_INCREMENT on MySQL) to max(key) + 1",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "defer_foreign_keys",
+        SettingKind::Enum(&["OFF", "POST_LOAD", "NOT_VALID"]),
+        "Add foreign keys of created tables once every pipeline has loaded; NOT_VALID (Postgres) adds them unchecked and validates them after",
+    )
+    .default("\"OFF\""),
+    SettingSpec::new(
+        "fast_load",
+        SettingKind::Enum(&["OFF", "UNLOGGED"]),
+        "UNLOGGED (Postgres) creates tables UNLOGGED for the load and switches them to logged after it",
+    )
+    .default("\"OFF\""),
+    SettingSpec::new(
+        "defer_indexes",
+        SettingKind::Bool,
+        "Drop the destination table's non-unique indexes before the load and rebuild them concurrently after it (Postgres)",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "migrate_grants",
+        SettingKind::Bool,
+        "Copy the source table's grants, through the roles {} block, and the schema's standalone sequences (Postgres)",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "emit_keymap",
+        SettingKind::Bool,
+        "Record the destination key each source row was loaded under, for other pipelines' lookup_key() calls",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "max_identifier_length",
+        SettingKind::Integer { min: 16, max: 255 },
+        "Longest identifier the destination keeps; longer index, constraint and sequence names are shortened",
+    ),
+    SettingSpec::new(
+        "cascade_schema",
+        SettingKind::Bool,
+        "Also create tables referenced by the destination table",
+    )
+    .default("false"),
+    SettingSpec::new(
+        "csv_header",
+        SettingKind::Bool,
+        "CSV sources have a header row",
+    )
+    .default("true"),
+    SettingSpec::new(
+        "csv_delimiter",
+        SettingKind::Char,
+        "Field delimiter for CSV sources and destinations",
+    )
+    .default("\",\""),
+    SettingSpec::new(
+        "csv_id_column",
+        SettingKind::String,
+        "Column used as the row id for CSV sources",
+    ),
+    SettingSpec::new(
+        "file_max_rows",
+        SettingKind::Integer {
+            min: 1,
+            max: 1_000_000_000,
+        },
+        "Start a new CSV destination file once the current one holds this many rows",
+    ),
+    SettingSpec::new(
+        "file_max_bytes",
+        SettingKind::Integer {
+            min: 1024,
+            max: 1 << 40,
+        },
+        "Start a new CSV destination file before the current one grows past this many bytes of uncompressed CSV",
+    ),
+    SettingSpec::new(
+        "file_max_secs",
+        SettingKind::Integer {
+            min: 1,
+            max: 7 * 24 * 3600,
+        },
+        "Start a new CSV destination file once the current one has been open this many seconds",
+    ),
+    SettingSpec::new(
+        "file_compression",
+        SettingKind::Enum(&["NONE", "GZIP"]),
+        "Compression of CSV destination files",
+    )
+    .default("\"NONE\""),
+    SettingSpec::new(
+        "file_schema",
+        SettingKind::Enum(&["JSON_SCHEMA", "ARROW", "NONE"]),
+        "Form of the schema file written beside CSV destination files",
+    )
+    .default("\"JSON_SCHEMA\""),
+    SettingSpec::new("workers", SettingKind::Any, "Parallel worker count")
+        .deprecated("has no effect; set `max_concurrency` in the `execution` block instead"),
+    SettingSpec::new("checkpoint", SettingKind::Any, "When to checkpoint state")
+        .deprecated("has no effect; progress is checkpointed after every batch"),
+    SettingSpec::new(
+        "offset_strategy",
+        SettingKind::Any,
+        "Default pagination strategy",
+    )
+    .deprecated("has no effect; use a `paginate` block instead"),
+];
+
+/// Looks up the declaration of a settings key.
+pub fn spec(key: &str) -> Option<&'static SettingSpec> {
+    SETTINGS.iter().find(|s| s.key == key)
+}
+
+/// Checks a settings map against the schema. Deprecated keys are logged;
+/// unknown keys and values of the wrong type or out of range are returned
+/// as errors, one per key.
+pub fn validate_settings(settings: &HashMap<String, Value>) -> Result<(), Vec<String>> {
+    let mut keys: Vec<&String> = settings.keys().collect();
+    keys.sort();
+
+    let mut errors = Vec::new();
+    for key in keys {
+        let Some(spec) = spec(key) else {
+            errors.push(unknown_key(key));
+            continue;
+        };
+
+        if let Some(note) = spec.deprecated {
+            warn!(setting = %key, "deprecated setting: {note}");
+            continue;
+        }
+
+        if let Err(e) = check_value(spec.kind, &settings[key]) {
+            errors.push(format!("setting '{key}' {e}"));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// [`validate_settings`] as a plan-build error; `scope` names the block,
+/// e.g. `pipeline 'orders'`.
+pub fn check_settings(scope: &str, settings: &HashMap<String, Value>) -> Result<(), ConvertError> {
+    validate_settings(settings).map_err(|errors| {
+        ConvertError::Plan(format!(
+            "invalid settings in {scope}: {}",
+            errors.join("; ")
+        ))
+    })
+}
+
+/// Value of a settings key as the engine sees it: the value set in
+/// `settings`, else the key's default, else null.
+pub fn effective_value(settings: &HashMap<String, Value>, key: &str) -> Result<Value, String> {
+    let Some(spec) = spec(key) else {
+        return Err(unknown_key(key));
+    };
+
+    match settings.get(key) {
+        Some(value) if !matches!(value, Value::Null) => Ok(value.clone()),
+        _ => Ok(spec.default.map(parse_default).unwrap_or(Value::Null)),
+    }
+}
+
+/// Parses a default as written in [`SETTINGS`].
+fn parse_default(default: &str) -> Value {
+    if let Some(s) = default.strip_prefix('"').and_then(|d| d.strip_suffix('"')) {
+        return Value::String(s.to_string());
+    }
+    match default {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        n => n
+            .parse()
+            .map(Value::Int)
+            .unwrap_or_else(|_| Value::String(n.to_string())),
+    }
+}
+
+fn unknown_key(key: &str) -> String {
+    match suggest(key) {
+        Some(known) => format!("unknown setting '{key}' (did you mean '{known}'?)"),
+        None => format!("unknown setting '{key}'"),
+    }
+}
+
+/// The known key closest to `key`, if any is close enough to be a typo.
+fn suggest(key: &str) -> Option<&'static str> {
+    SETTINGS
+        .iter()
+        .filter(|s| s.deprecated.is_none())
+        .map(|s| (strsim::damerau_levenshtein(key, s.key), s.key))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+fn check_value(kind: SettingKind, value: &Value) -> Result<(), String> {
+    // A null leaves the setting at its default
+    if matches!(value, Value::Null) {
+        return Ok(());
+    }
+
+    match kind {
+        SettingKind::Any => Ok(()),
+        SettingKind::Bool => match value {
+            Value::Boolean(_) => Ok(()),
+            other => Err(format!("must be true or false, got {}", describe(other))),
+        },
+        SettingKind::Integer { min, max } => {
+            let n = match value {
+                Value::Int(i) if *i >= 0 => Some(*i as u64),
+                Value::UInt(u) => Some(*u),
+                Value::Float(f) if *f >= 0.0 && f.fract() == 0.0 => Some(*f as u64),
+                _ => None,
+            };
+            match n {
+                Some(n) if (min..=max).contains(&n) => Ok(()),
+                Some(n) => Err(format!("must be between {min} and {max}, got {n}")),
+                None => Err(format!(
+                    "must be an integer between {min} and {max}, got {}",
+                    describe(value)
+                )),
+            }
+        }
+        SettingKind::Enum(options) => match value {
+            Value::String(s) if options.iter().any(|o| o.eq_ignore_ascii_case(s)) => Ok(()),
+            other => Err(format!(
+                "must be one of {}, got {}",
+                options.join(", "),
+                describe(other)
+            )),
+        },
+        SettingKind::Char => match value {
+            Value::String(s) if s.chars().count() == 1 => Ok(()),
+            other => Err(format!(
+                "must be a single character, got {}",
+                describe(other)
+            )),
+        },
+        SettingKind::String => match value {
+            Value::String(_) => Ok(()),
+            other => Err(format!("must be a string, got {}", describe(other))),
+        },
+        SettingKind::TimeZone => match value {
+            Value::String(s) if parse_zone(s).is_some() => Ok(()),
+            other => Err(format!(
+                "must be a time zone name such as \"Europe/Berlin\", got {}",
+                describe(other)
+            )),
+        },
+    }
+}
+
+/// Short rendering of a value for error messages.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{s}\""),
+        Value::Boolean(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::UInt(u) => u.to_string(),
+        Value::Float(f) => f.to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(entries: &[(&str, Value)]) -> HashMap<String, Value> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_settings() {
+        let map = settings(&[
+            ("batch_size", Value::Int(5000)),
+            ("copy_columns", Value::String("map_only".to_string())),
+            ("create_missing_tables", Value::Boolean(true)),
+            ("csv_delimiter", Value::String(";".to_string())),
+            ("workers", Value::Int(4)),
+        ]);
+        assert!(validate_settings(&map).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_key_suggests_closest() {
+        let map = settings(&[
+            ("batc_size", Value::Int(100)),
+            ("create_missing_table", Value::Boolean(true)),
+            ("parallelism", Value::Int(2)),
+        ]);
+        let errors = validate_settings(&map).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "unknown setting 'batc_size' (did you mean 'batch_size'?)",
+                "unknown setting 'create_missing_table' (did you mean 'create_missing_tables'?)",
+                "unknown setting 'parallelism'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrong_types_and_ranges() {
+        let map = settings(&[
+            ("batch_size", Value::String("5000".to_string())),
+            ("copy_columns", Value::String("SOME".to_string())),
+            ("create_missing_tables", Value::String("yes".to_string())),
+            ("csv_delimiter", Value::String("||".to_string())),
+            ("timezone", Value::String("Europe/Berlinn".to_string())),
+        ]);
+        let errors = validate_settings(&map).unwrap_err();
+        assert_eq!(errors.len(), 5);
+        assert!(errors[0].contains("'batch_size' must be an integer"));
+        assert!(errors[1].contains("must be one of ALL, MAP_ONLY"));
+        assert!(errors[2].contains("must be true or false"));
+        assert!(errors[3].contains("single character"));
+        assert!(errors[4].contains("'timezone' must be a time zone name"));
+
+        let map = settings(&[("batch_size", Value::Int(0))]);
+        let errors = validate_settings(&map).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["setting 'batch_size' must be between 1 and 1000000, got 0"]
+        );
+    }
+
+    #[test]
+    fn test_file_rotation_settings() {
+        let map = settings(&[
+            ("file_max_rows", Value::Int(100_000)),
+            ("file_max_secs", Value::Int(900)),
+            ("file_compression", Value::String("gzip".to_string())),
+            ("file_schema", Value::String("arrow".to_string())),
+        ]);
+        assert!(validate_settings(&map).is_ok());
+
+        let map = settings(&[
+            ("file_max_bytes", Value::Int(10)),
+            ("file_compression", Value::String("zstd".to_string())),
+        ]);
+        let errors = validate_settings(&map).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "setting 'file_compression' must be one of NONE, GZIP, got \"zstd\"",
+                "setting 'file_max_bytes' must be between 1024 and 1099511627776, got 10",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effective_value_falls_back_to_default() {
+        let map = settings(&[("batch_size", Value::Int(250))]);
+        assert!(matches!(
+            effective_value(&map, "batch_size"),
+            Ok(Value::Int(250))
+        ));
+        assert!(matches!(
+            effective_value(&map, "parallel_partitions"),
+            Ok(Value::Int(1))
+        ));
+        assert!(matches!(
+            effective_value(&map, "copy_columns"),
+            Ok(Value::String(s)) if s == "ALL"
+        ));
+        assert!(matches!(
+            effective_value(&map, "create_missing_tables"),
+            Ok(Value::Boolean(false))
+        ));
+        assert_eq!(
+            effective_value(&map, "batc_size").unwrap_err(),
+            "unknown setting 'batc_size' (did you mean 'batch_size'?)"
+        );
+    }
+}
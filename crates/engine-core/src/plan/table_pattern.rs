@@ -0,0 +1,284 @@
+//! Pipelines whose `from` table is a pattern such as `audit_*`.
+//!
+//! The plan keeps such a pipeline as written. Once the source connection
+//! can be listed, `ExecutionPlan::expand_table_patterns` replaces it with one
+//! pipeline per matching table, named `{pipeline}_{table}`, whose `to` and
+//! sink tables have `{table}` replaced by the name of the table it reads.
+//!
+//! A `migrate_schema` block is such a pipeline reading `*`, narrowed by its
+//! include and exclude patterns. Its expansions run in foreign key order:
+//! each runs after the expansions copying the tables it references.
+
+use crate::{
+    context::exec::ConnectionPool, drivers::DriverRef, schema::dep_graph::DependencyGraph,
+};
+use model::execution::{
+    errors::ConvertError,
+    pipeline::{DataSource, Pipeline},
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Stands for the matched source table in the `to` table of a pattern
+/// pipeline
+pub(crate) const TABLE_PLACEHOLDER: &str = "{table}";
+
+const ERR_PATTERN_NO_TABLES: &str =
+    "pipeline '{pipeline}': no table of connection '{connection}' matches '{pattern}'";
+const ERR_PATTERN_NAME: &str =
+    "pipeline '{pipeline}' expands into pipeline '{name}', but another pipeline has that name";
+
+/// Whether `table` matches `pattern`, where each `*` stands for any run of
+/// characters, none included.
+pub fn matches_table_pattern(pattern: &str, table: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = table.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*`: the whole table must be the pattern
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Whether the pattern pipeline reading `source` reads `table`
+fn selects(source: &DataSource, table: &str) -> bool {
+    match &source.schema_scope {
+        Some(scope) => {
+            scope
+                .include
+                .iter()
+                .any(|p| matches_table_pattern(p, table))
+                && !scope
+                    .exclude
+                    .iter()
+                    .any(|p| matches_table_pattern(p, table))
+        }
+        None => matches_table_pattern(&source.table, table),
+    }
+}
+
+/// Tables of a source connection read with a table pattern
+#[derive(Debug, Clone, Default)]
+pub struct SourceTables {
+    pub tables: Vec<String>,
+    /// `(table, referenced table)` of each foreign key of the tables a
+    /// `migrate_schema` block copies
+    pub references: Vec<(String, String)>,
+}
+
+/// Tables of each source connection a pipeline reads with a table pattern,
+/// by connection name.
+pub async fn list_pattern_sources(
+    pipelines: &[Pipeline],
+) -> Result<HashMap<String, SourceTables>, ConvertError> {
+    let mut pool = ConnectionPool::new();
+    let mut sources = HashMap::new();
+    for pipeline in pipelines.iter().filter(|p| p.source.is_table_pattern()) {
+        let connection = &pipeline.source.connection;
+        if sources.contains_key(&connection.name) {
+            continue;
+        }
+        let err = |e| {
+            ConvertError::Connection(format!(
+                "cannot list the tables of connection '{}': {e}",
+                connection.name
+            ))
+        };
+        let driver = DriverRef::resolve(&connection.driver, connection, &mut pool)
+            .await
+            .map_err(err)?;
+        let tables = driver.list_tables().await.map_err(err)?;
+
+        // Foreign keys are only read for the tables a schema copies
+        let copied: BTreeSet<&String> = pipelines
+            .iter()
+            .filter(|p| {
+                p.source.schema_scope.is_some() && p.source.connection.name == connection.name
+            })
+            .flat_map(|p| tables.iter().filter(|t| selects(&p.source, t)))
+            .collect();
+        let mut references = Vec::new();
+        for table in copied {
+            let metadata = driver.table_metadata(table).await.map_err(|e| {
+                ConvertError::Connection(format!(
+                    "cannot read the foreign keys of table '{table}' of connection '{}': {e}",
+                    connection.name
+                ))
+            })?;
+            references.extend(
+                metadata
+                    .foreign_keys
+                    .into_iter()
+                    .map(|fk| (table.clone(), fk.referenced_table)),
+            );
+        }
+        sources.insert(connection.name.clone(), SourceTables { tables, references });
+    }
+    Ok(sources)
+}
+
+/// `tables` ordered so that each comes after the tables it references.
+/// Tables referencing each other in a cycle follow the rest in name order,
+/// and only the references that agree with that order are kept, as
+/// `(table, referenced table)`.
+fn foreign_key_order(
+    tables: &[&String],
+    references: &[(String, String)],
+) -> (Vec<String>, Vec<(String, String)>) {
+    let copied: HashSet<&str> = tables.iter().map(|t| t.as_str()).collect();
+    let mut graph = DependencyGraph::new();
+    for table in tables {
+        graph.add_table(table.to_string());
+    }
+    for (table, referenced) in references {
+        if copied.contains(table.as_str()) && copied.contains(referenced.as_str()) {
+            graph.add_dependency(table.clone(), referenced.clone());
+        }
+    }
+    let order = graph.without_self_references().partial_topological_order();
+    let position: HashMap<&str, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.as_str(), i))
+        .collect();
+    let mut kept: Vec<(String, String)> = references
+        .iter()
+        .filter(|(table, referenced)| {
+            match (
+                position.get(table.as_str()),
+                position.get(referenced.as_str()),
+            ) {
+                (Some(t), Some(r)) => r < t,
+                _ => false,
+            }
+        })
+        .cloned()
+        .collect();
+    kept.sort();
+    kept.dedup();
+    (order, kept)
+}
+
+/// Replaces each pattern pipeline with a pipeline per table of `sources` it
+/// matches, in table name order, or foreign key order for a
+/// `migrate_schema` block, and returns the names each pattern pipeline was
+/// expanded into, by its own name.
+pub(crate) fn expand_pipelines(
+    pipelines: &mut Vec<Pipeline>,
+    sources: &HashMap<String, SourceTables>,
+) -> Result<HashMap<String, Vec<String>>, ConvertError> {
+    let mut expanded = Vec::with_capacity(pipelines.len());
+    let mut names: HashMap<String, Vec<String>> = HashMap::new();
+    for pipeline in std::mem::take(pipelines) {
+        if !pipeline.source.is_table_pattern() {
+            expanded.push(pipeline);
+            continue;
+        }
+
+        let connection = &pipeline.source.connection.name;
+        let source = sources.get(connection);
+        let mut matched: Vec<&String> = source
+            .into_iter()
+            .flat_map(|s| &s.tables)
+            .filter(|t| selects(&pipeline.source, t))
+            .collect();
+        matched.sort();
+        matched.dedup();
+        if matched.is_empty() {
+            return Err(ConvertError::Plan(
+                ERR_PATTERN_NO_TABLES
+                    .replace("{pipeline}", &pipeline.name)
+                    .replace("{connection}", connection)
+                    .replace("{pattern}", &pipeline.source.table),
+            ));
+        }
+
+        let expansion = |table: &str| format!("{}_{}", pipeline.name, table);
+        let (order, references) = match pipeline.source.schema_scope {
+            Some(_) => foreign_key_order(
+                &matched,
+                source.map(|s| s.references.as_slice()).unwrap_or_default(),
+            ),
+            None => (matched.into_iter().cloned().collect(), Vec::new()),
+        };
+
+        let mut expansions = Vec::with_capacity(order.len());
+        for table in &order {
+            let mut item = pipeline.clone();
+            item.name = expansion(table);
+            item.source.table = table.clone();
+            item.source.schema_scope = None;
+            for (_, referenced) in references.iter().filter(|(t, _)| t == table) {
+                let dependency = expansion(referenced);
+                if !item.dependencies.contains(&dependency) {
+                    item.dependencies.push(dependency);
+                }
+            }
+            if item.validate_only {
+                item.destination.table = table.clone();
+            } else {
+                item.destination.table = item.destination.table.replace(TABLE_PLACEHOLDER, table);
+            }
+            for sink in &mut item.sinks {
+                sink.table = sink.table.replace(TABLE_PLACEHOLDER, table);
+            }
+            expansions.push(item.name.clone());
+            expanded.push(item);
+        }
+        names.insert(pipeline.name, expansions);
+    }
+
+    let mut seen = HashSet::new();
+    for pipeline in &expanded {
+        if !seen.insert(pipeline.name.as_str()) {
+            let (pattern, _) = names
+                .iter()
+                .find(|(_, expansions)| expansions.contains(&pipeline.name))
+                .expect("only expansions can repeat a name");
+            return Err(ConvertError::Plan(
+                ERR_PATTERN_NAME
+                    .replace("{pipeline}", pattern)
+                    .replace("{name}", &pipeline.name),
+            ));
+        }
+    }
+    *pipelines = expanded;
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches_table_pattern;
+
+    #[test]
+    fn test_matches_table_pattern() {
+        let cases = [
+            ("audit_*", "audit_2024", true),
+            ("audit_*", "audit_", true),
+            ("audit_*", "audits_2024", false),
+            ("*_log", "access_log", true),
+            ("*_log", "access_logs", false),
+            ("a*_*z", "ab_cz", true),
+            ("a*_*z", "abcz", false),
+            ("*", "orders", true),
+            ("orders", "orders", true),
+            ("orders", "orders_old", false),
+        ];
+        for (pattern, table, expected) in cases {
+            assert_eq!(
+                matches_table_pattern(pattern, table),
+                expected,
+                "'{pattern}' against '{table}'"
+            );
+        }
+    }
+}
@@ -3,3 +3,4 @@ pub mod metrics;
 pub mod progress;
 pub mod retry;
 pub mod shutdown;
+pub mod timing;
@@ -1,6 +1,11 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicU64, Ordering},
+use crate::timing::{BatchTiming, RunTimings, TimingReport};
+use model::events::migration::CircuitState;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU8, AtomicU64, Ordering},
+    },
+    time::Duration,
 };
 
 #[derive(Debug, Default)]
@@ -12,6 +17,9 @@ struct InnerMetrics {
     rows_failed: AtomicU64,
     failure_count: AtomicU64,
     retry_count: AtomicU64,
+    circuit_state: AtomicU8,
+    circuit_trips: AtomicU64,
+    timings: Mutex<RunTimings>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +36,9 @@ pub struct MetricsSnapshot {
     pub rows_failed: u64,
     pub failure_count: u64,
     pub retry_count: u64,
+    pub circuit_state: CircuitState,
+    /// Times the destination circuit breaker has opened
+    pub circuit_trips: u64,
 }
 
 impl Metrics {
@@ -71,6 +82,46 @@ impl Metrics {
         self.inner.retry_count.fetch_add(count, Ordering::Relaxed);
     }
 
+    /// Record a circuit breaker transition; moving to `Open` counts as a trip.
+    pub fn set_circuit_state(&self, state: CircuitState) {
+        let prev = self
+            .inner
+            .circuit_state
+            .swap(state as u8, Ordering::Relaxed);
+        if state == CircuitState::Open && prev != CircuitState::Open as u8 {
+            self.inner.circuit_trips.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record how long a committed batch took to read and write.
+    pub fn record_batch_timing(&self, batch: BatchTiming) {
+        if let Ok(mut timings) = self.inner.timings.lock() {
+            timings.record_batch(batch);
+        }
+    }
+
+    /// Record how long a key range of a partitioned load took to read.
+    pub fn record_partition_timing(
+        &self,
+        part_id: String,
+        start: u64,
+        end: u64,
+        duration: Duration,
+    ) {
+        if let Ok(mut timings) = self.inner.timings.lock() {
+            timings.record_partition(part_id, start, end, duration);
+        }
+    }
+
+    /// Duration distribution of the batches and partitions recorded so far.
+    pub fn timing_report(&self) -> TimingReport {
+        self.inner
+            .timings
+            .lock()
+            .map(|timings| timings.report())
+            .unwrap_or_default()
+    }
+
     pub fn snapshot(&self) -> MetricsSnapshot {
         MetricsSnapshot {
             records_processed: self.inner.records_processed.load(Ordering::Relaxed),
@@ -80,6 +131,12 @@ impl Metrics {
             rows_failed: self.inner.rows_failed.load(Ordering::Relaxed),
             failure_count: self.inner.failure_count.load(Ordering::Relaxed),
             retry_count: self.inner.retry_count.load(Ordering::Relaxed),
+            circuit_state: match self.inner.circuit_state.load(Ordering::Relaxed) {
+                s if s == CircuitState::Open as u8 => CircuitState::Open,
+                s if s == CircuitState::HalfOpen as u8 => CircuitState::HalfOpen,
+                _ => CircuitState::Closed,
+            },
+            circuit_trips: self.inner.circuit_trips.load(Ordering::Relaxed),
         }
     }
 }
@@ -34,6 +34,9 @@ pub struct RetryPolicy {
     pub max_attempts: usize,
     pub base_delay: Duration,
     pub max_delay: Duration,
+    pub backoff: BackoffStrategy,
+    /// Draw each delay from `[delay / 2, delay]` instead of using it as is
+    pub jitter: bool,
 }
 
 impl Default for RetryPolicy {
@@ -42,6 +45,8 @@ impl Default for RetryPolicy {
             max_attempts: 3,
             base_delay: Duration::from_millis(200),
             max_delay: Duration::from_secs(5),
+            backoff: BackoffStrategy::Exponential,
+            jitter: false,
         }
     }
 }
@@ -56,6 +61,8 @@ impl RetryPolicy {
             } else {
                 max_delay
             },
+            backoff: BackoffStrategy::Exponential,
+            jitter: false,
         }
     }
 
@@ -65,28 +72,37 @@ impl RetryPolicy {
             max_attempts: 5,
             base_delay: Duration::from_millis(250),
             max_delay: Duration::from_secs(5),
+            backoff: BackoffStrategy::Exponential,
+            jitter: true,
         }
     }
 
+    pub fn with_backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
     /// Create a RetryPolicy from a RetryConfig, with optional fallback to database defaults.
     pub fn from_config(config: Option<&RetryConfig>) -> Self {
         match config {
             Some(cfg) => {
                 let base_delay = Duration::from_millis(cfg.delay_ms);
-                // Calculate max_delay based on backoff strategy
-                let max_delay = match cfg.backoff {
-                    BackoffStrategy::Fixed => base_delay,
-                    BackoffStrategy::Linear => {
-                        // Linear: max_delay = base * max_attempts
-                        base_delay.saturating_mul(cfg.max_attempts)
-                    }
-                    BackoffStrategy::Exponential => {
-                        // Exponential: cap at 5 seconds for safety
-                        Duration::from_secs(5)
-                    }
+                // Without an explicit cap, bound each strategy by its own growth
+                let max_delay = match (cfg.max_delay_ms, cfg.backoff) {
+                    (Some(ms), _) => Duration::from_millis(ms),
+                    (None, BackoffStrategy::Fixed) => base_delay,
+                    (None, BackoffStrategy::Linear) => base_delay.saturating_mul(cfg.max_attempts),
+                    (None, BackoffStrategy::Exponential) => base_delay.max(Duration::from_secs(5)),
                 };
 
                 Self::new(cfg.max_attempts as usize, base_delay, max_delay)
+                    .with_backoff(cfg.backoff)
+                    .with_jitter(cfg.jitter)
             }
             None => Self::for_database(),
         }
@@ -132,16 +148,25 @@ impl RetryPolicy {
         }
     }
 
-    fn backoff_delay(&self, attempt: usize) -> Duration {
+    /// Delay before retry number `attempt + 1`, capped at `max_delay`.
+    pub fn backoff_delay(&self, attempt: usize) -> Duration {
         if self.base_delay.is_zero() {
             return Duration::from_millis(0);
         }
 
-        let factor = 1u128 << attempt.min(6);
         let base_ms = self.base_delay.as_millis();
-        let delay_ms = base_ms.saturating_mul(factor);
-        let capped = delay_ms.min(self.max_delay.as_millis());
-        Duration::from_millis(capped as u64)
+        let delay_ms = match self.backoff {
+            BackoffStrategy::Fixed => base_ms,
+            BackoffStrategy::Linear => base_ms.saturating_mul(attempt as u128 + 1),
+            BackoffStrategy::Exponential => base_ms.saturating_mul(1u128 << attempt.min(16)),
+        };
+        let capped = delay_ms.min(self.max_delay.as_millis().max(base_ms)) as u64;
+
+        if self.jitter {
+            Duration::from_millis(fastrand::u64(capped / 2..=capped))
+        } else {
+            Duration::from_millis(capped)
+        }
     }
 }
 
@@ -219,4 +244,48 @@ mod tests {
         }
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
     }
+
+    fn policy(backoff: BackoffStrategy, max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::from_config(Some(&RetryConfig {
+            max_attempts,
+            delay_ms: 100,
+            backoff,
+            max_delay_ms: None,
+            jitter: false,
+        }))
+    }
+
+    #[test]
+    fn backoff_follows_configured_strategy() {
+        let delays = |p: RetryPolicy| {
+            (0..4)
+                .map(|a| p.backoff_delay(a).as_millis())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            delays(policy(BackoffStrategy::Fixed, 4)),
+            [100, 100, 100, 100]
+        );
+        assert_eq!(
+            delays(policy(BackoffStrategy::Linear, 4)),
+            [100, 200, 300, 400]
+        );
+        assert_eq!(
+            delays(policy(BackoffStrategy::Exponential, 4)),
+            [100, 200, 400, 800]
+        );
+
+        let capped = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(250));
+        assert_eq!(delays(capped), [100, 200, 250, 250]);
+    }
+
+    #[test]
+    fn jitter_stays_within_half_of_delay() {
+        let policy = policy(BackoffStrategy::Exponential, 5).with_jitter(true);
+        for _ in 0..50 {
+            let delay = policy.backoff_delay(2).as_millis();
+            assert!((200..=400).contains(&delay), "delay {delay}ms out of range");
+        }
+    }
 }
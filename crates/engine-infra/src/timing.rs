@@ -0,0 +1,278 @@
+use model::pagination::cursor::Cursor;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    time::Duration,
+};
+
+/// Slowest batches kept, with their key ranges, for the end-of-run report
+const SLOWEST_BATCHES: usize = 5;
+/// A key range taking this many times the median partition is reported as skewed
+const SKEW_FACTOR: f64 = 2.0;
+
+/// One committed batch: the keys it covered and where its time went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchTiming {
+    pub part_id: String,
+    /// Position the batch was read from, exclusive
+    pub from: String,
+    /// Position the batch ended on
+    pub to: String,
+    pub rows: u64,
+    /// Fetching and transforming the rows
+    pub read: Duration,
+    /// Writing and checkpointing the batch
+    pub write: Duration,
+}
+
+/// One key range of a partitioned load, once read to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionTiming {
+    pub part_id: String,
+    pub start: u64,
+    pub end: u64,
+    pub rows: u64,
+    pub duration: Duration,
+}
+
+/// Distribution of a set of durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurationSummary {
+    pub count: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+/// Where the time of a run went: how batch and partition durations are
+/// spread, the key ranges that took far longer than the rest and the
+/// slowest batches.
+#[derive(Debug, Clone, Default)]
+pub struct TimingReport {
+    pub batches: Option<DurationSummary>,
+    pub partitions: Option<DurationSummary>,
+    /// Partitions taking over twice the median, slowest first
+    pub skewed: Vec<PartitionTiming>,
+    /// Slowest first
+    pub slowest_batches: Vec<BatchTiming>,
+}
+
+/// Collects batch and partition durations as a run goes.
+#[derive(Debug, Default)]
+pub struct RunTimings {
+    batch_durations: Vec<Duration>,
+    /// Min-heap of the slowest batches seen so far
+    slowest: BinaryHeap<Reverse<(Duration, u64)>>,
+    slowest_batches: HashMap<u64, BatchTiming>,
+    rows_by_part: HashMap<String, u64>,
+    partitions: Vec<PartitionTiming>,
+}
+
+impl BatchTiming {
+    pub fn new(
+        part_id: String,
+        cursor: &Cursor,
+        next: &Cursor,
+        rows: u64,
+        read: Duration,
+        write: Duration,
+    ) -> Self {
+        Self {
+            part_id,
+            from: position(cursor).unwrap_or_else(|| "start".to_string()),
+            to: position(next).unwrap_or_else(|| "end".to_string()),
+            rows,
+            read,
+            write,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.read + self.write
+    }
+}
+
+impl DurationSummary {
+    fn of(mut durations: Vec<Duration>) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+        Some(Self {
+            count: durations.len(),
+            p50: percentile(&durations, 50),
+            p95: percentile(&durations, 95),
+            max: durations[durations.len() - 1],
+        })
+    }
+}
+
+impl RunTimings {
+    pub fn record_batch(&mut self, batch: BatchTiming) {
+        let duration = batch.duration();
+        let seq = self.batch_durations.len() as u64;
+        self.batch_durations.push(duration);
+        *self.rows_by_part.entry(batch.part_id.clone()).or_default() += batch.rows;
+
+        if self.slowest.len() < SLOWEST_BATCHES {
+            self.slowest.push(Reverse((duration, seq)));
+        } else if let Some(Reverse((fastest, _))) = self.slowest.peek()
+            && duration > *fastest
+        {
+            if let Some(Reverse((_, evicted))) = self.slowest.pop() {
+                self.slowest_batches.remove(&evicted);
+            }
+            self.slowest.push(Reverse((duration, seq)));
+        } else {
+            return;
+        }
+        self.slowest_batches.insert(seq, batch);
+    }
+
+    /// Records a finished key range; its rows are the rows of the batches
+    /// recorded under `part_id`.
+    pub fn record_partition(&mut self, part_id: String, start: u64, end: u64, duration: Duration) {
+        let rows = self.rows_by_part.get(&part_id).copied().unwrap_or(0);
+        self.partitions.push(PartitionTiming {
+            part_id,
+            start,
+            end,
+            rows,
+            duration,
+        });
+    }
+
+    pub fn report(&self) -> TimingReport {
+        let partitions = DurationSummary::of(self.partitions.iter().map(|p| p.duration).collect());
+
+        let mut skewed = Vec::new();
+        if let Some(summary) = partitions.filter(|s| s.count > 1) {
+            let threshold = summary.p50.mul_f64(SKEW_FACTOR);
+            skewed = self
+                .partitions
+                .iter()
+                .filter(|p| p.duration > threshold)
+                .cloned()
+                .collect();
+            skewed.sort_by_key(|p| Reverse(p.duration));
+        }
+
+        let mut slowest: Vec<_> = self.slowest.iter().map(|Reverse(entry)| *entry).collect();
+        slowest.sort_unstable_by_key(|entry| Reverse(*entry));
+        let slowest_batches = slowest
+            .into_iter()
+            .filter_map(|(_, seq)| self.slowest_batches.get(&seq).cloned())
+            .collect();
+
+        TimingReport {
+            batches: DurationSummary::of(self.batch_durations.clone()),
+            partitions,
+            skewed,
+            slowest_batches,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `durations`.
+fn percentile(durations: &[Duration], pct: usize) -> Duration {
+    let rank = (durations.len() * pct).div_ceil(100).max(1);
+    durations[rank - 1]
+}
+
+/// Key a cursor points at, as shown in the report; `None` for no position.
+fn position(cursor: &Cursor) -> Option<String> {
+    match cursor {
+        Cursor::None => None,
+        Cursor::Default { offset } => Some(format!("offset {offset}")),
+        Cursor::Pk { pk_col, id } => Some(format!("{}={id}", pk_col.column)),
+        Cursor::Numeric { col, val } => Some(format!("{}={val}", col.column)),
+        Cursor::Timestamp { col, ts } => Some(format!("{}={ts}", col.column)),
+        Cursor::CompositeNumPk {
+            num_col,
+            pk_col,
+            val,
+            id,
+        } => Some(format!(
+            "({}, {})=({val}, {id})",
+            num_col.column, pk_col.column
+        )),
+        Cursor::CompositeTsPk {
+            ts_col,
+            pk_col,
+            ts,
+            id,
+        } => Some(format!(
+            "({}, {})=({ts}, {id})",
+            ts_col.column, pk_col.column
+        )),
+        Cursor::NullPartition {
+            col, pk_col, id, ..
+        } => Some(match id {
+            Some(id) => format!("{} is null, {}={id}", col.column, pk_col.column),
+            None => format!("{} is null", col.column),
+        }),
+        Cursor::Union { table, inner } => Some(match position(inner) {
+            Some(position) => format!("table {table}, {position}"),
+            None => format!("table {table}"),
+        }),
+        Cursor::Opaque(token) => Some(token.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(part_id: &str, from: u64, ms: u64) -> BatchTiming {
+        BatchTiming {
+            part_id: part_id.to_string(),
+            from: from.to_string(),
+            to: (from + 100).to_string(),
+            rows: 100,
+            read: Duration::from_millis(ms),
+            write: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_report_skew_and_slowest_batches() {
+        let mut timings = RunTimings::default();
+        for i in 0..20 {
+            timings.record_batch(batch("part-0", i * 100, 10 + i));
+        }
+        timings.record_batch(batch("part-1", 5000, 900));
+        timings.record_partition("part-0".to_string(), 0, 2000, Duration::from_secs(10));
+        timings.record_partition("part-1".to_string(), 2000, 4000, Duration::from_secs(11));
+        timings.record_partition("part-2".to_string(), 4000, 6000, Duration::from_secs(40));
+
+        let report = timings.report();
+        let batches = report.batches.unwrap();
+        assert_eq!(batches.count, 21);
+        assert_eq!(batches.p50, Duration::from_millis(20));
+        assert_eq!(batches.max, Duration::from_millis(900));
+
+        let skewed: Vec<_> = report.skewed.iter().map(|p| p.part_id.as_str()).collect();
+        assert_eq!(skewed, ["part-2"]);
+
+        let slowest: Vec<_> = report
+            .slowest_batches
+            .iter()
+            .map(|b| b.from.as_str())
+            .collect();
+        assert_eq!(slowest, ["5000", "1900", "1800", "1700", "1600"]);
+        assert_eq!(report.slowest_batches[0].part_id, "part-1");
+    }
+
+    #[test]
+    fn test_partition_rows_from_batches() {
+        let mut timings = RunTimings::default();
+        timings.record_batch(batch("part-3", 0, 5));
+        timings.record_batch(batch("part-3", 100, 5));
+        timings.record_partition("part-3".to_string(), 0, 200, Duration::from_secs(1));
+
+        let report = timings.report();
+        assert!(report.skewed.is_empty());
+        assert_eq!(report.partitions.unwrap().count, 1);
+        assert_eq!(timings.partitions[0].rows, 200);
+    }
+}
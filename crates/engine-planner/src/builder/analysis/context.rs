@@ -21,6 +21,7 @@ pub struct AnalysisContextConfig {
     pub auto_mask_sensitive: bool,
     pub mask_columns: Vec<String>,
     pub use_exact_where: bool,
+    pub check_keys: bool,
 }
 
 /// Shared context passed to all analyzers during pipeline analysis
@@ -43,6 +44,9 @@ pub struct AnalysisContext<S: SchemaDriver, D: SchemaDriver> {
     /// Use exact COUNT for filtered rows (slower but accurate) vs EXPLAIN estimates (faster)
     pub use_exact_where: bool,
 
+    /// Count duplicate pagination keys that no unique index covers (a full-table scan)
+    pub check_keys: bool,
+
     pub source_dialect: Dialect,
     pub dest_dialect: Dialect,
 
@@ -100,6 +104,7 @@ impl<S: SchemaDriver, D: SchemaDriver> AnalysisContext<S, D> {
             sampling,
             masking,
             use_exact_where: config.use_exact_where,
+            check_keys: config.check_keys,
             source_dialect: src_dialect,
             dest_dialect,
             plugin_registry,
@@ -7,8 +7,10 @@ use crate::{
     plan::pagination::{cursor::CursorColumn, plan::PaginationPlan, strategy::PaginationStrategy},
 };
 use async_trait::async_trait;
+use connectors::sql::{metadata::table::TableMetadata, query::generator::QueryGenerator};
+use engine_core::plan::pagination::{uses_tiebreaker, with_default_tiebreaker};
 use engine_processing::io::driver::SchemaDriver;
-use model::execution::pipeline::Pagination;
+use model::{execution::pipeline::Pagination, pagination::offset_config::NullsOrder};
 use tracing::{info, warn};
 
 /// Analyzes pagination configuration to determine performance risks and strategy compatibility.
@@ -24,16 +26,59 @@ impl PaginationAnalyzer {
     ) -> Result<PaginationPlan, PaginationAnalyzerError> {
         let strategy = self.map_strategy_type(&pagination.strategy)?;
         let cursor_column = self.resolve_cursor_ref(&pagination.column, table)?;
-        self.verify_column_metadata(&cursor_column, ctx)?;
+        let column_nullable = self.verify_column_metadata(&cursor_column, ctx)?;
+        if strategy == PaginationStrategy::ServerCursor {
+            self.verify_server_cursor_key(&cursor_column, ctx)?;
+        }
+
+        if let Some(nulls) = &pagination.nulls
+            && nulls.parse::<NullsOrder>().is_err()
+        {
+            return Err(PaginationAnalyzerError::InvalidNullsOrder {
+                nulls: nulls.clone(),
+            });
+        }
+
+        let table_meta = self.table_metadata(&cursor_column.table, ctx)?;
+        let resolved = with_default_tiebreaker(pagination, table_meta);
+        let tiebreaker_inferred = pagination.tiebreaker.is_none() && resolved.tiebreaker.is_some();
 
-        let tiebreaker = if let Some(tb) = &pagination.tiebreaker {
+        let tiebreaker = if let Some(tb) = &resolved.tiebreaker {
             let tb_col = self.resolve_cursor_ref(tb, table)?;
             self.verify_column_metadata(&tb_col, ctx)?;
             Some(tb_col)
+        } else if uses_tiebreaker(&pagination.strategy) {
+            return Err(PaginationAnalyzerError::MissingTiebreaker {
+                table: cursor_column.table.clone(),
+                column: cursor_column.column.clone(),
+            });
         } else {
             None
         };
 
+        if let Some(tb_col) = tiebreaker.as_ref().filter(|_| tiebreaker_inferred) {
+            info!(
+                target: "analyzer",
+                table = %cursor_column.table,
+                cursor = %cursor_column.column,
+                tiebreaker = %tb_col.column,
+                "no tiebreaker configured, using primary key"
+            );
+        }
+
+        let keyset_unique = self
+            .verify_keyset_uniqueness(&strategy, &cursor_column, tiebreaker.as_ref(), ctx)
+            .await;
+
+        if keyset_unique == Some(false) {
+            warn!(
+                target: "analyzer",
+                table = %cursor_column.table,
+                cursor = %cursor_column.column,
+                "pagination keyset is not unique: rows sharing a key may be skipped or read twice"
+            );
+        }
+
         let column_indexed = ctx
             .source_cache
             .is_column_indexed(&cursor_column.table, &cursor_column.column)
@@ -48,6 +93,15 @@ impl PaginationAnalyzer {
             );
         }
 
+        if column_nullable && strategy != PaginationStrategy::Default {
+            warn!(
+                target: "analyzer",
+                table = %cursor_column.table,
+                column = %cursor_column.column,
+                "cursor column is nullable: NULL values cannot be compared by the keyset"
+            );
+        }
+
         info!(
             target: "analyzer",
             table = %cursor_column.table,
@@ -61,8 +115,12 @@ impl PaginationAnalyzer {
             strategy,
             cursor_column: Some(cursor_column),
             tiebreaker,
+            tiebreaker_inferred: Some(tiebreaker_inferred),
             timezone: pagination.timezone.clone(),
+            nulls: pagination.nulls.clone(),
             column_indexed: Some(column_indexed),
+            column_nullable: Some(column_nullable),
+            keyset_unique,
         })
     }
 
@@ -74,6 +132,7 @@ impl PaginationAnalyzer {
             "timestamp" => Ok(PaginationStrategy::Timestamp),
             "numeric" => Ok(PaginationStrategy::Numeric),
             "pk" => Ok(PaginationStrategy::Pk),
+            "server_cursor" => Ok(PaginationStrategy::ServerCursor),
             "default" | "offset" => Ok(PaginationStrategy::Default),
             _ => Err(PaginationAnalyzerError::UnsupportedStrategy {
                 strategy: strategy.to_string(),
@@ -106,33 +165,134 @@ impl PaginationAnalyzer {
         })
     }
 
+    fn table_metadata<'a, S: SchemaDriver, D: SchemaDriver>(
+        &self,
+        table: &str,
+        ctx: &'a AnalysisContext<S, D>,
+    ) -> Result<&'a TableMetadata, PaginationAnalyzerError> {
+        ctx.schema_plan.metadata_graph().get(table).ok_or_else(|| {
+            PaginationAnalyzerError::MetadataError {
+                table: table.to_string(),
+                reason: "Table metadata not found in plan graph.".into(),
+            }
+        })
+    }
+
+    /// Checks that the cursor column exists and returns whether it is nullable.
     fn verify_column_metadata<S: SchemaDriver, D: SchemaDriver>(
         &self,
         cursor: &CursorColumn,
         ctx: &AnalysisContext<S, D>,
-    ) -> Result<(), PaginationAnalyzerError> {
-        let table_meta = ctx
-            .schema_plan
-            .metadata_graph()
-            .get(&cursor.table)
-            .ok_or_else(|| PaginationAnalyzerError::MetadataError {
-                table: cursor.table.clone(),
-                reason: "Table metadata not found in plan graph.".into(),
-            })?;
-
-        let column_exists = table_meta
+    ) -> Result<bool, PaginationAnalyzerError> {
+        self.table_metadata(&cursor.table, ctx)?
             .columns()
             .iter()
-            .any(|col| col.name == cursor.column);
-
-        if !column_exists {
-            return Err(PaginationAnalyzerError::CursorColumnNotFound {
+            .find(|col| col.name == cursor.column)
+            .map(|col| col.is_nullable)
+            .ok_or_else(|| PaginationAnalyzerError::CursorColumnNotFound {
                 table: cursor.table.clone(),
                 column: cursor.column.clone(),
-            });
+            })
+    }
+
+    /// A server cursor checkpoints the last key it read as an integer, so a
+    /// key of any other type could not be resumed.
+    fn verify_server_cursor_key<S: SchemaDriver, D: SchemaDriver>(
+        &self,
+        cursor: &CursorColumn,
+        ctx: &AnalysisContext<S, D>,
+    ) -> Result<(), PaginationAnalyzerError> {
+        let table = self.table_metadata(&cursor.table, ctx)?;
+        match table.columns().iter().find(|col| col.name == cursor.column) {
+            Some(col) if !col.is_integer() => {
+                Err(PaginationAnalyzerError::ServerCursorKeyNotInteger {
+                    table: cursor.table.clone(),
+                    column: cursor.column.clone(),
+                    data_type: col.data_type.clone(),
+                })
+            }
+            _ => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Checks whether the keyset the strategy pages on identifies rows uniquely.
+    ///
+    /// Keysets covering the primary key or a unique index are unique by
+    /// construction; anything else is checked by counting duplicate keys in
+    /// the source, a full-table scan run only when `check_keys` asks for it.
+    /// Returns `None` when the check does not apply, was not asked for or
+    /// could not run.
+    async fn verify_keyset_uniqueness<S: SchemaDriver, D: SchemaDriver>(
+        &self,
+        strategy: &PaginationStrategy,
+        cursor: &CursorColumn,
+        tiebreaker: Option<&CursorColumn>,
+        ctx: &AnalysisContext<S, D>,
+    ) -> Option<bool> {
+        if *strategy == PaginationStrategy::Default {
+            return None;
+        }
+
+        let mut keys = vec![cursor.column.clone()];
+        if let Some(tb) = tiebreaker {
+            if tb.table != cursor.table {
+                return None;
+            }
+            if tb.column != cursor.column {
+                keys.push(tb.column.clone());
+            }
+        }
+
+        let covers = |columns: &[String]| {
+            !columns.is_empty() && columns.iter().all(|col| keys.contains(col))
+        };
+
+        let table_meta = self.table_metadata(&cursor.table, ctx).ok()?;
+        if covers(&table_meta.primary_keys) {
+            return Some(true);
+        }
+
+        if let Ok(indexes) = ctx.source_cache.index_metadata(&cursor.table).await
+            && indexes.iter().any(|idx| {
+                (idx.is_unique || idx.is_primary)
+                    && idx.condition.is_none()
+                    && covers(
+                        &idx.columns
+                            .iter()
+                            .map(|c| c.name.clone())
+                            .collect::<Vec<_>>(),
+                    )
+            })
+        {
+            return Some(true);
+        }
+
+        if !ctx.check_keys {
+            warn!(
+                target: "analyzer",
+                table = %cursor.table,
+                keyset = %keys.join(", "),
+                "pagination keyset uniqueness is unproven: no primary key or unique index covers it (plan --check-keys counts duplicates)"
+            );
+            return None;
+        }
+
+        let dialect = ctx.source_dialect.as_query_dialect();
+        let sql = QueryGenerator::new(dialect.as_ref()).duplicate_keys(&cursor.table, &keys);
+
+        match ctx.src_driver.query(&sql).await {
+            Ok(rows) => {
+                let duplicates = rows
+                    .first()
+                    .and_then(|row| row.get_value("duplicates").as_i64())
+                    .unwrap_or(0);
+                Some(duplicates == 0)
+            }
+            Err(e) => {
+                warn!(target: "analyzer", error = %e, sql = %sql, "keyset uniqueness check failed");
+                None
+            }
+        }
     }
 }
 
@@ -10,7 +10,7 @@ use crate::{
             method::SamplingMethod,
             preview::{SampleDataPreview, SampleQuery},
             row::{SampleRow, SampleRowStatus, SampleValidationResult, SampleValue},
-            stats::{SampleStats, ValidationStats},
+            stats::{ConversionStats, SampleStats, ValidationStats},
         },
         validation::plan::ValidationPlan,
     },
@@ -27,11 +27,18 @@ use engine_processing::{
     },
 };
 use model::{
-    core::value::Value, execution::pipeline::Pipeline, records::Record,
+    core::{cast::Lossiness, types::Type, value::Value},
+    execution::pipeline::Pipeline,
+    records::Record,
     transform::mapping::TransformationMetadata,
 };
-use std::{collections::HashMap, fmt::Write, sync::Arc, time::Instant};
-use tracing::info;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    sync::Arc,
+    time::Instant,
+};
+use tracing::{info, warn};
 
 /// Configuration for sample collection behavior and privacy
 #[derive(Clone, Debug)]
@@ -105,24 +112,36 @@ impl<S: SchemaDriver> SampleCollector<S> {
             mapping,
             mapped_columns_only,
             Arc::new(EnvContext::empty()),
+            None,
+            None,
+            HashMap::new(),
         )
         .map_err(|e| SampleCollectorError::PipelineBuildFailed(e.to_string()))?;
+        let column_types = self.destination_types(pipeline, ctx).await;
         let mut sample_rows = Vec::with_capacity(source_rows.len());
         let mut val_stats: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut conversions: BTreeMap<String, ConversionStats> = BTreeMap::new();
 
         for (idx, mut row) in source_rows.into_iter().enumerate() {
-            sample_rows.push(self.process_sample_row(
+            let mut sample = self.process_sample_row(
                 idx,
                 &mut row,
                 &transform_pipeline,
                 validations,
                 mapping,
                 &mut val_stats,
-            ));
+            );
+            if sample.output.is_some() {
+                self.check_conversions(&mut sample, &row, &column_types, &mut conversions);
+            }
+            sample_rows.push(sample);
         }
 
         info!(table = %pipeline.source.table, count = sample_rows.len(), "collected sample rows");
 
+        let mut stats = self.aggregate_stats(&sample_rows, &val_stats);
+        stats.conversions = conversions.into_values().collect();
+
         Ok(SampleDataPreview {
             enabled: true,
             sampled_at: Some(chrono::Utc::now()),
@@ -130,7 +149,7 @@ impl<S: SchemaDriver> SampleCollector<S> {
             sampling_method: self.config.method.clone(),
             duration_ms: Some(start.elapsed().as_millis() as u64),
             query,
-            stats: self.aggregate_stats(&sample_rows, &val_stats),
+            stats,
             issues: sample_rows
                 .iter()
                 .flat_map(|r| r.issues.iter().cloned())
@@ -214,6 +233,99 @@ impl<S: SchemaDriver> SampleCollector<S> {
         }
     }
 
+    /// Canonical and reported type of each destination column, by lowercase
+    /// name; empty when the table does not exist yet.
+    async fn destination_types<D: SchemaDriver>(
+        &self,
+        pipeline: &Pipeline,
+        ctx: &AnalysisContext<S, D>,
+    ) -> HashMap<String, (Type, String)> {
+        let table = &pipeline.destination.table;
+        if !ctx.dest_cache.table_exists(table).await.unwrap_or(false) {
+            return HashMap::new();
+        }
+        match ctx.dest_cache.table_metadata(table).await {
+            Ok(metadata) => metadata
+                .columns()
+                .iter()
+                .map(|col| {
+                    (
+                        col.name.to_lowercase(),
+                        (ctx.dest_dialect.to_canonical(col), col.data_type.clone()),
+                    )
+                })
+                .collect(),
+            Err(e) => {
+                warn!(table = %table, error = %e, "failed to fetch destination column types");
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Converts each output value to its destination column type the way
+    /// the writer will, reporting values that lose data or cannot convert.
+    fn check_conversions(
+        &self,
+        sample: &mut SampleRow,
+        row: &Record,
+        column_types: &HashMap<String, (Type, String)>,
+        conversions: &mut BTreeMap<String, ConversionStats>,
+    ) {
+        for field in &row.fields {
+            let (Some(value), Some((data_type, reported))) =
+                (&field.value, column_types.get(&field.name.to_lowercase()))
+            else {
+                continue;
+            };
+            let stats = || ConversionStats {
+                column: field.name.clone(),
+                target_type: reported.clone(),
+                lossiness: Lossiness::Exact,
+                lossy: 0,
+                failed: 0,
+            };
+            let issue = match value.convert_to(data_type) {
+                Ok((_, loss)) if loss.is_lossy() => {
+                    let entry = conversions.entry(field.name.clone()).or_insert_with(stats);
+                    entry.lossy += 1;
+                    entry.lossiness = entry.lossiness.max(loss);
+                    SampleIssue {
+                        level: SampleIssueLevel::Warning,
+                        code: "LOSSY_CONVERSION".into(),
+                        message: format!(
+                            "Column '{}' converts to {} with loss ({})",
+                            field.name,
+                            reported,
+                            loss.as_str()
+                        ),
+                        row_index: Some(sample.index),
+                        column: Some(field.name.clone()),
+                        suggestion: Some("Widen the destination column or cast the value".into()),
+                    }
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    conversions
+                        .entry(field.name.clone())
+                        .or_insert_with(stats)
+                        .failed += 1;
+                    SampleIssue {
+                        level: SampleIssueLevel::Warning,
+                        code: "CONVERSION_FAILED".into(),
+                        message: format!("Column '{}': {}", field.name, e),
+                        row_index: Some(sample.index),
+                        column: Some(field.name.clone()),
+                        suggestion: Some("The destination will likely reject this value".into()),
+                    }
+                }
+            };
+            if sample.status == SampleRowStatus::Ok {
+                sample.status = SampleRowStatus::Warning;
+            }
+            sample.issues.push(issue);
+        }
+    }
+
     fn record_passed(
         &self,
         validations: &[ValidationPlan],
@@ -510,6 +622,7 @@ impl<S: SchemaDriver> SampleCollector<S> {
                 .filter(|r| r.status == SampleRowStatus::Failed)
                 .count(),
             validation_stats: Vec::new(),
+            conversions: Vec::new(),
         };
 
         for (name, (passed, failed)) in val_results {
@@ -532,7 +645,7 @@ impl<S: SchemaDriver> SampleCollector<S> {
         ["id", "_id", "uuid", "pk", &self.config.id_column]
             .iter()
             .find_map(|&c| row.fields.iter().find(|f| f.name.eq_ignore_ascii_case(c)))
-            .and_then(|f| f.value.as_ref().map(|v| format!("{:?}", v)))
+            .and_then(|f| f.value.as_ref().and_then(Value::as_string))
     }
 
     fn empty_preview(&self, start: Instant, query: Option<SampleQuery>) -> SampleDataPreview {
@@ -63,6 +63,18 @@ impl SchemaAnalyzer {
             is_reversible: true,
         });
 
+        // Child partitions of a partitioned table
+        for (sql, partition) in ctx.schema_plan.partition_queries() {
+            changes.push(SchemaChange {
+                change_type: SchemaChangeType::CreateTable,
+                entity: partition.clone(),
+                description: format!("Create partition '{}' of '{}'", partition, dest_table),
+                ddl: Some(sql),
+                is_breaking: false,
+                is_reversible: true,
+            });
+        }
+
         // Custom Enum Types - enum_queries() returns a HashSet
         let enum_queries = ctx.schema_plan.enum_queries();
 
@@ -34,6 +34,7 @@ struct SourceTableMetrics {
     total_rows: RowCount,
     filtered_rows: Option<RowCount>,
     size_bytes: u64,
+    avg_row_bytes: Option<u64>,
 }
 
 /// Analyzes source tables to gather schema metadata and statistics
@@ -65,6 +66,12 @@ impl<S: SchemaDriver> SourceAnalyzer<S> {
             .table_size_bytes(&source.table)
             .await
             .unwrap_or(0);
+        let avg_row_bytes = ctx
+            .source_cache
+            .avg_row_bytes(&source.table)
+            .await
+            .ok()
+            .flatten();
 
         let driver = DatabaseDriver::from_name(&source.connection.driver);
         let plan = self.assemble_source_plan(
@@ -75,6 +82,7 @@ impl<S: SchemaDriver> SourceAnalyzer<S> {
                 total_rows,
                 filtered_rows,
                 size_bytes,
+                avg_row_bytes,
             },
             driver,
         );
@@ -265,8 +273,10 @@ impl<S: SchemaDriver> SourceAnalyzer<S> {
             filtered_rows: metrics.filtered_rows,
             columns: column_infos,
             primary_key: metrics.metadata.primary_keys,
+            collation: metrics.metadata.collation,
             indexes: index_infos,
             size_bytes: metrics.size_bytes,
+            avg_row_bytes: metrics.avg_row_bytes,
             last_analyzed: Utc::now(),
         }
     }
@@ -57,6 +57,7 @@ mod code {
     pub const VERY_LARGE_DATASET: &str = "VERY_LARGE_DATASET";
     pub const EMPTY_AFTER_FILTER: &str = "EMPTY_AFTER_FILTER";
     pub const EMPTY_SOURCE: &str = "EMPTY_SOURCE";
+    pub const NON_UTF8_CHARSET: &str = "NON_UTF8_CHARSET";
 
     // Destination codes
     pub const DESTRUCTIVE_MODE: &str = "DESTRUCTIVE_MODE";
@@ -82,6 +83,9 @@ mod code {
     pub const MISSING_PAGINATION: &str = "MISSING_PAGINATION";
     pub const UNINDEXED_CURSOR: &str = "UNINDEXED_CURSOR";
     pub const OFFSET_PAGINATION: &str = "OFFSET_PAGINATION";
+    pub const NULLABLE_CURSOR: &str = "NULLABLE_CURSOR";
+    pub const NON_UNIQUE_CURSOR: &str = "NON_UNIQUE_CURSOR";
+    pub const INFERRED_TIEBREAKER: &str = "INFERRED_TIEBREAKER";
 
     // Resource codes
     pub const HIGH_MEMORY_USAGE: &str = "HIGH_MEMORY_USAGE";
@@ -243,6 +247,24 @@ impl DiagnosticGenerator {
             );
         }
 
+        // A collation is named after its character set, e.g. latin1_swedish_ci
+        let charset = source
+            .collation
+            .as_deref()
+            .and_then(|c| c.split('_').next());
+        if let Some(charset) = charset
+            && !matches!(charset, "utf8" | "utf8mb3" | "utf8mb4" | "ascii" | "binary")
+        {
+            diagnostics.push(
+                Diagnostic::info(
+                    code::NON_UTF8_CHARSET,
+                    &Self::format_msg(msg::SOURCE, "non_utf8_charset", &[&source.fqn, charset]),
+                )
+                .with_pipeline(pipeline)
+                .with_suggestion(&Self::get_msg(msg::SOURCE, "non_utf8_charset_suggestion")),
+            );
+        }
+
         diagnostics
     }
 
@@ -536,6 +558,79 @@ impl DiagnosticGenerator {
                     );
                 }
 
+                if pag.column_nullable == Some(true)
+                    && pag.strategy != PaginationStrategy::Default
+                    && let Some(cursor_col) = &pag.cursor_column
+                {
+                    // Numeric and timestamp keysets read NULLs as a separate
+                    // partition; key-only strategies cannot reach them at all.
+                    let partitioned = matches!(
+                        pag.strategy,
+                        PaginationStrategy::Numeric | PaginationStrategy::Timestamp
+                    );
+                    let (message, suggestion) = if partitioned {
+                        ("nullable_cursor", "nullable_cursor_suggestion")
+                    } else {
+                        ("nullable_key", "nullable_key_suggestion")
+                    };
+
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            code::NULLABLE_CURSOR,
+                            &Self::format_msg(
+                                msg::PAGINATION,
+                                message,
+                                &[&cursor_col.table, &cursor_col.column],
+                            ),
+                        )
+                        .with_pipeline(pipeline)
+                        .with_suggestion(&Self::get_msg(msg::PAGINATION, suggestion)),
+                    );
+                }
+
+                if pag.keyset_unique == Some(false)
+                    && let Some(cursor_col) = &pag.cursor_column
+                {
+                    let keyset = match &pag.tiebreaker {
+                        Some(tb) if tb.column != cursor_col.column => {
+                            format!("{}, {}", cursor_col.column, tb.column)
+                        }
+                        _ => cursor_col.column.clone(),
+                    };
+
+                    diagnostics.push(
+                        Diagnostic::error(
+                            code::NON_UNIQUE_CURSOR,
+                            &Self::format_msg(
+                                msg::PAGINATION,
+                                "non_unique_cursor",
+                                &[&cursor_col.table, &keyset],
+                            ),
+                        )
+                        .with_pipeline(pipeline)
+                        .with_suggestion(&Self::get_msg(
+                            msg::PAGINATION,
+                            "non_unique_cursor_suggestion",
+                        )),
+                    );
+                }
+
+                if pag.tiebreaker_inferred == Some(true)
+                    && let Some(tb) = &pag.tiebreaker
+                {
+                    diagnostics.push(
+                        Diagnostic::info(
+                            code::INFERRED_TIEBREAKER,
+                            &Self::format_msg(
+                                msg::PAGINATION,
+                                "inferred_tiebreaker",
+                                &[&tb.table, &tb.column],
+                            ),
+                        )
+                        .with_pipeline(pipeline),
+                    );
+                }
+
                 if pag.strategy == PaginationStrategy::Default {
                     diagnostics.push(
                         Diagnostic::warning(
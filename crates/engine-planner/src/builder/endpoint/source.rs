@@ -71,8 +71,10 @@ impl DbPlanSourceEndpoint {
             filtered_rows: None,
             columns,
             primary_key: metadata.primary_keys.clone(),
+            collation: None,
             indexes: Vec::<IndexInfo>::new(),
             size_bytes: 0,
+            avg_row_bytes: None,
             last_analyzed: Utc::now(),
         };
 
@@ -140,8 +142,10 @@ impl WasmPlanSourceEndpoint {
             filtered_rows: None,
             columns,
             primary_key: Vec::new(),
+            collation: None,
             indexes: Vec::new(),
             size_bytes: 0,
+            avg_row_bytes: None,
             last_analyzed: Utc::now(),
         };
 
@@ -242,6 +242,23 @@ pub enum PaginationAnalyzerError {
 
     #[error("Timezone '{timezone}' is invalid")]
     InvalidTimezone { timezone: String },
+
+    #[error("Invalid NULL ordering '{nulls}': expected \"first\" or \"last\"")]
+    InvalidNullsOrder { nulls: String },
+
+    #[error(
+        "Cursor '{column}' on table '{table}' needs a tiebreaker: no tiebreaker is configured and the table has no single-column primary key"
+    )]
+    MissingTiebreaker { table: String, column: String },
+
+    #[error(
+        "server_cursor key '{column}' on table '{table}' is {data_type}: a server cursor resumes after the last key read, so the key must be an integer"
+    )]
+    ServerCursorKeyNotInteger {
+        table: String,
+        column: String,
+        data_type: String,
+    },
 }
 
 /// Hooks analysis errors
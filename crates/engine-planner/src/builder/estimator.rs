@@ -2,6 +2,7 @@ use crate::plan::{
     diagnostics::calibration::CalibrationData,
     estimation::{
         duration::DurationEstimate, pipeline::PipelineEstimations, resource::ResourceEstimations,
+        volume::VolumeEstimate,
     },
     execution::{
         execution_settings::{ExecutionSettings, ExecutionStrategy},
@@ -11,7 +12,7 @@ use crate::plan::{
         destination::{DestinationPlan, WriteMode},
         plan::PipelinePlan,
         settings::{CheckpointStrategy, PipelineSettings},
-        source::SourcePlan,
+        source::{ColumnInfo, SourcePlan},
     },
     transform::{
         join::JoinPlan,
@@ -118,8 +119,8 @@ impl DurationEstimator {
     ) -> PipelineEstimations {
         let tps = self.calculate_throughput(mappings, joins, settings, is_fast_path);
 
-        // Base duration: rows / rows-per-second
-        let rows = source.total_rows.value;
+        // Base duration: migrated rows / rows-per-second
+        let rows = source.effective_row_count().value;
         let base_seconds = rows.checked_div(tps).unwrap_or(0);
         let overhead_seconds = self.calculate_total_overhead(settings, destination);
 
@@ -132,12 +133,53 @@ impl DurationEstimator {
 
         PipelineEstimations {
             duration: DurationEstimate::from_seconds(base_seconds + overhead_seconds),
+            volume: Self::estimate_volume(source, rows),
             rows_per_second: tps,
             batches: batches.max(1),
             memory_mb: self.estimate_memory(settings, mappings),
         }
     }
 
+    /// Data read for `rows` source rows, using the row width from the table
+    /// statistics when the source has them.
+    fn estimate_volume(source: &SourcePlan, rows: u64) -> VolumeEstimate {
+        match source.avg_row_bytes {
+            Some(width) => VolumeEstimate::new(rows, width, true),
+            None => VolumeEstimate::new(
+                rows,
+                source.columns.iter().map(Self::column_width).sum(),
+                false,
+            ),
+        }
+    }
+
+    /// Typical stored width of a column, for sources without statistics
+    fn column_width(column: &ColumnInfo) -> u64 {
+        const VARIABLE_DEFAULT: u64 = 32;
+        const LOB_DEFAULT: u64 = 256;
+
+        let t = column.data_type.to_ascii_lowercase();
+        let base = t.split(['(', ' ']).next().unwrap_or_default();
+        match base {
+            "boolean" | "bool" | "tinyint" | "bit" => 1,
+            "smallint" | "int2" | "smallserial" | "year" => 2,
+            "integer" | "int" | "int4" | "mediumint" | "serial" | "real" | "float4" | "float"
+            | "date" => 4,
+            "bigint" | "int8" | "bigserial" | "double" | "float8" | "timestamp" | "timestamptz"
+            | "datetime" | "time" | "timetz" | "money" => 8,
+            "uuid" | "decimal" | "numeric" | "interval" => 16,
+            "char" | "character" | "varchar" | "nchar" | "nvarchar" | "binary" | "varbinary"
+            | "enum" => column
+                .max_length
+                // Variable-length values are rarely full
+                .map(|n| (n as u64 / 2).clamp(1, LOB_DEFAULT))
+                .unwrap_or(VARIABLE_DEFAULT),
+            "text" | "tinytext" | "mediumtext" | "longtext" | "json" | "jsonb" | "bytea"
+            | "blob" | "tinyblob" | "mediumblob" | "longblob" | "xml" => LOB_DEFAULT,
+            _ => VARIABLE_DEFAULT,
+        }
+    }
+
     /// Estimate duration for an execution stage (parallel pipelines)
     pub fn estimate_stage(pipelines: &[&PipelinePlan]) -> DurationEstimate {
         DurationEstimate::max_of(
@@ -259,6 +301,12 @@ impl ResourceEstimator {
     ) -> ResourceEstimations {
         ResourceEstimations {
             duration: Self::total_duration(pipelines, execution_order, settings),
+            volume: VolumeEstimate::combine(
+                &pipelines
+                    .iter()
+                    .map(|p| p.estimations.volume.clone())
+                    .collect::<Vec<_>>(),
+            ),
             peak_memory_mb: Self::peak_memory(pipelines, execution_order, settings),
             network_transfer_mb: Self::total_network(pipelines),
             disk_usage_mb: Self::total_disk(pipelines),
@@ -318,9 +366,8 @@ impl ResourceEstimator {
         pipelines
             .iter()
             .map(|p| {
-                let row_bytes =
-                    p.mappings.len() as f64 * 100.0 * p.source.effective_row_count().value as f64;
-                (row_bytes * 2.0) / (1024.0 * 1024.0) // Read + Write, convert to MB
+                let bytes = p.estimations.volume.total_bytes as f64;
+                (bytes * 2.0) / (1024.0 * 1024.0) // Read + Write, convert to MB
             })
             .sum()
     }
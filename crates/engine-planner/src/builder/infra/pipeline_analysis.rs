@@ -1,17 +1,26 @@
 use crate::builder::{
-    ReportBuilder, errors::ReportBuilderResult, infra::metadata_cache::MetadataCacheRef,
+    ReportBuilder,
+    errors::{PaginationAnalyzerError, ReportBuilderError, ReportBuilderResult},
+    infra::metadata_cache::MetadataCacheRef,
 };
 use connectors::traits::introspector::SchemaIntrospector;
-use engine_config::settings::validated::ValidatedSettings;
+use engine_config::settings::{SettingFinding, validated::ValidatedSettings};
 use engine_core::{
-    context::exec::ConnectionPool, dispatch_driver, drivers::DriverRef, schema::plan::SchemaPlan,
+    context::exec::ConnectionPool,
+    dispatch_driver,
+    drivers::DriverRef,
+    plan::pagination::{uses_tiebreaker, with_default_tiebreaker},
+    schema::plan::SchemaPlan,
 };
 use engine_processing::io::{
     destination::{Destination, IntoDestination},
     source::Source,
 };
 use engine_wasm::registry::{PluginRegistry, plugin_columns};
-use model::{execution::pipeline::Pipeline, transform::mapping::TransformationMetadata};
+use model::{
+    execution::pipeline::{Pagination, Pipeline},
+    transform::mapping::TransformationMetadata,
+};
 use query_builder::offsets::OffsetStrategyFactory;
 use std::sync::Arc;
 
@@ -27,6 +36,8 @@ pub struct PipelineAnalysisResources {
     pub core_data_destination: Destination,
     pub schema_plan: Arc<SchemaPlan>,
     pub validated_settings: ValidatedSettings,
+    /// Settings the pipeline's drivers will ignore
+    pub setting_findings: Vec<SettingFinding>,
 }
 
 impl PipelineAnalysisResources {
@@ -76,7 +87,10 @@ impl PipelineAnalysisResources {
         let mut mapping = TransformationMetadata::new(pipeline);
         mapping.set_plugin_columns(plugin_columns(pipeline, plugin_registry));
 
-        let offset_strategy = OffsetStrategyFactory::from_pagination(&pipeline.source.pagination);
+        let pagination = Self::resolve_pagination(pipeline, &src_driver).await?;
+        let offset_strategy = OffsetStrategyFactory::from_pagination(&pagination).map_err(|e| {
+            ReportBuilderError::Config(format!("pipeline '{}': {e}", pipeline.name))
+        })?;
 
         let core_data_source = dispatch_driver!(&src_driver, |d| {
             Arc::new(Source::new(d.clone(), pipeline, &mapping, offset_strategy).await?)
@@ -92,7 +106,7 @@ impl PipelineAnalysisResources {
             d.clone() as Arc<dyn SchemaIntrospector>
         });
 
-        let validated_settings = builder
+        let (validated_settings, setting_findings) = builder
             .validate_settings(
                 pipeline,
                 &core_data_source,
@@ -123,8 +137,35 @@ impl PipelineAnalysisResources {
             core_data_destination,
             schema_plan,
             validated_settings,
+            setting_findings,
         })
     }
+
+    /// Pagination with the tiebreaker defaulted to the source table's primary key.
+    async fn resolve_pagination(
+        pipeline: &Pipeline,
+        src_driver: &DriverRef,
+    ) -> ReportBuilderResult<Option<Pagination>> {
+        let Some(pagination) = &pipeline.source.pagination else {
+            return Ok(None);
+        };
+        if pagination.tiebreaker.is_some() || !uses_tiebreaker(&pagination.strategy) {
+            return Ok(Some(pagination.clone()));
+        }
+
+        let table = dispatch_driver!(src_driver, |d| {
+            d.table_metadata(&pipeline.source.table).await?
+        });
+        let resolved = with_default_tiebreaker(pagination, &table);
+        if resolved.tiebreaker.is_none() {
+            return Err(PaginationAnalyzerError::MissingTiebreaker {
+                table: pipeline.source.table.clone(),
+                column: pagination.column.clone(),
+            }
+            .into());
+        }
+        Ok(Some(resolved))
+    }
 }
 
 pub struct PipelineSettingsView<'a> {
@@ -41,9 +41,9 @@ use crate::{
         sample::method::SamplingMethod,
     },
 };
-use connectors::traits::introspector::SchemaIntrospector;
+use connectors::{error::DriverError, traits::introspector::SchemaIntrospector};
 use engine_config::settings::{
-    Settings, validated::ValidatedSettings, validator::SettingsValidator,
+    SettingFinding, Settings, validated::ValidatedSettings, validator::SettingsValidator,
 };
 use engine_core::{
     context::exec::ConnectionPool,
@@ -52,6 +52,7 @@ use engine_core::{
     plan::execution::ExecutionPlan as CoreExecutionPlan,
     retry::RetryPolicy,
     schema::{
+        error::NamingError,
         plan::SchemaPlan,
         planner::SchemaPlanner,
         type_registry::{Dialect, TypeRegistry},
@@ -61,7 +62,6 @@ use engine_processing::io::{destination::Destination, format::DataFormat, source
 use engine_runtime::dag::Dag;
 use engine_wasm::registry::{PluginRegistry, load_registry};
 use model::execution::flags::IntegrityMode;
-use model::execution::pipeline::RetryConfig as CoreRetryConfig;
 use model::{
     core::value::Value,
     execution::{
@@ -129,6 +129,10 @@ pub struct ReportBuilderConfig {
     /// Use exact COUNT for filtered rows (slower but accurate) vs EXPLAIN estimates (faster)
     pub exact_where: bool,
 
+    /// Count duplicate pagination keys in the source (a full-table scan) when
+    /// neither the primary key nor a unique index proves them unique
+    pub check_keys: bool,
+
     /// Verbosity level (0 = quiet, 1 = normal, 2+ = verbose)
     pub verbosity: u8,
 }
@@ -146,6 +150,7 @@ impl Default for ReportBuilderConfig {
             mask_columns: Vec::new(),
             auto_mask_sensitive: true,
             exact_where: false, // Use EXPLAIN by default (faster)
+            check_keys: false,
             verbosity: 1,
         }
     }
@@ -363,7 +368,7 @@ impl ReportBuilder {
             .calculate_execution_positions(dag, &pipeline.name)
             .unwrap_or((0, 0));
 
-        let diagnostics = DiagnosticGenerator::for_pipeline(
+        let mut diagnostics = DiagnosticGenerator::for_pipeline(
             &pipeline.name,
             &report.source,
             &report.destination,
@@ -372,6 +377,32 @@ impl ReportBuilder {
             &report.mappings,
             &report.pagination,
         );
+        diagnostics.extend(resources.setting_findings.iter().map(|f| {
+            Diagnostic::warning("SETTING_IGNORED", &f.message).with_pipeline(&pipeline.name)
+        }));
+        diagnostics.extend(Self::column_collision_diagnostics(
+            &pipeline.name,
+            &resources.schema_plan,
+        ));
+        if resources.validated_settings.requires_schema_op() {
+            diagnostics.extend(Self::identifier_diagnostics(
+                &pipeline.name,
+                &resources.schema_plan,
+            ));
+            diagnostics.extend(resources.schema_plan.partitioning_errors().iter().map(|e| {
+                Diagnostic::error("INVALID_PARTITIONING", e).with_pipeline(&pipeline.name)
+            }));
+            diagnostics.extend(
+                resources
+                    .schema_plan
+                    .column_type_errors()
+                    .await
+                    .iter()
+                    .map(|e| {
+                        Diagnostic::error("INVALID_COLUMN_TYPES", e).with_pipeline(&pipeline.name)
+                    }),
+            );
+        }
 
         Ok(PipelinePlan {
             name: pipeline.name.clone(),
@@ -397,6 +428,49 @@ impl ReportBuilder {
         })
     }
 
+    /// A warning for every identifier the destination will not keep under its
+    /// own name, or an error when two of them end up with the same name.
+    fn identifier_diagnostics(pipeline: &str, schema_plan: &SchemaPlan) -> Vec<Diagnostic> {
+        match schema_plan.identifier_report() {
+            Ok(renamed) => renamed
+                .iter()
+                .map(|r| {
+                    let code = if r.kind.is_truncated() {
+                        "IDENTIFIER_TRUNCATED"
+                    } else {
+                        "IDENTIFIER_SHORTENED"
+                    };
+                    Diagnostic::warning(code, &r.to_string()).with_pipeline(pipeline)
+                })
+                .collect(),
+            // Reported by `column_collision_diagnostics`
+            Err(NamingError::ColumnCollision(_)) => Vec::new(),
+            Err(e) => {
+                vec![
+                    Diagnostic::error("IDENTIFIER_COLLISION", &e.to_string())
+                        .with_pipeline(pipeline),
+                ]
+            }
+        }
+    }
+
+    /// An error for every destination column two source columns would be
+    /// written to, suggesting a free name for the column copied as is.
+    fn column_collision_diagnostics(pipeline: &str, schema_plan: &SchemaPlan) -> Vec<Diagnostic> {
+        schema_plan
+            .column_collisions()
+            .iter()
+            .map(|c| {
+                Diagnostic::error("COLUMN_COLLISION", &c.to_string())
+                    .with_suggestion(&format!(
+                        "Map '{}' to another name, e.g. `{} = {}` in the select block",
+                        c.column, c.suggested, c.column
+                    ))
+                    .with_pipeline(pipeline)
+            })
+            .collect()
+    }
+
     /// Orchestrates the actual analysis calls via the Registry.
     async fn run_pipeline_analysis(
         &self,
@@ -415,6 +489,7 @@ impl ReportBuilder {
             auto_mask_sensitive: config.auto_mask_sensitive,
             mask_columns: config.mask_columns.clone(),
             use_exact_where: config.exact_where,
+            check_keys: config.check_keys,
         };
 
         let analysis_input = PipelineAnalysisInput::new(
@@ -574,13 +649,14 @@ impl ReportBuilder {
         source: &Source,
         dest: &Destination,
         introspector: &dyn SchemaIntrospector,
-    ) -> ReportBuilderResult<ValidatedSettings> {
+    ) -> ReportBuilderResult<(ValidatedSettings, Vec<SettingFinding>)> {
         let settings = Settings::from_map(&pipeline.settings);
         let validator =
             SettingsValidator::new(source, dest, introspector, true, IntegrityMode::Off);
-        validator.validate(&settings).await.map_err(|e| {
+        let validated = validator.validate(&settings).await.map_err(|e| {
             ReportBuilderError::Config(format!("Validation failed for {}: {}", pipeline.name, e))
-        })
+        })?;
+        Ok((validated, validator.findings(&settings)))
     }
 
     pub(crate) async fn build_schema_plan(
@@ -592,9 +668,16 @@ impl ReportBuilder {
         settings: &ValidatedSettings,
     ) -> ReportBuilderResult<SchemaPlan> {
         let view = PipelineSettingsView::new(settings);
-        let target_dialect = DataFormat::parse(&pipeline.destination.connection.driver)
-            .map(|f| f.to_dialect())
-            .unwrap_or(Dialect::Postgres);
+        let connection = &pipeline.destination.connection;
+        let target_dialect = DataFormat::parse(&connection.driver)
+            .ok_or_else(|| DriverError::UnsupportedFormat(connection.driver.clone()))
+            .and_then(|f| f.to_dialect())
+            .map_err(|e| {
+                ReportBuilderError::Config(format!(
+                    "destination connection '{}' of pipeline '{}': {}",
+                    connection.name, pipeline.name, e
+                ))
+            })?;
         let type_registry = TypeRegistry::new(source_dialect, target_dialect);
         let planner = SchemaPlanner::new(
             introspector.clone(),
@@ -603,7 +686,10 @@ impl ReportBuilder {
             view.ignore_constraints(),
             view.mapped_columns_only(),
             type_registry,
-        );
+        )
+        .with_max_identifier_length(settings.max_identifier_length())
+        .with_materialize_generated(settings.materialize_generated_columns())
+        .with_source_timezones(pipeline.source.timezones.clone());
 
         let join_tables: Vec<&str> = pipeline
             .source
@@ -616,6 +702,15 @@ impl ReportBuilder {
             let meta = introspector.table_metadata(join_table).await?;
             plan.add_metadata(join_table, meta);
         }
+        if let Some(partition_by) = &pipeline.destination.partition_by {
+            plan.set_partitioning(&pipeline.destination.table, partition_by.clone());
+        }
+        if !pipeline.destination.column_types.is_empty() {
+            plan.set_column_types(
+                &pipeline.destination.table,
+                pipeline.destination.column_types.clone(),
+            );
+        }
 
         Ok(plan)
     }
@@ -664,7 +759,7 @@ impl ReportBuilder {
             ErrorHandlingPlan {
                 retry: Some(RetryConfig {
                     max_attempts: retry_policy.max_attempts,
-                    backoff: self.map_backoff(&eh.retry, &retry_policy),
+                    backoff: self.map_backoff(&retry_policy),
                 }),
                 failed_rows: self.map_failed_rows(eh),
                 after_max_retries: AfterMaxRetries::Fail,
@@ -672,28 +767,18 @@ impl ReportBuilder {
         })
     }
 
-    fn map_backoff(&self, r: &Option<CoreRetryConfig>, p: &RetryPolicy) -> BackoffConfig {
-        if let Some(r) = r {
-            let delay = Duration::from_millis(r.delay_ms);
-            match r.backoff {
-                BackoffStrategy::Fixed => BackoffConfig::Fixed {
-                    delay: format_duration(&delay),
-                },
-                BackoffStrategy::Exponential => BackoffConfig::Exponential {
-                    initial_delay: format_duration(&delay),
-                    max_delay: Some(format_duration(&Duration::from_secs(5))),
-                },
-                BackoffStrategy::Linear => BackoffConfig::Linear {
-                    delay: format_duration(&Duration::from_millis(
-                        r.delay_ms * r.max_attempts as u64,
-                    )),
-                },
-            }
-        } else {
-            BackoffConfig::Exponential {
+    fn map_backoff(&self, p: &RetryPolicy) -> BackoffConfig {
+        match p.backoff {
+            BackoffStrategy::Fixed => BackoffConfig::Fixed {
+                delay: format_duration(&p.base_delay),
+            },
+            BackoffStrategy::Exponential => BackoffConfig::Exponential {
                 initial_delay: format_duration(&p.base_delay),
                 max_delay: Some(format_duration(&p.max_delay)),
-            }
+            },
+            BackoffStrategy::Linear => BackoffConfig::Linear {
+                delay: format_duration(&p.base_delay),
+            },
         }
     }
 
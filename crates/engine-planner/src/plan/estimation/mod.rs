@@ -1,3 +1,4 @@
 pub mod duration;
 pub mod pipeline;
 pub mod resource;
+pub mod volume;
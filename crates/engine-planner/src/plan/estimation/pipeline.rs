@@ -1,10 +1,13 @@
-use crate::plan::estimation::duration::DurationEstimate;
+use crate::plan::estimation::{duration::DurationEstimate, volume::VolumeEstimate};
 use serde::Serialize;
 
 #[derive(Serialize, Debug, Clone, Default)]
 pub struct PipelineEstimations {
     pub duration: DurationEstimate,
 
+    /// Rows and bytes the pipeline reads from the source
+    pub volume: VolumeEstimate,
+
     /// Estimated throughput (rows processed per second)
     pub rows_per_second: u64,
 
@@ -1,4 +1,4 @@
-use crate::plan::estimation::duration::DurationEstimate;
+use crate::plan::estimation::{duration::DurationEstimate, volume::VolumeEstimate};
 use serde::{Serialize, Serializer};
 
 fn round_f64<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
@@ -12,6 +12,9 @@ where
 pub struct ResourceEstimations {
     pub duration: DurationEstimate,
 
+    /// Rows and bytes read from the sources across all pipelines
+    pub volume: VolumeEstimate,
+
     /// Peak memory usage across all pipelines (megabytes)
     pub peak_memory_mb: u64,
 
@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+/// Estimated amount of data a pipeline moves.
+/// Calculated from the migrated row count and the average source row width.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct VolumeEstimate {
+    /// Rows that will be migrated (after filters)
+    pub rows: u64,
+
+    /// Average row width in bytes
+    pub avg_row_bytes: u64,
+
+    /// Whether the row width comes from table statistics rather than the
+    /// column types
+    pub from_statistics: bool,
+
+    /// Total bytes read from the source
+    pub total_bytes: u64,
+
+    /// Human-readable format (e.g., "512 KB", "1.4 GB")
+    pub formatted: String,
+}
+
+impl VolumeEstimate {
+    pub fn new(rows: u64, avg_row_bytes: u64, from_statistics: bool) -> Self {
+        let total_bytes = rows.saturating_mul(avg_row_bytes);
+        Self {
+            rows,
+            avg_row_bytes,
+            from_statistics,
+            total_bytes,
+            formatted: Self::format_bytes(total_bytes),
+        }
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if bytes < 1024 {
+            return format!("{} B", bytes);
+        }
+        let mut value = bytes as f64;
+        let mut unit = UNITS[0];
+        for u in UNITS {
+            value /= 1024.0;
+            unit = u;
+            if value < 1024.0 {
+                break;
+            }
+        }
+        if value < 10.0 {
+            format!("{:.1} {}", value, unit)
+        } else {
+            format!("{:.0} {}", value, unit)
+        }
+    }
+
+    /// Combine multiple estimates (total across pipelines)
+    pub fn combine(estimates: &[VolumeEstimate]) -> Self {
+        let rows: u64 = estimates.iter().map(|e| e.rows).sum();
+        let total_bytes: u64 = estimates.iter().map(|e| e.total_bytes).sum();
+
+        Self {
+            rows,
+            avg_row_bytes: total_bytes.checked_div(rows).unwrap_or(0),
+            from_statistics: !estimates.is_empty() && estimates.iter().all(|e| e.from_statistics),
+            total_bytes,
+            formatted: Self::format_bytes(total_bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_estimate_formatting() {
+        assert_eq!(VolumeEstimate::new(10, 50, true).formatted, "500 B");
+        assert_eq!(VolumeEstimate::new(1_000, 120, true).formatted, "117 KB");
+        assert_eq!(
+            VolumeEstimate::new(2_000_000, 800, false).formatted,
+            "1.5 GB"
+        );
+    }
+
+    #[test]
+    fn test_volume_estimate_combine() {
+        let total = VolumeEstimate::combine(&[
+            VolumeEstimate::new(100, 10, true),
+            VolumeEstimate::new(300, 50, false),
+        ]);
+        assert_eq!(total.rows, 400);
+        assert_eq!(total.total_bytes, 16_000);
+        assert_eq!(total.avg_row_bytes, 40);
+        assert!(!total.from_statistics);
+    }
+}
@@ -11,12 +11,28 @@ pub struct PaginationPlan {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tiebreaker: Option<CursorColumn>,
 
+    /// Whether the tiebreaker was not configured and defaulted to the primary key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiebreaker_inferred: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timezone: Option<String>,
 
+    /// Where rows with a NULL cursor value are read ("first" or "last")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nulls: Option<String>,
+
     /// Whether the cursor column has an index (affects performance)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column_indexed: Option<bool>,
+
+    /// Whether the cursor column accepts NULLs (those rows need their own partition)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_nullable: Option<bool>,
+
+    /// Whether the cursor (plus tiebreaker) identifies rows uniquely; `None` if not checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyset_unique: Option<bool>,
 }
 
 impl Default for PaginationPlan {
@@ -25,8 +41,12 @@ impl Default for PaginationPlan {
             strategy: PaginationStrategy::Default,
             cursor_column: None,
             tiebreaker: None,
+            tiebreaker_inferred: None,
             timezone: None,
+            nulls: None,
             column_indexed: None,
+            column_nullable: None,
+            keyset_unique: None,
         }
     }
 }
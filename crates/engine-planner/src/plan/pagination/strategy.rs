@@ -6,5 +6,6 @@ pub enum PaginationStrategy {
     Timestamp,
     Numeric,
     Pk,
+    ServerCursor,
     Default,
 }
@@ -1,5 +1,10 @@
-use engine_config::settings::{CopyColumns, validated::ValidatedSettings};
-use model::execution::flags::IntegrityMode;
+use engine_config::settings::{
+    CopyColumns, DeferForeignKeys, FastLoad, validated::ValidatedSettings,
+};
+use model::{
+    core::encoding::{ArrayFormat, InvalidUtf8},
+    execution::flags::IntegrityMode,
+};
 use serde::Serialize;
 
 // Helper functions for skip_serializing_if
@@ -7,11 +12,39 @@ fn is_false(b: &bool) -> bool {
     !b
 }
 
+fn is_replace(i: &InvalidUtf8) -> bool {
+    *i == InvalidUtf8::Replace
+}
+
+fn is_json(f: &ArrayFormat) -> bool {
+    *f == ArrayFormat::Json
+}
+
+fn is_comma(d: &str) -> bool {
+    d == ","
+}
+
+fn is_not_deferred(d: &DeferForeignKeys) -> bool {
+    !d.is_deferred()
+}
+
+fn is_off(f: &FastLoad) -> bool {
+    *f == FastLoad::Off
+}
+
 #[derive(Serialize, Debug, Clone)]
 pub struct PipelineSettings {
     pub batch_size: usize,
     pub copy_columns: CopyColumns,
 
+    #[serde(skip_serializing_if = "is_false")]
+    pub materialize_generated_columns: bool,
+    #[serde(skip_serializing_if = "is_replace")]
+    pub invalid_utf8: InvalidUtf8,
+    #[serde(skip_serializing_if = "is_json")]
+    pub array_format: ArrayFormat,
+    #[serde(skip_serializing_if = "is_comma")]
+    pub array_delimiter: String,
     #[serde(skip_serializing_if = "is_false")]
     pub infer_schema: bool,
     #[serde(skip_serializing_if = "is_false")]
@@ -21,6 +54,20 @@ pub struct PipelineSettings {
     #[serde(skip_serializing_if = "is_false")]
     pub ignore_constraints: bool,
     #[serde(skip_serializing_if = "is_false")]
+    pub migrate_indexes: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub sync_sequences: bool,
+    #[serde(skip_serializing_if = "is_not_deferred")]
+    pub defer_foreign_keys: DeferForeignKeys,
+    #[serde(skip_serializing_if = "is_off")]
+    pub fast_load: FastLoad,
+    #[serde(skip_serializing_if = "is_false")]
+    pub defer_indexes: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub migrate_grants: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_identifier_length: Option<usize>,
+    #[serde(skip_serializing_if = "is_false")]
     pub dry_run: bool,
 
     pub workers: usize,
@@ -37,12 +84,23 @@ impl PipelineSettings {
         Self {
             batch_size: settings.batch_size,
             copy_columns: settings.copy_columns,
+            materialize_generated_columns: settings.materialize_generated_columns,
+            invalid_utf8: settings.invalid_utf8,
+            array_format: settings.array_format,
+            array_delimiter: settings.array_delimiter,
             infer_schema: settings.infer_schema,
             create_missing_tables: settings.create_missing_tables,
             create_missing_columns: settings.create_missing_columns,
             ignore_constraints: settings.ignore_constraints,
+            migrate_indexes: settings.migrate_indexes,
+            sync_sequences: settings.sync_sequences,
+            defer_foreign_keys: settings.defer_foreign_keys,
+            fast_load: settings.fast_load,
+            defer_indexes: settings.defer_indexes,
+            migrate_grants: settings.migrate_grants,
+            max_identifier_length: settings.max_identifier_length,
             dry_run: settings.dry_run,
-            workers: 1,
+            workers: settings.parallel_partitions,
             checkpoint: CheckpointStrategy::EveryBatch,
             timeout: None,
             memory_limit_mb: None,
@@ -52,11 +110,23 @@ impl PipelineSettings {
     pub fn as_validated(&self) -> ValidatedSettings {
         ValidatedSettings {
             batch_size: self.batch_size,
+            parallel_partitions: self.workers,
             copy_columns: self.copy_columns,
+            materialize_generated_columns: self.materialize_generated_columns,
+            invalid_utf8: self.invalid_utf8,
+            array_format: self.array_format,
+            array_delimiter: self.array_delimiter.clone(),
             infer_schema: self.infer_schema,
             create_missing_tables: self.create_missing_tables,
             create_missing_columns: self.create_missing_columns,
             ignore_constraints: self.ignore_constraints,
+            migrate_indexes: self.migrate_indexes,
+            sync_sequences: self.sync_sequences,
+            defer_foreign_keys: self.defer_foreign_keys,
+            fast_load: self.fast_load,
+            defer_indexes: self.defer_indexes,
+            migrate_grants: self.migrate_grants,
+            max_identifier_length: self.max_identifier_length,
             dry_run: self.dry_run,
             integrity: IntegrityMode::Off,
         }
@@ -29,6 +29,10 @@ pub struct SourcePlan {
     /// Primary key columns
     pub primary_key: Vec<String>,
 
+    /// Default collation of the table (MySQL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+
     /// Relevant indexes
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub indexes: Vec<IndexInfo>,
@@ -36,6 +40,10 @@ pub struct SourcePlan {
     /// Size of the table in bytes
     pub size_bytes: u64,
 
+    /// Average row width in bytes from the table statistics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_row_bytes: Option<u64>,
+
     /// Last analyzed timestamp (unix epoch ms)
     pub last_analyzed: DateTime<Utc>,
 }
@@ -1,3 +1,4 @@
+use model::core::cast::Lossiness;
 use serde::Serialize;
 
 #[derive(Serialize, Debug, Clone, Default)]
@@ -8,6 +9,10 @@ pub struct SampleStats {
     pub errors: usize,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub validation_stats: Vec<ValidationStats>,
+    /// Destination columns whose sampled values convert with loss or fail
+    /// to convert
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conversions: Vec<ConversionStats>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -26,3 +31,16 @@ pub struct ValidationStats {
     /// Percentage of rows that passed (0.0 to 1.0)
     pub pass_rate: f32,
 }
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ConversionStats {
+    pub column: String,
+    /// Destination column type as the database reports it
+    pub target_type: String,
+    /// Worst loss among the values that converted
+    pub lossiness: Lossiness,
+    /// Values that converted with loss
+    pub lossy: usize,
+    /// Values with no form in the column type
+    pub failed: usize,
+}
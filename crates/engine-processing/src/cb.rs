@@ -1,11 +1,23 @@
+use model::events::migration::CircuitState;
 use std::time::Duration;
 
+/// How long the breaker may stay open before the caller gives up on the
+/// failing system
+const MAX_OPEN: Duration = Duration::from_secs(300);
+
+/// Counts consecutive failures and trips open at a threshold.
+///
+/// While open, callers stop doing work and probe the failing system with
+/// [`CircuitBreaker::probe_delay`] between attempts. A successful probe moves
+/// the breaker to half-open: the next real attempt either closes it
+/// (`record_success`) or opens it again (`record_failure`).
 #[derive(Clone, Debug)]
 pub struct CircuitBreaker {
     threshold: u32,
     consecutive_failures: u32,
     base_delay: Duration,
     max_delay: Duration,
+    state: CircuitState,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -25,6 +37,7 @@ impl CircuitBreaker {
             } else {
                 max_delay
             },
+            state: CircuitState::Closed,
         }
     }
 
@@ -34,7 +47,9 @@ impl CircuitBreaker {
 
     pub fn record_failure(&mut self) -> CircuitBreakerState {
         self.consecutive_failures = self.consecutive_failures.saturating_add(1);
-        if self.consecutive_failures >= self.threshold {
+        // A failed trial write in half-open reopens at once
+        if self.consecutive_failures >= self.threshold || self.state == CircuitState::HalfOpen {
+            self.state = CircuitState::Open;
             CircuitBreakerState::Open
         } else {
             let delay = self.delay_for(self.consecutive_failures);
@@ -44,12 +59,33 @@ impl CircuitBreaker {
 
     pub fn record_success(&mut self) {
         self.consecutive_failures = 0;
+        self.state = CircuitState::Closed;
+    }
+
+    /// A probe succeeded while open; allow one trial attempt.
+    pub fn half_open(&mut self) {
+        if self.state == CircuitState::Open {
+            self.state = CircuitState::HalfOpen;
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.state
     }
 
     pub fn consecutive_failures(&self) -> u32 {
         self.consecutive_failures
     }
 
+    pub fn max_open(&self) -> Duration {
+        MAX_OPEN
+    }
+
+    /// Wait before probe number `probe` (0-based) while the breaker is open.
+    pub fn probe_delay(&self, probe: u32) -> Duration {
+        self.delay_for(probe.saturating_add(1))
+    }
+
     fn delay_for(&self, failures: u32) -> Duration {
         if self.base_delay.is_zero() {
             return Duration::from_millis(0);
@@ -101,4 +137,30 @@ mod tests {
         assert!(matches!(next_state, CircuitBreakerState::Open));
         assert_eq!(breaker.consecutive_failures(), 3);
     }
+
+    #[test]
+    fn half_open_closes_on_success_and_reopens_on_failure() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_millis(1), Duration::from_secs(1));
+        breaker.record_failure();
+        assert_eq!(breaker.record_failure(), CircuitBreakerState::Open);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        breaker.half_open();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(breaker.record_failure(), CircuitBreakerState::Open);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        breaker.half_open();
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[test]
+    fn probe_delay_backs_off_to_max() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(1), Duration::from_secs(5));
+        assert_eq!(breaker.probe_delay(0), Duration::from_secs(1));
+        assert_eq!(breaker.probe_delay(1), Duration::from_secs(2));
+        assert_eq!(breaker.probe_delay(10), Duration::from_secs(5));
+    }
 }
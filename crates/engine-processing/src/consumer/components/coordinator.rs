@@ -1,11 +1,27 @@
 use crate::{
-    consumer::components::writer::BatchWriter, error::ConsumerError, state_manager::StateManager,
+    consumer::components::{
+        write_context::WriteErrorContext,
+        writer::{BatchWriter, WriteResult},
+    },
+    error::ConsumerError,
+    state_manager::StateManager,
+    transform::failed_row_writer::FailedRowWriter,
+};
+use engine_core::{metrics::Metrics, state::models::Checkpoint, timing::BatchTiming};
+use model::{
+    core::value::Value,
+    execution::{
+        failed_row::{FailedRow, ProcessingStage},
+        pipeline::BatchHook,
+    },
+    records::batch::Batch,
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
 };
-use engine_core::{metrics::Metrics, state::models::Checkpoint};
-use engine_state::models::CheckpointStage;
-use model::records::batch::Batch;
 use tokio::sync::mpsc;
-use tracing::debug;
+use tracing::{debug, error, info, warn};
 
 /// Coordinates batch receiving, writing, and checkpointing.
 pub struct BatchCoordinator {
@@ -13,6 +29,18 @@ pub struct BatchCoordinator {
     state_manager: StateManager,
     metrics: Metrics,
     batch_rx: mpsc::Receiver<Batch>,
+    /// Batches received so far; numbers each batch for error reports
+    batch_seq: AtomicU64,
+    /// DLQ for rows a failed write can be traced to, with the pipeline name
+    failed_rows: Option<(FailedRowWriter, String)>,
+    /// Batch whose write failed with a transient error, with its sequence
+    /// number; it is written again before anything new is received
+    redeliver: Option<(u64, Batch)>,
+    /// Where the held batch's last write failed; its row is quarantined only
+    /// once the retries give up
+    redeliver_failure: Option<(Box<WriteErrorContext>, String)>,
+    /// SQL run on the destination every N committed batches
+    batch_hook: Option<BatchHook>,
 }
 
 impl BatchCoordinator {
@@ -27,9 +55,26 @@ impl BatchCoordinator {
             state_manager,
             metrics,
             batch_rx,
+            batch_seq: AtomicU64::new(0),
+            failed_rows: None,
+            redeliver: None,
+            redeliver_failure: None,
+            batch_hook: None,
         }
     }
 
+    /// Record rows that fail to load in the pipeline's failed-rows destination.
+    pub fn with_failed_rows(mut self, writer: FailedRowWriter, pipeline_name: String) -> Self {
+        self.failed_rows = Some((writer, pipeline_name));
+        self
+    }
+
+    /// Run `hook` on the destination after every `hook.every` committed batches.
+    pub fn with_batch_hook(mut self, hook: BatchHook) -> Self {
+        self.batch_hook = Some(hook);
+        self
+    }
+
     pub async fn prepare(&self) -> Result<(), ConsumerError> {
         self.writer.prepare().await
     }
@@ -38,30 +83,77 @@ impl BatchCoordinator {
         self.writer.finalize().await
     }
 
-    /// Try to receive and process one batch.
+    /// Try to receive and process one batch. A batch that failed with a
+    /// transient error is kept and retried on the next call; the row a
+    /// failed write is traced to is quarantined once the failure is final.
     pub async fn try_process_one(&mut self) -> Result<bool, ConsumerError> {
-        match self.batch_rx.try_recv() {
-            Ok(batch) => {
-                self.process_batch(batch).await?;
-                Ok(true)
-            }
-            Err(mpsc::error::TryRecvError::Empty) => Ok(false),
-            Err(mpsc::error::TryRecvError::Disconnected) => {
-                debug!("batch channel disconnected");
-                Ok(false)
+        let (batch_seq, batch) = match self.redeliver.take() {
+            Some(pending) => pending,
+            None => match self.batch_rx.try_recv() {
+                Ok(batch) => (self.batch_seq.fetch_add(1, Ordering::Relaxed) + 1, batch),
+                Err(mpsc::error::TryRecvError::Empty) => return Ok(false),
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    debug!("batch channel disconnected");
+                    return Ok(false);
+                }
+            },
+        };
+
+        self.redeliver_failure = None;
+        match self.process_batch(&batch, batch_seq).await {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                let failure = match &e {
+                    ConsumerError::Write {
+                        context, source, ..
+                    } => Some((context.clone(), source.to_string())),
+                    _ => None,
+                };
+                if e.is_fatal() {
+                    if let Some((context, error)) = failure {
+                        self.record_failed_row(&batch, &context, &error).await;
+                    }
+                } else {
+                    self.redeliver = Some((batch_seq, batch));
+                    self.redeliver_failure = failure;
+                }
+                Err(e)
             }
         }
     }
 
+    /// Check the destination is reachable.
+    pub async fn ping(&self) -> Result<(), ConsumerError> {
+        self.writer.ping().await
+    }
+
+    /// Record in the WAL that the circuit breaker gave up on the destination,
+    /// and quarantine the row the held batch's last write failed on.
+    pub async fn record_circuit_open(&self, failures: u32, last_error: &str) {
+        if let (Some((_, batch)), Some((context, error))) =
+            (&self.redeliver, &self.redeliver_failure)
+        {
+            self.record_failed_row(batch, context, error).await;
+        }
+        if let Err(e) = self
+            .state_manager
+            .record_circuit_open("write", failures, last_error)
+            .await
+        {
+            warn!(error = %e, "failed to record circuit breaker in WAL");
+        }
+    }
+
     pub fn is_channel_closed(&self) -> bool {
         self.batch_rx.is_closed()
     }
 
-    /// Process a single batch: write + checkpoint + metrics.
-    pub async fn process_batch(&self, batch: Batch) -> Result<(), ConsumerError> {
+    /// Process a single batch: begin + write + ack + metrics.
+    pub async fn process_batch(&self, batch: &Batch, batch_seq: u64) -> Result<(), ConsumerError> {
         let batch_id = batch.id.clone();
         let row_count = batch.rows.len();
         let byte_count = batch.size_bytes();
+        let started = Instant::now();
 
         debug!(
             batch_id = %batch_id,
@@ -72,48 +164,50 @@ impl BatchCoordinator {
             "processing batch"
         );
 
-        // Get current progress
-        let current_rows = self.get_progress().await?;
-
-        // Mark as being written
-        self.state_manager
-            .save_checkpoint(
-                &CheckpointStage::Write,
-                &batch.cursor,
-                Some(&batch.next),
-                &batch.id,
-                current_rows,
-            )
-            .await
-            .map_err(|e| ConsumerError::Checkpoint {
-                batch_id: batch.id.clone(),
-                source: e,
-            })?;
+        // Mark as in flight; the cursor only advances once the batch is acked
+        let ack =
+            self.state_manager
+                .begin_batch(batch)
+                .await
+                .map_err(|e| ConsumerError::Checkpoint {
+                    batch_id: batch.id.clone(),
+                    source: e,
+                })?;
 
         // Write to destination with retry
-        let write_result = self.writer.write_batch(&batch).await?;
-
-        let new_rows = current_rows + row_count as u64;
-
-        // Mark as committed
-        self.state_manager
-            .commit_batch(&batch.id)
-            .await
-            .map_err(|e| ConsumerError::Checkpoint {
-                batch_id: batch.id.clone(),
-                source: e,
-            })?;
+        let write_result = match self.write_batch(batch, batch_seq).await {
+            Ok(result) => result,
+            Err(err) => {
+                self.state_manager.abandon_batch(ack).await;
+                if let ConsumerError::Write {
+                    batch_id,
+                    context,
+                    source,
+                } = &err
+                {
+                    error!(
+                        batch_id = %batch_id,
+                        table = %context.table,
+                        batch_seq = context.batch_seq,
+                        column = context.column.as_deref().unwrap_or(""),
+                        row_index = ?context.row_index,
+                        error = %source,
+                        "batch write failed"
+                    );
+                }
+                return Err(err);
+            }
+        };
 
-        self.state_manager
-            .save_checkpoint(
-                &CheckpointStage::Committed,
-                &batch.next,
-                None,
-                &batch.id,
-                new_rows,
-            )
+        // Acknowledge the write so the state manager commits the batch. The
+        // rows are in the destination, so a failed ack stops the pipeline
+        // rather than redelivering them; resume reads the WAL to tell whether
+        // the commit was recorded.
+        let new_rows = self
+            .state_manager
+            .ack_batch(ack)
             .await
-            .map_err(|e| ConsumerError::Checkpoint {
+            .map_err(|e| ConsumerError::Ack {
                 batch_id: batch.id.clone(),
                 source: e,
             })?;
@@ -121,6 +215,14 @@ impl BatchCoordinator {
         self.metrics.increment_records(row_count as u64);
         self.metrics.increment_bytes(byte_count as u64);
         self.metrics.increment_batches(1);
+        self.metrics.record_batch_timing(BatchTiming::new(
+            self.state_manager.ids().part_id(),
+            &batch.cursor,
+            &batch.next,
+            row_count as u64,
+            batch.read_time,
+            started.elapsed(),
+        ));
 
         debug!(
             batch_id = %batch_id,
@@ -131,9 +233,115 @@ impl BatchCoordinator {
             "batch processed"
         );
 
+        self.run_batch_hook(&batch_id, batch_seq).await
+    }
+
+    /// Write `batch`. A batch with values too large for their columns is
+    /// written again once the columns are widened, if the pipeline allows it.
+    async fn write_batch(
+        &self,
+        batch: &Batch,
+        batch_seq: u64,
+    ) -> Result<WriteResult, ConsumerError> {
+        let err = match self.writer.write_batch(batch, batch_seq).await {
+            Err(err @ ConsumerError::Write { .. }) if err.is_overflow() => err,
+            result => return result,
+        };
+
+        let widened = self.writer.widen_columns(batch).await;
+        if widened.is_empty() {
+            return Err(err);
+        }
+        for w in &widened {
+            info!(
+                table = %w.table,
+                column = %w.column,
+                from = %w.from,
+                to = %w.to,
+                "widened destination column"
+            );
+            if let Err(e) = self
+                .state_manager
+                .record_column_widened(&w.table, &w.column, &w.from, &w.to, &w.sql)
+                .await
+            {
+                warn!(error = %e, "failed to record widened column in WAL");
+            }
+        }
+
+        self.writer.write_batch(batch, batch_seq).await
+    }
+
+    /// Run the `on_batch` SQL if this batch completes another interval. The
+    /// batch is already committed, so a failure here is not retried.
+    async fn run_batch_hook(&self, batch_id: &str, batch_seq: u64) -> Result<(), ConsumerError> {
+        let Some(hook) = &self.batch_hook else {
+            return Ok(());
+        };
+        if !batch_seq.is_multiple_of(hook.every) {
+            return Ok(());
+        }
+
+        for sql in &hook.sql {
+            debug!(batch_seq, sql = %sql, "running on_batch hook");
+            self.writer
+                .execute(sql)
+                .await
+                .map_err(|e| ConsumerError::BatchHook {
+                    batch_id: batch_id.to_string(),
+                    source: e,
+                })?;
+        }
+        info!(
+            batch_seq,
+            statements = hook.sql.len(),
+            "on_batch hook completed"
+        );
         Ok(())
     }
 
+    /// Send the row a failed write was traced to, if any, to the DLQ.
+    async fn record_failed_row(&self, batch: &Batch, context: &WriteErrorContext, error: &str) {
+        let Some((writer, pipeline_name)) = &self.failed_rows else {
+            return;
+        };
+        let Some(row) = context.row_index.and_then(|idx| {
+            batch
+                .rows
+                .iter()
+                .filter(|r| r.schema == context.table)
+                .nth(idx)
+                .or_else(|| batch.rows.get(idx))
+        }) else {
+            return;
+        };
+
+        let mut failed_row = FailedRow::new(
+            pipeline_name.clone(),
+            ProcessingStage::Load,
+            row.to_map(),
+            "WriteError".to_string(),
+            error.to_string(),
+        )
+        .with_execution_context(
+            self.state_manager.ids().run_id(),
+            Some(batch.id.clone()),
+            context.row_index,
+        )
+        .with_batch_offset(batch.cursor.clone())
+        .with_table(context.table.clone())
+        .with_error_details(context.to_string())
+        .with_metadata("batch_seq".to_string(), Value::UInt(context.batch_seq));
+        if let Some(column) = &context.column {
+            failed_row =
+                failed_row.with_metadata("column".to_string(), Value::String(column.clone()));
+        }
+
+        if let Err(e) = writer.write(&failed_row).await {
+            warn!(batch_id = %batch.id, error = %e, "failed to write row to DLQ");
+        }
+    }
+
     pub async fn load_last_checkpoint(&self) -> Result<Option<Checkpoint>, ConsumerError> {
         Ok(self.state_manager.load_checkpoint().await?)
     }
@@ -141,13 +349,4 @@ impl BatchCoordinator {
     pub fn rows_processed(&self) -> u64 {
         self.metrics.snapshot().records_processed
     }
-
-    async fn get_progress(&self) -> Result<u64, ConsumerError> {
-        Ok(self
-            .state_manager
-            .load_checkpoint()
-            .await?
-            .map(|cp| cp.rows_done)
-            .unwrap_or(0))
-    }
 }
@@ -0,0 +1,107 @@
+use connectors::sql::metadata::table::TableMetadata;
+
+/// How the destination table `actual` differs from the metadata `expected`
+/// the pipeline was planned against, as far as writes are concerned: dropped
+/// or retyped columns, and new columns an insert must fill. Empty when the
+/// table still takes the pipeline's rows.
+pub fn schema_changes(expected: &TableMetadata, actual: &TableMetadata) -> Vec<String> {
+    if actual.columns.is_empty() {
+        return vec![format!("table '{}' no longer exists", expected.name)];
+    }
+
+    let mut columns: Vec<_> = expected.columns.values().collect();
+    columns.sort_by_key(|c| c.ordinal);
+
+    let mut changes = Vec::new();
+    for column in columns {
+        match actual.column(&column.name) {
+            None => changes.push(format!("column '{}' was dropped", column.name)),
+            Some(now) if !now.data_type.eq_ignore_ascii_case(&column.data_type) => {
+                changes.push(format!(
+                    "column '{}' changed type from {} to {}",
+                    column.name, column.data_type, now.data_type
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mut added: Vec<_> = actual
+        .columns
+        .values()
+        .filter(|c| expected.column(&c.name).is_none())
+        .filter(|c| !c.is_nullable && !c.has_default && !c.is_auto_increment && !c.is_generated)
+        .collect();
+    added.sort_by_key(|c| c.ordinal);
+    for column in added {
+        changes.push(format!(
+            "column '{}' was added as NOT NULL without a default",
+            column.name
+        ));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connectors::sql::metadata::column::ColumnMetadata;
+    use std::collections::HashMap;
+
+    fn table(columns: &[(&str, &str, bool)]) -> TableMetadata {
+        let columns = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (name, data_type, is_nullable))| {
+                let column = ColumnMetadata {
+                    ordinal: i + 1,
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    is_nullable: *is_nullable,
+                    ..Default::default()
+                };
+                (name.to_string(), column)
+            })
+            .collect();
+        TableMetadata {
+            name: "posts".to_string(),
+            schema: None,
+            collation: None,
+            columns,
+            primary_keys: vec!["id".to_string()],
+            foreign_keys: vec![],
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let expected = table(&[("id", "integer", false), ("title", "text", true)]);
+        assert!(schema_changes(&expected, &expected).is_empty());
+
+        // A nullable column added by someone else does not break inserts
+        let widened = table(&[
+            ("id", "integer", false),
+            ("title", "text", true),
+            ("notes", "text", true),
+        ]);
+        assert!(schema_changes(&expected, &widened).is_empty());
+
+        let altered = table(&[("id", "bigint", false), ("slug", "text", false)]);
+        assert_eq!(
+            schema_changes(&expected, &altered),
+            vec![
+                "column 'id' changed type from integer to bigint",
+                "column 'title' was dropped",
+                "column 'slug' was added as NOT NULL without a default",
+            ]
+        );
+
+        assert_eq!(
+            schema_changes(&expected, &table(&[])),
+            vec!["table 'posts' no longer exists"]
+        );
+    }
+}
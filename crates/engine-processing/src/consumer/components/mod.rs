@@ -1,2 +1,5 @@
 pub mod coordinator;
+pub mod drift;
+pub mod widen;
+pub mod write_context;
 pub mod writer;
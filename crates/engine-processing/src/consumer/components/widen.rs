@@ -0,0 +1,255 @@
+//! Widening of destination columns a batch overflows, for pipelines with
+//! `auto_widen_columns`. Only length-limited text columns and fixed
+//! precision numerics are widened, never past what the destination allows.
+
+use crate::io::format::DataFormat;
+use connectors::sql::{
+    metadata::{column::ColumnMetadata, table::TableMetadata},
+    query::column::ColumnDef,
+};
+use model::{
+    core::{types::Type, value::Value},
+    records::Record,
+};
+
+/// A destination column and the type it is widened to.
+#[derive(Debug, Clone)]
+pub struct ColumnWidening {
+    pub from: Type,
+    /// The column restated with its new type
+    pub column: ColumnDef,
+}
+
+/// A column widened on the destination, as recorded in the WAL.
+#[derive(Debug, Clone)]
+pub struct WidenedColumn {
+    pub table: String,
+    pub column: String,
+    pub from: String,
+    pub to: String,
+    pub sql: String,
+}
+
+/// The columns of `meta` that some value of `rows` does not fit in, with
+/// the types that fit every value. Text columns grow to the next power of
+/// two; numerics gain the integer digits they lack and keep their scale.
+pub fn widenings(meta: &TableMetadata, rows: &[Record], format: DataFormat) -> Vec<ColumnWidening> {
+    let mut columns: Vec<_> = meta.columns.values().collect();
+    columns.sort_by_key(|c| c.ordinal);
+
+    columns
+        .into_iter()
+        .filter_map(|column| {
+            let values = rows
+                .iter()
+                .filter_map(|row| row.get(&column.name)?.value.as_ref());
+            let (from, to) = widened_type(column, values, format)?;
+            Some(ColumnWidening {
+                from,
+                column: column_def(column, to),
+            })
+        })
+        .collect()
+}
+
+fn widened_type<'a>(
+    column: &ColumnMetadata,
+    values: impl Iterator<Item = &'a Value>,
+    format: DataFormat,
+) -> Option<(Type, Type)> {
+    let (varchar_limit, char_limit, precision_limit) = match format {
+        DataFormat::Postgres => (10_485_760, 10_485_760, 1000),
+        // Characters of a utf8mb4 column that fit MySQL's 65535 byte row
+        DataFormat::MySql => (16_383, 255, 65),
+        _ => return None,
+    };
+
+    match column.data_type.to_ascii_lowercase().as_str() {
+        kind @ ("character varying" | "varchar" | "character" | "char" | "bpchar") => {
+            let length = column.char_max_length?;
+            let needed = values
+                .filter_map(|v| match v {
+                    Value::String(s) => Some(s.chars().count()),
+                    _ => None,
+                })
+                .max()?;
+            let is_char = !matches!(kind, "character varying" | "varchar");
+            let limit = if is_char { char_limit } else { varchar_limit };
+            if needed <= length || needed > limit {
+                return None;
+            }
+
+            let widened = needed.next_power_of_two().min(limit);
+            let text = |length| match is_char {
+                true => Type::Char {
+                    length: Some(length),
+                    charset: None,
+                },
+                false => Type::Varchar {
+                    length: Some(length),
+                    charset: None,
+                },
+            };
+            Some((text(length), text(widened)))
+        }
+        "numeric" | "decimal" => {
+            let precision = column.num_precision?;
+            let scale = column.num_scale.unwrap_or(0);
+            let needed = values.filter_map(integer_digits).max()? + scale;
+            if needed <= precision || needed > precision_limit {
+                return None;
+            }
+            let (precision, needed, scale) = (
+                u8::try_from(precision).ok()?,
+                u8::try_from(needed).ok()?,
+                u8::try_from(scale).ok()?,
+            );
+
+            let decimal = |precision| Type::Decimal {
+                precision: Some(precision),
+                scale: Some(scale),
+            };
+            Some((decimal(precision), decimal(needed)))
+        }
+        _ => None,
+    }
+}
+
+/// Digits left of the decimal point; none for values below one.
+fn integer_digits(value: &Value) -> Option<u32> {
+    let digits = match value {
+        Value::Int(n) => n.unsigned_abs().to_string(),
+        Value::UInt(n) => n.to_string(),
+        Value::Decimal(d) => d.abs().with_scale(0).to_string(),
+        Value::Float(f) if f.is_finite() => format!("{:.0}", f.abs().trunc()),
+        _ => return None,
+    };
+    Some(match digits.as_str() {
+        "0" => 0,
+        digits => digits.len() as u32,
+    })
+}
+
+fn column_def(column: &ColumnMetadata, data_type: Type) -> ColumnDef {
+    // Restated defaults must be SQL; introspected text defaults may be bare
+    let default = column.default_value.as_ref().map(|d| match &data_type {
+        Type::Char { .. } | Type::Varchar { .. } if !d.starts_with('\'') => {
+            format!("'{}'", d.replace('\'', "''"))
+        }
+        _ => d.clone(),
+    });
+
+    ColumnDef {
+        name: column.name.clone(),
+        data_type,
+        is_nullable: column.is_nullable,
+        is_primary_key: column.is_primary_key,
+        default,
+        char_max_length: None,
+        generated_expression: None,
+        is_stored: false,
+        is_generated: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use model::{core::value::FieldValue, records::OpType};
+    use std::{collections::HashMap, str::FromStr};
+
+    fn posts() -> TableMetadata {
+        let column = |name: &str, data_type: &str, ordinal: usize| ColumnMetadata {
+            ordinal,
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            ..Default::default()
+        };
+        let columns = [
+            ColumnMetadata {
+                char_max_length: Some(8),
+                ..column("title", "character varying", 1)
+            },
+            ColumnMetadata {
+                num_precision: Some(5),
+                num_scale: Some(2),
+                ..column("price", "numeric", 2)
+            },
+            column("body", "text", 3),
+        ];
+        TableMetadata {
+            name: "posts".to_string(),
+            schema: None,
+            collation: None,
+            columns: columns.into_iter().map(|c| (c.name.clone(), c)).collect(),
+            primary_keys: vec![],
+            foreign_keys: vec![],
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    fn row(title: &str, price: &str) -> Record {
+        let fields = [
+            ("title", Value::String(title.to_string())),
+            (
+                "price",
+                Value::Decimal(BigDecimal::from_str(price).unwrap()),
+            ),
+            ("body", Value::String(title.repeat(100))),
+        ]
+        .into_iter()
+        .map(|(name, value)| FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        })
+        .collect();
+        Record::new("posts", fields, OpType::Insert)
+    }
+
+    #[test]
+    fn test_widenings() {
+        let fits = [row("short", "999.99")];
+        assert!(widenings(&posts(), &fits, DataFormat::Postgres).is_empty());
+
+        let rows = [row("a much longer title", "12.5"), row("x", "-12345.6")];
+        let widened = widenings(&posts(), &rows, DataFormat::Postgres);
+        let types: Vec<_> = widened
+            .iter()
+            .map(|w| (w.column.name.as_str(), &w.from, &w.column.data_type))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                (
+                    "title",
+                    &Type::Varchar {
+                        length: Some(8),
+                        charset: None
+                    },
+                    &Type::Varchar {
+                        length: Some(32),
+                        charset: None
+                    }
+                ),
+                (
+                    "price",
+                    &Type::Decimal {
+                        precision: Some(5),
+                        scale: Some(2)
+                    },
+                    &Type::Decimal {
+                        precision: Some(7),
+                        scale: Some(2)
+                    }
+                ),
+            ]
+        );
+
+        // Never past what the destination allows
+        let huge = [row(&"x".repeat(20_000), "1")];
+        assert!(widenings(&posts(), &huge, DataFormat::MySql).is_empty());
+    }
+}
@@ -0,0 +1,353 @@
+//! Locates the row behind a failed destination write.
+//!
+//! Drivers report constraint violations as text - Postgres in the SQLSTATE
+//! detail (`Key (id)=(5) already exists.`), MySQL in the server message
+//! (`Column 'email' cannot be null`). The column and values named there are
+//! matched back to a row of the batch so the failure can be traced to it.
+
+use crate::io::error::SinkError;
+use connectors::{
+    error::{DbError, DriverError},
+    sql::metadata::table::TableMetadata,
+};
+use model::{core::value::Value, records::Record};
+use std::fmt;
+
+/// Column names whose values are never shown in a row snapshot
+const SENSITIVE_PATTERNS: [&str; 10] = [
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "key",
+    "auth",
+    "credential",
+    "salt",
+    "ssn",
+    "card",
+];
+
+/// Longest value shown in a row snapshot before it's truncated
+const MAX_VALUE_LEN: usize = 64;
+
+/// Where a failed write happened and, when the error names it, which row and
+/// column caused it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteErrorContext {
+    pub table: String,
+    /// 1-based position of the batch within this consumer's run
+    pub batch_seq: u64,
+    pub column: Option<String>,
+    /// Index of the offending row within the batch rows written to `table`
+    pub row_index: Option<usize>,
+    /// Offending row with sensitive and long values masked
+    pub row: Option<Vec<(String, String)>>,
+}
+
+impl WriteErrorContext {
+    pub fn new(meta: &TableMetadata, batch_seq: u64, rows: &[Record], error: &SinkError) -> Self {
+        let offender = Offender::parse(&error_text(error), meta);
+        let column = offender.as_ref().and_then(Offender::column);
+        let row_index = offender.as_ref().and_then(|o| o.locate(rows));
+
+        Self {
+            table: meta.name.clone(),
+            batch_seq,
+            column,
+            row_index,
+            row: row_index.and_then(|idx| rows.get(idx)).map(masked_snapshot),
+        }
+    }
+}
+
+impl fmt::Display for WriteErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "table '{}', batch #{}", self.table, self.batch_seq)?;
+        if let Some(column) = &self.column {
+            write!(f, ", column '{column}'")?;
+        }
+        if let Some(row) = &self.row {
+            let fields = row
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>();
+            write!(f, ", row {{{}}}", fields.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// What the database error says about the offending row.
+#[derive(Debug, Clone, PartialEq)]
+enum Offender {
+    /// Unique or foreign key violation with the key's columns and values
+    Key {
+        columns: Vec<String>,
+        values: Vec<String>,
+    },
+    /// NOT NULL violation
+    Null(String),
+    /// Bad value in a column; MySQL also gives the 1-based row in the statement
+    Column { column: String, row: Option<usize> },
+}
+
+impl Offender {
+    fn parse(text: &str, meta: &TableMetadata) -> Option<Self> {
+        // Errors often arrive as Debug output with escaped quotes
+        let text = text.replace("\\\"", "\"");
+
+        if let Some((columns, values)) =
+            between(&text, "Key (", ")=(").zip(between(&text, ")=(", ")"))
+        {
+            return Some(Self::Key {
+                columns: split_list(columns),
+                values: split_list(values),
+            });
+        }
+
+        if let Some(column) = between(&text, "null value in column \"", "\"")
+            .or_else(|| between(&text, "Column '", "' cannot be null"))
+        {
+            return Some(Self::Null(column.to_string()));
+        }
+
+        if let Some(column) = between(&text, "for column '", "'") {
+            let row = between(&text, " at row ", "")
+                .map(|rest| {
+                    rest.split(|c: char| !c.is_ascii_digit())
+                        .next()
+                        .unwrap_or("")
+                })
+                .and_then(|n| n.parse().ok());
+            return Some(Self::Column {
+                column: column.to_string(),
+                row,
+            });
+        }
+
+        if let Some((value, key)) = between(&text, "Duplicate entry '", "' for key '").zip(between(
+            &text,
+            "' for key '",
+            "'",
+        )) {
+            // MySQL 8 qualifies the key with the table name
+            let key = key.rsplit('.').next().unwrap_or(key);
+            let column = if key.eq_ignore_ascii_case("PRIMARY") && meta.primary_keys.len() == 1 {
+                meta.primary_keys[0].clone()
+            } else if meta.columns.contains_key(key) {
+                key.to_string()
+            } else {
+                return None;
+            };
+            return Some(Self::Key {
+                columns: vec![column],
+                values: vec![value.to_string()],
+            });
+        }
+
+        between(&text, "column: Some(\"", "\")").map(|column| Self::Column {
+            column: column.to_string(),
+            row: None,
+        })
+    }
+
+    fn column(&self) -> Option<String> {
+        match self {
+            Self::Key { columns, .. } => Some(columns.join(", ")),
+            Self::Null(column) | Self::Column { column, .. } => Some(column.clone()),
+        }
+    }
+
+    fn locate(&self, rows: &[Record]) -> Option<usize> {
+        match self {
+            Self::Key { columns, values } if columns.len() == values.len() => {
+                rows.iter().position(|row| {
+                    columns
+                        .iter()
+                        .zip(values)
+                        .all(|(column, value)| field_text(row, column).as_deref() == Some(value))
+                })
+            }
+            Self::Key { .. } => None,
+            Self::Null(column) => rows
+                .iter()
+                .position(|row| field_text(row, column).is_none()),
+            Self::Column { row, .. } => row.and_then(|n| n.checked_sub(1)),
+        }
+    }
+}
+
+/// Text of the error, preferring the structured Postgres fields when the
+/// driver kept them.
+fn error_text(error: &SinkError) -> String {
+    let pg = match error {
+        SinkError::TokioPostgres(e)
+        | SinkError::Db(DbError::PgError(e))
+        | SinkError::Driver(DriverError::PgError(e))
+        | SinkError::Driver(DriverError::DatabaseError(DbError::PgError(e))) => e.as_db_error(),
+        _ => None,
+    };
+
+    match pg {
+        Some(db) => format!(
+            "{} {} column: Some(\"{}\")",
+            db.message(),
+            db.detail().unwrap_or_default(),
+            db.column().unwrap_or_default()
+        ),
+        None => error.to_string(),
+    }
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &text[text.find(start)? + start.len()..];
+    if end.is_empty() {
+        return Some(rest);
+    }
+    let value = &rest[..rest.find(end)?];
+    (!value.is_empty()).then_some(value)
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(", ").map(|s| s.trim().to_string()).collect()
+}
+
+fn field_text(row: &Record, column: &str) -> Option<String> {
+    row.fields
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case(column))
+        .and_then(|f| f.value.as_ref())
+        .and_then(Value::as_string)
+}
+
+fn masked_snapshot(row: &Record) -> Vec<(String, String)> {
+    row.fields
+        .iter()
+        .map(|field| {
+            let value = match &field.value {
+                None | Some(Value::Null) => "NULL".to_string(),
+                Some(_) if is_sensitive(&field.name) => "***".to_string(),
+                Some(value) => truncate(value.as_string().unwrap_or_default()),
+            };
+            (field.name.clone(), value)
+        })
+        .collect()
+}
+
+fn is_sensitive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SENSITIVE_PATTERNS.iter().any(|p| name.contains(p))
+}
+
+fn truncate(value: String) -> String {
+    match value.char_indices().nth(MAX_VALUE_LEN) {
+        Some((idx, _)) => format!("{}...", &value[..idx]),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{
+        core::{types::Type, value::FieldValue},
+        records::OpType,
+    };
+    use std::collections::HashMap;
+
+    fn meta() -> TableMetadata {
+        TableMetadata {
+            name: "users".to_string(),
+            schema: None,
+            collation: None,
+            columns: HashMap::new(),
+            primary_keys: vec!["id".to_string()],
+            foreign_keys: Vec::new(),
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    fn row(id: i64, email: Option<&str>, password: &str) -> Record {
+        let field = |name: &str, value: Option<Value>| FieldValue {
+            name: name.to_string(),
+            value,
+            data_type: Type::Varchar {
+                length: None,
+                charset: None,
+            },
+        };
+        Record::new(
+            "users",
+            vec![
+                field("id", Some(Value::Int(id))),
+                field("email", email.map(|e| Value::String(e.to_string()))),
+                field("password", Some(Value::String(password.to_string()))),
+            ],
+            OpType::default(),
+        )
+    }
+
+    fn context(error: &str, rows: &[Record]) -> WriteErrorContext {
+        let error = SinkError::Driver(DriverError::QueryError(error.to_string()));
+        WriteErrorContext::new(&meta(), 3, rows, &error)
+    }
+
+    #[test]
+    fn test_locates_postgres_unique_violation() {
+        let rows = vec![
+            row(4, Some("a@x.io"), "hunter2"),
+            row(5, Some("b@x.io"), "pw"),
+        ];
+        let ctx = context(
+            r#"Error { kind: Db, cause: Some(DbError { code: SqlState(E23505), message: "duplicate key value violates unique constraint \"users_pkey\"", detail: Some("Key (id)=(5) already exists."), column: None }) }"#,
+            &rows,
+        );
+
+        assert_eq!(ctx.column.as_deref(), Some("id"));
+        assert_eq!(ctx.row_index, Some(1));
+        assert_eq!(
+            ctx.to_string(),
+            "table 'users', batch #3, column 'id', row {id=5, email=b@x.io, password=***}"
+        );
+    }
+
+    #[test]
+    fn test_locates_not_null_violation() {
+        let rows = vec![row(1, Some("a@x.io"), "pw"), row(2, None, "pw")];
+
+        let pg = context(
+            r#"DbError { message: "null value in column \"email\" of relation \"users\" violates not-null constraint" }"#,
+            &rows,
+        );
+        let mysql = context(
+            r#"Server(ServerError { code: 1048, message: "Column 'email' cannot be null", state: "23000" })"#,
+            &rows,
+        );
+
+        for ctx in [pg, mysql] {
+            assert_eq!(ctx.column.as_deref(), Some("email"));
+            assert_eq!(ctx.row_index, Some(1));
+        }
+    }
+
+    #[test]
+    fn test_locates_mysql_errors() {
+        let rows = vec![row(1, Some("a@x.io"), "pw"), row(2, Some("b@x.io"), "pw")];
+
+        let ctx = context("Duplicate entry '1' for key 'users.PRIMARY'", &rows);
+        assert_eq!(ctx.column.as_deref(), Some("id"));
+        assert_eq!(ctx.row_index, Some(0));
+
+        let ctx = context("Data too long for column 'email' at row 2", &rows);
+        assert_eq!(ctx.column.as_deref(), Some("email"));
+        assert_eq!(ctx.row_index, Some(1));
+    }
+
+    #[test]
+    fn test_unparseable_error_keeps_table_and_batch() {
+        let ctx = context("connection reset by peer", &[row(1, None, "pw")]);
+        assert_eq!(ctx.to_string(), "table 'users', batch #3");
+        assert!(ctx.row.is_none());
+    }
+}
@@ -1,14 +1,27 @@
 use crate::io::destination::Destination;
 use crate::{
+    consumer::components::{
+        drift::schema_changes,
+        widen::{WidenedColumn, widenings},
+        write_context::WriteErrorContext,
+    },
     error::ConsumerError,
     io::error::SinkError,
-    retry::{classify_driver_error, classify_sink_error},
+    retry::{classify_driver_error, classify_sink_error, is_schema_error},
+};
+use connectors::{
+    audit,
+    error::DriverError,
+    sql::{
+        metadata::{column::ColumnMetadata, table::TableMetadata},
+        query::generator::QueryGenerator,
+    },
 };
-use connectors::sql::metadata::table::TableMetadata;
 use engine_core::retry::RetryPolicy;
+use model::core::types::Type;
 use model::records::Record;
 use model::records::batch::Batch;
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, sync::Mutex};
 use tracing::{debug, trace, warn};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -32,6 +45,10 @@ pub struct BatchWriter {
     retry: RetryPolicy,
     strategy: WriteStrategy,
     meta: Vec<TableMetadata>,
+    /// Widen columns a batch overflows (`auto_widen_columns`)
+    auto_widen: bool,
+    /// Columns widened so far, keyed by table and column
+    widened: Mutex<HashMap<(String, String), ColumnMetadata>>,
 }
 
 impl BatchWriter {
@@ -41,9 +58,17 @@ impl BatchWriter {
             retry,
             strategy: WriteStrategy::Regular, // Default to regular
             meta: meta.to_owned(),
+            auto_widen: false,
+            widened: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Widen destination columns that a batch's values do not fit in.
+    pub fn with_auto_widen(mut self, auto_widen: bool) -> Self {
+        self.auto_widen = auto_widen;
+        self
+    }
+
     /// Create a writer with explicit strategy.
     pub fn with_strategy(mut self, strategy: WriteStrategy) -> Self {
         self.strategy = strategy;
@@ -69,12 +94,20 @@ impl BatchWriter {
         self
     }
 
-    /// Write a batch using the configured strategy.
-    pub async fn write_batch(&self, batch: &Batch) -> Result<WriteResult, ConsumerError> {
-        match self.strategy {
-            WriteStrategy::FastPath => self.write_batch_fast(batch).await,
-            WriteStrategy::Regular => self.write_batch_regular(batch).await,
-        }
+    /// Write a batch using the configured strategy. `batch_seq` is the batch's
+    /// position in the run, reported in the error context of a failed write.
+    pub async fn write_batch(
+        &self,
+        batch: &Batch,
+        batch_seq: u64,
+    ) -> Result<WriteResult, ConsumerError> {
+        audit::in_batch(&batch.id, async {
+            match self.strategy {
+                WriteStrategy::FastPath => self.write_batch_fast(batch, batch_seq).await,
+                WriteStrategy::Regular => self.write_batch_regular(batch, batch_seq).await,
+            }
+        })
+        .await
     }
 
     /// Run the destination sink's one-time setup before the first batch.
@@ -93,6 +126,78 @@ impl BatchWriter {
             .map_err(|e| ConsumerError::Sink(SinkError::Driver(e)))
     }
 
+    /// Check the destination is reachable, e.g. while the circuit breaker is open.
+    pub async fn ping(&self) -> Result<(), ConsumerError> {
+        self.destination
+            .ping()
+            .await
+            .map_err(|e| ConsumerError::Sink(SinkError::Driver(e)))
+    }
+
+    /// Run a statement on the destination, outside the write transaction.
+    pub async fn execute(&self, sql: &str) -> Result<(), DriverError> {
+        self.destination.execute(sql).await
+    }
+
+    /// Widen the destination columns that values of `batch` do not fit in,
+    /// when `auto_widen_columns` is on. A column whose `ALTER` fails is left
+    /// as it is, so the batch fails the way it did before.
+    pub async fn widen_columns(&self, batch: &Batch) -> Vec<WidenedColumn> {
+        let Some(dialect) = self.destination.dialect().filter(|_| self.auto_widen) else {
+            return vec![];
+        };
+        let generator = QueryGenerator::new(dialect.as_ref());
+
+        let mut widened = Vec::new();
+        for (meta, rows) in self.group_rows(&batch.rows) {
+            for widening in widenings(&meta, &rows, self.destination.format) {
+                let column = &widening.column;
+                let (sql, _) = generator.alter_column_type(&meta.name, column);
+                if let Err(e) = self.destination.execute(&sql).await {
+                    warn!(table = %meta.name, column = %column.name, error = %e, "failed to widen column");
+                    continue;
+                }
+
+                if let Some(current) = meta.column(&column.name) {
+                    let mut current = current.clone();
+                    match column.data_type {
+                        Type::Char { length, .. } | Type::Varchar { length, .. } => {
+                            current.char_max_length = length
+                        }
+                        Type::Decimal { precision, .. } => {
+                            current.num_precision = precision.map(u32::from)
+                        }
+                        _ => {}
+                    }
+                    self.widened
+                        .lock()
+                        .unwrap()
+                        .insert((meta.name.clone(), column.name.clone()), current);
+                }
+                widened.push(WidenedColumn {
+                    table: meta.name.clone(),
+                    column: column.name.clone(),
+                    from: dialect.render_data_type(&widening.from, None),
+                    to: dialect.render_data_type(&column.data_type, None),
+                    sql,
+                });
+            }
+        }
+        widened
+    }
+
+    /// `meta` with the columns widened during the run at their new width.
+    fn widened_meta<'a>(&self, meta: &'a TableMetadata) -> Cow<'a, TableMetadata> {
+        let widened = self.widened.lock().unwrap();
+        let mut meta = Cow::Borrowed(meta);
+        for ((table, name), column) in widened.iter() {
+            if *table == meta.name {
+                meta.to_mut().columns.insert(name.clone(), column.clone());
+            }
+        }
+        meta
+    }
+
     /// Get current write strategy.
     pub fn strategy(&self) -> WriteStrategy {
         self.strategy
@@ -111,7 +216,11 @@ impl BatchWriter {
     }
 
     /// Write batch using fast path (sink: COPY, MERGE, etc.).
-    async fn write_batch_fast(&self, batch: &Batch) -> Result<WriteResult, ConsumerError> {
+    async fn write_batch_fast(
+        &self,
+        batch: &Batch,
+        batch_seq: u64,
+    ) -> Result<WriteResult, ConsumerError> {
         let start = std::time::Instant::now();
 
         trace!(
@@ -132,7 +241,8 @@ impl BatchWriter {
 
         let mut rows_written = 0;
         for (meta, rows) in self.group_rows(&batch.rows) {
-            self.retry
+            let written = self
+                .retry
                 .run(
                     || {
                         let sink = self.destination.sink().clone();
@@ -142,11 +252,13 @@ impl BatchWriter {
                     },
                     classify_sink_error,
                 )
-                .await
-                .map_err(|e| ConsumerError::Write {
-                    batch_id: batch.id.clone(),
-                    source: e.into_inner(),
-                })?;
+                .await;
+            if let Err(e) = written {
+                let source = e.into_inner();
+                return Err(self
+                    .write_failed(batch, batch_seq, &meta, &rows, source)
+                    .await);
+            }
             rows_written += rows.len();
         }
 
@@ -170,7 +282,11 @@ impl BatchWriter {
     }
 
     /// Write batch using regular path (INSERT statements).
-    async fn write_batch_regular(&self, batch: &Batch) -> Result<WriteResult, ConsumerError> {
+    async fn write_batch_regular(
+        &self,
+        batch: &Batch,
+        batch_seq: u64,
+    ) -> Result<WriteResult, ConsumerError> {
         let start = std::time::Instant::now();
 
         trace!(
@@ -191,7 +307,8 @@ impl BatchWriter {
 
         let mut rows_written = 0;
         for (meta, rows) in self.group_rows(&batch.rows) {
-            self.retry
+            let written = self
+                .retry
                 .run(
                     || {
                         let sink = self.destination.sink().clone();
@@ -201,11 +318,13 @@ impl BatchWriter {
                     },
                     classify_driver_error,
                 )
-                .await
-                .map_err(|e| ConsumerError::Write {
-                    batch_id: batch.id.clone(),
-                    source: SinkError::Driver(e.into_inner()),
-                })?;
+                .await;
+            if let Err(e) = written {
+                let source = SinkError::Driver(e.into_inner());
+                return Err(self
+                    .write_failed(batch, batch_seq, &meta, &rows, source)
+                    .await);
+            }
             rows_written += rows.len();
         }
 
@@ -228,12 +347,48 @@ impl BatchWriter {
         })
     }
 
-    /// Group rows by their `schema` field and match to the corresponding TableMetadata.
+    /// The error for a failed write. An error that hints at a changed table
+    /// has the table introspected again, so a table altered since the run
+    /// started is reported as drift rather than as bad rows.
+    async fn write_failed(
+        &self,
+        batch: &Batch,
+        batch_seq: u64,
+        meta: &TableMetadata,
+        rows: &[Record],
+        source: SinkError,
+    ) -> ConsumerError {
+        if !is_schema_error(&source) {
+            return write_error(batch, batch_seq, meta, rows, source);
+        }
+
+        let changes = match self.destination.table_metadata(&meta.name).await {
+            Ok(Some(actual)) => schema_changes(meta, &actual),
+            Ok(None) => vec![],
+            Err(e) => {
+                warn!(table = %meta.name, error = %e, "failed to check the destination table for drift");
+                vec![]
+            }
+        };
+        if changes.is_empty() {
+            return write_error(batch, batch_seq, meta, rows, source);
+        }
+
+        warn!(table = %meta.name, changes = ?changes, "destination table changed during the run");
+        ConsumerError::SchemaDrift {
+            table: meta.name.clone(),
+            changes,
+            source,
+        }
+    }
+
+    /// Group rows by their `schema` field and match to the corresponding TableMetadata,
+    /// with any columns widened during the run at their new width.
     /// Falls back to `self.meta[0]` for rows whose schema has no explicit metadata entry.
-    fn group_rows<'a>(&'a self, rows: &'a [Record]) -> Vec<(&'a TableMetadata, Vec<Record>)> {
+    fn group_rows<'a>(&'a self, rows: &'a [Record]) -> Vec<(Cow<'a, TableMetadata>, Vec<Record>)> {
         if self.meta.len() == 1 {
             // Fast path: single table, no grouping needed
-            return vec![(&self.meta[0], rows.to_vec())];
+            return vec![(self.widened_meta(&self.meta[0]), rows.to_vec())];
         }
 
         // Build a lookup from table name -> metadata index
@@ -252,7 +407,7 @@ impl BatchWriter {
 
         let mut result: Vec<_> = groups
             .into_iter()
-            .map(|(idx, rows)| (&self.meta[idx], rows))
+            .map(|(idx, rows)| (self.widened_meta(&self.meta[idx]), rows))
             .collect();
 
         // Preserve insertion order for determinism
@@ -267,3 +422,17 @@ impl BatchWriter {
         result
     }
 }
+
+fn write_error(
+    batch: &Batch,
+    batch_seq: u64,
+    meta: &TableMetadata,
+    rows: &[Record],
+    source: SinkError,
+) -> ConsumerError {
+    ConsumerError::Write {
+        batch_id: batch.id.clone(),
+        context: Box::new(WriteErrorContext::new(meta, batch_seq, rows, &source)),
+        source,
+    }
+}
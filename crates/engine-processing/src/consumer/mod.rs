@@ -4,12 +4,13 @@ use crate::{
     error::ConsumerError,
     item::ItemId,
     state_manager::StateManager,
+    transform::failed_row_writer::FailedRowWriter,
 };
 use connectors::sql::metadata::table::TableMetadata;
-use engine_core::{metrics::Metrics, retry::RetryPolicy};
+use engine_core::{metrics::Metrics, plan::settings::effective_value, retry::RetryPolicy};
 use engine_infra::shutdown::ShutdownSignal;
 use engine_state::models::CheckpointStage;
-use model::records::batch::Batch;
+use model::{core::value::Value, records::batch::Batch};
 use tokio::sync::mpsc;
 use tracing::{debug, error, warn};
 
@@ -67,7 +68,7 @@ impl Consumer {
         let pipeline = ctx.pipeline.clone();
         let state_store = ctx.state.clone();
 
-        let part_id = "part-0".to_string();
+        let part_id = ctx.part_id.clone();
         let ids = ItemId::new(run_id, item_id, part_id);
 
         let meta = dest_metadata;
@@ -79,11 +80,30 @@ impl Consumer {
             .and_then(|eh| eh.retry.as_ref());
         let retry_policy = RetryPolicy::from_config(retry_config);
 
+        let auto_widen = matches!(
+            effective_value(&pipeline.settings, "auto_widen_columns"),
+            Ok(Value::Boolean(true))
+        );
+
         let writer = BatchWriter::new(destination.clone(), retry_policy, &meta)
+            .with_auto_widen(auto_widen)
             .auto_detect_strategy() // Detects fast path (COPY/MERGE) availability
             .await;
         let state_manager = StateManager::new(ids.clone(), state_store);
-        let coordinator = BatchCoordinator::new(writer, state_manager, metrics.clone(), batch_rx);
+        let mut coordinator =
+            BatchCoordinator::new(writer, state_manager, metrics.clone(), batch_rx);
+        if let Some(dest) = pipeline
+            .error_handling
+            .as_ref()
+            .and_then(|eh| eh.failed_rows.as_ref())
+            .and_then(|fr| fr.destination.as_ref())
+        {
+            let failed_rows = FailedRowWriter::new(dest.clone(), ctx.exec_ctx.clone());
+            coordinator = coordinator.with_failed_rows(failed_rows, pipeline.name.clone());
+        }
+        if let Some(hook) = pipeline.lifecycle.as_ref().and_then(|l| l.on_batch.clone()) {
+            coordinator = coordinator.with_batch_hook(hook);
+        }
 
         Self {
             coordinator,
@@ -191,8 +211,11 @@ impl Consumer {
                     }
                     Err(e) => {
                         error!(error = %e, "failed to process batch");
-                        // On error, enter flushing mode to clean up
-                        self.mode = ConsumerMode::Flushing;
+                        // A transient failure keeps the batch for redelivery;
+                        // anything else enters flushing mode to clean up
+                        if e.is_fatal() {
+                            self.mode = ConsumerMode::Flushing;
+                        }
                         Err(e)
                     }
                 }
@@ -224,6 +247,19 @@ impl Consumer {
         Ok(())
     }
 
+    /// Check the destination is reachable.
+    pub async fn ping_destination(&self) -> Result<(), ConsumerError> {
+        self.coordinator.ping().await
+    }
+
+    /// Record in the WAL that the circuit breaker gave up on the destination,
+    /// so progress reports the item as failed.
+    pub async fn record_circuit_open(&self, failures: u32, last_error: &str) {
+        self.coordinator
+            .record_circuit_open(failures, last_error)
+            .await
+    }
+
     pub fn rows_written(&self) -> u64 {
         self.coordinator.rows_processed()
     }
@@ -1,5 +1,5 @@
 use crate::io::{destination::Destination, source::Source};
-use engine_core::{context::exec::ExecutionContext, state::sled_store::SledStateStore};
+use engine_core::{context::exec::ExecutionContext, state::SharedStateStore};
 use engine_wasm::registry::PluginRegistry;
 use model::{
     execution::pipeline::Pipeline, pagination::cursor::Cursor,
@@ -13,11 +13,14 @@ pub struct PipelineContext {
     pub exec_ctx: Arc<ExecutionContext>,
     pub run_id: String,
     pub item_id: String,
+    /// Part of the item read by this context's producer; `part-0` unless
+    /// the load is partitioned.
+    pub part_id: String,
     pub source: Source,
     pub destination: Destination,
     pub pipeline: Pipeline,
     pub mapping: TransformationMetadata,
-    pub state: Arc<SledStateStore>,
+    pub state: SharedStateStore,
     pub offset_strategy: Arc<dyn OffsetStrategy>,
     pub cursor: Cursor,
     pub plugin_registry: Arc<PluginRegistry>,
@@ -27,17 +30,43 @@ impl PipelineContext {
     pub fn builder(exec_ctx: Arc<ExecutionContext>) -> PipelineContextBuilder {
         PipelineContextBuilder::new(exec_ctx)
     }
+
+    /// Context for one key range of a partitioned load, reading through
+    /// `source` from `cursor`.
+    pub fn for_partition(
+        &self,
+        part_id: String,
+        source: Source,
+        offset_strategy: Arc<dyn OffsetStrategy>,
+        cursor: Cursor,
+    ) -> PipelineContext {
+        PipelineContext {
+            exec_ctx: self.exec_ctx.clone(),
+            run_id: self.run_id.clone(),
+            item_id: self.item_id.clone(),
+            part_id,
+            source,
+            destination: self.destination.clone(),
+            pipeline: self.pipeline.clone(),
+            mapping: self.mapping.clone(),
+            state: self.state.clone(),
+            offset_strategy,
+            cursor,
+            plugin_registry: self.plugin_registry.clone(),
+        }
+    }
 }
 
 pub struct PipelineContextBuilder {
     exec_ctx: Arc<ExecutionContext>,
     run_id: Option<String>,
     item_id: Option<String>,
+    part_id: Option<String>,
     source: Option<Source>,
     destination: Option<Destination>,
     pipeline: Option<Pipeline>,
     mapping: Option<TransformationMetadata>,
-    state: Option<Arc<SledStateStore>>,
+    state: Option<SharedStateStore>,
     offset_strategy: Option<Arc<dyn OffsetStrategy>>,
     cursor: Option<Cursor>,
     plugin_registry: Option<Arc<PluginRegistry>>,
@@ -49,6 +78,7 @@ impl PipelineContextBuilder {
             exec_ctx,
             run_id: None,
             item_id: None,
+            part_id: None,
             source: None,
             destination: None,
             pipeline: None,
@@ -70,6 +100,11 @@ impl PipelineContextBuilder {
         self
     }
 
+    pub fn part_id(mut self, part_id: String) -> Self {
+        self.part_id = Some(part_id);
+        self
+    }
+
     pub fn source(mut self, source: Source) -> Self {
         self.source = Some(source);
         self
@@ -90,7 +125,7 @@ impl PipelineContextBuilder {
         self
     }
 
-    pub fn state(mut self, state: Arc<SledStateStore>) -> Self {
+    pub fn state(mut self, state: SharedStateStore) -> Self {
         self.state = Some(state);
         self
     }
@@ -115,6 +150,7 @@ impl PipelineContextBuilder {
             exec_ctx: self.exec_ctx,
             run_id: self.run_id.expect("run_id is required"),
             item_id: self.item_id.expect("item_id is required"),
+            part_id: self.part_id.unwrap_or_else(|| "part-0".to_string()),
             source: self.source.expect("source is required"),
             destination: self.destination.expect("destination is required"),
             pipeline: self.pipeline.expect("pipeline is required"),
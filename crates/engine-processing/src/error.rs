@@ -1,14 +1,21 @@
-use crate::{io::error::SinkError, transform::error::TransformError};
+use crate::{
+    consumer::components::write_context::WriteErrorContext,
+    io::error::SinkError,
+    retry::{classify_driver_error, classify_sink_error, is_overflow_error},
+    transform::error::TransformError,
+};
 use connectors::error::DriverError;
+use engine_core::retry::RetryDisposition;
 use engine_state::error::StateStoreError;
 use model::pagination::cursor::Cursor;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ConsumerError {
-    #[error("Failed to write batch '{batch_id}': {source}")]
+    #[error("Failed to write batch '{batch_id}' ({context}): {source}")]
     Write {
         batch_id: String,
+        context: Box<WriteErrorContext>,
         #[source]
         source: SinkError,
     },
@@ -23,6 +30,15 @@ pub enum ConsumerError {
         source: StateStoreError,
     },
 
+    /// The batch was written but its commit could not be recorded, so it
+    /// must not be written again in this run
+    #[error("Batch '{batch_id}' was written but could not be acknowledged: {source}")]
+    Ack {
+        batch_id: String,
+        #[source]
+        source: StateStoreError,
+    },
+
     #[error("Failed to toggle triggers for table '{table}': {source}")]
     ToggleTrigger {
         table: String,
@@ -41,6 +57,52 @@ pub enum ConsumerError {
 
     #[error("Circuit breaker opened for stage '{stage}': {last_error}")]
     CircuitBreakerOpen { stage: String, last_error: String },
+
+    #[error("Destination table '{table}' changed during the run: {}", changes.join("; "))]
+    SchemaDrift {
+        table: String,
+        changes: Vec<String>,
+        #[source]
+        source: SinkError,
+    },
+
+    #[error("on_batch hook failed after batch '{batch_id}': {source}")]
+    BatchHook {
+        batch_id: String,
+        #[source]
+        source: DriverError,
+    },
+}
+
+impl ConsumerError {
+    /// Returns true if retrying the batch can't help: the destination rejected
+    /// its data, or the error isn't about reaching an external system.
+    /// Everything else goes through the circuit breaker.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            ConsumerError::Write { source, .. } | ConsumerError::Sink(source) => {
+                classify_sink_error(source) == RetryDisposition::Stop
+            }
+            ConsumerError::ToggleTrigger { source, .. } => {
+                classify_driver_error(source) == RetryDisposition::Stop
+            }
+            ConsumerError::StateLoad(_) | ConsumerError::Checkpoint { .. } => false,
+            ConsumerError::Deserialization(_)
+            | ConsumerError::RetriesExhausted(_)
+            | ConsumerError::Ack { .. }
+            | ConsumerError::CircuitBreakerOpen { .. }
+            | ConsumerError::SchemaDrift { .. }
+            | ConsumerError::BatchHook { .. } => true,
+        }
+    }
+
+    /// Returns true if the destination rejected a value too large for its column.
+    pub fn is_overflow(&self) -> bool {
+        match self {
+            ConsumerError::Write { source, .. } => is_overflow_error(source),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -1,6 +1,13 @@
 use crate::io::{
     format::DataFormat,
-    sink::{Sink, mysql::MySqlSink, postgres::PostgresSink, wasm::WasmSinkAdapter},
+    sink::{
+        Sink,
+        csv::{CsvFileConfig, CsvFileSink},
+        discard::DiscardSink,
+        mysql::MySqlSink,
+        postgres::PostgresSink,
+        wasm::WasmSinkAdapter,
+    },
 };
 use connectors::{
     drivers::{mysql::driver::MySqlDriver, postgres::driver::PgDriver},
@@ -67,6 +74,20 @@ impl Destination {
         Destination { name, format, sink }
     }
 
+    /// A destination writing rotated CSV files into a directory.
+    pub fn csv(config: CsvFileConfig) -> Self {
+        let name = config.table.clone();
+        let format = DataFormat::Csv;
+        let sink = Arc::new(CsvFileSink::new(config));
+        Destination { name, format, sink }
+    }
+
+    /// A destination that drops every row, for pipelines that only check
+    /// the source. Takes its format from the source connection.
+    pub fn discard(table: &str, conn: &Connection) -> Result<Self, DriverError> {
+        Self::new(Arc::new(DiscardSink), table, conn)
+    }
+
     pub async fn write_batch(
         &self,
         meta: &TableMetadata,
@@ -83,6 +104,18 @@ impl Destination {
         self.sink.finalize().await
     }
 
+    pub async fn ping(&self) -> Result<(), DriverError> {
+        self.sink.ping().await
+    }
+
+    pub async fn execute(&self, sql: &str) -> Result<(), DriverError> {
+        self.sink.execute(sql).await
+    }
+
+    pub async fn table_metadata(&self, table: &str) -> Result<Option<TableMetadata>, DriverError> {
+        self.sink.table_metadata(table).await
+    }
+
     pub fn dialect(&self) -> Option<Box<dyn dialect::Dialect>> {
         match self.format {
             DataFormat::Postgres => Some(Box::new(dialect::Postgres)),
@@ -1,3 +1,6 @@
+use connectors::{
+    error::DriverError, registry::driver_capabilities, sql::metadata::capabilities::Capabilities,
+};
 use engine_core::schema::type_registry::Dialect;
 use std::fmt::Display;
 
@@ -24,18 +27,33 @@ impl DataFormat {
     pub fn parse(format: &str) -> Option<Self> {
         match format.to_lowercase().as_str() {
             "mysql" => Some(DataFormat::MySql),
-            "postgres" => Some(DataFormat::Postgres),
+            "postgres" | "postgresql" => Some(DataFormat::Postgres),
             "csv" => Some(DataFormat::Csv),
             "wasm" => Some(DataFormat::Wasm),
             _ => None,
         }
     }
 
-    pub fn to_dialect(self) -> Dialect {
+    /// What the format's driver supports, known without connecting. WASM
+    /// plugins report no capabilities.
+    pub fn capabilities(self) -> Capabilities {
+        let driver = match self {
+            DataFormat::MySql => "mysql",
+            DataFormat::Postgres => "postgres",
+            DataFormat::Csv => "csv",
+            DataFormat::Wasm => return Capabilities::default(),
+        };
+        driver_capabilities(driver).unwrap_or_default()
+    }
+
+    /// SQL dialect of the format; CSV and WASM have none.
+    pub fn to_dialect(self) -> Result<Dialect, DriverError> {
         match self {
-            DataFormat::MySql => Dialect::MySql,
-            DataFormat::Postgres => Dialect::Postgres,
-            _ => panic!("Unsupported format for dialect conversion: {:?}", self),
+            DataFormat::MySql => Ok(Dialect::MySql),
+            DataFormat::Postgres => Ok(Dialect::Postgres),
+            DataFormat::Csv | DataFormat::Wasm => Err(DriverError::UnsupportedFormat(format!(
+                "{self} has no SQL dialect"
+            ))),
         }
     }
 }
@@ -0,0 +1,312 @@
+//! Large binary values. With `lob_threshold` set, binary values over the
+//! threshold are read as NULL so a batch never holds them, and are streamed
+//! from the source row to the destination row in chunks once the pipeline
+//! has loaded, keyed by primary key.
+
+use connectors::{
+    error::DriverError,
+    sql::metadata::table::TableMetadata,
+    traits::{reader::DataReader, writer::DataWriter},
+};
+use engine_core::plan::{lineage::pipeline_lineage, settings::effective_value};
+use model::{
+    core::value::Value,
+    execution::{
+        lineage::{ColumnRef, ColumnUse, PipelineLineage},
+        pipeline::Pipeline,
+    },
+};
+use tracing::{debug, warn};
+
+const SETTING_LOB_THRESHOLD: &str = "lob_threshold";
+const SETTING_LOB_CHUNK_SIZE: &str = "lob_chunk_size";
+
+/// A source column and the destination column it is written to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LobColumn {
+    pub source: String,
+    pub destination: String,
+}
+
+/// The columns a pipeline streams outside its batches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LobPlan {
+    pub threshold: usize,
+    pub chunk_size: usize,
+    pub source_table: String,
+    pub destination_table: String,
+    /// Primary key of the source table, matched to the destination by value
+    pub keys: Vec<LobColumn>,
+    /// Binary columns whose values over `threshold` are streamed
+    pub columns: Vec<LobColumn>,
+}
+
+/// What a large-object pass streamed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LobStats {
+    pub values: u64,
+    pub bytes: u64,
+}
+
+impl LobPlan {
+    /// The plan for `pipeline` reading `source`, or `None` when
+    /// `lob_threshold` is unset or no value can be streamed. Only binary
+    /// columns copied or renamed as they are qualify; a column an expression
+    /// reads is read in full, since the expression would see NULL.
+    pub fn new(pipeline: &Pipeline, source: &TableMetadata) -> Option<Self> {
+        let threshold = setting_bytes(pipeline, SETTING_LOB_THRESHOLD)?;
+        let chunk_size = setting_bytes(pipeline, SETTING_LOB_CHUNK_SIZE)?;
+
+        let lineage = pipeline_lineage(pipeline);
+        let destination_table = lineage.destination_table.clone()?;
+
+        let mut binary: Vec<_> = source.columns.values().filter(|c| c.is_binary()).collect();
+        binary.sort_by_key(|c| c.ordinal);
+        let columns: Vec<LobColumn> = binary
+            .into_iter()
+            .filter_map(|c| destination_column(&lineage, &source.name, &c.name))
+            .collect();
+        if columns.is_empty() {
+            return None;
+        }
+
+        if source.primary_keys.is_empty() {
+            warn!(table = %source.name, "lob_threshold ignored: the source table has no primary key, so binary values are read in full");
+            return None;
+        }
+        let mut keys = Vec::new();
+        for key in &source.primary_keys {
+            let Some(column) = destination_column(&lineage, &source.name, key) else {
+                warn!(table = %source.name, column = %key, "lob_threshold ignored: the primary key column is not copied as it is, so binary values are read in full");
+                return None;
+            };
+            keys.push(column);
+        }
+
+        Some(Self {
+            threshold,
+            chunk_size,
+            source_table: source.name.clone(),
+            destination_table,
+            keys,
+            columns,
+        })
+    }
+
+    /// Source columns read as NULL when over the threshold.
+    pub fn source_columns(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.source.clone()).collect()
+    }
+}
+
+fn setting_bytes(pipeline: &Pipeline, key: &str) -> Option<usize> {
+    match effective_value(&pipeline.settings, key) {
+        Ok(Value::Int(n)) if n > 0 => Some(n as usize),
+        Ok(Value::UInt(n)) if n > 0 => Some(n as usize),
+        _ => None,
+    }
+}
+
+/// The one destination column of the pipeline's table that `column` is
+/// written to unchanged, if any. A renamed column is not also copied under
+/// its own name, so a mapping takes precedence over the copy.
+fn destination_column(lineage: &PipelineLineage, table: &str, column: &str) -> Option<LobColumn> {
+    let uses = lineage.uses(&ColumnRef::new(table, column));
+    let mut mapped = uses.iter().filter_map(|u| match u {
+        ColumnUse::Mapped(mapped) => Some(mapped),
+        _ => None,
+    });
+
+    let destination = match (mapped.next(), mapped.next()) {
+        (Some(mapped), None)
+            if mapped.expression.is_none()
+                && Some(&mapped.destination.table) == lineage.destination_table.as_ref() =>
+        {
+            mapped.destination.column.clone()
+        }
+        (Some(_), _) => return None,
+        (None, _) => uses.iter().find_map(|u| match u {
+            ColumnUse::Copied(copied) => Some(copied.column.clone()),
+            _ => None,
+        })?,
+    };
+
+    Some(LobColumn {
+        source: column.to_string(),
+        destination,
+    })
+}
+
+/// Streams every value over the plan's threshold from `source` to
+/// `destination`, one chunk at a time. Rows missing from the destination,
+/// such as rows a `where` filter left out, are skipped.
+pub async fn stream<S, D>(
+    plan: &LobPlan,
+    source: &S,
+    destination: &D,
+) -> Result<LobStats, DriverError>
+where
+    S: DataReader + ?Sized,
+    D: DataWriter + ?Sized,
+{
+    let key_columns: Vec<String> = plan.keys.iter().map(|k| k.source.clone()).collect();
+    let mut stats = LobStats::default();
+
+    for column in &plan.columns {
+        let rows = source
+            .oversized_keys(
+                &plan.source_table,
+                &key_columns,
+                &column.source,
+                plan.threshold,
+            )
+            .await?;
+        debug!(column = %column.source, rows = rows.len(), "streaming large values");
+
+        for row in rows {
+            let source_key: Vec<(String, Value)> = plan
+                .keys
+                .iter()
+                .map(|k| (k.source.clone(), row.get_value(&k.source)))
+                .collect();
+            let destination_key: Vec<(String, Value)> = plan
+                .keys
+                .iter()
+                .zip(&source_key)
+                .map(|(k, (_, value))| (k.destination.clone(), value.clone()))
+                .collect();
+
+            let mut offset = 0;
+            loop {
+                let chunk = source
+                    .read_chunk(
+                        &plan.source_table,
+                        &source_key,
+                        &column.source,
+                        offset,
+                        plan.chunk_size,
+                    )
+                    .await?;
+                if chunk.is_empty() && offset > 0 {
+                    break;
+                }
+
+                let written = destination
+                    .write_chunk(
+                        &plan.destination_table,
+                        &destination_key,
+                        &column.destination,
+                        &chunk,
+                        offset > 0,
+                    )
+                    .await?;
+                offset += chunk.len();
+                if written == 0 || chunk.len() < plan.chunk_size {
+                    break;
+                }
+            }
+
+            if offset > 0 {
+                stats.values += 1;
+                stats.bytes += offset as u64;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connectors::sql::metadata::column::ColumnMetadata;
+    use engine_core::{context::env::EnvContext, plan::execution::ExecutionPlan};
+    use smql_syntax::builder::parse;
+    use std::{collections::HashMap, sync::Arc};
+
+    fn pipeline(select: &str, settings: &str) -> Pipeline {
+        let block = |name: &str, body: &str| {
+            if body.is_empty() {
+                String::new()
+            } else {
+                format!("{name} {{ {body} }}")
+            }
+        };
+        let (select, settings) = (block("select", select), block("settings", settings));
+        let smql = format!(
+            r#"
+            connection "src" {{ driver = "mysql" url = "mysql://localhost/src" }}
+            connection "dst" {{ driver = "postgres" url = "postgres://localhost/dst" }}
+
+            pipeline "docs" {{
+                from {{ connection = connection.src table = "documents" }}
+                to {{ connection = connection.dst table = "documents_copy" }}
+                {select}
+                {settings}
+            }}
+            "#
+        );
+        let doc = parse(&smql).expect("Failed to parse SMQL");
+        let plan = ExecutionPlan::build(&doc, Arc::new(EnvContext::empty())).unwrap();
+        plan.pipelines.into_iter().next().unwrap()
+    }
+
+    fn documents() -> TableMetadata {
+        let column = |name: &str, data_type: &str, ordinal: usize| {
+            (
+                name.to_string(),
+                ColumnMetadata {
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    ordinal,
+                    ..Default::default()
+                },
+            )
+        };
+        TableMetadata {
+            name: "documents".to_string(),
+            schema: None,
+            collation: None,
+            columns: HashMap::from([
+                column("id", "int", 1),
+                column("body", "longblob", 2),
+                column("thumbnail", "blob", 3),
+                column("title", "varchar", 4),
+            ]),
+            primary_keys: vec!["id".to_string()],
+            foreign_keys: vec![],
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_lob_plan_streams_columns_copied_as_they_are() {
+        let lob = |source: &str, destination: &str| LobColumn {
+            source: source.to_string(),
+            destination: destination.to_string(),
+        };
+
+        // Off unless lob_threshold is set
+        assert_eq!(LobPlan::new(&pipeline("", ""), &documents()), None);
+
+        let plan = LobPlan::new(
+            &pipeline(
+                "content = documents.body\n thumbnail = concat(documents.thumbnail)",
+                "lob_threshold = 4096",
+            ),
+            &documents(),
+        )
+        .unwrap();
+        assert_eq!(plan.threshold, 4096);
+        assert_eq!(plan.chunk_size, 1_048_576);
+        assert_eq!(plan.destination_table, "documents_copy");
+        assert_eq!(plan.keys, vec![lob("id", "id")]);
+        // The thumbnail feeds an expression and is read in full
+        assert_eq!(plan.columns, vec![lob("body", "content")]);
+
+        // A key written through an expression cannot address the copied row
+        let shifted = pipeline("id = documents.id + 1000", "lob_threshold = 4096");
+        assert_eq!(LobPlan::new(&shifted, &documents()), None);
+    }
+}
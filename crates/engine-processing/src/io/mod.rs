@@ -4,5 +4,6 @@ pub mod error;
 pub mod filter;
 pub mod format;
 pub mod linked;
+pub mod lob;
 pub mod sink;
 pub mod source;
@@ -0,0 +1,625 @@
+//! CSV files in a directory, rotated into numbered parts.
+//!
+//! Rows go to `<table>_000001.csv`, `<table>_000002.csv` and so on, each
+//! part with its own header row, so downstream loaders can pick the parts up
+//! one by one. A new part starts once the current one holds `max_rows` rows,
+//! would grow past `max_bytes` bytes of CSV, or has been open for `max_age`.
+//! When the pipeline finishes, `<table>_manifest.json` lists the parts with
+//! their row counts and SHA-256 checksums, after `<table>_schema.json` has
+//! described the columns' types (see [`FileSchema`]).
+
+use crate::io::sink::{
+    Sink,
+    file_schema::{self, ColumnSchema, FileSchema},
+};
+use async_trait::async_trait;
+use connectors::{
+    error::{DbError, DriverError},
+    sql::metadata::table::TableMetadata,
+};
+use flate2::{Compression, write::GzEncoder};
+use model::{core::value::Value, records::Record};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Width of the zero-padded part number in file names
+const PART_DIGITS: usize = 6;
+
+/// When to close the current part and start the next one. A limit left at
+/// `None` never rotates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileRotation {
+    pub max_rows: Option<u64>,
+    /// Uncompressed CSV bytes, header included
+    pub max_bytes: Option<u64>,
+    pub max_age: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileCompression {
+    #[default]
+    None,
+    Gzip,
+}
+
+impl FileCompression {
+    fn extension(self) -> &'static str {
+        match self {
+            FileCompression::None => "csv",
+            FileCompression::Gzip => "csv.gz",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            FileCompression::None => "none",
+            FileCompression::Gzip => "gzip",
+        }
+    }
+}
+
+/// Where and how a CSV destination writes its files
+#[derive(Debug, Clone)]
+pub struct CsvFileConfig {
+    pub dir: PathBuf,
+    pub table: String,
+    pub delimiter: u8,
+    pub rotation: FileRotation,
+    pub compression: FileCompression,
+    pub schema: FileSchema,
+}
+
+impl CsvFileConfig {
+    fn part_name(&self, number: usize) -> String {
+        format!(
+            "{}_{number:0width$}.{}",
+            self.table,
+            self.compression.extension(),
+            width = PART_DIGITS
+        )
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.dir.join(format!("{}_manifest.json", self.table))
+    }
+
+    fn schema_name(&self) -> String {
+        format!("{}_schema.json", self.table)
+    }
+
+    /// Whether `name` is a part file of this table, from this or an
+    /// earlier run
+    fn is_part(&self, name: &str) -> bool {
+        let Some(rest) = name.strip_prefix(&format!("{}_", self.table)) else {
+            return false;
+        };
+        let Some((number, extension)) = rest.split_once('.') else {
+            return false;
+        };
+        number.len() == PART_DIGITS
+            && number.bytes().all(|b| b.is_ascii_digit())
+            && matches!(extension, "csv" | "csv.gz")
+    }
+}
+
+/// Writes rows to rotated CSV files
+pub struct CsvFileSink {
+    config: CsvFileConfig,
+    state: Mutex<SinkState>,
+}
+
+#[derive(Default)]
+struct SinkState {
+    /// Header of every part: the columns of the first row written
+    columns: Option<Vec<String>>,
+    /// Type and nullability of every header column, from the rows written
+    schema: Vec<ColumnSchema>,
+    current: Option<OpenPart>,
+    done: Vec<PartEntry>,
+}
+
+/// A part being written
+struct OpenPart {
+    file: String,
+    writer: PartWriter,
+    rows: u64,
+    bytes: u64,
+    opened: Instant,
+}
+
+/// A closed part, as listed in the manifest
+#[derive(Debug, Clone, PartialEq)]
+struct PartEntry {
+    file: String,
+    rows: u64,
+    /// Size of the file on disk
+    bytes: u64,
+    sha256: String,
+}
+
+impl CsvFileSink {
+    pub fn new(config: CsvFileConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(SinkState::default()),
+        }
+    }
+
+    fn write_rows(&self, state: &mut SinkState, rows: &[Record]) -> Result<(), DriverError> {
+        let columns = state
+            .columns
+            .get_or_insert_with(|| {
+                rows.first()
+                    .map(|row| row.fields.iter().map(|f| f.name.clone()).collect())
+                    .unwrap_or_default()
+            })
+            .clone();
+        if state.schema.is_empty() {
+            state.schema = columns
+                .iter()
+                .map(|c| ColumnSchema::new(c, Value::Null.data_type()))
+                .collect();
+        }
+        let header = encode(&columns, self.config.delimiter)?;
+
+        for row in rows {
+            if let Some(extra) = row
+                .fields
+                .iter()
+                .find(|f| !columns.iter().any(|c| c == &f.name))
+            {
+                return Err(write_error(format!(
+                    "column '{}' is not in the header of {} ({})",
+                    extra.name,
+                    self.config.table,
+                    columns.join(", ")
+                )));
+            }
+            for column in state.schema.iter_mut() {
+                match row.get(&column.name) {
+                    Some(field) => column.observe(field.value.as_ref(), &field.data_type),
+                    None => column.nullable = true,
+                }
+            }
+            let values: Vec<String> = columns
+                .iter()
+                .map(|c| csv_text(&row.get_value(c)))
+                .collect();
+            let line = encode(&values, self.config.delimiter)?;
+
+            if let Some(part) = &state.current
+                && self.is_full(part, line.len())
+            {
+                let part = state.current.take().expect("part is open");
+                state.done.push(close(part)?);
+            }
+            let part = match &mut state.current {
+                Some(part) => part,
+                None => {
+                    let number = state.done.len() + 1;
+                    state
+                        .current
+                        .insert(self.open(&self.config.part_name(number), &header)?)
+                }
+            };
+            part.writer.write_all(&line).map_err(write_error)?;
+            part.rows += 1;
+            part.bytes += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Whether the next row, `next` bytes long, belongs in a new part
+    fn is_full(&self, part: &OpenPart, next: usize) -> bool {
+        let rotation = &self.config.rotation;
+        rotation.max_rows.is_some_and(|max| part.rows >= max)
+            || rotation
+                .max_bytes
+                .is_some_and(|max| part.bytes + next as u64 > max)
+            || rotation
+                .max_age
+                .is_some_and(|max| part.opened.elapsed() >= max)
+    }
+
+    fn open(&self, file: &str, header: &[u8]) -> Result<OpenPart, DriverError> {
+        let out =
+            HashingWriter::new(File::create(self.config.dir.join(file)).map_err(write_error)?);
+        let mut writer = match self.config.compression {
+            FileCompression::None => PartWriter::Plain(out),
+            FileCompression::Gzip => PartWriter::Gzip(GzEncoder::new(out, Compression::default())),
+        };
+        writer.write_all(header).map_err(write_error)?;
+        Ok(OpenPart {
+            file: file.to_string(),
+            writer,
+            rows: 0,
+            bytes: header.len() as u64,
+            opened: Instant::now(),
+        })
+    }
+
+    /// Write the schema file, returning its name; `None` when the destination
+    /// writes none
+    fn write_schema(&self, state: &SinkState) -> Result<Option<String>, DriverError> {
+        let Some(schema) =
+            file_schema::render(self.config.schema, &self.config.table, &state.schema)
+        else {
+            return Ok(None);
+        };
+        let name = self.config.schema_name();
+        write_json(&self.config.dir.join(&name), &schema)?;
+        Ok(Some(name))
+    }
+
+    fn write_manifest(&self, state: &SinkState, schema: Option<String>) -> Result<(), DriverError> {
+        let parts: Vec<_> = state
+            .done
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "file": p.file,
+                    "rows": p.rows,
+                    "bytes": p.bytes,
+                    "sha256": p.sha256,
+                })
+            })
+            .collect();
+        let manifest = serde_json::json!({
+            "table": self.config.table,
+            "format": "csv",
+            "compression": self.config.compression.as_str(),
+            "columns": state.columns.clone().unwrap_or_default(),
+            "schema": schema,
+            "schema_format": schema.is_some().then(|| self.config.schema.as_str()),
+            "rows": state.done.iter().map(|p| p.rows).sum::<u64>(),
+            "parts": parts,
+        });
+        write_json(&self.config.manifest_path(), &manifest)
+    }
+}
+
+#[async_trait]
+impl Sink for CsvFileSink {
+    /// Create the directory and remove the parts, schema and manifest an earlier run
+    /// left, so every run writes the table's files afresh.
+    async fn prepare(&self) -> Result<(), DriverError> {
+        fs::create_dir_all(&self.config.dir).map_err(write_error)?;
+        for entry in fs::read_dir(&self.config.dir).map_err(write_error)? {
+            let path = entry.map_err(write_error)?.path();
+            let stale = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| self.config.is_part(n));
+            if stale
+                || path == self.config.manifest_path()
+                || path == self.config.dir.join(self.config.schema_name())
+            {
+                fs::remove_file(&path).map_err(write_error)?;
+            }
+        }
+        *self.state.lock().expect("csv sink mutex poisoned") = SinkState::default();
+        Ok(())
+    }
+
+    async fn write_batch(
+        &self,
+        _meta: &TableMetadata,
+        rows: &[Record],
+    ) -> Result<u64, DriverError> {
+        let mut state = self.state.lock().expect("csv sink mutex poisoned");
+        self.write_rows(&mut state, rows)?;
+        Ok(rows.len() as u64)
+    }
+
+    /// Close the last part and write the schema file and the manifest.
+    async fn finalize(&self) -> Result<(), DriverError> {
+        let mut state = self.state.lock().expect("csv sink mutex poisoned");
+        if let Some(part) = state.current.take() {
+            state.done.push(close(part)?);
+        }
+        let schema = self.write_schema(&state)?;
+        self.write_manifest(&state, schema)?;
+        info!(
+            table = %self.config.table,
+            parts = state.done.len(),
+            dir = %self.config.dir.display(),
+            "csv files written"
+        );
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<(), DriverError> {
+        check_dir(&self.config.dir)
+    }
+}
+
+fn close(part: OpenPart) -> Result<PartEntry, DriverError> {
+    let (bytes, sha256) = part.writer.finish().map_err(write_error)?;
+    Ok(PartEntry {
+        file: part.file,
+        rows: part.rows,
+        bytes,
+        sha256,
+    })
+}
+
+fn check_dir(dir: &Path) -> Result<(), DriverError> {
+    match fs::metadata(dir) {
+        Ok(meta) if meta.is_dir() => Ok(()),
+        Ok(_) => Err(write_error(format!("{} is not a directory", dir.display()))),
+        Err(e) => Err(write_error(e)),
+    }
+}
+
+/// Write `doc` beside `path` and rename it into place, so a loader never
+/// reads half a file
+fn write_json(path: &Path, doc: &serde_json::Value) -> Result<(), DriverError> {
+    let text = serde_json::to_vec_pretty(doc).map_err(write_error)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, text).map_err(write_error)?;
+    fs::rename(&tmp, path).map_err(write_error)
+}
+
+/// One CSV record, with its line ending
+fn encode<T: AsRef<[u8]>>(fields: &[T], delimiter: u8) -> Result<Vec<u8>, DriverError> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    writer.write_record(fields).map_err(write_error)?;
+    writer.into_inner().map_err(write_error)
+}
+
+/// A value as CSV text; nulls are empty fields
+fn csv_text(value: &Value) -> String {
+    value.as_string().unwrap_or_default()
+}
+
+fn write_error(e: impl Display) -> DriverError {
+    DbError::Write(e.to_string()).into()
+}
+
+/// File output of a part, gzipped or not
+enum PartWriter {
+    Plain(HashingWriter),
+    Gzip(GzEncoder<HashingWriter>),
+}
+
+impl PartWriter {
+    /// Flush the part to disk and return its size and checksum
+    fn finish(self) -> io::Result<(u64, String)> {
+        let out = match self {
+            PartWriter::Plain(out) => out,
+            PartWriter::Gzip(gz) => gz.finish()?,
+        };
+        out.finish()
+    }
+}
+
+impl Write for PartWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            PartWriter::Plain(out) => out.write(buf),
+            PartWriter::Gzip(gz) => gz.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            PartWriter::Plain(out) => out.flush(),
+            PartWriter::Gzip(gz) => gz.flush(),
+        }
+    }
+}
+
+/// Hashes and counts the bytes written to a file
+struct HashingWriter {
+    file: BufWriter<File>,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl HashingWriter {
+    fn new(file: File) -> Self {
+        Self {
+            file: BufWriter::new(file),
+            hasher: Sha256::new(),
+            bytes: 0,
+        }
+    }
+
+    fn finish(mut self) -> io::Result<(u64, String)> {
+        self.file.flush()?;
+        self.file.get_ref().sync_all()?;
+        Ok((self.bytes, format!("{:x}", self.hasher.finalize())))
+    }
+}
+
+impl Write for HashingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.file.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.bytes += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use model::{core::value::FieldValue, records::OpType};
+    use std::io::Read;
+
+    fn sink(dir: &Path, rotation: FileRotation, compression: FileCompression) -> CsvFileSink {
+        CsvFileSink::new(CsvFileConfig {
+            dir: dir.to_path_buf(),
+            table: "orders".to_string(),
+            delimiter: b',',
+            rotation,
+            compression,
+            schema: FileSchema::JsonSchema,
+        })
+    }
+
+    fn rows(ids: std::ops::Range<i64>) -> Vec<Record> {
+        ids.map(|id| {
+            let fields = vec![
+                FieldValue {
+                    name: "id".to_string(),
+                    value: Some(Value::Int(id)),
+                    data_type: Value::Int(id).data_type(),
+                },
+                FieldValue {
+                    name: "note".to_string(),
+                    value: Some(Value::Null),
+                    data_type: Value::Null.data_type(),
+                },
+            ];
+            Record::new("orders", fields, OpType::Insert)
+        })
+        .collect()
+    }
+
+    fn meta() -> TableMetadata {
+        TableMetadata {
+            name: "orders".to_string(),
+            schema: None,
+            collation: None,
+            columns: Default::default(),
+            primary_keys: Vec::new(),
+            foreign_keys: Vec::new(),
+            referenced_tables: Default::default(),
+            referencing_tables: Default::default(),
+        }
+    }
+
+    fn manifest(dir: &Path) -> serde_json::Value {
+        serde_json::from_slice(&fs::read(dir.join("orders_manifest.json")).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rotates_by_row_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = FileRotation {
+            max_rows: Some(2),
+            ..Default::default()
+        };
+        let sink = sink(dir.path(), rotation, FileCompression::None);
+
+        sink.prepare().await.unwrap();
+        sink.write_batch(&meta(), &rows(1..4)).await.unwrap();
+        sink.write_batch(&meta(), &rows(4..6)).await.unwrap();
+        sink.finalize().await.unwrap();
+
+        let first = fs::read_to_string(dir.path().join("orders_000001.csv")).unwrap();
+        assert_eq!(first, "id,note\n1,\n2,\n");
+        let last = fs::read_to_string(dir.path().join("orders_000003.csv")).unwrap();
+        assert_eq!(last, "id,note\n5,\n");
+
+        let manifest = manifest(dir.path());
+        assert_eq!(manifest["rows"], 5);
+        assert_eq!(manifest["columns"], serde_json::json!(["id", "note"]));
+        assert_eq!(manifest["schema"], "orders_schema.json");
+        let schema: serde_json::Value =
+            serde_json::from_slice(&fs::read(dir.path().join("orders_schema.json")).unwrap())
+                .unwrap();
+        assert_eq!(schema["properties"]["id"]["type"], "integer");
+        assert_eq!(
+            schema["properties"]["note"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+        let parts = manifest["parts"].as_array().unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0]["file"], "orders_000001.csv");
+        assert_eq!(parts[0]["rows"], 2);
+        assert_eq!(
+            parts[0]["sha256"],
+            format!("{:x}", Sha256::digest(first.as_bytes()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotates_by_size_before_the_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        // Header (8 bytes) and two 3-byte rows fit in 14 bytes
+        let rotation = FileRotation {
+            max_bytes: Some(14),
+            ..Default::default()
+        };
+        let sink = sink(dir.path(), rotation, FileCompression::None);
+
+        sink.prepare().await.unwrap();
+        sink.write_batch(&meta(), &rows(1..6)).await.unwrap();
+        sink.finalize().await.unwrap();
+
+        let parts = manifest(dir.path())["parts"].clone();
+        let counts: Vec<_> = parts
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["rows"].as_u64().unwrap())
+            .collect();
+        assert_eq!(counts, [2, 2, 1]);
+        assert!(
+            parts
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|p| p["bytes"].as_u64().unwrap() <= 14)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gzip_parts_and_stale_files_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("orders_000009.csv"), "old").unwrap();
+        fs::write(dir.path().join("orders_archive.csv"), "kept").unwrap();
+        let sink = sink(dir.path(), FileRotation::default(), FileCompression::Gzip);
+
+        sink.prepare().await.unwrap();
+        assert!(!dir.path().join("orders_000009.csv").exists());
+        assert!(dir.path().join("orders_archive.csv").exists());
+
+        sink.write_batch(&meta(), &rows(1..3)).await.unwrap();
+        sink.finalize().await.unwrap();
+
+        let mut text = String::new();
+        GzDecoder::new(File::open(dir.path().join("orders_000001.csv.gz")).unwrap())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "id,note\n1,\n2,\n");
+        assert_eq!(manifest(dir.path())["compression"], "gzip");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_columns_missing_from_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = sink(dir.path(), FileRotation::default(), FileCompression::None);
+        sink.prepare().await.unwrap();
+        sink.write_batch(&meta(), &rows(1..2)).await.unwrap();
+
+        let mut extra = rows(2..3);
+        extra[0].fields.push(FieldValue {
+            name: "total".to_string(),
+            value: Some(Value::Int(5)),
+            data_type: Value::Int(5).data_type(),
+        });
+        let err = sink.write_batch(&meta(), &extra).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("column 'total' is not in the header")
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::io::sink::Sink;
+use async_trait::async_trait;
+use connectors::{error::DriverError, sql::metadata::table::TableMetadata};
+use model::records::Record;
+
+/// Accepts every batch and writes it nowhere. The destination of a
+/// validate-only pipeline, which reads and checks rows but keeps none.
+pub struct DiscardSink;
+
+#[async_trait]
+impl Sink for DiscardSink {
+    async fn write_batch(
+        &self,
+        _meta: &TableMetadata,
+        rows: &[Record],
+    ) -> Result<u64, DriverError> {
+        Ok(rows.len() as u64)
+    }
+}
@@ -0,0 +1,335 @@
+//! Schema files written beside file destinations.
+//!
+//! Column types come from the rows written: source columns carry the type
+//! their table metadata declares and computed columns the type transform
+//! inference gave them. A column is nullable when any row held a null in it.
+
+use model::core::{
+    types::{FloatSize, IntSize, Type},
+    value::Value,
+};
+use serde_json::{Value as Json, json};
+
+/// Form of the schema file, if one is written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileSchema {
+    None,
+    /// JSON Schema (draft 2020-12) describing one row
+    #[default]
+    JsonSchema,
+    /// The JSON form of an Arrow schema
+    Arrow,
+}
+
+impl FileSchema {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FileSchema::None => "none",
+            FileSchema::JsonSchema => "json_schema",
+            FileSchema::Arrow => "arrow",
+        }
+    }
+}
+
+/// Type and nullability of one column, gathered while rows are written
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: Type,
+    pub nullable: bool,
+    /// Whether `data_type` came from a value rather than a null
+    typed: bool,
+}
+
+impl ColumnSchema {
+    pub fn new(name: impl Into<String>, data_type: Type) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            nullable: false,
+            typed: false,
+        }
+    }
+
+    /// Take in the type of one more value of the column. Nulls only mark the
+    /// column nullable; the first value decides the type, and decimals widen
+    /// to fit every value seen.
+    pub fn observe(&mut self, value: Option<&Value>, data_type: &Type) {
+        if matches!(value, None | Some(Value::Null)) {
+            self.nullable = true;
+            return;
+        }
+        if !self.typed {
+            self.data_type = data_type.clone();
+            self.typed = true;
+        } else if let Some(wider) = widen_decimal(&self.data_type, data_type) {
+            self.data_type = wider;
+        }
+    }
+}
+
+/// The smallest decimal holding values of both `a` and `b`, when both are
+/// decimals of known precision
+fn widen_decimal(a: &Type, b: &Type) -> Option<Type> {
+    let (
+        Type::Decimal {
+            precision: Some(pa),
+            scale: sa,
+        },
+        Type::Decimal {
+            precision: Some(pb),
+            scale: sb,
+        },
+    ) = (a, b)
+    else {
+        return None;
+    };
+    let (sa, sb) = (sa.unwrap_or(0), sb.unwrap_or(0));
+    let scale = sa.max(sb);
+    let digits = pa.saturating_sub(sa).max(pb.saturating_sub(sb));
+    Some(Type::Decimal {
+        precision: Some(digits.saturating_add(scale)),
+        scale: Some(scale),
+    })
+}
+
+/// The schema document for `columns` in the given form; `None` when no
+/// schema is written
+pub fn render(form: FileSchema, table: &str, columns: &[ColumnSchema]) -> Option<Json> {
+    match form {
+        FileSchema::None => None,
+        FileSchema::JsonSchema => Some(json_schema(table, columns)),
+        FileSchema::Arrow => Some(arrow_schema(columns)),
+    }
+}
+
+fn json_schema(table: &str, columns: &[ColumnSchema]) -> Json {
+    let properties: serde_json::Map<String, Json> = columns
+        .iter()
+        .map(|c| {
+            let mut property = json_property(&c.data_type);
+            if c.nullable
+                && let Some(Json::String(ty)) = property.get("type").cloned()
+            {
+                property["type"] = json!([ty, "null"]);
+            }
+            property["x-type"] = Json::String(type_name(&c.data_type));
+            (c.name.clone(), property)
+        })
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": table,
+        "type": "object",
+        "properties": properties,
+        "required": columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        "additionalProperties": false,
+    })
+}
+
+/// JSON Schema of one value, as the CSV text reads once parsed
+fn json_property(data_type: &Type) -> Json {
+    match data_type {
+        Type::Int { .. } | Type::Year => json!({ "type": "integer" }),
+        Type::Decimal { .. } | Type::Float { .. } => json!({ "type": "number" }),
+        Type::Boolean => json!({ "type": "boolean" }),
+        Type::Date => json!({ "type": "string", "format": "date" }),
+        Type::Uuid => json!({ "type": "string", "format": "uuid" }),
+        Type::Binary { .. } | Type::Varbinary { .. } | Type::Blob { .. } => {
+            json!({ "type": "string", "contentEncoding": "base64" })
+        }
+        Type::Enum { values, .. } if !values.is_empty() => {
+            json!({ "type": "string", "enum": values })
+        }
+        Type::Array { element } => json!({ "type": "array", "items": json_property(element) }),
+        Type::Domain { base_type, .. } => json_property(base_type),
+        // Any JSON document
+        Type::Json { .. } => json!({}),
+        _ => json!({ "type": "string" }),
+    }
+}
+
+fn arrow_schema(columns: &[ColumnSchema]) -> Json {
+    json!({
+        "fields": columns
+            .iter()
+            .map(|c| arrow_field(&c.name, &c.data_type, c.nullable))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn arrow_field(name: &str, data_type: &Type, nullable: bool) -> Json {
+    let children = match data_type {
+        Type::Array { element } => vec![arrow_field("item", element, true)],
+        _ => Vec::new(),
+    };
+    json!({
+        "name": name,
+        "nullable": nullable,
+        "type": arrow_type(data_type),
+        "children": children,
+    })
+}
+
+/// Arrow type of one value; types Arrow has no match for are text
+fn arrow_type(data_type: &Type) -> Json {
+    match data_type {
+        Type::Int { bits, unsigned, .. } => {
+            json!({ "name": "int", "bitWidth": int_bits(*bits), "isSigned": !unsigned })
+        }
+        Type::Year => json!({ "name": "int", "bitWidth": 16, "isSigned": true }),
+        Type::Float { bits } => json!({
+            "name": "floatingpoint",
+            "precision": match bits {
+                FloatSize::F32 => "SINGLE",
+                FloatSize::F64 => "DOUBLE",
+            },
+        }),
+        Type::Decimal {
+            precision: Some(precision),
+            scale,
+        } if *precision <= 76 => json!({
+            "name": "decimal",
+            "precision": precision,
+            "scale": scale.unwrap_or(0),
+            "bitWidth": if *precision <= 38 { 128 } else { 256 },
+        }),
+        Type::Boolean => json!({ "name": "bool" }),
+        Type::Binary { .. } | Type::Varbinary { .. } | Type::Blob { .. } => {
+            json!({ "name": "binary" })
+        }
+        Type::Date => json!({ "name": "date", "unit": "DAY" }),
+        Type::Time { .. } => json!({ "name": "time", "unit": "MICROSECOND", "bitWidth": 64 }),
+        Type::Timestamp { with_tz: true, .. } => {
+            json!({ "name": "timestamp", "unit": "MICROSECOND", "timezone": "UTC" })
+        }
+        Type::Timestamp { .. } => json!({ "name": "timestamp", "unit": "MICROSECOND" }),
+        Type::Array { .. } => json!({ "name": "list" }),
+        Type::Domain { base_type, .. } => arrow_type(base_type),
+        _ => json!({ "name": "utf8" }),
+    }
+}
+
+fn int_bits(bits: IntSize) -> u8 {
+    match bits {
+        IntSize::I8 => 8,
+        IntSize::I16 => 16,
+        IntSize::I24 | IntSize::I32 => 32,
+        IntSize::I64 => 64,
+    }
+}
+
+/// Short name of a column type, kept in the JSON Schema as `x-type`
+fn type_name(data_type: &Type) -> String {
+    match data_type {
+        Type::Int { bits, unsigned, .. } => {
+            format!("{}int{}", if *unsigned { "u" } else { "" }, int_bits(*bits))
+        }
+        Type::Decimal {
+            precision: Some(p),
+            scale,
+        } => format!("decimal({p},{})", scale.unwrap_or(0)),
+        Type::Decimal { .. } => "decimal".to_string(),
+        Type::Float {
+            bits: FloatSize::F32,
+        } => "float32".to_string(),
+        Type::Float {
+            bits: FloatSize::F64,
+        } => "float64".to_string(),
+        Type::Char { .. } | Type::Varchar { .. } | Type::Text { .. } => "string".to_string(),
+        Type::Binary { .. } | Type::Varbinary { .. } | Type::Blob { .. } => "binary".to_string(),
+        Type::Date => "date".to_string(),
+        Type::Time { with_tz: true, .. } => "timetz".to_string(),
+        Type::Time { .. } => "time".to_string(),
+        Type::Timestamp { with_tz: true, .. } => "timestamptz".to_string(),
+        Type::Timestamp { .. } => "timestamp".to_string(),
+        Type::Interval { .. } => "interval".to_string(),
+        Type::Year => "year".to_string(),
+        Type::Boolean => "boolean".to_string(),
+        Type::Uuid => "uuid".to_string(),
+        Type::Json { .. } => "json".to_string(),
+        Type::Bit { .. } => "bit".to_string(),
+        Type::Array { element } => format!("array<{}>", type_name(element)),
+        Type::Enum { .. } => "enum".to_string(),
+        Type::Set { .. } => "set".to_string(),
+        Type::Geometry { .. } => "geometry".to_string(),
+        Type::Inet => "inet".to_string(),
+        Type::Cidr => "cidr".to_string(),
+        Type::MacAddr => "macaddr".to_string(),
+        Type::Composite { name, .. } => name.clone(),
+        Type::Domain { base_type, .. } => type_name(base_type),
+        Type::Unknown { source_name, .. } => source_name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, values: &[Value]) -> ColumnSchema {
+        let mut column = ColumnSchema::new(name, Value::Null.data_type());
+        for value in values {
+            column.observe(Some(value), &value.data_type());
+        }
+        column
+    }
+
+    #[test]
+    fn test_column_types_from_values() {
+        let id = column("id", &[Value::Int(1), Value::Int(2)]);
+        assert!(!id.nullable);
+        assert_eq!(id.data_type, Value::Int(0).data_type());
+
+        let note = column("note", &[Value::Null, Value::String("x".to_string())]);
+        assert!(note.nullable);
+        assert_eq!(type_name(&note.data_type), "string");
+
+        let price = column(
+            "price",
+            &[
+                Value::Decimal("12.5".parse().unwrap()),
+                Value::Decimal("3.25".parse().unwrap()),
+            ],
+        );
+        assert_eq!(
+            price.data_type,
+            Type::Decimal {
+                precision: Some(5),
+                scale: Some(2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_render_schemas() {
+        let columns = vec![
+            column("id", &[Value::Int(1)]),
+            column("note", &[Value::Null]),
+        ];
+        assert_eq!(render(FileSchema::None, "orders", &columns), None);
+
+        let schema = render(FileSchema::JsonSchema, "orders", &columns).unwrap();
+        assert_eq!(
+            schema["properties"]["id"],
+            json!({ "type": "integer", "x-type": "int64" })
+        );
+        assert_eq!(
+            schema["properties"]["note"]["type"],
+            json!(["string", "null"])
+        );
+        assert_eq!(schema["required"], json!(["id", "note"]));
+
+        let schema = render(FileSchema::Arrow, "orders", &columns).unwrap();
+        assert_eq!(
+            schema["fields"][0],
+            json!({
+                "name": "id",
+                "nullable": false,
+                "type": { "name": "int", "bitWidth": 64, "isSigned": true },
+                "children": [],
+            })
+        );
+        assert_eq!(schema["fields"][1]["nullable"], true);
+        assert_eq!(schema["fields"][1]["type"], json!({ "name": "utf8" }));
+    }
+}
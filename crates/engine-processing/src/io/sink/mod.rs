@@ -3,6 +3,9 @@ use async_trait::async_trait;
 use connectors::{error::DriverError, sql::metadata::table::TableMetadata};
 use model::records::Record;
 
+pub mod csv;
+pub mod discard;
+pub mod file_schema;
 pub mod mysql;
 pub mod postgres;
 pub mod wasm;
@@ -36,4 +39,23 @@ pub trait Sink: Send + Sync {
     async fn finalize(&self) -> Result<(), DriverError> {
         Ok(())
     }
+
+    /// Cheap round trip to check the destination is reachable. Default: no-op.
+    async fn ping(&self) -> Result<(), DriverError> {
+        Ok(())
+    }
+
+    /// Current metadata of a destination table, to check it for drift after
+    /// a failed write. Default: unknown.
+    async fn table_metadata(&self, _table: &str) -> Result<Option<TableMetadata>, DriverError> {
+        Ok(None)
+    }
+
+    /// Run a statement on the destination outside any transaction, e.g. an
+    /// `on_batch` hook. Default: unsupported.
+    async fn execute(&self, _sql: &str) -> Result<(), DriverError> {
+        Err(DriverError::UnsupportedDriver(
+            "SQL statements are not supported by this sink".to_string(),
+        ))
+    }
 }
@@ -1,8 +1,9 @@
 use crate::io::sink::Sink;
 use async_trait::async_trait;
 use connectors::{
-    drivers::mysql::driver::MySqlDriver, sql::metadata::table::TableMetadata,
-    traits::writer::DataWriter,
+    drivers::mysql::driver::MySqlDriver,
+    sql::metadata::table::TableMetadata,
+    traits::{executor::QueryExecutor, introspector::SchemaIntrospector, writer::DataWriter},
 };
 use engine_core::schema::type_registry::{Dialect, TypeRegistry};
 use model::records::Record;
@@ -32,4 +33,19 @@ impl Sink for MySqlSink {
     ) -> Result<u64, connectors::error::DriverError> {
         self.driver.write_batch(meta, rows).await
     }
+
+    async fn ping(&self) -> Result<(), connectors::error::DriverError> {
+        self.driver.execute("SELECT 1").await
+    }
+
+    async fn execute(&self, sql: &str) -> Result<(), connectors::error::DriverError> {
+        self.driver.execute(sql).await
+    }
+
+    async fn table_metadata(
+        &self,
+        table: &str,
+    ) -> Result<Option<TableMetadata>, connectors::error::DriverError> {
+        self.driver.table_metadata(table).await.map(Some)
+    }
 }
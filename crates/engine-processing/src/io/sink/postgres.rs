@@ -8,7 +8,8 @@ use connectors::{
         query::generator::QueryGenerator,
     },
     traits::{
-        driver::Driver, executor::QueryExecutor, transaction::Transactional, writer::DataWriter,
+        driver::Driver, executor::QueryExecutor, introspector::SchemaIntrospector,
+        transaction::Transactional, writer::DataWriter,
     },
 };
 use engine_core::schema::type_registry::{Dialect, TypeRegistry};
@@ -121,6 +122,18 @@ impl Sink for PostgresSink {
         Ok(count)
     }
 
+    async fn ping(&self) -> Result<(), DriverError> {
+        self.driver.execute("SELECT 1").await
+    }
+
+    async fn execute(&self, sql: &str) -> Result<(), DriverError> {
+        self.driver.execute(sql).await
+    }
+
+    async fn table_metadata(&self, table: &str) -> Result<Option<TableMetadata>, DriverError> {
+        self.driver.table_metadata(table).await.map(Some)
+    }
+
     async fn support_fast_path(&self) -> Result<bool, SinkError> {
         let capabilities = self.driver.capabilities();
         // Fast path requires COPY protocol support
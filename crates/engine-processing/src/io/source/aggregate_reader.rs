@@ -0,0 +1,140 @@
+use crate::{io::source::reader::SourceReader, transform::aggregate::Aggregator};
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    execution::pipeline::Aggregation,
+    pagination::{cursor::Cursor, page::FetchResult},
+    records::Record,
+};
+use std::{sync::Arc, time::Instant};
+use tokio::sync::OnceCell;
+
+/// Reads a source whose rows cannot be grouped where they are stored: the
+/// first fetch reads every row of `inner` and groups them, and the groups
+/// are then paged out by offset.
+pub struct AggregatingReader {
+    inner: Arc<dyn SourceReader>,
+    aggregation: Aggregation,
+    table: String,
+    groups: OnceCell<Vec<Record>>,
+}
+
+impl AggregatingReader {
+    pub fn new(
+        inner: Arc<dyn SourceReader>,
+        aggregation: Aggregation,
+        table: impl Into<String>,
+    ) -> Self {
+        Self {
+            inner,
+            aggregation,
+            table: table.into(),
+            groups: OnceCell::new(),
+        }
+    }
+
+    async fn read_groups(&self, batch_size: usize) -> Result<Vec<Record>, DriverError> {
+        let mut aggregator = Aggregator::new(self.aggregation.clone(), self.table.clone());
+        let mut cursor = Cursor::None;
+        loop {
+            let page = self.inner.fetch(batch_size, cursor).await?;
+            for row in &page.rows {
+                aggregator
+                    .push(row)
+                    .map_err(|e| DriverError::QueryError(e.to_string()))?;
+            }
+            match page.next_cursor {
+                Some(next) if !page.reached_end && next != Cursor::None => cursor = next,
+                _ => break,
+            }
+        }
+        Ok(aggregator.finish())
+    }
+}
+
+#[async_trait]
+impl SourceReader for AggregatingReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let started = Instant::now();
+        let groups = self
+            .groups
+            .get_or_try_init(|| self.read_groups(batch_size))
+            .await?;
+
+        let offset = match cursor {
+            Cursor::Default { offset } => offset,
+            _ => 0,
+        };
+        let rows: Vec<Record> = groups
+            .iter()
+            .skip(offset)
+            .take(batch_size)
+            .cloned()
+            .collect();
+        let end = offset + rows.len();
+        let reached_end = end >= groups.len();
+
+        Ok(FetchResult {
+            row_count: rows.len(),
+            rows,
+            next_cursor: (!reached_end).then_some(Cursor::Default { offset: end }),
+            reached_end,
+            took_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::source::test_support::{self, PagedRows};
+    use model::{
+        core::value::Value,
+        execution::pipeline::{Aggregate, AggregateFunction},
+        pagination::cursor::QualCol,
+    };
+
+    fn row(customer: i64, amount: i64) -> Record {
+        test_support::row(
+            "orders",
+            vec![
+                ("customer_id", Value::Int(customer)),
+                ("amount", Value::Int(amount)),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_aggregating_reader_pages_groups() {
+        let col = |column: &str| QualCol {
+            table: "orders".to_string(),
+            column: column.to_string(),
+        };
+        let source = PagedRows(vec![row(1, 5), row(2, 7), row(1, 5), row(3, 1), row(2, 1)]);
+        let reader = AggregatingReader::new(
+            Arc::new(source),
+            Aggregation {
+                group_by: vec![col("customer_id")],
+                aggregates: vec![Aggregate {
+                    name: "total".to_string(),
+                    function: AggregateFunction::Sum,
+                    column: Some(col("amount")),
+                }],
+            },
+            "orders",
+        );
+
+        let first = reader.fetch(2, Cursor::None).await.unwrap();
+        assert_eq!(first.row_count, 2);
+        assert!(!first.reached_end);
+        assert_eq!(first.rows[0].get_value("total"), Value::Int(10));
+        assert_eq!(first.rows[1].get_value("total"), Value::Int(8));
+
+        let second = reader.fetch(2, first.next_cursor.unwrap()).await.unwrap();
+        assert_eq!(second.row_count, 1);
+        assert!(second.reached_end);
+        assert_eq!(second.next_cursor, None);
+        assert_eq!(second.rows[0].get_value("customer_id"), Value::Int(3));
+        assert_eq!(second.rows[0].get_value("total"), Value::Int(1));
+    }
+}
@@ -12,11 +12,12 @@ use connectors::{
         metadata::table::TableMetadata,
         request::{FetchRowsRequest, FetchRowsRequestBuilder},
     },
-    traits::reader::DataReader,
+    traits::{cursor::RowCursor, reader::DataReader},
 };
 use futures::future;
 use model::{
     core::value::Value,
+    execution::pipeline::Aggregation,
     pagination::{cursor::Cursor, page::FetchResult},
     records::Record,
 };
@@ -26,6 +27,7 @@ use std::{
     sync::Arc,
     time::Instant,
 };
+use tokio::sync::Mutex;
 
 pub struct DbSourceReader {
     /// The underlying DataReader for fetching rows from the source database.
@@ -49,6 +51,25 @@ pub struct DbSourceReader {
 
     /// The offset strategy to use for pagination.
     offset_strategy: Arc<dyn OffsetStrategy>,
+
+    /// Open server-side cursor over the primary table, when the offset
+    /// strategy reads through one. Declared lazily on the first fetch.
+    server_cursor: Mutex<Option<Box<dyn RowCursor>>>,
+
+    /// Binary columns of the primary table read as NULL over the
+    /// threshold, and the threshold in bytes
+    lob_columns: Option<(Vec<String>, usize)>,
+
+    /// Comment text every fetch query starts with, naming the run and
+    /// pipeline for DBAs
+    query_tag: Option<String>,
+
+    /// Grouping the primary table is read with, one row per group
+    aggregate: Option<Aggregation>,
+
+    /// Name the primary table is queried under when it is read in place of
+    /// the pipeline's source table, as a later table of a union is
+    alias: Option<String>,
 }
 
 impl DbSourceReader {
@@ -66,6 +87,11 @@ impl DbSourceReader {
             filter,
             cascade_joins: HashMap::new(),
             offset_strategy,
+            server_cursor: Mutex::new(None),
+            lob_columns: None,
+            query_tag: None,
+            aggregate: None,
+            alias: None,
         }
     }
 
@@ -83,6 +109,28 @@ impl DbSourceReader {
         self.related_meta = related;
     }
 
+    /// Read values of the primary table's `columns` over `threshold` bytes
+    /// as NULL, to be streamed separately.
+    pub fn set_lob_columns(&mut self, columns: Vec<String>, threshold: usize) {
+        self.lob_columns = Some((columns, threshold));
+    }
+
+    /// Start every fetch query with a `/* tag */` comment.
+    pub fn set_query_tag(&mut self, tag: String) {
+        self.query_tag = Some(tag);
+    }
+
+    /// Read the primary table grouped by the database, one row per group.
+    pub fn set_aggregate(&mut self, aggregation: Aggregation) {
+        self.aggregate = Some(aggregation);
+    }
+
+    /// Query the primary table as `alias`, so that filters, joins and
+    /// cursor columns naming the pipeline's source table apply to it.
+    pub fn set_alias(&mut self, alias: String) {
+        self.alias = Some(alias);
+    }
+
     /// Build a request for ANY table.  If `include_join_fields` is true,
     /// we also merge in `join.fields()` (used only for the primary table).
     fn build_request_for(
@@ -94,8 +142,19 @@ impl DbSourceReader {
         cursor: Cursor,
         include_join_fields: bool,
     ) -> FetchRowsRequest {
+        let alias = self
+            .alias
+            .as_deref()
+            .filter(|_| include_join_fields)
+            .unwrap_or(table);
+
         // base columns
         let mut columns = meta.select_fields();
+        if alias != table {
+            for column in &mut columns {
+                column.table = alias.to_string();
+            }
+        }
 
         // optionally merge in the JoinSource's extra fields
         if include_join_fields && let Some(join_source) = &self.join {
@@ -103,17 +162,26 @@ impl DbSourceReader {
         }
 
         // optional filter scoped to this table + these clauses
-        let filter_clause = self.filter.as_ref().map(|f| f.for_table(table, joins));
+        let filter_clause = self.filter.as_ref().map(|f| f.for_table(alias, joins));
 
-        FetchRowsRequestBuilder::new(table.to_string())
-            .alias(table.to_string())
+        let mut builder = FetchRowsRequestBuilder::new(table.to_string())
+            .alias(alias.to_string())
             .columns(columns)
             .joins(joins.to_vec())
             .filter(filter_clause)
             .limit(batch_size)
             .cursor(cursor)
             .strategy(self.offset_strategy.clone())
-            .build()
+            .tag(self.query_tag.clone());
+
+        if include_join_fields && let Some((lob_columns, threshold)) = &self.lob_columns {
+            builder = builder.lob_columns(lob_columns.clone(), *threshold);
+        }
+        if include_join_fields {
+            builder = builder.aggregate(self.aggregate.clone());
+        }
+
+        builder.build()
     }
 
     /// Build all requests: primary with join-fields, then the related ones without them.
@@ -321,7 +389,8 @@ impl DbSourceReader {
             .filter(filter_clause)
             .limit(i64::MAX as usize)
             .cursor(Cursor::None)
-            .strategy(self.offset_strategy.clone());
+            .strategy(self.offset_strategy.clone())
+            .tag(self.query_tag.clone());
 
         if let Some((col, values)) = in_clause {
             builder = builder.in_clause(col, values);
@@ -397,9 +466,9 @@ impl DbSourceReader {
         cursor: Cursor,
     ) -> Result<(Vec<Record>, Option<Cursor>, bool), DriverError> {
         let primary_req = self.build_primary_only_request(batch_size, cursor.clone());
-        let primary_rows = self.reader.fetch(primary_req).await?;
+        let primary_rows = self.fetch_primary(primary_req).await?;
 
-        let reached_end = primary_rows.len() < batch_size;
+        let exhausted = primary_rows.len() < batch_size;
         let primary_last_row = primary_rows.last().cloned();
 
         let primary_name = self
@@ -463,7 +532,8 @@ impl DbSourceReader {
         let rows: Vec<Record> = all_fetched.into_values().flatten().collect();
 
         let next_cursor =
-            self.compute_next_cursor(primary_last_row.as_ref(), &cursor, batch_size, reached_end);
+            self.compute_next_cursor(primary_last_row.as_ref(), &cursor, batch_size, exhausted);
+        let reached_end = exhausted && next_cursor.is_none();
 
         Ok((rows, next_cursor, reached_end))
     }
@@ -474,7 +544,16 @@ impl DbSourceReader {
         cursor: Cursor,
     ) -> Result<(Vec<Record>, Option<Cursor>, bool), DriverError> {
         let requests = self.build_fetch_rows_requests(batch_size, cursor.clone());
-        let futures = requests.into_iter().map(|req| self.reader.fetch(req));
+        let futures = requests
+            .into_iter()
+            .enumerate()
+            .map(|(idx, req)| async move {
+                if idx == 0 {
+                    self.fetch_primary(req).await
+                } else {
+                    self.reader.fetch(req).await
+                }
+            });
         let results = future::join_all(futures).await;
 
         let mut rows = Vec::new();
@@ -491,24 +570,57 @@ impl DbSourceReader {
         }
 
         let reference_count = primary_rows_count.unwrap_or(rows.len());
-        let reached_end = reference_count < batch_size;
+        let exhausted = reference_count < batch_size;
         let last_row = primary_last_row.or_else(|| rows.last().cloned());
 
         let next_cursor =
-            self.compute_next_cursor(last_row.as_ref(), &cursor, batch_size, reached_end);
+            self.compute_next_cursor(last_row.as_ref(), &cursor, batch_size, exhausted);
+        let reached_end = exhausted && next_cursor.is_none();
 
         Ok((rows, next_cursor, reached_end))
     }
 
+    /// Fetch a page of the primary table, through the server-side cursor when
+    /// the offset strategy uses one.
+    async fn fetch_primary(&self, request: FetchRowsRequest) -> Result<Vec<Record>, DriverError> {
+        if !self.offset_strategy.uses_server_cursor() {
+            return self.reader.fetch(request).await;
+        }
+
+        let mut slot = self.server_cursor.lock().await;
+        let page_size = request.limit;
+
+        // The request's cursor only matters when declaring: it scopes the
+        // query past the last checkpointed key. An open cursor just continues.
+        let mut cursor = match slot.take() {
+            Some(cursor) => cursor,
+            None => self.reader.open_cursor(request).await?,
+        };
+
+        // On error the cursor is dropped (its transaction is aborted anyway);
+        // a retry re-declares it from the same checkpointed key.
+        let rows = cursor.fetch_next(page_size).await?;
+
+        if rows.len() < page_size {
+            cursor.close().await?;
+        } else {
+            *slot = Some(cursor);
+        }
+
+        Ok(rows)
+    }
+
     fn compute_next_cursor(
         &self,
         last_row: Option<&Record>,
         current_cursor: &Cursor,
         batch_size: usize,
-        reached_end: bool,
+        exhausted: bool,
     ) -> Option<Cursor> {
-        if reached_end {
-            return None;
+        // An exhausted page ends the scan unless the strategy has another
+        // partition to read (e.g. NULL cursor values).
+        if exhausted {
+            return self.offset_strategy.next_partition(current_cursor);
         }
 
         last_row.map(|row| {
@@ -530,6 +642,48 @@ impl DbSourceReader {
 
 #[async_trait]
 impl SourceReader for DbSourceReader {
+    async fn key_bounds(&self, column: &str) -> Result<Option<(i64, i64)>, DriverError> {
+        let meta = self.primary_meta.as_ref().ok_or_else(|| {
+            DriverError::QueryError("key bounds require the source table metadata".to_string())
+        })?;
+        self.reader.key_bounds(&meta.name, column).await
+    }
+
+    async fn key_split_points(
+        &self,
+        column: &str,
+        partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        let meta = self.primary_meta.as_ref().ok_or_else(|| {
+            DriverError::QueryError(
+                "key split points require the source table metadata".to_string(),
+            )
+        })?;
+        self.reader
+            .key_split_points(&meta.name, column, partitions)
+            .await
+    }
+
+    fn with_offset_strategy(
+        &self,
+        offset_strategy: Arc<dyn OffsetStrategy>,
+    ) -> Option<Arc<dyn SourceReader>> {
+        Some(Arc::new(DbSourceReader {
+            reader: self.reader.clone(),
+            primary_meta: self.primary_meta.clone(),
+            related_meta: self.related_meta.clone(),
+            join: self.join.clone(),
+            filter: self.filter.clone(),
+            cascade_joins: self.cascade_joins.clone(),
+            offset_strategy,
+            server_cursor: Mutex::new(None),
+            lob_columns: self.lob_columns.clone(),
+            query_tag: self.query_tag.clone(),
+            aggregate: self.aggregate.clone(),
+            alias: self.alias.clone(),
+        }))
+    }
+
     async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
         let start = Instant::now();
 
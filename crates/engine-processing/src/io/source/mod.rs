@@ -6,30 +6,50 @@ use crate::io::{
     },
     format::DataFormat,
     linked::LinkedSource,
-    source::{db_reader::DbSourceReader, reader::SourceReader, wasm_reader::WasmSourceReader},
+    lob::LobPlan,
+    source::{
+        aggregate_reader::AggregatingReader,
+        db_reader::DbSourceReader,
+        pivot_reader::{PivotingReader, UnpivotingReader},
+        reader::SourceReader,
+        sort_reader::SortingReader,
+        union_reader::UnionReader,
+        unnest_reader::UnnestingReader,
+        wasm_reader::WasmSourceReader,
+    },
 };
 use connectors::{
     error::DriverError,
     sql::metadata::table::TableMetadata,
     traits::{introspector::SchemaIntrospector, reader::DataReader},
 };
+use engine_core::plan::settings::effective_value;
 use engine_wasm::runtime::instance::PluginInstance;
 use model::{
-    execution::pipeline::Pipeline,
+    core::value::Value,
+    execution::pipeline::{Aggregation, Pipeline, PivotDirection},
     pagination::{cursor::Cursor, page::FetchResult},
     transform::mapping::TransformationMetadata,
 };
-use query_builder::{
-    dialect::{self, Dialect},
-    offsets::OffsetStrategy,
-};
+use query_builder::offsets::OffsetStrategy;
 use std::{collections::HashMap, sync::Arc};
 
+pub mod aggregate_reader;
 pub mod db_reader;
+pub mod pivot_reader;
 pub mod plugin_introspector;
 pub mod reader;
+pub mod sort_reader;
+#[cfg(test)]
+mod test_support;
+pub mod union_reader;
+pub mod unnest_reader;
 pub mod wasm_reader;
 
+const SETTING_SORT_BUFFER_ROWS: &str = "sort_buffer_rows";
+/// The setting's default, for a value of another type
+const DEFAULT_SORT_BUFFER_ROWS: usize = 100_000;
+
 #[derive(Clone)]
 pub struct Source {
     pub name: String,
@@ -37,6 +57,11 @@ pub struct Source {
     pub primary: Arc<dyn SourceReader>,
     pub linked: Option<LinkedSource>,
     pub filter: Option<Filter>,
+    /// Binary columns streamed outside the batches, when `lob_threshold` is set
+    pub lob: Option<LobPlan>,
+    /// Primary key columns of the source table; empty when it has none or
+    /// its metadata could not be read
+    pub primary_keys: Vec<String>,
 }
 
 impl Source {
@@ -55,7 +80,17 @@ impl Source {
     pub fn from_plugin(plugin: PluginInstance, pipeline: &Pipeline) -> Result<Self, DriverError> {
         let name = pipeline.source.table.clone();
         let format = DataFormat::Wasm;
-        let reader = Arc::new(WasmSourceReader::new(plugin, name.clone()));
+        let mut reader: Arc<dyn SourceReader> =
+            Arc::new(WasmSourceReader::new(plugin, name.clone()));
+        // Plugins cannot group their rows; they are grouped as they are read
+        if let Some(aggregation) = &pipeline.aggregate {
+            reader = Arc::new(AggregatingReader::new(
+                reader,
+                aggregation.clone(),
+                name.clone(),
+            ));
+        }
+        let reader = sorted(reshaped(reader, pipeline, &name), pipeline);
 
         Ok(Source {
             name,
@@ -63,6 +98,8 @@ impl Source {
             primary: reader,
             linked: None, // joins not supported for WASM sources
             filter: None, // filter pushdown not supported; rely on validate{} rules
+            lob: None,
+            primary_keys: Vec::new(),
         })
     }
 
@@ -82,6 +119,14 @@ impl Source {
             DriverError::UnsupportedFormat(pipeline.source.connection.driver.clone())
         })?;
 
+        let strategy = offset_strategy.name();
+        if !driver.capabilities().supports_pagination(&strategy) {
+            return Err(DriverError::UnsupportedDriver(format!(
+                "{} sources do not support the '{strategy}' pagination strategy",
+                driver.info().name
+            )));
+        }
+
         let linked =
             LinkedSource::new(driver.clone(), &format, &pipeline.source.joins, mapping).await?;
         let filter = Self::create_filter(pipeline, &format)?;
@@ -89,24 +134,70 @@ impl Source {
         // Fetch primary table metadata upfront so the reader always knows which
         // columns to select, even for simple (non-cascade) pipelines.
         let primary_meta = driver.table_metadata(&name).await.ok();
+        // Grouped, pivoted or unnested rows have neither the source's large
+        // objects nor its keys
+        let grouped =
+            pipeline.aggregate.is_some() || pipeline.pivot.is_some() || pipeline.unnest.is_some();
+        let lob = primary_meta
+            .as_ref()
+            .filter(|_| !grouped)
+            .and_then(|meta| LobPlan::new(pipeline, meta));
+        let primary_keys = primary_meta
+            .as_ref()
+            .filter(|_| !grouped)
+            .map(|meta| meta.primary_keys.clone())
+            .unwrap_or_default();
 
-        let primary = Self::build_primary_reader(
+        let mut primary = Self::build_primary_reader(
             &name,
             &format,
-            driver,
+            driver.clone(),
             &linked,
             &filter,
-            offset_strategy,
+            offset_strategy.clone(),
             cascade_meta,
             primary_meta,
+            lob.as_ref(),
+            pipeline.query_tag(),
+            pipeline.aggregate.clone(),
+            None,
         )?;
 
+        // The tables of later `from` blocks are read after this one, each
+        // queried under its name
+        let source = &pipeline.source;
+        if !source.union.is_empty() || source.source_column.is_some() {
+            let mut tables = vec![(name.clone(), primary)];
+            for table in &source.union {
+                let meta = driver.table_metadata(table).await?;
+                let reader = Self::build_primary_reader(
+                    table,
+                    &format,
+                    driver.clone(),
+                    &linked,
+                    &filter,
+                    offset_strategy.clone(),
+                    None,
+                    Some(meta),
+                    lob.as_ref(),
+                    pipeline.query_tag(),
+                    None,
+                    Some(&name),
+                )?;
+                tables.push((table.clone(), reader));
+            }
+            primary = Arc::new(UnionReader::new(tables, source.source_column.clone()));
+        }
+        let primary = sorted(reshaped(primary, pipeline, &name), pipeline);
+
         Ok(Source {
             name,
             format,
             primary,
             linked,
             filter,
+            lob,
+            primary_keys,
         })
     }
 
@@ -118,6 +209,31 @@ impl Source {
         self.primary.fetch(batch_size, cursor).await
     }
 
+    /// Smallest and largest value of the integer key `column` of the source table.
+    pub async fn key_bounds(&self, column: &str) -> Result<Option<(i64, i64)>, DriverError> {
+        self.primary.key_bounds(column).await
+    }
+
+    /// Keys splitting the integer key `column` of the source table into
+    /// `partitions` ranges of about the same number of rows.
+    pub async fn key_split_points(
+        &self,
+        column: &str,
+        partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        self.primary.key_split_points(column, partitions).await
+    }
+
+    /// The same source paged by `offset_strategy`, for reading one key range
+    /// of a partitioned load.
+    pub fn with_offset_strategy(&self, offset_strategy: Arc<dyn OffsetStrategy>) -> Option<Self> {
+        let primary = self.primary.with_offset_strategy(offset_strategy)?;
+        Some(Source {
+            primary,
+            ..self.clone()
+        })
+    }
+
     pub fn format(&self) -> DataFormat {
         self.format
     }
@@ -126,14 +242,6 @@ impl Source {
         &self.name
     }
 
-    pub fn query_dialect(&self) -> Box<dyn Dialect> {
-        match self.format {
-            DataFormat::MySql => Box::new(dialect::MySql),
-            DataFormat::Postgres => Box::new(dialect::Postgres),
-            _ => panic!("Unsupported dialect for source"),
-        }
-    }
-
     /// Helper to isolate the complex logic of constructing the primary data reader
     #[allow(clippy::too_many_arguments)]
     fn build_primary_reader<D>(
@@ -145,6 +253,10 @@ impl Source {
         offset_strategy: Arc<dyn OffsetStrategy>,
         cascade_meta: Option<HashMap<String, TableMetadata>>,
         primary_meta_fallback: Option<TableMetadata>,
+        lob: Option<&LobPlan>,
+        query_tag: String,
+        aggregate: Option<Aggregation>,
+        alias: Option<&str>,
     ) -> Result<Arc<dyn SourceReader>, DriverError>
     where
         D: DataReader + SchemaIntrospector,
@@ -187,6 +299,17 @@ impl Source {
                     reader.set_primary_meta(meta);
                 }
 
+                if let Some(lob) = lob {
+                    reader.set_lob_columns(lob.source_columns(), lob.threshold);
+                }
+                reader.set_query_tag(query_tag);
+                if let Some(aggregation) = aggregate {
+                    reader.set_aggregate(aggregation);
+                }
+                if let Some(alias) = alias {
+                    reader.set_alias(alias.to_string());
+                }
+
                 Ok(Arc::new(reader))
             }
             _ => Err(DriverError::UnsupportedFormat(format!("{:?}", format))),
@@ -220,3 +343,37 @@ impl Source {
         }
     }
 }
+
+/// `reader` wrapped in the reader that reshapes its rows for the pipeline's
+/// pivot block or unnest() call, if it has one.
+fn reshaped(
+    reader: Arc<dyn SourceReader>,
+    pipeline: &Pipeline,
+    table: &str,
+) -> Arc<dyn SourceReader> {
+    match &pipeline.pivot {
+        Some(pivot) if pivot.direction == PivotDirection::Pivot => {
+            Arc::new(PivotingReader::new(reader, pivot.clone(), table))
+        }
+        Some(pivot) => Arc::new(UnpivotingReader::new(reader, pivot.clone())),
+        None => match &pipeline.unnest {
+            Some(unnest) => Arc::new(UnnestingReader::new(reader, unnest.clone())),
+            None => reader,
+        },
+    }
+}
+
+/// `reader` wrapped in the reader that sorts its rows by the `to` block's
+/// `order_by` columns, if it has any.
+fn sorted(reader: Arc<dyn SourceReader>, pipeline: &Pipeline) -> Arc<dyn SourceReader> {
+    let order_by = &pipeline.destination.order_by;
+    if order_by.is_empty() {
+        return reader;
+    }
+    let buffer_rows = match effective_value(&pipeline.settings, SETTING_SORT_BUFFER_ROWS) {
+        Ok(Value::Int(n)) if n > 0 => n as usize,
+        Ok(Value::UInt(n)) if n > 0 => n as usize,
+        _ => DEFAULT_SORT_BUFFER_ROWS,
+    };
+    Arc::new(SortingReader::new(reader, order_by.clone(), buffer_rows))
+}
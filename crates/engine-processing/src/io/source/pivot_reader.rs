@@ -0,0 +1,191 @@
+use crate::{
+    io::source::reader::SourceReader,
+    transform::pivot::{Pivoter, unpivot},
+};
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    execution::pipeline::Pivot,
+    pagination::{cursor::Cursor, page::FetchResult},
+    records::Record,
+};
+use std::{sync::Arc, time::Instant};
+use tokio::sync::OnceCell;
+use tracing::warn;
+
+/// Reads key-value rows as wide rows: the first fetch reads every row of
+/// `inner`, since an entity's rows may be anywhere in the source, and the
+/// wide rows are then paged out by offset.
+pub struct PivotingReader {
+    inner: Arc<dyn SourceReader>,
+    pivot: Pivot,
+    table: String,
+    rows: OnceCell<Vec<Record>>,
+}
+
+impl PivotingReader {
+    pub fn new(inner: Arc<dyn SourceReader>, pivot: Pivot, table: impl Into<String>) -> Self {
+        Self {
+            inner,
+            pivot,
+            table: table.into(),
+            rows: OnceCell::new(),
+        }
+    }
+
+    async fn read_rows(&self, batch_size: usize) -> Result<Vec<Record>, DriverError> {
+        let mut pivoter = Pivoter::new(self.pivot.clone(), self.table.clone());
+        let mut cursor = Cursor::None;
+        loop {
+            let page = self.inner.fetch(batch_size, cursor).await?;
+            for row in &page.rows {
+                pivoter
+                    .push(row)
+                    .map_err(|e| DriverError::QueryError(e.to_string()))?;
+            }
+            match page.next_cursor {
+                Some(next) if !page.reached_end && next != Cursor::None => cursor = next,
+                _ => break,
+            }
+        }
+        if pivoter.skipped() > 0 {
+            warn!(
+                table = %self.table,
+                skipped = pivoter.skipped(),
+                keys = ?self.pivot.keys,
+                "pivot skipped rows whose key is not one of the expected keys"
+            );
+        }
+        Ok(pivoter.finish())
+    }
+}
+
+#[async_trait]
+impl SourceReader for PivotingReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let started = Instant::now();
+        let all = self
+            .rows
+            .get_or_try_init(|| self.read_rows(batch_size))
+            .await?;
+
+        let offset = match cursor {
+            Cursor::Default { offset } => offset,
+            _ => 0,
+        };
+        let rows: Vec<Record> = all.iter().skip(offset).take(batch_size).cloned().collect();
+        let end = offset + rows.len();
+        let reached_end = end >= all.len();
+
+        Ok(FetchResult {
+            row_count: rows.len(),
+            rows,
+            next_cursor: (!reached_end).then_some(Cursor::Default { offset: end }),
+            reached_end,
+            took_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+/// Reads wide rows as key-value rows, page by page: each row of a page of
+/// `inner` becomes one row per listed column, so pages can hold more rows
+/// than the batch size.
+pub struct UnpivotingReader {
+    inner: Arc<dyn SourceReader>,
+    pivot: Pivot,
+}
+
+impl UnpivotingReader {
+    pub fn new(inner: Arc<dyn SourceReader>, pivot: Pivot) -> Self {
+        Self { inner, pivot }
+    }
+}
+
+#[async_trait]
+impl SourceReader for UnpivotingReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let mut page = self.inner.fetch(batch_size, cursor).await?;
+        page.rows = page
+            .rows
+            .iter()
+            .flat_map(|row| unpivot(&self.pivot, row))
+            .collect();
+        page.row_count = page.rows.len();
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::source::test_support::{self, PagedRows};
+    use model::{core::value::Value, execution::pipeline::PivotDirection};
+
+    fn row(product: i64, attr: &str, value: &str) -> Record {
+        test_support::row(
+            "product_attrs",
+            vec![
+                ("product_id", Value::Int(product)),
+                ("attr", Value::String(attr.to_string())),
+                ("val", Value::String(value.to_string())),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_pivot_and_unpivot_readers() {
+        let pivot = Pivot {
+            direction: PivotDirection::Pivot,
+            key: "attr".to_string(),
+            value: "val".to_string(),
+            keys: vec!["color".to_string(), "size".to_string()],
+            group_by: Vec::new(),
+        };
+        let source = PagedRows(vec![
+            row(1, "color", "red"),
+            row(2, "size", "S"),
+            row(1, "size", "L"),
+            row(3, "color", "green"),
+        ]);
+        let wide = Arc::new(PivotingReader::new(
+            Arc::new(source),
+            pivot.clone(),
+            "products",
+        ));
+
+        let first = wide.fetch(2, Cursor::None).await.unwrap();
+        assert_eq!(first.row_count, 2);
+        assert_eq!(
+            first.rows[0].get_value("color"),
+            Value::String("red".into())
+        );
+        assert_eq!(first.rows[0].get_value("size"), Value::String("L".into()));
+        let second = wide.fetch(2, first.next_cursor.unwrap()).await.unwrap();
+        assert!(second.reached_end);
+        assert_eq!(second.rows[0].get_value("product_id"), Value::Int(3));
+
+        // Unpivoting the wide rows gives back the non-null key-value rows
+        let long = UnpivotingReader::new(
+            wide,
+            Pivot {
+                direction: PivotDirection::Unpivot,
+                ..pivot
+            },
+        );
+        let page = long.fetch(10, Cursor::None).await.unwrap();
+        let pairs: Vec<_> = page
+            .rows
+            .iter()
+            .map(|r| (r.get_value("product_id"), r.get_value("attr")))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                (Value::Int(1), Value::String("color".into())),
+                (Value::Int(1), Value::String("size".into())),
+                (Value::Int(2), Value::String("size".into())),
+                (Value::Int(3), Value::String("color".into())),
+            ]
+        );
+    }
+}
@@ -61,6 +61,7 @@ impl PluginIntrospector {
         let meta = TableMetadata {
             name: String::new(),
             schema: None,
+            collation: None,
             columns,
             primary_keys: Vec::new(),
             foreign_keys: Vec::new(),
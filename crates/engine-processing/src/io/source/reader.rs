@@ -1,8 +1,38 @@
 use async_trait::async_trait;
 use connectors::error::DriverError;
 use model::pagination::{cursor::Cursor, page::FetchResult};
+use query_builder::offsets::OffsetStrategy;
+use std::sync::Arc;
 
 #[async_trait]
 pub trait SourceReader: Send + Sync {
     async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError>;
+
+    /// Smallest and largest value of the integer `column` of the read table,
+    /// or `None` when it is empty.
+    async fn key_bounds(&self, _column: &str) -> Result<Option<(i64, i64)>, DriverError> {
+        Err(DriverError::UnsupportedDriver(
+            "source does not support key bounds".to_string(),
+        ))
+    }
+
+    /// Keys splitting the integer `column` into `partitions` ranges of about
+    /// the same number of rows, from the table statistics. Empty when the
+    /// source has none.
+    async fn key_split_points(
+        &self,
+        _column: &str,
+        _partitions: usize,
+    ) -> Result<Vec<i64>, DriverError> {
+        Ok(Vec::new())
+    }
+
+    /// A reader of the same rows paged by `offset_strategy`, with its own
+    /// read state. `None` when the reader cannot be re-scoped.
+    fn with_offset_strategy(
+        &self,
+        _offset_strategy: Arc<dyn OffsetStrategy>,
+    ) -> Option<Arc<dyn SourceReader>> {
+        None
+    }
 }
@@ -0,0 +1,174 @@
+use crate::{
+    io::source::reader::SourceReader,
+    transform::sort::{ExternalSorter, SortedRows},
+};
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    pagination::{
+        cursor::{Cursor, QualCol},
+        page::FetchResult,
+    },
+    records::Record,
+};
+use std::{iter::Peekable, sync::Arc, time::Instant};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+/// Reads a source in the order of the `to` block's `order_by` columns: the
+/// first fetch reads every row of `inner` into an [`ExternalSorter`], and
+/// the sorted rows are then paged out by offset. A fetch at another offset
+/// than the one the last page ended at, as on resume, sorts the source
+/// again and skips to it.
+pub struct SortingReader {
+    inner: Arc<dyn SourceReader>,
+    order_by: Vec<QualCol>,
+    buffer_rows: usize,
+    sorted: Mutex<Option<SortState>>,
+}
+
+struct SortState {
+    rows: Peekable<SortedRows>,
+    /// Offset of the next row
+    position: usize,
+}
+
+impl SortingReader {
+    pub fn new(inner: Arc<dyn SourceReader>, order_by: Vec<QualCol>, buffer_rows: usize) -> Self {
+        Self {
+            inner,
+            order_by,
+            buffer_rows,
+            sorted: Mutex::new(None),
+        }
+    }
+
+    async fn sort(&self, batch_size: usize, offset: usize) -> Result<SortState, DriverError> {
+        let mut sorter = ExternalSorter::new(self.order_by.clone(), self.buffer_rows);
+        let mut cursor = Cursor::None;
+        loop {
+            let page = self.inner.fetch(batch_size, cursor).await?;
+            for row in page.rows {
+                sorter.push(row).map_err(query_error)?;
+            }
+            match page.next_cursor {
+                Some(next) if !page.reached_end && next != Cursor::None => cursor = next,
+                _ => break,
+            }
+        }
+        debug!(runs = sorter.spilled_runs(), "sorted source rows");
+
+        let mut rows = sorter.finish().map_err(query_error)?.peekable();
+        for _ in 0..offset {
+            if rows.next().transpose().map_err(query_error)?.is_none() {
+                break;
+            }
+        }
+        Ok(SortState {
+            rows,
+            position: offset,
+        })
+    }
+}
+
+#[async_trait]
+impl SourceReader for SortingReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let started = Instant::now();
+        let offset = match cursor {
+            Cursor::Default { offset } => offset,
+            _ => 0,
+        };
+
+        let mut sorted = self.sorted.lock().await;
+        let mut state = match sorted.take() {
+            Some(state) if state.position == offset => state,
+            _ => self.sort(batch_size, offset).await?,
+        };
+
+        let mut rows: Vec<Record> = Vec::with_capacity(batch_size);
+        while rows.len() < batch_size {
+            match state.rows.next() {
+                Some(row) => rows.push(row.map_err(query_error)?),
+                None => break,
+            }
+        }
+        state.position += rows.len();
+        let end = state.position;
+        // The spilled runs are dropped once every row was read
+        let reached_end = state.rows.peek().is_none();
+        if !reached_end {
+            *sorted = Some(state);
+        }
+
+        Ok(FetchResult {
+            row_count: rows.len(),
+            rows,
+            next_cursor: (!reached_end).then_some(Cursor::Default { offset: end }),
+            reached_end,
+            took_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+fn query_error(e: impl ToString) -> DriverError {
+    DriverError::QueryError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::source::test_support::{self, PagedRows};
+    use model::core::value::Value;
+
+    fn row(id: i64, created_at: i64) -> Record {
+        test_support::row(
+            "events",
+            vec![
+                ("id", Value::Int(id)),
+                ("created_at", Value::Int(created_at)),
+            ],
+        )
+    }
+
+    fn ids(page: &FetchResult) -> Vec<Value> {
+        page.rows.iter().map(|r| r.get_value("id")).collect()
+    }
+
+    #[tokio::test]
+    async fn test_sorting_reader_pages_sorted_rows() {
+        let source = PagedRows(vec![
+            row(1, 30),
+            row(2, 10),
+            row(3, 50),
+            row(4, 20),
+            row(5, 40),
+        ]);
+        let order_by = vec![QualCol {
+            table: "events".to_string(),
+            column: "created_at".to_string(),
+        }];
+        // Two rows a run, so the source is spilled and merged
+        let reader = SortingReader::new(Arc::new(source), order_by, 2);
+
+        let first = reader.fetch(3, Cursor::None).await.unwrap();
+        assert_eq!(
+            ids(&first),
+            vec![Value::Int(2), Value::Int(4), Value::Int(1)]
+        );
+        assert!(!first.reached_end);
+
+        let second = reader.fetch(3, first.next_cursor.unwrap()).await.unwrap();
+        assert_eq!(ids(&second), vec![Value::Int(5), Value::Int(3)]);
+        assert!(second.reached_end);
+        assert_eq!(second.next_cursor, None);
+
+        // Resuming at an offset sorts again and skips the rows before it
+        let resumed = reader
+            .fetch(3, Cursor::Default { offset: 4 })
+            .await
+            .unwrap();
+        assert_eq!(ids(&resumed), vec![Value::Int(3)]);
+        assert!(resumed.reached_end);
+    }
+}
@@ -0,0 +1,46 @@
+//! Fixtures shared by the tests of the readers that wrap another source.
+
+use crate::io::source::reader::SourceReader;
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    core::value::{FieldValue, Value},
+    pagination::{cursor::Cursor, page::FetchResult},
+    records::{OpType, Record},
+};
+
+/// Source read two rows a page with opaque cursors, like a plugin
+pub(super) struct PagedRows(pub Vec<Record>);
+
+#[async_trait]
+impl SourceReader for PagedRows {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let offset = match cursor {
+            Cursor::Opaque(s) => s.parse().unwrap(),
+            _ => 0,
+        };
+        let rows: Vec<Record> = self.0.iter().skip(offset).take(2).cloned().collect();
+        assert!(rows.len() <= batch_size);
+        let end = offset + rows.len();
+        Ok(FetchResult {
+            row_count: rows.len(),
+            rows,
+            next_cursor: Some(Cursor::Opaque(end.to_string())),
+            reached_end: end >= self.0.len(),
+            took_ms: 0,
+        })
+    }
+}
+
+/// An inserted row of `table` holding `fields`
+pub(super) fn row(table: &str, fields: Vec<(&str, Value)>) -> Record {
+    let fields = fields
+        .into_iter()
+        .map(|(name, value)| FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        })
+        .collect();
+    Record::new(table, fields, OpType::Insert)
+}
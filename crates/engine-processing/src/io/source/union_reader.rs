@@ -0,0 +1,193 @@
+use crate::io::source::reader::SourceReader;
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    core::value::{FieldValue, Value},
+    pagination::{cursor::Cursor, page::FetchResult},
+};
+use std::{sync::Arc, time::Instant};
+
+/// Reads several tables with the same columns one after another, as one
+/// source. Rows of every table carry the first table's name as their
+/// schema, so they are mapped like its rows, and `source_column`, when set,
+/// is added to each row with the name of the table it was read from.
+pub struct UnionReader {
+    tables: Vec<(String, Arc<dyn SourceReader>)>,
+    source_column: Option<String>,
+}
+
+impl UnionReader {
+    /// `tables` are read in order; the first one names the rows.
+    pub fn new(
+        tables: Vec<(String, Arc<dyn SourceReader>)>,
+        source_column: Option<String>,
+    ) -> Self {
+        Self {
+            tables,
+            source_column,
+        }
+    }
+}
+
+#[async_trait]
+impl SourceReader for UnionReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let started = Instant::now();
+        let schema = self
+            .tables
+            .first()
+            .map(|(table, _)| table.clone())
+            .unwrap_or_default();
+        let (mut index, mut cursor) = match cursor {
+            Cursor::Union { table, inner } => (table, *inner),
+            other => (0, other),
+        };
+
+        while let Some((table, reader)) = self.tables.get(index) {
+            let mut page = reader.fetch(batch_size, cursor).await?;
+            for row in &mut page.rows {
+                row.schema = schema.clone();
+                if let Some(column) = &self.source_column {
+                    let value = Value::String(table.clone());
+                    row.fields.retain(|f| !f.name.eq_ignore_ascii_case(column));
+                    row.fields.push(FieldValue {
+                        name: column.clone(),
+                        data_type: value.data_type(),
+                        value: Some(value),
+                    });
+                }
+            }
+
+            let exhausted = page.reached_end || page.next_cursor.is_none();
+            if exhausted && index + 1 < self.tables.len() {
+                // The next table starts where this one ends; an empty last
+                // page is skipped rather than returned
+                index += 1;
+                cursor = Cursor::None;
+                if page.rows.is_empty() {
+                    continue;
+                }
+                page.next_cursor = Some(Cursor::Union {
+                    table: index,
+                    inner: Box::new(Cursor::None),
+                });
+                page.reached_end = false;
+            } else {
+                page.next_cursor = page.next_cursor.map(|next| Cursor::Union {
+                    table: index,
+                    inner: Box::new(next),
+                });
+            }
+            page.took_ms = started.elapsed().as_millis();
+            return Ok(page);
+        }
+
+        Ok(FetchResult {
+            rows: Vec::new(),
+            next_cursor: None,
+            reached_end: true,
+            row_count: 0,
+            took_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::records::{OpType, Record};
+
+    /// Table read two rows a page with default offsets
+    struct PagedRows(String, usize);
+
+    #[async_trait]
+    impl SourceReader for PagedRows {
+        async fn fetch(
+            &self,
+            _batch_size: usize,
+            cursor: Cursor,
+        ) -> Result<FetchResult, DriverError> {
+            let offset = match cursor {
+                Cursor::Default { offset } => offset,
+                _ => 0,
+            };
+            let rows: Vec<Record> = (offset..self.1.min(offset + 2))
+                .map(|id| {
+                    Record::new(
+                        &self.0,
+                        vec![FieldValue {
+                            name: "id".to_string(),
+                            data_type: Value::Int(0).data_type(),
+                            value: Some(Value::Int(id as i64)),
+                        }],
+                        OpType::Insert,
+                    )
+                })
+                .collect();
+            let end = offset + rows.len();
+            Ok(FetchResult {
+                row_count: rows.len(),
+                next_cursor: (!rows.is_empty()).then_some(Cursor::Default { offset: end }),
+                rows,
+                reached_end: end >= self.1,
+                took_ms: 0,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_union_reader_reads_tables_in_turn() {
+        let table = |name: &str, rows: usize| -> (String, Arc<dyn SourceReader>) {
+            (
+                name.to_string(),
+                Arc::new(PagedRows(name.to_string(), rows)),
+            )
+        };
+        let reader = UnionReader::new(
+            vec![
+                table("orders_2021", 3),
+                table("orders_2022", 0),
+                table("orders_2023", 1),
+            ],
+            Some("source_table".to_string()),
+        );
+
+        let mut cursor = Cursor::None;
+        let mut rows = Vec::new();
+        loop {
+            let page = reader.fetch(2, cursor).await.unwrap();
+            rows.extend(page.rows);
+            match page.next_cursor {
+                Some(next) if !page.reached_end => cursor = next,
+                _ => break,
+            }
+        }
+
+        let read: Vec<_> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r.schema.as_str(),
+                    r.get_value("id"),
+                    r.get_value("source_table"),
+                )
+            })
+            .collect();
+        let row = |id: i64, table: &str| {
+            (
+                "orders_2021",
+                Value::Int(id),
+                Value::String(table.to_string()),
+            )
+        };
+        assert_eq!(
+            read,
+            [
+                row(0, "orders_2021"),
+                row(1, "orders_2021"),
+                row(2, "orders_2021"),
+                row(0, "orders_2023"),
+            ]
+        );
+    }
+}
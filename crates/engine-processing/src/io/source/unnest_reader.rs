@@ -0,0 +1,36 @@
+use crate::{io::source::reader::SourceReader, transform::unnest::unnest};
+use async_trait::async_trait;
+use connectors::error::DriverError;
+use model::{
+    execution::pipeline::Unnest,
+    pagination::{cursor::Cursor, page::FetchResult},
+};
+use std::sync::Arc;
+
+/// Reads rows with one column split into one row per element, page by page:
+/// each row of a page of `inner` becomes as many rows as the column has
+/// elements, so pages can hold more rows than the batch size.
+pub struct UnnestingReader {
+    inner: Arc<dyn SourceReader>,
+    unnest: Unnest,
+}
+
+impl UnnestingReader {
+    pub fn new(inner: Arc<dyn SourceReader>, unnest: Unnest) -> Self {
+        Self { inner, unnest }
+    }
+}
+
+#[async_trait]
+impl SourceReader for UnnestingReader {
+    async fn fetch(&self, batch_size: usize, cursor: Cursor) -> Result<FetchResult, DriverError> {
+        let mut page = self.inner.fetch(batch_size, cursor).await?;
+        page.rows = page
+            .rows
+            .iter()
+            .flat_map(|row| unnest(&self.unnest, row))
+            .collect();
+        page.row_count = page.rows.len();
+        Ok(page)
+    }
+}
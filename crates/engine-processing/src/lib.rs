@@ -5,6 +5,7 @@ pub mod error;
 pub mod hooks;
 pub mod io;
 pub mod item;
+pub mod partition;
 pub mod producer;
 pub mod retry;
 pub mod state_manager;
@@ -0,0 +1,360 @@
+use model::pagination::cursor::{Cursor, QualCol};
+use query_builder::offsets::{OffsetStrategy, RangeOffset};
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard};
+use tracing::info;
+
+/// One primary key range `[start, end)` of a partitioned load, read by a
+/// single worker. `end` can be lowered by [`PartitionScheduler`] to hand the
+/// tail of the range to an idle worker.
+#[derive(Debug)]
+pub struct KeyRange {
+    id: usize,
+    start: u64,
+    end: Arc<AtomicU64>,
+    /// First key not read yet
+    next: AtomicU64,
+    started: Instant,
+    /// Held across each fetch, so the range is never split while a page of
+    /// it is in flight.
+    gate: AsyncMutex<()>,
+    done: AtomicBool,
+    /// Never split, so its checkpoints stay valid across runs
+    fixed: bool,
+}
+
+impl KeyRange {
+    fn new(id: usize, start: u64, end: u64) -> Self {
+        Self {
+            id,
+            start,
+            end: Arc::new(AtomicU64::new(end)),
+            next: AtomicU64::new(start),
+            started: Instant::now(),
+            gate: AsyncMutex::new(()),
+            done: AtomicBool::new(false),
+            fixed: false,
+        }
+    }
+
+    /// A range read on its own rather than through a scheduler, such as a
+    /// work item of a distributed run. Its end never moves, so it resumes
+    /// from its checkpoint.
+    pub fn fixed(id: usize, start: u64, end: u64) -> Self {
+        Self {
+            fixed: true,
+            ..Self::new(id, start, end)
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn part_id(&self) -> String {
+        format!("part-{}", self.id)
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end.load(Ordering::Acquire)
+    }
+
+    /// Cursor the range is read from: just below its first key.
+    pub fn start_cursor(&self, pk: &QualCol) -> Cursor {
+        match self.start {
+            0 => Cursor::None,
+            start => Cursor::Pk {
+                pk_col: pk.clone(),
+                id: start - 1,
+            },
+        }
+    }
+
+    /// PK strategy bounded by the (shrinkable) end of this range.
+    pub fn offset_strategy(&self, pk: &QualCol) -> Arc<dyn OffsetStrategy> {
+        Arc::new(RangeOffset {
+            pk: pk.clone(),
+            end: self.end.clone(),
+        })
+    }
+
+    /// Locks the range for one fetch.
+    pub async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.gate.lock().await
+    }
+
+    /// Records the cursor a fetch ended on; the range is done once there is
+    /// no next page.
+    pub fn advance(&self, next: Option<&Cursor>) {
+        match next {
+            Some(Cursor::Pk { id, .. }) => self.next.store(id + 1, Ordering::Release),
+            _ => self.done.store(true, Ordering::Release),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// Keys left between the read position and the end of the range.
+    pub fn remaining(&self) -> u64 {
+        if self.is_done() {
+            return 0;
+        }
+        self.end().saturating_sub(self.next.load(Ordering::Acquire))
+    }
+
+    /// Time since the range was claimed.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Keys covered per second since the range was claimed.
+    pub fn keys_per_sec(&self) -> f64 {
+        let covered = self.next.load(Ordering::Acquire).saturating_sub(self.start);
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            covered as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimated seconds until the range is read; infinite for a range
+    /// that has not made progress yet.
+    fn eta_secs(&self) -> f64 {
+        match self.keys_per_sec() {
+            rate if rate > 0.0 => self.remaining() as f64 / rate,
+            _ => f64::INFINITY,
+        }
+    }
+}
+
+struct SchedulerState {
+    pending: VecDeque<Arc<KeyRange>>,
+    active: Vec<Arc<KeyRange>>,
+    next_id: usize,
+}
+
+/// Hands out the key ranges of a partitioned load. Ranges start out equal
+/// width, or split where the table statistics put equal numbers of rows;
+/// once they are all claimed, a worker asking for more work takes the second
+/// half of the range expected to finish last.
+pub struct PartitionScheduler {
+    state: Mutex<SchedulerState>,
+    /// Smallest range worth handing to another worker
+    min_split: u64,
+}
+
+impl PartitionScheduler {
+    /// Splits the keys `min..=max` into `partitions` ranges of equal width.
+    pub fn new(min: u64, max: u64, partitions: usize, min_split: u64) -> Self {
+        let span = max.saturating_sub(min) + 1;
+        let partitions = (partitions.max(1) as u64).min(span);
+        let width = span.div_ceil(partitions);
+
+        let points: Vec<u64> = (1..partitions).map(|i| min + i * width).collect();
+        Self::with_split_points(min, max, &points, min_split)
+    }
+
+    /// Splits the keys `min..=max` at `points`, each the first key of a
+    /// range. Points outside the keys or out of order are skipped.
+    pub fn with_split_points(min: u64, max: u64, points: &[u64], min_split: u64) -> Self {
+        let end = max.saturating_add(1);
+        let mut starts = vec![min];
+        for &point in points {
+            if point > *starts.last().unwrap() && point < end {
+                starts.push(point);
+            }
+        }
+
+        let pending = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let range_end = starts.get(i + 1).copied().unwrap_or(end);
+                Arc::new(KeyRange::new(i, start, range_end))
+            })
+            .collect::<VecDeque<_>>();
+
+        Self {
+            state: Mutex::new(SchedulerState {
+                next_id: pending.len(),
+                pending,
+                active: Vec::new(),
+            }),
+            min_split: min_split.max(1),
+        }
+    }
+
+    /// Next range for an idle worker, or `None` when there is nothing left
+    /// worth splitting.
+    pub async fn next_range(&self) -> Option<Arc<KeyRange>> {
+        let candidates = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(range) = state.pending.pop_front() {
+                state.active.push(range.clone());
+                return Some(range);
+            }
+
+            let mut candidates: Vec<_> = state
+                .active
+                .iter()
+                .filter(|range| !range.is_done())
+                .cloned()
+                .collect();
+            candidates.sort_by(|a, b| {
+                b.eta_secs()
+                    .total_cmp(&a.eta_secs())
+                    .then(b.remaining().cmp(&a.remaining()))
+            });
+            candidates
+        };
+
+        for victim in candidates {
+            if let Some(range) = self.split(&victim).await {
+                return Some(range);
+            }
+        }
+        None
+    }
+
+    async fn split(&self, victim: &KeyRange) -> Option<Arc<KeyRange>> {
+        // Waits out the victim's in-flight fetch, so its read position is final
+        let _gate = victim.lock().await;
+
+        let remaining = victim.remaining();
+        if remaining < 2 * self.min_split {
+            return None;
+        }
+
+        let end = victim.end();
+        let split_at = end - remaining / 2;
+
+        let mut state = self.state.lock().unwrap();
+        let range = Arc::new(KeyRange::new(state.next_id, split_at, end));
+        state.next_id += 1;
+        victim.end.store(split_at, Ordering::Release);
+        state.active.push(range.clone());
+
+        info!(
+            range = victim.id,
+            keys_per_sec = victim.keys_per_sec() as u64,
+            remaining,
+            split_at,
+            new_range = range.id,
+            "splitting slow key range to an idle worker"
+        );
+
+        Some(range)
+    }
+
+    /// Every range handed out or waiting, including finished ones.
+    pub fn ranges(&self) -> Vec<Arc<KeyRange>> {
+        let state = self.state.lock().unwrap();
+        state
+            .active
+            .iter()
+            .chain(state.pending.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn bounds(ranges: &[Arc<KeyRange>]) -> Vec<(u64, u64)> {
+        let mut bounds: Vec<_> = ranges.iter().map(|r| (r.start(), r.end())).collect();
+        bounds.sort();
+        bounds
+    }
+
+    #[tokio::test]
+    async fn test_equal_width_ranges() {
+        let scheduler = PartitionScheduler::new(1, 100, 4, 10);
+        let mut ranges = Vec::new();
+        for _ in 0..4 {
+            ranges.push(scheduler.next_range().await.unwrap());
+        }
+        assert_eq!(
+            bounds(&ranges),
+            vec![(1, 26), (26, 51), (51, 76), (76, 101)]
+        );
+
+        // Fewer keys than partitions
+        let scheduler = PartitionScheduler::new(5, 6, 4, 1);
+        assert_eq!(bounds(&scheduler.ranges()), vec![(5, 6), (6, 7)]);
+    }
+
+    #[test]
+    fn test_ranges_from_split_points() {
+        // Most rows sit at low keys, so the statistics put the splits there
+        let scheduler = PartitionScheduler::with_split_points(1, 1_000_000, &[50, 200, 5_000], 1);
+        assert_eq!(
+            bounds(&scheduler.ranges()),
+            vec![(1, 50), (50, 200), (200, 5_000), (5_000, 1_000_001)]
+        );
+
+        // Points outside the keys or repeated are skipped
+        let scheduler = PartitionScheduler::with_split_points(10, 99, &[5, 40, 40, 100], 1);
+        assert_eq!(bounds(&scheduler.ranges()), vec![(10, 40), (40, 100)]);
+    }
+
+    #[tokio::test]
+    async fn test_idle_worker_splits_slowest_range() {
+        let pk = QualCol::from_str("orders.id").unwrap();
+        let scheduler = PartitionScheduler::new(0, 999, 2, 10);
+        let fast = scheduler.next_range().await.unwrap();
+        let slow = scheduler.next_range().await.unwrap();
+        assert_eq!(fast.start_cursor(&pk), Cursor::None);
+
+        // The first range finishes, the second has barely started
+        fast.advance(None);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        slow.advance(Some(&Cursor::Pk {
+            pk_col: pk.clone(),
+            id: 509,
+        }));
+
+        let stolen = scheduler.next_range().await.unwrap();
+        assert_eq!((stolen.start(), stolen.end()), (755, 1000));
+        assert_eq!(slow.end(), 755);
+        assert_eq!(slow.remaining(), 245);
+        assert_eq!(
+            stolen.start_cursor(&pk),
+            Cursor::Pk {
+                pk_col: pk,
+                id: 754
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_small_ranges_are_not_split() {
+        let scheduler = PartitionScheduler::new(0, 29, 1, 10);
+        let range = scheduler.next_range().await.unwrap();
+        range.advance(Some(&Cursor::Pk {
+            pk_col: QualCol::from_str("t.id").unwrap(),
+            id: 14,
+        }));
+        assert!(scheduler.next_range().await.is_none());
+    }
+}
@@ -9,7 +9,7 @@ use model::{
     pagination::cursor::Cursor,
     records::{Record, batch::Batch},
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 
 /// Coordinates batch creation and delivery to consumers.
@@ -53,12 +53,14 @@ impl BatchCoordinator {
     }
 
     /// Hash (for integrity), send the batch to the consumer, and record stats.
+    /// `read_time` is how long the batch took to fetch and transform.
     pub async fn process_batch(
         &mut self,
         batch_id: String,
         current_cursor: Cursor,
         transform_result: TransformResult,
         next_cursor: Cursor,
+        read_time: Duration,
     ) -> Result<(), ProducerError> {
         let rows = transform_result.rows;
         let rows_count = rows.len();
@@ -68,7 +70,7 @@ impl BatchCoordinator {
         }
 
         // Send to consumer (which checkpoints after a successful write).
-        self.send_batch(batch_id, current_cursor, rows, next_cursor)
+        self.send_batch(batch_id, current_cursor, rows, next_cursor, read_time)
             .await?;
 
         // Only record stats after successful processing.
@@ -117,6 +119,7 @@ impl BatchCoordinator {
         cursor: Cursor,
         rows: Vec<Record>,
         next: Cursor,
+        read_time: Duration,
     ) -> Result<(), ProducerError> {
         let batch = Batch {
             id: batch_id,
@@ -124,6 +127,7 @@ impl BatchCoordinator {
             cursor,
             next,
             ts: chrono::Utc::now(),
+            read_time,
         };
 
         self.batch_tx
@@ -1,14 +1,17 @@
 use crate::transform::{
     error::{ErrorType, TransformError},
     failed_row_writer::FailedRowWriter,
+    keymap::{KeymapColumns, key_text},
     pipeline::{ApplyOutcome, TransformPipeline},
 };
 use engine_core::context::exec::ExecutionContext;
 use model::{
+    core::value::Value,
     execution::{
         failed_row::{FailedRow, ProcessingStage},
         pipeline::ErrorHandling,
     },
+    pagination::cursor::Cursor,
     records::Record,
 };
 use std::{collections::HashMap, sync::Arc};
@@ -23,13 +26,20 @@ pub struct TransformResult {
     pub rows_skipped: u64,
     /// Number of rows that failed transformation
     pub rows_failed: u64,
+    /// Destination key of each transformed row, by the text of its source
+    /// key, when the pipeline records a key crosswalk
+    pub keys: Vec<(String, Value)>,
 }
 
 /// Handles transformation of rows with batch processing and failed row tracking.
 pub struct TransformService {
     pipeline: TransformPipeline,
+    /// Transforms of the further destination tables, each run on its own
+    /// copy of every source row
+    sinks: Vec<TransformPipeline>,
     pipeline_name: String,
     failed_row_writer: Option<FailedRowWriter>,
+    keymap: Option<KeymapColumns>,
 }
 
 impl TransformService {
@@ -50,11 +60,25 @@ impl TransformService {
 
         Self {
             pipeline,
+            sinks: Vec::new(),
             pipeline_name,
             failed_row_writer,
+            keymap: None,
         }
     }
 
+    /// Record the destination key of each transformed row by its source key.
+    pub fn with_keymap(mut self, columns: KeymapColumns) -> Self {
+        self.keymap = Some(columns);
+        self
+    }
+
+    /// Also map a copy of every row for each further destination table.
+    pub fn with_sinks(mut self, sinks: Vec<TransformPipeline>) -> Self {
+        self.sinks = sinks;
+        self
+    }
+
     /// Apply transformations to a batch of rows.
     /// - Data/transformation errors: sent to DLQ, migration continues
     /// - Validation failures: sent to DLQ, migration stops (indicates bad pipeline config)
@@ -64,10 +88,13 @@ impl TransformService {
         &self,
         run_id: &str,
         batch_id: &str,
+        offset: &Cursor,
         rows: Vec<Record>,
     ) -> Result<TransformResult, TransformError> {
-        let (successful, filtered, failed_rows, has_fatal) =
-            self.transform_batch(run_id, batch_id, rows).await;
+        let mut keys = Vec::new();
+        let (successful, filtered, failed_rows, has_fatal) = self
+            .transform_batch(run_id, batch_id, offset, rows, &mut keys)
+            .await;
 
         if has_fatal {
             // Validation failure detected - stop migration
@@ -82,16 +109,20 @@ impl TransformService {
             rows: successful,
             rows_skipped: filtered.len() as u64,
             rows_failed: failed_rows.len() as u64,
+            keys,
         })
     }
 
-    /// Transform a batch of rows with fail-fast semantics.
+    /// Transform a batch of rows with fail-fast semantics, pushing the keys
+    /// of successful rows to `keys` when the pipeline records a crosswalk.
     /// Returns (successful_rows, filtered_rows, failed_rows, has_fatal_error).
     async fn transform_batch(
         &self,
         run_id: &str,
         batch_id: &str,
+        offset: &Cursor,
         rows: Vec<Record>,
+        keys: &mut Vec<(String, Value)>,
     ) -> (Vec<Record>, Vec<Record>, Vec<FailedRow>, bool) {
         // Cap the number of error messages we retain.
         const MAX_ERROR_SAMPLES: usize = 10;
@@ -102,41 +133,66 @@ impl TransformService {
         let mut error_samples = Vec::new();
         let mut has_fatal = false;
 
-        for mut row in rows {
-            // Apply pipeline - fail fast, no retry
-            match self.pipeline.apply(&mut row) {
-                Ok(ApplyOutcome::Success) | Ok(ApplyOutcome::Warning { .. }) => {
-                    // Row transformed successfully (warnings are non-fatal)
-                    successful.push(row);
-                }
-                Ok(ApplyOutcome::Skipped { .. }) => {
-                    // Row filtered out (not an error)
-                    filtered.push(row);
-                }
-                Err(e) => {
-                    // Check if this is a fatal error (validation failure)
-                    if e.is_fatal() {
-                        has_fatal = true;
+        for (row_index, row) in rows.into_iter().enumerate() {
+            // Each further destination table maps its own copy of the row
+            let copies: Vec<_> = self.sinks.iter().map(|sink| (sink, row.clone())).collect();
+            for (copy, (pipeline, mut row)) in std::iter::once((&self.pipeline, row))
+                .chain(copies)
+                .enumerate()
+            {
+                // Only the first destination's keys go into the crosswalk
+                let old_key = self.keymap.as_ref().filter(|_| copy == 0).and_then(|k| {
+                    row.get(&k.source_key)
+                        .and_then(|f| f.value.as_ref())
+                        .and_then(key_text)
+                });
+
+                // Apply pipeline - fail fast, no retry
+                match pipeline.apply(&mut row) {
+                    Ok(ApplyOutcome::Success) | Ok(ApplyOutcome::Warning { .. }) => {
+                        if let (Some(keymap), Some(old_key)) = (&self.keymap, old_key) {
+                            match row
+                                .get(&keymap.destination_key)
+                                .and_then(|f| f.value.clone())
+                            {
+                                Some(Value::Null) | None => {}
+                                Some(new_key) => keys.push((old_key, new_key)),
+                            }
+                        }
+                        // Row transformed successfully (warnings are non-fatal)
+                        successful.push(row);
+                    }
+                    Ok(ApplyOutcome::Skipped { .. }) => {
+                        // Row filtered out (not an error)
+                        filtered.push(row);
                     }
+                    Err(e) => {
+                        // Check if this is a fatal error (validation failure)
+                        if e.is_fatal() {
+                            has_fatal = true;
+                        }
 
-                    // The error is otherwise only captured inside the FailedRow.
-                    // Log it so the cause is diagnosable.
-                    let err_msg = e.to_string();
-                    debug!(
-                        pipeline = %self.pipeline_name,
-                        batch_id = %batch_id,
-                        error = %err_msg,
-                        "row transformation failed"
-                    );
+                        // The error is otherwise only captured inside the FailedRow.
+                        // Log it so the cause is diagnosable.
+                        let err_msg = e.to_string();
+                        debug!(
+                            pipeline = %self.pipeline_name,
+                            batch_id = %batch_id,
+                            error = %err_msg,
+                            "row transformation failed"
+                        );
 
-                    if error_samples.len() < MAX_ERROR_SAMPLES {
-                        error_samples.push(err_msg);
-                    }
+                        if error_samples.len() < MAX_ERROR_SAMPLES {
+                            error_samples.push(err_msg);
+                        }
 
-                    // Transformation failed - create FailedRow for DLQ
-                    let failed_row = self.create_failed_row(run_id, batch_id, &row, e);
-                    failed_rows.push(failed_row);
-                    // Continue processing remaining rows in batch
+                        // Transformation failed - create FailedRow for DLQ
+                        let failed_row = self
+                            .create_failed_row(run_id, batch_id, row_index, &row, e)
+                            .with_batch_offset(offset.clone());
+                        failed_rows.push(failed_row);
+                        // Continue processing remaining rows in batch
+                    }
                 }
             }
         }
@@ -168,6 +224,7 @@ impl TransformService {
         &self,
         run_id: &str,
         batch_id: &str,
+        row_index: usize,
         row: &Record,
         error: TransformError,
     ) -> FailedRow {
@@ -184,7 +241,11 @@ impl TransformService {
             format!("{:?}", error), // Error type
             error.to_string(),      // Error message
         )
-        .with_execution_context(run_id.to_string(), Some(batch_id.to_string()), None)
+        .with_execution_context(
+            run_id.to_string(),
+            Some(batch_id.to_string()),
+            Some(row_index),
+        )
         .with_table(row.schema.clone())
         .with_retryable(is_retryable)
     }
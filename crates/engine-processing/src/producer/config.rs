@@ -1,5 +1,20 @@
-use model::integrity::config::IntegrityConfig;
-use std::{num::NonZeroUsize, time::Duration};
+use crate::{
+    partition::KeyRange,
+    transform::{
+        keymap::{KeyMap, KeymapColumns},
+        validation::ValidationTally,
+    },
+};
+use model::{
+    core::encoding::{ArrayFormat, InvalidUtf8},
+    integrity::config::IntegrityConfig,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::{Arc, atomic::AtomicU64},
+    time::Duration,
+};
 
 /// Configuration for producer behavior.
 #[derive(Debug, Clone)]
@@ -25,6 +40,50 @@ pub struct ProducerConfig {
     /// When `Some`, the producer hashes each batch and writes a `VerificationReceipt`
     /// to sled on completion. `None` means zero overhead - no hashing occurs.
     pub integrity: Option<IntegrityConfig>,
+
+    /// Labels of the destination enum columns (table -> column -> labels).
+    /// Rows carrying any other value fail validation before they are written.
+    pub enum_labels: HashMap<String, HashMap<String, Vec<String>>>,
+
+    /// How text bytes that are not valid UTF-8 are decoded
+    pub invalid_utf8: InvalidUtf8,
+
+    /// Text columns of the destination tables (table -> columns). Raw bytes
+    /// bound for them are decoded with `invalid_utf8`.
+    pub text_columns: HashMap<String, HashSet<String>>,
+
+    /// How arrays are written to destination columns that do not hold arrays
+    pub array_format: ArrayFormat,
+
+    /// Separator between array elements with `ArrayFormat::Delimited`
+    pub array_delimiter: String,
+
+    /// Destination columns that do not hold arrays (table -> columns).
+    /// Arrays bound for them are serialized with `array_format`.
+    pub scalar_columns: HashMap<String, HashSet<String>>,
+
+    /// Key range read by this producer when the load is partitioned. The
+    /// producer reports its progress on it so idle workers can split it.
+    pub key_range: Option<Arc<KeyRange>>,
+
+    /// When `Some`, validate rules count their failures here instead of
+    /// skipping or failing rows (validate-only pipelines).
+    pub validation_tally: Option<Arc<ValidationTally>>,
+
+    /// Counter `row_number()` draws from, shared by every producer of the
+    /// pipeline so numbers are unique across partitions.
+    pub row_numbers: Option<Arc<AtomicU64>>,
+
+    /// Columns the pipeline records its key crosswalk from (`emit_keymap`)
+    pub keymap: Option<KeymapColumns>,
+
+    /// Key crosswalks the pipeline's `lookup_key()` calls read, by table
+    pub keymaps: HashMap<String, Arc<KeyMap>>,
+
+    /// Whether created destination tables are loaded unlogged. Changes
+    /// streamed into such a table are lost if the server crashes, so CDC
+    /// refuses to start.
+    pub unlogged_load: bool,
 }
 
 impl Default for ProducerConfig {
@@ -37,6 +96,18 @@ impl Default for ProducerConfig {
             max_retries: 3,
             retry_delay: Duration::from_secs(1),
             integrity: None,
+            enum_labels: HashMap::new(),
+            invalid_utf8: InvalidUtf8::Replace,
+            text_columns: HashMap::new(),
+            array_format: ArrayFormat::Json,
+            array_delimiter: ",".to_string(),
+            scalar_columns: HashMap::new(),
+            key_range: None,
+            validation_tally: None,
+            row_numbers: None,
+            keymap: None,
+            keymaps: HashMap::new(),
+            unlogged_load: false,
         }
     }
 }
@@ -61,4 +132,64 @@ impl ProducerConfig {
         self.integrity = Some(config);
         self
     }
+
+    pub fn with_enum_labels(
+        mut self,
+        labels: HashMap<String, HashMap<String, Vec<String>>>,
+    ) -> Self {
+        self.enum_labels = labels;
+        self
+    }
+
+    pub fn with_text_decoding(
+        mut self,
+        invalid_utf8: InvalidUtf8,
+        text_columns: HashMap<String, HashSet<String>>,
+    ) -> Self {
+        self.invalid_utf8 = invalid_utf8;
+        self.text_columns = text_columns;
+        self
+    }
+
+    pub fn with_array_serialization(
+        mut self,
+        format: ArrayFormat,
+        delimiter: &str,
+        scalar_columns: HashMap<String, HashSet<String>>,
+    ) -> Self {
+        self.array_format = format;
+        self.array_delimiter = delimiter.to_string();
+        self.scalar_columns = scalar_columns;
+        self
+    }
+
+    pub fn with_key_range(mut self, range: Arc<KeyRange>) -> Self {
+        self.key_range = Some(range);
+        self
+    }
+
+    pub fn with_validation_tally(mut self, tally: Arc<ValidationTally>) -> Self {
+        self.validation_tally = Some(tally);
+        self
+    }
+
+    pub fn with_row_numbers(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.row_numbers = Some(counter);
+        self
+    }
+
+    pub fn with_keymap(mut self, columns: KeymapColumns) -> Self {
+        self.keymap = Some(columns);
+        self
+    }
+
+    pub fn with_keymaps(mut self, keymaps: HashMap<String, Arc<KeyMap>>) -> Self {
+        self.keymaps = keymaps;
+        self
+    }
+
+    pub fn with_unlogged_load(mut self, unlogged: bool) -> Self {
+        self.unlogged_load = unlogged;
+        self
+    }
 }
@@ -11,40 +11,64 @@ use crate::{
     },
     state_manager::StateManager,
     transform::{
+        array::ArraySerializer,
         computed::ComputedTransform,
+        enums::EnumLabelValidator,
+        keymap::KeyMap,
         mapping::{FieldMapper, TableMapper},
         pipeline::{TransformPipeline, TransformPipelineExt},
         pruner::FieldPruner,
-        validation::PipelineValidator,
+        script::ScriptTransform,
+        text::TextDecoder,
+        timezone::TimezoneNormalizer,
+        validation::{PipelineValidator, ValidationTally},
     },
 };
 use engine_core::{context::env::EnvContext, retry::RetryPolicy};
-use engine_state::MerkleStore;
+use engine_state::{KeymapStore, MerkleStore};
 use engine_wasm::registry::PluginRegistry;
 use model::{
     execution::pipeline::Pipeline, pagination::cursor::Cursor, records::batch::Batch,
     transform::mapping::TransformationMetadata,
 };
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, atomic::AtomicU64},
+    time::Instant,
+};
 use tokio::sync::mpsc;
 use tracing::debug;
 
 pub mod components;
 pub mod config;
 
-#[allow(clippy::result_large_err)]
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
 pub fn build_transform_pipeline(
     pipeline: &Pipeline,
     plugin_registry: &PluginRegistry,
     mapping: &TransformationMetadata,
     mapped_columns_only: bool,
     env: Arc<EnvContext>,
+    validation_tally: Option<Arc<ValidationTally>>,
+    row_numbers: Option<Arc<AtomicU64>>,
+    keymaps: HashMap<String, Arc<KeyMap>>,
 ) -> Result<TransformPipeline, ProducerError> {
     let mut tp = TransformPipeline::new();
-
-    // Each transform is only added if it's needed. Entity/table remap and
-    // column renames come first so later stages see the target-shaped row.
+    // env() in computed columns sees the pipeline's own variables
+    let env = if pipeline.env.is_empty() {
+        env
+    } else {
+        Arc::new(env.scoped(&pipeline.env))
+    };
+
+    // Each transform is only added if it's needed. Source timestamps are
+    // put in UTC while they still carry source column names; entity/table
+    // remap and column renames follow so later stages see the target-shaped
+    // row.
     tp = tp
+        .add_if(!pipeline.source.timezones.is_empty(), || {
+            TimezoneNormalizer::new(&pipeline.source.timezones)
+        })
         .add_if(!mapping.entities.is_empty(), || {
             TableMapper::new(mapping.entities.clone())
         })
@@ -74,9 +98,25 @@ pub fn build_transform_pipeline(
     // Computed columns (including `when`) evaluate top-to-bottom and may
     // reference source columns, earlier computed columns, and plugin outputs.
     tp = tp.add_if(!mapping.field_mappings.computed_fields.is_empty(), || {
-        ComputedTransform::new(mapping.clone(), env.clone())
+        let computed = ComputedTransform::new(mapping.clone(), env.clone());
+        let computed = match row_numbers {
+            Some(counter) => computed.with_row_numbers(counter),
+            None => computed,
+        };
+        match keymaps.is_empty() {
+            true => computed,
+            false => computed.with_keymaps(keymaps),
+        }
     });
 
+    // The row script runs once the row has its mapped and computed columns,
+    // so it can rework anything the expression language produced.
+    if let Some(script) = &pipeline.script {
+        let script = ScriptTransform::new(script)
+            .map_err(|e| ProducerError::Other(format!("pipeline '{}': {e}", pipeline.name)))?;
+        tp = tp.add_transform(script);
+    }
+
     // Prune unmapped columns last, once plugin inputs have been consumed.
     tp = tp.add_if(mapped_columns_only, || FieldPruner::new(mapping.clone()));
 
@@ -87,7 +127,10 @@ pub fn build_transform_pipeline(
             env.clone(),
             plugin_registry,
         )?;
-        tp = tp.add_validator(validator);
+        tp = match validation_tally {
+            Some(tally) => tp.add_validator(validator.with_tally(tally)),
+            None => tp.add_validator(validator),
+        };
     }
 
     Ok(tp)
@@ -122,6 +165,9 @@ pub struct Producer {
     cursor: Cursor,
     mode: ProducerMode,
     ids: ItemId,
+    /// Table and store the transformed rows' keys are recorded under
+    /// (`emit_keymap`)
+    keymap_store: Option<(String, Arc<dyn KeymapStore>)>,
 
     // Config
     config: ProducerConfig,
@@ -137,7 +183,7 @@ impl Producer {
         let exec_ctx = ctx.exec_ctx.clone();
         let run_id = ctx.run_id.clone();
         let item_id = ctx.item_id.clone();
-        let part_id = "part-0".to_string();
+        let part_id = ctx.part_id.clone();
         let source = ctx.source.clone();
         let pipeline = ctx.pipeline.clone();
         let mapping = ctx.mapping.clone();
@@ -156,20 +202,71 @@ impl Producer {
         // Create components
         let reader = SnapshotReader::new(source, retry_policy, config.batch_size);
 
+        // Decoding and checks against the destination columns, which are
+        // looked up by each row's table
+        let destination_stages = |tp: TransformPipeline| {
+            tp.add_if(!config.text_columns.is_empty(), || {
+                TextDecoder::new(config.invalid_utf8, config.text_columns.clone())
+            })
+            .add_if(!config.scalar_columns.is_empty(), || {
+                ArraySerializer::new(
+                    config.array_format,
+                    &config.array_delimiter,
+                    config.scalar_columns.clone(),
+                )
+            })
+            .add_validator_if(!config.enum_labels.is_empty(), || {
+                EnumLabelValidator::new(config.enum_labels.clone())
+            })
+        };
+
         let env = exec_ctx.env.clone();
         let transform_pipeline = build_transform_pipeline(
             &pipeline,
             &ctx.plugin_registry,
             &mapping,
             mapped_columns_only,
-            env,
-        )?;
+            env.clone(),
+            config.validation_tally.clone(),
+            config.row_numbers.clone(),
+            config.keymaps.clone(),
+        )
+        .map(destination_stages)?;
+
+        // Each further destination table maps the source rows by its own
+        // select
+        let mut sinks = Vec::with_capacity(pipeline.sinks.len());
+        for sink in &pipeline.sinks {
+            let sink_pipeline = pipeline.sink_pipeline(sink);
+            let sink_mapping = TransformationMetadata::new(&sink_pipeline);
+            let sink_transforms = build_transform_pipeline(
+                &sink_pipeline,
+                &ctx.plugin_registry,
+                &sink_mapping,
+                mapped_columns_only,
+                env.clone(),
+                None,
+                None,
+                config.keymaps.clone(),
+            )?;
+            sinks.push(destination_stages(sink_transforms));
+        }
+
         let transformer = TransformService::new(
             exec_ctx,
             transform_pipeline,
             pipeline.name.clone(),
             pipeline.error_handling.clone(),
-        );
+        )
+        .with_sinks(sinks);
+        let (transformer, keymap_store) = match config.keymap.clone() {
+            Some(columns) => {
+                let store = state_store.clone() as Arc<dyn KeymapStore>;
+                let table = columns.table.clone();
+                (transformer.with_keymap(columns), Some((table, store)))
+            }
+            None => (transformer, None),
+        };
 
         let state_manager = StateManager::new(ids.clone(), state_store.clone());
         let mut coordinator = BatchCoordinator::new(batch_tx, state_manager);
@@ -185,6 +282,7 @@ impl Producer {
             cursor,
             mode: ProducerMode::Idle,
             ids,
+            keymap_store,
             config,
             pipeline_name: pipeline.name.clone(),
         })
@@ -196,6 +294,11 @@ impl Producer {
     }
 
     pub async fn start_cdc(&mut self) -> Result<(), ProducerError> {
+        if self.config.unlogged_load {
+            return Err(ProducerError::Other(
+                "fast_load = \"UNLOGGED\" cannot be used with CDC: changes written to an unlogged table are lost if the server crashes".to_string(),
+            ));
+        }
         self.mode = ProducerMode::Cdc;
         Ok(())
     }
@@ -206,7 +309,17 @@ impl Producer {
         item_id: &str,
         part_id: &str,
     ) -> Result<(), ProducerError> {
-        self.cursor = self.coordinator.state_manager().resume_cursor().await?;
+        // A partition is read from the start of its key range, since the
+        // range may have been split differently before; a fixed range
+        // resumes from its checkpoint, if it has one
+        match &self.config.key_range {
+            Some(range) if !range.is_fixed() => return Ok(()),
+            Some(_) => match self.coordinator.state_manager().resume_cursor().await? {
+                Cursor::None => return Ok(()),
+                cursor => self.cursor = cursor,
+            },
+            None => self.cursor = self.coordinator.state_manager().resume_cursor().await?,
+        }
         debug!(
             run_id = run_id,
             item_id = item_id,
@@ -257,7 +370,17 @@ impl Producer {
     }
 
     async fn process_snapshot_batch(&mut self) -> Result<ProducerStatus, ProducerError> {
+        let range = self.config.key_range.clone();
+        let gate = match &range {
+            Some(range) => Some(range.lock().await),
+            None => None,
+        };
+        let started = Instant::now();
         let fetch_result = self.reader.fetch(self.cursor.clone()).await?;
+        if let Some(range) = &range {
+            range.advance(fetch_result.next_cursor.as_ref());
+        }
+        drop(gate);
 
         // Handle empty/end cases
         if SnapshotReader::is_complete(&fetch_result) {
@@ -282,9 +405,22 @@ impl Producer {
         // Transform data - will process entire batch even if some rows fail
         let transform_result = self
             .transformer
-            .transform(&self.ids.run_id(), &batch_id, fetch_result.rows)
+            .transform(
+                &self.ids.run_id(),
+                &batch_id,
+                &self.cursor,
+                fetch_result.rows,
+            )
             .await?;
 
+        // Record the batch's keys before it can be written, so that a row in
+        // the destination always has its crosswalk entry
+        if let Some((table, store)) = &self.keymap_store
+            && !transform_result.keys.is_empty()
+        {
+            store.save_keys(table, &transform_result.keys).await?;
+        }
+
         // Process batch - stats are recorded only after successful completion
         self.coordinator
             .process_batch(
@@ -292,6 +428,7 @@ impl Producer {
                 self.cursor.clone(),
                 transform_result,
                 next.clone(),
+                started.elapsed(),
             )
             .await?;
 
@@ -10,7 +10,7 @@ pub fn classify_driver_error(err: &DriverError) -> RetryDisposition {
         DriverError::MySqlError(mysql_err) => classify_mysql_error(mysql_err),
         DriverError::PgError(pg_err) => classify_pg_error(pg_err),
         DriverError::ConnectionError(_) => RetryDisposition::Retry,
-        DriverError::QueryError(_) => RetryDisposition::Stop,
+        DriverError::QueryError(msg) => classify_error_text(msg),
         DriverError::TransactionError(_) => RetryDisposition::Stop,
         DriverError::Unknown(_) => RetryDisposition::Stop,
         DriverError::UnsupportedFormat(_) => RetryDisposition::Stop,
@@ -48,6 +48,32 @@ pub fn classify_sink_error(err: &SinkError) -> RetryDisposition {
     }
 }
 
+/// Writers report driver errors as text, so transient failures are
+/// recognised by the SQLSTATE, server code or message they carry.
+fn classify_error_text(msg: &str) -> RetryDisposition {
+    const TRANSIENT_MARKERS: [&str; 12] = [
+        "deadlock",
+        "sqlstate(e40001)",
+        "sqlstate(e40p01)",
+        "sqlstate(e55p03)",
+        "sqlstate(e57p01)",
+        "code: 1205",
+        "code: 1213",
+        "lock wait timeout",
+        "connection reset",
+        "connection closed",
+        "broken pipe",
+        "server has gone away",
+    ];
+
+    let msg = msg.to_lowercase();
+    if TRANSIENT_MARKERS.iter().any(|m| msg.contains(m)) {
+        RetryDisposition::Retry
+    } else {
+        RetryDisposition::Stop
+    }
+}
+
 fn classify_pg_error(err: &PgError) -> RetryDisposition {
     if err.is_closed() {
         return RetryDisposition::Retry;
@@ -108,3 +134,132 @@ fn is_retryable_mysql_server_error(code: u16, state: &str) -> bool {
 
     matches!(state, "40001" | "HYT00" | "08S01")
 }
+
+/// SQLSTATEs and MySQL server codes that single out a kind of write error.
+struct ErrorCodes {
+    sqlstates: &'static [&'static str],
+    mysql: &'static [u16],
+}
+
+/// A missing table or column, or a value the column's type rejects
+const SCHEMA_ERRORS: ErrorCodes = ErrorCodes {
+    sqlstates: &["42703", "42P01", "42804", "22P02"],
+    mysql: &[1054, 1146, 1366],
+};
+
+/// A value too long or too large for its column
+const OVERFLOW_ERRORS: ErrorCodes = ErrorCodes {
+    sqlstates: &["22001", "22003"],
+    mysql: &[1406, 1264],
+};
+
+/// Whether a failed write hints that the destination table no longer
+/// matches its metadata. Only a hint; the table is introspected to confirm it.
+pub fn is_schema_error(err: &SinkError) -> bool {
+    has_error_code(err, &SCHEMA_ERRORS)
+}
+
+/// Whether a failed write was rejected because a value does not fit its
+/// column, such as a string longer than a `varchar`.
+pub fn is_overflow_error(err: &SinkError) -> bool {
+    has_error_code(err, &OVERFLOW_ERRORS)
+}
+
+fn has_error_code(err: &SinkError, codes: &ErrorCodes) -> bool {
+    let db = |db_err: &DbError| match db_err {
+        DbError::MySqlError(mysql_err) => has_mysql_code(mysql_err, codes),
+        DbError::PgError(pg_err) => has_pg_code(pg_err, codes),
+        _ => false,
+    };
+    match err {
+        SinkError::Db(db_err) => db(db_err),
+        SinkError::TokioPostgres(pg_err) => has_pg_code(pg_err, codes),
+        SinkError::Driver(driver_err) => match driver_err {
+            DriverError::DatabaseError(db_err) => db(db_err),
+            DriverError::MySqlError(mysql_err) => has_mysql_code(mysql_err, codes),
+            DriverError::PgError(pg_err) => has_pg_code(pg_err, codes),
+            DriverError::QueryError(msg) => has_code_text(msg, codes),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Writers report driver errors as text, which carries the code as
+/// `SqlState(E22001)` or `code: 1406`.
+fn has_code_text(msg: &str, codes: &ErrorCodes) -> bool {
+    let msg = msg.to_lowercase();
+    codes
+        .sqlstates
+        .iter()
+        .any(|c| msg.contains(&format!("sqlstate(e{})", c.to_lowercase())))
+        || codes
+            .mysql
+            .iter()
+            .any(|c| msg.contains(&format!("code: {c}")))
+}
+
+fn has_pg_code(err: &PgError, codes: &ErrorCodes) -> bool {
+    err.code()
+        .is_some_and(|code| codes.sqlstates.contains(&code.code()))
+}
+
+fn has_mysql_code(err: &MySqlError, codes: &ErrorCodes) -> bool {
+    matches!(err, MySqlError::Server(server_err) if codes.mysql.contains(&server_err.code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stringified_transient_errors_are_retried() {
+        let retried = [
+            r#"Error { kind: Db, cause: Some(DbError { severity: "ERROR", code: SqlState(E40P01), message: "deadlock detected" }) }"#,
+            r#"Server(ServerError { code: 1213, message: "Deadlock found when trying to get lock; try restarting transaction", state: "40001" })"#,
+            "Io(Os { code: 104, kind: ConnectionReset, message: \"Connection reset by peer\" })",
+        ];
+        for msg in retried {
+            assert_eq!(
+                classify_driver_error(&DriverError::QueryError(msg.to_string())),
+                RetryDisposition::Retry,
+                "{msg}"
+            );
+        }
+
+        let stopped = r#"DbError { code: SqlState(E23505), message: "duplicate key value violates unique constraint" }"#;
+        assert_eq!(
+            classify_driver_error(&DriverError::QueryError(stopped.to_string())),
+            RetryDisposition::Stop
+        );
+    }
+
+    #[test]
+    fn test_schema_errors_are_recognised() {
+        let schema = [
+            r#"DbError { code: SqlState(E42703), message: "column \"title\" of relation \"posts\" does not exist" }"#,
+            r#"Server(ServerError { code: 1054, message: "Unknown column 'title' in 'field list'", state: "42S22" })"#,
+        ];
+        for msg in schema {
+            let err = SinkError::Driver(DriverError::QueryError(msg.to_string()));
+            assert!(is_schema_error(&err), "{msg}");
+        }
+
+        let other = r#"DbError { code: SqlState(E23505), message: "duplicate key value violates unique constraint" }"#;
+        let err = SinkError::Driver(DriverError::QueryError(other.to_string()));
+        assert!(!is_schema_error(&err));
+    }
+
+    #[test]
+    fn test_overflow_errors_are_recognised() {
+        let overflow = [
+            r#"DbError { code: SqlState(E22001), message: "value too long for type character varying(8)" }"#,
+            r#"Server(ServerError { code: 1406, message: "Data too long for column 'title' at row 1", state: "22001" })"#,
+        ];
+        for msg in overflow {
+            let err = SinkError::Driver(DriverError::QueryError(msg.to_string()));
+            assert!(is_overflow_error(&err), "{msg}");
+            assert!(!is_schema_error(&err), "{msg}");
+        }
+    }
+}
@@ -7,23 +7,196 @@ use engine_core::{
     },
 };
 use engine_state::models::CheckpointStage;
-use model::pagination::cursor::Cursor;
+use model::{pagination::cursor::Cursor, records::batch::Batch};
 use std::sync::Arc;
+use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
 /// Manages checkpoint and WAL operations.
+///
+/// Consumers drive the durable cursor through an acknowledgement protocol:
+/// [`StateManager::begin_batch`] records the batch as in flight and hands out
+/// a [`BatchAck`], and only [`StateManager::ack_batch`] - called once the
+/// destination write has succeeded - commits the batch and advances the
+/// cursor past it. One batch may be in flight at a time.
 pub struct StateManager {
     ids: ItemId,
     store: Arc<dyn StateStore>,
+    acks: Mutex<AckState>,
+}
+
+/// Token for a batch handed to the destination but not yet acknowledged.
+#[derive(Debug)]
+#[must_use = "an in-flight batch must be acknowledged or abandoned"]
+pub struct BatchAck {
+    batch_id: String,
+    next: Cursor,
+    rows: u64,
+}
+
+impl BatchAck {
+    pub fn batch_id(&self) -> &str {
+        &self.batch_id
+    }
+}
+
+#[derive(Default)]
+struct AckState {
+    in_flight: Option<String>,
+    /// Rows acknowledged so far; loaded from the checkpoint on first use
+    rows_done: Option<u64>,
 }
 
 impl StateManager {
     pub fn new(ids: ItemId, store: Arc<dyn StateStore>) -> Self {
-        Self { ids, store }
+        Self {
+            ids,
+            store,
+            acks: Mutex::new(AckState::default()),
+        }
+    }
+
+    /// Mark a batch as in flight before it is written to the destination.
+    ///
+    /// Appends a `BatchBegin` WAL entry and saves a `write` checkpoint whose
+    /// `src_offset` still points at the start of the batch, so a crash before
+    /// the ack re-reads it. Fails if the previous batch was never acked or
+    /// abandoned.
+    pub async fn begin_batch(&self, batch: &Batch) -> Result<BatchAck, StateStoreError> {
+        let mut acks = self.acks.lock().await;
+        if let Some(in_flight) = &acks.in_flight {
+            return Err(StateStoreError::Ack(format!(
+                "cannot begin batch '{}' while batch '{}' is unacknowledged",
+                batch.id, in_flight
+            )));
+        }
+
+        let rows_done = match acks.rows_done {
+            Some(rows) => rows,
+            None => self
+                .load_checkpoint()
+                .await?
+                .map(|cp| cp.rows_done)
+                .unwrap_or(0),
+        };
+
+        self.store
+            .append_wal(&WalEntry::BatchBegin {
+                run_id: self.ids.run_id(),
+                item_id: self.ids.item_id(),
+                part_id: self.ids.part_id(),
+                batch_id: batch.id.clone(),
+            })
+            .await?;
+        self.save_checkpoint(
+            &CheckpointStage::Write,
+            &batch.cursor,
+            Some(&batch.next),
+            &batch.id,
+            rows_done,
+        )
+        .await?;
+
+        acks.in_flight = Some(batch.id.clone());
+        acks.rows_done = Some(rows_done);
+
+        Ok(BatchAck {
+            batch_id: batch.id.clone(),
+            next: batch.next.clone(),
+            rows: batch.rows.len() as u64,
+        })
+    }
+
+    /// Acknowledge that an in-flight batch was written to the destination.
+    ///
+    /// Appends the `BatchCommit` WAL entry, then saves a `committed`
+    /// checkpoint at the batch's next cursor. Returns the total rows
+    /// acknowledged so far. An ack for any batch other than the one in
+    /// flight is rejected without touching the store. The token is spent
+    /// even when the store fails, so the batch is no longer in flight.
+    pub async fn ack_batch(&self, ack: BatchAck) -> Result<u64, StateStoreError> {
+        let mut acks = self.acks.lock().await;
+        if acks.in_flight.as_deref() != Some(ack.batch_id.as_str()) {
+            return Err(StateStoreError::Ack(format!(
+                "batch '{}' is not in flight",
+                ack.batch_id
+            )));
+        }
+
+        acks.in_flight = None;
+        let rows_done = acks.rows_done.unwrap_or(0) + ack.rows;
+
+        // Once the commit is in the WAL, resume continues past this batch
+        // even if the checkpoint below is never saved.
+        self.commit_batch(&ack.batch_id).await?;
+        acks.rows_done = Some(rows_done);
+        self.save_checkpoint(
+            &CheckpointStage::Committed,
+            &ack.next,
+            None,
+            &ack.batch_id,
+            rows_done,
+        )
+        .await?;
+
+        Ok(rows_done)
+    }
+
+    /// Give up on an in-flight batch after a failed write.
+    ///
+    /// The `write` checkpoint is left as is, so a resumed run re-reads the
+    /// batch from its start.
+    pub async fn abandon_batch(&self, ack: BatchAck) {
+        let mut acks = self.acks.lock().await;
+        if acks.in_flight.as_deref() == Some(ack.batch_id.as_str()) {
+            acks.in_flight = None;
+        }
+    }
+
+    /// Record that the circuit breaker gave up on this item.
+    pub async fn record_circuit_open(
+        &self,
+        stage: &str,
+        failures: u32,
+        last_error: &str,
+    ) -> Result<(), StateStoreError> {
+        self.store
+            .append_wal(&WalEntry::CircuitBreakerOpen {
+                run_id: self.ids.run_id(),
+                item_id: self.ids.item_id(),
+                part_id: self.ids.part_id(),
+                stage: stage.to_string(),
+                failures,
+                last_error: last_error.to_string(),
+            })
+            .await
+    }
+
+    /// Record a destination column widened to fit a batch.
+    pub async fn record_column_widened(
+        &self,
+        table: &str,
+        column: &str,
+        from: &str,
+        to: &str,
+        sql: &str,
+    ) -> Result<(), StateStoreError> {
+        self.store
+            .append_wal(&WalEntry::ColumnWidened {
+                run_id: self.ids.run_id(),
+                item_id: self.ids.item_id(),
+                part_id: self.ids.part_id(),
+                table: table.to_string(),
+                column: column.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                sql: sql.to_string(),
+            })
+            .await
     }
 
     /// Commit a batch by appending WAL entry.
-    pub async fn commit_batch(&self, batch_id: &str) -> Result<(), StateStoreError> {
+    async fn commit_batch(&self, batch_id: &str) -> Result<(), StateStoreError> {
         self.store
             .append_wal(&WalEntry::BatchCommit {
                 run_id: self.ids.run_id(),
@@ -36,7 +209,7 @@ impl StateManager {
     }
 
     /// Save a checkpoint with specific stage and cursor information.
-    pub async fn save_checkpoint(
+    async fn save_checkpoint(
         &self,
         stage: &CheckpointStage,
         src_offset: &Cursor,
@@ -137,7 +310,7 @@ impl StateManager {
                     );
                     resume_cursor
                 } else {
-                    // No commit in WAL - batch was lost, need to re-read
+                    // Never acked - the write may be partial or missing, re-read it
                     warn!(
                         cursor = ?summary.src_offset,
                         batch_id = %summary.batch_id,
@@ -169,3 +342,167 @@ impl StateManager {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use engine_state::{
+        models::{RunRecord, RunState},
+        sled_store::SledStateStore,
+    };
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    /// Sled store whose next `BatchCommit` append fails
+    struct FailingCommit {
+        inner: SledStateStore,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl StateStore for FailingCommit {
+        async fn save_checkpoint(&self, cp: &Checkpoint) -> Result<(), StateStoreError> {
+            self.inner.save_checkpoint(cp).await
+        }
+        async fn load_checkpoint(
+            &self,
+            run_id: &str,
+            item_id: &str,
+            part_id: &str,
+        ) -> Result<Option<Checkpoint>, StateStoreError> {
+            self.inner.load_checkpoint(run_id, item_id, part_id).await
+        }
+        async fn list_checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>, StateStoreError> {
+            self.inner.list_checkpoints(run_id).await
+        }
+        async fn append_wal(&self, entry: &WalEntry) -> Result<(), StateStoreError> {
+            if matches!(entry, WalEntry::BatchCommit { .. })
+                && self.fail.swap(false, Ordering::SeqCst)
+            {
+                return Err(StateStoreError::AppendWAL("disk full".to_string()));
+            }
+            self.inner.append_wal(entry).await
+        }
+        async fn iter_wal(&self, run_id: &str) -> Result<Vec<WalEntry>, StateStoreError> {
+            self.inner.iter_wal(run_id).await
+        }
+        async fn save_run_state(&self, state: &RunState) -> Result<(), StateStoreError> {
+            self.inner.save_run_state(state).await
+        }
+        async fn load_run_state(&self, run_id: &str) -> Result<Option<RunState>, StateStoreError> {
+            self.inner.load_run_state(run_id).await
+        }
+        async fn list_runs(&self) -> Result<Vec<RunState>, StateStoreError> {
+            self.inner.list_runs().await
+        }
+        async fn delete_run(&self, run_id: &str) -> Result<(), StateStoreError> {
+            self.inner.delete_run(run_id).await
+        }
+        async fn save_run_record(&self, record: &RunRecord) -> Result<(), StateStoreError> {
+            self.inner.save_run_record(record).await
+        }
+        async fn load_run_record(&self, id: &str) -> Result<Option<RunRecord>, StateStoreError> {
+            self.inner.load_run_record(id).await
+        }
+        async fn list_run_records(&self) -> Result<Vec<RunRecord>, StateStoreError> {
+            self.inner.list_run_records().await
+        }
+    }
+
+    fn manager(store: &Arc<dyn StateStore>) -> StateManager {
+        let ids = ItemId::new("run-1".into(), "item-1".into(), "part-0".into());
+        StateManager::new(ids, store.clone())
+    }
+
+    fn batch(id: &str, from: usize, to: usize) -> Batch {
+        Batch {
+            id: id.to_string(),
+            rows: Vec::new(),
+            cursor: Cursor::Default { offset: from },
+            next: Cursor::Default { offset: to },
+            ts: chrono::Utc::now(),
+            read_time: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursor_advances_only_on_ack() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn StateStore> = Arc::new(SledStateStore::open(dir.path()).unwrap());
+        let state = manager(&store);
+
+        let ack = state.begin_batch(&batch("b1", 0, 10)).await.unwrap();
+        // Crash before the ack: a fresh manager re-reads the batch
+        assert_eq!(
+            manager(&store).resume_cursor().await.unwrap(),
+            Cursor::Default { offset: 0 }
+        );
+
+        state.ack_batch(ack).await.unwrap();
+        assert_eq!(
+            manager(&store).resume_cursor().await.unwrap(),
+            Cursor::Default { offset: 10 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_one_batch_in_flight() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn StateStore> = Arc::new(SledStateStore::open(dir.path()).unwrap());
+        let state = manager(&store);
+
+        let ack = state.begin_batch(&batch("b1", 0, 10)).await.unwrap();
+        assert!(matches!(
+            state.begin_batch(&batch("b2", 10, 20)).await,
+            Err(StateStoreError::Ack(_))
+        ));
+
+        state.abandon_batch(ack).await;
+        let ack = state.begin_batch(&batch("b2", 10, 20)).await.unwrap();
+        assert_eq!(ack.batch_id(), "b2");
+
+        // A stale token for an abandoned batch can't advance the cursor
+        let stale = BatchAck {
+            batch_id: "b1".to_string(),
+            next: Cursor::Default { offset: 10 },
+            rows: 0,
+        };
+        assert!(matches!(
+            state.ack_batch(stale).await,
+            Err(StateStoreError::Ack(_))
+        ));
+        state.ack_batch(ack).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_ack_releases_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn StateStore> = Arc::new(FailingCommit {
+            inner: SledStateStore::open(dir.path()).unwrap(),
+            fail: AtomicBool::new(true),
+        });
+        let state = manager(&store);
+
+        let ack = state.begin_batch(&batch("b1", 0, 10)).await.unwrap();
+        assert!(matches!(
+            state.ack_batch(ack).await,
+            Err(StateStoreError::AppendWAL(_))
+        ));
+        // The commit never reached the WAL, so resume re-reads the batch
+        assert_eq!(
+            manager(&store).resume_cursor().await.unwrap(),
+            Cursor::Default { offset: 0 }
+        );
+
+        // The failed ack doesn't wedge the manager on the spent token
+        let ack = state.begin_batch(&batch("b1", 0, 10)).await.unwrap();
+        state.ack_batch(ack).await.unwrap();
+        assert_eq!(
+            manager(&store).resume_cursor().await.unwrap(),
+            Cursor::Default { offset: 10 }
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use crate::transform::error::TransformError;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use model::{
+    core::value::{FieldValue, Value},
+    execution::pipeline::{AggregateFunction, Aggregation},
+    records::{OpType, Record},
+};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// Groups rows and computes each group's aggregates in memory, for sources
+/// the grouping cannot be pushed down to.
+///
+/// Aggregates follow SQL: nulls are skipped, `count()` counts rows and
+/// `count(col)` non-null values, and `sum`, `min`, `max` and `avg` of a
+/// group with no values are null. Integer sums stay integers (decimals once
+/// they overflow `i64`), sums with a float are floats and `avg` is a float.
+/// Groups are emitted in the order their first row was seen; without
+/// `group_by` there is always exactly one, even when no row was read.
+pub struct Aggregator {
+    aggregation: Aggregation,
+    table: String,
+    /// Group index by the debug form of its key values
+    index: HashMap<String, usize>,
+    groups: Vec<(Vec<Value>, Vec<Accumulator>)>,
+}
+
+enum Accumulator {
+    Count(i64),
+    Sum(Option<Value>),
+    Min(Option<Value>),
+    Max(Option<Value>),
+    Avg { sum: f64, count: u64 },
+}
+
+impl Aggregator {
+    pub fn new(aggregation: Aggregation, table: impl Into<String>) -> Self {
+        Self {
+            aggregation,
+            table: table.into(),
+            index: HashMap::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn accumulators(&self) -> Vec<Accumulator> {
+        self.aggregation
+            .aggregates
+            .iter()
+            .map(|a| match a.function {
+                AggregateFunction::Count => Accumulator::Count(0),
+                AggregateFunction::Sum => Accumulator::Sum(None),
+                AggregateFunction::Min => Accumulator::Min(None),
+                AggregateFunction::Max => Accumulator::Max(None),
+                AggregateFunction::Avg => Accumulator::Avg { sum: 0.0, count: 0 },
+            })
+            .collect()
+    }
+
+    /// Add `row` to its group.
+    pub fn push(&mut self, row: &Record) -> Result<(), TransformError> {
+        let key: Vec<Value> = self
+            .aggregation
+            .group_by
+            .iter()
+            .map(|c| row.get_value(&c.column))
+            .collect();
+        let idx = match self.index.get(&format!("{key:?}")) {
+            Some(idx) => *idx,
+            None => {
+                self.index.insert(format!("{key:?}"), self.groups.len());
+                self.groups.push((key, self.accumulators()));
+                self.groups.len() - 1
+            }
+        };
+
+        let accumulators = &mut self.groups[idx].1;
+        for (aggregate, acc) in self.aggregation.aggregates.iter().zip(accumulators) {
+            let value = match &aggregate.column {
+                Some(column) => row.get_value(&column.column),
+                // count() counts the row itself
+                None => Value::Boolean(true),
+            };
+            if value == Value::Null {
+                continue;
+            }
+            let fail = |reason: &str| {
+                TransformError::Transformation(format!("aggregate '{}': {reason}", aggregate.name))
+            };
+            match acc {
+                Accumulator::Count(n) => *n += 1,
+                Accumulator::Sum(sum) => {
+                    *sum = Some(match sum.take() {
+                        Some(total) => add(&total, &value)
+                            .ok_or_else(|| fail(&format!("cannot sum {value:?}")))?,
+                        None if as_f64(&value).is_some() => value,
+                        None => return Err(fail(&format!("cannot sum {value:?}"))),
+                    });
+                }
+                Accumulator::Min(min) | Accumulator::Max(min) => {
+                    let keep_new = match min {
+                        None => true,
+                        Some(current) => {
+                            let ordering = compare(&value, current)
+                                .ok_or_else(|| fail(&format!("cannot compare {value:?}")))?;
+                            match aggregate.function {
+                                AggregateFunction::Min => ordering == Ordering::Less,
+                                _ => ordering == Ordering::Greater,
+                            }
+                        }
+                    };
+                    if keep_new {
+                        *min = Some(value);
+                    }
+                }
+                Accumulator::Avg { sum, count } => {
+                    *sum +=
+                        as_f64(&value).ok_or_else(|| fail(&format!("cannot average {value:?}")))?;
+                    *count += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One row per group: the grouping columns, then the aggregates.
+    pub fn finish(mut self) -> Vec<Record> {
+        if self.aggregation.group_by.is_empty() && self.groups.is_empty() {
+            self.groups.push((Vec::new(), self.accumulators()));
+        }
+        let Aggregator {
+            aggregation,
+            table,
+            groups,
+            ..
+        } = self;
+
+        groups
+            .into_iter()
+            .map(|(key, accumulators)| {
+                let keys = aggregation
+                    .group_by
+                    .iter()
+                    .zip(key)
+                    .map(|(c, v)| field(&c.column, v));
+                let aggregates = aggregation
+                    .aggregates
+                    .iter()
+                    .zip(accumulators)
+                    .map(|(a, acc)| {
+                        let value = match acc {
+                            Accumulator::Count(n) => Value::Int(n),
+                            Accumulator::Sum(v) | Accumulator::Min(v) | Accumulator::Max(v) => {
+                                v.unwrap_or(Value::Null)
+                            }
+                            Accumulator::Avg { count: 0, .. } => Value::Null,
+                            Accumulator::Avg { sum, count } => Value::Float(sum / count as f64),
+                        };
+                        field(&a.name, value)
+                    });
+                Record::new(&table, keys.chain(aggregates).collect(), OpType::Insert)
+            })
+            .collect()
+    }
+}
+
+fn field(name: &str, value: Value) -> FieldValue {
+    FieldValue {
+        name: name.to_string(),
+        data_type: value.data_type(),
+        value: Some(value),
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Decimal(d) => d.to_f64(),
+        other => other.as_f64(),
+    }
+}
+
+fn as_decimal(value: &Value) -> Option<BigDecimal> {
+    match value {
+        Value::Int(n) => Some(BigDecimal::from(*n)),
+        Value::UInt(n) => Some(BigDecimal::from(*n)),
+        Value::Decimal(d) => Some(d.clone()),
+        _ => None,
+    }
+}
+
+fn add(total: &Value, value: &Value) -> Option<Value> {
+    let int = |v: &Value| match v {
+        Value::Int(n) => Some(i128::from(*n)),
+        Value::UInt(n) => Some(i128::from(*n)),
+        _ => None,
+    };
+    if let (Some(a), Some(b)) = (int(total), int(value)) {
+        let sum = a + b;
+        return Some(match i64::try_from(sum) {
+            Ok(n) => Value::Int(n),
+            Err(_) => Value::Decimal(BigDecimal::from(sum)),
+        });
+    }
+    if matches!(total, Value::Float(_)) || matches!(value, Value::Float(_)) {
+        return Some(Value::Float(as_f64(total)? + as_f64(value)?));
+    }
+    Some(Value::Decimal(as_decimal(total)? + as_decimal(value)?))
+}
+
+pub(crate) fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int(_) | Value::UInt(_), Value::Int(_) | Value::UInt(_))
+        | (Value::Decimal(_), Value::Int(_) | Value::UInt(_) | Value::Decimal(_))
+        | (Value::Int(_) | Value::UInt(_), Value::Decimal(_)) => {
+            Some(as_decimal(a)?.cmp(&as_decimal(b)?))
+        }
+        (Value::Float(_), _) | (_, Value::Float(_)) => as_f64(a)?.partial_cmp(&as_f64(b)?),
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        (Value::Date(x), Value::Date(y)) => Some(x.cmp(y)),
+        (Value::Time { value: x, .. }, Value::Time { value: y, .. }) => Some(x.cmp(y)),
+        (Value::Timestamp { value: x, .. }, Value::Timestamp { value: y, .. }) => Some(x.cmp(y)),
+        (Value::Boolean(x), Value::Boolean(y)) => Some(x.cmp(y)),
+        (Value::Uuid(x), Value::Uuid(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{execution::pipeline::Aggregate, pagination::cursor::QualCol};
+
+    fn col(column: &str) -> QualCol {
+        QualCol {
+            table: "orders".to_string(),
+            column: column.to_string(),
+        }
+    }
+
+    fn aggregate(name: &str, function: AggregateFunction, column: Option<&str>) -> Aggregate {
+        Aggregate {
+            name: name.to_string(),
+            function,
+            column: column.map(col),
+        }
+    }
+
+    fn row(customer: i64, amount: Value) -> Record {
+        Record::new(
+            "orders",
+            vec![
+                field("customer_id", Value::Int(customer)),
+                field("amount", amount),
+            ],
+            OpType::Insert,
+        )
+    }
+
+    #[test]
+    fn test_aggregator_groups_rows() {
+        let mut aggregator = Aggregator::new(
+            Aggregation {
+                group_by: vec![col("customer_id")],
+                aggregates: vec![
+                    aggregate("total", AggregateFunction::Sum, Some("amount")),
+                    aggregate("orders", AggregateFunction::Count, None),
+                    aggregate("priced", AggregateFunction::Count, Some("amount")),
+                    aggregate("smallest", AggregateFunction::Min, Some("amount")),
+                    aggregate("largest", AggregateFunction::Max, Some("amount")),
+                    aggregate("average", AggregateFunction::Avg, Some("amount")),
+                ],
+            },
+            "orders",
+        );
+        for r in [
+            row(1, Value::Int(10)),
+            row(2, Value::Null),
+            row(1, Value::Int(30)),
+            row(1, Value::Null),
+        ] {
+            aggregator.push(&r).unwrap();
+        }
+        let rows = aggregator.finish();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].schema, "orders");
+        assert_eq!(rows[0].get_value("customer_id"), Value::Int(1));
+        assert_eq!(rows[0].get_value("total"), Value::Int(40));
+        assert_eq!(rows[0].get_value("orders"), Value::Int(3));
+        assert_eq!(rows[0].get_value("priced"), Value::Int(2));
+        assert_eq!(rows[0].get_value("smallest"), Value::Int(10));
+        assert_eq!(rows[0].get_value("largest"), Value::Int(30));
+        assert_eq!(rows[0].get_value("average"), Value::Float(20.0));
+
+        // A group with only nulls sums to null but still counts its rows
+        assert_eq!(rows[1].get_value("customer_id"), Value::Int(2));
+        assert_eq!(rows[1].get_value("total"), Value::Null);
+        assert_eq!(rows[1].get_value("orders"), Value::Int(1));
+        assert_eq!(rows[1].get_value("priced"), Value::Int(0));
+        assert_eq!(rows[1].get_value("average"), Value::Null);
+    }
+
+    #[test]
+    fn test_aggregator_sums_mixed_numbers() {
+        let aggregation = |function| Aggregation {
+            group_by: vec![],
+            aggregates: vec![aggregate("total", function, Some("amount"))],
+        };
+
+        // Without group_by an empty source still has its one group
+        let rows = Aggregator::new(aggregation(AggregateFunction::Sum), "orders").finish();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_value("total"), Value::Null);
+
+        let mut aggregator = Aggregator::new(aggregation(AggregateFunction::Sum), "orders");
+        aggregator.push(&row(1, Value::Int(i64::MAX))).unwrap();
+        aggregator.push(&row(1, Value::Int(1))).unwrap();
+        let rows = aggregator.finish();
+        assert_eq!(
+            rows[0].get_value("total"),
+            Value::Decimal("9223372036854775808".parse().unwrap())
+        );
+
+        let mut aggregator = Aggregator::new(aggregation(AggregateFunction::Sum), "orders");
+        aggregator
+            .push(&row(1, Value::Decimal("1.25".parse().unwrap())))
+            .unwrap();
+        aggregator.push(&row(1, Value::Int(2))).unwrap();
+        assert_eq!(
+            aggregator.finish()[0].get_value("total"),
+            Value::Decimal("3.25".parse().unwrap())
+        );
+
+        let mut aggregator = Aggregator::new(aggregation(AggregateFunction::Sum), "orders");
+        let err = aggregator
+            .push(&row(1, Value::String("ten".to_string())))
+            .unwrap_err();
+        assert!(err.to_string().contains("aggregate 'total': cannot sum"));
+
+        let mut aggregator = Aggregator::new(aggregation(AggregateFunction::Max), "orders");
+        aggregator
+            .push(&row(1, Value::String("apple".to_string())))
+            .unwrap();
+        aggregator
+            .push(&row(1, Value::String("pear".to_string())))
+            .unwrap();
+        assert_eq!(
+            aggregator.finish()[0].get_value("total"),
+            Value::String("pear".to_string())
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::transform::{
+    columns::{self, TableColumns},
+    error::TransformError,
+    pipeline::Transform,
+};
+use model::{
+    core::{encoding::ArrayFormat, types::Type, value::Value},
+    records::Record,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Serializes arrays bound for destination columns that do not hold arrays,
+/// such as MySQL columns or Postgres text columns, with the pipeline's
+/// `array_format`.
+pub struct ArraySerializer {
+    format: ArrayFormat,
+    delimiter: String,
+    /// Columns that do not hold arrays
+    columns: TableColumns<()>,
+}
+
+impl ArraySerializer {
+    pub fn new(
+        format: ArrayFormat,
+        delimiter: &str,
+        columns: HashMap<String, HashSet<String>>,
+    ) -> Self {
+        Self {
+            format,
+            delimiter: delimiter.to_string(),
+            columns: columns.into(),
+        }
+    }
+}
+
+impl Transform for ArraySerializer {
+    fn apply(&self, row: &mut Record) -> Result<(), TransformError> {
+        let Some(columns) = self.columns.table(&row.schema) else {
+            return Ok(());
+        };
+
+        for field in &mut row.fields {
+            let Some(Value::Array(items)) = &field.value else {
+                continue;
+            };
+            if !columns::contains(columns, &field.name) {
+                continue;
+            }
+            field.value = Some(Value::String(self.format.encode(items, &self.delimiter)));
+            field.data_type = Type::Text { charset: None };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{core::value::FieldValue, records::OpType};
+
+    fn row() -> Record {
+        let tags = Value::Array(vec![
+            Value::String("rust".to_string()),
+            Value::String("sql".to_string()),
+        ]);
+        let fields = ["Tags", "labels"]
+            .into_iter()
+            .map(|name| FieldValue {
+                name: name.to_string(),
+                value: Some(tags.clone()),
+                data_type: tags.data_type(),
+            })
+            .collect();
+        Record::new("posts", fields, OpType::Insert)
+    }
+
+    fn serializer(format: ArrayFormat) -> ArraySerializer {
+        let columns = HashSet::from(["tags".to_string()]);
+        ArraySerializer::new(format, "|", HashMap::from([("posts".to_string(), columns)]))
+    }
+
+    #[test]
+    fn test_array_serializer() {
+        let mut r = row();
+        serializer(ArrayFormat::Json).apply(&mut r).unwrap();
+        assert_eq!(
+            r.fields[0].value,
+            Some(Value::String(r#"["rust","sql"]"#.to_string()))
+        );
+        // Array destination columns keep the array
+        assert!(matches!(r.fields[1].value, Some(Value::Array(_))));
+
+        let mut r = row();
+        serializer(ArrayFormat::Delimited).apply(&mut r).unwrap();
+        assert_eq!(
+            r.fields[0].value,
+            Some(Value::String("rust|sql".to_string()))
+        );
+    }
+}
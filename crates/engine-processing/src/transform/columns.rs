@@ -0,0 +1,76 @@
+use std::collections::{HashMap, HashSet};
+
+/// Destination columns of each table the pipeline writes, with a value per
+/// column, matched by lowercased column name.
+pub struct TableColumns<T> {
+    /// table -> column (lowercased) -> value
+    tables: HashMap<String, HashMap<String, T>>,
+}
+
+impl<T> TableColumns<T> {
+    pub fn new(tables: HashMap<String, HashMap<String, T>>) -> Self {
+        let tables = tables
+            .into_iter()
+            .map(|(table, columns)| {
+                let columns = columns
+                    .into_iter()
+                    .map(|(column, value)| (column.to_ascii_lowercase(), value))
+                    .collect();
+                (table, columns)
+            })
+            .collect();
+        Self { tables }
+    }
+
+    /// The columns of `table`, if it has any.
+    pub fn table(&self, table: &str) -> Option<&HashMap<String, T>> {
+        self.tables.get(table).or_else(|| {
+            // Rows of a single-table pipeline may still carry the source name.
+            match self.tables.len() {
+                1 => self.tables.values().next(),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl From<HashMap<String, HashSet<String>>> for TableColumns<()> {
+    fn from(tables: HashMap<String, HashSet<String>>) -> Self {
+        let tables = tables
+            .into_iter()
+            .map(|(table, columns)| (table, columns.into_iter().map(|c| (c, ())).collect()))
+            .collect();
+        Self::new(tables)
+    }
+}
+
+/// Whether `columns` holds the column `name`, in any case.
+pub fn contains<T>(columns: &HashMap<String, T>, name: &str) -> bool {
+    columns.contains_key(&name.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_columns() {
+        let columns = TableColumns::from(HashMap::from([(
+            "posts".to_string(),
+            HashSet::from(["Tags".to_string()]),
+        )]));
+        let posts = columns.table("posts").unwrap();
+        assert!(contains(posts, "TAGS"));
+        assert!(!contains(posts, "title"));
+
+        // A single table also matches rows carrying the source name
+        assert!(columns.table("src_posts").is_some());
+
+        let two = TableColumns::new(HashMap::from([
+            ("a".to_string(), HashMap::from([("x".to_string(), 1)])),
+            ("b".to_string(), HashMap::from([("X".to_string(), 2)])),
+        ]));
+        assert_eq!(two.table("b").unwrap().get("x"), Some(&2));
+        assert!(two.table("c").is_none());
+    }
+}
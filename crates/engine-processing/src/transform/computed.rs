@@ -1,22 +1,163 @@
 use super::pipeline::Transform;
-use crate::transform::error::TransformError;
+use crate::transform::{
+    error::TransformError,
+    keymap::{KeyMap, key_text},
+};
 use engine_core::context::env::EnvContext;
 use expression_engine::Evaluator;
 use model::{
     core::value::{FieldValue, Value},
+    execution::expr::{CompiledExpression, FN_LOOKUP_KEY, FN_ROW_NUMBER},
     records::Record,
     transform::mapping::TransformationMetadata,
 };
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 pub struct ComputedTransform {
     mapping: TransformationMetadata,
     env: Arc<EnvContext>,
+    /// Counter `row_number()` draws from
+    row_numbers: Option<Arc<AtomicU64>>,
+    /// Key crosswalks `lookup_key()` reads, by lowercased table name
+    keymaps: HashMap<String, Arc<KeyMap>>,
+    /// (table, column) of the computed columns that call a bound function
+    bound_columns: HashSet<(String, String)>,
 }
 
 impl ComputedTransform {
     pub fn new(mapping: TransformationMetadata, env: Arc<EnvContext>) -> Self {
-        Self { mapping, env }
+        Self {
+            mapping,
+            env,
+            row_numbers: None,
+            keymaps: HashMap::new(),
+            bound_columns: HashSet::new(),
+        }
+    }
+
+    /// Binds `row_number()` to `counter`. Each row draws one number, shared
+    /// by all of its computed columns.
+    pub fn with_row_numbers(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.row_numbers = Some(counter);
+        self.find_bound_columns();
+        self
+    }
+
+    /// Binds `lookup_key(table, key)` to the crosswalks in `keymaps`. A key
+    /// missing from its crosswalk fails the row.
+    pub fn with_keymaps(mut self, keymaps: HashMap<String, Arc<KeyMap>>) -> Self {
+        self.keymaps = keymaps;
+        self.find_bound_columns();
+        self
+    }
+
+    fn find_bound_columns(&mut self) {
+        let bound: Vec<_> = self
+            .mapping
+            .field_mappings
+            .computed_fields
+            .iter()
+            .flat_map(|(table, fields)| {
+                fields
+                    .iter()
+                    .filter(|f| self.calls_bound_function(&f.expression))
+                    .map(|f| (table.clone(), f.name.clone()))
+            })
+            .collect();
+        self.bound_columns = bound.into_iter().collect();
+    }
+
+    fn is_bound_call(&self, expression: &CompiledExpression) -> bool {
+        (self.row_numbers.is_some() && is_call(expression, FN_ROW_NUMBER, 0))
+            || (!self.keymaps.is_empty() && is_call(expression, FN_LOOKUP_KEY, 2))
+    }
+
+    fn calls_bound_function(&self, expression: &CompiledExpression) -> bool {
+        expression
+            .clone()
+            .try_rewrite(&mut |e| match self.is_bound_call(e) {
+                true => Err(()),
+                false => Ok(None),
+            })
+            .is_err()
+    }
+
+    /// `expression` with its bound calls replaced by their values for
+    /// `row`, or `None` when the column calls none. The row's number is
+    /// drawn on first use.
+    fn bind(
+        &self,
+        column: &str,
+        expression: &CompiledExpression,
+        row: &Record,
+        env_getter: &dyn Fn(&str) -> Option<String>,
+        number: &mut Option<u64>,
+    ) -> Result<Option<CompiledExpression>, TransformError> {
+        if !self
+            .bound_columns
+            .contains(&(row.schema.clone(), column.to_string()))
+        {
+            return Ok(None);
+        }
+        let mut bound = expression.clone();
+        bound.try_rewrite(&mut |e| self.bind_call(e, row, env_getter, number))?;
+        Ok(Some(bound))
+    }
+
+    fn bind_call(
+        &self,
+        expression: &CompiledExpression,
+        row: &Record,
+        env_getter: &dyn Fn(&str) -> Option<String>,
+        number: &mut Option<u64>,
+    ) -> Result<Option<CompiledExpression>, TransformError> {
+        if !self.is_bound_call(expression) {
+            return Ok(None);
+        }
+        let CompiledExpression::FunctionCall { name, args } = expression else {
+            return Ok(None);
+        };
+        if name.eq_ignore_ascii_case(FN_ROW_NUMBER) {
+            let counter = self.row_numbers.as_ref().expect("row_number() is bound");
+            let n = *number.get_or_insert_with(|| counter.fetch_add(1, Ordering::Relaxed) + 1);
+            return Ok(Some(CompiledExpression::Literal(Value::Int(n as i64))));
+        }
+
+        // lookup_key(table, key); the key may itself call bound functions
+        let mut key = args[1].clone();
+        key.try_rewrite(&mut |e| self.bind_call(e, row, env_getter, number))?;
+        let table = match args[0].evaluate(row, &self.mapping, env_getter) {
+            Some(Value::String(table)) => table.to_ascii_lowercase(),
+            other => {
+                return Err(TransformError::Transformation(format!(
+                    "lookup_key expects a table name, got {other:?}"
+                )));
+            }
+        };
+        let keymap = self.keymaps.get(&table).ok_or_else(|| {
+            TransformError::Transformation(format!(
+                "lookup_key: no key crosswalk is loaded for '{table}'"
+            ))
+        })?;
+        let Some(old_key) = key
+            .evaluate(row, &self.mapping, env_getter)
+            .as_ref()
+            .and_then(key_text)
+        else {
+            return Ok(Some(CompiledExpression::Literal(Value::Null)));
+        };
+        match keymap.get(&old_key) {
+            Some(new_key) => Ok(Some(CompiledExpression::Literal(new_key.clone()))),
+            None => Err(TransformError::Transformation(format!(
+                "lookup_key: no key was recorded for {old_key} in '{table}'"
+            ))),
+        }
     }
 }
 
@@ -26,11 +167,17 @@ impl Transform for ComputedTransform {
         let env = self.env.clone();
         let env_getter = move |key: &str| env.get(key);
         if let Some(computed_fields) = self.mapping.field_mappings.computed_fields.get(&table) {
+            let mut number = None;
             for computed in computed_fields {
-                if let Some(value) = computed
-                    .expression
-                    .evaluate(row, &self.mapping, &env_getter)
-                {
+                let bound = self.bind(
+                    &computed.name,
+                    &computed.expression,
+                    row,
+                    &env_getter,
+                    &mut number,
+                )?;
+                let expression = bound.as_ref().unwrap_or(&computed.expression);
+                if let Some(value) = expression.evaluate(row, &self.mapping, &env_getter) {
                     update_row(row, &computed.name, &value);
                 } else {
                     return Err(TransformError::Transformation(format!(
@@ -44,6 +191,11 @@ impl Transform for ComputedTransform {
     }
 }
 
+fn is_call(expression: &CompiledExpression, function: &str, arity: usize) -> bool {
+    matches!(expression, CompiledExpression::FunctionCall { name, args }
+        if args.len() == arity && name.eq_ignore_ascii_case(function))
+}
+
 // TODO: Optimize this function to avoid searching for the column multiple times
 // and to handle the case where the column is not found.
 fn update_row(row: &mut Record, column: &str, column_value: &Value) {
@@ -61,3 +213,110 @@ fn update_row(row: &mut Record, column: &str, column_value: &Value) {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{
+        execution::expr::BinaryOp,
+        records::OpType,
+        transform::{computed_field::ComputedField, mapping::FieldTransformations},
+    };
+    use std::collections::HashMap;
+
+    fn row_number() -> CompiledExpression {
+        CompiledExpression::FunctionCall {
+            name: FN_ROW_NUMBER.to_string(),
+            args: vec![],
+        }
+    }
+
+    fn value(row: &Record, column: &str) -> Option<Value> {
+        row.get(column).and_then(|f| f.value.clone())
+    }
+
+    #[test]
+    fn test_row_number_is_drawn_once_per_row() {
+        let mut field_mappings = FieldTransformations::new();
+        let plus_100 = CompiledExpression::Binary {
+            left: Box::new(row_number()),
+            op: BinaryOp::Add,
+            right: Box::new(CompiledExpression::Literal(Value::Int(100))),
+        };
+        field_mappings.computed_fields.insert(
+            "orders".to_string(),
+            vec![
+                ComputedField::new("id", &row_number()),
+                ComputedField::new("legacy_id", &plus_100),
+            ],
+        );
+        let mapping = TransformationMetadata {
+            entities: Default::default(),
+            field_mappings,
+            foreign_fields: HashMap::new(),
+            plugin_columns: vec![],
+        };
+        let counter = Arc::new(AtomicU64::new(0));
+        let transform = ComputedTransform::new(mapping, Arc::new(EnvContext::empty()))
+            .with_row_numbers(counter.clone());
+
+        for n in 1..=2 {
+            let mut row = Record::new("orders", vec![], OpType::default());
+            transform.apply(&mut row).unwrap();
+            assert_eq!(value(&row, "id"), Some(Value::Int(n)));
+            assert_eq!(value(&row, "legacy_id"), Some(Value::Int(n + 100)));
+        }
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_lookup_key_reads_the_crosswalk() {
+        let lookup = CompiledExpression::FunctionCall {
+            name: FN_LOOKUP_KEY.to_string(),
+            args: vec![
+                CompiledExpression::Literal(Value::String("Customers".to_string())),
+                CompiledExpression::Identifier("customer_id".to_string()),
+            ],
+        };
+        let mut field_mappings = FieldTransformations::new();
+        field_mappings.computed_fields.insert(
+            "orders".to_string(),
+            vec![ComputedField::new("customer_id", &lookup)],
+        );
+        let mapping = TransformationMetadata {
+            entities: Default::default(),
+            field_mappings,
+            foreign_fields: HashMap::new(),
+            plugin_columns: vec![],
+        };
+        let keymap = KeyMap::from([("7".to_string(), Value::String("c-7".to_string()))]);
+        let transform = ComputedTransform::new(mapping, Arc::new(EnvContext::empty()))
+            .with_keymaps(HashMap::from([("customers".to_string(), Arc::new(keymap))]));
+
+        let order = |customer_id: Value| {
+            Record::new(
+                "orders",
+                vec![FieldValue {
+                    name: "customer_id".to_string(),
+                    data_type: customer_id.data_type(),
+                    value: Some(customer_id),
+                }],
+                OpType::default(),
+            )
+        };
+
+        let mut row = order(Value::Int(7));
+        transform.apply(&mut row).unwrap();
+        assert_eq!(
+            value(&row, "customer_id"),
+            Some(Value::String("c-7".to_string()))
+        );
+
+        let mut row = order(Value::Null);
+        transform.apply(&mut row).unwrap();
+        assert_eq!(value(&row, "customer_id"), Some(Value::Null));
+
+        let mut row = order(Value::Int(8));
+        assert!(transform.apply(&mut row).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::transform::{
+    columns::TableColumns,
+    error::TransformError,
+    pipeline::Validator,
+    validation::{ValidationAction, ValidationResult},
+};
+use model::{core::value::Value, records::Record};
+use std::collections::HashMap;
+
+/// Rejects rows carrying a value that is not a label of the destination
+/// enum column it is written to. Catching it here reports the offending
+/// table, column and value instead of a driver error for the whole batch.
+pub struct EnumLabelValidator {
+    /// Labels of each enum column
+    labels: TableColumns<Vec<String>>,
+}
+
+impl EnumLabelValidator {
+    pub fn new(labels: HashMap<String, HashMap<String, Vec<String>>>) -> Self {
+        Self {
+            labels: TableColumns::new(labels),
+        }
+    }
+}
+
+impl Validator for EnumLabelValidator {
+    fn validate(&self, row: &Record) -> Result<ValidationResult, TransformError> {
+        let Some(columns) = self.labels.table(&row.schema) else {
+            return Ok(ValidationResult::Pass);
+        };
+
+        for field in &row.fields {
+            let label = match &field.value {
+                Some(Value::Enum { value, .. }) | Some(Value::String(value)) => value,
+                _ => continue,
+            };
+            let Some(labels) = columns.get(&field.name.to_ascii_lowercase()) else {
+                continue;
+            };
+            if !labels.iter().any(|l| l == label) {
+                return Ok(ValidationResult::Failed {
+                    rule: "enum_label".to_string(),
+                    message: format!(
+                        "'{label}' is not a label of {}.{} (expected one of: {})",
+                        row.schema,
+                        field.name,
+                        labels.join(", ")
+                    ),
+                    action: ValidationAction::Fail,
+                });
+            }
+        }
+
+        Ok(ValidationResult::Pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{
+        core::{types::Type, value::FieldValue},
+        records::OpType,
+    };
+
+    fn row(table: &str, rating: Option<Value>) -> Record {
+        let fields = vec![FieldValue {
+            name: "Rating".to_string(),
+            value: rating,
+            data_type: Type::Text { charset: None },
+        }];
+        Record::new(table, fields, OpType::Insert)
+    }
+
+    fn validator() -> EnumLabelValidator {
+        let columns = HashMap::from([(
+            "rating".to_string(),
+            vec!["G".to_string(), "PG".to_string(), "R".to_string()],
+        )]);
+        EnumLabelValidator::new(HashMap::from([("film".to_string(), columns)]))
+    }
+
+    #[test]
+    fn test_enum_label_validator() {
+        let v = validator();
+        let enum_value = |value: &str| Value::Enum {
+            type_name: "rating".to_string(),
+            value: value.to_string(),
+        };
+
+        let pass = |r: Record| matches!(v.validate(&r).unwrap(), ValidationResult::Pass);
+        assert!(pass(row("film", Some(enum_value("PG")))));
+        assert!(pass(row("film", Some(Value::String("R".into())))));
+        assert!(pass(row("film", Some(Value::Null))));
+        assert!(pass(row("film", None)));
+
+        match v.validate(&row("film", Some(enum_value("XXX")))).unwrap() {
+            ValidationResult::Failed {
+                rule,
+                message,
+                action,
+            } => {
+                assert_eq!(rule, "enum_label");
+                assert!(message.contains("'XXX'"), "{message}");
+                assert_eq!(action, ValidationAction::Fail);
+            }
+            ValidationResult::Pass => panic!("unknown label passed"),
+        }
+
+        // A single-table pipeline still validates rows named after the source.
+        assert!(!pass(row("films_src", Some(Value::String("X".into())))));
+    }
+}
@@ -9,7 +9,13 @@ use model::{
     },
     records::Record,
 };
-use std::{fs::OpenOptions, io::Write, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::Arc,
+};
 use tracing::{debug, error, info};
 
 /// Writer for failed rows to various destinations.
@@ -151,12 +157,32 @@ impl FailedRowWriter {
     }
 }
 
+/// Rows a run appended to an NDJSON quarantine file, counted per pipeline.
+/// Lines that aren't failed-row records are ignored.
+pub fn quarantined_rows(path: &str, run_id: &str) -> std::io::Result<HashMap<String, u64>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut counts = HashMap::new();
+    for line in BufReader::new(file).lines() {
+        let Ok(row) = serde_json::from_str::<FailedRow>(&line?) else {
+            continue;
+        };
+        if row.metadata.run_id == run_id {
+            *counts.entry(row.pipeline_name).or_insert(0) += 1;
+        }
+    }
+    Ok(counts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use engine_core::{plan::execution::ExecutionPlan, state::sled_store::SledStateStore};
     use model::{core::value::Value, execution::failed_row::ProcessingStage};
-    use std::collections::HashMap;
     use tempfile::NamedTempFile;
 
     fn create_test_failed_row() -> FailedRow {
@@ -183,9 +209,16 @@ mod tests {
         let doc = SmqlDocument {
             define_block: None,
             execution_block: None,
+            state_block: None,
+            settings_block: None,
+            notify_blocks: vec![],
             connections: vec![],
             pipelines: vec![],
             plugins: vec![],
+            materialized_views: vec![],
+            runbook_block: None,
+            profiles: vec![],
+            barriers: vec![],
             span: Span::new(0, 0, 0, 0),
         };
         let env = Arc::new(EnvContext::empty());
@@ -286,4 +319,43 @@ mod tests {
         assert!(result.is_ok());
         assert!(path.exists());
     }
+
+    #[tokio::test]
+    async fn test_quarantined_rows_counts_run_rows_per_pipeline() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let destination = FailedRowsDestination::File {
+            path: path.clone(),
+            format: FileFormat::Json,
+        };
+        let writer = FailedRowWriter::new(destination, create_test_context().await);
+
+        let in_run = |pipeline: &str, run_id: &str| {
+            let mut row = create_test_failed_row().with_execution_context(
+                run_id.to_string(),
+                Some("batch-1".to_string()),
+                Some(0),
+            );
+            row.pipeline_name = pipeline.to_string();
+            row
+        };
+        let rows = vec![
+            in_run("orders", "run-a"),
+            in_run("orders", "run-a"),
+            in_run("users", "run-a"),
+            in_run("orders", "run-b"),
+        ];
+        writer.write_batch(&rows).await.unwrap();
+
+        let counts = quarantined_rows(&path, "run-a").unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["orders"], 2);
+        assert_eq!(counts["users"], 1);
+
+        assert!(
+            quarantined_rows("/nonexistent/quarantine.json", "run-a")
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,23 @@
+use model::core::value::Value;
+use std::collections::HashMap;
+
+/// Key crosswalk of one table: the destination key each source key was
+/// loaded under, by the source key's text
+pub type KeyMap = HashMap<String, Value>;
+
+/// Columns a pipeline with `emit_keymap = true` records its crosswalk from
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapColumns {
+    /// Destination table the crosswalk is recorded for
+    pub table: String,
+    /// Source primary key, read before the row is transformed
+    pub source_key: String,
+    /// Destination primary key, read once it is
+    pub destination_key: String,
+}
+
+/// Text a key is recorded and looked up by, so that `42` and `"42"` are
+/// the same key. Null keys have none.
+pub fn key_text(value: &Value) -> Option<String> {
+    value.as_string()
+}
@@ -1,9 +1,20 @@
+pub mod aggregate;
+pub mod array;
+pub mod columns;
 pub mod computed;
+pub mod enums;
 pub mod error;
 pub mod failed_row_writer;
 pub mod filter;
+pub mod keymap;
 pub mod mapping;
 pub mod pipeline;
+pub mod pivot;
 pub mod pruner;
+pub mod script;
+pub mod sort;
+pub mod text;
+pub mod timezone;
+pub mod unnest;
 pub mod validation;
 pub mod wasm;
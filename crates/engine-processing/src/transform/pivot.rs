@@ -0,0 +1,270 @@
+use crate::transform::error::TransformError;
+use model::{
+    core::value::{FieldValue, Value},
+    execution::pipeline::Pivot,
+    records::{OpType, Record},
+};
+use std::collections::HashMap;
+
+/// Turns key-value rows into one wide row per entity.
+///
+/// Rows belong to the same entity when their `group_by` columns (every
+/// column but the key and the value, when none are given) are equal. The
+/// wide row holds the entity columns, then one column per expected key in
+/// the order the block lists them; a key no row gave is null. Rows whose key
+/// is not expected, or is null, are skipped and counted. An entity giving the
+/// same key twice fails. Entities are emitted in the order their first row
+/// was seen.
+pub struct Pivoter {
+    pivot: Pivot,
+    table: String,
+    /// Entity index by the debug form of its columns
+    index: HashMap<String, usize>,
+    entities: Vec<Entity>,
+    skipped: u64,
+}
+
+struct Entity {
+    columns: Vec<FieldValue>,
+    /// Value of each expected key, in `keys` order
+    values: Vec<Option<FieldValue>>,
+}
+
+impl Pivoter {
+    pub fn new(pivot: Pivot, table: impl Into<String>) -> Self {
+        Self {
+            pivot,
+            table: table.into(),
+            index: HashMap::new(),
+            entities: Vec::new(),
+            skipped: 0,
+        }
+    }
+
+    /// Add `row`'s value to its entity.
+    pub fn push(&mut self, row: &Record) -> Result<(), TransformError> {
+        let key = row.get_value(&self.pivot.key).as_string();
+        let Some(slot) = key
+            .as_deref()
+            .and_then(|k| self.pivot.keys.iter().position(|e| e == k))
+        else {
+            self.skipped += 1;
+            return Ok(());
+        };
+
+        let columns: Vec<FieldValue> = if self.pivot.group_by.is_empty() {
+            row.fields
+                .iter()
+                .filter(|f| {
+                    !f.name.eq_ignore_ascii_case(&self.pivot.key)
+                        && !f.name.eq_ignore_ascii_case(&self.pivot.value)
+                })
+                .cloned()
+                .collect()
+        } else {
+            self.pivot
+                .group_by
+                .iter()
+                .map(|c| {
+                    row.get(c).cloned().unwrap_or_else(|| FieldValue {
+                        name: c.clone(),
+                        value: Some(Value::Null),
+                        data_type: Value::Null.data_type(),
+                    })
+                })
+                .collect()
+        };
+        let id = format!("{:?}", columns.iter().map(|f| &f.value).collect::<Vec<_>>());
+        let idx = match self.index.get(&id) {
+            Some(idx) => *idx,
+            None => {
+                self.index.insert(id, self.entities.len());
+                self.entities.push(Entity {
+                    columns,
+                    values: vec![None; self.pivot.keys.len()],
+                });
+                self.entities.len() - 1
+            }
+        };
+
+        let entity = &mut self.entities[idx];
+        if entity.values[slot].is_some() {
+            let columns: Vec<String> = entity
+                .columns
+                .iter()
+                .map(|f| format!("{}={:?}", f.name, f.value))
+                .collect();
+            return Err(TransformError::Transformation(format!(
+                "pivot: key '{}' appears more than once for ({})",
+                self.pivot.keys[slot],
+                columns.join(", ")
+            )));
+        }
+        let value = row.get(&self.pivot.value);
+        entity.values[slot] = Some(FieldValue {
+            name: self.pivot.keys[slot].clone(),
+            value: Some(value.and_then(|f| f.value.clone()).unwrap_or(Value::Null)),
+            data_type: value.map_or_else(|| Value::Null.data_type(), |f| f.data_type.clone()),
+        });
+        Ok(())
+    }
+
+    /// Rows skipped because their key was not expected
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// One wide row per entity.
+    pub fn finish(self) -> Vec<Record> {
+        let Pivoter {
+            pivot,
+            table,
+            entities,
+            ..
+        } = self;
+        entities
+            .into_iter()
+            .map(|entity| {
+                let mut fields = entity.columns;
+                fields.extend(
+                    entity
+                        .values
+                        .into_iter()
+                        .zip(&pivot.keys)
+                        .map(|(value, key)| {
+                            value.unwrap_or_else(|| FieldValue {
+                                name: key.clone(),
+                                value: Some(Value::Null),
+                                data_type: Value::Null.data_type(),
+                            })
+                        }),
+                );
+                Record::new(&table, fields, OpType::Insert)
+            })
+            .collect()
+    }
+}
+
+/// Turns a wide row into one key-value row per listed column, like SQL
+/// `UNPIVOT`: every other column is copied into each row, and a column that
+/// is null or missing gives no row.
+pub fn unpivot(pivot: &Pivot, row: &Record) -> Vec<Record> {
+    let is_listed = |name: &str| pivot.keys.iter().any(|k| k.eq_ignore_ascii_case(name));
+    let base: Vec<FieldValue> = row
+        .fields
+        .iter()
+        .filter(|f| !is_listed(&f.name))
+        .cloned()
+        .collect();
+
+    pivot
+        .keys
+        .iter()
+        .filter_map(|column| {
+            let field = row.get(column)?;
+            let value = field.value.clone().filter(|v| *v != Value::Null)?;
+            let mut fields = base.clone();
+            fields.push(FieldValue {
+                name: pivot.key.clone(),
+                value: Some(Value::String(column.clone())),
+                data_type: Value::String(column.clone()).data_type(),
+            });
+            fields.push(FieldValue {
+                name: pivot.value.clone(),
+                value: Some(value),
+                data_type: field.data_type.clone(),
+            });
+            Some(Record::new(&row.schema, fields, row.op_type))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::execution::pipeline::PivotDirection;
+
+    fn field(name: &str, value: Value) -> FieldValue {
+        FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        }
+    }
+
+    fn eav(id: i64, product: i64, attr: &str, value: &str) -> Record {
+        Record::new(
+            "product_attrs",
+            vec![
+                field("id", Value::Int(id)),
+                field("product_id", Value::Int(product)),
+                field("attr", Value::String(attr.to_string())),
+                field("val", Value::String(value.to_string())),
+            ],
+            OpType::Insert,
+        )
+    }
+
+    fn pivot(direction: PivotDirection, group_by: &[&str]) -> Pivot {
+        Pivot {
+            direction,
+            key: "attr".to_string(),
+            value: "val".to_string(),
+            keys: vec!["color".to_string(), "size".to_string()],
+            group_by: group_by.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_pivot_groups_entities() {
+        let mut pivoter = Pivoter::new(pivot(PivotDirection::Pivot, &["product_id"]), "products");
+        for row in [
+            eav(1, 10, "size", "L"),
+            eav(2, 11, "color", "blue"),
+            eav(3, 10, "color", "red"),
+            eav(4, 10, "legacy_flag", "x"),
+        ] {
+            pivoter.push(&row).unwrap();
+        }
+        assert_eq!(pivoter.skipped(), 1);
+
+        let rows = pivoter.finish();
+        assert_eq!(rows.len(), 2);
+        let names: Vec<_> = rows[0].fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["product_id", "color", "size"]);
+        assert_eq!(rows[0].get_value("color"), Value::String("red".to_string()));
+        assert_eq!(rows[0].get_value("size"), Value::String("L".to_string()));
+        assert_eq!(rows[1].get_value("product_id"), Value::Int(11));
+        assert_eq!(rows[1].get_value("size"), Value::Null);
+
+        let mut pivoter = Pivoter::new(pivot(PivotDirection::Pivot, &["product_id"]), "products");
+        pivoter.push(&eav(1, 10, "size", "L")).unwrap();
+        let err = pivoter.push(&eav(2, 10, "size", "M")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("key 'size' appears more than once")
+        );
+    }
+
+    #[test]
+    fn test_unpivot_skips_nulls() {
+        let row = Record::new(
+            "products",
+            vec![
+                field("product_id", Value::Int(10)),
+                field("color", Value::String("red".to_string())),
+                field("size", Value::Null),
+            ],
+            OpType::Insert,
+        );
+        let rows = unpivot(&pivot(PivotDirection::Unpivot, &[]), &row);
+        assert_eq!(rows.len(), 1);
+        let names: Vec<_> = rows[0].fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["product_id", "attr", "val"]);
+        assert_eq!(
+            rows[0].get_value("attr"),
+            Value::String("color".to_string())
+        );
+        assert_eq!(rows[0].get_value("val"), Value::String("red".to_string()));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::transform::{error::TransformError, pipeline::Transform};
+use model::{
+    core::value::{FieldValue, Value},
+    execution::pipeline::RowScript,
+    records::Record,
+};
+use rhai::{AST, Dynamic, Engine, Map, Scope};
+use tracing::debug;
+
+/// Name of the map holding the row's fields inside the script
+const ROW_VAR: &str = "row";
+
+/// Runs a Rhai script on every row.
+///
+/// The script sees the row's fields in the `row` map and changes the row by
+/// assigning to it: `row.status = "active"`. Numbers, booleans, strings and
+/// JSON arrive as their Rhai counterparts, nulls as `()` and every other type
+/// as its text. A key the script adds becomes a new column and a key it
+/// removes is set to null. Fields the script leaves alone keep their value
+/// and type exactly.
+pub struct ScriptTransform {
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptTransform {
+    /// Compile `script`, failing on a syntax error before any row is read
+    pub fn new(script: &RowScript) -> Result<Self, TransformError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(script.max_operations);
+        engine.on_print(|text| debug!(target: "script", "{text}"));
+        engine.on_debug(|text, _, pos| debug!(target: "script", "{pos}: {text}"));
+
+        let ast = engine
+            .compile(&script.code)
+            .map_err(|e| TransformError::Transformation(format!("script does not compile: {e}")))?;
+        Ok(Self { engine, ast })
+    }
+}
+
+impl Transform for ScriptTransform {
+    fn apply(&self, row: &mut Record) -> Result<(), TransformError> {
+        let before: Map = row
+            .fields
+            .iter()
+            .map(|f| {
+                let value = f.value.as_ref().map_or(Dynamic::UNIT, to_dynamic);
+                (f.name.as_str().into(), value)
+            })
+            .collect();
+
+        let mut scope = Scope::new();
+        scope.push(ROW_VAR, before.clone());
+        self.engine
+            .run_ast_with_scope(&mut scope, &self.ast)
+            .map_err(|e| TransformError::Transformation(format!("script failed: {e}")))?;
+
+        let Some(mut after) = scope.get_value::<Map>(ROW_VAR) else {
+            return Err(TransformError::Transformation(format!(
+                "script failed: '{ROW_VAR}' must stay a map"
+            )));
+        };
+
+        for field in row.fields.iter_mut() {
+            match after.remove(field.name.as_str()) {
+                Some(value) if unchanged(&before[field.name.as_str()], &value) => {}
+                Some(value) => {
+                    let value = from_dynamic(value)?;
+                    field.data_type = value.data_type();
+                    field.value = Some(value);
+                }
+                None => field.value = Some(Value::Null),
+            }
+        }
+        for (name, value) in after {
+            let value = from_dynamic(value)?;
+            row.fields.push(FieldValue {
+                name: name.to_string(),
+                data_type: value.data_type(),
+                value: Some(value),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Whether the script left a field as it found it
+fn unchanged(before: &Dynamic, after: &Dynamic) -> bool {
+    before.type_name() == after.type_name() && before.to_string() == after.to_string()
+}
+
+fn to_dynamic(value: &Value) -> Dynamic {
+    match value {
+        Value::Null => Dynamic::UNIT,
+        Value::Boolean(b) => Dynamic::from_bool(*b),
+        Value::Int(n) => Dynamic::from_int(*n),
+        Value::UInt(n) => match i64::try_from(*n) {
+            Ok(n) => Dynamic::from_int(n),
+            Err(_) => Dynamic::from_float(*n as f64),
+        },
+        Value::Float(f) => Dynamic::from_float(*f),
+        Value::String(s) => s.clone().into(),
+        Value::Json(_) | Value::Array(_) => {
+            rhai::serde::to_dynamic(value.to_json()).unwrap_or(Dynamic::UNIT)
+        }
+        other => other.as_string().map_or(Dynamic::UNIT, Into::into),
+    }
+}
+
+fn from_dynamic(value: Dynamic) -> Result<Value, TransformError> {
+    if value.is_unit() {
+        return Ok(Value::Null);
+    }
+    if let Ok(b) = value.as_bool() {
+        return Ok(Value::Boolean(b));
+    }
+    if let Ok(n) = value.as_int() {
+        return Ok(Value::Int(n));
+    }
+    if let Ok(f) = value.as_float() {
+        return Ok(Value::Float(f));
+    }
+    if value.is_map() || value.is_array() {
+        return rhai::serde::from_dynamic::<serde_json::Value>(&value)
+            .map(Value::Json)
+            .map_err(|e| TransformError::Transformation(format!("script failed: {e}")));
+    }
+    Ok(Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{execution::pipeline::DEFAULT_SCRIPT_MAX_OPERATIONS, records::OpType};
+
+    fn script(code: &str) -> ScriptTransform {
+        ScriptTransform::new(&RowScript {
+            code: code.to_string(),
+            max_operations: DEFAULT_SCRIPT_MAX_OPERATIONS,
+        })
+        .unwrap()
+    }
+
+    fn field(name: &str, value: Value) -> FieldValue {
+        FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        }
+    }
+
+    fn record(fields: Vec<FieldValue>) -> Record {
+        Record::new("orders", fields, OpType::Insert)
+    }
+
+    #[test]
+    fn test_script_unpacks_packed_field() {
+        let transform = script(
+            r#"
+            let parts = row.packed.split("|");
+            row.region = parts[0];
+            row.qty = parse_int(parts[1]);
+            row.remove("packed");
+            "#,
+        );
+        let mut row = record(vec![
+            field("id", Value::Decimal("12.50".parse().unwrap())),
+            field("packed", Value::String("EU|42".to_string())),
+        ]);
+        transform.apply(&mut row).unwrap();
+
+        // Untouched fields keep their type, not the text the script saw
+        assert_eq!(
+            row.get_value("id"),
+            Value::Decimal("12.50".parse().unwrap())
+        );
+        assert_eq!(row.get_value("packed"), Value::Null);
+        assert_eq!(row.get_value("region"), Value::String("EU".to_string()));
+        assert_eq!(row.get_value("qty"), Value::Int(42));
+        assert_eq!(row.get("qty").unwrap().data_type, Value::Int(0).data_type());
+    }
+
+    #[test]
+    fn test_script_maps_values() {
+        let transform = script(
+            r#"
+            row.total = row.price * 2.0;
+            row.active = row.flag == ();
+            row.tags.push("new");
+            "#,
+        );
+        let mut row = record(vec![
+            field("price", Value::Float(1.5)),
+            field("flag", Value::Null),
+            field("tags", Value::Json(serde_json::json!(["a"]))),
+        ]);
+        transform.apply(&mut row).unwrap();
+
+        assert_eq!(row.get_value("total"), Value::Float(3.0));
+        assert_eq!(row.get_value("active"), Value::Boolean(true));
+        assert_eq!(
+            row.get_value("tags"),
+            Value::Json(serde_json::json!(["a", "new"]))
+        );
+    }
+
+    #[test]
+    fn test_script_errors_fail_the_row() {
+        assert!(
+            ScriptTransform::new(&RowScript {
+                code: "row.x = ".to_string(),
+                max_operations: DEFAULT_SCRIPT_MAX_OPERATIONS,
+            })
+            .is_err()
+        );
+
+        let mut row = record(vec![field("id", Value::Int(1))]);
+        let err = script(r#"throw "bad packed field""#)
+            .apply(&mut row)
+            .unwrap_err();
+        assert!(err.to_string().contains("bad packed field"));
+
+        let runaway = ScriptTransform::new(&RowScript {
+            code: "loop { row.id += 1; }".to_string(),
+            max_operations: 1_000,
+        })
+        .unwrap();
+        assert!(runaway.apply(&mut row).is_err());
+
+        assert!(script("row = 1;").apply(&mut row).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::transform::{aggregate::compare, error::TransformError};
+use model::{core::value::Value, pagination::cursor::QualCol, records::Record};
+use serde_json::{StreamDeserializer, de::IoRead};
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{BufReader, BufWriter, Seek, SeekFrom, Write},
+    mem,
+};
+
+type RunRows = StreamDeserializer<'static, IoRead<BufReader<File>>, Record>;
+
+/// Sorts rows by the `order_by` columns of a `to` block in bounded memory.
+///
+/// Rows are sorted `buffer_rows` at a time; once a source has more, each
+/// sorted run is spilled as JSON lines to an anonymous temporary file and
+/// the runs are merged as the rows are read back. The sort is stable, so
+/// rows with equal keys keep the order they were read in. Nulls sort last,
+/// and values that cannot be compared, such as a string and a number, are
+/// treated as equal.
+pub struct ExternalSorter {
+    order_by: Vec<QualCol>,
+    buffer_rows: usize,
+    buffer: Vec<Record>,
+    runs: Vec<File>,
+}
+
+impl ExternalSorter {
+    pub fn new(order_by: Vec<QualCol>, buffer_rows: usize) -> Self {
+        Self {
+            order_by,
+            buffer_rows: buffer_rows.max(1),
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Add `row`, spilling the buffered rows once the buffer is full.
+    pub fn push(&mut self, row: Record) -> Result<(), TransformError> {
+        self.buffer.push(row);
+        if self.buffer.len() >= self.buffer_rows {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Number of runs spilled to disk so far.
+    pub fn spilled_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// The rows in order. Without a spilled run they are served from memory.
+    pub fn finish(mut self) -> Result<SortedRows, TransformError> {
+        if self.runs.is_empty() {
+            let rows = sort_rows(&self.order_by, mem::take(&mut self.buffer));
+            return Ok(SortedRows {
+                order_by: self.order_by,
+                memory: rows.into_iter(),
+                runs: Vec::new(),
+            });
+        }
+
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+        let mut runs = Vec::with_capacity(self.runs.len());
+        for mut file in self.runs {
+            file.seek(SeekFrom::Start(0)).map_err(spill_error)?;
+            let mut rows =
+                serde_json::Deserializer::from_reader(BufReader::new(file)).into_iter::<Record>();
+            let head = next_row(&mut rows, &self.order_by)?;
+            runs.push(Run { rows, head });
+        }
+        Ok(SortedRows {
+            order_by: self.order_by,
+            memory: Vec::new().into_iter(),
+            runs,
+        })
+    }
+
+    fn spill(&mut self) -> Result<(), TransformError> {
+        let rows = sort_rows(&self.order_by, mem::take(&mut self.buffer));
+        let mut writer = BufWriter::new(tempfile::tempfile().map_err(spill_error)?);
+        for row in rows {
+            serde_json::to_writer(&mut writer, &row)
+                .map_err(|e| TransformError::Transformation(format!("order_by: {e}")))?;
+            writer.write_all(b"\n").map_err(spill_error)?;
+        }
+        let file = writer
+            .into_inner()
+            .map_err(|e| spill_error(e.into_error()))?;
+        self.runs.push(file);
+        Ok(())
+    }
+}
+
+/// Sorted rows, read from memory or merged from the spilled runs.
+pub struct SortedRows {
+    order_by: Vec<QualCol>,
+    memory: std::vec::IntoIter<Record>,
+    runs: Vec<Run>,
+}
+
+/// A spilled run with its next row and that row's sort key
+struct Run {
+    rows: RunRows,
+    head: Option<(Vec<Value>, Record)>,
+}
+
+impl Iterator for SortedRows {
+    type Item = Result<Record, TransformError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.runs.is_empty() {
+            return self.memory.next().map(Ok);
+        }
+
+        // The first run holding the smallest key, so equal keys keep the
+        // order of the runs they were read into
+        let mut smallest: Option<usize> = None;
+        for (idx, run) in self.runs.iter().enumerate() {
+            let Some((key, _)) = &run.head else {
+                continue;
+            };
+            let less = match smallest.and_then(|s| self.runs[s].head.as_ref()) {
+                Some((current, _)) => compare_keys(key, current) == Ordering::Less,
+                None => true,
+            };
+            if less {
+                smallest = Some(idx);
+            }
+        }
+
+        let run = &mut self.runs[smallest?];
+        let (_, row) = run.head.take()?;
+        match next_row(&mut run.rows, &self.order_by) {
+            Ok(head) => run.head = head,
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(row))
+    }
+}
+
+fn sort_key(order_by: &[QualCol], row: &Record) -> Vec<Value> {
+    order_by.iter().map(|c| row.get_value(&c.column)).collect()
+}
+
+/// `rows` in key order; `sort_by` is stable.
+fn sort_rows(order_by: &[QualCol], rows: Vec<Record>) -> Vec<Record> {
+    let mut keyed: Vec<(Vec<Value>, Record)> = rows
+        .into_iter()
+        .map(|row| (sort_key(order_by, &row), row))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+    keyed.into_iter().map(|(_, row)| row).collect()
+}
+
+fn compare_keys(a: &[Value], b: &[Value]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let ordering = match (x, y) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Null, _) => Ordering::Greater,
+            (_, Value::Null) => Ordering::Less,
+            _ => compare(x, y).unwrap_or(Ordering::Equal),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn next_row(
+    rows: &mut RunRows,
+    order_by: &[QualCol],
+) -> Result<Option<(Vec<Value>, Record)>, TransformError> {
+    match rows.next() {
+        Some(Ok(row)) => Ok(Some((sort_key(order_by, &row), row))),
+        Some(Err(e)) => Err(TransformError::Transformation(format!(
+            "order_by: cannot read spilled rows: {e}"
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn spill_error(e: std::io::Error) -> TransformError {
+    TransformError::Transformation(format!("order_by: cannot spill sorted rows: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{core::value::FieldValue, records::OpType};
+
+    fn col(column: &str) -> QualCol {
+        QualCol {
+            table: "events".to_string(),
+            column: column.to_string(),
+        }
+    }
+
+    fn row(id: i64, day: Value) -> Record {
+        let field = |name: &str, value: Value| FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        };
+        Record::new(
+            "events",
+            vec![field("id", Value::Int(id)), field("day", day)],
+            OpType::Insert,
+        )
+    }
+
+    fn sorted_ids(sorter: ExternalSorter) -> Vec<i64> {
+        sorter
+            .finish()
+            .unwrap()
+            .map(|r| match r.unwrap().get_value("id") {
+                Value::Int(id) => id,
+                other => panic!("unexpected id {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sorts_in_memory() {
+        let mut sorter = ExternalSorter::new(vec![col("day")], 100);
+        for (id, day) in [(1, Value::Int(3)), (2, Value::Null), (3, Value::Int(1))] {
+            sorter.push(row(id, day)).unwrap();
+        }
+        assert_eq!(sorter.spilled_runs(), 0);
+        // Nulls sort last
+        assert_eq!(sorted_ids(sorter), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_merges_spilled_runs_stably() {
+        let mut sorter = ExternalSorter::new(vec![col("day")], 3);
+        let days = [5, 2, 9, 2, 7, 1, 5, 2, 8, 3];
+        for (id, day) in days.iter().enumerate() {
+            sorter.push(row(id as i64, Value::Int(*day))).unwrap();
+        }
+        assert_eq!(sorter.spilled_runs(), 3);
+
+        // Equal days keep the order their rows were read in
+        assert_eq!(sorted_ids(sorter), vec![5, 1, 3, 7, 9, 0, 6, 4, 8, 2]);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::transform::{
+    columns::{self, TableColumns},
+    error::TransformError,
+    pipeline::Transform,
+};
+use model::{
+    core::{encoding::InvalidUtf8, value::Value},
+    records::Record,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Decodes raw bytes bound for destination text columns, such as MySQL TEXT
+/// values or text holding bytes that are not valid UTF-8, with the
+/// pipeline's `invalid_utf8` handling.
+pub struct TextDecoder {
+    mode: InvalidUtf8,
+    /// Text columns
+    columns: TableColumns<()>,
+}
+
+impl TextDecoder {
+    pub fn new(mode: InvalidUtf8, columns: HashMap<String, HashSet<String>>) -> Self {
+        Self {
+            mode,
+            columns: columns.into(),
+        }
+    }
+}
+
+impl Transform for TextDecoder {
+    fn apply(&self, row: &mut Record) -> Result<(), TransformError> {
+        let Some(columns) = self.columns.table(&row.schema) else {
+            return Ok(());
+        };
+
+        for field in &mut row.fields {
+            let Some(Value::Binary(bytes)) = &field.value else {
+                continue;
+            };
+            if !columns::contains(columns, &field.name) {
+                continue;
+            }
+            let text = self.mode.decode(bytes).ok_or_else(|| {
+                TransformError::Transformation(format!(
+                    "{}.{} holds bytes that are not valid UTF-8",
+                    row.schema, field.name
+                ))
+            })?;
+            field.value = Some(Value::String(text));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::{
+        core::{types::Type, value::FieldValue},
+        records::OpType,
+    };
+
+    fn row(value: Value) -> Record {
+        let fields = vec![
+            FieldValue {
+                name: "Title".to_string(),
+                value: Some(value.clone()),
+                data_type: Type::Blob { max_bytes: None },
+            },
+            FieldValue {
+                name: "cover".to_string(),
+                value: Some(value),
+                data_type: Type::Blob { max_bytes: None },
+            },
+        ];
+        Record::new("books", fields, OpType::Insert)
+    }
+
+    fn decoder(mode: InvalidUtf8) -> TextDecoder {
+        let columns = HashSet::from(["title".to_string()]);
+        TextDecoder::new(mode, HashMap::from([("books".to_string(), columns)]))
+    }
+
+    #[test]
+    fn test_text_decoder() {
+        let latin1 = Value::Binary(b"Les Mis\xe9rables".to_vec());
+
+        let mut r = row(latin1.clone());
+        decoder(InvalidUtf8::Latin1).apply(&mut r).unwrap();
+        assert_eq!(
+            r.fields[0].value,
+            Some(Value::String("Les Misérables".to_string()))
+        );
+        // Binary destination columns keep their bytes
+        assert_eq!(r.fields[1].value, Some(latin1.clone()));
+
+        let mut r = row(latin1.clone());
+        decoder(InvalidUtf8::Replace).apply(&mut r).unwrap();
+        assert_eq!(
+            r.fields[0].value,
+            Some(Value::String("Les Mis\u{fffd}rables".to_string()))
+        );
+
+        let mut r = row(latin1);
+        let err = decoder(InvalidUtf8::Error).apply(&mut r).unwrap_err();
+        assert!(err.to_string().contains("books.Title"), "{err}");
+
+        let mut r = row(Value::Binary("Misérables".as_bytes().to_vec()));
+        decoder(InvalidUtf8::Error).apply(&mut r).unwrap();
+        assert_eq!(
+            r.fields[0].value,
+            Some(Value::String("Misérables".to_string()))
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::transform::{error::TransformError, pipeline::Transform};
+use model::{
+    core::{
+        timezone::{SourceTimezones, Tz, parse_zone, to_utc},
+        types::Type,
+        value::Value,
+    },
+    records::Record,
+};
+use std::collections::HashMap;
+
+/// Converts source timestamps that carry no offset, such as MySQL DATETIME
+/// values, from the time zone they were written in to UTC timestamps with
+/// a time zone. Runs on the source row, before columns are renamed.
+pub struct TimezoneNormalizer {
+    default: Option<Tz>,
+    /// source column (lowercased) -> zone
+    columns: HashMap<String, Tz>,
+}
+
+impl TimezoneNormalizer {
+    pub fn new(timezones: &SourceTimezones) -> Self {
+        // Zone names were checked when the plan was built
+        let default = timezones.default.as_deref().and_then(parse_zone);
+        let columns = timezones
+            .columns
+            .iter()
+            .filter_map(|(column, zone)| Some((column.to_ascii_lowercase(), parse_zone(zone)?)))
+            .collect();
+        Self { default, columns }
+    }
+}
+
+impl Transform for TimezoneNormalizer {
+    fn apply(&self, row: &mut Record) -> Result<(), TransformError> {
+        for field in &mut row.fields {
+            let Some(Value::Timestamp {
+                value,
+                offset_secs: None,
+            }) = &field.value
+            else {
+                continue;
+            };
+            let Some(tz) = self
+                .columns
+                .get(&field.name.to_ascii_lowercase())
+                .or(self.default.as_ref())
+            else {
+                continue;
+            };
+
+            field.value = Some(Value::Timestamp {
+                value: to_utc(*value, *tz),
+                offset_secs: Some(0),
+            });
+            if let Type::Timestamp { precision, .. } = field.data_type {
+                field.data_type = Type::Timestamp {
+                    precision,
+                    with_tz: true,
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use model::{core::value::FieldValue, records::OpType};
+    use std::collections::BTreeMap;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn field(name: &str, offset_secs: Option<i32>) -> FieldValue {
+        FieldValue {
+            name: name.to_string(),
+            value: Some(Value::Timestamp {
+                value: at("2024-01-15 10:00:00"),
+                offset_secs,
+            }),
+            data_type: Type::Timestamp {
+                precision: None,
+                with_tz: offset_secs.is_some(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_timezone_normalizer() {
+        let normalizer = TimezoneNormalizer::new(&SourceTimezones {
+            default: Some("Europe/Berlin".to_string()),
+            columns: BTreeMap::from([("shipped_at".to_string(), "Asia/Tokyo".to_string())]),
+        });
+        let mut row = Record::new(
+            "orders",
+            vec![
+                field("created_at", None),
+                field("Shipped_At", None),
+                // Already UTC, e.g. a MySQL TIMESTAMP
+                field("updated_at", Some(0)),
+            ],
+            OpType::Insert,
+        );
+        normalizer.apply(&mut row).unwrap();
+
+        let utc = |s: &str| {
+            Some(Value::Timestamp {
+                value: at(s),
+                offset_secs: Some(0),
+            })
+        };
+        assert_eq!(row.fields[0].value, utc("2024-01-15 09:00:00"));
+        assert_eq!(row.fields[1].value, utc("2024-01-15 01:00:00"));
+        assert_eq!(row.fields[2].value, utc("2024-01-15 10:00:00"));
+        assert!(matches!(
+            row.fields[0].data_type,
+            Type::Timestamp { with_tz: true, .. }
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use model::{
+    core::value::{FieldValue, Value},
+    execution::pipeline::Unnest,
+    records::Record,
+};
+
+/// Splits `row` into one row per element of the unnested column, like SQL
+/// `UNNEST`: every other column, the parent's key among them, is copied into
+/// each row. Text is split on the delimiter, with elements trimmed and empty
+/// ones dropped; arrays and JSON arrays are split into their items. A null
+/// or missing column, or one with no elements, gives no row.
+pub fn unnest(unnest: &Unnest, row: &Record) -> Vec<Record> {
+    let Some(field) = row.get(&unnest.column) else {
+        return Vec::new();
+    };
+    let elements: Vec<Value> = match field.value.as_ref().unwrap_or(&Value::Null) {
+        Value::Null => Vec::new(),
+        Value::String(text) => text
+            .split(unnest.delimiter.as_str())
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .map(|e| Value::String(e.to_string()))
+            .collect(),
+        Value::Array(items) => items.clone(),
+        Value::Json(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                serde_json::Value::String(s) => Value::String(s.clone()),
+                other => Value::Json(other.clone()),
+            })
+            .collect(),
+        other => vec![other.clone()],
+    };
+
+    elements
+        .into_iter()
+        .map(|element| {
+            let fields = row
+                .fields
+                .iter()
+                .map(|f| {
+                    if f.name.eq_ignore_ascii_case(&unnest.column) {
+                        FieldValue {
+                            name: f.name.clone(),
+                            data_type: element.data_type(),
+                            value: Some(element.clone()),
+                        }
+                    } else {
+                        f.clone()
+                    }
+                })
+                .collect();
+            Record::new(&row.schema, fields, row.op_type)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::records::OpType;
+
+    fn post(tags: Value) -> Record {
+        let field = |name: &str, value: Value| FieldValue {
+            name: name.to_string(),
+            data_type: value.data_type(),
+            value: Some(value),
+        };
+        Record::new(
+            "posts",
+            vec![field("id", Value::Int(7)), field("tags", tags)],
+            OpType::Insert,
+        )
+    }
+
+    fn split(tags: Value) -> Vec<(Value, Value)> {
+        let spec = Unnest {
+            column: "tags".to_string(),
+            delimiter: ",".to_string(),
+        };
+        unnest(&spec, &post(tags))
+            .iter()
+            .map(|r| (r.get_value("id"), r.get_value("tags")))
+            .collect()
+    }
+
+    #[test]
+    fn test_unnest_text_and_arrays() {
+        let tag = |s: &str| (Value::Int(7), Value::String(s.to_string()));
+
+        assert_eq!(
+            split(Value::String("rust, sql,,etl ".to_string())),
+            [tag("rust"), tag("sql"), tag("etl")]
+        );
+        assert_eq!(
+            split(Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ])),
+            [tag("a"), tag("b")]
+        );
+        assert_eq!(
+            split(Value::Json(serde_json::json!(["x", "y"]))),
+            [tag("x"), tag("y")]
+        );
+        assert!(split(Value::Null).is_empty());
+        assert!(split(Value::String(" , ".to_string())).is_empty());
+    }
+}
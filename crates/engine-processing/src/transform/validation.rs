@@ -36,6 +36,66 @@ pub enum ValidationResult {
     },
 }
 
+/// Failures of one validation rule, counted over a whole run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTally {
+    pub rule: String,
+    pub action: ValidationAction,
+    pub failures: u64,
+    /// Message of the first failing row
+    pub sample: Option<String>,
+}
+
+/// Per-rule failure counts of a validate-only pipeline. Its rules are
+/// counted over every row instead of acting on the first failure, so the
+/// run reports how much of the source breaks each one. Shared by the
+/// producers of a partitioned load.
+#[derive(Debug)]
+pub struct ValidationTally {
+    rules: Mutex<Vec<RuleTally>>,
+}
+
+impl ValidationTally {
+    pub fn new(rules: &[ValidationRule]) -> Self {
+        let rules = rules
+            .iter()
+            .map(|rule| RuleTally {
+                rule: rule.label.clone(),
+                action: action_for(rule),
+                failures: 0,
+                sample: None,
+            })
+            .collect();
+        Self {
+            rules: Mutex::new(rules),
+        }
+    }
+
+    fn record(&self, index: usize, message: &str) {
+        let mut rules = self.rules.lock().expect("validation tally mutex poisoned");
+        let tally = &mut rules[index];
+        tally.failures += 1;
+        tally.sample.get_or_insert_with(|| message.to_string());
+    }
+
+    /// The counts so far, in rule order.
+    pub fn snapshot(&self) -> Vec<RuleTally> {
+        self.rules
+            .lock()
+            .expect("validation tally mutex poisoned")
+            .clone()
+    }
+}
+
+fn action_for(rule: &ValidationRule) -> ValidationAction {
+    match rule.action {
+        model::execution::pipeline::ValidationAction::Skip => ValidationAction::Skip,
+        model::execution::pipeline::ValidationAction::Fail => ValidationAction::Fail,
+        model::execution::pipeline::ValidationAction::Warn => ValidationAction::Warn,
+        model::execution::pipeline::ValidationAction::Continue => ValidationAction::Warn,
+    }
+}
+
 /// Pre-built per-rule state. Indexed by rule position.
 enum CompiledRule {
     Assert,
@@ -52,6 +112,7 @@ pub struct PipelineValidator {
     compiled: Vec<CompiledRule>,
     metadata: TransformationMetadata,
     env: Arc<EnvContext>,
+    tally: Option<Arc<ValidationTally>>,
 }
 
 impl PipelineValidator {
@@ -84,9 +145,17 @@ impl PipelineValidator {
             compiled,
             metadata,
             env,
+            tally: None,
         })
     }
 
+    /// Counts every failing rule in `tally` and passes the row, instead of
+    /// acting on the first failure.
+    pub fn with_tally(mut self, tally: Arc<ValidationTally>) -> Self {
+        self.tally = Some(tally);
+        self
+    }
+
     fn evaluate_assert(
         &self,
         check: &CompiledExpression,
@@ -125,7 +194,7 @@ impl PipelineValidator {
 
 impl Validator for PipelineValidator {
     fn validate(&self, row: &Record) -> Result<ValidationResult, TransformError> {
-        for (rule, compiled) in self.rules.iter().zip(self.compiled.iter()) {
+        for (index, (rule, compiled)) in self.rules.iter().zip(self.compiled.iter()).enumerate() {
             let (passed, reject_reason) = match (&rule.kind, compiled) {
                 (ValidationKind::Assert { check }, CompiledRule::Assert) => {
                     (self.evaluate_assert(check, &rule.label, row)?, None)
@@ -140,25 +209,122 @@ impl Validator for PipelineValidator {
                 _ => unreachable!("rules and compiled state diverged"),
             };
 
-            if !passed {
-                let action = match rule.action {
-                    model::execution::pipeline::ValidationAction::Skip => ValidationAction::Skip,
-                    model::execution::pipeline::ValidationAction::Fail => ValidationAction::Fail,
-                    model::execution::pipeline::ValidationAction::Warn => ValidationAction::Warn,
-                    model::execution::pipeline::ValidationAction::Continue => {
-                        ValidationAction::Warn
-                    }
-                };
-
-                let message = reject_reason.unwrap_or_else(|| rule.message.clone());
-                return Ok(ValidationResult::Failed {
-                    rule: rule.label.clone(),
-                    message,
-                    action,
-                });
+            if passed {
+                continue;
             }
+
+            let message = reject_reason.unwrap_or_else(|| rule.message.clone());
+            if let Some(tally) = &self.tally {
+                tally.record(index, &message);
+                continue;
+            }
+            return Ok(ValidationResult::Failed {
+                rule: rule.label.clone(),
+                message,
+                action: action_for(rule),
+            });
         }
 
         Ok(ValidationResult::Pass)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_wasm::runtime::engine::WasmEngineConfig;
+    use model::{
+        core::{types::Type, value::FieldValue},
+        execution::{
+            expr::BinaryOp,
+            pipeline::{ValidationAction as RuleAction, ValidationSeverity},
+        },
+        records::OpType,
+    };
+
+    fn rule(label: &str, check: CompiledExpression, action: RuleAction) -> ValidationRule {
+        ValidationRule {
+            label: label.to_string(),
+            severity: ValidationSeverity::Assert,
+            message: format!("{label} failed"),
+            action,
+            kind: ValidationKind::Assert { check },
+        }
+    }
+
+    fn row(email: Option<&str>, total: f64) -> Record {
+        let fields = vec![
+            FieldValue {
+                name: "email".to_string(),
+                value: email.map(|e| Value::String(e.to_string())),
+                data_type: Type::Text { charset: None },
+            },
+            FieldValue {
+                name: "total".to_string(),
+                value: Some(Value::Float(total)),
+                data_type: Type::Decimal {
+                    precision: None,
+                    scale: None,
+                },
+            },
+        ];
+        Record::new("orders", fields, OpType::Insert)
+    }
+
+    #[tokio::test]
+    async fn test_validation_tally() {
+        let rules = vec![
+            rule(
+                "has_email",
+                CompiledExpression::IsNotNull(Box::new(CompiledExpression::Identifier(
+                    "email".to_string(),
+                ))),
+                RuleAction::Fail,
+            ),
+            rule(
+                "positive_total",
+                CompiledExpression::Binary {
+                    left: Box::new(CompiledExpression::Identifier("total".to_string())),
+                    op: BinaryOp::GreaterOrEqual,
+                    right: Box::new(CompiledExpression::Literal(Value::Float(0.0))),
+                },
+                RuleAction::Warn,
+            ),
+        ];
+        let registry = PluginRegistry::new(&WasmEngineConfig::default()).unwrap();
+        let tally = Arc::new(ValidationTally::new(&rules));
+        let validator = PipelineValidator::new(
+            rules,
+            TransformationMetadata {
+                entities: Default::default(),
+                field_mappings: Default::default(),
+                foreign_fields: HashMap::new(),
+                plugin_columns: Vec::new(),
+            },
+            Arc::new(EnvContext::empty()),
+            &registry,
+        )
+        .unwrap()
+        .with_tally(tally.clone());
+
+        for row in [
+            row(Some("a@example.com"), 10.0),
+            row(None, -1.0),
+            row(None, 5.0),
+        ] {
+            // Failing rows are counted, not acted on
+            assert!(matches!(
+                validator.validate(&row).unwrap(),
+                ValidationResult::Pass
+            ));
+        }
+
+        let counts = tally.snapshot();
+        assert_eq!(counts[0].rule, "has_email");
+        assert_eq!(counts[0].action, ValidationAction::Fail);
+        assert_eq!(counts[0].failures, 2);
+        assert_eq!(counts[0].sample.as_deref(), Some("has_email failed"));
+        assert_eq!(counts[1].action, ValidationAction::Warn);
+        assert_eq!(counts[1].failures, 1);
+    }
+}
@@ -7,7 +7,7 @@ use engine_processing::{
     consumer::{Consumer, ConsumerStatus},
     error::ConsumerError,
 };
-use model::events::migration::MigrationEvent;
+use model::events::migration::{CircuitState, MigrationEvent};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
@@ -21,6 +21,7 @@ struct ConsumerTask {
     event_bus: EventBus,
     run_id: String,
     item_id: String,
+    cancel_token: CancellationToken,
 
     // Progress tracking
     start_time: Instant,
@@ -38,6 +39,7 @@ impl ConsumerTask {
         event_bus: EventBus,
         run_id: String,
         item_id: String,
+        cancel_token: CancellationToken,
     ) -> Self {
         let now = Instant::now();
         Self {
@@ -47,6 +49,7 @@ impl ConsumerTask {
             event_bus,
             run_id,
             item_id,
+            cancel_token,
             start_time: now,
             last_progress_report: now,
             progress_interval: Duration::from_millis(500),
@@ -70,7 +73,11 @@ impl ConsumerTask {
                 "circuit breaker recovered"
             );
         }
+        let was_tripped = self.breaker.state() != CircuitState::Closed;
         self.breaker.record_success();
+        if was_tripped {
+            self.publish_circuit_state(None).await;
+        }
         self.report_progress().await;
 
         match status {
@@ -93,6 +100,14 @@ impl ConsumerTask {
         self.metrics.increment_failures(1);
         error!(run_id = %self.run_id, item_id = %self.item_id, error = %e, "consumer tick failed");
 
+        if e.is_fatal() {
+            let _ = self.consumer.stop().await;
+            if let ConsumerError::SchemaDrift { table, changes, .. } = e {
+                return TickAction::Failed(ActorError::SchemaDrift { table, changes });
+            }
+            return TickAction::Failed(ActorError::Internal(e.to_string()));
+        }
+
         match self.breaker.record_failure() {
             CircuitBreakerState::RetryAfter(delay) => {
                 warn!(
@@ -106,18 +121,92 @@ impl ConsumerTask {
                 TickAction::Continue
             }
             CircuitBreakerState::Open => {
-                error!(
+                warn!(
                     run_id = %self.run_id,
                     item_id = %self.item_id,
                     failures = self.breaker.consecutive_failures(),
-                    "circuit breaker open, stopping consumer"
+                    "circuit breaker open, pausing writes until the destination is healthy"
+                );
+                self.publish_circuit_state(Some(e.to_string())).await;
+                self.wait_until_healthy(e).await
+            }
+        }
+    }
+
+    /// Probe the destination with backoff while the breaker is open. Moves
+    /// the breaker to half-open once a probe succeeds, so the next tick
+    /// retries the held batch; gives up after the breaker's `max_open`.
+    async fn wait_until_healthy(&mut self, last_error: ConsumerError) -> TickAction {
+        let opened_at = Instant::now();
+        let mut probe = 0;
+
+        loop {
+            if opened_at.elapsed() >= self.breaker.max_open() {
+                let failures = self.breaker.consecutive_failures();
+                let err = ConsumerError::CircuitBreakerOpen {
+                    stage: "write".to_string(),
+                    last_error: last_error.to_string(),
+                };
+                error!(
+                    run_id = %self.run_id,
+                    item_id = %self.item_id,
+                    failures,
+                    open_secs = opened_at.elapsed().as_secs(),
+                    "destination still unhealthy, stopping consumer"
                 );
+                self.consumer
+                    .record_circuit_open(failures, &last_error.to_string())
+                    .await;
                 let _ = self.consumer.stop().await;
-                TickAction::Done
+                return TickAction::Failed(ActorError::Internal(err.to_string()));
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.breaker.probe_delay(probe)) => {}
+                _ = self.cancel_token.cancelled() => return TickAction::Continue,
+            }
+            probe += 1;
+
+            match self.consumer.ping_destination().await {
+                Ok(()) => {
+                    info!(
+                        run_id = %self.run_id,
+                        item_id = %self.item_id,
+                        probes = probe,
+                        "destination reachable, circuit breaker half-open"
+                    );
+                    self.breaker.half_open();
+                    self.publish_circuit_state(None).await;
+                    return TickAction::Continue;
+                }
+                Err(e) => {
+                    warn!(
+                        run_id = %self.run_id,
+                        item_id = %self.item_id,
+                        probes = probe,
+                        error = %e,
+                        "destination probe failed"
+                    );
+                }
             }
         }
     }
 
+    async fn publish_circuit_state(&self, error: Option<String>) {
+        let state = self.breaker.state();
+        self.metrics.set_circuit_state(state);
+        self.event_bus
+            .publish(MigrationEvent::CircuitBreakerChanged {
+                run_id: self.run_id.clone(),
+                item_id: self.item_id.clone(),
+                state,
+                consecutive_failures: self.breaker.consecutive_failures(),
+                error,
+                timestamp: chrono::Utc::now(),
+            })
+            .await;
+    }
+
     async fn handle_stop(
         &mut self,
         run_id: String,
@@ -204,7 +293,14 @@ pub async fn run_consumer(
             None => return Ok(()), // Cancelled or Stopped before starting
         };
 
-    let mut task = ConsumerTask::new(consumer, metrics, event_bus, run_id, item_id);
+    let mut task = ConsumerTask::new(
+        consumer,
+        metrics,
+        event_bus,
+        run_id,
+        item_id,
+        cancel_token.clone(),
+    );
     let idle_delay = Duration::from_millis(100);
     let mut tick_interval = tokio::time::interval(Duration::from_millis(1));
     tick_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
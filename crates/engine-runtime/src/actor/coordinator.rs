@@ -7,7 +7,7 @@ use engine_core::{event_bus::bus::EventBus, metrics::Metrics};
 use engine_processing::{consumer::Consumer, producer::Producer};
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{Instrument, error, info};
 
 /// Coordinates the Producer and Consumer tasks in a data pipeline.
 pub struct PipelineCoordinator {
@@ -29,21 +29,28 @@ impl PipelineCoordinator {
         let (producer_tx, producer_rx) = mpsc::channel::<ProducerMsg>(100);
         let (consumer_tx, consumer_rx) = mpsc::channel::<ConsumerMsg>(100);
 
-        let producer_handle = tokio::spawn(run_producer(
-            producer,
-            producer_rx,
-            cancel_token.clone(),
-            event_bus.clone(),
-            metrics.clone(),
-        ));
-
-        let consumer_handle = tokio::spawn(run_consumer(
-            consumer,
-            consumer_rx,
-            cancel_token.clone(),
-            event_bus,
-            metrics,
-        ));
+        // Spawned in the pipeline's span, which routes their logs
+        let producer_handle = tokio::spawn(
+            run_producer(
+                producer,
+                producer_rx,
+                cancel_token.clone(),
+                event_bus.clone(),
+                metrics.clone(),
+            )
+            .in_current_span(),
+        );
+
+        let consumer_handle = tokio::spawn(
+            run_consumer(
+                consumer,
+                consumer_rx,
+                cancel_token.clone(),
+                event_bus,
+                metrics,
+            )
+            .in_current_span(),
+        );
 
         Self {
             producer_tx,
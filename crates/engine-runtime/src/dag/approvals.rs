@@ -0,0 +1,62 @@
+//! Approval gates: pipelines that wait for an operator before they start.
+//!
+//! A level holding a pipeline whose gate is not approved pauses the run. The
+//! operator approves it with `stratum approve <run> <gate>`, which logs the
+//! approval to the WAL, and resumes the run with the same config.
+
+use crate::{dag::executor::DagExecutor, error::MigrationError};
+use engine_core::{state::models::WalEntry, utils::make_item_id};
+use model::events::migration::MigrationEvent;
+use tracing::info;
+
+impl DagExecutor {
+    /// First gate among `pipelines` that has not been approved, if any.
+    /// Webhooks are told about every unapproved gate, so that all of a
+    /// level's gates can be approved before the run is resumed.
+    pub(super) async fn pending_approval(
+        &self,
+        pipelines: &[String],
+    ) -> Result<Option<String>, MigrationError> {
+        let gated: Vec<_> = self
+            .plan
+            .pipelines
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| pipelines.contains(&p.name))
+            .filter_map(|(idx, p)| p.approval_gate.as_ref().map(|gate| (idx, p, gate)))
+            .collect();
+        if gated.is_empty() {
+            return Ok(None);
+        }
+
+        let run_id = self.exec_ctx.run_id();
+        let wal = self.exec_ctx.state.iter_wal(&run_id).await?;
+        let approved = WalEntry::approved_gates(&wal);
+
+        let mut pending = None;
+        for (idx, pipeline, gate) in gated {
+            if approved.contains(&gate.name) {
+                info!(gate = %gate.name, "approval gate already approved");
+                continue;
+            }
+
+            info!(
+                gate = %gate.name,
+                message = gate.message.as_deref().unwrap_or(""),
+                "waiting for approval: run `stratum approve {run_id} {}`, then resume",
+                gate.name
+            );
+            self.event_bus
+                .publish(MigrationEvent::ApprovalRequired {
+                    run_id: run_id.clone(),
+                    item_id: make_item_id(self.plan.hash(), &pipeline.destination.table, idx),
+                    gate: gate.name.clone(),
+                    message: gate.message.clone(),
+                    timestamp: chrono::Utc::now(),
+                })
+                .await;
+            pending.get_or_insert_with(|| gate.name.clone());
+        }
+        Ok(pending)
+    }
+}
@@ -0,0 +1,58 @@
+//! Pins the sources of each barrier's pipelines to one point in time before
+//! the first level runs.
+//!
+//! MySQL sources start a consistent snapshot while their servers are briefly
+//! read-locked, and the barrier's one PostgreSQL source, if any, imports a
+//! snapshot exported during the lock, so every pipeline of a barrier reads
+//! the data as it was at the same moment. The pinned drivers are pooled, so
+//! the pin holds for the rest of the run.
+
+use crate::{dag::executor::DagExecutor, error::MigrationError};
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+impl DagExecutor {
+    /// Pins the sources of every barrier with a pipeline still to run.
+    pub(super) async fn pin_barriers(
+        &self,
+        completed_pipelines: &HashSet<String>,
+    ) -> Result<(), MigrationError> {
+        for barrier in &self.plan.barriers {
+            let done = barrier
+                .pipelines
+                .iter()
+                .filter(|p| completed_pipelines.contains(*p))
+                .count();
+            if done == barrier.pipelines.len() {
+                continue;
+            }
+            if done > 0 {
+                warn!(
+                    barrier = %barrier.name,
+                    completed = done,
+                    "resuming barrier: remaining pipelines are pinned to a new point in time"
+                );
+            }
+
+            let sources = barrier.sources(&self.plan.pipelines);
+            let positions = self.exec_ctx.pin_sources(&sources).await?;
+            let connections: Vec<&str> = sources.iter().map(|c| c.name.as_str()).collect();
+            info!(
+                barrier = %barrier.name,
+                connections = ?connections,
+                "pinned barrier sources"
+            );
+            for (connection, position) in positions {
+                match position {
+                    Some(position) => {
+                        info!(barrier = %barrier.name, %connection, %position, "binlog position")
+                    }
+                    None => {
+                        info!(barrier = %barrier.name, %connection, "binary log disabled")
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,619 @@
+//! Coordinator/worker execution over a shared state store.
+//!
+//! The coordinator publishes one work item per pipeline to the run's work
+//! queue and tracks them; workers, each running the same config, claim items
+//! whose dependencies are done and run them with the usual checkpointing.
+//! A pipeline read in key ranges (`parallel_partitions`) is split across
+//! workers: the worker claiming it runs its schema changes, seeds and before
+//! hooks, then publishes one item per key range and a closing item for the
+//! large values, post-load schema changes, sequence sync and after hooks,
+//! which becomes claimable once every range is read. Pipelines that depend
+//! on it wait for all of its items.
+//! A worker that dies stops renewing its lease, and once the lease expires
+//! another worker picks the item up and resumes it from its checkpoint. A
+//! worker that cannot renew its lease stops the pipeline at the next batch
+//! boundary while the lease still runs, so two workers never write the same
+//! rows.
+
+use crate::{
+    dag::executor::DagExecutor, error::MigrationError, execution::orchestrator::Execution,
+};
+use engine_core::utils::make_item_id;
+use engine_infra::shutdown::ShutdownSignal;
+use engine_state::{
+    SharedStateStore,
+    models::{Checkpoint, PauseReason, PipelineStatus, RunState, WorkItem, WorkKind, WorkStatus},
+};
+use model::execution::execution_config::FailureStrategy;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// How often the coordinator and idle workers poll the work queue.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a claim stays valid without being renewed.
+pub const LEASE: Duration = Duration::from_secs(60);
+
+/// Workers renew their lease this many times per lease period. A renewal
+/// that fails stops the item, leaving two thirds of the lease to drain its
+/// last batch, more than the orchestrator's shutdown timeout.
+const RENEWALS_PER_LEASE: u32 = 3;
+
+impl DagExecutor {
+    /// Runs as the coordinator of a distributed run: publishes the plan's
+    /// pipelines as work items and waits for workers to finish them. Does not
+    /// run any pipeline itself.
+    pub async fn coordinate(self) -> Result<(), MigrationError> {
+        let mut failed_pipelines = HashSet::new();
+        let (mut run_state, mut completed_pipelines, record) = self.init_or_resume_run().await?;
+
+        let run_id = self.exec_ctx.run_id();
+        let items: Vec<WorkItem> = self
+            .plan
+            .pipelines
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| {
+                let item_id = make_item_id(self.plan.hash(), &p.destination.table, idx);
+                let mut item = WorkItem::new(&p.name, &item_id, p.dependencies.clone());
+                if completed_pipelines.contains(&p.name) {
+                    item.status = WorkStatus::Done { rows: 0 };
+                }
+                item
+            })
+            .collect();
+
+        self.exec_ctx.state.publish_work(&run_id, items).await?;
+        info!(
+            run_id = %run_id,
+            pipelines = self.plan.pipelines.len(),
+            "work published, waiting for workers"
+        );
+
+        let run_result = self
+            .track_work(
+                &mut run_state,
+                &mut completed_pipelines,
+                &mut failed_pipelines,
+            )
+            .await;
+
+        let result = self
+            .finalize_run(run_result, run_state, failed_pipelines)
+            .await;
+
+        let record = self.finish_run_record(record, &result).await;
+        self.report_run(&record).await;
+        result
+    }
+
+    /// Follows the work queue until every item is done or failed, mirroring
+    /// worker outcomes into the run state.
+    async fn track_work(
+        &self,
+        run_state: &mut RunState,
+        completed_pipelines: &mut HashSet<String>,
+        failed_pipelines: &mut HashSet<String>,
+    ) -> Result<(), MigrationError> {
+        let run_id = self.exec_ctx.run_id();
+        let mut views = self.load_view_refreshes().await?;
+
+        loop {
+            if self.shutdown.cancel.is_cancelled() {
+                warn!("shutdown requested, withdrawing unclaimed work");
+                self.withdraw_pending(&run_id, "run stopped").await?;
+                return Err(MigrationError::ShutdownRequested);
+            }
+
+            if self.shutdown.pause.is_cancelled() {
+                info!("pause requested, withdrawing unclaimed work");
+                self.withdraw_pending(&run_id, "run paused").await?;
+                self.save_paused_state(
+                    run_state,
+                    failed_pipelines,
+                    completed_pipelines,
+                    PauseReason::Manual,
+                )
+                .await?;
+                return Err(MigrationError::Paused);
+            }
+
+            let work = self.exec_ctx.state.list_work(&run_id).await?;
+            let mut progressed = false;
+            let checkpoints = if work
+                .iter()
+                .any(|i| matches!(i.status, WorkStatus::Claimed { .. }))
+            {
+                self.exec_ctx.state.list_checkpoints(&run_id).await?
+            } else {
+                Vec::new()
+            };
+
+            for (pipeline, items) in by_pipeline(&work) {
+                let failed = items.iter().find_map(|i| match &i.status {
+                    WorkStatus::Failed { error } => Some((i, error)),
+                    _ => None,
+                });
+                if let Some((item, error)) = failed {
+                    if failed_pipelines.insert(pipeline.to_string()) {
+                        error!(
+                            pipeline,
+                            item = %item.key(),
+                            worker = item.worker_id.as_deref().unwrap_or("-"),
+                            error = %error,
+                            "pipeline failed"
+                        );
+
+                        if matches!(self.exec_config.on_failure, FailureStrategy::FailFast) {
+                            self.withdraw_pending(&run_id, "run failed").await?;
+                            return Err(MigrationError::PipelinesFailed(vec![
+                                pipeline.to_string(),
+                            ]));
+                        }
+                    }
+                    continue;
+                }
+
+                let rows: Option<u64> = items
+                    .iter()
+                    .map(|i| match i.status {
+                        WorkStatus::Done { rows } => Some(rows),
+                        _ => None,
+                    })
+                    .sum();
+                match rows {
+                    Some(rows) if !completed_pipelines.contains(pipeline) => {
+                        info!(
+                            pipeline,
+                            workers = %workers(&items),
+                            rows,
+                            "pipeline completed by workers"
+                        );
+                        self.mark_pipeline_completed(
+                            pipeline,
+                            rows,
+                            run_state,
+                            completed_pipelines,
+                        )
+                        .await?;
+                    }
+                    None if items
+                        .iter()
+                        .any(|i| matches!(i.status, WorkStatus::Claimed { .. })) =>
+                    {
+                        progressed |= record_progress(items[0], &checkpoints, run_state);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Pending items behind a failed pipeline, or of one, would never
+            // become claimable
+            for item in work.iter().filter(|i| i.status == WorkStatus::Pending) {
+                let reason = if item.depends_on.iter().any(|d| failed_pipelines.contains(d)) {
+                    "dependency failed"
+                } else if failed_pipelines.contains(&item.pipeline) {
+                    "pipeline failed"
+                } else {
+                    continue;
+                };
+                warn!(pipeline = %item.pipeline, item = %item.key(), reason, "skipping work item");
+                self.exec_ctx
+                    .state
+                    .skip_work(&run_id, &item.key(), reason)
+                    .await?;
+            }
+
+            if progressed {
+                self.exec_ctx.state.save_run_state(run_state).await?;
+            }
+
+            if let Err(e) = self
+                .refresh_ready_views(completed_pipelines, failed_pipelines, &mut views)
+                .await
+            {
+                self.withdraw_pending(&run_id, "run failed").await?;
+                return Err(e);
+            }
+
+            if work.iter().all(|i| i.status.is_terminal()) {
+                return self.apply_post_load_ops(failed_pipelines).await;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = self.shutdown.cancel.cancelled() => {}
+                _ = self.shutdown.pause.cancelled() => {}
+            }
+        }
+    }
+
+    /// Fails every unclaimed item so workers stop picking up new work.
+    /// Items already claimed run to their next batch boundary as usual.
+    async fn withdraw_pending(&self, run_id: &str, reason: &str) -> Result<(), MigrationError> {
+        let work = self.exec_ctx.state.list_work(run_id).await?;
+        for item in work.iter().filter(|i| i.status == WorkStatus::Pending) {
+            self.exec_ctx
+                .state
+                .skip_work(run_id, &item.key(), reason)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Runs as a worker of a distributed run: claims work items published by
+    /// the coordinator of the same config and runs them one at a time until
+    /// none are left.
+    pub async fn work(mut self, worker_id: &str) -> Result<(), MigrationError> {
+        let result = self.claim_and_run(worker_id).await;
+        self.flush_notifications().await;
+        result
+    }
+
+    async fn claim_and_run(&self, worker_id: &str) -> Result<(), MigrationError> {
+        let run_id = self.exec_ctx.run_id();
+        info!(run_id = %run_id, worker = worker_id, "worker started");
+
+        let mut waiting_logged = false;
+        loop {
+            if self.shutdown.cancel.is_cancelled() {
+                return Err(MigrationError::ShutdownRequested);
+            }
+            if self.shutdown.pause.is_cancelled() {
+                return Err(MigrationError::Paused);
+            }
+
+            let lease = chrono::Duration::from_std(LEASE).unwrap_or_default();
+            match self
+                .exec_ctx
+                .state
+                .claim_work(&run_id, worker_id, lease)
+                .await?
+            {
+                Some(item) => {
+                    waiting_logged = false;
+                    self.run_work_item(&run_id, worker_id, item).await?;
+                    continue;
+                }
+                None => {
+                    let work = self.exec_ctx.state.list_work(&run_id).await?;
+                    if !work.is_empty() && work.iter().all(|i| i.status.is_terminal()) {
+                        info!(worker = worker_id, "no work left");
+                        return Ok(());
+                    }
+                    if !waiting_logged {
+                        if work.is_empty() {
+                            info!(run_id = %run_id, "waiting for the coordinator to publish work");
+                        } else {
+                            info!("waiting for claimable work");
+                        }
+                        waiting_logged = true;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = self.shutdown.cancel.cancelled() => {}
+                _ = self.shutdown.pause.cancelled() => {}
+            }
+        }
+    }
+
+    /// Runs one claimed item, renewing the lease in the background, and
+    /// reports the outcome to the queue.
+    async fn run_work_item(
+        &self,
+        run_id: &str,
+        worker_id: &str,
+        item: WorkItem,
+    ) -> Result<(), MigrationError> {
+        let key = item.key();
+        info!(pipeline = %item.pipeline, item = %key, worker = worker_id, "claimed work item");
+
+        if self.current_run.get().is_none() {
+            // run.* references resolve to the coordinator's open run record
+            let run = self
+                .exec_ctx
+                .state
+                .list_run_records()
+                .await?
+                .into_iter()
+                .rev()
+                .find(|r| r.run_id == run_id && r.ended_at.is_none())
+                .map(|r| (r.id, r.started_at))
+                .unwrap_or_else(|| (run_id.to_string(), chrono::Utc::now()));
+            let _ = self.current_run.set(run);
+        }
+
+        // The pipeline stops on the run's shutdown or when the lease is lost
+        let stop = self.shutdown.cancel.child_token();
+        let heartbeat = spawn_heartbeat(
+            self.exec_ctx.state.clone(),
+            run_id,
+            &key,
+            worker_id,
+            LEASE,
+            stop.clone(),
+        );
+        let shutdown = ShutdownSignal {
+            pause: self.shutdown.pause.clone(),
+            cancel: stop,
+        };
+
+        let result = self.execute_work_item(&item, shutdown).await;
+        heartbeat.abort();
+
+        if matches!(heartbeat.await, Ok(true)) && !self.shutdown.cancel.is_cancelled() {
+            // Hand the item back if it is still ours; whoever claims it next
+            // resumes it from its checkpoint
+            self.exec_ctx
+                .state
+                .release_work(run_id, &key, worker_id, WorkStatus::Pending)
+                .await?;
+            warn!(
+                pipeline = %item.pipeline,
+                item = %key,
+                "stopped work item after its lease could not be renewed"
+            );
+            return Ok(());
+        }
+
+        let held = match result {
+            Ok(Execution::Loaded(rows)) => {
+                self.exec_ctx
+                    .state
+                    .release_work(run_id, &key, worker_id, WorkStatus::Done { rows })
+                    .await?
+            }
+            Ok(Execution::Split(ranges)) => {
+                let parts = ranges
+                    .into_iter()
+                    .map(|(id, start, end)| item.part(WorkKind::Range { id, start, end }))
+                    .chain(std::iter::once(item.part(WorkKind::Finish)))
+                    .collect();
+                self.exec_ctx
+                    .state
+                    .split_work(run_id, &key, worker_id, parts)
+                    .await?
+            }
+            Err(e @ (MigrationError::Paused | MigrationError::ShutdownRequested)) => {
+                // Hand the item back; its checkpoint lets the next claim resume it
+                self.exec_ctx
+                    .state
+                    .release_work(run_id, &key, worker_id, WorkStatus::Pending)
+                    .await?;
+                return Err(e);
+            }
+            Err(e) => {
+                error!(pipeline = %item.pipeline, item = %key, error = %e, "work item failed");
+                let failed = WorkStatus::Failed {
+                    error: e.to_string(),
+                };
+                self.exec_ctx
+                    .state
+                    .release_work(run_id, &key, worker_id, failed)
+                    .await?
+            }
+        };
+
+        if !held {
+            warn!(
+                pipeline = %item.pipeline,
+                item = %key,
+                "work item was reclaimed by another worker after its lease expired"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The run's items grouped by pipeline.
+fn by_pipeline(work: &[WorkItem]) -> BTreeMap<&str, Vec<&WorkItem>> {
+    let mut pipelines: BTreeMap<&str, Vec<&WorkItem>> = BTreeMap::new();
+    for item in work {
+        pipelines.entry(&item.pipeline).or_default().push(item);
+    }
+    pipelines
+}
+
+/// The workers that ran `items`, for logging.
+fn workers(items: &[&WorkItem]) -> String {
+    let mut workers: Vec<&str> = items
+        .iter()
+        .filter_map(|i| i.worker_id.as_deref())
+        .collect();
+    workers.sort();
+    workers.dedup();
+    workers.join(",")
+}
+
+/// Renews the lease on a claimed item until aborted. When a renewal fails,
+/// or finds the item reclaimed after the lease expired, cancels `stop` and
+/// returns true: the worker can no longer be sure it holds the item, so it
+/// stops writing while the lease it last renewed still has most of its time
+/// left.
+fn spawn_heartbeat(
+    state: SharedStateStore,
+    run_id: &str,
+    key: &str,
+    worker_id: &str,
+    lease: Duration,
+    stop: CancellationToken,
+) -> JoinHandle<bool> {
+    let (run_id, key, worker_id) = (run_id.to_string(), key.to_string(), worker_id.to_string());
+    let every = lease / RENEWALS_PER_LEASE;
+    let lease = chrono::Duration::from_std(lease).unwrap_or_default();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let renewal = state.renew_lease(&run_id, &key, &worker_id, lease);
+            match tokio::time::timeout(every, renewal).await {
+                Ok(Ok(true)) => continue,
+                Ok(Ok(false)) => warn!(item = %key, "lost claim on work item"),
+                Ok(Err(e)) => warn!(item = %key, error = %e, "failed to renew lease"),
+                Err(_) => warn!(item = %key, "lease renewal timed out"),
+            }
+            stop.cancel();
+            return true;
+        }
+    })
+}
+
+/// Copies a running item's checkpointed row count, summed over its
+/// partitions, into the run state, so `stratum status` shows progress.
+/// Returns true if anything changed.
+fn record_progress(item: &WorkItem, checkpoints: &[Checkpoint], run_state: &mut RunState) -> bool {
+    let Some(ps) = run_state
+        .pipelines
+        .iter_mut()
+        .find(|p| p.name == item.pipeline)
+    else {
+        return false;
+    };
+
+    let mut parts = checkpoints
+        .iter()
+        .filter(|cp| cp.item_id == item.item_id)
+        .peekable();
+    let rows_done = match parts.peek() {
+        Some(_) => parts.map(|cp| cp.rows_done).sum(),
+        None => ps.rows_done,
+    };
+
+    let changed = ps.status != PipelineStatus::Running || ps.rows_done != rows_done;
+    ps.status = PipelineStatus::Running;
+    ps.rows_done = rows_done;
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_state::{
+        models::{CheckpointStage, PipelineRunState, RunStatus},
+        sled_store::SledStateStore,
+    };
+    use model::pagination::cursor::Cursor;
+    use std::sync::Arc;
+
+    fn checkpoint(item_id: &str, part_id: &str, rows_done: u64) -> Checkpoint {
+        Checkpoint {
+            run_id: "run-1".to_string(),
+            item_id: item_id.to_string(),
+            part_id: part_id.to_string(),
+            stage: CheckpointStage::Committed,
+            src_offset: Cursor::None,
+            pending_offset: None,
+            batch_id: "b1".to_string(),
+            rows_done,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_progress_sums_every_partition() {
+        let mut run_state = RunState {
+            run_id: "run-1".to_string(),
+            config_path: "migration.smql".to_string(),
+            config_hash: "hash".to_string(),
+            fingerprint: Default::default(),
+            status: RunStatus::Running,
+            started_at: chrono::Utc::now(),
+            total_pipelines: 1,
+            pipelines: vec![PipelineRunState {
+                name: "orders".to_string(),
+                item_id: "item-orders".to_string(),
+                status: PipelineStatus::Pending,
+                rows_done: 0,
+                total_rows: None,
+            }],
+        };
+        let item = WorkItem::new("orders", "item-orders", Vec::new());
+        let checkpoints = vec![
+            checkpoint("item-orders", "part-0", 100),
+            checkpoint("item-orders", "part-1", 40),
+            checkpoint("item-customers", "part-0", 7),
+            checkpoint("item-orders", "part-4", 2),
+        ];
+
+        assert!(record_progress(&item, &checkpoints, &mut run_state));
+        assert_eq!(run_state.pipelines[0].status, PipelineStatus::Running);
+        assert_eq!(run_state.pipelines[0].rows_done, 142);
+        assert!(!record_progress(&item, &checkpoints, &mut run_state));
+
+        // No checkpoint yet keeps the count already reported
+        assert!(!record_progress(&item, &[], &mut run_state));
+        assert_eq!(run_state.pipelines[0].rows_done, 142);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_stops_item_when_lease_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let state: SharedStateStore = Arc::new(SledStateStore::open(dir.path()).unwrap());
+        let lease = Duration::from_millis(300);
+        let chrono_lease = chrono::Duration::from_std(lease).unwrap();
+        state
+            .publish_work(
+                "run-1",
+                vec![WorkItem::new("orders", "item-orders", Vec::new())],
+            )
+            .await
+            .unwrap();
+        state
+            .claim_work("run-1", "w1", chrono_lease)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let stop = CancellationToken::new();
+        let heartbeat =
+            spawn_heartbeat(state.clone(), "run-1", "orders", "w1", lease, stop.clone());
+
+        // Renewals keep the item from other workers
+        tokio::time::sleep(lease * 2).await;
+        assert!(!stop.is_cancelled());
+        assert!(
+            state
+                .claim_work("run-1", "w2", chrono_lease)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // w1 stalls past its lease and w2 takes the item over
+        state
+            .release_work(
+                "run-1",
+                "orders",
+                "w1",
+                WorkStatus::Claimed {
+                    lease_until: chrono::Utc::now() - chrono::Duration::seconds(1),
+                },
+            )
+            .await
+            .unwrap();
+        let reclaimed = state
+            .claim_work("run-1", "w2", chrono_lease)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reclaimed.worker_id.as_deref(), Some("w2"));
+
+        let lost = tokio::time::timeout(lease, heartbeat)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(lost);
+        assert!(stop.is_cancelled());
+    }
+}
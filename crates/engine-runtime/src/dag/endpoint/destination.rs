@@ -1,8 +1,11 @@
 use super::{DestinationEndpoint, HookPhase, SourceEndpoint};
 use crate::error::MigrationError;
 use async_trait::async_trait;
-use connectors::sql::metadata::{column::ColumnMetadata, table::TableMetadata};
-use engine_config::settings::{self, ValidatedSettings};
+use connectors::{
+    sql::metadata::{column::ColumnMetadata, table::TableMetadata},
+    traits::{ddl::DdlWriter, writer::DataWriter},
+};
+use engine_config::settings::{self, Settings, ValidatedSettings};
 use engine_core::{
     dispatch_driver,
     drivers::DriverRef,
@@ -14,19 +17,23 @@ use engine_core::{
 use engine_processing::{
     context::PipelineContext,
     hooks::executor::HookExecutor,
-    io::destination::{Destination, IntoDestination},
+    io::{
+        destination::{Destination, IntoDestination},
+        sink::csv::CsvFileConfig,
+    },
 };
 use engine_wasm::{
     registry::PluginRegistry, runtime::instance::PluginInstance, schema::PluginField,
 };
 use model::execution::{
     flags::IntegrityMode,
-    pipeline::{LifecycleHooks, Pipeline},
+    pipeline::{LifecycleHooks, Pipeline, SeedTable},
 };
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
 };
+use tracing::info;
 
 pub struct DbDestinationEndpoint(pub DriverRef);
 
@@ -133,6 +140,21 @@ impl DestinationEndpoint for DbDestinationEndpoint {
         Ok(())
     }
 
+    async fn seed(&self, seed: &SeedTable) -> Result<u64, MigrationError> {
+        let meta =
+            self.0.table_metadata(&seed.table).await.map_err(|e| {
+                MigrationError::PipelineFailed(format!("seed '{}': {e}", seed.table))
+            })?;
+        let meta = seed_metadata(meta, seed)?;
+        let rows = seed.records();
+        let inserted = dispatch_driver!(&self.0, |d| {
+            d.insert_missing(&meta, &rows).await.map_err(|e| {
+                MigrationError::PipelineFailed(format!("seed '{}' failed: {e}", seed.table))
+            })?
+        });
+        Ok(inserted)
+    }
+
     async fn destination_metadata(
         &self,
         ctx: &PipelineContext,
@@ -158,6 +180,27 @@ impl DestinationEndpoint for DbDestinationEndpoint {
         }
         Ok(metas)
     }
+
+    async fn sync_sequences(&self, tables: &[TableMetadata]) -> Result<(), MigrationError> {
+        for table in tables {
+            for column in table.columns.values().filter(|c| c.is_auto_increment) {
+                let next = dispatch_driver!(&self.0, |d| {
+                    d.sync_sequence(&table.name, &column.name)
+                        .await
+                        .map_err(|e| {
+                            MigrationError::PipelineFailed(format!(
+                                "Failed to sync sequence for {}.{}: {e}",
+                                table.name, column.name
+                            ))
+                        })?
+                });
+                if let Some(next) = next {
+                    info!(table = %table.name, column = %column.name, next, "sequence synced");
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -208,6 +251,13 @@ impl DestinationEndpoint for WasmDestinationEndpoint {
         ))
     }
 
+    async fn seed(&self, seed: &SeedTable) -> Result<u64, MigrationError> {
+        Err(MigrationError::PipelineFailed(format!(
+            "seed '{}': seeding is not supported on a WASM destination",
+            seed.table
+        )))
+    }
+
     async fn destination_metadata(
         &self,
         ctx: &PipelineContext,
@@ -221,6 +271,216 @@ impl DestinationEndpoint for WasmDestinationEndpoint {
             &self.input_schema,
         )])
     }
+
+    async fn sync_sequences(&self, _tables: &[TableMetadata]) -> Result<(), MigrationError> {
+        // The validator rejects sync_sequences for plugin destinations.
+        Ok(())
+    }
+}
+
+/// The destination of a validate-only pipeline: rows are checked and then
+/// dropped, so nothing is planned, created or written.
+pub struct DiscardDestinationEndpoint;
+
+#[async_trait]
+impl DestinationEndpoint for DiscardDestinationEndpoint {
+    async fn build(
+        &self,
+        pipeline: &Pipeline,
+        _source_dialect: Option<Dialect>,
+    ) -> Result<Destination, MigrationError> {
+        let dest = &pipeline.destination;
+        Ok(Destination::discard(&dest.table, &dest.connection)?)
+    }
+
+    async fn plan_settings(
+        &self,
+        _ctx: &mut PipelineContext,
+        _source: &dyn SourceEndpoint,
+        pipeline: &Pipeline,
+        dry_run: bool,
+        integrity: IntegrityMode,
+    ) -> Result<(ValidatedSettings, SchemaOps), MigrationError> {
+        // No destination table, so no DDL.
+        Ok((
+            ValidatedSettings::from_pipeline(&pipeline.settings, dry_run, integrity),
+            SchemaOps::empty(),
+        ))
+    }
+
+    async fn apply_schema_ops(
+        &self,
+        _ops: &[SchemaOp],
+        _phase: &str,
+    ) -> Result<(), MigrationError> {
+        Err(MigrationError::PipelineFailed(
+            "schema operations are not supported without a destination".into(),
+        ))
+    }
+
+    async fn run_hooks(
+        &self,
+        _phase: HookPhase,
+        _hooks: &LifecycleHooks,
+    ) -> Result<(), MigrationError> {
+        // The plan builder rejects hooks on validate-only pipelines.
+        Err(MigrationError::HookExecutionFailed(
+            "lifecycle SQL hooks are not supported without a destination".into(),
+        ))
+    }
+
+    async fn seed(&self, seed: &SeedTable) -> Result<u64, MigrationError> {
+        Err(MigrationError::PipelineFailed(format!(
+            "seed '{}': seeding is not supported without a destination",
+            seed.table
+        )))
+    }
+
+    async fn destination_metadata(
+        &self,
+        ctx: &PipelineContext,
+        _cascade: &[String],
+    ) -> Result<Vec<TableMetadata>, MigrationError> {
+        // The consumer drops batches without any table metadata.
+        Ok(vec![dest_meta_from_schema(&ctx.destination.name, &[])])
+    }
+
+    async fn sync_sequences(&self, _tables: &[TableMetadata]) -> Result<(), MigrationError> {
+        Ok(())
+    }
+}
+
+/// A CSV destination: rows go to rotated files in the connection's `path`
+/// directory, so there is no schema to plan and no SQL to run.
+pub struct CsvFileDestinationEndpoint;
+
+#[async_trait]
+impl DestinationEndpoint for CsvFileDestinationEndpoint {
+    async fn build(
+        &self,
+        pipeline: &Pipeline,
+        _source_dialect: Option<Dialect>,
+    ) -> Result<Destination, MigrationError> {
+        let dest = &pipeline.destination;
+        let dir = dest
+            .connection
+            .properties
+            .get_string("path")
+            .ok_or_else(|| {
+                MigrationError::PipelineFailed(format!(
+                    "csv connection '{}' is missing required property `path`",
+                    dest.connection.name
+                ))
+            })?;
+        let settings = Settings::from_map(&pipeline.settings);
+        // Every partition would start its own set of files
+        if settings.parallel_partitions > 1 {
+            return Err(MigrationError::PipelineFailed(
+                "parallel_partitions is not supported on a CSV destination".into(),
+            ));
+        }
+        let delimiter = u8::try_from(settings.csv_delimiter).map_err(|_| {
+            MigrationError::PipelineFailed(format!(
+                "csv_delimiter '{}' must be an ASCII character for a CSV destination",
+                settings.csv_delimiter
+            ))
+        })?;
+
+        Ok(Destination::csv(CsvFileConfig {
+            dir: dir.into(),
+            table: dest.table.clone(),
+            delimiter,
+            rotation: settings.file_rotation(),
+            compression: settings.file_compression,
+            schema: settings.file_schema,
+        }))
+    }
+
+    async fn plan_settings(
+        &self,
+        _ctx: &mut PipelineContext,
+        _source: &dyn SourceEndpoint,
+        pipeline: &Pipeline,
+        dry_run: bool,
+        integrity: IntegrityMode,
+    ) -> Result<(ValidatedSettings, SchemaOps), MigrationError> {
+        // Files have no schema, so no DDL.
+        Ok((
+            ValidatedSettings::from_pipeline(&pipeline.settings, dry_run, integrity),
+            SchemaOps::empty(),
+        ))
+    }
+
+    async fn apply_schema_ops(
+        &self,
+        _ops: &[SchemaOp],
+        _phase: &str,
+    ) -> Result<(), MigrationError> {
+        Err(MigrationError::PipelineFailed(
+            "schema operations are not supported on a CSV destination".into(),
+        ))
+    }
+
+    async fn run_hooks(
+        &self,
+        _phase: HookPhase,
+        _hooks: &LifecycleHooks,
+    ) -> Result<(), MigrationError> {
+        Err(MigrationError::HookExecutionFailed(
+            "lifecycle SQL hooks are not supported on a CSV destination".into(),
+        ))
+    }
+
+    async fn seed(&self, seed: &SeedTable) -> Result<u64, MigrationError> {
+        Err(MigrationError::PipelineFailed(format!(
+            "seed '{}': seeding is not supported on a CSV destination",
+            seed.table
+        )))
+    }
+
+    async fn destination_metadata(
+        &self,
+        ctx: &PipelineContext,
+        _cascade: &[String],
+    ) -> Result<Vec<TableMetadata>, MigrationError> {
+        // The header comes from the first row written; the consumer only
+        // needs the table to be known.
+        Ok(vec![dest_meta_from_schema(&ctx.destination.name, &[])])
+    }
+
+    async fn sync_sequences(&self, _tables: &[TableMetadata]) -> Result<(), MigrationError> {
+        Ok(())
+    }
+}
+
+/// `meta` cut down to the seeded columns, so the columns a seed leaves out
+/// keep their defaults. The seed must set the primary key: it is how rows
+/// already there are told apart.
+fn seed_metadata(
+    mut meta: TableMetadata,
+    seed: &SeedTable,
+) -> Result<TableMetadata, MigrationError> {
+    let fail =
+        |msg: String| MigrationError::PipelineFailed(format!("seed '{}': {msg}", seed.table));
+
+    if let Some(column) = seed.columns.iter().find(|c| meta.get_column(c).is_none()) {
+        return Err(fail(format!("table has no column '{column}'")));
+    }
+    if meta.primary_keys.is_empty() {
+        return Err(fail(
+            "table has no primary key to skip existing rows by".into(),
+        ));
+    }
+    if let Some(pk) = meta
+        .primary_keys
+        .iter()
+        .find(|pk| !seed.columns.contains(pk))
+    {
+        return Err(fail(format!("rows must set primary key column '{pk}'")));
+    }
+
+    meta.columns.retain(|name, _| seed.columns.contains(name));
+    Ok(meta)
 }
 
 /// Build a TableMetadata from a sink plugin's declared input schema. Column
@@ -247,6 +507,7 @@ fn dest_meta_from_schema(table: &str, schema: &[PluginField]) -> TableMetadata {
     TableMetadata {
         name: table.to_string(),
         schema: None,
+        collation: None,
         columns,
         primary_keys: Vec::new(),
         foreign_keys: Vec::new(),
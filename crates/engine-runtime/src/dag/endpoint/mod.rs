@@ -1,7 +1,10 @@
 use crate::error::MigrationError;
 use async_trait::async_trait;
 use connectors::{sql::metadata::table::TableMetadata, traits::introspector::SchemaIntrospector};
-pub use destination::{DbDestinationEndpoint, WasmDestinationEndpoint};
+pub use destination::{
+    CsvFileDestinationEndpoint, DbDestinationEndpoint, DiscardDestinationEndpoint,
+    WasmDestinationEndpoint,
+};
 use engine_config::settings::ValidatedSettings;
 use engine_core::{
     context::exec::ExecutionContext,
@@ -19,7 +22,7 @@ use model::{
     execution::{
         connection::Connection,
         flags::IntegrityMode,
-        pipeline::{LifecycleHooks, Pipeline},
+        pipeline::{LifecycleHooks, Pagination, Pipeline, SeedTable},
     },
     transform::mapping::TransformationMetadata,
 };
@@ -62,6 +65,15 @@ pub trait SourceEndpoint: Send + Sync {
         &self,
         dest_dialect: Dialect,
     ) -> Option<(Arc<dyn SchemaIntrospector>, Dialect)>;
+
+    /// The pipeline's pagination with schema-dependent defaults (such as the
+    /// tiebreaker) filled in. Sources without a schema return it unchanged.
+    async fn resolve_pagination(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Option<Pagination>, MigrationError> {
+        Ok(pipeline.source.pagination.clone())
+    }
 }
 
 #[async_trait]
@@ -93,6 +105,10 @@ pub trait DestinationEndpoint: Send + Sync {
         hooks: &LifecycleHooks,
     ) -> Result<(), MigrationError>;
 
+    /// Insert the seed rows whose primary key the table does not have yet;
+    /// returns the number inserted.
+    async fn seed(&self, seed: &SeedTable) -> Result<u64, MigrationError>;
+
     /// Destination table metadata for the consumer (introspected for DB,
     /// synthesized for plugin).
     async fn destination_metadata(
@@ -100,6 +116,10 @@ pub trait DestinationEndpoint: Send + Sync {
         ctx: &PipelineContext,
         cascade_tables: &[String],
     ) -> Result<Vec<TableMetadata>, MigrationError>;
+
+    /// Advance the sequences behind the auto-increment columns of `tables`
+    /// past the loaded keys.
+    async fn sync_sequences(&self, tables: &[TableMetadata]) -> Result<(), MigrationError>;
 }
 
 fn wasm_plugin_name(conn: &Connection) -> Result<String, MigrationError> {
@@ -126,15 +146,20 @@ pub async fn resolve_source(
 }
 
 pub async fn resolve_destination(
-    conn: &Connection,
+    pipeline: &Pipeline,
     exec: &ExecutionContext,
     registry: &Arc<PluginRegistry>,
 ) -> Result<Box<dyn DestinationEndpoint>, MigrationError> {
+    if pipeline.validate_only {
+        return Ok(Box::new(DiscardDestinationEndpoint));
+    }
+    let conn = &pipeline.destination.connection;
     match DataFormat::parse(&conn.driver) {
         Some(DataFormat::Wasm) => Ok(Box::new(WasmDestinationEndpoint::new(
             registry.clone(),
             wasm_plugin_name(conn)?,
         )?)),
+        Some(DataFormat::Csv) => Ok(Box::new(CsvFileDestinationEndpoint)),
         _ => Ok(Box::new(DbDestinationEndpoint(
             exec.resolve_driver(conn).await?,
         ))),
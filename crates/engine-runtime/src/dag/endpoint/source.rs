@@ -2,10 +2,14 @@ use super::{SourceArtifacts, SourceEndpoint};
 use crate::error::MigrationError;
 use async_trait::async_trait;
 use connectors::{sql::metadata::table::TableMetadata, traits::introspector::SchemaIntrospector};
+use engine_config::settings::Settings;
 use engine_core::{
     dispatch_driver,
     drivers::DriverRef,
-    plan::cascade::resolve_cascade_tables,
+    plan::{
+        cascade::resolve_cascade_tables,
+        pagination::{uses_tiebreaker, with_default_tiebreaker},
+    },
     schema::{
         graph_expander::GraphExpander,
         schema_ops::SchemaOps,
@@ -16,13 +20,14 @@ use engine_processing::io::source::{Source, plugin_introspector::PluginIntrospec
 use engine_wasm::registry::PluginRegistry;
 use model::{
     execution::{
-        pipeline::Pipeline,
+        pipeline::{Pagination, Pipeline},
         references::{DataMode, GraphReferences},
     },
     transform::mapping::TransformationMetadata,
 };
 use query_builder::offsets::OffsetStrategy;
 use std::{collections::HashMap, sync::Arc};
+use tracing::info;
 
 pub struct DbSourceEndpoint(pub DriverRef);
 
@@ -37,12 +42,14 @@ impl DbSourceEndpoint {
         root_table: &str,
         mapping: &TransformationMetadata,
         refs: &GraphReferences,
+        max_identifier_length: Option<usize>,
     ) -> Result<(Option<SchemaOps>, Option<HashMap<String, TableMetadata>>), MigrationError> {
         let source_dialect = self.0.dialect();
         let result = dispatch_driver!(&self.0, |d| {
             let introspector: Arc<dyn SchemaIntrospector> = d.clone() as _;
             let type_registry = Arc::new(TypeRegistry::new(source_dialect, Dialect::Postgres)); // TODO: from dest
-            let expander = GraphExpander::new(introspector, type_registry, source_dialect);
+            let expander = GraphExpander::new(introspector, type_registry, source_dialect)
+                .with_max_identifier_length(max_identifier_length);
             expander
                 .expand(root_table, refs, mapping, false, false)
                 .await
@@ -52,6 +59,26 @@ impl DbSourceEndpoint {
             matches!(refs.data_mode, DataMode::Cascade).then_some(result.discovered_tables);
         Ok((Some(result.schema_ops), cascade_meta))
     }
+
+    /// A server cursor resumes after the last key it read, which checkpoints
+    /// hold as an integer, so its key must be an integer column.
+    async fn check_server_cursor_key(
+        &self,
+        pipeline: &Pipeline,
+        key: &str,
+    ) -> Result<(), MigrationError> {
+        let table = dispatch_driver!(&self.0, |d| {
+            d.table_metadata(&pipeline.source.table).await
+        })?;
+        let column = key.rsplit('.').next().unwrap_or(key);
+        match table.columns().iter().find(|c| c.name == column) {
+            Some(col) if !col.is_integer() => Err(MigrationError::InitializationError(format!(
+                "server_cursor key '{key}' on table '{}' is {}: a server cursor resumes after the last key read, so the key must be an integer",
+                pipeline.source.table, col.data_type
+            ))),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
@@ -64,7 +91,9 @@ impl SourceEndpoint for DbSourceEndpoint {
     ) -> Result<SourceArtifacts, MigrationError> {
         let (schema_ops, cascade_meta) = match &pipeline.source.graph_references {
             Some(refs) => {
-                self.expand_graph(&pipeline.source.table, mapping, refs)
+                let max_identifier_length =
+                    Settings::from_map(&pipeline.settings).max_identifier_length;
+                self.expand_graph(&pipeline.source.table, mapping, refs, max_identifier_length)
                     .await?
             }
             None => (None, None),
@@ -93,6 +122,36 @@ impl SourceEndpoint for DbSourceEndpoint {
         let introspector = dispatch_driver!(&self.0, |d| d.clone() as Arc<dyn SchemaIntrospector>);
         Some((introspector, self.0.dialect()))
     }
+
+    async fn resolve_pagination(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Option<Pagination>, MigrationError> {
+        let Some(pagination) = &pipeline.source.pagination else {
+            return Ok(None);
+        };
+        if pagination.strategy.eq_ignore_ascii_case("server_cursor") {
+            self.check_server_cursor_key(pipeline, &pagination.column)
+                .await?;
+        }
+        if pagination.tiebreaker.is_some() || !uses_tiebreaker(&pagination.strategy) {
+            return Ok(Some(pagination.clone()));
+        }
+
+        let table = dispatch_driver!(&self.0, |d| {
+            d.table_metadata(&pipeline.source.table).await
+        })?;
+        let resolved = with_default_tiebreaker(pagination, &table);
+        let Some(tiebreaker) = &resolved.tiebreaker else {
+            return Err(MigrationError::InitializationError(format!(
+                "Pagination on '{}' needs a tiebreaker: table '{}' has no single-column primary key",
+                pagination.column, pipeline.source.table
+            )));
+        };
+
+        info!(cursor = %pagination.column, %tiebreaker, "no tiebreaker configured, using primary key");
+        Ok(Some(resolved))
+    }
 }
 
 #[async_trait]
@@ -4,13 +4,19 @@ use crate::{
         endpoint::{resolve_destination, resolve_source},
     },
     error::MigrationError,
-    execution::orchestrator::PipelineOrchestrator,
+    execution::{
+        artifacts, email,
+        notify::NotifySubscriber,
+        orchestrator::{Execution, PipelineOrchestrator},
+    },
 };
+use chrono::{DateTime, Utc};
+use engine_config::settings::DeferForeignKeys;
 use engine_core::{
     context::{env::EnvContext, exec::ExecutionContext},
     event_bus::bus::EventBus,
     plan::execution::ExecutionPlan,
-    state::{StateStore, models::WalEntry, sled_store::SledStateStore},
+    state::{models::WalEntry, open_state_store},
     utils::make_item_id,
 };
 use engine_infra::shutdown::ShutdownSignal;
@@ -18,7 +24,10 @@ use engine_processing::{
     context::PipelineContext,
     io::{destination::Destination, source::Source},
 };
-use engine_state::models::{PauseReason, PipelineRunState, PipelineStatus, RunState, RunStatus};
+use engine_state::models::{
+    PauseReason, PipelineRunState, PipelineStatus, RunRecord, RunState, RunStatus, WorkItem,
+    WorkKind,
+};
 use engine_wasm::registry::{PluginRegistry, load_registry, plugin_columns};
 use futures::stream::{self, StreamExt};
 use model::{
@@ -32,21 +41,29 @@ use model::{
 use model::{pagination::cursor::Cursor, transform::mapping::TransformationMetadata};
 use query_builder::offsets::{OffsetStrategy, OffsetStrategyFactory};
 use std::{
-    collections::HashSet,
-    sync::{Arc, Mutex},
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::{Arc, Mutex, OnceLock},
 };
-use tokio::sync::mpsc;
+use tokio::sync::{Semaphore, SemaphorePermit, mpsc};
 use tracing::{debug, error, info, instrument, warn};
 
 pub struct DagExecutor {
-    plan: ExecutionPlan,
+    pub(super) plan: ExecutionPlan,
     flags: ExecutionFlags,
-    shutdown: ShutdownSignal,
-    exec_ctx: ExecutionContext,
-    exec_config: ExecutionConfig,
-    event_bus: EventBus,
+    pub(super) shutdown: ShutdownSignal,
+    pub(super) exec_ctx: ExecutionContext,
+    pub(super) exec_config: ExecutionConfig,
+    pub(super) event_bus: EventBus,
     done_ops: Arc<Mutex<HashSet<String>>>,
+    /// Serializes the schema changes of pipelines sharing a destination table
+    ddl_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Slots of the connections with `max_concurrent_pipelines`, by name
+    connection_slots: HashMap<String, Semaphore>,
     plugin_registry: Arc<PluginRegistry>,
+    notifier: Option<NotifySubscriber>,
+    /// Id and start time of this execution's run record, which `run.id`
+    /// and `run.started_at` references resolve to
+    pub(super) current_run: OnceLock<(String, DateTime<Utc>)>,
 }
 
 impl DagExecutor {
@@ -84,14 +101,24 @@ impl DagExecutor {
             MigrationError::InitializationError("Could not determine home directory".to_string())
         })?;
 
-        let state = Arc::new(
-            SledStateStore::open(home_dir.join(".stratum/state")).map_err(|e| {
-                MigrationError::InitializationError(format!("Failed to open state store: {e}"))
-            })?,
-        );
+        let state = open_state_store(&plan.state, &home_dir.join(".stratum/state"))
+            .await
+            .map_err(|e| MigrationError::InitializationError(e.to_string()))?;
+        info!(backend = plan.state.backend_name(), "state store opened");
         let exec_ctx = ExecutionContext::new(&plan, state, env).await?;
         let exec_config = plan.execution_config.clone();
         let plugin_registry = load_registry(&plan.plugins)?;
+        let notifier = NotifySubscriber::start(&plan, &event_bus).await;
+        let connection_slots = plan
+            .connections
+            .iter()
+            .filter_map(|c| {
+                Some((
+                    c.name.clone(),
+                    Semaphore::new(c.max_concurrent_pipelines()?),
+                ))
+            })
+            .collect();
 
         Ok(Self {
             plan,
@@ -101,7 +128,11 @@ impl DagExecutor {
             exec_config,
             event_bus,
             done_ops: Arc::new(Mutex::new(HashSet::new())),
+            ddl_lock: Arc::new(tokio::sync::Mutex::new(())),
+            connection_slots,
             plugin_registry,
+            notifier,
+            current_run: OnceLock::new(),
         })
     }
 
@@ -118,28 +149,48 @@ impl DagExecutor {
         debug!("event subscriber configured");
     }
 
-    pub async fn execute(self, dag: Dag) -> Result<(), MigrationError> {
+    pub async fn execute(mut self, dag: Dag) -> Result<(), MigrationError> {
         let mut failed_pipelines = HashSet::new();
 
         // Initialize state or resume from a paused run
-        let (mut run_state, mut completed_pipelines) = self.init_or_resume_run().await?;
-
-        // Execute levels, updating run_state as pipelines complete
-        let run_result = self
-            .execute_levels(
-                &dag,
-                &mut run_state,
-                &mut completed_pipelines,
-                &mut failed_pipelines,
-            )
-            .await;
+        let (mut run_state, mut completed_pipelines, record) = self.init_or_resume_run().await?;
+
+        // Pin barrier sources, then execute levels, updating run_state as
+        // pipelines complete
+        let run_result = match self.pin_barriers(&completed_pipelines).await {
+            Ok(()) => {
+                self.execute_levels(
+                    &dag,
+                    &mut run_state,
+                    &mut completed_pipelines,
+                    &mut failed_pipelines,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
 
         // Complete run and finalize state
-        self.finalize_run(run_result, run_state, failed_pipelines)
-            .await
+        let result = self
+            .finalize_run(run_result, run_state, failed_pipelines)
+            .await;
+
+        let record = self.finish_run_record(record, &result).await;
+        self.report_run(&record).await;
+        self.flush_notifications().await;
+        result
+    }
+
+    /// Delivers webhook notifications still queued for this run.
+    pub(super) async fn flush_notifications(&mut self) {
+        if let Some(notifier) = self.notifier.take() {
+            notifier.finish(&self.event_bus).await;
+        }
     }
 
-    async fn init_or_resume_run(&self) -> Result<(RunState, HashSet<String>), MigrationError> {
+    pub(super) async fn init_or_resume_run(
+        &self,
+    ) -> Result<(RunState, HashSet<String>, RunRecord), MigrationError> {
         let run_id = self.exec_ctx.run_id();
         let existing_run = self.exec_ctx.state.load_run_state(&run_id).await?;
 
@@ -181,7 +232,66 @@ impl DagExecutor {
         };
         self.exec_ctx.state.append_wal(&wal_entry).await?;
 
-        Ok((run_state, completed_pipelines))
+        let mut record = RunRecord::new(&run_state, resuming, chrono::Utc::now());
+        let base_id = record.id.clone();
+        let mut n = 1;
+        while self
+            .exec_ctx
+            .state
+            .load_run_record(&record.id)
+            .await?
+            .is_some()
+        {
+            n += 1;
+            record.id = format!("{base_id}-{n}");
+        }
+        self.exec_ctx.state.save_run_record(&record).await?;
+        let _ = self.current_run.set((record.id.clone(), record.started_at));
+
+        Ok((run_state, completed_pipelines, record))
+    }
+
+    /// Close this execution's run history entry with its outcome and return
+    /// it. The history is informational, so a failed write is logged rather
+    /// than returned.
+    pub(super) async fn finish_run_record(
+        &self,
+        mut record: RunRecord,
+        result: &Result<(), MigrationError>,
+    ) -> RunRecord {
+        let run_state = match self.exec_ctx.state.load_run_state(&record.run_id).await {
+            Ok(Some(run_state)) => run_state,
+            Ok(None) => return record,
+            Err(e) => {
+                warn!(run_id = %record.run_id, error = %e, "failed to load run state for history");
+                return record;
+            }
+        };
+
+        let ended_at = chrono::Utc::now();
+        let status = match result {
+            // Completion and pauses are already reflected in the persisted run state
+            Ok(()) | Err(MigrationError::Paused) => run_state.status.clone(),
+            Err(e) => RunStatus::Failed {
+                error: e.to_string(),
+                failed_at: ended_at,
+            },
+        };
+
+        record.finish(&run_state, status, ended_at);
+        if let Err(e) = self.exec_ctx.state.save_run_record(&record).await {
+            warn!(run_id = %record.run_id, error = %e, "failed to save run history");
+        }
+        record
+    }
+
+    /// Uploads this execution's artifacts when the state block sets
+    /// `artifacts_url`, and emails its summary to the plan's mailing lists.
+    pub(super) async fn report_run(&self, record: &RunRecord) {
+        if let Some(url) = &self.plan.artifacts_url {
+            artifacts::upload(url, &self.plan, self.exec_ctx.state.as_ref(), record).await;
+        }
+        email::send_run_summary(&self.plan.email, record).await;
     }
 
     fn build_initial_run_state(
@@ -234,6 +344,7 @@ impl DagExecutor {
             run_id: run_id.to_string(),
             config_path: self.plan.config_path.clone(),
             config_hash: self.plan.hash().to_string(),
+            fingerprint: self.plan.fingerprint(),
             status: RunStatus::Running,
             started_at: existing_run
                 .map(|r| r.started_at)
@@ -257,7 +368,13 @@ impl DagExecutor {
             "starting migration"
         );
 
+        let mut views = self.load_view_refreshes().await?;
+
         for (level_idx, level) in execution_order.iter().enumerate() {
+            // Views whose pipelines finished in earlier levels (or runs)
+            self.refresh_ready_views(completed_pipelines, failed_pipelines, &mut views)
+                .await?;
+
             let level_remaining: Vec<String> = level
                 .iter()
                 .filter(|name| !completed_pipelines.contains(*name))
@@ -303,6 +420,18 @@ impl DagExecutor {
                 return Err(MigrationError::Paused);
             }
 
+            // Gated pipelines wait for an operator's approval
+            if let Some(gate) = self.pending_approval(&executable).await? {
+                self.save_paused_state(
+                    run_state,
+                    failed_pipelines,
+                    completed_pipelines,
+                    PauseReason::Approval { gate },
+                )
+                .await?;
+                return Err(MigrationError::Paused);
+            }
+
             // Execute the current level's pipelines
             match self
                 .execute_level(
@@ -329,16 +458,31 @@ impl DagExecutor {
 
                     return Err(MigrationError::Paused);
                 }
+                Err(MigrationError::SchemaDrift { table, changes }) => {
+                    self.save_paused_state(
+                        run_state,
+                        failed_pipelines,
+                        completed_pipelines,
+                        PauseReason::SchemaDrift { table, changes },
+                    )
+                    .await?;
+
+                    return Err(MigrationError::Paused);
+                }
                 Err(e) => return Err(e),
                 Ok(()) => {}
             }
         }
 
-        Ok(())
+        self.refresh_ready_views(completed_pipelines, failed_pipelines, &mut views)
+            .await?;
+
+        // Deferred foreign keys and other DDL that waits for the whole load
+        self.apply_post_load_ops(failed_pipelines).await
     }
 
-    async fn finalize_run(
-        self,
+    pub(super) async fn finalize_run(
+        &self,
         run_result: Result<(), MigrationError>,
         mut run_state: RunState,
         failed_pipelines: HashSet<String>,
@@ -350,7 +494,6 @@ impl DagExecutor {
                     completed_at: chrono::Utc::now(),
                 };
 
-                let run_id = self.exec_ctx.run_id();
                 for ps in &mut run_state.pipelines {
                     if ps.status == PipelineStatus::Pending {
                         if failed_pipelines.contains(&ps.name) {
@@ -363,13 +506,8 @@ impl DagExecutor {
                     }
 
                     // Always refresh rows_done from checkpoint (authoritative cumulative count)
-                    if let Ok(Some(cp)) = self
-                        .exec_ctx
-                        .state
-                        .load_checkpoint(&run_id, &ps.item_id, "part-0")
-                        .await
-                    {
-                        ps.rows_done = cp.rows_done;
+                    if let Some(rows) = self.checkpointed_rows(&ps.item_id).await {
+                        ps.rows_done = rows;
                     }
                 }
 
@@ -405,7 +543,7 @@ impl DagExecutor {
         }
     }
 
-    fn finalize(self, failed_pipelines: HashSet<String>) -> Result<(), MigrationError> {
+    fn finalize(&self, failed_pipelines: HashSet<String>) -> Result<(), MigrationError> {
         if failed_pipelines.is_empty() {
             return Ok(());
         }
@@ -474,8 +612,11 @@ impl DagExecutor {
                 let mut results = Vec::new();
                 for name in executable {
                     let result = self.execute_pipeline(name).await;
-                    let should_fail_fast = result.is_err()
-                        && matches!(self.exec_config.on_failure, FailureStrategy::FailFast);
+                    // A drifted table pauses the run, so the rest of the level waits
+                    let should_fail_fast =
+                        matches!(result, Err(MigrationError::SchemaDrift { .. }))
+                            || result.is_err()
+                                && matches!(self.exec_config.on_failure, FailureStrategy::FailFast);
                     results.push((name.clone(), result));
                     if should_fail_fast {
                         break;
@@ -511,6 +652,10 @@ impl DagExecutor {
                     info!(pipeline = %name, "pipeline stopped due to shutdown");
                     return Err(MigrationError::ShutdownRequested);
                 }
+                Err(e @ MigrationError::SchemaDrift { .. }) => {
+                    warn!(pipeline = %name, error = %e, "pipeline paused on schema drift");
+                    return Err(e);
+                }
                 Err(e) => {
                     error!(pipeline = %name, error = %e, "pipeline failed");
                     failed_pipelines.insert(name.clone());
@@ -526,7 +671,7 @@ impl DagExecutor {
     }
 
     /// Mark a pipeline as completed in the RunState and persist to sled.
-    async fn mark_pipeline_completed(
+    pub(super) async fn mark_pipeline_completed(
         &self,
         name: &str,
         rows_done: u64,
@@ -539,13 +684,8 @@ impl DagExecutor {
             ps.status = PipelineStatus::Completed;
             // Read cumulative rows from checkpoint (authoritative source),
             // fall back to prior count + current session metrics.
-            if let Ok(Some(cp)) = self
-                .exec_ctx
-                .state
-                .load_checkpoint(&self.exec_ctx.run_id(), &ps.item_id, "part-0")
-                .await
-            {
-                ps.rows_done = cp.rows_done;
+            if let Some(rows) = self.checkpointed_rows(&ps.item_id).await {
+                ps.rows_done = rows;
             } else {
                 ps.rows_done += rows_done;
             }
@@ -555,8 +695,25 @@ impl DagExecutor {
         Ok(())
     }
 
+    /// Rows a pipeline has written per its checkpoints, summed over its
+    /// partitions; `None` before its first checkpoint.
+    async fn checkpointed_rows(&self, item_id: &str) -> Option<u64> {
+        let checkpoints = self
+            .exec_ctx
+            .state
+            .list_checkpoints(&self.exec_ctx.run_id())
+            .await
+            .ok()?;
+        let mut parts = checkpoints
+            .iter()
+            .filter(|cp| cp.item_id == item_id)
+            .peekable();
+        parts.peek()?;
+        Some(parts.map(|cp| cp.rows_done).sum())
+    }
+
     /// Save RunState as paused with current pipeline statuses.
-    async fn save_paused_state(
+    pub(super) async fn save_paused_state(
         &self,
         base_state: &engine_state::models::RunState,
         failed_pipelines: &HashSet<String>,
@@ -571,7 +728,6 @@ impl DagExecutor {
             paused_at: chrono::Utc::now(),
         };
 
-        let run_id = self.exec_ctx.run_id();
         for ps in &mut state.pipelines {
             if completed_pipelines.contains(&ps.name) {
                 ps.status = PipelineStatus::Completed;
@@ -582,13 +738,8 @@ impl DagExecutor {
             }
 
             // Update rows_done from checkpoint (cumulative) for all pipelines
-            if let Ok(Some(cp)) = self
-                .exec_ctx
-                .state
-                .load_checkpoint(&run_id, &ps.item_id, "part-0")
-                .await
-            {
-                ps.rows_done = cp.rows_done;
+            if let Some(rows) = self.checkpointed_rows(&ps.item_id).await {
+                ps.rows_done = rows;
             }
         }
 
@@ -605,7 +756,10 @@ impl DagExecutor {
     }
 
     /// Returns (rows_processed) on success.
-    async fn execute_pipeline(&self, pipeline_name: &str) -> Result<u64, MigrationError> {
+    pub(super) async fn execute_pipeline(
+        &self,
+        pipeline_name: &str,
+    ) -> Result<u64, MigrationError> {
         let (idx, pipeline) = self
             .plan
             .pipelines
@@ -616,34 +770,155 @@ impl DagExecutor {
                 MigrationError::PipelineFailed(format!("Pipeline '{}' not found", pipeline_name))
             })?;
 
-        self.run_pipeline(idx, pipeline).await
+        let _slots = self.acquire_connection_slots(pipeline).await;
+        self.run_pipeline(idx, pipeline, self.shutdown.clone())
+            .await
+    }
+
+    /// Runs one claimed item of a distributed run: a pipeline, which may
+    /// split into key ranges, or one range or the closing steps of a split
+    /// pipeline. Stops at a batch boundary on `shutdown`.
+    #[instrument(skip_all, fields(pipeline = %item.pipeline, item = %item.key()))]
+    pub(super) async fn execute_work_item(
+        &self,
+        item: &WorkItem,
+        shutdown: ShutdownSignal,
+    ) -> Result<Execution, MigrationError> {
+        let (idx, pipeline) = self
+            .plan
+            .pipelines
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.name == item.pipeline)
+            .ok_or_else(|| {
+                MigrationError::PipelineFailed(format!("Pipeline '{}' not found", item.pipeline))
+            })?;
+
+        let _slots = self.acquire_connection_slots(pipeline).await;
+        let orchestrator = self.orchestrator(idx, pipeline, shutdown).await?;
+        match item.kind {
+            WorkKind::Pipeline => orchestrator.execute_or_split().await,
+            WorkKind::Range { id, start, end } => orchestrator
+                .execute_range(id, start, end)
+                .await
+                .map(Execution::Loaded),
+            WorkKind::Finish => {
+                orchestrator.execute_finish().await?;
+                Ok(Execution::Loaded(0))
+            }
+        }
+    }
+
+    /// Take a slot on each capped connection the pipeline reads or writes
+    /// through, waiting while other pipelines hold them all. Slots are taken
+    /// in name order, so two pipelines never wait on each other's.
+    async fn acquire_connection_slots(&self, pipeline: &Pipeline) -> Vec<SemaphorePermit<'_>> {
+        let names: BTreeSet<&str> = [
+            pipeline.source.connection.name.as_str(),
+            pipeline.destination.connection.name.as_str(),
+        ]
+        .into();
+
+        let mut permits = Vec::new();
+        for name in names {
+            let Some(slots) = self.connection_slots.get(name) else {
+                continue;
+            };
+            if slots.available_permits() == 0 {
+                info!(
+                    pipeline = %pipeline.name,
+                    connection = name,
+                    "waiting for a connection slot (max_concurrent_pipelines)"
+                );
+            }
+            if let Ok(permit) = slots.acquire().await {
+                permits.push(permit);
+            }
+        }
+        permits
     }
 
     #[instrument(
         skip_all,
         fields(pipeline = %pipeline.name, table = %pipeline.destination.table)
     )]
-    async fn run_pipeline(&self, idx: usize, pipeline: &Pipeline) -> Result<u64, MigrationError> {
+    async fn run_pipeline(
+        &self,
+        idx: usize,
+        pipeline: &Pipeline,
+        shutdown: ShutdownSignal,
+    ) -> Result<u64, MigrationError> {
         let start_time = std::time::Instant::now();
         info!("starting pipeline");
 
+        let orchestrator = self.orchestrator(idx, pipeline, shutdown).await?;
+
+        // Execute: pre-DDL -> data migration -> post-DDL
+        let rows = orchestrator.execute().await?;
+
+        info!(
+            rows,
+            elapsed_secs = start_time.elapsed().as_secs_f64(),
+            "pipeline finished"
+        );
+
+        Ok(rows)
+    }
+
+    /// The orchestrator publishes its own lifecycle events; failures before
+    /// it exists (connections, schema checks) are published here.
+    async fn orchestrator(
+        &self,
+        idx: usize,
+        pipeline: &Pipeline,
+        shutdown: ShutdownSignal,
+    ) -> Result<PipelineOrchestrator, MigrationError> {
+        match self.prepare_pipeline(idx, pipeline, shutdown).await {
+            Ok(orchestrator) => Ok(orchestrator),
+            Err(e) => {
+                self.event_bus
+                    .publish(MigrationEvent::Failed {
+                        run_id: self.exec_ctx.run_id(),
+                        item_id: make_item_id(self.plan.hash(), &pipeline.destination.table, idx),
+                        error: e.to_string(),
+                        error_code: None,
+                        rows_processed: 0,
+                        timestamp: chrono::Utc::now(),
+                    })
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Resolves the pipeline's endpoints and settings into an orchestrator
+    /// ready to run, which stops at a batch boundary on `shutdown`.
+    async fn prepare_pipeline(
+        &self,
+        idx: usize,
+        pipeline: &Pipeline,
+        shutdown: ShutdownSignal,
+    ) -> Result<PipelineOrchestrator, MigrationError> {
+        let mut pipeline = pipeline.clone();
+        if let Some((run_id, started_at)) = self.current_run.get() {
+            pipeline.bind_run(run_id, *started_at);
+        }
+        let pipeline = &pipeline;
+
         let source_ep = resolve_source(
             &pipeline.source.connection,
             &self.exec_ctx,
             &self.plugin_registry,
         )
         .await?;
-        let dest_ep = resolve_destination(
-            &pipeline.destination.connection,
-            &self.exec_ctx,
-            &self.plugin_registry,
-        )
-        .await?;
+        let dest_ep = resolve_destination(pipeline, &self.exec_ctx, &self.plugin_registry).await?;
 
         let mut mapping = TransformationMetadata::new(pipeline);
         mapping.set_plugin_columns(plugin_columns(pipeline, &self.plugin_registry));
 
-        let offset_strategy = OffsetStrategyFactory::from_pagination(&pipeline.source.pagination);
+        let pagination = source_ep.resolve_pagination(pipeline).await?;
+        let offset_strategy = OffsetStrategyFactory::from_pagination(&pagination)
+            .map_err(MigrationError::InitializationError)?;
 
         let source = source_ep
             .build(pipeline, &mapping, offset_strategy.clone())
@@ -676,30 +951,24 @@ impl DagExecutor {
         // Graph-expansion ops (already topo-sorted) replace settings-based ops.
         if let Some(expanded) = source.schema_ops {
             schema_ops = expanded;
+            let defer = settings.defer_foreign_keys();
+            if defer.is_deferred() {
+                schema_ops.defer_foreign_keys(defer == DeferForeignKeys::NotValid);
+            }
         }
 
-        let orchestrator = PipelineOrchestrator::new(
+        Ok(PipelineOrchestrator::new(
             pipeline.clone(),
             pipeline_ctx,
             dest_ep,
             settings,
             schema_ops,
-            self.shutdown.clone(),
+            shutdown,
             self.event_bus.clone(),
             self.done_ops.clone(),
+            self.ddl_lock.clone(),
             source.cascade_tables,
-        );
-
-        // Execute: pre-DDL -> data migration -> post-DDL
-        let rows = orchestrator.execute().await?;
-
-        info!(
-            rows,
-            elapsed_secs = start_time.elapsed().as_secs_f64(),
-            "pipeline finished"
-        );
-
-        Ok(rows)
+        ))
     }
 
     /// Initializes the `PipelineContext` and commits the initialization event to the WAL.
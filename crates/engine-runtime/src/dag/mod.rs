@@ -1,10 +1,15 @@
 use crate::dag::builder::PipelineNode;
 use std::collections::HashMap;
 
+mod approvals;
+mod barrier;
 pub mod builder;
+pub mod distributed;
 pub mod endpoint;
 pub mod error;
 pub mod executor;
+mod post_load;
+mod views;
 
 #[derive(Debug, Clone)]
 pub struct Dag {
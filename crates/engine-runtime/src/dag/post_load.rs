@@ -0,0 +1,191 @@
+//! Post-load schema operations: DDL that waits until every pipeline of the
+//! run has loaded, such as the foreign keys held back by `defer_foreign_keys`.
+//!
+//! Pipelines record their post-load ops in the WAL as they finish, so a run
+//! that was paused and resumed still runs the ops of pipelines that
+//! completed before the pause.
+
+use crate::{dag::executor::DagExecutor, error::MigrationError};
+use engine_config::settings;
+use engine_core::{dispatch_driver, schema::schema_ops::SchemaOp, state::models::WalEntry};
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+impl DagExecutor {
+    /// Runs the post-load ops recorded by this run's pipelines, grouped by
+    /// destination connection and in the order they were recorded. When a
+    /// pipeline failed only the ops that change just their own table run,
+    /// since a constraint could reference a table that did not load.
+    pub(super) async fn apply_post_load_ops(
+        &self,
+        failed_pipelines: &HashSet<String>,
+    ) -> Result<(), MigrationError> {
+        // Failed views are reported alongside pipelines but do not matter here
+        let pipeline_failed = self
+            .plan
+            .pipelines
+            .iter()
+            .any(|p| failed_pipelines.contains(&p.name));
+
+        let run_id = self.exec_ctx.run_id();
+        let wal = self.exec_ctx.state.iter_wal(&run_id).await?;
+        let wal = WalEntry::since_run_start(&wal);
+        let count =
+            |ops: &[(String, Vec<SchemaOp>)]| ops.iter().map(|(_, o)| o.len()).sum::<usize>();
+        let mut ops = deferred_ops(wal, false);
+        if pipeline_failed {
+            let kept = deferred_ops(wal, true);
+            if count(&kept) < count(&ops) {
+                warn!(
+                    ops = count(&ops) - count(&kept),
+                    "skipping post-load schema operations: some pipelines failed"
+                );
+            }
+            ops = kept;
+        }
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        for (connection, ops) in ops {
+            let Some(conn) = self
+                .plan
+                .pipelines
+                .iter()
+                .map(|p| &p.destination.connection)
+                .find(|c| c.name == connection)
+            else {
+                warn!(connection = %connection, "skipping post-load schema operations: connection is no longer in the plan");
+                continue;
+            };
+
+            info!(connection = %connection, count = ops.len(), "executing post-load schema operations");
+            let driver = self.exec_ctx.resolve_driver(conn).await?;
+            dispatch_driver!(&driver, |d| {
+                settings::apply_schema_ops(d.as_ref(), &ops)
+                    .await
+                    .map_err(|e| {
+                        MigrationError::PipelineFailed(format!(
+                            "post-load schema operation failed: {e}"
+                        ))
+                    })?
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Deferred ops per connection, first recorded first, each statement once;
+/// with `table_only`, just the ops that change only their own table.
+fn deferred_ops(wal: &[WalEntry], table_only: bool) -> Vec<(String, Vec<SchemaOp>)> {
+    let mut seen = HashSet::new();
+    let mut grouped: Vec<(String, Vec<SchemaOp>)> = Vec::new();
+
+    for entry in wal {
+        let WalEntry::SchemaOpDeferred {
+            connection,
+            sql,
+            description,
+            idempotent,
+            skip_if_missing_ref,
+            table_only: changes_table_only,
+            ..
+        } = entry
+        else {
+            continue;
+        };
+        if table_only && !changes_table_only {
+            continue;
+        }
+        if !seen.insert(sql.clone()) {
+            continue;
+        }
+
+        let op = SchemaOp {
+            sql: sql.clone(),
+            description: description.clone(),
+            idempotent: *idempotent,
+            skip_if_missing_ref: *skip_if_missing_ref,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: None,
+        };
+        match grouped.iter_mut().find(|(c, _)| c == connection) {
+            Some((_, ops)) => ops.push(op),
+            None => grouped.push((connection.clone(), vec![op])),
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deferred(connection: &str, sql: &str) -> WalEntry {
+        WalEntry::SchemaOpDeferred {
+            run_id: "run-1".to_string(),
+            item_id: "item".to_string(),
+            connection: connection.to_string(),
+            sql: sql.to_string(),
+            description: sql.to_string(),
+            idempotent: true,
+            skip_if_missing_ref: true,
+            table_only: false,
+        }
+    }
+
+    #[test]
+    fn test_deferred_ops_grouped_by_connection_once_each() {
+        let wal = vec![
+            deferred(
+                "dest",
+                "ALTER TABLE a ADD FOREIGN KEY (b_id) REFERENCES b (id);",
+            ),
+            WalEntry::RunResumed {
+                run_id: "run-1".to_string(),
+            },
+            deferred(
+                "archive",
+                "ALTER TABLE c ADD FOREIGN KEY (a_id) REFERENCES a (id);",
+            ),
+            deferred(
+                "dest",
+                "ALTER TABLE a ADD FOREIGN KEY (b_id) REFERENCES b (id);",
+            ),
+            deferred(
+                "dest",
+                "ALTER TABLE b ADD FOREIGN KEY (d_id) REFERENCES d (id);",
+            ),
+        ];
+
+        let grouped = deferred_ops(&wal, false);
+        let summary: Vec<(&str, Vec<&str>)> = grouped
+            .iter()
+            .map(|(conn, ops)| {
+                (
+                    conn.as_str(),
+                    ops.iter().map(|op| op.sql.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "dest",
+                    vec![
+                        "ALTER TABLE a ADD FOREIGN KEY (b_id) REFERENCES b (id);",
+                        "ALTER TABLE b ADD FOREIGN KEY (d_id) REFERENCES d (id);",
+                    ]
+                ),
+                (
+                    "archive",
+                    vec!["ALTER TABLE c ADD FOREIGN KEY (a_id) REFERENCES a (id);"]
+                ),
+            ]
+        );
+    }
+}
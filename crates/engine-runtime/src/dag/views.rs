@@ -0,0 +1,200 @@
+//! Refreshes destination materialized views once the pipelines they read
+//! have loaded.
+//!
+//! Views are refreshed as soon as their dependencies allow rather than at the
+//! end of the run, in the order the plan sorted them. Each refresh is logged
+//! to the WAL so a resumed run does not refresh a view twice.
+
+use crate::{dag::executor::DagExecutor, error::MigrationError};
+use connectors::traits::executor::QueryExecutor;
+use engine_core::{dispatch_driver, retry::RetryPolicy, state::models::WalEntry};
+use engine_processing::retry::classify_driver_error;
+use model::{
+    events::migration::MigrationEvent,
+    execution::{execution_config::FailureStrategy, materialized_view::MaterializedView},
+};
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Instant,
+};
+use tracing::{error, info, warn};
+
+/// Views refreshed or given up on so far in this run.
+#[derive(Debug, Default)]
+pub(super) struct ViewRefreshes {
+    done: HashSet<String>,
+    failed: HashSet<String>,
+}
+
+/// Name a failed view is reported under alongside failed pipelines.
+fn failure_label(view: &MaterializedView) -> String {
+    format!("materialized_view.{}", view.name)
+}
+
+impl DagExecutor {
+    /// Views already refreshed by an earlier attempt at this run.
+    pub(super) async fn load_view_refreshes(&self) -> Result<ViewRefreshes, MigrationError> {
+        let mut refreshes = ViewRefreshes::default();
+        if self.plan.materialized_views.is_empty() {
+            return Ok(refreshes);
+        }
+
+        for entry in self
+            .exec_ctx
+            .state
+            .iter_wal(&self.exec_ctx.run_id())
+            .await?
+        {
+            if let WalEntry::ViewRefreshed { view, .. } = entry {
+                refreshes.done.insert(view);
+            }
+        }
+        Ok(refreshes)
+    }
+
+    /// Refreshes every view whose pipelines have completed and whose upstream
+    /// views are refreshed. A view behind a failed pipeline or view is
+    /// skipped; failed and skipped views are added to `failed_pipelines`.
+    /// Returns an error only when a refresh fails under `fail_fast`.
+    pub(super) async fn refresh_ready_views(
+        &self,
+        completed_pipelines: &HashSet<String>,
+        failed_pipelines: &mut HashSet<String>,
+        refreshes: &mut ViewRefreshes,
+    ) -> Result<(), MigrationError> {
+        let total = self.plan.materialized_views.len();
+
+        for (idx, view) in self.plan.materialized_views.iter().enumerate() {
+            if refreshes.done.contains(&view.name) || refreshes.failed.contains(&view.name) {
+                continue;
+            }
+
+            let blocked = view.pipelines.iter().any(|p| failed_pipelines.contains(p))
+                || view.views.iter().any(|v| refreshes.failed.contains(v));
+            if blocked {
+                warn!(view = %view.name, "skipping materialized view refresh: dependency failed");
+                self.publish_view_failed(view, "dependency failed".to_string())
+                    .await;
+                refreshes.failed.insert(view.name.clone());
+                failed_pipelines.insert(failure_label(view));
+                continue;
+            }
+
+            let ready = view
+                .pipelines
+                .iter()
+                .all(|p| completed_pipelines.contains(p))
+                && view.views.iter().all(|v| refreshes.done.contains(v));
+            if !ready {
+                continue;
+            }
+
+            match self.refresh_view(view, idx + 1, total).await {
+                Ok(()) => {
+                    refreshes.done.insert(view.name.clone());
+                }
+                Err(e) => {
+                    error!(view = %view.name, error = %e, "materialized view refresh failed");
+                    refreshes.failed.insert(view.name.clone());
+                    failed_pipelines.insert(failure_label(view));
+
+                    if matches!(self.exec_config.on_failure, FailureStrategy::FailFast) {
+                        return Err(MigrationError::PipelinesFailed(vec![failure_label(view)]));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn refresh_view(
+        &self,
+        view: &MaterializedView,
+        position: usize,
+        total: usize,
+    ) -> Result<(), MigrationError> {
+        let run_id = self.exec_ctx.run_id();
+        info!(view = %view.name, position, total, "refreshing materialized view");
+        self.event_bus
+            .publish(MigrationEvent::ViewRefreshStarted {
+                run_id: run_id.clone(),
+                view: view.name.clone(),
+                position,
+                total,
+                timestamp: chrono::Utc::now(),
+            })
+            .await;
+
+        let started = Instant::now();
+        let attempts = AtomicU32::new(0);
+        let result = self.run_refresh(view, &attempts).await;
+        let attempts = attempts.load(Ordering::Relaxed);
+
+        if let Err(e) = result {
+            self.publish_view_failed(view, e.to_string()).await;
+            return Err(e);
+        }
+
+        self.exec_ctx
+            .state
+            .append_wal(&WalEntry::ViewRefreshed {
+                run_id: run_id.clone(),
+                view: view.name.clone(),
+                attempts,
+            })
+            .await?;
+
+        let duration_ms = started.elapsed().as_millis() as u64;
+        info!(view = %view.name, attempts, duration_ms, "materialized view refreshed");
+        self.event_bus
+            .publish(MigrationEvent::ViewRefreshCompleted {
+                run_id,
+                view: view.name.clone(),
+                attempts,
+                duration_ms,
+                timestamp: chrono::Utc::now(),
+            })
+            .await;
+        Ok(())
+    }
+
+    /// Runs the refresh statement, retrying transient errors up to the
+    /// view's `max_attempts`.
+    async fn run_refresh(
+        &self,
+        view: &MaterializedView,
+        attempts: &AtomicU32,
+    ) -> Result<(), MigrationError> {
+        let driver = self.exec_ctx.resolve_driver(&view.connection).await?;
+        let sql = view.refresh_sql();
+        let policy = RetryPolicy {
+            max_attempts: view.max_attempts as usize,
+            ..RetryPolicy::for_database()
+        };
+
+        policy
+            .run(
+                || {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    let (driver, sql) = (&driver, &sql);
+                    async move { dispatch_driver!(driver, |d| d.execute(sql).await) }
+                },
+                classify_driver_error,
+            )
+            .await
+            .map_err(|e| MigrationError::DriverError(e.into_inner()))
+    }
+
+    async fn publish_view_failed(&self, view: &MaterializedView, error: String) {
+        self.event_bus
+            .publish(MigrationEvent::ViewRefreshFailed {
+                run_id: self.exec_ctx.run_id(),
+                view: view.name.clone(),
+                error,
+                timestamp: chrono::Utc::now(),
+            })
+            .await;
+    }
+}
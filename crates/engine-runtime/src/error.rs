@@ -66,6 +66,14 @@ pub enum MigrationError {
     /// Wasm error.
     #[error("Wasm error: {0}")]
     Wasm(#[from] WasmError),
+
+    /// A producer or consumer task failed.
+    #[error(transparent)]
+    Actor(ActorError),
+
+    /// A destination table was altered while its pipeline was writing.
+    #[error("Destination table '{table}' changed during the run: {}", changes.join("; "))]
+    SchemaDrift { table: String, changes: Vec<String> },
 }
 
 /// Common error type for all actors in the engine.
@@ -76,4 +84,18 @@ pub enum ActorError {
 
     #[error("Actor internal error: {0}")]
     Internal(String),
+
+    #[error("Destination table '{table}' changed during the run: {}", changes.join("; "))]
+    SchemaDrift { table: String, changes: Vec<String> },
+}
+
+impl From<ActorError> for MigrationError {
+    fn from(e: ActorError) -> Self {
+        match e {
+            ActorError::SchemaDrift { table, changes } => {
+                MigrationError::SchemaDrift { table, changes }
+            }
+            e => MigrationError::Actor(e),
+        }
+    }
 }
@@ -0,0 +1,209 @@
+//! Uploads of run artifacts to object storage.
+//!
+//! A state block with `artifacts_url` has every execution end by copying the
+//! evidence of what it did - its run record, its journal, its integrity
+//! receipts and the files its failed rows were quarantined to - under that
+//! prefix, so runs on ephemeral CI runners keep them. Like webhooks, uploads
+//! are best effort: failures are logged and never fail the migration.
+
+use engine_core::{
+    plan::execution::ExecutionPlan,
+    state::{StateBackend, models::WalEntry},
+};
+use engine_state::models::RunRecord;
+use object_store::{ObjectStore, PutPayload, path::Path};
+use serde::Serialize;
+use std::collections::HashSet;
+use tracing::{info, warn};
+use url::Url;
+
+/// Placeholders in `artifacts_url` replaced with the id of the run record,
+/// so every execution gets its own prefix
+const RUN_ID_PLACEHOLDERS: [&str; 2] = ["${run_id}", "${run.id}"];
+
+/// One file uploaded under the artifacts prefix
+#[derive(Debug, Clone, PartialEq)]
+struct Artifact {
+    /// Path relative to the prefix, `/`-separated
+    name: String,
+    body: Vec<u8>,
+}
+
+/// Uploads the artifacts of the execution `record` describes to `url`.
+pub async fn upload(url: &str, plan: &ExecutionPlan, state: &dyn StateBackend, record: &RunRecord) {
+    let prefix = artifacts_prefix(url, &record.id);
+    let (store, root) = match open_store(&prefix) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!(url = %prefix, error = %e, "failed to open artifacts store");
+            return;
+        }
+    };
+
+    let artifacts = collect(plan, state, record).await;
+    let uploaded = put_all(store.as_ref(), &root, &artifacts).await;
+    info!(
+        url = %prefix,
+        uploaded,
+        total = artifacts.len(),
+        "run artifacts uploaded"
+    );
+}
+
+/// `url` with its run id placeholders filled in, ending in `/`
+fn artifacts_prefix(url: &str, run_id: &str) -> String {
+    let mut prefix = RUN_ID_PLACEHOLDERS
+        .iter()
+        .fold(url.to_string(), |url, placeholder| {
+            url.replace(placeholder, run_id)
+        });
+    if !prefix.ends_with('/') {
+        prefix.push('/');
+    }
+    prefix
+}
+
+/// The store `prefix` points into and the path of the prefix inside it.
+/// Credentials come from the environment (`AWS_*`, `GOOGLE_*`), as for the
+/// providers' own tools.
+fn open_store(prefix: &str) -> Result<(Box<dyn ObjectStore>, Path), String> {
+    let url = Url::parse(prefix).map_err(|e| e.to_string())?;
+    let options = std::env::vars().map(|(key, value)| (key.to_ascii_lowercase(), value));
+    object_store::parse_url_opts(&url, options).map_err(|e| e.to_string())
+}
+
+/// The run record, the journal since the run last started, the run's
+/// receipts and its quarantine files. Any that cannot be read are logged
+/// and left out.
+async fn collect(
+    plan: &ExecutionPlan,
+    state: &dyn StateBackend,
+    record: &RunRecord,
+) -> Vec<Artifact> {
+    let mut artifacts = Vec::new();
+    push_json(&mut artifacts, "run.json", record);
+
+    match state.iter_wal(&record.run_id).await {
+        Ok(wal) => push_json(
+            &mut artifacts,
+            "journal.json",
+            WalEntry::since_run_start(&wal),
+        ),
+        Err(e) => warn!(run_id = %record.run_id, error = %e, "failed to read run journal"),
+    }
+
+    match state.list_receipts().await {
+        Ok(receipts) => {
+            let receipts: Vec<_> = receipts
+                .into_iter()
+                .filter(|r| r.run_id == record.run_id)
+                .collect();
+            if !receipts.is_empty() {
+                push_json(&mut artifacts, "receipts.json", &receipts);
+            }
+        }
+        Err(e) => warn!(run_id = %record.run_id, error = %e, "failed to read integrity receipts"),
+    }
+
+    let mut seen = HashSet::new();
+    let paths = plan.pipelines.iter().filter_map(|p| {
+        p.error_handling
+            .as_ref()?
+            .failed_rows
+            .as_ref()?
+            .quarantine_path()
+    });
+    for path in paths.filter(|path| seen.insert(*path)) {
+        let file_name = std::path::Path::new(path).file_name().map_or_else(
+            || path.to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        match tokio::fs::read(path).await {
+            Ok(body) => artifacts.push(Artifact {
+                name: format!("failed_rows/{file_name}"),
+                body,
+            }),
+            // No row failed, so the file was never created
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!(path = %path, error = %e, "failed to read quarantine file"),
+        }
+    }
+
+    artifacts
+}
+
+fn push_json<T: Serialize + ?Sized>(artifacts: &mut Vec<Artifact>, name: &str, value: &T) {
+    match serde_json::to_vec_pretty(value) {
+        Ok(body) => artifacts.push(Artifact {
+            name: name.to_string(),
+            body,
+        }),
+        Err(e) => warn!(artifact = %name, error = %e, "failed to serialize run artifact"),
+    }
+}
+
+/// Puts each artifact under `root`, returning how many were uploaded
+async fn put_all(store: &dyn ObjectStore, root: &Path, artifacts: &[Artifact]) -> usize {
+    let mut uploaded = 0;
+    for artifact in artifacts {
+        let path = artifact
+            .name
+            .split('/')
+            .fold(root.clone(), |path, part| path.child(part));
+        match store
+            .put(&path, PutPayload::from(artifact.body.clone()))
+            .await
+        {
+            Ok(_) => uploaded += 1,
+            Err(e) => warn!(artifact = %artifact.name, error = %e, "failed to upload run artifact"),
+        }
+    }
+    uploaded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[test]
+    fn test_artifacts_prefix_fills_in_run_id() {
+        assert_eq!(
+            artifacts_prefix("s3://bucket/migrations/${run_id}/", "20260101-abc"),
+            "s3://bucket/migrations/20260101-abc/"
+        );
+        assert_eq!(
+            artifacts_prefix("gs://bucket/runs/${run.id}", "20260101-abc"),
+            "gs://bucket/runs/20260101-abc/"
+        );
+        assert_eq!(artifacts_prefix("s3://bucket", "x"), "s3://bucket/");
+    }
+
+    #[tokio::test]
+    async fn test_put_all_writes_under_prefix() {
+        let store = InMemory::new();
+        let root = Path::from("migrations/20260101-abc");
+        let artifacts = vec![
+            Artifact {
+                name: "run.json".to_string(),
+                body: b"{}".to_vec(),
+            },
+            Artifact {
+                name: "failed_rows/orders.ndjson".to_string(),
+                body: b"{\"id\":1}\n".to_vec(),
+            },
+        ];
+
+        assert_eq!(put_all(&store, &root, &artifacts).await, 2);
+        let body = store
+            .get(&Path::from(
+                "migrations/20260101-abc/failed_rows/orders.ndjson",
+            ))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"{\"id\":1}\n");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    dag::{builder::DagBuilder, executor::DagExecutor},
+    dag::{Dag, builder::DagBuilder, executor::DagExecutor},
     error::MigrationError,
 };
 use engine_core::{context::env::EnvContext, plan::execution::ExecutionPlan};
@@ -14,7 +14,47 @@ pub async fn run(
     shutdown: ShutdownSignal,
     env: Arc<EnvContext>,
 ) -> Result<(), MigrationError> {
-    // Build DAG from the execution plan
+    let dag = build_dag(&plan)?;
+
+    DagExecutor::new(plan, flags, shutdown, env)
+        .await?
+        .execute(dag)
+        .await
+}
+
+/// Runs the plan as the coordinator of a distributed run: publishes its
+/// pipelines to the shared work queue and waits for workers to finish them.
+pub async fn coordinate(
+    plan: ExecutionPlan,
+    flags: ExecutionFlags,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+) -> Result<(), MigrationError> {
+    // Rejects dependency cycles before any work is published
+    build_dag(&plan)?;
+
+    DagExecutor::new(plan, flags, shutdown, env)
+        .await?
+        .coordinate()
+        .await
+}
+
+/// Runs the plan as a worker of a distributed run, claiming pipelines
+/// published by its coordinator until none are left.
+pub async fn work(
+    plan: ExecutionPlan,
+    flags: ExecutionFlags,
+    shutdown: ShutdownSignal,
+    env: Arc<EnvContext>,
+    worker_id: &str,
+) -> Result<(), MigrationError> {
+    DagExecutor::new(plan, flags, shutdown, env)
+        .await?
+        .work(worker_id)
+        .await
+}
+
+fn build_dag(plan: &ExecutionPlan) -> Result<Dag, MigrationError> {
     let mut builder = DagBuilder::new();
 
     for pipeline in &plan.pipelines {
@@ -34,8 +74,5 @@ pub async fn run(
         debug!(level = level_idx + 1, pipelines = ?level, "execution level");
     }
 
-    DagExecutor::new(plan, flags, shutdown, env)
-        .await?
-        .execute(dag)
-        .await
+    Ok(dag)
 }
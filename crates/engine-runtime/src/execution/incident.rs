@@ -0,0 +1,273 @@
+//! Incidents opened in PagerDuty or Opsgenie.
+//!
+//! A `notify` block with a `pagerduty` or `opsgenie` key pages the on-call
+//! when a pipeline fails or stalls, instead of leaving an overnight failure
+//! to be found in the morning. Each pipeline event opens one incident, keyed
+//! by run, pipeline and event so a retried delivery does not page twice.
+
+use crate::execution::notify::{Notifier, WebhookPayload};
+use async_trait::async_trait;
+use model::execution::notify_config::{IncidentConfig, IncidentService, NotifyEvent};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+/// Name incidents are reported as coming from
+const SOURCE: &str = "stratum";
+
+/// Opsgenie rejects alert messages longer than this
+const OPSGENIE_MESSAGE_LIMIT: usize = 130;
+
+/// Notifier opening incidents for `config`
+pub fn notifier(client: reqwest::Client, config: IncidentConfig) -> Arc<dyn Notifier> {
+    match config.service {
+        IncidentService::PagerDuty => Arc::new(PagerDuty { client, config }),
+        IncidentService::Opsgenie => Arc::new(Opsgenie { client, config }),
+    }
+}
+
+/// Triggers PagerDuty incidents through the Events API v2
+pub struct PagerDuty {
+    client: reqwest::Client,
+    config: IncidentConfig,
+}
+
+#[async_trait]
+impl Notifier for PagerDuty {
+    fn wants(&self, event: NotifyEvent) -> bool {
+        self.config.wants(event)
+    }
+
+    fn validation_threshold(&self) -> Option<u64> {
+        self.config.validation_threshold
+    }
+
+    fn stall_after(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.config.stall_after))
+    }
+
+    fn target(&self) -> &str {
+        IncidentService::PagerDuty.as_str()
+    }
+
+    async fn send(&self, kind: NotifyEvent, payload: &WebhookPayload) -> Result<(), String> {
+        post(
+            self.client.post(&self.config.url),
+            self.config.timeout,
+            &pagerduty_event(&self.config.key, kind, payload),
+        )
+        .await
+    }
+}
+
+/// Creates Opsgenie alerts through the Alert API
+pub struct Opsgenie {
+    client: reqwest::Client,
+    config: IncidentConfig,
+}
+
+#[async_trait]
+impl Notifier for Opsgenie {
+    fn wants(&self, event: NotifyEvent) -> bool {
+        self.config.wants(event)
+    }
+
+    fn validation_threshold(&self) -> Option<u64> {
+        self.config.validation_threshold
+    }
+
+    fn stall_after(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.config.stall_after))
+    }
+
+    fn target(&self) -> &str {
+        IncidentService::Opsgenie.as_str()
+    }
+
+    async fn send(&self, kind: NotifyEvent, payload: &WebhookPayload) -> Result<(), String> {
+        let request = self
+            .client
+            .post(&self.config.url)
+            .header("Authorization", format!("GenieKey {}", self.config.key));
+        post(request, self.config.timeout, &opsgenie_alert(kind, payload)).await
+    }
+}
+
+async fn post(
+    request: reqwest::RequestBuilder,
+    timeout: u64,
+    body: &serde_json::Value,
+) -> Result<(), String> {
+    request
+        .timeout(Duration::from_secs(timeout))
+        .json(body)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map(|_| ())
+        .map_err(|e| e.without_url().to_string())
+}
+
+fn pagerduty_event(
+    routing_key: &str,
+    kind: NotifyEvent,
+    payload: &WebhookPayload,
+) -> serde_json::Value {
+    let severity = match kind {
+        NotifyEvent::Failed => "critical",
+        NotifyEvent::Stalled => "error",
+        _ => "warning",
+    };
+    serde_json::json!({
+        "routing_key": routing_key,
+        "event_action": "trigger",
+        "dedup_key": dedup_key(payload),
+        "payload": {
+            "summary": summary(kind, payload),
+            "source": SOURCE,
+            "severity": severity,
+            "timestamp": payload.timestamp.to_rfc3339(),
+            "component": payload.pipeline,
+            "group": payload.run_id,
+            "class": payload.event,
+            "custom_details": payload.details,
+        },
+    })
+}
+
+fn opsgenie_alert(kind: NotifyEvent, payload: &WebhookPayload) -> serde_json::Value {
+    let priority = match kind {
+        NotifyEvent::Failed => "P1",
+        NotifyEvent::Stalled => "P2",
+        _ => "P3",
+    };
+    let summary = summary(kind, payload);
+    let message: String = summary.chars().take(OPSGENIE_MESSAGE_LIMIT).collect();
+
+    // Opsgenie only takes text details
+    let mut details = BTreeMap::from([
+        ("run_id".to_string(), payload.run_id.clone()),
+        ("pipeline".to_string(), payload.pipeline.clone()),
+    ]);
+    if let Some(fields) = payload.details.as_object() {
+        for (key, value) in fields {
+            let text = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            details.insert(key.clone(), text);
+        }
+    }
+
+    serde_json::json!({
+        "message": message,
+        "alias": dedup_key(payload),
+        "description": summary,
+        "source": SOURCE,
+        "entity": payload.pipeline,
+        "priority": priority,
+        "tags": [SOURCE, kind.as_str()],
+        "details": details,
+    })
+}
+
+/// One incident per run, pipeline and event
+fn dedup_key(payload: &WebhookPayload) -> String {
+    format!(
+        "{SOURCE}/{}/{}/{}",
+        payload.run_id, payload.pipeline, payload.event
+    )
+}
+
+/// One-line description of the event, e.g. the error a pipeline failed with
+fn summary(kind: NotifyEvent, payload: &WebhookPayload) -> String {
+    let pipeline = &payload.pipeline;
+    let details = &payload.details;
+    match kind {
+        NotifyEvent::Failed => match details["error"].as_str() {
+            Some(error) => format!("Stratum pipeline '{pipeline}' failed: {error}"),
+            None => format!("Stratum pipeline '{pipeline}' failed"),
+        },
+        NotifyEvent::Stalled => {
+            let idle = details["idle_secs"].as_u64().unwrap_or_default();
+            format!(
+                "Stratum pipeline '{pipeline}' stalled: no progress for {}",
+                format_idle(idle)
+            )
+        }
+        NotifyEvent::ValidationThreshold => format!(
+            "Stratum pipeline '{pipeline}' rejected {} rows",
+            details["rows_rejected"]
+        ),
+        other => format!("Stratum pipeline '{pipeline}' {other}"),
+    }
+}
+
+fn format_idle(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h{}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn payload(event: &str, details: serde_json::Value) -> WebhookPayload {
+        WebhookPayload {
+            event: event.to_string(),
+            run_id: "run-1".to_string(),
+            pipeline: "orders".to_string(),
+            timestamp: Utc::now(),
+            details,
+        }
+    }
+
+    #[test]
+    fn test_pagerduty_event_triggers_with_dedup_key() {
+        let failed = payload(
+            "pipeline.failed",
+            serde_json::json!({ "error": "connection reset", "rows_processed": 10 }),
+        );
+        let event = pagerduty_event("R0UT1NGKEY", NotifyEvent::Failed, &failed);
+
+        assert_eq!(event["routing_key"], "R0UT1NGKEY");
+        assert_eq!(event["event_action"], "trigger");
+        assert_eq!(event["dedup_key"], "stratum/run-1/orders/pipeline.failed");
+        assert_eq!(
+            event["payload"]["summary"],
+            "Stratum pipeline 'orders' failed: connection reset"
+        );
+        assert_eq!(event["payload"]["severity"], "critical");
+        assert_eq!(event["payload"]["custom_details"]["rows_processed"], 10);
+    }
+
+    #[test]
+    fn test_opsgenie_alert_has_text_details_and_short_message() {
+        let stalled = payload(
+            "pipeline.stalled",
+            serde_json::json!({ "idle_secs": 5400, "stall_after_secs": 1800 }),
+        );
+        let alert = opsgenie_alert(NotifyEvent::Stalled, &stalled);
+
+        assert_eq!(
+            alert["message"],
+            "Stratum pipeline 'orders' stalled: no progress for 1h30m"
+        );
+        assert_eq!(alert["priority"], "P2");
+        assert_eq!(alert["details"]["idle_secs"], "5400");
+        assert_eq!(alert["details"]["run_id"], "run-1");
+
+        let failed = payload(
+            "pipeline.failed",
+            serde_json::json!({ "error": "x".repeat(500) }),
+        );
+        let alert = opsgenie_alert(NotifyEvent::Failed, &failed);
+        assert_eq!(
+            alert["message"].as_str().unwrap().len(),
+            OPSGENIE_MESSAGE_LIMIT
+        );
+        assert_eq!(alert["description"].as_str().unwrap().len(), 534);
+    }
+}
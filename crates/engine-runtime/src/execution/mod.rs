@@ -1,2 +1,6 @@
+pub mod artifacts;
+pub mod email;
 pub mod executor;
+pub mod incident;
+pub mod notify;
 pub mod orchestrator;
@@ -0,0 +1,705 @@
+//! Notifications for pipeline lifecycle events.
+//!
+//! Each `notify` block in the plan is a [`Notifier`]: a webhook, or a
+//! PagerDuty or Opsgenie service that incidents are opened for. A subscriber
+//! on the event bus turns pipeline start, completion and failure events - the
+//! point where a pipeline's rejected rows reach the block's
+//! `validation_threshold`, and a running pipeline going quiet for its
+//! `stall_after` - into notifications, as are approval gates the run pauses
+//! at. Delivery is best effort: failed requests are logged and never fail the
+//! migration.
+
+use crate::execution::incident;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use engine_core::{
+    event_bus::bus::{EventBus, Subscription},
+    plan::execution::ExecutionPlan,
+    utils::make_item_id,
+};
+use model::{
+    core::redact::redact_credentials,
+    events::migration::MigrationEvent,
+    execution::notify_config::{NotifyConfig, NotifyEvent},
+};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, warn};
+
+/// Progress events are frequent; a slow webhook must not cause lifecycle
+/// events behind them to be dropped by the bus.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// How long a finished run waits for queued notifications to be delivered
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often running pipelines are checked for stalls
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Body of a webhook request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebhookPayload {
+    /// `pipeline.started`, `pipeline.completed`, `pipeline.failed`,
+    /// `pipeline.validation_threshold`, `pipeline.stalled` or
+    /// `pipeline.approval_required`; `runbook.approval_required` for runbook
+    /// steps
+    pub event: String,
+    pub run_id: String,
+    /// Pipeline, or runbook step, the event is about
+    pub pipeline: String,
+    pub timestamp: DateTime<Utc>,
+    /// Event-specific fields, e.g. row counts or the error message
+    pub details: serde_json::Value,
+}
+
+/// Somewhere pipeline events are delivered to. The subscriber decides which
+/// events each notifier receives from what it reports here; implementations
+/// only deliver them.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Whether `event` is delivered to this notifier
+    fn wants(&self, event: NotifyEvent) -> bool;
+
+    /// Rejected rows in one pipeline at which a `validation_threshold` event
+    /// is delivered
+    fn validation_threshold(&self) -> Option<u64> {
+        None
+    }
+
+    /// How long a running pipeline may go without events before a
+    /// `stalled` event is delivered
+    fn stall_after(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Where notifications go, for logs. Must not include credentials.
+    fn target(&self) -> &str;
+
+    /// Delivers one event. Errors are logged by the caller.
+    async fn send(&self, kind: NotifyEvent, payload: &WebhookPayload) -> Result<(), String>;
+}
+
+/// Notifier POSTing each event as JSON to the url of a `notify` block
+pub struct Webhook {
+    client: reqwest::Client,
+    config: NotifyConfig,
+}
+
+impl Webhook {
+    pub fn new(client: reqwest::Client, config: NotifyConfig) -> Self {
+        Self { client, config }
+    }
+}
+
+#[async_trait]
+impl Notifier for Webhook {
+    fn wants(&self, event: NotifyEvent) -> bool {
+        self.config.wants(event)
+    }
+
+    fn validation_threshold(&self) -> Option<u64> {
+        self.config.validation_threshold
+    }
+
+    fn stall_after(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.config.stall_after))
+    }
+
+    // The url often embeds a token, so only the host is logged
+    fn target(&self) -> &str {
+        webhook_host(&self.config.url)
+    }
+
+    async fn send(&self, _kind: NotifyEvent, payload: &WebhookPayload) -> Result<(), String> {
+        self.client
+            .post(&self.config.url)
+            .timeout(Duration::from_secs(self.config.timeout))
+            .json(payload)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.without_url().to_string())
+    }
+}
+
+/// Notifiers for the plan's `notify` blocks that receive pipeline events
+pub fn notifiers(plan: &ExecutionPlan) -> Vec<Arc<dyn Notifier>> {
+    let client = reqwest::Client::new();
+    let webhooks = plan
+        .notify
+        .iter()
+        .map(|config| Arc::new(Webhook::new(client.clone(), config.clone())) as Arc<dyn Notifier>);
+    let incidents = plan
+        .incidents
+        .iter()
+        .map(|config| incident::notifier(client.clone(), config.clone()));
+    webhooks.chain(incidents).collect()
+}
+
+/// Running notification subscriber; [`NotifySubscriber::finish`] flushes it.
+pub struct NotifySubscriber {
+    subscription: Subscription,
+    task: JoinHandle<()>,
+}
+
+impl NotifySubscriber {
+    /// Subscribes to pipeline events when the plan has `notify` blocks
+    /// with webhooks or incident services.
+    pub async fn start(plan: &ExecutionPlan, event_bus: &EventBus) -> Option<Self> {
+        let notifiers = notifiers(plan);
+        if notifiers.is_empty() {
+            return None;
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Arc<MigrationEvent>>(CHANNEL_CAPACITY);
+        let subscription = event_bus.subscribe::<MigrationEvent>(tx).await;
+
+        let count = notifiers.len();
+        let mut router = NotifyRouter::new(plan, notifiers);
+        let task = tokio::spawn(async move {
+            let mut stall_check = tokio::time::interval(STALL_CHECK_INTERVAL);
+            loop {
+                let deliveries = tokio::select! {
+                    event = rx.recv() => match event {
+                        Some(event) => router.route(&event, Instant::now()),
+                        None => break,
+                    },
+                    _ = stall_check.tick() => router.check_stalls(Instant::now()),
+                };
+                for delivery in deliveries {
+                    let notifier = router.notifiers[delivery.notifier].as_ref();
+                    deliver(notifier, delivery.kind, &delivery.payload).await;
+                }
+            }
+        });
+
+        debug!(notifiers = count, "notifications configured");
+        Some(Self { subscription, task })
+    }
+
+    /// Stops listening and waits for notifications already queued, so the
+    /// final pipeline events are delivered before the process exits.
+    pub async fn finish(self, event_bus: &EventBus) {
+        // Dropping the bus's sender closes the channel once it is drained
+        event_bus.unsubscribe(self.subscription).await;
+        if tokio::time::timeout(DRAIN_TIMEOUT, self.task)
+            .await
+            .is_err()
+        {
+            warn!(
+                timeout_secs = DRAIN_TIMEOUT.as_secs(),
+                "gave up waiting for notifications to be sent"
+            );
+        }
+    }
+}
+
+/// Sends `payload` to each of `webhooks` subscribed to `kind`, outside a run's
+/// event bus (e.g. for runbook steps).
+pub async fn send_event(webhooks: &[NotifyConfig], kind: NotifyEvent, payload: &WebhookPayload) {
+    let client = reqwest::Client::new();
+    for webhook in webhooks.iter().filter(|w| w.wants(kind)) {
+        deliver(
+            &Webhook::new(client.clone(), webhook.clone()),
+            kind,
+            payload,
+        )
+        .await;
+    }
+}
+
+async fn deliver(notifier: &dyn Notifier, kind: NotifyEvent, payload: &WebhookPayload) {
+    match notifier.send(kind, payload).await {
+        Ok(()) => debug!(
+            event = %payload.event,
+            pipeline = %payload.pipeline,
+            target = %notifier.target(),
+            "notification sent"
+        ),
+        Err(e) => warn!(
+            event = %payload.event,
+            pipeline = %payload.pipeline,
+            target = %notifier.target(),
+            error = %e,
+            "notification failed"
+        ),
+    }
+}
+
+fn webhook_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?', '#']).next().unwrap_or(rest)
+}
+
+/// An event to deliver to one notifier
+#[derive(Debug, Clone, PartialEq)]
+struct Delivery {
+    /// Index into the router's notifiers
+    notifier: usize,
+    kind: NotifyEvent,
+    payload: WebhookPayload,
+}
+
+/// Decides which notifiers receive which events.
+struct NotifyRouter {
+    notifiers: Vec<Arc<dyn Notifier>>,
+    /// item_id -> pipeline name
+    pipelines: HashMap<String, String>,
+    /// (notifier, item_id) pairs whose threshold notification was sent
+    breached: HashSet<(usize, String)>,
+    /// item_id -> run_id and last event of each running pipeline
+    running: HashMap<String, (String, Instant)>,
+    /// (notifier, item_id) pairs whose stall notification was sent since
+    /// the pipeline's last event
+    stalled: HashSet<(usize, String)>,
+}
+
+impl NotifyRouter {
+    fn new(plan: &ExecutionPlan, notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        let pipelines = plan
+            .pipelines
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| {
+                (
+                    make_item_id(plan.hash(), &p.destination.table, idx),
+                    p.name.clone(),
+                )
+            })
+            .collect();
+
+        Self {
+            notifiers,
+            pipelines,
+            breached: HashSet::new(),
+            running: HashMap::new(),
+            stalled: HashSet::new(),
+        }
+    }
+
+    /// Deliveries for `event`, which arrived at `now`.
+    fn route(&mut self, event: &MigrationEvent, now: Instant) -> Vec<Delivery> {
+        self.track(event, now);
+        let mut deliveries = self.lifecycle(event);
+        deliveries.extend(self.check_threshold(event));
+        deliveries
+    }
+
+    /// Records that the pipeline `event` is about was active at `now`.
+    fn track(&mut self, event: &MigrationEvent, now: Instant) {
+        let (Some(run_id), Some(item_id)) = (event.run_id(), event.item_id()) else {
+            return;
+        };
+        match event {
+            MigrationEvent::Started { .. } | MigrationEvent::Resumed { .. } => {
+                self.running
+                    .insert(item_id.to_string(), (run_id.to_string(), now));
+            }
+            // Finished, waiting on someone, or streaming changes, where a
+            // quiet pipeline is expected
+            MigrationEvent::Completed { .. }
+            | MigrationEvent::Failed { .. }
+            | MigrationEvent::Cancelled { .. }
+            | MigrationEvent::Paused { .. }
+            | MigrationEvent::ApprovalRequired { .. }
+            | MigrationEvent::CdcStarted { .. } => {
+                self.running.remove(item_id);
+            }
+            _ => match self.running.get_mut(item_id) {
+                Some((_, last_event)) => *last_event = now,
+                None => return,
+            },
+        }
+        self.stalled.retain(|(_, stalled)| stalled != item_id);
+    }
+
+    /// Stall deliveries for running pipelines that, at `now`, have gone
+    /// without events for a notifier's `stall_after`. Each stall is
+    /// reported once per notifier until the pipeline shows signs of life.
+    fn check_stalls(&mut self, now: Instant) -> Vec<Delivery> {
+        let mut deliveries = Vec::new();
+        for (idx, notifier) in self.notifiers.iter().enumerate() {
+            let Some(stall_after) = notifier.stall_after() else {
+                continue;
+            };
+            if !notifier.wants(NotifyEvent::Stalled) {
+                continue;
+            }
+            for (item_id, (run_id, last_event)) in &self.running {
+                let idle = now.saturating_duration_since(*last_event);
+                if idle < stall_after || !self.stalled.insert((idx, item_id.clone())) {
+                    continue;
+                }
+
+                let details = serde_json::json!({
+                    "idle_secs": idle.as_secs(),
+                    "stall_after_secs": stall_after.as_secs(),
+                });
+                deliveries.push(Delivery {
+                    notifier: idx,
+                    kind: NotifyEvent::Stalled,
+                    payload: self.payload(
+                        NotifyEvent::Stalled,
+                        run_id,
+                        item_id,
+                        Utc::now(),
+                        details,
+                    ),
+                });
+            }
+        }
+        deliveries
+    }
+
+    fn lifecycle(&self, event: &MigrationEvent) -> Vec<Delivery> {
+        let (kind, run_id, item_id, timestamp, details) = match event {
+            MigrationEvent::Started {
+                run_id,
+                item_id,
+                source,
+                destination,
+                timestamp,
+            } => (
+                NotifyEvent::Started,
+                run_id,
+                item_id,
+                timestamp,
+                serde_json::json!({ "source": source, "destination": destination }),
+            ),
+            MigrationEvent::Completed {
+                run_id,
+                item_id,
+                rows_processed,
+                rows_skipped,
+                rows_failed,
+                duration_ms,
+                timestamp,
+            } => (
+                NotifyEvent::Completed,
+                run_id,
+                item_id,
+                timestamp,
+                serde_json::json!({
+                    "rows_processed": rows_processed,
+                    "rows_skipped": rows_skipped,
+                    "rows_failed": rows_failed,
+                    "duration_ms": duration_ms,
+                }),
+            ),
+            MigrationEvent::Failed {
+                run_id,
+                item_id,
+                error,
+                rows_processed,
+                timestamp,
+                ..
+            } => (
+                NotifyEvent::Failed,
+                run_id,
+                item_id,
+                timestamp,
+                // Every notifier delivers this payload, so it is masked once
+                serde_json::json!({
+                    "error": redact_credentials(error),
+                    "rows_processed": rows_processed,
+                }),
+            ),
+            MigrationEvent::ApprovalRequired {
+                run_id,
+                item_id,
+                gate,
+                message,
+                timestamp,
+            } => (
+                NotifyEvent::ApprovalRequired,
+                run_id,
+                item_id,
+                timestamp,
+                serde_json::json!({
+                    "gate": gate,
+                    "message": message,
+                    "approve": format!("stratum approve {run_id} {gate}"),
+                }),
+            ),
+            _ => return Vec::new(),
+        };
+
+        self.notifiers
+            .iter()
+            .enumerate()
+            .filter(|(_, notifier)| notifier.wants(kind))
+            .map(|(idx, _)| Delivery {
+                notifier: idx,
+                kind,
+                payload: self.payload(kind, run_id, item_id, *timestamp, details.clone()),
+            })
+            .collect()
+    }
+
+    /// Threshold deliveries for notifiers whose `validation_threshold` the
+    /// event's rejected row count reached for the first time.
+    fn check_threshold(&mut self, event: &MigrationEvent) -> Vec<Delivery> {
+        let (run_id, item_id, rows_skipped, rows_failed, timestamp) = match event {
+            MigrationEvent::Progress {
+                run_id,
+                item_id,
+                rows_skipped,
+                rows_failed,
+                timestamp,
+                ..
+            }
+            | MigrationEvent::Completed {
+                run_id,
+                item_id,
+                rows_skipped,
+                rows_failed,
+                timestamp,
+                ..
+            } => (run_id, item_id, *rows_skipped, *rows_failed, *timestamp),
+            _ => return Vec::new(),
+        };
+        let rejected = rows_skipped + rows_failed;
+
+        let mut deliveries = Vec::new();
+        for (idx, notifier) in self.notifiers.iter().enumerate() {
+            let Some(threshold) = notifier.validation_threshold() else {
+                continue;
+            };
+            if rejected < threshold
+                || !notifier.wants(NotifyEvent::ValidationThreshold)
+                || !self.breached.insert((idx, item_id.clone()))
+            {
+                continue;
+            }
+
+            let details = serde_json::json!({
+                "threshold": threshold,
+                "rows_rejected": rejected,
+                "rows_skipped": rows_skipped,
+                "rows_failed": rows_failed,
+            });
+            deliveries.push(Delivery {
+                notifier: idx,
+                kind: NotifyEvent::ValidationThreshold,
+                payload: self.payload(
+                    NotifyEvent::ValidationThreshold,
+                    run_id,
+                    item_id,
+                    timestamp,
+                    details,
+                ),
+            });
+        }
+        deliveries
+    }
+
+    fn payload(
+        &self,
+        kind: NotifyEvent,
+        run_id: &str,
+        item_id: &str,
+        timestamp: DateTime<Utc>,
+        details: serde_json::Value,
+    ) -> WebhookPayload {
+        WebhookPayload {
+            event: format!("pipeline.{kind}"),
+            run_id: run_id.to_string(),
+            pipeline: self
+                .pipelines
+                .get(item_id)
+                .cloned()
+                .unwrap_or_else(|| item_id.to_string()),
+            timestamp,
+            details,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_core::context::env::EnvContext;
+    use smql_syntax::builder::parse;
+
+    fn router(notify: &str) -> (NotifyRouter, String) {
+        let smql = format!(
+            r#"
+            {notify}
+            connection "db" {{ driver = "postgres" url = "postgres://localhost/db" }}
+            pipeline "orders" {{
+                from {{ connection = connection.db table = "orders" }}
+                to   {{ connection = connection.db table = "orders_copy" }}
+            }}
+        "#
+        );
+        let doc = parse(&smql).unwrap();
+        let plan = ExecutionPlan::build(&doc, Arc::new(EnvContext::empty())).unwrap();
+        let item_id = make_item_id(plan.hash(), "orders_copy", 0);
+        (NotifyRouter::new(&plan, notifiers(&plan)), item_id)
+    }
+
+    fn progress(item_id: &str, rows_skipped: u64, rows_failed: u64) -> MigrationEvent {
+        MigrationEvent::Progress {
+            run_id: "run-1".to_string(),
+            item_id: item_id.to_string(),
+            rows_processed: 1000,
+            rows_skipped,
+            rows_failed,
+            bytes_transferred: 0,
+            rows_per_second: 0.0,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_routes_lifecycle_events_to_subscribed_webhooks() {
+        let (mut router, item_id) = router(
+            r#"
+            notify { url = "https://a.example.com" events = ["failed"] }
+            notify { url = "https://b.example.com" }
+        "#,
+        );
+
+        let started = MigrationEvent::Started {
+            run_id: "run-1".to_string(),
+            item_id: item_id.clone(),
+            source: "db".to_string(),
+            destination: "db".to_string(),
+            timestamp: Utc::now(),
+        };
+        let routed = router.route(&started, Instant::now());
+        assert_eq!(routed.len(), 1);
+        assert_eq!(routed[0].notifier, 1);
+        assert_eq!(routed[0].payload.event, "pipeline.started");
+        assert_eq!(routed[0].payload.pipeline, "orders");
+
+        let failed = MigrationEvent::Failed {
+            run_id: "run-1".to_string(),
+            item_id,
+            error: "connection reset".to_string(),
+            error_code: None,
+            rows_processed: 10,
+            timestamp: Utc::now(),
+        };
+        let routed = router.route(&failed, Instant::now());
+        assert_eq!(
+            routed.iter().map(|d| d.notifier).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(routed[0].payload.event, "pipeline.failed");
+        assert_eq!(routed[0].payload.details["error"], "connection reset");
+    }
+
+    #[test]
+    fn test_validation_threshold_fires_once_per_pipeline() {
+        let (mut router, item_id) =
+            router(r#"notify { url = "https://a.example.com" validation_threshold = 100 }"#);
+
+        let now = Instant::now();
+        assert!(router.route(&progress(&item_id, 40, 59), now).is_empty());
+
+        let routed = router.route(&progress(&item_id, 60, 40), now);
+        assert_eq!(routed.len(), 1);
+        let payload = &routed[0].payload;
+        assert_eq!(payload.event, "pipeline.validation_threshold");
+        assert_eq!(payload.details["rows_rejected"], 100);
+        assert_eq!(payload.details["threshold"], 100);
+
+        assert!(router.route(&progress(&item_id, 500, 0), now).is_empty());
+    }
+
+    #[test]
+    fn test_approval_required_tells_how_to_approve() {
+        let (mut router, item_id) = router(
+            r#"
+            notify { url = "https://a.example.com" events = ["approval_required"] }
+            notify { url = "https://b.example.com" events = ["failed"] }
+        "#,
+        );
+
+        let waiting = MigrationEvent::ApprovalRequired {
+            run_id: "run-1".to_string(),
+            item_id,
+            gate: "orders".to_string(),
+            message: Some("Confirm cutover".to_string()),
+            timestamp: Utc::now(),
+        };
+        let routed = router.route(&waiting, Instant::now());
+        assert_eq!(routed.len(), 1);
+        assert_eq!(routed[0].notifier, 0);
+        let payload = &routed[0].payload;
+        assert_eq!(payload.event, "pipeline.approval_required");
+        assert_eq!(payload.details["message"], "Confirm cutover");
+        assert_eq!(payload.details["approve"], "stratum approve run-1 orders");
+    }
+
+    #[test]
+    fn test_stall_reported_once_until_pipeline_is_active_again() {
+        let (mut router, item_id) = router(
+            r#"
+            notify { pagerduty = "key" stall_after = "10m" }
+            notify { url = "https://a.example.com" }
+        "#,
+        );
+        let minutes = |n: u64| Duration::from_secs(n * 60);
+        let start = Instant::now();
+        let started = MigrationEvent::Started {
+            run_id: "run-1".to_string(),
+            item_id: item_id.clone(),
+            source: "db".to_string(),
+            destination: "db".to_string(),
+            timestamp: Utc::now(),
+        };
+
+        // Webhooks come first; incident services are not told about starts
+        let routed = router.route(&started, start);
+        assert_eq!(
+            routed.iter().map(|d| d.notifier).collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert!(router.check_stalls(start + minutes(9)).is_empty());
+
+        // Only the incident service lists `stalled`
+        let stalled = router.check_stalls(start + minutes(10));
+        assert_eq!(stalled.len(), 1);
+        assert_eq!(stalled[0].notifier, 1);
+        assert_eq!(stalled[0].kind, NotifyEvent::Stalled);
+        assert_eq!(stalled[0].payload.event, "pipeline.stalled");
+        assert_eq!(stalled[0].payload.pipeline, "orders");
+        assert_eq!(stalled[0].payload.details["idle_secs"], 600);
+        assert!(router.check_stalls(start + minutes(30)).is_empty());
+
+        // Progress rearms the check, and a finished pipeline is never stalled
+        router.route(&progress(&item_id, 0, 0), start + minutes(31));
+        assert_eq!(router.check_stalls(start + minutes(41)).len(), 1);
+        let completed = MigrationEvent::Completed {
+            run_id: "run-1".to_string(),
+            item_id,
+            rows_processed: 1000,
+            rows_skipped: 0,
+            rows_failed: 0,
+            duration_ms: 1,
+            timestamp: Utc::now(),
+        };
+        router.route(&completed, start + minutes(42));
+        assert!(router.check_stalls(start + minutes(120)).is_empty());
+    }
+
+    #[test]
+    fn test_webhook_host_hides_path_and_query() {
+        assert_eq!(
+            webhook_host("https://hooks.slack.com/services/T0/B0/secret"),
+            "hooks.slack.com"
+        );
+        assert_eq!(
+            webhook_host("http://localhost:8080?token=x"),
+            "localhost:8080"
+        );
+    }
+}
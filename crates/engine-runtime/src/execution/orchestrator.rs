@@ -5,31 +5,59 @@ use crate::{
 };
 use chrono;
 use connectors::sql::metadata::table::TableMetadata;
-use engine_config::settings::validated::ValidatedSettings;
-use engine_core::{event_bus::bus::EventBus, metrics::Metrics, schema::schema_ops::SchemaOps};
+use engine_config::settings::{FastLoad, validated::ValidatedSettings};
+use engine_core::{
+    dispatch_drivers,
+    drivers::DriverRef,
+    event_bus::bus::EventBus,
+    metrics::Metrics,
+    plan::settings::effective_value,
+    schema::schema_ops::{SchemaOp, SchemaOps},
+    state::models::WalEntry,
+    timing::TimingReport,
+};
 use engine_infra::shutdown::ShutdownSignal;
 use engine_processing::{
     consumer::Consumer,
     context::PipelineContext,
+    io::{format::DataFormat, lob},
+    partition::{KeyRange, PartitionScheduler},
     producer::{Producer, config::ProducerConfig},
+    transform::{
+        keymap::KeymapColumns,
+        validation::{RuleTally, ValidationAction, ValidationTally},
+    },
 };
+use futures::future;
 use model::integrity::{algorithm::HashAlgorithm, config::IntegrityConfig};
 use model::{
+    core::value::Value,
     events::migration::MigrationEvent,
-    execution::{pipeline::Pipeline, references::DataMode},
+    execution::{expr::FN_ROW_NUMBER, pipeline::Pipeline, references::DataMode},
+    pagination::cursor::QualCol,
     records::batch::Batch,
 };
 use std::{
-    collections::HashSet,
-    sync::{Arc, Mutex},
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, atomic::AtomicU64},
     time::Duration,
 };
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 const BATCH_CHANNEL_CAPACITY: usize = 64;
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// How a pipeline run by a distributed worker ended.
+pub enum Execution {
+    /// Loaded in one piece, with the rows processed.
+    Loaded(u64),
+    /// Split into key ranges `(id, start, end)` for other workers to read;
+    /// the steps before the load have run.
+    Split(Vec<(usize, u64, u64)>),
+}
+
 /// Orchestrates the complete pipeline execution lifecycle including hooks.
 /// The orchestrator ensures proper sequencing and error handling across all phases.
 pub struct PipelineOrchestrator {
@@ -41,7 +69,14 @@ pub struct PipelineOrchestrator {
     shutdown: ShutdownSignal,
     event_bus: EventBus,
     done_ops: Arc<Mutex<HashSet<String>>>,
+    /// Held while a pipeline that shares its destination table with others
+    /// changes the table's schema
+    ddl_lock: Arc<tokio::sync::Mutex<()>>,
     cascade_tables: Vec<String>,
+    /// Failures per validate rule of a validate-only pipeline
+    validation_tally: Option<Arc<ValidationTally>>,
+    /// Counter `row_number()` draws from across the pipeline's partitions
+    row_numbers: Arc<AtomicU64>,
 }
 
 impl PipelineOrchestrator {
@@ -55,8 +90,12 @@ impl PipelineOrchestrator {
         shutdown: ShutdownSignal,
         event_bus: EventBus,
         done_ops: Arc<Mutex<HashSet<String>>>,
+        ddl_lock: Arc<tokio::sync::Mutex<()>>,
         cascade_tables: Vec<String>,
     ) -> Self {
+        let validation_tally = pipeline
+            .validate_only
+            .then(|| Arc::new(ValidationTally::new(&pipeline.validations)));
         Self {
             pipeline,
             ctx,
@@ -66,29 +105,137 @@ impl PipelineOrchestrator {
             shutdown,
             event_bus,
             done_ops,
+            ddl_lock,
             cascade_tables,
+            validation_tally,
+            row_numbers: Arc::new(AtomicU64::new(0)),
         }
     }
 
     /// Executes the complete pipeline lifecycle:
-    /// pre-DDL -> before hooks -> data migration -> post-DDL -> after hooks
+    /// pre-DDL -> seed rows -> before hooks -> data migration -> large-value streaming
+    /// -> post-DDL -> sequence sync -> after hooks. Post-load DDL is recorded for the executor to run once every pipeline
+    /// has loaded, as is the post-DDL of a table other pipelines may still be writing.
     /// Returns the number of rows processed.
     pub async fn execute(&self) -> Result<u64, MigrationError> {
-        self.execute_schema_ops("pre-migration", &self.schema_ops.pre)
+        self.before_load().await?;
+        let rows = self.load().await?;
+        self.after_load().await?;
+        Ok(rows)
+    }
+
+    /// Like `execute`, except that a load read in key ranges stops after the
+    /// steps before the load and returns its ranges, which `execute_range`
+    /// reads and `execute_finish` follows up on, on any worker. Validate-only
+    /// pipelines and those calling `row_number()` count across all their
+    /// rows, so they load in one piece.
+    pub async fn execute_or_split(&self) -> Result<Execution, MigrationError> {
+        self.before_load().await?;
+
+        let splits = !self.is_schema_only()
+            && !self.pipeline.validate_only
+            && !self.pipeline.calls(FN_ROW_NUMBER);
+        if splits && let Some((scheduler, _)) = self.partition_scheduler().await? {
+            let ranges: Vec<_> = scheduler
+                .ranges()
+                .iter()
+                .map(|r| (r.id(), r.start(), r.end()))
+                .collect();
+            info!(
+                ranges = ranges.len(),
+                "split load into key ranges for workers to read"
+            );
+            return Ok(Execution::Split(ranges));
+        }
+
+        let rows = self.load().await?;
+        self.after_load().await?;
+        Ok(Execution::Loaded(rows))
+    }
+
+    /// Reads the keys `[start, end)` of a split pipeline as partition `id`,
+    /// resuming from the partition's checkpoint. Returns the rows processed.
+    pub async fn execute_range(
+        &self,
+        id: usize,
+        start: u64,
+        end: u64,
+    ) -> Result<u64, MigrationError> {
+        let Some(pk) = self.ctx.offset_strategy.range_key().cloned() else {
+            return Err(MigrationError::InitializationError(
+                "source cannot be read in key ranges".to_string(),
+            ));
+        };
+        info!(part = id, start, end, "starting key range");
+
+        self.publish_started().await;
+        let start_time = std::time::Instant::now();
+        let metrics = Metrics::new();
+        let dest_metas = self.fetch_destination_metadata().await?;
+        let config = self
+            .with_keymaps(self.build_producer_config(&dest_metas), &dest_metas)
             .await?;
-        self.execute_hooks(HookPhase::Before).await?;
 
-        let rows = if self.is_schema_only() {
+        let range = Arc::new(KeyRange::fixed(id, start, end));
+        let cancel = self.shutdown.cancel.child_token();
+        let read = self.run_range(&range, &pk, &dest_metas, &config, &metrics, cancel);
+        self.await_completion_or_cancel(read, &metrics, start_time)
+            .await?;
+
+        Ok(metrics.snapshot().records_processed)
+    }
+
+    /// The steps after the load of a split pipeline, once every range is read.
+    pub async fn execute_finish(&self) -> Result<(), MigrationError> {
+        self.after_load().await
+    }
+
+    /// Schema changes and seed rows, then the before hooks.
+    async fn before_load(&self) -> Result<(), MigrationError> {
+        let shared = !self.pipeline.concurrent_writers.is_empty();
+        {
+            // Writers of a shared table change its schema one at a time
+            let _ddl = if shared {
+                Some(self.ddl_lock.lock().await)
+            } else {
+                None
+            };
+            self.execute_schema_ops("pre-migration", &self.schema_ops.pre)
+                .await?;
+            self.seed_tables().await?;
+        }
+        self.execute_hooks(HookPhase::Before).await
+    }
+
+    /// Returns the number of rows processed.
+    async fn load(&self) -> Result<u64, MigrationError> {
+        if self.is_schema_only() {
             info!("schema-only mode, skipping data migration");
-            0
-        } else {
-            self.execute_pipeline().await?
-        };
+            return Ok(0);
+        }
+        self.execute_pipeline().await
+    }
+
+    /// Large values, post-migration schema changes, sequence sync and the
+    /// after hooks.
+    async fn after_load(&self) -> Result<(), MigrationError> {
+        let shared = !self.pipeline.concurrent_writers.is_empty();
+        if !self.is_schema_only() {
+            self.stream_large_values().await?;
+        }
 
-        self.execute_schema_ops("post-migration", &self.schema_ops.post)
+        let post = self.with_index_rebuilds(&self.schema_ops.post).await?;
+        if shared {
+            self.defer_schema_ops(&post, true).await?;
+        } else {
+            self.execute_schema_ops("post-migration", &post).await?;
+        }
+        if !self.is_schema_only() {
+            self.sync_sequences().await?;
+        }
+        self.defer_schema_ops(&self.schema_ops.post_load, false)
             .await?;
-        self.execute_hooks(HookPhase::After).await?;
-        Ok(rows)
+        self.execute_hooks(HookPhase::After).await
     }
 
     /// Execute a batch of schema operations against the destination endpoint,
@@ -96,7 +243,7 @@ impl PipelineOrchestrator {
     async fn execute_schema_ops(
         &self,
         phase: &str,
-        ops: &[engine_core::schema::schema_ops::SchemaOp],
+        ops: &[SchemaOp],
     ) -> Result<(), MigrationError> {
         if ops.is_empty() {
             return Ok(());
@@ -113,6 +260,19 @@ impl PipelineOrchestrator {
                 }
             }
 
+            if let Some(index) = &op.dropped_index {
+                self.ctx
+                    .state
+                    .append_wal(&WalEntry::IndexDropped {
+                        run_id: self.ctx.run_id.clone(),
+                        item_id: self.ctx.item_id.clone(),
+                        table: index.table.clone(),
+                        index: index.name.clone(),
+                        sql: index.rebuild_sql.clone(),
+                    })
+                    .await?;
+            }
+
             self.dest_ep
                 .apply_schema_ops(std::slice::from_ref(op), phase)
                 .await?;
@@ -123,6 +283,141 @@ impl PipelineOrchestrator {
         Ok(())
     }
 
+    /// The post-migration ops plus the rebuilds of indexes this pipeline
+    /// dropped earlier in the run, which a run resumed after a crash no longer
+    /// plans since the destination has lost them.
+    async fn with_index_rebuilds(
+        &self,
+        post: &[SchemaOp],
+    ) -> Result<Vec<SchemaOp>, MigrationError> {
+        let mut ops = post.to_vec();
+        if self.settings.is_dry_run() {
+            return Ok(ops);
+        }
+
+        let wal = self.ctx.state.iter_wal(&self.ctx.run_id).await?;
+        for rebuild in index_rebuilds(WalEntry::since_run_start(&wal), &self.ctx.item_id) {
+            if !ops.iter().any(|op| op.sql == rebuild.sql) {
+                ops.push(rebuild);
+            }
+        }
+        Ok(ops)
+    }
+
+    /// Insert the rows of the pipeline's seed blocks that the destination
+    /// does not have yet.
+    async fn seed_tables(&self) -> Result<(), MigrationError> {
+        if self.pipeline.seeds.is_empty() {
+            return Ok(());
+        }
+        if self.settings.is_dry_run() {
+            info!(
+                count = self.pipeline.seeds.len(),
+                "dry run, skipping seed rows"
+            );
+            return Ok(());
+        }
+
+        for seed in &self.pipeline.seeds {
+            let inserted = self.dest_ep.seed(seed).await?;
+            info!(
+                table = %seed.table,
+                rows = seed.rows.len(),
+                inserted,
+                "seeded table"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Copy the binary values the batches read as NULL for being over
+    /// `lob_threshold`, in chunks of `lob_chunk_size` bytes.
+    async fn stream_large_values(&self) -> Result<(), MigrationError> {
+        let Some(plan) = &self.ctx.source.lob else {
+            return Ok(());
+        };
+        if self.settings.is_dry_run() {
+            info!("dry run, skipping large-value streaming");
+            return Ok(());
+        }
+
+        let exec = &self.ctx.exec_ctx;
+        let source = exec
+            .resolve_driver(&self.pipeline.source.connection)
+            .await?;
+        let destination = exec
+            .resolve_driver(&self.pipeline.destination.connection)
+            .await?;
+        let stats = dispatch_drivers!(&source, &destination, |s, d| {
+            lob::stream(plan, s.as_ref(), d.as_ref())
+                .await
+                .map_err(|e| {
+                    MigrationError::PipelineFailed(format!(
+                        "Failed to stream large values of {}: {e}",
+                        plan.source_table
+                    ))
+                })?
+        });
+
+        if stats.values > 0 {
+            info!(
+                table = %plan.destination_table,
+                values = stats.values,
+                bytes = stats.bytes,
+                "large values streamed"
+            );
+        }
+        Ok(())
+    }
+
+    /// Advance the destination's sequences past the keys just loaded, when
+    /// `sync_sequences` is set.
+    async fn sync_sequences(&self) -> Result<(), MigrationError> {
+        if !self.settings.sync_sequences() || self.settings.is_dry_run() {
+            return Ok(());
+        }
+
+        let dest_metas = self.fetch_destination_metadata().await?;
+        self.dest_ep.sync_sequences(&dest_metas).await
+    }
+
+    /// Record post-load schema operations in the WAL, where the executor
+    /// finds them after the last pipeline, also when the run was resumed.
+    /// With `table_only`, the ops other than foreign keys run even when a
+    /// pipeline failed.
+    async fn defer_schema_ops(
+        &self,
+        ops: &[SchemaOp],
+        table_only: bool,
+    ) -> Result<(), MigrationError> {
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            count = ops.len(),
+            "deferring schema operations until every pipeline has loaded"
+        );
+        for op in ops {
+            self.ctx
+                .state
+                .append_wal(&WalEntry::SchemaOpDeferred {
+                    run_id: self.ctx.run_id.clone(),
+                    item_id: self.ctx.item_id.clone(),
+                    connection: self.pipeline.destination.connection.name.clone(),
+                    sql: op.sql.clone(),
+                    description: op.description.clone(),
+                    idempotent: op.idempotent,
+                    skip_if_missing_ref: op.skip_if_missing_ref,
+                    table_only: table_only && op.foreign_key.is_none(),
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
     fn is_schema_only(&self) -> bool {
         self.pipeline
             .source
@@ -153,38 +448,81 @@ impl PipelineOrchestrator {
     /// Returns the number of rows processed.
     async fn execute_pipeline(&self) -> Result<u64, MigrationError> {
         info!("starting data migration");
+        self.check_resumable().await?;
 
         self.publish_started().await;
         let start_time = std::time::Instant::now();
+        let metrics = Metrics::new();
+        let dest_metas = self.fetch_destination_metadata().await?;
+        let config = self
+            .with_keymaps(self.build_producer_config(&dest_metas), &dest_metas)
+            .await?;
 
-        // Initialize Producer, Consumer, and Coordinator
-        let (coordinator, metrics) = self.build_coordinator().await?;
+        match self.partition_scheduler().await? {
+            Some((scheduler, pk)) => {
+                let partitions =
+                    self.run_partitions(&scheduler, &pk, &dest_metas, &config, &metrics);
+                self.await_completion_or_cancel(partitions, &metrics, start_time)
+                    .await?;
+            }
+            None => {
+                let coordinator = self
+                    .build_coordinator(
+                        &self.ctx,
+                        config,
+                        dest_metas,
+                        &metrics,
+                        self.shutdown.cancel.clone(),
+                    )
+                    .await?;
+                self.start_coordinator(&coordinator, self.ctx.part_id.clone())
+                    .await?;
+                let wait = async { coordinator.wait().await.map_err(MigrationError::from) };
+                self.await_completion_or_cancel(wait, &metrics, start_time)
+                    .await?;
+            }
+        }
 
-        // Run the pipeline with cancellation support
-        self.await_completion_or_cancel(coordinator, &metrics, start_time)
-            .await?;
+        if let Some(tally) = &self.validation_tally {
+            report_validation(&tally.snapshot())?;
+        }
 
         Ok(metrics.snapshot().records_processed)
     }
 
-    async fn build_coordinator(&self) -> Result<(PipelineCoordinator, Metrics), MigrationError> {
-        let (batch_tx, batch_rx) = mpsc::channel::<Batch>(BATCH_CHANNEL_CAPACITY);
-        let metrics = Metrics::new();
+    /// A CSV destination writes its parts afresh on every run, so a load
+    /// resumed from a checkpoint would leave only the rows after it behind
+    /// a manifest that looks complete. Such a load has to start over.
+    async fn check_resumable(&self) -> Result<(), MigrationError> {
+        if self.ctx.destination.format != DataFormat::Csv {
+            return Ok(());
+        }
+        let checkpoints = self.ctx.state.list_checkpoints(&self.ctx.run_id).await?;
+        if checkpoints.iter().any(|cp| cp.item_id == self.ctx.item_id) {
+            return Err(MigrationError::PipelineFailed(format!(
+                "the CSV export of '{}' cannot be resumed from its checkpoint; run `stratum reset` and apply the config again",
+                self.ctx.destination.name
+            )));
+        }
+        Ok(())
+    }
 
-        let dest_metas = self.fetch_destination_metadata().await?;
-        let config = self.build_producer_config(&dest_metas);
+    async fn build_coordinator(
+        &self,
+        ctx: &PipelineContext,
+        config: ProducerConfig,
+        dest_metas: Vec<TableMetadata>,
+        metrics: &Metrics,
+        cancel: CancellationToken,
+    ) -> Result<PipelineCoordinator, MigrationError> {
+        let (batch_tx, batch_rx) = mpsc::channel::<Batch>(BATCH_CHANNEL_CAPACITY);
 
-        let producer = Producer::new(
-            &self.ctx,
-            batch_tx,
-            config,
-            self.settings.mapped_columns_only(),
-        )
-        .await
-        .map_err(|e| MigrationError::InitializationError(e.to_string()))?;
+        let producer = Producer::new(ctx, batch_tx, config, self.settings.mapped_columns_only())
+            .await
+            .map_err(|e| MigrationError::InitializationError(e.to_string()))?;
 
         let consumer = Consumer::new(
-            &self.ctx,
+            ctx,
             batch_rx,
             dest_metas,
             self.shutdown.clone(),
@@ -192,26 +530,249 @@ impl PipelineOrchestrator {
         )
         .await;
 
-        let coordinator = PipelineCoordinator::new(
+        Ok(PipelineCoordinator::new(
             producer,
             consumer,
             metrics.clone(),
-            self.shutdown.cancel.clone(),
+            cancel,
             self.event_bus.clone(),
+        ))
+    }
+
+    async fn start_coordinator(
+        &self,
+        coordinator: &PipelineCoordinator,
+        part_id: String,
+    ) -> Result<(), MigrationError> {
+        coordinator
+            .start_snapshot_pipeline(self.ctx.run_id.clone(), self.ctx.item_id.clone(), part_id)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "failed to start snapshot pipeline");
+                MigrationError::PipelineFailed(format!("Failed to start pipeline: {}", e))
+            })
+    }
+
+    /// Key ranges to read concurrently when `parallel_partitions` is set and
+    /// the source can be read by primary key range; `None` reads the table
+    /// as a single partition.
+    async fn partition_scheduler(
+        &self,
+    ) -> Result<Option<(PartitionScheduler, QualCol)>, MigrationError> {
+        let partitions = self.settings.parallel_partitions();
+        if partitions <= 1 {
+            return Ok(None);
+        }
+
+        let Some(pk) = self.ctx.offset_strategy.range_key().cloned() else {
+            warn!(
+                strategy = %self.ctx.offset_strategy.name(),
+                "parallel_partitions requires the pk pagination strategy; reading a single partition"
+            );
+            return Ok(None);
+        };
+        if !self.cascade_tables.is_empty() {
+            warn!("parallel_partitions is not supported with cascade; reading a single partition");
+            return Ok(None);
+        }
+
+        match self.ctx.source.key_bounds(&pk.column).await? {
+            Some((min, max)) if min >= 0 => {
+                let min_split = self.settings.batch_size.max(1) as u64;
+                let points = self.key_split_points(&pk, partitions).await;
+                let scheduler = if points.is_empty() {
+                    info!(partitions, min, max, key = %pk.column, "reading source in equal-width key ranges");
+                    PartitionScheduler::new(min as u64, max as u64, partitions, min_split)
+                } else {
+                    info!(partitions, min, max, key = %pk.column, ?points, "reading source in key ranges split by table statistics");
+                    PartitionScheduler::with_split_points(
+                        min as u64, max as u64, &points, min_split,
+                    )
+                };
+                Ok(Some((scheduler, pk)))
+            }
+            Some((min, _)) => {
+                warn!(
+                    min,
+                    "parallel_partitions requires non-negative keys; reading a single partition"
+                );
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Keys where the source statistics put equal numbers of rows on either
+    /// side, so skewed keys still give balanced ranges. Empty, for equal-width
+    /// ranges, when the source has no usable statistics.
+    async fn key_split_points(&self, pk: &QualCol, partitions: usize) -> Vec<u64> {
+        match self
+            .ctx
+            .source
+            .key_split_points(&pk.column, partitions)
+            .await
+        {
+            Ok(points) => points
+                .into_iter()
+                .filter_map(|p| u64::try_from(p).ok())
+                .collect(),
+            Err(e) => {
+                warn!(error = %e, "could not read key statistics; splitting key ranges evenly");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Runs one worker per partition. A worker reads the ranges it is handed
+    /// until the scheduler has nothing left to split. The first failure stops
+    /// the other workers.
+    async fn run_partitions(
+        &self,
+        scheduler: &PartitionScheduler,
+        pk: &QualCol,
+        dest_metas: &[TableMetadata],
+        config: &ProducerConfig,
+        metrics: &Metrics,
+    ) -> Result<(), MigrationError> {
+        let cancel = self.shutdown.cancel.child_token();
+
+        let workers = (0..self.settings.parallel_partitions()).map(|_| async {
+            while let Some(range) = scheduler.next_range().await {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                if let Err(e) = self
+                    .run_range(
+                        &range,
+                        pk,
+                        dest_metas,
+                        config,
+                        metrics,
+                        cancel.child_token(),
+                    )
+                    .await
+                {
+                    cancel.cancel();
+                    return Err(e);
+                }
+            }
+            Ok(())
+        });
+
+        future::join_all(workers).await.into_iter().collect()
+    }
+
+    async fn run_range(
+        &self,
+        range: &Arc<KeyRange>,
+        pk: &QualCol,
+        dest_metas: &[TableMetadata],
+        config: &ProducerConfig,
+        metrics: &Metrics,
+        cancel: CancellationToken,
+    ) -> Result<(), MigrationError> {
+        let part_id = range.part_id();
+        debug!(
+            part_id,
+            start = range.start(),
+            end = range.end(),
+            "reading key range"
+        );
+
+        let offset_strategy = range.offset_strategy(pk);
+        let source = self
+            .ctx
+            .source
+            .with_offset_strategy(offset_strategy.clone())
+            .ok_or_else(|| {
+                MigrationError::InitializationError(
+                    "source cannot be read in key ranges".to_string(),
+                )
+            })?;
+        let ctx = self.ctx.for_partition(
+            part_id.clone(),
+            source,
+            offset_strategy,
+            range.start_cursor(pk),
         );
 
-        Ok((coordinator, metrics))
+        let config = config.clone().with_key_range(range.clone());
+        let coordinator = self
+            .build_coordinator(&ctx, config, dest_metas.to_vec(), metrics, cancel)
+            .await?;
+        self.start_coordinator(&coordinator, part_id.clone())
+            .await?;
+
+        coordinator
+            .wait()
+            .await
+            .map_err(|e| match MigrationError::from(e) {
+                drift @ MigrationError::SchemaDrift { .. } => drift,
+                e => MigrationError::PipelineFailed(format!("{part_id}: {e}")),
+            })?;
+        metrics.record_partition_timing(part_id, range.start(), range.end(), range.elapsed());
+        Ok(())
     }
 
     /// Fetches destination table metadata.
-    /// In cascade mode, fetches metadata for all discovered tables.
-    /// Otherwise, just the single destination table.
+    /// In cascade mode, fetches metadata for all discovered tables; with
+    /// several `to` blocks, for each of their tables. Otherwise, just the
+    /// single destination table.
     async fn fetch_destination_metadata(&self) -> Result<Vec<TableMetadata>, MigrationError> {
+        if self.cascade_tables.is_empty() && !self.pipeline.sinks.is_empty() {
+            let tables: Vec<String> = std::iter::once(self.ctx.destination.name.clone())
+                .chain(self.pipeline.sinks.iter().map(|s| s.table.clone()))
+                .collect();
+            return self.dest_ep.destination_metadata(&self.ctx, &tables).await;
+        }
         self.dest_ep
             .destination_metadata(&self.ctx, &self.cascade_tables)
             .await
     }
 
+    /// `config` with the pipeline's key crosswalks: the columns it records
+    /// its own from (`emit_keymap`) and the crosswalks its `lookup_key()`
+    /// calls read, loaded in full.
+    async fn with_keymaps(
+        &self,
+        mut config: ProducerConfig,
+        dest_metas: &[TableMetadata],
+    ) -> Result<ProducerConfig, MigrationError> {
+        let emit = matches!(
+            effective_value(&self.pipeline.settings, "emit_keymap"),
+            Ok(Value::Boolean(true))
+        );
+        if emit && !self.pipeline.validate_only {
+            let table = &self.pipeline.destination.table;
+            let source_key = single_key(&self.ctx.source.primary_keys, &self.ctx.source.name)?;
+            let destination_keys = dest_metas
+                .iter()
+                .find(|m| m.name.eq_ignore_ascii_case(table))
+                .map(|m| m.primary_keys.clone())
+                .unwrap_or_default();
+            let destination_key = single_key(&destination_keys, table)?;
+            info!(table = %table, source_key = %source_key, destination_key = %destination_key, "recording key crosswalk");
+            config = config.with_keymap(KeymapColumns {
+                table: table.to_ascii_lowercase(),
+                source_key,
+                destination_key,
+            });
+        }
+
+        let mut keymaps = HashMap::new();
+        for table in self.pipeline.lookup_key_tables() {
+            let keys = self.ctx.state.load_keys(&table).await?;
+            if keys.is_empty() {
+                return Err(MigrationError::InitializationError(format!(
+                    "lookup_key(\"{table}\", ...) has no keys to read; set emit_keymap = true on the pipeline that loads '{table}' and run it first"
+                )));
+            }
+            info!(table = %table, keys = keys.len(), "loaded key crosswalk");
+            keymaps.insert(table, Arc::new(keys));
+        }
+        Ok(config.with_keymaps(keymaps))
+    }
+
     fn build_producer_config(&self, dest_metas: &[TableMetadata]) -> ProducerConfig {
         let mut config = ProducerConfig::default().with_batch_size(self.settings.batch_size);
 
@@ -249,28 +810,79 @@ impl PipelineOrchestrator {
             config = config.with_integrity(integrity);
         }
 
+        // Labels of destination enum columns, checked before rows are written
+        let mut enum_labels = HashMap::new();
+        for meta in dest_metas {
+            let labels: HashMap<_, _> = meta
+                .columns
+                .values()
+                .filter_map(|c| Some((c.name.clone(), c.enum_values()?)))
+                .filter(|(_, labels)| !labels.is_empty())
+                .collect();
+            if !labels.is_empty() {
+                enum_labels.insert(meta.name.clone(), labels);
+            }
+        }
+
+        // Text columns of the destination; raw bytes bound for them are
+        // decoded per the invalid_utf8 setting
+        let text_columns = dest_metas
+            .iter()
+            .map(|meta| {
+                let columns: HashSet<_> = meta
+                    .columns
+                    .values()
+                    .filter(|c| c.is_text())
+                    .map(|c| c.name.clone())
+                    .collect();
+                (meta.name.clone(), columns)
+            })
+            .filter(|(_, columns)| !columns.is_empty())
+            .collect();
+
+        // Columns of database destinations that do not hold arrays; arrays
+        // bound for them are serialized per the array_format setting
+        let scalar_columns = match self.ctx.destination.format {
+            DataFormat::MySql | DataFormat::Postgres => dest_metas
+                .iter()
+                .map(|meta| {
+                    let columns: HashSet<_> = meta
+                        .columns
+                        .values()
+                        .filter(|c| !c.is_array())
+                        .map(|c| c.name.clone())
+                        .collect();
+                    (meta.name.clone(), columns)
+                })
+                .filter(|(_, columns)| !columns.is_empty())
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        config = config
+            .with_enum_labels(enum_labels)
+            .with_text_decoding(self.settings.invalid_utf8(), text_columns)
+            .with_array_serialization(
+                self.settings.array_format(),
+                self.settings.array_delimiter(),
+                scalar_columns,
+            );
+        if let Some(tally) = &self.validation_tally {
+            config = config.with_validation_tally(tally.clone());
+        }
         config
+            .with_row_numbers(self.row_numbers.clone())
+            .with_unlogged_load(self.settings.fast_load() == FastLoad::Unlogged)
     }
 
     async fn await_completion_or_cancel(
         &self,
-        coordinator: PipelineCoordinator,
+        wait_fut: impl Future<Output = Result<(), MigrationError>>,
         metrics: &Metrics,
         start_time: std::time::Instant,
     ) -> Result<(), MigrationError> {
-        let part_id = "part-0".to_string(); // TODO: Make this dynamic when multi-part support is added
-
-        coordinator
-            .start_snapshot_pipeline(self.ctx.run_id.clone(), self.ctx.item_id.clone(), part_id)
-            .await
-            .map_err(|e| {
-                error!(error = %e, "failed to start snapshot pipeline");
-                MigrationError::PipelineFailed(format!("Failed to start pipeline: {}", e))
-            })?;
-
         let cancel_fut = self.shutdown.cancel.cancelled();
         let pause_fut = self.shutdown.pause.cancelled();
-        let wait_fut = coordinator.wait();
 
         tokio::pin!(cancel_fut);
         tokio::pin!(pause_fut);
@@ -291,7 +903,7 @@ impl PipelineOrchestrator {
 
     async fn handle_pipeline_result(
         &self,
-        result: Result<(), impl std::fmt::Display>,
+        result: Result<(), MigrationError>,
         metrics: &Metrics,
         start_time: std::time::Instant,
     ) -> Result<(), MigrationError> {
@@ -306,9 +918,16 @@ impl PipelineOrchestrator {
                     );
                 }
                 debug!("data migration completed");
+                log_timing_report(&metrics.timing_report());
                 self.publish_completed(metrics, start_time).await;
                 Ok(())
             }
+            Err(e @ MigrationError::SchemaDrift { .. }) => {
+                // Not a failure of the data: the item pauses until the
+                // table is reconciled, then resumes from its checkpoint
+                warn!(error = %e, "destination schema drift, pausing pipeline");
+                Err(e)
+            }
             Err(e) => {
                 error!(error = %e, "pipeline error");
                 self.publish_failed(&e.to_string(), metrics).await;
@@ -322,7 +941,7 @@ impl PipelineOrchestrator {
 
     async fn handle_pause(
         &self,
-        wait_fut: impl Future<Output = Result<(), impl std::fmt::Display>>,
+        wait_fut: impl Future<Output = Result<(), MigrationError>>,
     ) -> Result<(), MigrationError> {
         info!("pause signal received, draining current batch");
 
@@ -353,7 +972,7 @@ impl PipelineOrchestrator {
 
     async fn handle_shutdown(
         &self,
-        wait_fut: impl Future<Output = Result<(), impl std::fmt::Display>>,
+        wait_fut: impl Future<Output = Result<(), MigrationError>>,
     ) -> Result<(), MigrationError> {
         warn!(
             timeout_secs = SHUTDOWN_TIMEOUT.as_secs(),
@@ -423,3 +1042,163 @@ impl PipelineOrchestrator {
             .await;
     }
 }
+
+/// Logs how many rows broke each rule of a validate-only pipeline. Fails the
+/// pipeline when any row broke a rule whose action is `fail`.
+/// Rebuild ops for the indexes `item_id` recorded dropping, each once.
+fn index_rebuilds(wal: &[WalEntry], item_id: &str) -> Vec<SchemaOp> {
+    let mut ops: Vec<SchemaOp> = Vec::new();
+    for entry in wal {
+        let WalEntry::IndexDropped {
+            item_id: dropped_by,
+            table,
+            index,
+            sql,
+            ..
+        } = entry
+        else {
+            continue;
+        };
+        if dropped_by != item_id || ops.iter().any(|op| &op.sql == sql) {
+            continue;
+        }
+        ops.push(SchemaOp {
+            sql: sql.clone(),
+            description: format!("Rebuild index '{index}' on '{table}'"),
+            idempotent: true,
+            skip_if_missing_ref: false,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: None,
+        });
+    }
+    ops
+}
+
+fn report_validation(rules: &[RuleTally]) -> Result<(), MigrationError> {
+    for rule in rules {
+        match &rule.sample {
+            None => info!(rule = %rule.rule, "validation rule passed"),
+            Some(sample) => warn!(
+                rule = %rule.rule,
+                failures = rule.failures,
+                sample = %sample,
+                "validation rule failed"
+            ),
+        }
+    }
+
+    let failed: Vec<_> = rules
+        .iter()
+        .filter(|r| r.failures > 0 && r.action == ValidationAction::Fail)
+        .map(|r| format!("'{}' ({} rows)", r.rule, r.failures))
+        .collect();
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(MigrationError::PipelineFailed(format!(
+        "validation failed: {}",
+        failed.join(", ")
+    )))
+}
+
+/// Logs how batch and partition durations were spread, the key ranges that
+/// took far longer than the rest and the slowest batches, so the next run
+/// can be partitioned or indexed around them.
+fn log_timing_report(report: &TimingReport) {
+    if let Some(batches) = report.batches {
+        info!(
+            batches = batches.count,
+            p50_ms = batches.p50.as_millis() as u64,
+            p95_ms = batches.p95.as_millis() as u64,
+            max_ms = batches.max.as_millis() as u64,
+            "batch durations"
+        );
+    }
+    if let Some(partitions) = report.partitions {
+        info!(
+            partitions = partitions.count,
+            p50_ms = partitions.p50.as_millis() as u64,
+            p95_ms = partitions.p95.as_millis() as u64,
+            max_ms = partitions.max.as_millis() as u64,
+            "partition durations"
+        );
+        for part in &report.skewed {
+            warn!(
+                part_id = %part.part_id,
+                start = part.start,
+                end = part.end,
+                rows = part.rows,
+                duration_ms = part.duration.as_millis() as u64,
+                median_ms = partitions.p50.as_millis() as u64,
+                "key range took over twice the median partition; consider more parallel_partitions or an index on the key"
+            );
+        }
+    }
+    for batch in &report.slowest_batches {
+        info!(
+            part_id = %batch.part_id,
+            from = %batch.from,
+            to = %batch.to,
+            rows = batch.rows,
+            read_ms = batch.read.as_millis() as u64,
+            write_ms = batch.write.as_millis() as u64,
+            "slow batch"
+        );
+    }
+}
+
+/// The one column of a primary key `emit_keymap` records a crosswalk by.
+fn single_key(keys: &[String], table: &str) -> Result<String, MigrationError> {
+    match keys {
+        [key] => Ok(key.clone()),
+        _ => Err(MigrationError::InitializationError(format!(
+            "emit_keymap needs a single-column primary key on '{table}', which has {}",
+            match keys.len() {
+                0 => "none".to_string(),
+                n => format!("{n} key columns"),
+            }
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dropped(item_id: &str, index: &str) -> WalEntry {
+        WalEntry::IndexDropped {
+            run_id: "run-1".to_string(),
+            item_id: item_id.to_string(),
+            table: "orders".to_string(),
+            index: index.to_string(),
+            sql: format!(
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS {index} ON public.orders USING btree (id);"
+            ),
+        }
+    }
+
+    #[test]
+    fn test_index_rebuilds_of_this_pipeline_once_each() {
+        let wal = vec![
+            dropped("orders", "idx_a"),
+            WalEntry::RunResumed {
+                run_id: "run-1".to_string(),
+            },
+            dropped("users", "idx_b"),
+            dropped("orders", "idx_a"),
+            dropped("orders", "idx_c"),
+        ];
+
+        let rebuilds = index_rebuilds(&wal, "orders");
+        let descriptions: Vec<&str> = rebuilds.iter().map(|op| op.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Rebuild index 'idx_a' on 'orders'",
+                "Rebuild index 'idx_c' on 'orders'",
+            ]
+        );
+        assert!(rebuilds.iter().all(|op| op.idempotent));
+    }
+}
@@ -109,7 +109,10 @@ fn convert_type(source: &Type) -> ConversionResult {
         Type::Boolean => ConversionResult::Exact(source.clone()),
         Type::Uuid => ConversionResult::Exact(source.clone()),
 
-        // ENUM -> VARCHAR (PostgreSQL has native ENUM but requires CREATE TYPE)
+        // ENUM -> enum type, created ahead of the table with CREATE TYPE
+        Type::Enum { values, .. } if !values.is_empty() => ConversionResult::Exact(source.clone()),
+
+        // ENUM with unknown labels -> VARCHAR
         Type::Enum { .. } => ConversionResult::RequiresTransform {
             target: Type::Varchar {
                 length: Some(255),
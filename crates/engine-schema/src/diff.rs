@@ -0,0 +1,339 @@
+//! Structural comparison of a source table with the destination table it is
+//! copied to. `create_missing_columns` adds the columns reported missing
+//! here; `stratum schema diff` reports every difference.
+
+use crate::{type_registry::Dialect, type_registry::TypeRegistry, utils::create_column_def};
+use connectors::sql::{
+    metadata::{column::ColumnMetadata, index::IndexMetadata, table::TableMetadata},
+    query::column::ColumnDef,
+};
+use model::core::types::Type;
+use serde::Serialize;
+
+/// Differences between a source table and its destination table. Columns are
+/// named as they are in the destination.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TableDiff {
+    pub source_table: String,
+    pub destination_table: String,
+    pub missing_columns: Vec<MissingColumn>,
+    pub type_mismatches: Vec<TypeMismatch>,
+    pub nullable_differences: Vec<NullableDifference>,
+    pub missing_indexes: Vec<MissingIndex>,
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing_columns.is_empty()
+            && self.type_mismatches.is_empty()
+            && self.nullable_differences.is_empty()
+            && self.missing_indexes.is_empty()
+    }
+}
+
+/// Source column with no destination column of its name.
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingColumn {
+    pub column: String,
+    pub source_column: String,
+    /// Type the column would be created with
+    pub expected_type: String,
+}
+
+/// Destination column whose type differs from the converted source type.
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeMismatch {
+    pub column: String,
+    pub expected_type: String,
+    pub actual_type: String,
+}
+
+/// Column that allows NULL on one side only.
+#[derive(Debug, Clone, Serialize)]
+pub struct NullableDifference {
+    pub column: String,
+    pub source_nullable: bool,
+    pub destination_nullable: bool,
+}
+
+/// Source index with no destination index over the same columns.
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingIndex {
+    /// Name of the source index
+    pub name: String,
+    /// Destination column names, in index order
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub primary: bool,
+}
+
+/// Definitions of the destination columns that `columns` (source and
+/// destination name pairs) are copied to but `dest` does not have. Fails
+/// with the name of a source column that `source` does not have.
+pub fn missing_column_defs<T: Fn(&ColumnMetadata) -> (Type, Option<usize>)>(
+    columns: &[(String, String)],
+    source: &TableMetadata,
+    dest: &TableMetadata,
+    type_conv: &T,
+) -> Result<Vec<ColumnDef>, String> {
+    let mut defs = Vec::new();
+    for (src_col, dst_col) in columns {
+        if dest.get_column(dst_col).is_some() {
+            continue;
+        }
+        let meta = source.column(src_col).ok_or_else(|| src_col.clone())?;
+        defs.push(create_column_def(dst_col, type_conv, meta));
+    }
+    Ok(defs)
+}
+
+/// Compares `source` with `dest`. `columns` pairs each compared source
+/// column with its destination name; source columns not in it are ignored.
+pub fn diff_table(
+    columns: &[(String, String)],
+    source: (&TableMetadata, &[IndexMetadata]),
+    dest: (&TableMetadata, &[IndexMetadata]),
+    source_dialect: Dialect,
+    dest_dialect: Dialect,
+) -> Result<TableDiff, String> {
+    let (source_meta, source_indexes) = source;
+    let (dest_meta, dest_indexes) = dest;
+    let registry = TypeRegistry::new(source_dialect, dest_dialect);
+    let type_conv = |meta: &ColumnMetadata| -> (Type, Option<usize>) {
+        let source_type = source_dialect.to_canonical(meta);
+        (
+            registry.convert(&source_type).target_type(),
+            meta.char_max_length,
+        )
+    };
+
+    let mut diff = TableDiff {
+        source_table: source_meta.name.clone(),
+        destination_table: dest_meta.name.clone(),
+        ..Default::default()
+    };
+
+    for def in missing_column_defs(columns, source_meta, dest_meta, &type_conv)? {
+        let source_column = columns
+            .iter()
+            .find(|(_, dst)| *dst == def.name)
+            .map(|(src, _)| src.clone())
+            .unwrap_or_else(|| def.name.clone());
+        diff.missing_columns.push(MissingColumn {
+            column: def.name,
+            source_column,
+            expected_type: def.data_type.name(),
+        });
+    }
+
+    for (src_col, dst_col) in columns {
+        let (Some(src), Some(dst)) = (source_meta.column(src_col), dest_meta.get_column(dst_col))
+        else {
+            continue;
+        };
+
+        let expected = type_conv(src).0.name();
+        let actual = dest_dialect.to_canonical(dst).name();
+        if expected != actual {
+            diff.type_mismatches.push(TypeMismatch {
+                column: dst_col.clone(),
+                expected_type: expected,
+                actual_type: actual,
+            });
+        }
+
+        if src.is_nullable != dst.is_nullable {
+            diff.nullable_differences.push(NullableDifference {
+                column: dst_col.clone(),
+                source_nullable: src.is_nullable,
+                destination_nullable: dst.is_nullable,
+            });
+        }
+    }
+
+    for index in source_indexes {
+        let Some(index_columns) = index
+            .columns
+            .iter()
+            .map(|c| {
+                columns
+                    .iter()
+                    .find(|(src, _)| src.eq_ignore_ascii_case(&c.name))
+                    .map(|(_, dst)| dst.clone())
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            // An index over a column that is not copied cannot exist in the destination
+            continue;
+        };
+
+        let covered = dest_indexes.iter().any(|d| {
+            (d.is_unique || d.is_primary || !(index.is_unique || index.is_primary))
+                && d.columns.len() == index_columns.len()
+                && d.columns
+                    .iter()
+                    .zip(&index_columns)
+                    .all(|(d, c)| d.name.eq_ignore_ascii_case(c))
+        });
+        if !covered {
+            diff.missing_indexes.push(MissingIndex {
+                name: index.name.clone(),
+                columns: index_columns,
+                unique: index.is_unique,
+                primary: index.is_primary,
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Each column of `table` paired with itself, in column order.
+pub fn same_name_columns(table: &TableMetadata) -> Vec<(String, String)> {
+    let mut columns: Vec<_> = table.columns.values().collect();
+    columns.sort_by_key(|c| c.ordinal);
+    columns
+        .into_iter()
+        .map(|c| (c.name.clone(), c.name.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connectors::sql::metadata::index::{IndexColumn, IndexType, NullsOrder, SortOrder};
+    use std::collections::HashMap;
+
+    fn column(ordinal: usize, name: &str, data_type: &str, nullable: bool) -> ColumnMetadata {
+        ColumnMetadata {
+            ordinal,
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: nullable,
+            has_default: false,
+            default_value: None,
+            char_max_length: None,
+            num_precision: None,
+            num_scale: None,
+            is_primary_key: false,
+            is_unique: false,
+            is_auto_increment: false,
+            comment: None,
+            collation: None,
+            charset: None,
+            is_generated: false,
+            is_stored: false,
+            generated_expression: None,
+            full_column_type: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<ColumnMetadata>) -> TableMetadata {
+        TableMetadata {
+            name: name.to_string(),
+            schema: None,
+            collation: None,
+            columns: columns.into_iter().map(|c| (c.name.clone(), c)).collect(),
+            primary_keys: vec![],
+            foreign_keys: vec![],
+            referenced_tables: HashMap::new(),
+            referencing_tables: HashMap::new(),
+        }
+    }
+
+    fn index(name: &str, columns: &[&str], unique: bool) -> IndexMetadata {
+        IndexMetadata {
+            name: name.to_string(),
+            table: "orders".to_string(),
+            schema: String::new(),
+            index_type: IndexType::BTree,
+            columns: columns
+                .iter()
+                .map(|c| IndexColumn {
+                    name: c.to_string(),
+                    sort_order: SortOrder::Asc,
+                    nulls_order: NullsOrder::Default,
+                })
+                .collect(),
+            is_unique: unique,
+            is_primary: false,
+            condition: None,
+            tablespace: None,
+            fill_factor: None,
+            size_bytes: None,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_table_reports_each_kind_of_difference() {
+        let source = table(
+            "orders",
+            vec![
+                column(1, "id", "integer", false),
+                column(2, "status", "text", false),
+                column(3, "total", "numeric", true),
+                column(4, "note", "text", true),
+            ],
+        );
+        let dest = table(
+            "orders",
+            vec![
+                column(1, "id", "integer", false),
+                column(2, "status", "text", true),
+                column(3, "total", "text", true),
+            ],
+        );
+        let source_indexes = vec![
+            index("orders_status_idx", &["status"], false),
+            index("orders_id_key", &["id"], true),
+        ];
+        let dest_indexes = vec![index("orders_status", &["status"], false)];
+
+        let diff = diff_table(
+            &same_name_columns(&source),
+            (&source, &source_indexes),
+            (&dest, &dest_indexes),
+            Dialect::Postgres,
+            Dialect::Postgres,
+        )
+        .unwrap();
+
+        assert!(!diff.is_empty());
+        assert_eq!(diff.missing_columns.len(), 1);
+        assert_eq!(diff.missing_columns[0].column, "note");
+        assert_eq!(diff.missing_columns[0].expected_type, "text");
+
+        assert_eq!(diff.type_mismatches.len(), 1);
+        assert_eq!(diff.type_mismatches[0].column, "total");
+        assert_eq!(diff.type_mismatches[0].expected_type, "decimal");
+        assert_eq!(diff.type_mismatches[0].actual_type, "text");
+
+        assert_eq!(diff.nullable_differences.len(), 1);
+        assert_eq!(diff.nullable_differences[0].column, "status");
+        assert!(diff.nullable_differences[0].destination_nullable);
+
+        assert_eq!(diff.missing_indexes.len(), 1);
+        assert_eq!(diff.missing_indexes[0].name, "orders_id_key");
+        assert!(diff.missing_indexes[0].unique);
+    }
+
+    #[test]
+    fn test_diff_table_follows_renamed_columns() {
+        let source = table("users", vec![column(1, "mail", "text", false)]);
+        let dest = table("users", vec![column(1, "email", "text", false)]);
+        let columns = vec![("mail".to_string(), "email".to_string())];
+        let source_indexes = vec![index("users_mail_key", &["mail"], true)];
+        let dest_indexes = vec![index("users_email_key", &["email"], true)];
+
+        let diff = diff_table(
+            &columns,
+            (&source, &source_indexes),
+            (&dest, &dest_indexes),
+            Dialect::Postgres,
+            Dialect::Postgres,
+        )
+        .unwrap();
+        assert!(diff.is_empty(), "{diff:?}");
+    }
+}
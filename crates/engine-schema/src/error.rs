@@ -1,3 +1,4 @@
+use crate::naming::ColumnCollision;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -24,3 +25,19 @@ pub enum TypeConversionError {
     #[error("Type not found: {0}")]
     NotFound(String),
 }
+
+#[derive(Error, Debug)]
+pub enum NamingError {
+    #[error("{0}; map '{column}' to another name such as '{suggested}'", column = .0.column, suggested = .0.suggested)]
+    ColumnCollision(ColumnCollision),
+
+    #[error(
+        "{first} and {second} both become '{name}' at the {max_len}-byte identifier limit; rename one of them"
+    )]
+    Collision {
+        first: String,
+        second: String,
+        name: String,
+        max_len: usize,
+    },
+}
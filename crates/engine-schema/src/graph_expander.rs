@@ -1,4 +1,5 @@
 use crate::{
+    naming::log_renamed_identifiers,
     plan::SchemaPlan,
     planner::SchemaPlanner,
     schema_ops::SchemaOps,
@@ -38,6 +39,7 @@ pub struct GraphExpander {
     introspector: Arc<dyn SchemaIntrospector>,
     type_registry: Arc<TypeRegistry>,
     source_dialect: Dialect,
+    max_identifier_length: Option<usize>,
 }
 
 impl GraphExpander {
@@ -50,9 +52,16 @@ impl GraphExpander {
             introspector,
             type_registry,
             source_dialect,
+            max_identifier_length: None,
         }
     }
 
+    /// Overrides the destination's identifier length limit for planned schema.
+    pub fn with_max_identifier_length(mut self, max_len: Option<usize>) -> Self {
+        self.max_identifier_length = max_len;
+        self
+    }
+
     /// Expand the FK graph from the root table and produce schema operations.
     pub async fn expand(
         &self,
@@ -228,13 +237,19 @@ impl GraphExpander {
             }
         }
 
+        let Some(mut plan) = merged_plan else {
+            return Ok(SchemaOps::empty());
+        };
+
         // Build ops from the merged plan
-        Ok(merged_plan
-            .map(|mut p| {
-                p.set_drop_constraints(drop_constraints);
-                p.build_ops()
-            })
-            .unwrap_or_else(SchemaOps::empty))
+        plan.set_drop_constraints(drop_constraints);
+        plan.set_max_identifier_length(self.max_identifier_length);
+        let renamed = plan
+            .identifier_report()
+            .map_err(|e| DriverError::QueryError(e.to_string()))?;
+        log_renamed_identifiers(&renamed);
+
+        Ok(plan.build_ops())
     }
 }
 
@@ -248,6 +263,7 @@ mod tests {
             introspector: Arc::new(MockIntrospector),
             type_registry: Arc::new(TypeRegistry::new(Dialect::MySql, Dialect::Postgres)),
             source_dialect: Dialect::MySql,
+            max_identifier_length: None,
         };
 
         let patterns = vec!["audit_logs".to_string(), "temp_*".to_string()];
@@ -264,6 +280,7 @@ mod tests {
             introspector: Arc::new(MockIntrospector),
             type_registry: Arc::new(TypeRegistry::new(Dialect::MySql, Dialect::Postgres)),
             source_dialect: Dialect::MySql,
+            max_identifier_length: None,
         };
 
         let patterns = vec!["*_log".to_string()];
@@ -279,6 +296,7 @@ mod tests {
             introspector: Arc::new(MockIntrospector),
             type_registry: Arc::new(TypeRegistry::new(Dialect::MySql, Dialect::Postgres)),
             source_dialect: Dialect::MySql,
+            max_identifier_length: None,
         };
 
         let patterns = vec!["*".to_string()];
@@ -292,6 +310,7 @@ mod tests {
             introspector: Arc::new(MockIntrospector),
             type_registry: Arc::new(TypeRegistry::new(Dialect::MySql, Dialect::Postgres)),
             source_dialect: Dialect::MySql,
+            max_identifier_length: None,
         };
 
         // Build a simple graph: orders -> customers -> addresses
@@ -337,6 +356,7 @@ mod tests {
             introspector: Arc::new(MockIntrospector),
             type_registry: Arc::new(TypeRegistry::new(Dialect::MySql, Dialect::Postgres)),
             source_dialect: Dialect::MySql,
+            max_identifier_length: None,
         };
 
         let mut graph = HashMap::new();
@@ -384,6 +404,7 @@ mod tests {
         TableMetadata {
             name: name.to_string(),
             schema: None,
+            collation: None,
             columns: HashMap::new(),
             primary_keys: vec![],
             foreign_keys,
@@ -1,8 +1,10 @@
 pub mod converters;
 pub mod dep_graph;
+pub mod diff;
 pub mod error;
 pub mod graph_expander;
 pub mod metadata_cache;
+pub mod naming;
 pub mod plan;
 pub mod planner;
 pub mod row_counter;
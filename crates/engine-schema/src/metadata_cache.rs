@@ -150,6 +150,13 @@ impl<D: SchemaIntrospector + DataReader + Send + Sync + 'static> MetadataCache<D
             })
     }
 
+    pub async fn avg_row_bytes(&self, table: &str) -> Result<Option<u64>, DriverError> {
+        // Not cached, like the table size
+        self.driver().avg_row_bytes(table).await.inspect_err(|e| {
+            error!(table = %table, error = %e, "failed to fetch average row width");
+        })
+    }
+
     pub async fn count_rows(&self, table: &str, filter: Option<&SqlFilter>) -> RowCount {
         let key = RowCountKey {
             table: table.to_string(),
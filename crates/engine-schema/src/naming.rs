@@ -0,0 +1,282 @@
+//! Keeps identifiers within the destination's length limit.
+//!
+//! Postgres truncates identifiers longer than 63 bytes without an error, so
+//! two long names sharing a prefix end up naming the same object. Names the
+//! schema layer creates on its own (indexes, constraints, sequences) are
+//! shortened here to a prefix plus a hash of the full name, which is the same
+//! on every run. Table and column names are not rewritten, because rows are
+//! written under those names and the database truncates them consistently;
+//! they are reported and checked for collisions instead.
+
+use crate::error::NamingError;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+use tracing::info;
+
+/// Hex digits of the md5 digest appended to a shortened name.
+const HASH_SUFFIX_LEN: usize = 8;
+
+/// Kind of schema object an identifier names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentifierKind {
+    Table,
+    Column,
+    Index,
+    ForeignKey,
+    UniqueConstraint,
+    CheckConstraint,
+    Sequence,
+    Partition,
+}
+
+impl IdentifierKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierKind::Table => "table",
+            IdentifierKind::Column => "column",
+            IdentifierKind::Index => "index",
+            IdentifierKind::ForeignKey => "foreign key",
+            IdentifierKind::UniqueConstraint => "unique constraint",
+            IdentifierKind::CheckConstraint => "check constraint",
+            IdentifierKind::Sequence => "sequence",
+            IdentifierKind::Partition => "partition",
+        }
+    }
+
+    /// Whether the database truncates the name itself rather than it being
+    /// shortened before the DDL is generated.
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, IdentifierKind::Table | IdentifierKind::Column)
+    }
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An identifier that will not reach the destination under its own name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamedIdentifier {
+    pub kind: IdentifierKind,
+    /// Table the object belongs to; empty for sequences that no column owns
+    pub table: String,
+    pub original: String,
+    pub renamed: String,
+}
+
+impl RenamedIdentifier {
+    fn describe(kind: IdentifierKind, table: &str, name: &str) -> String {
+        if table.is_empty() || kind == IdentifierKind::Table {
+            format!("{kind} '{name}'")
+        } else {
+            format!("{kind} '{name}' on '{table}'")
+        }
+    }
+}
+
+impl fmt::Display for RenamedIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subject = Self::describe(self.kind, &self.table, &self.original);
+        if self.kind.is_truncated() {
+            write!(f, "{subject} is truncated to '{}'", self.renamed)
+        } else {
+            write!(f, "{subject} is renamed to '{}'", self.renamed)
+        }
+    }
+}
+
+/// A destination column that two source columns would be written to: one
+/// copied under its own name and another renamed onto it. Inserting such a
+/// row fails, as does the DDL that creates the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnCollision {
+    pub table: String,
+    /// Source column copied under its own name
+    pub column: String,
+    /// Source column renamed to `column`
+    pub renamed_from: String,
+    /// Unused name the copied column can be mapped to instead
+    pub suggested: String,
+}
+
+impl fmt::Display for ColumnCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column '{}' on '{}' is both copied from the source and the target of '{}'",
+            self.column, self.table, self.renamed_from
+        )
+    }
+}
+
+/// The first of `base_1`, `base_2`, ... that is not in `taken` (lowercase
+/// names) and fits in `max_len` bytes. Depends only on its inputs, so the
+/// same plan always suggests the same name.
+pub fn free_column_name(base: &str, taken: &HashSet<String>, max_len: usize) -> String {
+    (1..)
+        .map(|n| {
+            let suffix = format!("_{n}");
+            let prefix = truncate_identifier(base, max_len.saturating_sub(suffix.len()));
+            format!("{prefix}{suffix}")
+        })
+        .find(|name| !taken.contains(&name.to_ascii_lowercase()))
+        .expect("unbounded candidates")
+}
+
+/// Longest prefix of `name` that fits in `max_len` bytes without splitting a
+/// character; this is how the database truncates it.
+pub fn truncate_identifier(name: &str, max_len: usize) -> &str {
+    if name.len() <= max_len {
+        return name;
+    }
+    let mut end = max_len;
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+/// `name` unchanged if it fits in `max_len` bytes, otherwise a prefix of it
+/// followed by `_` and the first hex digits of its md5 digest.
+pub fn shorten_identifier(name: &str, max_len: usize) -> String {
+    if name.len() <= max_len {
+        return name.to_string();
+    }
+    let digest = format!("{:x}", md5::compute(name.as_bytes()));
+    let prefix = truncate_identifier(name, max_len.saturating_sub(HASH_SUFFIX_LEN + 1));
+    format!(
+        "{}_{}",
+        prefix.trim_end_matches('_'),
+        &digest[..HASH_SUFFIX_LEN]
+    )
+}
+
+/// Final names claimed in one namespace, e.g. the relations of a schema or
+/// the columns of a table. Names are compared case-insensitively.
+#[derive(Debug)]
+pub(crate) struct IdentifierNamespace {
+    max_len: usize,
+    claimed: HashMap<String, (IdentifierKind, String, String)>,
+}
+
+impl IdentifierNamespace {
+    pub(crate) fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            claimed: HashMap::new(),
+        }
+    }
+
+    /// Records that `original` reaches the destination as `name`. Fails when
+    /// a different identifier already ends up with the same name.
+    pub(crate) fn claim(
+        &mut self,
+        kind: IdentifierKind,
+        table: &str,
+        original: &str,
+        name: &str,
+    ) -> Result<(), NamingError> {
+        let key = name.to_ascii_lowercase();
+        match self.claimed.get(&key) {
+            Some((_, _, existing)) if existing.eq_ignore_ascii_case(original) => Ok(()),
+            Some((other_kind, other_table, existing)) => Err(NamingError::Collision {
+                first: RenamedIdentifier::describe(*other_kind, other_table, existing),
+                second: RenamedIdentifier::describe(kind, table, original),
+                name: name.to_string(),
+                max_len: self.max_len,
+            }),
+            None => {
+                self.claimed
+                    .insert(key, (kind, table.to_string(), original.to_string()));
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Logs every identifier in `renamed`.
+pub fn log_renamed_identifiers(renamed: &[RenamedIdentifier]) {
+    for r in renamed {
+        info!(
+            kind = %r.kind,
+            table = %r.table,
+            original = %r.original,
+            renamed = %r.renamed,
+            "identifier exceeds destination length limit"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten_identifier_keeps_short_names() {
+        assert_eq!(
+            shorten_identifier("idx_orders_status", 63),
+            "idx_orders_status"
+        );
+        let exact = "a".repeat(63);
+        assert_eq!(shorten_identifier(&exact, 63), exact);
+    }
+
+    #[test]
+    fn test_shorten_identifier_is_deterministic_and_distinct() {
+        let base = format!("idx_{}", "customer_order_line_item".repeat(3));
+        let a = format!("{base}_created_at");
+        let b = format!("{base}_updated_at");
+
+        let short_a = shorten_identifier(&a, 63);
+        let short_b = shorten_identifier(&b, 63);
+
+        assert_eq!(short_a.len(), 63);
+        assert_eq!(short_a, shorten_identifier(&a, 63));
+        assert_ne!(short_a, short_b);
+        // Plain truncation would have merged the two
+        assert_eq!(truncate_identifier(&a, 63), truncate_identifier(&b, 63));
+        assert!(short_a.starts_with("idx_customer_order_line_item"));
+    }
+
+    #[test]
+    fn test_truncate_identifier_respects_char_boundaries() {
+        let name = format!("{}é", "a".repeat(62));
+        assert_eq!(name.len(), 64);
+        assert_eq!(truncate_identifier(&name, 63), "a".repeat(62));
+        assert!(shorten_identifier(&name, 63).len() <= 63);
+    }
+
+    #[test]
+    fn test_free_column_name_skips_taken_names() {
+        let taken: HashSet<String> = ["id", "id_1"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(free_column_name("id", &taken, 63), "id_2");
+        assert_eq!(free_column_name("ID", &HashSet::new(), 63), "ID_1");
+
+        let long = "c".repeat(63);
+        let name = free_column_name(&long, &taken, 63);
+        assert_eq!(name.len(), 63);
+        assert!(name.ends_with("_1"));
+    }
+
+    #[test]
+    fn test_namespace_reports_collisions() {
+        let mut ns = IdentifierNamespace::new(63);
+        ns.claim(IdentifierKind::Column, "orders", "a_long_name", "a_long")
+            .unwrap();
+        // The same identifier claimed twice is not a collision
+        ns.claim(IdentifierKind::Column, "orders", "A_LONG_NAME", "a_long")
+            .unwrap();
+
+        let err = ns
+            .claim(IdentifierKind::Column, "orders", "a_long_other", "A_LONG")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "column 'a_long_name' on 'orders' and column 'a_long_other' on 'orders' both become 'A_LONG' at the 63-byte identifier limit; rename one of them"
+        );
+    }
+}
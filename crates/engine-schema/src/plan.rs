@@ -1,25 +1,34 @@
 use crate::{
     dep_graph::DependencyGraph,
-    schema_ops::{SchemaOp, SchemaOps},
+    error::NamingError,
+    naming::{
+        ColumnCollision, IdentifierKind, IdentifierNamespace, RenamedIdentifier, free_column_name,
+        shorten_identifier, truncate_identifier,
+    },
+    schema_ops::{ForeignKeyRef, SchemaOp, SchemaOps},
     types::{ComputedTypes, TypeEngine},
 };
 use connectors::sql::{
-    metadata::table::TableMetadata,
+    metadata::{index::IndexMetadata, table::TableMetadata},
     query::{
         column::ColumnDef,
         constraint::{CheckConstraintDef, UniqueConstraintDef},
         fk::ForeignKeyDef,
         generator::QueryGenerator,
-        index::IndexDef,
+        index::{IndexColumnDef, IndexDef},
         sequence::SequenceDef,
     },
 };
 use model::{
-    core::types::Type, execution::expr::CompiledExpression,
+    core::{timezone::SourceTimezones, types::Type},
+    execution::{
+        expr::CompiledExpression,
+        partition::{PartitionScheme, TablePartitioning},
+    },
     transform::mapping::TransformationMetadata,
 };
 use query_builder::dialect::{self, Dialect};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tracing::warn;
 
 /// Represents the schema migration plan from source to target, including type conversion,
@@ -51,6 +60,13 @@ pub struct SchemaPlan {
     /// Foreign key creation strategy.
     fk_creation: FkCreationStrategy,
 
+    /// Longest identifier the destination keeps; `None` uses the target dialect's limit.
+    max_identifier_length: Option<usize>,
+
+    /// Create generated source columns as plain columns instead of recreating
+    /// their expressions, so the source's computed values can be copied.
+    materialize_generated: bool,
+
     /// Mapping of table names from source to target database.
     mapping: TransformationMetadata,
 
@@ -77,6 +93,17 @@ pub struct SchemaPlan {
 
     /// CHECK constraint definitions collected for each table.
     check_constraint_definitions: HashMap<String, Vec<CheckConstraintDef>>,
+
+    /// Partitioning of created tables, keyed by destination table name.
+    partitioning: HashMap<String, TablePartitioning>,
+
+    /// Column types overriding the inferred ones, keyed by destination table
+    /// and then destination column name.
+    column_types: HashMap<String, BTreeMap<String, String>>,
+
+    /// Time zones of source timestamps without an offset; such columns are
+    /// created as timestamps with a time zone, since they are loaded in UTC.
+    source_timezones: SourceTimezones,
 }
 
 /// Strategy for when indexes are created relative to data migration.
@@ -114,6 +141,8 @@ impl SchemaPlan {
             drop_constraints: false,
             index_creation: IndexCreationStrategy::default(),
             fk_creation: FkCreationStrategy::default(),
+            max_identifier_length: None,
+            materialize_generated: false,
             mapping,
             metadata_graph: HashMap::new(),
             column_definitions: HashMap::new(),
@@ -123,6 +152,9 @@ impl SchemaPlan {
             sequence_definitions: Vec::new(),
             unique_constraint_definitions: HashMap::new(),
             check_constraint_definitions: HashMap::new(),
+            partitioning: HashMap::new(),
+            column_types: HashMap::new(),
+            source_timezones: SourceTimezones::default(),
         }
     }
 
@@ -142,6 +174,45 @@ impl SchemaPlan {
         self.drop_constraints = drop;
     }
 
+    pub fn set_max_identifier_length(&mut self, max_len: Option<usize>) {
+        self.max_identifier_length = max_len;
+    }
+
+    pub fn set_materialize_generated(&mut self, materialize: bool) {
+        self.materialize_generated = materialize;
+    }
+
+    pub fn set_source_timezones(&mut self, timezones: SourceTimezones) {
+        self.source_timezones = timezones;
+    }
+
+    /// Create the destination table `table` as a partitioned table.
+    pub fn set_partitioning(&mut self, table: &str, partitioning: TablePartitioning) {
+        self.partitioning.insert(table.to_string(), partitioning);
+    }
+
+    /// Whether the destination table `table` is created as a partitioned table.
+    pub fn is_partitioned(&self, table: &str) -> bool {
+        self.partitioning.contains_key(table)
+    }
+
+    /// Create the named columns of the destination table `table` with the
+    /// given types instead of the types inferred from the source.
+    pub fn set_column_types(&mut self, table: &str, types: BTreeMap<String, String>) {
+        self.column_types.insert(table.to_string(), types);
+    }
+
+    /// Longest identifier, in bytes, the destination keeps without truncating.
+    pub fn identifier_limit(&self) -> usize {
+        self.max_identifier_length
+            .unwrap_or_else(|| self.target_dialect.max_identifier_length())
+    }
+
+    /// Name an index, constraint or sequence is created under.
+    fn object_name(&self, name: &str) -> String {
+        shorten_identifier(name, self.identifier_limit())
+    }
+
     pub fn type_engine(&self) -> &TypeEngine {
         &self.type_engine
     }
@@ -151,12 +222,32 @@ impl SchemaPlan {
             .insert(table_name.to_string(), column_defs);
     }
 
+    /// Adds `extra` to the table's columns. A column named like an existing
+    /// one retypes it instead of adding a duplicate, as a computed field
+    /// replaces the source value at runtime.
     pub fn extend_column_defs(&mut self, table_name: &str, extra: Vec<ColumnDef>) {
-        if !extra.is_empty() {
-            self.column_definitions
-                .entry(table_name.to_string())
-                .or_default()
-                .extend(extra);
+        if extra.is_empty() {
+            return;
+        }
+        let cols = self
+            .column_definitions
+            .entry(table_name.to_string())
+            .or_default();
+        for def in extra {
+            match cols
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(&def.name))
+            {
+                // Keep nullability and primary-key as-is, like plugin outputs
+                Some(existing) => {
+                    existing.data_type = def.data_type;
+                    existing.char_max_length = def.char_max_length;
+                    existing.generated_expression = None;
+                    existing.is_generated = false;
+                    existing.is_stored = false;
+                }
+                None => cols.push(def),
+            }
         }
     }
 
@@ -221,6 +312,46 @@ impl SchemaPlan {
             .extend(indexes);
     }
 
+    /// Adds the secondary indexes of source table `table`, renaming the table
+    /// and columns through the mapping and converting index types to the
+    /// target dialect. Primary keys are created with the table and skipped.
+    pub fn add_source_indexes(&mut self, table: &str, indexes: &[IndexMetadata]) {
+        let resolved_table = self.mapping.entities.resolve(table);
+        let registry = self.type_engine.type_registry();
+
+        let index_defs: Vec<IndexDef> = indexes
+            .iter()
+            .filter(|idx| !idx.is_primary)
+            .map(|idx| {
+                let columns = idx
+                    .columns
+                    .iter()
+                    .map(|col| IndexColumnDef {
+                        name: self
+                            .mapping
+                            .field_mappings
+                            .resolve(&resolved_table, &col.name),
+                        sort_order: col.sort_order.clone(),
+                        nulls_order: col.nulls_order.clone(),
+                    })
+                    .collect();
+
+                IndexDef {
+                    name: idx.name.clone(),
+                    table: resolved_table.clone(),
+                    columns,
+                    unique: idx.is_unique,
+                    index_type: Some(registry.convert_index_type(&idx.index_type)),
+                    condition: idx.condition.clone(),
+                }
+            })
+            .collect();
+
+        if !index_defs.is_empty() {
+            self.add_index_defs(table, index_defs);
+        }
+    }
+
     pub fn add_sequence(&mut self, seq: SequenceDef) {
         self.sequence_definitions.push(seq);
     }
@@ -284,49 +415,58 @@ impl SchemaPlan {
             FkCreationStrategy::BeforeData => pre.extend(self.constraint_ops()),
         }
 
-        SchemaOps { pre, post }
+        SchemaOps {
+            pre,
+            post,
+            post_load: Vec::new(),
+        }
     }
 
     /// Generate CREATE TYPE ... AS ENUM ops.
     fn enum_ops(&self) -> Vec<SchemaOp> {
         let qgen = QueryGenerator::new(self.target_dialect.as_ref());
-        let mut ops = Vec::new();
+
+        self.enum_types()
+            .into_iter()
+            .map(|(name, values)| {
+                let (sql, _) = qgen.create_enum(&name, &values);
+                SchemaOp {
+                    sql,
+                    description: format!("Create enum type '{}'", name),
+                    idempotent: true,
+                    skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Enum types the target needs created before the tables using them, as
+    /// `(name, labels)`. Empty for targets declaring enum labels inline.
+    fn enum_types(&self) -> BTreeMap<String, Vec<String>> {
+        let mut types = BTreeMap::new();
+        if !self.target_dialect.has_enum_types() {
+            return types;
+        }
 
         for (table, column) in &self.enum_definitions {
-            // Prefer full_column_type (e.g. "enum('G','PG','PG-13','R','NC-17')")
-            // over data_type (which is just "enum" from MySQL INFORMATION_SCHEMA.DATA_TYPE).
-            let enum_type = self
+            let target_type = self
                 .metadata_graph
                 .get(table)
                 .and_then(|meta| meta.columns.get(column))
-                .and_then(|col| {
-                    col.full_column_type.clone().or_else(|| {
-                        if col.data_type.contains('(') {
-                            Some(col.data_type.clone())
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .unwrap_or_default();
+                .map(|col| self.type_engine.convert_column(col).0);
 
-            if enum_type.is_empty() {
-                warn!(column = %column, table = %table, "could not find enum type for column");
-                continue;
+            match target_type {
+                Some(Type::Enum { name, values }) if !values.is_empty() => {
+                    types.entry(name).or_insert(values);
+                }
+                _ => warn!(column = %column, table = %table, "could not find enum type for column"),
             }
-
-            let variants = Self::parse_enum(&enum_type);
-            let (sql, _) = qgen.create_enum(column, &variants);
-
-            ops.push(SchemaOp {
-                sql,
-                description: format!("Create enum type '{}'", column),
-                idempotent: true,
-                skip_if_missing_ref: false,
-            });
         }
 
-        ops
+        types
     }
 
     /// Generate CREATE SEQUENCE ops.
@@ -336,12 +476,19 @@ impl SchemaPlan {
         self.sequence_definitions
             .iter()
             .map(|seq| {
-                let (sql, _) = qgen.create_sequence(seq);
+                let seq = SequenceDef {
+                    name: self.object_name(&seq.name),
+                    ..seq.clone()
+                };
+                let (sql, _) = qgen.create_sequence(&seq);
                 SchemaOp {
                     sql,
                     description: format!("Create sequence '{}'", seq.name),
                     idempotent: true,
                     skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
                 }
             })
             .collect()
@@ -373,6 +520,7 @@ impl SchemaPlan {
             if self.mapped_columns_only {
                 resolved_columns = self.filter_to_mapped_columns(&resolved_table, resolved_columns);
             }
+            self.apply_column_types(&resolved_table, &mut resolved_columns);
 
             // Computed columns are async - we handle them synchronously for build_ops
             // by using the pre-collected column defs. Callers should ensure computed
@@ -390,6 +538,9 @@ impl SchemaPlan {
                 description: format!("Create table '{}'", resolved_table),
                 idempotent: true,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: Some(resolved_table.clone()),
+                dropped_index: None,
             });
         }
 
@@ -410,12 +561,19 @@ impl SchemaPlan {
         all_indexes.sort_by_key(|idx| idx.unique);
 
         for index in all_indexes {
-            let (sql, _) = qgen.create_index(index);
+            let index = IndexDef {
+                name: self.object_name(&index.name),
+                ..index.clone()
+            };
+            let (sql, _) = qgen.create_index(&index);
             ops.push(SchemaOp {
                 sql,
                 description: format!("Create index '{}'", index.name),
                 idempotent: true,
                 skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
             });
         }
 
@@ -429,43 +587,7 @@ impl SchemaPlan {
         }
 
         let qgen = QueryGenerator::new(self.target_dialect.as_ref());
-        let mut ops = Vec::new();
-
-        for (table, fks) in &self.fk_definitions {
-            let resolved_table = self.mapping.entities.resolve(table);
-
-            for fk in fks {
-                let ref_table = self.mapping.entities.resolve(&fk.referenced_table);
-                let ref_columns: Vec<String> = fk
-                    .referenced_columns
-                    .iter()
-                    .map(|col| self.mapping.field_mappings.resolve(&ref_table, col))
-                    .collect();
-                let columns: Vec<String> = fk
-                    .columns
-                    .iter()
-                    .map(|col| self.mapping.field_mappings.resolve(&resolved_table, col))
-                    .collect();
-
-                let resolved_fk = ForeignKeyDef {
-                    constraint_name: fk.constraint_name.clone(),
-                    referenced_table: ref_table,
-                    referenced_columns: ref_columns,
-                    columns: columns.clone(),
-                    on_delete: fk.on_delete.clone(),
-                    on_update: fk.on_update.clone(),
-                };
-
-                let (sql, _) = qgen.add_foreign_key(&resolved_table, &resolved_fk);
-                let desc = fk.constraint_name.as_deref().unwrap_or("FK");
-                ops.push(SchemaOp {
-                    sql,
-                    description: format!("Add foreign key '{}' on '{}'", desc, resolved_table),
-                    idempotent: true,
-                    skip_if_missing_ref: true,
-                });
-            }
-        }
+        let mut ops = self.fk_ops();
 
         // UNIQUE constraints
         for (table, constraints) in &self.unique_constraint_definitions {
@@ -479,13 +601,13 @@ impl SchemaPlan {
                     .collect();
 
                 let resolved_uc = UniqueConstraintDef {
-                    constraint_name: uc.constraint_name.clone(),
+                    constraint_name: uc.constraint_name.as_deref().map(|n| self.object_name(n)),
                     table: resolved_table.clone(),
                     columns,
                 };
 
                 let (sql, _) = qgen.add_unique_constraint(&resolved_table, &resolved_uc);
-                let desc = uc.constraint_name.as_deref().unwrap_or("UNIQUE");
+                let desc = resolved_uc.constraint_name.as_deref().unwrap_or("UNIQUE");
                 ops.push(SchemaOp {
                     sql,
                     description: format!(
@@ -494,6 +616,9 @@ impl SchemaPlan {
                     ),
                     idempotent: true,
                     skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
                 });
             }
         }
@@ -504,18 +629,76 @@ impl SchemaPlan {
 
             for cc in constraints {
                 let resolved_cc = CheckConstraintDef {
-                    constraint_name: cc.constraint_name.clone(),
+                    constraint_name: cc.constraint_name.as_deref().map(|n| self.object_name(n)),
                     table: resolved_table.clone(),
                     expression: cc.expression.clone(),
                 };
 
                 let (sql, _) = qgen.add_check_constraint(&resolved_table, &resolved_cc);
-                let desc = cc.constraint_name.as_deref().unwrap_or("CHECK");
+                let desc = resolved_cc.constraint_name.as_deref().unwrap_or("CHECK");
                 ops.push(SchemaOp {
                     sql,
                     description: format!("Add check constraint '{}' on '{}'", desc, resolved_table),
                     idempotent: true,
                     skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
+                });
+            }
+        }
+
+        ops
+    }
+
+    /// Generate ALTER TABLE ADD CONSTRAINT ops for the foreign keys, marked
+    /// with the constraint they create so that they can be deferred.
+    pub fn fk_ops(&self) -> Vec<SchemaOp> {
+        if self.ignore_constraints {
+            return Vec::new();
+        }
+
+        let qgen = QueryGenerator::new(self.target_dialect.as_ref());
+        let mut ops = Vec::new();
+
+        for (table, fks) in &self.fk_definitions {
+            let resolved_table = self.mapping.entities.resolve(table);
+
+            for fk in fks {
+                let ref_table = self.mapping.entities.resolve(&fk.referenced_table);
+                let ref_columns: Vec<String> = fk
+                    .referenced_columns
+                    .iter()
+                    .map(|col| self.mapping.field_mappings.resolve(&ref_table, col))
+                    .collect();
+                let columns: Vec<String> = fk
+                    .columns
+                    .iter()
+                    .map(|col| self.mapping.field_mappings.resolve(&resolved_table, col))
+                    .collect();
+
+                let resolved_fk = ForeignKeyDef {
+                    constraint_name: fk.constraint_name.as_deref().map(|n| self.object_name(n)),
+                    referenced_table: ref_table,
+                    referenced_columns: ref_columns,
+                    columns: columns.clone(),
+                    on_delete: fk.on_delete.clone(),
+                    on_update: fk.on_update.clone(),
+                };
+
+                let (sql, _) = qgen.add_foreign_key(&resolved_table, &resolved_fk);
+                let desc = resolved_fk.constraint_name.as_deref().unwrap_or("FK");
+                ops.push(SchemaOp {
+                    sql,
+                    description: format!("Add foreign key '{}' on '{}'", desc, resolved_table),
+                    idempotent: true,
+                    skip_if_missing_ref: true,
+                    foreign_key: Some(ForeignKeyRef {
+                        table: resolved_table.clone(),
+                        name: resolved_fk.constraint_name,
+                    }),
+                    created_table: None,
+                    dropped_index: None,
                 });
             }
         }
@@ -533,10 +716,10 @@ impl SchemaPlan {
             let quoted_table = self.target_dialect.quote_identifier(&resolved_table);
 
             for fk in fks {
-                let Some(name) = &fk.constraint_name else {
+                let Some(name) = fk.constraint_name.as_deref().map(|n| self.object_name(n)) else {
                     continue; // can't reference anonymous constraints by name
                 };
-                let quoted_name = self.target_dialect.quote_identifier(name);
+                let quoted_name = self.target_dialect.quote_identifier(&name);
                 ops.push(SchemaOp {
                     sql: format!(
                         "ALTER TABLE {quoted_table} DROP CONSTRAINT IF EXISTS {quoted_name};"
@@ -547,6 +730,9 @@ impl SchemaPlan {
                     ),
                     idempotent: true, // IF EXISTS makes this a no-op when constraint is absent
                     skip_if_missing_ref: false,
+                    foreign_key: None,
+                    created_table: None,
+                    dropped_index: None,
                 });
             }
         }
@@ -554,6 +740,204 @@ impl SchemaPlan {
         ops
     }
 
+    /// Destination columns that two source columns would be written to,
+    /// because a column is renamed onto the name of another column that is
+    /// copied as is. Only possible when every source column is copied.
+    pub fn column_collisions(&self) -> Vec<ColumnCollision> {
+        if self.mapped_columns_only {
+            return Vec::new();
+        }
+        let max_len = self.identifier_limit();
+        let mut collisions = Vec::new();
+
+        let mut tables: Vec<&String> = self.column_definitions.keys().collect();
+        tables.sort();
+        for table in tables {
+            let resolved_table = self.mapping.entities.resolve(table);
+            let Some(renames) = self.mapping.field_mappings.get_entity(&resolved_table) else {
+                continue;
+            };
+            // A computed field replaces the value of the column it is named
+            // after, so it never adds a second one
+            let computed: HashSet<String> = self
+                .mapping
+                .field_mappings
+                .get_computed(&resolved_table)
+                .map(|fields| fields.iter().map(|f| f.name.to_ascii_lowercase()).collect())
+                .unwrap_or_default();
+
+            let columns = &self.column_definitions[table];
+            let mut taken: HashSet<String> = self
+                .resolve_column_definitions(table, columns)
+                .iter()
+                .map(|c| c.name.to_ascii_lowercase())
+                .collect();
+
+            for col in columns {
+                let name = col.name.to_ascii_lowercase();
+                if renames.source_to_target.contains_key(&name) || computed.contains(&name) {
+                    continue;
+                }
+                let Some(renamed_from) = renames
+                    .target_to_source
+                    .get(&name)
+                    .filter(|source| **source != name)
+                    .and_then(|source| {
+                        columns.iter().find(|c| c.name.eq_ignore_ascii_case(source))
+                    })
+                else {
+                    continue;
+                };
+
+                let suggested = free_column_name(&col.name, &taken, max_len);
+                taken.insert(suggested.to_ascii_lowercase());
+                collisions.push(ColumnCollision {
+                    table: resolved_table.clone(),
+                    column: col.name.clone(),
+                    renamed_from: renamed_from.name.clone(),
+                    suggested,
+                });
+            }
+        }
+
+        collisions
+    }
+
+    /// Every identifier that will not reach the destination under its own
+    /// name: indexes, constraints and sequences shortened by
+    /// [`shorten_identifier`], and tables and columns the database will
+    /// truncate. Fails when two identifiers end up with the same name,
+    /// including two source columns written to the same column.
+    pub fn identifier_report(&self) -> Result<Vec<RenamedIdentifier>, NamingError> {
+        if let Some(collision) = self.column_collisions().into_iter().next() {
+            return Err(NamingError::ColumnCollision(collision));
+        }
+
+        let max_len = self.identifier_limit();
+        let mut renamed = Vec::new();
+        let mut record = |ns: &mut IdentifierNamespace,
+                          kind: IdentifierKind,
+                          table: &str,
+                          original: &str,
+                          name: String|
+         -> Result<(), NamingError> {
+            ns.claim(kind, table, original, &name)?;
+            if name != original {
+                renamed.push(RenamedIdentifier {
+                    kind,
+                    table: table.to_string(),
+                    original: original.to_string(),
+                    renamed: name,
+                });
+            }
+            Ok(())
+        };
+
+        // Tables, indexes and sequences share the schema's relation namespace
+        let mut relations = IdentifierNamespace::new(max_len);
+
+        let mut tables: Vec<&String> = self.column_definitions.keys().collect();
+        tables.sort();
+        for table in tables {
+            let resolved_table = self.mapping.entities.resolve(table);
+            let truncated = truncate_identifier(&resolved_table, max_len).to_string();
+            record(
+                &mut relations,
+                IdentifierKind::Table,
+                &resolved_table,
+                &resolved_table,
+                truncated,
+            )?;
+
+            let mut columns = IdentifierNamespace::new(max_len);
+            for col in self.resolve_column_definitions(table, &self.column_definitions[table]) {
+                let truncated = truncate_identifier(&col.name, max_len).to_string();
+                record(
+                    &mut columns,
+                    IdentifierKind::Column,
+                    &resolved_table,
+                    &col.name,
+                    truncated,
+                )?;
+            }
+        }
+
+        let mut indexes: Vec<&IndexDef> = self.index_definitions.values().flatten().collect();
+        indexes.sort_by(|a, b| (&a.table, &a.name).cmp(&(&b.table, &b.name)));
+        for index in indexes {
+            record(
+                &mut relations,
+                IdentifierKind::Index,
+                &index.table,
+                &index.name,
+                self.object_name(&index.name),
+            )?;
+        }
+
+        for seq in &self.sequence_definitions {
+            let table = seq.owned_by.as_ref().map_or("", |(t, _)| t.as_str());
+            record(
+                &mut relations,
+                IdentifierKind::Sequence,
+                table,
+                &seq.name,
+                self.object_name(&seq.name),
+            )?;
+        }
+
+        let mut partitioned: Vec<_> = self.partitioning.iter().collect();
+        partitioned.sort_by_key(|(table, _)| *table);
+        for (table, partitioning) in partitioned {
+            for part in partitioning.partitions(table) {
+                record(
+                    &mut relations,
+                    IdentifierKind::Partition,
+                    table,
+                    &part.name,
+                    self.object_name(&part.name),
+                )?;
+            }
+        }
+
+        // Constraint names only need to be unique within their table
+        let mut constraints: HashMap<String, Vec<(IdentifierKind, &str)>> = HashMap::new();
+        if !self.ignore_constraints {
+            for (table, fks) in &self.fk_definitions {
+                let names = fks.iter().filter_map(|fk| fk.constraint_name.as_deref());
+                constraints
+                    .entry(self.mapping.entities.resolve(table))
+                    .or_default()
+                    .extend(names.map(|n| (IdentifierKind::ForeignKey, n)));
+            }
+            for (table, ucs) in &self.unique_constraint_definitions {
+                let names = ucs.iter().filter_map(|uc| uc.constraint_name.as_deref());
+                constraints
+                    .entry(self.mapping.entities.resolve(table))
+                    .or_default()
+                    .extend(names.map(|n| (IdentifierKind::UniqueConstraint, n)));
+            }
+            for (table, ccs) in &self.check_constraint_definitions {
+                let names = ccs.iter().filter_map(|cc| cc.constraint_name.as_deref());
+                constraints
+                    .entry(self.mapping.entities.resolve(table))
+                    .or_default()
+                    .extend(names.map(|n| (IdentifierKind::CheckConstraint, n)));
+            }
+        }
+
+        let mut constraints: Vec<_> = constraints.into_iter().collect();
+        constraints.sort_by(|a, b| a.0.cmp(&b.0));
+        for (table, mut names) in constraints {
+            names.sort_by_key(|(_, name)| *name);
+            let mut ns = IdentifierNamespace::new(max_len);
+            for (kind, name) in names {
+                record(&mut ns, kind, &table, name, self.object_name(name))?;
+            }
+        }
+
+        Ok(renamed)
+    }
+
     pub async fn table_queries(&self) -> HashSet<(String, String)> {
         let mut queries = HashSet::new();
 
@@ -577,13 +961,23 @@ impl SchemaPlan {
                 .filter(|col| !existing_names.contains(&col.name))
                 .collect();
             resolved_columns.extend(new_computed);
-
-            let (sql, _) = QueryGenerator::new(self.target_dialect.as_ref()).create_table(
-                &resolved_table,
-                &resolved_columns,
-                self.ignore_constraints,
-                false,
-            );
+            self.apply_column_types(&resolved_table, &mut resolved_columns);
+
+            let qgen = QueryGenerator::new(self.target_dialect.as_ref());
+            let (sql, _) = match self.partitioning.get(&resolved_table) {
+                Some(partitioning) => qgen.create_partitioned_table(
+                    &resolved_table,
+                    &resolved_columns,
+                    self.ignore_constraints,
+                    partitioning,
+                ),
+                None => qgen.create_table(
+                    &resolved_table,
+                    &resolved_columns,
+                    self.ignore_constraints,
+                    false,
+                ),
+            };
 
             queries.insert((sql, resolved_table));
         }
@@ -591,6 +985,94 @@ impl SchemaPlan {
         queries
     }
 
+    /// CREATE TABLE ... PARTITION OF for the child partitions of every
+    /// partitioned table, run after the parents are created.
+    pub fn partition_queries(&self) -> Vec<(String, String)> {
+        let qgen = QueryGenerator::new(self.target_dialect.as_ref());
+        let mut tables: Vec<_> = self.partitioning.iter().collect();
+        tables.sort_by_key(|(table, _)| *table);
+
+        tables
+            .into_iter()
+            .flat_map(|(table, partitioning)| {
+                partitioning.partitions(table).into_iter().map(|mut part| {
+                    part.name = self.object_name(&part.name);
+                    (qgen.create_partition(table, &part).0, part.name)
+                })
+            })
+            .collect()
+    }
+
+    /// Problems with the partition_by of created tables: a partition column
+    /// the table does not have, or a range column that is not a date or
+    /// timestamp.
+    pub fn partitioning_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (table, columns) in &self.column_definitions {
+            let resolved_table = self.mapping.entities.resolve(table);
+            let Some(partitioning) = self.partitioning.get(&resolved_table) else {
+                continue;
+            };
+
+            let columns = self.resolve_column_definitions(table, columns);
+            match columns.iter().find(|c| c.name == partitioning.column) {
+                None => errors.push(format!(
+                    "partition_by: table '{}' has no column '{}'",
+                    resolved_table, partitioning.column
+                )),
+                Some(col)
+                    if matches!(partitioning.scheme, PartitionScheme::Range { .. })
+                        && !matches!(col.data_type, Type::Date | Type::Timestamp { .. }) =>
+                {
+                    errors.push(format!(
+                        "partition_by: range partitioning needs a date or timestamp column, but '{}.{}' is {:?}",
+                        resolved_table, col.name, col.data_type
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        errors.sort();
+        errors
+    }
+
+    /// Columns named in a types {} block that the created table does not
+    /// have.
+    pub async fn column_type_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (table, columns) in &self.column_definitions {
+            let resolved_table = self.mapping.entities.resolve(table);
+            let Some(types) = self.column_types.get(&resolved_table) else {
+                continue;
+            };
+
+            let mut names: HashSet<String> = self
+                .resolve_column_definitions(table, columns)
+                .into_iter()
+                .map(|c| c.name)
+                .collect();
+            names.extend(
+                self.computed_column_defs(table)
+                    .await
+                    .into_iter()
+                    .map(|c| c.name),
+            );
+            errors.extend(
+                types
+                    .keys()
+                    .filter(|column| !names.contains(*column))
+                    .map(|column| {
+                        format!(
+                            "types: table '{}' has no column '{}'",
+                            resolved_table, column
+                        )
+                    }),
+            );
+        }
+        errors.sort();
+        errors
+    }
+
     pub fn fk_queries(&self) -> HashSet<(String, String)> {
         if self.ignore_constraints {
             return HashSet::new();
@@ -614,7 +1096,7 @@ impl SchemaPlan {
                         .collect();
 
                     let resolved_fk = ForeignKeyDef {
-                        constraint_name: fk.constraint_name.clone(),
+                        constraint_name: fk.constraint_name.as_deref().map(|n| self.object_name(n)),
                         referenced_table: ref_table,
                         referenced_columns: ref_columns,
                         columns: columns.clone(),
@@ -632,50 +1114,34 @@ impl SchemaPlan {
     }
 
     pub fn enum_queries(&self) -> HashSet<(String, String)> {
-        let mut queries = HashSet::new();
-
-        for (table, column) in &self.enum_definitions {
-            // Prefer full_column_type (e.g. "enum('G','PG','PG-13','R','NC-17')")
-            // over data_type (which is just "enum" from MySQL INFORMATION_SCHEMA.DATA_TYPE).
-            let enum_type = self
-                .metadata_graph
-                .get(table)
-                .and_then(|meta| meta.columns.get(column))
-                .and_then(|col| {
-                    col.full_column_type.clone().or_else(|| {
-                        if col.data_type.contains('(') {
-                            Some(col.data_type.clone())
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .unwrap_or_default();
-
-            if enum_type.is_empty() {
-                warn!(column = %column, table = %table, "could not find enum type for column");
-                continue;
-            }
-
-            let variants = Self::parse_enum(&enum_type);
-            let (sql, _) =
-                QueryGenerator::new(self.target_dialect.as_ref()).create_enum(column, &variants);
-
-            queries.insert((sql, column.clone()));
-        }
+        let qgen = QueryGenerator::new(self.target_dialect.as_ref());
 
-        queries
+        self.enum_types()
+            .into_iter()
+            .map(|(name, values)| (qgen.create_enum(&name, &values).0, name))
+            .collect()
     }
 
     pub fn index_queries(&self) -> Vec<(String, String)> {
         let qgen = QueryGenerator::new(self.target_dialect.as_ref());
 
-        self.index_definitions
+        // Non-unique indexes first, as in `index_ops`
+        let mut all_indexes: Vec<&IndexDef> = self
+            .index_definitions
             .values()
             .flat_map(|idxs| idxs.iter())
+            .collect();
+        all_indexes.sort_by_key(|idx| idx.unique);
+
+        all_indexes
+            .into_iter()
             .map(|index| {
-                let (sql, _) = qgen.create_index(index);
-                (sql, index.name.clone())
+                let index = IndexDef {
+                    name: self.object_name(&index.name),
+                    ..index.clone()
+                };
+                let (sql, _) = qgen.create_index(&index);
+                (sql, index.name)
             })
             .collect()
     }
@@ -763,9 +1229,12 @@ impl SchemaPlan {
             .into_iter()
             .map(|col| {
                 let (data_type, char_max_length) = self.type_engine.convert_column(&col);
+                let data_type = self.source_timezones.column_type(&col.name, data_type);
+                let is_generated = col.is_generated && !self.materialize_generated;
                 let generated_expression = col
                     .generated_expression
                     .as_deref()
+                    .filter(|_| is_generated)
                     .map(|e| self.type_engine.normalize_generated_expression(e));
                 ColumnDef {
                     name: col.name.clone(),
@@ -775,8 +1244,8 @@ impl SchemaPlan {
                     default: col.default_value.clone(),
                     char_max_length,
                     generated_expression,
-                    is_stored: col.is_stored,
-                    is_generated: col.is_generated,
+                    is_stored: col.is_stored && is_generated,
+                    is_generated,
                 }
             })
             .collect()
@@ -885,6 +1354,7 @@ impl SchemaPlan {
                 .filter(|col| !existing_names.contains(&col.name))
                 .collect();
             resolved_columns.extend(new_computed);
+            self.apply_column_types(&resolved_table, &mut resolved_columns);
             resolved_defs.extend(resolved_columns);
         }
 
@@ -949,19 +1419,18 @@ impl SchemaPlan {
             .collect()
     }
 
-    fn parse_enum(raw: &str) -> Vec<String> {
-        let start = raw.find('(').map(|i| i + 1).unwrap_or(0);
-        let end = raw.rfind(')').unwrap_or(raw.len());
-
-        if start > end {
-            return vec![];
+    /// Replaces the inferred type of columns named in the types {} block of
+    /// `table`; the type given there is written into the DDL as is.
+    fn apply_column_types(&self, table: &str, columns: &mut [ColumnDef]) {
+        let Some(types) = self.column_types.get(table) else {
+            return;
+        };
+        for col in columns.iter_mut() {
+            if let Some(data_type) = types.get(&col.name) {
+                col.data_type = column_type(data_type);
+                col.char_max_length = None;
+            }
         }
-
-        raw[start..end]
-            .split(',')
-            .map(|s| s.trim().trim_matches('\'').to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
     }
 
     fn filter_to_mapped_columns(&self, table: &str, columns: Vec<ColumnDef>) -> Vec<ColumnDef> {
@@ -984,6 +1453,14 @@ impl SchemaPlan {
     }
 }
 
+/// Destination type written by the user, rendered verbatim by every dialect.
+pub fn column_type(data_type: &str) -> Type {
+    Type::Unknown {
+        source_name: data_type.to_string(),
+        fallback_ddl: data_type.to_string(),
+    }
+}
+
 /// Rewrite column name references inside a SQL expression (e.g. a generated column body).
 /// Performs whole-word replacement so `rental_rate` is not matched inside `original_rental_rate`.
 fn rewrite_column_refs(
@@ -6,16 +6,15 @@ use crate::{
 use connectors::{
     error::DriverError,
     sql::{
-        metadata::{index::IndexMetadata, table::TableMetadata},
+        metadata::table::TableMetadata,
         query::{
             constraint::{CheckConstraintDef, UniqueConstraintDef},
-            index::{IndexColumnDef, IndexDef},
             sequence::SequenceDef,
         },
     },
     traits::introspector::SchemaIntrospector,
 };
-use model::transform::mapping::TransformationMetadata;
+use model::{core::timezone::SourceTimezones, transform::mapping::TransformationMetadata};
 use std::sync::Arc;
 
 /// Responsible for orchestrating metadata retrieval and populating a robust SchemaPlan.
@@ -26,6 +25,9 @@ pub struct SchemaPlanner {
     ignore_constraints: bool,
     mapped_columns_only: bool,
     type_registry: Arc<TypeRegistry>,
+    max_identifier_length: Option<usize>,
+    materialize_generated: bool,
+    source_timezones: SourceTimezones,
 }
 
 impl SchemaPlanner {
@@ -44,9 +46,31 @@ impl SchemaPlanner {
             ignore_constraints,
             mapped_columns_only,
             type_registry: Arc::new(type_registry),
+            max_identifier_length: None,
+            materialize_generated: false,
+            source_timezones: SourceTimezones::default(),
         }
     }
 
+    /// Overrides the destination's identifier length limit for planned schema.
+    pub fn with_max_identifier_length(mut self, max_len: Option<usize>) -> Self {
+        self.max_identifier_length = max_len;
+        self
+    }
+
+    /// Plans the source's generated columns as plain columns.
+    pub fn with_materialize_generated(mut self, materialize: bool) -> Self {
+        self.materialize_generated = materialize;
+        self
+    }
+
+    /// Plans source timestamps without an offset that are converted to UTC
+    /// as timestamps with a time zone.
+    pub fn with_source_timezones(mut self, timezones: SourceTimezones) -> Self {
+        self.source_timezones = timezones;
+        self
+    }
+
     /// Primary entry point: Orchestrates the construction of a SchemaPlan for a source table.
     pub async fn plan_schema(&self, table: &str) -> Result<SchemaPlan, DriverError> {
         let meta = self.introspector.table_metadata(table).await?;
@@ -55,7 +79,7 @@ impl SchemaPlanner {
         let mut plan = self.init_plan()?;
 
         self.add_table_details(&mut plan, table, &meta);
-        self.add_index_details(&mut plan, table, &indexes);
+        plan.add_source_indexes(table, &indexes);
         self.add_sequence_details(&mut plan, table, &meta);
         self.add_constraint_details(&mut plan, table).await?;
 
@@ -77,12 +101,16 @@ impl SchemaPlanner {
             self.source_dialect,
         );
 
-        Ok(SchemaPlan::new(
+        let mut plan = SchemaPlan::new(
             type_engine,
             self.ignore_constraints,
             self.mapped_columns_only,
             self.mapping.clone(),
-        ))
+        );
+        plan.set_max_identifier_length(self.max_identifier_length);
+        plan.set_materialize_generated(self.materialize_generated);
+        plan.set_source_timezones(self.source_timezones.clone());
+        Ok(plan)
     }
 
     /// Helper to populate SchemaPlan with table definitions.
@@ -107,44 +135,6 @@ impl SchemaPlanner {
         }
     }
 
-    /// Populate SchemaPlan with index definitions from introspected metadata.
-    /// Converts source `IndexType` to target dialect via TypeRegistry.
-    fn add_index_details(&self, plan: &mut SchemaPlan, table: &str, indexes: &[IndexMetadata]) {
-        let resolved_table = self.mapping.entities.resolve(table);
-
-        let index_defs: Vec<IndexDef> = indexes
-            .iter()
-            .filter(|idx| !idx.is_primary)
-            .map(|idx| {
-                let columns = idx
-                    .columns
-                    .iter()
-                    .map(|col| IndexColumnDef {
-                        name: self
-                            .mapping
-                            .field_mappings
-                            .resolve(&resolved_table, &col.name),
-                        sort_order: col.sort_order.clone(),
-                        nulls_order: col.nulls_order.clone(),
-                    })
-                    .collect();
-
-                IndexDef {
-                    name: idx.name.clone(),
-                    table: resolved_table.clone(),
-                    columns,
-                    unique: idx.is_unique,
-                    index_type: Some(self.type_registry.convert_index_type(&idx.index_type)),
-                    condition: idx.condition.clone(),
-                }
-            })
-            .collect();
-
-        if !index_defs.is_empty() {
-            plan.add_index_defs(table, index_defs);
-        }
-    }
-
     /// Populate SchemaPlan with UNIQUE and CHECK constraint definitions from introspected metadata.
     async fn add_constraint_details(
         &self,
@@ -1,3 +1,5 @@
+use query_builder::dialect::{Dialect, Postgres};
+
 /// A single schema operation (DDL statement) to execute against the destination.
 #[derive(Debug, Clone)]
 pub struct SchemaOp {
@@ -10,16 +12,43 @@ pub struct SchemaOp {
     /// If true, "relation does not exist" errors are silently skipped.
     /// Used for FK constraints where the referenced table may not be in the destination.
     pub skip_if_missing_ref: bool,
+    /// The foreign key an `ADD CONSTRAINT` op creates, so that it can be deferred.
+    pub foreign_key: Option<ForeignKeyRef>,
+    /// The table a `CREATE TABLE` op creates, so that it can be created unlogged.
+    /// `None` for partitioned tables and partitions, which cannot be.
+    pub created_table: Option<String>,
+    /// The index a `DROP INDEX` op drops for the load, journaled before the
+    /// drop so that a resumed run can rebuild it.
+    pub dropped_index: Option<DroppedIndex>,
+}
+
+/// Destination table and constraint name of a foreign key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyRef {
+    pub table: String,
+    /// `None` for anonymous constraints, which cannot be validated by name.
+    pub name: Option<String>,
+}
+
+/// A destination index dropped before the load and the statement that
+/// rebuilds it afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedIndex {
+    pub table: String,
+    pub name: String,
+    pub rebuild_sql: String,
 }
 
-/// Collected schema operations split into pre-migration and post-migration phases.
+/// Collected schema operations split into pre-migration, post-migration and post-load phases.
 ///
 /// Pre-migration ops run before data transfer (CREATE ENUM, CREATE TABLE, ADD COLUMN).
-/// Post-migration ops run after data transfer (ALTER TABLE ADD CONSTRAINT for FKs, CREATE INDEX).
+/// Post-migration ops run after the pipeline's data transfer (ALTER TABLE ADD CONSTRAINT
+/// for FKs, CREATE INDEX). Post-load ops run once every pipeline of the run has loaded.
 #[derive(Debug, Clone, Default)]
 pub struct SchemaOps {
     pub pre: Vec<SchemaOp>,
     pub post: Vec<SchemaOp>,
+    pub post_load: Vec<SchemaOp>,
 }
 
 impl SchemaOps {
@@ -28,12 +57,293 @@ impl SchemaOps {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.pre.is_empty() && self.post.is_empty()
+        self.pre.is_empty() && self.post.is_empty() && self.post_load.is_empty()
     }
 
-    /// Merge another `SchemaOps` into this one, appending both pre and post ops.
+    /// Merge another `SchemaOps` into this one, appending the ops of every phase.
     pub fn merge(&mut self, other: SchemaOps) {
         self.pre.extend(other.pre);
         self.post.extend(other.post);
+        self.post_load.extend(other.post_load);
+    }
+
+    /// Move the foreign keys of the post-migration phase to the post-load phase,
+    /// so that no table is loaded with a foreign key in place.
+    ///
+    /// With `not_valid` (Postgres), named keys are added `NOT VALID`, which
+    /// skips checking the loaded rows, and then validated by a separate
+    /// `VALIDATE CONSTRAINT` that does not block writes to the table.
+    pub fn defer_foreign_keys(&mut self, not_valid: bool) {
+        let (deferred, post) = std::mem::take(&mut self.post)
+            .into_iter()
+            .partition(|op| op.foreign_key.is_some());
+        self.post = post;
+
+        let deferred: Vec<SchemaOp> = deferred;
+        if !not_valid {
+            self.post_load.extend(deferred);
+            return;
+        }
+
+        let mut validations = Vec::new();
+        for op in deferred {
+            let Some(ForeignKeyRef {
+                table,
+                name: Some(name),
+            }) = op.foreign_key.clone()
+            else {
+                self.post_load.push(op);
+                continue;
+            };
+
+            let sql = op.sql.trim_end().trim_end_matches(';');
+            self.post_load.push(SchemaOp {
+                sql: format!("{sql} NOT VALID;"),
+                description: format!("{} (not valid)", op.description),
+                ..op
+            });
+            validations.push(SchemaOp {
+                sql: format!(
+                    "ALTER TABLE {} VALIDATE CONSTRAINT {};",
+                    Postgres.quote_identifier(&table),
+                    Postgres.quote_identifier(&name)
+                ),
+                description: format!("Validate foreign key '{name}' on '{table}'"),
+                idempotent: false,
+                skip_if_missing_ref: true,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
+            });
+        }
+        self.post_load.extend(validations);
+    }
+
+    /// Create the tables of the pre-migration phase `UNLOGGED` (Postgres) and
+    /// switch them, along with `also`, back to logged at the start of the
+    /// post-migration phase, before indexes and foreign keys are added.
+    ///
+    /// Writes to an unlogged table skip the WAL, but the table is emptied if
+    /// the server crashes before it is switched. The switch only rewrites
+    /// tables that are still unlogged, so `also` can name tables an earlier,
+    /// paused run created. Returns the number of tables created unlogged.
+    pub fn load_unlogged(&mut self, also: &[String]) -> usize {
+        let mut tables = Vec::new();
+        for op in &mut self.pre {
+            let Some(table) = &op.created_table else {
+                continue;
+            };
+            if let Some(rest) = op.sql.strip_prefix("CREATE TABLE ") {
+                op.sql = format!("CREATE UNLOGGED TABLE {rest}");
+                op.description = format!("{} (unlogged)", op.description);
+                tables.push(table.clone());
+            }
+        }
+        let created = tables.len();
+
+        for table in also {
+            if !tables.contains(table) {
+                tables.push(table.clone());
+            }
+        }
+        let switches = tables.into_iter().map(|table| {
+            let quoted = Postgres.quote_identifier(&table);
+            SchemaOp {
+                sql: format!(
+                    "DO $$ BEGIN IF EXISTS (SELECT 1 FROM pg_class WHERE oid = to_regclass('{}') AND relpersistence = 'u') THEN ALTER TABLE {quoted} SET LOGGED; END IF; END $$;",
+                    quoted.replace('\'', "''")
+                ),
+                description: format!("Switch table '{table}' to logged"),
+                idempotent: false,
+                skip_if_missing_ref: false,
+                foreign_key: None,
+                created_table: None,
+                dropped_index: None,
+            }
+        });
+        self.post.splice(0..0, switches);
+
+        created
+    }
+
+    /// Drop an existing index of `table` before the load and rebuild it
+    /// `CONCURRENTLY` (Postgres) at the end of the post-migration phase, so
+    /// that the load does not maintain it row by row.
+    ///
+    /// `definition` is the index's `pg_get_indexdef`. Returns false, leaving
+    /// the ops as they are, for a definition other than a plain
+    /// `CREATE INDEX`.
+    pub fn defer_index(&mut self, table: &str, schema: &str, name: &str, definition: &str) -> bool {
+        let Some(rest) = definition.strip_prefix("CREATE INDEX ") else {
+            return false;
+        };
+        let rebuild_sql = format!("CREATE INDEX CONCURRENTLY IF NOT EXISTS {rest};");
+
+        self.pre.push(SchemaOp {
+            sql: format!(
+                "DROP INDEX IF EXISTS {}.{};",
+                Postgres.quote_identifier(schema),
+                Postgres.quote_identifier(name)
+            ),
+            description: format!("Drop index '{name}' on '{table}' for the load"),
+            idempotent: false,
+            skip_if_missing_ref: false,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: Some(DroppedIndex {
+                table: table.to_string(),
+                name: name.to_string(),
+                rebuild_sql: rebuild_sql.clone(),
+            }),
+        });
+        self.post.push(SchemaOp {
+            sql: rebuild_sql,
+            description: format!("Rebuild index '{name}' on '{table}'"),
+            idempotent: true,
+            skip_if_missing_ref: false,
+            foreign_key: None,
+            created_table: None,
+            dropped_index: None,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(sql: &str, foreign_key: Option<ForeignKeyRef>) -> SchemaOp {
+        SchemaOp {
+            sql: sql.to_string(),
+            description: sql.to_string(),
+            idempotent: true,
+            skip_if_missing_ref: foreign_key.is_some(),
+            foreign_key,
+            created_table: None,
+            dropped_index: None,
+        }
+    }
+
+    fn ops() -> SchemaOps {
+        SchemaOps {
+            pre: vec![op("CREATE TABLE orders ();", None)],
+            post: vec![
+                op("CREATE INDEX idx ON orders (id);", None),
+                op(
+                    "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id);",
+                    Some(ForeignKeyRef {
+                        table: "orders".to_string(),
+                        name: Some("fk_user".to_string()),
+                    }),
+                ),
+                op(
+                    "ALTER TABLE orders ADD FOREIGN KEY (item_id) REFERENCES items (id);",
+                    Some(ForeignKeyRef {
+                        table: "orders".to_string(),
+                        name: None,
+                    }),
+                ),
+            ],
+            post_load: Vec::new(),
+        }
+    }
+
+    fn sql(ops: &[SchemaOp]) -> Vec<&str> {
+        ops.iter().map(|op| op.sql.as_str()).collect()
+    }
+
+    #[test]
+    fn test_defer_foreign_keys_moves_them_to_post_load() {
+        let mut ops = ops();
+        ops.defer_foreign_keys(false);
+
+        assert_eq!(ops.pre.len(), 1);
+        assert_eq!(sql(&ops.post), vec!["CREATE INDEX idx ON orders (id);"]);
+        assert_eq!(
+            sql(&ops.post_load),
+            vec![
+                "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id);",
+                "ALTER TABLE orders ADD FOREIGN KEY (item_id) REFERENCES items (id);",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_unlogged_switches_tables_before_post_ops() {
+        let mut ops = ops();
+        ops.pre[0].created_table = Some("orders".to_string());
+        ops.pre.push(op(
+            "CREATE TABLE events_p0 PARTITION OF events FOR VALUES WITH (MODULUS 2, REMAINDER 0);",
+            None,
+        ));
+
+        let created = ops.load_unlogged(&["orders".to_string(), "users".to_string()]);
+
+        assert_eq!(created, 1);
+        assert_eq!(
+            sql(&ops.pre),
+            vec![
+                "CREATE UNLOGGED TABLE orders ();",
+                "CREATE TABLE events_p0 PARTITION OF events FOR VALUES WITH (MODULUS 2, REMAINDER 0);",
+            ]
+        );
+        assert_eq!(ops.post.len(), 5);
+        assert!(
+            ops.post[0]
+                .sql
+                .contains(r#"ALTER TABLE "orders" SET LOGGED"#)
+        );
+        assert!(ops.post[1].sql.contains(r#"to_regclass('"users"')"#));
+        assert_eq!(ops.post[2].sql, "CREATE INDEX idx ON orders (id);");
+    }
+
+    #[test]
+    fn test_defer_index_drops_before_and_rebuilds_after() {
+        let mut ops = ops();
+        assert!(ops.defer_index(
+            "orders",
+            "public",
+            "idx_orders_user",
+            "CREATE INDEX idx_orders_user ON public.orders USING btree (user_id)",
+        ));
+        assert!(!ops.defer_index(
+            "orders",
+            "public",
+            "uq_orders_ref",
+            "CREATE UNIQUE INDEX uq_orders_ref ON public.orders USING btree (ref)",
+        ));
+
+        let drop = ops.pre.last().unwrap();
+        assert_eq!(
+            drop.sql,
+            r#"DROP INDEX IF EXISTS "public"."idx_orders_user";"#
+        );
+        let rebuild = ops.post.last().unwrap();
+        assert_eq!(
+            rebuild.sql,
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_orders_user ON public.orders USING btree (user_id);"
+        );
+        assert_eq!(
+            drop.dropped_index.as_ref().map(|i| &i.rebuild_sql),
+            Some(&rebuild.sql)
+        );
+        assert_eq!(ops.pre.len(), 2);
+        assert_eq!(ops.post.len(), 4);
+    }
+
+    #[test]
+    fn test_defer_foreign_keys_not_valid_validates_named_keys() {
+        let mut ops = ops();
+        ops.defer_foreign_keys(true);
+
+        assert_eq!(
+            sql(&ops.post_load),
+            vec![
+                "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) NOT VALID;",
+                "ALTER TABLE orders ADD FOREIGN KEY (item_id) REFERENCES items (id);",
+                r#"ALTER TABLE "orders" VALIDATE CONSTRAINT "fk_user";"#,
+            ]
+        );
     }
 }
@@ -275,10 +275,19 @@ mod tests {
         let registry = TypeRegistry::new(Dialect::MySql, Dialect::Postgres);
 
         let enum_type = Type::Enum {
-            name: String::new(),
+            name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
         };
         let result = registry.convert(&enum_type);
+        assert!(result.is_exact());
+        assert_eq!(result.target_type(), enum_type);
+
+        // Without labels there is no type to create
+        let unlabeled = Type::Enum {
+            name: "status".to_string(),
+            values: vec![],
+        };
+        let result = registry.convert(&unlabeled);
         assert!(matches!(result, ConversionResult::RequiresTransform { .. }));
     }
 
@@ -181,36 +181,118 @@ impl TypeInferencer for ExpressionWrapper {
             }),
 
             CompiledExpression::Binary { left, right, .. } => {
-                let lt = ExpressionWrapper((**left).clone())
-                    .infer_type(
-                        columns,
-                        computed_types,
-                        mapping,
-                        introspector,
-                        source_dialect,
-                    )
-                    .await?;
-                let rt = ExpressionWrapper((**right).clone())
-                    .infer_type(
-                        columns,
-                        computed_types,
-                        mapping,
-                        introspector,
-                        source_dialect,
-                    )
-                    .await?;
-                Some(get_numeric_type(&lt.0, &rt.0))
+                let lt = match json_field_operand(left) {
+                    Some(ty) => ty,
+                    None => {
+                        ExpressionWrapper((**left).clone())
+                            .infer_type(
+                                columns,
+                                computed_types,
+                                mapping,
+                                introspector,
+                                source_dialect,
+                            )
+                            .await?
+                            .0
+                    }
+                };
+                let rt = match json_field_operand(right) {
+                    Some(ty) => ty,
+                    None => {
+                        ExpressionWrapper((**right).clone())
+                            .infer_type(
+                                columns,
+                                computed_types,
+                                mapping,
+                                introspector,
+                                source_dialect,
+                            )
+                            .await?
+                            .0
+                    }
+                };
+                Some(get_numeric_type(&lt, &rt))
             }
 
-            CompiledExpression::FunctionCall { name, .. } => {
+            CompiledExpression::FunctionCall { name, args } => {
                 match name.to_ascii_lowercase().as_str() {
-                    "lower" | "upper" | "concat" => Some((
+                    "lower" | "upper" | "concat" | "json_get" | "format_date" | "regex_extract"
+                    | "regex_replace" | "sha256" | "md5" | "hmac" | "mask" | "fake_name"
+                    | "fake_email" | "fake_phone" => Some((
                         Type::Varchar {
                             length: None,
                             charset: None,
                         },
                         None,
                     )),
+                    // The type of the first value in the lookup table
+                    "map_values" => {
+                        let CompiledExpression::Literal(Value::Json(table)) = args.get(1)? else {
+                            return None;
+                        };
+                        let value = table.as_object()?.values().find(|v| !v.is_null())?;
+                        ExpressionWrapper(CompiledExpression::Literal(Value::from_json(value)))
+                            .infer_type(
+                                columns,
+                                computed_types,
+                                mapping,
+                                introspector,
+                                source_dialect,
+                            )
+                            .await
+                    }
+                    "regex_match" => Some((Type::Boolean, None)),
+                    "uuid_v4" | "uuid_v5" => Some((Type::Uuid, None)),
+                    "now" => Some((
+                        Type::Timestamp {
+                            precision: None,
+                            with_tz: true,
+                        },
+                        None,
+                    )),
+                    // Truncating or shifting keeps the type of the date or timestamp
+                    "date_trunc" | "date_add" => {
+                        let ts = match name.to_ascii_lowercase().as_str() {
+                            "date_trunc" => args.get(1)?,
+                            _ => args.first()?,
+                        };
+                        ExpressionWrapper(ts.clone())
+                            .infer_type(
+                                columns,
+                                computed_types,
+                                mapping,
+                                introspector,
+                                source_dialect,
+                            )
+                            .await
+                    }
+                    // The type of the first value the conditional can return
+                    "if" | "coalesce" | "nullif" => {
+                        let skip = usize::from(name.eq_ignore_ascii_case("if"));
+                        for arg in args.iter().skip(skip) {
+                            let ty = ExpressionWrapper(arg.clone())
+                                .infer_type(
+                                    columns,
+                                    computed_types,
+                                    mapping,
+                                    introspector,
+                                    source_dialect,
+                                )
+                                .await;
+                            if ty.is_some() {
+                                return ty;
+                            }
+                        }
+                        None
+                    }
+                    "extract" | "row_number" => Some((
+                        Type::Int {
+                            bits: IntSize::I64,
+                            unsigned: false,
+                            auto_increment: false,
+                        },
+                        None,
+                    )),
                     "env" => Some((
                         Type::Varchar {
                             length: None,
@@ -312,6 +394,19 @@ impl TypeInferencer for ExpressionWrapper {
     }
 }
 
+/// A JSON field used in arithmetic is read as a number; on its own it is a
+/// string.
+fn json_field_operand(expr: &CompiledExpression) -> Option<Type> {
+    match expr {
+        CompiledExpression::FunctionCall { name, .. } if name.eq_ignore_ascii_case("json_get") => {
+            Some(Type::Float {
+                bits: FloatSize::F64,
+            })
+        }
+        _ => None,
+    }
+}
+
 fn get_numeric_type(left: &Type, right: &Type) -> (Type, Option<usize>) {
     let default_int = Type::Int {
         bits: IntSize::I64,
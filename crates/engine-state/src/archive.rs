@@ -0,0 +1,307 @@
+use crate::{
+    backend::StateBackend,
+    error::StateStoreError,
+    models::{Checkpoint, RunRecord, RunState, WalEntry},
+};
+use chrono::{DateTime, Utc};
+use model::integrity::receipt::VerificationReceipt;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+/// Version of the archive layout written by `RunArchive::write`
+pub const ARCHIVE_FORMAT: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const RUN: &str = "run.json";
+const CHECKPOINTS: &str = "checkpoints.json";
+const WAL: &str = "wal.json";
+const HISTORY: &str = "history.json";
+const RECEIPTS: &str = "receipts.json";
+const WORK_QUEUE: &str = "work_queue.bin";
+
+/// zstd level of written archives; state compresses well at the default
+const ZSTD_LEVEL: i32 = 0;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    run_id: String,
+    exported_at: DateTime<Utc>,
+}
+
+/// Everything a state store holds for one run, so that the run can be
+/// resumed from another store or kept for later inspection.
+///
+/// Written as a zstd-compressed tar of JSON files, so an archive can also be
+/// read with `tar --zstd -xf`. Key crosswalks are shared by all runs and are
+/// not part of it.
+#[derive(Debug, Clone)]
+pub struct RunArchive {
+    pub run: RunState,
+    pub checkpoints: Vec<Checkpoint>,
+    pub wal: Vec<WalEntry>,
+    /// Run history entries of the run's executions
+    pub records: Vec<RunRecord>,
+    /// Integrity receipts the run wrote
+    pub receipts: Vec<VerificationReceipt>,
+    /// Encoded work queue of a distributed run
+    pub work_queue: Option<Vec<u8>>,
+}
+
+impl RunArchive {
+    /// Reads the state of `run_id` from `store`; `None` when the store has
+    /// no such run.
+    pub async fn collect(
+        store: &dyn StateBackend,
+        run_id: &str,
+    ) -> Result<Option<Self>, StateStoreError> {
+        let Some(run) = store.load_run_state(run_id).await? else {
+            return Ok(None);
+        };
+        let records = store
+            .list_run_records()
+            .await?
+            .into_iter()
+            .filter(|r| r.run_id == run_id)
+            .collect();
+        let receipts = store
+            .list_receipts()
+            .await?
+            .into_iter()
+            .filter(|r| r.run_id == run_id)
+            .collect();
+
+        Ok(Some(Self {
+            checkpoints: store.list_checkpoints(run_id).await?,
+            wal: store.iter_wal(run_id).await?,
+            work_queue: store.load_queue(run_id).await?,
+            run,
+            records,
+            receipts,
+        }))
+    }
+
+    /// Writes the run's state into `store`. The store must not hold state
+    /// for the run already; see `StateStore::delete_run`.
+    pub async fn restore(&self, store: &dyn StateBackend) -> Result<(), StateStoreError> {
+        let run_id = &self.run.run_id;
+        for checkpoint in &self.checkpoints {
+            store.save_checkpoint(checkpoint).await?;
+        }
+        for entry in &self.wal {
+            store.append_wal(entry).await?;
+        }
+        for record in &self.records {
+            store.save_run_record(record).await?;
+        }
+        for receipt in &self.receipts {
+            store.save_receipt(receipt).await?;
+        }
+        if let Some(queue) = &self.work_queue
+            && !store.swap_queue(run_id, None, queue).await?
+        {
+            return Err(StateStoreError::Archive(format!(
+                "run '{run_id}' already has a work queue"
+            )));
+        }
+        // Saved last, so an interrupted import leaves no run to resume
+        store.save_run_state(&self.run).await
+    }
+
+    /// Writes the archive to `out`.
+    pub fn write(&self, out: impl Write) -> Result<(), StateStoreError> {
+        let manifest = Manifest {
+            format: ARCHIVE_FORMAT,
+            run_id: self.run.run_id.clone(),
+            exported_at: Utc::now(),
+        };
+        let mtime = manifest.exported_at.timestamp().max(0) as u64;
+
+        let encoder = zstd::Encoder::new(out, ZSTD_LEVEL).map_err(archive_err)?;
+        let mut tar = tar::Builder::new(encoder);
+        let mut append = |path: &str, data: Vec<u8>| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(mtime);
+            header.set_cksum();
+            tar.append_data(&mut header, path, data.as_slice())
+                .map_err(archive_err)
+        };
+        append(MANIFEST, to_json(&manifest)?)?;
+        append(RUN, to_json(&self.run)?)?;
+        append(CHECKPOINTS, to_json(&self.checkpoints)?)?;
+        append(WAL, to_json(&self.wal)?)?;
+        append(HISTORY, to_json(&self.records)?)?;
+        append(RECEIPTS, to_json(&self.receipts)?)?;
+        if let Some(queue) = &self.work_queue {
+            append(WORK_QUEUE, queue.clone())?;
+        }
+
+        let encoder = tar.into_inner().map_err(archive_err)?;
+        encoder.finish().map_err(archive_err)?;
+        Ok(())
+    }
+
+    /// Reads an archive written by `write`.
+    pub fn read(input: impl Read) -> Result<Self, StateStoreError> {
+        let decoder = zstd::Decoder::new(input).map_err(archive_err)?;
+        let mut tar = tar::Archive::new(decoder);
+        let mut files = HashMap::new();
+        for entry in tar.entries().map_err(archive_err)? {
+            let mut entry = entry.map_err(archive_err)?;
+            let path = entry.path().map_err(archive_err)?;
+            let path = path.to_string_lossy().into_owned();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).map_err(archive_err)?;
+            files.insert(path, data);
+        }
+
+        let manifest: Manifest = from_json(&files, MANIFEST)?;
+        if manifest.format != ARCHIVE_FORMAT {
+            return Err(StateStoreError::Archive(format!(
+                "unsupported archive format {} (this version reads format {ARCHIVE_FORMAT})",
+                manifest.format
+            )));
+        }
+        let run: RunState = from_json(&files, RUN)?;
+        if run.run_id != manifest.run_id {
+            return Err(StateStoreError::Archive(format!(
+                "{RUN} holds run '{}', but the manifest names '{}'",
+                run.run_id, manifest.run_id
+            )));
+        }
+
+        Ok(Self {
+            run,
+            checkpoints: from_json(&files, CHECKPOINTS)?,
+            wal: from_json(&files, WAL)?,
+            records: from_json(&files, HISTORY)?,
+            receipts: from_json(&files, RECEIPTS)?,
+            work_queue: files.remove(WORK_QUEUE),
+        })
+    }
+}
+
+fn archive_err(e: std::io::Error) -> StateStoreError {
+    StateStoreError::Archive(e.to_string())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, StateStoreError> {
+    serde_json::to_vec_pretty(value).map_err(|e| StateStoreError::Serialization(e.to_string()))
+}
+
+fn from_json<T: DeserializeOwned>(
+    files: &HashMap<String, Vec<u8>>,
+    path: &str,
+) -> Result<T, StateStoreError> {
+    let bytes = files
+        .get(path)
+        .ok_or_else(|| StateStoreError::Archive(format!("archive has no {path}")))?;
+    serde_json::from_slice(bytes)
+        .map_err(|e| StateStoreError::Serialization(format!("{path}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{CheckpointStage, ConfigFingerprint, RunStatus},
+        sled_store::SledStateStore,
+        store::StateStore,
+        work_queue::WorkQueue,
+    };
+    use model::pagination::cursor::Cursor;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn archive_moves_a_run_between_stores() {
+        let now = Utc::now();
+        let run = RunState {
+            run_id: "run-1".into(),
+            config_path: "migration.smql".into(),
+            config_hash: "abc".into(),
+            fingerprint: ConfigFingerprint::default(),
+            status: RunStatus::Running,
+            started_at: now,
+            total_pipelines: 1,
+            pipelines: Vec::new(),
+        };
+        let checkpoint = Checkpoint {
+            run_id: "run-1".into(),
+            item_id: "item".into(),
+            part_id: "part".into(),
+            stage: CheckpointStage::Committed,
+            src_offset: Cursor::Default { offset: 500 },
+            pending_offset: None,
+            batch_id: "batch-5".into(),
+            rows_done: 500,
+            updated_at: now,
+        };
+
+        let dir = tempdir().unwrap();
+        let source = SledStateStore::open(dir.path().join("source")).unwrap();
+        source.save_run_state(&run).await.unwrap();
+        source.save_checkpoint(&checkpoint).await.unwrap();
+        source
+            .append_wal(&WalEntry::RunStart {
+                run_id: "run-1".into(),
+                plan_hash: "abc".into(),
+            })
+            .await
+            .unwrap();
+        source
+            .save_run_record(&RunRecord::new(&run, false, now))
+            .await
+            .unwrap();
+        source.swap_queue("run-1", None, b"queue").await.unwrap();
+        // Another run's state stays behind
+        source
+            .save_run_state(&RunState {
+                run_id: "run-2".into(),
+                ..run.clone()
+            })
+            .await
+            .unwrap();
+
+        let archive = RunArchive::collect(&source, "run-1")
+            .await
+            .unwrap()
+            .unwrap();
+        let mut bytes = Vec::new();
+        archive.write(&mut bytes).unwrap();
+        assert!(
+            RunArchive::collect(&source, "run-9")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let target = SledStateStore::open(dir.path().join("target")).unwrap();
+        RunArchive::read(bytes.as_slice())
+            .unwrap()
+            .restore(&target)
+            .await
+            .unwrap();
+
+        let restored = target.load_run_state("run-1").await.unwrap().unwrap();
+        assert_eq!(restored.config_hash, "abc");
+        let restored = target
+            .load_checkpoint("run-1", "item", "part")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.batch_id, "batch-5");
+        assert_eq!(restored.rows_done, 500);
+        assert_eq!(target.iter_wal("run-1").await.unwrap().len(), 1);
+        assert_eq!(target.list_run_records().await.unwrap().len(), 1);
+        assert_eq!(
+            target.load_queue("run-1").await.unwrap().as_deref(),
+            Some(b"queue".as_slice())
+        );
+        assert_eq!(target.list_runs().await.unwrap().len(), 1);
+    }
+}
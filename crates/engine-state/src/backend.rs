@@ -0,0 +1,49 @@
+use crate::{
+    error::StateStoreError, keymap_store::KeymapStore, merkle_store::MerkleStore,
+    redis_store::RedisStateStore, sled_store::SledStateStore, store::StateStore,
+    work_queue::WorkQueue,
+};
+use model::execution::state_config::StateConfig;
+use std::{path::Path, sync::Arc};
+
+/// A store for run state, integrity receipts, key crosswalks and the
+/// distributed work queue, as used by the engine.
+pub trait StateBackend: StateStore + MerkleStore + KeymapStore + WorkQueue {}
+
+impl<T: StateStore + MerkleStore + KeymapStore + WorkQueue> StateBackend for T {}
+
+pub type SharedStateStore = Arc<dyn StateBackend>;
+
+/// Opens the state backend selected by `config`.
+/// `default_path` is used by the local backend when no path is configured.
+pub async fn open_state_store(
+    config: &StateConfig,
+    default_path: &Path,
+) -> Result<SharedStateStore, StateStoreError> {
+    match config {
+        StateConfig::Local => open_sled(default_path),
+        StateConfig::LocalPath(path) => open_sled(Path::new(path)),
+        StateConfig::Redis {
+            url,
+            key_prefix,
+            ttl,
+        } => {
+            let store = RedisStateStore::connect(url, key_prefix, *ttl)
+                .await
+                .map_err(|e| {
+                    StateStoreError::Storage(format!("Failed to connect to Redis state store: {e}"))
+                })?;
+            Ok(Arc::new(store))
+        }
+    }
+}
+
+fn open_sled(path: &Path) -> Result<SharedStateStore, StateStoreError> {
+    let store = SledStateStore::open(path).map_err(|e| {
+        StateStoreError::Storage(format!(
+            "Failed to open state store at {}: {e}",
+            path.display()
+        ))
+    })?;
+    Ok(Arc::new(store))
+}
@@ -19,4 +19,10 @@ pub enum StateStoreError {
 
     #[error("Storage error: {0}")]
     Storage(String),
+
+    #[error("Batch acknowledgement rejected: {0}")]
+    Ack(String),
+
+    #[error("State archive error: {0}")]
+    Archive(String),
 }
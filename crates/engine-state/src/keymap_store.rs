@@ -0,0 +1,22 @@
+use crate::error::StateStoreError;
+use async_trait::async_trait;
+use model::core::value::Value;
+use std::collections::HashMap;
+
+/// Persistence layer for key crosswalks: the destination key each source
+/// key of a table was loaded under, recorded by pipelines with
+/// `emit_keymap = true` and read by `lookup_key()`.
+///
+/// Crosswalks are kept across runs, so a later run can look up keys loaded
+/// by an earlier one. Keys are stored by their text, so `42` and `"42"`
+/// are the same key.
+#[async_trait]
+pub trait KeymapStore: Send + Sync {
+    /// Record the new keys of `table`'s rows, by their old key. A key
+    /// recorded again is overwritten.
+    async fn save_keys(&self, table: &str, keys: &[(String, Value)])
+    -> Result<(), StateStoreError>;
+
+    /// Every key recorded for `table`, by old key.
+    async fn load_keys(&self, table: &str) -> Result<HashMap<String, Value>, StateStoreError>;
+}
@@ -1,8 +1,17 @@
+pub mod archive;
+pub mod backend;
 pub mod error;
+pub mod keymap_store;
 pub mod merkle_store;
 pub mod models;
+pub mod redis_store;
 pub mod sled_store;
 pub mod store;
+pub mod work_queue;
 
+pub use archive::RunArchive;
+pub use backend::{SharedStateStore, StateBackend, open_state_store};
+pub use keymap_store::KeymapStore;
 pub use merkle_store::MerkleStore;
 pub use store::StateStore;
+pub use work_queue::WorkQueue;
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use model::pagination::cursor::Cursor;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CheckpointStage {
@@ -119,6 +120,53 @@ pub enum WalEntry {
         failures: u32,
         last_error: String,
     },
+    /// A destination column widened to fit a batch (`auto_widen_columns`)
+    ColumnWidened {
+        run_id: String,
+        item_id: String,
+        part_id: String,
+        table: String,
+        column: String,
+        from: String,
+        to: String,
+        sql: String,
+    },
+    ViewRefreshed {
+        run_id: String,
+        view: String,
+        attempts: u32,
+    },
+    /// An operator approved the run's gate with `stratum approve`
+    GateApproved {
+        run_id: String,
+        gate: String,
+        at: DateTime<Utc>,
+    },
+    /// A pipeline's schema op that runs once every pipeline has loaded
+    SchemaOpDeferred {
+        run_id: String,
+        item_id: String,
+        /// Destination connection the op runs on
+        connection: String,
+        sql: String,
+        description: String,
+        idempotent: bool,
+        skip_if_missing_ref: bool,
+        /// The op changes only its own table, like an index rebuild, so it
+        /// runs even when a pipeline of the run failed
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        table_only: bool,
+    },
+    /// A destination index dropped for the load (`defer_indexes`), recorded
+    /// before the drop so that a resumed run rebuilds it
+    IndexDropped {
+        run_id: String,
+        item_id: String,
+        table: String,
+        index: String,
+        /// Statement that rebuilds the index
+        sql: String,
+    },
 }
 
 impl WalEntry {
@@ -134,8 +182,35 @@ impl WalEntry {
             WalEntry::ItemStart { run_id, .. } => run_id,
             WalEntry::Heartbeat { run_id, .. } => run_id,
             WalEntry::CircuitBreakerOpen { run_id, .. } => run_id,
+            WalEntry::ColumnWidened { run_id, .. } => run_id,
+            WalEntry::ViewRefreshed { run_id, .. } => run_id,
+            WalEntry::GateApproved { run_id, .. } => run_id,
+            WalEntry::SchemaOpDeferred { run_id, .. } => run_id,
+            WalEntry::IndexDropped { run_id, .. } => run_id,
         }
     }
+
+    /// Entries since the run last started from scratch; a resumed run
+    /// continues the same stretch.
+    pub fn since_run_start(wal: &[WalEntry]) -> &[WalEntry] {
+        let start = wal
+            .iter()
+            .rposition(|e| matches!(e, WalEntry::RunStart { .. }))
+            .unwrap_or(0);
+        &wal[start..]
+    }
+
+    /// Gates approved since the run last started from scratch. A resumed
+    /// run keeps its approvals; a new run of the same config needs new ones.
+    pub fn approved_gates(wal: &[WalEntry]) -> HashSet<String> {
+        Self::since_run_start(wal)
+            .iter()
+            .filter_map(|e| match e {
+                WalEntry::GateApproved { gate, .. } => Some(gate.clone()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -160,6 +235,11 @@ pub enum PauseReason {
     Manual,
     /// Time limit reached (--run-for, --run-until)
     TimeLimit,
+    /// Waiting at an approval gate (`stratum approve`)
+    Approval { gate: String },
+    /// A destination table was altered during the run; resume once it has
+    /// been reconciled
+    SchemaDrift { table: String, changes: Vec<String> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -167,12 +247,138 @@ pub struct RunState {
     pub run_id: String,
     pub config_path: String,
     pub config_hash: String,
+    /// What the config resolved to when the run last started or resumed
+    #[serde(default)]
+    pub fingerprint: ConfigFingerprint,
     pub status: RunStatus,
     pub started_at: DateTime<Utc>,
     pub total_pipelines: usize,
     pub pipelines: Vec<PipelineRunState>,
 }
 
+/// What a run's config resolved to, kept with the run so that a resume can
+/// tell what was edited since. Environment values are only kept as hashes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigFingerprint {
+    /// Hash of each pipeline's plan, in plan order
+    pub pipelines: Vec<(String, String)>,
+    /// Hash of each connection, by name
+    pub connections: BTreeMap<String, String>,
+    /// Hash of the value of each environment variable the config reads, by
+    /// variable name
+    pub env: BTreeMap<String, String>,
+}
+
+impl ConfigFingerprint {
+    /// Whether the fingerprint was recorded; runs saved before fingerprints
+    /// were kept have an empty one.
+    pub fn is_recorded(&self) -> bool {
+        !self.pipelines.is_empty()
+    }
+
+    /// Edits in `current` that change what a run already underway writes:
+    /// a connection or environment variable with another value, and a
+    /// `started` pipeline that was changed, removed or moved, since its
+    /// checkpoints are keyed by its position. Pipelines that have not
+    /// started may change freely.
+    pub fn incompatible_changes(
+        &self,
+        current: &ConfigFingerprint,
+        started: &HashSet<String>,
+    ) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        for (idx, (name, hash)) in self.pipelines.iter().enumerate() {
+            if !started.contains(name) {
+                continue;
+            }
+            match current.pipelines.iter().position(|(n, _)| n == name) {
+                None => changes.push(format!("pipeline '{name}' was removed")),
+                Some(pos) if pos != idx => changes.push(format!(
+                    "pipeline '{name}' moved from position {} to {}",
+                    idx + 1,
+                    pos + 1
+                )),
+                Some(pos) if current.pipelines[pos].1 != *hash => {
+                    changes.push(format!("pipeline '{name}' changed"))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut changed =
+            |kind: &str, before: &BTreeMap<String, String>, after: &BTreeMap<String, String>| {
+                for (name, hash) in before {
+                    match after.get(name) {
+                        None => changes.push(format!("{kind} '{name}' was removed")),
+                        Some(h) if h != hash => changes.push(format!("{kind} '{name}' changed")),
+                        Some(_) => {}
+                    }
+                }
+                for name in after.keys().filter(|n| !before.contains_key(*n)) {
+                    changes.push(format!("{kind} '{name}' was added"));
+                }
+            };
+        changed("connection", &self.connections, &current.connections);
+        changed("environment variable", &self.env, &current.env);
+
+        changes
+    }
+}
+
+/// One execution of a migration, kept in the run history.
+///
+/// `RunState` is keyed by the plan-derived `run_id` and is overwritten by every
+/// later run of the same config; a `RunRecord` is written once per execution
+/// (resumes included) and survives `stratum reset`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Unique per execution: `<start time in ms>-<plan hash prefix>`, sortable
+    /// by start time. An id already in the history gets a `-<n>` suffix.
+    pub id: String,
+    pub run_id: String,
+    pub config_path: String,
+    pub plan_hash: String,
+    /// What the config resolved to for this execution
+    #[serde(default)]
+    pub fingerprint: ConfigFingerprint,
+    pub status: RunStatus,
+    /// Whether this execution resumed a paused run.
+    pub resumed: bool,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Cumulative rows written across the run, including earlier executions it resumed.
+    pub rows_migrated: u64,
+    pub pipelines: Vec<PipelineRunState>,
+}
+
+impl RunRecord {
+    pub fn new(state: &RunState, resumed: bool, started_at: DateTime<Utc>) -> Self {
+        let hash_prefix = &state.config_hash[..state.config_hash.len().min(8)];
+        Self {
+            id: format!("{}-{}", started_at.format("%Y%m%d%H%M%S%3f"), hash_prefix),
+            run_id: state.run_id.clone(),
+            config_path: state.config_path.clone(),
+            plan_hash: state.config_hash.clone(),
+            fingerprint: state.fingerprint.clone(),
+            status: state.status.clone(),
+            resumed,
+            started_at,
+            ended_at: None,
+            rows_migrated: state.pipelines.iter().map(|p| p.rows_done).sum(),
+            pipelines: state.pipelines.clone(),
+        }
+    }
+
+    /// Refresh the record from the final run state.
+    pub fn finish(&mut self, state: &RunState, status: RunStatus, ended_at: DateTime<Utc>) {
+        self.status = status;
+        self.ended_at = Some(ended_at);
+        self.rows_migrated = state.pipelines.iter().map(|p| p.rows_done).sum();
+        self.pipelines = state.pipelines.clone();
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineRunState {
     pub name: String,
@@ -190,3 +396,93 @@ pub enum PipelineStatus {
     Failed { error: String },
     Blocked, // waiting for another pipeline to complete
 }
+
+/// One unit of a distributed run, published to the work queue and claimed
+/// by a worker: a pipeline, or one part of a pipeline split across workers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkItem {
+    pub pipeline: String,
+    pub item_id: String,
+    pub kind: WorkKind,
+    /// Pipelines that must be done, every item of them, before this item
+    /// can be claimed.
+    pub depends_on: Vec<String>,
+    pub status: WorkStatus,
+    /// Worker that last claimed the item.
+    pub worker_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkItem {
+    pub fn new(pipeline: &str, item_id: &str, depends_on: Vec<String>) -> Self {
+        Self {
+            pipeline: pipeline.to_string(),
+            item_id: item_id.to_string(),
+            kind: WorkKind::Pipeline,
+            depends_on,
+            status: WorkStatus::Pending,
+            worker_id: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// A part of this pipeline's item, pending.
+    pub fn part(&self, kind: WorkKind) -> Self {
+        Self {
+            kind,
+            status: WorkStatus::Pending,
+            worker_id: None,
+            updated_at: Utc::now(),
+            ..self.clone()
+        }
+    }
+
+    /// Identifies the item within its run's queue.
+    pub fn key(&self) -> String {
+        match &self.kind {
+            WorkKind::Pipeline => self.pipeline.clone(),
+            WorkKind::Range { id, .. } => format!("{}/part-{id}", self.pipeline),
+            WorkKind::Finish => format!("{}/finish", self.pipeline),
+        }
+    }
+}
+
+/// What a work item runs.
+///
+/// Every pipeline is published as a `Pipeline` item. A worker claiming one
+/// of a pipeline read in key ranges (`parallel_partitions`) runs its schema
+/// changes, seeds and before hooks, then publishes a `Range` item per key
+/// range and a `Finish` item in its place, so the ranges are read by
+/// whichever workers claim them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkKind {
+    /// The whole pipeline, or the steps before its load when it splits.
+    Pipeline,
+    /// Keys `[start, end)` of a split pipeline, checkpointed as `part-{id}`.
+    Range { id: usize, start: u64, end: u64 },
+    /// Large values, post-load schema changes, sequence sync and after hooks
+    /// of a split pipeline, claimable once all its ranges are done.
+    Finish,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkStatus {
+    Pending,
+    /// Held by `WorkItem::worker_id` until the lease runs out; an expired
+    /// lease means the worker died and the item can be claimed again.
+    Claimed {
+        lease_until: DateTime<Utc>,
+    },
+    Done {
+        rows: u64,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl WorkStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, WorkStatus::Done { .. } | WorkStatus::Failed { .. })
+    }
+}
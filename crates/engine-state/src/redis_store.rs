@@ -0,0 +1,438 @@
+use crate::error::StateStoreError;
+use crate::keymap_store::KeymapStore;
+use crate::merkle_store::MerkleStore;
+use crate::models::{Checkpoint, CheckpointStage, RunRecord, RunState, WalEntry};
+use crate::store::StateStore;
+use crate::work_queue::WorkQueue;
+use async_trait::async_trait;
+use model::{core::value::Value, integrity::receipt::VerificationReceipt};
+use redis::{
+    AsyncCommands, Script,
+    aio::{ConnectionManager, ConnectionManagerConfig},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{collections::HashMap, time::Duration};
+
+/// Bounds connection attempts so an unreachable server fails fast instead of
+/// retrying with backoff.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const CONNECT_RETRIES: usize = 2;
+const CONNECT_MAX_DELAY_MS: u64 = 1_000;
+
+/// Applies the same "don't step back over an uncommitted batch" rule as the
+/// sled transaction in `SledStateStore::save_checkpoint`, atomically.
+///
+/// KEYS[1] = checkpoint hash
+/// ARGV    = encoded checkpoint, batch id, stage, committed stage, ttl (0 = none)
+const SAVE_CHECKPOINT_SCRIPT: &str = r"
+local batch = redis.call('HGET', KEYS[1], 'batch')
+if batch then
+  local stage = tonumber(redis.call('HGET', KEYS[1], 'stage'))
+  local new_stage = tonumber(ARGV[3])
+  if batch == ARGV[2] then
+    if new_stage < stage then return 0 end
+  elseif stage ~= tonumber(ARGV[4]) then
+    return 0
+  end
+end
+redis.call('HSET', KEYS[1], 'data', ARGV[1], 'batch', ARGV[2], 'stage', ARGV[3])
+if tonumber(ARGV[5]) > 0 then redis.call('EXPIRE', KEYS[1], ARGV[5]) end
+return 1
+";
+
+/// Compare-and-swap for a run's work queue.
+///
+/// KEYS[1] = queue key
+/// ARGV    = expected present (1/0), expected value, new value, ttl (0 = none)
+const SWAP_QUEUE_SCRIPT: &str = r"
+local current = redis.call('GET', KEYS[1])
+if ARGV[1] == '1' then
+  if current ~= ARGV[2] then return 0 end
+elseif current then
+  return 0
+end
+redis.call('SET', KEYS[1], ARGV[3])
+if tonumber(ARGV[4]) > 0 then redis.call('EXPIRE', KEYS[1], ARGV[4]) end
+return 1
+";
+
+/// State store backed by a Redis server, for deployments without a
+/// persistent disk.
+///
+/// Checkpoints, WAL entries and run state expire after `ttl` seconds without
+/// a write; run history, integrity receipts and key crosswalks never expire.
+pub struct RedisStateStore {
+    conn: ConnectionManager,
+    prefix: String,
+    ttl: Option<u64>,
+    save_checkpoint: Script,
+    swap_queue: Script,
+}
+
+impl RedisStateStore {
+    pub async fn connect(
+        url: &str,
+        prefix: &str,
+        ttl: Option<u64>,
+    ) -> Result<Self, redis::RedisError> {
+        let client = redis::Client::open(url)?;
+        let config = ConnectionManagerConfig::new()
+            .set_connection_timeout(CONNECT_TIMEOUT)
+            .set_number_of_retries(CONNECT_RETRIES)
+            .set_factor(2)
+            .set_max_delay(CONNECT_MAX_DELAY_MS);
+        let conn = client.get_connection_manager_with_config(config).await?;
+        Ok(Self {
+            conn,
+            prefix: prefix.to_string(),
+            ttl,
+            save_checkpoint: Script::new(SAVE_CHECKPOINT_SCRIPT),
+            swap_queue: Script::new(SWAP_QUEUE_SCRIPT),
+        })
+    }
+
+    /// Namespaces a key under the configured prefix
+    #[inline]
+    fn key(&self, key: &str) -> String {
+        format!("{}:{}", self.prefix, key)
+    }
+
+    #[inline]
+    fn chk_key(&self, run_id: &str, item_id: &str, part_id: &str) -> String {
+        self.key(&format!("chk:{}:{}:{}", run_id, item_id, part_id))
+    }
+
+    /// Expiry in seconds as passed to Redis; 0 disables it.
+    fn ttl_secs(&self) -> u64 {
+        self.ttl.unwrap_or(0)
+    }
+
+    /// Loads every value stored under `{prefix}:{kind}:{id}` for the ids in an
+    /// index set, dropping index entries whose value has expired.
+    async fn load_indexed<T: DeserializeOwned>(
+        &self,
+        index: &str,
+        kind: &str,
+        decode: fn(&[u8]) -> Result<T, StateStoreError>,
+    ) -> Result<Vec<T>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let index_key = self.key(index);
+        let mut ids: Vec<String> = conn.smembers(&index_key).await.map_err(storage)?;
+        ids.sort();
+
+        let mut values = Vec::with_capacity(ids.len());
+        for id in ids {
+            let bytes: Option<Vec<u8>> = conn
+                .get(self.key(&format!("{kind}:{id}")))
+                .await
+                .map_err(storage)?;
+            match bytes {
+                Some(bytes) => values.push(decode(&bytes)?),
+                None => {
+                    let _: () = conn.srem(&index_key, &id).await.map_err(storage)?;
+                }
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+fn storage(e: redis::RedisError) -> StateStoreError {
+    StateStoreError::Storage(e.to_string())
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, StateStoreError> {
+    bincode::serialize(value).map_err(|e| StateStoreError::Serialization(e.to_string()))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StateStoreError> {
+    bincode::deserialize(bytes).map_err(|e| StateStoreError::Serialization(e.to_string()))
+}
+
+fn decode_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StateStoreError> {
+    serde_json::from_slice(bytes).map_err(|e| StateStoreError::Serialization(e.to_string()))
+}
+
+#[async_trait]
+impl StateStore for RedisStateStore {
+    async fn save_checkpoint(&self, cp: &Checkpoint) -> Result<(), StateStoreError> {
+        let key = self.chk_key(&cp.run_id, &cp.item_id, &cp.part_id);
+        let bytes = encode(cp)?;
+
+        let mut conn = self.conn.clone();
+        let _: i32 = self
+            .save_checkpoint
+            .key(key)
+            .arg(bytes)
+            .arg(&cp.batch_id)
+            .arg(cp.stage as u8)
+            .arg(CheckpointStage::Committed as u8)
+            .arg(self.ttl_secs())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(storage)?;
+        Ok(())
+    }
+
+    async fn load_checkpoint(
+        &self,
+        run_id: &str,
+        item_id: &str,
+        part_id: &str,
+    ) -> Result<Option<Checkpoint>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let bytes: Option<Vec<u8>> = conn
+            .hget(self.chk_key(run_id, item_id, part_id), "data")
+            .await
+            .map_err(storage)?;
+        bytes.map(|b| decode(&b)).transpose()
+    }
+
+    async fn list_checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>, StateStoreError> {
+        let mut conn = self.conn.clone();
+
+        // Checkpoint keys are only known by pattern
+        let pattern = self.chk_key(run_id, "*", "*");
+        let mut chk_keys = Vec::new();
+        {
+            let mut iter: redis::AsyncIter<String> =
+                conn.scan_match(&pattern).await.map_err(storage)?;
+            while let Some(key) = iter.next_item().await {
+                chk_keys.push(key);
+            }
+        }
+        chk_keys.sort();
+
+        let mut checkpoints = Vec::with_capacity(chk_keys.len());
+        for key in chk_keys {
+            let bytes: Option<Vec<u8>> = conn.hget(&key, "data").await.map_err(storage)?;
+            if let Some(bytes) = bytes {
+                checkpoints.push(decode(&bytes)?);
+            }
+        }
+        Ok(checkpoints)
+    }
+
+    async fn append_wal(&self, entry: &WalEntry) -> Result<(), StateStoreError> {
+        let key = self.key(&format!("wal:{}", entry.run_id()));
+        let bytes = encode(entry)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().rpush(&key, bytes).ignore();
+        if let Some(ttl) = self.ttl {
+            pipe.expire(&key, ttl as i64).ignore();
+        }
+
+        let mut conn = self.conn.clone();
+        let _: () = pipe.query_async(&mut conn).await.map_err(storage)?;
+        Ok(())
+    }
+
+    async fn iter_wal(&self, run_id: &str) -> Result<Vec<WalEntry>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let items: Vec<Vec<u8>> = conn
+            .lrange(self.key(&format!("wal:{}", run_id)), 0, -1)
+            .await
+            .map_err(storage)?;
+        items.iter().map(|b| decode(b)).collect()
+    }
+
+    async fn save_run_state(&self, state: &RunState) -> Result<(), StateStoreError> {
+        let key = self.key(&format!("run:{}", state.run_id));
+        let bytes = encode(state)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        match self.ttl {
+            Some(ttl) => pipe.set_ex(&key, bytes, ttl).ignore(),
+            None => pipe.set(&key, bytes).ignore(),
+        };
+        pipe.sadd(self.key("runs"), &state.run_id).ignore();
+
+        let mut conn = self.conn.clone();
+        let _: () = pipe.query_async(&mut conn).await.map_err(storage)?;
+        Ok(())
+    }
+
+    async fn load_run_state(&self, run_id: &str) -> Result<Option<RunState>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let bytes: Option<Vec<u8>> = conn
+            .get(self.key(&format!("run:{}", run_id)))
+            .await
+            .map_err(storage)?;
+        bytes.map(|b| decode(&b)).transpose()
+    }
+
+    async fn list_runs(&self) -> Result<Vec<RunState>, StateStoreError> {
+        self.load_indexed("runs", "run", decode).await
+    }
+
+    async fn delete_run(&self, run_id: &str) -> Result<(), StateStoreError> {
+        let mut conn = self.conn.clone();
+
+        // Checkpoint keys are only known by pattern
+        let pattern = self.chk_key(run_id, "*", "*");
+        let mut chk_keys = Vec::new();
+        {
+            let mut iter: redis::AsyncIter<String> =
+                conn.scan_match(&pattern).await.map_err(storage)?;
+            while let Some(key) = iter.next_item().await {
+                chk_keys.push(key);
+            }
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .del(self.key(&format!("run:{}", run_id)))
+            .ignore()
+            .del(self.key(&format!("wal:{}", run_id)))
+            .ignore()
+            .del(self.key(&format!("work:{}", run_id)))
+            .ignore()
+            .srem(self.key("runs"), run_id)
+            .ignore();
+        if !chk_keys.is_empty() {
+            pipe.del(chk_keys).ignore();
+        }
+
+        let _: () = pipe.query_async(&mut conn).await.map_err(storage)?;
+        Ok(())
+    }
+
+    async fn save_run_record(&self, record: &RunRecord) -> Result<(), StateStoreError> {
+        let bytes = encode(record)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .set(self.key(&format!("history:{}", record.id)), bytes)
+            .ignore()
+            .sadd(self.key("history"), &record.id)
+            .ignore();
+
+        let mut conn = self.conn.clone();
+        let _: () = pipe.query_async(&mut conn).await.map_err(storage)?;
+        Ok(())
+    }
+
+    async fn load_run_record(&self, id: &str) -> Result<Option<RunRecord>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let bytes: Option<Vec<u8>> = conn
+            .get(self.key(&format!("history:{}", id)))
+            .await
+            .map_err(storage)?;
+        bytes.map(|b| decode(&b)).transpose()
+    }
+
+    async fn list_run_records(&self) -> Result<Vec<RunRecord>, StateStoreError> {
+        // Record ids start with the start time, so sorted ids are chronological.
+        self.load_indexed("history", "history", decode).await
+    }
+}
+
+#[async_trait]
+impl MerkleStore for RedisStateStore {
+    async fn save_receipt(&self, receipt: &VerificationReceipt) -> Result<(), StateStoreError> {
+        let id = format!("{}:{}", receipt.pipeline_name, receipt.table_name);
+        let value = serde_json::to_vec(receipt)
+            .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .set(self.key(&format!("receipt:{}", id)), value)
+            .ignore()
+            .sadd(self.key("receipts"), &id)
+            .ignore();
+
+        let mut conn = self.conn.clone();
+        let _: () = pipe.query_async(&mut conn).await.map_err(storage)?;
+        Ok(())
+    }
+
+    async fn load_receipt(
+        &self,
+        pipeline_name: &str,
+        table_name: &str,
+    ) -> Result<Option<VerificationReceipt>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let bytes: Option<Vec<u8>> = conn
+            .get(self.key(&format!("receipt:{}:{}", pipeline_name, table_name)))
+            .await
+            .map_err(storage)?;
+        bytes.map(|b| decode_json(&b)).transpose()
+    }
+
+    async fn list_receipts(&self) -> Result<Vec<VerificationReceipt>, StateStoreError> {
+        self.load_indexed("receipts", "receipt", decode_json).await
+    }
+}
+
+#[async_trait]
+impl KeymapStore for RedisStateStore {
+    async fn save_keys(
+        &self,
+        table: &str,
+        keys: &[(String, Value)],
+    ) -> Result<(), StateStoreError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let fields = keys
+            .iter()
+            .map(|(old, new)| {
+                serde_json::to_vec(new)
+                    .map(|value| (old.as_str(), value))
+                    .map_err(|e| StateStoreError::Serialization(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut conn = self.conn.clone();
+        let _: () = conn
+            .hset_multiple(self.key(&format!("keymap:{}", table)), &fields)
+            .await
+            .map_err(storage)?;
+        Ok(())
+    }
+
+    async fn load_keys(&self, table: &str) -> Result<HashMap<String, Value>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let fields: HashMap<String, Vec<u8>> = conn
+            .hgetall(self.key(&format!("keymap:{}", table)))
+            .await
+            .map_err(storage)?;
+        fields
+            .into_iter()
+            .map(|(old, bytes)| Ok((old, decode_json(&bytes)?)))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl WorkQueue for RedisStateStore {
+    async fn load_queue(&self, run_id: &str) -> Result<Option<Vec<u8>>, StateStoreError> {
+        let mut conn = self.conn.clone();
+        conn.get(self.key(&format!("work:{}", run_id)))
+            .await
+            .map_err(storage)
+    }
+
+    async fn swap_queue(
+        &self,
+        run_id: &str,
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, StateStoreError> {
+        let mut conn = self.conn.clone();
+        let swapped: i32 = self
+            .swap_queue
+            .key(self.key(&format!("work:{}", run_id)))
+            .arg(u8::from(expected.is_some()))
+            .arg(expected.unwrap_or_default())
+            .arg(new)
+            .arg(self.ttl_secs())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(storage)?;
+        Ok(swapped == 1)
+    }
+}
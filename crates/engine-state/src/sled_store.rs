@@ -1,11 +1,13 @@
 use crate::error::StateStoreError;
+use crate::keymap_store::KeymapStore;
 use crate::merkle_store::MerkleStore;
-use crate::models::{Checkpoint, CheckpointStage, RunState, WalEntry};
+use crate::models::{Checkpoint, CheckpointStage, RunRecord, RunState, WalEntry};
 use crate::store::StateStore;
+use crate::work_queue::WorkQueue;
 use async_trait::async_trait;
-use model::integrity::receipt::VerificationReceipt;
+use model::{core::value::Value, integrity::receipt::VerificationReceipt};
 use sled::transaction::{ConflictableTransactionError, TransactionError};
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 pub struct SledStateStore {
     db: sled::Db,
@@ -89,6 +91,20 @@ impl StateStore for SledStateStore {
         }
     }
 
+    async fn list_checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>, StateStoreError> {
+        let prefix = format!("chk:{}:", run_id);
+        let mut checkpoints = Vec::new();
+
+        for item in self.db.scan_prefix(prefix) {
+            let (_key, value) = item.map_err(|e| StateStoreError::Storage(e.to_string()))?;
+            let checkpoint: Checkpoint = bincode::deserialize(&value)
+                .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+            checkpoints.push(checkpoint);
+        }
+
+        Ok(checkpoints)
+    }
+
     async fn append_wal(&self, entry: &WalEntry) -> Result<(), StateStoreError> {
         let seq = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
         let key = format!("wal:{}:{}", entry.run_id(), seq);
@@ -169,6 +185,11 @@ impl StateStore for SledStateStore {
                 .map_err(|e| StateStoreError::Storage(e.to_string()))?;
         }
 
+        // Delete the distributed work queue, if any
+        self.db
+            .remove(format!("work:{}", run_id))
+            .map_err(|e| StateStoreError::Storage(e.to_string()))?;
+
         // Delete all WAL entries for this run
         for item in self.db.scan_prefix(format!("wal:{}:", run_id)) {
             let (key, _) = item.map_err(|e| StateStoreError::Storage(e.to_string()))?;
@@ -183,6 +204,47 @@ impl StateStore for SledStateStore {
 
         Ok(())
     }
+
+    async fn save_run_record(&self, record: &RunRecord) -> Result<(), StateStoreError> {
+        let key = format!("history:{}", record.id);
+        let value = bincode::serialize(record)
+            .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+        self.db
+            .insert(key, value)
+            .map_err(|e| StateStoreError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn load_run_record(&self, id: &str) -> Result<Option<RunRecord>, StateStoreError> {
+        let key = format!("history:{}", id);
+        match self
+            .db
+            .get(key)
+            .map_err(|e| StateStoreError::Storage(e.to_string()))?
+        {
+            Some(bytes) => {
+                Ok(Some(bincode::deserialize(&bytes).map_err(|e| {
+                    StateStoreError::Serialization(e.to_string())
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn list_run_records(&self) -> Result<Vec<RunRecord>, StateStoreError> {
+        // Record ids start with the start time, so key order is chronological.
+        let prefix = "history:";
+        let mut records = Vec::new();
+
+        for item in self.db.scan_prefix(prefix) {
+            let (_key, value) = item.map_err(|e| StateStoreError::Storage(e.to_string()))?;
+            let record: RunRecord = bincode::deserialize(&value)
+                .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
 }
 
 #[async_trait]
@@ -230,6 +292,62 @@ impl MerkleStore for SledStateStore {
     }
 }
 
+#[async_trait]
+impl KeymapStore for SledStateStore {
+    async fn save_keys(
+        &self,
+        table: &str,
+        keys: &[(String, Value)],
+    ) -> Result<(), StateStoreError> {
+        let mut batch = sled::Batch::default();
+        for (old, new) in keys {
+            let value = serde_json::to_vec(new)
+                .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+            batch.insert(format!("keymap:{}:{}", table, old).as_bytes(), value);
+        }
+        self.db
+            .apply_batch(batch)
+            .map_err(|e| StateStoreError::Storage(e.to_string()))
+    }
+
+    async fn load_keys(&self, table: &str) -> Result<HashMap<String, Value>, StateStoreError> {
+        let prefix = format!("keymap:{}:", table);
+        let mut keys = HashMap::new();
+        for item in self.db.scan_prefix(&prefix) {
+            let (key, value) = item.map_err(|e| StateStoreError::Storage(e.to_string()))?;
+            let old = String::from_utf8_lossy(&key[prefix.len()..]).into_owned();
+            let new: Value = serde_json::from_slice(&value)
+                .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+            keys.insert(old, new);
+        }
+        Ok(keys)
+    }
+}
+
+#[async_trait]
+impl WorkQueue for SledStateStore {
+    async fn load_queue(&self, run_id: &str) -> Result<Option<Vec<u8>>, StateStoreError> {
+        Ok(self
+            .db
+            .get(format!("work:{}", run_id))
+            .map_err(|e| StateStoreError::Storage(e.to_string()))?
+            .map(|bytes| bytes.to_vec()))
+    }
+
+    async fn swap_queue(
+        &self,
+        run_id: &str,
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, StateStoreError> {
+        let swapped = self
+            .db
+            .compare_and_swap(format!("work:{}", run_id), expected, Some(new))
+            .map_err(|e| StateStoreError::Storage(e.to_string()))?;
+        Ok(swapped.is_ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +424,234 @@ mod tests {
         assert_eq!(cp.stage, CheckpointStage::Read);
         assert_eq!(cp.batch_id, "batch-2");
     }
+
+    #[tokio::test]
+    async fn run_history_outlives_reset() {
+        use crate::models::{RunState, RunStatus};
+        use chrono::{TimeZone, Utc};
+
+        let dir = tempdir().unwrap();
+        let store = SledStateStore::open(dir.path()).unwrap();
+
+        let state = RunState {
+            run_id: "run".into(),
+            config_path: "migration.smql".into(),
+            config_hash: "0123456789abcdef".into(),
+            fingerprint: Default::default(),
+            status: RunStatus::Running,
+            started_at: Utc::now(),
+            total_pipelines: 0,
+            pipelines: vec![],
+        };
+        store.save_run_state(&state).await.unwrap();
+
+        let later = Utc.with_ymd_and_hms(2026, 2, 1, 8, 0, 0).unwrap();
+        let earlier = Utc.with_ymd_and_hms(2026, 1, 1, 8, 0, 0).unwrap();
+        for started_at in [later, earlier] {
+            let mut record = RunRecord::new(&state, false, started_at);
+            record.finish(
+                &state,
+                RunStatus::Completed {
+                    completed_at: started_at,
+                },
+                started_at,
+            );
+            store.save_run_record(&record).await.unwrap();
+        }
+
+        store.delete_run("run").await.unwrap();
+        assert!(store.load_run_state("run").await.unwrap().is_none());
+
+        let records = store.list_run_records().await.unwrap();
+        let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            ["20260101080000000-01234567", "20260201080000000-01234567"]
+        );
+
+        let record = store
+            .load_run_record("20260101080000000-01234567")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.ended_at, Some(earlier));
+    }
+
+    #[tokio::test]
+    async fn approvals_last_until_the_next_run_start() {
+        use crate::models::WalEntry;
+        use chrono::Utc;
+
+        let dir = tempdir().unwrap();
+        let store = SledStateStore::open(dir.path()).unwrap();
+        let approve = |gate: &str| WalEntry::GateApproved {
+            run_id: "run".into(),
+            gate: gate.into(),
+            at: Utc::now(),
+        };
+        let start = WalEntry::RunStart {
+            run_id: "run".into(),
+            plan_hash: "hash".into(),
+        };
+
+        store.append_wal(&start).await.unwrap();
+        store.append_wal(&approve("cutover")).await.unwrap();
+        store
+            .append_wal(&WalEntry::RunResumed {
+                run_id: "run".into(),
+            })
+            .await
+            .unwrap();
+        let wal = store.iter_wal("run").await.unwrap();
+        assert!(WalEntry::approved_gates(&wal).contains("cutover"));
+
+        store.append_wal(&start).await.unwrap();
+        store.append_wal(&approve("backfill")).await.unwrap();
+        let wal = store.iter_wal("run").await.unwrap();
+        let approved = WalEntry::approved_gates(&wal);
+        assert!(!approved.contains("cutover"));
+        assert!(approved.contains("backfill"));
+    }
+
+    #[tokio::test]
+    async fn republished_work_keeps_done_items() {
+        use crate::models::{WorkItem, WorkStatus};
+
+        let dir = tempdir().unwrap();
+        let store = SledStateStore::open(dir.path()).unwrap();
+        let lease = chrono::Duration::seconds(30);
+        let items = vec![
+            WorkItem::new("customers", "item-1", vec![]),
+            WorkItem::new("orders", "item-2", vec!["customers".into()]),
+        ];
+
+        store.publish_work("run", items.clone()).await.unwrap();
+        let claimed = store.claim_work("run", "w1", lease).await.unwrap().unwrap();
+        assert_eq!(claimed.pipeline, "customers");
+        assert!(
+            store
+                .claim_work("run", "w2", lease)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let done = WorkStatus::Done { rows: 5 };
+        assert!(
+            !store
+                .release_work("run", "customers", "w2", done.clone())
+                .await
+                .unwrap()
+        );
+        assert!(
+            store
+                .release_work("run", "customers", "w1", done.clone())
+                .await
+                .unwrap()
+        );
+
+        store.publish_work("run", items).await.unwrap();
+        let work = store.list_work("run").await.unwrap();
+        assert_eq!(work[0].status, done);
+        assert_eq!(work[1].status, WorkStatus::Pending);
+
+        store.delete_run("run").await.unwrap();
+        assert!(store.list_work("run").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn republished_work_keeps_split_pipeline_parts() {
+        use crate::models::{WorkItem, WorkKind, WorkStatus};
+
+        let dir = tempdir().unwrap();
+        let store = SledStateStore::open(dir.path()).unwrap();
+        let lease = chrono::Duration::seconds(30);
+        let items = vec![WorkItem::new("customers", "item-1", vec![])];
+        let range = |id, start, end| WorkKind::Range { id, start, end };
+
+        store.publish_work("run", items.clone()).await.unwrap();
+        let claimed = store.claim_work("run", "w1", lease).await.unwrap().unwrap();
+        let parts = vec![
+            claimed.part(range(0, 1, 50)),
+            claimed.part(range(1, 50, 100)),
+            claimed.part(WorkKind::Finish),
+        ];
+        assert!(
+            !store
+                .split_work("run", "customers", "w2", parts.clone())
+                .await
+                .unwrap()
+        );
+        assert!(
+            store
+                .split_work("run", "customers", "w1", parts)
+                .await
+                .unwrap()
+        );
+
+        let first = store.claim_work("run", "w1", lease).await.unwrap().unwrap();
+        assert_eq!(first.key(), "customers/part-0");
+        let done = WorkStatus::Done { rows: 49 };
+        assert!(
+            store
+                .release_work("run", "customers/part-0", "w1", done.clone())
+                .await
+                .unwrap()
+        );
+        let second = store.claim_work("run", "w2", lease).await.unwrap().unwrap();
+        assert_eq!(second.key(), "customers/part-1");
+        assert!(
+            store
+                .release_work(
+                    "run",
+                    "customers/part-1",
+                    "w2",
+                    WorkStatus::Failed {
+                        error: "boom".into()
+                    }
+                )
+                .await
+                .unwrap()
+        );
+
+        // A resumed run keeps the split and retries the failed range
+        store.publish_work("run", items).await.unwrap();
+        let work = store.list_work("run").await.unwrap();
+        let status: Vec<_> = work.iter().map(|i| (i.key(), i.status.clone())).collect();
+        assert_eq!(
+            status,
+            vec![
+                ("customers".to_string(), WorkStatus::Done { rows: 0 }),
+                ("customers/part-0".to_string(), done),
+                ("customers/part-1".to_string(), WorkStatus::Pending),
+                ("customers/finish".to_string(), WorkStatus::Pending),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn keymap_is_kept_per_table() {
+        let dir = tempdir().unwrap();
+        let store = SledStateStore::open(dir.path()).unwrap();
+
+        let keys = vec![
+            ("1".to_string(), Value::String("a".into())),
+            ("2".to_string(), Value::String("b".into())),
+        ];
+        store.save_keys("orders", &keys).await.unwrap();
+        store
+            .save_keys("orders", &[("2".to_string(), Value::String("c".into()))])
+            .await
+            .unwrap();
+        store
+            .save_keys("order_items", &[("1".to_string(), Value::Int(7))])
+            .await
+            .unwrap();
+
+        let orders = store.load_keys("orders").await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders["1"], Value::String("a".into()));
+        assert_eq!(orders["2"], Value::String("c".into()));
+        assert!(store.load_keys("customers").await.unwrap().is_empty());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::error::StateStoreError;
-use crate::models::{Checkpoint, CheckpointSummary, RunState, WalEntry};
+use crate::models::{Checkpoint, CheckpointSummary, RunRecord, RunState, WalEntry};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -24,6 +24,9 @@ pub trait StateStore: Send + Sync {
             .map(CheckpointSummary::from))
     }
 
+    /// Every checkpoint of a run, one per item partition.
+    async fn list_checkpoints(&self, run_id: &str) -> Result<Vec<Checkpoint>, StateStoreError>;
+
     async fn append_wal(&self, entry: &WalEntry) -> Result<(), StateStoreError>;
     async fn iter_wal(&self, run_id: &str) -> Result<Vec<WalEntry>, StateStoreError>;
 
@@ -32,5 +35,12 @@ pub trait StateStore: Send + Sync {
     async fn list_runs(&self) -> Result<Vec<RunState>, StateStoreError>;
 
     /// Delete all state for a given run: run state, checkpoints, and WAL entries.
+    /// The run history is kept.
     async fn delete_run(&self, run_id: &str) -> Result<(), StateStoreError>;
+
+    /// Insert or update a run history entry.
+    async fn save_run_record(&self, record: &RunRecord) -> Result<(), StateStoreError>;
+    async fn load_run_record(&self, id: &str) -> Result<Option<RunRecord>, StateStoreError>;
+    /// All run history entries, oldest first.
+    async fn list_run_records(&self) -> Result<Vec<RunRecord>, StateStoreError>;
 }
@@ -0,0 +1,362 @@
+use crate::error::StateStoreError;
+use crate::models::{WorkItem, WorkKind, WorkStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
+
+/// Retries before a queue update gives up on a contended swap
+const MAX_SWAP_ATTEMPTS: usize = 100;
+
+/// Shared queue of the work items of distributed runs.
+///
+/// A run's queue is stored as one value that is only ever replaced by
+/// compare-and-swap, so claims are atomic across processes without locks.
+/// Backends provide `load_queue` and `swap_queue`; the queue operations are
+/// built on top of them.
+#[async_trait]
+pub trait WorkQueue: Send + Sync {
+    /// The encoded queue for a run, if one has been published.
+    async fn load_queue(&self, run_id: &str) -> Result<Option<Vec<u8>>, StateStoreError>;
+
+    /// Replaces the encoded queue if it still equals `expected`. Returns false
+    /// when another process changed it first.
+    async fn swap_queue(
+        &self,
+        run_id: &str,
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, StateStoreError>;
+
+    async fn list_work(&self, run_id: &str) -> Result<Vec<WorkItem>, StateStoreError> {
+        match self.load_queue(run_id).await? {
+            Some(bytes) => decode(&bytes),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Publishes the work for a run. Items that are done, or held by a
+    /// worker under a live lease, are kept, so re-publishing a resumed run
+    /// neither redoes nor doubles up pipelines; everything else starts over
+    /// as published. A pipeline already split keeps its parts, and the parts
+    /// not done or held start over.
+    async fn publish_work(
+        &self,
+        run_id: &str,
+        items: Vec<WorkItem>,
+    ) -> Result<(), StateStoreError> {
+        let now = Utc::now();
+        update_queue(self, run_id, |queue| {
+            let mut published = Vec::new();
+            for item in &items {
+                let Some(kept) = queue
+                    .iter()
+                    .find(|q| q.key() == item.key() && is_kept(q, now))
+                else {
+                    published.push(item.clone());
+                    continue;
+                };
+                published.push(kept.clone());
+                let parts = queue
+                    .iter()
+                    .filter(|q| q.pipeline == item.pipeline && q.kind != WorkKind::Pipeline);
+                for part in parts {
+                    published.push(match is_kept(part, now) {
+                        true => part.clone(),
+                        false => part.part(part.kind.clone()),
+                    });
+                }
+            }
+            *queue = published;
+        })
+        .await
+    }
+
+    /// Claims the first pending item whose dependencies are done, or one
+    /// whose previous holder let its lease expire. A split pipeline's
+    /// `Finish` item waits for all its ranges.
+    async fn claim_work(
+        &self,
+        run_id: &str,
+        worker_id: &str,
+        lease: Duration,
+    ) -> Result<Option<WorkItem>, StateStoreError> {
+        update_queue(self, run_id, |queue| {
+            claim(queue, worker_id, Utc::now(), lease)
+        })
+        .await
+    }
+
+    /// Extends the lease on the item with `key` this worker holds. Returns
+    /// false if the item is no longer held by it.
+    async fn renew_lease(
+        &self,
+        run_id: &str,
+        key: &str,
+        worker_id: &str,
+        lease: Duration,
+    ) -> Result<bool, StateStoreError> {
+        let now = Utc::now();
+        self.release_work(
+            run_id,
+            key,
+            worker_id,
+            WorkStatus::Claimed {
+                lease_until: now + lease,
+            },
+        )
+        .await
+    }
+
+    /// Sets the status of the item with `key` this worker holds: its
+    /// outcome, or `Pending` to hand it back. Returns false if the item is no
+    /// longer held by this worker.
+    async fn release_work(
+        &self,
+        run_id: &str,
+        key: &str,
+        worker_id: &str,
+        status: WorkStatus,
+    ) -> Result<bool, StateStoreError> {
+        update_queue(self, run_id, |queue| {
+            let Some(item) = held(queue, key, worker_id) else {
+                return false;
+            };
+            item.status = status.clone();
+            item.updated_at = Utc::now();
+            true
+        })
+        .await
+    }
+
+    /// Marks the pipeline item with `key` this worker holds done and adds
+    /// `parts` to the queue in the same update, so the pipeline is never
+    /// seen done before its parts are. Returns false if the item is no
+    /// longer held by this worker.
+    async fn split_work(
+        &self,
+        run_id: &str,
+        key: &str,
+        worker_id: &str,
+        parts: Vec<WorkItem>,
+    ) -> Result<bool, StateStoreError> {
+        update_queue(self, run_id, |queue| {
+            let Some(item) = held(queue, key, worker_id) else {
+                return false;
+            };
+            item.status = WorkStatus::Done { rows: 0 };
+            item.updated_at = Utc::now();
+            queue.extend(parts.iter().cloned());
+            true
+        })
+        .await
+    }
+
+    /// Fails the pending item with `key` without running it, e.g. because a
+    /// dependency failed. Returns false if the item was not pending.
+    async fn skip_work(
+        &self,
+        run_id: &str,
+        key: &str,
+        error: &str,
+    ) -> Result<bool, StateStoreError> {
+        update_queue(self, run_id, |queue| {
+            let Some(item) = queue
+                .iter_mut()
+                .find(|i| i.key() == key && i.status == WorkStatus::Pending)
+            else {
+                return false;
+            };
+            item.status = WorkStatus::Failed {
+                error: error.to_string(),
+            };
+            item.updated_at = Utc::now();
+            true
+        })
+        .await
+    }
+}
+
+/// Applies `update` to the run's queue and swaps the result in, retrying
+/// when another process won the race. Nothing is written if `update` leaves
+/// the queue unchanged.
+async fn update_queue<Q, T, F>(store: &Q, run_id: &str, mut update: F) -> Result<T, StateStoreError>
+where
+    Q: WorkQueue + ?Sized,
+    F: FnMut(&mut Vec<WorkItem>) -> T + Send,
+    T: Send,
+{
+    for _ in 0..MAX_SWAP_ATTEMPTS {
+        let current = store.load_queue(run_id).await?;
+        let mut queue = match &current {
+            Some(bytes) => decode(bytes)?,
+            None => Vec::new(),
+        };
+
+        let before = queue.clone();
+        let result = update(&mut queue);
+        if queue == before {
+            return Ok(result);
+        }
+
+        let new = bincode::serialize(&queue)
+            .map_err(|e| StateStoreError::Serialization(e.to_string()))?;
+        if store.swap_queue(run_id, current.as_deref(), &new).await? {
+            return Ok(result);
+        }
+    }
+
+    Err(StateStoreError::Storage(format!(
+        "Work queue for run {run_id} is too contended to update"
+    )))
+}
+
+fn decode(bytes: &[u8]) -> Result<Vec<WorkItem>, StateStoreError> {
+    bincode::deserialize(bytes).map_err(|e| StateStoreError::Serialization(e.to_string()))
+}
+
+/// Whether re-publishing keeps the item as it is: done, or held under a
+/// live lease.
+fn is_kept(item: &WorkItem, now: DateTime<Utc>) -> bool {
+    match item.status {
+        WorkStatus::Done { .. } => true,
+        WorkStatus::Claimed { lease_until } => lease_until > now,
+        WorkStatus::Pending | WorkStatus::Failed { .. } => false,
+    }
+}
+
+fn held<'a>(queue: &'a mut [WorkItem], key: &str, worker_id: &str) -> Option<&'a mut WorkItem> {
+    queue.iter_mut().find(|i| {
+        i.key() == key
+            && matches!(i.status, WorkStatus::Claimed { .. })
+            && i.worker_id.as_deref() == Some(worker_id)
+    })
+}
+
+fn claim(
+    queue: &mut [WorkItem],
+    worker_id: &str,
+    now: DateTime<Utc>,
+    lease: Duration,
+) -> Option<WorkItem> {
+    // A pipeline is done once every item of it is, its parts included
+    let not_done: HashSet<&str> = queue
+        .iter()
+        .filter(|i| !matches!(i.status, WorkStatus::Done { .. }))
+        .map(|i| i.pipeline.as_str())
+        .collect();
+    let done: HashSet<String> = queue
+        .iter()
+        .filter(|i| !not_done.contains(i.pipeline.as_str()))
+        .map(|i| i.pipeline.clone())
+        .collect();
+    let ranges_left: HashSet<String> = queue
+        .iter()
+        .filter(|i| {
+            matches!(i.kind, WorkKind::Range { .. }) && !matches!(i.status, WorkStatus::Done { .. })
+        })
+        .map(|i| i.pipeline.clone())
+        .collect();
+
+    let item = queue.iter_mut().find(|i| {
+        let claimable = match i.status {
+            WorkStatus::Pending => true,
+            WorkStatus::Claimed { lease_until } => lease_until <= now,
+            WorkStatus::Done { .. } | WorkStatus::Failed { .. } => false,
+        };
+        claimable
+            && i.depends_on.iter().all(|dep| done.contains(dep))
+            && (i.kind != WorkKind::Finish || !ranges_left.contains(&i.pipeline))
+    })?;
+
+    item.status = WorkStatus::Claimed {
+        lease_until: now + lease,
+    };
+    item.worker_id = Some(worker_id.to_string());
+    item.updated_at = now;
+    Some(item.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(pipeline: &str, deps: &[&str]) -> WorkItem {
+        WorkItem::new(
+            pipeline,
+            &format!("item-{pipeline}"),
+            deps.iter().map(|d| d.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn claim_waits_for_dependencies() {
+        let now = Utc::now();
+        let lease = Duration::seconds(30);
+        let mut queue = vec![item("orders", &["customers"]), item("customers", &[])];
+
+        let first = claim(&mut queue, "w1", now, lease).unwrap();
+        assert_eq!(first.pipeline, "customers");
+        assert!(claim(&mut queue, "w2", now, lease).is_none());
+
+        queue[1].status = WorkStatus::Done { rows: 10 };
+        let second = claim(&mut queue, "w2", now, lease).unwrap();
+        assert_eq!(second.pipeline, "orders");
+        assert_eq!(second.worker_id.as_deref(), Some("w2"));
+    }
+
+    #[test]
+    fn expired_lease_can_be_reclaimed() {
+        let now = Utc::now();
+        let lease = Duration::seconds(30);
+        let mut queue = vec![item("customers", &[])];
+
+        claim(&mut queue, "w1", now, lease).unwrap();
+        assert!(claim(&mut queue, "w2", now + Duration::seconds(10), lease).is_none());
+
+        let reclaimed = claim(&mut queue, "w2", now + Duration::seconds(31), lease).unwrap();
+        assert_eq!(reclaimed.worker_id.as_deref(), Some("w2"));
+    }
+
+    #[test]
+    fn split_pipeline_finishes_after_its_ranges() {
+        let now = Utc::now();
+        let lease = Duration::seconds(30);
+        let customers = item("customers", &[]);
+        let mut queue = vec![
+            WorkItem {
+                status: WorkStatus::Done { rows: 0 },
+                ..customers.clone()
+            },
+            customers.part(WorkKind::Finish),
+            customers.part(WorkKind::Range {
+                id: 0,
+                start: 1,
+                end: 50,
+            }),
+            customers.part(WorkKind::Range {
+                id: 1,
+                start: 50,
+                end: 100,
+            }),
+            item("orders", &["customers"]),
+        ];
+
+        // Both ranges go out before the finish step or the dependent
+        let first = claim(&mut queue, "w1", now, lease).unwrap();
+        let second = claim(&mut queue, "w2", now, lease).unwrap();
+        assert_eq!(first.key(), "customers/part-0");
+        assert_eq!(second.key(), "customers/part-1");
+        assert!(claim(&mut queue, "w3", now, lease).is_none());
+
+        queue[2].status = WorkStatus::Done { rows: 49 };
+        assert!(claim(&mut queue, "w3", now, lease).is_none());
+        queue[3].status = WorkStatus::Done { rows: 50 };
+
+        let finish = claim(&mut queue, "w3", now, lease).unwrap();
+        assert_eq!(finish.key(), "customers/finish");
+        assert!(claim(&mut queue, "w1", now, lease).is_none());
+
+        queue[1].status = WorkStatus::Done { rows: 0 };
+        assert_eq!(claim(&mut queue, "w1", now, lease).unwrap().key(), "orders");
+    }
+}
@@ -3,7 +3,7 @@ mod tests {
     use std::{fs, path::PathBuf};
 
     use crate::{
-        reset_postgres_schema,
+        TEST_MYSQL_URL_ORDERS, TEST_MYSQL_URL_SAKILA, reset_postgres_schema,
         utils::{
             ACTORS_TABLE_DDL, DbType, ORDERS_FLAT_FILTER_QUERY, ORDERS_FLAT_JOIN_QUERY,
             PIPELINE_FAILURES_TABLE_DDL, assert_column_exists, assert_row_count,
@@ -11,6 +11,10 @@ mod tests {
             get_column_names, get_row_count, run_smql,
         },
     };
+    use connectors::{
+        drivers::mysql::{cursor::MySqlStreamCursor, driver::MySqlDriver},
+        traits::cursor::RowCursor,
+    };
     use engine_core::plan::execution::ExecutionPlan as CoreExecutionPlan;
     use engine_planner::{
         builder::{ReportBuilder, ReportBuilderConfig},
@@ -18,6 +22,8 @@ mod tests {
     };
     use engine_processing::EnvContext;
     use engine_runtime::dag::builder::DagBuilder;
+    use model::core::value::Value;
+    use mysql_async::{Params, prelude::Queryable};
     use smql_syntax::builder::parse;
     use tracing_test::traced_test;
 
@@ -1983,4 +1989,71 @@ mod tests {
         println!("  - {} warnings", report.summary.warning_count);
         println!("  - Report is executable: {}", report.is_executable);
     }
+
+    // Test Settings: session settings on a MySQL driver.
+    // Scenario: The same pooled connection is checked out twice; the pool resets it in between.
+    // Expected Outcome: The setting holds on both checkouts.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn mysql_session_settings_survive_pool_reset() {
+        let settings = vec![("sql_select_limit".to_string(), Value::Int(7))];
+        let driver = MySqlDriver::connect_with_session(TEST_MYSQL_URL_ORDERS, &settings)
+            .await
+            .expect("connect mysql");
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let mut conn = driver
+                .pool()
+                .get_conn()
+                .await
+                .expect("check out connection");
+            let limit: Option<u64> = conn
+                .query_first("SELECT @@SESSION.sql_select_limit")
+                .await
+                .expect("read session setting");
+            assert_eq!(limit, Some(7));
+            ids.push(conn.id());
+            drop(conn);
+            // Let the pool reset the connection and take it back
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        }
+        assert_eq!(
+            ids[0], ids[1],
+            "the second checkout should reuse the connection"
+        );
+    }
+
+    // Test Settings: a streaming MySQL cursor.
+    // Scenario: The reader closes the cursor after one page of a very large result set.
+    // Expected Outcome: The query no longer runs on the server.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn mysql_stream_cursor_stops_query_on_early_close() {
+        let driver = MySqlDriver::connect(TEST_MYSQL_URL_SAKILA)
+            .await
+            .expect("connect mysql");
+        let sql = "SELECT a.actor_id FROM actor a, actor b, actor c /* early close */";
+
+        let mut cursor = Box::new(MySqlStreamCursor::open(
+            driver.pool(),
+            "actor",
+            sql.to_string(),
+            Params::Empty,
+        ));
+        assert_eq!(cursor.fetch_next(10).await.expect("first page").len(), 10);
+        cursor.close().await.expect("close cursor");
+
+        let mut conn = driver
+            .pool()
+            .get_conn()
+            .await
+            .expect("check out connection");
+        let running: Vec<u64> = conn
+            .query(
+                "SELECT ID FROM information_schema.PROCESSLIST \
+                 WHERE INFO LIKE '%/* early close */%' AND INFO NOT LIKE '%PROCESSLIST%'",
+            )
+            .await
+            .expect("list processes");
+        assert!(running.is_empty(), "query still running: {running:?}");
+    }
 }
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    use crate::TEST_REDIS_URL;
+    use chrono::Utc;
+    use engine_state::{
+        StateStore, WorkQueue,
+        models::{
+            Checkpoint, CheckpointStage, RunRecord, RunState, RunStatus, WalEntry, WorkItem,
+            WorkStatus,
+        },
+        redis_store::RedisStateStore,
+    };
+    use model::pagination::cursor::Cursor;
+    use std::time::Duration;
+
+    /// Store under a key prefix of its own, so tests never see each other's
+    /// keys or those of an earlier run.
+    async fn store(test: &str, ttl: Option<u64>) -> RedisStateStore {
+        let prefix = format!(
+            "stratum-test:{test}:{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        RedisStateStore::connect(TEST_REDIS_URL, &prefix, ttl)
+            .await
+            .expect("connect redis")
+    }
+
+    fn mk_cp(stage: CheckpointStage, batch: &str, cursor: Cursor) -> Checkpoint {
+        Checkpoint {
+            run_id: "run".into(),
+            item_id: "item".into(),
+            part_id: "part".into(),
+            stage,
+            src_offset: cursor,
+            pending_offset: None,
+            batch_id: batch.to_string(),
+            rows_done: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn run_state() -> RunState {
+        RunState {
+            run_id: "run".into(),
+            config_path: "migration.smql".into(),
+            config_hash: "0123456789abcdef".into(),
+            fingerprint: Default::default(),
+            status: RunStatus::Running,
+            started_at: Utc::now(),
+            total_pipelines: 0,
+            pipelines: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_uncommitted_write_over_future_read() {
+        let store = store("uncommitted", None).await;
+
+        store
+            .save_checkpoint(&mk_cp(CheckpointStage::Write, "batch-1", Cursor::None))
+            .await
+            .unwrap();
+
+        // Another batch cannot replace an uncommitted one...
+        store
+            .save_checkpoint(&mk_cp(
+                CheckpointStage::Read,
+                "batch-2",
+                Cursor::Default { offset: 1 },
+            ))
+            .await
+            .unwrap();
+        // ...and the same batch cannot step back a stage
+        store
+            .save_checkpoint(&mk_cp(CheckpointStage::Read, "batch-1", Cursor::None))
+            .await
+            .unwrap();
+
+        let cp = store
+            .load_checkpoint("run", "item", "part")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cp.stage, CheckpointStage::Write);
+        assert_eq!(cp.batch_id, "batch-1");
+
+        store.delete_run("run").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn advances_after_commit() {
+        let store = store("commit", None).await;
+
+        store
+            .save_checkpoint(&mk_cp(CheckpointStage::Write, "batch-1", Cursor::None))
+            .await
+            .unwrap();
+        store
+            .save_checkpoint(&mk_cp(CheckpointStage::Committed, "batch-1", Cursor::None))
+            .await
+            .unwrap();
+        store
+            .save_checkpoint(&mk_cp(
+                CheckpointStage::Read,
+                "batch-2",
+                Cursor::Default { offset: 1 },
+            ))
+            .await
+            .unwrap();
+
+        let cp = store
+            .load_checkpoint("run", "item", "part")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cp.stage, CheckpointStage::Read);
+        assert_eq!(cp.batch_id, "batch-2");
+        assert_eq!(store.list_checkpoints("run").await.unwrap().len(), 1);
+
+        store.delete_run("run").await.unwrap();
+        assert!(store.list_checkpoints("run").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn wal_round_trips_in_order() {
+        let store = store("wal", None).await;
+        let entries = vec![
+            WalEntry::RunStart {
+                run_id: "run".into(),
+                plan_hash: "hash".into(),
+            },
+            WalEntry::ItemStart {
+                run_id: "run".into(),
+                item_id: "item".into(),
+            },
+            WalEntry::RunResumed {
+                run_id: "run".into(),
+            },
+        ];
+        for entry in &entries {
+            store.append_wal(entry).await.unwrap();
+        }
+        store
+            .append_wal(&WalEntry::RunStart {
+                run_id: "other".into(),
+                plan_hash: "hash".into(),
+            })
+            .await
+            .unwrap();
+
+        let wal = store.iter_wal("run").await.unwrap();
+        let read: Vec<String> = wal.iter().map(|e| format!("{e:?}")).collect();
+        let written: Vec<String> = entries.iter().map(|e| format!("{e:?}")).collect();
+        assert_eq!(read, written);
+
+        store.delete_run("run").await.unwrap();
+        assert!(store.iter_wal("run").await.unwrap().is_empty());
+        assert_eq!(store.iter_wal("other").await.unwrap().len(), 1);
+        store.delete_run("other").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn queue_swap_only_replaces_the_expected_value() {
+        let store = store("swap", None).await;
+
+        assert!(store.swap_queue("run", None, b"v1").await.unwrap());
+        // Publishing again as if the queue were absent loses the race
+        assert!(!store.swap_queue("run", None, b"v2").await.unwrap());
+        assert!(
+            !store
+                .swap_queue("run", Some(b"stale"), b"v2")
+                .await
+                .unwrap()
+        );
+        assert_eq!(store.load_queue("run").await.unwrap().unwrap(), b"v1");
+
+        assert!(store.swap_queue("run", Some(b"v1"), b"v2").await.unwrap());
+        assert_eq!(store.load_queue("run").await.unwrap().unwrap(), b"v2");
+
+        store.delete_run("run").await.unwrap();
+        assert!(store.load_queue("run").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn concurrent_claims_hand_out_each_item_once() {
+        let store = std::sync::Arc::new(store("claims", None).await);
+        let lease = chrono::Duration::seconds(30);
+        let items = (0..4)
+            .map(|i| WorkItem::new(&format!("pipeline-{i}"), &format!("item-{i}"), vec![]))
+            .collect();
+        store.publish_work("run", items).await.unwrap();
+
+        let claims = (0..8).map(|w| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .claim_work("run", &format!("w{w}"), lease)
+                    .await
+                    .unwrap()
+            })
+        });
+        let mut claimed: Vec<String> = futures::future::join_all(claims)
+            .await
+            .into_iter()
+            .filter_map(|r| r.unwrap().map(|item| item.pipeline))
+            .collect();
+        claimed.sort();
+        assert_eq!(
+            claimed,
+            ["pipeline-0", "pipeline-1", "pipeline-2", "pipeline-3"]
+        );
+
+        let work = store.list_work("run").await.unwrap();
+        assert!(
+            work.iter()
+                .all(|i| matches!(i.status, WorkStatus::Claimed { .. }))
+        );
+
+        store.delete_run("run").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_data_expires_but_history_does_not() {
+        let store = store("ttl", Some(1)).await;
+        let state = run_state();
+
+        store
+            .save_checkpoint(&mk_cp(CheckpointStage::Committed, "batch-1", Cursor::None))
+            .await
+            .unwrap();
+        store
+            .append_wal(&WalEntry::RunStart {
+                run_id: "run".into(),
+                plan_hash: "hash".into(),
+            })
+            .await
+            .unwrap();
+        store.save_run_state(&state).await.unwrap();
+        store
+            .publish_work("run", vec![WorkItem::new("customers", "item", vec![])])
+            .await
+            .unwrap();
+        let record = RunRecord::new(&state, false, state.started_at);
+        store.save_run_record(&record).await.unwrap();
+
+        assert!(store.load_run_state("run").await.unwrap().is_some());
+        assert_eq!(store.list_runs().await.unwrap().len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+
+        assert!(
+            store
+                .load_checkpoint("run", "item", "part")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.iter_wal("run").await.unwrap().is_empty());
+        assert!(store.load_run_state("run").await.unwrap().is_none());
+        assert!(store.list_work("run").await.unwrap().is_empty());
+        // The expired run drops out of the run index too
+        assert!(store.list_runs().await.unwrap().is_empty());
+
+        let kept = store.load_run_record(&record.id).await.unwrap().unwrap();
+        assert_eq!(kept.id, record.id);
+    }
+}
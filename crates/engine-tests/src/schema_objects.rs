@@ -243,21 +243,20 @@ mod tests {
             "enum labels or order mismatch"
         );
 
-        // film.rating column exists in the destination
-        let col_type: String = pg
+        // film.rating column uses the enum type, not a plain string
+        let row = pg
             .query_one(
-                "SELECT data_type FROM information_schema.columns \
+                "SELECT data_type, udt_name FROM information_schema.columns \
                  WHERE table_schema = 'public' AND table_name = 'film' AND column_name = 'rating'",
                 &[],
             )
             .await
-            .expect("film.rating column not found")
-            .get(0);
-        // The type converter maps MySQL ENUM -> PostgreSQL VARCHAR(255).
-        // The CREATE TYPE rating is created first (pre-DDL) to allow future migration to use it.
+            .expect("film.rating column not found");
+        let (col_type, udt_name): (String, String) = (row.get(0), row.get(1));
         assert_eq!(
-            col_type, "character varying",
-            "film.rating must be character varying (MySQL ENUM maps to VARCHAR), got '{col_type}'"
+            (col_type.as_str(), udt_name.as_str()),
+            ("USER-DEFINED", "rating"),
+            "film.rating must use the rating enum type, got '{col_type}' ({udt_name})"
         );
 
         // Row count must match source
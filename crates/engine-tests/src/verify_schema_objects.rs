@@ -61,9 +61,9 @@ mod tests {
 
     /// Verify works after an ENUM migration.
     ///
-    /// MySQL ENUM('G','PG','PG-13','R','NC-17') -> PostgreSQL VARCHAR(255).
-    /// MySQL text protocol sends ENUM values as strings; PG stores them as
-    /// varchar. Canonical hashing treats both as TAG_STRING - hashes must match.
+    /// MySQL ENUM('G','PG','PG-13','R','NC-17') -> PostgreSQL enum type `rating`.
+    /// MySQL text protocol sends ENUM values as strings; PG returns enum labels.
+    /// Canonical hashing encodes both as the label string - hashes must match.
     #[traced_test]
     #[tokio::test(flavor = "multi_thread")]
     async fn verify_phase2_enum_migration() {
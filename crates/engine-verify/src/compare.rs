@@ -0,0 +1,743 @@
+//! Post-copy comparison of a pipeline's source table with its destination.
+//!
+//! Both tables are split into chunks over the verify key, with the chunk
+//! bounds taken from the source, and every chunk is compared by row count
+//! plus, depending on the mode, per-column aggregates or md5 checksums.
+//! Values are compared in their canonical encoding so that the same data read
+//! from MySQL and PostgreSQL compares equal.
+
+use crate::error::VerifyError;
+use bigdecimal::BigDecimal;
+use connectors::{sql::metadata::table::TableMetadata, traits::executor::QueryExecutor};
+use engine_core::{
+    context::exec::ConnectionPool, dispatch_driver, drivers::DriverRef,
+    plan::execution::ExecutionPlan,
+};
+use engine_processing::io::filter::{
+    compiler::{FilterCompiler, sql::SqlFilterCompiler},
+    utils::combine_filters,
+};
+use model::{
+    core::value::Value,
+    execution::{
+        pipeline::Pipeline,
+        verify::{DEFAULT_VERIFY_CHUNK_SIZE, VerifyAsOf, VerifyConfig, VerifyMode},
+    },
+    integrity::{
+        canonical::serialize_value,
+        coerce::coerce_row_for_hash,
+        comparison::{ChunkDiscrepancy, ComparisonReport},
+    },
+    records::Record,
+    transform::mapping::TransformationMetadata,
+};
+use query_builder::dialect::{self, Dialect};
+use std::{collections::HashMap, time::Instant};
+use tracing::{info, warn};
+
+const ROW_COUNT: &str = "row_count";
+const KEY_ALIAS: &str = "k";
+
+/// Compares every pipeline that has a `verify` block. With `mode` set, every
+/// pipeline is compared in that mode, using its `verify` block, if any, for
+/// the key, chunk size and columns.
+pub async fn compare(
+    plan: &ExecutionPlan,
+    mode: Option<VerifyMode>,
+) -> Result<Vec<ComparisonReport>, VerifyError> {
+    let mut pool = ConnectionPool::with_snapshots(plan.source_snapshots.clone());
+    let mut reports = Vec::new();
+
+    for pipeline in &plan.pipelines {
+        let config = match (&pipeline.verify, mode) {
+            (Some(config), None) => config.clone(),
+            (Some(config), Some(mode)) => VerifyConfig {
+                mode,
+                columns: if mode == VerifyMode::Count {
+                    Vec::new()
+                } else {
+                    config.columns.clone()
+                },
+                ..config.clone()
+            },
+            (None, Some(mode)) => VerifyConfig {
+                mode,
+                key: None,
+                chunk_size: DEFAULT_VERIFY_CHUNK_SIZE,
+                columns: Vec::new(),
+                as_of: VerifyAsOf::Now,
+            },
+            (None, None) => continue,
+        };
+
+        reports.push(compare_pipeline(pipeline, &config, &mut pool).await?);
+    }
+
+    Ok(reports)
+}
+
+async fn compare_pipeline(
+    pipeline: &Pipeline,
+    config: &VerifyConfig,
+    pool: &mut ConnectionPool,
+) -> Result<ComparisonReport, VerifyError> {
+    let start = Instant::now();
+    let source = &pipeline.source;
+    let destination = &pipeline.destination;
+    info!(
+        pipeline = %pipeline.name,
+        mode = config.mode.as_str(),
+        "comparing source and destination"
+    );
+
+    let src_driver =
+        DriverRef::resolve(&source.connection.driver, &source.connection, pool).await?;
+    let dst_driver = DriverRef::resolve(
+        &destination.connection.driver,
+        &destination.connection,
+        pool,
+    )
+    .await?;
+    let src_meta = src_driver.table_metadata(&source.table).await?;
+    let dst_meta = dst_driver.table_metadata(&destination.table).await?;
+
+    let mapping = TransformationMetadata::new(pipeline);
+    let entity = destination.table.to_ascii_lowercase();
+    let to_destination = |column: &str| mapping.field_mappings.resolve(&entity, column);
+
+    let key = source_key(pipeline, config.key.as_deref(), &src_meta)?;
+    let key = ComparedColumn::new(pipeline, &key, &to_destination(&key), &src_meta, &dst_meta)?;
+
+    let columns = match config.mode {
+        VerifyMode::Count => Vec::new(),
+        _ if !config.columns.is_empty() => config
+            .columns
+            .iter()
+            .map(|c| ComparedColumn::new(pipeline, c, &to_destination(c), &src_meta, &dst_meta))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => copied_columns(&mapping, &entity, &key, &src_meta, &dst_meta),
+    };
+
+    let src = Side {
+        sql: TableSql {
+            dialect: sql_dialect(&src_driver),
+            table: source.table.clone(),
+            key: key.source.clone(),
+            columns: columns.iter().map(|c| c.source.clone()).collect(),
+            filter: source_filter(pipeline)?,
+        },
+        driver: src_driver,
+    };
+    let dst = Side {
+        sql: TableSql {
+            dialect: sql_dialect(&dst_driver),
+            table: destination.table.clone(),
+            key: key.destination.clone(),
+            columns: columns.iter().map(|c| c.destination.clone()).collect(),
+            filter: None,
+        },
+        driver: dst_driver,
+    };
+    let col_types: HashMap<String, String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (column_alias(i), c.destination_type.clone()))
+        .collect();
+
+    let mut report = ComparisonReport {
+        pipeline: pipeline.name.clone(),
+        source_table: source.table.clone(),
+        destination_table: destination.table.clone(),
+        mode: config.mode,
+        key: key.destination.clone(),
+        chunks: 0,
+        source_rows: 0,
+        destination_rows: 0,
+        discrepancies: Vec::new(),
+        duration_ms: 0,
+    };
+
+    let mut after = None;
+    loop {
+        let through = src.chunk_end(after.as_ref(), config.chunk_size).await?;
+        let range = KeyRange {
+            after: after.clone(),
+            through: through.clone(),
+        };
+
+        let expected = src
+            .summarize(&range, config.mode, &columns, &col_types)
+            .await?;
+        let actual = dst
+            .summarize(&range, config.mode, &columns, &col_types)
+            .await?;
+        report.source_rows += expected.rows;
+        report.destination_rows += actual.rows;
+
+        let differing: Vec<String> = columns
+            .iter()
+            .zip(expected.columns.iter().zip(actual.columns.iter()))
+            .filter(|(_, (e, a))| e != a)
+            .map(|(c, _)| c.destination.clone())
+            .collect();
+        if expected.rows != actual.rows || !differing.is_empty() {
+            report.discrepancies.push(ChunkDiscrepancy {
+                chunk_index: report.chunks,
+                key_after: range.after.as_ref().map(display_key),
+                key_through: range.through.as_ref().map(display_key),
+                source_rows: expected.rows,
+                destination_rows: actual.rows,
+                columns: differing,
+            });
+        }
+        report.chunks += 1;
+
+        match through {
+            Some(key) => after = Some(key),
+            None => break,
+        }
+    }
+
+    report.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(report)
+}
+
+/// A compared column under its source and destination names.
+struct ComparedColumn {
+    source: String,
+    destination: String,
+    destination_type: String,
+    kind: ColumnKind,
+}
+
+impl ComparedColumn {
+    fn new(
+        pipeline: &Pipeline,
+        source: &str,
+        destination: &str,
+        src_meta: &TableMetadata,
+        dst_meta: &TableMetadata,
+    ) -> Result<Self, VerifyError> {
+        let missing = |table: &str, column: &str| {
+            VerifyError::InitializationError(format!(
+                "pipeline '{}': verify column '{}' does not exist in table '{}'",
+                pipeline.name, column, table
+            ))
+        };
+        let src_col =
+            find_column(src_meta, source).ok_or_else(|| missing(&src_meta.name, source))?;
+        let dst_col = find_column(dst_meta, destination)
+            .ok_or_else(|| missing(&dst_meta.name, destination))?;
+
+        Ok(Self::from_metadata(
+            &src_col.name,
+            &src_col.data_type,
+            &dst_col.name,
+            &dst_col.data_type,
+        ))
+    }
+
+    fn from_metadata(source: &str, source_type: &str, destination: &str, dest_type: &str) -> Self {
+        let src_kind = ColumnKind::of(source_type);
+        Self {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            destination_type: dest_type.to_string(),
+            // Aggregates are only comparable when both sides agree on the kind
+            kind: if src_kind == ColumnKind::of(dest_type) {
+                src_kind
+            } else {
+                ColumnKind::Other
+            },
+        }
+    }
+}
+
+/// The configured verify key, or else the source table's primary key when it
+/// has a single column.
+pub(crate) fn source_key(
+    pipeline: &Pipeline,
+    configured: Option<&str>,
+    src_meta: &TableMetadata,
+) -> Result<String, VerifyError> {
+    if let Some(key) = configured {
+        return Ok(key.to_string());
+    }
+    match src_meta.primary_keys.as_slice() {
+        [pk] => Ok(pk.clone()),
+        _ => Err(VerifyError::InitializationError(format!(
+            "pipeline '{}': table '{}' has no single-column primary key; set `key` in the verify block",
+            pipeline.name, pipeline.source.table
+        ))),
+    }
+}
+
+pub(crate) fn find_column<'a>(
+    meta: &'a TableMetadata,
+    name: &str,
+) -> Option<&'a connectors::sql::metadata::column::ColumnMetadata> {
+    meta.columns
+        .values()
+        .find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+/// Source columns copied unchanged into an existing destination column, in
+/// source column order. Computed `select` fields are left out because their
+/// values differ from the source by design.
+fn copied_columns(
+    mapping: &TransformationMetadata,
+    entity: &str,
+    key: &ComparedColumn,
+    src_meta: &TableMetadata,
+    dst_meta: &TableMetadata,
+) -> Vec<ComparedColumn> {
+    let computed: Vec<String> = mapping
+        .field_mappings
+        .get_computed(entity)
+        .map(|fields| fields.iter().map(|f| f.name.to_ascii_lowercase()).collect())
+        .unwrap_or_default();
+
+    let mut src_columns: Vec<_> = src_meta.columns.values().collect();
+    src_columns.sort_by_key(|c| c.ordinal);
+
+    src_columns
+        .into_iter()
+        .filter(|c| !c.name.eq_ignore_ascii_case(&key.source))
+        .filter_map(|c| {
+            let dest_name = mapping.field_mappings.resolve(entity, &c.name);
+            if computed.contains(&dest_name.to_ascii_lowercase()) {
+                return None;
+            }
+            let dest = find_column(dst_meta, &dest_name)?;
+            Some(ComparedColumn::from_metadata(
+                &c.name,
+                &c.data_type,
+                &dest.name,
+                &dest.data_type,
+            ))
+        })
+        .collect()
+}
+
+/// Which aggregates a column supports in `aggregate` mode. Text columns only
+/// get a non-null count: min and max depend on the collation, which commonly
+/// differs between the source and the destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numeric,
+    Temporal,
+    Other,
+}
+
+impl ColumnKind {
+    fn of(data_type: &str) -> Self {
+        let t = data_type.to_ascii_lowercase();
+        let base = t.split(['(', ' ']).next().unwrap_or_default();
+        match base {
+            "smallint" | "integer" | "int" | "int2" | "int4" | "int8" | "bigint" | "mediumint"
+            | "decimal" | "numeric" | "real" | "double" | "float" | "float4" | "float8"
+            | "smallserial" | "serial" | "bigserial" => Self::Numeric,
+            "date" | "datetime" | "timestamp" | "timestamptz" | "time" | "timetz" | "year" => {
+                Self::Temporal
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+/// One side of the comparison
+struct Side {
+    driver: DriverRef,
+    sql: TableSql,
+}
+
+/// Builds the chunk queries for one table
+struct TableSql {
+    dialect: Box<dyn Dialect>,
+    table: String,
+    key: String,
+    columns: Vec<String>,
+    /// Pipeline `where` conditions, applied to the source only
+    filter: Option<String>,
+}
+
+/// Keys in `(after, through]`; an open end is unbounded.
+struct KeyRange {
+    after: Option<Value>,
+    through: Option<Value>,
+}
+
+/// Row count and one fingerprint per compared column for a chunk
+struct ChunkSummary {
+    rows: u64,
+    columns: Vec<Vec<u8>>,
+}
+
+impl TableSql {
+    fn table_ref(&self) -> String {
+        table_ref(self.dialect.as_ref(), &self.table)
+    }
+
+    fn where_clause(&self, range: &KeyRange) -> (String, Vec<Value>) {
+        let key = self.dialect.quote_identifier(&self.key);
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if let Some(after) = &range.after {
+            conditions.push(format!(
+                "{key} > {}",
+                self.dialect.get_placeholder(params.len())
+            ));
+            params.push(after.clone());
+        }
+        if let Some(through) = &range.through {
+            conditions.push(format!(
+                "{key} <= {}",
+                self.dialect.get_placeholder(params.len())
+            ));
+            params.push(through.clone());
+        }
+        if let Some(filter) = &self.filter {
+            conditions.push(format!("({filter})"));
+        }
+
+        if conditions.is_empty() {
+            (String::new(), params)
+        } else {
+            (format!(" WHERE {}", conditions.join(" AND ")), params)
+        }
+    }
+
+    /// The `chunk_size`-th key after `after`
+    fn chunk_end(&self, after: Option<&Value>, chunk_size: usize) -> (String, Vec<Value>) {
+        let range = KeyRange {
+            after: after.cloned(),
+            through: None,
+        };
+        let (where_clause, params) = self.where_clause(&range);
+        let key = self.dialect.quote_identifier(&self.key);
+        let sql = format!(
+            "SELECT {key} AS {KEY_ALIAS} FROM {}{where_clause} ORDER BY {key} LIMIT 1 OFFSET {}",
+            self.table_ref(),
+            chunk_size - 1
+        );
+        (sql, params)
+    }
+
+    /// `COUNT(*)` and, per column, the aggregates its kind supports
+    fn aggregate(&self, range: &KeyRange, columns: &[ComparedColumn]) -> (String, Vec<Value>) {
+        let mut select = vec![format!("COUNT(*) AS {ROW_COUNT}")];
+        for (i, (name, column)) in self.columns.iter().zip(columns).enumerate() {
+            let col = self.dialect.quote_identifier(name);
+            select.push(format!("COUNT({col}) AS n{i}"));
+            if column.kind != ColumnKind::Other {
+                select.push(format!("MIN({col}) AS min{i}"));
+                select.push(format!("MAX({col}) AS max{i}"));
+            }
+            if column.kind == ColumnKind::Numeric {
+                select.push(format!("SUM({col}) AS sum{i}"));
+            }
+        }
+        self.select(&select, range)
+    }
+
+    /// The key and compared columns of every row in the chunk
+    fn rows(&self, range: &KeyRange) -> (String, Vec<Value>) {
+        let mut select = vec![format!(
+            "{} AS {KEY_ALIAS}",
+            self.dialect.quote_identifier(&self.key)
+        )];
+        for (i, name) in self.columns.iter().enumerate() {
+            select.push(format!(
+                "{} AS {}",
+                self.dialect.quote_identifier(name),
+                column_alias(i)
+            ));
+        }
+        self.select(&select, range)
+    }
+
+    fn select(&self, select: &[String], range: &KeyRange) -> (String, Vec<Value>) {
+        let (where_clause, params) = self.where_clause(range);
+        let sql = format!(
+            "SELECT {} FROM {}{where_clause}",
+            select.join(", "),
+            self.table_ref()
+        );
+        (sql, params)
+    }
+}
+
+impl Side {
+    /// Key closing the chunk that starts after `after`, or `None` when the
+    /// rest of the table fits in one chunk.
+    async fn chunk_end(
+        &self,
+        after: Option<&Value>,
+        chunk_size: usize,
+    ) -> Result<Option<Value>, VerifyError> {
+        let (sql, params) = self.sql.chunk_end(after, chunk_size);
+        let rows = self.query(&sql, &params).await?;
+        Ok(rows.first().map(|r| r.get_value(KEY_ALIAS)))
+    }
+
+    async fn summarize(
+        &self,
+        range: &KeyRange,
+        mode: VerifyMode,
+        columns: &[ComparedColumn],
+        col_types: &HashMap<String, String>,
+    ) -> Result<ChunkSummary, VerifyError> {
+        match mode {
+            VerifyMode::Count | VerifyMode::Aggregate => self.aggregate(range, columns).await,
+            VerifyMode::Checksum => self.checksum(range, col_types).await,
+        }
+    }
+
+    async fn aggregate(
+        &self,
+        range: &KeyRange,
+        columns: &[ComparedColumn],
+    ) -> Result<ChunkSummary, VerifyError> {
+        let (sql, params) = self.sql.aggregate(range, columns);
+        let rows = self.query(&sql, &params).await?;
+        let Some(row) = rows.first() else {
+            return Ok(ChunkSummary {
+                rows: 0,
+                columns: vec![Vec::new(); columns.len()],
+            });
+        };
+
+        let summaries = (0..columns.len())
+            .map(|i| {
+                let mut buf = Vec::new();
+                for prefix in ["n", "min", "max", "sum"] {
+                    if let Some(field) = row.get(&format!("{prefix}{i}")) {
+                        let value = field.value.clone().unwrap_or(Value::Null);
+                        serialize_value(&normalize_aggregate(value), &mut buf);
+                    }
+                }
+                buf
+            })
+            .collect();
+
+        Ok(ChunkSummary {
+            rows: count_of(row, ROW_COUNT),
+            columns: summaries,
+        })
+    }
+
+    /// Per column, the sum of md5(key, value) over the chunk's rows. The sum
+    /// does not depend on row order, so the two sides need not sort alike.
+    async fn checksum(
+        &self,
+        range: &KeyRange,
+        col_types: &HashMap<String, String>,
+    ) -> Result<ChunkSummary, VerifyError> {
+        let (sql, params) = self.sql.rows(range);
+        let rows = self.query(&sql, &params).await?;
+
+        let mut sums = vec![0u128; self.sql.columns.len()];
+        let mut key_buf = Vec::new();
+        let mut value_buf = Vec::new();
+        for row in &rows {
+            let row = coerce_row_for_hash(row, col_types);
+            key_buf.clear();
+            serialize_value(&row.get_value(KEY_ALIAS), &mut key_buf);
+
+            for (i, sum) in sums.iter_mut().enumerate() {
+                value_buf.clear();
+                serialize_value(&row.get_value(&column_alias(i)), &mut value_buf);
+
+                let mut ctx = md5::Context::new();
+                ctx.consume(&key_buf);
+                ctx.consume(&value_buf);
+                *sum = sum.wrapping_add(u128::from_be_bytes(ctx.finalize().0));
+            }
+        }
+
+        Ok(ChunkSummary {
+            rows: rows.len() as u64,
+            columns: sums.iter().map(|s| s.to_be_bytes().to_vec()).collect(),
+        })
+    }
+
+    async fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Record>, VerifyError> {
+        let rows = dispatch_driver!(&self.driver, |d| d.query_params(sql, params).await?);
+        Ok(rows)
+    }
+}
+
+/// `table AS alias`; the alias lets pipeline filters refer to `table.column`.
+pub(crate) fn table_ref(dialect: &dyn Dialect, table: &str) -> String {
+    let alias = table.rsplit('.').next().unwrap_or(table);
+    format!(
+        "{} AS {}",
+        dialect.quote_identifier(table),
+        dialect.quote_identifier(alias)
+    )
+}
+
+pub(crate) fn sql_dialect(driver: &DriverRef) -> Box<dyn Dialect> {
+    match driver {
+        DriverRef::Postgres(_) => Box::new(dialect::Postgres),
+        DriverRef::MySql(_) => Box::new(dialect::MySql),
+    }
+}
+
+fn column_alias(index: usize) -> String {
+    format!("c{index}")
+}
+
+/// The pipeline's `where` conditions on the source table as SQL, so that
+/// rows the pipeline never copied are not counted as missing.
+pub(crate) fn source_filter(pipeline: &Pipeline) -> Result<Option<String>, VerifyError> {
+    let Some(condition) = combine_filters(&pipeline.source.filters) else {
+        return Ok(None);
+    };
+    let filter = SqlFilterCompiler::compile(&condition)
+        .map_err(|e| VerifyError::InitializationError(e.to_string()))?;
+
+    let source_table = &pipeline.source.table;
+    if filter
+        .tables()
+        .iter()
+        .any(|t| !t.eq_ignore_ascii_case(source_table))
+    {
+        warn!(
+            pipeline = %pipeline.name,
+            "verify ignores where conditions on joined tables; row counts may differ"
+        );
+    }
+
+    Ok(filter
+        .for_table(source_table, &[])
+        .expr
+        .map(|expr| expr.to_sql()))
+}
+
+/// Sums and extremes come back as different numeric types from different
+/// databases (`SUM(int)` is DECIMAL in MySQL and BIGINT in PostgreSQL);
+/// compare numbers by value.
+fn normalize_aggregate(value: Value) -> Value {
+    match value {
+        Value::Int(n) => Value::Decimal(BigDecimal::from(n).normalized()),
+        Value::UInt(n) => Value::Decimal(BigDecimal::from(n).normalized()),
+        Value::Decimal(d) => Value::Decimal(d.normalized()),
+        // Float sums depend on summation order; compare to 9 significant digits
+        Value::Float(f) => Value::String(format!("{f:.9e}")),
+        other => other,
+    }
+}
+
+fn count_of(row: &Record, field: &str) -> u64 {
+    match row.get_value(field) {
+        Value::Int(n) => n.max(0) as u64,
+        Value::UInt(n) => n,
+        Value::Decimal(d) => d.to_string().parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+pub(crate) fn display_key(value: &Value) -> String {
+    value.as_string().unwrap_or_else(|| "NULL".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_kind() {
+        assert_eq!(ColumnKind::of("integer"), ColumnKind::Numeric);
+        assert_eq!(ColumnKind::of("decimal(10,2)"), ColumnKind::Numeric);
+        assert_eq!(ColumnKind::of("double precision"), ColumnKind::Numeric);
+        assert_eq!(
+            ColumnKind::of("timestamp without time zone"),
+            ColumnKind::Temporal
+        );
+        assert_eq!(ColumnKind::of("datetime"), ColumnKind::Temporal);
+        assert_eq!(ColumnKind::of("interval"), ColumnKind::Other);
+        assert_eq!(ColumnKind::of("varchar(255)"), ColumnKind::Other);
+    }
+
+    #[test]
+    fn test_aggregate_kind_needs_both_sides() {
+        let col = ComparedColumn::from_metadata("active", "tinyint", "active", "boolean");
+        assert_eq!(col.kind, ColumnKind::Other);
+
+        let col = ComparedColumn::from_metadata("total", "decimal", "total", "numeric");
+        assert_eq!(col.kind, ColumnKind::Numeric);
+    }
+
+    #[test]
+    fn test_normalize_aggregate_compares_numbers_by_value() {
+        let mut mysql = Vec::new();
+        let mut postgres = Vec::new();
+        serialize_value(
+            &normalize_aggregate(Value::Decimal("1500.00".parse().unwrap())),
+            &mut mysql,
+        );
+        serialize_value(&normalize_aggregate(Value::Int(1500)), &mut postgres);
+        assert_eq!(mysql, postgres);
+    }
+
+    fn orders_sql(dialect: Box<dyn Dialect>) -> TableSql {
+        TableSql {
+            dialect,
+            table: "orders".to_string(),
+            key: "id".to_string(),
+            columns: vec!["total".to_string(), "note".to_string()],
+            filter: Some("orders.status = 'paid'".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_where_clause_binds_key_range() {
+        let sql = orders_sql(Box::new(dialect::Postgres));
+        let (clause, params) = sql.where_clause(&KeyRange {
+            after: Some(Value::Int(10)),
+            through: Some(Value::Int(20)),
+        });
+        assert_eq!(
+            clause,
+            r#" WHERE "id" > $1 AND "id" <= $2 AND (orders.status = 'paid')"#
+        );
+        assert_eq!(params, vec![Value::Int(10), Value::Int(20)]);
+    }
+
+    #[test]
+    fn test_chunk_end_query() {
+        let sql = orders_sql(Box::new(dialect::MySql));
+        let (query, params) = sql.chunk_end(Some(&Value::Int(100)), 500);
+        assert_eq!(
+            query,
+            "SELECT `id` AS k FROM `orders` AS `orders` WHERE `id` > ? AND (orders.status = 'paid') \
+             ORDER BY `id` LIMIT 1 OFFSET 499"
+        );
+        assert_eq!(params, vec![Value::Int(100)]);
+    }
+
+    #[test]
+    fn test_aggregate_query_skips_min_max_for_text() {
+        let sql = orders_sql(Box::new(dialect::Postgres));
+        let columns = [
+            ComparedColumn::from_metadata("total", "numeric", "total", "numeric"),
+            ComparedColumn::from_metadata("note", "text", "note", "text"),
+        ];
+        let (query, params) = sql.aggregate(
+            &KeyRange {
+                after: None,
+                through: None,
+            },
+            &columns,
+        );
+        assert_eq!(
+            query,
+            "SELECT COUNT(*) AS row_count, COUNT(\"total\") AS n0, MIN(\"total\") AS min0, \
+             MAX(\"total\") AS max0, SUM(\"total\") AS sum0, COUNT(\"note\") AS n1 \
+             FROM \"orders\" AS \"orders\" WHERE (orders.status = 'paid')"
+        );
+        assert!(params.is_empty());
+    }
+}
@@ -19,4 +19,7 @@ pub enum VerifyError {
 
     #[error("Verification failed: one or more tables have mismatched hashes")]
     Mismatch,
+
+    #[error("Verification failed: source and destination differ for one or more pipelines")]
+    Discrepancy,
 }
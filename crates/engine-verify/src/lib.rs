@@ -1,5 +1,8 @@
+pub mod compare;
 pub mod error;
 pub mod reader;
+pub mod reconcile;
+pub mod snapshot;
 pub mod verifier;
 
 pub use verifier::verify;
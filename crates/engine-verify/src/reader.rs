@@ -32,17 +32,25 @@ impl TableReader {
     /// Returns `(rows, next_cursor)` where `next_cursor = None` signals the table is exhausted.
     pub async fn next_batch(
         &self,
-        cursor: Cursor,
+        mut cursor: Cursor,
         limit: usize,
     ) -> Result<(Vec<Record>, Option<Cursor>), crate::error::VerifyError> {
-        let request = self.build_request(&cursor, limit);
-        let rows = self.driver.fetch(request).await?;
+        loop {
+            let request = self.build_request(&cursor, limit);
+            let rows = self.driver.fetch(request).await?;
 
-        let reached_end = rows.len() < limit;
-        let last_row = rows.last().cloned();
-        let next_cursor = self.compute_next_cursor(last_row.as_ref(), &cursor, reached_end, limit);
+            let exhausted = rows.len() < limit;
+            let last_row = rows.last().cloned();
+            let next_cursor =
+                self.compute_next_cursor(last_row.as_ref(), &cursor, exhausted, limit);
 
-        Ok((rows, next_cursor))
+            // An empty page that only switches partition (e.g. to the NULL
+            // cursor values) is not a batch of its own; read on from there.
+            match next_cursor {
+                Some(next) if rows.is_empty() => cursor = next,
+                _ => return Ok((rows, next_cursor)),
+            }
+        }
     }
 
     fn build_request(&self, cursor: &Cursor, limit: usize) -> FetchRowsRequest {
@@ -63,11 +71,11 @@ impl TableReader {
         &self,
         last_row: Option<&Record>,
         current_cursor: &Cursor,
-        reached_end: bool,
+        exhausted: bool,
         limit: usize,
     ) -> Option<Cursor> {
-        if reached_end {
-            return None;
+        if exhausted {
+            return self.offset_strategy.next_partition(current_cursor);
         }
 
         last_row.map(|row| {
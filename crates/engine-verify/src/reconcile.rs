@@ -0,0 +1,425 @@
+//! Sampling-based reconciliation of a pipeline's destination with its source.
+//!
+//! A random sample of source rows is run through the pipeline's transforms in
+//! memory, and every resulting row is compared field by field with the
+//! destination row of the same key. Where `compare` tells which chunk of keys
+//! differs, this names the row and the column, including computed fields.
+
+use crate::{
+    compare::{display_key, find_column, source_filter, source_key, sql_dialect, table_ref},
+    error::VerifyError,
+};
+use connectors::{sql::metadata::table::TableMetadata, traits::executor::QueryExecutor};
+use engine_config::settings::{CopyColumns, Settings};
+use engine_core::{
+    context::{env::EnvContext, exec::ConnectionPool},
+    dispatch_driver,
+    drivers::DriverRef,
+    plan::execution::ExecutionPlan,
+};
+use engine_processing::{
+    producer::build_transform_pipeline,
+    transform::pipeline::{ApplyOutcome, TransformPipeline},
+};
+use engine_wasm::registry::{PluginRegistry, load_registry};
+use expression_engine::ExpressionAnalyzer;
+use model::{
+    core::value::Value,
+    execution::pipeline::Pipeline,
+    integrity::{
+        canonical::serialize_value,
+        coerce::coerce_row_for_hash,
+        reconciliation::{FieldMismatch, ReconciliationReport},
+    },
+    records::Record,
+    transform::{computed_field::ComputedField, mapping::TransformationMetadata},
+};
+use query_builder::dialect::Dialect;
+use std::{collections::HashMap, sync::Arc, time::Instant};
+use tracing::{debug, info, warn};
+
+/// Keys per destination lookup query
+const LOOKUP_BATCH_SIZE: usize = 500;
+
+/// Characters of a value shown in a mismatch
+const MAX_DISPLAY_LEN: usize = 80;
+
+/// Reconciles up to `sample_size` random source rows of every pipeline with
+/// the destination.
+pub async fn reconcile(
+    plan: &ExecutionPlan,
+    sample_size: usize,
+    env: Arc<EnvContext>,
+) -> Result<Vec<ReconciliationReport>, VerifyError> {
+    let registry = load_registry(&plan.plugins)
+        .map_err(|e| VerifyError::InitializationError(e.to_string()))?;
+    let mut pool = ConnectionPool::new();
+    let mut reports = Vec::new();
+
+    for pipeline in &plan.pipelines {
+        if pipeline.destination.table.is_empty() {
+            warn!(
+                pipeline = %pipeline.name,
+                "pipeline has no single destination table; skipping reconciliation"
+            );
+            continue;
+        }
+        reports.push(
+            reconcile_pipeline(pipeline, sample_size, &registry, env.clone(), &mut pool).await?,
+        );
+    }
+
+    Ok(reports)
+}
+
+async fn reconcile_pipeline(
+    pipeline: &Pipeline,
+    sample_size: usize,
+    registry: &PluginRegistry,
+    env: Arc<EnvContext>,
+    pool: &mut ConnectionPool,
+) -> Result<ReconciliationReport, VerifyError> {
+    let start = Instant::now();
+    let source = &pipeline.source;
+    let destination = &pipeline.destination;
+    info!(
+        pipeline = %pipeline.name,
+        sample = sample_size,
+        "reconciling sampled rows with destination"
+    );
+
+    let src_driver =
+        DriverRef::resolve(&source.connection.driver, &source.connection, pool).await?;
+    let dst_driver = DriverRef::resolve(
+        &destination.connection.driver,
+        &destination.connection,
+        pool,
+    )
+    .await?;
+    let src_meta = src_driver.table_metadata(&source.table).await?;
+    let dst_meta = dst_driver.table_metadata(&destination.table).await?;
+
+    let mapping = TransformationMetadata::new(pipeline);
+    let entity = destination.table.to_ascii_lowercase();
+    let key = source_key(
+        pipeline,
+        pipeline.verify.as_ref().and_then(|v| v.key.as_deref()),
+        &src_meta,
+    )?;
+    let dest_key = mapping.field_mappings.resolve(&entity, &key);
+    let dest_key = find_column(&dst_meta, &dest_key)
+        .ok_or_else(|| {
+            VerifyError::InitializationError(format!(
+                "pipeline '{}': key column '{}' does not exist in table '{}'",
+                pipeline.name, dest_key, destination.table
+            ))
+        })?
+        .name
+        .clone();
+
+    let unchecked = join_dependent_fields(
+        mapping
+            .field_mappings
+            .get_computed(&entity)
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+        &source.table,
+    );
+    let mapped_columns_only =
+        Settings::from_map(&pipeline.settings).copy_columns == CopyColumns::MapOnly;
+    let transforms = build_transform_pipeline(
+        pipeline,
+        registry,
+        &mapping,
+        mapped_columns_only,
+        env,
+        None,
+        None,
+        HashMap::new(),
+    )
+    .map_err(|e| VerifyError::InitializationError(e.to_string()))?;
+
+    let src_dialect = sql_dialect(&src_driver);
+    let sql = sample_query(
+        src_dialect.as_ref(),
+        &source.table,
+        source_filter(pipeline)?.as_deref(),
+        sample_size,
+    );
+    let rows = dispatch_driver!(&src_driver, |d| d.query_params(&sql, &[]).await?);
+
+    let mut report = ReconciliationReport {
+        pipeline: pipeline.name.clone(),
+        source_table: source.table.clone(),
+        destination_table: destination.table.clone(),
+        key: dest_key.clone(),
+        sampled: rows.len() as u64,
+        skipped: 0,
+        missing: Vec::new(),
+        mismatches: Vec::new(),
+        unchecked_columns: unchecked
+            .iter()
+            .filter_map(|f| find_column(&dst_meta, f).map(|c| c.name.clone()))
+            .collect(),
+        duration_ms: 0,
+    };
+
+    let expected = transform_sample(pipeline, rows, &transforms, &mut report);
+    let col_types = column_types(&dst_meta);
+    let actual = fetch_destination(
+        &dst_driver,
+        &destination.table,
+        &dest_key,
+        &expected,
+        &col_types,
+    )
+    .await?;
+
+    for row in &expected {
+        let expected_values = canonical_fields(row, &col_types);
+        let key_value = row.get_value(&dest_key);
+        let Some(found) = expected_values
+            .get(&dest_key.to_ascii_lowercase())
+            .and_then(|k| actual.get(k))
+        else {
+            report.missing.push(display_key(&key_value));
+            continue;
+        };
+        let actual_values = canonical_fields(found, &col_types);
+
+        for field in &row.fields {
+            let name = field.name.to_ascii_lowercase();
+            if unchecked.contains(&name) {
+                continue;
+            }
+            // Fields without a destination column are never written
+            let Some(column) = find_column(&dst_meta, &field.name) else {
+                continue;
+            };
+            if expected_values.get(&name) != actual_values.get(&name) {
+                report.mismatches.push(FieldMismatch {
+                    key: display_key(&key_value),
+                    column: column.name.clone(),
+                    expected: display_value(&row.get_value(&field.name)),
+                    actual: display_value(&found.get_value(&column.name)),
+                });
+            }
+        }
+    }
+
+    report.duration_ms = start.elapsed().as_millis() as u64;
+    Ok(report)
+}
+
+/// Runs the transforms over the sampled rows and returns the ones the
+/// pipeline would write; the rest are counted as skipped.
+fn transform_sample(
+    pipeline: &Pipeline,
+    rows: Vec<Record>,
+    transforms: &TransformPipeline,
+    report: &mut ReconciliationReport,
+) -> Vec<Record> {
+    let mut expected = Vec::with_capacity(rows.len());
+    for mut row in rows {
+        row.schema = pipeline.source.table.clone();
+        match transforms.apply(&mut row) {
+            Ok(ApplyOutcome::Skipped { .. }) => report.skipped += 1,
+            Ok(_) => expected.push(row),
+            Err(e) => {
+                debug!(pipeline = %pipeline.name, error = %e, "sampled row rejected by transforms");
+                report.skipped += 1;
+            }
+        }
+    }
+    expected
+}
+
+/// Destination rows for the keys of `expected`, by the canonical encoding of
+/// their key.
+async fn fetch_destination(
+    driver: &DriverRef,
+    table: &str,
+    key: &str,
+    expected: &[Record],
+    col_types: &HashMap<String, String>,
+) -> Result<HashMap<Vec<u8>, Record>, VerifyError> {
+    let dialect = sql_dialect(driver);
+    let keys: Vec<Value> = expected.iter().map(|r| r.get_value(key)).collect();
+    let key_lower = key.to_ascii_lowercase();
+
+    let mut rows = HashMap::new();
+    for batch in keys.chunks(LOOKUP_BATCH_SIZE) {
+        let sql = lookup_query(dialect.as_ref(), table, key, batch.len());
+        let found = dispatch_driver!(driver, |d| d.query_params(&sql, batch).await?);
+        for row in found {
+            if let Some(k) = canonical_fields(&row, col_types).remove(&key_lower) {
+                rows.insert(k, row);
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// `sample_size` rows of `table` matching `filter`, in random order.
+fn sample_query(
+    dialect: &dyn Dialect,
+    table: &str,
+    filter: Option<&str>,
+    sample_size: usize,
+) -> String {
+    let where_clause = filter.map(|f| format!(" WHERE ({f})")).unwrap_or_default();
+    format!(
+        "SELECT * FROM {}{where_clause} ORDER BY {} LIMIT {sample_size}",
+        table_ref(dialect, table),
+        dialect.random_function()
+    )
+}
+
+/// Rows of `table` whose `key` is one of `count` bound values.
+fn lookup_query(dialect: &dyn Dialect, table: &str, key: &str, count: usize) -> String {
+    let placeholders: Vec<String> = (0..count).map(|i| dialect.get_placeholder(i)).collect();
+    format!(
+        "SELECT * FROM {} WHERE {} IN ({})",
+        dialect.quote_identifier(table),
+        dialect.quote_identifier(key),
+        placeholders.join(", ")
+    )
+}
+
+/// Destination column types by lowercase column name.
+fn column_types(meta: &TableMetadata) -> HashMap<String, String> {
+    meta.columns
+        .values()
+        .map(|c| (c.name.to_ascii_lowercase(), c.data_type.clone()))
+        .collect()
+}
+
+/// Canonical encoding of every field of `row` by lowercase name, after the
+/// coercions for the destination column type, so that a value produced by the
+/// transforms and the same value read back from the destination compare
+/// equal.
+fn canonical_fields(row: &Record, col_types: &HashMap<String, String>) -> HashMap<String, Vec<u8>> {
+    let mut row = row.clone();
+    for field in &mut row.fields {
+        field.name = field.name.to_ascii_lowercase();
+    }
+    coerce_row_for_hash(&row, col_types)
+        .fields
+        .into_iter()
+        .map(|f| {
+            let mut buf = Vec::new();
+            serialize_value(&f.value.unwrap_or(Value::Null), &mut buf);
+            (f.name, buf)
+        })
+        .collect()
+}
+
+/// Computed fields that read a table other than the source, directly or
+/// through another computed field. The sampled rows carry the source columns
+/// only, so these cannot be recomputed.
+fn join_dependent_fields(computed: &[ComputedField], source_table: &str) -> Vec<String> {
+    let source_alias = source_table.rsplit('.').next().unwrap_or(source_table);
+    let mut dependent: Vec<String> = Vec::new();
+
+    loop {
+        let before = dependent.len();
+        for field in computed {
+            let name = field.name.to_ascii_lowercase();
+            if dependent.contains(&name) {
+                continue;
+            }
+            let reads_other = ExpressionAnalyzer::extract_columns(&field.expression)
+                .iter()
+                .any(|column| {
+                    let column = column.to_ascii_lowercase();
+                    match column.split_once('.') {
+                        Some((table, _)) => {
+                            !table.eq_ignore_ascii_case(source_table)
+                                && !table.eq_ignore_ascii_case(source_alias)
+                        }
+                        None => dependent.contains(&column),
+                    }
+                });
+            if reads_other {
+                dependent.push(name);
+            }
+        }
+        if dependent.len() == before {
+            break;
+        }
+    }
+
+    dependent.sort();
+    dependent
+}
+
+fn display_value(value: &Value) -> Option<String> {
+    let s = value.as_string()?;
+    if s.chars().count() <= MAX_DISPLAY_LEN {
+        return Some(s);
+    }
+    let mut truncated: String = s.chars().take(MAX_DISPLAY_LEN).collect();
+    truncated.push('…');
+    Some(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::execution::expr::{BinaryOp, CompiledExpression};
+    use query_builder::dialect::{MySql, Postgres};
+
+    fn ident(name: &str) -> CompiledExpression {
+        CompiledExpression::Identifier(name.to_string())
+    }
+
+    fn path(table: &str, column: &str) -> CompiledExpression {
+        CompiledExpression::DotPath(vec![table.to_string(), column.to_string()])
+    }
+
+    #[test]
+    fn test_join_dependent_fields_follow_computed_chains() {
+        let computed = vec![
+            ComputedField::new(
+                "total",
+                &CompiledExpression::Binary {
+                    left: Box::new(ident("price")),
+                    op: BinaryOp::Multiply,
+                    right: Box::new(path("orders", "quantity")),
+                },
+            ),
+            ComputedField::new("customer_name", &path("c", "name")),
+            ComputedField::new("label", &ident("customer_name")),
+        ];
+
+        assert_eq!(
+            join_dependent_fields(&computed, "public.orders"),
+            vec!["customer_name".to_string(), "label".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sample_and_lookup_queries() {
+        assert_eq!(
+            sample_query(&Postgres, "orders", Some("orders.status = 'paid'"), 100),
+            r#"SELECT * FROM "orders" AS "orders" WHERE (orders.status = 'paid') ORDER BY RANDOM() LIMIT 100"#
+        );
+        assert_eq!(
+            sample_query(&MySql, "orders", None, 10),
+            "SELECT * FROM `orders` AS `orders` ORDER BY RAND() LIMIT 10"
+        );
+        assert_eq!(
+            lookup_query(&Postgres, "orders", "id", 3),
+            r#"SELECT * FROM "orders" WHERE "id" IN ($1, $2, $3)"#
+        );
+    }
+
+    #[test]
+    fn test_display_value_truncates_long_values() {
+        assert_eq!(display_value(&Value::Null), None);
+        assert_eq!(display_value(&Value::Int(7)), Some("7".to_string()));
+        let long = display_value(&Value::String("x".repeat(200))).unwrap();
+        assert_eq!(long.chars().count(), MAX_DISPLAY_LEN + 1);
+        assert!(long.ends_with('…'));
+    }
+}
@@ -0,0 +1,71 @@
+//! Pinning sources to one snapshot for a whole run.
+//!
+//! A pipeline whose `verify` block has `as_of = "snapshot"` pins its
+//! PostgreSQL source: a snapshot is exported before the copy starts, and
+//! every source connection of the run, server-side cursors included, reads
+//! at it. The comparison after the copy reads the source at the same
+//! snapshot, so rows written to the source while the copy ran are not
+//! reported as discrepancies.
+
+use crate::error::VerifyError;
+use connectors::drivers::postgres::snapshot::PgSnapshot;
+use engine_core::plan::execution::ExecutionPlan;
+use model::execution::{connection::Connection, verify::VerifyAsOf};
+use tracing::info;
+
+/// Source connections of the pipelines that verify `as_of = "snapshot"`,
+/// each listed once.
+pub fn snapshot_sources(plan: &ExecutionPlan) -> Vec<&Connection> {
+    let mut sources: Vec<&Connection> = Vec::new();
+    for pipeline in &plan.pipelines {
+        let pinned = pipeline
+            .verify
+            .as_ref()
+            .is_some_and(|v| v.as_of == VerifyAsOf::Snapshot);
+        let connection = &pipeline.source.connection;
+        if pinned && sources.iter().all(|c| c.name != connection.name) {
+            sources.push(connection);
+        }
+    }
+    sources
+}
+
+/// Export a snapshot for every source in `snapshot_sources` and record it in
+/// `plan.source_snapshots`. The snapshots stay importable only while the
+/// returned values are alive, so hold them until the comparison is done.
+pub async fn pin_source_snapshots(
+    plan: &mut ExecutionPlan,
+) -> Result<Vec<PgSnapshot>, VerifyError> {
+    let sources: Vec<Connection> = snapshot_sources(plan).into_iter().cloned().collect();
+
+    let mut snapshots = Vec::with_capacity(sources.len());
+    for connection in sources {
+        // A pinned connection is read-only, so no pipeline may write to it
+        if let Some(writer) = plan
+            .pipelines
+            .iter()
+            .find(|p| p.destination.connection.name == connection.name)
+        {
+            return Err(VerifyError::InitializationError(format!(
+                "connection '{}' cannot be pinned to a snapshot: pipeline '{}' writes to it",
+                connection.name, writer.name
+            )));
+        }
+
+        let url = connection.properties.get_string("url").ok_or_else(|| {
+            VerifyError::InitializationError(format!("connection '{}' has no url", connection.name))
+        })?;
+        let snapshot = PgSnapshot::export(&url).await?;
+        info!(
+            connection = %connection.name,
+            snapshot = %snapshot.id(),
+            "pinned source to snapshot"
+        );
+
+        plan.source_snapshots
+            .insert(connection.name.clone(), snapshot.id().to_string());
+        snapshots.push(snapshot);
+    }
+
+    Ok(snapshots)
+}
@@ -7,17 +7,22 @@ use engine_core::{
     context::{env::EnvContext, exec::ExecutionContext},
     dispatch_driver,
     drivers::DriverRef,
-    plan::{cascade::resolve_cascade_tables, execution::ExecutionPlan},
+    plan::{
+        cascade::resolve_cascade_tables,
+        execution::ExecutionPlan,
+        pagination::{uses_tiebreaker, with_default_tiebreaker},
+    },
     schema::{
         graph_expander::GraphExpander,
         type_registry::{Dialect, TypeRegistry},
     },
 };
-use engine_state::{MerkleStore, sled_store::SledStateStore};
+use engine_state::{SharedStateStore, open_state_store};
 use model::{
     execution::{
         pipeline::{Pagination, Pipeline},
         references::{DataMode, GraphReferences},
+        state_config::StateConfig,
     },
     integrity::{
         coerce::coerce_row_for_hash,
@@ -41,7 +46,7 @@ pub async fn verify(
     plan: ExecutionPlan,
     env: Arc<EnvContext>,
 ) -> Result<Vec<VerificationResult>, VerifyError> {
-    let state = init_state()?;
+    let state = init_state(&plan.state).await?;
     let exec_ctx = ExecutionContext::new(&plan, state.clone(), env).await?;
     let mut results: Vec<VerificationResult> = Vec::new();
 
@@ -52,28 +57,44 @@ pub async fn verify(
     Ok(results)
 }
 
-fn init_state() -> Result<Arc<SledStateStore>, VerifyError> {
+async fn init_state(config: &StateConfig) -> Result<SharedStateStore, VerifyError> {
     let home_dir = dirs::home_dir().ok_or_else(|| {
         VerifyError::InitializationError("Failed to determine home directory".to_string())
     })?;
     let path = home_dir.join(".stratum/state");
 
-    SledStateStore::open(path)
-        .map(Arc::new)
+    open_state_store(config, &path)
+        .await
         .map_err(|e| VerifyError::InitializationError(e.to_string()))
 }
 
 async fn verify_pipeline(
     pipeline: &Pipeline,
     exec_ctx: &ExecutionContext,
-    state: &Arc<SledStateStore>,
+    state: &SharedStateStore,
     results: &mut Vec<VerificationResult>,
 ) -> Result<(), VerifyError> {
     let driver = exec_ctx
         .resolve_driver(&pipeline.destination.connection)
         .await?;
     let mapping = TransformationMetadata::new(pipeline);
-    let resolved_pagination = resolve_pagination(&pipeline.source.pagination, &mapping);
+    let mut resolved_pagination = resolve_pagination(&pipeline.source.pagination, &mapping);
+
+    // The run defaulted a missing tiebreaker to the primary key; page the same way.
+    if let Some(pag) = &resolved_pagination
+        && pag.tiebreaker.is_none()
+        && uses_tiebreaker(&pag.strategy)
+    {
+        let meta = fetch_table_metadata(&driver, &pipeline.destination.table).await?;
+        let pag = with_default_tiebreaker(pag, &meta);
+        if pag.tiebreaker.is_none() {
+            return Err(VerifyError::InitializationError(format!(
+                "Pagination on '{}' needs a tiebreaker: table '{}' has no single-column primary key",
+                pag.column, pipeline.destination.table
+            )));
+        }
+        resolved_pagination = Some(pag);
+    }
 
     if resolved_pagination.is_none() {
         warn!(
@@ -84,7 +105,8 @@ async fn verify_pipeline(
         );
     }
 
-    let offset_strategy = OffsetStrategyFactory::from_pagination(&resolved_pagination);
+    let offset_strategy = OffsetStrategyFactory::from_pagination(&resolved_pagination)
+        .map_err(VerifyError::InitializationError)?;
 
     let cascade_meta = get_graph_expansion(pipeline, &driver, &mapping).await?;
     let cascade_tables = resolve_cascade_tables(pipeline, &mapping, &cascade_meta);
@@ -437,6 +459,7 @@ fn resolve_pagination(
             .as_ref()
             .map(|tb| resolve_qualified_column(tb, mapping)),
         timezone: pag.timezone.clone(),
+        nulls: pag.nulls.clone(),
     })
 }
 
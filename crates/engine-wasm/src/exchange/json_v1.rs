@@ -231,7 +231,7 @@ fn value_to_json(value: &Value) -> JsonValue {
         Value::Json(j) => json!({ "type": "json", "value": j }),
         Value::Enum { value: v, .. } => json!({ "type": "string", "value": v }),
         // For types that don't have a clean JSON representation, serialize as string
-        other => json!({ "type": "string", "value": format!("{:?}", other) }),
+        other => json!({ "type": "string", "value": other.as_string() }),
     }
 }
 
@@ -38,10 +38,8 @@ impl ExpressionAnalyzer {
     fn extract_columns_recursive(expr: &CompiledExpression, columns: &mut Vec<String>) {
         match expr {
             CompiledExpression::Identifier(name) => columns.push(name.clone()),
-            CompiledExpression::DotPath(segments) => {
-                if segments.len() >= 2 {
-                    columns.push(format!("{}.{}", segments[0], segments[1]));
-                }
+            CompiledExpression::DotPath(segments) if segments.len() >= 2 => {
+                columns.push(format!("{}.{}", segments[0], segments[1]));
             }
             CompiledExpression::Binary { left, right, .. } => {
                 Self::extract_columns_recursive(left, columns);
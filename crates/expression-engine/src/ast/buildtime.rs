@@ -59,6 +59,14 @@ pub fn eval_ast_expression(
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(Value::Array(values))
         }
+        ExpressionKind::Map(entries) => {
+            let mut map = serde_json::Map::new();
+            for entry in entries {
+                let value = eval_ast_expression(&entry.value, definitions, env_getter)?;
+                map.insert(entry.key.clone(), value.to_json());
+            }
+            Ok(Value::Json(serde_json::Value::Object(map)))
+        }
         _ => Err(ExpressionError::InvalidFunctionArgs {
             function: "eval_ast_expression".to_string(),
             message: format!("cannot evaluate complex expression: {:?}", expr),
@@ -1,3 +1,4 @@
+use bigdecimal::BigDecimal;
 use model::{core::value::Value, execution::expr::BinaryOp};
 use tracing::warn;
 
@@ -30,30 +31,15 @@ impl<'a> BinaryOpEvaluator<'a> {
             (Float(l), UInt(r)) => self.eval_float(*l, *r as f64),
             (UInt(l), Float(r)) => self.eval_float(*l as f64, *r),
 
-            // Decimal operations
+            // Decimal operations. Any decimal operand keeps the result exact;
+            // the other side is promoted rather than the decimal demoted to float.
             (Decimal(l), Decimal(r)) => self.eval_decimal_values(l, r),
-            (Decimal(l), Int(r)) => {
-                let r_dec = bigdecimal::BigDecimal::from(*r);
-                self.eval_decimal_values(l, &r_dec)
-            }
-            (Int(l), Decimal(r)) => {
-                let l_dec = bigdecimal::BigDecimal::from(*l);
-                self.eval_decimal_values(&l_dec, r)
-            }
-            (Decimal(l), Float(r)) => {
-                if let Ok(r_dec) = bigdecimal::BigDecimal::try_from(*r) {
-                    self.eval_decimal_values(l, &r_dec)
-                } else {
-                    None
-                }
-            }
-            (Float(l), Decimal(r)) => {
-                if let Ok(l_dec) = bigdecimal::BigDecimal::try_from(*l) {
-                    self.eval_decimal_values(&l_dec, r)
-                } else {
-                    None
-                }
-            }
+            (Decimal(l), Int(r)) => self.eval_decimal_values(l, &BigDecimal::from(*r)),
+            (Int(l), Decimal(r)) => self.eval_decimal_values(&BigDecimal::from(*l), r),
+            (Decimal(l), UInt(r)) => self.eval_decimal_values(l, &BigDecimal::from(*r)),
+            (UInt(l), Decimal(r)) => self.eval_decimal_values(&BigDecimal::from(*l), r),
+            (Decimal(l), Float(r)) => self.eval_decimal_values(l, &float_to_decimal(*r)?),
+            (Float(l), Decimal(r)) => self.eval_decimal_values(&float_to_decimal(*l)?, r),
 
             // String operations
             (String(l), String(r)) => self.eval_string(l, r),
@@ -157,13 +143,7 @@ impl<'a> BinaryOpEvaluator<'a> {
         })
     }
 
-    fn eval_decimal_values(
-        &self,
-        l: &bigdecimal::BigDecimal,
-        r: &bigdecimal::BigDecimal,
-    ) -> Option<Value> {
-        use bigdecimal::BigDecimal;
-
+    fn eval_decimal_values(&self, l: &BigDecimal, r: &BigDecimal) -> Option<Value> {
         Some(match self.op {
             BinaryOp::Add => Value::Decimal(l + r),
             BinaryOp::Subtract => Value::Decimal(l - r),
@@ -239,6 +219,16 @@ impl<'a> BinaryOpEvaluator<'a> {
     }
 }
 
+/// Promote a float operand to a decimal through its shortest round-trip text,
+/// so a literal such as `1.1` means 1.1 and not the nearest double's expansion.
+fn float_to_decimal(value: f64) -> Option<BigDecimal> {
+    if !value.is_finite() {
+        warn!(value, "non-finite float cannot be combined with a decimal");
+        return None;
+    }
+    value.to_string().parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,4 +309,62 @@ mod tests {
         let evaluator = BinaryOpEvaluator::new(&left, &right, &BinaryOp::Or);
         assert_eq!(evaluator.evaluate(), Some(Value::Boolean(true)));
     }
+
+    #[test]
+    fn test_decimal_operations_stay_exact() {
+        let dec = |s: &str| Value::Decimal(s.parse().unwrap());
+        let cases = vec![
+            (dec("0.10"), dec("0.20"), BinaryOp::Add, dec("0.30")),
+            (
+                dec("19.99"),
+                Value::Int(3),
+                BinaryOp::Multiply,
+                dec("59.97"),
+            ),
+            (
+                Value::UInt(2),
+                dec("0.005"),
+                BinaryOp::Multiply,
+                dec("0.010"),
+            ),
+            (
+                dec("100.00"),
+                Value::Float(1.1),
+                BinaryOp::Multiply,
+                dec("110"),
+            ),
+            (Value::Float(0.1), dec("0.2"), BinaryOp::Add, dec("0.3")),
+            (
+                dec("12345678901234567890.12"),
+                dec("0.01"),
+                BinaryOp::Subtract,
+                dec("12345678901234567890.11"),
+            ),
+            (
+                dec("1.50"),
+                dec("1.5"),
+                BinaryOp::Equal,
+                Value::Boolean(true),
+            ),
+        ];
+
+        for (left, right, op, expected) in cases {
+            let result = BinaryOpEvaluator::new(&left, &right, &op).evaluate();
+            assert_eq!(
+                result,
+                Some(expected),
+                "Failed for {left:?} {op:?} {right:?}"
+            );
+        }
+
+        let zero = dec("0");
+        assert_eq!(
+            BinaryOpEvaluator::new(&dec("1"), &zero, &BinaryOp::Divide).evaluate(),
+            None
+        );
+        assert_eq!(
+            BinaryOpEvaluator::new(&dec("1"), &Value::Float(f64::NAN), &BinaryOp::Add).evaluate(),
+            None
+        );
+    }
 }
@@ -1,4 +1,4 @@
-mod binary;
+pub(crate) mod binary;
 pub mod runtime;
 
 pub use runtime::Evaluator;
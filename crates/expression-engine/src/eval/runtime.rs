@@ -1,4 +1,8 @@
-use crate::{context::EvalContext, eval::binary::BinaryOpEvaluator, functions::FunctionRegistry};
+use crate::{
+    context::EvalContext,
+    eval::binary::BinaryOpEvaluator,
+    functions::{conditional::CONDITIONAL_FUNCTIONS, FunctionRegistry},
+};
 use model::{
     core::value::Value,
     execution::expr::{BinaryOp, CompiledExpression},
@@ -40,6 +44,21 @@ impl Evaluator for CompiledExpression {
                 eval_binary_op(&left_val, &right_val, op)
             }
 
+            CompiledExpression::FunctionCall { name, args }
+                if CONDITIONAL_FUNCTIONS.contains(&name.to_ascii_lowercase().as_str()) =>
+            {
+                // Conditionals decide what a null argument means, so it is
+                // passed on instead of making the call null
+                let evaluated_args: Vec<Value> = args
+                    .iter()
+                    .map(|arg| {
+                        arg.evaluate(row, mapping, env_getter)
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect();
+                eval_function(name, &evaluated_args, row, mapping, env_getter)
+            }
+
             CompiledExpression::FunctionCall { name, args } => {
                 let evaluated_args: Vec<Value> = args
                     .iter()
@@ -0,0 +1,119 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+    eval::binary::BinaryOpEvaluator,
+};
+use model::{core::value::Value, execution::expr::BinaryOp};
+
+/// Functions that decide what a null argument means, so a null argument
+/// must not make the whole call null
+pub const CONDITIONAL_FUNCTIONS: &[&str] = &["if", "coalesce", "nullif"];
+
+/// Evaluate if(cond, a, b): `a` when `cond` is true, otherwise `b`
+///
+/// Like a `when` branch, a null or non-boolean condition counts as false.
+pub fn eval_if(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    match args {
+        [cond, then, otherwise] => Ok(match cond {
+            Value::Boolean(true) => then.clone(),
+            _ => otherwise.clone(),
+        }),
+        _ => Err(ExpressionError::InvalidFunctionArgs {
+            function: "if".to_string(),
+            message: format!("Expected 3 arguments, got {}", args.len()),
+        }),
+    }
+}
+
+/// Evaluate coalesce(a, b, ...), the first argument that is not null
+pub fn eval_coalesce(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(args
+        .iter()
+        .find(|arg| !matches!(arg, Value::Null))
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+/// Evaluate nullif(a, b): null when `a` equals `b`, otherwise `a`
+pub fn eval_nullif(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    match args {
+        [value, other] => {
+            let equal = BinaryOpEvaluator::new(value, other, &BinaryOp::Equal).evaluate();
+            Ok(match equal {
+                Some(Value::Boolean(true)) => Value::Null,
+                _ => value.clone(),
+            })
+        }
+        _ => Err(ExpressionError::InvalidFunctionArgs {
+            function: "nullif".to_string(),
+            message: format!("Expected 2 arguments, got {}", args.len()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn test_if() {
+        let args = |cond| [cond, s("active"), s("inactive")];
+        assert_eq!(
+            call(eval_if, &args(Value::Boolean(true))).unwrap(),
+            s("active")
+        );
+        assert_eq!(
+            call(eval_if, &args(Value::Boolean(false))).unwrap(),
+            s("inactive")
+        );
+        assert_eq!(call(eval_if, &args(Value::Null)).unwrap(), s("inactive"));
+        assert!(call(eval_if, &[Value::Boolean(true), s("a")]).is_err());
+    }
+
+    #[test]
+    fn test_coalesce() {
+        assert_eq!(
+            call(eval_coalesce, &[Value::Null, s("name"), s("N/A")]).unwrap(),
+            s("name")
+        );
+        assert_eq!(
+            call(eval_coalesce, &[Value::Null, Value::Null]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(call(eval_coalesce, &[]).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_nullif() {
+        assert_eq!(
+            call(eval_nullif, &[Value::Int(0), Value::Int(0)]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(
+            call(eval_nullif, &[Value::Int(7), Value::Int(0)]).unwrap(),
+            Value::Int(7)
+        );
+        assert_eq!(
+            call(eval_nullif, &[s("n/a"), s("n/a")]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(
+            call(eval_nullif, &[Value::Null, Value::Int(0)]).unwrap(),
+            Value::Null
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+    functions::arg_count,
+};
+use chrono::{
+    format::{Item, StrftimeItems},
+    Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Utc,
+};
+use model::core::value::{IntervalValue, Value};
+
+/// Evaluate now(), the current time in UTC
+pub fn eval_now(_args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(Value::Timestamp {
+        value: Utc::now().naive_utc(),
+        offset_secs: Some(0),
+    })
+}
+
+/// Truncate a date or timestamp: `date_trunc("month", orders.created_at)`
+///
+/// Units are year, quarter, month, week (starting Monday), day, hour,
+/// minute and second. The result keeps the type of `ts`.
+pub fn eval_date_trunc(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [unit, ts] = args else {
+        return Err(arg_count("date_trunc", "2", args));
+    };
+    let unit = unit_name("date_trunc", unit)?;
+    let Some(dt) = datetime("date_trunc", ts)? else {
+        return Ok(Value::Null);
+    };
+
+    let date = dt.date();
+    let start_of = |d: NaiveDate| d.and_hms_opt(0, 0, 0);
+    let truncated = match unit.as_str() {
+        "year" => date.with_ordinal(1).and_then(start_of),
+        "quarter" => date
+            .with_day(1)
+            .and_then(|d| d.with_month0(d.month0() / 3 * 3))
+            .and_then(start_of),
+        "month" => date.with_day(1).and_then(start_of),
+        "week" => start_of(date - Days::new(date.weekday().num_days_from_monday().into())),
+        "day" => start_of(date),
+        "hour" => date.and_hms_opt(dt.hour(), 0, 0),
+        "minute" => date.and_hms_opt(dt.hour(), dt.minute(), 0),
+        "second" => dt.with_nanosecond(0),
+        _ => return Err(unknown_unit("date_trunc", &unit)),
+    };
+
+    truncated
+        .map(|dt| with_datetime(ts, dt))
+        .ok_or_else(|| out_of_range("date_trunc"))
+}
+
+/// Shift a date or timestamp: `date_add(orders.created_at, "1 month 2 days")`
+///
+/// The interval is an interval value or text of `<n> <unit>` pairs, where a
+/// negative `n` goes back in time. The result keeps the type of `ts`.
+pub fn eval_date_add(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [ts, interval] = args else {
+        return Err(arg_count("date_add", "2", args));
+    };
+    let Some(dt) = datetime("date_add", ts)? else {
+        return Ok(Value::Null);
+    };
+    let interval = match interval {
+        Value::Interval(interval) => interval.clone(),
+        Value::String(s) => parse_interval(s)?,
+        other => return Err(expected("date_add", "interval", other)),
+    };
+
+    let months = Months::new(interval.months.unsigned_abs());
+    let days = Days::new(interval.days.unsigned_abs().into());
+    let shifted = match interval.months < 0 {
+        true => dt.checked_sub_months(months),
+        false => dt.checked_add_months(months),
+    }
+    .and_then(|dt| match interval.days < 0 {
+        true => dt.checked_sub_days(days),
+        false => dt.checked_add_days(days),
+    })
+    .and_then(|dt| dt.checked_add_signed(Duration::microseconds(interval.microseconds)));
+
+    shifted
+        .map(|dt| with_datetime(ts, dt))
+        .ok_or_else(|| out_of_range("date_add"))
+}
+
+/// Format a date or timestamp as text with strftime specifiers:
+/// `format_date(orders.created_at, "%Y-%m")`
+pub fn eval_format_date(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [ts, format] = args else {
+        return Err(arg_count("format_date", "2", args));
+    };
+    let Some(dt) = datetime("format_date", ts)? else {
+        return Ok(Value::Null);
+    };
+    let Value::String(format) = format else {
+        return Err(expected("format_date", "format string", format));
+    };
+
+    let items: Vec<_> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(ExpressionError::InvalidFunctionArgs {
+            function: "format_date".to_string(),
+            message: format!("Invalid format '{format}'"),
+        });
+    }
+    Ok(Value::String(
+        dt.format_with_items(items.into_iter()).to_string(),
+    ))
+}
+
+/// Extract a field of a date or timestamp as an integer:
+/// `extract("year", orders.created_at)`
+///
+/// Parts are year, quarter, month, week (ISO), day, dow (0 is Sunday), doy,
+/// hour, minute, second and epoch (seconds since 1970-01-01 UTC).
+pub fn eval_extract(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [part, ts] = args else {
+        return Err(arg_count("extract", "2", args));
+    };
+    let part = unit_name("extract", part)?;
+    let Some(dt) = datetime("extract", ts)? else {
+        return Ok(Value::Null);
+    };
+
+    let value = match part.as_str() {
+        "year" => dt.year().into(),
+        "quarter" => (dt.month0() / 3 + 1).into(),
+        "month" => dt.month().into(),
+        "week" => dt.iso_week().week().into(),
+        "day" => dt.day().into(),
+        "dow" => dt.weekday().num_days_from_sunday().into(),
+        "doy" => dt.ordinal().into(),
+        "hour" => dt.hour().into(),
+        "minute" => dt.minute().into(),
+        "second" => dt.second().into(),
+        "epoch" => dt.and_utc().timestamp(),
+        _ => return Err(unknown_unit("extract", &part)),
+    };
+    Ok(Value::Int(value))
+}
+
+/// Parse `<n> <unit>` pairs such as "1 month -2 days" into an interval.
+fn parse_interval(text: &str) -> Result<IntervalValue> {
+    let invalid = || ExpressionError::InvalidFunctionArgs {
+        function: "date_add".to_string(),
+        message: format!("Invalid interval '{text}'"),
+    };
+
+    let mut interval = IntervalValue::new(0, 0, 0);
+    let mut tokens = text.split_whitespace();
+    while let Some(amount) = tokens.next() {
+        let amount: i32 = amount.parse().map_err(|_| invalid())?;
+        let unit = tokens.next().ok_or_else(invalid)?.to_ascii_lowercase();
+        let micros = |per: i64| i64::from(amount) * per;
+        match unit.trim_end_matches('s') {
+            "year" => interval.months += amount * 12,
+            "month" => interval.months += amount,
+            "week" => interval.days += amount * 7,
+            "day" => interval.days += amount,
+            "hour" => interval.microseconds += micros(3_600_000_000),
+            "minute" => interval.microseconds += micros(60_000_000),
+            "second" => interval.microseconds += micros(1_000_000),
+            _ => return Err(invalid()),
+        }
+    }
+
+    if text.trim().is_empty() {
+        return Err(invalid());
+    }
+    Ok(interval)
+}
+
+/// The date or timestamp argument as a datetime; dates start at midnight.
+/// `None` for NULL, which every date function maps to NULL.
+fn datetime(function: &str, value: &Value) -> Result<Option<NaiveDateTime>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Date(date) => Ok(Some(date.and_time(Default::default()))),
+        Value::Timestamp { value, .. } => Ok(Some(*value)),
+        other => Err(expected(function, "date or timestamp", other)),
+    }
+}
+
+/// `dt` as a value of the same type as `original`.
+fn with_datetime(original: &Value, dt: NaiveDateTime) -> Value {
+    match original {
+        Value::Timestamp { offset_secs, .. } => Value::Timestamp {
+            value: dt,
+            offset_secs: *offset_secs,
+        },
+        _ => Value::Date(dt.date()),
+    }
+}
+
+fn unit_name(function: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.to_ascii_lowercase()),
+        other => Err(expected(function, "unit string", other)),
+    }
+}
+
+fn expected(function: &str, what: &str, got: &Value) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: format!("Expected {}, got {:?}", what, got),
+    }
+}
+
+fn unknown_unit(function: &str, unit: &str) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: format!("Unknown unit '{unit}'"),
+    }
+}
+
+fn out_of_range(function: &str) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: "Result is out of range".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    fn ts(text: &str) -> Value {
+        Value::Timestamp {
+            value: text.parse().unwrap(),
+            offset_secs: Some(0),
+        }
+    }
+
+    fn date(text: &str) -> Value {
+        Value::Date(text.parse().unwrap())
+    }
+
+    #[test]
+    fn test_date_trunc() {
+        let at = ts("2024-08-14T15:42:07.5");
+        let trunc = |unit| call(eval_date_trunc, &[s(unit), at.clone()]).unwrap();
+        assert_eq!(trunc("year"), ts("2024-01-01T00:00:00"));
+        assert_eq!(trunc("QUARTER"), ts("2024-07-01T00:00:00"));
+        assert_eq!(trunc("month"), ts("2024-08-01T00:00:00"));
+        assert_eq!(trunc("week"), ts("2024-08-12T00:00:00"));
+        assert_eq!(trunc("hour"), ts("2024-08-14T15:00:00"));
+        assert_eq!(trunc("second"), ts("2024-08-14T15:42:07"));
+
+        // Dates stay dates
+        assert_eq!(
+            call(eval_date_trunc, &[s("month"), date("2024-08-14")]).unwrap(),
+            date("2024-08-01")
+        );
+        assert!(call(eval_date_trunc, &[s("fortnight"), at]).is_err());
+        assert_eq!(
+            call(eval_date_trunc, &[s("month"), Value::Null]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_date_add() {
+        let add = |at: Value, interval| call(eval_date_add, &[at, s(interval)]).unwrap();
+        assert_eq!(
+            add(ts("2024-01-31T10:00:00"), "1 month 2 hours"),
+            ts("2024-02-29T12:00:00")
+        );
+        assert_eq!(
+            add(ts("2024-03-01T00:00:00"), "-1 day 30 minutes"),
+            ts("2024-02-29T00:30:00")
+        );
+        assert_eq!(add(date("2024-12-25"), "1 week"), date("2025-01-01"));
+
+        let interval = Value::Interval(IntervalValue::new(12, 0, 0));
+        assert_eq!(
+            call(eval_date_add, &[date("2024-02-29"), interval]).unwrap(),
+            date("2025-02-28")
+        );
+        assert!(call(eval_date_add, &[date("2024-02-29"), s("3 fortnights")]).is_err());
+        assert_eq!(
+            call(eval_date_add, &[Value::Null, s("1 day")]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        let at = ts("2024-08-14T15:42:07");
+        assert_eq!(
+            call(eval_format_date, &[at.clone(), s("%Y-%m-%d %H:%M")]).unwrap(),
+            s("2024-08-14 15:42")
+        );
+        assert!(call(eval_format_date, &[at, s("%Q")]).is_err());
+        assert_eq!(
+            call(eval_format_date, &[Value::Null, s("%Y")]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_extract() {
+        let at = ts("2024-08-14T15:42:07");
+        let extract = |part| call(eval_extract, &[s(part), at.clone()]).unwrap();
+        assert_eq!(extract("year"), Value::Int(2024));
+        assert_eq!(extract("quarter"), Value::Int(3));
+        assert_eq!(extract("week"), Value::Int(33));
+        assert_eq!(extract("dow"), Value::Int(3));
+        assert_eq!(extract("doy"), Value::Int(227));
+        assert_eq!(extract("minute"), Value::Int(42));
+        assert_eq!(extract("epoch"), Value::Int(1_723_650_127));
+        assert_eq!(
+            call(eval_extract, &[s("year"), Value::Null]).unwrap(),
+            Value::Null
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+};
+use model::core::value::Value;
+use sha2::{Digest, Sha256};
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Alice", "Amara", "Ana", "Arjun", "Ben", "Carla", "Chen", "Clara", "David",
+    "Elena", "Emil", "Fatima", "Grace", "Hana", "Ivan", "Jonas", "Julia", "Kai", "Laura", "Leo",
+    "Lina", "Luca", "Maya", "Mateo", "Nadia", "Noah", "Olga", "Omar", "Priya", "Rosa", "Sam",
+    "Sofia", "Tom", "Una", "Victor", "Wei", "Yusuf", "Zoe",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Andersen", "Baker", "Costa", "Dubois", "Evans", "Fischer", "Garcia", "Hansen", "Ito",
+    "Jensen", "Kowalski", "Larsen", "Martin", "Nakamura", "Novak", "Okafor", "Olsen", "Patel",
+    "Quinn", "Rossi", "Santos", "Schmidt", "Silva", "Smith", "Tanaka", "Torres", "Usman", "Varga",
+    "Walker", "Weber", "Xu", "Yilmaz", "Young", "Zhang", "Ziegler", "Moreau", "Kim", "Lopez",
+    "Nguyen", "Brown",
+];
+
+/// Domains reserved for documentation (RFC 2606), so generated addresses
+/// can never reach a real mailbox
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+/// Evaluate fake_name(seed), a "First Last" name picked by `seed`
+pub fn eval_fake_name(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let seed = seed("fake_name", args)?;
+    let (first, last) = name(&seed);
+    Ok(Value::String(format!("{first} {last}")))
+}
+
+/// Evaluate fake_email(seed), an address at a reserved example domain
+///
+/// The address is built from the name `fake_name` gives the same seed.
+pub fn eval_fake_email(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let seed = seed("fake_email", args)?;
+    let (first, last) = name(&seed);
+    let number = pick(&seed, 16, 1000);
+    let domain = EMAIL_DOMAINS[pick(&seed, 24, EMAIL_DOMAINS.len())];
+    Ok(Value::String(format!(
+        "{}.{}{number}@{domain}",
+        first.to_ascii_lowercase(),
+        last.to_ascii_lowercase()
+    )))
+}
+
+/// Evaluate fake_phone(seed), a North American number in the unassigned
+/// 555 area code: "+1-555-NXX-XXXX"
+pub fn eval_fake_phone(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let seed = seed("fake_phone", args)?;
+    let exchange = 200 + pick(&seed, 0, 800);
+    let line = pick(&seed, 8, 10_000);
+    Ok(Value::String(format!("+1-555-{exchange:03}-{line:04}")))
+}
+
+/// The SHA-256 digest of the seed argument, from which every choice is
+/// taken, so a value fakes the same way in every run and on every platform.
+fn seed(function: &str, args: &[Value]) -> Result<[u8; 32]> {
+    let [value] = args else {
+        return Err(ExpressionError::InvalidFunctionArgs {
+            function: function.to_string(),
+            message: format!("Expected 1 arguments, got {}", args.len()),
+        });
+    };
+    let bytes = match value {
+        Value::Binary(bytes) => bytes.clone(),
+        other => other.as_string().map(String::into_bytes).ok_or_else(|| {
+            ExpressionError::InvalidFunctionArgs {
+                function: function.to_string(),
+                message: format!("Expected a seed value, got {:?}", other),
+            }
+        })?,
+    };
+    Ok(Sha256::digest(&bytes).into())
+}
+
+fn name(seed: &[u8; 32]) -> (&'static str, &'static str) {
+    (
+        FIRST_NAMES[pick(seed, 0, FIRST_NAMES.len())],
+        LAST_NAMES[pick(seed, 8, LAST_NAMES.len())],
+    )
+}
+
+/// A number below `n` from the eight digest bytes at `offset`.
+fn pick(seed: &[u8; 32], offset: usize, n: usize) -> usize {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&seed[offset..offset + 8]);
+    (u64::from_be_bytes(bytes) % n as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn text(value: Value) -> String {
+        match value {
+            Value::String(s) => s,
+            other => panic!("expected string, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_fake_values_are_stable_per_seed() {
+        for f in [eval_fake_name, eval_fake_email, eval_fake_phone] {
+            assert_eq!(
+                call(f, &[Value::Int(42)]).unwrap(),
+                call(f, &[Value::String("42".to_string())]).unwrap()
+            );
+            assert_ne!(
+                call(f, &[Value::Int(1)]).unwrap(),
+                call(f, &[Value::Int(2)]).unwrap()
+            );
+            assert!(call(f, &[]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fake_email_matches_fake_name() {
+        let seed = [Value::Int(7)];
+        let name = text(call(eval_fake_name, &seed).unwrap()).to_ascii_lowercase();
+        let email = text(call(eval_fake_email, &seed).unwrap());
+
+        let (first, last) = name.split_once(' ').unwrap();
+        assert!(email.starts_with(&format!("{first}.{last}")));
+        let domain = email.split_once('@').unwrap().1;
+        assert!(EMAIL_DOMAINS.contains(&domain));
+    }
+
+    #[test]
+    fn test_fake_phone_format() {
+        let phone = text(call(eval_fake_phone, &[Value::Int(7)]).unwrap());
+        assert_eq!(phone.len(), "+1-555-NXX-XXXX".len());
+        assert!(phone.starts_with("+1-555-"));
+        assert!(phone[7..].chars().all(|c| c.is_ascii_digit() || c == '-'));
+    }
+}
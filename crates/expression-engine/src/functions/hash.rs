@@ -0,0 +1,190 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+    functions::arg_count,
+};
+use hmac::{Hmac, Mac};
+use model::core::value::Value;
+use sha2::{Digest, Sha256};
+
+/// Characters `mask` leaves visible when no count is given
+const DEFAULT_KEEP_LAST: usize = 4;
+
+/// Evaluate sha256(x), the hex SHA-256 digest of `x`
+pub fn eval_sha256(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [value] = args else {
+        return Err(arg_count("sha256", "1", args));
+    };
+    let Some(bytes) = input_bytes("sha256", value)? else {
+        return Ok(Value::Null);
+    };
+    Ok(Value::String(hex::encode(Sha256::digest(&bytes))))
+}
+
+/// Evaluate md5(x), the hex MD5 digest of `x`
+///
+/// MD5 is fine for matching values across tables, but is easily reversed for
+/// short inputs; prefer `hmac` to pseudonymize PII.
+pub fn eval_md5(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [value] = args else {
+        return Err(arg_count("md5", "1", args));
+    };
+    let Some(bytes) = input_bytes("md5", value)? else {
+        return Ok(Value::Null);
+    };
+    Ok(Value::String(format!("{:x}", md5::compute(&bytes))))
+}
+
+/// Evaluate hmac(x, key), the hex HMAC-SHA256 of `x` under `key`
+///
+/// Unlike a bare hash, the result cannot be recomputed from a guessed input
+/// without the key, so the same key pseudonymizes a value the same way in
+/// every table and run.
+pub fn eval_hmac(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [value, key] = args else {
+        return Err(arg_count("hmac", "2", args));
+    };
+    let Some(bytes) = input_bytes("hmac", value)? else {
+        return Ok(Value::Null);
+    };
+    let key = match key {
+        Value::String(key) if !key.is_empty() => key,
+        other => {
+            return Err(ExpressionError::InvalidFunctionArgs {
+                function: "hmac".to_string(),
+                message: format!("Expected non-empty string key, got {:?}", other),
+            })
+        }
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).map_err(|e| {
+        ExpressionError::InvalidFunctionArgs {
+            function: "hmac".to_string(),
+            message: e.to_string(),
+        }
+    })?;
+    mac.update(&bytes);
+    Ok(Value::String(hex::encode(mac.finalize().into_bytes())))
+}
+
+/// Evaluate mask(x, keep_last): `x` with every character but the last
+/// `keep_last` (4 by default) replaced by `*`
+pub fn eval_mask(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let (value, keep_last) = match args {
+        [value] => (value, DEFAULT_KEEP_LAST),
+        [value, Value::Int(n)] if *n >= 0 => (value, *n as usize),
+        [value, Value::UInt(n)] => (value, *n as usize),
+        [_, other] => {
+            return Err(ExpressionError::InvalidFunctionArgs {
+                function: "mask".to_string(),
+                message: format!("Expected non-negative count, got {:?}", other),
+            })
+        }
+        _ => return Err(arg_count("mask", "1 or 2", args)),
+    };
+    if let Value::Null = value {
+        return Ok(Value::Null);
+    }
+    let text = value
+        .as_string()
+        .ok_or_else(|| expected_scalar("mask", value))?;
+
+    let len = text.chars().count();
+    let masked = text
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i + keep_last < len { '*' } else { c })
+        .collect();
+    Ok(Value::String(masked))
+}
+
+/// The bytes hashed for `value`: binary as is, anything else as its text,
+/// so `sha256(42)` and `sha256("42")` agree. `None` for NULL, which hashes
+/// to NULL.
+fn input_bytes(function: &str, value: &Value) -> Result<Option<Vec<u8>>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Binary(bytes) => Ok(Some(bytes.clone())),
+        other => other
+            .as_string()
+            .map(|text| Some(text.into_bytes()))
+            .ok_or_else(|| expected_scalar(function, other)),
+    }
+}
+
+fn expected_scalar(function: &str, got: &Value) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: format!("Expected a value to hash, got {:?}", got),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn test_digests() {
+        assert_eq!(
+            call(eval_sha256, &[s("abc")]).unwrap(),
+            s("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            call(eval_md5, &[s("abc")]).unwrap(),
+            s("900150983cd24fb0d6963f7d28e17f72")
+        );
+        assert_eq!(
+            call(eval_sha256, &[Value::Int(42)]).unwrap(),
+            call(eval_sha256, &[s("42")]).unwrap()
+        );
+        assert_eq!(call(eval_sha256, &[Value::Null]).unwrap(), Value::Null);
+        assert_eq!(call(eval_md5, &[Value::Null]).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            call(eval_hmac, &[s("what do ya want for nothing?"), s("Jefe")]).unwrap(),
+            s("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        assert!(call(eval_hmac, &[s("x"), s("")]).is_err());
+        assert!(call(eval_hmac, &[s("x")]).is_err());
+        assert_eq!(
+            call(eval_hmac, &[Value::Null, s("Jefe")]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(
+            call(eval_mask, &[s("4111111111111111")]).unwrap(),
+            s("************1111")
+        );
+        assert_eq!(
+            call(eval_mask, &[s("ada@example.com"), Value::Int(0)]).unwrap(),
+            s("***************")
+        );
+        assert_eq!(
+            call(eval_mask, &[s("abc"), Value::Int(4)]).unwrap(),
+            s("abc")
+        );
+        assert!(call(eval_mask, &[s("abc"), Value::Int(-1)]).is_err());
+        assert_eq!(call(eval_mask, &[Value::Null]).unwrap(), Value::Null);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+};
+use model::core::value::Value;
+use serde_json::Value as JsonValue;
+
+/// Extract a field from a JSON value: `json_get(column, "$.path.to.field")`
+///
+/// The column may hold JSON or a string of JSON. Paths start at `$` and step
+/// into objects with `.key` or `["key"]` and into arrays with `[index]`.
+/// Scalars come back as strings, numbers and booleans; objects and arrays
+/// as JSON. A missing field is null.
+pub fn eval_json_get(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let (document, path) = match args {
+        [document, Value::String(path)] => (document, path),
+        [_, other] => {
+            return Err(invalid_args(format!(
+                "Expected string path, got {:?}",
+                other
+            )))
+        }
+        _ => {
+            return Err(invalid_args(format!(
+                "Expected 2 arguments, got {}",
+                args.len()
+            )))
+        }
+    };
+
+    let parsed;
+    let json = match document {
+        Value::Json(json) => json,
+        Value::String(s) => {
+            parsed = serde_json::from_str::<JsonValue>(s)
+                .map_err(|e| invalid_args(format!("Invalid JSON: {e}")))?;
+            &parsed
+        }
+        Value::Null => return Ok(Value::Null),
+        other => return Err(invalid_args(format!("Expected JSON, got {:?}", other))),
+    };
+
+    let found = parse_path(path)?
+        .iter()
+        .try_fold(json, |json, step| match step {
+            PathStep::Key(key) => json.get(key),
+            PathStep::Index(index) => json.get(index),
+        });
+
+    Ok(match found {
+        None | Some(JsonValue::Null) => Value::Null,
+        Some(JsonValue::String(s)) => Value::String(s.clone()),
+        Some(JsonValue::Bool(b)) => Value::Boolean(*b),
+        Some(JsonValue::Number(n)) => {
+            if let Some(i) = n.as_i64() {
+                Value::Int(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::UInt(u)
+            } else {
+                Value::Float(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        Some(other) => Value::Json(other.clone()),
+    })
+}
+
+enum PathStep {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_path(path: &str) -> Result<Vec<PathStep>> {
+    let invalid = || invalid_args(format!("Invalid JSON path '{path}'"));
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(invalid)?;
+
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            steps.push(PathStep::Key(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let inner = after[..end].trim();
+            let quoted = ['"', '\'']
+                .into_iter()
+                .find_map(|q| inner.strip_prefix(q).and_then(|key| key.strip_suffix(q)));
+            steps.push(match quoted {
+                Some(key) => PathStep::Key(key.to_string()),
+                None => PathStep::Index(inner.parse().map_err(|_| invalid())?),
+            });
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(steps)
+}
+
+fn invalid_args(message: String) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: "json_get".to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn json_get(document: Value, path: &str) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        eval_json_get(&[document, Value::String(path.to_string())], &ctx)
+    }
+
+    #[test]
+    fn test_json_get() {
+        let document = Value::Json(serde_json::json!({
+            "customer": { "name": "Ada", "vip": true },
+            "items": [{ "sku": "A-1", "qty": 2, "price": 9.5 }],
+            "first.last": "dotted",
+        }));
+
+        let get = |path| json_get(document.clone(), path).unwrap();
+        assert_eq!(get("$.customer.name"), Value::String("Ada".to_string()));
+        assert_eq!(get("$.customer.vip"), Value::Boolean(true));
+        assert_eq!(get("$.items[0].qty"), Value::Int(2));
+        assert_eq!(get("$.items[0].price"), Value::Float(9.5));
+        assert_eq!(
+            get("$[\"first.last\"]"),
+            Value::String("dotted".to_string())
+        );
+        assert_eq!(get("$.items[1].sku"), Value::Null);
+        assert_eq!(get("$.missing"), Value::Null);
+        assert_eq!(
+            get("$.customer"),
+            Value::Json(serde_json::json!({ "name": "Ada", "vip": true }))
+        );
+
+        // JSON stored as text
+        let text = Value::String(r#"{"a": {"b": "c"}}"#.to_string());
+        assert_eq!(
+            json_get(text, "$.a.b").unwrap(),
+            Value::String("c".to_string())
+        );
+
+        assert!(json_get(document.clone(), "customer.name").is_err());
+        assert!(json_get(document, "$.items[x]").is_err());
+    }
+}
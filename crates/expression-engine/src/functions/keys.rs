@@ -0,0 +1,134 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+    functions::arg_count,
+};
+use model::core::value::Value;
+use uuid::Uuid;
+
+/// Evaluate uuid_v4(), a random UUID
+pub fn eval_uuid_v4(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    if !args.is_empty() {
+        return Err(arg_count("uuid_v4", "0", args));
+    }
+    Ok(Value::Uuid(Uuid::new_v4()))
+}
+
+/// Evaluate uuid_v5(namespace, value), the name-based UUID of `value`
+///
+/// The namespace is "dns", "url", "oid", "x500" or a UUID of your own. The
+/// same value always gives the same UUID, so a key computed from a source
+/// key here and from a foreign key elsewhere match.
+pub fn eval_uuid_v5(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [namespace, value] = args else {
+        return Err(arg_count("uuid_v5", "2", args));
+    };
+    let namespace = match namespace {
+        Value::Uuid(uuid) => *uuid,
+        Value::String(name) => match name.to_ascii_lowercase().as_str() {
+            "dns" => Uuid::NAMESPACE_DNS,
+            "url" => Uuid::NAMESPACE_URL,
+            "oid" => Uuid::NAMESPACE_OID,
+            "x500" => Uuid::NAMESPACE_X500,
+            _ => Uuid::parse_str(name).map_err(|_| invalid_namespace(namespace))?,
+        },
+        other => return Err(invalid_namespace(other)),
+    };
+    let name = match value {
+        Value::Null => return Ok(Value::Null),
+        Value::Binary(bytes) => bytes.clone(),
+        other => other.as_string().map(String::into_bytes).ok_or_else(|| {
+            ExpressionError::InvalidFunctionArgs {
+                function: "uuid_v5".to_string(),
+                message: format!("Expected a value to name, got {:?}", other),
+            }
+        })?,
+    };
+    Ok(Value::Uuid(Uuid::new_v5(&namespace, &name)))
+}
+
+/// Evaluate row_number(), the number of the row in the pipeline's run
+///
+/// Computed columns bind it to a counter shared by the pipeline's readers
+/// when the pipeline runs; outside a run, such as when sampling a plan, it
+/// evaluates to null.
+pub fn eval_row_number(_args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(Value::Null)
+}
+
+/// Evaluate lookup_key(table, key), the key `key` was loaded under in
+/// `table` by a pipeline with `emit_keymap = true`
+///
+/// Computed columns bind it to the table's key crosswalk when the pipeline
+/// runs; outside a run it evaluates to null.
+pub fn eval_lookup_key(_args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(Value::Null)
+}
+
+fn invalid_namespace(got: &Value) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: "uuid_v5".to_string(),
+        message: format!(
+            "Expected namespace \"dns\", \"url\", \"oid\", \"x500\" or a UUID, got {:?}",
+            got
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn test_uuid_v4() {
+        let a = call(eval_uuid_v4, &[]).unwrap();
+        assert!(matches!(a, Value::Uuid(uuid) if uuid.get_version_num() == 4));
+        assert_ne!(a, call(eval_uuid_v4, &[]).unwrap());
+        assert!(call(eval_uuid_v4, &[s("x")]).is_err());
+    }
+
+    #[test]
+    fn test_uuid_v5() {
+        // RFC 9562 test vector
+        let expected =
+            Value::Uuid(Uuid::parse_str("2ed6657d-e927-568b-95e1-2665a8aea6a2").unwrap());
+        assert_eq!(
+            call(eval_uuid_v5, &[s("dns"), s("www.example.com")]).unwrap(),
+            expected
+        );
+        assert_eq!(
+            call(
+                eval_uuid_v5,
+                &[
+                    s("6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+                    s("www.example.com")
+                ]
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            call(eval_uuid_v5, &[s("url"), Value::Int(42)]).unwrap(),
+            call(eval_uuid_v5, &[s("url"), s("42")]).unwrap()
+        );
+        assert!(call(eval_uuid_v5, &[s("orders"), s("42")]).is_err());
+        assert_eq!(
+            call(eval_uuid_v5, &[s("dns"), Value::Null]).unwrap(),
+            Value::Null
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+};
+use model::core::value::Value;
+
+const FN_MAP_VALUES: &str = "map_values";
+
+/// What map_values returns for a value the table has no entry for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unmatched {
+    /// The value itself, unchanged
+    Pass,
+    /// Null
+    Null,
+    /// An error, failing the row
+    Fail,
+}
+
+impl Unmatched {
+    fn parse(value: &Value) -> Result<Self> {
+        match value.as_string().as_deref() {
+            Some("pass") => Ok(Unmatched::Pass),
+            Some("null") => Ok(Unmatched::Null),
+            Some("fail") => Ok(Unmatched::Fail),
+            _ => Err(invalid_args(format!(
+                "Unmatched policy must be 'pass', 'null' or 'fail', got {value:?}"
+            ))),
+        }
+    }
+}
+
+/// Evaluate map_values(value, table, [unmatched]): the entry of the map
+/// literal `table` keyed by `value`, compared as text
+///
+/// `unmatched` decides what a value without an entry becomes: `"pass"` (the
+/// default) keeps it, `"null"` nulls it and `"fail"` fails the row. A null
+/// value stays null.
+pub fn eval_map_values(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let (value, table, unmatched) = match args {
+        [value, table] => (value, table, Unmatched::Pass),
+        [value, table, unmatched] => (value, table, Unmatched::parse(unmatched)?),
+        _ => {
+            return Err(invalid_args(format!(
+                "Expected 2 or 3 arguments, got {}",
+                args.len()
+            )));
+        }
+    };
+
+    let Value::Json(serde_json::Value::Object(table)) = table else {
+        return Err(invalid_args(format!(
+            "Second argument must be a map, got {table:?}"
+        )));
+    };
+    if matches!(value, Value::Null) {
+        return Ok(Value::Null);
+    }
+
+    let key = value.as_string();
+    match key.as_deref().and_then(|key| table.get(key)) {
+        Some(mapped) => Ok(Value::from_json(mapped)),
+        None => match unmatched {
+            Unmatched::Pass => Ok(value.clone()),
+            Unmatched::Null => Ok(Value::Null),
+            Unmatched::Fail => Err(invalid_args(format!(
+                "No entry for {}",
+                key.map_or_else(|| format!("{value:?}"), |key| format!("'{key}'"))
+            ))),
+        },
+    }
+}
+
+fn invalid_args(message: String) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: FN_MAP_VALUES.to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn call(args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        eval_map_values(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    fn statuses() -> Value {
+        Value::Json(serde_json::json!({ "A": "active", "I": "inactive", "1": 10 }))
+    }
+
+    #[test]
+    fn test_map_values_matches() {
+        assert_eq!(call(&[s("A"), statuses()]).unwrap(), s("active"));
+        assert_eq!(call(&[s("I"), statuses()]).unwrap(), s("inactive"));
+        // Keys are compared as text, and values keep their JSON type
+        assert_eq!(call(&[Value::Int(1), statuses()]).unwrap(), Value::Int(10));
+        assert_eq!(call(&[Value::Null, statuses()]).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_map_values_unmatched_policy() {
+        assert_eq!(call(&[s("X"), statuses()]).unwrap(), s("X"));
+        assert_eq!(call(&[s("X"), statuses(), s("pass")]).unwrap(), s("X"));
+        assert_eq!(call(&[s("X"), statuses(), s("null")]).unwrap(), Value::Null);
+
+        let err = call(&[s("X"), statuses(), s("fail")]).unwrap_err();
+        assert!(err.to_string().contains("No entry for 'X'"));
+        assert!(call(&[s("X"), statuses(), s("skip")]).is_err());
+    }
+
+    #[test]
+    fn test_map_values_requires_map() {
+        assert!(call(&[s("A"), s("A")]).is_err());
+        assert!(call(&[s("A")]).is_err());
+    }
+}
@@ -1,11 +1,23 @@
+pub mod conditional;
+pub mod datetime;
 pub mod env;
+pub mod fake;
+pub mod hash;
+pub mod json;
+pub mod keys;
+pub mod lookup;
+pub mod regex;
+pub mod run;
 pub mod string;
 
 use crate::{
     context::EvalContext,
     error::{ExpressionError, Result},
 };
-use model::core::value::Value;
+use model::{
+    core::value::Value,
+    execution::expr::{FN_LOOKUP_KEY, FN_ROW_NUMBER, FN_RUN_ID, FN_RUN_STARTED_AT},
+};
 use std::collections::HashMap;
 
 /// Type alias for function implementations
@@ -28,6 +40,32 @@ impl FunctionRegistry {
         registry.register("lower", string::eval_lower);
         registry.register("upper", string::eval_upper);
         registry.register("concat", string::eval_concat);
+        registry.register("json_get", json::eval_json_get);
+        registry.register("regex_match", regex::eval_regex_match);
+        registry.register("regex_extract", regex::eval_regex_extract);
+        registry.register("regex_replace", regex::eval_regex_replace);
+        registry.register("sha256", hash::eval_sha256);
+        registry.register("md5", hash::eval_md5);
+        registry.register("hmac", hash::eval_hmac);
+        registry.register("mask", hash::eval_mask);
+        registry.register("fake_name", fake::eval_fake_name);
+        registry.register("fake_email", fake::eval_fake_email);
+        registry.register("fake_phone", fake::eval_fake_phone);
+        registry.register("uuid_v4", keys::eval_uuid_v4);
+        registry.register("uuid_v5", keys::eval_uuid_v5);
+        registry.register(FN_ROW_NUMBER, keys::eval_row_number);
+        registry.register(FN_LOOKUP_KEY, keys::eval_lookup_key);
+        registry.register("map_values", lookup::eval_map_values);
+        registry.register("if", conditional::eval_if);
+        registry.register("coalesce", conditional::eval_coalesce);
+        registry.register("nullif", conditional::eval_nullif);
+        registry.register("now", datetime::eval_now);
+        registry.register("date_trunc", datetime::eval_date_trunc);
+        registry.register("date_add", datetime::eval_date_add);
+        registry.register("format_date", datetime::eval_format_date);
+        registry.register("extract", datetime::eval_extract);
+        registry.register(FN_RUN_ID, run::eval_run_id);
+        registry.register(FN_RUN_STARTED_AT, run::eval_run_started_at);
 
         registry
     }
@@ -54,6 +92,15 @@ impl FunctionRegistry {
     }
 }
 
+/// Error for a call with the wrong number of arguments; `expected` reads
+/// as a count, such as "2" or "1 or 2".
+pub(crate) fn arg_count(function: &str, expected: &str, args: &[Value]) -> ExpressionError {
+    ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: format!("Expected {} arguments, got {}", expected, args.len()),
+    }
+}
+
 impl Default for FunctionRegistry {
     fn default() -> Self {
         Self::new()
@@ -76,6 +123,32 @@ mod tests {
         assert!(registry.has_function("lower"));
         assert!(registry.has_function("upper"));
         assert!(registry.has_function("concat"));
+        assert!(registry.has_function("json_get"));
+        assert!(registry.has_function("regex_match"));
+        assert!(registry.has_function("regex_extract"));
+        assert!(registry.has_function("regex_replace"));
+        assert!(registry.has_function("sha256"));
+        assert!(registry.has_function("md5"));
+        assert!(registry.has_function("hmac"));
+        assert!(registry.has_function("mask"));
+        assert!(registry.has_function("fake_name"));
+        assert!(registry.has_function("fake_email"));
+        assert!(registry.has_function("fake_phone"));
+        assert!(registry.has_function("uuid_v4"));
+        assert!(registry.has_function("uuid_v5"));
+        assert!(registry.has_function("row_number"));
+        assert!(registry.has_function("lookup_key"));
+        assert!(registry.has_function("map_values"));
+        assert!(registry.has_function("if"));
+        assert!(registry.has_function("coalesce"));
+        assert!(registry.has_function("nullif"));
+        assert!(registry.has_function("now"));
+        assert!(registry.has_function("date_trunc"));
+        assert!(registry.has_function("date_add"));
+        assert!(registry.has_function("format_date"));
+        assert!(registry.has_function("extract"));
+        assert!(registry.has_function("run_id"));
+        assert!(registry.has_function("run_started_at"));
     }
 
     #[test]
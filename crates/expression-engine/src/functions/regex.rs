@@ -0,0 +1,198 @@
+use crate::{
+    context::EvalContext,
+    error::{ExpressionError, Result},
+    functions::arg_count,
+};
+use model::core::value::Value;
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+/// Patterns compiled so far; expressions are evaluated row by row, so each
+/// pattern is compiled once rather than once per row
+static PATTERNS: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+/// Compiled patterns kept before the cache is emptied, in case patterns are
+/// built from row data
+const MAX_CACHED_PATTERNS: usize = 256;
+
+/// Evaluate regex_match(s, pattern): whether the pattern matches anywhere in
+/// `s`. Anchor it with `^...$` to match the whole string.
+pub fn eval_regex_match(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [text, pattern] = args else {
+        return Err(arg_count("regex_match", "2", args));
+    };
+    if let Value::Null = text {
+        return Ok(Value::Null);
+    }
+    let text = string_arg("regex_match", text)?;
+    let regex = compile("regex_match", pattern)?;
+    Ok(Value::Boolean(regex.is_match(text)))
+}
+
+/// Evaluate regex_extract(s, pattern, group): the text of the first match's
+/// capture group, by number or name, or null when nothing matches. Group 0,
+/// the default, is the whole match.
+pub fn eval_regex_extract(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let (text, pattern, group) = match args {
+        [text, pattern] => (text, pattern, &Value::Int(0)),
+        [text, pattern, group] => (text, pattern, group),
+        _ => return Err(arg_count("regex_extract", "2 or 3", args)),
+    };
+    if let Value::Null = text {
+        return Ok(Value::Null);
+    }
+    let text = string_arg("regex_extract", text)?;
+    let regex = compile("regex_extract", pattern)?;
+
+    let Some(captures) = regex.captures(text) else {
+        return Ok(Value::Null);
+    };
+    let found = match group {
+        Value::Int(n) if *n >= 0 => captures.get(*n as usize),
+        Value::UInt(n) => captures.get(*n as usize),
+        Value::String(name) => captures.name(name),
+        other => {
+            return Err(ExpressionError::InvalidFunctionArgs {
+                function: "regex_extract".to_string(),
+                message: format!("Expected group number or name, got {:?}", other),
+            })
+        }
+    };
+    Ok(found
+        .map(|m| Value::String(m.as_str().to_string()))
+        .unwrap_or(Value::Null))
+}
+
+/// Evaluate regex_replace(s, pattern, repl): `s` with every match replaced
+/// by `repl`, which may refer to capture groups as `$1` or `${name}`.
+pub fn eval_regex_replace(args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    let [text, pattern, replacement] = args else {
+        return Err(arg_count("regex_replace", "3", args));
+    };
+    if let Value::Null = text {
+        return Ok(Value::Null);
+    }
+    let text = string_arg("regex_replace", text)?;
+    let regex = compile("regex_replace", pattern)?;
+    let replacement = string_arg("regex_replace", replacement)?;
+    Ok(Value::String(
+        regex.replace_all(text, replacement).into_owned(),
+    ))
+}
+
+/// The compiled `pattern`, from the cache when it was compiled before.
+fn compile(function: &str, pattern: &Value) -> Result<Regex> {
+    let pattern = string_arg(function, pattern)?;
+    let cache = PATTERNS.get_or_init(Default::default);
+
+    if let Some(regex) = cache.lock().unwrap().get(pattern) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Regex::new(pattern).map_err(|e| ExpressionError::InvalidFunctionArgs {
+        function: function.to_string(),
+        message: format!("Invalid pattern '{pattern}': {e}"),
+    })?;
+    let mut cache = cache.lock().unwrap();
+    if cache.len() >= MAX_CACHED_PATTERNS {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+fn string_arg<'a>(function: &str, value: &'a Value) -> Result<&'a str> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => Err(ExpressionError::InvalidFunctionArgs {
+            function: function.to_string(),
+            message: format!("Expected string, got {:?}", other),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(f: fn(&[Value], &EvalContext) -> Result<Value>, args: &[Value]) -> Result<Value> {
+        let definitions = HashMap::new();
+        let ctx = EvalContext::BuildTime {
+            definitions: &definitions,
+            env_getter: &|_| None,
+        };
+        f(args, &ctx)
+    }
+
+    fn s(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+
+    #[test]
+    fn test_regex_match() {
+        let email = s(r"^[^@\s]+@[^@\s]+\.[a-z]+$");
+        assert_eq!(
+            call(eval_regex_match, &[s("ada@example.com"), email.clone()]).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            call(eval_regex_match, &[s("not an email"), email]).unwrap(),
+            Value::Boolean(false)
+        );
+        assert!(call(eval_regex_match, &[s("x"), s("(")]).is_err());
+        assert_eq!(
+            call(eval_regex_match, &[Value::Null, s("x")]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_regex_extract() {
+        let pattern = s(r"(?<area>\d{3})-(\d{4})");
+        let extract = |group| {
+            call(
+                eval_regex_extract,
+                &[s("call 555-0199 now"), pattern.clone(), group],
+            )
+        };
+        assert_eq!(extract(Value::Int(0)).unwrap(), s("555-0199"));
+        assert_eq!(extract(Value::Int(2)).unwrap(), s("0199"));
+        assert_eq!(extract(s("area")).unwrap(), s("555"));
+        assert_eq!(extract(Value::Int(5)).unwrap(), Value::Null);
+        assert_eq!(
+            call(eval_regex_extract, &[s("no digits"), pattern.clone()]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(
+            call(eval_regex_extract, &[Value::Null, pattern]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_regex_replace() {
+        assert_eq!(
+            call(
+                eval_regex_replace,
+                &[s("+1 (555) 010-0199"), s(r"\D"), s("")]
+            )
+            .unwrap(),
+            s("15550100199")
+        );
+        assert_eq!(
+            call(
+                eval_regex_replace,
+                &[s("Lovelace, Ada"), s(r"(\w+), (\w+)"), s("$2 $1")]
+            )
+            .unwrap(),
+            s("Ada Lovelace")
+        );
+        assert_eq!(
+            call(eval_regex_replace, &[Value::Null, s(r"\D"), s("")]).unwrap(),
+            Value::Null
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{context::EvalContext, error::Result};
+use model::core::value::Value;
+
+/// Evaluate run_id(), the id of the current run
+///
+/// `run.id` references compile to this call and are replaced with the id
+/// when the pipeline runs; outside a run, such as when sampling a plan, it
+/// evaluates to null.
+pub fn eval_run_id(_args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(Value::Null)
+}
+
+/// Evaluate run_started_at(), the time the current run started
+///
+/// Bound like run_id(); null outside a run.
+pub fn eval_run_started_at(_args: &[Value], _ctx: &EvalContext) -> Result<Value> {
+    Ok(Value::Null)
+}
@@ -3,7 +3,7 @@ use model::{
         types::{FloatSize, IntSize, Type},
         value::Value,
     },
-    execution::expr::CompiledExpression,
+    execution::expr::{BinaryOp, CompiledExpression, FN_ROW_NUMBER, FN_RUN_ID, FN_RUN_STARTED_AT},
 };
 use tracing::warn;
 
@@ -56,19 +56,57 @@ where
             _ => Type::Text { charset: None }, // Fallback for other types
         }),
 
-        CompiledExpression::Binary { left, right, .. } => {
-            let lt = infer_expression_type(left, column_lookup)?;
-            let rt = infer_expression_type(right, column_lookup)?;
-            Some(get_numeric_type(&lt, &rt))
+        CompiledExpression::Binary { left, op, right } => {
+            let lt = operand_type(left, column_lookup)?;
+            let rt = operand_type(right, column_lookup)?;
+            Some(get_numeric_type(op, &lt, &rt))
         }
 
-        CompiledExpression::FunctionCall { name, .. } => match name.to_ascii_lowercase().as_str() {
-            "lower" | "upper" | "concat" | "env" => Some(Type::Varchar {
-                length: None,
-                charset: None,
-            }),
-            _ => None,
-        },
+        CompiledExpression::FunctionCall { name, args } => {
+            match name.to_ascii_lowercase().as_str() {
+                "lower" | "upper" | "concat" | "env" | "json_get" | "format_date"
+                | "regex_extract" | "regex_replace" | "sha256" | "md5" | "hmac" | "mask"
+                | "fake_name" | "fake_email" | "fake_phone" | FN_RUN_ID => Some(Type::Varchar {
+                    length: None,
+                    charset: None,
+                }),
+                "now" | FN_RUN_STARTED_AT => Some(Type::Timestamp {
+                    precision: None,
+                    with_tz: true,
+                }),
+                // Truncating or shifting keeps the type of the date or timestamp
+                "date_trunc" => infer_expression_type(args.get(1)?, column_lookup),
+                "date_add" => infer_expression_type(args.first()?, column_lookup),
+                // The type of the first value the conditional can return
+                "if" => args
+                    .iter()
+                    .skip(1)
+                    .find_map(|arg| infer_expression_type(arg, column_lookup)),
+                "coalesce" | "nullif" => args
+                    .iter()
+                    .find_map(|arg| infer_expression_type(arg, column_lookup)),
+                // The type of the first value in the lookup table
+                "map_values" => match args.get(1)? {
+                    CompiledExpression::Literal(Value::Json(serde_json::Value::Object(table))) => {
+                        table.values().find(|v| !v.is_null()).and_then(|v| {
+                            infer_expression_type(
+                                &CompiledExpression::Literal(Value::from_json(v)),
+                                column_lookup,
+                            )
+                        })
+                    }
+                    _ => None,
+                },
+                "regex_match" => Some(Type::Boolean),
+                "uuid_v4" | "uuid_v5" => Some(Type::Uuid),
+                "extract" | FN_ROW_NUMBER => Some(Type::Int {
+                    bits: IntSize::I64,
+                    unsigned: false,
+                    auto_increment: false,
+                }),
+                _ => None,
+            }
+        }
 
         // DotPath with 2+ segments = cross-entity reference (table.column)
         // Caller should handle fetching metadata asynchronously
@@ -103,7 +141,23 @@ where
     }
 }
 
-fn get_numeric_type(left: &Type, right: &Type) -> Type {
+/// Type of a binary operand. A JSON field is a string on its own, but one
+/// used in arithmetic is read as a number.
+fn operand_type<F>(expr: &CompiledExpression, column_lookup: &F) -> Option<Type>
+where
+    F: Fn(&str) -> Option<Type>,
+{
+    match expr {
+        CompiledExpression::FunctionCall { name, .. } if name.eq_ignore_ascii_case("json_get") => {
+            Some(Type::Float {
+                bits: FloatSize::F64,
+            })
+        }
+        _ => infer_expression_type(expr, column_lookup),
+    }
+}
+
+fn get_numeric_type(op: &BinaryOp, left: &Type, right: &Type) -> Type {
     match (left, right) {
         (Type::Int { .. }, Type::Int { .. }) => {
             // For int + int, use 64-bit signed as safe default
@@ -125,10 +179,9 @@ fn get_numeric_type(left: &Type, right: &Type) -> Type {
                 bits: FloatSize::F64,
             }
         }
-        (Type::Decimal { .. }, _) | (_, Type::Decimal { .. }) => Type::Decimal {
-            precision: None,
-            scale: None,
-        },
+        (Type::Decimal { .. }, _) | (_, Type::Decimal { .. }) => {
+            decimal_result_type(op, left, right)
+        }
         _ => {
             warn!(left = ?left, right = ?right, "incompatible types for arithmetic operation");
             Type::Text { charset: None } // Fallback to Text for unsupported types
@@ -136,6 +189,55 @@ fn get_numeric_type(left: &Type, right: &Type) -> Type {
     }
 }
 
+/// Precision and scale of a decimal arithmetic result, following the usual SQL
+/// rules so a derived monetary column keeps its fractional digits. Falls back
+/// to an unconstrained decimal when either side has no fixed shape.
+fn decimal_result_type(op: &BinaryOp, left: &Type, right: &Type) -> Type {
+    let shape = decimal_shape(left)
+        .zip(decimal_shape(right))
+        .and_then(|((p1, s1), (p2, s2))| match op {
+            BinaryOp::Add | BinaryOp::Subtract => {
+                let scale = s1.max(s2);
+                Some(((p1 - s1).max(p2 - s2) + scale + 1, scale))
+            }
+            BinaryOp::Multiply => Some((p1 + p2, s1 + s2)),
+            _ => None,
+        });
+
+    match shape.and_then(|(p, s)| Some((u8::try_from(p).ok()?, u8::try_from(s).ok()?))) {
+        Some((precision, scale)) => Type::Decimal {
+            precision: Some(precision),
+            scale: Some(scale),
+        },
+        None => Type::Decimal {
+            precision: None,
+            scale: None,
+        },
+    }
+}
+
+/// `(precision, scale)` of an exact numeric operand.
+fn decimal_shape(ty: &Type) -> Option<(u32, u32)> {
+    match ty {
+        Type::Decimal {
+            precision: Some(p),
+            scale,
+        } => Some((*p as u32, scale.unwrap_or(0) as u32)),
+        Type::Int { bits, unsigned, .. } => {
+            let digits = match bits {
+                IntSize::I8 => 3,
+                IntSize::I16 => 5,
+                IntSize::I24 => 8,
+                IntSize::I32 => 10,
+                IntSize::I64 if *unsigned => 20,
+                IntSize::I64 => 19,
+            };
+            Some((digits, 0))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::f64;
@@ -230,6 +332,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_infer_json_get() {
+        let no_lookup = |_: &str| None;
+        let json_get = CompiledExpression::FunctionCall {
+            name: "json_get".to_string(),
+            args: vec![],
+        };
+        assert_eq!(
+            infer_expression_type(&json_get, &no_lookup),
+            Some(Type::Varchar {
+                length: None,
+                charset: None
+            })
+        );
+
+        // Used in arithmetic, the field is a number
+        let expr = CompiledExpression::Binary {
+            left: Box::new(json_get),
+            op: BinaryOp::Multiply,
+            right: Box::new(CompiledExpression::Literal(Value::Int(100))),
+        };
+        assert_eq!(
+            infer_expression_type(&expr, &no_lookup),
+            Some(Type::Float {
+                bits: FloatSize::F64
+            })
+        );
+    }
+
+    #[test]
+    fn test_infer_datetime_functions() {
+        let lookup = |name: &str| (name == "created_on").then_some(Type::Date);
+        let call = |name: &str, args: Vec<CompiledExpression>| CompiledExpression::FunctionCall {
+            name: name.to_string(),
+            args,
+        };
+        let unit = || CompiledExpression::Literal(Value::String("month".to_string()));
+        let column = || CompiledExpression::Identifier("created_on".to_string());
+
+        assert_eq!(
+            infer_expression_type(&call("date_trunc", vec![unit(), column()]), &lookup),
+            Some(Type::Date)
+        );
+        assert_eq!(
+            infer_expression_type(&call("date_add", vec![column(), unit()]), &lookup),
+            Some(Type::Date)
+        );
+        assert_eq!(
+            infer_expression_type(&call("extract", vec![unit(), column()]), &lookup),
+            Some(Type::Int {
+                bits: IntSize::I64,
+                unsigned: false,
+                auto_increment: false,
+            })
+        );
+        assert_eq!(
+            infer_expression_type(&call("now", vec![]), &lookup),
+            Some(Type::Timestamp {
+                precision: None,
+                with_tz: true,
+            })
+        );
+    }
+
     #[test]
     fn test_infer_is_null() {
         let no_lookup = |_: &str| None;
@@ -239,4 +405,51 @@ mod tests {
             Some(Type::Boolean)
         );
     }
+
+    #[test]
+    fn test_infer_decimal_arithmetic() {
+        let column_lookup = |name: &str| match name {
+            "price" => Some(Type::Decimal {
+                precision: Some(10),
+                scale: Some(2),
+            }),
+            "rate" => Some(Type::Decimal {
+                precision: Some(5),
+                scale: Some(4),
+            }),
+            "qty" => Some(Type::Int {
+                bits: IntSize::I32,
+                unsigned: false,
+                auto_increment: false,
+            }),
+            _ => None,
+        };
+        let binary = |l: &str, op: BinaryOp, r: &str| CompiledExpression::Binary {
+            left: Box::new(CompiledExpression::Identifier(l.to_string())),
+            op,
+            right: Box::new(CompiledExpression::Identifier(r.to_string())),
+        };
+
+        assert_eq!(
+            infer_expression_type(&binary("price", BinaryOp::Add, "rate"), &column_lookup),
+            Some(Type::Decimal {
+                precision: Some(13),
+                scale: Some(4),
+            })
+        );
+        assert_eq!(
+            infer_expression_type(&binary("price", BinaryOp::Multiply, "qty"), &column_lookup),
+            Some(Type::Decimal {
+                precision: Some(20),
+                scale: Some(2),
+            })
+        );
+        assert_eq!(
+            infer_expression_type(&binary("price", BinaryOp::Divide, "qty"), &column_lookup),
+            Some(Type::Decimal {
+                precision: None,
+                scale: None,
+            })
+        );
+    }
 }
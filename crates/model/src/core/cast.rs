@@ -0,0 +1,859 @@
+//! Conversion of values to column types.
+//!
+//! [`Value::convert_to`] is the one conversion matrix the writers use to fit
+//! a value to its destination column, and the plan's sample preview uses to
+//! report which columns convert with loss. A conversion either succeeds,
+//! saying whether the converted value still holds everything the original
+//! did, or fails when the value has no form in the target type; writers then
+//! pass the original value on and leave the verdict to the database.
+//!
+//! | Target | Accepts |
+//! |--------|---------|
+//! | integers, `YEAR` | integers, booleans, years, floats and decimals (rounded), numeric text; out of range fails |
+//! | `DECIMAL` | numbers and numeric text, rounded to the scale; too many integer digits fails |
+//! | `FLOAT`, `DOUBLE` | numbers and numeric text, rounded to the float's precision |
+//! | `BOOLEAN` | booleans, integers (non-zero is true), `true`/`false`/`t`/`f`/`yes`/`no`/`1`/`0` |
+//! | text | any value as its text; invalid UTF-8 is replaced; longer than the column fails |
+//! | binary | bytes, text, UUIDs and geometries; longer than the column fails |
+//! | `DATE`, `TIME`, `TIMESTAMP` | temporal values and their ISO 8601 text; offsets are dropped for columns without a time zone |
+//! | `UUID` | UUIDs, UUID text and 16 bytes |
+//! | `JSON` | JSON, JSON text and any other value as its plain JSON form |
+//! | `ENUM`, `SET` | labels that are in the type's list |
+//! | arrays | arrays, sets and JSON arrays (element by element), array text (`{a,b}`, `["a","b"]` or `a,b`) |
+//! | network, bit, interval, geometry, composite | values of the same kind |
+
+use crate::core::{
+    types::{FloatSize, IntSize, Type},
+    value::Value,
+};
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive, Zero};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SubsecRound};
+use serde::Serialize;
+use std::{net::IpAddr, str::FromStr};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// What a conversion lost. Variants are ordered by how much a reader of the
+/// converted value would notice, so the worst of several is their `max`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lossiness {
+    /// The converted value holds everything the original did
+    #[default]
+    Exact,
+    /// A time zone offset was dropped; the instant is kept as UTC wall time
+    OffsetDropped,
+    /// Digits beyond the target's scale or precision were rounded away
+    Rounded,
+    /// A part of the value was dropped, such as the time of day of a date
+    Truncated,
+    /// Bytes that are not valid UTF-8 were replaced
+    Replaced,
+    /// Distinct values became the same one, such as integers other than 0
+    /// and 1 as booleans
+    Collapsed,
+}
+
+impl Lossiness {
+    pub fn is_lossy(self) -> bool {
+        self != Lossiness::Exact
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Lossiness::Exact => "exact",
+            Lossiness::OffsetDropped => "offset_dropped",
+            Lossiness::Rounded => "rounded",
+            Lossiness::Truncated => "truncated",
+            Lossiness::Replaced => "replaced",
+            Lossiness::Collapsed => "collapsed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConversionError {
+    #[error("cannot convert {from} to {to}")]
+    Unsupported { from: String, to: String },
+
+    #[error("'{value}' is not a valid {to}")]
+    Invalid { value: String, to: String },
+
+    #[error("{value} is out of range for {to}")]
+    OutOfRange { value: String, to: String },
+
+    #[error("a value of length {length} does not fit {to}")]
+    TooLong { length: usize, to: String },
+}
+
+type Converted = Result<(Value, Lossiness), ConversionError>;
+
+impl Value {
+    /// Converts the value to `target`, reporting what the conversion lost.
+    /// Null converts to null in every type.
+    pub fn convert_to(&self, target: &Type) -> Converted {
+        if matches!(self, Value::Null) {
+            return Ok((Value::Null, Lossiness::Exact));
+        }
+        match target {
+            Type::Int { bits, unsigned, .. } => to_int(self, *bits, *unsigned, target),
+            Type::Year => to_year(self, target),
+            Type::Decimal { precision, scale } => to_decimal(self, *precision, *scale, target),
+            Type::Float { bits } => to_float(self, *bits, target),
+            Type::Boolean => to_bool(self, target),
+            Type::Char { length, .. } | Type::Varchar { length, .. } => {
+                to_text(self, *length, target)
+            }
+            Type::Text { .. } => to_text(self, None, target),
+            Type::Binary { length } | Type::Varbinary { length } => {
+                to_binary(self, *length, target)
+            }
+            Type::Blob { max_bytes } => to_binary(self, *max_bytes, target),
+            Type::Date => to_date(self, target),
+            Type::Time { precision, with_tz } => to_time(self, *precision, *with_tz, target),
+            Type::Timestamp { precision, with_tz } => {
+                to_timestamp(self, *precision, *with_tz, target)
+            }
+            Type::Uuid => to_uuid(self, target),
+            Type::Json { .. } => to_json(self, target),
+            Type::Enum { name, values } => to_enum(self, name, values, target),
+            Type::Set { values } => to_set(self, values, target),
+            Type::Array { element } => to_array(self, element),
+            Type::Domain { name, base_type } => match self {
+                // Enum domains keep the label under the domain's name
+                Value::Enum { value, .. } => Ok((
+                    Value::Enum {
+                        type_name: name.clone(),
+                        value: value.clone(),
+                    },
+                    Lossiness::Exact,
+                )),
+                _ => self.convert_to(base_type),
+            },
+            Type::Inet => match self {
+                Value::IpAddr(_) => exact(self.clone()),
+                Value::String(s) => s
+                    .trim()
+                    .parse::<IpAddr>()
+                    .map(|addr| (Value::IpAddr(addr), Lossiness::Exact))
+                    .map_err(|_| invalid(s, target)),
+                _ => unsupported(self, target),
+            },
+            Type::Cidr => to_cidr(self, target),
+            Type::Interval { .. } => same_kind(self, matches!(self, Value::Interval(_)), target),
+            Type::Bit { .. } => same_kind(self, matches!(self, Value::Bits(_)), target),
+            Type::MacAddr => same_kind(self, matches!(self, Value::MacAddr(_)), target),
+            Type::Geometry { .. } => match self {
+                Value::Geometry(_) => exact(self.clone()),
+                Value::Binary(bytes) => exact(Value::Geometry(bytes.clone())),
+                _ => unsupported(self, target),
+            },
+            Type::Composite { .. } => {
+                same_kind(self, matches!(self, Value::Composite { .. }), target)
+            }
+            // Nothing is known of the column, so the database decides
+            Type::Unknown { .. } => exact(self.clone()),
+        }
+    }
+}
+
+fn exact(value: Value) -> Converted {
+    Ok((value, Lossiness::Exact))
+}
+
+fn same_kind(value: &Value, same: bool, target: &Type) -> Converted {
+    if same {
+        exact(value.clone())
+    } else {
+        unsupported(value, target)
+    }
+}
+
+fn unsupported(value: &Value, target: &Type) -> Converted {
+    Err(ConversionError::Unsupported {
+        from: value.data_type().name(),
+        to: target.name(),
+    })
+}
+
+fn invalid(text: &str, target: &Type) -> ConversionError {
+    ConversionError::Invalid {
+        value: text.to_string(),
+        to: target.name(),
+    }
+}
+
+fn out_of_range(value: impl ToString, target: &Type) -> ConversionError {
+    ConversionError::OutOfRange {
+        value: value.to_string(),
+        to: target.name(),
+    }
+}
+
+/// The value as a whole number, rounding away any fraction
+fn whole_number(value: &Value, target: &Type) -> Result<(i128, Lossiness), ConversionError> {
+    match value {
+        Value::Int(v) => Ok((i128::from(*v), Lossiness::Exact)),
+        Value::UInt(v) => Ok((i128::from(*v), Lossiness::Exact)),
+        Value::Year(v) => Ok((i128::from(*v), Lossiness::Exact)),
+        Value::Boolean(b) => Ok((i128::from(*b), Lossiness::Exact)),
+        Value::Float(v) => {
+            let rounded = v.round();
+            if !rounded.is_finite() || rounded.abs() >= 2f64.powi(127) {
+                return Err(out_of_range(v, target));
+            }
+            Ok((rounded as i128, lossy_if(rounded != *v, Lossiness::Rounded)))
+        }
+        Value::Decimal(d) => {
+            let rounded = d.with_scale_round(0, RoundingMode::HalfUp);
+            let whole = rounded.to_i128().ok_or_else(|| out_of_range(d, target))?;
+            Ok((whole, lossy_if(!d.is_integer(), Lossiness::Rounded)))
+        }
+        Value::String(s) => match s.trim().parse::<i128>() {
+            Ok(v) => Ok((v, Lossiness::Exact)),
+            Err(_) => {
+                let d = BigDecimal::from_str(s.trim()).map_err(|_| invalid(s, target))?;
+                whole_number(&Value::Decimal(d), target)
+            }
+        },
+        _ => Err(ConversionError::Unsupported {
+            from: value.data_type().name(),
+            to: target.name(),
+        }),
+    }
+}
+
+fn lossy_if(lossy: bool, loss: Lossiness) -> Lossiness {
+    if lossy { loss } else { Lossiness::Exact }
+}
+
+fn int_range(bits: IntSize, unsigned: bool) -> (i128, i128) {
+    let width = match bits {
+        IntSize::I8 => 8,
+        IntSize::I16 => 16,
+        IntSize::I24 => 24,
+        IntSize::I32 => 32,
+        IntSize::I64 => 64,
+    };
+    if unsigned {
+        (0, (1i128 << width) - 1)
+    } else {
+        (-(1i128 << (width - 1)), (1i128 << (width - 1)) - 1)
+    }
+}
+
+fn to_int(value: &Value, bits: IntSize, unsigned: bool, target: &Type) -> Converted {
+    let (whole, loss) = whole_number(value, target)?;
+    let (min, max) = int_range(bits, unsigned);
+    if whole < min || whole > max {
+        return Err(out_of_range(whole, target));
+    }
+    let converted = if unsigned {
+        Value::UInt(whole as u64)
+    } else {
+        Value::Int(whole as i64)
+    };
+    Ok((converted, loss))
+}
+
+fn to_year(value: &Value, target: &Type) -> Converted {
+    let (whole, loss) = whole_number(value, target)?;
+    let year = i16::try_from(whole).map_err(|_| out_of_range(whole, target))?;
+    Ok((Value::Year(year), loss))
+}
+
+fn decimal_of(value: &Value, target: &Type) -> Result<BigDecimal, ConversionError> {
+    match value {
+        Value::Decimal(d) => Ok(d.clone()),
+        Value::Int(v) => Ok(BigDecimal::from(*v)),
+        Value::UInt(v) => Ok(BigDecimal::from(*v)),
+        Value::Year(v) => Ok(BigDecimal::from(*v)),
+        Value::Boolean(b) => Ok(BigDecimal::from(u8::from(*b))),
+        // Through the shortest round-trip text, so 0.1 stays 0.1 rather than
+        // the exact binary expansion of the nearest double
+        Value::Float(v) if v.is_finite() => {
+            BigDecimal::from_str(&v.to_string()).map_err(|_| out_of_range(v, target))
+        }
+        Value::Float(v) => Err(out_of_range(v, target)),
+        Value::String(s) => BigDecimal::from_str(s.trim()).map_err(|_| invalid(s, target)),
+        _ => Err(ConversionError::Unsupported {
+            from: value.data_type().name(),
+            to: target.name(),
+        }),
+    }
+}
+
+fn to_decimal(value: &Value, precision: Option<u8>, scale: Option<u8>, target: &Type) -> Converted {
+    let mut d = decimal_of(value, target)?;
+    let mut loss = Lossiness::Exact;
+    if let Some(scale) = scale
+        && d.fractional_digit_count() > i64::from(scale)
+    {
+        d = d.with_scale_round(i64::from(scale), RoundingMode::HalfUp);
+        loss = Lossiness::Rounded;
+    }
+    if let Some(precision) = precision {
+        let integer_digits = d.digits() as i64 - d.fractional_digit_count();
+        let allowed = i64::from(precision) - i64::from(scale.unwrap_or(0));
+        if !d.is_zero() && integer_digits > allowed {
+            return Err(out_of_range(&d, target));
+        }
+    }
+    Ok((Value::Decimal(d), loss))
+}
+
+fn to_float(value: &Value, bits: FloatSize, target: &Type) -> Converted {
+    let (float, mut loss) = match value {
+        Value::Float(v) => (*v, Lossiness::Exact),
+        Value::Int(v) => (
+            *v as f64,
+            lossy_if((*v as f64) as i64 != *v, Lossiness::Rounded),
+        ),
+        Value::UInt(v) => (
+            *v as f64,
+            lossy_if((*v as f64) as u64 != *v, Lossiness::Rounded),
+        ),
+        Value::Year(v) => (f64::from(*v), Lossiness::Exact),
+        Value::Boolean(b) => (f64::from(u8::from(*b)), Lossiness::Exact),
+        Value::Decimal(d) => {
+            let float: f64 = d.to_string().parse().map_err(|_| out_of_range(d, target))?;
+            let back = BigDecimal::from_str(&float.to_string()).ok();
+            (
+                float,
+                lossy_if(back.as_ref() != Some(d), Lossiness::Rounded),
+            )
+        }
+        Value::String(s) => {
+            let float: f64 = s.trim().parse().map_err(|_| invalid(s, target))?;
+            (float, Lossiness::Exact)
+        }
+        _ => return unsupported(value, target),
+    };
+    if bits == FloatSize::F32 {
+        let single = f64::from(float as f32);
+        if float.is_finite() && !single.is_finite() {
+            return Err(out_of_range(float, target));
+        }
+        if single != float && !float.is_nan() {
+            loss = loss.max(Lossiness::Rounded);
+        }
+        return Ok((Value::Float(single), loss));
+    }
+    Ok((Value::Float(float), loss))
+}
+
+fn to_bool(value: &Value, target: &Type) -> Converted {
+    match value {
+        Value::Boolean(_) => exact(value.clone()),
+        Value::Int(_) | Value::UInt(_) => {
+            let (whole, _) = whole_number(value, target)?;
+            Ok((
+                Value::Boolean(whole != 0),
+                lossy_if(whole != 0 && whole != 1, Lossiness::Collapsed),
+            ))
+        }
+        Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "t" | "yes" | "y" | "1" => exact(Value::Boolean(true)),
+            "false" | "f" | "no" | "n" | "0" => exact(Value::Boolean(false)),
+            _ => Err(invalid(s, target)),
+        },
+        _ => unsupported(value, target),
+    }
+}
+
+fn to_text(value: &Value, length: Option<usize>, target: &Type) -> Converted {
+    let (text, loss) = match value {
+        Value::String(s) => (s.clone(), Lossiness::Exact),
+        Value::Binary(bytes) => match String::from_utf8(bytes.clone()) {
+            Ok(text) => (text, Lossiness::Exact),
+            Err(_) => (
+                String::from_utf8_lossy(bytes).into_owned(),
+                Lossiness::Replaced,
+            ),
+        },
+        Value::Timestamp {
+            offset_secs: Some(offset),
+            ..
+        } if *offset != 0 => (
+            value.as_string().unwrap_or_default(),
+            Lossiness::OffsetDropped,
+        ),
+        Value::Decimal(d) => (d.to_plain_string(), Lossiness::Exact),
+        other => match other.as_string() {
+            Some(text) => (text, Lossiness::Exact),
+            None => return unsupported(value, target),
+        },
+    };
+    if let Some(length) = length {
+        let chars = text.chars().count();
+        if chars > length {
+            return Err(ConversionError::TooLong {
+                length: chars,
+                to: format!("{}({length})", target.name()),
+            });
+        }
+    }
+    Ok((Value::String(text), loss))
+}
+
+fn to_binary(value: &Value, length: Option<usize>, target: &Type) -> Converted {
+    let bytes = match value {
+        Value::Binary(bytes) | Value::Geometry(bytes) => bytes.clone(),
+        Value::String(s) => s.as_bytes().to_vec(),
+        Value::Uuid(uuid) => uuid.as_bytes().to_vec(),
+        _ => return unsupported(value, target),
+    };
+    if let Some(length) = length
+        && bytes.len() > length
+    {
+        return Err(ConversionError::TooLong {
+            length: bytes.len(),
+            to: format!("{}({length})", target.name()),
+        });
+    }
+    exact(Value::Binary(bytes))
+}
+
+/// Timestamp text as a UTC wall time and its offset; text without an
+/// offset is read as UTC
+fn parse_timestamp(raw: &str) -> Option<(NaiveDateTime, Option<i32>)> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Some((dt.naive_utc(), Some(dt.offset().local_minus_utc())));
+    }
+    NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f"))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .map(|dt| (dt, None))
+}
+
+fn to_date(value: &Value, target: &Type) -> Converted {
+    let from_timestamp = |ts: NaiveDateTime| {
+        Ok((
+            Value::Date(ts.date()),
+            lossy_if(ts.time() != NaiveTime::MIN, Lossiness::Truncated),
+        ))
+    };
+    match value {
+        Value::Date(_) => exact(value.clone()),
+        Value::Timestamp { value: ts, .. } => from_timestamp(*ts),
+        Value::String(s) => {
+            let raw = s.trim();
+            if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+                return exact(Value::Date(date));
+            }
+            match parse_timestamp(raw) {
+                Some((ts, _)) => from_timestamp(ts),
+                None => Err(invalid(s, target)),
+            }
+        }
+        _ => unsupported(value, target),
+    }
+}
+
+/// Rounds fractional seconds to `precision` digits
+fn round_subsecs<T: SubsecRound + PartialEq + Copy>(
+    value: T,
+    precision: Option<u8>,
+) -> (T, Lossiness) {
+    match precision {
+        Some(digits) if digits < 9 => {
+            let rounded = value.round_subsecs(u16::from(digits));
+            (rounded, lossy_if(rounded != value, Lossiness::Rounded))
+        }
+        _ => (value, Lossiness::Exact),
+    }
+}
+
+/// The offset a column keeps: none without a time zone, UTC when the
+/// value has none
+fn column_offset(offset_secs: Option<i32>, with_tz: bool) -> (Option<i32>, Lossiness) {
+    match (offset_secs, with_tz) {
+        (Some(offset), false) => (None, lossy_if(offset != 0, Lossiness::OffsetDropped)),
+        (None, true) => (Some(0), Lossiness::Exact),
+        (offset, _) => (offset, Lossiness::Exact),
+    }
+}
+
+fn to_time(value: &Value, precision: Option<u8>, with_tz: bool, target: &Type) -> Converted {
+    let (time, offset, loss) = match value {
+        Value::Time { value, offset_secs } => (*value, *offset_secs, Lossiness::Exact),
+        Value::Timestamp { value, offset_secs } => {
+            (value.time(), *offset_secs, Lossiness::Truncated)
+        }
+        Value::String(s) => {
+            let raw = s.trim();
+            let time = NaiveTime::parse_from_str(raw, "%H:%M:%S%.f")
+                .or_else(|_| NaiveTime::parse_from_str(raw, "%H:%M"))
+                .map_err(|_| invalid(s, target))?;
+            (time, None, Lossiness::Exact)
+        }
+        _ => return unsupported(value, target),
+    };
+    let (offset, offset_loss) = column_offset(offset, with_tz);
+    let (rounded, round_loss) = round_subsecs(time, precision);
+    // Rounding can carry into the next day, which a time of day cannot hold
+    if rounded < time {
+        return Err(out_of_range(time, target));
+    }
+    Ok((
+        Value::Time {
+            value: rounded,
+            offset_secs: offset,
+        },
+        loss.max(offset_loss).max(round_loss),
+    ))
+}
+
+fn to_timestamp(value: &Value, precision: Option<u8>, with_tz: bool, target: &Type) -> Converted {
+    let (ts, offset) = match value {
+        Value::Timestamp { value, offset_secs } => (*value, *offset_secs),
+        Value::Date(date) => (date.and_time(NaiveTime::MIN), None),
+        Value::String(s) => parse_timestamp(s.trim())
+            .or_else(|| {
+                NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                    .ok()
+                    .map(|date| (date.and_time(NaiveTime::MIN), None))
+            })
+            .ok_or_else(|| invalid(s, target))?,
+        _ => return unsupported(value, target),
+    };
+    let (offset, offset_loss) = column_offset(offset, with_tz);
+    let (ts, round_loss) = round_subsecs(ts, precision);
+    Ok((
+        Value::Timestamp {
+            value: ts,
+            offset_secs: offset,
+        },
+        offset_loss.max(round_loss),
+    ))
+}
+
+fn to_uuid(value: &Value, target: &Type) -> Converted {
+    match value {
+        Value::Uuid(_) => exact(value.clone()),
+        Value::String(s) => Uuid::parse_str(s.trim())
+            .map(|uuid| (Value::Uuid(uuid), Lossiness::Exact))
+            .map_err(|_| invalid(s, target)),
+        Value::Binary(bytes) => Uuid::from_slice(bytes)
+            .map(|uuid| (Value::Uuid(uuid), Lossiness::Exact))
+            .map_err(|_| ConversionError::TooLong {
+                length: bytes.len(),
+                to: target.name(),
+            }),
+        _ => unsupported(value, target),
+    }
+}
+
+fn to_json(value: &Value, target: &Type) -> Converted {
+    match value {
+        Value::Json(_) => exact(value.clone()),
+        Value::String(s) => serde_json::from_str(s)
+            .map(|json| (Value::Json(json), Lossiness::Exact))
+            .map_err(|_| invalid(s, target)),
+        other => exact(Value::Json(other.to_json())),
+    }
+}
+
+fn check_label(label: &str, labels: &[String], target: &Type) -> Result<(), ConversionError> {
+    if labels.is_empty() || labels.iter().any(|l| l == label) {
+        Ok(())
+    } else {
+        Err(invalid(label, target))
+    }
+}
+
+fn to_enum(value: &Value, name: &str, labels: &[String], target: &Type) -> Converted {
+    let label = match value {
+        Value::Enum { value, .. } | Value::String(value) => value,
+        _ => return unsupported(value, target),
+    };
+    check_label(label, labels, target)?;
+    exact(Value::Enum {
+        type_name: name.to_string(),
+        value: label.clone(),
+    })
+}
+
+fn to_set(value: &Value, labels: &[String], target: &Type) -> Converted {
+    let members: Vec<String> = match value {
+        Value::Set(members) => members.clone(),
+        Value::String(s) if s.trim().is_empty() => Vec::new(),
+        Value::String(s) => s.split(',').map(|m| m.trim().to_string()).collect(),
+        Value::Enum { value, .. } => vec![value.clone()],
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_string().ok_or_else(|| invalid("NULL", target)))
+            .collect::<Result<_, _>>()?,
+        _ => return unsupported(value, target),
+    };
+    for member in &members {
+        check_label(member, labels, target)?;
+    }
+    exact(Value::Set(members))
+}
+
+fn to_array(value: &Value, element: &Type) -> Converted {
+    let items: Vec<Value> = match value {
+        Value::Array(items) => items.clone(),
+        Value::Set(members) => members.iter().cloned().map(Value::String).collect(),
+        Value::Json(serde_json::Value::Array(items)) => {
+            items.iter().map(Value::from_json).collect()
+        }
+        Value::Enum { value, .. } => vec![Value::String(value.clone())],
+        Value::String(s) => parse_array_text(s).into_iter().map(Value::String).collect(),
+        other => {
+            return Err(ConversionError::Unsupported {
+                from: other.data_type().name(),
+                to: format!("{}[]", element.name()),
+            });
+        }
+    };
+    let mut loss = Lossiness::Exact;
+    let mut converted = Vec::with_capacity(items.len());
+    for item in &items {
+        let (item, item_loss) = item.convert_to(element)?;
+        loss = loss.max(item_loss);
+        converted.push(item);
+    }
+    Ok((Value::Array(converted), loss))
+}
+
+fn to_cidr(value: &Value, target: &Type) -> Converted {
+    match value {
+        Value::Cidr { .. } => exact(value.clone()),
+        Value::IpAddr(addr) => exact(Value::Cidr {
+            addr: *addr,
+            prefix: if addr.is_ipv4() { 32 } else { 128 },
+        }),
+        Value::String(s) => {
+            let (addr, prefix) = s.trim().split_once('/').ok_or_else(|| invalid(s, target))?;
+            let addr: IpAddr = addr.parse().map_err(|_| invalid(s, target))?;
+            let prefix: u8 = prefix.parse().map_err(|_| invalid(s, target))?;
+            exact(Value::Cidr { addr, prefix })
+        }
+        _ => unsupported(value, target),
+    }
+}
+
+/// Elements of array text: a JSON array, a PostgreSQL array literal
+/// (`{a,"b c"}`) or comma-separated values
+pub fn parse_array_text(raw: &str) -> Vec<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+
+    if let Ok(items) = serde_json::from_str::<Vec<String>>(trimmed) {
+        return items;
+    }
+
+    if trimmed.starts_with('{') && trimmed.ends_with('}') && trimmed.len() > 2 {
+        let inner = &trimmed[1..trimmed.len() - 1];
+        return inner
+            .split(',')
+            .map(|item| unescape_array_item(item.trim()))
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    trimmed
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.trim_matches('"').trim_matches('\'').to_string())
+        .collect()
+}
+
+/// Unescapes a single item of a PostgreSQL array literal
+fn unescape_array_item(raw: &str) -> String {
+    let unquoted = raw.trim_matches('"');
+    let mut result = String::new();
+    let mut chars = unquoted.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(&next) = chars.peek() {
+                chars.next();
+                result.push(next);
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(bits: IntSize, unsigned: bool) -> Type {
+        Type::Int {
+            bits,
+            unsigned,
+            auto_increment: false,
+        }
+    }
+
+    fn dec(s: &str) -> Value {
+        Value::Decimal(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_numeric_conversions() {
+        assert_eq!(
+            Value::String("42".into()).convert_to(&int(IntSize::I32, false)),
+            Ok((Value::Int(42), Lossiness::Exact))
+        );
+        assert_eq!(
+            Value::Float(2.6).convert_to(&int(IntSize::I64, false)),
+            Ok((Value::Int(3), Lossiness::Rounded))
+        );
+        assert_eq!(
+            Value::Int(7).convert_to(&int(IntSize::I8, true)),
+            Ok((Value::UInt(7), Lossiness::Exact))
+        );
+        assert!(matches!(
+            Value::Int(300).convert_to(&int(IntSize::I8, false)),
+            Err(ConversionError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            Value::Int(-1).convert_to(&int(IntSize::I32, true)),
+            Err(ConversionError::OutOfRange { .. })
+        ));
+
+        let money = Type::Decimal {
+            precision: Some(6),
+            scale: Some(2),
+        };
+        assert_eq!(
+            dec("12.345").convert_to(&money),
+            Ok((dec("12.35"), Lossiness::Rounded))
+        );
+        assert_eq!(
+            Value::Float(0.1).convert_to(&money),
+            Ok((dec("0.1"), Lossiness::Exact))
+        );
+        assert!(matches!(
+            dec("123456.7").convert_to(&money),
+            Err(ConversionError::OutOfRange { .. })
+        ));
+
+        let single = Type::Float {
+            bits: FloatSize::F32,
+        };
+        assert_eq!(
+            Value::Float(0.1).convert_to(&single).unwrap().1,
+            Lossiness::Rounded
+        );
+        assert_eq!(
+            Value::Float(0.5).convert_to(&single),
+            Ok((Value::Float(0.5), Lossiness::Exact))
+        );
+        assert_eq!(
+            Value::Int(3).convert_to(&Type::Boolean),
+            Ok((Value::Boolean(true), Lossiness::Collapsed))
+        );
+    }
+
+    #[test]
+    fn test_text_and_temporal_conversions() {
+        let varchar = Type::Varchar {
+            length: Some(3),
+            charset: None,
+        };
+        assert_eq!(
+            Value::Int(12).convert_to(&varchar),
+            Ok((Value::String("12".into()), Lossiness::Exact))
+        );
+        assert_eq!(
+            Value::Binary(vec![b'a', 0xff]).convert_to(&varchar),
+            Ok((Value::String("a\u{fffd}".into()), Lossiness::Replaced))
+        );
+        assert!(matches!(
+            Value::String("abcd".into()).convert_to(&varchar),
+            Err(ConversionError::TooLong { length: 4, .. })
+        ));
+
+        let naive = Type::Timestamp {
+            precision: None,
+            with_tz: false,
+        };
+        let (ts, loss) = Value::String("2024-03-01T10:00:00+02:00".into())
+            .convert_to(&naive)
+            .unwrap();
+        assert_eq!(loss, Lossiness::OffsetDropped);
+        assert_eq!(
+            ts,
+            Value::Timestamp {
+                value: "2024-03-01T08:00:00".parse().unwrap(),
+                offset_secs: None,
+            }
+        );
+        assert_eq!(
+            ts.convert_to(&Type::Date),
+            Ok((
+                Value::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()),
+                Lossiness::Truncated
+            ))
+        );
+        let seconds = Type::Timestamp {
+            precision: Some(0),
+            with_tz: true,
+        };
+        assert_eq!(
+            Value::String("2024-03-01 10:00:00.7".into()).convert_to(&seconds),
+            Ok((
+                Value::Timestamp {
+                    value: "2024-03-01T10:00:01".parse().unwrap(),
+                    offset_secs: Some(0),
+                },
+                Lossiness::Rounded
+            ))
+        );
+    }
+
+    #[test]
+    fn test_labels_and_arrays() {
+        let mood = Type::Enum {
+            name: "mood".into(),
+            values: vec!["happy".into(), "sad".into()],
+        };
+        assert_eq!(
+            Value::String("sad".into()).convert_to(&mood),
+            Ok((
+                Value::Enum {
+                    type_name: "mood".into(),
+                    value: "sad".into()
+                },
+                Lossiness::Exact
+            ))
+        );
+        assert!(Value::String("angry".into()).convert_to(&mood).is_err());
+
+        let ints = Type::Array {
+            element: Box::new(int(IntSize::I32, false)),
+        };
+        assert_eq!(
+            Value::String("{1,2}".into()).convert_to(&ints),
+            Ok((
+                Value::Array(vec![Value::Int(1), Value::Int(2)]),
+                Lossiness::Exact
+            ))
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Float(1.5), Value::Null])
+                .convert_to(&ints)
+                .unwrap()
+                .1,
+            Lossiness::Rounded
+        );
+        assert!(matches!(
+            Value::Uuid(Uuid::nil()).convert_to(&Type::Boolean),
+            Err(ConversionError::Unsupported { .. })
+        ));
+        assert_eq!(parse_array_text(r#"{a,"b\"c"}"#), vec!["a", "b\"c"]);
+        assert_eq!(parse_array_text(r#"["a","b"]"#), vec!["a", "b"]);
+    }
+}
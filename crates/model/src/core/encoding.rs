@@ -0,0 +1,110 @@
+use crate::core::value::Value;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What to do with text bytes that are not valid UTF-8, e.g. latin1 data
+/// stored in a column declared with another charset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvalidUtf8 {
+    /// Replace each invalid sequence with U+FFFD
+    #[default]
+    Replace,
+    /// Decode the value as Latin-1 (ISO-8859-1) instead
+    Latin1,
+    /// Fail the row
+    Error,
+}
+
+impl InvalidUtf8 {
+    /// Decodes `bytes` as UTF-8, falling back to this handling when they are
+    /// not valid UTF-8. `None` when the handling is `Error`.
+    pub fn decode(&self, bytes: &[u8]) -> Option<String> {
+        if let Ok(s) = std::str::from_utf8(bytes) {
+            return Some(s.to_string());
+        }
+        match self {
+            InvalidUtf8::Replace => Some(String::from_utf8_lossy(bytes).into_owned()),
+            InvalidUtf8::Latin1 => Some(bytes.iter().map(|&b| b as char).collect()),
+            InvalidUtf8::Error => None,
+        }
+    }
+}
+
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidUtf8::Replace => write!(f, "REPLACE"),
+            InvalidUtf8::Latin1 => write!(f, "LATIN1"),
+            InvalidUtf8::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+/// How an array is written to a destination column that does not hold
+/// arrays, such as any MySQL column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrayFormat {
+    /// A JSON array, e.g. `["a","b"]`
+    #[default]
+    Json,
+    /// The elements as text joined with a delimiter, e.g. `a,b`
+    Delimited,
+}
+
+impl ArrayFormat {
+    /// `items` in this format. NULL elements are JSON nulls, or empty when
+    /// delimited.
+    pub fn encode(&self, items: &[Value], delimiter: &str) -> String {
+        match self {
+            ArrayFormat::Json => Value::Array(items.to_vec()).to_json().to_string(),
+            ArrayFormat::Delimited => items
+                .iter()
+                .map(|item| item.as_string().unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(delimiter),
+        }
+    }
+}
+
+impl fmt::Display for ArrayFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayFormat::Json => write!(f, "JSON"),
+            ArrayFormat::Delimited => write!(f, "DELIMITED"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        // "café" in latin1
+        let latin1 = b"caf\xe9";
+
+        assert_eq!(
+            InvalidUtf8::Error.decode("café".as_bytes()).unwrap(),
+            "café"
+        );
+        assert_eq!(InvalidUtf8::Replace.decode(latin1).unwrap(), "caf\u{fffd}");
+        assert_eq!(InvalidUtf8::Latin1.decode(latin1).unwrap(), "café");
+        assert_eq!(InvalidUtf8::Error.decode(latin1), None);
+    }
+
+    #[test]
+    fn test_encode_array() {
+        let items = vec![
+            Value::String("a \"b\"".to_string()),
+            Value::Int(2),
+            Value::Null,
+        ];
+
+        assert_eq!(
+            ArrayFormat::Json.encode(&items, ","),
+            r#"["a \"b\"",2,null]"#
+        );
+        assert_eq!(ArrayFormat::Delimited.encode(&items, "|"), "a \"b\"|2|");
+    }
+}
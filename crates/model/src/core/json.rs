@@ -0,0 +1,248 @@
+//! Plain JSON form of [`Value`] for reports, failed-row capture and file output.
+//!
+//! `Value`'s derived serde impl is tagged (`{"Int":5}`) so it round-trips
+//! through the state store. Output read by people and other tools uses this
+//! form instead: numbers stay numbers, decimals are strings (no precision
+//! loss), binary is base64, temporal values are ISO 8601 strings and network
+//! values use their usual text notation.
+//!
+//! Fields opt in with `#[serde(with = "model::core::json::plain")]` or
+//! `#[serde(with = "model::core::json::plain_map")]`.
+
+use crate::core::value::Value;
+use base64::Engine;
+use serde::{
+    Serialize, Serializer,
+    ser::{SerializeMap, SerializeSeq},
+};
+
+/// Serializes the wrapped value in the plain JSON form.
+pub struct PlainValue<'a>(pub &'a Value);
+
+impl Serialize for PlainValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Null => serializer.serialize_none(),
+            Value::Int(v) => serializer.serialize_i64(*v),
+            Value::UInt(v) => serializer.serialize_u64(*v),
+            Value::Float(v) => serializer.serialize_f64(*v),
+            Value::Year(v) => serializer.serialize_i16(*v),
+            Value::Boolean(v) => serializer.serialize_bool(*v),
+            Value::Decimal(v) => serializer.collect_str(v),
+            Value::String(v) => serializer.serialize_str(v),
+            Value::Enum { value, .. } => serializer.serialize_str(value),
+            Value::Binary(v) | Value::Geometry(v) => {
+                serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(v))
+            }
+            Value::Date(v) => serializer.collect_str(&v.format("%Y-%m-%d")),
+            Value::Time { value, offset_secs } => serializer.serialize_str(&format!(
+                "{}{}",
+                value.format("%H:%M:%S%.f"),
+                offset_suffix(*offset_secs)
+            )),
+            Value::Timestamp { value, offset_secs } => serializer.serialize_str(&format!(
+                "{}{}",
+                value.format("%Y-%m-%dT%H:%M:%S%.f"),
+                offset_suffix(*offset_secs)
+            )),
+            Value::Interval(v) => v.serialize(serializer),
+            Value::Uuid(v) => serializer.collect_str(v),
+            Value::Json(v) => v.serialize(serializer),
+            Value::Bits(bits) => serializer.serialize_str(
+                &bits
+                    .iter()
+                    .map(|b| if *b { '1' } else { '0' })
+                    .collect::<String>(),
+            ),
+            Value::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(&PlainValue(item))?;
+                }
+                seq.end()
+            }
+            Value::Set(items) => items.serialize(serializer),
+            Value::IpAddr(v) => serializer.collect_str(v),
+            Value::Cidr { addr, prefix } => serializer.serialize_str(&format!("{addr}/{prefix}")),
+            Value::MacAddr(m) => serializer.serialize_str(&format!(
+                "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+                m[0], m[1], m[2], m[3], m[4], m[5]
+            )),
+            Value::Composite { fields, .. } => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in fields {
+                    map.serialize_entry(name, &PlainValue(value))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+fn offset_suffix(offset_secs: Option<i32>) -> String {
+    match offset_secs {
+        None => String::new(),
+        Some(0) => "Z".to_string(),
+        Some(secs) => {
+            let sign = if secs < 0 { '-' } else { '+' };
+            let secs = secs.unsigned_abs();
+            format!("{sign}{:02}:{:02}", secs / 3600, secs % 3600 / 60)
+        }
+    }
+}
+
+impl Value {
+    /// The value in the plain JSON form.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(PlainValue(self)).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Best-effort inverse of [`Value::to_json`]. Strings stay strings, since
+    /// JSON doesn't record whether one was a date, a decimal or base64.
+    pub fn from_json(json: &serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Boolean(*b),
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Value::Int(i)
+                } else if let Some(u) = n.as_u64() {
+                    Value::UInt(u)
+                } else {
+                    Value::Float(n.as_f64().unwrap_or(0.0))
+                }
+            }
+            serde_json::Value::String(s) => Value::String(s.clone()),
+            other => Value::Json(other.clone()),
+        }
+    }
+}
+
+/// `#[serde(with)]` module for a single `Value` field.
+pub mod plain {
+    use super::PlainValue;
+    use crate::core::value::Value;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+        PlainValue(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        serde_json::Value::deserialize(deserializer).map(|json| Value::from_json(&json))
+    }
+}
+
+/// `#[serde(with)]` module for a `HashMap<String, Value>` field.
+pub mod plain_map {
+    use super::PlainValue;
+    use crate::core::value::Value;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer>(
+        map: &HashMap<String, Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(k, v)| (k, PlainValue(v))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Value>, D::Error> {
+        let map = HashMap::<String, serde_json::Value>::deserialize(deserializer)?;
+        Ok(map
+            .into_iter()
+            .map(|(k, json)| (k, Value::from_json(&json)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::value::IntervalValue;
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(Value::Int(-5).to_json(), json!(-5));
+        assert_eq!(Value::Boolean(true).to_json(), json!(true));
+        assert_eq!(Value::Null.to_json(), json!(null));
+        assert_eq!(
+            Value::Decimal(BigDecimal::from_str("12345678901234567890.01").unwrap()).to_json(),
+            json!("12345678901234567890.01")
+        );
+        assert_eq!(Value::Binary(b"hi!".to_vec()).to_json(), json!("aGkh"));
+        assert_eq!(Value::Float(f64::NAN).to_json(), json!(null));
+    }
+
+    #[test]
+    fn test_temporal_values_are_iso_8601() {
+        let ts = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_milli_opt(10, 30, 0, 250)
+            .unwrap();
+        assert_eq!(
+            Value::Timestamp {
+                value: ts,
+                offset_secs: Some(-5 * 3600)
+            }
+            .to_json(),
+            json!("2024-03-01T10:30:00.250-05:00")
+        );
+        assert_eq!(
+            Value::Timestamp {
+                value: ts,
+                offset_secs: None
+            }
+            .to_json(),
+            json!("2024-03-01T10:30:00.250")
+        );
+        assert_eq!(Value::Date(ts.date()).to_json(), json!("2024-03-01"));
+        assert_eq!(
+            Value::Interval(IntervalValue::from_hms(1, 0, 0)).to_json(),
+            json!({ "months": 0, "days": 0, "microseconds": 3_600_000_000i64 })
+        );
+    }
+
+    #[test]
+    fn test_nested_values() {
+        let value = Value::Composite {
+            type_name: "address".to_string(),
+            fields: vec![
+                ("zip".to_string(), Value::String("02139".to_string())),
+                (
+                    "tags".to_string(),
+                    Value::Array(vec![Value::Int(1), Value::Null]),
+                ),
+                ("mac".to_string(), Value::MacAddr([0, 26, 43, 60, 77, 94])),
+            ],
+        };
+        assert_eq!(
+            value.to_json(),
+            json!({ "zip": "02139", "tags": [1, null], "mac": "00:1a:2b:3c:4d:5e" })
+        );
+    }
+
+    #[test]
+    fn test_plain_map_round_trip() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Row {
+            #[serde(with = "plain_map")]
+            data: std::collections::HashMap<String, Value>,
+        }
+
+        let row = Row {
+            data: [("id".to_string(), Value::Int(7))].into_iter().collect(),
+        };
+        let text = serde_json::to_string(&row).unwrap();
+        assert_eq!(text, r#"{"data":{"id":7}}"#);
+
+        let back: Row = serde_json::from_str(&text).unwrap();
+        assert_eq!(back.data["id"], Value::Int(7));
+    }
+}
@@ -1,4 +1,9 @@
+pub mod cast;
 pub mod convert;
+pub mod encoding;
+pub mod json;
+pub mod redact;
+pub mod timezone;
 pub mod types;
 pub mod utils;
 pub mod value;
@@ -0,0 +1,135 @@
+use crate::core::types::Type;
+use chrono::{Days, LocalResult, NaiveDateTime, Offset, TimeDelta, TimeZone};
+pub use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Time zones the source's timestamps without an offset, such as MySQL
+/// DATETIME values, were written in. Without one such timestamps are
+/// taken as UTC.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceTimezones {
+    /// Zone of every such column, from the `timezone` setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Zones of single source columns from the timezones {} block,
+    /// overriding `default`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub columns: BTreeMap<String, String>,
+}
+
+impl SourceTimezones {
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.columns.is_empty()
+    }
+
+    /// Name of the zone the source column `column` was written in, if any.
+    pub fn zone_name(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(column))
+            .map(|(_, zone)| zone)
+            .or(self.default.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Destination type of the source column `column` given its converted
+    /// type: timestamps read in a zone are loaded in UTC, so they need a
+    /// type with a time zone.
+    pub fn column_type(&self, column: &str, data_type: Type) -> Type {
+        match data_type {
+            Type::Timestamp {
+                precision,
+                with_tz: false,
+            } if self.zone_name(column).is_some() => Type::Timestamp {
+                precision,
+                with_tz: true,
+            },
+            other => other,
+        }
+    }
+}
+
+/// Parses an IANA time zone name such as `Europe/Berlin`.
+pub fn parse_zone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+/// The UTC time of `local`, a wall-clock time in `tz`. A time that occurs
+/// twice when clocks go back is taken as the earlier instant; a time skipped
+/// when clocks go forward is read with the offset before the change, so it
+/// lands just after the gap.
+pub fn to_utc(local: NaiveDateTime, tz: Tz) -> NaiveDateTime {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt.naive_utc(),
+        LocalResult::None => {
+            let offset = local
+                .checked_sub_days(Days::new(1))
+                .and_then(|day_before| tz.from_local_datetime(&day_before).earliest())
+                .map(|dt| dt.offset().fix().local_minus_utc())
+                .unwrap_or(0);
+            local - TimeDelta::seconds(offset.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_to_utc() {
+        let berlin = parse_zone("Europe/Berlin").unwrap();
+        assert_eq!(
+            to_utc(at("2024-01-15 10:00:00"), berlin),
+            at("2024-01-15 09:00:00")
+        );
+        assert_eq!(
+            to_utc(at("2024-07-15 10:00:00"), berlin),
+            at("2024-07-15 08:00:00")
+        );
+        // Clocks go back at 03:00; 02:30 happens twice
+        assert_eq!(
+            to_utc(at("2024-10-27 02:30:00"), berlin),
+            at("2024-10-27 00:30:00")
+        );
+        // Clocks go forward at 02:00; 02:30 does not exist and reads as 03:30
+        assert_eq!(
+            to_utc(at("2024-03-31 02:30:00"), berlin),
+            at("2024-03-31 01:30:00")
+        );
+        assert!(parse_zone("Mars/Olympus").is_none());
+    }
+
+    #[test]
+    fn test_zone_name_and_column_type() {
+        let zones = SourceTimezones {
+            default: Some("Europe/Berlin".to_string()),
+            columns: BTreeMap::from([("Shipped_At".to_string(), "Asia/Tokyo".to_string())]),
+        };
+        assert_eq!(zones.zone_name("shipped_at"), Some("Asia/Tokyo"));
+        assert_eq!(zones.zone_name("created_at"), Some("Europe/Berlin"));
+        assert_eq!(SourceTimezones::default().zone_name("created_at"), None);
+
+        let naive = Type::Timestamp {
+            precision: Some(3),
+            with_tz: false,
+        };
+        assert_eq!(
+            zones.column_type("created_at", naive.clone()),
+            Type::Timestamp {
+                precision: Some(3),
+                with_tz: true,
+            }
+        );
+        assert_eq!(
+            SourceTimezones::default().column_type("created_at", naive.clone()),
+            naive
+        );
+        assert_eq!(zones.column_type("created_at", Type::Date), Type::Date);
+    }
+}
@@ -154,7 +154,10 @@ impl Value {
             Value::Json(v) => Some(v.to_string()),
             Value::Enum { value, .. } => Some(value.clone()),
             Value::Null => None,
-            _ => Some(format!("{:?}", self)),
+            _ => Some(match self.to_json() {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            }),
         }
     }
 
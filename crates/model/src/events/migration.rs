@@ -63,6 +63,15 @@ pub enum MigrationEvent {
         timestamp: DateTime<Utc>,
     },
 
+    /// Emitted when a run pauses at an approval gate
+    ApprovalRequired {
+        run_id: String,
+        item_id: String,
+        gate: String,
+        message: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
     // === Phase Transitions ===
     /// Emitted when snapshot phase begins
     SnapshotStarted {
@@ -242,6 +251,17 @@ pub enum MigrationEvent {
         timestamp: DateTime<Utc>,
     },
 
+    /// Emitted when the destination circuit breaker opens, half-opens or closes
+    CircuitBreakerChanged {
+        run_id: String,
+        item_id: String,
+        state: CircuitState,
+        consecutive_failures: u32,
+        /// Error that tripped the breaker, when it opened
+        error: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
     // === Coordination Events ===
     /// Emitted when all consumers are ready to start
     AllConsumersReady {
@@ -304,6 +324,34 @@ pub enum MigrationEvent {
         error: String,
         timestamp: DateTime<Utc>,
     },
+
+    // === Materialized View Events ===
+    /// Emitted when a materialized view refresh starts
+    ViewRefreshStarted {
+        run_id: String,
+        view: String,
+        /// 1-based position of the view in refresh order
+        position: usize,
+        total: usize,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Emitted when a materialized view refresh completes
+    ViewRefreshCompleted {
+        run_id: String,
+        view: String,
+        attempts: u32,
+        duration_ms: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Emitted when a materialized view refresh fails or is skipped
+    ViewRefreshFailed {
+        run_id: String,
+        view: String,
+        error: String,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 // === Supporting Enums ===
@@ -334,6 +382,16 @@ pub enum ProducerMode {
     Cdc,
 }
 
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
 impl fmt::Display for ProducerMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -353,6 +411,19 @@ pub enum ConnectionType {
     Destination,
 }
 
+/// Circuit breaker state for a pipeline's destination writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Writes flow normally
+    #[default]
+    Closed,
+    /// Writes are paused while the destination is probed
+    Open,
+    /// A probe succeeded; the next write decides whether to close or reopen
+    HalfOpen,
+}
+
 /// Type of validation performed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -467,6 +538,25 @@ impl fmt::Display for MigrationEvent {
                 item_id
             ),
 
+            MigrationEvent::ApprovalRequired {
+                run_id,
+                item_id,
+                gate,
+                message,
+                timestamp,
+            } => write!(
+                f,
+                "[{}] Waiting for approval of '{}'{} (run={}, item={})",
+                timestamp.format("%Y-%m-%d %H:%M:%S"),
+                gate,
+                message
+                    .as_ref()
+                    .map(|m| format!(": {m}"))
+                    .unwrap_or_default(),
+                run_id,
+                item_id
+            ),
+
             MigrationEvent::Progress {
                 run_id,
                 item_id,
@@ -808,6 +898,24 @@ impl fmt::Display for MigrationEvent {
                 item_id
             ),
 
+            MigrationEvent::CircuitBreakerChanged {
+                run_id,
+                item_id,
+                state,
+                consecutive_failures,
+                error,
+                timestamp,
+            } => write!(
+                f,
+                "[{}] Circuit breaker {} after {} failures{} (run={}, item={})",
+                timestamp.format("%Y-%m-%d %H:%M:%S"),
+                state,
+                consecutive_failures,
+                error.as_ref().map(|e| format!(": {e}")).unwrap_or_default(),
+                run_id,
+                item_id
+            ),
+
             MigrationEvent::AllConsumersReady {
                 run_id,
                 item_id,
@@ -919,6 +1027,52 @@ impl fmt::Display for MigrationEvent {
                 run_id,
                 item_id
             ),
+
+            MigrationEvent::ViewRefreshStarted {
+                run_id,
+                view,
+                position,
+                total,
+                timestamp,
+            } => write!(
+                f,
+                "[{}] Refreshing materialized view {} ({}/{}) (run={})",
+                timestamp.format("%Y-%m-%d %H:%M:%S"),
+                view,
+                position,
+                total,
+                run_id
+            ),
+
+            MigrationEvent::ViewRefreshCompleted {
+                run_id,
+                view,
+                attempts,
+                duration_ms,
+                timestamp,
+            } => write!(
+                f,
+                "[{}] Materialized view {} refreshed in {}ms after {} attempt(s) (run={})",
+                timestamp.format("%Y-%m-%d %H:%M:%S"),
+                view,
+                duration_ms,
+                attempts,
+                run_id
+            ),
+
+            MigrationEvent::ViewRefreshFailed {
+                run_id,
+                view,
+                error,
+                timestamp,
+            } => write!(
+                f,
+                "[{}] Materialized view {} refresh failed: {} (run={})",
+                timestamp.format("%Y-%m-%d %H:%M:%S"),
+                view,
+                error,
+                run_id
+            ),
         }
     }
 }
@@ -933,6 +1087,7 @@ impl MigrationEvent {
             MigrationEvent::Paused { .. } => "migration.paused",
             MigrationEvent::Resumed { .. } => "migration.resumed",
             MigrationEvent::Cancelled { .. } => "migration.cancelled",
+            MigrationEvent::ApprovalRequired { .. } => "migration.approval_required",
             MigrationEvent::SnapshotStarted { .. } => "snapshot.started",
             MigrationEvent::SnapshotCompleted { .. } => "snapshot.completed",
             MigrationEvent::CdcStarted { .. } => "cdc.started",
@@ -952,6 +1107,7 @@ impl MigrationEvent {
             MigrationEvent::BackpressureRelieved { .. } => "backpressure.relieved",
             MigrationEvent::ConnectionLost { .. } => "connection.lost",
             MigrationEvent::ConnectionRestored { .. } => "connection.restored",
+            MigrationEvent::CircuitBreakerChanged { .. } => "circuit_breaker.changed",
             MigrationEvent::AllConsumersReady { .. } => "consumers.ready",
             MigrationEvent::ConsumerLagging { .. } => "consumer.lagging",
             MigrationEvent::ValidationFailed { .. } => "validation.failed",
@@ -959,6 +1115,9 @@ impl MigrationEvent {
             MigrationEvent::SchemaCreationStarted { .. } => "schema.creation.started",
             MigrationEvent::SchemaCreationCompleted { .. } => "schema.creation.completed",
             MigrationEvent::SchemaCreationFailed { .. } => "schema.creation.failed",
+            MigrationEvent::ViewRefreshStarted { .. } => "view.refresh.started",
+            MigrationEvent::ViewRefreshCompleted { .. } => "view.refresh.completed",
+            MigrationEvent::ViewRefreshFailed { .. } => "view.refresh.failed",
         }
     }
 
@@ -971,6 +1130,7 @@ impl MigrationEvent {
             | MigrationEvent::Paused { run_id, .. }
             | MigrationEvent::Resumed { run_id, .. }
             | MigrationEvent::Cancelled { run_id, .. }
+            | MigrationEvent::ApprovalRequired { run_id, .. }
             | MigrationEvent::SnapshotStarted { run_id, .. }
             | MigrationEvent::SnapshotCompleted { run_id, .. }
             | MigrationEvent::CdcStarted { run_id, .. }
@@ -989,13 +1149,17 @@ impl MigrationEvent {
             | MigrationEvent::BackpressureRelieved { run_id, .. }
             | MigrationEvent::ConnectionLost { run_id, .. }
             | MigrationEvent::ConnectionRestored { run_id, .. }
+            | MigrationEvent::CircuitBreakerChanged { run_id, .. }
             | MigrationEvent::AllConsumersReady { run_id, .. }
             | MigrationEvent::ConsumerLagging { run_id, .. }
             | MigrationEvent::ValidationFailed { run_id, .. }
             | MigrationEvent::ValidationPassed { run_id, .. }
             | MigrationEvent::SchemaCreationStarted { run_id, .. }
             | MigrationEvent::SchemaCreationCompleted { run_id, .. }
-            | MigrationEvent::SchemaCreationFailed { run_id, .. } => Some(run_id),
+            | MigrationEvent::SchemaCreationFailed { run_id, .. }
+            | MigrationEvent::ViewRefreshStarted { run_id, .. }
+            | MigrationEvent::ViewRefreshCompleted { run_id, .. }
+            | MigrationEvent::ViewRefreshFailed { run_id, .. } => Some(run_id),
             MigrationEvent::ActorError { run_id, .. } => run_id.as_deref(),
         }
     }
@@ -1009,6 +1173,7 @@ impl MigrationEvent {
             | MigrationEvent::Paused { item_id, .. }
             | MigrationEvent::Resumed { item_id, .. }
             | MigrationEvent::Cancelled { item_id, .. }
+            | MigrationEvent::ApprovalRequired { item_id, .. }
             | MigrationEvent::SnapshotStarted { item_id, .. }
             | MigrationEvent::SnapshotCompleted { item_id, .. }
             | MigrationEvent::CdcStarted { item_id, .. }
@@ -1027,6 +1192,7 @@ impl MigrationEvent {
             | MigrationEvent::BackpressureRelieved { item_id, .. }
             | MigrationEvent::ConnectionLost { item_id, .. }
             | MigrationEvent::ConnectionRestored { item_id, .. }
+            | MigrationEvent::CircuitBreakerChanged { item_id, .. }
             | MigrationEvent::AllConsumersReady { item_id, .. }
             | MigrationEvent::ConsumerLagging { item_id, .. }
             | MigrationEvent::ValidationFailed { item_id, .. }
@@ -1035,6 +1201,9 @@ impl MigrationEvent {
             | MigrationEvent::SchemaCreationCompleted { item_id, .. }
             | MigrationEvent::SchemaCreationFailed { item_id, .. } => Some(item_id),
             MigrationEvent::ActorError { item_id, .. } => item_id.as_deref(),
+            MigrationEvent::ViewRefreshStarted { .. }
+            | MigrationEvent::ViewRefreshCompleted { .. }
+            | MigrationEvent::ViewRefreshFailed { .. } => None,
         }
     }
 
@@ -1047,6 +1216,7 @@ impl MigrationEvent {
             | MigrationEvent::Paused { timestamp, .. }
             | MigrationEvent::Resumed { timestamp, .. }
             | MigrationEvent::Cancelled { timestamp, .. }
+            | MigrationEvent::ApprovalRequired { timestamp, .. }
             | MigrationEvent::SnapshotStarted { timestamp, .. }
             | MigrationEvent::SnapshotCompleted { timestamp, .. }
             | MigrationEvent::CdcStarted { timestamp, .. }
@@ -1066,13 +1236,17 @@ impl MigrationEvent {
             | MigrationEvent::BackpressureRelieved { timestamp, .. }
             | MigrationEvent::ConnectionLost { timestamp, .. }
             | MigrationEvent::ConnectionRestored { timestamp, .. }
+            | MigrationEvent::CircuitBreakerChanged { timestamp, .. }
             | MigrationEvent::AllConsumersReady { timestamp, .. }
             | MigrationEvent::ConsumerLagging { timestamp, .. }
             | MigrationEvent::ValidationFailed { timestamp, .. }
             | MigrationEvent::ValidationPassed { timestamp, .. }
             | MigrationEvent::SchemaCreationStarted { timestamp, .. }
             | MigrationEvent::SchemaCreationCompleted { timestamp, .. }
-            | MigrationEvent::SchemaCreationFailed { timestamp, .. } => timestamp,
+            | MigrationEvent::SchemaCreationFailed { timestamp, .. }
+            | MigrationEvent::ViewRefreshStarted { timestamp, .. }
+            | MigrationEvent::ViewRefreshCompleted { timestamp, .. }
+            | MigrationEvent::ViewRefreshFailed { timestamp, .. } => timestamp,
         }
     }
 
@@ -1085,6 +1259,7 @@ impl MigrationEvent {
                 | MigrationEvent::ActorError { .. }
                 | MigrationEvent::ValidationFailed { .. }
                 | MigrationEvent::SchemaCreationFailed { .. }
+                | MigrationEvent::ViewRefreshFailed { .. }
                 | MigrationEvent::ConnectionLost { .. }
         )
     }
@@ -1099,6 +1274,7 @@ impl MigrationEvent {
                 | MigrationEvent::Paused { .. }
                 | MigrationEvent::Resumed { .. }
                 | MigrationEvent::Cancelled { .. }
+                | MigrationEvent::ApprovalRequired { .. }
         )
     }
 
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Point at which a run pauses until an operator approves it with
+/// `stratum approve <run> <gate>`. Compiled from an approve {} block in a
+/// pipeline or runbook step, and named after it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalGate {
+    pub name: String,
+    /// Shown to the operator and sent to webhooks while the run waits
+    pub message: Option<String>,
+}
@@ -0,0 +1,27 @@
+use crate::execution::{connection::Connection, pipeline::Pipeline};
+use serde::{Deserialize, Serialize};
+
+/// Pipelines whose sources are pinned to one point in time when the run
+/// starts, so related tables are copied consistently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Barrier {
+    pub name: String,
+    pub pipelines: Vec<String>,
+}
+
+impl Barrier {
+    /// Source connections of the barrier's pipelines, each listed once
+    pub fn sources<'a>(&self, pipelines: &'a [Pipeline]) -> Vec<&'a Connection> {
+        let mut sources: Vec<&Connection> = Vec::new();
+        for pipeline in pipelines
+            .iter()
+            .filter(|p| self.pipelines.contains(&p.name))
+        {
+            let connection = &pipeline.source.connection;
+            if sources.iter().all(|c| c.name != connection.name) {
+                sources.push(connection);
+            }
+        }
+        sources
+    }
+}
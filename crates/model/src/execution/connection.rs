@@ -2,6 +2,21 @@ use crate::{core::value::Value, execution::properties::Properties};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Nested block of session settings applied to every session the
+/// connection opens
+pub const SESSION_BLOCK: &str = "session";
+
+/// Nested block of a file connection saying how its CSV files write
+/// numbers and dates, and which text encoding they use
+pub const CSV_BLOCK: &str = "csv";
+
+/// Nested block of a database connection saying how it uses TLS
+pub const TLS_BLOCK: &str = "tls";
+
+/// Attribute capping how many pipelines reading or writing through the
+/// connection run at once
+pub const MAX_CONCURRENT_PIPELINES: &str = "max_concurrent_pipelines";
+
 /// Connection block compiled to runtime configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
@@ -10,3 +25,21 @@ pub struct Connection {
     pub properties: Properties,
     pub nested_configs: HashMap<String, HashMap<String, Value>>,
 }
+
+impl Connection {
+    /// Session settings from the connection's `session` block, by name
+    pub fn session_settings(&self) -> Vec<(String, Value)> {
+        let mut settings: Vec<_> = self
+            .nested_configs
+            .get(SESSION_BLOCK)
+            .map(|block| block.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        settings.sort_by(|a, b| a.0.cmp(&b.0));
+        settings
+    }
+
+    /// Most pipelines that may use the connection at once, when capped
+    pub fn max_concurrent_pipelines(&self) -> Option<usize> {
+        self.properties.get_usize(MAX_CONCURRENT_PIPELINES)
+    }
+}
@@ -1,6 +1,22 @@
 use crate::core::value::Value;
 use serde::{Deserialize, Serialize};
 
+/// Function a `run.id` reference compiles to; bound to the run's id when
+/// the pipeline runs
+pub const FN_RUN_ID: &str = "run_id";
+/// Function a `run.started_at` reference compiles to
+pub const FN_RUN_STARTED_AT: &str = "run_started_at";
+/// Function bound to the row's number by computed columns when the
+/// pipeline runs
+pub const FN_ROW_NUMBER: &str = "row_number";
+/// Function bound to the key crosswalk of the table it names by computed
+/// columns when the pipeline runs
+pub const FN_LOOKUP_KEY: &str = "lookup_key";
+/// Select function that splits a source column into one row per element
+pub const FN_UNNEST: &str = "unnest";
+/// Alias of `unnest`
+pub const FN_EXPLODE: &str = "explode";
+
 /// Compiled expression ready for runtime evaluation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CompiledExpression {
@@ -30,6 +46,82 @@ pub enum CompiledExpression {
     Grouped(Box<CompiledExpression>),
 }
 
+impl CompiledExpression {
+    /// Tables named by the expression's `lookup_key("table", key)` calls,
+    /// lowercased.
+    pub fn lookup_key_tables(&self) -> Vec<String> {
+        let mut tables = Vec::new();
+        let Ok(()) = self.clone().try_rewrite(&mut |e| {
+            if let CompiledExpression::FunctionCall { name, args } = e
+                && name.eq_ignore_ascii_case(FN_LOOKUP_KEY)
+                && let Some(CompiledExpression::Literal(Value::String(table))) = args.first()
+            {
+                tables.push(table.to_ascii_lowercase());
+            }
+            Ok::<_, std::convert::Infallible>(None)
+        });
+        tables
+    }
+
+    /// Whether the expression calls `function` anywhere.
+    pub fn calls(&self, function: &str) -> bool {
+        self.clone()
+            .try_rewrite(&mut |e| match e {
+                CompiledExpression::FunctionCall { name, .. }
+                    if name.eq_ignore_ascii_case(function) =>
+                {
+                    Err(())
+                }
+                _ => Ok(None),
+            })
+            .is_err()
+    }
+
+    /// Walks the expression top-down, replacing every node for which `f`
+    /// returns a new expression. Replacements are not walked again.
+    pub fn try_rewrite<E>(
+        &mut self,
+        f: &mut impl FnMut(&CompiledExpression) -> Result<Option<CompiledExpression>, E>,
+    ) -> Result<(), E> {
+        if let Some(replacement) = f(self)? {
+            *self = replacement;
+            return Ok(());
+        }
+
+        match self {
+            CompiledExpression::Literal(_)
+            | CompiledExpression::Identifier(_)
+            | CompiledExpression::DotPath(_) => {}
+            CompiledExpression::Binary { left, right, .. } => {
+                left.try_rewrite(f)?;
+                right.try_rewrite(f)?;
+            }
+            CompiledExpression::Unary { operand, .. } => operand.try_rewrite(f)?,
+            CompiledExpression::FunctionCall { args, .. } | CompiledExpression::Array(args) => {
+                for arg in args {
+                    arg.try_rewrite(f)?;
+                }
+            }
+            CompiledExpression::When {
+                branches,
+                else_expr,
+            } => {
+                for branch in branches {
+                    branch.condition.try_rewrite(f)?;
+                    branch.value.try_rewrite(f)?;
+                }
+                if let Some(else_expr) = else_expr {
+                    else_expr.try_rewrite(f)?;
+                }
+            }
+            CompiledExpression::IsNull(inner)
+            | CompiledExpression::IsNotNull(inner)
+            | CompiledExpression::Grouped(inner) => inner.try_rewrite(f)?,
+        }
+        Ok(())
+    }
+}
+
 /// Binary operators matching AST BinaryOperator
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum BinaryOp {
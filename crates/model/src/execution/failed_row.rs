@@ -1,8 +1,10 @@
 use crate::{
     core::{
+        json::plain_map,
         types::Type,
         value::{FieldValue, Value},
     },
+    pagination::cursor::Cursor,
     records::{OpType, Record},
 };
 use chrono::{DateTime, Utc};
@@ -15,6 +17,7 @@ pub struct FailedRow {
     pub id: String,
     pub pipeline_name: String,
     pub stage: ProcessingStage,
+    #[serde(with = "plain_map")]
     pub original_data: HashMap<String, Value>,
     pub error: FailureError,
     pub metadata: FailureMetadata,
@@ -55,8 +58,11 @@ pub struct FailureError {
 pub struct FailureMetadata {
     pub run_id: String,
     pub batch_id: Option<String>,
+    /// Source cursor the batch was read from
+    pub batch_offset: Option<Cursor>,
     pub row_index: Option<usize>, // row index within the batch
     pub source: Option<String>,
+    #[serde(with = "plain_map")]
     pub custom: HashMap<String, Value>,
 }
 
@@ -83,6 +89,7 @@ impl FailedRow {
             metadata: FailureMetadata {
                 run_id: uuid::Uuid::new_v4().to_string(),
                 batch_id: None,
+                batch_offset: None,
                 row_index: None,
                 source: None,
                 custom: HashMap::new(),
@@ -113,6 +120,7 @@ impl FailedRow {
             metadata: FailureMetadata {
                 run_id: uuid::Uuid::new_v4().to_string(),
                 batch_id: None,
+                batch_offset: None,
                 row_index: None,
                 source: None,
                 custom: HashMap::new(),
@@ -155,6 +163,11 @@ impl FailedRow {
         self
     }
 
+    pub fn with_batch_offset(mut self, offset: Cursor) -> Self {
+        self.metadata.batch_offset = Some(offset);
+        self
+    }
+
     pub fn with_table(mut self, table_name: String) -> Self {
         self.table_name = Some(table_name);
         self
@@ -224,6 +237,12 @@ impl FailedRow {
             map.insert("batch_id".to_string(), Value::String(batch_id.clone()));
         }
 
+        if let Some(offset) = &self.metadata.batch_offset
+            && let Ok(json) = serde_json::to_string(offset)
+        {
+            map.insert("batch_offset".to_string(), Value::String(json));
+        }
+
         if let Some(row_index) = self.metadata.row_index {
             map.insert("row_index".to_string(), Value::UInt(row_index as u64));
         }
@@ -233,21 +252,31 @@ impl FailedRow {
         }
 
         // Original data as JSON string for easy storage
-        if let Ok(json) = serde_json::to_string(&self.original_data) {
-            map.insert("original_data".to_string(), Value::String(json));
-        }
+        map.insert(
+            "original_data".to_string(),
+            Value::String(plain_json(&self.original_data)),
+        );
 
         // Custom metadata as JSON string
-        if !self.metadata.custom.is_empty()
-            && let Ok(json) = serde_json::to_string(&self.metadata.custom)
-        {
-            map.insert("custom_metadata".to_string(), Value::String(json));
+        if !self.metadata.custom.is_empty() {
+            map.insert(
+                "custom_metadata".to_string(),
+                Value::String(plain_json(&self.metadata.custom)),
+            );
         }
 
         map
     }
 }
 
+fn plain_json(values: &HashMap<String, Value>) -> String {
+    let object = values
+        .iter()
+        .map(|(k, v)| (k.clone(), v.to_json()))
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::Value::Object(object).to_string()
+}
+
 impl std::fmt::Display for ProcessingStage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -387,7 +416,13 @@ mod tests {
             storage_map.get("table_name"),
             Some(&Value::String("orders".to_string()))
         );
-        assert!(storage_map.contains_key("original_data"));
+        let Some(Value::String(original)) = storage_map.get("original_data") else {
+            panic!("original_data should be a JSON string");
+        };
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(original).unwrap(),
+            serde_json::json!({ "id": 1, "name": "Test" })
+        );
         assert!(storage_map.contains_key("failed_at"));
     }
 
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A column of a source table, as written in the config (`table.column`).
+/// Join aliases are resolved to the table they name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ColumnRef {
+    pub table: String,
+    pub column: String,
+}
+
+impl ColumnRef {
+    pub fn new(table: impl Into<String>, column: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            column: column.into(),
+        }
+    }
+
+    /// Parses `table.column`; identifiers are compared case-insensitively.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (table, column) = s.split_once('.')?;
+        if table.is_empty() || column.is_empty() {
+            return None;
+        }
+        Some(Self::new(table, column))
+    }
+
+    pub fn matches(&self, other: &ColumnRef) -> bool {
+        self.table.eq_ignore_ascii_case(&other.table)
+            && self.column.eq_ignore_ascii_case(&other.column)
+    }
+}
+
+impl fmt::Display for ColumnRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.table, self.column)
+    }
+}
+
+/// Where one destination column's values come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLineage {
+    pub destination: ColumnRef,
+    /// Source columns the value is computed from; empty for constants
+    pub sources: Vec<ColumnRef>,
+    /// Text of the expression, or `None` for a plain copy or rename
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+}
+
+/// Column-level lineage of one pipeline, derived from its select blocks,
+/// plugin outputs and `where`/join conditions when the plan is built.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineLineage {
+    pub pipeline: String,
+    pub source_table: String,
+    /// `None` for validate-only pipelines, which write nothing
+    pub destination_table: Option<String>,
+    pub columns: Vec<ColumnLineage>,
+    /// Source columns without a mapping are copied under their own name
+    /// (`copy_columns = "ALL"`)
+    pub copies_unmapped: bool,
+    /// Columns read by `where` filters and join conditions
+    pub filter_columns: Vec<ColumnRef>,
+}
+
+/// How a pipeline uses a given source column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnUse<'a> {
+    /// Feeds a mapped destination column
+    Mapped(&'a ColumnLineage),
+    /// Copied to the destination column of the same name
+    Copied(ColumnRef),
+    /// Read by a filter or join condition only
+    Filter,
+}
+
+impl PipelineLineage {
+    /// Every way this pipeline reads `source`, for impact analysis.
+    pub fn uses(&self, source: &ColumnRef) -> Vec<ColumnUse<'_>> {
+        let mut uses: Vec<ColumnUse<'_>> = self
+            .columns
+            .iter()
+            .filter(|c| c.sources.iter().any(|s| s.matches(source)))
+            .map(ColumnUse::Mapped)
+            .collect();
+
+        // An unmapped column of the main table is copied unless a mapping
+        // already writes a destination column of that name.
+        if self.copies_unmapped
+            && source.table.eq_ignore_ascii_case(&self.source_table)
+            && let Some(destination) = &self.destination_table
+            && !self
+                .columns
+                .iter()
+                .any(|c| c.destination.column.eq_ignore_ascii_case(&source.column))
+        {
+            uses.push(ColumnUse::Copied(ColumnRef::new(
+                destination.clone(),
+                source.column.clone(),
+            )));
+        }
+
+        if uses.is_empty() && self.filter_columns.iter().any(|c| c.matches(source)) {
+            uses.push(ColumnUse::Filter);
+        }
+        uses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uses_reports_mapped_copied_and_filter_reads() {
+        let lineage = PipelineLineage {
+            pipeline: "orders".to_string(),
+            source_table: "orders".to_string(),
+            destination_table: Some("orders_copy".to_string()),
+            columns: vec![ColumnLineage {
+                destination: ColumnRef::new("orders_copy", "email"),
+                sources: vec![ColumnRef::new("users", "email")],
+                expression: Some("lower(users.email)".to_string()),
+            }],
+            copies_unmapped: true,
+            filter_columns: vec![ColumnRef::new("users", "active")],
+        };
+
+        let email = ColumnRef::parse("Users.Email").unwrap();
+        assert!(matches!(lineage.uses(&email)[..], [ColumnUse::Mapped(_)]));
+        assert_eq!(
+            lineage.uses(&ColumnRef::new("orders", "total")),
+            vec![ColumnUse::Copied(ColumnRef::new("orders_copy", "total"))]
+        );
+        // A mapping named `email` shadows the copy of orders.email
+        assert!(lineage.uses(&ColumnRef::new("orders", "email")).is_empty());
+        assert_eq!(
+            lineage.uses(&ColumnRef::new("users", "active")),
+            vec![ColumnUse::Filter]
+        );
+        assert_eq!(ColumnRef::parse("email"), None);
+    }
+}
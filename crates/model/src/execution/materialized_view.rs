@@ -0,0 +1,29 @@
+use crate::execution::connection::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Default number of attempts for a refresh
+pub const DEFAULT_REFRESH_ATTEMPTS: u32 = 3;
+
+/// Destination materialized view refreshed once the data it reads has loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterializedView {
+    pub name: String,
+    pub connection: Connection,
+    /// Pipelines that must complete before the refresh
+    pub pipelines: Vec<String>,
+    /// Views that must be refreshed first
+    pub views: Vec<String>,
+    /// Refresh without locking out readers (needs a unique index on the view)
+    pub concurrently: bool,
+    pub max_attempts: u32,
+}
+
+impl MaterializedView {
+    pub fn refresh_sql(&self) -> String {
+        if self.concurrently {
+            format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", self.name)
+        } else {
+            format!("REFRESH MATERIALIZED VIEW {}", self.name)
+        }
+    }
+}
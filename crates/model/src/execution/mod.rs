@@ -1,3 +1,5 @@
+pub mod approval;
+pub mod barrier;
 pub mod connection;
 pub mod define;
 pub mod errors;
@@ -6,8 +8,16 @@ pub mod expr;
 pub mod failed_row;
 pub mod flags;
 pub mod item_id;
+pub mod lineage;
+pub mod log_config;
+pub mod materialized_view;
+pub mod notify_config;
+pub mod partition;
 pub mod pipeline;
 pub mod plugin;
 pub mod properties;
 pub mod references;
 pub mod row_count;
+pub mod runbook;
+pub mod state_config;
+pub mod verify;
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Default timeout (in seconds) for a webhook request
+pub const DEFAULT_NOTIFY_TIMEOUT_SECS: u64 = 10;
+
+/// Default time (in seconds) a running pipeline may go without progress
+/// before a `stalled` event is sent
+pub const DEFAULT_STALL_AFTER_SECS: u64 = 30 * 60;
+
+/// Pipeline lifecycle events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    Started,
+    Completed,
+    Failed,
+    /// Rows rejected by a pipeline reached the block's `validation_threshold`
+    ValidationThreshold,
+    /// The run paused at an approval gate
+    ApprovalRequired,
+    /// A running pipeline made no progress for the block's `stall_after`
+    Stalled,
+}
+
+impl NotifyEvent {
+    pub const ALL: [NotifyEvent; 6] = [
+        NotifyEvent::Started,
+        NotifyEvent::Completed,
+        NotifyEvent::Failed,
+        NotifyEvent::ValidationThreshold,
+        NotifyEvent::ApprovalRequired,
+        NotifyEvent::Stalled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotifyEvent::Started => "started",
+            NotifyEvent::Completed => "completed",
+            NotifyEvent::Failed => "failed",
+            NotifyEvent::ValidationThreshold => "validation_threshold",
+            NotifyEvent::ApprovalRequired => "approval_required",
+            NotifyEvent::Stalled => "stalled",
+        }
+    }
+}
+
+impl fmt::Display for NotifyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NotifyEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|e| e.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown notify event '{s}'"))
+    }
+}
+
+/// What a notify {} block compiles to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotifyTarget {
+    Webhook(NotifyConfig),
+    Email(EmailConfig),
+    Incident(IncidentConfig),
+}
+
+/// Webhook that receives a JSON POST for each subscribed pipeline event.
+/// Compiled from a notify {} block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    pub url: String,
+
+    /// Events to send; every event but `stalled` when the block doesn't
+    /// list any
+    pub events: Vec<NotifyEvent>,
+
+    /// Rejected rows (skipped or failed) in one pipeline at which a
+    /// `validation_threshold` event is sent, once per pipeline
+    pub validation_threshold: Option<u64>,
+
+    /// Seconds without progress after which a `stalled` event is sent
+    pub stall_after: u64,
+
+    /// Request timeout in seconds
+    pub timeout: u64,
+}
+
+impl NotifyConfig {
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// Mailing list that receives the run summary, with an HTML report
+/// attached, when a run completes or fails. Compiled from a notify {} block
+/// with a `to` list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// `smtp://` or `smtps://` server url, with credentials when the server
+    /// needs them
+    pub url: String,
+
+    pub from: String,
+
+    pub to: Vec<String>,
+
+    /// `completed` and/or `failed`; both when the block doesn't list any
+    pub events: Vec<NotifyEvent>,
+
+    /// SMTP timeout in seconds
+    pub timeout: u64,
+}
+
+impl EmailConfig {
+    /// Run outcomes an email can be sent for
+    pub const EVENTS: [NotifyEvent; 2] = [NotifyEvent::Completed, NotifyEvent::Failed];
+
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// Incident management service paged by a notify {} block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentService {
+    PagerDuty,
+    Opsgenie,
+}
+
+impl IncidentService {
+    /// Attribute of the notify block holding the service's key
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentService::PagerDuty => "pagerduty",
+            IncidentService::Opsgenie => "opsgenie",
+        }
+    }
+
+    /// Endpoint incidents are created at unless the block sets `url`
+    pub fn default_url(&self) -> &'static str {
+        match self {
+            IncidentService::PagerDuty => "https://events.pagerduty.com/v2/enqueue",
+            IncidentService::Opsgenie => "https://api.opsgenie.com/v2/alerts",
+        }
+    }
+}
+
+impl fmt::Display for IncidentService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// PagerDuty service or Opsgenie team an incident is opened for when a
+/// pipeline fails or stalls. Compiled from a notify {} block with a
+/// `pagerduty` or `opsgenie` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncidentConfig {
+    pub service: IncidentService,
+
+    /// PagerDuty integration key or Opsgenie API key
+    pub key: String,
+
+    /// Endpoint incidents are created at, e.g. Opsgenie's EU instance
+    pub url: String,
+
+    /// Subset of [`IncidentConfig::EVENTS`]; `failed` and `stalled` when
+    /// the block doesn't list any
+    pub events: Vec<NotifyEvent>,
+
+    /// Rejected rows in one pipeline at which an incident is opened, when
+    /// `validation_threshold` is listed in `events`
+    pub validation_threshold: Option<u64>,
+
+    /// Seconds without progress after which a `stalled` incident is opened
+    pub stall_after: u64,
+
+    /// Request timeout in seconds
+    pub timeout: u64,
+}
+
+impl IncidentConfig {
+    /// Events worth waking someone up for
+    pub const EVENTS: [NotifyEvent; 3] = [
+        NotifyEvent::Failed,
+        NotifyEvent::Stalled,
+        NotifyEvent::ValidationThreshold,
+    ];
+
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Declarative partitioning of a created destination table, compiled from
+/// the partition_by {} block of a to {} block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TablePartitioning {
+    /// Destination column rows are partitioned on
+    pub column: String,
+    pub scheme: PartitionScheme,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartitionScheme {
+    /// One partition per interval in `[start, end)`, plus a default
+    /// partition for rows outside it
+    Range {
+        interval: PartitionInterval,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// `partitions` partitions, rows spread by the hash of the column
+    Hash { partitions: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartitionInterval {
+    Day,
+    Month,
+    Year,
+}
+
+/// Bounds of one child partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionBound {
+    /// Rows with `from <= column < to`
+    Range { from: NaiveDate, to: NaiveDate },
+    /// Rows whose hash modulo `modulus` is `remainder`
+    Hash { modulus: u32, remainder: u32 },
+    /// Rows no other partition takes
+    Default,
+}
+
+/// A child partition to create, named after its parent table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionDef {
+    pub name: String,
+    pub bound: PartitionBound,
+}
+
+impl PartitionInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+
+    /// Start of the interval following the one starting at `date`.
+    fn next(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date.succ_opt().unwrap_or(NaiveDate::MAX),
+            Self::Month => date
+                .checked_add_months(Months::new(1))
+                .unwrap_or(NaiveDate::MAX),
+            Self::Year => date
+                .checked_add_months(Months::new(12))
+                .unwrap_or(NaiveDate::MAX),
+        }
+    }
+
+    /// Start of the interval `date` falls in.
+    fn floor(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Month => date.with_day(1).unwrap_or(date),
+            Self::Year => date.with_ordinal(1).unwrap_or(date),
+        }
+    }
+
+    /// Partition name suffix of the interval starting at `date`.
+    fn suffix(&self, date: NaiveDate) -> String {
+        match self {
+            Self::Day => date.format("%Y_%m_%d").to_string(),
+            Self::Month => date.format("%Y_%m").to_string(),
+            Self::Year => date.format("%Y").to_string(),
+        }
+    }
+}
+
+impl FromStr for PartitionInterval {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "month" => Ok(Self::Month),
+            "year" => Ok(Self::Year),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TablePartitioning {
+    /// Parses a range bound written as `YYYY-MM-DD`.
+    pub fn parse_bound(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+    }
+
+    /// Child partitions of `table`, in bound order.
+    pub fn partitions(&self, table: &str) -> Vec<PartitionDef> {
+        match &self.scheme {
+            PartitionScheme::Range {
+                interval,
+                start,
+                end,
+            } => {
+                let mut parts = Vec::new();
+                let mut from = interval.floor(*start);
+                while from < *end {
+                    let to = interval.next(from);
+                    parts.push(PartitionDef {
+                        name: format!("{table}_p{}", interval.suffix(from)),
+                        bound: PartitionBound::Range { from, to },
+                    });
+                    if to == NaiveDate::MAX {
+                        break;
+                    }
+                    from = to;
+                }
+                parts.push(PartitionDef {
+                    name: format!("{table}_default"),
+                    bound: PartitionBound::Default,
+                });
+                parts
+            }
+            PartitionScheme::Hash { partitions } => (0..*partitions)
+                .map(|remainder| PartitionDef {
+                    name: format!("{table}_p{remainder}"),
+                    bound: PartitionBound::Hash {
+                        modulus: *partitions,
+                        remainder,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_monthly_range_partitions() {
+        let partitioning = TablePartitioning {
+            column: "created_at".to_string(),
+            scheme: PartitionScheme::Range {
+                interval: PartitionInterval::Month,
+                start: date("2024-11-15"),
+                end: date("2025-02-01"),
+            },
+        };
+
+        let parts = partitioning.partitions("events");
+        let names: Vec<&str> = parts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "events_p2024_11",
+                "events_p2024_12",
+                "events_p2025_01",
+                "events_default"
+            ]
+        );
+        assert_eq!(
+            parts[0].bound,
+            PartitionBound::Range {
+                from: date("2024-11-01"),
+                to: date("2024-12-01"),
+            }
+        );
+        assert_eq!(parts[3].bound, PartitionBound::Default);
+    }
+
+    #[test]
+    fn test_hash_partitions() {
+        let partitioning = TablePartitioning {
+            column: "customer_id".to_string(),
+            scheme: PartitionScheme::Hash { partitions: 4 },
+        };
+
+        let parts = partitioning.partitions("orders");
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[3].name, "orders_p3");
+        assert_eq!(
+            parts[3].bound,
+            PartitionBound::Hash {
+                modulus: 4,
+                remainder: 3
+            }
+        );
+    }
+}
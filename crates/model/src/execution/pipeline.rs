@@ -1,9 +1,26 @@
 use crate::{
-    core::value::Value,
-    execution::{connection::Connection, expr::CompiledExpression, references::GraphReferences},
+    core::{
+        timezone::SourceTimezones,
+        value::{FieldValue, Value},
+    },
+    execution::{
+        approval::ApprovalGate,
+        connection::Connection,
+        expr::{CompiledExpression, FN_RUN_ID, FN_RUN_STARTED_AT},
+        log_config::PipelineLog,
+        partition::TablePartitioning,
+        references::GraphReferences,
+        verify::VerifyConfig,
+    },
+    pagination::cursor::QualCol,
+    records::{OpType, Record},
 };
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+};
 
 /// Pipeline block compiled to execution instructions
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,16 +28,193 @@ pub struct Pipeline {
     pub name: String,
     pub description: Option<String>,
     pub dependencies: Vec<String>,
+    /// Dependencies added because the pipeline reads a table another
+    /// pipeline writes; also listed in `dependencies`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inferred_dependencies: Vec<String>,
+    /// Pipelines that write one of this pipeline's destination tables and
+    /// may run at the same time. Their schema changes run one at a time, and
+    /// the DDL that follows their loads waits until every pipeline loaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub concurrent_writers: Vec<String>,
     pub source: DataSource,
     pub destination: DataDestination,
     pub transformations: Vec<Transformation>,
     /// Field mappings for referenced (cascade) tables keyed by source table name.
     pub named_transformations: HashMap<String, Vec<Transformation>>,
+    /// Further destination tables of the `to` blocks after the first, each
+    /// written a copy of every source row
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sinks: Vec<Sink>,
     pub validations: Vec<ValidationRule>,
     pub lifecycle: Option<LifecycleHooks>,
     pub error_handling: Option<ErrorHandling>,
     pub settings: HashMap<String, Value>,
+    /// Environment variables the pipeline needs, checked when the plan is
+    /// built
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires_env: Vec<String>,
+    /// Variables seen only by this pipeline's `env()` calls, shadowing the
+    /// process environment
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
     pub plugin_transforms: Vec<PluginTransformCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<VerifyConfig>,
+    /// Pause before the pipeline starts until it is approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_gate: Option<ApprovalGate>,
+    /// Reference rows inserted into the destination before the pipeline loads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seeds: Vec<SeedTable>,
+    /// Script run on every row after its field mappings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<RowScript>,
+    /// Source rows grouped into one row per group before they are mapped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregate: Option<Aggregation>,
+    /// Source rows reshaped between key-value and wide form before they are
+    /// mapped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pivot: Option<Pivot>,
+    /// Source column split so each source row becomes one row per element
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unnest: Option<Unnest>,
+    /// The pipeline has no `to` block: it only runs its validate rules
+    /// against the source data and writes nothing. `destination` then
+    /// mirrors the source and is never written to.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub validate_only: bool,
+    /// Where the pipeline's logs go. Left out of the plan hash, so that
+    /// changing it does not start a new run.
+    #[serde(skip)]
+    pub log: Option<PipelineLog>,
+    /// Id of the run the pipeline is bound to by `bind_run`
+    #[serde(skip)]
+    pub run_id: Option<String>,
+}
+
+impl Pipeline {
+    /// Every compiled expression of the pipeline: source filters, field
+    /// mappings and assert checks.
+    pub fn expressions_mut(&mut self) -> Vec<&mut CompiledExpression> {
+        let mut exprs: Vec<&mut CompiledExpression> = Vec::new();
+        exprs.extend(self.source.filters.iter_mut().map(|f| &mut f.condition));
+        exprs.extend(self.transformations.iter_mut().map(|t| &mut t.expression));
+        exprs.extend(
+            self.named_transformations
+                .values_mut()
+                .flatten()
+                .map(|t| &mut t.expression),
+        );
+        exprs.extend(
+            self.sinks
+                .iter_mut()
+                .flat_map(|s| &mut s.transformations)
+                .map(|t| &mut t.expression),
+        );
+        exprs.extend(
+            self.validations
+                .iter_mut()
+                .filter_map(|v| match &mut v.kind {
+                    ValidationKind::Assert { check } => Some(check),
+                    ValidationKind::WasmFilter { .. } => None,
+                }),
+        );
+        exprs
+    }
+
+    /// Tables whose key crosswalks the pipeline's selects look up with
+    /// `lookup_key`, lowercased and without duplicates.
+    pub fn lookup_key_tables(&self) -> Vec<String> {
+        let mut tables: Vec<String> = self
+            .transformations
+            .iter()
+            .chain(self.named_transformations.values().flatten())
+            .chain(self.sinks.iter().flat_map(|s| &s.transformations))
+            .flat_map(|t| t.expression.lookup_key_tables())
+            .collect();
+        tables.sort();
+        tables.dedup();
+        tables
+    }
+
+    /// Whether any of the pipeline's selects calls `function`.
+    pub fn calls(&self, function: &str) -> bool {
+        self.transformations
+            .iter()
+            .chain(self.named_transformations.values().flatten())
+            .chain(self.sinks.iter().flat_map(|s| &s.transformations))
+            .any(|t| t.expression.calls(function))
+    }
+
+    /// The pipeline as it writes `sink`: the sink's table and field mappings
+    /// in place of the first destination's. Validations, the row script and
+    /// plugin outputs belong to the first destination and are left out.
+    pub fn sink_pipeline(&self, sink: &Sink) -> Pipeline {
+        Pipeline {
+            destination: DataDestination {
+                table: sink.table.clone(),
+                table_map: HashMap::new(),
+                ..self.destination.clone()
+            },
+            transformations: sink.transformations.clone(),
+            named_transformations: HashMap::new(),
+            sinks: Vec::new(),
+            validations: Vec::new(),
+            plugin_transforms: Vec::new(),
+            script: None,
+            ..self.clone()
+        }
+    }
+
+    /// Text of the comment the pipeline's source queries start with, so a
+    /// DBA can tell which run and pipeline issued them.
+    pub fn query_tag(&self) -> String {
+        match &self.run_id {
+            Some(run_id) => format!("stratum run={run_id} pipeline={}", self.name),
+            None => format!("stratum pipeline={}", self.name),
+        }
+    }
+
+    /// Fills in the `run.id` and `run.started_at` references of the
+    /// pipeline's expressions and `${run.*}` placeholders of its hooks.
+    pub fn bind_run(&mut self, run_id: &str, started_at: DateTime<Utc>) {
+        self.run_id = Some(run_id.to_string());
+        let started_at_value = Value::Timestamp {
+            value: started_at.naive_utc(),
+            offset_secs: Some(0),
+        };
+        for expr in self.expressions_mut() {
+            let Ok(()) = expr.try_rewrite(&mut |e| {
+                Ok::<_, Infallible>(match e {
+                    CompiledExpression::FunctionCall { name, args } if args.is_empty() => {
+                        match name.as_str() {
+                            FN_RUN_ID => Some(CompiledExpression::Literal(Value::String(
+                                run_id.to_string(),
+                            ))),
+                            FN_RUN_STARTED_AT => {
+                                Some(CompiledExpression::Literal(started_at_value.clone()))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+            });
+        }
+
+        let started_at = started_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        if let Some(lifecycle) = &mut self.lifecycle {
+            let Ok(()) = lifecycle.try_substitute(&mut |name| {
+                Ok::<_, Infallible>(match name {
+                    "run.id" => Some(run_id.to_string()),
+                    "run.started_at" => Some(started_at.clone()),
+                    _ => None,
+                })
+            });
+        }
+    }
 }
 
 /// From block - data source configuration
@@ -28,10 +222,41 @@ pub struct Pipeline {
 pub struct DataSource {
     pub connection: Connection,
     pub table: String,
+    /// Tables of the `from` blocks after the first, read after `table` in
+    /// this order; they have its columns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub union: Vec<String>,
+    /// Column added to every row, holding the name of the table it was
+    /// read from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_column: Option<String>,
     pub filters: Vec<Filter>,
     pub joins: Vec<Join>,
     pub pagination: Option<Pagination>,
     pub graph_references: Option<GraphReferences>,
+    /// Time zones of source timestamps that carry no offset
+    #[serde(default, skip_serializing_if = "SourceTimezones::is_empty")]
+    pub timezones: SourceTimezones,
+    /// Tables a `migrate_schema` block copies; `table` is then `*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_scope: Option<SchemaScope>,
+}
+
+/// Table patterns of a `migrate_schema` block: a table is copied when it
+/// matches an `include` pattern and no `exclude` pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaScope {
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl DataSource {
+    /// Whether `table` is a pattern such as `audit_*`, naming every source
+    /// table it matches rather than one table
+    pub fn is_table_pattern(&self) -> bool {
+        self.table.contains('*')
+    }
 }
 
 /// To block - data destination configuration
@@ -41,6 +266,29 @@ pub struct DataDestination {
     pub table: String,
     pub mode: WriteMode,
     pub table_map: HashMap<String, String>,
+    /// Partitioning of the destination table when the migration creates it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_by: Option<TablePartitioning>,
+    /// Destination column types from the types {} block, overriding the
+    /// inferred type of each named column when the migration creates it
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_types: BTreeMap<String, String>,
+    /// Destination role of each source role from the roles {} block, for the
+    /// grants `migrate_grants` copies; other roles keep their names
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub role_map: BTreeMap<String, String>,
+    /// Source columns the rows are sorted by before they are written, for
+    /// destinations that benefit from clustered insertion order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order_by: Vec<QualCol>,
+}
+
+/// A `to` block after the first: another table on the destination
+/// connection, with its own select
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sink {
+    pub table: String,
+    pub transformations: Vec<Transformation>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +321,7 @@ pub struct Pagination {
     pub column: String,
     pub tiebreaker: Option<String>,
     pub timezone: Option<String>,
+    pub nulls: Option<String>,
 }
 
 /// Select block field mapping
@@ -123,6 +372,171 @@ pub enum ValidationAction {
 pub struct LifecycleHooks {
     pub before: Vec<String>,
     pub after: Vec<String>,
+    #[serde(default)]
+    pub on_batch: Option<BatchHook>,
+}
+
+impl LifecycleHooks {
+    /// Replaces `${name}` placeholders in every hook statement with the
+    /// text `lookup` returns for `name`. Placeholders it returns `None` for
+    /// are left as written.
+    pub fn try_substitute<E>(
+        &mut self,
+        lookup: &mut impl FnMut(&str) -> Result<Option<String>, E>,
+    ) -> Result<(), E> {
+        let on_batch = self
+            .on_batch
+            .iter_mut()
+            .flat_map(|hook| hook.sql.iter_mut());
+        for sql in self
+            .before
+            .iter_mut()
+            .chain(self.after.iter_mut())
+            .chain(on_batch)
+        {
+            *sql = substitute_placeholders(sql, lookup)?;
+        }
+        Ok(())
+    }
+}
+
+fn substitute_placeholders<E>(
+    sql: &str,
+    lookup: &mut impl FnMut(&str) -> Result<Option<String>, E>,
+) -> Result<String, E> {
+    let mut out = String::with_capacity(sql.len());
+    let mut rest = sql;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        out.push_str(&rest[..start]);
+        match lookup(name)? {
+            Some(text) => out.push_str(&text),
+            None => out.push_str(&rest[start..start + 3 + len]),
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Seed block - static rows inserted into a destination table on the
+/// pipeline's destination connection. Rows whose primary key is already
+/// there are left alone, so seeding again changes nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedTable {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// One value per column, in `columns` order
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl SeedTable {
+    /// The seed rows as records to write.
+    pub fn records(&self) -> Vec<Record> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let fields = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|(name, value)| FieldValue {
+                        name: name.clone(),
+                        value: Some(value.clone()),
+                        data_type: value.data_type(),
+                    })
+                    .collect();
+                Record::new(&self.table, fields, OpType::Insert)
+            })
+            .collect()
+    }
+}
+
+/// Default cap on the operations a row script may run for one row
+pub const DEFAULT_SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+
+/// Script block - a Rhai script run on every row, with the row's fields in
+/// the `row` map, for transformations the expression language cannot do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowScript {
+    pub code: String,
+    /// Operations the script may run for one row before it fails the row
+    pub max_operations: u64,
+}
+
+/// Aggregate block - source rows grouped by `group_by`, each group read as
+/// one row holding the grouping columns and the aggregates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aggregation {
+    /// Grouping columns; each keeps its column name in the grouped row
+    pub group_by: Vec<QualCol>,
+    pub aggregates: Vec<Aggregate>,
+}
+
+/// One aggregate column of a grouped row, e.g. `total = sum(orders.amount)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aggregate {
+    /// Column name of the aggregate in the grouped row
+    pub name: String,
+    pub function: AggregateFunction,
+    /// Aggregated column; `None` counts the group's rows (`count()`)
+    pub column: Option<QualCol>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AggregateFunction {
+    Sum,
+    Count,
+    Min,
+    Max,
+    Avg,
+}
+
+/// Pivot block - key-value source rows turned into one wide row per
+/// entity, or wide rows turned into one key-value row per column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pivot {
+    pub direction: PivotDirection,
+    /// Column holding the key: read when pivoting, written when unpivoting
+    pub key: String,
+    /// Column holding the value: read when pivoting, written when unpivoting
+    pub value: String,
+    /// Keys that become columns, or columns that become keys, in order
+    pub keys: Vec<String>,
+    /// Columns identifying the entity a key-value row belongs to; empty
+    /// groups by every column but `key` and `value`. Unused when unpivoting.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PivotDirection {
+    /// Key-value rows into wide rows
+    Pivot,
+    /// Wide rows into key-value rows
+    Unpivot,
+}
+
+/// A select field's `unnest(column, delimiter)` call - each source row
+/// becomes one row per element of `column`, with the other columns copied
+/// into every row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unnest {
+    /// Column split; each row holds one element in its place
+    pub column: String,
+    /// Separator of the elements of a text column. Array values are split
+    /// into their items.
+    pub delimiter: String,
+}
+
+/// On_batch block - SQL run on the destination every `every` committed batches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchHook {
+    pub sql: Vec<String>,
+    pub every: u64,
 }
 
 /// On_error block configuration
@@ -135,11 +549,18 @@ pub struct ErrorHandling {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetryConfig {
     pub max_attempts: u32,
+    /// Delay before the first retry
     pub delay_ms: u64,
     pub backoff: BackoffStrategy,
+    /// Upper bound on any single delay; defaults depend on `backoff`
+    #[serde(default)]
+    pub max_delay_ms: Option<u64>,
+    /// Randomize each delay so concurrent writers don't retry in lockstep
+    #[serde(default)]
+    pub jitter: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BackoffStrategy {
     Fixed,
     Exponential,
@@ -152,11 +573,26 @@ pub struct FailedRowsConfig {
     pub destination: Option<FailedRowsDestination>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FailedRowsConfig {
+    /// Path of the NDJSON quarantine file, when failed rows go to one.
+    pub fn quarantine_path(&self) -> Option<&str> {
+        match &self.destination {
+            Some(FailedRowsDestination::File {
+                path,
+                format: FileFormat::Json,
+            }) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FailedRowsAction {
     Skip,
     Log,
     SaveToTable,
+    /// Append failed rows to a local NDJSON quarantine file
+    SaveToFile,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
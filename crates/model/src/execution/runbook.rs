@@ -0,0 +1,38 @@
+use crate::execution::{approval::ApprovalGate, notify_config::NotifyConfig, verify::VerifyMode};
+use serde::{Deserialize, Serialize};
+
+/// Ordered list of migration configs run one after another by
+/// `stratum runbook run`, e.g. schema, backfill, then cutover.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Runbook {
+    pub name: String,
+    pub steps: Vec<RunbookStep>,
+    /// Webhooks told when a step waits for approval
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notify: Vec<NotifyConfig>,
+}
+
+impl Runbook {
+    /// Index of the step called `name`.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.steps.iter().position(|s| s.name == name)
+    }
+}
+
+/// One config of a runbook and the gates that must pass around it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunbookStep {
+    pub name: String,
+    /// Path of the step's SMQL config, relative to the runbook file
+    pub config: String,
+    /// Ask for confirmation before the step runs
+    pub approval: bool,
+    /// Pause before the step until it is approved with `stratum approve`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_gate: Option<ApprovalGate>,
+    /// Compare every pipeline of the step against its source in this mode
+    /// once the step has run; any discrepancy stops the runbook
+    pub verify: Option<VerifyMode>,
+    /// Most rows the step may quarantine before the runbook stops
+    pub max_failed_rows: Option<u64>,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Default key prefix for the Redis state backend
+pub const DEFAULT_REDIS_KEY_PREFIX: &str = "stratum";
+
+/// Where run state (checkpoints, WAL, run history, receipts) is persisted.
+/// Compiled from the state {} block or the `--state` CLI flag.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StateConfig {
+    /// Embedded sled database on the local disk (default: ~/.stratum/state)
+    #[default]
+    Local,
+
+    /// Embedded sled database at an explicit path
+    LocalPath(String),
+
+    /// Redis server, for containers and clusters without a persistent disk
+    Redis {
+        url: String,
+
+        /// Prefix for every key, so several deployments can share one server
+        key_prefix: String,
+
+        /// Expiry (in seconds) for checkpoints, WAL entries and run state.
+        /// Refreshed on every write; `None` keeps them until `stratum reset`.
+        ttl: Option<u64>,
+    },
+}
+
+impl StateConfig {
+    /// Builds a config from a state location: a `redis://` / `rediss://` URL
+    /// selects Redis, anything else is a local directory.
+    pub fn from_location(location: &str, ttl: Option<u64>) -> Self {
+        if location.starts_with("redis://") || location.starts_with("rediss://") {
+            Self::Redis {
+                url: location.to_string(),
+                key_prefix: DEFAULT_REDIS_KEY_PREFIX.to_string(),
+                ttl,
+            }
+        } else {
+            Self::LocalPath(location.to_string())
+        }
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        match self {
+            Self::Local | Self::LocalPath(_) => "local",
+            Self::Redis { .. } => "redis",
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Default number of source rows per compared chunk
+pub const DEFAULT_VERIFY_CHUNK_SIZE: usize = 10_000;
+
+/// Post-copy comparison of a pipeline's source and destination tables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyConfig {
+    pub mode: VerifyMode,
+    /// Source column the tables are chunked by; the primary key when unset
+    pub key: Option<String>,
+    pub chunk_size: usize,
+    /// Source columns to compare; every copied column when empty
+    pub columns: Vec<String>,
+    /// Source state compared against. Omitted from the hash when `Now` so
+    /// existing plans keep their run_id.
+    #[serde(default, skip_serializing_if = "VerifyAsOf::is_now")]
+    pub as_of: VerifyAsOf,
+}
+
+/// Which state of the source the destination is compared against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerifyAsOf {
+    /// The source as it is when the comparison runs (default)
+    #[default]
+    Now,
+    /// The snapshot the copy read, when the run pinned its source to one
+    Snapshot,
+}
+
+impl VerifyAsOf {
+    pub fn is_now(&self) -> bool {
+        *self == Self::Now
+    }
+}
+
+impl FromStr for VerifyAsOf {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "now" => Ok(Self::Now),
+            "snapshot" => Ok(Self::Snapshot),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What is compared for each chunk of keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerifyMode {
+    /// Row counts only
+    Count,
+    /// Row counts plus per-column non-null counts, min/max and sums
+    Aggregate,
+    /// Row counts plus an md5 checksum of each column's values (default)
+    Checksum,
+}
+
+impl VerifyMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Aggregate => "aggregate",
+            Self::Checksum => "checksum",
+        }
+    }
+}
+
+impl FromStr for VerifyMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "count" => Ok(Self::Count),
+            "aggregate" => Ok(Self::Aggregate),
+            "checksum" => Ok(Self::Checksum),
+            _ => Err(()),
+        }
+    }
+}
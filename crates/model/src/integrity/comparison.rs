@@ -0,0 +1,40 @@
+use crate::execution::verify::VerifyMode;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of comparing a pipeline's source table with its destination table
+/// chunk by chunk over the verify key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub pipeline: String,
+    pub source_table: String,
+    pub destination_table: String,
+    pub mode: VerifyMode,
+    /// Destination name of the column the tables were chunked by.
+    pub key: String,
+    pub chunks: u64,
+    pub source_rows: u64,
+    pub destination_rows: u64,
+    pub discrepancies: Vec<ChunkDiscrepancy>,
+    pub duration_ms: u64,
+}
+
+impl ComparisonReport {
+    pub fn is_match(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// A chunk of keys whose source and destination rows disagree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkDiscrepancy {
+    pub chunk_index: u64,
+    /// Exclusive lower key bound; `None` for the first chunk.
+    pub key_after: Option<String>,
+    /// Inclusive upper key bound; `None` for the last chunk.
+    pub key_through: Option<String>,
+    pub source_rows: u64,
+    pub destination_rows: u64,
+    /// Destination columns whose aggregates or checksums differ.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
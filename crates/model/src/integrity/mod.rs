@@ -1,8 +1,10 @@
 pub mod algorithm;
 pub mod canonical;
 pub mod coerce;
+pub mod comparison;
 pub mod config;
 pub mod hasher;
 pub mod merkle;
 pub mod receipt;
+pub mod reconciliation;
 pub mod result;
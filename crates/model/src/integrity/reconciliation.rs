@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of re-running a pipeline's transforms over a random sample of
+/// source rows and comparing the result with the destination row of the same
+/// key, field by field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub pipeline: String,
+    pub source_table: String,
+    pub destination_table: String,
+    /// Destination name of the column rows were matched on.
+    pub key: String,
+    /// Source rows drawn for the sample.
+    pub sampled: u64,
+    /// Sampled rows the pipeline filters out or rejects, which are not
+    /// expected in the destination.
+    pub skipped: u64,
+    /// Keys of sampled rows that have no destination row.
+    pub missing: Vec<String>,
+    pub mismatches: Vec<FieldMismatch>,
+    /// Destination columns that cannot be recomputed from the source row
+    /// alone, such as fields read from a joined table.
+    #[serde(default)]
+    pub unchecked_columns: Vec<String>,
+    pub duration_ms: u64,
+}
+
+impl ReconciliationReport {
+    pub fn is_match(&self) -> bool {
+        self.missing.is_empty() && self.mismatches.is_empty()
+    }
+
+    /// Sampled rows that were found in the destination and compared.
+    pub fn compared(&self) -> u64 {
+        self.sampled - self.skipped - self.missing.len() as u64
+    }
+}
+
+/// A destination field that differs from the value the pipeline produces for
+/// the same source row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMismatch {
+    pub key: String,
+    pub column: String,
+    /// Value the transforms produce, or `None` for NULL.
+    pub expected: Option<String>,
+    /// Value found in the destination, or `None` for NULL.
+    pub actual: Option<String>,
+}
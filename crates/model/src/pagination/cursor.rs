@@ -62,6 +62,21 @@ pub enum Cursor {
         id: u64, // tie-breaker id
     },
 
+    /// Position inside the NULL partition of a nullable cursor column.
+    /// NULL values cannot be compared with `>`, so those rows are paged
+    /// separately by the tie-breaker. `id` is `None` before the first NULL row;
+    /// `done` marks the partition as read when NULLs come first.
+    NullPartition {
+        col: QualCol,
+        pk_col: QualCol,
+        id: Option<u64>,
+        done: bool,
+    },
+
+    /// Position in the tables of a union read one after another: `table`
+    /// indexes the table being read, `inner` is the position within it.
+    Union { table: usize, inner: Box<Cursor> },
+
     /// Opaque cursor produced and consumed by a WASM source plugin.
     /// The host does not interpret the payload; it round-trips it verbatim.
     Opaque(String),
@@ -1,4 +1,5 @@
 use crate::pagination::cursor::QualCol;
+use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub struct OffsetConfig {
@@ -6,4 +7,25 @@ pub struct OffsetConfig {
     pub cursor: Option<QualCol>,     // e.g., "id" column for incremental fetch
     pub tiebreaker: Option<QualCol>, // required when cursor is not unique
     pub timezone: Option<String>,    // optional, for DATETIME <-> TIMESTAMP handling
+    pub nulls: Option<String>,       // "first" | "last", placement of NULL cursor values
+}
+
+/// Where rows with a NULL cursor value are read relative to the non-NULL ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullsOrder {
+    First,
+    #[default]
+    Last,
+}
+
+impl FromStr for NullsOrder {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(NullsOrder::First),
+            "last" => Ok(NullsOrder::Last),
+            _ => Err(()),
+        }
+    }
 }
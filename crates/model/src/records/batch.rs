@@ -1,4 +1,5 @@
 use crate::{pagination::cursor::Cursor, records::Record};
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct Batch {
@@ -7,6 +8,7 @@ pub struct Batch {
     pub cursor: Cursor,    // cursor used to start this batch (last committed offset)
     pub next: Cursor,      // resume-from cursor (end of this batch)
     pub ts: chrono::DateTime<chrono::Utc>,
+    pub read_time: Duration, // time spent fetching and transforming the rows
 }
 
 impl Batch {
@@ -510,12 +510,15 @@ mod tests {
         expr::{BinaryOp, CompiledExpression, WhenBranch},
         pipeline::{DataDestination, DataSource, Join, Pipeline, Transformation, WriteMode},
     };
+    use std::collections::BTreeMap;
 
     fn make_test_pipeline() -> Pipeline {
         Pipeline {
             name: "test_pipeline".to_string(),
             description: None,
             dependencies: vec![],
+            inferred_dependencies: vec![],
+            concurrent_writers: vec![],
             source: DataSource {
                 connection: Connection {
                     name: "source_db".to_string(),
@@ -524,6 +527,8 @@ mod tests {
                     nested_configs: HashMap::new(),
                 },
                 table: "customers".to_string(),
+                union: vec![],
+                source_column: None,
                 filters: vec![],
                 joins: vec![
                     Join {
@@ -539,6 +544,8 @@ mod tests {
                 ],
                 pagination: None,
                 graph_references: None,
+                timezones: Default::default(),
+                schema_scope: None,
             },
             destination: DataDestination {
                 connection: Connection {
@@ -550,6 +557,10 @@ mod tests {
                 table: "customers_clean".to_string(),
                 mode: WriteMode::Insert,
                 table_map: HashMap::new(),
+                partition_by: None,
+                column_types: Default::default(),
+                role_map: Default::default(),
+                order_by: Vec::new(),
             },
             transformations: vec![
                 // Simple field rename: id = id
@@ -593,11 +604,24 @@ mod tests {
                 },
             ],
             named_transformations: HashMap::new(),
+            sinks: Vec::new(),
             validations: vec![],
             lifecycle: None,
             error_handling: None,
             settings: HashMap::new(),
+            requires_env: vec![],
+            env: BTreeMap::new(),
             plugin_transforms: vec![],
+            verify: None,
+            approval_gate: None,
+            seeds: vec![],
+            script: None,
+            aggregate: None,
+            pivot: None,
+            unnest: None,
+            validate_only: false,
+            log: None,
+            run_id: None,
         }
     }
 
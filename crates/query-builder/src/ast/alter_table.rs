@@ -16,5 +16,10 @@ pub struct AlterTable {
 pub enum AlterTableOperation {
     AddColumn(ColumnDef),
     AddConstraint(TableConstraint),
-    ToggleTriggers { enabled: bool },
+    /// Change a column's type, e.g. to widen it. Dialects that restate the
+    /// whole column also keep its nullability and default.
+    AlterColumnType(ColumnDef),
+    ToggleTriggers {
+        enabled: bool,
+    },
 }
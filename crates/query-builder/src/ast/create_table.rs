@@ -1,7 +1,7 @@
 //! Defines the AST for a CREATE TABLE statement.
 
 use crate::ast::{common::TableRef, expr::Expr};
-use model::core::types::Type;
+use model::{core::types::Type, execution::partition::PartitionBound};
 
 /// Represents a complete CREATE TABLE statement.
 #[derive(Debug, Clone, Default)]
@@ -11,6 +11,23 @@ pub struct CreateTable {
     pub constraints: Vec<TableConstraint>,
     pub if_not_exists: bool,
     pub temp: bool,
+    /// Makes the table a partitioned parent (PostgreSQL declarative partitioning)
+    pub partition_by: Option<PartitionBy>,
+    /// Makes the table a partition of an existing parent; columns are taken
+    /// from the parent
+    pub partition_of: Option<PartitionOf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartitionBy {
+    Range { column: String },
+    Hash { column: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionOf {
+    pub parent: TableRef,
+    pub bound: PartitionBound,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,6 +21,10 @@ pub struct Select {
     /// The WHERE clause condition.
     pub where_clause: Option<Expr>,
 
+    /// The GROUP BY clause.
+    /// e.g., `GROUP BY customer_id`
+    pub group_by: Vec<Expr>,
+
     /// The ORDER BY clause.
     pub order_by: Vec<OrderByExpr>,
 
@@ -88,6 +88,13 @@ impl AlterTableBuilder {
         self
     }
 
+    pub fn alter_column_type(mut self, column: ColumnDef) -> Self {
+        self.ast
+            .operations
+            .push(AlterTableOperation::AlterColumnType(column));
+        self
+    }
+
     pub fn toggle_triggers(mut self, enabled: bool) -> Self {
         self.ast
             .operations
@@ -2,10 +2,10 @@
 
 use crate::ast::{
     common::TableRef,
-    create_table::{ColumnDef, CreateTable, TableConstraint},
+    create_table::{ColumnDef, CreateTable, PartitionBy, PartitionOf, TableConstraint},
     expr::Expr,
 };
-use model::core::types::Type;
+use model::{core::types::Type, execution::partition::PartitionBound};
 
 #[derive(Debug, Clone)]
 pub struct CreateTableBuilder {
@@ -43,6 +43,16 @@ impl CreateTableBuilder {
         self
     }
 
+    pub fn partition_by(mut self, partition_by: PartitionBy) -> Self {
+        self.ast.partition_by = Some(partition_by);
+        self
+    }
+
+    pub fn partition_of(mut self, parent: TableRef, bound: PartitionBound) -> Self {
+        self.ast.partition_of = Some(PartitionOf { parent, bound });
+        self
+    }
+
     pub fn build(self) -> CreateTable {
         self.ast
     }
@@ -1,6 +1,10 @@
 //! Provides a fluent builder for constructing `Insert` ASTs.
 
-use crate::ast::{common::TableRef, expr::Expr, insert::Insert};
+use crate::ast::{
+    common::TableRef,
+    expr::Expr,
+    insert::{Insert, OnConflict},
+};
 
 #[derive(Debug, Clone)]
 pub struct InsertBuilder {
@@ -30,6 +34,12 @@ impl InsertBuilder {
         self
     }
 
+    /// Resolves rows that collide with an existing key as `on_conflict` says.
+    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.ast.on_conflict = Some(on_conflict);
+        self
+    }
+
     pub fn build(self) -> Insert {
         self.ast
     }
@@ -93,6 +93,12 @@ impl SelectBuilder<FromState> {
         self
     }
 
+    /// Adds a `GROUP BY` clause with a list of grouping expressions.
+    pub fn group_by(mut self, exprs: Vec<Expr>) -> Self {
+        self.ast.group_by = exprs;
+        self
+    }
+
     /// Adds an `ORDER BY` clause to the query.
     pub fn order_by(mut self, expr: Expr, direction: Option<OrderDir>) -> Self {
         self.ast.order_by.push(OrderByExpr { expr, direction });
@@ -142,12 +148,15 @@ mod tests {
         },
         builder::select::SelectBuilder,
         ident_q,
-        offsets::OffsetStrategyFactory,
+        offsets::{NumericOffset, OffsetStrategy, OffsetStrategyFactory, ServerCursorOffset},
     };
     use chrono::NaiveDateTime;
     use model::{
         core::value::Value,
-        pagination::cursor::{Cursor, QualCol},
+        pagination::{
+            cursor::{Cursor, QualCol},
+            offset_config::{NullsOrder, OffsetConfig},
+        },
     };
 
     fn ident(name: &str) -> Expr {
@@ -434,6 +443,211 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_pagination_server_cursor_resume() {
+        let key = QualCol {
+            table: "orders".to_string(),
+            column: "id".to_string(),
+        };
+        let cursor = Cursor::Pk {
+            pk_col: key.clone(),
+            id: 500,
+        };
+        let start = std::sync::Arc::new(ServerCursorOffset { key: key.clone() });
+
+        let ast = SelectBuilder::new()
+            .select(vec![ident("id")])
+            .from(table("orders"), None)
+            .limit(value(Value::Int(100)))
+            .paginate(start, &cursor, 100)
+            .build();
+
+        // The cursor is drained with FETCH FORWARD, so the query has no LIMIT.
+        assert!(ast.limit.is_none());
+
+        assert_eq!(ast.order_by.len(), 1);
+        assert_eq!(ast.order_by[0].expr, ident_q(&key));
+        assert_eq!(ast.order_by[0].direction, Some(OrderDir::Asc));
+
+        // Expected: (id > 500)
+        assert_eq!(
+            ast.where_clause.unwrap(),
+            Expr::BinaryOp(Box::new(BinaryOp {
+                left: ident_q(&key),
+                op: BinaryOperator::Gt,
+                right: value(Value::UInt(500)),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_build_pagination_server_cursor_resume_negative_key() {
+        let key = QualCol {
+            table: "ledger".to_string(),
+            column: "entry_id".to_string(),
+        };
+        let start = std::sync::Arc::new(ServerCursorOffset { key: key.clone() });
+        let union = Cursor::Union {
+            table: 0,
+            inner: Box::new(Cursor::Numeric {
+                col: key.clone(),
+                val: -20,
+            }),
+        };
+
+        let ast = SelectBuilder::new()
+            .select(vec![ident("entry_id")])
+            .from(table("ledger"), None)
+            .paginate(start, &union, 100)
+            .build();
+
+        // Expected: (entry_id > -20)
+        assert_eq!(
+            ast.where_clause.unwrap(),
+            Expr::BinaryOp(Box::new(BinaryOp {
+                left: ident_q(&key),
+                op: BinaryOperator::Gt,
+                right: value(Value::Int(-20)),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_offset_factory_reports_missing_columns() {
+        let config = |strategy: &str| OffsetConfig {
+            strategy: Some(strategy.to_string()),
+            cursor: None,
+            tiebreaker: None,
+            timezone: None,
+            nulls: None,
+        };
+
+        let err = OffsetStrategyFactory::from_config(&config("server_cursor")).err();
+        assert_eq!(
+            err.as_deref(),
+            Some("server_cursor pagination requires a 'cursor' column")
+        );
+        let err = OffsetStrategyFactory::from_config(&config("keyset")).err();
+        assert_eq!(
+            err.as_deref(),
+            Some("unsupported pagination strategy 'keyset'")
+        );
+        assert!(OffsetStrategyFactory::from_config(&config("default")).is_ok());
+    }
+
+    fn nullable_numeric(nulls: NullsOrder) -> (NumericOffset, QualCol, QualCol) {
+        let col = QualCol {
+            table: "orders".to_string(),
+            column: "shipped_seq".to_string(),
+        };
+        let pk = QualCol {
+            table: "orders".to_string(),
+            column: "id".to_string(),
+        };
+        let strategy = NumericOffset {
+            col: col.clone(),
+            pk: pk.clone(),
+            nulls,
+        };
+        (strategy, col, pk)
+    }
+
+    #[test]
+    fn test_build_pagination_numeric_excludes_nulls_from_keyset() {
+        let (strategy, col, _) = nullable_numeric(NullsOrder::Last);
+
+        let ast = SelectBuilder::new()
+            .select(vec![ident("id")])
+            .from(table("orders"), None)
+            .paginate(std::sync::Arc::new(strategy), &Cursor::None, 100)
+            .build();
+
+        // Expected: (shipped_seq IS NOT NULL)
+        assert_eq!(
+            ast.where_clause.unwrap(),
+            Expr::IsNull {
+                expr: Box::new(ident_q(&col)),
+                negated: true,
+            }
+        );
+        assert_eq!(ast.order_by.len(), 2);
+    }
+
+    #[test]
+    fn test_build_pagination_null_partition() {
+        let (strategy, col, pk) = nullable_numeric(NullsOrder::Last);
+        let cursor = Cursor::NullPartition {
+            col: col.clone(),
+            pk_col: pk.clone(),
+            id: Some(7),
+            done: false,
+        };
+
+        let ast = SelectBuilder::new()
+            .select(vec![ident("id")])
+            .from(table("orders"), None)
+            .paginate(std::sync::Arc::new(strategy), &cursor, 100)
+            .build();
+
+        // NULL rows are ordered by the tie-breaker alone.
+        assert_eq!(ast.order_by.len(), 1);
+        assert_eq!(ast.order_by[0].expr, ident_q(&pk));
+        assert_eq!(ast.limit, Some(value(Value::Int(100))));
+
+        // Expected: (shipped_seq IS NULL AND id > 7)
+        assert_eq!(
+            ast.where_clause.unwrap(),
+            Expr::BinaryOp(Box::new(BinaryOp {
+                left: Expr::IsNull {
+                    expr: Box::new(ident_q(&col)),
+                    negated: false,
+                },
+                op: BinaryOperator::And,
+                right: Expr::BinaryOp(Box::new(BinaryOp {
+                    left: ident_q(&pk),
+                    op: BinaryOperator::Gt,
+                    right: value(Value::UInt(7)),
+                })),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_null_partition_order() {
+        let (last, col, pk) = nullable_numeric(NullsOrder::Last);
+        let null_start = Cursor::NullPartition {
+            col: col.clone(),
+            pk_col: pk.clone(),
+            id: None,
+            done: false,
+        };
+
+        // NULLS LAST: keyset range first, then the NULL partition, then done.
+        assert_eq!(last.next_partition(&Cursor::None), Some(null_start.clone()));
+        assert_eq!(last.next_partition(&null_start), None);
+
+        // NULLS FIRST: the scan starts in the NULL partition.
+        let (first, _, _) = nullable_numeric(NullsOrder::First);
+        let ast = SelectBuilder::new()
+            .select(vec![ident("id")])
+            .from(table("orders"), None)
+            .paginate(std::sync::Arc::from(first.clone_box()), &Cursor::None, 100)
+            .build();
+        assert_eq!(ast.order_by[0].expr, ident_q(&pk));
+
+        let nulls_done = first.next_partition(&Cursor::None).unwrap();
+        assert_eq!(
+            nulls_done,
+            Cursor::NullPartition {
+                col,
+                pk_col: pk,
+                id: None,
+                done: true,
+            }
+        );
+        assert_eq!(first.next_partition(&nulls_done), None);
+    }
+
     #[test]
     fn test_order_by_random() {
         let builder = SelectBuilder::new();
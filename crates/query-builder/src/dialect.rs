@@ -22,6 +22,31 @@ pub trait Dialect: Send + Sync {
     /// Returns the name of the dialect (e.g., "PostgreSQL", "MySQL").
     fn name(&self) -> String;
 
+    /// Longest identifier, in bytes, the database keeps without truncating.
+    ///
+    /// - PostgreSQL keeps 63 bytes (`NAMEDATALEN - 1`)
+    /// - MySQL allows 64 characters
+    fn max_identifier_length(&self) -> usize;
+
+    /// Whether enums are named types created ahead of the tables using them.
+    ///
+    /// - PostgreSQL uses `CREATE TYPE ... AS ENUM`
+    /// - MySQL declares the labels inline: `ENUM('a', 'b')`
+    fn has_enum_types(&self) -> bool;
+
+    /// Whether an insert resolves key conflicts with a trailing clause that
+    /// names no columns.
+    ///
+    /// - PostgreSQL uses `ON CONFLICT (key) DO ...`
+    /// - MySQL uses `ON DUPLICATE KEY UPDATE ...`
+    fn has_on_duplicate_key(&self) -> bool;
+
+    /// Whether a column's type is changed by restating the whole column.
+    ///
+    /// - PostgreSQL uses `ALTER COLUMN col TYPE type`
+    /// - MySQL uses `MODIFY COLUMN col type [NOT NULL] [DEFAULT ...]`
+    fn has_modify_column(&self) -> bool;
+
     /// Generates the SQL query and a corresponding list of parameters to bind
     /// for efficiently checking the existence of multiple composite keys.
     fn build_key_existence_query(
@@ -166,6 +191,22 @@ impl Dialect for Postgres {
         "PostgreSQL".into()
     }
 
+    fn max_identifier_length(&self) -> usize {
+        63
+    }
+
+    fn has_enum_types(&self) -> bool {
+        true
+    }
+
+    fn has_on_duplicate_key(&self) -> bool {
+        false
+    }
+
+    fn has_modify_column(&self) -> bool {
+        false
+    }
+
     fn build_key_existence_query(
         &self,
         table_name: &str,
@@ -354,6 +395,22 @@ impl Dialect for MySql {
         "MySQL".into()
     }
 
+    fn max_identifier_length(&self) -> usize {
+        64
+    }
+
+    fn has_enum_types(&self) -> bool {
+        false
+    }
+
+    fn has_on_duplicate_key(&self) -> bool {
+        true
+    }
+
+    fn has_modify_column(&self) -> bool {
+        true
+    }
+
     fn build_key_existence_query(
         &self,
         _table_name: &str,
@@ -13,11 +13,18 @@ use model::{
     execution::pipeline::Pagination,
     pagination::{
         cursor::{Cursor, QualCol},
-        offset_config::OffsetConfig,
+        offset_config::{NullsOrder, OffsetConfig},
     },
     records::Record,
 };
-use std::{convert::TryFrom, str::FromStr, sync::Arc};
+use std::{
+    convert::TryFrom,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 #[async_trait]
 pub trait OffsetStrategy: Send + Sync {
@@ -37,6 +44,23 @@ pub trait OffsetStrategy: Send + Sync {
 
     /// Returns the name of the offset strategy.
     fn name(&self) -> String;
+
+    /// Whether pages are drained from a server-side cursor instead of being
+    /// fetched with one `LIMIT` query per page.
+    fn uses_server_cursor(&self) -> bool {
+        false
+    }
+
+    /// Cursor that starts the next partition once the one addressed by
+    /// `cursor` is exhausted, or `None` when the scan is complete.
+    fn next_partition(&self, _cursor: &Cursor) -> Option<Cursor> {
+        None
+    }
+    /// Integer key the rows are paged by, when the strategy can read a
+    /// bounded range of it (see [`RangeOffset`]).
+    fn range_key(&self) -> Option<&QualCol> {
+        None
+    }
 }
 
 pub struct PkOffset {
@@ -46,18 +70,36 @@ pub struct PkOffset {
 pub struct NumericOffset {
     pub col: QualCol,
     pub pk: QualCol,
+    pub nulls: NullsOrder,
 }
 
 pub struct TimestampOffset {
     pub ts_col: QualCol,
     pub pk: QualCol,
     pub tz: chrono_tz::Tz,
+    pub nulls: NullsOrder,
 }
 
 pub struct DefaultOffset {
     pub offset: usize,
 }
 
+/// Keyset-ordered read through a server-side cursor (a declared cursor on
+/// Postgres, a streamed result set on MySQL). The query is issued once and
+/// drained page by page, so it carries no `LIMIT`; the key only orders the rows
+/// and scopes the query when resuming from a checkpoint.
+pub struct ServerCursorOffset {
+    pub key: QualCol,
+}
+
+/// Keyset read of one key range of a partitioned load: the PK strategy, bounded
+/// above by `end` (exclusive). Another worker may lower `end` between fetches to
+/// take over the tail of the range.
+pub struct RangeOffset {
+    pub pk: QualCol,
+    pub end: Arc<AtomicU64>,
+}
+
 /// Helper for constructing a binary expression.
 fn binary_expr(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
     Expr::BinaryOp(Box::new(BinaryOp { left, op, right }))
@@ -104,6 +146,77 @@ fn numeric_literal(val: i128) -> Expr {
     }
 }
 
+fn is_null(col: &QualCol, negated: bool) -> Expr {
+    Expr::IsNull {
+        expr: Box::new(ident_q(col)),
+        negated,
+    }
+}
+
+fn extract_pk_id(pk: &Value) -> Option<u64> {
+    match pk {
+        Value::UInt(id) => Some(*id),
+        Value::Int(i) if *i >= 0 => Some(*i as u64),
+        Value::String(s) => s.parse::<u64>().ok(),
+        _ => None,
+    }
+}
+
+/// Position inside the NULL partition addressed by `cursor`, if any. With
+/// NULLS FIRST the scan starts in that partition, so `Cursor::None` counts too.
+fn null_partition_position(cursor: &Cursor, nulls: NullsOrder) -> Option<Option<u64>> {
+    match cursor {
+        Cursor::NullPartition {
+            id, done: false, ..
+        } => Some(*id),
+        Cursor::None if nulls == NullsOrder::First => Some(None),
+        _ => None,
+    }
+}
+
+/// Pages through rows whose cursor column is NULL:
+/// `WHERE col IS NULL [AND pk > ?] ORDER BY pk ASC LIMIT ?`.
+fn apply_null_partition(
+    mut builder: SelectBuilder<FromState>,
+    col: &QualCol,
+    pk: &QualCol,
+    id: Option<u64>,
+    limit: usize,
+) -> SelectBuilder<FromState> {
+    let mut predicate = is_null(col, false);
+    if let Some(id) = id {
+        let pk_gt = binary_expr(ident_q(pk), BinaryOperator::Gt, uint_literal(id));
+        predicate = binary_expr(predicate, BinaryOperator::And, pk_gt);
+    }
+
+    builder = append_where(builder, predicate);
+    builder = builder.order_by(ident_q(pk), Some(OrderDir::Asc));
+    builder.limit(limit_expr(limit))
+}
+
+/// Keyset comparisons never match NULL, so nullable cursor columns are read in
+/// two partitions: the non-NULL keyset range and the NULL rows, in `nulls` order.
+fn next_null_partition(
+    cursor: &Cursor,
+    col: &QualCol,
+    pk: &QualCol,
+    nulls: NullsOrder,
+) -> Option<Cursor> {
+    let in_nulls = null_partition_position(cursor, nulls).is_some();
+    let done = match nulls {
+        NullsOrder::First if in_nulls => true,
+        NullsOrder::Last if !matches!(cursor, Cursor::NullPartition { .. }) => false,
+        _ => return None,
+    };
+
+    Some(Cursor::NullPartition {
+        col: col.clone(),
+        pk_col: pk.clone(),
+        id: None,
+        done,
+    })
+}
+
 fn default_pk() -> QualCol {
     QualCol {
         table: "".to_string(),
@@ -161,6 +274,47 @@ impl OffsetStrategy for PkOffset {
     fn name(&self) -> String {
         "pk".to_string()
     }
+
+    fn range_key(&self) -> Option<&QualCol> {
+        Some(&self.pk)
+    }
+}
+
+impl OffsetStrategy for RangeOffset {
+    fn apply_to_builder(
+        &self,
+        builder: SelectBuilder<FromState>,
+        cursor: &Cursor,
+        limit: usize,
+    ) -> SelectBuilder<FromState> {
+        let builder = PkOffset {
+            pk: self.pk.clone(),
+        }
+        .apply_to_builder(builder, cursor, limit);
+
+        // AND pk < end
+        let end = self.end.load(Ordering::Acquire);
+        let where_cond = binary_expr(ident_q(&self.pk), BinaryOperator::Lt, uint_literal(end));
+        append_where(builder, where_cond)
+    }
+
+    fn next_cursor(&self, row: &Record) -> Cursor {
+        PkOffset {
+            pk: self.pk.clone(),
+        }
+        .next_cursor(row)
+    }
+
+    fn clone_box(&self) -> Box<dyn OffsetStrategy> {
+        Box::new(RangeOffset {
+            pk: self.pk.clone(),
+            end: self.end.clone(),
+        })
+    }
+
+    fn name(&self) -> String {
+        "pk".to_string()
+    }
 }
 
 impl OffsetStrategy for NumericOffset {
@@ -170,10 +324,16 @@ impl OffsetStrategy for NumericOffset {
         cursor: &Cursor,
         limit: usize,
     ) -> SelectBuilder<FromState> {
-        if let Some(predicate) = self.where_clause(cursor) {
-            builder = append_where(builder, predicate);
+        if let Some(id) = null_partition_position(cursor, self.nulls) {
+            return apply_null_partition(builder, &self.col, &self.pk, id, limit);
         }
 
+        // First page of the non-NULL range: NULLs are read as their own partition.
+        let predicate = self
+            .where_clause(cursor)
+            .unwrap_or_else(|| is_null(&self.col, true));
+        builder = append_where(builder, predicate);
+
         builder = builder.order_by(ident_q(&self.col), Some(OrderDir::Asc));
         builder = builder.order_by(ident_q(&self.pk), Some(OrderDir::Asc));
 
@@ -184,16 +344,15 @@ impl OffsetStrategy for NumericOffset {
 
     fn next_cursor(&self, row: &Record) -> Cursor {
         let num_v = row.get_value(&self.col.column);
-        let pk_v = row.get_value(&self.pk.column);
-
-        let pk_id: Option<u64> = match &pk_v {
-            Value::UInt(id) => Some(*id),
-            Value::Int(i) if *i >= 0 => Some(*i as u64),
-            Value::String(s) => s.parse::<u64>().ok(),
-            _ => None,
-        };
+        let pk_id = extract_pk_id(&row.get_value(&self.pk.column));
 
         match (extract_numeric_value(&num_v), pk_id) {
+            (None, Some(id)) if matches!(num_v, Value::Null) => Cursor::NullPartition {
+                col: self.col.clone(),
+                pk_col: self.pk.clone(),
+                id: Some(id),
+                done: false,
+            },
             (Some(val), Some(id)) => Cursor::CompositeNumPk {
                 num_col: self.col.clone(),
                 pk_col: self.pk.clone(),
@@ -208,12 +367,17 @@ impl OffsetStrategy for NumericOffset {
         Box::new(NumericOffset {
             col: self.col.clone(),
             pk: self.pk.clone(),
+            nulls: self.nulls,
         })
     }
 
     fn name(&self) -> String {
         "numeric".to_string()
     }
+
+    fn next_partition(&self, cursor: &Cursor) -> Option<Cursor> {
+        next_null_partition(cursor, &self.col, &self.pk, self.nulls)
+    }
 }
 
 impl NumericOffset {
@@ -257,6 +421,10 @@ impl OffsetStrategy for TimestampOffset {
         cursor: &Cursor,
         limit: usize,
     ) -> SelectBuilder<FromState> {
+        if let Some(id) = null_partition_position(cursor, self.nulls) {
+            return apply_null_partition(builder, &self.ts_col, &self.pk, id, limit);
+        }
+
         // Add WHERE clause based on cursor
         if let Cursor::CompositeTsPk { ts, id, .. } = cursor {
             let dt_local = Utc
@@ -281,6 +449,9 @@ impl OffsetStrategy for TimestampOffset {
             let where_cond = binary_expr(cond1, BinaryOperator::Or, cond2);
 
             builder = append_where(builder, where_cond);
+        } else {
+            // First page of the non-NULL range: NULLs are read as their own partition.
+            builder = append_where(builder, is_null(&self.ts_col, true));
         }
 
         // Add ORDER BY
@@ -297,16 +468,16 @@ impl OffsetStrategy for TimestampOffset {
         let ts_v = row.get_value(&self.ts_col.column);
         let pk_v = row.get_value(&self.pk.column);
 
-        let extract_pk_id = |pk: &Value| -> u64 {
-            match pk {
-                Value::UInt(id) => *id,
-                Value::Int(i) if *i >= 0 => *i as u64,
-                Value::String(s) => s.parse::<u64>().unwrap_or(0),
-                _ => 0,
-            }
-        };
+        let extract_pk_id = |pk: &Value| -> u64 { extract_pk_id(pk).unwrap_or(0) };
 
         match ts_v {
+            // NULL timestamps are paged by the tie-breaker in their own partition
+            Value::Null => Cursor::NullPartition {
+                col: self.ts_col.clone(),
+                pk_col: self.pk.clone(),
+                id: Some(extract_pk_id(&pk_v)),
+                done: false,
+            },
             // Timestamp with timezone offset
             Value::Timestamp {
                 value: dt_local,
@@ -351,12 +522,17 @@ impl OffsetStrategy for TimestampOffset {
             ts_col: self.ts_col.clone(),
             pk: self.pk.clone(),
             tz: self.tz,
+            nulls: self.nulls,
         })
     }
 
     fn name(&self) -> String {
         "timestamp".to_string()
     }
+
+    fn next_partition(&self, cursor: &Cursor) -> Option<Cursor> {
+        next_null_partition(cursor, &self.ts_col, &self.pk, self.nulls)
+    }
 }
 
 impl OffsetStrategy for DefaultOffset {
@@ -393,60 +569,128 @@ impl OffsetStrategy for DefaultOffset {
         "default".to_string()
     }
 }
+impl ServerCursorOffset {
+    /// Last key read before `cursor`, which a resumed cursor starts after.
+    /// The planner only accepts integer keys, whose checkpoints hold a `Pk`
+    /// cursor, or a `Numeric` one for a negative key; any other cursor would
+    /// belong to another strategy, and an edited `paginate` block stops a
+    /// resume before it gets here.
+    fn last_key(cursor: &Cursor) -> Option<Expr> {
+        match cursor {
+            Cursor::None => None,
+            Cursor::Pk { id, .. } => Some(uint_literal(*id)),
+            Cursor::Numeric { val, .. } => Some(numeric_literal(*val)),
+            // Every table of a union is paged the same way
+            Cursor::Union { inner, .. } => Self::last_key(inner),
+            other => unreachable!("server_cursor cannot resume from {other:?}"),
+        }
+    }
+}
+
+impl OffsetStrategy for ServerCursorOffset {
+    fn apply_to_builder(
+        &self,
+        mut builder: SelectBuilder<FromState>,
+        cursor: &Cursor,
+        _limit: usize,
+    ) -> SelectBuilder<FromState> {
+        // Resuming: WHERE key > ?
+        if let Some(last) = Self::last_key(cursor) {
+            let where_cond = binary_expr(ident_q(&self.key), BinaryOperator::Gt, last);
+            builder = append_where(builder, where_cond);
+        }
+
+        // ORDER BY key ASC, no LIMIT - page size is applied by the cursor.
+        builder = builder.order_by(ident_q(&self.key), Some(OrderDir::Asc));
+        builder.ast.limit = None;
+
+        builder
+    }
+
+    fn next_cursor(&self, row: &Record) -> Cursor {
+        // Checkpoints record the last key seen, exactly like the PK strategy,
+        // so an interrupted run re-declares the cursor after that key.
+        match row.get_value(&self.key.column) {
+            // Negative keys do not fit a PK cursor
+            Value::Int(i) if i < 0 => Cursor::Numeric {
+                col: self.key.clone(),
+                val: i as i128,
+            },
+            _ => PkOffset {
+                pk: self.key.clone(),
+            }
+            .next_cursor(row),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn OffsetStrategy> {
+        Box::new(ServerCursorOffset {
+            key: self.key.clone(),
+        })
+    }
+
+    fn name(&self) -> String {
+        "server_cursor".to_string()
+    }
+
+    fn uses_server_cursor(&self) -> bool {
+        true
+    }
+}
+
 pub struct OffsetStrategyFactory;
 
 impl OffsetStrategyFactory {
-    /// Build a strategy from configuration.
-    pub fn from_config(config: &OffsetConfig) -> Arc<dyn OffsetStrategy> {
+    /// Build a strategy from configuration. Fails when the strategy is
+    /// unknown or is missing a column it pages on.
+    pub fn from_config(config: &OffsetConfig) -> Result<Arc<dyn OffsetStrategy>, String> {
         // If user didn't specify a cursor column -> default PK "id".
         let strategy = config
             .strategy
             .as_deref()
             .unwrap_or("default")
             .to_lowercase();
+        let required = |column: &Option<QualCol>, what: &str| {
+            column
+                .clone()
+                .ok_or_else(|| format!("{strategy} pagination requires a '{what}' column"))
+        };
 
-        match strategy.as_str() {
+        let offset: Arc<dyn OffsetStrategy> = match strategy.as_str() {
             "pk" => Arc::new(PkOffset {
-                pk: config
-                    .cursor
-                    .clone()
-                    .expect("PK offset requires 'cursor' column"),
+                pk: required(&config.cursor, "cursor")?,
             }),
 
-            "numeric" => {
-                let col = config
-                    .cursor
-                    .clone()
-                    .expect("Numeric offset requires 'cursor' column");
-                let pk = config
-                    .tiebreaker
-                    .clone()
-                    .expect("Numeric offset requires 'tiebreaker' column");
-                Arc::new(NumericOffset { col, pk })
-            }
+            "numeric" => Arc::new(NumericOffset {
+                col: required(&config.cursor, "cursor")?,
+                pk: required(&config.tiebreaker, "tiebreaker")?,
+                nulls: Self::nulls_order(config),
+            }),
 
             "timestamp" => {
-                let ts_col = config
-                    .cursor
-                    .clone()
-                    .unwrap_or_else(|| panic!("Timestamp offset requires 'cursor' column"));
-                let pk = config
-                    .tiebreaker
-                    .clone()
-                    .expect("Timestamp offset requires 'tiebreaker' column");
                 let tz = config
                     .timezone
                     .as_deref()
                     .unwrap_or("UTC")
                     .parse::<chrono_tz::Tz>()
                     .unwrap_or(chrono_tz::UTC);
-                Arc::new(TimestampOffset { ts_col, pk, tz })
+                Arc::new(TimestampOffset {
+                    ts_col: required(&config.cursor, "cursor")?,
+                    pk: required(&config.tiebreaker, "tiebreaker")?,
+                    tz,
+                    nulls: Self::nulls_order(config),
+                })
             }
 
+            "server_cursor" => Arc::new(ServerCursorOffset {
+                key: required(&config.cursor, "cursor")?,
+            }),
+
             "default" => Arc::new(DefaultOffset { offset: 0 }),
 
-            other => panic!("Unsupported offset strategy: {other}"),
-        }
+            other => return Err(format!("unsupported pagination strategy '{other}'")),
+        };
+        Ok(offset)
     }
 
     /// Build a strategy from a concrete cursor (e.g., when resuming).
@@ -459,6 +703,7 @@ impl OffsetStrategyFactory {
                 Arc::new(NumericOffset {
                     col: col.clone(),
                     pk: default_pk(),
+                    nulls: NullsOrder::default(),
                 })
             }
 
@@ -467,31 +712,47 @@ impl OffsetStrategyFactory {
             } => Arc::new(NumericOffset {
                 col: num_col.clone(),
                 pk: pk_col.clone(),
+                nulls: NullsOrder::default(),
             }),
 
             Cursor::Timestamp { col, .. } => Arc::new(TimestampOffset {
                 ts_col: col.clone(),
                 pk: default_pk(),
                 tz: chrono_tz::UTC,
+                nulls: NullsOrder::default(),
             }),
 
             Cursor::CompositeTsPk { ts_col, pk_col, .. } => Arc::new(TimestampOffset {
                 ts_col: ts_col.clone(),
                 pk: pk_col.clone(),
                 tz: chrono_tz::UTC,
+                nulls: NullsOrder::default(),
+            }),
+
+            // The NULL partition is paged by the tie-breaker alone, whatever the
+            // cursor column's type.
+            Cursor::NullPartition { col, pk_col, .. } => Arc::new(NumericOffset {
+                col: col.clone(),
+                pk: pk_col.clone(),
+                nulls: NullsOrder::default(),
             }),
 
             Cursor::Default { offset } => Arc::new(DefaultOffset { offset: *offset }),
 
             Cursor::None => Arc::new(DefaultOffset { offset: 0 }), // start from beginning
 
+            // Every table of a union is paged the same way
+            Cursor::Union { inner, .. } => Self::from_cursor(inner),
+
             Cursor::Opaque(_) => {
                 unreachable!("Cursor::Opaque is consumed by WASM source readers, not SQL offsets")
             }
         }
     }
 
-    pub fn from_pagination(pagination: &Option<Pagination>) -> Arc<dyn OffsetStrategy> {
+    pub fn from_pagination(
+        pagination: &Option<Pagination>,
+    ) -> Result<Arc<dyn OffsetStrategy>, String> {
         if let Some(pagination) = pagination {
             let mut cursor: Option<QualCol> = None;
             let mut tiebreaker: Option<QualCol> = None;
@@ -514,14 +775,23 @@ impl OffsetStrategyFactory {
                 cursor,
                 tiebreaker,
                 timezone,
+                nulls: pagination.nulls.clone(),
             };
 
             OffsetStrategyFactory::from_config(&config)
         } else {
-            OffsetStrategyFactory::default_strategy()
+            Ok(OffsetStrategyFactory::default_strategy())
         }
     }
 
+    fn nulls_order(config: &OffsetConfig) -> NullsOrder {
+        config
+            .nulls
+            .as_deref()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or_default()
+    }
+
     pub fn default_strategy() -> Arc<dyn OffsetStrategy> {
         Arc::new(DefaultOffset { offset: 0 })
     }
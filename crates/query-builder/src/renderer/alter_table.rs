@@ -36,6 +36,20 @@ impl Render for AlterTableOperation {
                 r.sql.push_str("ADD ");
                 constraint.render(r);
             }
+            AlterTableOperation::AlterColumnType(col_def) => {
+                if r.dialect.has_modify_column() {
+                    r.sql.push_str("MODIFY COLUMN ");
+                    render_add_column(col_def, r);
+                } else {
+                    r.sql.push_str("ALTER COLUMN ");
+                    r.sql.push_str(&r.dialect.quote_identifier(&col_def.name));
+                    r.sql.push_str(" TYPE ");
+                    r.sql.push_str(
+                        &r.dialect
+                            .render_data_type(&col_def.data_type, col_def.max_length),
+                    );
+                }
+            }
             AlterTableOperation::ToggleTriggers { enabled } => {
                 let action = if *enabled { "ENABLE" } else { "DISABLE" };
                 r.sql.push_str(action);
@@ -71,8 +85,9 @@ mod tests {
             alter_table::{AlterTable, AlterTableOperation},
             common::TableRef,
             create_table::ColumnDef,
+            expr::Expr,
         },
-        dialect::Postgres,
+        dialect::{MySql, Postgres},
         renderer::{Render, Renderer},
     };
 
@@ -112,4 +127,42 @@ ALTER TABLE "posts" DISABLE TRIGGER ALL;
 ALTER TABLE "posts" ENABLE TRIGGER ALL;"#;
         assert_eq!(sql, expected_sql);
     }
+
+    #[test]
+    fn test_render_alter_column_type() {
+        let ast = AlterTable {
+            table: TableRef {
+                schema: None,
+                name: "posts".to_string(),
+            },
+            operations: vec![AlterTableOperation::AlterColumnType(ColumnDef {
+                name: "title".to_string(),
+                data_type: Type::Varchar {
+                    length: Some(512),
+                    charset: None,
+                },
+                is_nullable: false,
+                is_primary_key: false,
+                default_value: Some(Expr::Literal("'untitled'".to_string())),
+                max_length: None,
+                generated_expression: None,
+                is_stored: false,
+            })],
+        };
+
+        let render = |dialect: &dyn crate::dialect::Dialect| {
+            let mut renderer = Renderer::new(dialect);
+            ast.render(&mut renderer);
+            renderer.finish().0
+        };
+
+        assert_eq!(
+            render(&Postgres),
+            r#"ALTER TABLE "posts" ALTER COLUMN "title" TYPE varchar(512);"#
+        );
+        assert_eq!(
+            render(&MySql),
+            "ALTER TABLE `posts` MODIFY COLUMN `title` VARCHAR(512) NOT NULL DEFAULT 'untitled';"
+        );
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
-    ast::create_table::{ColumnDef, CreateTable, TableConstraint},
+    ast::create_table::{ColumnDef, CreateTable, PartitionBy, PartitionOf, TableConstraint},
     renderer::{Render, Renderer},
 };
+use model::execution::partition::PartitionBound;
 
 impl Render for CreateTable {
     fn render(&self, r: &mut Renderer) {
@@ -15,6 +16,13 @@ impl Render for CreateTable {
         }
         r.sql
             .push_str(&r.dialect.quote_identifier(&self.table.name));
+
+        if let Some(partition_of) = &self.partition_of {
+            partition_of.render(r);
+            r.sql.push(';');
+            return;
+        }
+
         r.sql.push_str(" (");
 
         let num_cols = self.columns.len();
@@ -34,7 +42,45 @@ impl Render for CreateTable {
             }
         }
 
-        r.sql.push_str("\n);");
+        r.sql.push_str("\n)");
+        if let Some(partition_by) = &self.partition_by {
+            partition_by.render(r);
+        }
+        r.sql.push(';');
+    }
+}
+
+impl Render for PartitionBy {
+    fn render(&self, r: &mut Renderer) {
+        let (method, column) = match self {
+            PartitionBy::Range { column } => ("RANGE", column),
+            PartitionBy::Hash { column } => ("HASH", column),
+        };
+        r.sql.push_str(&format!(
+            " PARTITION BY {} ({})",
+            method,
+            r.dialect.quote_identifier(column)
+        ));
+    }
+}
+
+impl Render for PartitionOf {
+    fn render(&self, r: &mut Renderer) {
+        r.sql.push_str(" PARTITION OF ");
+        r.sql
+            .push_str(&r.dialect.quote_identifier(&self.parent.name));
+        match &self.bound {
+            PartitionBound::Range { from, to } => r.sql.push_str(&format!(
+                " FOR VALUES FROM ('{}') TO ('{}')",
+                from.format("%Y-%m-%d"),
+                to.format("%Y-%m-%d")
+            )),
+            PartitionBound::Hash { modulus, remainder } => r.sql.push_str(&format!(
+                " FOR VALUES WITH (MODULUS {}, REMAINDER {})",
+                modulus, remainder
+            )),
+            PartitionBound::Default => r.sql.push_str(" DEFAULT"),
+        }
     }
 }
 
@@ -156,13 +202,18 @@ impl Render for TableConstraint {
 
 #[cfg(test)]
 mod tests {
-    use model::core::types::{IntSize, Type};
+    use chrono::NaiveDate;
+    use model::{
+        core::types::{IntSize, Type},
+        execution::partition::PartitionBound,
+    };
 
     use crate::{
         ast::{
             common::TableRef,
-            create_table::{ColumnDef, CreateTable, TableConstraint},
+            create_table::{ColumnDef, CreateTable, PartitionBy, TableConstraint},
         },
+        builder::create_table::CreateTableBuilder,
         dialect::Postgres,
         renderer::{Render, Renderer},
     };
@@ -208,6 +259,8 @@ mod tests {
             constraints: vec![TableConstraint::PrimaryKey {
                 columns: vec!["id".to_string()],
             }],
+            partition_by: None,
+            partition_of: None,
         };
 
         let dialect = Postgres;
@@ -222,4 +275,48 @@ mod tests {
 );"#;
         assert_eq!(sql, expected_sql);
     }
+
+    #[test]
+    fn test_render_partitioned_table() {
+        let events = TableRef {
+            schema: None,
+            name: "events".to_string(),
+        };
+        let parent = CreateTableBuilder::new(events.clone())
+            .column("created_at", Type::Date, None)
+            .add()
+            .partition_by(PartitionBy::Range {
+                column: "created_at".to_string(),
+            })
+            .build();
+        let child = CreateTableBuilder::new(TableRef {
+            schema: None,
+            name: "events_p2024_01".to_string(),
+        })
+        .partition_of(
+            events,
+            PartitionBound::Range {
+                from: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                to: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            },
+        )
+        .build();
+
+        let dialect = Postgres;
+        let mut renderer = Renderer::new(&dialect);
+        parent.render(&mut renderer);
+        let (sql, _) = renderer.finish();
+        assert_eq!(
+            sql,
+            "CREATE TABLE \"events\" (\n\t\"created_at\" date NOT NULL\n) PARTITION BY RANGE (\"created_at\");"
+        );
+
+        let mut renderer = Renderer::new(&dialect);
+        child.render(&mut renderer);
+        let (sql, _) = renderer.finish();
+        assert_eq!(
+            sql,
+            "CREATE TABLE \"events_p2024_01\" PARTITION OF \"events\" FOR VALUES FROM ('2024-01-01') TO ('2024-02-01');"
+        );
+    }
 }
@@ -53,6 +53,10 @@ fn render_on_conflict(on_conflict: &OnConflict, r: &mut super::Renderer) {
     if on_conflict.columns.is_empty() {
         return;
     }
+    if r.dialect.has_on_duplicate_key() {
+        render_on_duplicate_key(on_conflict, r);
+        return;
+    }
 
     r.sql.push_str(" ON CONFLICT (");
     let quoted: Vec<String> = on_conflict
@@ -85,6 +89,29 @@ fn render_on_conflict(on_conflict: &OnConflict, r: &mut super::Renderer) {
     }
 }
 
+/// MySQL form of a conflict clause. Doing nothing is written as assigning
+/// the first key column to itself.
+fn render_on_duplicate_key(on_conflict: &OnConflict, r: &mut super::Renderer) {
+    r.sql.push_str(" ON DUPLICATE KEY UPDATE ");
+    match &on_conflict.action {
+        ConflictAction::DoUpdate { assignments } if !assignments.is_empty() => {
+            for (i, assignment) in assignments.iter().enumerate() {
+                if i > 0 {
+                    r.sql.push_str(", ");
+                }
+                r.sql
+                    .push_str(&r.dialect.quote_identifier(&assignment.column));
+                r.sql.push_str(" = ");
+                assignment.value.render(r);
+            }
+        }
+        _ => {
+            let key = r.dialect.quote_identifier(&on_conflict.columns[0]);
+            r.sql.push_str(&format!("{key} = {key}"));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use model::core::value::Value;
@@ -242,4 +269,35 @@ mod tests {
         );
         assert!(params.is_empty());
     }
+
+    #[test]
+    fn test_render_insert_do_nothing_mysql() {
+        let ast = Insert {
+            table: TableRef {
+                schema: None,
+                name: "statuses".to_string(),
+            },
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![vec![
+                value(Value::Int(1)),
+                value(Value::String("new".to_string())),
+            ]],
+            select: None,
+            on_conflict: Some(OnConflict {
+                columns: vec!["id".to_string()],
+                action: ConflictAction::DoNothing,
+            }),
+        };
+
+        let dialect = MySql;
+        let mut renderer = Renderer::new(&dialect);
+        ast.render(&mut renderer);
+        let (sql, params) = renderer.finish();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO `statuses` (`id`, `name`) VALUES (?, ?) ON DUPLICATE KEY UPDATE `id` = `id`;"
+        );
+        assert_eq!(params.len(), 2);
+    }
 }
@@ -35,7 +35,18 @@ impl Render for Select {
             where_clause.render(r);
         }
 
-        // 5. ORDER BY
+        // 5. GROUP BY
+        if !self.group_by.is_empty() {
+            r.sql.push_str(" GROUP BY ");
+            for (i, expr) in self.group_by.iter().enumerate() {
+                if i > 0 {
+                    r.sql.push_str(", ");
+                }
+                expr.render(r);
+            }
+        }
+
+        // 6. ORDER BY
         if !self.order_by.is_empty() {
             r.sql.push_str(" ORDER BY ");
             for (i, order) in self.order_by.iter().enumerate() {
@@ -46,13 +57,13 @@ impl Render for Select {
             }
         }
 
-        // 6. LIMIT
+        // 7. LIMIT
         if let Some(limit) = &self.limit {
             r.sql.push_str(" LIMIT ");
             limit.render(r);
         }
 
-        // 7. OFFSET
+        // 8. OFFSET
         if let Some(offset) = &self.offset {
             r.sql.push_str(" OFFSET ");
             offset.render(r);
@@ -233,6 +244,7 @@ mod tests {
                 op: BinaryOperator::NotEq,
                 right: value(Value::String("inactive".to_string())),
             }))),
+            group_by: vec![qual_ident("u", "id"), qual_ident("u", "created_at")],
             order_by: vec![OrderByExpr {
                 expr: qual_ident("u", "created_at"),
                 direction: Some(OrderDir::Desc),
@@ -246,7 +258,7 @@ mod tests {
         ast.render(&mut renderer);
         let (sql, params) = renderer.finish();
 
-        let expected_sql = r#"SELECT "u"."id", COUNT("p"."id") AS "post_count" FROM "users" AS "u" LEFT JOIN "posts" AS "p" ON ("u"."id" = "p"."user_id") WHERE ("u"."status" <> $1) ORDER BY "u"."created_at" DESC LIMIT $2 OFFSET $3"#;
+        let expected_sql = r#"SELECT "u"."id", COUNT("p"."id") AS "post_count" FROM "users" AS "u" LEFT JOIN "posts" AS "p" ON ("u"."id" = "p"."user_id") WHERE ("u"."status" <> $1) GROUP BY "u"."id", "u"."created_at" ORDER BY "u"."created_at" DESC LIMIT $2 OFFSET $3"#;
         assert_eq!(sql, expected_sql);
         assert_eq!(
             params,
@@ -1,4 +1,8 @@
-use crate::ast::{attribute::Attribute, pipeline::NestedBlock, span::Span};
+use crate::ast::{
+    attribute::Attribute,
+    pipeline::{ApproveBlock, NestedBlock},
+    span::Span,
+};
 use serde::{Deserialize, Serialize};
 
 /// Define block for constants/computed values
@@ -17,6 +21,22 @@ pub struct ExecutionBlock {
     pub span: Span,
 }
 
+/// State block selecting the state backend
+/// Syntax: state { backend = "redis", url = env("REDIS_URL"), ttl = "7d" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Notify block registering a webhook for pipeline lifecycle events
+/// Syntax: notify { url = env("WEBHOOK_URL"), events = ["failed"] }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotifyBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
 /// Connection block for data sources
 /// Syntax: connection "mysql_prod" { driver = "mysql", ... }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -27,6 +47,54 @@ pub struct ConnectionBlock {
     pub span: Span,
 }
 
+/// Defines and connection attributes that replace the document's own when
+/// the profile is selected
+/// Syntax: profile "staging" { connection "db" { host = "staging-db" } }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileBlock {
+    pub name: String,
+    pub define_block: Option<DefineBlock>,
+    pub connections: Vec<ConnectionBlock>,
+    pub span: Span,
+}
+
+/// Materialized view refreshed after the pipelines it depends on
+/// Syntax: materialized_view "mv_orders" { connection = connection.dw, after = [pipeline.orders] }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterializedViewBlock {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Pipelines whose sources are pinned to one point in time
+/// Syntax: barrier "orders" { pipelines = [pipeline.orders, pipeline.order_items] }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BarrierBlock {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Ordered list of configs run by `stratum runbook run`
+/// Syntax: runbook "cutover" { step "backfill" { config = "backfill.smql", approval = true } }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunbookBlock {
+    pub name: String,
+    pub steps: Vec<StepBlock>,
+    pub span: Span,
+}
+
+/// One config of a runbook and the gates around it
+/// Syntax: step "schema" { config = "schema.smql", verify = "count" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepBlock {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub approve_block: Option<ApproveBlock>,
+    pub span: Span,
+}
+
 // Plugin block for WASM plugin definitions
 // Syntax: plugin "my_plugin" { path = "./plugins/my_plugin.wasm", allow_http = true, ... }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1,7 +1,14 @@
-use crate::ast::{
-    block::{ConnectionBlock, DefineBlock, ExecutionBlock, PluginBlock},
-    pipeline::PipelineBlock,
-    span::Span,
+use crate::{
+    ast::{
+        attribute::Attribute,
+        block::{
+            BarrierBlock, ConnectionBlock, DefineBlock, ExecutionBlock, MaterializedViewBlock,
+            NotifyBlock, PluginBlock, ProfileBlock, RunbookBlock, StateBlock,
+        },
+        pipeline::{PipelineBlock, SettingsBlock},
+        span::Span,
+    },
+    errors::BuildError,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,12 +17,110 @@ use serde::{Deserialize, Serialize};
 pub struct SmqlDocument {
     pub define_block: Option<DefineBlock>,
     pub execution_block: Option<ExecutionBlock>,
+    pub state_block: Option<StateBlock>,
+    pub settings_block: Option<SettingsBlock>,
+    pub notify_blocks: Vec<NotifyBlock>,
     pub connections: Vec<ConnectionBlock>,
     pub pipelines: Vec<PipelineBlock>,
     pub plugins: Vec<PluginBlock>,
+    pub materialized_views: Vec<MaterializedViewBlock>,
+    /// Set in runbook files, which `stratum runbook run` reads in place of
+    /// a migration config
+    #[serde(default)]
+    pub runbook_block: Option<RunbookBlock>,
+    /// Overrides selected with `--profile`
+    #[serde(default)]
+    pub profiles: Vec<ProfileBlock>,
+    #[serde(default)]
+    pub barriers: Vec<BarrierBlock>,
     pub span: Span,
 }
 
+impl SmqlDocument {
+    /// Applies the `profile` blocks named `name`, in order: each define and
+    /// connection attribute they set replaces the document's own, and
+    /// attributes of a connection's nested blocks, like `pool`, are
+    /// replaced one by one.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), BuildError> {
+        let profiles: Vec<ProfileBlock> = self
+            .profiles
+            .iter()
+            .filter(|p| p.name == name)
+            .cloned()
+            .collect();
+        if profiles.is_empty() {
+            let mut declared: Vec<&str> = self.profiles.iter().map(|p| p.name.as_str()).collect();
+            declared.sort();
+            declared.dedup();
+            let message = match declared.as_slice() {
+                [] => format!("profile '{name}' is not declared; the config has no profiles"),
+                _ => format!(
+                    "profile '{name}' is not declared. Declared profiles: {}",
+                    declared.join(", ")
+                ),
+            };
+            return Err(BuildError {
+                message,
+                line: 1,
+                column: 1,
+            });
+        }
+
+        for profile in profiles {
+            if let Some(define) = profile.define_block {
+                match &mut self.define_block {
+                    Some(block) => override_attributes(&mut block.attributes, define.attributes),
+                    None => self.define_block = Some(define),
+                }
+            }
+            for overrides in profile.connections {
+                let Some(connection) = self
+                    .connections
+                    .iter_mut()
+                    .find(|c| c.name == overrides.name)
+                else {
+                    return Err(BuildError {
+                        message: format!(
+                            "profile '{name}' sets connection '{}', which is not declared",
+                            overrides.name
+                        ),
+                        line: overrides.span.line,
+                        column: overrides.span.column,
+                    });
+                };
+                override_attributes(&mut connection.attributes, overrides.attributes);
+                for nested in overrides.nested_blocks {
+                    match connection
+                        .nested_blocks
+                        .iter_mut()
+                        .find(|b| b.kind == nested.kind)
+                    {
+                        Some(block) => {
+                            override_attributes(&mut block.attributes, nested.attributes)
+                        }
+                        None => connection.nested_blocks.push(nested),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `attributes` with each of `overrides` replacing the attribute of its key,
+/// or added after them
+fn override_attributes(attributes: &mut Vec<Attribute>, overrides: Vec<Attribute>) {
+    for attribute in overrides {
+        match attributes
+            .iter_mut()
+            .find(|a| a.key.name == attribute.key.name)
+        {
+            Some(existing) => *existing = attribute,
+            None => attributes.push(attribute),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,9 +131,16 @@ mod tests {
         let doc = SmqlDocument {
             define_block: None,
             execution_block: None,
+            state_block: None,
+            settings_block: None,
+            notify_blocks: vec![],
             connections: vec![],
             pipelines: vec![],
             plugins: vec![],
+            materialized_views: vec![],
+            runbook_block: None,
+            profiles: vec![],
+            barriers: vec![],
             span,
         };
 
@@ -38,6 +38,8 @@ pub enum ExpressionKind {
         arguments: Vec<Expression>,
     },
     Array(Vec<Expression>),
+    /// `{ "A" = "active", "I" = "inactive" }`
+    Map(Vec<MapEntry>),
     WhenExpression {
         branches: Vec<WhenBranch>,
         else_value: Option<Box<Expression>>,
@@ -48,6 +50,15 @@ pub enum ExpressionKind {
     PluginCall(PluginCall),
 }
 
+/// Map literal entry. Keys are kept as written, without quotes, so
+/// `1 = "one"` and `"1" = "one"` are the same key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapEntry {
+    pub key: String,
+    pub value: Expression,
+    pub span: Span,
+}
+
 /// When expression branch
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WhenBranch {
@@ -15,8 +15,18 @@ pub struct PipelineBlock {
     pub name: String,
     pub description: Option<String>,
     pub after: Option<Vec<Expression>>,
+    /// `requires_env`: variables that must be set before the pipeline is planned
+    pub requires_env: Option<Expression>,
+    /// `env`: variables visible only to this pipeline's `env()` calls
+    pub env: Option<Expression>,
     pub from: Option<FromBlock>,
+    /// `from` blocks after the first, whose tables are read after the
+    /// first's into the same destination
+    pub unions: Vec<FromBlock>,
     pub to: Option<ToBlock>,
+    /// `to` blocks after the first, each writing the same source rows to
+    /// another table
+    pub sinks: Vec<ToBlock>,
     pub where_clauses: Vec<WhereClause>,
     pub with_block: Option<WithBlock>,
     pub select_block: Option<SelectBlock>,
@@ -26,10 +36,32 @@ pub struct PipelineBlock {
     pub paginate_block: Option<PaginateBlock>,
     pub before_block: Option<BeforeBlock>,
     pub after_block: Option<AfterBlock>,
+    pub on_batch_block: Option<OnBatchBlock>,
+    pub verify_block: Option<VerifyBlock>,
+    pub approve_block: Option<ApproveBlock>,
+    pub log_block: Option<LogBlock>,
+    pub types_block: Option<TypesBlock>,
+    pub timezones_block: Option<TimezonesBlock>,
+    pub seed_blocks: Vec<SeedBlock>,
+    pub script_block: Option<ScriptBlock>,
+    pub aggregate_block: Option<AggregateBlock>,
+    pub pivot_block: Option<PivotBlock>,
     pub settings_block: Option<SettingsBlock>,
+    /// Set for a `migrate_schema` block, which copies every source table
+    /// its patterns select
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_scope: Option<SchemaScope>,
     pub span: Span,
 }
 
+/// `include` and `exclude` of a `migrate_schema` block: arrays of table
+/// patterns such as `"audit_*"`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaScope {
+    pub include: Option<Expression>,
+    pub exclude: Option<Expression>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FromBlock {
     pub attributes: Vec<Attribute>,
@@ -43,6 +75,8 @@ pub struct ToBlock {
     pub attributes: Vec<Attribute>,
     pub nested_blocks: Vec<NestedBlock>,
     pub map_block: Option<MapBlock>,
+    /// `select`: field mappings of a `to` block after the first
+    pub select_block: Option<SelectBlock>,
     pub span: Span,
 }
 
@@ -120,6 +154,86 @@ pub struct PaginateBlock {
     pub span: Span,
 }
 
+/// Post-copy comparison of the source and destination tables
+/// Syntax: verify { mode = "checksum" key = orders.id chunk_size = 10000 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Gate the run pauses at until an operator approves it
+/// Syntax: approve { message = "Confirm cutover" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApproveBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Log level and file of a single pipeline
+/// Syntax: log { level = "debug" file = "logs/orders.log" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Destination column types overriding the inferred ones
+/// Syntax: types { order_total = "numeric(12,2)" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypesBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Static rows inserted into a destination table before the pipeline loads
+/// Syntax: seed { table = "statuses" rows = [{ id = 1, name = "new" }] }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedBlock {
+    pub attributes: Vec<Attribute>,
+    pub rows: Vec<SeedRow>,
+    pub span: Span,
+}
+
+/// One row of a seed block: `{ column = value, ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedRow {
+    pub fields: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Rhai script run on every row
+/// Syntax: script { code = "row.city = row.packed.sub_string(0, 20);" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Grouping of source rows into one destination row per group
+/// Syntax: aggregate { group_by = [orders.customer_id] total = sum(orders.amount) }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Reshaping of key-value source rows into wide rows, or the reverse
+/// Syntax: pivot { key = attribute value = value keys = ["color", "size"] }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PivotBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
+/// Time zones single source columns were written in
+/// Syntax: timezones { shipped_at = "America/New_York" }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimezonesBlock {
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BeforeBlock {
     pub sql: Vec<String>,
@@ -132,6 +246,15 @@ pub struct AfterBlock {
     pub span: Span,
 }
 
+/// SQL run on the destination every N committed batches
+/// Syntax: on_batch { sql = ["REFRESH MATERIALIZED VIEW mv"] every = 10 }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnBatchBlock {
+    pub sql: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SettingsBlock {
     pub attributes: Vec<Attribute>,
@@ -1,17 +1,22 @@
 use crate::{
     ast::{
         attribute::Attribute,
-        block::{ConnectionBlock, DefineBlock, ExecutionBlock, PluginBlock},
+        block::{
+            BarrierBlock, ConnectionBlock, DefineBlock, ExecutionBlock, MaterializedViewBlock,
+            NotifyBlock, PluginBlock, ProfileBlock, RunbookBlock, StateBlock, StepBlock,
+        },
         doc::SmqlDocument,
         dotpath::DotPath,
-        expr::{Expression, ExpressionKind, PluginCall, PluginInputField, WhenBranch},
+        expr::{Expression, ExpressionKind, MapEntry, PluginCall, PluginInputField, WhenBranch},
         ident::Identifier,
         literal::Literal,
         operator::BinaryOperator,
         pipeline::{
-            AfterBlock, BeforeBlock, FieldMapping, FromBlock, JoinClause, MapBlock,
-            NamedSelectBlock, NestedBlock, PaginateBlock, PipelineBlock, ReferencesBlock,
-            SelectBlock, SettingsBlock, ToBlock, WhereClause, WithBlock,
+            AfterBlock, AggregateBlock, ApproveBlock, BeforeBlock, FieldMapping, FromBlock,
+            JoinClause, LogBlock, MapBlock, NamedSelectBlock, NestedBlock, OnBatchBlock,
+            PaginateBlock, PipelineBlock, PivotBlock, ReferencesBlock, SchemaScope, ScriptBlock,
+            SeedBlock, SeedRow, SelectBlock, SettingsBlock, TimezonesBlock, ToBlock, TypesBlock,
+            VerifyBlock, WhereClause, WithBlock,
         },
         span::Span,
         validation::{
@@ -26,11 +31,46 @@ use pest::{
     Parser,
     iterators::{Pair, Pairs},
 };
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 pub type BuildResult<T> = Result<T, BuildError>;
 
-/// Parse SMQL text into a typed AST
+/// An `include "path"` statement
+struct Include {
+    path: String,
+    span: Span,
+}
+
+/// Parse SMQL text into a typed AST. An `include` statement names a file
+/// relative to the file it is in, so text with one is read with
+/// `parse_file` instead.
 pub fn parse(input: &str) -> BuildResult<SmqlDocument> {
+    let (doc, includes) = parse_program(input)?;
+    match includes.first() {
+        Some(include) => Err(BuildError {
+            message: format!(
+                "include \"{}\" is resolved against the file it is in; read the file with parse_file",
+                include.path
+            ),
+            line: include.span.line,
+            column: include.span.column,
+        }),
+        None => Ok(doc),
+    }
+}
+
+/// Parse an SMQL file into a typed AST, with the connections, defines,
+/// plugins and profiles of the files it includes, named relative to the including file.
+/// A file included more than once is read once; a file that includes itself,
+/// directly or through others, is an error.
+pub fn parse_file(path: impl AsRef<Path>) -> BuildResult<SmqlDocument> {
+    parse_included(path.as_ref(), &mut Vec::new(), &mut HashSet::new())
+}
+
+fn parse_program(input: &str) -> BuildResult<(SmqlDocument, Vec<Include>)> {
     let pairs = SmqlParser::parse(Rule::program, input).map_err(|e| BuildError {
         message: format!("Syntax error: {}", e),
         line: 1,
@@ -40,7 +80,113 @@ pub fn parse(input: &str) -> BuildResult<SmqlDocument> {
     build_document(pairs)
 }
 
-fn build_document(mut pairs: Pairs<Rule>) -> BuildResult<SmqlDocument> {
+/// `path` with its includes merged in. `chain` holds the files including
+/// it, outermost first, and `seen` every file read so far, both canonical.
+fn parse_included(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    seen: &mut HashSet<PathBuf>,
+) -> BuildResult<SmqlDocument> {
+    let in_file = |e: BuildError| BuildError {
+        message: format!("{}: {}", path.display(), e.message),
+        ..e
+    };
+    let input = std::fs::read_to_string(path).map_err(|e| BuildError {
+        message: format!("cannot read {}: {e}", path.display()),
+        line: 1,
+        column: 1,
+    })?;
+    let (mut doc, includes) = parse_program(&input).map_err(in_file)?;
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    seen.insert(canonical.clone());
+    chain.push(canonical);
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    // Included blocks come before the file's own, in include order
+    let mut connections = Vec::new();
+    let mut plugins = Vec::new();
+    let mut profiles = Vec::new();
+    let mut defines: Option<DefineBlock> = None;
+    for include in includes {
+        let at = |message: String| {
+            in_file(BuildError {
+                message,
+                line: include.span.line,
+                column: include.span.column,
+            })
+        };
+        let target = dir.join(&include.path);
+        let canonical = target
+            .canonicalize()
+            .map_err(|e| at(format!("cannot include \"{}\": {e}", include.path)))?;
+        if let Some(start) = chain.iter().position(|p| *p == canonical) {
+            let cycle: Vec<String> = chain[start..]
+                .iter()
+                .chain([&canonical])
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(at(format!("include cycle: {}", cycle.join(" -> "))));
+        }
+        if seen.contains(&canonical) {
+            continue;
+        }
+
+        let included = parse_included(&target, chain, seen)?;
+        if let Some(block) = unshared_block(&included) {
+            return Err(at(format!(
+                "included file \"{}\" has a {block} block, but an included file may only hold connection, define, plugin, profile and include statements",
+                include.path
+            )));
+        }
+        connections.extend(included.connections);
+        plugins.extend(included.plugins);
+        profiles.extend(included.profiles);
+        if let Some(define) = included.define_block {
+            match &mut defines {
+                Some(block) => block.attributes.extend(define.attributes),
+                None => defines = Some(define),
+            }
+        }
+    }
+    chain.pop();
+
+    connections.append(&mut doc.connections);
+    plugins.append(&mut doc.plugins);
+    profiles.append(&mut doc.profiles);
+    doc.connections = connections;
+    doc.plugins = plugins;
+    doc.profiles = profiles;
+    doc.define_block = match (defines, doc.define_block) {
+        (Some(mut included), Some(own)) => {
+            included.attributes.extend(own.attributes);
+            included.span = own.span;
+            Some(included)
+        }
+        (included, own) => own.or(included),
+    };
+    Ok(doc)
+}
+
+/// A block an included file may not hold: it would configure the run of
+/// every file including it.
+fn unshared_block(doc: &SmqlDocument) -> Option<&'static str> {
+    let blocks = [
+        ("execution", doc.execution_block.is_some()),
+        ("state", doc.state_block.is_some()),
+        ("settings", doc.settings_block.is_some()),
+        ("notify", !doc.notify_blocks.is_empty()),
+        ("pipeline", !doc.pipelines.is_empty()),
+        ("materialized_view", !doc.materialized_views.is_empty()),
+        ("barrier", !doc.barriers.is_empty()),
+        ("runbook", doc.runbook_block.is_some()),
+    ];
+    blocks
+        .into_iter()
+        .find(|(_, used)| *used)
+        .map(|(block, _)| block)
+}
+
+fn build_document(mut pairs: Pairs<Rule>) -> BuildResult<(SmqlDocument, Vec<Include>)> {
     let program = pairs.next().ok_or_else(|| BuildError {
         message: "Empty input".to_string(),
         line: 1,
@@ -50,40 +196,86 @@ fn build_document(mut pairs: Pairs<Rule>) -> BuildResult<SmqlDocument> {
     let span = pair_to_span(&program);
     let mut define_block = None;
     let mut execution_block = None;
+    let mut state_block = None;
+    let mut settings_block = None;
+    let mut notify_blocks = Vec::new();
     let mut connections = Vec::new();
     let mut pipelines = Vec::new();
     let mut plugins = Vec::new();
+    let mut materialized_views = Vec::new();
+    let mut runbook_block = None;
+    let mut profiles = Vec::new();
+    let mut barriers = Vec::new();
+    let mut includes = Vec::new();
 
     for pair in program.into_inner() {
         match pair.as_rule() {
+            Rule::include_stmt => {
+                let span = pair_to_span(&pair);
+                let path = pair
+                    .into_inner()
+                    .find(|p| p.as_rule() == Rule::lit_string)
+                    .map(|p| parse_string_literal(p.as_str()))
+                    .unwrap_or_default();
+                includes.push(Include { path, span });
+            }
             Rule::define_block => {
                 define_block = Some(build_define_block(pair)?);
             }
             Rule::execution_block => {
                 execution_block = Some(build_execution_block(pair)?);
             }
+            Rule::state_block => {
+                state_block = Some(build_state_block(pair)?);
+            }
+            Rule::settings_block => {
+                settings_block = Some(build_settings_block(pair)?);
+            }
+            Rule::notify_block => {
+                notify_blocks.push(build_notify_block(pair)?);
+            }
             Rule::connection_block => {
                 connections.push(build_connection_block(pair)?);
             }
-            Rule::pipeline_block => {
+            Rule::pipeline_block | Rule::migrate_schema_block => {
                 pipelines.push(build_pipeline_block(pair)?);
             }
             Rule::plugin_block => {
                 plugins.push(build_plugin_block(pair)?);
             }
+            Rule::materialized_view_block => {
+                materialized_views.push(build_materialized_view_block(pair)?);
+            }
+            Rule::barrier_block => {
+                barriers.push(build_barrier_block(pair)?);
+            }
+            Rule::runbook_block => {
+                runbook_block = Some(build_runbook_block(pair)?);
+            }
+            Rule::profile_block => {
+                profiles.push(build_profile_block(pair)?);
+            }
             Rule::EOI => {}
             _ => {}
         }
     }
 
-    Ok(SmqlDocument {
+    let doc = SmqlDocument {
         define_block,
         execution_block,
+        state_block,
+        settings_block,
+        notify_blocks,
         connections,
         pipelines,
         plugins,
+        materialized_views,
+        runbook_block,
+        profiles,
+        barriers,
         span,
-    })
+    };
+    Ok((doc, includes))
 }
 
 fn pair_to_span(pair: &Pair<Rule>) -> crate::ast::span::Span {
@@ -118,6 +310,162 @@ fn build_execution_block(pair: Pair<Rule>) -> BuildResult<ExecutionBlock> {
     Ok(ExecutionBlock { attributes, span })
 }
 
+fn build_state_block(pair: Pair<Rule>) -> BuildResult<StateBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(StateBlock { attributes, span })
+}
+
+fn build_notify_block(pair: Pair<Rule>) -> BuildResult<NotifyBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(NotifyBlock { attributes, span })
+}
+
+fn build_materialized_view_block(pair: Pair<Rule>) -> BuildResult<MaterializedViewBlock> {
+    let span = pair_to_span(&pair);
+    let mut name = String::new();
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::lit_string => {
+                name = parse_string_literal(inner.as_str());
+            }
+            Rule::attribute => {
+                attributes.push(build_attribute(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(MaterializedViewBlock {
+        name,
+        attributes,
+        span,
+    })
+}
+
+fn build_barrier_block(pair: Pair<Rule>) -> BuildResult<BarrierBlock> {
+    let span = pair_to_span(&pair);
+    let mut name = String::new();
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::lit_string => {
+                name = parse_string_literal(inner.as_str());
+            }
+            Rule::attribute => {
+                attributes.push(build_attribute(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(BarrierBlock {
+        name,
+        attributes,
+        span,
+    })
+}
+
+fn build_runbook_block(pair: Pair<Rule>) -> BuildResult<RunbookBlock> {
+    let span = pair_to_span(&pair);
+    let mut name = String::new();
+    let mut steps = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::lit_string => {
+                name = parse_string_literal(inner.as_str());
+            }
+            Rule::step_block => {
+                steps.push(build_step_block(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(RunbookBlock { name, steps, span })
+}
+
+fn build_profile_block(pair: Pair<Rule>) -> BuildResult<ProfileBlock> {
+    let span = pair_to_span(&pair);
+    let mut name = String::new();
+    let mut define_block: Option<DefineBlock> = None;
+    let mut connections = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::lit_string => {
+                name = parse_string_literal(inner.as_str());
+            }
+            Rule::define_block => {
+                let define = build_define_block(inner)?;
+                match &mut define_block {
+                    Some(block) => block.attributes.extend(define.attributes),
+                    None => define_block = Some(define),
+                }
+            }
+            Rule::connection_block => {
+                connections.push(build_connection_block(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ProfileBlock {
+        name,
+        define_block,
+        connections,
+        span,
+    })
+}
+
+fn build_step_block(pair: Pair<Rule>) -> BuildResult<StepBlock> {
+    let span = pair_to_span(&pair);
+    let mut name = String::new();
+    let mut attributes = Vec::new();
+    let mut approve_block = None;
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::lit_string => {
+                name = parse_string_literal(inner.as_str());
+            }
+            Rule::attribute => {
+                attributes.push(build_attribute(inner)?);
+            }
+            Rule::approve_block => {
+                approve_block = Some(build_approve_block(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(StepBlock {
+        name,
+        attributes,
+        approve_block,
+        span,
+    })
+}
+
 fn build_connection_block(pair: Pair<Rule>) -> BuildResult<ConnectionBlock> {
     let span = pair_to_span(&pair);
     let mut name = String::new();
@@ -151,8 +499,12 @@ fn build_pipeline_block(pair: Pair<Rule>) -> BuildResult<PipelineBlock> {
     let span = pair_to_span(&pair);
     let mut name = String::new();
     let mut after = None;
+    let mut requires_env = None;
+    let mut env = None;
     let mut from = None;
+    let mut unions = Vec::new();
     let mut to = None;
+    let mut sinks = Vec::new();
     let mut where_clauses = Vec::new();
     let mut with_block = None;
     let mut select_block = None;
@@ -162,21 +514,53 @@ fn build_pipeline_block(pair: Pair<Rule>) -> BuildResult<PipelineBlock> {
     let mut paginate_block = None;
     let mut before_block = None;
     let mut after_block = None;
+    let mut on_batch_block = None;
+    let mut verify_block = None;
+    let mut approve_block = None;
+    let mut log_block = None;
+    let mut types_block = None;
+    let mut timezones_block = None;
+    let mut seed_blocks = Vec::new();
+    let mut script_block = None;
+    let mut aggregate_block = None;
+    let mut pivot_block = None;
     let mut settings_block = None;
+    let mut schema_scope = None;
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
+            Rule::kw_migrate_schema => {
+                schema_scope = Some(SchemaScope {
+                    include: None,
+                    exclude: None,
+                });
+            }
             Rule::lit_string => {
                 name = parse_string_literal(inner.as_str());
             }
             Rule::attribute => {
-                after = Some(build_after_dependencies(inner)?);
+                let attribute = build_attribute(inner)?;
+                match (attribute.key.name.as_str(), &mut schema_scope) {
+                    ("requires_env", _) => requires_env = Some(attribute.value),
+                    ("env", _) => env = Some(attribute.value),
+                    ("include", Some(scope)) => scope.include = Some(attribute.value),
+                    ("exclude", Some(scope)) => scope.exclude = Some(attribute.value),
+                    _ => after = Some(vec![attribute.value]),
+                }
             }
             Rule::from_block => {
-                from = Some(build_from_block(inner)?);
+                let block = build_from_block(inner)?;
+                match from {
+                    None => from = Some(block),
+                    Some(_) => unions.push(block),
+                }
             }
             Rule::to_block => {
-                to = Some(build_to_block(inner)?);
+                let block = build_to_block(inner)?;
+                match to {
+                    None => to = Some(block),
+                    Some(_) => sinks.push(block),
+                }
             }
             Rule::where_block => {
                 where_clauses.push(build_where_clause(inner)?);
@@ -205,6 +589,36 @@ fn build_pipeline_block(pair: Pair<Rule>) -> BuildResult<PipelineBlock> {
             Rule::after_block => {
                 after_block = Some(build_after_block(inner)?);
             }
+            Rule::on_batch_block => {
+                on_batch_block = Some(build_on_batch_block(inner)?);
+            }
+            Rule::verify_block => {
+                verify_block = Some(build_verify_block(inner)?);
+            }
+            Rule::approve_block => {
+                approve_block = Some(build_approve_block(inner)?);
+            }
+            Rule::log_block => {
+                log_block = Some(build_log_block(inner)?);
+            }
+            Rule::types_block => {
+                types_block = Some(build_types_block(inner)?);
+            }
+            Rule::timezones_block => {
+                timezones_block = Some(build_timezones_block(inner)?);
+            }
+            Rule::seed_block => {
+                seed_blocks.push(build_seed_block(inner)?);
+            }
+            Rule::script_block => {
+                script_block = Some(build_script_block(inner)?);
+            }
+            Rule::aggregate_block => {
+                aggregate_block = Some(build_aggregate_block(inner)?);
+            }
+            Rule::pivot_block => {
+                pivot_block = Some(build_pivot_block(inner)?);
+            }
             Rule::settings_block => {
                 settings_block = Some(build_settings_block(inner)?);
             }
@@ -216,8 +630,12 @@ fn build_pipeline_block(pair: Pair<Rule>) -> BuildResult<PipelineBlock> {
         name,
         description: None,
         after,
+        requires_env,
+        env,
         from,
+        unions,
         to,
+        sinks,
         where_clauses,
         with_block,
         select_block,
@@ -227,7 +645,18 @@ fn build_pipeline_block(pair: Pair<Rule>) -> BuildResult<PipelineBlock> {
         paginate_block,
         before_block,
         after_block,
+        on_batch_block,
+        verify_block,
+        approve_block,
+        log_block,
+        types_block,
+        timezones_block,
+        seed_blocks,
+        script_block,
+        aggregate_block,
+        pivot_block,
         settings_block,
+        schema_scope,
         span,
     })
 }
@@ -278,18 +707,6 @@ fn build_nested_block(pair: Pair<Rule>) -> BuildResult<NestedBlock> {
     })
 }
 
-fn build_after_dependencies(pair: Pair<Rule>) -> BuildResult<Vec<Expression>> {
-    let mut deps = Vec::new();
-
-    for inner in pair.into_inner() {
-        if inner.as_rule() == Rule::expression {
-            deps.push(build_expression(inner)?);
-        }
-    }
-
-    Ok(deps)
-}
-
 fn build_from_block(pair: Pair<Rule>) -> BuildResult<FromBlock> {
     let span = pair_to_span(&pair);
     let mut attributes = Vec::new();
@@ -324,6 +741,7 @@ fn build_to_block(pair: Pair<Rule>) -> BuildResult<ToBlock> {
     let mut attributes = Vec::new();
     let mut nested_blocks = Vec::new();
     let mut map_block = None;
+    let mut select_block = None;
 
     for inner in pair.into_inner() {
         match inner.as_rule() {
@@ -333,6 +751,9 @@ fn build_to_block(pair: Pair<Rule>) -> BuildResult<ToBlock> {
             Rule::map_block => {
                 map_block = Some(build_map_block(inner)?);
             }
+            Rule::select_block => {
+                select_block = Some(build_select_block(inner)?);
+            }
             Rule::nested_block => {
                 nested_blocks.push(build_nested_block(inner)?);
             }
@@ -344,6 +765,7 @@ fn build_to_block(pair: Pair<Rule>) -> BuildResult<ToBlock> {
         attributes,
         nested_blocks,
         map_block,
+        select_block,
         span,
     })
 }
@@ -733,6 +1155,149 @@ fn build_paginate_block(pair: Pair<Rule>) -> BuildResult<PaginateBlock> {
     Ok(PaginateBlock { attributes, span })
 }
 
+fn build_verify_block(pair: Pair<Rule>) -> BuildResult<VerifyBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(VerifyBlock { attributes, span })
+}
+
+fn build_approve_block(pair: Pair<Rule>) -> BuildResult<ApproveBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(ApproveBlock { attributes, span })
+}
+
+fn build_log_block(pair: Pair<Rule>) -> BuildResult<LogBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(LogBlock { attributes, span })
+}
+
+fn build_types_block(pair: Pair<Rule>) -> BuildResult<TypesBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(TypesBlock { attributes, span })
+}
+
+fn build_seed_block(pair: Pair<Rule>) -> BuildResult<SeedBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+    let mut rows = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::attribute => attributes.push(build_attribute(inner)?),
+            Rule::seed_rows => {
+                for row in inner.into_inner() {
+                    if row.as_rule() == Rule::seed_row {
+                        rows.push(build_seed_row(row)?);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(SeedBlock {
+        attributes,
+        rows,
+        span,
+    })
+}
+
+fn build_seed_row(pair: Pair<Rule>) -> BuildResult<SeedRow> {
+    let span = pair_to_span(&pair);
+    let mut fields = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            fields.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(SeedRow { fields, span })
+}
+
+fn build_script_block(pair: Pair<Rule>) -> BuildResult<ScriptBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(ScriptBlock { attributes, span })
+}
+
+fn build_aggregate_block(pair: Pair<Rule>) -> BuildResult<AggregateBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(AggregateBlock { attributes, span })
+}
+
+fn build_pivot_block(pair: Pair<Rule>) -> BuildResult<PivotBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(PivotBlock { attributes, span })
+}
+
+fn build_timezones_block(pair: Pair<Rule>) -> BuildResult<TimezonesBlock> {
+    let span = pair_to_span(&pair);
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::attribute {
+            attributes.push(build_attribute(inner)?);
+        }
+    }
+
+    Ok(TimezonesBlock { attributes, span })
+}
+
 fn build_before_block(pair: Pair<Rule>) -> BuildResult<BeforeBlock> {
     let span = pair_to_span(&pair);
     let mut sql = Vec::new();
@@ -785,6 +1350,41 @@ fn build_after_block(pair: Pair<Rule>) -> BuildResult<AfterBlock> {
     Ok(AfterBlock { sql, span })
 }
 
+fn build_on_batch_block(pair: Pair<Rule>) -> BuildResult<OnBatchBlock> {
+    let span = pair_to_span(&pair);
+    let mut sql = Vec::new();
+    let mut attributes = Vec::new();
+
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::sql_attr => {
+                for sql_inner in inner.into_inner() {
+                    if sql_inner.as_rule() == Rule::array_literal {
+                        let array_expr = build_array_literal(sql_inner, span)?;
+                        if let ExpressionKind::Array(elements) = array_expr.kind {
+                            for elem in elements {
+                                if let ExpressionKind::Literal(Literal::String(s)) = elem.kind {
+                                    sql.push(s);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Rule::attribute => {
+                attributes.push(build_attribute(inner)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(OnBatchBlock {
+        sql,
+        attributes,
+        span,
+    })
+}
+
 fn build_settings_block(pair: Pair<Rule>) -> BuildResult<SettingsBlock> {
     let span = pair_to_span(&pair);
     let mut attributes = Vec::new();
@@ -940,6 +1540,38 @@ fn build_array_literal(pair: Pair<Rule>, span: Span) -> BuildResult<Expression>
     Ok(Expression::new(ExpressionKind::Array(elements), span))
 }
 
+fn build_map_literal(pair: Pair<Rule>, span: Span) -> BuildResult<Expression> {
+    let mut entries = Vec::new();
+
+    for entry in pair.into_inner() {
+        if entry.as_rule() != Rule::map_entry {
+            continue;
+        }
+        let entry_span = pair_to_span(&entry);
+        let mut key = None;
+        let mut value = None;
+        for item in entry.into_inner() {
+            match item.as_rule() {
+                Rule::lit_string => key = Some(parse_string_literal(item.as_str())),
+                Rule::lit_number | Rule::lit_boolean | Rule::ident => {
+                    key = Some(item.as_str().to_string())
+                }
+                Rule::expression => value = Some(build_expression_inner(item, entry_span)?),
+                _ => {}
+            }
+        }
+        if let (Some(key), Some(value)) = (key, value) {
+            entries.push(MapEntry {
+                key,
+                value,
+                span: entry_span,
+            });
+        }
+    }
+
+    Ok(Expression::new(ExpressionKind::Map(entries), span))
+}
+
 fn build_when_expression(pair: Pair<Rule>, span: Span) -> BuildResult<Expression> {
     let mut branches = Vec::new();
     let mut else_value = None;
@@ -1095,6 +1727,7 @@ fn build_primary_inner(pair: Pair<Rule>, span: Span) -> BuildResult<Expression>
         }
         Rule::fn_call => Ok(build_function_call(pair, span)?),
         Rule::array_literal => Ok(build_array_literal(pair, span)?),
+        Rule::map_literal => Ok(build_map_literal(pair, span)?),
         Rule::when_expr => Ok(build_when_expression(pair, span)?),
         Rule::expression => {
             // Grouped expression - just build the inner expression
@@ -1,7 +1,7 @@
 use crate::{
     ast::{
         attribute::Attribute,
-        block::{ConnectionBlock, DefineBlock},
+        block::{BarrierBlock, ConnectionBlock, DefineBlock, MaterializedViewBlock, RunbookBlock},
         doc::SmqlDocument,
         expr::{Expression, ExpressionKind},
         pipeline::{FromBlock, PipelineBlock, ToBlock},
@@ -112,9 +112,27 @@ impl SemanticValidator {
             self.validate_connection_block(conn);
         }
 
+        if let Some(settings) = &document.settings_block {
+            for attr in &settings.attributes {
+                self.validate_expression(&attr.value);
+            }
+        }
+
         for pipeline in &document.pipelines {
             self.validate_pipeline_block(pipeline);
         }
+
+        for view in &document.materialized_views {
+            self.validate_materialized_view_block(view);
+        }
+
+        for barrier in &document.barriers {
+            self.validate_barrier_block(barrier);
+        }
+
+        if let Some(runbook) = &document.runbook_block {
+            self.validate_runbook_block(runbook);
+        }
     }
 
     fn validate_define_block(&mut self, block: &DefineBlock) {
@@ -174,6 +192,101 @@ impl SemanticValidator {
         }
     }
 
+    fn validate_runbook_block(&mut self, block: &RunbookBlock) {
+        if block.steps.is_empty() {
+            self.issues.add_warning(ValidationIssue::warning(
+                ValidationIssueKind::EmptyBlock {
+                    block_type: "runbook".to_string(),
+                },
+                block.span,
+            ));
+        }
+
+        for step in &block.steps {
+            if !step.attributes.iter().any(|a| a.key.name == "config") {
+                self.issues.add_error(ValidationIssue::error(
+                    ValidationIssueKind::MissingRequiredField {
+                        block_type: "step".to_string(),
+                        field: "config".to_string(),
+                    },
+                    step.span,
+                ));
+            }
+
+            for attr in &step.attributes {
+                self.validate_expression(&attr.value);
+            }
+        }
+    }
+
+    fn validate_materialized_view_block(&mut self, block: &MaterializedViewBlock) {
+        if !block.attributes.iter().any(|a| a.key.name == "connection") {
+            self.issues.add_error(ValidationIssue::error(
+                ValidationIssueKind::MissingRequiredField {
+                    block_type: "materialized_view".to_string(),
+                    field: "connection".to_string(),
+                },
+                block.span,
+            ));
+        }
+
+        for attr in &block.attributes {
+            self.validate_expression(&attr.value);
+            match attr.key.name.as_str() {
+                "connection" => self.validate_endpoint_ref(&attr.value),
+                // Other views are resolved when the plan is built
+                "after" => self.validate_pipeline_refs(&attr.value),
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_barrier_block(&mut self, block: &BarrierBlock) {
+        if !block.attributes.iter().any(|a| a.key.name == "pipelines") {
+            self.issues.add_error(ValidationIssue::error(
+                ValidationIssueKind::MissingRequiredField {
+                    block_type: "barrier".to_string(),
+                    field: "pipelines".to_string(),
+                },
+                block.span,
+            ));
+        }
+
+        for attr in &block.attributes {
+            self.validate_expression(&attr.value);
+            if attr.key.name == "pipelines" {
+                self.validate_pipeline_refs(&attr.value);
+            }
+        }
+    }
+
+    /// Checks each `pipeline.<name>` in `value`, a reference or a list of
+    /// them, names a declared pipeline
+    fn validate_pipeline_refs(&mut self, value: &Expression) {
+        let refs = match &value.kind {
+            ExpressionKind::Array(items) => items.iter().collect(),
+            _ => vec![value],
+        };
+        for item in refs {
+            if let ExpressionKind::DotNotation(path) = &item.kind
+                && path.segments.len() == 2
+                && path.segments[0] == "pipeline"
+            {
+                let pipeline_name = &path.segments[1];
+                self.symbols.mark_pipeline_used(pipeline_name);
+
+                if !self.symbols.pipelines.contains_key(pipeline_name) {
+                    self.issues.add_error(ValidationIssue::error(
+                        ValidationIssueKind::UndefinedPipeline {
+                            name: pipeline_name.clone(),
+                        },
+                        item.span,
+                    ));
+                }
+            }
+        }
+    }
+
     fn validate_pipeline_block(&mut self, block: &PipelineBlock) {
         // Check required blocks
         if block.from.is_none() {
@@ -186,7 +299,13 @@ impl SemanticValidator {
             ));
         }
 
-        if block.to.is_none() {
+        // A pipeline with validate rules and no `to` block checks the source
+        // data without writing it anywhere.
+        let validate_only = block
+            .validate_block
+            .as_ref()
+            .is_some_and(|v| !v.checks.is_empty() || !v.wasm_rules.is_empty());
+        if block.to.is_none() && !validate_only {
             self.issues.add_error(ValidationIssue::error(
                 ValidationIssueKind::MissingRequiredField {
                     block_type: "pipeline".to_string(),
@@ -224,10 +343,36 @@ impl SemanticValidator {
             self.validate_from_block(from_block);
         }
 
+        // Further `from` blocks read through the first one's connection
+        for union in &block.unions {
+            for attr in &union.attributes {
+                self.validate_expression(&attr.value);
+                if attr.key.name == "connection" {
+                    self.validate_endpoint_ref(&attr.value);
+                }
+            }
+        }
+
         if let Some(to_block) = &block.to {
             self.validate_to_block(to_block);
         }
 
+        // Further `to` blocks write through the first one's connection, so
+        // theirs may be left out
+        for sink in &block.sinks {
+            for attr in &sink.attributes {
+                self.validate_expression(&attr.value);
+                if attr.key.name == "connection" {
+                    self.validate_endpoint_ref(&attr.value);
+                }
+            }
+            if let Some(select) = &sink.select_block {
+                for field in &select.fields {
+                    self.validate_expression(&field.value);
+                }
+            }
+        }
+
         for where_clause in &block.where_clauses {
             for condition in &where_clause.conditions {
                 self.validate_expression(condition);
@@ -285,22 +430,22 @@ impl SemanticValidator {
 
     fn validate_expression(&mut self, expr: &Expression) {
         match &expr.kind {
-            ExpressionKind::DotNotation(path) => {
-                // Check for define.* references
-                if path.segments.len() == 2 && path.segments[0] == "define" {
-                    let const_name = &path.segments[1];
-                    self.symbols.mark_define_constant_used(const_name);
+            // Check for define.* references; connection.* and pipeline.*
+            // references are checked in context
+            ExpressionKind::DotNotation(path)
+                if path.segments.len() == 2 && path.segments[0] == "define" =>
+            {
+                let const_name = &path.segments[1];
+                self.symbols.mark_define_constant_used(const_name);
 
-                    if !self.symbols.define_constants.contains_key(const_name) {
-                        self.issues.add_error(ValidationIssue::error(
-                            ValidationIssueKind::UndefinedDefineConstant {
-                                name: const_name.clone(),
-                            },
-                            expr.span,
-                        ));
-                    }
+                if !self.symbols.define_constants.contains_key(const_name) {
+                    self.issues.add_error(ValidationIssue::error(
+                        ValidationIssueKind::UndefinedDefineConstant {
+                            name: const_name.clone(),
+                        },
+                        expr.span,
+                    ));
                 }
-                // connection.* and pipeline.* references are checked in context
             }
             ExpressionKind::Binary { left, right, .. } => {
                 self.validate_expression(left);
@@ -319,6 +464,11 @@ impl SemanticValidator {
                     self.validate_expression(elem);
                 }
             }
+            ExpressionKind::Map(entries) => {
+                for entry in entries {
+                    self.validate_expression(&entry.value);
+                }
+            }
             ExpressionKind::WhenExpression {
                 branches,
                 else_value,
@@ -334,17 +484,17 @@ impl SemanticValidator {
             ExpressionKind::IsNull(operand) | ExpressionKind::IsNotNull(operand) => {
                 self.validate_expression(operand);
             }
-            ExpressionKind::PluginCall(call) => {
-                if !self.symbols.plugins.contains_key(&call.plugin_name) {
-                    self.issues.add_error(ValidationIssue::error(
-                        ValidationIssueKind::UndefinedPlugin {
-                            name: call.plugin_name.clone(),
-                        },
-                        call.span,
-                    ));
-                }
-                // input fields' source_refs are bare table.column DotPaths;
-                // those don't go through the symbol table.
+            // input fields' source_refs are bare table.column DotPaths;
+            // those don't go through the symbol table.
+            ExpressionKind::PluginCall(call)
+                if !self.symbols.plugins.contains_key(&call.plugin_name) =>
+            {
+                self.issues.add_error(ValidationIssue::error(
+                    ValidationIssueKind::UndefinedPlugin {
+                        name: call.plugin_name.clone(),
+                    },
+                    call.span,
+                ));
             }
             ExpressionKind::Grouped(inner) => {
                 self.validate_expression(inner);
@@ -525,10 +675,10 @@ impl SemanticValidator {
         let mut refs = Vec::new();
 
         match &expr.kind {
-            ExpressionKind::DotNotation(path) => {
-                if path.segments.len() == 2 && path.segments[0] == "define" {
-                    refs.push(path.segments[1].clone());
-                }
+            ExpressionKind::DotNotation(path)
+                if path.segments.len() == 2 && path.segments[0] == "define" =>
+            {
+                refs.push(path.segments[1].clone());
             }
             ExpressionKind::Binary { left, right, .. } => {
                 refs.extend(Self::extract_define_references(left));
@@ -547,6 +697,11 @@ impl SemanticValidator {
                     refs.extend(Self::extract_define_references(elem));
                 }
             }
+            ExpressionKind::Map(entries) => {
+                for entry in entries {
+                    refs.extend(Self::extract_define_references(&entry.value));
+                }
+            }
             ExpressionKind::WhenExpression {
                 branches,
                 else_value,
@@ -1,6 +1,6 @@
 //! Tests for block structure parsing
 
-use smql_syntax::builder::parse;
+use smql_syntax::{ast::expr::ExpressionKind, builder::parse};
 
 #[test]
 fn test_parse_simple_define() {
@@ -66,6 +66,81 @@ fn test_parse_execution_block_with_timeouts() {
     assert_eq!(execution.attributes.len(), 5);
 }
 
+#[test]
+fn test_parse_state_block() {
+    let input = r#"
+        state {
+            backend = "redis"
+            url = env("REDIS_URL")
+            ttl = "7d"
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let state = doc.state_block.expect("state block");
+    assert_eq!(state.attributes.len(), 3);
+    assert_eq!(state.attributes[0].key.name, "backend");
+    assert_eq!(state.attributes[1].key.name, "url");
+    assert_eq!(state.attributes[2].key.name, "ttl");
+}
+
+#[test]
+fn test_parse_notify_blocks() {
+    let input = r#"
+        notify {
+            url = env("SLACK_WEBHOOK_URL")
+            events = ["failed", "validation_threshold"]
+            validation_threshold = 100
+        }
+        notify {
+            url = "https://hooks.example.com/stratum"
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    assert_eq!(doc.notify_blocks.len(), 2);
+    assert_eq!(doc.notify_blocks[0].attributes.len(), 3);
+    assert_eq!(doc.notify_blocks[0].attributes[1].key.name, "events");
+    assert_eq!(doc.notify_blocks[1].attributes[0].key.name, "url");
+}
+
+#[test]
+fn test_parse_global_settings_block() {
+    let input = r#"
+        settings {
+            batch_size = 5000
+            create_missing_tables = true
+        }
+
+        pipeline "p" {
+            settings { batch_size = 100 }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let settings = doc.settings_block.expect("global settings block");
+    assert_eq!(settings.attributes.len(), 2);
+    assert_eq!(settings.attributes[0].key.name, "batch_size");
+    assert_eq!(
+        doc.pipelines[0]
+            .settings_block
+            .as_ref()
+            .unwrap()
+            .attributes
+            .len(),
+        1
+    );
+}
+
 #[test]
 fn test_parse_connection() {
     let input = r#"
@@ -234,6 +309,378 @@ fn test_parse_on_error_block() {
     assert!(on_error.failed_rows.is_some());
 }
 
+#[test]
+fn test_parse_on_batch_block() {
+    let input = r#"
+        pipeline "sync" {
+            from { connection = connection.db }
+            to { connection = connection.db }
+
+            on_batch {
+                sql = ["REFRESH MATERIALIZED VIEW CONCURRENTLY mv_orders"]
+                every = 10
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let on_batch = doc.pipelines[0].on_batch_block.as_ref().unwrap();
+    assert_eq!(
+        on_batch.sql,
+        vec!["REFRESH MATERIALIZED VIEW CONCURRENTLY mv_orders"]
+    );
+    assert_eq!(on_batch.attributes.len(), 1);
+    assert_eq!(on_batch.attributes[0].key.name, "every");
+}
+
+#[test]
+fn test_parse_verify_block() {
+    let input = r#"
+        pipeline "sync" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            verify {
+                mode       = "checksum"
+                key        = "id"
+                chunk_size = 5000
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let verify = doc.pipelines[0].verify_block.as_ref().unwrap();
+    let keys: Vec<_> = verify
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["mode", "key", "chunk_size"]);
+}
+
+#[test]
+fn test_parse_approve_block() {
+    let input = r#"
+        pipeline "cutover" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            approve {
+                message = "Confirm cutover"
+            }
+        }
+        runbook "orders_cutover" {
+            step "switch" {
+                config = "switch.smql"
+                approve {}
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let approve = doc.pipelines[0].approve_block.as_ref().unwrap();
+    assert_eq!(approve.attributes.len(), 1);
+    assert_eq!(approve.attributes[0].key.name, "message");
+
+    let step = &doc.runbook_block.unwrap().steps[0];
+    assert!(step.approve_block.as_ref().unwrap().attributes.is_empty());
+    assert_eq!(step.attributes.len(), 1);
+}
+
+#[test]
+fn test_parse_log_block() {
+    let input = r#"
+        pipeline "orders" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            log {
+                level = "debug"
+                file = "logs/orders.log"
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let log = doc.pipelines[0].log_block.as_ref().unwrap();
+    let keys: Vec<_> = log.attributes.iter().map(|a| a.key.name.as_str()).collect();
+    assert_eq!(keys, vec!["level", "file"]);
+}
+
+#[test]
+fn test_parse_types_block() {
+    let input = r#"
+        pipeline "orders" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            types {
+                order_total = "numeric(12,2)"
+                notes       = "text"
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let types = doc.pipelines[0].types_block.as_ref().unwrap();
+    let keys: Vec<_> = types
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["order_total", "notes"]);
+}
+
+#[test]
+fn test_parse_seed_blocks() {
+    let input = r#"
+        pipeline "orders" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            seed {
+                table = "order_statuses"
+                rows = [
+                    { id = 1, name = "new" },
+                    { id = 2 name = "shipped" },
+                ]
+            }
+
+            seed {
+                table = "currencies"
+                rows = []
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let seeds = &doc.pipelines[0].seed_blocks;
+    assert_eq!(seeds.len(), 2);
+    assert_eq!(seeds[0].attributes[0].key.name, "table");
+    assert_eq!(seeds[0].rows.len(), 2);
+    let fields: Vec<_> = seeds[0].rows[1]
+        .fields
+        .iter()
+        .map(|f| f.key.name.as_str())
+        .collect();
+    assert_eq!(fields, vec!["id", "name"]);
+    assert!(seeds[1].rows.is_empty());
+}
+
+#[test]
+fn test_parse_script_block() {
+    let input = r#"
+        pipeline "orders" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            script {
+                code = "
+                    let parts = row.packed.split(`|`);
+                    row.region = parts[0];
+                "
+                max_operations = 5000
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let script = doc.pipelines[0].script_block.as_ref().unwrap();
+    let keys: Vec<_> = script
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["code", "max_operations"]);
+}
+
+#[test]
+fn test_parse_aggregate_block() {
+    let input = r#"
+        pipeline "daily_totals" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.dw table = "customer_totals" }
+
+            aggregate {
+                group_by = [orders.customer_id, orders.status]
+                total    = sum(orders.amount)
+                orders   = count()
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let aggregate = doc.pipelines[0].aggregate_block.as_ref().unwrap();
+    let keys: Vec<_> = aggregate
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["group_by", "total", "orders"]);
+    assert!(matches!(
+        &aggregate.attributes[2].value.kind,
+        ExpressionKind::FunctionCall { name, arguments } if name == "count" && arguments.is_empty()
+    ));
+}
+
+#[test]
+fn test_parse_pivot_block() {
+    let input = r#"
+        pipeline "product_attributes" {
+            from { connection = connection.legacy table = "product_attrs" }
+            to { connection = connection.db table = "products" }
+
+            pivot {
+                key   = attr_name
+                value = attr_value
+                keys  = ["color", "size", "weight"]
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let pivot = doc.pipelines[0].pivot_block.as_ref().unwrap();
+    let keys: Vec<_> = pivot
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["key", "value", "keys"]);
+    assert!(matches!(
+        &pivot.attributes[2].value.kind,
+        ExpressionKind::Array(items) if items.len() == 3
+    ));
+}
+
+#[test]
+fn test_parse_timezones_block() {
+    let input = r#"
+        pipeline "orders" {
+            from { connection = connection.db table = "orders" }
+            to { connection = connection.db table = "orders_copy" }
+
+            timezones {
+                shipped_at = "America/New_York"
+            }
+            settings { timezone = "Europe/Berlin" }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let timezones = doc.pipelines[0].timezones_block.as_ref().unwrap();
+    assert_eq!(timezones.attributes.len(), 1);
+    assert_eq!(timezones.attributes[0].key.name, "shipped_at");
+}
+
+#[test]
+fn test_parse_materialized_view_block() {
+    let input = r#"
+        materialized_view "order_totals" {
+            connection   = connection.dest
+            after        = [pipeline.orders, materialized_view.daily_orders]
+            concurrently = true
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    assert_eq!(doc.materialized_views.len(), 1);
+    let view = &doc.materialized_views[0];
+    assert_eq!(view.name, "order_totals");
+    let keys: Vec<_> = view
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(keys, vec!["connection", "after", "concurrently"]);
+}
+
+#[test]
+fn test_parse_barrier_block() {
+    let input = r#"
+        barrier "orders" {
+            pipelines = [pipeline.orders, pipeline.order_items]
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    assert_eq!(doc.barriers.len(), 1);
+    let barrier = &doc.barriers[0];
+    assert_eq!(barrier.name, "orders");
+    assert_eq!(barrier.attributes.len(), 1);
+    assert_eq!(barrier.attributes[0].key.name, "pipelines");
+}
+
+#[test]
+fn test_parse_runbook_block() {
+    let input = r#"
+        runbook "orders_cutover" {
+            step "schema" {
+                config = "schema.smql"
+            }
+            step "backfill" {
+                config          = "backfill.smql"
+                approval        = true
+                verify          = "checksum"
+                max_failed_rows = 0
+            }
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let doc = result.unwrap();
+    let runbook = doc.runbook_block.expect("runbook block");
+    assert_eq!(runbook.name, "orders_cutover");
+    let names: Vec<_> = runbook.steps.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["schema", "backfill"]);
+    let keys: Vec<_> = runbook.steps[1]
+        .attributes
+        .iter()
+        .map(|a| a.key.name.as_str())
+        .collect();
+    assert_eq!(
+        keys,
+        vec!["config", "approval", "verify", "max_failed_rows"]
+    );
+}
+
 #[test]
 fn test_parse_with_comments() {
     let input = r#"
@@ -1,6 +1,6 @@
 //! Tests for expression parsing and AST building
 
-use smql_syntax::builder::parse;
+use smql_syntax::{ast::expr::ExpressionKind, builder::parse};
 
 #[test]
 fn test_parse_expressions() {
@@ -78,6 +78,59 @@ fn test_parse_when_expression() {
     assert_eq!(define.attributes.len(), 1);
 }
 
+#[test]
+fn test_parse_conditional_functions() {
+    let input = r#"
+        define {
+            status = if(code == 1, "active", "inactive")
+            nick = coalesce(nickname, name, "N/A")
+            score = nullif(score, 0)
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let define = result.unwrap().define_block.unwrap();
+    let names: Vec<_> = define
+        .attributes
+        .iter()
+        .map(|attr| match &attr.value.kind {
+            ExpressionKind::FunctionCall { name, .. } => name.as_str(),
+            other => panic!("expected a function call, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, vec!["if", "coalesce", "nullif"]);
+}
+
+#[test]
+fn test_parse_map_literals() {
+    let input = r#"
+        define {
+            statuses = { "A" = "active", "I" = "inactive" }
+            codes = {
+                1 = "one"
+                two = 2
+            }
+            empty = {}
+        }
+    "#;
+
+    let result = parse(input);
+    assert!(result.is_ok(), "Failed to parse: {:?}", result.err());
+
+    let define = result.unwrap().define_block.unwrap();
+    let keys: Vec<Vec<_>> = define
+        .attributes
+        .iter()
+        .map(|attr| match &attr.value.kind {
+            ExpressionKind::Map(entries) => entries.iter().map(|e| e.key.as_str()).collect(),
+            other => panic!("expected a map, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(keys, vec![vec!["A", "I"], vec!["1", "two"], vec![]]);
+}
+
 #[test]
 fn test_build_plugin_call_in_select_block() {
     use smql_syntax::ast::expr::ExpressionKind;
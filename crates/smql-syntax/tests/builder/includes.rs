@@ -0,0 +1,98 @@
+//! Tests for include statements resolved by parse_file
+
+use smql_syntax::builder::{parse, parse_file};
+use std::path::{Path, PathBuf};
+
+/// A fresh directory holding the given files
+fn write_files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("smql_include_{test}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (name, content) in files {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn parse_err(path: &Path) -> String {
+    parse_file(path).unwrap_err().to_string()
+}
+
+#[test]
+fn test_include_connections_and_defines() {
+    let dir = write_files(
+        "shared",
+        &[
+            (
+                "shared/connections.smql",
+                r#"
+                include "defines.smql"
+                connection "source" { driver = "mysql" url = env("SOURCE_URL") }
+                "#,
+            ),
+            ("shared/defines.smql", r#"define { batch = 500 }"#),
+            (
+                "orders.smql",
+                r#"
+                include "shared/connections.smql"
+                include "shared/defines.smql"
+                define { cutoff = "2024-01-01" }
+                connection "dest" { driver = "postgres" url = env("DEST_URL") }
+                pipeline "orders" {
+                    from { connection = connection.source table = "orders" }
+                    to   { connection = connection.dest table = "orders" }
+                }
+                "#,
+            ),
+        ],
+    );
+
+    let doc = parse_file(dir.join("orders.smql")).unwrap();
+    let connections: Vec<_> = doc.connections.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(connections, ["source", "dest"]);
+    // defines.smql is read once, though included twice
+    let defines: Vec<_> = doc
+        .define_block
+        .unwrap()
+        .attributes
+        .iter()
+        .map(|a| a.key.name.clone())
+        .collect();
+    assert_eq!(defines, ["batch", "cutoff"]);
+    assert_eq!(doc.pipelines.len(), 1);
+}
+
+#[test]
+fn test_include_errors() {
+    let dir = write_files(
+        "errors",
+        &[
+            ("a.smql", r#"include "b.smql""#),
+            ("b.smql", r#"include "a.smql""#),
+            ("missing.smql", r#"include "nowhere.smql""#),
+            ("runs.smql", r#"include "pipelines.smql""#),
+            (
+                "pipelines.smql",
+                r#"pipeline "p" { from { connection = connection.db table = "t" } }"#,
+            ),
+        ],
+    );
+
+    let err = parse_err(&dir.join("a.smql"));
+    assert!(err.contains("include cycle:"), "{err}");
+    assert!(
+        err.contains("a.smql -> ") && err.ends_with("a.smql"),
+        "{err}"
+    );
+    assert!(parse_err(&dir.join("missing.smql")).contains("cannot include \"nowhere.smql\""));
+    assert!(
+        parse_err(&dir.join("runs.smql")).contains("has a pipeline block, but an included file")
+    );
+    assert!(
+        parse(r#"include "a.smql""#)
+            .unwrap_err()
+            .to_string()
+            .contains("read the file with parse_file")
+    );
+}
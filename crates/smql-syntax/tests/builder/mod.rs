@@ -6,4 +6,6 @@
 mod blocks;
 mod complete_documents;
 mod expressions;
+mod includes;
+mod profiles;
 mod validation_integration;
@@ -0,0 +1,77 @@
+//! Tests for profile blocks and SmqlDocument::apply_profile
+
+use smql_syntax::{
+    ast::{expr::ExpressionKind, literal::Literal},
+    builder::parse,
+};
+
+const CONFIG: &str = r#"
+define { batch = 1000 cutoff = "2024-01-01" }
+
+connection "db" {
+  driver = "postgres"
+  host   = "localhost"
+  pool { max_size = 20 timeout = "30s" }
+}
+
+profile "staging" {
+  define { batch = 100 }
+  connection "db" {
+    host = "staging-db"
+    pool { max_size = 5 }
+  }
+}
+
+profile "prod" {
+  connection "db" { host = "prod-db" sslmode = "require" }
+}
+"#;
+
+fn string_value(kind: &ExpressionKind) -> String {
+    match kind {
+        ExpressionKind::Literal(Literal::String(s)) => s.clone(),
+        ExpressionKind::Literal(literal) => literal.to_string(),
+        other => panic!("unexpected value {other:?}"),
+    }
+}
+
+#[test]
+fn test_apply_profile() {
+    let mut doc = parse(CONFIG).unwrap();
+    assert_eq!(doc.profiles.len(), 2);
+    doc.apply_profile("staging").unwrap();
+
+    let attr = |attrs: &[smql_syntax::ast::attribute::Attribute], key: &str| {
+        attrs
+            .iter()
+            .find(|a| a.key.name == key)
+            .map(|a| string_value(&a.value.kind))
+    };
+    let define = &doc.define_block.as_ref().unwrap().attributes;
+    assert_eq!(attr(define, "batch").as_deref(), Some("100"));
+    assert_eq!(attr(define, "cutoff").as_deref(), Some("2024-01-01"));
+
+    let db = &doc.connections[0];
+    assert_eq!(attr(&db.attributes, "host").as_deref(), Some("staging-db"));
+    let pool = &db.nested_blocks[0].attributes;
+    assert_eq!(attr(pool, "max_size").as_deref(), Some("5"));
+    assert_eq!(attr(pool, "timeout").as_deref(), Some("30s"));
+
+    let mut doc = parse(CONFIG).unwrap();
+    doc.apply_profile("prod").unwrap();
+    assert_eq!(
+        attr(&doc.connections[0].attributes, "sslmode").as_deref(),
+        Some("require")
+    );
+}
+
+#[test]
+fn test_apply_profile_errors() {
+    let mut doc = parse(CONFIG).unwrap();
+    let err = doc.apply_profile("dev").unwrap_err().to_string();
+    assert!(err.contains("profile 'dev' is not declared. Declared profiles: prod, staging"));
+
+    let mut doc = parse(r#"profile "dev" { connection "missing" { host = "x" } }"#).unwrap();
+    let err = doc.apply_profile("dev").unwrap_err().to_string();
+    assert!(err.contains("sets connection 'missing', which is not declared"));
+}
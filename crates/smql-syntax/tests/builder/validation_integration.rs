@@ -97,3 +97,23 @@ fn test_parse_error_missing_required_fields() {
     assert!(result.has_errors());
     assert!(result.errors.len() >= 2); // Missing driver and url
 }
+
+#[test]
+fn test_parse_error_runbook_step_without_config() {
+    let input = r#"
+        runbook "cutover" {
+            step "schema" {
+                config = "schema.smql"
+            }
+            step "backfill" {
+                approval = true
+            }
+        }
+    "#;
+
+    let doc = parse(input).expect("Parse should succeed");
+    let result = validate(&doc);
+
+    assert!(result.has_errors());
+    assert_eq!(result.errors.len(), 1); // Missing config on "backfill"
+}
@@ -61,6 +61,19 @@ define {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_parse_map_literals() {
+    let input = r#"
+define {
+  statuses = { "A" = "active", "I" = "inactive" }
+  nested = { low = 1, high = { max = 10 } }
+  empty = {}
+}
+"#;
+    let result = SmqlParser::parse(Rule::program, input);
+    assert!(result.is_ok());
+}
+
 #[test]
 fn test_parse_when_expression() {
     let input = r#"
@@ -57,10 +57,17 @@ fn test_circular_define_dependency() {
             span: s,
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![],
         span: s,
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -83,14 +90,21 @@ fn test_circular_pipeline_dependency() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![
             PipelineBlock {
                 description: None,
                 name: "pipeline1".to_string(),
                 after: Some(vec![dot_notation(&["pipeline", "pipeline2"], s)]),
+                requires_env: None,
+                env: None,
                 from: None,
                 to: None,
+                unions: vec![],
+                sinks: vec![],
                 where_clauses: vec![],
                 with_block: None,
                 select_block: None,
@@ -100,15 +114,30 @@ fn test_circular_pipeline_dependency() {
                 paginate_block: None,
                 before_block: None,
                 after_block: None,
+                on_batch_block: None,
+                verify_block: None,
+                approve_block: None,
+                log_block: None,
+                types_block: None,
+                timezones_block: None,
+                seed_blocks: vec![],
+                script_block: None,
+                aggregate_block: None,
+                pivot_block: None,
                 settings_block: None,
+                schema_scope: None,
                 span: s,
             },
             PipelineBlock {
                 description: None,
                 name: "pipeline2".to_string(),
                 after: Some(vec![dot_notation(&["pipeline", "pipeline1"], s)]),
+                requires_env: None,
+                env: None,
                 from: None,
                 to: None,
+                unions: vec![],
+                sinks: vec![],
                 where_clauses: vec![],
                 with_block: None,
                 select_block: None,
@@ -118,12 +147,27 @@ fn test_circular_pipeline_dependency() {
                 paginate_block: None,
                 before_block: None,
                 after_block: None,
+                on_batch_block: None,
+                verify_block: None,
+                approve_block: None,
+                log_block: None,
+                types_block: None,
+                timezones_block: None,
+                seed_blocks: vec![],
+                script_block: None,
+                aggregate_block: None,
+                pivot_block: None,
                 settings_block: None,
+                schema_scope: None,
                 span: s,
             },
         ],
         span: s,
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -16,6 +16,9 @@ fn test_duplicate_connection_names() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![
             ConnectionBlock {
                 name: "db1".to_string(),
@@ -33,6 +36,10 @@ fn test_duplicate_connection_names() {
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -50,14 +57,21 @@ fn test_duplicate_pipeline_names() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![
             PipelineBlock {
                 description: None,
                 name: "sync1".to_string(),
                 after: None,
+                requires_env: None,
+                env: None,
                 from: None,
                 to: None,
+                unions: vec![],
+                sinks: vec![],
                 where_clauses: vec![],
                 with_block: None,
                 select_block: None,
@@ -67,15 +81,30 @@ fn test_duplicate_pipeline_names() {
                 paginate_block: None,
                 before_block: None,
                 after_block: None,
+                on_batch_block: None,
+                verify_block: None,
+                approve_block: None,
+                log_block: None,
+                types_block: None,
+                timezones_block: None,
+                seed_blocks: vec![],
+                script_block: None,
+                aggregate_block: None,
+                pivot_block: None,
                 settings_block: None,
+                schema_scope: None,
                 span: span(1, 1),
             },
             PipelineBlock {
                 description: None,
                 name: "sync1".to_string(),
                 after: None,
+                requires_env: None,
+                env: None,
                 from: None,
                 to: None,
+                unions: vec![],
+                sinks: vec![],
                 where_clauses: vec![],
                 with_block: None,
                 select_block: None,
@@ -85,12 +114,27 @@ fn test_duplicate_pipeline_names() {
                 paginate_block: None,
                 before_block: None,
                 after_block: None,
+                on_batch_block: None,
+                verify_block: None,
+                approve_block: None,
+                log_block: None,
+                types_block: None,
+                timezones_block: None,
+                seed_blocks: vec![],
+                script_block: None,
+                aggregate_block: None,
+                pivot_block: None,
                 settings_block: None,
+                schema_scope: None,
                 span: span(10, 1),
             },
         ],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -121,10 +165,17 @@ fn test_duplicate_define_attributes() {
             span: span(1, 1),
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -19,6 +19,9 @@ fn test_undefined_connection_reference() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![ConnectionBlock {
             name: "db1".to_string(),
             attributes: vec![
@@ -40,6 +43,8 @@ fn test_undefined_connection_reference() {
             description: None,
             name: "sync".to_string(),
             after: None,
+            requires_env: None,
+            env: None,
             from: Some(FromBlock {
                 attributes: vec![Attribute {
                     key: ident("connection", span(8, 5)),
@@ -58,8 +63,11 @@ fn test_undefined_connection_reference() {
                 }],
                 nested_blocks: vec![],
                 map_block: None,
+                select_block: None,
                 span: span(10, 3),
             }),
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -69,11 +77,26 @@ fn test_undefined_connection_reference() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: span(6, 1),
         }],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -92,6 +115,9 @@ fn test_undefined_pipeline_reference() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![PipelineBlock {
             description: None,
@@ -100,8 +126,12 @@ fn test_undefined_pipeline_reference() {
                 &["pipeline", "nonexistent"],
                 span(2, 12),
             )]),
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -111,11 +141,26 @@ fn test_undefined_pipeline_reference() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: span(1, 1),
         }],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -141,13 +186,20 @@ fn test_undefined_define_constant() {
             span: span(1, 1),
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![PipelineBlock {
             description: None,
             name: "sync".to_string(),
             after: None,
+            requires_env: None,
+            env: None,
             from: None,
             to: None,
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![WhereClause {
                 label: None,
                 conditions: vec![Expression::new(
@@ -168,11 +220,26 @@ fn test_undefined_define_constant() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: span(5, 1),
         }],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -186,6 +253,38 @@ fn test_undefined_define_constant() {
     assert_eq!(undefined_errors.len(), 1);
 }
 
+#[test]
+fn test_barrier_undefined_pipeline_reference() {
+    let doc = parse(
+        r#"
+            connection "db" { driver = "postgres" url = "localhost" }
+            pipeline "orders" {
+                from { connection = connection.db table = "orders" }
+                to   { connection = connection.db table = "orders_copy" }
+            }
+            barrier "sales" { pipelines = [pipeline.orders, pipeline.order_items] }
+            barrier "empty" {}
+        "#,
+    )
+    .expect("parse ok");
+
+    let result = validate(&doc);
+    let undefined: Vec<_> = result
+        .errors
+        .iter()
+        .filter_map(|e| match &e.kind {
+            ValidationIssueKind::UndefinedPipeline { name } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(undefined, vec!["order_items"]);
+    assert!(result.errors.iter().any(|e| matches!(
+        &e.kind,
+        ValidationIssueKind::MissingRequiredField { block_type, field }
+            if block_type == "barrier" && field == "pipelines"
+    )));
+}
+
 #[test]
 fn test_pipeline_uses_plugin_as_source_via_dotpath() {
     // `connection = plugin.fraud` where the plugin IS declared - no errors.
@@ -343,3 +442,40 @@ fn test_validate_wasm_rule_undefined_plugin() {
         .collect();
     assert_eq!(plugin_errs.len(), 1);
 }
+
+#[test]
+fn test_validate_only_pipeline_without_to_block() {
+    let doc = parse(
+        r#"
+            connection "src" { driver = "mysql" url = "u" }
+            pipeline "quality" {
+                from { connection = connection.src table = "orders" }
+                validate {
+                    assert "positive_total" {
+                        check   = orders.total >= 0
+                        message = "Order total cannot be negative"
+                        action  = fail
+                    }
+                }
+            }
+            pipeline "copy" {
+                from { connection = connection.src table = "orders" }
+            }
+        "#,
+    )
+    .expect("parse ok");
+
+    let result = validate(&doc);
+    let missing: Vec<_> = result
+        .errors
+        .iter()
+        .filter(|e| {
+            matches!(
+                &e.kind,
+                ValidationIssueKind::MissingRequiredField { field, .. } if field == "to"
+            )
+        })
+        .collect();
+    // Only the pipeline without validate rules needs a destination
+    assert_eq!(missing.len(), 1, "{:?}", result.errors);
+}
@@ -19,6 +19,9 @@ fn test_missing_required_fields_connection() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![ConnectionBlock {
             name: "db1".to_string(),
             attributes: vec![
@@ -30,6 +33,10 @@ fn test_missing_required_fields_connection() {
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -43,13 +50,20 @@ fn test_missing_required_fields_pipeline() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![PipelineBlock {
             description: None,
             name: "sync".to_string(),
             after: None,
+            requires_env: None,
+            env: None,
             from: None, // Missing
             to: None,   // Missing
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -59,11 +73,26 @@ fn test_missing_required_fields_pipeline() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: span(1, 1),
         }],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -77,6 +106,9 @@ fn test_unused_connection_warning() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![ConnectionBlock {
             name: "unused_db".to_string(),
             attributes: vec![
@@ -97,6 +129,10 @@ fn test_unused_connection_warning() {
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -121,10 +157,17 @@ fn test_unused_define_constant_warning() {
             span: span(1, 1),
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -145,10 +188,17 @@ fn test_empty_define_block_warning() {
             span: span(1, 1),
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![],
         pipelines: vec![],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -175,6 +225,9 @@ fn test_valid_document_no_errors() {
             span: s,
         }),
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![ConnectionBlock {
             name: "db1".to_string(),
             attributes: vec![
@@ -196,6 +249,8 @@ fn test_valid_document_no_errors() {
             description: None,
             name: "sync".to_string(),
             after: None,
+            requires_env: None,
+            env: None,
             from: Some(FromBlock {
                 attributes: vec![Attribute {
                     key: ident("connection", s),
@@ -214,8 +269,11 @@ fn test_valid_document_no_errors() {
                 }],
                 nested_blocks: vec![],
                 map_block: None,
+                select_block: None,
                 span: s,
             }),
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: Some(SelectBlock {
@@ -239,11 +297,26 @@ fn test_valid_document_no_errors() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: s,
         }],
         span: s,
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);
@@ -258,6 +331,9 @@ fn test_multiple_errors_collected() {
     let doc = SmqlDocument {
         define_block: None,
         execution_block: None,
+        state_block: None,
+        settings_block: None,
+        notify_blocks: vec![],
         connections: vec![
             ConnectionBlock {
                 name: "db1".to_string(),
@@ -281,8 +357,12 @@ fn test_multiple_errors_collected() {
                 &["pipeline", "nonexistent"],
                 span(10, 12),
             )]),
+            requires_env: None,
+            env: None,
             from: None, // Missing
             to: None,   // Missing
+            unions: vec![],
+            sinks: vec![],
             where_clauses: vec![],
             with_block: None,
             select_block: None,
@@ -292,11 +372,26 @@ fn test_multiple_errors_collected() {
             paginate_block: None,
             before_block: None,
             after_block: None,
+            on_batch_block: None,
+            verify_block: None,
+            approve_block: None,
+            log_block: None,
+            types_block: None,
+            timezones_block: None,
+            seed_blocks: vec![],
+            script_block: None,
+            aggregate_block: None,
+            pivot_block: None,
             settings_block: None,
+            schema_scope: None,
             span: span(9, 1),
         }],
         span: span(1, 1),
         plugins: vec![],
+        materialized_views: vec![],
+        runbook_block: None,
+        profiles: vec![],
+        barriers: vec![],
     };
 
     let result = validate(&doc);